@@ -1,7 +1,9 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, num::NonZeroUsize};
 
 use codecs::{encoding::FramingConfig, TextSerializerConfig};
-use criterion::{criterion_group, BatchSize, BenchmarkId, Criterion, SamplingMode, Throughput};
+use criterion::{
+    criterion_group, BatchSize, Bencher, BenchmarkId, Criterion, SamplingMode, Throughput,
+};
 use futures::TryFutureExt;
 use hyper::{
     service::{make_service_fn, service_fn},
@@ -34,51 +36,53 @@ fn benchmark_http(c: &mut Criterion) {
             BenchmarkId::new("compression", compression),
             compression,
             |b, compression| {
-                b.iter_batched(
-                    || {
-                        let mut config = config::Config::builder();
-                        config.add_source(
-                            "in",
-                            sources::socket::SocketConfig::make_basic_tcp_config(in_addr),
-                        );
-                        let mut batch = BatchConfig::default();
-                        batch.max_bytes = Some(num_lines * line_size);
-
-                        config.add_sink(
-                            "out",
-                            &["in"],
-                            sinks::http::HttpSinkConfig {
-                                uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
-                                compression: *compression,
-                                method: Default::default(),
-                                auth: Default::default(),
-                                headers: Default::default(),
-                                batch,
-                                encoding: (None::<FramingConfig>, TextSerializerConfig::default())
-                                    .into(),
-                                request: Default::default(),
-                                tls: Default::default(),
-                                acknowledgements: Default::default(),
-                            },
-                        );
-
-                        let rt = runtime();
-                        let topology = rt.block_on(async move {
-                            let (topology, _crash) =
-                                start_topology(config.build().unwrap(), false).await;
-                            wait_for_tcp(in_addr).await;
-                            topology
-                        });
-                        (rt, topology)
-                    },
-                    |(rt, topology)| {
-                        rt.block_on(async move {
-                            let lines = random_lines(line_size).take(num_lines);
-                            send_lines(in_addr, lines).await.unwrap();
-                            topology.stop().await;
-                        })
-                    },
-                    BatchSize::PerIteration,
+                bench_sink(
+                    b,
+                    in_addr,
+                    out_addr,
+                    num_lines,
+                    line_size,
+                    *compression,
+                    None,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Compares building requests on the sink task against building them on their own tasks, with
+/// batches small enough for several requests to be built at the same time.
+fn benchmark_http_encoding_concurrency(c: &mut Criterion) {
+    let num_lines: usize = 10_000;
+    let line_size: usize = 100;
+
+    let in_addr = next_addr();
+    let out_addr = next_addr();
+
+    let _srv = serve(out_addr);
+
+    let mut group = c.benchmark_group("http_encoding_concurrency");
+    group.throughput(Throughput::Bytes((num_lines * line_size) as u64));
+    group.sampling_mode(SamplingMode::Flat);
+
+    for concurrency in [None, NonZeroUsize::new(8)] {
+        group.bench_with_input(
+            BenchmarkId::new(
+                "gzip",
+                concurrency.map_or_else(|| "default".to_owned(), |c| c.to_string()),
+            ),
+            &concurrency,
+            |b, concurrency| {
+                bench_sink(
+                    b,
+                    in_addr,
+                    out_addr,
+                    num_lines,
+                    line_size,
+                    Compression::gzip_default(),
+                    *concurrency,
                 )
             },
         );
@@ -87,6 +91,67 @@ fn benchmark_http(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_sink(
+    b: &mut Bencher,
+    in_addr: SocketAddr,
+    out_addr: SocketAddr,
+    num_lines: usize,
+    line_size: usize,
+    compression: Compression,
+    concurrency: Option<NonZeroUsize>,
+) {
+    b.iter_batched(
+        || {
+            let mut config = config::Config::builder();
+            config.add_source(
+                "in",
+                sources::socket::SocketConfig::make_basic_tcp_config(in_addr),
+            );
+            let mut batch = BatchConfig::default();
+            batch.max_bytes = Some(1_000 * line_size);
+
+            config.add_sink(
+                "out",
+                &["in"],
+                sinks::http::HttpSinkConfig {
+                    uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                    compression,
+                    compression_dictionary: Default::default(),
+                    method: Default::default(),
+                    auth: Default::default(),
+                    headers: Default::default(),
+                    batch,
+                    encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+                    encoding_concurrency: concurrency,
+                    payload_prefix: Default::default(),
+                    payload_suffix: Default::default(),
+                    idempotency_key: Default::default(),
+                    request: Default::default(),
+                    http_version: Default::default(),
+                    tls: Default::default(),
+                    acknowledgements: Default::default(),
+                },
+            );
+
+            let rt = runtime();
+            let topology = rt.block_on(async move {
+                let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+                wait_for_tcp(in_addr).await;
+                topology
+            });
+            (rt, topology)
+        },
+        |(rt, topology)| {
+            rt.block_on(async move {
+                let lines = random_lines(line_size).take(num_lines);
+                send_lines(in_addr, lines).await.unwrap();
+                topology.stop().await;
+            })
+        },
+        BatchSize::PerIteration,
+    );
+}
+
 fn serve(addr: SocketAddr) -> Runtime {
     let rt = runtime();
     rt.spawn(async move {
@@ -104,4 +169,4 @@ fn serve(addr: SocketAddr) -> Runtime {
     rt
 }
 
-criterion_group!(benches, benchmark_http);
+criterion_group!(benches, benchmark_http, benchmark_http_encoding_concurrency);
@@ -12,6 +12,7 @@ mod native;
 mod native_json;
 mod raw_message;
 mod text;
+mod text_with_context;
 
 use std::fmt::Debug;
 
@@ -25,6 +26,9 @@ pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
 pub use text::{TextSerializer, TextSerializerConfig};
+pub use text_with_context::{
+    TextWithContextSerializer, TextWithContextSerializerConfig, TextWithContextSerializerOptions,
+};
 use vector_core::event::Event;
 
 /// Serialize a structured event into a byte frame.
@@ -0,0 +1,188 @@
+use crate::encoding::BuildError;
+use bytes::{BufMut, BytesMut};
+use chrono::SecondsFormat;
+use lookup::lookup_v2::ConfigTargetPath;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+use vrl::value::Kind;
+
+/// The placeholder written in the header for fields missing from the event.
+const MISSING_FIELD: &str = "-";
+
+/// Config used to build a `TextWithContextSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct TextWithContextSerializerConfig {
+    /// Options for the text with context serializer.
+    pub text_with_context: TextWithContextSerializerOptions,
+}
+
+impl TextWithContextSerializerConfig {
+    /// Creates a new `TextWithContextSerializerConfig`.
+    pub const fn new(text_with_context: TextWithContextSerializerOptions) -> Self {
+        Self { text_with_context }
+    }
+
+    /// Build the `TextWithContextSerializer` from this configuration.
+    pub fn build(&self) -> Result<TextWithContextSerializer, BuildError> {
+        if self.text_with_context.header_fields.is_empty() {
+            Err("At least one header field must be specified".into())
+        } else {
+            Ok(TextWithContextSerializer::new(
+                self.text_with_context.header_fields.clone(),
+                self.text_with_context.separator.clone(),
+            ))
+        }
+    }
+
+    /// The data type of events that are accepted by `TextWithContextSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty().required_meaning(log_schema().message_key(), Kind::any())
+    }
+}
+
+/// Options for the text with context serializer.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct TextWithContextSerializerOptions {
+    /// The fields written in the header line that precedes each message, in order.
+    ///
+    /// Fields missing from the event are written as `-`, and timestamps are formatted as RFC 3339.
+    #[configurable(metadata(docs::examples = "timestamp"))]
+    #[configurable(metadata(docs::examples = "kubernetes.pod_name"))]
+    #[configurable(metadata(docs::examples = "level"))]
+    pub header_fields: Vec<ConfigTargetPath>,
+
+    /// The string written between the fields of the header.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+}
+
+fn default_separator() -> String {
+    " ".to_owned()
+}
+
+/// Serializer that writes a header line of selected fields, followed by the message of the log
+/// as is.
+///
+/// Messages spanning several lines, such as stack traces, keep their formatting, and the header
+/// marks where each event starts for someone reading the output.
+#[derive(Debug, Clone)]
+pub struct TextWithContextSerializer {
+    header_fields: Vec<ConfigTargetPath>,
+    separator: String,
+}
+
+impl TextWithContextSerializer {
+    /// Creates a new `TextWithContextSerializer`.
+    pub const fn new(header_fields: Vec<ConfigTargetPath>, separator: String) -> Self {
+        Self {
+            header_fields,
+            separator,
+        }
+    }
+
+    fn write_header(&self, log: &LogEvent, buffer: &mut BytesMut) {
+        for (index, field) in self.header_fields.iter().enumerate() {
+            if index > 0 {
+                buffer.put(self.separator.as_bytes());
+            }
+            match log.get(field) {
+                None | Some(Value::Null) => buffer.put(MISSING_FIELD.as_bytes()),
+                Some(Value::Bytes(bytes)) => buffer.put(bytes.as_ref()),
+                Some(Value::Timestamp(timestamp)) => buffer.put(
+                    timestamp
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                        .as_bytes(),
+                ),
+                Some(value) => buffer.put(value.to_string_lossy().as_bytes()),
+            }
+        }
+    }
+}
+
+impl Encoder<Event> for TextWithContextSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        self.write_header(&log, buffer);
+        buffer.put_u8(b'\n');
+
+        let message_key = log_schema().message_key();
+        if let Some(bytes) = log
+            .get_by_meaning(message_key)
+            .or_else(|| log.get(message_key))
+            .map(|value| value.coerce_to_bytes())
+        {
+            buffer.put(bytes);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn serializer(fields: &[&str]) -> TextWithContextSerializer {
+        TextWithContextSerializerConfig::new(TextWithContextSerializerOptions {
+            header_fields: fields
+                .iter()
+                .map(|field| ConfigTargetPath::try_from(field.to_string()).unwrap())
+                .collect(),
+            separator: default_separator(),
+        })
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn build_error_on_empty_fields() {
+        let config = TextWithContextSerializerConfig::new(TextWithContextSerializerOptions {
+            header_fields: vec![],
+            separator: default_separator(),
+        });
+        let err = config.build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "At least one header field must be specified"
+        );
+    }
+
+    #[test]
+    fn serialize_multiline_message_with_header() {
+        let mut log =
+            LogEvent::from_str_legacy("panic: boom\n\tat main.go:12\n\tat runtime.go:250");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 6, 1, 12, 30, 0).unwrap(),
+        );
+        log.insert("kubernetes.pod_name", "api-7d9f");
+        let mut buffer = BytesMut::new();
+
+        serializer(&["timestamp", "kubernetes.pod_name", "level"])
+            .encode(Event::from(log), &mut buffer)
+            .unwrap();
+
+        assert_eq!(
+            buffer.freeze(),
+            Bytes::from(
+                "2023-06-01T12:30:00Z api-7d9f -\npanic: boom\n\tat main.go:12\n\tat runtime.go:250"
+            )
+        );
+    }
+}
@@ -12,7 +12,8 @@ pub use format::{
     CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
     JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
     NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig, TextWithContextSerializer,
+    TextWithContextSerializerConfig, TextWithContextSerializerOptions,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -240,6 +241,13 @@ pub enum SerializerConfig {
     /// transform) and removing the message field while doing additional parsing on it, as this
     /// could lead to the encoding emitting empty strings for the given event.
     Text(TextSerializerConfig),
+
+    /// Plain text encoding, with a header line of selected fields before each message.
+    ///
+    /// This encoding uses the `message` field of a log event, keeping the formatting of messages
+    /// spanning several lines, and is meant for people reading the output, such as files tailed
+    /// during an investigation.
+    TextWithContext(TextWithContextSerializerConfig),
 }
 
 impl From<AvroSerializerConfig> for SerializerConfig {
@@ -296,6 +304,12 @@ impl From<TextSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<TextWithContextSerializerConfig> for SerializerConfig {
+    fn from(config: TextWithContextSerializerConfig) -> Self {
+        Self::TextWithContext(config)
+    }
+}
+
 impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
     pub fn build(&self) -> Result<Serializer, Box<dyn std::error::Error + Send + Sync + 'static>> {
@@ -315,6 +329,9 @@ impl SerializerConfig {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
             SerializerConfig::Text(config) => Ok(Serializer::Text(config.build())),
+            SerializerConfig::TextWithContext(config) => {
+                Ok(Serializer::TextWithContext(config.build()?))
+            }
         }
     }
 
@@ -341,7 +358,8 @@ impl SerializerConfig {
             | SerializerConfig::Logfmt
             | SerializerConfig::NativeJson
             | SerializerConfig::RawMessage
-            | SerializerConfig::Text(_) => FramingConfig::NewlineDelimited,
+            | SerializerConfig::Text(_)
+            | SerializerConfig::TextWithContext(_) => FramingConfig::NewlineDelimited,
         }
     }
 
@@ -359,6 +377,7 @@ impl SerializerConfig {
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
             SerializerConfig::Text(config) => config.input_type(),
+            SerializerConfig::TextWithContext(config) => config.input_type(),
         }
     }
 
@@ -376,6 +395,7 @@ impl SerializerConfig {
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
             SerializerConfig::Text(config) => config.schema_requirement(),
+            SerializerConfig::TextWithContext(config) => config.schema_requirement(),
         }
     }
 }
@@ -401,6 +421,8 @@ pub enum Serializer {
     RawMessage(RawMessageSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
+    /// Uses a `TextWithContextSerializer` for serialization.
+    TextWithContext(TextWithContextSerializer),
 }
 
 impl Serializer {
@@ -412,6 +434,7 @@ impl Serializer {
             | Serializer::Csv(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
+            | Serializer::TextWithContext(_)
             | Serializer::Native(_)
             | Serializer::RawMessage(_) => false,
         }
@@ -432,6 +455,7 @@ impl Serializer {
             | Serializer::Csv(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
+            | Serializer::TextWithContext(_)
             | Serializer::Native(_)
             | Serializer::RawMessage(_) => {
                 panic!("Serializer does not support JSON")
@@ -494,6 +518,12 @@ impl From<TextSerializer> for Serializer {
     }
 }
 
+impl From<TextWithContextSerializer> for Serializer {
+    fn from(serializer: TextWithContextSerializer) -> Self {
+        Self::TextWithContext(serializer)
+    }
+}
+
 impl tokio_util::codec::Encoder<Event> for Serializer {
     type Error = vector_common::Error;
 
@@ -508,6 +538,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
+            Serializer::TextWithContext(serializer) => serializer.encode(event, buffer),
         }
     }
 }
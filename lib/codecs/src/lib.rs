@@ -26,6 +26,7 @@ pub use encoding::{
     LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
     NativeSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
     RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    TextWithContextSerializer, TextWithContextSerializerConfig,
 };
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
 use vector_config::configurable_component;
@@ -10,13 +10,12 @@ pub mod logproto {
 
 pub mod util {
     use super::logproto;
-    use prost::Message;
     use std::collections::HashMap;
 
     const NANOS_RANGE: i64 = 1_000_000_000;
 
-    // (<Timestamp in nanos>, <Line>)
-    pub struct Entry(pub i64, pub String);
+    // (<Timestamp in nanos>, <Line>, <Structured metadata>)
+    pub struct Entry(pub i64, pub String, pub Vec<(String, String)>);
 
     impl From<Entry> for logproto::EntryAdapter {
         fn from(entry: Entry) -> Self {
@@ -26,6 +25,11 @@ pub mod util {
                     nanos: (entry.0 % NANOS_RANGE) as i32,
                 }),
                 line: entry.1,
+                structured_metadata: entry
+                    .2
+                    .into_iter()
+                    .map(|(name, value)| logproto::LabelPairAdapter { name, value })
+                    .collect(),
             }
         }
     }
@@ -51,13 +55,48 @@ pub mod util {
 
     impl Batch {
         pub fn encode(self) -> Vec<u8> {
-            let streams: Vec<logproto::StreamAdapter> =
-                self.0.into_iter().map(|stream| stream.into()).collect();
-            let push_request = logproto::PushRequest { streams };
-            let buf = push_request.encode_to_vec();
+            let mut encoder = BatchEncoder::default();
+            for stream in self.0 {
+                encoder.push(stream);
+            }
+            encoder.finish()
+        }
+    }
+
+    /// Encodes a `PushRequest` one stream at a time.
+    ///
+    /// Only the stream being pushed is held as a `StreamAdapter`, instead of every stream of the
+    /// request at once.
+    #[derive(Default)]
+    pub struct BatchEncoder {
+        buf: Vec<u8>,
+    }
+
+    impl BatchEncoder {
+        pub fn push(&mut self, stream: Stream) {
+            encode_stream(stream, &mut self.buf);
+        }
+
+        /// The snappy compressed request.
+        pub fn finish(self) -> Vec<u8> {
             let mut encoder = snap::raw::Encoder::new();
-            encoder.compress_vec(&buf).expect("out of memory")
+            encoder.compress_vec(&self.buf).expect("out of memory")
         }
+
+        /// The request as is, which is how it is sent over gRPC.
+        pub fn finish_uncompressed(self) -> Vec<u8> {
+            self.buf
+        }
+    }
+
+    /// Appends the stream to the buffer as a stream of a `PushRequest`.
+    ///
+    /// A request is the concatenation of its encoded streams, so an uncompressed request can be
+    /// written out one stream at a time.
+    pub fn encode_stream(stream: Stream, buf: &mut Vec<u8>) {
+        let stream = logproto::StreamAdapter::from(stream);
+        // The streams are the repeated field 1 of the request.
+        prost::encoding::message::encode(1, &stream, buf);
     }
 
     const RESERVED_LABEL_TENANT_ID: &str = "__tenant_id__";
@@ -103,12 +142,12 @@ mod tests {
             .timestamp_opt(1640244790, 0)
             .single()
             .expect("invalid timestamp");
-        let entry1 = Entry(ts1.timestamp_nanos(), "hello".into());
+        let entry1 = Entry(ts1.timestamp_nanos(), "hello".into(), vec![]);
         let ts2 = Utc
             .timestamp_opt(1640244791, 0)
             .single()
             .expect("invalid timestamp");
-        let entry2 = Entry(ts2.timestamp_nanos(), "world".into());
+        let entry2 = Entry(ts2.timestamp_nanos(), "world".into(), vec![]);
         let labels = vec![("source".into(), "protobuf-test".into())]
             .into_iter()
             .collect();
@@ -123,4 +162,75 @@ mod tests {
         let buf = batch.encode();
         assert_eq!(expect, buf);
     }
+
+    #[test]
+    fn encode_streams_one_at_a_time() {
+        use prost::Message;
+
+        let stream = |source: &str, line: &str| {
+            let labels = vec![("source".into(), source.into())].into_iter().collect();
+            Stream(labels, vec![Entry(1640244790, line.into(), vec![])])
+        };
+        let buf = Batch(vec![stream("a", "hello"), stream("b", "world")]).encode();
+
+        let decoded = snap::raw::Decoder::new().decompress_vec(&buf).unwrap();
+        let request = crate::logproto::PushRequest::decode(decoded.as_slice()).unwrap();
+        assert_eq!(
+            request,
+            crate::logproto::PushRequest {
+                streams: vec![stream("a", "hello").into(), stream("b", "world").into()],
+            }
+        );
+    }
+
+    #[test]
+    fn encode_uncompressed() {
+        use prost::Message;
+
+        let labels = vec![("source".into(), "grpc".into())].into_iter().collect();
+        let stream = || {
+            Stream(
+                labels.clone(),
+                vec![Entry(1640244790, "hello".into(), vec![])],
+            )
+        };
+        let mut encoder = crate::util::BatchEncoder::default();
+        encoder.push(stream());
+
+        let request =
+            crate::logproto::PushRequest::decode(encoder.finish_uncompressed().as_slice()).unwrap();
+        assert_eq!(request.streams, vec![stream().into()]);
+    }
+
+    #[test]
+    fn encode_concatenated_streams() {
+        use prost::Message;
+
+        let stream = |source: &str| {
+            let labels = vec![("source".into(), source.into())].into_iter().collect();
+            Stream(labels, vec![Entry(1640244790, "hello".into(), vec![])])
+        };
+        let mut buf = Vec::new();
+        crate::util::encode_stream(stream("a"), &mut buf);
+        crate::util::encode_stream(stream("b"), &mut buf);
+
+        let request = crate::logproto::PushRequest::decode(buf.as_slice()).unwrap();
+        assert_eq!(
+            request.streams,
+            vec![stream("a").into(), stream("b").into()]
+        );
+    }
+
+    #[test]
+    fn encode_structured_metadata() {
+        let entry = Entry(0, "hello".into(), vec![("trace_id".into(), "abc".into())]);
+        let entry = crate::logproto::EntryAdapter::from(entry);
+        assert_eq!(
+            entry.structured_metadata,
+            vec![crate::logproto::LabelPairAdapter {
+                name: "trace_id".into(),
+                value: "abc".into(),
+            }]
+        );
+    }
 }
@@ -1,6 +1,7 @@
 fn main() {
     println!("cargo:rerun-if-changed=proto/prometheus-remote.proto");
     println!("cargo:rerun-if-changed=proto/prometheus-types.proto");
+    println!("cargo:rerun-if-changed=proto/prometheus-write-v2.proto");
     let mut prost_build = prost_build::Config::new();
     prost_build.btree_map(["."]);
     // It would be nice to just add these derives to all the types, but
@@ -10,7 +11,10 @@ fn main() {
     prost_build.type_attribute("MetricType", "#[derive(num_enum::TryFromPrimitive)]");
     prost_build
         .compile_protos(
-            &["proto/prometheus-remote.proto"],
+            &[
+                "proto/prometheus-remote.proto",
+                "proto/prometheus-write-v2.proto",
+            ],
             &["proto", "../../proto"],
         )
         .unwrap();
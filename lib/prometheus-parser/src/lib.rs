@@ -32,6 +32,15 @@ pub mod proto {
             }
         }
     }
+
+    /// The messages of the [Remote Write 2.0][rw2] protocol.
+    ///
+    /// [rw2]: https://prometheus.io/docs/specs/remote_write_spec_2_0/
+    pub mod write_v2 {
+        include!(concat!(env!("OUT_DIR"), "/io.prometheus.write.v2.rs"));
+
+        pub use metadata::MetricType;
+    }
 }
 
 #[derive(Debug, snafu::Snafu, PartialEq)]
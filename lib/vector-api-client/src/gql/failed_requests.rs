@@ -0,0 +1,43 @@
+//! Failed requests queries, for debugging the requests sinks send.
+
+use async_trait::async_trait;
+use graphql_client::GraphQLQuery;
+
+/// Shorthand for a Chrono datetime, set to UTC.
+type DateTime = chrono::DateTime<chrono::Utc>;
+
+/// SinkFailedRequestsQuery returns the failed requests captured by the sinks matching the
+/// component ID patterns.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/queries/sink_failed_requests.graphql",
+    response_derives = "Debug, Serialize"
+)]
+pub struct SinkFailedRequestsQuery;
+
+/// Extension methods for failed requests queries.
+#[async_trait]
+pub trait FailedRequestsQueryExt {
+    /// Executes a failed requests query.
+    async fn sink_failed_requests(
+        &self,
+        component_id_patterns: Vec<String>,
+    ) -> crate::QueryResult<SinkFailedRequestsQuery>;
+}
+
+#[async_trait]
+impl FailedRequestsQueryExt for crate::Client {
+    /// Executes a failed requests query.
+    async fn sink_failed_requests(
+        &self,
+        component_id_patterns: Vec<String>,
+    ) -> crate::QueryResult<SinkFailedRequestsQuery> {
+        self.query::<SinkFailedRequestsQuery>(&SinkFailedRequestsQuery::build_query(
+            sink_failed_requests_query::Variables {
+                component_id_patterns,
+            },
+        ))
+        .await
+    }
+}
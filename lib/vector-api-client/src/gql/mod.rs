@@ -1,12 +1,14 @@
 //! Queries, subscriptions, and extension methods for executing them
 
 mod components;
+mod failed_requests;
 mod health;
 mod meta;
 mod metrics;
 mod tap;
 
 pub use components::*;
+pub use failed_requests::*;
 pub use health::*;
 pub use metrics::*;
 pub use tap::*;
@@ -273,6 +273,7 @@ where
             BufferType::DiskV2 {
                 max_size: max_size_bytes,
                 when_full,
+                evictable: false,
             }
         }
         s => panic!(
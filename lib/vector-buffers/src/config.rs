@@ -38,7 +38,7 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 5] = ["type", "max_events", "max_size", "when_full", "evictable"];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +51,7 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut evictable: Option<bool> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +78,12 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "evictable" => {
+                    if evictable.is_some() {
+                        return Err(de::Error::duplicate_field("evictable"));
+                    }
+                    evictable = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -92,6 +99,12 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if evictable.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "evictable",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
@@ -101,12 +114,13 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "when_full", "evictable"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    evictable: evictable.unwrap_or_default(),
                 })
             }
         }
@@ -175,6 +189,12 @@ impl DiskUsage {
     }
 }
 
+/// Gets the directory holding the disk buffer of the given component, under the global data
+/// directory.
+pub fn disk_buffer_dir(global_data_dir: &Path, id: &ComponentKey) -> PathBuf {
+    crate::variants::disk_v2::get_disk_v2_data_dir_path(global_data_dir, id.id())
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -218,6 +238,14 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        /// Whether the oldest data files of the buffer can be deleted to make room for other disk
+        /// buffers, once the disk buffers use up the `data_dir_budget`.
+        ///
+        /// This only applies when the `evict_oldest` policy is configured for the budget. The
+        /// events held by the deleted data files are dropped.
+        #[serde(default)]
+        evictable: bool,
     },
 }
 
@@ -286,9 +314,13 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                evictable,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, max_size).evictable(evictable),
+                    when_full,
+                );
             }
         };
 
@@ -350,6 +382,13 @@ impl BufferConfig {
         }
     }
 
+    /// Whether any of the configured stages stores its items on disk.
+    pub fn has_disk_stage(&self) -> bool {
+        self.stages()
+            .iter()
+            .any(|stage| matches!(stage, BufferType::DiskV2 { .. }))
+    }
+
     /// Builds the buffer components represented by this configuration.
     ///
     /// The caller gets back a `Sink` and `Stream` implementation that represent a way to push items
@@ -519,7 +558,31 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                evictable: false,
             },
         );
     }
+
+    #[test]
+    fn parse_evictable_disk_buffer() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          evictable: true
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                evictable: true,
+            },
+        );
+
+        let source = r#"
+          type: memory
+          evictable: true
+          "#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
 }
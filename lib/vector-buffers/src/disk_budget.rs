@@ -0,0 +1,326 @@
+//! Disk budget of the global data directory.
+//!
+//! Each disk buffer is bounded by its own `max_size`, but nothing bounds their sum, so a data
+//! directory holding many buffers can fill up the disk it lives on. The `data_dir_budget` global
+//! option caps the total size of the disk buffers, and decides what happens once they reach it:
+//! writers wait for space to be freed, the oldest data files of the buffers marked `evictable` are
+//! deleted, or an error is only logged.
+
+use std::{
+    cmp::Reverse,
+    fmt, io,
+    num::NonZeroU64,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
+};
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+use vector_common::internal_event::emit;
+use vector_config::configurable_component;
+
+use crate::internal_events::{
+    DataDirBudgetEviction, DataDirBudgetEvictionFailed, DataDirBudgetExceeded,
+};
+
+static BUDGET: RwLock<Option<Arc<DiskBudget>>> = RwLock::new(None);
+
+/// Disk budget of the global data directory.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DataDirBudgetConfig {
+    /// The maximum number of bytes that the disk buffers in the data directory can use in total.
+    ///
+    /// By default, disk buffers are only bounded by their own `max_size`.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: Option<NonZeroU64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub policy: DataDirBudgetPolicy,
+}
+
+/// What happens once the disk buffers use up the disk budget of the data directory.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDirBudgetPolicy {
+    /// Disk buffers are considered full until space is freed, and apply their `when_full`
+    /// behavior.
+    #[default]
+    Block,
+
+    /// The oldest data files of the disk buffers marked `evictable` are deleted to make room,
+    /// starting with the largest buffer, and the events they held are dropped.
+    ///
+    /// The data files being read are never evicted, so disk buffers are considered full when
+    /// there is nothing left to evict.
+    EvictOldest,
+
+    /// Disk buffers keep growing past the budget, and an error is logged.
+    Alert,
+}
+
+/// A disk buffer whose oldest data can be deleted to make room for others.
+#[async_trait]
+pub(crate) trait Evictable: Send + Sync {
+    /// The number of bytes used by the buffer.
+    fn used_bytes(&self) -> u64;
+
+    /// Deletes the oldest data that can be deleted, returning the number of bytes freed.
+    async fn evict_oldest(&self) -> io::Result<u64>;
+}
+
+/// Tracks the bytes used by the disk buffers against the disk budget.
+pub struct DiskBudget {
+    max_bytes: u64,
+    policy: DataDirBudgetPolicy,
+    used_bytes: AtomicU64,
+    exceeded: AtomicBool,
+    space_freed: Notify,
+    evictables: Mutex<Vec<Weak<dyn Evictable>>>,
+    evicting: tokio::sync::Mutex<()>,
+}
+
+/// Applies the disk budget of the data directory.
+///
+/// Global options can't change while reloading, so this only takes effect for the disk buffers
+/// built afterwards.
+///
+/// # Panics
+///
+/// Panics if the lock of the budget is poisoned.
+pub fn apply(config: DataDirBudgetConfig) {
+    let budget = config
+        .max_bytes
+        .map(|max_bytes| Arc::new(DiskBudget::new(max_bytes.get(), config.policy)));
+    *BUDGET.write().expect("Disk budget lock is poisoned") = budget;
+}
+
+/// Gets the disk budget of the data directory, if any.
+///
+/// # Panics
+///
+/// Panics if the lock of the budget is poisoned.
+pub fn current() -> Option<Arc<DiskBudget>> {
+    BUDGET.read().expect("Disk budget lock is poisoned").clone()
+}
+
+impl DiskBudget {
+    pub(crate) fn new(max_bytes: u64, policy: DataDirBudgetPolicy) -> Self {
+        Self {
+            max_bytes,
+            policy,
+            used_bytes: AtomicU64::new(0),
+            exceeded: AtomicBool::new(false),
+            space_freed: Notify::new(),
+            evictables: Mutex::default(),
+            evicting: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// The maximum number of bytes the disk buffers can use in total.
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// The number of bytes used by the disk buffers in total.
+    pub fn used_bytes(&self) -> u64 {
+        self.used_bytes.load(Ordering::Acquire)
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.used_bytes() > self.max_bytes
+    }
+
+    /// Whether writes to disk buffers must wait for space to be freed.
+    pub(crate) fn blocks_writes(&self) -> bool {
+        self.policy != DataDirBudgetPolicy::Alert && self.is_exceeded()
+    }
+
+    pub(crate) fn add(&self, amount: u64) {
+        let used_bytes = self.used_bytes.fetch_add(amount, Ordering::AcqRel) + amount;
+        if used_bytes > self.max_bytes && !self.exceeded.swap(true, Ordering::AcqRel) {
+            emit(DataDirBudgetExceeded {
+                used_bytes,
+                max_bytes: self.max_bytes,
+                policy: self.policy,
+            });
+        }
+    }
+
+    pub(crate) fn release(&self, amount: u64) {
+        let previous = self
+            .used_bytes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used_bytes| {
+                Some(used_bytes.saturating_sub(amount))
+            })
+            .unwrap_or_else(|used_bytes| used_bytes);
+        if previous > self.max_bytes {
+            if previous.saturating_sub(amount) <= self.max_bytes {
+                self.exceeded.store(false, Ordering::Release);
+            }
+            self.space_freed.notify_waiters();
+        }
+    }
+
+    /// Waits for space to be freed, if the budget is exceeded.
+    pub(crate) async fn wait_for_space(&self) {
+        let notified = self.space_freed.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_exceeded() {
+            notified.await;
+        }
+    }
+
+    pub(crate) fn register(&self, evictable: Weak<dyn Evictable>) {
+        let mut evictables = self
+            .evictables
+            .lock()
+            .expect("Disk budget mutex is poisoned");
+        evictables.retain(|evictable| evictable.strong_count() > 0);
+        evictables.push(evictable);
+    }
+
+    /// Evicts the oldest data of the largest evictable buffer that has some, if the budget is
+    /// exceeded and the policy allows it.
+    ///
+    /// Returns `false` if the budget is still exceeded because there was nothing left to evict.
+    pub(crate) async fn evict(&self) -> bool {
+        if self.policy != DataDirBudgetPolicy::EvictOldest {
+            return false;
+        }
+
+        // Writers of several buffers may find the budget exceeded at once, but one eviction is
+        // usually enough for all of them.
+        let _evicting = self.evicting.lock().await;
+        if !self.is_exceeded() {
+            return true;
+        }
+
+        let mut evictables = self
+            .evictables
+            .lock()
+            .expect("Disk budget mutex is poisoned")
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+        evictables.sort_by_key(|evictable| Reverse(evictable.used_bytes()));
+
+        for evictable in evictables {
+            match evictable.evict_oldest().await {
+                Ok(0) => {}
+                Ok(bytes) => {
+                    emit(DataDirBudgetEviction { bytes });
+                    return true;
+                }
+                Err(error) => emit(DataDirBudgetEvictionFailed { error }),
+            }
+        }
+        false
+    }
+}
+
+impl fmt::Debug for DiskBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiskBudget")
+            .field("max_bytes", &self.max_bytes)
+            .field("policy", &self.policy)
+            .field("used_bytes", &self.used_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestEvictable {
+        budget: Arc<DiskBudget>,
+        files: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl Evictable for TestEvictable {
+        fn used_bytes(&self) -> u64 {
+            self.files.lock().unwrap().iter().sum()
+        }
+
+        async fn evict_oldest(&self) -> io::Result<u64> {
+            let mut files = self.files.lock().unwrap();
+            if files.is_empty() {
+                return Ok(0);
+            }
+            let bytes = files.remove(0);
+            self.budget.release(bytes);
+            Ok(bytes)
+        }
+    }
+
+    fn evictable(budget: &Arc<DiskBudget>, files: Vec<u64>) -> Arc<TestEvictable> {
+        budget.add(files.iter().sum());
+        let evictable = Arc::new(TestEvictable {
+            budget: Arc::clone(budget),
+            files: Mutex::new(files),
+        });
+        let weak: Weak<dyn Evictable> = Arc::downgrade(&evictable);
+        budget.register(weak);
+        evictable
+    }
+
+    #[tokio::test]
+    async fn blocks_writes_until_space_is_freed() {
+        let budget = Arc::new(DiskBudget::new(100, DataDirBudgetPolicy::Block));
+        budget.add(150);
+        assert!(budget.blocks_writes());
+        assert!(!budget.evict().await);
+
+        let freed = Arc::new(AtomicU64::new(0));
+        let waiter = tokio::spawn({
+            let budget = Arc::clone(&budget);
+            let freed = Arc::clone(&freed);
+            async move {
+                budget.wait_for_space().await;
+                freed.load(Ordering::Acquire)
+            }
+        });
+        tokio::task::yield_now().await;
+
+        freed.store(60, Ordering::Release);
+        budget.release(60);
+        assert_eq!(waiter.await.unwrap(), 60);
+        assert!(!budget.blocks_writes());
+        assert_eq!(budget.used_bytes(), 90);
+    }
+
+    #[tokio::test]
+    async fn evicts_from_the_largest_evictable_buffer() {
+        let budget = Arc::new(DiskBudget::new(100, DataDirBudgetPolicy::EvictOldest));
+        let small = evictable(&budget, vec![30]);
+        let large = evictable(&budget, vec![40, 50]);
+        assert!(budget.blocks_writes());
+
+        assert!(budget.evict().await);
+        assert_eq!(*large.files.lock().unwrap(), vec![50]);
+        assert_eq!(*small.files.lock().unwrap(), vec![30]);
+        assert_eq!(budget.used_bytes(), 80);
+
+        // Nothing is evicted while the budget isn't exceeded.
+        assert!(budget.evict().await);
+        assert_eq!(budget.used_bytes(), 80);
+    }
+
+    #[tokio::test]
+    async fn alert_never_blocks_writes() {
+        let budget = DiskBudget::new(100, DataDirBudgetPolicy::Alert);
+        budget.add(150);
+        assert!(!budget.blocks_writes());
+        assert!(!budget.evict().await);
+    }
+}
@@ -1,6 +1,8 @@
 use metrics::{counter, decrement_gauge, gauge, increment_gauge};
 use vector_common::internal_event::{error_type, InternalEvent};
 
+use crate::disk_budget::DataDirBudgetPolicy;
+
 pub struct BufferCreated {
     pub idx: usize,
     pub max_size_events: usize,
@@ -112,3 +114,65 @@ impl InternalEvent for BufferReadError {
         );
     }
 }
+
+pub struct DataDirBudgetExceeded {
+    pub used_bytes: u64,
+    pub max_bytes: u64,
+    pub policy: DataDirBudgetPolicy,
+}
+
+impl InternalEvent for DataDirBudgetExceeded {
+    fn emit(self) {
+        let policy = match self.policy {
+            DataDirBudgetPolicy::Block => "block",
+            DataDirBudgetPolicy::EvictOldest => "evict_oldest",
+            DataDirBudgetPolicy::Alert => "alert",
+        };
+        error!(
+            message = "Disk buffers exceeded the disk budget of the data directory.",
+            used_bytes = %self.used_bytes,
+            max_bytes = %self.max_bytes,
+            policy,
+            internal_log_rate_limit = true,
+        );
+        counter!("data_dir_budget_exceeded_total", 1, "policy" => policy);
+    }
+}
+
+pub struct DataDirBudgetEviction {
+    pub bytes: u64,
+}
+
+impl InternalEvent for DataDirBudgetEviction {
+    fn emit(self) {
+        warn!(
+            message = "Evicted the oldest data file of a disk buffer to free space in the data directory.",
+            bytes = %self.bytes,
+            internal_log_rate_limit = true,
+        );
+        counter!("data_dir_budget_evicted_files_total", 1);
+        counter!("data_dir_budget_evicted_bytes_total", self.bytes);
+    }
+}
+
+pub struct DataDirBudgetEvictionFailed {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for DataDirBudgetEvictionFailed {
+    fn emit(self) {
+        error!(
+            message = "Failed evicting data file of disk buffer.",
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = "processing",
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "buffer_errors_total", 1,
+            "error_code" => "eviction_failed",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => "processing",
+        );
+    }
+}
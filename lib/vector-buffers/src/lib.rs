@@ -16,6 +16,7 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
+pub mod disk_budget;
 pub use config::{BufferConfig, BufferType};
 use encoding::Encodable;
 use vector_config::configurable_component;
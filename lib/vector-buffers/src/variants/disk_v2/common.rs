@@ -1,5 +1,6 @@
 use std::{
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -11,6 +12,7 @@ use super::{
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
 };
+use crate::disk_budget::DiskBudget;
 
 // We don't want data files to be bigger than 128MB, but we might end up overshooting slightly.
 pub const DEFAULT_MAX_DATA_FILE_SIZE: usize = 128 * 1024 * 1024;
@@ -159,6 +161,15 @@ pub struct DiskBufferConfig<FS> {
     /// implementation essentially defines how we open and delete data files, as well as the type of
     /// the data file objects we get when opening a data file.
     pub(crate) filesystem: FS,
+
+    /// Disk budget shared with the other disk buffers in the data directory.
+    ///
+    /// The size of the buffer counts towards the budget, and writes are limited by it in addition
+    /// to the maximum buffer size.
+    pub(crate) budget: Option<Arc<DiskBudget>>,
+
+    /// Whether the oldest data files of the buffer can be deleted when the budget is exceeded.
+    pub(crate) evictable: bool,
 }
 
 /// Builder for [`DiskBufferConfig`].
@@ -174,6 +185,8 @@ where
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
     pub(crate) filesystem: FS,
+    pub(crate) budget: Option<Arc<DiskBudget>>,
+    pub(crate) evictable: bool,
 }
 
 impl DiskBufferConfigBuilder {
@@ -189,6 +202,8 @@ impl DiskBufferConfigBuilder {
             write_buffer_size: None,
             flush_interval: None,
             filesystem: ProductionFilesystem,
+            budget: None,
+            evictable: false,
         }
     }
 }
@@ -268,6 +283,26 @@ where
         self
     }
 
+    /// Sets the disk budget shared with the other disk buffers in the data directory.
+    ///
+    /// When the budget is exceeded, writes wait for space to be freed in any of the buffers
+    /// sharing it, unless the policy of the budget says otherwise.
+    ///
+    /// Defaults to no budget.
+    pub fn budget(mut self, budget: Option<Arc<DiskBudget>>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Sets whether the oldest data files of the buffer can be deleted to bring the disk budget
+    /// back within its limit.
+    ///
+    /// Defaults to `false`.
+    pub fn evictable(mut self, evictable: bool) -> Self {
+        self.evictable = evictable;
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -288,6 +323,8 @@ where
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
             filesystem,
+            budget: self.budget,
+            evictable: self.evictable,
         }
     }
 
@@ -302,6 +339,8 @@ where
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
         let filesystem = self.filesystem;
+        let budget = self.budget;
+        let evictable = self.evictable;
 
         // Validate the input parameters.
         if max_data_file_size == 0 {
@@ -413,6 +452,8 @@ where
             write_buffer_size,
             flush_interval,
             filesystem,
+            budget,
+            evictable,
         })
     }
 }
@@ -1,11 +1,14 @@
 use std::{
-    fmt, io, mem,
+    fmt,
+    io::{self, ErrorKind},
+    mem,
     path::PathBuf,
     sync::atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering},
     sync::Arc,
     time::Instant,
 };
 
+use async_trait::async_trait;
 use bytecheck::CheckBytes;
 use bytes::BytesMut;
 use crossbeam_utils::atomic::AtomicCell;
@@ -13,7 +16,11 @@ use fslock::LockFile;
 use futures::StreamExt;
 use rkyv::{with::Atomic, Archive, Serialize};
 use snafu::{ResultExt, Snafu};
-use tokio::{fs, io::AsyncWriteExt, sync::Notify};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{Mutex, MutexGuard, Notify},
+};
 use vector_common::finalizer::OrderedFinalizer;
 
 use super::{
@@ -23,7 +30,7 @@ use super::{
     ser::SerializeError,
     Filesystem,
 };
-use crate::buffer_usage_data::BufferUsageHandle;
+use crate::{buffer_usage_data::BufferUsageHandle, disk_budget::Evictable};
 
 pub const LEDGER_LEN: usize = align16(mem::size_of::<ArchivedLedgerState>());
 
@@ -232,6 +239,8 @@ where
     last_flush: AtomicCell<Instant>,
     // Tracks usage data about the buffer.
     usage_handle: BufferUsageHandle,
+    // Serializes the reader opening a data file with data files being evicted.
+    data_file_lock: Mutex<()>,
 }
 
 impl<FS> Ledger<FS>
@@ -281,6 +290,9 @@ where
     /// Increments the total number of bytes for all unread records in the buffer.
     pub fn increment_total_buffer_size(&self, amount: u64) {
         let last_total_buffer_size = self.total_buffer_size.fetch_add(amount, Ordering::AcqRel);
+        if let Some(budget) = &self.config.budget {
+            budget.add(amount);
+        }
         trace!(
             previous_buffer_size = last_total_buffer_size,
             new_buffer_size = last_total_buffer_size + amount,
@@ -291,6 +303,9 @@ where
     /// Decrements the total number of bytes for all unread records in the buffer.
     pub fn decrement_total_buffer_size(&self, amount: u64) {
         let last_total_buffer_size = self.total_buffer_size.fetch_sub(amount, Ordering::AcqRel);
+        if let Some(budget) = &self.config.budget {
+            budget.release(amount);
+        }
         trace!(
             previous_buffer_size = last_total_buffer_size,
             new_buffer_size = last_total_buffer_size - amount,
@@ -298,6 +313,21 @@ where
         );
     }
 
+    /// Returns `true` if writes must wait for the disk budget shared with other buffers to be
+    /// freed, regardless of the size of this buffer.
+    pub fn is_over_budget(&self) -> bool {
+        matches!(&self.config.budget, Some(budget) if budget.blocks_writes())
+    }
+
+    /// Waits for a signal that the disk budget shared with other buffers has been freed.
+    ///
+    /// Returns immediately if there is no budget, or if it isn't exceeded.
+    pub async fn wait_for_budget(&self) {
+        if let Some(budget) = &self.config.budget {
+            budget.wait_for_space().await;
+        }
+    }
+
     /// Gets the current reader file ID.
     ///
     /// This is internally adjusted to compensate for the fact that the reader can read far past
@@ -333,6 +363,15 @@ where
         (reader, writer)
     }
 
+    /// Locks the data files against eviction.
+    ///
+    /// The reader holds this while it opens its current data file, so that a data file is either
+    /// opened by the reader before it can be considered for eviction, or evicted before the reader
+    /// gets to it, in which case the reader will not find it and skip it.
+    pub async fn lock_data_files(&self) -> MutexGuard<'_, ()> {
+        self.data_file_lock.lock().await
+    }
+
     /// Gets the current reader data file path, accounting for the unacknowledged offset.
     pub fn get_current_reader_data_file_path(&self) -> PathBuf {
         self.get_data_file_path(self.get_current_reader_file_id())
@@ -650,6 +689,7 @@ where
             unacked_reader_file_id_offset: AtomicU16::new(0),
             last_flush: AtomicCell::new(Instant::now()),
             usage_handle,
+            data_file_lock: Mutex::new(()),
         };
         ledger.update_buffer_size().await?;
 
@@ -703,6 +743,57 @@ where
         Ok(())
     }
 
+    /// Deletes the oldest data file that the reader can't get to before the deletion completes,
+    /// dropping the records it holds, and returns its size.
+    ///
+    /// Only data files at least two past the current reader data file, and before the current
+    /// writer data file, are evicted.  The data files are locked against the reader opening them
+    /// until the deletion is accounted for, so the reader either has the data file open already, in
+    /// which case it is its current data file and isn't evicted, or doesn't find it.  The reader
+    /// treats the missing data file as an empty one, and the records it held show up as skipped.
+    ///
+    /// Returns zero if there is no such data file.
+    ///
+    /// # Errors
+    ///
+    /// If there is an error while getting the size of the data file, or deleting it, an error
+    /// variant will be returned describing the error.
+    pub async fn evict_oldest_data_file(&self) -> io::Result<u64> {
+        // Held until the data file is deleted and accounted for, as the reader could otherwise
+        // open the data file in the meantime and read records whose size we'd subtract again.
+        let _data_files = self.lock_data_files().await;
+
+        let (reader_file_id, writer_file_id) = self.get_current_reader_writer_file_id();
+        let writer_offset = writer_file_id.wrapping_sub(reader_file_id);
+
+        for offset in 2..writer_offset {
+            let data_file_path = self.get_data_file_path(reader_file_id.wrapping_add(offset));
+            let data_file = match self.filesystem().open_file_readable(&data_file_path).await {
+                Ok(data_file) => data_file,
+                // Already evicted.
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let data_file_size = data_file.metadata().await?.len();
+            drop(data_file);
+
+            self.filesystem().delete_file(&data_file_path).await?;
+            self.decrement_total_buffer_size(data_file_size);
+
+            debug!(
+                data_file_path = data_file_path.to_string_lossy().as_ref(),
+                data_file_size, "Evicted data file."
+            );
+
+            // Writers of this buffer may be waiting for space too.
+            self.notify_reader_waiters();
+
+            return Ok(data_file_size);
+        }
+
+        Ok(0)
+    }
+
     #[must_use]
     pub(super) fn spawn_finalizer(self: Arc<Self>) -> OrderedFinalizer<u64> {
         let (finalizer, mut stream) = OrderedFinalizer::new(None);
@@ -716,6 +807,33 @@ where
     }
 }
 
+#[async_trait]
+impl<FS> Evictable for Ledger<FS>
+where
+    FS: Filesystem + 'static,
+    FS::File: Unpin,
+{
+    fn used_bytes(&self) -> u64 {
+        self.get_total_buffer_size()
+    }
+
+    async fn evict_oldest(&self) -> io::Result<u64> {
+        self.evict_oldest_data_file().await
+    }
+}
+
+impl<FS> Drop for Ledger<FS>
+where
+    FS: Filesystem,
+{
+    fn drop(&mut self) {
+        // The data files left behind count towards the budget again once the buffer is loaded.
+        if let Some(budget) = &self.config.budget {
+            budget.release(self.get_total_buffer_size());
+        }
+    }
+}
+
 impl<FS> fmt::Debug for Ledger<FS>
 where
     FS: Filesystem + fmt::Debug,
@@ -175,7 +175,7 @@ use std::{
     marker::PhantomData,
     num::NonZeroU64,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Weak},
 };
 
 use async_trait::async_trait;
@@ -204,6 +204,7 @@ pub use self::{
 };
 use crate::{
     buffer_usage_data::BufferUsageHandle,
+    disk_budget::{self, Evictable},
     topology::{
         builder::IntoBuffer,
         channel::{ReceiverAdapter, SenderAdapter},
@@ -252,6 +253,10 @@ where
             .await
             .context(LedgerSnafu)?;
         let ledger = Arc::new(ledger);
+        if let (Some(budget), true) = (&ledger.config().budget, ledger.config().evictable) {
+            let evictable: Weak<dyn Evictable> = Arc::downgrade(&ledger);
+            budget.register(evictable);
+        }
 
         let mut writer = Writer::new(Arc::clone(&ledger));
         writer
@@ -301,6 +306,7 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    evictable: bool,
 }
 
 impl DiskV2Buffer {
@@ -309,8 +315,16 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            evictable: false,
         }
     }
+
+    /// Allows the oldest data files of the buffer to be deleted when the disk budget of the data
+    /// directory is exceeded.
+    pub fn evictable(mut self, evictable: bool) -> Self {
+        self.evictable = evictable;
+        self
+    }
 }
 
 #[async_trait]
@@ -331,6 +345,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.evictable,
         )
         .await?;
 
@@ -343,6 +358,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    evictable: bool,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -358,6 +374,8 @@ where
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
     let config = DiskBufferConfigBuilder::from_path(buffer_path)
         .max_buffer_size(max_size.get())
+        .budget(disk_budget::current())
+        .evictable(evictable)
         .build()?;
     Buffer::from_config(config, usage_handle)
         .await
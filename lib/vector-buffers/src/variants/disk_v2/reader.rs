@@ -3,7 +3,7 @@ use std::{
     io::{self, ErrorKind},
     marker::PhantomData,
     num::NonZeroU64,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
@@ -511,11 +511,43 @@ where
         // occur at all, so we're relying on this method to correct the buffer size for us.  This is
         // why `bytes_read` is optional: when it's specified, we calculate a delta for handling
         // partial-read scenarios, otherwise, we just use the entire data file size as is.
-        let data_file = self
+        //
+        // Data files evicted to free the disk budget are already gone, and were accounted for when
+        // they were deleted.
+        let data_file = match self
             .ledger
             .filesystem()
             .open_file_readable(&data_file_path)
-            .await?;
+            .await
+        {
+            Ok(data_file) => Some(data_file),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        if let Some(data_file) = data_file {
+            self.delete_data_file(data_file, &data_file_path, bytes_read)
+                .await?;
+        }
+
+        self.ledger.increment_acked_reader_file_id();
+        self.ledger.flush()?;
+
+        debug!("Flushed after deleting data file, notifying writers and continuing.");
+
+        // Notify any waiting writers that we've deleted a data file, which they may be waiting on
+        // because they're looking to reuse the file ID of the file we just finished reading.
+        self.ledger.notify_reader_waiters();
+
+        Ok(())
+    }
+
+    async fn delete_data_file(
+        &self,
+        data_file: FS::File,
+        data_file_path: &Path,
+        bytes_read: Option<u64>,
+    ) -> io::Result<()> {
         let metadata = data_file.metadata().await?;
 
         let decrease_amount = bytes_read.map_or_else(
@@ -540,21 +572,8 @@ where
 
         drop(data_file);
 
-        // Delete the current data file, and increment our actual reader file ID.
-        self.ledger
-            .filesystem()
-            .delete_file(&data_file_path)
-            .await?;
-        self.ledger.increment_acked_reader_file_id();
-        self.ledger.flush()?;
-
-        debug!("Flushed after deleting data file, notifying writers and continuing.");
-
-        // Notify any waiting writers that we've deleted a data file, which they may be waiting on
-        // because they're looking to reuse the file ID of the file we just finished reading.
-        self.ledger.notify_reader_waiters();
-
-        Ok(())
+        // Delete the current data file, and have the caller increment our actual reader file ID.
+        self.ledger.filesystem().delete_file(data_file_path).await
     }
 
     #[cfg_attr(test, instrument(skip(self), level = "debug"))]
@@ -753,14 +772,18 @@ where
         // we'll simply wait for the writer to signal to us that progress has been made, which
         // implies a data file existing.
         loop {
+            // The data file can't be evicted between getting its path and opening it.
+            let data_files = self.ledger.lock_data_files().await;
             let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
             let data_file_path = self.ledger.get_current_reader_data_file_path();
-            let data_file = match self
+            let result = self
                 .ledger
                 .filesystem()
                 .open_file_readable(&data_file_path)
-                .await
-            {
+                .await;
+            drop(data_files);
+
+            let data_file = match result {
                 Ok(data_file) => data_file,
                 Err(e) => match e.kind() {
                     ErrorKind::NotFound => {
@@ -770,6 +793,15 @@ where
                                 "Data file does not yet exist. Waiting for writer to create."
                             );
                             self.ledger.wait_for_writer().await;
+                        } else if self.ready_to_read {
+                            // The data file was evicted to free the disk budget, so it's skipped
+                            // like an empty one, and the records it held are accounted for as
+                            // skipped once the records after it are acknowledged.
+                            debug!(
+                                data_file_path = data_file_path.to_string_lossy().as_ref(),
+                                "Data file was evicted. Skipping to next data file."
+                            );
+                            self.roll_to_next_data_file();
                         } else {
                             self.ledger.increment_acked_reader_file_id();
                         }
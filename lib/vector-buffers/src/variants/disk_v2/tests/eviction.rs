@@ -0,0 +1,129 @@
+use tokio_test::{assert_pending, assert_ready, task::spawn};
+
+use super::{
+    create_buffer_v2_with_max_data_file_size_and_usage,
+    get_minimum_data_file_size_for_record_payload, read_next, read_next_some,
+};
+use crate::{
+    assert_buffer_is_empty, assert_buffer_size, assert_reader_writer_v2_file_positions,
+    test::{acknowledge, with_temp_dir, SizedRecord},
+};
+
+#[tokio::test]
+async fn reader_skips_evicted_data_file() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Each record is too big to share a data file with any other, so the four records end
+            // up in data files 0 through 3.
+            let records = [80, 84, 88, 92].map(SizedRecord::new);
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&records[3]);
+            let (mut writer, mut reader, ledger, usage) =
+                create_buffer_v2_with_max_data_file_size_and_usage(data_dir, max_data_file_size)
+                    .await;
+
+            let mut bytes_written = Vec::new();
+            for record in records.clone() {
+                let written = writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+                bytes_written.push(written as u64);
+            }
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            let total_bytes_written: u64 = bytes_written.iter().sum();
+            assert_buffer_size!(ledger, 4, total_bytes_written);
+            assert_reader_writer_v2_file_positions!(ledger, 0, 3);
+
+            // Only data file 2 is far enough ahead of the reader, and behind the writer, to be
+            // evicted, so the second eviction has nothing left to do.
+            let evicted_data_file_path = ledger.get_data_file_path(2);
+            let evicted = ledger
+                .evict_oldest_data_file()
+                .await
+                .expect("eviction should not fail");
+            assert_eq!(bytes_written[2], evicted);
+            assert!(!evicted_data_file_path.exists());
+            assert_buffer_size!(ledger, 4, total_bytes_written - evicted);
+
+            let evicted = ledger
+                .evict_oldest_data_file()
+                .await
+                .expect("eviction should not fail");
+            assert_eq!(0, evicted);
+            assert_buffer_size!(ledger, 4, total_bytes_written - bytes_written[2]);
+
+            // The reader goes straight from the second record to the fourth one.
+            for expected in [&records[0], &records[1], &records[3]] {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(expected, &record);
+                acknowledge(record).await;
+            }
+            assert_eq!(None, read_next(&mut reader).await);
+
+            // The record in the evicted data file was only accounted for once, and shows up as
+            // dropped, while the deletion marker for the missing data file still moved the reader
+            // along to the last data file.
+            assert_buffer_is_empty!(ledger);
+            assert_reader_writer_v2_file_positions!(ledger, 3, 3);
+
+            let snapshot = usage.snapshot();
+            assert_eq!(3, snapshot.sent_event_count);
+            assert_eq!(
+                total_bytes_written - bytes_written[2],
+                snapshot.sent_byte_size
+            );
+            assert_eq!(1, snapshot.dropped_event_count);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn eviction_waits_for_reader_to_open_data_file() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let records = [80, 84, 88, 92].map(SizedRecord::new);
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&records[3]);
+            let (mut writer, _reader, ledger, _usage) =
+                create_buffer_v2_with_max_data_file_size_and_usage::<_, SizedRecord>(
+                    data_dir,
+                    max_data_file_size,
+                )
+                .await;
+
+            let mut bytes_written = Vec::new();
+            for record in records.clone() {
+                let written = writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+                bytes_written.push(written as u64);
+            }
+            writer.flush().await.expect("flush should not fail");
+
+            let total_bytes_written: u64 = bytes_written.iter().sum();
+
+            // While the reader is opening a data file, eviction can't pick a data file to delete,
+            // since the reader could be about to open the very same one.
+            let data_files = ledger.lock_data_files().await;
+
+            let mut eviction = spawn(ledger.evict_oldest_data_file());
+            assert_pending!(eviction.poll());
+            assert_buffer_size!(ledger, 4, total_bytes_written);
+
+            drop(data_files);
+            assert!(eviction.is_woken());
+
+            let evicted = assert_ready!(eviction.poll()).expect("eviction should not fail");
+            assert_eq!(bytes_written[2], evicted);
+            assert_buffer_size!(ledger, 4, total_bytes_written - evicted);
+        }
+    })
+    .await;
+}
@@ -25,6 +25,7 @@ type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod eviction;
 mod initialization;
 mod invariants;
 mod known_errors;
@@ -339,6 +340,37 @@ where
         .expect("should not fail to create buffer")
 }
 
+/// Creates a disk v2 buffer with the specified maximum data file size, but returns a handle to the
+/// buffer usage tracker.
+///
+/// We additionally constrain our maximum record size to the maximum data file size in order to satisfy the configuration builder.
+pub(crate) async fn create_buffer_v2_with_max_data_file_size_and_usage<P, R>(
+    data_dir: P,
+    max_data_file_size: u64,
+) -> (
+    Writer<R, FilesystemUnderTest>,
+    Reader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+    BufferUsageHandle,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let max_record_size = usize::try_from(max_data_file_size).unwrap();
+
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .max_data_file_size(max_data_file_size)
+        .max_record_size(max_record_size)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+    let (writer, reader, ledger) = Buffer::from_config_inner(config, usage_handle.clone())
+        .await
+        .expect("should not fail to create buffer");
+    (writer, reader, ledger, usage_handle)
+}
+
 /// Creates a disk v2 buffer with the specified write buffer size.
 pub(crate) async fn create_buffer_v2_with_write_buffer_size<P, R>(
     data_dir: P,
@@ -990,7 +990,28 @@ where
     fn is_buffer_full(&self) -> bool {
         let total_buffer_size = self.ledger.get_total_buffer_size() + self.unflushed_bytes;
         let max_buffer_size = self.config.max_buffer_size;
-        total_buffer_size >= max_buffer_size
+        total_buffer_size >= max_buffer_size || self.ledger.is_over_budget()
+    }
+
+    /// Waits for the reader to make progress, or for the disk budget to be freed when it's what
+    /// keeps the buffer full.
+    async fn wait_for_space(&self) {
+        if self.ledger.is_over_budget() {
+            tokio::select! {
+                () = self.ledger.wait_for_reader() => {},
+                () = self.ledger.wait_for_budget() => {},
+            }
+        } else {
+            self.ledger.wait_for_reader().await;
+        }
+    }
+
+    /// Evicts data from the buffers marked as evictable while the disk budget is exceeded, if its
+    /// policy allows it.
+    async fn enforce_budget(&self) {
+        if let Some(budget) = &self.config.budget {
+            while budget.blocks_writes() && budget.evict().await {}
+        }
     }
 
     /// Ensures this writer is ready to attempt writer the next record.
@@ -1013,7 +1034,7 @@ where
                 "Buffer size limit reached. Waiting for reader progress."
             );
 
-            self.ledger.wait_for_reader().await;
+            self.wait_for_space().await;
         }
 
         // If we already have an open writer, and we have no more space in the data file to write,
@@ -1174,6 +1195,9 @@ where
         &mut self,
         mut record: T,
     ) -> Result<Result<usize, T>, WriterError<T>> {
+        // Make room within the disk budget first, in case the buffers can be evicted.
+        self.enforce_budget().await;
+
         // If the buffer is already full, we definitely can't complete this write.
         if self.is_buffer_full() {
             return Ok(Err(record));
@@ -1293,7 +1317,7 @@ where
                 Ok(bytes_written) => return Ok(bytes_written),
                 Err(old_record) => {
                     record = old_record;
-                    self.ledger.wait_for_reader().await;
+                    self.wait_for_space().await;
                     continue;
                 }
             }
@@ -0,0 +1,66 @@
+use std::net::SocketAddr;
+
+use vector_config::configurable_component;
+
+/// DNS configuration.
+///
+/// Configures how the hostnames that components connect to are resolved.
+///
+/// Connections that stay open for a long time, such as those of the `socket` and `websocket`
+/// sinks, resolve their host again whenever its records expire, and reconnect when the records
+/// changed.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DnsConfig {
+    /// The nameservers to query, including their port.
+    ///
+    /// By default, hostnames are resolved by the system resolver, which also looks them up in
+    /// local sources such as `/etc/hosts`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[configurable(metadata(docs::examples = "10.0.0.2:53"))]
+    pub nameservers: Vec<SocketAddr>,
+
+    /// The minimum number of seconds to use resolved records for, overriding lower TTLs.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub min_ttl_secs: Option<u64>,
+
+    /// The maximum number of seconds to use resolved records for, overriding higher TTLs.
+    ///
+    /// The system resolver does not return TTLs, so its records are considered valid for 60
+    /// seconds, within the minimum and maximum.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub max_ttl_secs: Option<u64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub ip_strategy: DnsIpStrategy,
+}
+
+/// The IP versions of the addresses to connect to, and their order.
+///
+/// When connecting, the addresses of a host are tried in order. HTTP connections race the IPv4
+/// and IPv6 addresses ("happy eyeballs") when both are used.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DnsIpStrategy {
+    /// Both IPv4 and IPv6 addresses are used, in the order they are resolved.
+    #[default]
+    Ipv4AndIpv6,
+
+    /// Only IPv4 addresses are used.
+    Ipv4Only,
+
+    /// Only IPv6 addresses are used.
+    Ipv6Only,
+
+    /// IPv4 addresses are tried before IPv6 addresses.
+    PreferIpv4,
+
+    /// IPv6 addresses are tried before IPv4 addresses.
+    PreferIpv6,
+}
@@ -1,11 +1,12 @@
-use std::{fs::DirBuilder, path::PathBuf, time::Duration};
+use std::{fs::DirBuilder, num::NonZeroUsize, path::PathBuf, time::Duration};
 
 use snafu::{ResultExt, Snafu};
+use vector_buffers::disk_budget::DataDirBudgetConfig;
 use vector_common::TimeZone;
 use vector_config::configurable_component;
 
 use super::super::default_data_dir;
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{dns::DnsConfig, proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -74,6 +75,13 @@ pub struct GlobalOptions {
     )]
     pub proxy: ProxyConfig,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub dns: DnsConfig,
+
     /// Controls how acknowledgements are handled for all sinks by default.
     ///
     /// See [End-to-end Acknowledgements][e2e_acks] for more information on how Vector handles event
@@ -115,6 +123,30 @@ pub struct GlobalOptions {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub expire_metrics_secs: Option<f64>,
+
+    /// The maximum number of bytes of events held by all sinks together, from when they take
+    /// events from their buffers until they are done sending them.
+    ///
+    /// This covers the events being batched and encoded into requests, as well as those of
+    /// requests in flight or being retried, which are not bounded by buffers. Once reached, sinks
+    /// wait for their requests to complete before taking more events, applying backpressure to
+    /// their buffers.
+    ///
+    /// Not set by default, which allows sinks to hold as many events as their batching and
+    /// request settings allow.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub sink_in_flight_bytes_limit: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub data_dir_budget: DataDirBudgetConfig,
 }
 
 impl GlobalOptions {
@@ -189,6 +221,20 @@ impl GlobalOptions {
             errors.push("conflicting values for 'proxy.no_proxy' found".to_owned());
         }
 
+        if self.dns != DnsConfig::default()
+            && with.dns != DnsConfig::default()
+            && self.dns != with.dns
+        {
+            errors.push("conflicting values for 'dns' found".to_owned());
+        }
+
+        if self.data_dir_budget != DataDirBudgetConfig::default()
+            && with.data_dir_budget != DataDirBudgetConfig::default()
+            && self.data_dir_budget != with.data_dir_budget
+        {
+            errors.push("conflicting values for 'data_dir_budget' found".to_owned());
+        }
+
         if conflicts(&self.timezone, &with.timezone) {
             errors.push("conflicting values for 'timezone' found".to_owned());
         }
@@ -225,8 +271,21 @@ impl GlobalOptions {
                 acknowledgements: self.acknowledgements.merge_default(&with.acknowledgements),
                 timezone: self.timezone.or(with.timezone),
                 proxy: self.proxy.merge(&with.proxy),
+                dns: if self.dns == DnsConfig::default() {
+                    with.dns
+                } else {
+                    self.dns.clone()
+                },
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
                 expire_metrics_secs: self.expire_metrics_secs.or(with.expire_metrics_secs),
+                sink_in_flight_bytes_limit: self
+                    .sink_in_flight_bytes_limit
+                    .or(with.sink_in_flight_bytes_limit),
+                data_dir_budget: if self.data_dir_budget == DataDirBudgetConfig::default() {
+                    with.data_dir_budget
+                } else {
+                    self.data_dir_budget
+                },
             })
         } else {
             Err(errors)
@@ -250,6 +309,7 @@ mod tests {
     use chrono_tz::Tz;
 
     use super::*;
+    use crate::config::dns::DnsIpStrategy;
 
     #[test]
     fn merges_data_dir() {
@@ -310,6 +370,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merges_dns() {
+        let merge = |a, b| merge("dns.ip_strategy", a, b, |result| result.dns.ip_strategy);
+
+        assert_eq!(merge(None, None), Ok(DnsIpStrategy::Ipv4AndIpv6));
+        assert_eq!(merge(Some("ipv4_only"), None), Ok(DnsIpStrategy::Ipv4Only));
+        assert_eq!(merge(None, Some("ipv6_only")), Ok(DnsIpStrategy::Ipv6Only));
+        assert_eq!(
+            merge(Some("prefer_ipv4"), Some("prefer_ipv4")),
+            Ok(DnsIpStrategy::PreferIpv4)
+        );
+        assert_eq!(
+            merge(Some("prefer_ipv4"), Some("prefer_ipv6")),
+            Err(vec!["conflicting values for 'dns' found".into()])
+        );
+    }
+
     #[test]
     fn merges_acknowledgements() {
         let merge = |a, b| merge("acknowledgements", a, b, |result| result.acknowledgements);
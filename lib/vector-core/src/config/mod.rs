@@ -4,6 +4,7 @@ use bitmask_enum::bitmask;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 
+pub mod dns;
 mod global_options;
 mod log_schema;
 pub mod output_id;
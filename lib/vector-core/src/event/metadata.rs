@@ -3,6 +3,7 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use lookup::path;
 use serde::{Deserialize, Serialize};
 use vector_common::EventDataEq;
 use vrl::value::{Kind, Secrets, Value};
@@ -38,6 +39,34 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// The metadata of a metric received from the Datadog Agent.
+    #[serde(default, skip)]
+    datadog_metric_metadata: Option<Arc<DatadogMetricMetadata>>,
+}
+
+/// The metadata the Datadog Agent sends with a metric, beyond its name, value and tags.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DatadogMetricMetadata {
+    /// The unit of the metric, such as `byte` or `second`.
+    pub unit: Option<String>,
+
+    /// The product, category and service the metric originates from.
+    pub origin: Option<DatadogMetricOrigin>,
+}
+
+/// The origin of a metric within Datadog, as the identifiers of its product, category and
+/// service.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DatadogMetricOrigin {
+    /// The product the metric originates from.
+    pub product: u32,
+
+    /// The category of the product the metric originates from.
+    pub category: u32,
+
+    /// The service of the product the metric originates from.
+    pub service: u32,
 }
 
 fn default_metadata_value() -> Value {
@@ -94,6 +123,31 @@ impl EventMetadata {
         self.secrets.insert(DATADOG_API_KEY, secret);
     }
 
+    /// Returns the Datadog metric metadata, if it exists.
+    pub fn datadog_metric_metadata(&self) -> Option<&DatadogMetricMetadata> {
+        self.datadog_metric_metadata.as_deref()
+    }
+
+    /// Sets the Datadog metric metadata to the passed value.
+    pub fn set_datadog_metric_metadata(&mut self, metadata: Arc<DatadogMetricMetadata>) {
+        self.datadog_metric_metadata = Some(metadata);
+    }
+
+    /// Returns the deterministic ID the source stamped the event with, if any.
+    ///
+    /// The ID is stored in the metadata value, as `%vector.dedupe_id`.
+    pub fn dedupe_id(&self) -> Option<&str> {
+        match self.value.get(path!("vector", "dedupe_id")) {
+            Some(Value::Bytes(id)) => std::str::from_utf8(id).ok(),
+            _ => None,
+        }
+    }
+
+    /// Sets the deterministic ID of the event to the passed value.
+    pub fn set_dedupe_id(&mut self, id: String) {
+        self.value.insert(path!("vector", "dedupe_id"), id);
+    }
+
     /// Return the splunk hec token, if it exists
     pub fn splunk_hec_token(&self) -> Option<Arc<str>> {
         self.secrets.get(SPLUNK_HEC_TOKEN).cloned()
@@ -113,6 +167,7 @@ impl Default for EventMetadata {
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
             source_id: None,
+            datadog_metric_metadata: None,
         }
     }
 }
@@ -173,9 +228,13 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// If Datadog metric metadata is not set in `self`, the one from `other` will be used.
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
         self.secrets.merge(other.secrets);
+        if self.datadog_metric_metadata.is_none() {
+            self.datadog_metric_metadata = other.datadog_metric_metadata;
+        }
     }
 
     /// Update the finalizer(s) status.
@@ -264,4 +323,39 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn get_set_dedupe_id() {
+        let mut metadata = EventMetadata::default();
+        assert_eq!(metadata.dedupe_id(), None);
+        metadata.set_dedupe_id("0123456789abcdef".to_owned());
+        assert_eq!(metadata.dedupe_id(), Some("0123456789abcdef"));
+    }
+
+    #[test]
+    fn merge_keeps_datadog_metric_metadata() {
+        let unit = |unit: &str| {
+            Arc::new(DatadogMetricMetadata {
+                unit: Some(unit.to_owned()),
+                origin: None,
+            })
+        };
+
+        let mut metadata = EventMetadata::default();
+        let mut other = EventMetadata::default();
+        other.set_datadog_metric_metadata(unit("byte"));
+        metadata.merge(other);
+        assert_eq!(
+            metadata.datadog_metric_metadata(),
+            Some(unit("byte").as_ref())
+        );
+
+        let mut other = EventMetadata::default();
+        other.set_datadog_metric_metadata(unit("second"));
+        metadata.merge(other);
+        assert_eq!(
+            metadata.datadog_metric_metadata(),
+            Some(unit("byte").as_ref())
+        );
+    }
 }
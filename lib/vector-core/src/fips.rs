@@ -0,0 +1,105 @@
+//! FIPS 140 mode.
+//!
+//! Once enabled, OpenSSL only uses the algorithms of its FIPS module for TLS, so non-compliant
+//! certificates, keys or protocols fail when the TLS settings of a component are built, TLS cipher
+//! suites that aren't approved fail config validation, and the VRL functions implemented with
+//! non-validated cryptography are not available.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use openssl::error::ErrorStack;
+use snafu::{ResultExt, Snafu};
+use vrl::compiler::Function;
+
+/// Whether this build of Vector always runs in FIPS mode.
+pub const REQUIRED: bool = cfg!(feature = "fips");
+
+/// VRL functions whose cryptography isn't provided by a FIPS validated module.
+pub const NON_APPROVED_VRL_FUNCTIONS: [&str; 10] = [
+    "decrypt",
+    "decrypt_envelope",
+    "encrypt",
+    "encrypt_envelope",
+    "hmac",
+    "md5",
+    "random_bytes",
+    "sha1",
+    "sha2",
+    "sha3",
+];
+
+/// TLS cipher suites allowed in FIPS mode, by their OpenSSL names: the AES-GCM and AES-CCM suites of
+/// TLS 1.3, and the AES-GCM suites of TLS 1.2 with an ephemeral key exchange.
+pub const APPROVED_TLS_CIPHERSUITES: [&str; 9] = [
+    "TLS_AES_128_CCM_SHA256",
+    "TLS_AES_128_GCM_SHA256",
+    "TLS_AES_256_GCM_SHA384",
+    "DHE-RSA-AES128-GCM-SHA256",
+    "DHE-RSA-AES256-GCM-SHA384",
+    "ECDHE-ECDSA-AES128-GCM-SHA256",
+    "ECDHE-ECDSA-AES256-GCM-SHA384",
+    "ECDHE-RSA-AES128-GCM-SHA256",
+    "ECDHE-RSA-AES256-GCM-SHA384",
+];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Snafu)]
+pub enum FipsError {
+    #[snafu(display("Could not enable the OpenSSL FIPS module: {}", source))]
+    EnableFailed { source: ErrorStack },
+    #[snafu(display("OpenSSL did not switch to FIPS mode"))]
+    NotEnabled,
+}
+
+/// Switches OpenSSL to FIPS mode, and verifies that it is in effect.
+///
+/// # Errors
+///
+/// If the linked OpenSSL doesn't provide a FIPS module. The vendored OpenSSL doesn't, so FIPS
+/// builds must link a FIPS capable OpenSSL by setting `OPENSSL_NO_VENDOR`.
+pub fn enable() -> Result<(), FipsError> {
+    openssl::fips::enable(true).context(EnableFailedSnafu)?;
+    if !openssl::fips::enabled() {
+        return Err(FipsError::NotEnabled);
+    }
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Whether FIPS mode is enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Removes the non-approved VRL functions when FIPS mode is enabled, so that programs calling them
+/// fail to compile.
+pub fn retain_approved_vrl_functions(functions: &mut Vec<Box<dyn Function>>) {
+    if is_enabled() {
+        retain_approved(functions);
+    }
+}
+
+fn retain_approved(functions: &mut Vec<Box<dyn Function>>) {
+    functions.retain(|function| !NON_APPROVED_VRL_FUNCTIONS.contains(&function.identifier()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_non_approved_vrl_functions() {
+        let mut functions = vrl::stdlib::all();
+        retain_approved(&mut functions);
+
+        let identifiers = functions
+            .iter()
+            .map(|function| function.identifier())
+            .collect::<Vec<_>>();
+        assert!(identifiers.contains(&"parse_json"));
+        for identifier in NON_APPROVED_VRL_FUNCTIONS {
+            assert!(!identifiers.contains(&identifier), "{identifier}");
+        }
+    }
+}
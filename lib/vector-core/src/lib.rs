@@ -30,6 +30,7 @@
 pub mod config;
 pub mod event;
 pub mod fanout;
+pub mod fips;
 pub mod metrics;
 pub mod partition;
 pub mod schema;
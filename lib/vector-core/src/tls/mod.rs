@@ -107,6 +107,8 @@ pub enum TlsError {
         source
     ))]
     EncodeAlpnProtocols { source: TryFromIntError },
+    #[snafu(display("Error setting cipher suites: {}", source))]
+    SetCipherSuites { source: ErrorStack },
     #[snafu(display("PKCS#12 parse failed: {}", source))]
     ParsePkcs12 { source: ErrorStack },
     #[snafu(display("TCP bind failed: {}", source))]
@@ -114,6 +114,19 @@ pub struct TlsConfig {
     #[configurable(metadata(docs::examples = "h2"))]
     pub alpn_protocols: Option<Vec<String>>,
 
+    /// Sets the list of supported cipher suites, by their OpenSSL names.
+    ///
+    /// TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+    /// `ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+    /// none listed are the OpenSSL defaults.
+    ///
+    /// In FIPS mode, only the FIPS approved cipher suites can be listed.
+    #[configurable(metadata(
+        docs::examples = "TLS_AES_256_GCM_SHA384",
+        docs::examples = "ECDHE-RSA-AES256-GCM-SHA384"
+    ))]
+    pub ciphersuites: Option<Vec<String>>,
+
     /// Absolute path to an additional CA certificate file.
     ///
     /// The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
@@ -169,6 +182,10 @@ pub struct TlsSettings {
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
     alpn_protocols: Option<Vec<u8>>,
+    /// The TLS 1.3 cipher suites, in the format of OpenSSL.
+    ciphersuites: Option<String>,
+    /// The TLS 1.2 cipher suites, in the format of OpenSSL.
+    cipher_list: Option<String>,
 }
 
 #[derive(Clone)]
@@ -203,6 +220,8 @@ impl TlsSettings {
             authorities: options.load_authorities()?,
             identity: options.load_identity()?,
             alpn_protocols: options.parse_alpn_protocols()?,
+            ciphersuites: options.join_ciphersuites(true),
+            cipher_list: options.join_ciphersuites(false),
         })
     }
 
@@ -300,6 +319,17 @@ impl TlsSettings {
                 .context(SetAlpnProtocolsSnafu)?;
         }
 
+        if let Some(ciphersuites) = &self.ciphersuites {
+            context
+                .set_ciphersuites(ciphersuites)
+                .context(SetCipherSuitesSnafu)?;
+        }
+        if let Some(cipher_list) = &self.cipher_list {
+            context
+                .set_cipher_list(cipher_list)
+                .context(SetCipherSuitesSnafu)?;
+        }
+
         Ok(())
     }
 
@@ -360,6 +390,19 @@ impl TlsConfig {
         }
     }
 
+    /// Joins the configured cipher suites of TLS 1.3, whose names start with `TLS_`, or of TLS 1.2,
+    /// in the colon separated format of OpenSSL.
+    fn join_ciphersuites(&self, tls13: bool) -> Option<String> {
+        let ciphersuites = self
+            .ciphersuites
+            .as_ref()?
+            .iter()
+            .filter(|ciphersuite| ciphersuite.starts_with("TLS_") == tls13)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        (!ciphersuites.is_empty()).then(|| ciphersuites.join(":"))
+    }
+
     /// Parse identity from a PEM encoded certificate + key pair of files
     fn parse_pem_identity(&self, pem: &str, crt_file: &Path) -> Result<Option<IdentityStore>> {
         match &self.key_file {
@@ -628,6 +671,28 @@ mod test {
         assert_eq!(settings.alpn_protocols, Some(vec![2, 104, 50]));
     }
 
+    #[test]
+    fn splits_ciphersuites_by_version() {
+        let options = TlsConfig {
+            ciphersuites: Some(vec![
+                "TLS_AES_256_GCM_SHA384".to_owned(),
+                "ECDHE-RSA-AES256-GCM-SHA384".to_owned(),
+                "TLS_AES_128_GCM_SHA256".to_owned(),
+            ]),
+            ..Default::default()
+        };
+        let settings =
+            TlsSettings::from_options(&Some(options)).expect("Failed to parse ciphersuites");
+        assert_eq!(
+            settings.ciphersuites.as_deref(),
+            Some("TLS_AES_256_GCM_SHA384:TLS_AES_128_GCM_SHA256")
+        );
+        assert_eq!(
+            settings.cipher_list.as_deref(),
+            Some("ECDHE-RSA-AES256-GCM-SHA384")
+        );
+    }
+
     #[test]
     fn from_options_pkcs12() {
         let options = TlsConfig {
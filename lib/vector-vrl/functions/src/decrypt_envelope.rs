@@ -0,0 +1,82 @@
+use vrl::prelude::*;
+
+use crate::envelope::Envelope;
+
+fn decrypt_envelope(value: Value, keys: Value) -> std::result::Result<Value, ExpressionError> {
+    let envelope = Envelope::from_value(&value)
+        .map_err(|error| format!("unable to decrypt value: {}", error))?;
+    let keys = keys.try_object()?;
+    let key = keys
+        .get(&envelope.kid)
+        .ok_or_else(|| format!("unable to decrypt value: unknown key ID {:?}", envelope.kid))?
+        .clone()
+        .try_bytes()?;
+    envelope
+        .open(&key)
+        .map_err(|error| format!("unable to decrypt value: {}", error).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecryptEnvelope;
+
+impl Function for DecryptEnvelope {
+    fn identifier(&self) -> &'static str {
+        "decrypt_envelope"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "keys",
+                kind: kind::OBJECT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Decrypt an envelope",
+            source: r#"
+                key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+                envelope = encrypt_envelope!("jane@example.com", key: key, key_id: "2024-q1")
+                decrypt_envelope!(envelope, keys: { "2024-q1": key })
+            "#,
+            result: Ok("jane@example.com"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let keys = arguments.required("keys");
+        Ok(DecryptEnvelopeFn { value, keys }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DecryptEnvelopeFn {
+    value: Box<dyn Expression>,
+    keys: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DecryptEnvelopeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let keys = self.keys.resolve(ctx)?;
+        decrypt_envelope(value, keys)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
@@ -0,0 +1,105 @@
+use vrl::prelude::*;
+use vrl::value::kind::Collection;
+
+use crate::envelope::Envelope;
+
+fn encrypt_envelope(
+    value: Value,
+    key: Value,
+    key_id: Value,
+    aad: Option<Value>,
+) -> std::result::Result<Value, ExpressionError> {
+    let key = key.try_bytes()?;
+    let key_id = key_id.try_bytes_utf8_lossy()?;
+    let aad = aad.map(|aad| aad.try_bytes_utf8_lossy().map(|aad| aad.into_owned()));
+    let aad = aad.transpose()?;
+    Envelope::seal(&value, &key_id, &key, aad.as_deref())
+        .map(Into::into)
+        .map_err(|error| format!("unable to encrypt value: {}", error).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncryptEnvelope;
+
+impl Function for EncryptEnvelope {
+    fn identifier(&self) -> &'static str {
+        "encrypt_envelope"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key_id",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "aad",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Encrypt a value into an envelope",
+            source: r#"
+                envelope = encrypt_envelope!("jane@example.com", key: "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef", key_id: "2024-q1")
+                envelope.kid
+            "#,
+            result: Ok("2024-q1"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let key_id = arguments.required("key_id");
+        let aad = arguments.optional("aad");
+        Ok(EncryptEnvelopeFn {
+            value,
+            key,
+            key_id,
+            aad,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncryptEnvelopeFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    key_id: Box<dyn Expression>,
+    aad: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncryptEnvelopeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+        let key_id = self.key_id.resolve(ctx)?;
+        let aad = self.aad.as_ref().map(|aad| aad.resolve(ctx)).transpose()?;
+        encrypt_envelope(value, key, key_id, aad)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
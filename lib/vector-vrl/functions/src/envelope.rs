@@ -0,0 +1,334 @@
+//! The envelope of the fields encrypted by Vector.
+//!
+//! An encrypted field is replaced with an object holding everything needed to decrypt it, except
+//! the key, so that any hop downstream, or any consumer holding the key, can detect and decrypt it:
+//!
+//! ```json
+//! {
+//!   "v": 1,
+//!   "alg": "A256CBC-HS512",
+//!   "kid": "2024-q1",
+//!   "iv": "<base64url>",
+//!   "ct": "<base64url>",
+//!   "tag": "<base64url>",
+//!   "aad": "tenant=acme"
+//! }
+//! ```
+//!
+//! The plaintext is the JSON encoding of the value, so that the type of the value survives the
+//! round trip, except for timestamps, which are decrypted as RFC 3339 strings.
+//!
+//! `A256CBC-HS512` is the authenticated encryption algorithm of [RFC 7518, section 5.2.5][rfc],
+//! also used by JWE: AES-256 in CBC mode, authenticated with a HMAC-SHA-512 truncated to 256 bits.
+//! Its 64-byte key is the 32-byte MAC key followed by the 32-byte encryption key. The additional
+//! authenticated data is the `aad` string, or empty if there is none.
+//!
+//! [rfc]: https://www.rfc-editor.org/rfc/rfc7518#section-5.2.5
+
+use std::{collections::BTreeMap, fmt};
+
+use aes::Aes256;
+use base64::prelude::{Engine as _, BASE64_URL_SAFE_NO_PAD};
+use cbc::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use vrl::value::Value;
+
+/// The version of the envelope format.
+pub const VERSION: i64 = 1;
+
+/// The only algorithm of version 1 of the envelope format.
+pub const ALGORITHM: &str = "A256CBC-HS512";
+
+/// The length of the keys of [`ALGORITHM`], in bytes.
+pub const KEY_LENGTH: usize = 64;
+
+const IV_LENGTH: usize = 16;
+const TAG_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The value is not an envelope, or one of its fields is missing or malformed.
+    Malformed(&'static str),
+    /// The envelope is of a later version of the format.
+    UnsupportedVersion(i64),
+    /// The envelope was encrypted with an algorithm that isn't supported.
+    UnsupportedAlgorithm(String),
+    /// The key is not [`KEY_LENGTH`] bytes long.
+    InvalidKeyLength(usize),
+    /// The ciphertext, the additional authenticated data, or the key is wrong.
+    AuthenticationFailed,
+    /// The value could not be encoded as JSON, or the plaintext could not be decoded.
+    Encoding(String),
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(reason) => write!(f, "malformed envelope: {}", reason),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported envelope version {}", version)
+            }
+            Self::UnsupportedAlgorithm(alg) => write!(f, "unsupported algorithm {:?}", alg),
+            Self::InvalidKeyLength(length) => write!(
+                f,
+                "keys must be {} bytes long, found {} bytes",
+                KEY_LENGTH, length
+            ),
+            Self::AuthenticationFailed => write!(
+                f,
+                "authentication failed, the key or the envelope is not the right one"
+            ),
+            Self::Encoding(error) => write!(f, "invalid plaintext: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+/// An encrypted value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    /// The ID of the key the value was encrypted with.
+    pub kid: String,
+    /// The additional authenticated data, which is not encrypted but can't be altered.
+    pub aad: Option<String>,
+    iv: Vec<u8>,
+    ct: Vec<u8>,
+    tag: Vec<u8>,
+}
+
+impl Envelope {
+    /// Encrypts the value with the key `kid`.
+    pub fn seal(
+        value: &Value,
+        kid: &str,
+        key: &[u8],
+        aad: Option<&str>,
+    ) -> Result<Self, EnvelopeError> {
+        let (mac_key, enc_key) = split_key(key)?;
+        let plaintext = serde_json::to_vec(value)
+            .map_err(|error| EnvelopeError::Encoding(error.to_string()))?;
+
+        let mut iv = [0; IV_LENGTH];
+        getrandom::getrandom(&mut iv)
+            .map_err(|error| EnvelopeError::Encoding(error.to_string()))?;
+        let ct = cbc::Encryptor::<Aes256>::new_from_slices(enc_key, &iv)
+            .expect("the key and IV lengths are checked")
+            .encrypt_padded_vec_mut::<Pkcs7>(&plaintext);
+        let tag =
+            authenticate(mac_key, aad, &iv, &ct).finalize().into_bytes()[..TAG_LENGTH].to_vec();
+
+        Ok(Self {
+            kid: kid.to_owned(),
+            aad: aad.map(ToOwned::to_owned),
+            iv: iv.to_vec(),
+            ct,
+            tag,
+        })
+    }
+
+    /// Decrypts the value, with the key the envelope names in `kid`.
+    pub fn open(&self, key: &[u8]) -> Result<Value, EnvelopeError> {
+        let (mac_key, enc_key) = split_key(key)?;
+        authenticate(mac_key, self.aad.as_deref(), &self.iv, &self.ct)
+            .verify_truncated_left(&self.tag)
+            .map_err(|_| EnvelopeError::AuthenticationFailed)?;
+
+        let plaintext = cbc::Decryptor::<Aes256>::new_from_slices(enc_key, &self.iv)
+            .map_err(|_| EnvelopeError::Malformed("`iv` must be 16 bytes long"))?
+            .decrypt_padded_vec_mut::<Pkcs7>(&self.ct)
+            .map_err(|_| EnvelopeError::Malformed("invalid padding"))?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|error| EnvelopeError::Encoding(error.to_string()))
+    }
+
+    /// Parses the envelope from its object representation.
+    pub fn from_value(value: &Value) -> Result<Self, EnvelopeError> {
+        let Value::Object(fields) = value else {
+            return Err(EnvelopeError::Malformed("envelopes must be objects"));
+        };
+        match fields.get("v") {
+            Some(Value::Integer(VERSION)) => (),
+            Some(Value::Integer(version)) => {
+                return Err(EnvelopeError::UnsupportedVersion(*version))
+            }
+            _ => return Err(EnvelopeError::Malformed("`v` must be an integer")),
+        }
+        let alg = string_field(fields, "alg")?;
+        if alg != ALGORITHM {
+            return Err(EnvelopeError::UnsupportedAlgorithm(alg));
+        }
+        let aad = match fields.get("aad") {
+            None | Some(Value::Null) => None,
+            Some(_) => Some(string_field(fields, "aad")?),
+        };
+        Ok(Self {
+            kid: string_field(fields, "kid")?,
+            aad,
+            iv: base64_field(fields, "iv")?,
+            ct: base64_field(fields, "ct")?,
+            tag: base64_field(fields, "tag")?,
+        })
+    }
+}
+
+impl From<Envelope> for Value {
+    fn from(envelope: Envelope) -> Self {
+        let mut fields = BTreeMap::from([
+            ("v".to_owned(), Value::Integer(VERSION)),
+            ("alg".to_owned(), ALGORITHM.into()),
+            ("kid".to_owned(), envelope.kid.into()),
+            (
+                "iv".to_owned(),
+                BASE64_URL_SAFE_NO_PAD.encode(envelope.iv).into(),
+            ),
+            (
+                "ct".to_owned(),
+                BASE64_URL_SAFE_NO_PAD.encode(envelope.ct).into(),
+            ),
+            (
+                "tag".to_owned(),
+                BASE64_URL_SAFE_NO_PAD.encode(envelope.tag).into(),
+            ),
+        ]);
+        if let Some(aad) = envelope.aad {
+            fields.insert("aad".to_owned(), aad.into());
+        }
+        Value::Object(fields)
+    }
+}
+
+/// Whether the value looks like an envelope, which may still be of an unsupported version.
+pub fn is_envelope(value: &Value) -> bool {
+    match value {
+        Value::Object(fields) => ["v", "alg", "kid", "ct"]
+            .iter()
+            .all(|field| fields.contains_key(*field)),
+        _ => false,
+    }
+}
+
+/// Splits the key into its MAC key and its encryption key.
+fn split_key(key: &[u8]) -> Result<(&[u8], &[u8]), EnvelopeError> {
+    if key.len() != KEY_LENGTH {
+        return Err(EnvelopeError::InvalidKeyLength(key.len()));
+    }
+    Ok(key.split_at(KEY_LENGTH / 2))
+}
+
+/// The HMAC of `AAD || IV || ciphertext || AL`, where `AL` is the length of the AAD in bits.
+fn authenticate(mac_key: &[u8], aad: Option<&str>, iv: &[u8], ct: &[u8]) -> Hmac<Sha512> {
+    let aad = aad.unwrap_or_default().as_bytes();
+    let mut mac = Hmac::<Sha512>::new_from_slice(mac_key).expect("HMAC accepts keys of any size");
+    mac.update(aad);
+    mac.update(iv);
+    mac.update(ct);
+    mac.update(&(aad.len() as u64 * 8).to_be_bytes());
+    mac
+}
+
+fn string_field(
+    fields: &BTreeMap<String, Value>,
+    name: &'static str,
+) -> Result<String, EnvelopeError> {
+    match fields.get(name) {
+        Some(Value::Bytes(bytes)) => String::from_utf8(bytes.to_vec())
+            .map_err(|_| EnvelopeError::Malformed("fields must be valid UTF-8")),
+        _ => Err(EnvelopeError::Malformed(
+            "`alg`, `kid` and `aad` must be strings",
+        )),
+    }
+}
+
+fn base64_field(
+    fields: &BTreeMap<String, Value>,
+    name: &'static str,
+) -> Result<Vec<u8>, EnvelopeError> {
+    match fields.get(name) {
+        Some(Value::Bytes(bytes)) => BASE64_URL_SAFE_NO_PAD.decode(bytes).map_err(|_| {
+            EnvelopeError::Malformed("`iv`, `ct` and `tag` must be base64url strings")
+        }),
+        _ => Err(EnvelopeError::Malformed(
+            "`iv`, `ct` and `tag` must be base64url strings",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> Vec<u8> {
+        vec![byte; KEY_LENGTH]
+    }
+
+    fn value() -> Value {
+        let mut fields = BTreeMap::new();
+        fields.insert("email".to_owned(), "jane@example.com".into());
+        fields.insert("age".to_owned(), Value::Integer(42));
+        Value::Object(fields)
+    }
+
+    #[test]
+    fn round_trips() {
+        let envelope = Envelope::seal(&value(), "2024-q1", &key(1), Some("tenant=acme")).unwrap();
+        let encoded = Value::from(envelope.clone());
+        assert!(is_envelope(&encoded));
+
+        let parsed = Envelope::from_value(&encoded).unwrap();
+        assert_eq!(parsed, envelope);
+        assert_eq!(parsed.kid, "2024-q1");
+        assert_eq!(parsed.open(&key(1)).unwrap(), value());
+    }
+
+    #[test]
+    fn rejects_altered_envelopes() {
+        let envelope = Envelope::seal(&value(), "2024-q1", &key(1), Some("tenant=acme")).unwrap();
+        assert_eq!(
+            envelope.open(&key(2)),
+            Err(EnvelopeError::AuthenticationFailed)
+        );
+
+        let mut altered = envelope.clone();
+        altered.aad = Some("tenant=other".to_owned());
+        assert_eq!(
+            altered.open(&key(1)),
+            Err(EnvelopeError::AuthenticationFailed)
+        );
+
+        let mut altered = envelope;
+        altered.ct[0] ^= 1;
+        assert_eq!(
+            altered.open(&key(1)),
+            Err(EnvelopeError::AuthenticationFailed)
+        );
+
+        assert_eq!(
+            Envelope::seal(&value(), "short", &[0; 32], None),
+            Err(EnvelopeError::InvalidKeyLength(32))
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_envelopes() {
+        let Value::Object(mut fields) =
+            Value::from(Envelope::seal(&value(), "2024-q1", &key(1), None).unwrap())
+        else {
+            unreachable!()
+        };
+        fields.insert("v".to_owned(), Value::Integer(2));
+        assert_eq!(
+            Envelope::from_value(&Value::Object(fields.clone())),
+            Err(EnvelopeError::UnsupportedVersion(2))
+        );
+
+        fields.insert("v".to_owned(), Value::Integer(1));
+        fields.insert("alg".to_owned(), "A256GCM".into());
+        assert_eq!(
+            Envelope::from_value(&Value::Object(fields)),
+            Err(EnvelopeError::UnsupportedAlgorithm("A256GCM".to_owned()))
+        );
+        assert!(!is_envelope(&Value::from("plain")));
+    }
+}
@@ -3,6 +3,9 @@
 use vrl::compiler::Function;
 use vrl::path::OwnedTargetPath;
 
+pub mod decrypt_envelope;
+pub mod encrypt_envelope;
+pub mod envelope;
 pub mod get_secret;
 pub mod remove_secret;
 pub mod set_secret;
@@ -23,5 +26,7 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(get_secret::GetSecret) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
+        Box::new(encrypt_envelope::EncryptEnvelope) as _,
+        Box::new(decrypt_envelope::DecryptEnvelope) as _,
     ]
 }
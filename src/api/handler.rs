@@ -6,6 +6,8 @@ use std::sync::{
 use serde_json::json;
 use warp::{reply::json, Rejection, Reply};
 
+use crate::topology::scaling;
+
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
 // when shutting down
 pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
@@ -21,3 +23,18 @@ pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejec
         ))
     }
 }
+
+// Scaling signals handler, responds with the latest signals, or 404 when scaling signals are not
+// enabled. The signals can be polled by the KEDA `metrics-api` scaler.
+pub(super) async fn scaling() -> Result<impl Reply, Rejection> {
+    match scaling::latest() {
+        Some(signals) => Ok(warp::reply::with_status(
+            json(&signals),
+            warp::http::StatusCode::OK,
+        )),
+        None => Ok(warp::reply::with_status(
+            json(&json!({"error": "Scaling signals are not enabled."})),
+            warp::http::StatusCode::NOT_FOUND,
+        )),
+    }
+}
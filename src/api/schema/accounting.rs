@@ -0,0 +1,96 @@
+use async_graphql::{Enum, Object, SimpleObject};
+
+use crate::topology::accounting::{self, Direction, Entry};
+
+/// Whether events were ingested by a source, or delivered to a sink
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum AccountingDirection {
+    Ingested,
+    Delivered,
+}
+
+impl From<Direction> for AccountingDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Ingested => Self::Ingested,
+            Direction::Delivered => Self::Delivered,
+        }
+    }
+}
+
+/// The value of an accounting dimension
+#[derive(SimpleObject)]
+pub struct AccountingDimension {
+    /// Dimension name
+    name: String,
+
+    /// Dimension value, or `null` if the events don't have it
+    value: Option<String>,
+}
+
+/// The events and bytes of a group of events of a component, since Vector started
+#[derive(SimpleObject)]
+pub struct AccountingGroup {
+    /// Component component_id
+    component_id: String,
+
+    /// Whether the events were ingested by a source, or delivered to a sink
+    direction: AccountingDirection,
+
+    /// The dimensions of the group. Empty for the overflow group
+    dimensions: Vec<AccountingDimension>,
+
+    /// Whether the group holds the events of the groups above `accounting.max_groups`
+    overflow: bool,
+
+    /// Number of events
+    events: i64,
+
+    /// Estimated size of the events once encoded as JSON, in bytes
+    bytes: i64,
+}
+
+impl From<Entry> for AccountingGroup {
+    fn from(entry: Entry) -> Self {
+        Self {
+            component_id: entry.component.id().to_string(),
+            direction: entry.direction.into(),
+            overflow: entry.dimensions.is_none(),
+            dimensions: entry
+                .dimensions
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, value)| AccountingDimension { name, value })
+                .collect(),
+            events: entry.counts.events as i64,
+            bytes: entry.counts.bytes as i64,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct AccountingQuery;
+
+#[Object]
+impl AccountingQuery {
+    /// Gets the accounted events and bytes of each group of each source and sink, optionally
+    /// filtered by component. Requires `accounting.enabled` to be set
+    async fn accounting(
+        &self,
+        component_id: Option<String>,
+    ) -> async_graphql::Result<Vec<AccountingGroup>> {
+        if !accounting::is_enabled() {
+            return Err("Accounting is not enabled. Set `accounting.enabled` to `true`.".into());
+        }
+
+        Ok(accounting::snapshot()
+            .into_iter()
+            .filter(|entry| {
+                component_id
+                    .as_ref()
+                    .map_or(true, |id| entry.component.id() == id)
+            })
+            .map(Into::into)
+            .collect())
+    }
+}
@@ -0,0 +1,106 @@
+use async_graphql::Object;
+#[cfg(feature = "sinks-dual_write")]
+use async_graphql::{Enum, SimpleObject};
+
+#[cfg(feature = "sinks-dual_write")]
+use crate::{
+    audit,
+    config::ComponentKey,
+    sinks::dual_write::{DualWriteMode, DualWriteModes},
+};
+
+#[cfg(feature = "sinks-dual_write")]
+/// The sink whose outcome is used to acknowledge events
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum DualWriteAuthority {
+    /// The sink being migrated from
+    Primary,
+    /// The sink being migrated to
+    Secondary,
+}
+
+#[cfg(feature = "sinks-dual_write")]
+impl From<DualWriteMode> for DualWriteAuthority {
+    fn from(mode: DualWriteMode) -> Self {
+        match mode {
+            DualWriteMode::Primary => Self::Primary,
+            DualWriteMode::Secondary => Self::Secondary,
+        }
+    }
+}
+
+#[cfg(feature = "sinks-dual_write")]
+impl From<DualWriteAuthority> for DualWriteMode {
+    fn from(authority: DualWriteAuthority) -> Self {
+        match authority {
+            DualWriteAuthority::Primary => Self::Primary,
+            DualWriteAuthority::Secondary => Self::Secondary,
+        }
+    }
+}
+
+#[cfg(feature = "sinks-dual_write")]
+/// The mode of a `dual_write` sink
+#[derive(SimpleObject)]
+pub struct DualWriteSinkMode {
+    /// Sink component_id
+    component_id: String,
+
+    /// The sink whose outcome is used to acknowledge events
+    mode: DualWriteAuthority,
+}
+
+#[derive(Default)]
+pub(super) struct DualWriteQuery;
+
+#[Object]
+impl DualWriteQuery {
+    #[cfg(feature = "sinks-dual_write")]
+    /// Gets the mode of a running `dual_write` sink
+    async fn dual_write_mode(&self, component_id: String) -> Option<DualWriteSinkMode> {
+        let key = ComponentKey::from(component_id);
+        DualWriteModes::get(&key).map(|modes| DualWriteSinkMode {
+            component_id: key.id().to_string(),
+            mode: modes.mode().into(),
+        })
+    }
+}
+
+#[derive(Default)]
+pub(super) struct DualWriteMutation;
+
+#[Object]
+impl DualWriteMutation {
+    #[cfg(feature = "sinks-dual_write")]
+    /// Sets the sink acknowledging the events of a running `dual_write` sink, without restarting
+    /// it. Batches already sent are acknowledged with the previous mode. The configured `mode` is
+    /// restored when the configuration is reloaded. Requires `api.mutations` to be set
+    async fn set_dual_write_mode(
+        &self,
+        component_id: String,
+        mode: DualWriteAuthority,
+    ) -> async_graphql::Result<DualWriteSinkMode> {
+        super::ensure_mutations_enabled()?;
+
+        let key = ComponentKey::from(component_id);
+        let modes = DualWriteModes::get(&key)
+            .ok_or_else(|| format!("Sink {:?} isn't a running dual_write sink", key.id()))?;
+
+        audit::record(audit::AuditAction::ApiMutation {
+            mutation: "setDualWriteMode",
+            arguments: serde_json::json!({
+                "componentId": key.id(),
+                "mode": match mode {
+                    DualWriteAuthority::Primary => "primary",
+                    DualWriteAuthority::Secondary => "secondary",
+                },
+            }),
+        });
+
+        modes.set_mode(mode.into());
+        Ok(DualWriteSinkMode {
+            component_id: key.id().to_string(),
+            mode,
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use async_graphql::{Object, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use crate::request_capture::{self, RequestCapture};
+
+/// An HTTP header
+#[derive(SimpleObject)]
+pub struct HttpHeader {
+    /// Header name
+    name: String,
+
+    /// Header value, or `<redacted>` for credentials
+    value: String,
+}
+
+/// A failed request of a sink, and the response it got
+#[derive(SimpleObject)]
+pub struct SinkFailedRequest {
+    /// Sink component_id
+    component_id: String,
+
+    /// Sequence number of the request among those captured by the sink
+    id: i64,
+
+    /// When the request was sent
+    timestamp: DateTime<Utc>,
+
+    /// Request method
+    method: String,
+
+    /// Request URI, with credentials redacted
+    uri: String,
+
+    /// Request headers, with credentials redacted
+    request_headers: Vec<HttpHeader>,
+
+    /// Request body, truncated to `debug.captured_body_bytes`
+    request_body: String,
+
+    /// Response status, or `null` if the request got no response
+    status: Option<i32>,
+
+    /// Response headers, with credentials redacted
+    response_headers: Vec<HttpHeader>,
+
+    /// Response body, truncated to `debug.captured_body_bytes`
+    response_body: String,
+
+    /// Why the request got no response
+    error: Option<String>,
+}
+
+fn headers(headers: Vec<(String, String)>) -> Vec<HttpHeader> {
+    headers
+        .into_iter()
+        .map(|(name, value)| HttpHeader { name, value })
+        .collect()
+}
+
+impl SinkFailedRequest {
+    fn new(component_id: String, request: request_capture::FailedRequest) -> Self {
+        Self {
+            component_id,
+            id: request.id as i64,
+            timestamp: request.timestamp,
+            method: request.method,
+            uri: request.uri,
+            request_headers: headers(request.request_headers),
+            request_body: request.request_body,
+            status: request.status.map(i32::from),
+            response_headers: headers(request.response_headers),
+            response_body: request.response_body,
+            error: request.error,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct FailedRequestsQuery;
+
+#[Object]
+impl FailedRequestsQuery {
+    /// Gets the failed requests captured by the running sinks matching the component ID patterns,
+    /// which capture them when `debug.capture_failed_requests` is set
+    async fn sink_failed_requests(
+        &self,
+        component_id_patterns: Vec<String>,
+    ) -> async_graphql::Result<Vec<SinkFailedRequest>> {
+        let patterns = component_id_patterns
+            .iter()
+            .map(|pattern| glob::Pattern::new(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut requests = Vec::new();
+        for (key, capture) in RequestCapture::all() {
+            if patterns.iter().any(|pattern| pattern.matches(key.id())) {
+                requests.extend(
+                    capture
+                        .failed_requests()
+                        .into_iter()
+                        .map(|request| SinkFailedRequest::new(key.id().to_string(), request)),
+                );
+            }
+        }
+        requests.sort_by_key(|request| request.timestamp);
+        Ok(requests)
+    }
+}
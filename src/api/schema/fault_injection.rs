@@ -0,0 +1,93 @@
+use async_graphql::{Object, SimpleObject};
+
+use crate::{
+    audit,
+    config::{fault_injection::ComponentFaults, ComponentKey},
+    topology::fault_injection,
+};
+
+/// The faults injected into a component
+#[derive(SimpleObject)]
+pub struct Faults {
+    /// Component component_id
+    component_id: String,
+
+    /// Latency, in milliseconds, added to each batch of events
+    latency_ms: i64,
+
+    /// Ratio of batches of events that are rejected
+    error_rate: f64,
+
+    /// Whether the component stopped processing events
+    stall: bool,
+}
+
+impl Faults {
+    fn new(key: &ComponentKey, faults: ComponentFaults) -> Self {
+        Self {
+            component_id: key.id().to_string(),
+            latency_ms: faults.latency_ms as i64,
+            error_rate: faults.error_rate,
+            stall: faults.stall,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct FaultInjectionQuery;
+
+#[Object]
+impl FaultInjectionQuery {
+    /// Gets the faults injected into a component
+    async fn component_faults(&self, component_id: String) -> Faults {
+        let key = ComponentKey::from(component_id);
+        Faults::new(&key, fault_injection::get(&key))
+    }
+}
+
+#[derive(Default)]
+pub(super) struct FaultInjectionMutation;
+
+#[Object]
+impl FaultInjectionMutation {
+    /// Injects faults into a running component, replacing its current faults. Omitted faults are
+    /// cleared. The faults are replaced by the configured ones when the configuration is reloaded.
+    /// Requires `fault_injection.enabled` to be set
+    async fn set_component_faults(
+        &self,
+        component_id: String,
+        #[graphql(validator(minimum = 0))] latency_ms: Option<i64>,
+        error_rate: Option<f64>,
+        stall: Option<bool>,
+    ) -> async_graphql::Result<Faults> {
+        if !fault_injection::is_enabled() {
+            return Err(
+                "Fault injection is not enabled. Set `fault_injection.enabled` to `true`.".into(),
+            );
+        }
+
+        if !(0.0..=1.0).contains(&error_rate.unwrap_or_default()) {
+            return Err("`errorRate` must be between 0.0 and 1.0.".into());
+        }
+
+        let key = ComponentKey::from(component_id);
+        let faults = ComponentFaults {
+            latency_ms: latency_ms.unwrap_or_default() as u64,
+            error_rate: error_rate.unwrap_or_default(),
+            stall: stall.unwrap_or_default(),
+        };
+
+        audit::record(audit::AuditAction::ApiMutation {
+            mutation: "setComponentFaults",
+            arguments: serde_json::json!({
+                "componentId": key.id(),
+                "latencyMs": faults.latency_ms,
+                "errorRate": faults.error_rate,
+                "stall": faults.stall,
+            }),
+        });
+
+        fault_injection::set(&key, faults);
+        Ok(Faults::new(&key, faults))
+    }
+}
@@ -1,13 +1,24 @@
+mod accounting;
 pub mod components;
+mod dual_write;
 pub mod events;
+mod failed_requests;
+mod fault_injection;
 pub mod filter;
 mod health;
 mod meta;
 mod metrics;
 mod relay;
+mod request_limits;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
+
+use crate::config::Config;
+
+static MUTATIONS_ENABLED: AtomicBool = AtomicBool::new(false);
 
 #[derive(MergedObject, Default)]
 pub struct Query(
@@ -15,6 +26,18 @@ pub struct Query(
     components::ComponentsQuery,
     metrics::MetricsQuery,
     meta::MetaQuery,
+    request_limits::RequestLimitsQuery,
+    failed_requests::FailedRequestsQuery,
+    fault_injection::FaultInjectionQuery,
+    dual_write::DualWriteQuery,
+    accounting::AccountingQuery,
+);
+
+#[derive(MergedObject, Default)]
+pub struct Mutation(
+    request_limits::RequestLimitsMutation,
+    fault_injection::FaultInjectionMutation,
+    dual_write::DualWriteMutation,
 );
 
 #[derive(MergedSubscription, Default)]
@@ -25,7 +48,26 @@ pub struct Subscription(
     events::EventsSubscription,
 );
 
+/// Update whether mutations overriding the configuration of running components are enabled
+pub fn update_config(config: &Config) {
+    MUTATIONS_ENABLED.store(config.api.mutations, Ordering::Relaxed);
+}
+
+/// Rejects mutations overriding the configuration of running components, unless `api.mutations`
+/// is set
+fn ensure_mutations_enabled() -> async_graphql::Result<()> {
+    if MUTATIONS_ENABLED.load(Ordering::Relaxed) {
+        Ok(())
+    } else {
+        Err("API mutations are not enabled. Set `api.mutations` to `true`.".into())
+    }
+}
+
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
 }
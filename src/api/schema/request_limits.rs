@@ -0,0 +1,85 @@
+use async_graphql::{Object, SimpleObject};
+
+use crate::{audit, config::ComponentKey, sinks::util::service::RequestLimits};
+
+/// The overrides of the request limits of a sink
+#[derive(SimpleObject)]
+pub struct SinkRequestLimits {
+    /// Sink component_id
+    component_id: String,
+
+    /// Overridden `request.rate_limit_num`, or `null` if the configured value is used
+    rate_limit_num: Option<i64>,
+
+    /// Overridden `request.concurrency`, or `null` if the configured value is used
+    concurrency: Option<i32>,
+}
+
+impl SinkRequestLimits {
+    fn new(key: &ComponentKey, limits: &RequestLimits) -> Self {
+        let overrides = limits.overrides();
+        Self {
+            component_id: key.id().to_string(),
+            rate_limit_num: overrides.rate_limit_num.map(|num| num as i64),
+            concurrency: overrides.concurrency.map(|concurrency| concurrency as i32),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct RequestLimitsQuery;
+
+#[Object]
+impl RequestLimitsQuery {
+    /// Gets the overrides of the request limits of a running sink
+    async fn sink_request_limits(&self, component_id: String) -> Option<SinkRequestLimits> {
+        let key = ComponentKey::from(component_id);
+        RequestLimits::get(&key).map(|limits| SinkRequestLimits::new(&key, &limits))
+    }
+}
+
+#[derive(Default)]
+pub(super) struct RequestLimitsMutation;
+
+#[Object]
+impl RequestLimitsMutation {
+    /// Overrides the request limits of a running sink, without restarting it. A `null` limit
+    /// restores the configured value. Limits computed by `request_limits` expressions replace
+    /// these overrides on their next evaluation. Requires `api.mutations` to be set
+    async fn set_sink_request_limits(
+        &self,
+        component_id: String,
+        #[graphql(validator(minimum = 1))] rate_limit_num: Option<i64>,
+        #[graphql(validator(minimum = 1))] concurrency: Option<i32>,
+    ) -> async_graphql::Result<SinkRequestLimits> {
+        super::ensure_mutations_enabled()?;
+
+        let key = ComponentKey::from(component_id);
+        let limits = RequestLimits::get(&key).ok_or_else(|| {
+            format!(
+                "Sink {:?} isn't running, or doesn't support request limits",
+                key.id()
+            )
+        })?;
+        if concurrency.is_some() && limits.strict_ordering() {
+            return Err(format!(
+                "Sink {:?} has strict ordering, its concurrency can't be overridden",
+                key.id()
+            )
+            .into());
+        }
+
+        audit::record(audit::AuditAction::ApiMutation {
+            mutation: "setSinkRequestLimits",
+            arguments: serde_json::json!({
+                "componentId": key.id(),
+                "rateLimitNum": rate_limit_num,
+                "concurrency": concurrency,
+            }),
+        });
+
+        limits.set_rate_limit_num(rate_limit_num.map(|num| num as u64));
+        limits.set_concurrency(concurrency.map(|concurrency| concurrency as usize));
+        Ok(SinkRequestLimits::new(&key, &limits))
+    }
+}
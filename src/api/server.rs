@@ -55,6 +55,7 @@ impl Server {
 
         // Update component schema with the config before starting the server.
         schema::components::update_config(config);
+        schema::update_config(config);
 
         // Spawn the server in the background.
         runtime.spawn(server);
@@ -71,7 +72,8 @@ impl Server {
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly.
     pub fn update_config(&self, config: &config::Config) {
-        schema::components::update_config(config)
+        schema::components::update_config(config);
+        schema::update_config(config);
     }
 }
 
@@ -87,6 +89,11 @@ fn make_routes(
         .and(with_shared(running))
         .and_then(handler::health);
 
+    // Scaling signals.
+    let scaling = warp::path("scaling")
+        .and(warp::get())
+        .and_then(handler::scaling);
+
     // 404.
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
@@ -146,6 +153,7 @@ fn make_routes(
     // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
     // cross-origin interaction with the Vector API.
     health
+        .or(scaling)
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
@@ -20,6 +20,7 @@ use super::{
     ShutdownRx, ShutdownTx,
 };
 use crate::{
+    audit,
     config::ComponentKey,
     event::{EventArray, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
@@ -159,6 +160,7 @@ impl TapTransformer {
 #[derive(Debug)]
 pub struct TapController {
     _shutdown: ShutdownTx,
+    session_id: u64,
 }
 
 impl TapController {
@@ -167,10 +169,22 @@ impl TapController {
     /// of handlers when the `TapSink` drops out of scope.
     pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
+        let session_id = audit::tap_started(patterns.all_patterns());
 
         tokio::spawn(tap_handler(patterns, tap_tx, watch_rx, shutdown_rx));
 
-        Self { _shutdown }
+        Self {
+            _shutdown,
+            session_id,
+        }
+    }
+}
+
+impl Drop for TapController {
+    fn drop(&mut self) {
+        audit::record(audit::AuditAction::TapStopped {
+            session_id: self.session_id,
+        });
     }
 }
 
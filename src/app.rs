@@ -22,6 +22,7 @@ use crate::metrics;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    audit,
     cli::{handle_config_errors, LogFormat, Opts, RootOpts},
     config::{self, Config, ConfigPath},
     heartbeat,
@@ -97,6 +98,8 @@ impl ApplicationConfig {
         #[cfg(feature = "api")]
         let api = config.api;
 
+        audit::config_loaded(&config, &config_paths);
+
         let result = topology::start_validated(config, diff, pieces).await;
         let (topology, (graceful_crash_sender, graceful_crash_receiver)) =
             result.ok_or(exitcode::CONFIG)?;
@@ -177,6 +180,21 @@ impl Application {
             opts.root.internal_log_rate_limit,
         );
 
+        if opts.root.fips || vector_core::fips::REQUIRED {
+            if let Err(error) = vector_core::fips::enable() {
+                error!(message = "Failed to enable FIPS mode.", %error);
+                return Err(exitcode::CONFIG);
+            }
+            info!("FIPS mode enabled.");
+        }
+
+        if let Some(path) = &opts.root.audit_log {
+            if let Err(error) = audit::init(path) {
+                error!(message = "Failed to open the audit log.", path = ?path, %error);
+                return Err(exitcode::CANTCREAT);
+            }
+        }
+
         let runtime = build_runtime(opts.root.threads, "vector-worker")?;
 
         // Signal handler for OS and provider messages.
@@ -334,14 +352,23 @@ impl FinishedApplication {
         match signal {
             SignalTo::Shutdown => {
                 emit!(VectorStopped);
-                tokio::select! {
-                    _ = topology_controller.stop() => (), // Graceful shutdown finished
+                audit::record(audit::AuditAction::DrainStarted {
+                    graceful_shutdown_limit_secs: topology_controller
+                        .topology
+                        .config()
+                        .graceful_shutdown_duration
+                        .map(|duration| duration.as_secs()),
+                });
+                let forced = tokio::select! {
+                    _ = topology_controller.stop() => false, // Graceful shutdown finished
                     _ = signal_rx.recv() => {
                         // It is highly unlikely that this event will exit from topology.
                         emit!(VectorQuit);
                         // Dropping the shutdown future will immediately shut the server down
+                        true
                     }
-                }
+                };
+                audit::record(audit::AuditAction::DrainFinished { forced });
             }
             SignalTo::Quit => {
                 // It is highly unlikely that this event will exit from topology.
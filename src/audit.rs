@@ -0,0 +1,326 @@
+//! Audit log of configuration changes and administrative actions.
+//!
+//! Every record is emitted as an internal log under the `vector::audit` target, so that it can be
+//! collected with the `internal_logs` source, and is appended as a line of JSON to the audit log
+//! file when one is set with `--audit-log`.
+
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+
+use crate::config::{to_sorted_json_string, Config, ConfigPath};
+
+static AUDIT_LOG: OnceCell<Mutex<File>> = OnceCell::new();
+
+static TAP_SESSIONS: AtomicU64 = AtomicU64::new(0);
+
+/// An audited action.
+#[derive(Debug, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditAction {
+    /// The initial configuration was loaded.
+    ConfigLoaded {
+        config_hash: String,
+        config_paths: Vec<String>,
+    },
+
+    /// A new configuration was applied.
+    ConfigReloaded {
+        previous_config_hash: String,
+        config_hash: String,
+        added: Vec<String>,
+        changed: Vec<ComponentChange>,
+        removed: Vec<String>,
+    },
+
+    /// A new configuration failed to load or to be applied.
+    ConfigReloadFailed {
+        config_hash: Option<String>,
+        rolled_back: bool,
+    },
+
+    /// A mutation was executed through the API.
+    ApiMutation {
+        mutation: &'static str,
+        arguments: serde_json::Value,
+    },
+
+    /// A tap session started observing events.
+    TapStarted {
+        session_id: u64,
+        patterns: Vec<String>,
+    },
+
+    /// A tap session ended.
+    TapStopped { session_id: u64 },
+
+    /// Vector started draining its components before stopping.
+    DrainStarted {
+        graceful_shutdown_limit_secs: Option<u64>,
+    },
+
+    /// Vector finished draining its components, or was forced to stop.
+    DrainFinished { forced: bool },
+}
+
+/// A component whose configuration changed in a reload.
+#[derive(Debug, Serialize)]
+pub struct ComponentChange {
+    pub component: String,
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    action: &'a AuditAction,
+}
+
+/// Appends the audit records to the file at `path`, which is created if it doesn't exist.
+pub fn init(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    AUDIT_LOG
+        .set(Mutex::new(file))
+        .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "audit log already set"))
+}
+
+/// Records an audited action.
+pub fn record(action: AuditAction) {
+    let record = AuditRecord {
+        timestamp: Utc::now(),
+        action: &action,
+    };
+    let line = serde_json::to_string(&record).expect("Audit records are serializable.");
+    info!(target: "vector::audit", message = "Audit record.", record = %line);
+
+    if let Some(file) = AUDIT_LOG.get() {
+        let mut file = file.lock().expect("Audit log mutex is poisoned");
+        if let Err(error) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+            error!(message = "Failed writing to the audit log.", %error);
+        }
+    }
+}
+
+/// Records the initial configuration.
+pub fn config_loaded(config: &Config, config_paths: &[ConfigPath]) {
+    record(AuditAction::ConfigLoaded {
+        config_hash: config_hash(config),
+        config_paths: config_paths
+            .iter()
+            .map(|path| <&PathBuf>::from(path).display().to_string())
+            .collect(),
+    });
+}
+
+/// Records a reload, comparing the component hashes of the previous configuration with the
+/// hashes of the applied one.
+pub fn config_reloaded(previous_hashes: &BTreeMap<String, String>, config: &Config) {
+    let hashes = component_hashes(config);
+
+    let added = hashes
+        .keys()
+        .filter(|component| !previous_hashes.contains_key(*component))
+        .cloned()
+        .collect();
+    let removed = previous_hashes
+        .keys()
+        .filter(|component| !hashes.contains_key(*component))
+        .cloned()
+        .collect();
+    let changed = hashes
+        .iter()
+        .filter_map(|(component, hash)| {
+            let previous_hash = previous_hashes.get(component)?;
+            (previous_hash != hash).then(|| ComponentChange {
+                component: component.clone(),
+                previous_hash: previous_hash.clone(),
+                hash: hash.clone(),
+            })
+        })
+        .collect();
+
+    record(AuditAction::ConfigReloaded {
+        previous_config_hash: hash(previous_hashes),
+        config_hash: hash(&hashes),
+        added,
+        changed,
+        removed,
+    });
+}
+
+/// Records a tap session, returning its identifier.
+pub fn tap_started(patterns: impl IntoIterator<Item = String>) -> u64 {
+    let session_id = TAP_SESSIONS.fetch_add(1, Ordering::Relaxed) + 1;
+    let mut patterns = patterns.into_iter().collect::<Vec<_>>();
+    patterns.sort();
+    record(AuditAction::TapStarted {
+        session_id,
+        patterns,
+    });
+    session_id
+}
+
+/// A hash of the configuration, which doesn't depend on the order of its components.
+pub fn config_hash(config: &Config) -> String {
+    hash(&component_hashes(config))
+}
+
+/// The hash of the global options and of each component, keyed by `<kind>.<component_id>`.
+pub fn component_hashes(config: &Config) -> BTreeMap<String, String> {
+    let mut hashes = BTreeMap::from([("global".to_owned(), hash(&config.global))]);
+    hashes.extend(
+        config
+            .sources()
+            .map(|(key, source)| (format!("sources.{}", key), hash(source))),
+    );
+    hashes.extend(
+        config
+            .transforms()
+            .map(|(key, transform)| (format!("transforms.{}", key), hash(transform))),
+    );
+    hashes.extend(
+        config
+            .sinks()
+            .map(|(key, sink)| (format!("sinks.{}", key), hash(sink))),
+    );
+    hashes.extend(
+        config
+            .enrichment_tables
+            .iter()
+            .map(|(key, table)| (format!("enrichment_tables.{}", key), hash(table))),
+    );
+    hashes
+}
+
+/// SHA256 hexadecimal representation of the order-stable JSON of a value.
+fn hash<T: Serialize>(value: T) -> String {
+    openssl::sha::sha256(to_sorted_json_string(value).as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    fn config(toml: &str) -> Config {
+        ConfigBuilder::from_toml(toml).build().unwrap()
+    }
+
+    const CONFIG: &str = indoc! {r#"
+        [sources.in]
+          type = "test_basic"
+
+        [transforms.foo]
+          type = "test_basic"
+          inputs = ["in"]
+          suffix = "foo"
+          increase = 1.25
+
+        [sinks.out]
+          type = "test_basic"
+          inputs = ["foo"]
+    "#};
+
+    #[test]
+    fn hashes_are_independent_of_component_order() {
+        let reordered = indoc! {r#"
+            [sinks.out]
+              type = "test_basic"
+              inputs = ["foo"]
+
+            [transforms.foo]
+              type = "test_basic"
+              inputs = ["in"]
+              suffix = "foo"
+              increase = 1.25
+
+            [sources.in]
+              type = "test_basic"
+        "#};
+        assert_eq!(
+            config_hash(&config(CONFIG)),
+            config_hash(&config(reordered))
+        );
+    }
+
+    #[test]
+    fn diffs_component_hashes() {
+        let previous = component_hashes(&config(CONFIG));
+        let hashes = component_hashes(&config(indoc! {r#"
+            [sources.in]
+              type = "test_basic"
+
+            [transforms.foo]
+              type = "test_basic"
+              inputs = ["in"]
+              suffix = "bar"
+              increase = 1.25
+
+            [sinks.other]
+              type = "test_basic"
+              inputs = ["foo"]
+        "#}));
+
+        assert_eq!(previous["global"], hashes["global"]);
+        assert_eq!(previous["sources.in"], hashes["sources.in"]);
+        assert_ne!(previous["transforms.foo"], hashes["transforms.foo"]);
+        assert!(!hashes.contains_key("sinks.out"));
+        assert!(hashes.contains_key("sinks.other"));
+    }
+
+    #[test]
+    fn serializes_records() {
+        let action = AuditAction::ConfigReloadFailed {
+            config_hash: None,
+            rolled_back: true,
+        };
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            action: &action,
+        };
+        let value = serde_json::to_value(record).unwrap();
+        assert_eq!(value["action"], "config_reload_failed");
+        assert_eq!(value["rolled_back"], true);
+        assert!(value["timestamp"].is_string());
+    }
+
+    #[test]
+    fn appends_to_the_audit_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        init(&path).unwrap();
+
+        // Other tests can record actions concurrently, so only the records of this test are
+        // looked at.
+        let session_id = u64::MAX;
+        record(AuditAction::TapStopped { session_id });
+        record(AuditAction::TapStopped { session_id });
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let records = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .filter(|record| record["session_id"] == session_id)
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["action"], "tap_stopped");
+    }
+}
@@ -0,0 +1,637 @@
+#![allow(missing_docs)]
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use clap::Parser;
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use vector_core::event::{Metric, MetricValue};
+
+use crate::{
+    config::{self, log_schema, unit_test::UnitTestStreamSourceConfig, ComponentKey},
+    event::{Event, LogEvent},
+    metrics::Controller,
+    replay::read_archive,
+    signal::{SignalHandler, SignalTo},
+    topology,
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The ID of the source to inject the benchmark input into.
+    ///
+    /// The source is replaced by the input for the duration of the benchmark, and all other sources
+    /// are disabled. Defaults to the only source of the configuration.
+    #[arg(long)]
+    source: Option<String>,
+
+    /// An archive of recorded events to use as input, in the format read by `vector replay`.
+    ///
+    /// The archive is replayed in a loop until the benchmark ends. Defaults to generated events.
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// The size, in bytes, of the message of generated events.
+    #[arg(long, default_value = "256")]
+    message_size: NonZeroUsize,
+
+    /// How long to run the benchmark for, in seconds.
+    #[arg(long, default_value = "30")]
+    duration: NonZeroU32,
+
+    /// The maximum number of events to inject per second. Defaults to injecting events as fast as
+    /// the topology accepts them.
+    #[arg(long)]
+    rate: Option<NonZeroU32>,
+
+    /// Write the report as JSON to this file.
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// A report of a previous run to compare this one against.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Exit with an error if the throughput is lower than the baseline by more than this
+    /// percentage.
+    #[arg(long, requires = "baseline")]
+    max_regression: Option<u32>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Vector config files to load the topology from. If none are specified the
+    /// default config path `/etc/vector/vector.toml` will be targeted.
+    #[arg(id = "config", short, long, value_delimiter(','))]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// The report of a benchmark run.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Report {
+    /// The time from the start of the input to the end of the flush of the sinks.
+    pub duration_secs: f64,
+
+    /// The number of events injected into the source.
+    pub input_events: u64,
+
+    /// The number of events sent by the sinks.
+    pub output_events: u64,
+
+    /// The estimated JSON size of the events sent by the sinks.
+    pub output_bytes: u64,
+
+    /// The number of events sent by the sinks per second.
+    pub events_per_sec: f64,
+
+    /// The estimated JSON size of the events sent by the sinks per second.
+    pub bytes_per_sec: f64,
+
+    /// The CPU time used by the process, if it can be measured on this platform.
+    pub cpu_secs: Option<f64>,
+
+    /// The bytes allocated by the components, if allocation tracing is enabled.
+    pub allocated_bytes: Option<u64>,
+
+    /// The measurements of each component.
+    pub components: BTreeMap<String, ComponentReport>,
+}
+
+/// The measurements of a component in a benchmark run.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ComponentReport {
+    /// The kind of the component: `source`, `transform`, or `sink`.
+    pub kind: String,
+
+    /// The number of events received by the component.
+    pub received_events: u64,
+
+    /// The number of events sent by the component, on all its outputs.
+    pub sent_events: u64,
+
+    /// The last reported ratio of time the component was busy, between 0 and 1.
+    pub utilization: Option<f64>,
+
+    /// The bytes allocated by the component, if allocation tracing is enabled.
+    pub allocated_bytes: Option<u64>,
+}
+
+pub async fn cmd(opts: &Opts, signal_handler: &mut SignalHandler) -> exitcode::ExitCode {
+    let recorded = match opts.input.as_deref().map(read_archive).transpose() {
+        Ok(recorded) => recorded,
+        Err(error) => {
+            error!(message = "Failed to read input.", path = ?opts.input, %error);
+            return exitcode::NOINPUT;
+        }
+    };
+    if recorded.as_ref().map_or(false, Vec::is_empty) {
+        error!(message = "Input is empty.", path = ?opts.input);
+        return exitcode::NOINPUT;
+    }
+    let baseline = match opts.baseline.as_deref().map(read_report).transpose() {
+        Ok(baseline) => baseline,
+        Err(error) => {
+            error!(message = "Failed to read baseline report.", path = ?opts.baseline, %error);
+            return exitcode::NOINPUT;
+        }
+    };
+
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+    let mut builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, warnings)) => {
+            for warning in warnings {
+                warn!("{}", warning);
+            }
+            builder
+        }
+        Err(errors) => {
+            for error in errors {
+                error!(message = "Configuration error.", %error);
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    let target = match &opts.source {
+        Some(source) => ComponentKey::from(source.as_str()),
+        None if builder.sources.len() == 1 => builder.sources.keys().next().unwrap().clone(),
+        None => {
+            error!("The configuration has several sources, select one with `--source`.");
+            return exitcode::USAGE;
+        }
+    };
+    if !builder.sources.contains_key(&target) {
+        error!(message = "Source not found in configuration.", source = %target);
+        return exitcode::CONFIG;
+    }
+
+    // Disable the other sources by replacing them with empty streams, which keeps the inputs of
+    // downstream components valid.
+    let others = builder
+        .sources
+        .keys()
+        .filter(|key| **key != target)
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in others {
+        builder.add_source(key.id(), UnitTestStreamSourceConfig::new(stream::empty()));
+    }
+    let duration = Duration::from_secs(opts.duration.get().into());
+    // The deadline is set when the source first polls the input, once the topology is started.
+    let input = input_stream(recorded, opts.message_size, opts.rate)
+        .take_until(async move { tokio::time::sleep(duration).await });
+    builder.add_source(target.id(), UnitTestStreamSourceConfig::new(input));
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => {
+            for error in errors {
+                error!(message = "Configuration error.", %error);
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+    let cpu_start = cpu_time();
+    let start = Instant::now();
+    let (topology, _) = match topology::start_validated(config, diff, pieces).await {
+        Some(started) => started,
+        None => return exitcode::CONFIG,
+    };
+
+    info!(message = "Running benchmark.", source = %target, duration_secs = opts.duration.get());
+
+    let mut signals = signal_handler.subscribe();
+    tokio::select! {
+        _ = topology.sources_finished() => {
+            info!("Benchmark input finished, flushing sinks.");
+        }
+        Ok(SignalTo::Shutdown | SignalTo::Quit) = signals.recv() => {
+            info!("Benchmark interrupted.");
+        }
+    }
+    topology.stop().await;
+
+    let elapsed = start.elapsed();
+    let cpu = cpu_start
+        .zip(cpu_time())
+        .map(|(start, end)| end.saturating_sub(start));
+    let metrics = Controller::get()
+        .map(Controller::capture_metrics)
+        .unwrap_or_default();
+    let report = Report::new(&metrics, &target, elapsed, cpu);
+
+    println!("{}", report.summary());
+    if let Some(path) = &opts.output {
+        let written = serde_json::to_vec_pretty(&report)
+            .map_err(io::Error::from)
+            .and_then(|json| fs::write(path, json));
+        if let Err(error) = written {
+            error!(message = "Failed to write report.", path = ?path, %error);
+            return exitcode::CANTCREAT;
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        println!("{}", report.comparison(&baseline));
+        if let Some(max_regression) = opts.max_regression {
+            let regression = report.regression(&baseline);
+            if regression > f64::from(max_regression) {
+                error!(
+                    message = "Throughput regressed beyond the allowed maximum.",
+                    regression_percent = regression,
+                    max_regression_percent = max_regression,
+                );
+                return exitcode::SOFTWARE;
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+fn read_report(path: &Path) -> crate::Result<Report> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+/// The events injected into the source: the recorded events in a loop, or generated events.
+fn input_stream(
+    recorded: Option<Vec<LogEvent>>,
+    message_size: NonZeroUsize,
+    rate: Option<NonZeroU32>,
+) -> stream::BoxStream<'static, Event> {
+    let events = match recorded {
+        Some(recorded) => stream::iter(recorded.into_iter().cycle())
+            .map(Event::Log)
+            .boxed(),
+        None => stream::iter(0..)
+            .map(move |index| Event::Log(generate_event(index, message_size)))
+            .boxed(),
+    };
+
+    match rate {
+        None => events,
+        Some(rate) => {
+            let period = Duration::from_secs(1) / rate.get();
+            tokio_stream::StreamExt::throttle(events, period).boxed()
+        }
+    }
+}
+
+/// Generates an event whose message is made of its index followed by letters, so that runs are
+/// reproducible.
+fn generate_event(index: u64, message_size: NonZeroUsize) -> LogEvent {
+    let mut message = format!("{} ", index);
+    message.extend(
+        ('a'..='z')
+            .cycle()
+            .take(message_size.get().saturating_sub(message.len())),
+    );
+    message.truncate(message_size.get());
+
+    let mut log = LogEvent::from(message);
+    if let Some(timestamp_key) = log_schema().timestamp_key() {
+        log.insert((lookup::PathPrefix::Event, timestamp_key), Utc::now());
+    }
+    log
+}
+
+/// The CPU time used by the process so far.
+#[cfg(unix)]
+fn cpu_time() -> Option<Duration> {
+    use nix::sys::{
+        resource::{getrusage, UsageWho},
+        time::TimeValLike,
+    };
+
+    let usage = getrusage(UsageWho::RUSAGE_SELF).ok()?;
+    let micros = usage.user_time().num_microseconds() + usage.system_time().num_microseconds();
+    Some(Duration::from_micros(micros.try_into().ok()?))
+}
+
+#[cfg(not(unix))]
+const fn cpu_time() -> Option<Duration> {
+    None
+}
+
+/// The metrics of components the report is made of.
+const COMPONENT_METRICS: [&str; 4] = [
+    "component_received_events_total",
+    "component_sent_events_total",
+    "utilization",
+    "component_allocated_bytes_total",
+];
+
+fn counter_value(metric: &Metric) -> Option<u64> {
+    match metric.value() {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => Some(*value as u64),
+        _ => None,
+    }
+}
+
+impl Report {
+    fn new(
+        metrics: &[Metric],
+        source: &ComponentKey,
+        elapsed: Duration,
+        cpu: Option<Duration>,
+    ) -> Self {
+        let mut components = BTreeMap::<String, ComponentReport>::new();
+        for metric in metrics {
+            let (Some(id), Some(kind)) = (
+                metric.tag_value("component_id"),
+                metric.tag_value("component_kind"),
+            ) else {
+                continue;
+            };
+            if !COMPONENT_METRICS.contains(&metric.name()) {
+                continue;
+            }
+            let component = components.entry(id).or_default();
+            component.kind = kind;
+            match metric.name() {
+                "component_received_events_total" => {
+                    component.received_events += counter_value(metric).unwrap_or_default();
+                }
+                // Sources and transforms with several outputs have a series for each of them.
+                "component_sent_events_total" => {
+                    component.sent_events += counter_value(metric).unwrap_or_default();
+                }
+                "utilization" => {
+                    if let MetricValue::Gauge { value } = metric.value() {
+                        component.utilization = Some(*value);
+                    }
+                }
+                _ => {
+                    component.allocated_bytes = counter_value(metric);
+                }
+            }
+        }
+
+        let sinks = metrics
+            .iter()
+            .filter(|metric| metric.tag_value("component_kind").as_deref() == Some("sink"));
+        let output_bytes = sinks
+            .filter(|metric| metric.name() == "component_sent_event_bytes_total")
+            .filter_map(counter_value)
+            .sum();
+        let output_events = components
+            .values()
+            .filter(|component| component.kind == "sink")
+            .map(|component| component.sent_events)
+            .sum();
+        let input_events = components
+            .get(source.id())
+            .map(|component| component.sent_events)
+            .unwrap_or_default();
+        let allocated_bytes = components
+            .values()
+            .filter_map(|component| component.allocated_bytes)
+            .fold(None, |total, bytes| Some(total.unwrap_or(0) + bytes));
+
+        let duration_secs = elapsed.as_secs_f64();
+        Self {
+            duration_secs,
+            input_events,
+            output_events,
+            output_bytes,
+            events_per_sec: output_events as f64 / duration_secs,
+            bytes_per_sec: output_bytes as f64 / duration_secs,
+            cpu_secs: cpu.map(|cpu| cpu.as_secs_f64()),
+            allocated_bytes,
+            components,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = format!(
+            "Ran for {:.2}s: {} events in, {} events out, {:.0} events/s, {:.0} bytes/s",
+            self.duration_secs,
+            self.input_events,
+            self.output_events,
+            self.events_per_sec,
+            self.bytes_per_sec,
+        );
+        if let Some(cpu_secs) = self.cpu_secs {
+            summary += &format!(
+                ", {:.2}s CPU ({:.0}%)",
+                cpu_secs,
+                cpu_secs / self.duration_secs * 100.0
+            );
+        }
+        if let Some(allocated_bytes) = self.allocated_bytes {
+            summary += &format!(", {} bytes allocated", allocated_bytes);
+        }
+        for (id, component) in &self.components {
+            summary += &format!(
+                "\n  {} {}: {} events in, {} events out",
+                component.kind, id, component.received_events, component.sent_events
+            );
+            if let Some(utilization) = component.utilization {
+                summary += &format!(", {:.1}% utilization", utilization * 100.0);
+            }
+        }
+        summary
+    }
+
+    fn comparison(&self, baseline: &Self) -> String {
+        let change = |current: f64, baseline: f64| {
+            if baseline == 0.0 {
+                "n/a".to_owned()
+            } else {
+                format!("{:+.1}%", (current - baseline) / baseline * 100.0)
+            }
+        };
+        let mut comparison = format!(
+            "Compared to the baseline: {} events/s, {} bytes/s",
+            change(self.events_per_sec, baseline.events_per_sec),
+            change(self.bytes_per_sec, baseline.bytes_per_sec),
+        );
+        if let (Some(cpu_secs), Some(baseline_cpu_secs)) = (self.cpu_secs, baseline.cpu_secs) {
+            // CPU time is compared per event, as runs at different rates use different amounts.
+            comparison += &format!(
+                ", {} CPU per event",
+                change(
+                    cpu_secs / self.output_events.max(1) as f64,
+                    baseline_cpu_secs / baseline.output_events.max(1) as f64
+                )
+            );
+        }
+        for (id, component) in &self.components {
+            if let (Some(utilization), Some(baseline_utilization)) = (
+                component.utilization,
+                baseline
+                    .components
+                    .get(id)
+                    .and_then(|component| component.utilization),
+            ) {
+                comparison += &format!(
+                    "\n  {} {}: {:+.1} points of utilization",
+                    component.kind,
+                    id,
+                    (utilization - baseline_utilization) * 100.0
+                );
+            }
+        }
+        comparison
+    }
+
+    /// The percentage by which the throughput is lower than the one of the baseline.
+    fn regression(&self, baseline: &Self) -> f64 {
+        if baseline.events_per_sec == 0.0 {
+            0.0
+        } else {
+            (baseline.events_per_sec - self.events_per_sec) / baseline.events_per_sec * 100.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::MetricKind;
+
+    use super::*;
+
+    fn metric(name: &str, kind: &str, id: &str, value: MetricValue) -> Metric {
+        Metric::new(name, MetricKind::Absolute, value).with_tags(Some(
+            [
+                ("component_kind".to_owned(), kind.to_owned()),
+                ("component_id".to_owned(), id.to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    fn counter(name: &str, kind: &str, id: &str, value: f64) -> Metric {
+        metric(name, kind, id, MetricValue::Counter { value })
+    }
+
+    #[test]
+    fn generates_events_of_the_given_size() {
+        let size = NonZeroUsize::new(16).unwrap();
+        let log = generate_event(42, size);
+        assert_eq!(log["message"], "42 abcdefghijklm".into());
+
+        let log = generate_event(1_000_000, NonZeroUsize::new(4).unwrap());
+        assert_eq!(log["message"], "1000".into());
+    }
+
+    #[test]
+    fn builds_report_from_metrics() {
+        let metrics = vec![
+            counter("component_sent_events_total", "source", "in", 1000.0),
+            counter(
+                "component_received_events_total",
+                "transform",
+                "parse",
+                1000.0,
+            ),
+            counter("component_sent_events_total", "transform", "parse", 900.0),
+            metric(
+                "utilization",
+                "transform",
+                "parse",
+                MetricValue::Gauge { value: 0.5 },
+            ),
+            counter("component_received_events_total", "sink", "out", 900.0),
+            counter("component_sent_events_total", "sink", "out", 900.0),
+            counter("component_sent_event_bytes_total", "sink", "out", 90000.0),
+            counter("buffer_events", "other", "ignored", 1.0),
+        ];
+        let report = Report::new(
+            &metrics,
+            &ComponentKey::from("in"),
+            Duration::from_secs(10),
+            Some(Duration::from_secs(5)),
+        );
+
+        assert_eq!(report.input_events, 1000);
+        assert_eq!(report.output_events, 900);
+        assert_eq!(report.events_per_sec, 90.0);
+        assert_eq!(report.bytes_per_sec, 9000.0);
+        assert_eq!(report.cpu_secs, Some(5.0));
+        assert_eq!(report.allocated_bytes, None);
+        assert_eq!(report.components["parse"].utilization, Some(0.5));
+        assert_eq!(report.components["out"].kind, "sink");
+        assert_eq!(report.components.len(), 3);
+    }
+
+    #[test]
+    fn compares_against_baseline() {
+        let baseline = Report {
+            events_per_sec: 1000.0,
+            bytes_per_sec: 100_000.0,
+            ..Default::default()
+        };
+        let report = Report {
+            events_per_sec: 800.0,
+            bytes_per_sec: 100_000.0,
+            ..Default::default()
+        };
+
+        assert_eq!(report.regression(&baseline), 20.0);
+        assert!(report
+            .comparison(&baseline)
+            .starts_with("Compared to the baseline: -20.0% events/s, +0.0% bytes/s"));
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
@@ -9,8 +9,10 @@ use crate::service;
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
-use crate::{generate_schema, signal};
+use crate::{
+    bench, config, convert, generate, get_version, graph, list, replay, unit_test, validate,
+};
+use crate::{generate_schema, signal, vrl_cli};
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -33,6 +35,7 @@ impl Opts {
         let (quiet_level, verbose_level) = match self.sub_command {
             Some(SubCommand::Validate(_))
             | Some(SubCommand::Graph(_))
+            | Some(SubCommand::Convert(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
             | Some(SubCommand::Test(_)) => {
@@ -181,6 +184,16 @@ pub struct RootOpts {
     )]
     pub no_graceful_shutdown_limit: bool,
 
+    /// Run in FIPS mode: TLS only uses the FIPS module of OpenSSL, and the VRL functions relying on
+    /// non-validated cryptography are unavailable. Always enabled in builds with the `fips` feature.
+    #[arg(long, env = "VECTOR_FIPS")]
+    pub fips: bool,
+
+    /// Append a JSON record of every configuration load and reload, API mutation, tap session and
+    /// shutdown to this file. The records are also emitted as internal logs.
+    #[arg(long, env = "VECTOR_AUDIT_LOG")]
+    pub audit_log: Option<PathBuf>,
+
     /// Set runtime allocation tracing
     #[cfg(feature = "allocation-tracing")]
     #[arg(long, env = "ALLOCATION_TRACING", default_value = "false")]
@@ -236,6 +249,11 @@ pub enum SubCommand {
     #[command(hide = true)]
     Config(config::Opts),
 
+    /// Convert a Fluent Bit or Filebeat configuration into a Vector configuration, then exit.
+    ///
+    /// Settings that cannot be converted are marked with `TODO` comments in the output.
+    Convert(convert::Opts),
+
     /// List available components, then exit.
     List(list::Opts),
 
@@ -243,6 +261,19 @@ pub enum SubCommand {
     /// For guidance on how to write unit tests check out <https://vector.dev/guides/level-up/unit-testing/>.
     Test(unit_test::Opts),
 
+    /// Replay an archive of events through the topology, then exit.
+    ///
+    /// The events are injected into the given source at a controlled rate, with their original or
+    /// rewritten timestamps.
+    Replay(replay::Opts),
+
+    /// Benchmark the target config, then exit.
+    ///
+    /// Generated or recorded events are injected into a source for a given duration, and the
+    /// throughput, CPU time, allocations, and utilization of each component are reported and
+    /// optionally compared against a previous report.
+    Bench(bench::Opts),
+
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
@@ -259,7 +290,7 @@ pub enum SubCommand {
     Service(service::Opts),
 
     /// Vector Remap Language CLI
-    Vrl(vrl::cli::Opts),
+    Vrl(vrl_cli::Opts),
 }
 
 impl SubCommand {
@@ -269,11 +300,14 @@ impl SubCommand {
         color: bool,
     ) -> exitcode::ExitCode {
         match self {
+            Self::Bench(b) => bench::cmd(b, &mut signals.handler).await,
             Self::Config(c) => config::cmd(c),
+            Self::Convert(c) => convert::cmd(c),
             Self::Generate(g) => generate::cmd(g),
             Self::GenerateSchema => generate_schema::cmd(),
             Self::Graph(g) => graph::cmd(g),
             Self::List(l) => list::cmd(l),
+            Self::Replay(r) => replay::cmd(r, &mut signals.handler).await,
             #[cfg(windows)]
             Self::Service(s) => service::cmd(s),
             #[cfg(feature = "api-client")]
@@ -282,11 +316,7 @@ impl SubCommand {
             #[cfg(feature = "api-client")]
             Self::Top(t) => top::cmd(t).await,
             Self::Validate(v) => validate::validate(v, color).await,
-            Self::Vrl(s) => {
-                let mut functions = vrl::stdlib::all();
-                functions.extend(vector_vrl_functions::all());
-                vrl::cli::cmd::cmd(s, functions)
-            }
+            Self::Vrl(s) => vrl_cli::cmd(s),
         }
     }
 }
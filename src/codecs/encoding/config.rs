@@ -114,7 +114,8 @@ impl EncodingConfigWithFraming {
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
-                | Serializer::Text(_),
+                | Serializer::Text(_)
+                | Serializer::TextWithContext(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
 
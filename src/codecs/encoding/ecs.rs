@@ -0,0 +1,248 @@
+#![deny(missing_docs)]
+
+use std::collections::BTreeMap;
+
+use lookup::{
+    event_path,
+    lookup_v2::{OptionalValuePath, OwnedTargetPath, OwnedValuePath},
+    PathPrefix,
+};
+use serde::{Deserialize, Deserializer};
+use vector_config::configurable_component;
+use vector_core::event::LogEvent;
+use vrl::value::Value;
+
+use crate::serde::skip_serializing_if_default;
+
+/// The fields set by Vector sources, and the ECS fields holding the same data.
+const DEFAULT_MAPPING: [(&str, &str); 24] = [
+    ("timestamp", r#""@timestamp""#),
+    ("host", "host.name"),
+    ("source_type", "event.module"),
+    ("%vector.source_type", "event.module"),
+    ("%vector.ingest_timestamp", "event.ingested"),
+    ("appname", "process.name"),
+    ("container_id", "container.id"),
+    ("container_name", "container.name"),
+    ("facility", "log.syslog.facility.name"),
+    ("file", "log.file.path"),
+    ("hostname", "host.hostname"),
+    ("image", "container.image.name"),
+    ("kubernetes.container_name", "kubernetes.container.name"),
+    ("kubernetes.pod_labels", "kubernetes.labels"),
+    ("kubernetes.pod_name", "kubernetes.pod.name"),
+    ("kubernetes.pod_namespace", "kubernetes.namespace"),
+    ("kubernetes.pod_node_name", "kubernetes.node.name"),
+    ("kubernetes.pod_uid", "kubernetes.pod.uid"),
+    ("level", "log.level"),
+    ("procid", "process.pid"),
+    ("service", "service.name"),
+    ("source_ip", "source.ip"),
+    ("span_id", "span.id"),
+    ("trace_id", "trace.id"),
+];
+
+/// Elastic Common Schema (ECS) mapping.
+///
+/// Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+/// data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+/// an object have it moved to `message`.
+///
+/// [ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+#[configurable_component(no_deser)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EcsConfig {
+    /// Whether or not to move fields to their ECS name.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    enabled: bool,
+
+    /// Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+    /// to.
+    ///
+    /// Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+    /// leaves it where it is.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    #[configurable(metadata(docs::additional_props_description = "The path of an ECS field."))]
+    #[configurable(metadata(docs::examples = "ecs_mapping_examples()"))]
+    mapping: BTreeMap<String, OptionalValuePath>,
+
+    /// The fields moved, with the overrides applied.
+    #[serde(skip)]
+    fields: Vec<(OwnedTargetPath, OwnedValuePath)>,
+}
+
+fn ecs_mapping_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("app".to_string(), "service.name".to_string()),
+        ("file".to_string(), String::new()),
+    ])
+}
+
+impl<'de> Deserialize<'de> for EcsConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct EcsConfigInner {
+            #[serde(default)]
+            enabled: bool,
+            #[serde(default)]
+            mapping: BTreeMap<String, OptionalValuePath>,
+        }
+
+        let inner: EcsConfigInner = Deserialize::deserialize(deserializer)?;
+        Self::new(inner.enabled, inner.mapping).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<bool> for EcsConfig {
+    fn from(enabled: bool) -> Self {
+        Self::new(enabled, BTreeMap::new()).expect("default ECS mapping is valid")
+    }
+}
+
+impl EcsConfig {
+    /// Creates a new `EcsConfig`.
+    ///
+    /// Returns `Err` if a field of `mapping` is not a valid path.
+    pub fn new(
+        enabled: bool,
+        mapping: BTreeMap<String, OptionalValuePath>,
+    ) -> Result<Self, crate::Error> {
+        let mut ecs_fields = DEFAULT_MAPPING
+            .iter()
+            .map(|(field, ecs_field)| {
+                let ecs_field = OwnedValuePath::try_from(ecs_field.to_string())
+                    .expect("default ECS field is a valid path");
+                (field.to_string(), Some(ecs_field))
+            })
+            .collect::<BTreeMap<_, _>>();
+        ecs_fields.extend(
+            mapping
+                .iter()
+                .map(|(field, ecs_field)| (field.clone(), ecs_field.path.clone())),
+        );
+
+        let mut fields = Vec::new();
+        for (field, ecs_field) in ecs_fields {
+            let path = OwnedTargetPath::try_from(field.clone())
+                .map_err(|_| format!("Invalid field {:?} in the ECS mapping.", field))?;
+            if let Some(ecs_field) = ecs_field {
+                fields.push((path, ecs_field));
+            }
+        }
+
+        Ok(Self {
+            enabled,
+            mapping,
+            fields: if enabled { fields } else { Vec::new() },
+        })
+    }
+
+    /// Moves the fields of the event to their ECS name.
+    pub(super) fn apply(&self, log: &mut LogEvent) {
+        if !self.enabled {
+            return;
+        }
+
+        if !log.value().is_object() {
+            let message = std::mem::replace(log.value_mut(), Value::Object(BTreeMap::new()));
+            log.insert(event_path!("message"), message);
+        }
+
+        // Every field is removed before any is inserted, so that a field moved under another,
+        // such as `hostname` under `host`, is not overwritten by it.
+        let moved = self
+            .fields
+            .iter()
+            .filter_map(|(field, ecs_field)| Some((ecs_field, log.remove(field)?)))
+            .collect::<Vec<_>>();
+        for (ecs_field, value) in moved {
+            log.insert((PathPrefix::Event, ecs_field), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::metadata_path;
+
+    use super::*;
+    use crate::{codecs::Transformer, event::Event};
+
+    #[test]
+    fn maps_fields() {
+        let transformer: Transformer = toml::from_str(indoc::indoc! {r#"
+            [ecs]
+            enabled = true
+            mapping.app = "service.name"
+            mapping.file = ""
+        "#})
+        .unwrap();
+
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("host", "web-1");
+        log.insert("hostname", "web-1.local");
+        log.insert("file", "/var/log/app.log");
+        log.insert("app", "checkout");
+        log.insert("kubernetes.pod_name", "checkout-1");
+        log.insert("level", "info");
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+
+        let log = event.into_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert_eq!(log.get("host.name"), Some(&Value::from("web-1")));
+        assert_eq!(log.get("host.hostname"), Some(&Value::from("web-1.local")));
+        assert_eq!(log.get("file"), Some(&Value::from("/var/log/app.log")));
+        assert_eq!(log.get("service.name"), Some(&Value::from("checkout")));
+        assert_eq!(
+            log.get("kubernetes.pod.name"),
+            Some(&Value::from("checkout-1"))
+        );
+        assert_eq!(log.get("log.level"), Some(&Value::from("info")));
+        assert!(log.get("app").is_none());
+        assert!(log.get("level").is_none());
+        assert!(log.get("kubernetes.pod_name").is_none());
+    }
+
+    #[test]
+    fn maps_vector_namespace_events() {
+        let transformer: Transformer = toml::from_str("ecs = true").unwrap();
+
+        let mut log = LogEvent::from(Value::from("hello"));
+        log.insert(metadata_path!("vector", "source_type"), "demo_logs");
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+
+        let log = event.into_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        assert_eq!(log.get("event.module"), Some(&Value::from("demo_logs")));
+        assert!(log.get(metadata_path!("vector", "source_type")).is_none());
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let transformer: Transformer = toml::from_str("").unwrap();
+
+        let mut log = LogEvent::default();
+        log.insert("host", "web-1");
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+
+        assert_eq!(event.as_log().get("host"), Some(&Value::from("web-1")));
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        let error = toml::from_str::<Transformer>(indoc::indoc! {r#"
+            [ecs]
+            mapping."a[" = "b"
+        "#})
+        .unwrap_err();
+        assert!(error.to_string().contains("Invalid field"));
+    }
+}
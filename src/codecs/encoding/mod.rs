@@ -1,7 +1,11 @@
 mod config;
+mod ecs;
 mod encoder;
+mod timestamp;
 mod transformer;
 
 pub use config::{EncodingConfig, EncodingConfigWithFraming, SinkType};
+pub use ecs::EcsConfig;
 pub use encoder::Encoder;
+pub use timestamp::{TimestampEncoding, TimestampUnit};
 pub use transformer::{TimestampFormat, Transformer};
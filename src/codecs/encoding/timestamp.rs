@@ -0,0 +1,217 @@
+#![deny(missing_docs)]
+
+use chrono::{
+    format::{strftime::StrftimeItems, Item},
+    DateTime, SecondsFormat, SubsecRound, Utc,
+};
+use serde::{Deserialize, Deserializer};
+use vector_config::configurable_component;
+use vrl::value::Value;
+
+/// The unit, or precision, of encoded timestamps.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampUnit {
+    /// Seconds.
+    #[default]
+    S,
+
+    /// Milliseconds.
+    Ms,
+
+    /// Microseconds.
+    Us,
+
+    /// Nanoseconds.
+    Ns,
+}
+
+impl TimestampUnit {
+    /// The number of fractional digits of a second kept at this unit.
+    const fn digits(self) -> u16 {
+        match self {
+            Self::S => 0,
+            Self::Ms => 3,
+            Self::Us => 6,
+            Self::Ns => 9,
+        }
+    }
+
+    const fn seconds_format(self) -> SecondsFormat {
+        match self {
+            Self::S => SecondsFormat::Secs,
+            Self::Ms => SecondsFormat::Millis,
+            Self::Us => SecondsFormat::Micros,
+            Self::Ns => SecondsFormat::Nanos,
+        }
+    }
+}
+
+/// Timestamp encoding.
+///
+/// Converts the timestamp fields of events to the representation, and the unit, expected by the
+/// destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+#[configurable_component(no_deser)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimestampEncoding {
+    /// The representation of timestamps.
+    ///
+    /// Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+    /// [`strftime` format][strftime].
+    ///
+    /// [strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+    #[serde(default = "default_format")]
+    #[configurable(metadata(docs::examples = "unix"))]
+    #[configurable(metadata(docs::examples = "rfc3339"))]
+    #[configurable(metadata(docs::examples = "%Y-%m-%d %H:%M:%S%.3f"))]
+    format: String,
+
+    /// The unit of Unix timestamps, and the precision of the others.
+    ///
+    /// Timestamps are truncated to this unit.
+    #[serde(default)]
+    unit: TimestampUnit,
+}
+
+fn default_format() -> String {
+    "unix".to_owned()
+}
+
+impl<'de> Deserialize<'de> for TimestampEncoding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct TimestampEncodingInner {
+            #[serde(default = "default_format")]
+            format: String,
+            #[serde(default)]
+            unit: TimestampUnit,
+        }
+
+        let inner: TimestampEncodingInner = Deserialize::deserialize(deserializer)?;
+        Self::new(inner.format, inner.unit).map_err(serde::de::Error::custom)
+    }
+}
+
+impl TimestampEncoding {
+    /// Creates a new `TimestampEncoding`.
+    ///
+    /// Returns `Err` if `format` is neither `unix`, `rfc3339`, nor a valid `strftime` format.
+    pub fn new(format: String, unit: TimestampUnit) -> Result<Self, crate::Error> {
+        if format != "unix"
+            && format != "rfc3339"
+            && StrftimeItems::new(&format).any(|item| matches!(item, Item::Error))
+        {
+            return Err(format!("Invalid timestamp format {:?}.", format).into());
+        }
+        Ok(Self { format, unit })
+    }
+
+    /// Encodes a timestamp, or returns `None` if it is out of the range of Unix timestamps of the
+    /// unit.
+    pub(super) fn encode(&self, timestamp: &DateTime<Utc>) -> Option<Value> {
+        let timestamp = timestamp.trunc_subsecs(self.unit.digits());
+        Some(match self.format.as_str() {
+            "unix" => Value::Integer(match self.unit {
+                TimestampUnit::S => timestamp.timestamp(),
+                TimestampUnit::Ms => timestamp.timestamp_millis(),
+                TimestampUnit::Us => timestamp.timestamp_micros(),
+                TimestampUnit::Ns => timestamp
+                    .timestamp()
+                    .checked_mul(1_000_000_000)?
+                    .checked_add(timestamp.timestamp_subsec_nanos().into())?,
+            }),
+            "rfc3339" => timestamp
+                .to_rfc3339_opts(self.unit.seconds_format(), true)
+                .into(),
+            format => timestamp.format(format).to_string().into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::LogEvent;
+
+    use crate::{codecs::Transformer, event::Event};
+
+    use super::*;
+
+    fn encode(config: &str) -> Value {
+        let transformer: Transformer = toml::from_str(config).unwrap();
+        let timestamp = Utc
+            .timestamp_opt(1_700_000_000, 123_456_789)
+            .single()
+            .unwrap();
+
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("nested.timestamp", timestamp);
+        let mut event = Event::from(log);
+        transformer.transform(&mut event);
+
+        let log = event.into_log();
+        assert_eq!(log.get("message"), Some(&Value::from("hello")));
+        log.get("nested.timestamp").unwrap().clone()
+    }
+
+    #[test]
+    fn encodes_unix_timestamps() {
+        assert_eq!(encode("timestamp.format = \"unix\""), 1_700_000_000.into());
+        assert_eq!(
+            encode("timestamp.unit = \"ms\""),
+            1_700_000_000_123i64.into()
+        );
+        assert_eq!(
+            encode("timestamp.unit = \"us\""),
+            1_700_000_000_123_456i64.into()
+        );
+        assert_eq!(
+            encode("timestamp.unit = \"ns\""),
+            1_700_000_000_123_456_789i64.into()
+        );
+    }
+
+    #[test]
+    fn encodes_formatted_timestamps() {
+        assert_eq!(
+            encode(indoc::indoc! {r#"
+                timestamp.format = "rfc3339"
+                timestamp.unit = "ms"
+            "#}),
+            "2023-11-14T22:13:20.123Z".into()
+        );
+        assert_eq!(
+            encode(indoc::indoc! {r#"
+                timestamp.format = "%Y-%m-%d %H:%M:%S%.6f"
+                timestamp.unit = "ms"
+            "#}),
+            "2023-11-14 22:13:20.123000".into()
+        );
+    }
+
+    #[test]
+    fn leaves_out_of_range_timestamps() {
+        let encoding = TimestampEncoding::new("unix".to_owned(), TimestampUnit::Ns).unwrap();
+        let timestamp = Utc.with_ymd_and_hms(2300, 1, 1, 0, 0, 0).single().unwrap();
+        assert_eq!(encoding.encode(&timestamp), None);
+    }
+
+    #[test]
+    fn rejects_invalid_formats() {
+        let error = toml::from_str::<Transformer>("timestamp.format = \"%Q\"").unwrap_err();
+        assert!(error.to_string().contains("Invalid timestamp format"));
+
+        let error = toml::from_str::<Transformer>(indoc::indoc! {r#"
+            timestamp_format = "unix"
+            timestamp.unit = "ms"
+        "#})
+        .unwrap_err();
+        assert!(error.to_string().contains("mutually exclusive"));
+    }
+}
@@ -3,6 +3,7 @@
 use core::fmt::Debug;
 use std::collections::BTreeMap;
 
+use chrono::{DateTime, Utc};
 use lookup::lookup_v2::ConfigValuePath;
 use lookup::{
     event_path,
@@ -14,6 +15,7 @@ use vector_config::configurable_component;
 use vector_core::event::{LogEvent, MaybeAsLogMut};
 use vrl::value::Value;
 
+use super::{EcsConfig, TimestampEncoding};
 use crate::{event::Event, serde::skip_serializing_if_default};
 
 /// Transformations to prepare an event for serialization.
@@ -31,6 +33,14 @@ pub struct Transformer {
     /// Format used for timestamp fields.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     timestamp_format: Option<TimestampFormat>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    timestamp: Option<TimestampEncoding>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    ecs: EcsConfig,
 }
 
 impl<'de> Deserialize<'de> for Transformer {
@@ -47,15 +57,27 @@ impl<'de> Deserialize<'de> for Transformer {
             except_fields: Option<Vec<String>>,
             #[serde(default)]
             timestamp_format: Option<TimestampFormat>,
+            #[serde(default)]
+            timestamp: Option<TimestampEncoding>,
+            #[serde(default, deserialize_with = "crate::serde::bool_or_struct")]
+            ecs: EcsConfig,
         }
 
         let inner: TransformerInner = Deserialize::deserialize(deserializer)?;
-        Self::new(
+        if inner.timestamp_format.is_some() && inner.timestamp.is_some() {
+            return Err(serde::de::Error::custom(
+                "`timestamp_format` and `timestamp` should be mutually exclusive.",
+            ));
+        }
+        let mut transformer = Self::new(
             inner.only_fields,
             inner.except_fields,
             inner.timestamp_format,
         )
-        .map_err(serde::de::Error::custom)
+        .map_err(serde::de::Error::custom)?;
+        transformer.timestamp = inner.timestamp;
+        transformer.ecs = inner.ecs;
+        Ok(transformer)
     }
 }
 
@@ -76,6 +98,8 @@ impl Transformer {
             only_fields,
             except_fields,
             timestamp_format,
+            timestamp: None,
+            ecs: EcsConfig::default(),
         })
     }
 
@@ -119,9 +143,11 @@ impl Transformer {
     pub fn transform(&self, event: &mut Event) {
         // Rules are currently applied to logs only.
         if let Some(log) = event.maybe_as_log_mut() {
-            // Ordering in here should not matter.
+            // Fields are selected by the names they have in Vector, before being moved to their
+            // ECS names.
             self.apply_except_fields(log);
             self.apply_only_fields(log);
+            self.ecs.apply(log);
             self.apply_timestamp_format(log);
         }
     }
@@ -147,30 +173,12 @@ impl Transformer {
     }
 
     fn apply_timestamp_format(&self, log: &mut LogEvent) {
-        if let Some(timestamp_format) = self.timestamp_format.as_ref() {
+        if let Some(timestamp) = self.timestamp.as_ref() {
+            Self::encode_timestamps(log, |ts| timestamp.encode(ts));
+        } else if let Some(timestamp_format) = self.timestamp_format.as_ref() {
             match timestamp_format {
                 TimestampFormat::Unix => {
-                    if log.value().is_object() {
-                        let mut unix_timestamps = Vec::new();
-                        for (k, v) in log.all_fields().expect("must be an object") {
-                            if let Value::Timestamp(ts) = v {
-                                unix_timestamps.push((k.clone(), Value::Integer(ts.timestamp())));
-                            }
-                        }
-                        for (k, v) in unix_timestamps {
-                            log.insert(k.as_str(), v);
-                        }
-                    } else {
-                        // root is not an object
-                        let timestamp = if let Value::Timestamp(ts) = log.value() {
-                            Some(ts.timestamp())
-                        } else {
-                            None
-                        };
-                        if let Some(ts) = timestamp {
-                            log.insert(event_path!(), Value::Integer(ts));
-                        }
-                    }
+                    Self::encode_timestamps(log, |ts| Some(Value::Integer(ts.timestamp())));
                 }
                 // RFC3339 is the default serialization of a timestamp.
                 TimestampFormat::Rfc3339 => (),
@@ -178,6 +186,33 @@ impl Transformer {
         }
     }
 
+    /// Replaces the timestamp fields of the event with their encoding, if any.
+    fn encode_timestamps(log: &mut LogEvent, encode: impl Fn(&DateTime<Utc>) -> Option<Value>) {
+        if log.value().is_object() {
+            let mut encoded_timestamps = Vec::new();
+            for (k, v) in log.all_fields().expect("must be an object") {
+                if let Value::Timestamp(ts) = v {
+                    if let Some(encoded) = encode(ts) {
+                        encoded_timestamps.push((k.clone(), encoded));
+                    }
+                }
+            }
+            for (k, v) in encoded_timestamps {
+                log.insert(k.as_str(), v);
+            }
+        } else {
+            // root is not an object
+            let encoded = if let Value::Timestamp(ts) = log.value() {
+                encode(ts)
+            } else {
+                None
+            };
+            if let Some(encoded) = encoded {
+                log.insert(event_path!(), encoded);
+            }
+        }
+    }
+
     /// Set the `except_fields` value.
     ///
     /// Returns `Err` if the new `except_fields` fail validation, i.e. are not mutually exclusive
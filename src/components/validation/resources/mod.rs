@@ -186,7 +186,9 @@ fn serializer_config_to_deserializer(config: &SerializerConfig) -> decoding::Des
         SerializerConfig::Logfmt => todo!(),
         SerializerConfig::Native => DeserializerConfig::Native,
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson(Default::default()),
-        SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
+        SerializerConfig::RawMessage
+        | SerializerConfig::Text(_)
+        | SerializerConfig::TextWithContext(_) => DeserializerConfig::Bytes,
     };
 
     deserializer_config.build()
@@ -101,6 +101,7 @@ impl InputEdge {
             while let Some(test_event) = rx.recv().await {
                 let request = PushEventsRequest {
                     events: vec![test_event.into_event().into()],
+                    ..Default::default()
                 };
 
                 if let Err(e) = client.push_events(request).await {
@@ -40,11 +40,12 @@ impl ConditionalConfig for VrlConfig {
         //     },
         // };
 
-        let functions = vrl::stdlib::all()
+        let mut functions = vrl::stdlib::all()
             .into_iter()
             .chain(enrichment::vrl_functions().into_iter())
             .chain(vector_vrl_functions::all())
             .collect::<Vec<_>>();
+        vector_core::fips::retain_approved_vrl_functions(&mut functions);
 
         let state = TypeState::default();
 
@@ -0,0 +1,74 @@
+use indexmap::IndexMap;
+use vector_config::configurable_component;
+
+const fn default_max_groups() -> usize {
+    10_000
+}
+
+/// Accounting options.
+///
+/// Accounting counts the events and bytes ingested by every source and delivered to every sink,
+/// grouped by dimensions of the events such as their namespace or tenant, to attribute the cost of
+/// the pipeline. The counts are exposed through the API, and can be emitted into the pipeline by
+/// the `internal_accounting` source.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Whether or not events are accounted.
+    pub enabled: bool,
+
+    /// The dimensions by which events are grouped, keyed by name.
+    ///
+    /// Each dimension is the path of a field of log and trace events, or the name of a tag of
+    /// metrics. Events without the field are grouped under a missing value for the dimension.
+    #[configurable(metadata(
+        docs::additional_props_description = "The field holding the dimension."
+    ))]
+    #[configurable(metadata(docs::examples = "example_dimensions()"))]
+    pub dimensions: IndexMap<String, String>,
+
+    /// The maximum number of groups accounted for each component.
+    ///
+    /// Once reached, events of new groups are accounted together in an overflow group, which has
+    /// no dimensions.
+    #[configurable(metadata(docs::type_unit = "groups"))]
+    pub max_groups: usize,
+}
+
+fn example_dimensions() -> IndexMap<String, String> {
+    IndexMap::from([
+        (
+            "namespace".to_owned(),
+            "kubernetes.pod_namespace".to_owned(),
+        ),
+        ("service".to_owned(), "service".to_owned()),
+        ("tenant".to_owned(), "tenant_id".to_owned()),
+    ])
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dimensions: IndexMap::new(),
+            max_groups: default_max_groups(),
+        }
+    }
+}
+
+impl Options {
+    pub fn merge(&mut self, other: Self, errors: &mut Vec<String>) {
+        self.enabled |= other.enabled;
+        if other.max_groups != default_max_groups() {
+            self.max_groups = other.max_groups;
+        }
+        for (name, field) in other.dimensions {
+            if self.dimensions.contains_key(&name) {
+                errors.push(format!("duplicate accounting dimension: {}", name));
+            } else {
+                self.dimensions.insert(name, field);
+            }
+        }
+    }
+}
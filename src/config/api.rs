@@ -18,6 +18,11 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// Whether or not mutations overriding the configuration of running components, such as the
+    /// request limits of sinks, are available on the API endpoint.
+    #[serde(default = "default_mutations")]
+    pub mutations: bool,
 }
 
 impl Default for Options {
@@ -26,6 +31,7 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            mutations: default_mutations(),
         }
     }
 }
@@ -45,6 +51,10 @@ const fn default_playground() -> bool {
     true
 }
 
+const fn default_mutations() -> bool {
+    false
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -70,6 +80,7 @@ impl Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            mutations: self.mutations | other.mutations,
         };
 
         *self = options;
@@ -83,6 +94,7 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        mutations: true,
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +105,7 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            mutations: true,
         }
     );
 }
@@ -104,6 +117,7 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        mutations: false,
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +128,7 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            mutations: false,
         }
     );
 }
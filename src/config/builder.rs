@@ -1,9 +1,6 @@
-#[cfg(feature = "enterprise")]
-use std::collections::BTreeMap;
-use std::{path::Path, time::Duration};
+use std::{collections::BTreeMap, path::Path, time::Duration};
 
 use indexmap::IndexMap;
-#[cfg(feature = "enterprise")]
 use serde_json::Value;
 use vector_config::configurable_component;
 use vector_core::config::GlobalOptions;
@@ -15,9 +12,9 @@ use super::api;
 #[cfg(feature = "enterprise")]
 use super::enterprise;
 use super::{
-    compiler, schema, BoxedSink, BoxedSource, BoxedTransform, ComponentKey, Config,
-    EnrichmentTableOuter, HealthcheckOptions, SinkOuter, SourceOuter, TestDefinition,
-    TransformOuter,
+    accounting, compiler, fault_injection, scaling, schema, BoxedSink, BoxedSource, BoxedTransform,
+    ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions, SinkOuter, SourceOuter,
+    TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -47,6 +44,27 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub fault_injection: fault_injection::Options,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub accounting: accounting::Options,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub scaling: scaling::Options,
+
     /// All configured enrichment tables.
     #[serde(default)]
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
@@ -130,8 +148,7 @@ impl ConfigBuilderHash<'_> {
 ///
 /// Rather than rely on the opaque underlying serde structures, we are explicit
 /// about sorting, sacrificing a bit of potential convenience for correctness.
-#[cfg(feature = "enterprise")]
-fn to_sorted_json_string<T>(value: T) -> String
+pub(crate) fn to_sorted_json_string<T>(value: T) -> String
 where
     T: ::serde::Serialize,
 {
@@ -141,7 +158,6 @@ where
     serde_json::to_string(&value).expect("Should serialize Value to JSON string. Please report.")
 }
 
-#[cfg(feature = "enterprise")]
 fn sort_json_value(value: &mut Value) {
     match value {
         Value::Array(arr) => {
@@ -194,6 +210,9 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            fault_injection,
+            accounting,
+            scaling,
             enrichment_tables,
             sources,
             sinks,
@@ -224,6 +243,9 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            fault_injection,
+            accounting,
+            scaling,
             enrichment_tables,
             sources,
             sinks,
@@ -343,6 +365,13 @@ impl ConfigBuilder {
 
         self.healthchecks.merge(with.healthchecks);
 
+        self.fault_injection
+            .merge(with.fault_injection, &mut errors);
+
+        self.accounting.merge(with.accounting, &mut errors);
+
+        self.scaling.merge(with.scaling, &mut errors);
+
         with.enrichment_tables.keys().for_each(|k| {
             if self.enrichment_tables.contains_key(k) {
                 errors.push(format!("duplicate enrichment_table name found: {}", k));
@@ -55,6 +55,18 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Rewrite deprecated options of the configuration files to their replacements.
+    ///
+    /// The changes are printed as a diff, unless `--write` is given.
+    Migrate(super::migrate::Opts),
 }
 
 impl Opts {
@@ -163,6 +175,10 @@ fn serialize_to_json(
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Some(Command::Migrate(opts)) = &opts.command {
+        return super::migrate::cmd(opts);
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
@@ -35,6 +35,32 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
+    if let Err(fault_errors) = validation::check_fault_injection(&builder) {
+        errors.extend(fault_errors);
+    }
+
+    if let Err(ordering_errors) = validation::check_ordering(&builder) {
+        errors.extend(ordering_errors);
+    }
+
+    if let Err(accounting_errors) = validation::check_accounting(&builder) {
+        errors.extend(accounting_errors);
+    }
+
+    if let Err(scaling_errors) = validation::check_scaling(&builder) {
+        errors.extend(scaling_errors);
+    }
+
+    if let Err(dead_letter_errors) = validation::check_dead_letters(&builder) {
+        errors.extend(dead_letter_errors);
+    }
+
+    if vector_core::fips::is_enabled() {
+        if let Err(fips_errors) = validation::check_fips_ciphersuites(&builder) {
+            errors.extend(fips_errors);
+        }
+    }
+
     #[cfg(feature = "enterprise")]
     let hash = Some(builder.sha256_hash());
 
@@ -49,6 +75,9 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         #[cfg(feature = "enterprise")]
         enterprise,
         healthchecks,
+        fault_injection,
+        accounting,
+        scaling,
         enrichment_tables,
         sources,
         sinks,
@@ -106,6 +135,9 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             enterprise,
             hash,
             healthchecks,
+            fault_injection,
+            accounting,
+            scaling,
             enrichment_tables,
             sources,
             sinks,
@@ -0,0 +1,67 @@
+use indexmap::IndexMap;
+use vector_config::configurable_component;
+
+use super::ComponentKey;
+
+/// Fault injection options.
+///
+/// Faults simulate a degraded component, to rehearse incidents and verify that acknowledgements are
+/// handled end-to-end. They are not meant to be used in production.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Whether or not faults can be injected, through this configuration or the API.
+    pub enabled: bool,
+
+    /// The faults injected into each component, keyed by component ID.
+    #[configurable(metadata(docs::additional_props_description = "The faults of a component."))]
+    pub components: IndexMap<ComponentKey, ComponentFaults>,
+}
+
+impl Options {
+    pub fn merge(&mut self, other: Self, errors: &mut Vec<String>) {
+        self.enabled |= other.enabled;
+        for (key, faults) in other.components {
+            if self.components.contains_key(&key) {
+                errors.push(format!(
+                    "duplicate fault_injection definition for component: {}",
+                    key
+                ));
+            } else {
+                self.components.insert(key, faults);
+            }
+        }
+    }
+}
+
+/// Faults injected into a component.
+///
+/// For sources, faults apply to the events they send. For transforms and sinks, faults apply to the
+/// events they receive.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComponentFaults {
+    /// The latency, in milliseconds, added to each batch of events.
+    #[configurable(metadata(docs::examples = 500))]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub latency_ms: u64,
+
+    /// The ratio of batches of events that are rejected, between `0.0` and `1.0`.
+    ///
+    /// The events of a rejected batch are dropped, and their acknowledgement is negative.
+    #[configurable(metadata(docs::examples = 0.1))]
+    pub error_rate: f64,
+
+    /// Whether to stop processing events, until unset.
+    ///
+    /// Events back up in the buffers of the component, which are eventually full.
+    pub stall: bool,
+}
+
+impl ComponentFaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -0,0 +1,428 @@
+//! Migration of configurations from deprecated options to their replacements.
+
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use colored::*;
+use serde_json::{Map, Value};
+
+use super::{format, Format};
+
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The configuration files to migrate.
+    ///
+    /// The format of each file is detected from its name.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Rewrite the files in place, instead of printing the changes.
+    ///
+    /// Comments and the ordering of options are not preserved in the rewritten files.
+    #[arg(short, long)]
+    write: bool,
+}
+
+/// A change to the options of a component type.
+#[derive(Clone, Copy, Debug)]
+enum Change {
+    /// The option was renamed. Nested options are separated by `.`.
+    Rename {
+        from: &'static str,
+        to: &'static str,
+    },
+
+    /// A value of the option was renamed.
+    RenameValue {
+        option: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+/// A deprecated option of a component type and its replacement.
+#[derive(Clone, Copy, Debug)]
+struct Migration {
+    /// The table of the component: `sources`, `transforms`, or `sinks`.
+    table: &'static str,
+    component_type: &'static str,
+    change: Change,
+}
+
+const fn rename(
+    table: &'static str,
+    component_type: &'static str,
+    from: &'static str,
+    to: &'static str,
+) -> Migration {
+    Migration {
+        table,
+        component_type,
+        change: Change::Rename { from, to },
+    }
+}
+
+const fn rename_value(
+    table: &'static str,
+    component_type: &'static str,
+    option: &'static str,
+    from: &'static str,
+    to: &'static str,
+) -> Migration {
+    Migration {
+        table,
+        component_type,
+        change: Change::RenameValue { option, from, to },
+    }
+}
+
+/// The deprecated options still accepted as aliases of their replacements.
+///
+/// Options are renamed in the order of this list, so that a migration can build on an earlier one.
+const MIGRATIONS: &[Migration] = &[
+    rename("sources", "demo_logs", "batch_interval", "interval"),
+    rename_value("sources", "demo_logs", "format", "rfc5424", "syslog"),
+    rename_value("sources", "demo_logs", "format", "rfc3164", "bsd_syslog"),
+    rename("sources", "file", "ignore_older", "ignore_older_secs"),
+    rename(
+        "sources",
+        "file",
+        "glob_minimum_cooldown",
+        "glob_minimum_cooldown_ms",
+    ),
+    rename("sources", "file", "fingerprinting", "fingerprint"),
+    rename(
+        "sources",
+        "file",
+        "fingerprint.fingerprint_bytes",
+        "fingerprint.bytes",
+    ),
+    rename("sources", "file", "remove_after", "remove_after_secs"),
+    rename(
+        "sources",
+        "kubernetes_logs",
+        "annotation_fields",
+        "pod_annotation_fields",
+    ),
+    rename("sources", "fluent", "max_connections", "connection_limit"),
+    rename("sources", "logstash", "max_connections", "connection_limit"),
+    rename("sources", "nats", "name", "connection_name"),
+    rename("sources", "prometheus_scrape", "hosts", "endpoints"),
+    rename("sources", "socket", "max_connections", "connection_limit"),
+    rename_value("sources", "socket", "mode", "unix", "unix_stream"),
+    rename("sources", "syslog", "max_connections", "connection_limit"),
+    rename("transforms", "aws_ec2_metadata", "host", "endpoint"),
+    rename(
+        "sinks",
+        "aws_cloudwatch_metrics",
+        "namespace",
+        "default_namespace",
+    ),
+    rename("sinks", "clickhouse", "host", "endpoint"),
+    rename("sinks", "elasticsearch", "normal", "bulk"),
+    rename_value("sinks", "elasticsearch", "mode", "normal", "bulk"),
+    rename("sinks", "humio_logs", "host", "endpoint"),
+    rename("sinks", "humio_metrics", "host", "endpoint"),
+    rename(
+        "sinks",
+        "influxdb_metrics",
+        "namespace",
+        "default_namespace",
+    ),
+    rename("sinks", "kafka", "headers_field", "headers_key"),
+    rename("sinks", "mezmo", "host", "endpoint"),
+    rename("sinks", "nats", "name", "connection_name"),
+    rename(
+        "sinks",
+        "prometheus_exporter",
+        "namespace",
+        "default_namespace",
+    ),
+    rename("sinks", "pulsar", "address", "endpoint"),
+    rename("sinks", "redis", "list", "list_option"),
+    rename("sinks", "redis", "url", "endpoint"),
+    rename("sinks", "sematext_logs", "host", "endpoint"),
+    rename("sinks", "splunk_hec_logs", "token", "default_token"),
+    rename("sinks", "splunk_hec_metrics", "token", "default_token"),
+    rename("sinks", "statsd", "namespace", "default_namespace"),
+];
+
+/// Removes the option at the dotted path, creating nothing on the way.
+fn take(options: &mut Map<String, Value>, path: &str) -> Option<Value> {
+    match path.split_once('.') {
+        None => options.remove(path),
+        Some((head, rest)) => take(options.get_mut(head)?.as_object_mut()?, rest),
+    }
+}
+
+fn get<'a>(options: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    match path.split_once('.') {
+        None => options.get(path),
+        Some((head, rest)) => get(options.get(head)?.as_object()?, rest),
+    }
+}
+
+fn get_mut<'a>(options: &'a mut Map<String, Value>, path: &str) -> Option<&'a mut Value> {
+    match path.split_once('.') {
+        None => options.get_mut(path),
+        Some((head, rest)) => get_mut(options.get_mut(head)?.as_object_mut()?, rest),
+    }
+}
+
+/// Sets the option at the dotted path, creating the intermediate tables.
+fn set(options: &mut Map<String, Value>, path: &str, value: Value) -> bool {
+    match path.split_once('.') {
+        None => {
+            options.insert(path.to_owned(), value);
+            true
+        }
+        Some((head, rest)) => match options
+            .entry(head)
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(table) => set(table, rest, value),
+            _ => false,
+        },
+    }
+}
+
+impl Migration {
+    /// Applies the migration to the options of a component, returning a description of the
+    /// change if there was one.
+    fn apply(&self, id: &str, options: &mut Map<String, Value>) -> Option<String> {
+        if options.get("type").and_then(Value::as_str) != Some(self.component_type) {
+            return None;
+        }
+        match self.change {
+            Change::Rename { from, to } => {
+                // Both options were set: the replacement wins, as it does when loading.
+                if get(options, from).is_none() || get(options, to).is_some() {
+                    return None;
+                }
+                let value = take(options, from)?;
+                set(options, to, value)
+                    .then(|| format!("{}.{}: renamed `{}` to `{}`.", self.table, id, from, to))
+            }
+            Change::RenameValue { option, from, to } => {
+                let value = get_mut(options, option)?;
+                (value.as_str() == Some(from)).then(|| {
+                    *value = Value::from(to);
+                    format!(
+                        "{}.{}: renamed `{}` value `{}` to `{}`.",
+                        self.table, id, option, from, to
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Migrates the deprecated options of every component of the configuration, returning a
+/// description of each change.
+fn migrate(config: &mut Value) -> Vec<String> {
+    let mut changes = Vec::new();
+    let Some(config) = config.as_object_mut() else {
+        return changes;
+    };
+    for migration in MIGRATIONS {
+        let Some(components) = config
+            .get_mut(migration.table)
+            .and_then(Value::as_object_mut)
+        else {
+            continue;
+        };
+        for (id, options) in components.iter_mut() {
+            if let Some(options) = options.as_object_mut() {
+                changes.extend(migration.apply(id, options));
+            }
+        }
+    }
+    changes
+}
+
+fn serialize(config: &Value, format: Format) -> Result<String, String> {
+    match format {
+        Format::Toml => toml::to_string(config).map_err(|error| error.to_string()),
+        Format::Yaml => serde_yaml::to_string(config).map_err(|error| error.to_string()),
+        Format::Json => serde_json::to_string_pretty(config)
+            .map(|json| json + "\n")
+            .map_err(|error| error.to_string()),
+    }
+}
+
+/// A line diff of the two texts, with removed lines prefixed with `-` and added lines with `+`.
+fn diff(before: &str, after: &str) -> String {
+    let before = before.lines().collect::<Vec<_>>();
+    let after = after.lines().collect::<Vec<_>>();
+
+    // The length of the longest common subsequence of the lines from each position.
+    let mut lengths = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lengths[i][j] = if before[i] == after[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            output.push_str(&format!("  {}\n", before[i]));
+            i += 1;
+            j += 1;
+        } else if i < before.len() && (j == after.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            output.push_str(&format!("- {}\n", before[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("+ {}\n", after[j]));
+            j += 1;
+        }
+    }
+    output
+}
+
+/// Migrates a configuration file, returning the description of each change and the migrated
+/// configuration, in its format and as it was before.
+fn migrate_file(path: &PathBuf) -> Result<(Vec<String>, String, String), String> {
+    let format = Format::from_path(path)
+        .map_err(|_| "The format of the file can't be detected from its name.".to_owned())?;
+    let content = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let mut config: Value =
+        format::deserialize(&content, format).map_err(|errors| errors.join(", "))?;
+
+    // The configuration before the migration is serialized again, so that the diff only shows the
+    // migrated options and not the formatting of the file.
+    let before = serialize(&config, format)?;
+    let changes = migrate(&mut config);
+    let after = serialize(&config, format)?;
+    Ok((changes, before, after))
+}
+
+/// Function used by the `vector config migrate` subcommand, rewriting deprecated options to their
+/// replacements.
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    for path in &opts.paths {
+        let (changes, before, after) = match migrate_file(path) {
+            Ok(migrated) => migrated,
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!(
+                        "{}",
+                        format!("Failed to migrate {:?}: {}", path, error).red()
+                    );
+                }
+                return exitcode::DATAERR;
+            }
+        };
+
+        #[allow(clippy::print_stdout)]
+        {
+            if changes.is_empty() {
+                println!("{:?}: nothing to migrate.", path);
+                continue;
+            }
+            println!("{:?}:", path);
+            for change in &changes {
+                println!("  {}", change);
+            }
+        }
+
+        if opts.write {
+            if let Err(error) = fs::write(path, after) {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("{}", format!("Failed to write {:?}: {}", path, error).red());
+                }
+                return exitcode::CANTCREAT;
+            }
+        } else {
+            #[allow(clippy::print_stdout)]
+            {
+                println!();
+                for line in diff(&before, &after).lines() {
+                    match line.as_bytes().first() {
+                        Some(b'+') => println!("{}", line.green()),
+                        Some(b'-') => println!("{}", line.red()),
+                        _ => println!("{}", line),
+                    }
+                }
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn renames_deprecated_options() {
+        let mut config = json!({
+            "sources": {
+                "logs": {
+                    "type": "file",
+                    "include": ["/var/log/*.log"],
+                    "ignore_older": 600,
+                    "fingerprinting": {"strategy": "checksum", "fingerprint_bytes": 256},
+                },
+                "demo": {"type": "demo_logs", "format": "rfc5424", "batch_interval": 1.0},
+            },
+            "sinks": {
+                "out": {"type": "clickhouse", "inputs": ["logs"], "host": "http://localhost:8123"},
+            },
+        });
+
+        let changes = migrate(&mut config);
+
+        assert_eq!(
+            config,
+            json!({
+                "sources": {
+                    "logs": {
+                        "type": "file",
+                        "include": ["/var/log/*.log"],
+                        "ignore_older_secs": 600,
+                        "fingerprint": {"strategy": "checksum", "bytes": 256},
+                    },
+                    "demo": {"type": "demo_logs", "format": "syslog", "interval": 1.0},
+                },
+                "sinks": {
+                    "out": {"type": "clickhouse", "inputs": ["logs"], "endpoint": "http://localhost:8123"},
+                },
+            })
+        );
+        assert_eq!(changes.len(), 6);
+        assert!(changes.contains(&"sinks.out: renamed `host` to `endpoint`.".to_owned()));
+    }
+
+    #[test]
+    fn keeps_replacement_when_both_are_set() {
+        let mut config = json!({
+            "sinks": {
+                "out": {"type": "pulsar", "address": "pulsar://old:6650", "endpoint": "pulsar://new:6650"},
+            },
+        });
+        let expected = config.clone();
+
+        assert!(migrate(&mut config).is_empty());
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn diffs_lines() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nd\nc\n"),
+            "  a\n- b\n+ d\n  c\n".to_owned()
+        );
+    }
+}
@@ -18,6 +18,7 @@ pub use vector_core::config::{
 
 use crate::{conditions, event::Metric, secrets::SecretBackends, serde::OneOrMany};
 
+pub mod accounting;
 pub mod api;
 mod builder;
 mod cmd;
@@ -26,11 +27,14 @@ mod diff;
 mod enrichment_table;
 #[cfg(feature = "enterprise")]
 pub mod enterprise;
+pub mod fault_injection;
 pub mod format;
 mod graph;
 mod id;
 mod loading;
+mod migrate;
 pub mod provider;
+pub mod scaling;
 pub mod schema;
 mod secret;
 mod sink;
@@ -41,6 +45,7 @@ mod validation;
 mod vars;
 pub mod watcher;
 
+pub(crate) use builder::to_sorted_json_string;
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
 pub use diff::ConfigDiff;
@@ -54,8 +59,13 @@ pub use loading::{
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
-pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
+pub use sink::{
+    BoxedSink, BufferMigrationConfig, SinkConfig, SinkContext, SinkHealthcheckOptions,
+    SinkOrdering, SinkOuter,
+};
+pub use source::{
+    BoxedSource, DedupeIdConfig, SourceConfig, SourceContext, SourceOuter, SourceWalConfig,
+};
 pub use transform::{
     get_transform_output_ids, BoxedTransform, TransformConfig, TransformContext, TransformOuter,
 };
@@ -100,6 +110,9 @@ pub struct Config {
     pub enterprise: Option<enterprise::Options>,
     pub global: GlobalOptions,
     pub healthchecks: HealthcheckOptions,
+    pub fault_injection: fault_injection::Options,
+    pub accounting: accounting::Options,
+    pub scaling: scaling::Options,
     sources: IndexMap<ComponentKey, SourceOuter>,
     sinks: IndexMap<ComponentKey, SinkOuter<OutputId>>,
     transforms: IndexMap<ComponentKey, TransformOuter<OutputId>>,
@@ -635,6 +648,199 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn bad_fault_injection() {
+        let err = load(
+            r#"
+            [fault_injection.components.out]
+            error_rate = 1.5
+
+            [fault_injection.components.missing]
+            stall = true
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Faults are configured, but fault injection is not enabled. Set `fault_injection.enabled` to `true`.",
+                "Component \"out\" has an `error_rate` of 1.5, which must be between 0.0 and 1.0",
+                "Faults are configured for non-existent component \"missing\"",
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_accounting() {
+        let err = load(
+            r#"
+            [accounting.dimensions]
+            tenant = ""
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec!["Accounting dimension \"tenant\" has an empty field"],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_scaling() {
+        let err = load(
+            r#"
+            [scaling.webhook]
+            uri = "http://[::1"
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.len(), 2);
+        assert_eq!(
+            err[0],
+            "A scaling webhook is configured, but scaling signals are not enabled. Set `scaling.enabled` to `true`."
+        );
+        assert!(err[1].starts_with("Invalid scaling webhook URI \"http://[::1\""));
+    }
+
+    #[test]
+    #[cfg(feature = "sinks-http")]
+    fn fips_rejects_non_approved_ciphersuites() {
+        let builder: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [sources.in]
+                type = "test_basic"
+
+                [sinks.out]
+                type = "http"
+                inputs = ["in"]
+                uri = "https://localhost"
+                encoding.codec = "json"
+                tls.ciphersuites = ["TLS_AES_256_GCM_SHA384", "TLS_CHACHA20_POLY1305_SHA256"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+
+        assert_eq!(
+            super::validation::check_fips_ciphersuites(&builder),
+            Err(vec![
+                "Component \"out\" lists the TLS cipher suite \"TLS_CHACHA20_POLY1305_SHA256\", which is not allowed in FIPS mode".to_owned()
+            ]),
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "sinks-loki", feature = "sources-dead_letter"))]
+    async fn bad_dead_letter() {
+        let mut err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sources.rejected]
+            type = "dead_letter"
+
+            [sinks.out]
+            type = "loki"
+            inputs = ["in"]
+            endpoint = "http://localhost:3100"
+            encoding.codec = "json"
+            labels = {}
+            dead_letter = "in"
+
+            [sinks.other]
+            type = "loki"
+            inputs = ["in"]
+            endpoint = "http://localhost:3100"
+            encoding.codec = "json"
+            labels = {}
+            dead_letter = "missing"
+
+            [sinks.rejected_out]
+            type = "test_basic"
+            inputs = ["rejected"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+        err.sort();
+
+        assert_eq!(
+            vec![
+                "Sink \"other\" sends rejected events to \"missing\", which is not a `dead_letter` source",
+                "Sink \"out\" sends rejected events to \"in\", which is not a `dead_letter` source",
+            ],
+            err,
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_strict_ordering() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            ordering = "strict"
+            request_limits.concurrency = "1"
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 100
+            when_full = "overflow"
+
+            [[sinks.out.buffer]]
+            type = "memory"
+            max_events = 100
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                "Sink \"out\" has strict ordering, but its buffer overflows to another stage, which reorders events",
+                "Sink \"out\" has strict ordering, but its concurrency is computed by `request_limits.concurrency`",
+            ],
+            err,
+        );
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn conflicting_stdin_and_fd_resources() {
@@ -0,0 +1,73 @@
+use std::num::NonZeroU64;
+
+use vector_config::configurable_component;
+
+use crate::http::Auth;
+
+/// Scaling signals options.
+///
+/// Scaling signals summarize how saturated Vector is, so that an autoscaler such as the Kubernetes
+/// HorizontalPodAutoscaler or KEDA can scale aggregators before their buffers are full.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// Whether or not scaling signals are computed.
+    pub enabled: bool,
+
+    /// The interval between two computations of the signals, in seconds.
+    ///
+    /// Rates are computed over this interval.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub interval_secs: NonZeroU64,
+
+    #[configurable(derived)]
+    pub webhook: Option<WebhookOptions>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: NonZeroU64::new(15).expect("static"),
+            webhook: None,
+        }
+    }
+}
+
+impl Options {
+    pub fn merge(&mut self, other: Self, errors: &mut Vec<String>) {
+        let default = Self::default();
+        self.enabled |= other.enabled;
+        if other.interval_secs != default.interval_secs {
+            if self.interval_secs != default.interval_secs
+                && self.interval_secs != other.interval_secs
+            {
+                errors.push("conflicting values for 'scaling.interval_secs' found".to_owned());
+            }
+            self.interval_secs = other.interval_secs;
+        }
+        match (&self.webhook, other.webhook) {
+            (Some(webhook), Some(other)) if *webhook != other => {
+                errors.push("conflicting values for 'scaling.webhook' found".to_owned());
+            }
+            (None, other) => self.webhook = other,
+            _ => {}
+        }
+    }
+}
+
+/// Scaling webhook options.
+///
+/// The signals are sent as a JSON object in a `POST` request each time they are computed.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookOptions {
+    /// The URI the signals are sent to.
+    #[configurable(metadata(docs::examples = "http://keda-push-scaler.keda:8080/signals"))]
+    pub uri: String,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+}
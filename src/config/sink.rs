@@ -15,7 +15,14 @@ use vector_core::{
 };
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{util::UriSerde, Healthcheck};
+use crate::{
+    http::HttpPoolConfig,
+    request_capture::SinkDebugConfig,
+    sinks::{
+        util::{service::RequestLimitsConfig, UriSerde},
+        Healthcheck,
+    },
+};
 
 pub type BoxedSink = Box<dyn SinkConfig>;
 
@@ -79,6 +86,41 @@ where
     )]
     proxy: ProxyConfig,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub connection_pool: HttpPoolConfig,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub request_limits: RequestLimitsConfig,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub ordering: SinkOrdering,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub debug: SinkDebugConfig,
+
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub buffer_migration: BufferMigrationConfig,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: BoxedSink,
@@ -100,6 +142,11 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            connection_pool: Default::default(),
+            request_limits: Default::default(),
+            ordering: Default::default(),
+            debug: Default::default(),
+            buffer_migration: Default::default(),
         }
     }
 
@@ -111,6 +158,18 @@ where
                 BufferType::DiskV2 { .. } => resources.push(Resource::DiskBuffer(id.to_string())),
             }
         }
+        // The disk buffers left behind are taken over, so no running sink may still use them.
+        if self.buffer_migration.enabled {
+            if !self.buffer.has_disk_stage() {
+                resources.push(Resource::DiskBuffer(id.to_string()));
+            }
+            resources.extend(
+                self.buffer_migration
+                    .previous_ids
+                    .iter()
+                    .map(|previous_id| Resource::DiskBuffer(previous_id.to_string())),
+            );
+        }
         resources
     }
 
@@ -156,6 +215,11 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            connection_pool: self.connection_pool,
+            request_limits: self.request_limits,
+            ordering: self.ordering,
+            debug: self.debug,
+            buffer_migration: self.buffer_migration,
         }
     }
 }
@@ -200,6 +264,48 @@ impl From<UriSerde> for SinkHealthcheckOptions {
     }
 }
 
+/// Migration of the disk buffers left behind by a sink.
+///
+/// Disk buffers are stored in a directory named after their sink, so the events they hold are
+/// left behind on disk when the sink is renamed, or no longer uses a disk buffer.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BufferMigrationConfig {
+    /// Whether the disk buffers left behind by the sink are taken over when it is built.
+    ///
+    /// When the sink uses a disk buffer and has none yet, the disk buffer of the first of its
+    /// previous IDs that has one is renamed to it. Any other disk buffer left behind, including its
+    /// own when it no longer uses a disk buffer, is drained into the sink ahead of its buffer, and
+    /// deleted once all of its events are acknowledged.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The IDs the sink had before being renamed.
+    ///
+    /// These can't be the IDs of sinks still in the configuration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[configurable(metadata(docs::examples = "old_sink_id"))]
+    pub previous_ids: Vec<ComponentKey>,
+}
+
+/// The ordering guarantee of the events sent by a sink.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkOrdering {
+    /// Requests are sent concurrently, so events can reach the destination out of order.
+    #[default]
+    Unordered,
+
+    /// Requests are sent one at a time, and a request is retried before the next one is sent, so
+    /// that the events of each partition reach the destination in the order they were received.
+    ///
+    /// This disables `request.concurrency` and its adjustments, and can't be combined with buffers
+    /// overflowing to another stage.
+    Strict,
+}
+
 /// Generalized interface for describing and building sink components.
 #[async_trait]
 #[typetag::serde(tag = "type")]
@@ -240,6 +346,7 @@ pub struct SinkContext {
     pub healthcheck: SinkHealthcheckOptions,
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
+    pub connection_pool: HttpPoolConfig,
     pub schema: schema::Options,
 }
 
@@ -250,6 +357,7 @@ impl SinkContext {
             healthcheck: SinkHealthcheckOptions::default(),
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
+            connection_pool: HttpPoolConfig::default(),
             schema: schema::Options::default(),
         }
     }
@@ -261,4 +369,9 @@ impl SinkContext {
     pub const fn proxy(&self) -> &ProxyConfig {
         &self.proxy
     }
+
+    /// Gets the connection pool settings of the HTTP clients of the sink.
+    pub const fn connection_pool(&self) -> &HttpPoolConfig {
+        &self.connection_pool
+    }
 }
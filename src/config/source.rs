@@ -1,8 +1,11 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::num::NonZeroU64;
 
 use async_trait::async_trait;
 use dyn_clone::DynClone;
+use lookup::lookup_v2::ConfigTargetPath;
+use vector_common::TimeZone;
 use vector_config::{
     configurable_component, Configurable, GenerateError, Metadata, NamedComponent,
 };
@@ -57,6 +60,44 @@ pub struct SourceOuter {
     )]
     pub proxy: ProxyConfig,
 
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub wal: Option<SourceWalConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedupe_id: Option<DedupeIdConfig>,
+
+    /// The name of the timezone to apply to timestamps emitted by this source that do not contain
+    /// an explicit time zone.
+    ///
+    /// Timestamps that the source or its decoder leave as strings are parsed as they leave the
+    /// source. Downstream `remap` transforms also use it for the VRL timestamp conversions of the
+    /// events of this source, unless they set their own `timezone`.
+    ///
+    /// This overrides the [global `timezone`][global_timezone] option. The time zone name may be
+    /// any name in the [TZ database][tz_database], or `local` to indicate system local time.
+    ///
+    /// [global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
+    /// [tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced))]
+    pub timezone: Option<TimeZone>,
+
+    /// The format used to parse timestamps emitted by this source that are strings.
+    ///
+    /// The format uses the [`strptime` specifiers][chrono_strptime]. If unset, while `timezone` is
+    /// set, a set of common timestamp formats is tried instead.
+    ///
+    /// [chrono_strptime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "%d/%b/%Y:%H:%M:%S"))]
+    pub timestamp_format: Option<String>,
+
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
 
@@ -69,12 +110,85 @@ impl SourceOuter {
     pub(crate) fn new<I: Into<BoxedSource>>(inner: I) -> Self {
         Self {
             proxy: Default::default(),
+            wal: None,
+            dedupe_id: None,
+            timezone: None,
+            timestamp_format: None,
             sink_acknowledgements: false,
             inner: inner.into(),
         }
     }
 }
 
+/// Source write-ahead log configuration.
+///
+/// When enabled, events are durably written to disk before being sent into the topology, and
+/// removed once they have been delivered downstream, or rejected. Events that errored are sent
+/// again, and events still pending when Vector stopped are replayed on startup, protecting against
+/// data loss from in-memory buffers for sources that cannot replay data themselves, such as UDP
+/// syslog or HTTP webhooks.
+///
+/// Replayed events are delivered at least once, and lose any acknowledgement back to the client
+/// that originally sent them.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SourceWalConfig {
+    /// The maximum size of the write-ahead log on disk.
+    ///
+    /// Once reached, the source is paused until pending events are delivered downstream.
+    #[serde(default = "default_wal_max_size")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_size: NonZeroU64,
+
+    /// The maximum number of times events that errored downstream are sent again.
+    ///
+    /// Events that still error afterwards are dropped, like rejected events are.
+    #[serde(default = "default_wal_max_retries")]
+    pub max_retries: u32,
+
+    /// The directory used to persist the write-ahead log.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    pub data_dir: Option<std::path::PathBuf>,
+}
+
+const fn default_wal_max_retries() -> u32 {
+    3
+}
+
+fn default_wal_max_size() -> NonZeroU64 {
+    NonZeroU64::new(256 * 1024 * 1024).expect("not zero")
+}
+
+/// Dedupe ID configuration.
+///
+/// When enabled, each event leaving the source is stamped with a deterministic ID, stored in its
+/// metadata as `%vector.dedupe_id`. The ID is a hash of the source ID and of the fields identifying
+/// the event, so an event read again after a retry or a restart gets the same ID, while distinct
+/// events with the same content don't.
+///
+/// The `http` sink can send the IDs of a request as an idempotency key, and other sinks can
+/// propagate the IDs for the destination or its consumers to discard duplicates.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DedupeIdConfig {
+    /// The fields of log and trace events hashed into the ID.
+    ///
+    /// Together, they must identify an event, such as the file and the offset of a line, or the
+    /// partition and the offset of a Kafka record. Log and trace events are only stamped if fields
+    /// are set. Metric events are stamped from their series, value, and timestamp, if they have a
+    /// timestamp.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "message", docs::examples = "offset"))]
+    #[configurable(metadata(docs::examples = "%kafka.partition"))]
+    pub fields: Vec<ConfigTargetPath>,
+}
+
 /// Generalized interface for describing and building source components.
 #[async_trait]
 #[typetag::serde(tag = "type")]
@@ -4,6 +4,7 @@ use std::collections::{HashMap, HashSet};
 use async_trait::async_trait;
 use dyn_clone::DynClone;
 use serde::Serialize;
+use vector_common::TimeZone;
 use vector_config::{
     configurable_component,
     schema::{SchemaGenerator, SchemaObject},
@@ -121,6 +122,12 @@ pub struct TransformContext {
     pub merged_schema_definition: schema::Definition,
 
     pub schema: SchemaOptions,
+
+    /// The timezones of the sources setting one, by source ID.
+    ///
+    /// Used by transforms that parse timestamps when they don't have a timezone configured
+    /// themselves, to parse those of each event in the timezone of its source.
+    pub source_timezones: HashMap<ComponentKey, TimeZone>,
 }
 
 impl Default for TransformContext {
@@ -132,6 +139,7 @@ impl Default for TransformContext {
             schema_definitions: HashMap::from([(None, HashMap::new())]),
             merged_schema_definition: schema::Definition::any(),
             schema: SchemaOptions::default(),
+            source_timezones: HashMap::new(),
         }
     }
 }
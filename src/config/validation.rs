@@ -3,11 +3,12 @@ use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
+use vector_buffers::{BufferType, WhenFull};
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use super::{
-    builder::ConfigBuilder, transform::get_transform_output_ids, ComponentKey, Config, OutputId,
-    Resource,
+    builder::ConfigBuilder, transform::get_transform_output_ids, ComponentKey, Config, DataType,
+    OutputId, Resource, SinkOrdering,
 };
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
@@ -192,6 +193,235 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that faults are only injected into existing components, when fault injection is enabled.
+pub fn check_fault_injection(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let options = &config.fault_injection;
+    let mut errors = Vec::new();
+
+    if !options.enabled && !options.components.is_empty() {
+        errors.push(
+            "Faults are configured, but fault injection is not enabled. Set `fault_injection.enabled` to `true`.".to_owned(),
+        );
+    }
+
+    for (key, faults) in options.components.iter() {
+        if !config.sources.contains_key(key)
+            && !config.transforms.contains_key(key)
+            && !config.sinks.contains_key(key)
+        {
+            errors.push(format!(
+                "Faults are configured for non-existent component \"{}\"",
+                key
+            ));
+        }
+        if !(0.0..=1.0).contains(&faults.error_rate) {
+            errors.push(format!(
+                "Component \"{}\" has an `error_rate` of {}, which must be between 0.0 and 1.0",
+                key, faults.error_rate
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+pub fn check_ordering(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, sink) in config
+        .sinks
+        .iter()
+        .filter(|(_, sink)| sink.ordering == SinkOrdering::Strict)
+    {
+        let overflows = sink.buffer.stages().iter().any(|stage| match stage {
+            BufferType::Memory { when_full, .. } | BufferType::DiskV2 { when_full, .. } => {
+                *when_full == WhenFull::Overflow
+            }
+        });
+        if overflows {
+            errors.push(format!(
+                "Sink \"{}\" has strict ordering, but its buffer overflows to another stage, which reorders events",
+                key
+            ));
+        }
+        if sink.request_limits.concurrency.is_some() {
+            errors.push(format!(
+                "Sink \"{}\" has strict ordering, but its concurrency is computed by `request_limits.concurrency`",
+                key
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the accounting dimensions are valid, and that accounting is enabled when its counts
+/// are emitted.
+pub fn check_accounting(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let options = &config.accounting;
+    let mut errors = Vec::new();
+
+    for (name, field) in &options.dimensions {
+        if field.is_empty() {
+            errors.push(format!(
+                "Accounting dimension \"{}\" has an empty field",
+                name
+            ));
+        }
+    }
+
+    if !options.enabled {
+        for (key, _) in config
+            .sources
+            .iter()
+            .filter(|(_, source)| source.inner.get_component_name() == "internal_accounting")
+        {
+            errors.push(format!(
+                "Source \"{}\" emits accounting summaries, but accounting is not enabled. Set `accounting.enabled` to `true`.",
+                key
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the scaling webhook is valid, and that scaling signals are enabled when it's set.
+pub fn check_scaling(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let options = &config.scaling;
+    let mut errors = Vec::new();
+
+    if let Some(webhook) = &options.webhook {
+        if !options.enabled {
+            errors.push(
+                "A scaling webhook is configured, but scaling signals are not enabled. Set `scaling.enabled` to `true`.".to_owned(),
+            );
+        }
+        if let Err(error) = webhook.uri.parse::<http::Uri>() {
+            errors.push(format!(
+                "Invalid scaling webhook URI \"{}\": {}",
+                webhook.uri, error
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the `dead_letter` option of sinks names a `dead_letter` source.
+pub fn check_dead_letters(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, sink) in config.sinks.iter() {
+        let Ok(value) = serde_json::to_value(&sink.inner) else {
+            continue;
+        };
+        let Some(id) = value.get("dead_letter").and_then(|id| id.as_str()) else {
+            continue;
+        };
+        let is_dead_letter_source = config
+            .sources
+            .get(&ComponentKey::from(id))
+            .map_or(false, |source| {
+                source.inner.get_component_name() == "dead_letter"
+            });
+        if !is_dead_letter_source {
+            errors.push(format!(
+                "Sink \"{}\" sends rejected events to \"{}\", which is not a `dead_letter` source",
+                key, id
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that components only list the TLS cipher suites allowed in FIPS mode.
+pub fn check_fips_ciphersuites(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let components = config
+        .sources
+        .iter()
+        .map(|(key, source)| (key, serde_json::to_value(&source.inner)))
+        .chain(
+            config
+                .transforms
+                .iter()
+                .map(|(key, transform)| (key, serde_json::to_value(&transform.inner))),
+        )
+        .chain(
+            config
+                .sinks
+                .iter()
+                .map(|(key, sink)| (key, serde_json::to_value(&sink.inner))),
+        );
+
+    let mut errors = Vec::new();
+    for (key, value) in components {
+        let Ok(value) = value else {
+            continue;
+        };
+        let mut ciphersuites = Vec::new();
+        tls_ciphersuites(&value, &mut ciphersuites);
+        for ciphersuite in ciphersuites {
+            if !vector_core::fips::APPROVED_TLS_CIPHERSUITES.contains(&ciphersuite) {
+                errors.push(format!(
+                    "Component \"{}\" lists the TLS cipher suite \"{}\", which is not allowed in FIPS mode",
+                    key, ciphersuite
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Collects the `ciphersuites` of the `tls` options found anywhere in the configuration of a
+/// component.
+fn tls_ciphersuites<'a>(value: &'a serde_json::Value, ciphersuites: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (name, value) in object {
+                if name == "tls" {
+                    if let Some(list) = value.get("ciphersuites").and_then(|list| list.as_array()) {
+                        ciphersuites.extend(list.iter().filter_map(|item| item.as_str()));
+                    }
+                }
+                tls_ciphersuites(value, ciphersuites);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                tls_ciphersuites(item, ciphersuites);
+            }
+        }
+        _ => {}
+    }
+}
+
 pub async fn check_buffer_preconditions(config: &Config) -> Result<(), Vec<String>> {
     // We need to assert that Vector's data directory is located on a mountpoint that has enough
     // capacity to allow all sinks with disk buffers configured to be able to use up to their
@@ -368,6 +598,24 @@ pub fn warnings(config: &Config) -> Vec<String> {
         }
     }
 
+    for (key, source) in &config.sources {
+        let without_fields = source
+            .dedupe_id
+            .as_ref()
+            .map_or(false, |dedupe_id| dedupe_id.fields.is_empty());
+        let emits_logs_or_traces = source
+            .inner
+            .outputs(config.schema.log_namespace())
+            .iter()
+            .any(|output| output.ty.intersects(DataType::Log | DataType::Trace));
+        if without_fields && emits_logs_or_traces {
+            warnings.push(format!(
+                "Source \"{}\" has a `dedupe_id` without `fields`, so its logs and traces are not stamped with a dedupe ID",
+                key
+            ));
+        }
+    }
+
     warnings
 }
 
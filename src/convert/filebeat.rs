@@ -0,0 +1,535 @@
+//! Conversion of Filebeat YAML configurations.
+
+use serde_yaml::{Mapping, Value as Yaml};
+use toml::Value;
+
+use super::{component_id, vrl_path, vrl_string, Component, Kind, Topology};
+
+/// Looks up a setting that may be written with dotted keys, nested mappings, or a mix of both,
+/// such as `output.elasticsearch.hosts`.
+fn lookup<'a>(value: &'a Yaml, path: &str) -> Option<&'a Yaml> {
+    let mapping = value.as_mapping()?;
+    if let Some(value) = mapping.get(path) {
+        return Some(value);
+    }
+    path.match_indices('.').find_map(|(index, _)| {
+        mapping
+            .get(&path[..index])
+            .and_then(|value| lookup(value, &path[index + 1..]))
+    })
+}
+
+fn string(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(value) => Some(value.clone()),
+        Yaml::Number(value) => Some(value.to_string()),
+        Yaml::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn strings(value: Option<&Yaml>) -> Vec<String> {
+    match value {
+        Some(Yaml::Sequence(values)) => values.iter().filter_map(string).collect(),
+        Some(value) => string(value).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns a VRL literal for a YAML value.
+fn vrl_literal(value: &Yaml) -> String {
+    match value {
+        Yaml::String(value) => vrl_string(value),
+        Yaml::Sequence(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(vrl_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Yaml::Mapping(mapping) => format!(
+            "{{ {} }}",
+            mapping
+                .iter()
+                .filter_map(|(key, value)| Some(format!(
+                    "{}: {}",
+                    vrl_string(&string(key)?),
+                    vrl_literal(value)
+                )))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Yaml::Null => "null".to_string(),
+        value => string(value).unwrap_or_else(|| "null".to_string()),
+    }
+}
+
+/// An input of the Filebeat configuration, whose settings are consumed as they are converted.
+struct Input {
+    settings: Mapping,
+}
+
+impl Input {
+    fn take(&mut self, key: &str) -> Option<Yaml> {
+        self.settings.remove(key)
+    }
+
+    fn take_string(&mut self, key: &str) -> Option<String> {
+        self.take(key).as_ref().and_then(string)
+    }
+
+    /// Removes a setting along with any dotted settings nested under it.
+    fn take_prefixed(&mut self, key: &str) {
+        let nested = format!("{}.", key);
+        let keys = self
+            .settings
+            .keys()
+            .filter(|k| string(k).map_or(false, |k| k == key || k.starts_with(&nested)))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.settings.remove(&key);
+        }
+    }
+
+    fn report_leftovers(self, component: &mut Component) {
+        for (key, _) in self.settings {
+            if let Some(key) = string(&key) {
+                component.todo(format!("input setting `{}` is not converted", key));
+            }
+        }
+    }
+}
+
+pub(super) fn convert(input: &str) -> Result<Topology, String> {
+    let config: Yaml = serde_yaml::from_str(input).map_err(|error| error.to_string())?;
+    let mut topology = Topology::default();
+    let mut heads = Vec::new();
+
+    let inputs = match lookup(&config, "filebeat.inputs") {
+        Some(Yaml::Sequence(inputs)) => inputs.clone(),
+        _ => Vec::new(),
+    };
+    if inputs.is_empty() {
+        topology.todo("no `filebeat.inputs` found");
+    }
+    if lookup(&config, "filebeat.modules").is_some()
+        || lookup(&config, "filebeat.config.modules").is_some()
+    {
+        topology.todo("Filebeat modules are not converted; configure their inputs explicitly");
+    }
+    if lookup(&config, "processors").is_some() {
+        topology.todo("global `processors` are not converted; use a `remap` transform");
+    }
+
+    for (index, settings) in inputs.into_iter().enumerate() {
+        let Yaml::Mapping(settings) = settings else {
+            continue;
+        };
+        let mut input = Input { settings };
+
+        let input_type = input
+            .take_string("type")
+            .unwrap_or_else(|| "log".to_string());
+        let id = input
+            .take_string("id")
+            .map(|id| component_id(&id))
+            .unwrap_or_else(|| component_id(&format!("{}_{}", input_type, index)));
+
+        if input.take("enabled").and_then(|enabled| enabled.as_bool()) == Some(false) {
+            topology.todo(format!("input `{}` is disabled and was skipped", id));
+            continue;
+        }
+
+        let Some(mut source) = convert_input(&input_type, &id, &mut input) else {
+            topology.todo(format!(
+                "input type `{}` has no equivalent Vector source",
+                input_type
+            ));
+            continue;
+        };
+
+        let enrich = enrichment(&id, &source.id, &mut input);
+        if input.take("processors").is_some() {
+            source.todo("input `processors` are not converted; use a `remap` transform");
+        }
+        input.report_leftovers(&mut source);
+        topology.components.push(source);
+
+        match enrich {
+            Some(transform) => {
+                heads.push(transform.id.clone());
+                topology.components.push(transform);
+            }
+            None => heads.push(id),
+        }
+    }
+
+    let outputs = [
+        "elasticsearch",
+        "logstash",
+        "kafka",
+        "redis",
+        "file",
+        "console",
+    ];
+    let mut converted = false;
+    for output in outputs {
+        let Some(Yaml::Mapping(settings)) = lookup(&config, &format!("output.{}", output)) else {
+            continue;
+        };
+        if settings.get("enabled").and_then(Yaml::as_bool) == Some(false) {
+            continue;
+        }
+
+        match convert_output(output, settings) {
+            Some(mut sink) => {
+                sink.set_inputs(heads.clone());
+                topology.components.push(sink);
+                converted = true;
+            }
+            None => topology.todo(format!("output `{}` has no equivalent Vector sink", output)),
+        }
+    }
+    if !converted {
+        topology.todo("no supported output found; add a sink");
+    }
+
+    Ok(topology)
+}
+
+fn convert_input(input_type: &str, id: &str, input: &mut Input) -> Option<Component> {
+    let source = match input_type {
+        "log" | "filestream" | "container" => {
+            let mut source = Component::new(Kind::Source, id, "file");
+            source.set("include", strings(input.take("paths").as_ref()));
+            if input.take("exclude_files").is_some() {
+                source.todo(
+                    "`exclude_files` holds regular expressions; rewrite them as globs in `exclude`",
+                );
+            }
+            if input_type == "container" {
+                source.todo("container log lines are not parsed; consider the `kubernetes_logs` or `docker_logs` source");
+            }
+            source
+        }
+        "journald" => Component::new(Kind::Source, id, "journald"),
+        "syslog" => {
+            let mut source = Component::new(Kind::Source, id, "syslog");
+            let settings = Yaml::Mapping(input.settings.clone());
+            let (mode, host) = ["udp", "tcp"]
+                .into_iter()
+                .find_map(|mode| {
+                    let host =
+                        lookup(&settings, &format!("protocol.{}.host", mode)).and_then(string)?;
+                    Some((mode, host))
+                })
+                .unwrap_or(("udp", "0.0.0.0:514".to_string()));
+            input.take_prefixed("protocol");
+            source.set("mode", mode);
+            source.set("address", host);
+            source
+        }
+        "tcp" | "udp" => {
+            let mut source = Component::new(Kind::Source, id, "socket");
+            source.set("mode", input_type);
+            source.set(
+                "address",
+                input
+                    .take_string("host")
+                    .unwrap_or_else(|| "0.0.0.0:9000".to_string()),
+            );
+            source
+        }
+        "stdin" => Component::new(Kind::Source, id, "stdin"),
+        "http_endpoint" => {
+            let mut source = Component::new(Kind::Source, id, "http_server");
+            let host = input
+                .take_string("listen_address")
+                .unwrap_or_else(|| "127.0.0.1".to_string());
+            let port = input
+                .take_string("listen_port")
+                .unwrap_or_else(|| "8000".to_string());
+            source.set("address", format!("{}:{}", host, port));
+            source.set("decoding.codec", "json");
+            source
+        }
+        "kafka" => {
+            let mut source = Component::new(Kind::Source, id, "kafka");
+            source.set(
+                "bootstrap_servers",
+                strings(input.take("hosts").as_ref()).join(","),
+            );
+            source.set(
+                "topics",
+                strings(input.take("topics").as_ref())
+                    .into_iter()
+                    .map(Value::from)
+                    .collect::<Vec<_>>(),
+            );
+            source.set(
+                "group_id",
+                input
+                    .take_string("group_id")
+                    .unwrap_or_else(|| "filebeat".to_string()),
+            );
+            source
+        }
+        _ => return None,
+    };
+    Some(source)
+}
+
+/// Converts the `fields` and `tags` settings of an input into a `remap` transform.
+fn enrichment(id: &str, source: &str, input: &mut Input) -> Option<Component> {
+    let under_root = input
+        .take("fields_under_root")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let mut program = Vec::new();
+
+    if let Some(Yaml::Mapping(fields)) = input.take("fields") {
+        for (key, value) in fields {
+            let Some(key) = string(&key) else {
+                continue;
+            };
+            let path = if under_root {
+                vrl_path(&key)
+            } else {
+                format!(".fields{}", vrl_path(&key))
+            };
+            program.push(format!("{} = {}", path, vrl_literal(&value)));
+        }
+    }
+    if let Some(tags) = input.take("tags") {
+        program.push(format!(".tags = {}", vrl_literal(&tags)));
+    }
+
+    if program.is_empty() {
+        return None;
+    }
+    let mut transform = Component::new(Kind::Transform, format!("{}_fields", id), "remap");
+    transform.set("source", program.join("\n"));
+    transform.set_inputs(vec![source.to_string()]);
+    Some(transform)
+}
+
+fn hosts(settings: &Mapping, default_scheme: Option<&str>) -> Vec<String> {
+    let scheme = settings
+        .get("protocol")
+        .and_then(string)
+        .or_else(|| default_scheme.map(ToString::to_string));
+    strings(settings.get("hosts"))
+        .into_iter()
+        .map(|host| match &scheme {
+            Some(scheme) if !host.contains("://") => format!("{}://{}", scheme, host),
+            _ => host,
+        })
+        .collect()
+}
+
+fn convert_output(output: &str, settings: &Mapping) -> Option<Component> {
+    let mut sink = match output {
+        "elasticsearch" => {
+            let mut sink = Component::new(Kind::Sink, output, "elasticsearch");
+            sink.set(
+                "endpoints",
+                hosts(settings, Some("http"))
+                    .into_iter()
+                    .map(Value::from)
+                    .collect::<Vec<_>>(),
+            );
+            if let Some(user) = settings.get("username").and_then(string) {
+                sink.set("auth.strategy", "basic");
+                sink.set("auth.user", user);
+                sink.set(
+                    "auth.password",
+                    settings
+                        .get("password")
+                        .and_then(string)
+                        .unwrap_or_default(),
+                );
+            }
+            if settings.get("api_key").is_some() {
+                sink.todo(
+                    "API key authentication is not converted; set `request.headers.Authorization`",
+                );
+            }
+            if let Some(index) = settings.get("index").and_then(string) {
+                if index.contains("%{") {
+                    sink.todo(format!(
+                        "index `{}` uses Filebeat format strings; rewrite it as a Vector template",
+                        index
+                    ));
+                } else {
+                    sink.set("bulk.index", index);
+                }
+            }
+            if let Some(Yaml::Mapping(ssl)) = settings.get("ssl") {
+                if let Some(ca) = strings(ssl.get("certificate_authorities")).first() {
+                    sink.set("tls.ca_file", ca.as_str());
+                }
+                if ssl.get("verification_mode").and_then(string).as_deref() == Some("none") {
+                    sink.set("tls.verify_certificate", false);
+                }
+            }
+            sink
+        }
+        "kafka" => {
+            let mut sink = Component::new(Kind::Sink, output, "kafka");
+            sink.set("bootstrap_servers", hosts(settings, None).join(","));
+            let topic = settings.get("topic").and_then(string).unwrap_or_default();
+            if topic.contains("%{") {
+                sink.todo(format!(
+                    "topic `{}` uses Filebeat format strings; rewrite it as a Vector template",
+                    topic
+                ));
+            }
+            sink.set("topic", topic);
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "redis" => {
+            let mut sink = Component::new(Kind::Sink, output, "redis");
+            let host = hosts(settings, Some("redis"))
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| "redis://127.0.0.1:6379".to_string());
+            sink.set("endpoint", host);
+            sink.set(
+                "key",
+                settings
+                    .get("key")
+                    .and_then(string)
+                    .unwrap_or_else(|| "filebeat".to_string()),
+            );
+            let data_type = settings
+                .get("datatype")
+                .and_then(string)
+                .unwrap_or_else(|| "list".to_string());
+            sink.set(
+                "data_type",
+                if data_type == "channel" {
+                    "channel"
+                } else {
+                    "list"
+                },
+            );
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "file" => {
+            let mut sink = Component::new(Kind::Sink, output, "file");
+            let path = settings
+                .get("path")
+                .and_then(string)
+                .unwrap_or_else(|| "/tmp/filebeat".to_string());
+            let filename = settings
+                .get("filename")
+                .and_then(string)
+                .unwrap_or_else(|| "filebeat".to_string());
+            sink.set(
+                "path",
+                format!("{}/{}", path.trim_end_matches('/'), filename),
+            );
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "console" => {
+            let mut sink = Component::new(Kind::Sink, output, "console");
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        _ => return None,
+    };
+
+    if settings.get("codec").is_some() {
+        sink.todo("the output `codec` is not converted; events are encoded as JSON");
+    }
+    Some(sink)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+filebeat.inputs:
+  - type: filestream
+    id: app-logs
+    paths:
+      - /var/log/app/*.log
+    fields:
+      env: production
+    tags: ["app"]
+    parsers:
+      - ndjson: {}
+  - type: log
+    enabled: false
+    paths: ["/var/log/old.log"]
+  - type: syslog
+    protocol.tcp:
+      host: "0.0.0.0:9514"
+
+processors:
+  - add_host_metadata: ~
+
+output.elasticsearch:
+  hosts: ["es1:9200", "https://es2:9200"]
+  username: elastic
+  password: changeme
+  index: "app-%{+yyyy.MM.dd}"
+"#;
+
+    #[test]
+    fn looks_up_dotted_and_nested_keys() {
+        let config: Yaml = serde_yaml::from_str(
+            r#"
+output:
+  elasticsearch.hosts: ["a"]
+filebeat.config:
+  modules:
+    enabled: true
+"#,
+        )
+        .unwrap();
+
+        assert!(lookup(&config, "output.elasticsearch.hosts").is_some());
+        assert!(lookup(&config, "filebeat.config.modules.enabled").is_some());
+        assert!(lookup(&config, "output.kafka").is_none());
+    }
+
+    #[test]
+    fn converts_pipeline() {
+        let rendered = convert(CONFIG).unwrap().render();
+        let config: Value = toml::from_str(&rendered).unwrap();
+
+        let source = &config["sources"]["app-logs"];
+        assert_eq!(source["type"].as_str(), Some("file"));
+        assert_eq!(source["include"][0].as_str(), Some("/var/log/app/*.log"));
+        assert!(rendered.contains("# TODO: input setting `parsers` is not converted"));
+
+        let fields = &config["transforms"]["app-logs_fields"];
+        assert_eq!(
+            fields["source"].as_str(),
+            Some(".fields.env = \"production\"\n.tags = [\"app\"]")
+        );
+        assert_eq!(fields["inputs"][0].as_str(), Some("app-logs"));
+
+        let syslog = &config["sources"]["syslog_2"];
+        assert_eq!(syslog["mode"].as_str(), Some("tcp"));
+        assert_eq!(syslog["address"].as_str(), Some("0.0.0.0:9514"));
+
+        let sink = &config["sinks"]["elasticsearch"];
+        assert_eq!(sink["endpoints"][0].as_str(), Some("http://es1:9200"));
+        assert_eq!(sink["endpoints"][1].as_str(), Some("https://es2:9200"));
+        assert_eq!(sink["auth"]["user"].as_str(), Some("elastic"));
+        assert_eq!(sink["inputs"][0].as_str(), Some("app-logs_fields"));
+        assert_eq!(sink["inputs"][1].as_str(), Some("syslog_2"));
+        assert!(sink.get("bulk").is_none());
+
+        assert!(rendered.contains("# TODO: input `log_1` is disabled and was skipped"));
+        assert!(rendered.contains("# TODO: global `processors` are not converted"));
+    }
+}
@@ -0,0 +1,867 @@
+//! Conversion of Fluent Bit configurations in the classic, INI-like format.
+
+use std::collections::HashMap;
+
+use toml::{map::Map, Value};
+
+use super::{component_id, vrl_path, vrl_string, Component, Kind, Topology};
+
+/// A `[SECTION]` of the configuration, with its entries in order.
+#[derive(Debug)]
+struct Section {
+    name: String,
+    entries: Vec<(String, String)>,
+}
+
+impl Section {
+    /// Removes and returns the first value of a key. Keys are case-insensitive.
+    fn take(&mut self, key: &str) -> Option<String> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(k, _)| k.eq_ignore_ascii_case(key))?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Removes and returns all values of a key.
+    fn take_all(&mut self, key: &str) -> Vec<String> {
+        let mut values = Vec::new();
+        while let Some(value) = self.take(key) {
+            values.push(value);
+        }
+        values
+    }
+
+    fn take_bool(&mut self, key: &str) -> Option<bool> {
+        self.take(key).map(|value| {
+            matches!(
+                value.to_ascii_lowercase().as_str(),
+                "on" | "true" | "yes" | "1"
+            )
+        })
+    }
+
+    /// Adds a TODO to the component for every entry that was not converted.
+    fn report_leftovers(self, component: &mut Component) {
+        for (key, value) in self.entries {
+            component.todo(format!("option `{} {}` is not converted", key, value));
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<(Vec<Section>, Vec<String>), String> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut variables = HashMap::new();
+    let mut todos = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push(Section {
+                name: name.trim().to_ascii_uppercase(),
+                entries: Vec::new(),
+            });
+            continue;
+        }
+
+        if let Some(definition) = line.strip_prefix("@SET") {
+            if let Some((name, value)) = definition.trim().split_once('=') {
+                variables.insert(name.trim().to_string(), value.trim().to_string());
+            }
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("@INCLUDE") {
+            todos.push(format!(
+                "included file `{}` is not converted; convert it separately",
+                path.trim()
+            ));
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once(char::is_whitespace)
+            .map(|(key, value)| (key, value.trim()))
+            .unwrap_or((line, ""));
+        let section = sections
+            .last_mut()
+            .ok_or_else(|| format!("line {}: entry outside of a section", index + 1))?;
+        section
+            .entries
+            .push((key.to_string(), substitute(value, &variables)));
+    }
+
+    Ok((sections, todos))
+}
+
+/// Replaces `${NAME}` with the value set by `@SET NAME=...`. Other references are environment
+/// variables, which Vector interpolates the same way.
+fn substitute(value: &str, variables: &HashMap<String, String>) -> String {
+    let mut value = value.to_string();
+    for (name, replacement) in variables {
+        value = value.replace(&format!("${{{}}}", name), replacement);
+    }
+    value
+}
+
+/// Whether a Fluent Bit `Match` pattern, which may contain `*` wildcards, matches a tag.
+fn tag_matches(pattern: &str, tag: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == tag,
+        Some((prefix, rest)) => {
+            let Some(tag) = tag.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=tag.len())
+                .filter(|i| tag.is_char_boundary(*i))
+                .any(|i| tag_matches(rest, &tag[i..]))
+        }
+    }
+}
+
+/// A converted input, and the component its records currently flow out of.
+struct Stream {
+    tag: String,
+    head: String,
+}
+
+/// Returns the components that the records matched by a `Match` pattern currently flow out of.
+fn matched_inputs(
+    section: &mut Section,
+    streams: &[Stream],
+    component: &mut Component,
+) -> Vec<String> {
+    if let Some(pattern) = section.take("Match_Regex") {
+        component.todo(format!(
+            "`Match_Regex {}` is not converted; set `inputs` manually",
+            pattern
+        ));
+        return Vec::new();
+    }
+
+    let pattern = section.take("Match").unwrap_or_else(|| "*".to_string());
+    let mut inputs = Vec::new();
+    for stream in streams.iter().filter(|s| tag_matches(&pattern, &s.tag)) {
+        if !inputs.contains(&stream.head) {
+            inputs.push(stream.head.clone());
+        }
+    }
+    inputs
+}
+
+pub(super) fn convert(input: &str) -> Result<Topology, String> {
+    let (sections, todos) = parse(input)?;
+    let mut topology = Topology {
+        todos,
+        ..Default::default()
+    };
+    let mut streams = Vec::new();
+    let mut counts = HashMap::new();
+
+    let mut next_id = |section: &mut Section, plugin: &str| {
+        let count = counts.entry(plugin.to_string()).or_insert(0);
+        let id = section
+            .take("Alias")
+            .map(|alias| component_id(&alias))
+            .unwrap_or_else(|| component_id(&format!("{}_{}", plugin, count)));
+        let tag = format!("{}.{}", plugin, count);
+        *count += 1;
+        (id, tag)
+    };
+
+    for mut section in sections {
+        let name = section.name.clone();
+        match name.as_str() {
+            "SERVICE" => {
+                for (key, value) in section.entries {
+                    topology.todo(format!(
+                        "service option `{} {}` has no direct equivalent; see the global options",
+                        key, value
+                    ));
+                }
+            }
+            "PARSER" | "MULTILINE_PARSER" => {
+                let name = section.take("Name").unwrap_or_default();
+                topology.todo(format!(
+                    "parser `{}` is not converted; use the VRL `parse_*` functions in a `remap` transform",
+                    name
+                ));
+            }
+            "INPUT" => {
+                let plugin = section
+                    .take("Name")
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let (id, default_tag) = next_id(&mut section, &plugin);
+                let tag = section.take("Tag").unwrap_or(default_tag);
+
+                match convert_input(&plugin, &id, &mut section) {
+                    Some(mut source) => {
+                        section.report_leftovers(&mut source);
+                        streams.push(Stream {
+                            tag,
+                            head: source.id.clone(),
+                        });
+                        topology.components.push(source);
+                    }
+                    None => topology.todo(format!(
+                        "input plugin `{}` (tag `{}`) has no equivalent Vector source",
+                        plugin, tag
+                    )),
+                }
+            }
+            "FILTER" => {
+                let plugin = section
+                    .take("Name")
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let (id, _) = next_id(&mut section, &plugin);
+
+                match convert_filter(&plugin, &id, &mut section) {
+                    Some(mut transform) => {
+                        let pattern = section
+                            .entries
+                            .iter()
+                            .find(|(key, _)| key.eq_ignore_ascii_case("Match"))
+                            .map(|(_, pattern)| pattern.clone())
+                            .unwrap_or_else(|| "*".to_string());
+                        let inputs = matched_inputs(&mut section, &streams, &mut transform);
+                        section.report_leftovers(&mut transform);
+                        transform.set_inputs(inputs);
+
+                        for stream in streams.iter_mut().filter(|s| tag_matches(&pattern, &s.tag)) {
+                            stream.head = transform.id.clone();
+                        }
+                        topology.components.push(transform);
+                    }
+                    None => topology.todo(format!(
+                        "filter plugin `{}` is not converted; records pass through unmodified",
+                        plugin
+                    )),
+                }
+            }
+            "OUTPUT" => {
+                let plugin = section
+                    .take("Name")
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let (id, _) = next_id(&mut section, &plugin);
+
+                match convert_output(&plugin, &id, &mut section) {
+                    Some(mut sink) => {
+                        let inputs = matched_inputs(&mut section, &streams, &mut sink);
+                        section.report_leftovers(&mut sink);
+                        sink.set_inputs(inputs);
+                        topology.components.push(sink);
+                    }
+                    None => topology.todo(format!(
+                        "output plugin `{}` has no equivalent Vector sink",
+                        plugin
+                    )),
+                }
+            }
+            other => topology.todo(format!("section `[{}]` is not converted", other)),
+        }
+    }
+
+    Ok(topology)
+}
+
+fn address(section: &mut Section, default_port: u16) -> String {
+    let host = section
+        .take("Listen")
+        .unwrap_or_else(|| "0.0.0.0".to_string());
+    let port = section
+        .take("Port")
+        .unwrap_or_else(|| default_port.to_string());
+    format!("{}:{}", host, port)
+}
+
+fn split_list(value: &str) -> Vec<Value> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(Value::from)
+        .collect()
+}
+
+fn convert_input(plugin: &str, id: &str, section: &mut Section) -> Option<Component> {
+    let source = match plugin {
+        "tail" => {
+            let mut source = Component::new(Kind::Source, id, "file");
+            source.set(
+                "include",
+                split_list(&section.take("Path").unwrap_or_default()),
+            );
+            if let Some(exclude) = section.take("Exclude_Path") {
+                source.set("exclude", split_list(&exclude));
+            }
+            if section.take_bool("Read_from_Head") == Some(true) {
+                source.set("read_from", "beginning");
+            }
+            if section.take("DB").is_some() {
+                source.todo("checkpoints are kept in the global `data_dir` instead of `DB`");
+            }
+            source
+        }
+        "systemd" => {
+            let mut source = Component::new(Kind::Source, id, "journald");
+            let mut units = Vec::new();
+            let mut matches = Map::new();
+            for filter in section.take_all("Systemd_Filter") {
+                let Some((field, value)) = filter.split_once('=') else {
+                    continue;
+                };
+                if field == "_SYSTEMD_UNIT" {
+                    units.push(Value::from(value));
+                } else {
+                    match matches
+                        .entry(field)
+                        .or_insert_with(|| Value::Array(Vec::new()))
+                    {
+                        Value::Array(values) => values.push(value.into()),
+                        _ => unreachable!(),
+                    }
+                }
+            }
+            if !units.is_empty() {
+                source.set("include_units", units);
+            }
+            if !matches.is_empty() {
+                source.set("include_matches", Value::Table(matches));
+            }
+            if section.take_bool("Read_From_Tail") == Some(true) {
+                source.set("since_now", true);
+            }
+            source
+        }
+        "forward" => {
+            let mut source = Component::new(Kind::Source, id, "fluent");
+            source.set("address", address(section, 24224));
+            source
+        }
+        "syslog" => {
+            let mut source = Component::new(Kind::Source, id, "syslog");
+            let mode = section
+                .take("Mode")
+                .unwrap_or_else(|| "unix_udp".to_string())
+                .to_ascii_lowercase();
+            match mode.as_str() {
+                "tcp" | "udp" => {
+                    source.set("mode", mode.as_str());
+                    source.set("address", address(section, 5140));
+                }
+                _ => {
+                    source.set("mode", "unix");
+                    source.set("path", section.take("Path").unwrap_or_default());
+                    if mode == "unix_udp" {
+                        source.todo("Vector only listens on stream-oriented Unix sockets");
+                    }
+                }
+            }
+            source
+        }
+        "tcp" | "udp" => {
+            let mut source = Component::new(Kind::Source, id, "socket");
+            source.set("mode", plugin);
+            source.set("address", address(section, 5170));
+            if section
+                .take("Format")
+                .map_or(true, |format| format.eq_ignore_ascii_case("json"))
+            {
+                source.set("decoding.codec", "json");
+            }
+            source
+        }
+        "http" => {
+            let mut source = Component::new(Kind::Source, id, "http_server");
+            source.set("address", address(section, 9880));
+            source.set("decoding.codec", "json");
+            source
+        }
+        "stdin" => Component::new(Kind::Source, id, "stdin"),
+        "cpu" | "mem" | "disk" | "netif" => {
+            let mut source = Component::new(Kind::Source, id, "host_metrics");
+            let collector = match plugin {
+                "cpu" => "cpu",
+                "mem" => "memory",
+                "disk" => "disk",
+                _ => "network",
+            };
+            source.set("collectors", vec![Value::from(collector)]);
+            if let Some(interval) = section.take("Interval_Sec") {
+                if let Ok(interval) = interval.parse::<f64>() {
+                    source.set("scrape_interval_secs", interval);
+                }
+            }
+            source.todo("Vector emits metric events, where Fluent Bit emits records");
+            source
+        }
+        "dummy" => {
+            let mut source = Component::new(Kind::Source, id, "demo_logs");
+            source.set("format", "shuffle");
+            let record = section
+                .take("Dummy")
+                .unwrap_or_else(|| r#"{"message":"dummy"}"#.to_string());
+            source.set("lines", vec![Value::from(record)]);
+            if let Some(rate) = section.take("Rate").and_then(|r| r.parse::<f64>().ok()) {
+                if rate > 0.0 {
+                    source.set("interval", 1.0 / rate);
+                }
+            }
+            source.set("decoding.codec", "json");
+            source
+        }
+        "exec" => {
+            let mut source = Component::new(Kind::Source, id, "exec");
+            let command = section.take("Command").unwrap_or_default();
+            source.set(
+                "command",
+                vec![Value::from("sh"), Value::from("-c"), Value::from(command)],
+            );
+            source.set("mode", "scheduled");
+            if let Some(interval) = section
+                .take("Interval_Sec")
+                .and_then(|i| i.parse::<i64>().ok())
+            {
+                source.set("scheduled.exec_interval_secs", interval);
+            }
+            source
+        }
+        _ => return None,
+    };
+    Some(source)
+}
+
+/// Splits a `KEY VALUE` rule, as used by the `grep` and `modify` filters.
+fn rule(value: &str) -> (&str, &str) {
+    value
+        .split_once(char::is_whitespace)
+        .map(|(key, value)| (key, value.trim()))
+        .unwrap_or((value, ""))
+}
+
+fn convert_filter(plugin: &str, id: &str, section: &mut Section) -> Option<Component> {
+    let transform = match plugin {
+        "grep" => {
+            let mut conditions = Vec::new();
+            for (key, negate) in [("Regex", false), ("Exclude", true)] {
+                for value in section.take_all(key) {
+                    let (field, regex) = rule(&value);
+                    if field.starts_with('$') {
+                        return None;
+                    }
+                    conditions.push(format!(
+                        "{}match(string({}) ?? \"\", r'{}')",
+                        if negate { "!" } else { "" },
+                        vrl_path(field),
+                        regex.replace('\'', "\\'")
+                    ));
+                }
+            }
+            let mut transform = Component::new(Kind::Transform, id, "filter");
+            transform.set("condition", conditions.join(" && "));
+            transform
+        }
+        "modify" | "record_modifier" => {
+            let mut program = Vec::new();
+            let mut leftovers = Vec::new();
+            for (key, value) in std::mem::take(&mut section.entries) {
+                let (field, argument) = rule(&value);
+                let statement = match key.to_ascii_lowercase().as_str() {
+                    "set" | "record" => {
+                        Some(format!("{} = {}", vrl_path(field), vrl_string(argument)))
+                    }
+                    "add" => Some(format!(
+                        "if !exists({path}) {{ {path} = {value} }}",
+                        path = vrl_path(field),
+                        value = vrl_string(argument)
+                    )),
+                    "remove" | "remove_key" => Some(format!("del({})", vrl_path(field))),
+                    "rename" => Some(format!(
+                        "if exists({from}) && !exists({to}) {{ {to} = del({from}) }}",
+                        to = vrl_path(argument),
+                        from = vrl_path(field)
+                    )),
+                    "hard_rename" => {
+                        Some(format!("{} = del({})", vrl_path(argument), vrl_path(field)))
+                    }
+                    "copy" => Some(format!(
+                        "if exists({from}) && !exists({to}) {{ {to} = {from} }}",
+                        to = vrl_path(argument),
+                        from = vrl_path(field)
+                    )),
+                    "hard_copy" => Some(format!("{} = {}", vrl_path(argument), vrl_path(field))),
+                    _ => None,
+                };
+                match statement {
+                    Some(statement) => program.push(statement),
+                    None => leftovers.push((key, value)),
+                }
+            }
+            section.entries = leftovers;
+
+            let mut transform = Component::new(Kind::Transform, id, "remap");
+            transform.set("source", program.join("\n"));
+            transform
+        }
+        "throttle" => {
+            let mut transform = Component::new(Kind::Transform, id, "throttle");
+            let rate = section
+                .take("Rate")
+                .and_then(|rate| rate.parse::<i64>().ok())
+                .unwrap_or(1);
+            transform.set("threshold", rate);
+            let interval = section
+                .take("Interval")
+                .and_then(|interval| parse_duration_secs(&interval))
+                .unwrap_or(1);
+            transform.set("window_secs", interval);
+            if section.take("Window").is_some() {
+                transform.todo("Vector limits each window independently instead of averaging over `Window` intervals");
+            }
+            transform
+        }
+        _ => return None,
+    };
+    Some(transform)
+}
+
+/// Parses a Fluent Bit duration such as `1s`, `5m`, or `2h`.
+fn parse_duration_secs(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.parse::<i64>().ok()?;
+    match unit {
+        "" | "s" => Some(number),
+        "m" => Some(number * 60),
+        "h" => Some(number * 3600),
+        "d" => Some(number * 86400),
+        _ => None,
+    }
+}
+
+/// Builds an endpoint from the `Host`, `Port`, and `tls` options, and applies the TLS options.
+fn endpoint(
+    section: &mut Section,
+    component: &mut Component,
+    default_host: &str,
+    default_port: u16,
+) -> String {
+    let host = section
+        .take("Host")
+        .unwrap_or_else(|| default_host.to_string());
+    let port = section
+        .take("Port")
+        .unwrap_or_else(|| default_port.to_string());
+    let tls = section.take_bool("tls").unwrap_or(false);
+    if section.take_bool("tls.verify") == Some(false) {
+        component.set("tls.verify_certificate", false);
+    }
+    if let Some(ca_file) = section.take("tls.ca_file") {
+        component.set("tls.ca_file", ca_file);
+    }
+    format!("{}://{}:{}", if tls { "https" } else { "http" }, host, port)
+}
+
+fn basic_auth(section: &mut Section, component: &mut Component) {
+    if let Some(user) = section.take("HTTP_User") {
+        component.set("auth.strategy", "basic");
+        component.set("auth.user", user);
+        component.set(
+            "auth.password",
+            section.take("HTTP_Passwd").unwrap_or_default(),
+        );
+    }
+}
+
+fn convert_output(plugin: &str, id: &str, section: &mut Section) -> Option<Component> {
+    let sink = match plugin {
+        "stdout" => {
+            let mut sink = Component::new(Kind::Sink, id, "console");
+            sink.set("encoding.codec", "json");
+            section.take("Format");
+            sink
+        }
+        "es" => {
+            let mut sink = Component::new(Kind::Sink, id, "elasticsearch");
+            let endpoint = endpoint(section, &mut sink, "127.0.0.1", 9200);
+            sink.set("endpoints", vec![Value::from(endpoint)]);
+            basic_auth(section, &mut sink);
+            if section.take_bool("Logstash_Format") == Some(true) {
+                let prefix = section
+                    .take("Logstash_Prefix")
+                    .unwrap_or_else(|| "logstash".to_string());
+                sink.set("bulk.index", format!("{}-%Y.%m.%d", prefix));
+            } else if let Some(index) = section.take("Index") {
+                sink.set("bulk.index", index);
+            }
+            sink
+        }
+        "loki" => {
+            let mut sink = Component::new(Kind::Sink, id, "loki");
+            let endpoint = endpoint(section, &mut sink, "127.0.0.1", 3100);
+            sink.set("endpoint", endpoint);
+            basic_auth(section, &mut sink);
+            if let Some(tenant) = section.take("tenant_id") {
+                sink.set("tenant_id", tenant);
+            }
+
+            let mut labels = Map::new();
+            let mut definitions = section.take_all("labels");
+            definitions.extend(section.take_all("label_keys"));
+            for label in definitions.iter().flat_map(|labels| labels.split(',')) {
+                let label = label.trim();
+                if let Some((key, value)) = label.split_once('=') {
+                    labels.insert(key.trim().into(), value.trim().into());
+                } else if let Some(field) = label.strip_prefix('$') {
+                    labels.insert(
+                        component_id(field),
+                        format!("{{{{ {} }}}}", field.replace(['[', ']', '\''], ".")).into(),
+                    );
+                }
+            }
+            if labels.is_empty() {
+                labels.insert("job".into(), "fluent-bit".into());
+            }
+            sink.set("labels", Value::Table(labels));
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "kafka" => {
+            let mut sink = Component::new(Kind::Sink, id, "kafka");
+            sink.set(
+                "bootstrap_servers",
+                section.take("Brokers").unwrap_or_default(),
+            );
+            let topics = section.take("Topics").unwrap_or_default();
+            let mut topics = topics.split(',').map(str::trim);
+            sink.set("topic", topics.next().unwrap_or_default());
+            if topics.next().is_some() {
+                sink.todo("only the first of `Topics` is used; use a template to select others");
+            }
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "http" => {
+            let mut sink = Component::new(Kind::Sink, id, "http");
+            let endpoint = endpoint(section, &mut sink, "127.0.0.1", 80);
+            let uri = section.take("URI").unwrap_or_else(|| "/".to_string());
+            sink.set("uri", format!("{}{}", endpoint, uri));
+            basic_auth(section, &mut sink);
+            for header in section.take_all("Header") {
+                let (name, value) = rule(&header);
+                sink.set(&format!("request.headers.{}", name), value);
+            }
+            let format = section.take("Format").unwrap_or_default();
+            if format.eq_ignore_ascii_case("json_lines") {
+                sink.set("framing.method", "newline_delimited");
+            } else if !format.is_empty() && !format.eq_ignore_ascii_case("json") {
+                sink.todo(format!(
+                    "format `{}` is not supported; events are sent as JSON",
+                    format
+                ));
+            }
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "file" => {
+            let mut sink = Component::new(Kind::Sink, id, "file");
+            let directory = section.take("Path").unwrap_or_else(|| ".".to_string());
+            let file = section.take("File").unwrap_or_else(|| {
+                sink.todo("Fluent Bit names the file after the record tag; choose a file name");
+                "vector.log".to_string()
+            });
+            sink.set(
+                "path",
+                format!("{}/{}", directory.trim_end_matches('/'), file),
+            );
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "null" => Component::new(Kind::Sink, id, "blackhole"),
+        "splunk" => {
+            let mut sink = Component::new(Kind::Sink, id, "splunk_hec_logs");
+            let endpoint = endpoint(section, &mut sink, "127.0.0.1", 8088);
+            sink.set("endpoint", endpoint);
+            sink.set(
+                "default_token",
+                section.take("Splunk_Token").unwrap_or_default(),
+            );
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "datadog" => {
+            let mut sink = Component::new(Kind::Sink, id, "datadog_logs");
+            sink.set(
+                "default_api_key",
+                section.take("apikey").unwrap_or_default(),
+            );
+            if let Some(host) = section.take("Host") {
+                sink.set("site", host.trim_start_matches("http-intake.logs."));
+            }
+            section.take("tls");
+            sink
+        }
+        "cloudwatch_logs" => {
+            let mut sink = Component::new(Kind::Sink, id, "aws_cloudwatch_logs");
+            sink.set("region", section.take("region").unwrap_or_default());
+            sink.set(
+                "group_name",
+                section.take("log_group_name").unwrap_or_default(),
+            );
+            let stream = section
+                .take("log_stream_name")
+                .or_else(|| section.take("log_stream_prefix"))
+                .unwrap_or_default();
+            sink.set("stream_name", stream);
+            if section.take_bool("auto_create_group") == Some(true) {
+                sink.set("create_missing_group", true);
+            }
+            sink.set("encoding.codec", "json");
+            sink
+        }
+        "s3" => {
+            let mut sink = Component::new(Kind::Sink, id, "aws_s3");
+            sink.set("bucket", section.take("bucket").unwrap_or_default());
+            sink.set("region", section.take("region").unwrap_or_default());
+            if section
+                .take("compression")
+                .map_or(false, |c| c.eq_ignore_ascii_case("gzip"))
+            {
+                sink.set("compression", "gzip");
+            } else {
+                sink.set("compression", "none");
+            }
+            sink.set("encoding.codec", "json");
+            sink.set("framing.method", "newline_delimited");
+            sink
+        }
+        _ => return None,
+    };
+    Some(sink)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[SERVICE]
+    Flush 5
+
+@SET LOG_DIR=/var/log/app
+
+[INPUT]
+    Name tail
+    Tag  app.*
+    Path ${LOG_DIR}/*.log
+    Read_from_Head on
+    Parser docker
+
+[INPUT]
+    Name  systemd
+    Tag   host.systemd
+    Systemd_Filter _SYSTEMD_UNIT=docker.service
+
+[FILTER]
+    Name  grep
+    Match app.*
+    Regex level ^(warn|error)$
+
+[FILTER]
+    Name  modify
+    Match *
+    Add   cluster prod
+
+[OUTPUT]
+    Name  es
+    Match app.*
+    Host  es.internal
+    Port  9200
+    tls   on
+    Index app-logs
+
+[OUTPUT]
+    Name  forward
+    Match *
+
+[OUTPUT]
+    Name  loki
+    Match host.*
+    labels job=systemd, $hostname
+"#;
+
+    #[test]
+    fn matches_tags() {
+        assert!(tag_matches("*", "anything"));
+        assert!(tag_matches("app.*", "app.web"));
+        assert!(tag_matches("kube.*.log", "kube.pod.log"));
+        assert!(!tag_matches("app.*", "host.systemd"));
+        assert!(tag_matches("host.systemd", "host.systemd"));
+    }
+
+    #[test]
+    fn converts_pipeline() {
+        let topology = convert(CONFIG).unwrap();
+        let rendered = topology.render();
+        let config: Value = toml::from_str(&rendered).unwrap();
+
+        let tail = &config["sources"]["tail_0"];
+        assert_eq!(tail["type"].as_str(), Some("file"));
+        assert_eq!(tail["include"][0].as_str(), Some("/var/log/app/*.log"));
+        assert_eq!(tail["read_from"].as_str(), Some("beginning"));
+        assert!(rendered.contains("# TODO: option `Parser docker` is not converted"));
+
+        assert_eq!(
+            config["sources"]["systemd_0"]["include_units"][0].as_str(),
+            Some("docker.service")
+        );
+
+        let grep = &config["transforms"]["grep_0"];
+        assert_eq!(
+            grep["condition"].as_str(),
+            Some(r#"match(string(.level) ?? "", r'^(warn|error)$')"#)
+        );
+        assert_eq!(grep["inputs"][0].as_str(), Some("tail_0"));
+
+        // The `modify` filter matches both inputs; the tail records come out of `grep` first.
+        let modify = &config["transforms"]["modify_0"];
+        assert_eq!(
+            modify["source"].as_str(),
+            Some(r#"if !exists(.cluster) { .cluster = "prod" }"#)
+        );
+        assert_eq!(modify["inputs"][0].as_str(), Some("grep_0"));
+        assert_eq!(modify["inputs"][1].as_str(), Some("systemd_0"));
+
+        let es = &config["sinks"]["es_0"];
+        assert_eq!(
+            es["endpoints"][0].as_str(),
+            Some("https://es.internal:9200")
+        );
+        assert_eq!(es["bulk"]["index"].as_str(), Some("app-logs"));
+        assert_eq!(es["inputs"][0].as_str(), Some("modify_0"));
+
+        let loki = &config["sinks"]["loki_0"];
+        assert_eq!(loki["labels"]["job"].as_str(), Some("systemd"));
+        assert_eq!(loki["labels"]["hostname"].as_str(), Some("{{ hostname }}"));
+
+        assert!(rendered.contains("# TODO: output plugin `forward` has no equivalent Vector sink"));
+        assert!(rendered.contains("# TODO: service option `Flush 5`"));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration_secs("1s"), Some(1));
+        assert_eq!(parse_duration_secs("5m"), Some(300));
+        assert_eq!(parse_duration_secs("30"), Some(30));
+        assert_eq!(parse_duration_secs("1w"), None);
+    }
+}
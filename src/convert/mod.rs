@@ -0,0 +1,267 @@
+#![allow(missing_docs)]
+//! Conversion of configurations written for other log shippers into Vector configurations.
+
+mod filebeat;
+mod fluent_bit;
+
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use colored::*;
+use toml::{map::Map, Value};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The format of the configuration to convert.
+    #[arg(long, value_enum)]
+    from: Format,
+
+    /// The configuration file to convert.
+    input: PathBuf,
+
+    /// Write the converted configuration to this file instead of stdout.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// A Fluent Bit configuration in the classic format.
+    FluentBit,
+
+    /// A Filebeat YAML configuration.
+    Filebeat,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let input = match fs::read_to_string(&opts.input) {
+        Ok(input) => input,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!(
+                    "{}",
+                    format!("Failed to read {:?}: {}", opts.input, error).red()
+                );
+            }
+            return exitcode::NOINPUT;
+        }
+    };
+
+    let converted = match opts.from {
+        Format::FluentBit => fluent_bit::convert(&input),
+        Format::Filebeat => filebeat::convert(&input),
+    };
+    let output = match converted {
+        Ok(topology) => topology.render(),
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!(
+                    "{}",
+                    format!("Failed to convert configuration: {}", error).red()
+                );
+            }
+            return exitcode::DATAERR;
+        }
+    };
+
+    match &opts.file {
+        Some(path) => {
+            if let Err(error) = fs::write(path, output) {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("{}", format!("Failed to write {:?}: {}", path, error).red());
+                }
+                return exitcode::CANTCREAT;
+            }
+        }
+        None => {
+            #[allow(clippy::print_stdout)]
+            {
+                print!("{}", output);
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl Kind {
+    const fn table(self) -> &'static str {
+        match self {
+            Self::Source => "sources",
+            Self::Transform => "transforms",
+            Self::Sink => "sinks",
+        }
+    }
+}
+
+/// A component of the converted topology.
+#[derive(Debug)]
+struct Component {
+    kind: Kind,
+    id: String,
+    options: Map<String, Value>,
+    todos: Vec<String>,
+}
+
+impl Component {
+    fn new(kind: Kind, id: impl Into<String>, component_type: &str) -> Self {
+        let mut options = Map::new();
+        options.insert("type".into(), component_type.into());
+        Self {
+            kind,
+            id: id.into(),
+            options,
+            todos: Vec::new(),
+        }
+    }
+
+    /// Sets an option, creating the intermediate tables of a dotted key such as `bulk.index`.
+    fn set(&mut self, key: &str, value: impl Into<Value>) {
+        let mut table = &mut self.options;
+        let mut parts = key.split('.').peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                table.insert(part.into(), value.into());
+                return;
+            }
+            table = match table
+                .entry(part)
+                .or_insert_with(|| Value::Table(Map::new()))
+            {
+                Value::Table(table) => table,
+                _ => unreachable!("option {} is not a table", part),
+            };
+        }
+    }
+
+    fn set_inputs(&mut self, inputs: Vec<String>) {
+        if inputs.is_empty() {
+            self.todo("no converted component feeds into this one; set `inputs` manually");
+        }
+        self.set(
+            "inputs",
+            inputs.into_iter().map(Value::from).collect::<Vec<_>>(),
+        );
+    }
+
+    fn todo(&mut self, message: impl Into<String>) {
+        self.todos.push(message.into());
+    }
+}
+
+/// The result of a conversion: Vector components, and notes about what could not be converted.
+#[derive(Debug, Default)]
+struct Topology {
+    components: Vec<Component>,
+    todos: Vec<String>,
+}
+
+impl Topology {
+    fn todo(&mut self, message: impl Into<String>) {
+        self.todos.push(message.into());
+    }
+
+    fn render(&self) -> String {
+        let mut output = String::new();
+        for todo in &self.todos {
+            output.push_str(&format!("# TODO: {}\n", todo));
+        }
+
+        for kind in [Kind::Source, Kind::Transform, Kind::Sink] {
+            for component in self.components.iter().filter(|c| c.kind == kind) {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                for todo in &component.todos {
+                    output.push_str(&format!("# TODO: {}\n", todo));
+                }
+
+                let mut ids = Map::new();
+                ids.insert(
+                    component.id.clone(),
+                    Value::Table(component.options.clone()),
+                );
+                let mut root = Map::new();
+                root.insert(kind.table().into(), Value::Table(ids));
+                output.push_str(
+                    &toml::to_string(&Value::Table(root))
+                        .expect("converted options are always valid TOML"),
+                );
+            }
+        }
+
+        output
+    }
+}
+
+/// Turns a plugin name or alias into a valid component ID.
+fn component_id(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Returns a VRL path expression for a field name.
+fn vrl_path(field: &str) -> String {
+    if !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        format!(".{}", field)
+    } else {
+        format!(".{:?}", field)
+    }
+}
+
+/// Returns a VRL string literal.
+fn vrl_string(value: &str) -> String {
+    format!("{:?}", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_todos_and_nested_options() {
+        let mut source = Component::new(Kind::Source, "in", "stdin");
+        source.todo("check me");
+        let mut sink = Component::new(Kind::Sink, "out", "elasticsearch");
+        sink.set("bulk.index", "logs");
+        sink.set_inputs(vec!["in".into()]);
+
+        let topology = Topology {
+            components: vec![sink, source],
+            todos: vec!["global".into()],
+        };
+        let rendered = topology.render();
+
+        assert!(rendered.starts_with("# TODO: global\n"));
+        assert!(rendered.find("[sources.in]").unwrap() < rendered.find("[sinks.out]").unwrap());
+        assert!(rendered.contains("# TODO: check me\n[sources.in]"));
+        assert!(rendered.contains("[sinks.out.bulk]\nindex = \"logs\""));
+
+        let parsed: toml::Value = toml::from_str(&rendered).unwrap();
+        assert_eq!(parsed["sinks"]["out"]["inputs"][0].as_str(), Some("in"));
+    }
+
+    #[test]
+    fn quotes_vrl_paths() {
+        assert_eq!(vrl_path("log_level"), ".log_level");
+        assert_eq!(vrl_path("kubernetes.pod"), ".\"kubernetes.pod\"");
+    }
+}
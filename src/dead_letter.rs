@@ -0,0 +1,74 @@
+//! Queues carrying the events sinks could not deliver to the `dead_letter` sources.
+//!
+//! Sinks have no outputs in the topology, so the events they give up on are sent to a queue
+//! registered under the ID of a `dead_letter` source instead, which emits them to the components
+//! taking it as input.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use once_cell::sync::Lazy;
+use snafu::Snafu;
+use tokio::sync::mpsc;
+
+use crate::event::Event;
+
+/// The number of batches of events a queue holds before sinks wait for its source.
+const QUEUE_CAPACITY: usize = 16;
+
+static QUEUES: Lazy<Mutex<HashMap<String, mpsc::Sender<Vec<Event>>>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Snafu)]
+pub enum DeadLetterError {
+    #[snafu(display("No dead_letter source {:?} is running", id))]
+    NotFound { id: String },
+    #[snafu(display("The dead_letter source {:?} was stopped", id))]
+    Closed { id: String },
+}
+
+/// Registers the queue of a `dead_letter` source, replacing the queue of a previous instance of
+/// the source when it is reloaded.
+pub fn register(id: &str) -> mpsc::Receiver<Vec<Event>> {
+    let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+    QUEUES
+        .lock()
+        .expect("poisoned lock")
+        .insert(id.to_owned(), sender);
+    receiver
+}
+
+/// Sends events to the queue of the `dead_letter` source with the ID, waiting for the source if
+/// its queue is full.
+pub async fn send(id: &str, events: Vec<Event>) -> Result<(), DeadLetterError> {
+    let sender = QUEUES.lock().expect("poisoned lock").get(id).cloned();
+    let sender = sender.ok_or_else(|| DeadLetterError::NotFound { id: id.to_owned() })?;
+    sender
+        .send(events)
+        .await
+        .map_err(|_| DeadLetterError::Closed { id: id.to_owned() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[tokio::test]
+    async fn sends_events_to_registered_queue() {
+        let mut receiver = register("dead_letter_test");
+        send("dead_letter_test", vec![LogEvent::from("rejected").into()])
+            .await
+            .unwrap();
+        let events = receiver.recv().await.unwrap();
+        assert_eq!(events[0].as_log()["message"], "rejected".into());
+
+        drop(receiver);
+        assert!(matches!(
+            send("dead_letter_test", Vec::new()).await,
+            Err(DeadLetterError::Closed { .. })
+        ));
+        assert!(matches!(
+            send("dead_letter_missing", Vec::new()).await,
+            Err(DeadLetterError::NotFound { .. })
+        ));
+    }
+}
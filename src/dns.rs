@@ -1,51 +1,223 @@
 #![allow(missing_docs)]
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
+    sync::{Arc, RwLock},
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::{future::BoxFuture, FutureExt};
 use hyper::client::connect::dns::Name;
+use once_cell::sync::Lazy;
 use snafu::ResultExt;
-use tokio::task::spawn_blocking;
+use tokio::{
+    task::spawn_blocking,
+    time::{sleep_until, Instant},
+};
 use tower::Service;
+use trust_dns_resolver::{
+    config::{LookupIpStrategy, NameServerConfigGroup, ResolverConfig, ResolverOpts},
+    error::ResolveError,
+    TokioAsyncResolver,
+};
+use vector_core::config::dns::{DnsConfig, DnsIpStrategy};
+
+/// How long the records returned by the system resolver, which does not return TTLs, are used.
+const SYSTEM_RESOLVER_TTL: Duration = Duration::from_secs(60);
+
+static SETTINGS: Lazy<RwLock<Arc<Settings>>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct Settings {
+    /// Queries the configured nameservers, instead of using the system resolver.
+    nameservers: Option<TokioAsyncResolver>,
+    min_ttl: Option<Duration>,
+    max_ttl: Option<Duration>,
+    ip_strategy: DnsIpStrategy,
+}
+
+impl Settings {
+    fn new(config: &DnsConfig) -> Result<Self, DnsError> {
+        let min_ttl = config.min_ttl_secs.map(Duration::from_secs);
+        let max_ttl = config.max_ttl_secs.map(Duration::from_secs);
+
+        let nameservers = if config.nameservers.is_empty() {
+            None
+        } else {
+            let mut group = NameServerConfigGroup::new();
+            for nameserver in &config.nameservers {
+                group.merge(NameServerConfigGroup::from_ips_clear(
+                    &[nameserver.ip()],
+                    nameserver.port(),
+                    true,
+                ));
+            }
+
+            let mut options = ResolverOpts::default();
+            options.positive_min_ttl = min_ttl;
+            options.positive_max_ttl = max_ttl;
+            options.ip_strategy = match config.ip_strategy {
+                DnsIpStrategy::Ipv4Only => LookupIpStrategy::Ipv4Only,
+                DnsIpStrategy::Ipv6Only => LookupIpStrategy::Ipv6Only,
+                // The addresses are ordered once resolved.
+                DnsIpStrategy::Ipv4AndIpv6
+                | DnsIpStrategy::PreferIpv4
+                | DnsIpStrategy::PreferIpv6 => LookupIpStrategy::Ipv4AndIpv6,
+            };
+
+            Some(
+                TokioAsyncResolver::tokio(
+                    ResolverConfig::from_parts(None, Vec::new(), group),
+                    options,
+                )
+                .context(ResolverSnafu)?,
+            )
+        };
+
+        Ok(Self {
+            nameservers,
+            min_ttl,
+            max_ttl,
+            ip_strategy: config.ip_strategy,
+        })
+    }
+
+    fn valid_until(&self, valid_until: Instant) -> Instant {
+        let now = Instant::now();
+        let mut ttl = valid_until.saturating_duration_since(now);
+        if let Some(min_ttl) = self.min_ttl {
+            ttl = ttl.max(min_ttl);
+        }
+        if let Some(max_ttl) = self.max_ttl {
+            ttl = ttl.min(max_ttl);
+        }
+        now + ttl
+    }
+
+    fn order(&self, mut addresses: Vec<IpAddr>) -> Vec<IpAddr> {
+        match self.ip_strategy {
+            DnsIpStrategy::Ipv4AndIpv6 => {}
+            DnsIpStrategy::Ipv4Only => addresses.retain(IpAddr::is_ipv4),
+            DnsIpStrategy::Ipv6Only => addresses.retain(IpAddr::is_ipv6),
+            DnsIpStrategy::PreferIpv4 => addresses.sort_by_key(IpAddr::is_ipv6),
+            DnsIpStrategy::PreferIpv6 => addresses.sort_by_key(IpAddr::is_ipv4),
+        }
+        addresses
+    }
+}
+
+/// Applies the global DNS configuration.
+///
+/// Global options can't change while reloading, so this only takes effect for topologies started
+/// afterwards.
+pub fn apply(config: &DnsConfig) -> Result<(), DnsError> {
+    let settings = Settings::new(config)?;
+    *SETTINGS.write().expect("DNS settings lock is poisoned") = Arc::new(settings);
+    Ok(())
+}
+
+fn settings() -> Arc<Settings> {
+    Arc::clone(&SETTINGS.read().expect("DNS settings lock is poisoned"))
+}
+
+#[derive(Clone, Debug)]
+pub struct LookupIp {
+    addresses: std::vec::IntoIter<IpAddr>,
+    valid_until: Instant,
+}
+
+impl LookupIp {
+    /// When the records expire.
+    pub const fn valid_until(&self) -> Instant {
+        self.valid_until
+    }
 
-pub struct LookupIp(std::vec::IntoIter<SocketAddr>);
+    /// Resolves once the records of the host differ from these, resolving the host again
+    /// whenever its records expire.
+    ///
+    /// Long-lived connections reconnect once this resolves, which rebalances them across the
+    /// addresses of their host.
+    pub async fn changed(self, host: String) {
+        let mut records = self.addresses.as_slice().to_vec();
+        records.sort_unstable();
+        let mut valid_until = self.valid_until;
+
+        loop {
+            sleep_until(valid_until).await;
+            match Resolver.lookup_ip(host.clone()).await {
+                Ok(lookup) => {
+                    let mut current = lookup.addresses.as_slice().to_vec();
+                    current.sort_unstable();
+                    if current != records {
+                        debug!(message = "DNS records changed.", %host);
+                        return;
+                    }
+                    valid_until = lookup.valid_until;
+                }
+                Err(error) => {
+                    // The connection is kept until the host resolves again.
+                    debug!(message = "Failed resolving host again.", %host, %error);
+                    valid_until = Instant::now() + SYSTEM_RESOLVER_TTL;
+                }
+            }
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub(super) struct Resolver;
 
 impl Resolver {
     pub(crate) async fn lookup_ip(self, name: String) -> Result<LookupIp, DnsError> {
-        // We need to add port with the name so that `to_socket_addrs`
-        // resolves it properly. We will be discarding the port afterwards.
-        //
-        // Any port will do, but `9` is a well defined port for discarding
-        // packets.
-        let dummy_port = 9;
+        let settings = settings();
+
         // https://tools.ietf.org/html/rfc6761#section-6.3
         if name == "localhost" {
             // Not all operating systems support `localhost` as IPv6 `::1`, so
             // we resolving it to it's IPv4 value.
-            Ok(LookupIp(
-                vec![SocketAddr::new(Ipv4Addr::LOCALHOST.into(), dummy_port)].into_iter(),
-            ))
-        } else {
-            spawn_blocking(move || {
-                let name_ref = match name.as_str() {
-                    // strip IPv6 prefix and suffix
-                    name if name.starts_with('[') && name.ends_with(']') => {
-                        &name[1..name.len() - 1]
-                    }
-                    name => name,
-                };
-                (name_ref, dummy_port).to_socket_addrs()
-            })
-            .await
-            .context(JoinSnafu)?
-            .map(LookupIp)
-            .context(UnableLookupSnafu)
+            return Ok(LookupIp {
+                addresses: vec![Ipv4Addr::LOCALHOST.into()].into_iter(),
+                valid_until: settings.valid_until(Instant::now() + SYSTEM_RESOLVER_TTL),
+            });
         }
+
+        // strip IPv6 prefix and suffix
+        let name = if name.starts_with('[') && name.ends_with(']') {
+            name[1..name.len() - 1].to_owned()
+        } else {
+            name
+        };
+
+        let (addresses, valid_until) = match &settings.nameservers {
+            Some(resolver) => {
+                let lookup = resolver.lookup_ip(name).await.context(ResolveSnafu)?;
+                (
+                    lookup.iter().collect::<Vec<_>>(),
+                    Instant::from_std(lookup.valid_until()),
+                )
+            }
+            None => {
+                // We need to add port with the name so that `to_socket_addrs`
+                // resolves it properly. We will be discarding the port afterwards.
+                //
+                // Any port will do, but `9` is a well defined port for discarding
+                // packets.
+                let dummy_port = 9;
+                let addresses =
+                    spawn_blocking(move || (name.as_str(), dummy_port).to_socket_addrs())
+                        .await
+                        .context(JoinSnafu)?
+                        .context(UnableLookupSnafu)?
+                        .map(|address| address.ip())
+                        .collect::<Vec<_>>();
+                (addresses, Instant::now() + SYSTEM_RESOLVER_TTL)
+            }
+        };
+
+        Ok(LookupIp {
+            addresses: settings.order(addresses).into_iter(),
+            valid_until: settings.valid_until(valid_until),
+        })
     }
 }
 
@@ -53,7 +225,7 @@ impl Iterator for LookupIp {
     type Item = IpAddr;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|address| address.ip())
+        self.addresses.next()
     }
 }
 
@@ -71,17 +243,51 @@ impl Service<Name> for Resolver {
     }
 }
 
+/// Resolves the hosts of HTTP connections, which are given the port to connect to.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpResolver;
+
+impl Service<Name> for HttpResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = DnsError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Ok(()).into()
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        Resolver
+            .lookup_ip(name.as_str().to_owned())
+            .map(|lookup| {
+                lookup.map(|addresses| {
+                    addresses
+                        .map(|ip| SocketAddr::new(ip, 0))
+                        .collect::<Vec<_>>()
+                        .into_iter()
+                })
+            })
+            .boxed()
+    }
+}
+
 #[derive(Debug, snafu::Snafu)]
 pub enum DnsError {
     #[snafu(display("Unable to resolve name: {}", source))]
     UnableLookup { source: tokio::io::Error },
     #[snafu(display("Failed to join with resolving future: {}", source))]
     JoinError { source: tokio::task::JoinError },
+    #[snafu(display("Unable to resolve name: {}", source))]
+    Resolve { source: ResolveError },
+    #[snafu(display("Invalid DNS configuration: {}", source))]
+    Resolver { source: ResolveError },
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Resolver;
+    use std::net::Ipv6Addr;
+
+    use super::*;
 
     async fn resolve(name: &str) -> bool {
         let resolver = Resolver;
@@ -107,4 +313,46 @@ mod tests {
     async fn resolve_ipv6() {
         assert!(resolve("::1").await);
     }
+
+    #[test]
+    fn orders_addresses() {
+        let v4 = IpAddr::from(Ipv4Addr::new(10, 0, 0, 1));
+        let v6 = IpAddr::from(Ipv6Addr::LOCALHOST);
+        let order = |ip_strategy| {
+            Settings {
+                ip_strategy,
+                ..Default::default()
+            }
+            .order(vec![v6, v4])
+        };
+
+        assert_eq!(order(DnsIpStrategy::Ipv4AndIpv6), vec![v6, v4]);
+        assert_eq!(order(DnsIpStrategy::Ipv4Only), vec![v4]);
+        assert_eq!(order(DnsIpStrategy::Ipv6Only), vec![v6]);
+        assert_eq!(order(DnsIpStrategy::PreferIpv4), vec![v4, v6]);
+        assert_eq!(order(DnsIpStrategy::PreferIpv6), vec![v6, v4]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn clamps_ttls() {
+        let settings = Settings {
+            min_ttl: Some(Duration::from_secs(10)),
+            max_ttl: Some(Duration::from_secs(100)),
+            ..Default::default()
+        };
+        let now = Instant::now();
+
+        assert_eq!(
+            settings.valid_until(now + Duration::from_secs(1)),
+            now + Duration::from_secs(10)
+        );
+        assert_eq!(
+            settings.valid_until(now + Duration::from_secs(50)),
+            now + Duration::from_secs(50)
+        );
+        assert_eq!(
+            settings.valid_until(now + Duration::from_secs(1000)),
+            now + Duration::from_secs(100)
+        );
+    }
 }
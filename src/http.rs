@@ -1,9 +1,12 @@
 #![allow(missing_docs)]
 use std::{
     fmt,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
+use bytes::Bytes;
 use futures::future::BoxFuture;
 use headers::{Authorization, HeaderMapExt};
 use http::{header::HeaderValue, request::Builder, uri::InvalidUri, HeaderMap, Request, Uri};
@@ -22,7 +25,9 @@ use vector_config::configurable_component;
 
 use crate::{
     config::ProxyConfig,
+    dns::HttpResolver,
     internal_events::http_client,
+    request_capture::RequestCapture,
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
 };
 
@@ -39,12 +44,16 @@ pub enum HttpError {
     CallRequest { source: hyper::Error },
     #[snafu(display("Failed to build HTTP request: {}", source))]
     BuildRequest { source: http::Error },
+    #[snafu(display("Failed to read HTTP request body: {}", source))]
+    ReadRequestBody { source: crate::Error },
 }
 
 impl HttpError {
     pub const fn is_retriable(&self) -> bool {
         match self {
-            HttpError::BuildRequest { .. } | HttpError::MakeProxyConnector { .. } => false,
+            HttpError::BuildRequest { .. }
+            | HttpError::MakeProxyConnector { .. }
+            | HttpError::ReadRequestBody { .. } => false,
             HttpError::CallRequest { .. }
             | HttpError::BuildTlsConnector { .. }
             | HttpError::MakeHttpsConnector { .. } => true,
@@ -53,20 +62,33 @@ impl HttpError {
 }
 
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
-type HttpProxyConnector = ProxyConnector<HttpsConnector<HttpConnector>>;
+type HttpProxyConnector = ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>;
 
 pub struct HttpClient<B = Body> {
     client: Client<HttpProxyConnector, B>,
+    rotation: Option<Arc<PoolRotation<B>>>,
+    capture: Option<Arc<RequestCapture>>,
     user_agent: HeaderValue,
     proxy_connector: HttpProxyConnector,
 }
 
+/// Replaces the connection pool of a client once it reaches the maximum connection lifetime.
+///
+/// Connections of the replaced pool finish their requests, and are closed instead of returning to
+/// the pool.
+struct PoolRotation<B> {
+    client_builder: client::Builder,
+    lifetime: Duration,
+    current: Mutex<(Client<HttpProxyConnector, B>, Instant)>,
+}
+
 impl<B> HttpClient<B>
 where
-    B: fmt::Debug + HttpBody + Send + 'static,
+    B: fmt::Debug + HttpBody + From<Bytes> + Send + 'static,
     B::Data: Send,
     B::Error: Into<crate::Error>,
 {
+    /// Creates a client with the default connection pool settings.
     pub fn new(
         tls_settings: impl Into<MaybeTlsSettings>,
         proxy_config: &ProxyConfig,
@@ -74,13 +96,69 @@ where
         HttpClient::new_with_custom_client(tls_settings, proxy_config, &mut Client::builder())
     }
 
+    /// Creates a client with the connection pool settings of a sink.
+    pub fn new_with_pool(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy_config: &ProxyConfig,
+        pool: &HttpPoolConfig,
+    ) -> Result<HttpClient<B>, HttpError> {
+        HttpClient::new_with_version(tls_settings, proxy_config, HttpVersion::Http1, pool)
+    }
+
+    /// Creates a client sending requests with the HTTP version, with the connection pool settings
+    /// of a sink.
+    pub fn new_with_version(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy_config: &ProxyConfig,
+        version: HttpVersion,
+        pool: &HttpPoolConfig,
+    ) -> Result<HttpClient<B>, HttpError> {
+        let mut client_builder = Client::builder();
+        client_builder.http2_only(version == HttpVersion::Http2);
+        HttpClient::build(
+            tls_settings,
+            proxy_config,
+            version,
+            pool,
+            &mut client_builder,
+        )
+    }
+
     pub fn new_with_custom_client(
         tls_settings: impl Into<MaybeTlsSettings>,
         proxy_config: &ProxyConfig,
         client_builder: &mut client::Builder,
     ) -> Result<HttpClient<B>, HttpError> {
-        let proxy_connector = build_proxy_connector(tls_settings.into(), proxy_config)?;
+        HttpClient::build(
+            tls_settings,
+            proxy_config,
+            HttpVersion::Http1,
+            &HttpPoolConfig::default(),
+            client_builder,
+        )
+    }
+
+    /// Builds the client with the connection pool settings, and the capture settings of the sink
+    /// being built.
+    fn build(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy_config: &ProxyConfig,
+        version: HttpVersion,
+        pool: &HttpPoolConfig,
+        client_builder: &mut client::Builder,
+    ) -> Result<HttpClient<B>, HttpError> {
+        pool.configure(client_builder);
+
+        let proxy_connector =
+            build_proxy_connector_with_options(tls_settings.into(), proxy_config, version, pool)?;
         let client = client_builder.build(proxy_connector.clone());
+        let rotation = pool.max_connection_lifetime_secs.map(|lifetime| {
+            Arc::new(PoolRotation {
+                client_builder: client_builder.clone(),
+                lifetime: Duration::from_secs(lifetime),
+                current: Mutex::new((client.clone(), Instant::now())),
+            })
+        });
 
         let version = crate::get_version();
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
@@ -88,11 +166,34 @@ where
 
         Ok(HttpClient {
             client,
+            rotation,
+            capture: RequestCapture::current(),
             user_agent,
             proxy_connector,
         })
     }
 
+    /// Gets the client to send the next request with, replacing its pool once it reached the
+    /// maximum connection lifetime.
+    fn client(&self) -> Client<HttpProxyConnector, B> {
+        let rotation = match &self.rotation {
+            Some(rotation) => rotation,
+            None => return self.client.clone(),
+        };
+
+        let mut current = rotation
+            .current
+            .lock()
+            .expect("HTTP client pool mutex is poisoned");
+        if current.1.elapsed() >= rotation.lifetime {
+            *current = (
+                rotation.client_builder.build(self.proxy_connector.clone()),
+                Instant::now(),
+            );
+        }
+        current.0.clone()
+    }
+
     pub fn send(
         &self,
         mut request: Request<B>,
@@ -105,9 +206,27 @@ where
 
         emit!(http_client::AboutToSendHttpRequest { request: &request });
 
-        let response = self.client.request(request);
+        let client = self.client();
+        let capture = self.capture.clone();
 
         let fut = async move {
+            // Failed requests are captured along with their body, which has to be read before
+            // sending it.
+            let (request, mut pending) = match capture {
+                Some(capture) => {
+                    let (parts, body) = request.into_parts();
+                    let body = hyper::body::to_bytes(body).await.map_err(|error| {
+                        HttpError::ReadRequestBody {
+                            source: error.into(),
+                        }
+                    })?;
+                    let pending = capture.request(&parts, &body);
+                    (Request::from_parts(parts, B::from(body)), Some(pending))
+                }
+                None => (request, None),
+            };
+            let response = client.request(request);
+
             // Capture the time right before we issue the request.
             // Request doesn't start the processing until we start polling it.
             let before = std::time::Instant::now();
@@ -127,6 +246,9 @@ where
                         error: &error,
                         roundtrip
                     });
+                    if let Some(pending) = pending.take() {
+                        pending.error(error.to_string());
+                    }
                     error
                 })
                 .context(CallRequestSnafu)?;
@@ -136,7 +258,18 @@ where
                 response: &response,
                 roundtrip
             });
-            Ok(response)
+
+            match pending {
+                Some(pending) if !response.status().is_success() => {
+                    let (parts, body) = response.into_parts();
+                    let body = hyper::body::to_bytes(body)
+                        .await
+                        .context(CallRequestSnafu)?;
+                    pending.response(&parts, &body);
+                    Ok(http::Response::from_parts(parts, Body::from(body)))
+                }
+                _ => Ok(response),
+            }
         }
         .instrument(span.clone().or_current());
 
@@ -158,12 +291,31 @@ where
 pub fn build_proxy_connector(
     tls_settings: MaybeTlsSettings,
     proxy_config: &ProxyConfig,
-) -> Result<ProxyConnector<HttpsConnector<HttpConnector>>, HttpError> {
+) -> Result<ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>, HttpError> {
+    build_proxy_connector_with_options(
+        tls_settings,
+        proxy_config,
+        HttpVersion::Http1,
+        &HttpPoolConfig::default(),
+    )
+}
+
+fn build_proxy_connector_with_options(
+    tls_settings: MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+    version: HttpVersion,
+    pool: &HttpPoolConfig,
+) -> Result<ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>, HttpError> {
     // Create dedicated TLS connector for the proxied connection with user TLS settings.
-    let tls = tls_connector_builder(&tls_settings)
-        .context(BuildTlsConnectorSnafu)?
-        .build();
-    let https = build_tls_connector(tls_settings)?;
+    let mut tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
+    // Connections tunneled through a proxy are not told which protocol was negotiated, so only
+    // HTTP/2, which doesn't depend on it, is offered through them.
+    if let (HttpVersion::Http2, Some(protocols)) = (version, version.alpn_protocols()) {
+        tls.set_alpn_protos(protocols)
+            .context(MakeHttpsConnectorSnafu)?;
+    }
+    let tls = tls.build();
+    let https = build_tls_connector_with_options(tls_settings, version, pool)?;
     let mut proxy = ProxyConnector::new(https).unwrap();
     // Make proxy connector aware of user TLS settings by setting the TLS connector:
     // https://github.com/vectordotdev/vector/issues/13683
@@ -176,11 +328,26 @@ pub fn build_proxy_connector(
 
 pub fn build_tls_connector(
     tls_settings: MaybeTlsSettings,
-) -> Result<HttpsConnector<HttpConnector>, HttpError> {
-    let mut http = HttpConnector::new();
-    http.enforce_http(false);
+) -> Result<HttpsConnector<HttpConnector<HttpResolver>>, HttpError> {
+    build_tls_connector_with_options(tls_settings, HttpVersion::Http1, &HttpPoolConfig::default())
+}
 
-    let tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
+fn build_tls_connector_with_options(
+    tls_settings: MaybeTlsSettings,
+    version: HttpVersion,
+    pool: &HttpPoolConfig,
+) -> Result<HttpsConnector<HttpConnector<HttpResolver>>, HttpError> {
+    let mut http = HttpConnector::new_with_resolver(HttpResolver);
+    http.enforce_http(false);
+    http.set_keepalive(pool.tcp_keepalive_secs.map(Duration::from_secs));
+
+    let mut tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
+    // The protocols configured in the TLS options take precedence, as they are applied to each
+    // connection.
+    if let Some(protocols) = version.alpn_protocols() {
+        tls.set_alpn_protos(protocols)
+            .context(MakeHttpsConnectorSnafu)?;
+    }
     let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnectorSnafu)?;
 
     let settings = tls_settings.tls().cloned();
@@ -212,7 +379,7 @@ fn default_request_headers<B>(request: &mut Request<B>, user_agent: &HeaderValue
 
 impl<B> Service<Request<B>> for HttpClient<B>
 where
-    B: fmt::Debug + HttpBody + Send + 'static,
+    B: fmt::Debug + HttpBody + From<Bytes> + Send + 'static,
     B::Data: Send,
     B::Error: Into<crate::Error> + Send,
 {
@@ -233,6 +400,8 @@ impl<B> Clone for HttpClient<B> {
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            rotation: self.rotation.clone(),
+            capture: self.capture.clone(),
             user_agent: self.user_agent.clone(),
             proxy_connector: self.proxy_connector.clone(),
         }
@@ -248,6 +417,116 @@ impl<B> fmt::Debug for HttpClient<B> {
     }
 }
 
+/// The HTTP version used to send requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpVersion {
+    /// HTTP/1.1.
+    ///
+    /// HTTP/2 is only used when the `tls.alpn_protocols` option negotiates it.
+    #[default]
+    Http1,
+
+    /// HTTP/2 when the server supports it, and HTTP/1.1 otherwise.
+    ///
+    /// Both versions are offered to the server when connecting over TLS. Connections in plain
+    /// text or through a proxy use HTTP/1.1.
+    Auto,
+
+    /// HTTP/2.
+    ///
+    /// Connections in plain text use HTTP/2 right away ("prior knowledge"), which the server must
+    /// support. Requests to a host are multiplexed over a single connection, up to the number of
+    /// concurrent streams the server allows.
+    Http2,
+}
+
+impl HttpVersion {
+    /// The protocols offered through ALPN when connecting over TLS.
+    const fn alpn_protocols(self) -> Option<&'static [u8]> {
+        match self {
+            Self::Http1 => None,
+            Self::Auto => Some(b"\x02h2\x08http/1.1"),
+            Self::Http2 => Some(b"\x02h2"),
+        }
+    }
+}
+
+/// Connection pool settings of the HTTP clients of a sink.
+///
+/// Load balancers and NATs close connections that are idle for too long, usually without
+/// notifying either end, which shows up as connection resets on the next request. Keeping idle
+/// connections for less time than them, or sending TCP keepalives, avoids this.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HttpPoolConfig {
+    /// The maximum number of idle connections kept open to each host.
+    ///
+    /// With HTTP/1.1, a connection sends one request at a time, so as many connections as
+    /// concurrent requests are needed to avoid reconnecting. With HTTP/2, a single connection
+    /// carries all of the requests to a host.
+    ///
+    /// By default, the number of idle connections is not limited.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    pub max_idle_per_host: Option<usize>,
+
+    /// The number of seconds idle connections are kept open for.
+    ///
+    /// Defaults to 90 seconds.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub idle_timeout_secs: Option<u64>,
+
+    /// The number of seconds a connection is idle before TCP keepalive probes are sent.
+    ///
+    /// By default, TCP keepalive is disabled.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub tcp_keepalive_secs: Option<u64>,
+
+    /// The maximum number of seconds connections are used for.
+    ///
+    /// Afterwards, new requests are sent over new connections, while the old connections are
+    /// closed once their requests complete. This spreads the connections across the instances
+    /// behind a load balancer as they change.
+    ///
+    /// By default, connections are used for as long as they stay open.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub max_connection_lifetime_secs: Option<u64>,
+
+    /// The initial HTTP/2 flow control window of each request, which bounds the bytes of a
+    /// response sent before it is read.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub http2_initial_stream_window_bytes: Option<u32>,
+
+    /// The initial HTTP/2 flow control window of each connection, shared by its requests.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub http2_initial_connection_window_bytes: Option<u32>,
+
+    /// Whether HTTP/2 flow control windows adapt to the bandwidth and latency of the connection,
+    /// overriding the initial windows.
+    #[serde(default)]
+    pub http2_adaptive_window: bool,
+}
+
+impl HttpPoolConfig {
+    fn configure(&self, client_builder: &mut client::Builder) {
+        if let Some(max_idle_per_host) = self.max_idle_per_host {
+            client_builder.pool_max_idle_per_host(max_idle_per_host);
+        }
+        if let Some(idle_timeout_secs) = self.idle_timeout_secs {
+            client_builder.pool_idle_timeout(Duration::from_secs(idle_timeout_secs));
+        }
+        client_builder
+            .http2_initial_stream_window_size(self.http2_initial_stream_window_bytes)
+            .http2_initial_connection_window_size(self.http2_initial_connection_window_bytes);
+        if self.http2_adaptive_window {
+            client_builder.http2_adaptive_window(true);
+        }
+    }
+}
+
 /// Configuration of the authentication strategy for HTTP requests.
 ///
 /// HTTP authentication should be used with HTTPS only, as the authentication credentials are passed as an
@@ -339,7 +618,18 @@ pub fn get_http_scheme_from_uri(uri: &Uri) -> &'static str {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hyper::{
+        server::Server,
+        service::{make_service_fn, service_fn},
+    };
+
     use super::*;
+    use crate::test_util::next_addr;
 
     #[test]
     fn test_default_request_headers_defaults() {
@@ -370,4 +660,71 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    #[tokio::test]
+    async fn sends_http2_with_prior_knowledge() {
+        let addr = next_addr();
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|request: Request<Body>| async move {
+                Ok::<_, Infallible>(http::Response::new(Body::from(format!(
+                    "{:?}",
+                    request.version()
+                ))))
+            }))
+        });
+        tokio::spawn(Server::bind(&addr).http2_only(true).serve(make_service));
+
+        let client =
+            HttpClient::<Body>::new_with_version(None, &ProxyConfig::default(), HttpVersion::Http2)
+                .unwrap();
+        let request = Request::get(format!("http://{}/", addr))
+            .body(Body::empty())
+            .unwrap();
+        let response = client.send(request).await.unwrap();
+
+        assert_eq!(response.version(), http::Version::HTTP_2);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body, "HTTP/2.0");
+    }
+
+    async fn connections_for_two_requests(pool: HttpPoolConfig) -> usize {
+        let addr = next_addr();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = Arc::clone(&connections);
+        let make_service = make_service_fn(move |_| {
+            accepted.fetch_add(1, Ordering::Relaxed);
+            async {
+                Ok::<_, Infallible>(service_fn(|_: Request<Body>| async {
+                    Ok::<_, Infallible>(http::Response::new(Body::empty()))
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+
+        let client =
+            HttpClient::<Body>::new_with_pool(None, &ProxyConfig::default(), &pool).unwrap();
+        for _ in 0..2 {
+            let request = Request::get(format!("http://{}/", addr))
+                .body(Body::empty())
+                .unwrap();
+            let response = client.send(request).await.unwrap();
+            hyper::body::to_bytes(response.into_body()).await.unwrap();
+        }
+
+        connections.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn rotates_connections_past_their_lifetime() {
+        assert_eq!(
+            connections_for_two_requests(HttpPoolConfig::default()).await,
+            1
+        );
+
+        let pool = HttpPoolConfig {
+            max_connection_lifetime_secs: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(connections_for_two_requests(pool).await, 2);
+    }
 }
@@ -0,0 +1,18 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AnomalyDetected {
+    pub score: f64,
+}
+
+impl InternalEvent for AnomalyDetected {
+    fn emit(self) {
+        debug!(
+            message = "Metric value deviates from its baseline.",
+            score = %self.score,
+            internal_log_rate_limit = true,
+        );
+        counter!("anomalies_detected_total", 1);
+    }
+}
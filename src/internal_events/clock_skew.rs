@@ -0,0 +1,23 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ClockSkewDetected {
+    pub skew_secs: f64,
+    pub policy: &'static str,
+}
+
+impl InternalEvent for ClockSkewDetected {
+    fn emit(self) {
+        debug!(
+            message = "Event timestamp outside of the accepted window.",
+            skew_secs = %self.skew_secs,
+            policy = %self.policy,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "clock_skew_events_total", 1,
+            "policy" => self.policy,
+        );
+    }
+}
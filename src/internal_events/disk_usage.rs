@@ -0,0 +1,32 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ComponentDiskUsage<'a> {
+    pub component_id: &'a str,
+    pub kind: &'static str,
+    pub bytes: u64,
+}
+
+impl<'a> InternalEvent for ComponentDiskUsage<'a> {
+    fn emit(self) {
+        gauge!(
+            "component_disk_usage_bytes", self.bytes as f64,
+            "component_id" => self.component_id.to_owned(),
+            "kind" => self.kind,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DataDirBudgetUsage {
+    pub used_bytes: u64,
+    pub max_bytes: u64,
+}
+
+impl InternalEvent for DataDirBudgetUsage {
+    fn emit(self) {
+        gauge!("data_dir_budget_used_bytes", self.used_bytes as f64);
+        gauge!("data_dir_budget_max_bytes", self.max_bytes as f64);
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use metrics::{counter, histogram};
+use vector_core::{event::BatchStatus, internal_event::InternalEvent};
+
+#[derive(Debug)]
+pub struct DualWriteBatchReconciled {
+    pub count: usize,
+    pub primary: BatchStatus,
+    pub secondary: BatchStatus,
+    pub primary_latency: Duration,
+    pub secondary_latency: Duration,
+}
+
+impl InternalEvent for DualWriteBatchReconciled {
+    fn emit(self) {
+        for (sink, status, latency) in [
+            ("primary", self.primary, self.primary_latency),
+            ("secondary", self.secondary, self.secondary_latency),
+        ] {
+            let status = match status {
+                BatchStatus::Delivered => "delivered",
+                BatchStatus::Errored => "errored",
+                BatchStatus::Rejected => "rejected",
+            };
+            counter!(
+                "dual_write_events_total", self.count as u64,
+                "sink" => sink,
+                "status" => status,
+            );
+            histogram!("dual_write_latency_seconds", latency, "sink" => sink);
+        }
+
+        let (slower, delta) = if self.primary_latency >= self.secondary_latency {
+            ("primary", self.primary_latency - self.secondary_latency)
+        } else {
+            ("secondary", self.secondary_latency - self.primary_latency)
+        };
+        histogram!("dual_write_latency_delta_seconds", delta, "slower" => slower);
+
+        if self.primary != self.secondary {
+            warn!(
+                message = "Primary and secondary sinks diverged on a batch of events.",
+                count = self.count,
+                primary = ?self.primary,
+                secondary = ?self.secondary,
+                internal_log_rate_limit = true,
+            );
+            counter!("dual_write_divergent_events_total", self.count as u64);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::num::ParseIntError;
+
+use crate::emit;
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct ElasticsearchInvalidDocumentVersionError<'a> {
+    pub version: &'a str,
+    pub error: ParseIntError,
+}
+
+impl<'a> InternalEvent for ElasticsearchInvalidDocumentVersionError<'a> {
+    fn emit(self) {
+        let reason = "Document version is not a non-negative integer.";
+        error!(
+            message = reason,
+            version = %self.version,
+            error = %self.error,
+            error_code = "invalid_document_version",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_document_version",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchBulkActionRejected<'a> {
+    pub error_code: &'a str,
+    pub reason: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for ElasticsearchBulkActionRejected<'a> {
+    fn emit(self) {
+        error!(
+            message = "Bulk action rejected by Elasticsearch.",
+            error_code = %self.error_code,
+            reason = %self.reason,
+            count = self.count,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", self.count as u64,
+            "error_code" => self.error_code.to_string(),
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+
+#[derive(Debug, Copy, Clone)]
+pub struct FaultInjectionEventsRejected {
+    pub count: usize,
+}
+
+impl InternalEvent for FaultInjectionEventsRejected {
+    fn emit(self) {
+        let reason = "Events rejected by an injected fault.";
+        error!(
+            message = reason,
+            count = self.count,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+use vector_vrl_functions::envelope::EnvelopeError;
+
+use crate::emit;
+
+#[derive(Debug)]
+pub struct FieldEncryptionError<'a> {
+    pub field: &'a str,
+    pub error: EnvelopeError,
+}
+
+impl InternalEvent for FieldEncryptionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to encrypt field.",
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: 1,
+            reason: "Failed to encrypt field.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct FieldDecryptionError<'a> {
+    pub field: &'a str,
+    pub error: String,
+}
+
+impl InternalEvent for FieldDecryptionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to decrypt field, leaving its envelope as is.",
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -87,3 +87,27 @@ impl InternalEvent for HttpClientHttpError {
         counter!("http_request_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct HttpClientCheckpointError {
+    pub error: std::io::Error,
+    pub path: String,
+}
+
+impl InternalEvent for HttpClientCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Could not persist checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::IO_FAILED,
+        );
+    }
+}
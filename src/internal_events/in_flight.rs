@@ -0,0 +1,12 @@
+use metrics::{register_gauge, Gauge};
+use vector_common::registered_event;
+
+registered_event! {
+    SinkInFlightBytes => {
+        in_flight_bytes: Gauge = register_gauge!("sink_in_flight_bytes"),
+    }
+
+    fn emit(&self, bytes: usize) {
+        self.in_flight_bytes.set(bytes as f64);
+    }
+}
@@ -0,0 +1,34 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SloEvaluated<'a> {
+    pub objective: &'a str,
+    pub value: Option<f64>,
+    pub met: bool,
+    pub error_budget_remaining: Option<f64>,
+}
+
+impl<'a> InternalEvent for SloEvaluated<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Objective evaluated.",
+            objective = %self.objective,
+            value = ?self.value,
+            met = %self.met,
+        );
+        gauge!(
+            "slo_objective_met", if self.met { 1.0 } else { 0.0 },
+            "objective" => self.objective.to_owned(),
+        );
+        if let Some(value) = self.value {
+            gauge!("slo_indicator_ratio", value, "objective" => self.objective.to_owned());
+        }
+        if let Some(remaining) = self.error_budget_remaining {
+            gauge!(
+                "slo_error_budget_remaining_ratio", remaining,
+                "objective" => self.objective.to_owned(),
+            );
+        }
+    }
+}
@@ -122,3 +122,30 @@ impl InternalEvent for JournaldCheckpointFileOpenError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct JournaldReliabilitySignal<'a> {
+    pub kind: &'static str,
+    pub unit: Option<&'a str>,
+}
+
+impl<'a> InternalEvent for JournaldReliabilitySignal<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Reliability signal recognized.",
+            kind = self.kind,
+            unit = ?self.unit,
+        );
+        match self.unit {
+            Some(unit) => counter!(
+                "journald_reliability_events_total", 1,
+                "kind" => self.kind,
+                "unit" => unit.to_owned(),
+            ),
+            None => counter!(
+                "journald_reliability_events_total", 1,
+                "kind" => self.kind,
+            ),
+        }
+    }
+}
@@ -184,3 +184,28 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         counter!("kafka_header_extraction_failures_total", 1);
     }
 }
+
+pub struct KafkaRecordRejectedError<'a> {
+    pub topic: &'a str,
+    pub schema_id: Option<u32>,
+}
+
+impl InternalEvent for KafkaRecordRejectedError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Record rejected by broker-side validation. If schema ID validation is enabled for the topic, check that the payload's schema ID is registered for it.",
+            error_code = "invalid_record",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            topic = self.topic,
+            schema_id = ?self.schema_id,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_record",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
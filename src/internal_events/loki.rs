@@ -1,4 +1,4 @@
-use crate::emit;
+use crate::{dead_letter::DeadLetterError, emit};
 use metrics::counter;
 use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
@@ -72,3 +72,228 @@ impl InternalEvent for LokiOutOfOrderEventRewritten {
         counter!("rewritten_timestamp_events_total", self.count as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct LokiTenantLabelsDisallowed<'a> {
+    pub tenant_id: &'a str,
+    pub count: usize,
+    pub action: &'static str,
+}
+
+impl<'a> InternalEvent for LokiTenantLabelsDisallowed<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Event carried labels not allowed for its tenant.",
+            tenant_id = %self.tenant_id,
+            count = self.count,
+            action = self.action,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "disallowed_labels_total", self.count as u64,
+            "tenant_id" => self.tenant_id.to_owned(),
+            "action" => self.action,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiStreamsSharded {
+    pub count: usize,
+}
+
+impl InternalEvent for LokiStreamsSharded {
+    fn emit(self) {
+        info!(
+            message = "Sharding streams exceeding the per-stream rate limit.",
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("sharded_streams_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiLabelCardinalityExceeded {
+    pub count: usize,
+    pub action: &'static str,
+}
+
+impl InternalEvent for LokiLabelCardinalityExceeded {
+    fn emit(self) {
+        warn!(
+            message = "Event carried label values over the cardinality limit.",
+            count = self.count,
+            action = self.action,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "label_cardinality_exceeded_total", self.count as u64,
+            "action" => self.action,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiLabelsLimited {
+    pub count: usize,
+    pub limit: &'static str,
+}
+
+impl InternalEvent for LokiLabelsLimited {
+    fn emit(self) {
+        warn!(
+            message = "Event carried labels over the limits of Loki.",
+            count = self.count,
+            limit = self.limit,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "limited_labels_total", self.count as u64,
+            "limit" => self.limit,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiAttachmentTooLarge {
+    pub size: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for LokiAttachmentTooLarge {
+    fn emit(self) {
+        warn!(
+            message = "Event attachment exceeded the maximum size, sent without it.",
+            size = self.size,
+            max_bytes = self.max_bytes,
+            internal_log_rate_limit = true,
+        );
+        counter!("oversized_attachments_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiTenantRateLimited {
+    pub retry_after: Option<std::time::Duration>,
+}
+
+impl InternalEvent for LokiTenantRateLimited {
+    fn emit(self) {
+        debug!(
+            message = "Tenant rate limited by Loki, holding back its requests.",
+            retry_after_secs = ?self.retry_after.map(|delay| delay.as_secs_f64()),
+            internal_log_rate_limit = true,
+        );
+        counter!("rate_limited_requests_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiStreamEntriesSent<'a> {
+    pub stream: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for LokiStreamEntriesSent<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Entries of stream sent.",
+            stream = %self.stream,
+            count = self.count,
+        );
+        counter!(
+            "stream_sent_entries_total", self.count as u64,
+            "stream" => self.stream.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiStreamEntriesDropped {
+    pub stream: String,
+    pub count: usize,
+    pub reason: &'static str,
+}
+
+impl InternalEvent for LokiStreamEntriesDropped {
+    fn emit(self) {
+        debug!(
+            message = "Entries of stream dropped.",
+            stream = %self.stream,
+            count = self.count,
+            reason = self.reason,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "stream_dropped_entries_total", self.count as u64,
+            "stream" => self.stream,
+            "reason" => self.reason,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiStreamEntriesRejected<'a> {
+    pub stream: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for LokiStreamEntriesRejected<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Entries of stream rejected by Loki.",
+            stream = %self.stream,
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "stream_rejected_entries_total", self.count as u64,
+            "stream" => self.stream.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiDeadLettersSent<'a> {
+    pub dead_letter: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for LokiDeadLettersSent<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Events rejected by Loki sent to the dead letter source.",
+            dead_letter = %self.dead_letter,
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("dead_letter_events_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiDeadLettersError {
+    pub error: DeadLetterError,
+    pub count: usize,
+}
+
+impl InternalEvent for LokiDeadLettersError {
+    fn emit(self) {
+        error!(
+            message = "Events rejected by Loki could not be sent to the dead letter source.",
+            error = %self.error,
+            count = self.count,
+            error_code = "dead_letter_failed",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "dead_letter_failed",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct LokiSourceRequestError {
+    pub api: &'static str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for LokiSourceRequestError {
+    fn emit(self) {
+        error!(
+            message = "Request to Loki failed.",
+            api = %self.api,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "api" => self.api,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiSourceParseError {
+    pub api: &'static str,
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LokiSourceParseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse response from Loki.",
+            api = %self.api,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "api" => self.api,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LokiSourceEntriesDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for LokiSourceEntriesDropped {
+    fn emit(self) {
+        warn!(
+            message = "Loki dropped entries of the tail, as they were not read fast enough.",
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("tail_dropped_entries_total", self.count as u64);
+    }
+}
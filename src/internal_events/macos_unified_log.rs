@@ -0,0 +1,72 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct MacosUnifiedLogStartError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for MacosUnifiedLogStartError {
+    fn emit(self) {
+        error!(
+            message = "Error starting log process.",
+            error = %self.error,
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::COMMAND_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MacosUnifiedLogReadError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for MacosUnifiedLogReadError {
+    fn emit(self) {
+        error!(
+            message = "Could not read from the unified log.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::READER_FAILED,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MacosUnifiedLogInvalidRecordError {
+    pub error: serde_json::Error,
+    pub text: String,
+}
+
+impl InternalEvent for MacosUnifiedLogInvalidRecordError {
+    fn emit(self) {
+        error!(
+            message = "Invalid entry from the unified log, discarding.",
+            error = %self.error,
+            text = %self.text,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::PARSER_FAILED,
+        );
+    }
+}
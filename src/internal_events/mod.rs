@@ -5,6 +5,8 @@ mod adaptive_concurrency;
 mod aggregate;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 mod amqp;
+#[cfg(feature = "transforms-anomaly_detection")]
+mod anomaly_detection;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
 #[cfg(feature = "api")]
@@ -27,6 +29,8 @@ mod aws_kinesis_firehose;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
 mod batch;
+#[cfg(feature = "transforms-clock_skew")]
+mod clock_skew;
 mod codecs;
 mod common;
 mod conditions;
@@ -38,15 +42,28 @@ mod datadog_traces;
 mod dedupe;
 #[cfg(feature = "sources-demo_logs")]
 mod demo_logs;
+mod disk_usage;
 #[cfg(feature = "sources-dnstap")]
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "sinks-dual_write")]
+mod dual_write;
+#[cfg(feature = "sinks-dynatrace")]
+mod dynatrace;
+#[cfg(feature = "sinks-elasticsearch")]
+mod elasticsearch;
 mod encoding_transcode;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
 mod exec;
+mod fault_injection;
+#[cfg(any(
+    feature = "transforms-decrypt_fields",
+    feature = "transforms-encrypt_fields"
+))]
+mod field_encryption;
 #[cfg(any(feature = "sources-file-descriptor", feature = "sources-stdin"))]
 mod file_descriptor;
 #[cfg(feature = "transforms-filter")]
@@ -64,12 +81,15 @@ mod http;
 pub mod http_client;
 #[cfg(feature = "sources-utils-http-client")]
 mod http_client_source;
+mod in_flight;
 #[cfg(feature = "sinks-influxdb")]
 mod influxdb;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 mod internal_metrics;
+#[cfg(feature = "sources-internal_slo")]
+mod internal_slo;
 #[cfg(all(unix, feature = "sources-journald"))]
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
@@ -80,8 +100,12 @@ mod log_to_metric;
 mod logplex;
 #[cfg(feature = "sinks-loki")]
 mod loki;
+#[cfg(feature = "sources-loki")]
+mod loki_source;
 #[cfg(feature = "transforms-lua")]
 mod lua;
+#[cfg(feature = "sources-macos_unified_log")]
+mod macos_unified_log;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
@@ -90,7 +114,11 @@ mod mongodb_metrics;
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
+#[cfg(feature = "sources-okta")]
+mod okta;
 mod open;
+#[cfg(feature = "sinks-opentelemetry")]
+mod opentelemetry;
 mod parser;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
@@ -104,9 +132,13 @@ mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
+mod request_limits;
 mod sample;
+mod scaling;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "transforms-sessionize")]
+mod sessionize;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -138,6 +170,8 @@ pub(crate) use mongodb_metrics::*;
 pub(crate) use self::aggregate::*;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
 pub(crate) use self::amqp::*;
+#[cfg(feature = "transforms-anomaly_detection")]
+pub(crate) use self::anomaly_detection::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
@@ -159,6 +193,8 @@ pub(crate) use self::aws_kinesis::*;
 pub(crate) use self::aws_kinesis_firehose::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "transforms-clock_skew")]
+pub(crate) use self::clock_skew::*;
 pub(crate) use self::codecs::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
@@ -172,10 +208,21 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "sinks-dual_write")]
+pub(crate) use self::dual_write::*;
+#[cfg(feature = "sinks-dynatrace")]
+pub(crate) use self::dynatrace::*;
+#[cfg(feature = "sinks-elasticsearch")]
+pub(crate) use self::elasticsearch::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
 pub(crate) use self::exec::*;
+#[cfg(any(
+    feature = "transforms-decrypt_fields",
+    feature = "transforms-encrypt_fields"
+))]
+pub(crate) use self::field_encryption::*;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
@@ -209,6 +256,8 @@ pub(crate) use self::influxdb::*;
 pub(crate) use self::internal_logs::*;
 #[cfg(feature = "sources-internal_metrics")]
 pub(crate) use self::internal_metrics::*;
+#[cfg(feature = "sources-internal_slo")]
+pub(crate) use self::internal_slo::*;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
@@ -220,14 +269,22 @@ pub(crate) use self::log_to_metric::*;
 pub(crate) use self::logplex::*;
 #[cfg(feature = "sinks-loki")]
 pub(crate) use self::loki::*;
+#[cfg(feature = "sources-loki")]
+pub(crate) use self::loki_source::*;
 #[cfg(feature = "transforms-lua")]
 pub(crate) use self::lua::*;
+#[cfg(feature = "sources-macos_unified_log")]
+pub(crate) use self::macos_unified_log::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
 #[cfg(feature = "sinks-nats")]
 pub(crate) use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sources-okta")]
+pub(crate) use self::okta::*;
+#[cfg(feature = "sinks-opentelemetry")]
+pub(crate) use self::opentelemetry::*;
 pub(crate) use self::parser::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
@@ -245,6 +302,8 @@ pub(crate) use self::remap::*;
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "transforms-sessionize")]
+pub(crate) use self::sessionize::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
@@ -260,8 +319,9 @@ pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
 pub use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, batch::*, common::*, conditions::*, disk_usage::*,
+    encoding_transcode::*, fault_injection::*, heartbeat::*, in_flight::*, open::*, process::*,
+    request_limits::*, scaling::*, socket::*, tcp::*, template::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
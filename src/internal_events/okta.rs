@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct OktaRequestError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for OktaRequestError {
+    fn emit(self) {
+        error!(
+            message = "Request to Okta failed.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OktaParseError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for OktaParseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse System Log events from Okta.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OktaRateLimited {
+    pub delay: Duration,
+}
+
+impl InternalEvent for OktaRateLimited {
+    fn emit(self) {
+        warn!(
+            message = "Rate limit of the Okta System Log API reached, waiting for it to reset.",
+            delay_secs = %self.delay.as_secs(),
+            internal_log_rate_limit = true,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct OktaCheckpointError {
+    pub error: std::io::Error,
+    pub path: String,
+}
+
+impl InternalEvent for OktaCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Could not persist checkpoint.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::IO_FAILED,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{emit, event::metric::Metric};
+use vector_common::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct OpentelemetryUnsupportedMetricError<'a> {
+    pub metric: &'a Metric,
+}
+
+impl<'a> InternalEvent for OpentelemetryUnsupportedMetricError<'a> {
+    fn emit(self) {
+        let reason = "Metric type has no OpenTelemetry equivalent.";
+        error!(
+            message = reason,
+            error_code = "unsupported_metric",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            value = ?self.metric.value(),
+            kind = ?self.metric.kind(),
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "unsupported_metric",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,28 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug, Copy, Clone)]
+pub struct RequestLimitsEvaluationError<'a> {
+    pub limit: &'static str,
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for RequestLimitsEvaluationError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Request limit expression evaluation failed.",
+            limit = %self.limit,
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ScalingSaturation {
+    pub saturation: f64,
+}
+
+impl InternalEvent for ScalingSaturation {
+    fn emit(self) {
+        gauge!("scaling_saturation_ratio", self.saturation);
+    }
+}
+
+#[derive(Debug)]
+pub struct ScalingComponentSignals<'a> {
+    pub component_id: &'a str,
+    pub buffer_fill_ratio: Option<f64>,
+    pub buffer_fill_rate: Option<f64>,
+    pub buffer_time_to_full_seconds: Option<f64>,
+    pub source_lag_seconds: Option<f64>,
+}
+
+impl<'a> InternalEvent for ScalingComponentSignals<'a> {
+    fn emit(self) {
+        let signals = [
+            ("scaling_buffer_fill_ratio", self.buffer_fill_ratio),
+            ("scaling_buffer_fill_rate", self.buffer_fill_rate),
+            (
+                "scaling_buffer_time_to_full_seconds",
+                self.buffer_time_to_full_seconds,
+            ),
+            ("scaling_source_lag_seconds", self.source_lag_seconds),
+        ];
+        for (name, value) in signals {
+            if let Some(value) = value {
+                gauge!(name, value, "component_id" => self.component_id.to_owned());
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScalingWebhookError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for ScalingWebhookError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed sending scaling signals to the webhook.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
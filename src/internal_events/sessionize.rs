@@ -0,0 +1,17 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SessionizeSessionClosed {
+    pub reason: &'static str,
+}
+
+impl InternalEvent for SessionizeSessionClosed {
+    fn emit(self) {
+        trace!(message = "Session closed.", reason = %self.reason);
+        counter!(
+            "sessions_closed_total", 1,
+            "reason" => self.reason,
+        );
+    }
+}
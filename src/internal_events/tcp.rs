@@ -149,3 +149,21 @@ impl InternalEvent for TcpBytesReceived {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct TcpConnectionsPerIpLimitReached {
+    pub peer_addr: SocketAddr,
+    pub limit: u32,
+}
+
+impl InternalEvent for TcpConnectionsPerIpLimitReached {
+    fn emit(self) {
+        warn!(
+            message = "Closing connection, its IP address has the maximum number of connections open.",
+            peer_addr = %self.peer_addr,
+            limit = self.limit,
+            internal_log_rate_limit = true,
+        );
+        counter!("connection_rejected_total", 1, "mode" => "tcp");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::emit;
 use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
 
 #[derive(Debug)]
@@ -29,3 +30,25 @@ impl InternalEvent for ThrottleEventDiscarded {
         })
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct ThrottleSyncError {
+    pub error: redis::RedisError,
+}
+
+impl InternalEvent for ThrottleSyncError {
+    fn emit(self) {
+        error!(
+            message = "Failed to sync the throttle counts with Redis.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -63,3 +63,19 @@ impl InternalEvent for UdpSendIncompleteError {
         emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
     }
 }
+
+#[derive(Debug)]
+pub struct UdpReceiveBufferOverflow {
+    pub count: u64,
+}
+
+impl InternalEvent for UdpReceiveBufferOverflow {
+    fn emit(self) {
+        warn!(
+            message = "Datagrams were dropped because the socket receive buffer was full.",
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("udp_receive_buffer_dropped_datagrams_total", self.count);
+    }
+}
@@ -62,11 +62,15 @@ pub mod amqp;
 pub mod api;
 pub mod app;
 pub mod async_read;
+pub mod audit;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub mod bench;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
+pub mod convert;
+pub mod dead_letter;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
 #[cfg(feature = "gcp")]
@@ -87,6 +91,8 @@ pub mod net;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
+pub mod replay;
+pub mod request_capture;
 pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
@@ -118,6 +124,8 @@ pub(crate) mod utilization;
 pub mod validate;
 #[cfg(windows)]
 pub mod vector_windows;
+pub mod vrl_cli;
+pub mod zstd_dictionary;
 
 pub use source_sender::SourceSender;
 pub use vector_common::{shutdown, Error, Result};
@@ -0,0 +1,448 @@
+#![allow(missing_docs)]
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use flate2::read::MultiGzDecoder;
+use futures::{stream, StreamExt};
+use lookup::PathPrefix;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::{
+    config::{self, log_schema, unit_test::UnitTestStreamSourceConfig, ComponentKey},
+    event::{Event, LogEvent, Value},
+    signal::{SignalHandler, SignalTo},
+    topology,
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The archive to replay.
+    ///
+    /// Archives are newline-delimited JSON, one log event per line, as written by the `file` sink
+    /// with the `json` codec or by `vector tap --format json`. Archives ending in `.gz` are
+    /// decompressed.
+    archive: PathBuf,
+
+    /// The ID of the source to inject the replayed events into.
+    ///
+    /// The source is replaced by the archive for the duration of the replay, and all other sources
+    /// are disabled, so that only the replayed events flow through the topology.
+    #[arg(long)]
+    source: String,
+
+    /// How to set the timestamp of replayed events.
+    #[arg(long, value_enum, default_value_t = TimestampMode::Original)]
+    timestamps: TimestampMode,
+
+    /// The maximum number of events to replay per second. Defaults to replaying as fast as the
+    /// topology accepts events.
+    #[arg(long)]
+    rate: Option<NonZeroU32>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Vector config files to load the topology from. If none are specified the
+    /// default config path `/etc/vector/vector.toml` will be targeted.
+    #[arg(id = "config", short, long, value_delimiter(','))]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// How the timestamps of replayed events are set.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampMode {
+    /// Keep the timestamps recorded in the archive.
+    Original,
+
+    /// Shift all timestamps by the same offset so that the first event is stamped with the time
+    /// the replay started, preserving the spacing between events.
+    Shift,
+
+    /// Stamp each event with the time it is replayed.
+    Now,
+}
+
+/// The number of events read from the archive ahead of the topology.
+const READ_AHEAD: usize = 1024;
+
+pub async fn cmd(opts: &Opts, signal_handler: &mut SignalHandler) -> exitcode::ExitCode {
+    // Only the first timestamp is read up front, the events are read as they are replayed.
+    let first_timestamp = match opts.timestamps {
+        TimestampMode::Shift => {
+            open_archive(&opts.archive).and_then(|archive| first_timestamp(parse_ndjson(archive)))
+        }
+        TimestampMode::Original | TimestampMode::Now => Ok(None),
+    };
+    let archive = first_timestamp.and_then(|first_timestamp| {
+        open_archive(&opts.archive).map(|archive| (archive, first_timestamp))
+    });
+    let (archive, first_timestamp) = match archive {
+        Ok(archive) => archive,
+        Err(error) => {
+            error!(
+                message = "Failed to read archive.",
+                path = ?opts.archive,
+                %error
+            );
+            return exitcode::NOINPUT;
+        }
+    };
+
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+    let mut builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, warnings)) => {
+            for warning in warnings {
+                warn!("{}", warning);
+            }
+            builder
+        }
+        Err(errors) => {
+            for error in errors {
+                error!(message = "Configuration error.", %error);
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    let target = ComponentKey::from(opts.source.as_str());
+    if !builder.sources.contains_key(&target) {
+        error!(message = "Source not found in configuration.", source = %opts.source);
+        return exitcode::CONFIG;
+    }
+
+    // Disable the other sources by replacing them with empty streams, which keeps the inputs of
+    // downstream components valid.
+    let others = builder
+        .sources
+        .keys()
+        .filter(|key| **key != target)
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in others {
+        builder.add_source(key.id(), UnitTestStreamSourceConfig::new(stream::empty()));
+    }
+    let failed = Arc::new(AtomicBool::new(false));
+    builder.add_source(
+        target.id(),
+        UnitTestStreamSourceConfig::new(replay_stream(
+            archive,
+            opts.timestamps,
+            first_timestamp,
+            opts.rate,
+            Arc::clone(&failed),
+        )),
+    );
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => {
+            for error in errors {
+                error!(message = "Configuration error.", %error);
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+    let (topology, _) = match topology::start_validated(config, diff, pieces).await {
+        Some(started) => started,
+        None => return exitcode::CONFIG,
+    };
+
+    info!(message = "Replaying archive.", path = ?opts.archive);
+
+    let mut signals = signal_handler.subscribe();
+    let interrupted = tokio::select! {
+        _ = topology.sources_finished() => {
+            info!("Replay finished, flushing sinks.");
+            false
+        }
+        Ok(SignalTo::Shutdown | SignalTo::Quit) = signals.recv() => {
+            info!("Replay interrupted.");
+            true
+        }
+    };
+    topology.stop().await;
+
+    if interrupted {
+        // Only part of the archive was replayed, so the replay can be run again.
+        exitcode::TEMPFAIL
+    } else if failed.load(Ordering::Relaxed) {
+        exitcode::DATAERR
+    } else {
+        exitcode::OK
+    }
+}
+
+pub(crate) fn read_archive(path: &Path) -> io::Result<Vec<LogEvent>> {
+    parse_ndjson(open_archive(path)?).collect()
+}
+
+/// Opens the archive, decompressing it if needed.
+fn open_archive(path: &Path) -> io::Result<Box<dyn BufRead + Send>> {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    if extension == Some("parquet") {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Parquet archives are not supported by this build; convert the archive to newline-delimited JSON",
+        ));
+    }
+
+    let file = File::open(path)?;
+    let reader: Box<dyn Read + Send> = if extension == Some("gz") {
+        Box::new(MultiGzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    Ok(Box::new(BufReader::new(reader)))
+}
+
+/// Parses the events of the archive, a line at a time.
+fn parse_ndjson(reader: impl BufRead) -> impl Iterator<Item = io::Result<LogEvent>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(parse_line(index, &line)),
+            Err(error) => Some(Err(error)),
+        })
+}
+
+fn parse_line(index: usize, line: &str) -> io::Result<LogEvent> {
+    let invalid = |error: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {}: {}", index + 1, error),
+        )
+    };
+    let json = serde_json::from_str::<serde_json::Value>(line)
+        .map_err(|error| invalid(error.to_string()))?;
+    let mut log = LogEvent::try_from(json).map_err(|error| invalid(error.to_string()))?;
+    parse_timestamp(&mut log);
+    Ok(log)
+}
+
+/// Archived timestamps are serialized as RFC 3339 strings; turn them back into timestamps.
+fn parse_timestamp(log: &mut LogEvent) {
+    let Some(key) = log_schema().timestamp_key() else {
+        return;
+    };
+    let parsed = log
+        .get((PathPrefix::Event, key))
+        .and_then(Value::as_str)
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|value| value.with_timezone(&Utc));
+    if let Some(timestamp) = parsed {
+        log.insert((PathPrefix::Event, key), timestamp);
+    }
+}
+
+fn retimestamp(log: &mut LogEvent, mode: TimestampMode, offset: Option<chrono::Duration>) {
+    let Some(key) = log_schema().timestamp_key() else {
+        return;
+    };
+    let path = (PathPrefix::Event, key);
+    match mode {
+        TimestampMode::Original => {}
+        TimestampMode::Now => {
+            log.insert(path, Utc::now());
+        }
+        TimestampMode::Shift => {
+            let shifted = offset.and_then(|offset| match log.get(path) {
+                Some(Value::Timestamp(timestamp)) => Some(*timestamp + offset),
+                _ => None,
+            });
+            if let Some(timestamp) = shifted {
+                log.insert(path, timestamp);
+            }
+        }
+    }
+}
+
+/// Reads events until the first one with a timestamp, and returns its timestamp.
+fn first_timestamp(
+    events: impl Iterator<Item = io::Result<LogEvent>>,
+) -> io::Result<Option<DateTime<Utc>>> {
+    let Some(key) = log_schema().timestamp_key() else {
+        return Ok(None);
+    };
+    for log in events {
+        if let Some(Value::Timestamp(timestamp)) = log?.get((PathPrefix::Event, key)) {
+            return Ok(Some(*timestamp));
+        }
+    }
+    Ok(None)
+}
+
+fn replay_stream(
+    archive: Box<dyn BufRead + Send>,
+    mode: TimestampMode,
+    first_timestamp: Option<DateTime<Utc>>,
+    rate: Option<NonZeroU32>,
+    failed: Arc<AtomicBool>,
+) -> stream::BoxStream<'static, Event> {
+    let offset = (mode == TimestampMode::Shift)
+        .then(|| first_timestamp.map(|timestamp| Utc::now() - timestamp))
+        .flatten();
+
+    // The archive is read on a blocking thread, as the topology accepts its events.
+    let (tx, rx) = mpsc::channel(READ_AHEAD);
+    tokio::task::spawn_blocking(move || {
+        for log in parse_ndjson(archive) {
+            match log {
+                Ok(log) => {
+                    if tx.blocking_send(log).is_err() {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    error!(message = "Failed to read archive, stopping the replay.", %error);
+                    failed.store(true, Ordering::Relaxed);
+                    break;
+                }
+            }
+        }
+    });
+
+    let events = ReceiverStream::new(rx).map(move |mut log| {
+        retimestamp(&mut log, mode, offset);
+        Event::Log(log)
+    });
+
+    match rate {
+        None => events.boxed(),
+        Some(rate) => {
+            let period = Duration::from_secs(1) / rate.get();
+            tokio_stream::StreamExt::throttle(events, period).boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARCHIVE: &str = r#"{"message":"first","timestamp":"2023-05-01T12:00:00Z"}
+
+{"message":"second","timestamp":"2023-05-01T12:00:05Z"}
+"#;
+
+    fn parse(archive: &str) -> io::Result<Vec<LogEvent>> {
+        parse_ndjson(archive.as_bytes()).collect()
+    }
+
+    #[test]
+    fn parses_ndjson_archive() {
+        let events = parse(ARCHIVE).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["message"], "second".into());
+        assert!(matches!(
+            events[0].get("timestamp"),
+            Some(Value::Timestamp(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        let error = parse("[1, 2]\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("line 1:"));
+    }
+
+    #[test]
+    fn shift_preserves_spacing() {
+        let mut events = parse(ARCHIVE).unwrap();
+        let now = DateTime::parse_from_rfc3339("2023-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let first = first_timestamp(parse_ndjson(ARCHIVE.as_bytes())).unwrap();
+        let offset = first.map(|first| now - first);
+        for log in &mut events {
+            retimestamp(log, TimestampMode::Shift, offset);
+        }
+
+        assert_eq!(events[0]["timestamp"], Value::Timestamp(now));
+        assert_eq!(
+            events[1]["timestamp"],
+            Value::Timestamp(now + chrono::Duration::seconds(5))
+        );
+    }
+
+    #[test]
+    fn first_timestamp_stops_at_first_event() {
+        let archive = format!("{}\nnot json\n", ARCHIVE.lines().next().unwrap());
+        let first = first_timestamp(parse_ndjson(archive.as_bytes())).unwrap();
+        assert_eq!(
+            first,
+            Some(
+                DateTime::parse_from_rfc3339("2023-05-01T12:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+    }
+}
@@ -119,7 +119,7 @@ impl SinkConfig for AppsignalSinkConfig {
         let buffer = JsonArrayBuffer::new(batch_settings.size);
 
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
 
         let sink = BatchedHttpSink::new(
             self.clone(),
@@ -16,11 +16,8 @@ use futures_util::TryFutureExt;
 use indexmap::IndexMap;
 use tokio::sync::oneshot;
 use tower::{
-    buffer::Buffer,
-    limit::{ConcurrencyLimit, RateLimit},
-    retry::Retry,
-    timeout::Timeout,
-    Service, ServiceBuilder, ServiceExt,
+    buffer::Buffer, limit::ConcurrencyLimit, retry::Retry, timeout::Timeout, Service,
+    ServiceBuilder, ServiceExt,
 };
 use vector_common::{json_size::JsonSize, request_metadata::MetaDescriptive};
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
@@ -33,14 +30,16 @@ use crate::{
             sink::BatchCloudwatchRequest, CloudwatchKey,
         },
         util::{
-            retries::FixedRetryPolicy, EncodedLength, TowerRequestConfig, TowerRequestSettings,
+            retries::FixedRetryPolicy,
+            service::{DynamicRateLimit, DynamicRateLimitLayer},
+            EncodedLength, TowerRequestConfig, TowerRequestSettings,
         },
     },
 };
 
 type Svc = Buffer<
     ConcurrencyLimit<
-        RateLimit<
+        DynamicRateLimit<
             Retry<
                 FixedRetryPolicy<CloudwatchRetryLogic<()>>,
                 Buffer<Timeout<CloudwatchLogsSvc>, Vec<InputLogEvent>>,
@@ -178,10 +177,11 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
             let svc = ServiceBuilder::new()
                 .buffer(1)
                 .concurrency_limit(1)
-                .rate_limit(
+                .layer(DynamicRateLimitLayer::new(
                     self.request_settings.rate_limit_num,
                     self.request_settings.rate_limit_duration,
-                )
+                    self.request_settings.limits.clone(),
+                ))
                 .retry(
                     self.request_settings
                         .retry_policy(CloudwatchRetryLogic::new()),
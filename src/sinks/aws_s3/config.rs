@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{collections::BTreeMap, convert::TryInto};
 
 use aws_sdk_s3::Client as S3Client;
 use codecs::{
@@ -208,7 +208,19 @@ impl S3SinkConfig {
             .cloned()
             .map(|ssekms_key_id| Template::try_from(ssekms_key_id.as_str()))
             .transpose()?;
-        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
+        let tags = self
+            .options
+            .tags
+            .as_ref()
+            .map(|tags| {
+                tags.iter()
+                    .map(|(name, value)| {
+                        Template::try_from(value.as_str()).map(|value| (name.clone(), value))
+                    })
+                    .collect::<Result<BTreeMap<_, _>, _>>()
+            })
+            .transpose()?;
+        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id, tags);
 
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
@@ -84,8 +84,10 @@ impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for S3RequestOptions {
         };
 
         let ssekms_key_id = s3metadata.partition_key.ssekms_key_id.clone();
+        let tags = s3metadata.partition_key.tags.clone();
         let mut s3_options = self.api_options.clone();
         s3_options.ssekms_key_id = ssekms_key_id;
+        s3_options.tags = tags;
 
         let extension = self
             .filename_extension
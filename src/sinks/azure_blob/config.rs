@@ -12,7 +12,10 @@ use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
-            self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
+            self,
+            config::{AzureBlobRetryLogic, AzureBlobType},
+            service::AzureBlobService,
+            sink::AzureBlobSink,
         },
         util::{
             partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
@@ -116,8 +119,14 @@ pub struct AzureBlobSinkConfig {
     ///
     /// This ensures there are no name collisions, and can be useful in high-volume workloads where
     /// blob keys must be unique.
+    ///
+    /// Ignored when `blob_type` is `append`.
     pub blob_append_uuid: Option<bool>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub blob_type: AzureBlobType,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
@@ -156,6 +165,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_prefix: default_blob_prefix(),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
+            blob_type: AzureBlobType::default(),
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::default()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
@@ -199,6 +209,7 @@ impl SinkConfig for AzureBlobSinkConfig {
 const DEFAULT_KEY_PREFIX: &str = "blob/%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
+const DEFAULT_APPEND_FILENAME_TIME_FORMAT: &str = "%Y-%m-%dT%H";
 
 impl AzureBlobSinkConfig {
     pub fn build_processor(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
@@ -207,19 +218,23 @@ impl AzureBlobSinkConfig {
             .unwrap_with(&TowerRequestConfig::default().rate_limit_num(250));
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(client, self.blob_type));
 
         // Configure our partitioning/batching.
         let batcher_settings = self.batch.into_batcher_settings()?;
 
-        let blob_time_format = self
-            .blob_time_format
-            .as_ref()
-            .cloned()
-            .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into());
-        let blob_append_uuid = self
-            .blob_append_uuid
-            .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
+        let append = self.blob_type == AzureBlobType::Append;
+        let blob_time_format = self.blob_time_format.as_ref().cloned().unwrap_or_else(|| {
+            if append {
+                DEFAULT_APPEND_FILENAME_TIME_FORMAT.into()
+            } else {
+                DEFAULT_FILENAME_TIME_FORMAT.into()
+            }
+        });
+        let blob_append_uuid = !append
+            && self
+                .blob_append_uuid
+                .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
@@ -226,6 +226,7 @@ impl AzureBlobSinkConfig {
                 blob_prefix: Default::default(),
                 blob_time_format: None,
                 blob_append_uuid: None,
+                blob_type: Default::default(),
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
                 compression: Compression::None,
                 batch: Default::default(),
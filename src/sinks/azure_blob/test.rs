@@ -22,6 +22,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
+        blob_type: Default::default(),
         encoding,
         compression: Compression::gzip_default(),
         batch: Default::default(),
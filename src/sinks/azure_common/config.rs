@@ -3,7 +3,7 @@ use std::sync::Arc;
 use azure_core::{error::HttpError, RetryOptions};
 use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
 use azure_storage::{prelude::*, CloudLocation, ConnectionString};
-use azure_storage_blobs::{blob::operations::PutBlockBlobResponse, prelude::*};
+use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
@@ -12,6 +12,7 @@ use vector_common::{
     json_size::JsonSize,
     request_metadata::{MetaDescriptive, RequestMetadata},
 };
+use vector_config::configurable_component;
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
 use crate::{
@@ -48,6 +49,24 @@ pub struct AzureBlobMetadata {
     pub finalizers: EventFinalizers,
 }
 
+/// The type of blobs to write.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AzureBlobType {
+    /// Each request creates a new block blob.
+    #[default]
+    Block,
+
+    /// Requests are appended to the blob named by the blob prefix and time format, which is
+    /// created as needed.
+    ///
+    /// When a blob reaches the limit of 50,000 blocks, writes roll over to a new blob whose name
+    /// has a `-1`, `-2`, ... suffix before the extension. No UUID is appended to blob names, and
+    /// the time format defaults to one blob per hour.
+    Append,
+}
+
 #[derive(Debug, Clone)]
 pub struct AzureBlobRetryLogic;
 
@@ -63,7 +82,6 @@ impl RetryLogic for AzureBlobRetryLogic {
 
 #[derive(Debug)]
 pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
     pub count: usize,
     pub events_byte_size: JsonSize,
     pub byte_size: usize,
@@ -1,30 +1,162 @@
 use std::{
+    collections::HashMap,
     result::Result as StdResult,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
 };
 
+use azure_core::error::HttpError;
 use azure_storage_blobs::prelude::*;
+use bytes::Bytes;
 use futures::future::BoxFuture;
 use tower::Service;
 use tracing::Instrument;
 
-use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse};
+use crate::sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse, AzureBlobType};
+
+/// The largest block accepted by the Append Block operation.
+const MAX_APPEND_BLOCK_BYTES: usize = 4 * 1024 * 1024;
+
+/// The largest number of blocks an append blob can hold.
+const MAX_APPEND_BLOCKS: u32 = 50_000;
+
+/// Append blobs above this count that aren't being written to are forgotten.
+const MAX_TRACKED_APPEND_BLOBS: usize = 64;
+
+type Error = Box<dyn std::error::Error + std::marker::Send + std::marker::Sync>;
+
+/// What is known about an append blob and its rollovers.
+#[derive(Debug, Default)]
+struct AppendState {
+    /// How many times the blob rolled over because it was full.
+    generation: u32,
+
+    /// The number of blocks in the current generation of the blob, if known.
+    committed_blocks: Option<u32>,
+}
 
 #[derive(Clone)]
 pub(crate) struct AzureBlobService {
     client: Arc<ContainerClient>,
+    blob_type: AzureBlobType,
+    append_blobs: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<AppendState>>>>>,
 }
 
 impl AzureBlobService {
-    pub fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+    pub fn new(client: Arc<ContainerClient>, blob_type: AzureBlobType) -> AzureBlobService {
+        AzureBlobService {
+            client,
+            blob_type,
+            append_blobs: Arc::default(),
+        }
+    }
+
+    async fn put_block_blob(&self, request: &AzureBlobRequest) -> StdResult<(), Error> {
+        let client = self
+            .client
+            .blob_client(request.metadata.partition_key.as_str());
+        let blob = client
+            .put_block_blob(request.blob_data.clone())
+            .content_type(request.content_type);
+        let blob = match request.content_encoding {
+            Some(encoding) => blob.content_encoding(encoding),
+            None => blob,
+        };
+
+        blob.into_future()
+            .instrument(info_span!("request").or_current())
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    /// Appends the request to its append blob, creating the blob if needed, and rolling over to a
+    /// new blob when it is full.
+    ///
+    /// Appends to the same blob are serialized, so that the blocks of a request are never
+    /// interleaved with those of another.
+    async fn append_blob(&self, request: &AzureBlobRequest) -> StdResult<(), Error> {
+        let name = request.metadata.partition_key.as_str();
+        let state = self.append_state(name);
+        let mut state = state.lock().await;
+
+        let blocks: Vec<Bytes> = (0..request.blob_data.len())
+            .step_by(MAX_APPEND_BLOCK_BYTES)
+            .map(|start| {
+                let end = (start + MAX_APPEND_BLOCK_BYTES).min(request.blob_data.len());
+                request.blob_data.slice(start..end)
+            })
+            .collect();
+
+        let full = state.committed_blocks.map_or(false, |committed| {
+            committed as usize + blocks.len() > MAX_APPEND_BLOCKS as usize
+        });
+        if full {
+            state.generation += 1;
+            state.committed_blocks = None;
+        }
+
+        let mut created = false;
+        let mut appended = 0;
+        while appended < blocks.len() {
+            let client = self
+                .client
+                .blob_client(rolled_blob_name(name, state.generation));
+
+            match client
+                .append_block(blocks[appended].clone())
+                .into_future()
+                .instrument(info_span!("request").or_current())
+                .await
+            {
+                Ok(response) => {
+                    state.committed_blocks = Some(response.blob_committed_block_count);
+                    appended += 1;
+                }
+                Err(error) => match error_code(&error) {
+                    Some("BlobNotFound") if !created => {
+                        let blob = client.put_append_blob().content_type(request.content_type);
+                        let blob = match request.content_encoding {
+                            Some(encoding) => blob.content_encoding(encoding),
+                            None => blob,
+                        };
+                        blob.into_future()
+                            .instrument(info_span!("request").or_current())
+                            .await?;
+                        created = true;
+                        state.committed_blocks = Some(0);
+                    }
+                    // Only roll over before the first block, so that a request is never split
+                    // across blobs.
+                    Some("BlockCountExceedsLimit") if appended == 0 => {
+                        debug!(
+                            message = "Append blob is full, rolling over.",
+                            blob = %rolled_blob_name(name, state.generation),
+                        );
+                        state.generation += 1;
+                        state.committed_blocks = None;
+                        created = false;
+                    }
+                    _ => return Err(error.into()),
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn append_state(&self, name: &str) -> Arc<tokio::sync::Mutex<AppendState>> {
+        let mut blobs = self.append_blobs.lock().expect("mutex poisoned");
+        if blobs.len() > MAX_TRACKED_APPEND_BLOBS && !blobs.contains_key(name) {
+            blobs.retain(|_, state| Arc::strong_count(state) > 1);
+        }
+        Arc::clone(blobs.entry(name.to_owned()).or_default())
     }
 }
 
 impl Service<AzureBlobRequest> for AzureBlobService {
     type Response = AzureBlobResponse;
-    type Error = Box<dyn std::error::Error + std::marker::Send + std::marker::Sync>;
+    type Error = Error;
     type Future = BoxFuture<'static, StdResult<Self::Response, Self::Error>>;
 
     // Emission of an internal event in case of errors is handled upstream by the caller.
@@ -37,26 +169,13 @@ impl Service<AzureBlobRequest> for AzureBlobService {
         let this = self.clone();
 
         Box::pin(async move {
-            let client = this
-                .client
-                .blob_client(request.metadata.partition_key.as_str());
             let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
+            let result = match this.blob_type {
+                AzureBlobType::Block => this.put_block_blob(&request).await,
+                AzureBlobType::Append => this.append_blob(&request).await,
             };
 
-            let result = blob
-                .into_future()
-                .instrument(info_span!("request").or_current())
-                .await
-                .map_err(|err| err.into());
-
-            result.map(|inner| AzureBlobResponse {
-                inner,
+            result.map(|()| AzureBlobResponse {
                 count: request.metadata.count,
                 events_byte_size: request.metadata.byte_size,
                 byte_size,
@@ -64,3 +183,42 @@ impl Service<AzureBlobRequest> for AzureBlobService {
         })
     }
 }
+
+fn error_code(error: &azure_core::Error) -> Option<&str> {
+    error.downcast_ref::<HttpError>()?.error_code()
+}
+
+/// Returns the name of a rolled over append blob, by inserting the generation before the
+/// extension of the original name.
+fn rolled_blob_name(name: &str, generation: u32) -> String {
+    if generation == 0 {
+        return name.to_owned();
+    }
+
+    let file_start = name.rfind('/').map_or(0, |index| index + 1);
+    match name[file_start..].find('.') {
+        Some(dot) => {
+            let (stem, extension) = name.split_at(file_start + dot);
+            format!("{}-{}{}", stem, generation, extension)
+        }
+        None => format!("{}-{}", name, generation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rolled_blob_name;
+
+    #[test]
+    fn rolls_blob_names() {
+        assert_eq!(
+            rolled_blob_name("logs/2023-01-15T10.log.gz", 0),
+            "logs/2023-01-15T10.log.gz"
+        );
+        assert_eq!(
+            rolled_blob_name("logs/2023-01-15T10.log.gz", 2),
+            "logs/2023-01-15T10-2.log.gz"
+        );
+        assert_eq!(rolled_blob_name("logs.d/2023", 1), "logs.d/2023-1");
+    }
+}
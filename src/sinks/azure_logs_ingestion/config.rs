@@ -0,0 +1,204 @@
+use std::sync::Arc;
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::{RealtimeSizeBasedDefaultBatchSettings, UriSerde},
+    },
+};
+
+use super::{
+    encoder::LogsIngestionEncoder,
+    service::{LogsIngestionRetryLogic, LogsIngestionService},
+    sink::AzureLogsIngestionSink,
+};
+
+/// The maximum size of a request accepted by the Logs Ingestion API.
+pub(super) const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+fn default_token_resource() -> String {
+    "https://monitor.azure.com".to_owned()
+}
+
+fn default_timestamp_field() -> OptionalValuePath {
+    owned_value_path!("TimeGenerated").into()
+}
+
+/// Configuration for the `azure_logs_ingestion` sink.
+#[configurable_component(sink(
+    "azure_logs_ingestion",
+    "Publish log events to Azure Monitor Logs through the Logs Ingestion API."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureLogsIngestionConfig {
+    /// The logs ingestion endpoint of the data collection rule, or of its data collection endpoint.
+    #[configurable(metadata(
+        docs::examples = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+    ))]
+    pub endpoint: UriSerde,
+
+    /// The immutable ID of the data collection rule.
+    #[configurable(metadata(docs::examples = "dcr-000a00a000a00000a000000aa000a0aa"))]
+    pub dcr_immutable_id: String,
+
+    /// The stream of the data collection rule to send events to.
+    ///
+    /// The rule routes each stream to its table, so templating the stream name routes events to
+    /// tables dynamically. Events for which the template cannot be rendered are dropped.
+    #[configurable(metadata(docs::examples = "Custom-MyTable_CL"))]
+    #[configurable(metadata(docs::examples = "Custom-{{ .service }}_CL"))]
+    pub stream_name: Template,
+
+    /// The resource access tokens are requested for.
+    ///
+    /// Tokens are obtained with the default Azure credential chain, which includes the
+    /// `AZURE_TENANT_ID`, `AZURE_CLIENT_ID`, and `AZURE_CLIENT_SECRET` environment variables,
+    /// managed identities, and the Azure CLI. The identity needs the Monitoring Metrics Publisher
+    /// role on the data collection rule.
+    #[configurable(metadata(docs::examples = "https://monitor.azure.us"))]
+    #[serde(default = "default_token_resource")]
+    pub token_resource: String,
+
+    /// The field the timestamp of the events is written to, as expected by the stream.
+    ///
+    /// The timestamp is moved from the `log_schema.timestamp_key` field, or set to the current
+    /// time if the event has none. Set to `""` to send events as they are.
+    #[configurable(metadata(docs::examples = "TimeGenerated"))]
+    #[serde(default = "default_timestamp_field")]
+    pub timestamp_field: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureLogsIngestionConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+            dcr_immutable_id = "dcr-000a00a000a00000a000000aa000a0aa"
+            stream_name = "Custom-MyTable_CL"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_logs_ingestion")]
+impl SinkConfig for AzureLogsIngestionConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.dcr_immutable_id.is_empty() {
+            return Err("dcr_immutable_id can't be an empty string".into());
+        }
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
+        let credential: Arc<dyn TokenCredential> = Arc::new(AutoRefreshingTokenCredential::new(
+            Arc::new(DefaultAzureCredential::default()),
+        ));
+
+        let healthcheck = healthcheck(Arc::clone(&credential), self.token_resource.clone()).boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let encoder = LogsIngestionEncoder::new(
+            self.encoding.clone(),
+            self.timestamp_field.path.clone(),
+            self.compression,
+            MAX_REQUEST_BYTES,
+        );
+
+        let service = LogsIngestionService::new(
+            client,
+            self.endpoint.with_default_parts(),
+            self.dcr_immutable_id.clone(),
+            credential,
+            self.token_resource.clone(),
+        );
+        let service = ServiceBuilder::new()
+            .settings(request_settings, LogsIngestionRetryLogic)
+            .service(service);
+
+        let sink =
+            AzureLogsIngestionSink::new(self.stream_name.clone(), batch_settings, encoder, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that an access token can be obtained, as the API has no endpoint to check without
+/// sending events.
+async fn healthcheck(credential: Arc<dyn TokenCredential>, resource: String) -> crate::Result<()> {
+    credential.get_token(&resource).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureLogsIngestionConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let cfg = toml::from_str::<AzureLogsIngestionConfig>(
+            r#"
+            endpoint = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+            dcr_immutable_id = "dcr-000a00a000a00000a000000aa000a0aa"
+            stream_name = "Custom-{{ .service }}_CL"
+        "#,
+        )
+        .unwrap();
+        assert!(cfg.stream_name.is_dynamic());
+        assert_eq!(cfg.token_resource, "https://monitor.azure.com");
+        assert_eq!(
+            cfg.timestamp_field.path,
+            Some(owned_value_path!("TimeGenerated"))
+        );
+    }
+}
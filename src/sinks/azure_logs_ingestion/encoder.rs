@@ -0,0 +1,209 @@
+use std::io::Write;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::{OwnedValuePath, PathPrefix};
+
+use crate::{
+    internal_events::EncoderSerializeError,
+    sinks::{prelude::*, util::Compressor},
+};
+
+use super::service::LogsIngestionRequest;
+
+/// Encodes batches of events as JSON arrays, split in as many requests as needed to keep each
+/// under the size limit of the API.
+#[derive(Clone)]
+pub(super) struct LogsIngestionEncoder {
+    transformer: Transformer,
+    timestamp_field: Option<OwnedValuePath>,
+    compression: Compression,
+    max_request_bytes: usize,
+}
+
+/// The events of a request being encoded.
+#[derive(Default)]
+struct Chunk {
+    body: Vec<u8>,
+    finalizers: EventFinalizers,
+    count: usize,
+    byte_size: usize,
+    json_size: JsonSize,
+}
+
+impl LogsIngestionEncoder {
+    pub(super) const fn new(
+        transformer: Transformer,
+        timestamp_field: Option<OwnedValuePath>,
+        compression: Compression,
+        max_request_bytes: usize,
+    ) -> Self {
+        Self {
+            transformer,
+            timestamp_field,
+            compression,
+            max_request_bytes,
+        }
+    }
+
+    fn encode_event(&self, mut event: Event) -> serde_json::Result<Vec<u8>> {
+        self.transformer.transform(&mut event);
+        let mut log = event.into_log();
+
+        if let Some(timestamp_field) = &self.timestamp_field {
+            let timestamp = match log.remove_timestamp() {
+                Some(Value::Timestamp(timestamp)) => timestamp,
+                _ => Utc::now(),
+            };
+            log.insert((PathPrefix::Event, timestamp_field), timestamp);
+        }
+
+        serde_json::to_vec(&log)
+    }
+
+    /// Encodes the events sent to the stream, dropping those that don't fit in a request on their
+    /// own.
+    pub(super) fn encode(&self, stream: String, events: Vec<Event>) -> Vec<LogsIngestionRequest> {
+        let mut requests = Vec::new();
+        let mut chunk = Chunk::default();
+
+        for mut event in events {
+            let finalizers = event.take_finalizers();
+            let byte_size = event.size_of();
+            let json_size = event.estimated_json_encoded_size_of();
+
+            let encoded = match self.encode_event(event) {
+                Ok(encoded) => encoded,
+                Err(error) => {
+                    let error: crate::Error = error.into();
+                    emit!(EncoderSerializeError { error: &error });
+                    finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
+            };
+            // The brackets of the array.
+            if encoded.len() + 2 > self.max_request_bytes {
+                let error: crate::Error = format!(
+                    "Event of {} bytes exceeds the limit of {} bytes of a request.",
+                    encoded.len(),
+                    self.max_request_bytes
+                )
+                .into();
+                emit!(EncoderSerializeError { error: &error });
+                finalizers.update_status(EventStatus::Rejected);
+                continue;
+            }
+            // The separator and closing bracket.
+            if chunk.count > 0 && chunk.body.len() + encoded.len() + 2 > self.max_request_bytes {
+                let full = std::mem::take(&mut chunk);
+                requests.push(self.finish(stream.clone(), full));
+            }
+
+            chunk.body.push(if chunk.count == 0 { b'[' } else { b',' });
+            chunk.body.extend_from_slice(&encoded);
+            chunk.finalizers.merge(finalizers);
+            chunk.count += 1;
+            chunk.byte_size += byte_size;
+            chunk.json_size += json_size;
+        }
+
+        if chunk.count > 0 {
+            requests.push(self.finish(stream, chunk));
+        }
+        requests
+    }
+
+    fn finish(&self, stream: String, mut chunk: Chunk) -> LogsIngestionRequest {
+        chunk.body.push(b']');
+        let uncompressed_size = chunk.body.len();
+
+        let payload = if self.compression.is_compressed() {
+            let mut compressor = Compressor::from(self.compression);
+            compressor
+                .write_all(&chunk.body)
+                .expect("Writing to a Vec can't fail.");
+            let payload = compressor.into_inner().freeze();
+            EncodeResult::compressed(payload, uncompressed_size)
+        } else {
+            EncodeResult::uncompressed(Bytes::from(chunk.body))
+        };
+
+        let metadata = RequestMetadataBuilder::new(chunk.count, chunk.byte_size, chunk.json_size)
+            .build(&payload);
+
+        LogsIngestionRequest {
+            stream,
+            compression: self.compression,
+            finalizers: chunk.finalizers,
+            payload: payload.into_payload(),
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use lookup::owned_value_path;
+
+    use super::*;
+
+    fn encoder(max_request_bytes: usize) -> LogsIngestionEncoder {
+        LogsIngestionEncoder::new(
+            Transformer::default(),
+            Some(owned_value_path!("TimeGenerated")),
+            Compression::None,
+            max_request_bytes,
+        )
+    }
+
+    fn event(message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap(),
+        );
+        log.into()
+    }
+
+    #[test]
+    fn encodes_json_array_with_time_generated() {
+        let requests = encoder(1024).encode("Custom-MyTable_CL".to_owned(), vec![event("hello")]);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].stream, "Custom-MyTable_CL");
+
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].payload).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "message": "hello",
+                "TimeGenerated": "2023-06-01T12:00:00Z",
+            }])
+        );
+    }
+
+    #[test]
+    fn splits_requests_at_limit() {
+        let events = (0..10).map(|_| event(&"x".repeat(40))).collect();
+        let requests = encoder(256).encode("Custom-MyTable_CL".to_owned(), events);
+
+        assert!(requests.len() > 1);
+        let mut count = 0;
+        for request in &requests {
+            assert!(request.payload.len() <= 256);
+            let body: Vec<serde_json::Value> = serde_json::from_slice(&request.payload).unwrap();
+            assert_eq!(body.len(), request.metadata.event_count());
+            count += body.len();
+        }
+        assert_eq!(count, 10);
+    }
+
+    #[test]
+    fn drops_events_over_limit() {
+        let events = vec![event(&"x".repeat(300)), event("small")];
+        let requests = encoder(256).encode("Custom-MyTable_CL".to_owned(), events);
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].metadata.event_count(), 1);
+    }
+}
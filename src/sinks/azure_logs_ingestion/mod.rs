@@ -0,0 +1,17 @@
+//! Azure Monitor Logs Ingestion sink
+//!
+//! This sink ships log events to Azure Monitor through the Logs Ingestion API, which sends them to
+//! a stream of a data collection rule (DCR). The rule transforms the events and routes each stream
+//! to its table.
+//!
+//! <https://learn.microsoft.com/en-us/azure/azure-monitor/logs/logs-ingestion-api-overview>
+//!
+//! Requests are authenticated with Microsoft Entra ID (formerly Azure AD) access tokens. Events are
+//! partitioned by their rendered stream name, and each batch is split into requests of at most
+//! 1MB, the limit of the API.
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::AzureLogsIngestionConfig;
@@ -0,0 +1,230 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{header, StatusCode};
+use snafu::Snafu;
+use tracing::Instrument;
+
+use crate::{
+    http::HttpClient,
+    sinks::{prelude::*, util::UriSerde},
+};
+
+/// The version of the Logs Ingestion API.
+const API_VERSION: &str = "2023-01-01";
+
+#[derive(Clone)]
+pub struct LogsIngestionRetryLogic;
+
+impl RetryLogic for LogsIngestionRetryLogic {
+    type Error = LogsIngestionError;
+    type Response = LogsIngestionResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            LogsIngestionError::ServerError { code } => {
+                *code == StatusCode::TOO_MANY_REQUESTS || code.is_server_error()
+            }
+            LogsIngestionError::HttpError { .. } | LogsIngestionError::TokenError { .. } => true,
+            LogsIngestionError::InvalidStream { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum LogsIngestionError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+    #[snafu(display("Failed to get an access token: {}", error))]
+    TokenError { error: azure_core::Error },
+    #[snafu(display("Invalid stream name {:?}", stream))]
+    InvalidStream { stream: String },
+}
+
+#[derive(Debug)]
+pub struct LogsIngestionResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for LogsIngestionResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct LogsIngestionRequest {
+    pub stream: String,
+    pub compression: Compression,
+    pub finalizers: EventFinalizers,
+    pub payload: Bytes,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for LogsIngestionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for LogsIngestionRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+#[derive(Clone)]
+pub struct LogsIngestionService {
+    client: HttpClient,
+    endpoint: UriSerde,
+    dcr_immutable_id: String,
+    credential: Arc<dyn TokenCredential>,
+    token_resource: String,
+}
+
+impl LogsIngestionService {
+    pub fn new(
+        client: HttpClient,
+        endpoint: UriSerde,
+        dcr_immutable_id: String,
+        credential: Arc<dyn TokenCredential>,
+        token_resource: String,
+    ) -> Self {
+        Self {
+            client,
+            endpoint,
+            dcr_immutable_id,
+            credential,
+            token_resource,
+        }
+    }
+}
+
+/// The URI of a stream, whose name may only contain letters, digits, `-`, and `_`.
+fn stream_uri(endpoint: &UriSerde, dcr_immutable_id: &str, stream: &str) -> Option<UriSerde> {
+    let valid = !stream.is_empty()
+        && stream
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return None;
+    }
+    endpoint
+        .append_path(&format!(
+            "dataCollectionRules/{}/streams/{}?api-version={}",
+            dcr_immutable_id, stream, API_VERSION
+        ))
+        .ok()
+}
+
+impl Service<LogsIngestionRequest> for LogsIngestionService {
+    type Response = LogsIngestionResponse;
+    type Error = LogsIngestionError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: LogsIngestionRequest) -> Self::Future {
+        let Some(uri) = stream_uri(&self.endpoint, &self.dcr_immutable_id, &request.stream) else {
+            let stream = request.stream;
+            return Box::pin(async move { Err(LogsIngestionError::InvalidStream { stream }) });
+        };
+
+        let mut req =
+            http::Request::post(&uri.uri).header(header::CONTENT_TYPE, "application/json");
+        if let Some(ce) = request.compression.content_encoding() {
+            req = req.header(header::CONTENT_ENCODING, ce);
+        }
+
+        let metadata = request.get_metadata();
+        let body = hyper::Body::from(request.payload);
+        let mut req = req.body(body).unwrap();
+
+        let mut client = self.client.clone();
+        let credential = Arc::clone(&self.credential);
+        let token_resource = self.token_resource.clone();
+
+        Box::pin(async move {
+            let token = credential
+                .get_token(&token_resource)
+                .await
+                .map_err(|error| LogsIngestionError::TokenError { error })?;
+            let authorization = format!("Bearer {}", token.token.secret());
+            req.headers_mut().insert(
+                header::AUTHORIZATION,
+                authorization
+                    .parse()
+                    .expect("Access tokens are valid header values."),
+            );
+
+            match client.call(req).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        Ok(LogsIngestionResponse { metadata })
+                    } else {
+                        Err(LogsIngestionError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(LogsIngestionError::HttpError { error }),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_stream_uri() {
+        let endpoint: UriSerde = "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com"
+            .parse()
+            .unwrap();
+        let uri = stream_uri(&endpoint, "dcr-0000", "Custom-MyTable_CL").unwrap();
+        assert_eq!(
+            uri.uri.to_string(),
+            "https://my-dce-5kyl.eastus-1.ingest.monitor.azure.com/dataCollectionRules/dcr-0000/streams/Custom-MyTable_CL?api-version=2023-01-01"
+        );
+
+        assert!(stream_uri(&endpoint, "dcr-0000", "Custom-My Table").is_none());
+        assert!(stream_uri(&endpoint, "dcr-0000", "").is_none());
+    }
+
+    #[test]
+    fn retries_throttling_and_server_errors() {
+        let logic = LogsIngestionRetryLogic;
+        assert!(logic.is_retriable_error(&LogsIngestionError::ServerError {
+            code: StatusCode::TOO_MANY_REQUESTS
+        }));
+        assert!(logic.is_retriable_error(&LogsIngestionError::ServerError {
+            code: StatusCode::SERVICE_UNAVAILABLE
+        }));
+        assert!(!logic.is_retriable_error(&LogsIngestionError::ServerError {
+            code: StatusCode::PAYLOAD_TOO_LARGE
+        }));
+        assert!(!logic.is_retriable_error(&LogsIngestionError::ServerError {
+            code: StatusCode::FORBIDDEN
+        }));
+    }
+}
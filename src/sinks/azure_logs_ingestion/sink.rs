@@ -0,0 +1,69 @@
+use crate::sinks::prelude::*;
+
+use super::{
+    encoder::LogsIngestionEncoder,
+    service::{LogsIngestionRetryLogic, LogsIngestionService},
+};
+
+/// Partitions events by their rendered stream name.
+struct StreamPartitioner(Template);
+
+impl Partitioner for StreamPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("stream_name"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub struct AzureLogsIngestionSink {
+    stream_name: Template,
+    batch_settings: BatcherSettings,
+    encoder: LogsIngestionEncoder,
+    service: Svc<LogsIngestionService, LogsIngestionRetryLogic>,
+}
+
+impl AzureLogsIngestionSink {
+    pub(super) const fn new(
+        stream_name: Template,
+        batch_settings: BatcherSettings,
+        encoder: LogsIngestionEncoder,
+        service: Svc<LogsIngestionService, LogsIngestionRetryLogic>,
+    ) -> Self {
+        Self {
+            stream_name,
+            batch_settings,
+            encoder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoder = self.encoder;
+        input
+            .batched_partitioned(StreamPartitioner(self.stream_name), self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |stream| (stream, batch)) })
+            // Batches are split into requests under the size limit of the API.
+            .flat_map(move |(stream, events)| futures::stream::iter(encoder.encode(stream, events)))
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for AzureLogsIngestionSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
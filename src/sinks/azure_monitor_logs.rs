@@ -192,7 +192,7 @@ impl SinkConfig for AzureMonitorLogsConfig {
         let time_generated_key = self.time_generated_key.clone().and_then(|k| k.path);
 
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(Some(tls_settings), &cx.proxy)?;
+        let client = HttpClient::new_with_pool(Some(tls_settings), &cx.proxy, &cx.connection_pool)?;
 
         let sink = AzureMonitorLogsSink::new(self, time_generated_key)?;
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
@@ -4,7 +4,11 @@ use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use snafu::ResultExt;
 
-use super::ClickhouseConfig;
+use super::{
+    config::ClickhouseFormat,
+    native::{NativeRow, NativeSink},
+    ClickhouseConfig,
+};
 use crate::{
     codecs::Transformer,
     config::SinkContext,
@@ -14,7 +18,7 @@ use crate::{
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpRetryLogic, HttpSink},
             retries::{RetryAction, RetryLogic},
-            Buffer, TowerRequestConfig,
+            Buffer, TowerRequestConfig, VecBuffer,
         },
         Healthcheck, HealthcheckError, UriParseSnafu, VectorSink,
     },
@@ -25,30 +29,52 @@ pub(crate) async fn build_http_sink(
     cfg: &ClickhouseConfig,
     cx: SinkContext,
 ) -> crate::Result<(VectorSink, Healthcheck)> {
-    let batch = cfg.batch.into_batch_settings()?;
     let request = cfg.request.unwrap_with(&TowerRequestConfig::default());
     let tls_settings = TlsSettings::from_options(&cfg.tls)?;
-    let client = HttpClient::new(tls_settings, &cx.proxy)?;
+    let client = HttpClient::new_with_pool(tls_settings, &cx.proxy, &cx.connection_pool)?;
 
     let config = ClickhouseConfig {
         auth: cfg.auth.choose_one(&cfg.endpoint.auth)?,
         ..cfg.clone()
     };
 
-    let sink = BatchedHttpSink::with_logic(
-        config.clone(),
-        Buffer::new(batch.size, cfg.compression),
-        ClickhouseRetryLogic::default(),
-        request,
-        batch.timeout,
-        client.clone(),
-    )
-    .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error));
+    let sink = match config.format {
+        ClickhouseFormat::JsonEachRow => {
+            if !config.columns.is_empty() {
+                return Err("`columns` is only used by the `native` format.".into());
+            }
+            let batch = cfg.batch.into_batch_settings()?;
+            let sink = BatchedHttpSink::with_logic(
+                config.clone(),
+                Buffer::new(batch.size, cfg.compression),
+                ClickhouseRetryLogic::default(),
+                request,
+                batch.timeout,
+                client.clone(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error));
+            #[allow(deprecated)]
+            VectorSink::from_event_sink(sink)
+        }
+        ClickhouseFormat::Native => {
+            let batch = cfg.batch.into_batch_settings::<VecBuffer<NativeRow>>()?;
+            let sink = BatchedHttpSink::with_logic(
+                NativeSink::new(config.clone())?,
+                VecBuffer::new(batch.size),
+                ClickhouseRetryLogic::default(),
+                request,
+                batch.timeout,
+                client.clone(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error));
+            #[allow(deprecated)]
+            VectorSink::from_event_sink(sink)
+        }
+    };
 
     let healthcheck = healthcheck(client, config).boxed();
 
-    #[allow(deprecated)]
-    Ok((VectorSink::from_event_sink(sink), healthcheck))
+    Ok((sink, healthcheck))
 }
 
 pub struct ClickhouseEventEncoder {
@@ -80,35 +106,46 @@ impl HttpSink for ClickhouseConfig {
     }
 
     async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
-        let database = if let Some(database) = &self.database {
-            database.as_str()
-        } else {
-            "default"
-        };
+        insert_request(self, "application/x-ndjson", events.freeze())
+    }
+}
 
-        let uri = set_uri_query(
-            &self.endpoint.with_default_parts().uri,
-            database,
-            &self.table,
-            self.skip_unknown_fields,
-            self.date_time_best_effort,
-        )
-        .expect("Unable to encode uri");
+/// Builds the request inserting the body, encoded in the format of the config.
+pub(super) fn insert_request(
+    config: &ClickhouseConfig,
+    content_type: &str,
+    body: Bytes,
+) -> crate::Result<http::Request<Bytes>> {
+    let database = if let Some(database) = &config.database {
+        database.as_str()
+    } else {
+        "default"
+    };
 
-        let mut builder = Request::post(&uri).header("Content-Type", "application/x-ndjson");
+    let uri = set_uri_query(
+        &config.endpoint.with_default_parts().uri,
+        database,
+        &config.table,
+        config.format,
+        config.skip_unknown_fields,
+        config.date_time_best_effort,
+        config.async_insert,
+    )
+    .expect("Unable to encode uri");
 
-        if let Some(ce) = self.compression.content_encoding() {
-            builder = builder.header("Content-Encoding", ce);
-        }
+    let mut builder = Request::post(&uri).header("Content-Type", content_type);
 
-        let mut request = builder.body(events.freeze()).unwrap();
+    if let Some(ce) = config.compression.content_encoding() {
+        builder = builder.header("Content-Encoding", ce);
+    }
 
-        if let Some(auth) = &self.auth {
-            auth.apply(&mut request);
-        }
+    let mut request = builder.body(body).unwrap();
 
-        Ok(request)
+    if let Some(auth) = &config.auth {
+        auth.apply(&mut request);
     }
+
+    Ok(request)
 }
 
 async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Result<()> {
@@ -132,16 +169,23 @@ fn set_uri_query(
     uri: &Uri,
     database: &str,
     table: &str,
+    format: ClickhouseFormat,
     skip_unknown: bool,
     date_time_best_effort: bool,
+    async_insert: bool,
 ) -> crate::Result<Uri> {
+    let format = match format {
+        ClickhouseFormat::JsonEachRow => "JSONEachRow",
+        ClickhouseFormat::Native => "Native",
+    };
     let query = url::form_urlencoded::Serializer::new(String::new())
         .append_pair(
             "query",
             format!(
-                "INSERT INTO \"{}\".\"{}\" FORMAT JSONEachRow",
+                "INSERT INTO \"{}\".\"{}\" FORMAT {}",
                 database,
-                table.replace('\"', "\\\"")
+                table.replace('\"', "\\\""),
+                format
             )
             .as_str(),
         )
@@ -158,6 +202,10 @@ fn set_uri_query(
     if date_time_best_effort {
         uri.push_str("date_time_input_format=best_effort&")
     }
+    if async_insert {
+        // Waiting for the insert keeps the events from being acknowledged before they are stored.
+        uri.push_str("async_insert=1&wait_for_async_insert=1&");
+    }
     uri.push_str(query.as_str());
 
     uri.parse::<Uri>()
@@ -219,8 +267,10 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
+            ClickhouseFormat::JsonEachRow,
             false,
             true,
+            false,
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&date_time_input_format=best_effort&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
@@ -229,6 +279,8 @@ mod tests {
             &"http://localhost:80".parse().unwrap(),
             "my_database",
             "my_\"table\"",
+            ClickhouseFormat::JsonEachRow,
+            false,
             false,
             false,
         )
@@ -236,12 +288,29 @@ mod tests {
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
     }
 
+    #[test]
+    fn encode_native_async_insert() {
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            ClickhouseFormat::Native,
+            false,
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&async_insert=1&wait_for_async_insert=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+Native");
+    }
+
     #[test]
     fn encode_invalid() {
         set_uri_query(
             &"localhost:80".parse().unwrap(),
             "my_database",
             "my_table",
+            ClickhouseFormat::JsonEachRow,
+            false,
             false,
             false,
         )
@@ -2,4 +2,5 @@ mod config;
 mod http_sink;
 #[cfg(all(test, feature = "clickhouse-integration-tests"))]
 mod integration_tests;
+mod native;
 pub use self::config::ClickhouseConfig;
@@ -0,0 +1,459 @@
+//! Encoding of events in the `Native` format of ClickHouse, in which a batch is sent as a block of
+//! columns holding values of their types.
+//!
+//! See <https://clickhouse.com/docs/en/interfaces/formats#native>.
+
+use std::{io::Write, sync::Arc};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use lookup::{lookup_v2::parse_target_path, OwnedTargetPath};
+use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
+
+use super::{http_sink::insert_request, ClickhouseConfig};
+use crate::{
+    codecs::Transformer,
+    event::{Event, Value},
+    internal_events::EncoderSerializeError,
+    sinks::util::{
+        http::{HttpEventEncoder, HttpSink},
+        Compressor, EncodedLength,
+    },
+};
+
+/// The type of a ClickHouse column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    /// `String`.
+    String,
+
+    /// `Int64`.
+    Int64,
+
+    /// `UInt64`.
+    #[serde(rename = "uint64")]
+    UInt64,
+
+    /// `Float64`.
+    Float64,
+
+    /// `Bool`.
+    Bool,
+
+    /// `DateTime`, with a precision of seconds.
+    DateTime,
+
+    /// `DateTime64(3)`, with a precision of milliseconds.
+    DateTime64,
+}
+
+impl ColumnType {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::String => "String",
+            Self::Int64 => "Int64",
+            Self::UInt64 => "UInt64",
+            Self::Float64 => "Float64",
+            Self::Bool => "Bool",
+            Self::DateTime => "DateTime",
+            Self::DateTime64 => "DateTime64(3)",
+        }
+    }
+
+    /// Converts a value of an event to a value of the type, or `None` if it can't be.
+    ///
+    /// Strings are parsed, and integers are taken as Unix timestamps by the `DateTime` types.
+    fn convert(self, value: &Value) -> Option<Cell> {
+        match (self, value) {
+            (Self::String, Value::Bytes(bytes)) => Some(Cell::String(bytes.clone())),
+            (Self::String, value) => Some(Cell::String(Bytes::from(
+                value.to_string_lossy().into_owned(),
+            ))),
+            (Self::Int64, Value::Integer(int)) => Some(Cell::Int(*int)),
+            (Self::Int64, Value::Bytes(bytes)) => parse(bytes).map(Cell::Int),
+            (Self::UInt64, Value::Integer(int)) => u64::try_from(*int).ok().map(Cell::UInt),
+            (Self::UInt64, Value::Bytes(bytes)) => parse(bytes).map(Cell::UInt),
+            (Self::Float64, Value::Float(float)) => Some(Cell::Float(float.into_inner())),
+            (Self::Float64, Value::Integer(int)) => Some(Cell::Float(*int as f64)),
+            (Self::Float64, Value::Bytes(bytes)) => parse(bytes).map(Cell::Float),
+            (Self::Bool, Value::Boolean(boolean)) => Some(Cell::Bool(*boolean)),
+            (Self::Bool, Value::Bytes(bytes)) => parse(bytes).map(Cell::Bool),
+            (Self::DateTime, value) => {
+                let seconds = match value {
+                    Value::Integer(int) => *int,
+                    value => timestamp(value)?.timestamp(),
+                };
+                u32::try_from(seconds)
+                    .ok()
+                    .map(|seconds| Cell::UInt(seconds.into()))
+            }
+            (Self::DateTime64, Value::Integer(int)) => Some(Cell::Int(*int)),
+            (Self::DateTime64, value) => {
+                timestamp(value).map(|ts| Cell::Int(ts.timestamp_millis()))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn parse<T: std::str::FromStr>(bytes: &Bytes) -> Option<T> {
+    std::str::from_utf8(bytes).ok()?.trim().parse().ok()
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Bytes(bytes) => DateTime::parse_from_rfc3339(std::str::from_utf8(bytes).ok()?)
+            .ok()
+            .map(Into::into),
+        _ => None,
+    }
+}
+
+/// A column of the table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ClickhouseColumn {
+    /// The name of the column.
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "status"))]
+    pub name: String,
+
+    #[configurable(derived)]
+    #[serde(rename = "type")]
+    pub column_type: ColumnType,
+
+    /// The field of the events the values of the column are taken from.
+    ///
+    /// Defaults to the name of the column.
+    #[configurable(metadata(docs::examples = "http.status_code"))]
+    pub field: Option<String>,
+
+    /// Whether the column is `Nullable`.
+    ///
+    /// Events missing the field, or whose field is null, insert `NULL` in nullable columns and the
+    /// default value of the type in the others.
+    #[serde(default)]
+    pub nullable: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Column {
+    name: String,
+    column_type: ColumnType,
+    path: OwnedTargetPath,
+    nullable: bool,
+}
+
+impl Column {
+    fn type_name(&self) -> String {
+        if self.nullable {
+            format!("Nullable({})", self.column_type.name())
+        } else {
+            self.column_type.name().to_owned()
+        }
+    }
+}
+
+/// A value of a row, of the type of its column.
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Null,
+    String(Bytes),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+}
+
+/// The values of the columns for an event.
+#[derive(Clone, Debug)]
+pub struct NativeRow {
+    cells: Vec<Cell>,
+}
+
+impl EncodedLength for NativeRow {
+    fn encoded_length(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| match cell {
+                Cell::String(bytes) => bytes.len() + 1,
+                Cell::Bool(_) => 1,
+                _ => 8,
+            })
+            .sum()
+    }
+}
+
+impl ByteSizeOf for NativeRow {
+    fn allocated_bytes(&self) -> usize {
+        self.cells.capacity() * std::mem::size_of::<Cell>()
+            + self
+                .cells
+                .iter()
+                .map(|cell| match cell {
+                    Cell::String(bytes) => bytes.len(),
+                    _ => 0,
+                })
+                .sum::<usize>()
+    }
+}
+
+pub struct NativeEncoder {
+    transformer: Transformer,
+    columns: Arc<Vec<Column>>,
+}
+
+impl HttpEventEncoder<NativeRow> for NativeEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<NativeRow> {
+        self.transformer.transform(&mut event);
+        let log = event.into_log();
+
+        let mut cells = Vec::with_capacity(self.columns.len());
+        for column in self.columns.iter() {
+            let cell = match log.get(&column.path) {
+                None | Some(Value::Null) => Cell::Null,
+                Some(value) => match column.column_type.convert(value) {
+                    Some(cell) => cell,
+                    None => {
+                        let error: crate::Error = format!(
+                            "Value of the column {:?} can't be converted to {}.",
+                            column.name,
+                            column.column_type.name()
+                        )
+                        .into();
+                        emit!(EncoderSerializeError { error: &error });
+                        return None;
+                    }
+                },
+            };
+            cells.push(cell);
+        }
+        Some(NativeRow { cells })
+    }
+}
+
+/// The sink inserting the events in the `Native` format.
+pub struct NativeSink {
+    config: ClickhouseConfig,
+    columns: Arc<Vec<Column>>,
+}
+
+impl NativeSink {
+    pub fn new(config: ClickhouseConfig) -> crate::Result<Self> {
+        if config.columns.is_empty() {
+            return Err("`columns` must be set to use the `native` format.".into());
+        }
+        let columns = config
+            .columns
+            .iter()
+            .map(|column| {
+                let field = column.field.as_deref().unwrap_or(&column.name);
+                let path = parse_target_path(field)
+                    .map_err(|_| format!("Invalid field path {:?}.", field))?;
+                Ok(Column {
+                    name: column.name.clone(),
+                    column_type: column.column_type,
+                    path,
+                    nullable: column.nullable,
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            config,
+            columns: Arc::new(columns),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for NativeSink {
+    type Input = NativeRow;
+    type Output = Vec<NativeRow>;
+    type Encoder = NativeEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        NativeEncoder {
+            transformer: self.config.encoding.clone(),
+            columns: Arc::clone(&self.columns),
+        }
+    }
+
+    async fn build_request(&self, rows: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let mut compressor = Compressor::from(self.config.compression);
+        compressor.write_all(&encode_block(&self.columns, &rows))?;
+        let body = compressor.finish()?.freeze();
+
+        insert_request(&self.config, "application/octet-stream", body)
+    }
+}
+
+/// Encodes the rows as a block, holding the number of columns and rows followed by the name, type,
+/// and values of each column.
+fn encode_block(columns: &[Column], rows: &[NativeRow]) -> BytesMut {
+    let mut block = BytesMut::new();
+    put_var_uint(&mut block, columns.len() as u64);
+    put_var_uint(&mut block, rows.len() as u64);
+
+    for (index, column) in columns.iter().enumerate() {
+        put_string(&mut block, column.name.as_bytes());
+        put_string(&mut block, column.type_name().as_bytes());
+
+        let cells = rows.iter().map(|row| &row.cells[index]);
+        if column.nullable {
+            for cell in cells.clone() {
+                block.put_u8(u8::from(*cell == Cell::Null));
+            }
+        }
+        // Null values are written as the default value of the type.
+        for cell in cells {
+            match (column.column_type, cell) {
+                (ColumnType::String, Cell::String(bytes)) => put_string(&mut block, bytes),
+                (ColumnType::String, _) => put_var_uint(&mut block, 0),
+                (ColumnType::Int64 | ColumnType::DateTime64, Cell::Int(int)) => {
+                    block.put_i64_le(*int)
+                }
+                (ColumnType::UInt64, Cell::UInt(uint)) => block.put_u64_le(*uint),
+                (ColumnType::DateTime, Cell::UInt(uint)) => block.put_u32_le(*uint as u32),
+                (ColumnType::Float64, Cell::Float(float)) => block.put_f64_le(*float),
+                (ColumnType::Bool, Cell::Bool(boolean)) => block.put_u8(u8::from(*boolean)),
+                (ColumnType::Bool, _) => block.put_u8(0),
+                (ColumnType::DateTime, _) => block.put_u32_le(0),
+                (_, _) => block.put_u64_le(0),
+            }
+        }
+    }
+    block
+}
+
+/// Writes a LEB128 variable-length integer.
+fn put_var_uint(buf: &mut BytesMut, mut value: u64) {
+    while value >= 0x80 {
+        buf.put_u8((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.put_u8(value as u8);
+}
+
+fn put_string(buf: &mut BytesMut, bytes: &[u8]) {
+    put_var_uint(buf, bytes.len() as u64);
+    buf.put_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn sink() -> NativeSink {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+                endpoint = "http://localhost:8123"
+                table = "logs"
+                format = "native"
+
+                [[columns]]
+                name = "message"
+                type = "string"
+
+                [[columns]]
+                name = "timestamp"
+                type = "date_time64"
+
+                [[columns]]
+                name = "status"
+                type = "uint64"
+                field = "http.status"
+                nullable = true
+            "#,
+        )
+        .unwrap();
+        NativeSink::new(config).unwrap()
+    }
+
+    fn encode(sink: &NativeSink, log: LogEvent) -> Option<NativeRow> {
+        sink.build_encoder().encode_event(log.into())
+    }
+
+    #[test]
+    fn encodes_block() {
+        let sink = sink();
+        let mut log = LogEvent::from("GET /");
+        log.insert(
+            "timestamp",
+            Utc.timestamp_millis_opt(1_700_000_000_123).unwrap(),
+        );
+        log.insert("http.status", "200");
+        let first = encode(&sink, log).unwrap();
+        let second = encode(&sink, LogEvent::default()).unwrap();
+
+        let block = encode_block(&sink.columns, &[first, second]);
+
+        let mut expected = BytesMut::new();
+        expected.put_slice(&[3, 2]);
+        expected.put_slice(b"\x07message\x06String\x05GET /\x00");
+        expected.put_slice(b"\x09timestamp\x0dDateTime64(3)");
+        expected.put_i64_le(1_700_000_000_123);
+        expected.put_i64_le(0);
+        expected.put_slice(b"\x06status\x10Nullable(UInt64)\x00\x01");
+        expected.put_u64_le(200);
+        expected.put_u64_le(0);
+        assert_eq!(block, expected);
+    }
+
+    #[test]
+    fn drops_unconvertible_events() {
+        let sink = sink();
+        let mut log = LogEvent::from("GET /");
+        log.insert("http.status", -1);
+        assert!(encode(&sink, log).is_none());
+    }
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(
+            ColumnType::DateTime.convert(&"2023-11-14T22:13:20Z".into()),
+            Some(Cell::UInt(1_700_000_000))
+        );
+        assert_eq!(ColumnType::DateTime.convert(&(-1).into()), None);
+        assert_eq!(
+            ColumnType::Float64.convert(&2.into()),
+            Some(Cell::Float(2.0))
+        );
+        assert_eq!(
+            ColumnType::Bool.convert(&"true".into()),
+            Some(Cell::Bool(true))
+        );
+        assert_eq!(
+            ColumnType::String.convert(&200.into()),
+            Some(Cell::String(Bytes::from("200")))
+        );
+        assert_eq!(ColumnType::Int64.convert(&true.into()), None);
+    }
+
+    #[test]
+    fn encodes_var_uint() {
+        let mut buf = BytesMut::new();
+        put_var_uint(&mut buf, 300);
+        assert_eq!(&buf[..], &[0xac, 0x02]);
+    }
+
+    #[test]
+    fn requires_columns() {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+                endpoint = "http://localhost:8123"
+                table = "logs"
+                format = "native"
+            "#,
+        )
+        .unwrap();
+        assert!(NativeSink::new(config).is_err());
+    }
+}
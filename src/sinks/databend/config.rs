@@ -93,7 +93,7 @@ impl GenerateConfig for DatabendConfig {
 impl DatabendConfig {
     pub(super) fn build_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
         Ok(client)
     }
 
@@ -8,7 +8,7 @@ use vrl::value::Kind;
 use crate::{
     common::datadog::{get_base_domain_region, Region},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{HttpClient, HttpPoolConfig},
     sinks::{
         datadog::{
             events::{
@@ -64,9 +64,13 @@ impl DatadogEventsConfig {
         http::Uri::try_from(format!("{}/api/v1/events", api_base_endpoint)).expect("URI not valid")
     }
 
-    fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    fn build_client(
+        &self,
+        proxy: &ProxyConfig,
+        pool: &HttpPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls = MaybeTlsSettings::from_config(&self.dd_common.tls, false)?;
-        let client = HttpClient::new(tls, proxy)?;
+        let client = HttpClient::new_with_pool(tls, proxy, pool)?;
         Ok(client)
     }
 
@@ -95,7 +99,7 @@ impl DatadogEventsConfig {
 #[typetag::serde(name = "datadog_events")]
 impl SinkConfig for DatadogEventsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.build_client(cx.proxy())?;
+        let client = self.build_client(cx.proxy(), cx.connection_pool())?;
         let healthcheck = self
             .dd_common
             .build_healthcheck(client.clone(), self.region.as_ref())?;
@@ -11,7 +11,7 @@ use crate::{
     codecs::Transformer,
     common::datadog::Region,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{HttpClient, HttpPoolConfig},
     schema,
     sinks::{
         datadog::{logs::service::LogApiService, DatadogCommonConfig},
@@ -145,7 +145,11 @@ impl DatadogLogsConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    pub fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    pub fn create_client(
+        &self,
+        proxy: &ProxyConfig,
+        pool: &HttpPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls_settings = MaybeTlsSettings::from_config(
             &Some(
                 self.dd_common
@@ -155,7 +159,7 @@ impl DatadogLogsConfig {
             ),
             false,
         )?;
-        Ok(HttpClient::new(tls_settings, proxy)?)
+        Ok(HttpClient::new_with_pool(tls_settings, proxy, pool)?)
     }
 }
 
@@ -163,7 +167,7 @@ impl DatadogLogsConfig {
 #[typetag::serde(name = "datadog_logs")]
 impl SinkConfig for DatadogLogsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.create_client(&cx.proxy)?;
+        let client = self.create_client(&cx.proxy, &cx.connection_pool)?;
 
         let healthcheck = self
             .dd_common
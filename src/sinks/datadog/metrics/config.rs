@@ -12,7 +12,7 @@ use super::{
 use crate::{
     common::datadog::{get_base_domain_region, Region},
     config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{HttpClient, HttpPoolConfig},
     sinks::{
         datadog::DatadogCommonConfig,
         util::{batch::BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
@@ -30,6 +30,10 @@ use crate::{
 pub const MAXIMUM_PAYLOAD_COMPRESSED_SIZE: usize = 3_200_000;
 pub const MAXIMUM_PAYLOAD_SIZE: usize = 62_914_560;
 
+// The v2 series API accepts smaller payloads than the v1 series API and the sketches API.
+pub const SERIES_V2_MAXIMUM_PAYLOAD_COMPRESSED_SIZE: usize = 512_000;
+pub const SERIES_V2_MAXIMUM_PAYLOAD_SIZE: usize = 5_242_880;
+
 // TODO: revisit our concurrency and batching defaults
 const DEFAULT_REQUEST_RETRY_ATTEMPTS: usize = 5;
 
@@ -47,7 +51,7 @@ impl SinkBatchSettings for DatadogMetricsDefaultBatchSettings {
 /// Each of these corresponds to a specific request path when making a request to the agent API.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DatadogMetricsEndpoint {
-    Series,
+    Series(SeriesApiVersion),
     Sketches,
 }
 
@@ -55,8 +59,51 @@ impl DatadogMetricsEndpoint {
     /// Gets the content type associated with the specific encoder for a given metric endpoint.
     pub const fn content_type(self) -> &'static str {
         match self {
-            DatadogMetricsEndpoint::Series => "application/json",
-            DatadogMetricsEndpoint::Sketches => "application/x-protobuf",
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => "application/json",
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V2)
+            | DatadogMetricsEndpoint::Sketches => "application/x-protobuf",
+        }
+    }
+
+    /// Gets the uncompressed and compressed payload size limits of a given metric endpoint.
+    pub const fn payload_limits(self) -> (usize, usize) {
+        match self {
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V2) => (
+                SERIES_V2_MAXIMUM_PAYLOAD_SIZE,
+                SERIES_V2_MAXIMUM_PAYLOAD_COMPRESSED_SIZE,
+            ),
+            // According to the datadog-agent code, sketches use the same payload size limits as
+            // series data.
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1)
+            | DatadogMetricsEndpoint::Sketches => {
+                (MAXIMUM_PAYLOAD_SIZE, MAXIMUM_PAYLOAD_COMPRESSED_SIZE)
+            }
+        }
+    }
+}
+
+/// The version of the Datadog series API that series metrics are sent to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SeriesApiVersion {
+    /// The v1 series API, which takes series encoded as JSON.
+    #[default]
+    V1,
+
+    /// The v2 series API, which takes series encoded as Protocol Buffers, like the Datadog Agent
+    /// sends them.
+    ///
+    /// Unlike the v1 series API, it accepts the unit and origin of metrics received from the
+    /// Datadog Agent.
+    V2,
+}
+
+impl SeriesApiVersion {
+    const fn path(self) -> &'static str {
+        match self {
+            SeriesApiVersion::V1 => "/api/v1/series",
+            SeriesApiVersion::V2 => "/api/v2/series",
         }
     }
 }
@@ -79,7 +126,7 @@ impl DatadogMetricsEndpointConfiguration {
     /// Gets the URI for the given Datadog metrics endpoint.
     pub fn get_uri_for_endpoint(&self, endpoint: DatadogMetricsEndpoint) -> Uri {
         match endpoint {
-            DatadogMetricsEndpoint::Series => self.series_endpoint.clone(),
+            DatadogMetricsEndpoint::Series(_) => self.series_endpoint.clone(),
             DatadogMetricsEndpoint::Sketches => self.sketches_endpoint.clone(),
         }
     }
@@ -106,6 +153,13 @@ pub struct DatadogMetricsConfig {
     #[serde(default)]
     pub region: Option<Region>,
 
+    /// The version of the Datadog series API to send series metrics to.
+    ///
+    /// The unit and origin of series metrics received from the Datadog Agent are only forwarded
+    /// with the `v2` API. The origin of sketches is forwarded with either version.
+    #[serde(default)]
+    pub series_api_version: SeriesApiVersion,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<DatadogMetricsDefaultBatchSettings>,
@@ -121,7 +175,7 @@ impl_generate_config_from_default!(DatadogMetricsConfig);
 #[typetag::serde(name = "datadog_metrics")]
 impl SinkConfig for DatadogMetricsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.build_client(&cx.proxy)?;
+        let client = self.build_client(&cx.proxy, &cx.connection_pool)?;
         let healthcheck = self
             .dd_common
             .build_healthcheck(client.clone(), self.region.as_ref())?;
@@ -164,7 +218,7 @@ impl DatadogMetricsConfig {
         &self,
     ) -> crate::Result<DatadogMetricsEndpointConfiguration> {
         let base_uri = self.get_base_agent_endpoint();
-        let series_endpoint = build_uri(&base_uri, "/api/v1/series")?;
+        let series_endpoint = build_uri(&base_uri, self.series_api_version.path())?;
         let sketches_endpoint = build_uri(&base_uri, "/api/beta/sketches")?;
 
         Ok(DatadogMetricsEndpointConfiguration::new(
@@ -173,7 +227,11 @@ impl DatadogMetricsConfig {
         ))
     }
 
-    fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    fn build_client(
+        &self,
+        proxy: &ProxyConfig,
+        pool: &HttpPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls_settings = MaybeTlsSettings::from_config(
             &Some(
                 self.dd_common
@@ -183,7 +241,7 @@ impl DatadogMetricsConfig {
             ),
             false,
         )?;
-        let client = HttpClient::new(tls_settings, proxy)?;
+        let client = HttpClient::new_with_pool(tls_settings, proxy, pool)?;
         Ok(client)
     }
 
@@ -206,10 +264,17 @@ impl DatadogMetricsConfig {
         let request_builder = DatadogMetricsRequestBuilder::new(
             endpoint_configuration,
             self.default_namespace.clone(),
+            self.series_api_version,
         )?;
 
         let protocol = self.get_protocol();
-        let sink = DatadogMetricsSink::new(service, request_builder, batcher_settings, protocol);
+        let sink = DatadogMetricsSink::new(
+            service,
+            request_builder,
+            batcher_settings,
+            protocol,
+            self.series_api_version,
+        );
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
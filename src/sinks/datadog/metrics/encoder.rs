@@ -11,12 +11,10 @@ use prost::Message;
 use snafu::{ResultExt, Snafu};
 use vector_core::{
     config::{log_schema, LogSchema},
-    event::{metric::MetricSketch, Metric, MetricTags, MetricValue},
+    event::{metric::MetricSketch, DatadogMetricMetadata, Metric, MetricTags, MetricValue},
 };
 
-use super::config::{
-    DatadogMetricsEndpoint, MAXIMUM_PAYLOAD_COMPRESSED_SIZE, MAXIMUM_PAYLOAD_SIZE,
-};
+use super::config::{DatadogMetricsEndpoint, SeriesApiVersion};
 use crate::{
     common::datadog::{DatadogMetricType, DatadogPoint, DatadogSeriesMetric},
     sinks::util::{encode_namespace, Compression, Compressor},
@@ -25,6 +23,8 @@ use crate::{
 const SERIES_PAYLOAD_HEADER: &[u8] = b"{\"series\":[";
 const SERIES_PAYLOAD_FOOTER: &[u8] = b"]}";
 const SERIES_PAYLOAD_DELIMITER: &[u8] = b",";
+/// The tag of the `series` field of the `MetricPayload` message of the v2 series API.
+const SERIES_PAYLOAD_FIELD: u32 = 1;
 
 #[allow(warnings, clippy::pedantic, clippy::nursery)]
 mod ddmetric_proto {
@@ -127,13 +127,12 @@ impl DatadogMetricsEncoder {
         endpoint: DatadogMetricsEndpoint,
         default_namespace: Option<String>,
     ) -> Result<Self, CreateError> {
-        // According to the datadog-agent code, sketches use the same payload size limits as series
-        // data. We're just gonna go with that for now.
+        let (uncompressed_limit, compressed_limit) = endpoint.payload_limits();
         Self::with_payload_limits(
             endpoint,
             default_namespace,
-            MAXIMUM_PAYLOAD_SIZE,
-            MAXIMUM_PAYLOAD_COMPRESSED_SIZE,
+            uncompressed_limit,
+            compressed_limit,
         )
     }
 
@@ -180,7 +179,7 @@ impl DatadogMetricsEncoder {
 
         match self.endpoint {
             // Series metrics are encoded via JSON, in an incremental fashion.
-            DatadogMetricsEndpoint::Series => {
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => {
                 // A single `Metric` might generate multiple Datadog series metrics.
                 let all_series =
                     generate_series_metrics(&metric, &self.default_namespace, self.log_schema)?;
@@ -199,6 +198,21 @@ impl DatadogMetricsEncoder {
                         .context(JsonEncodingFailedSnafu)?;
                 }
             }
+            // Series metrics of the v2 API are encoded via Protocol Buffers, which can also be done
+            // incrementally: a payload is made of its series, each encoded as the `series` field
+            // of the `MetricPayload` message, without any header, delimiter or footer.
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V2) => {
+                let metadata = metric.metadata().datadog_metric_metadata();
+                for series in
+                    generate_series_metrics(&metric, &self.default_namespace, self.log_schema)?
+                {
+                    prost::encoding::message::encode(
+                        SERIES_PAYLOAD_FIELD,
+                        &series_v2(series, metadata),
+                        &mut self.state.buf,
+                    );
+                }
+            }
             // We can't encode sketches incrementally (yet), so we don't do any encoding here.  We
             // simply store it for later, and in `try_encode_pending`, any such pending metrics will be
             // encoded in a single operation.
@@ -481,6 +495,63 @@ fn generate_series_metrics(
     Ok(results)
 }
 
+/// Converts a series to its representation in the v2 series API, along with the unit and origin of
+/// the metric it was generated from.
+fn series_v2(
+    series: DatadogSeriesMetric,
+    metadata: Option<&DatadogMetricMetadata>,
+) -> ddmetric_proto::metric_payload::MetricSeries {
+    use ddmetric_proto::metric_payload::{MetricPoint, MetricSeries, MetricType, Resource};
+
+    let mut tags = series.tags.unwrap_or_default();
+    // The v2 series API has no device field, so the Datadog Agent sends the device as a tag.
+    if let Some(device) = series.device {
+        tags.push(format!("device:{}", device));
+    }
+    let resources = series
+        .host
+        .map(|host| Resource {
+            r#type: "host".to_owned(),
+            name: host,
+        })
+        .into_iter()
+        .collect();
+    let r#type = match series.r#type {
+        DatadogMetricType::Gauge => MetricType::Gauge,
+        DatadogMetricType::Count => MetricType::Count,
+        DatadogMetricType::Rate => MetricType::Rate,
+    };
+
+    MetricSeries {
+        resources,
+        metric: series.metric,
+        tags,
+        points: series
+            .points
+            .into_iter()
+            .map(|DatadogPoint(timestamp, value)| MetricPoint { value, timestamp })
+            .collect(),
+        r#type: r#type.into(),
+        unit: metadata
+            .and_then(|metadata| metadata.unit.clone())
+            .unwrap_or_default(),
+        source_type_name: series.source_type_name.unwrap_or_default(),
+        interval: series.interval.map_or(0, i64::from),
+        metadata: origin_metadata(metadata),
+    }
+}
+
+fn origin_metadata(metadata: Option<&DatadogMetricMetadata>) -> Option<ddmetric_proto::Metadata> {
+    let origin = metadata?.origin?;
+    Some(ddmetric_proto::Metadata {
+        origin: Some(ddmetric_proto::Origin {
+            origin_product: origin.product,
+            origin_category: origin.category,
+            origin_service: origin.service,
+        }),
+    })
+}
+
 fn write_sketches<B>(
     metrics: &[Metric],
     default_namespace: &Option<Arc<str>>,
@@ -539,6 +610,7 @@ where
                             k,
                             n,
                         }],
+                        metadata: origin_metadata(metric.metadata().datadog_metric_metadata()),
                     };
 
                     sketches.push(sketch);
@@ -623,7 +695,7 @@ fn write_payload_header(
     writer: &mut dyn io::Write,
 ) -> io::Result<usize> {
     match endpoint {
-        DatadogMetricsEndpoint::Series => writer
+        DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => writer
             .write_all(SERIES_PAYLOAD_HEADER)
             .map(|_| SERIES_PAYLOAD_HEADER.len()),
         _ => Ok(0),
@@ -635,7 +707,7 @@ fn write_payload_delimiter(
     writer: &mut dyn io::Write,
 ) -> io::Result<usize> {
     match endpoint {
-        DatadogMetricsEndpoint::Series => writer
+        DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => writer
             .write_all(SERIES_PAYLOAD_DELIMITER)
             .map(|_| SERIES_PAYLOAD_DELIMITER.len()),
         _ => Ok(0),
@@ -647,7 +719,7 @@ fn write_payload_footer(
     writer: &mut dyn io::Write,
 ) -> io::Result<usize> {
     match endpoint {
-        DatadogMetricsEndpoint::Series => writer
+        DatadogMetricsEndpoint::Series(SeriesApiVersion::V1) => writer
             .write_all(SERIES_PAYLOAD_FOOTER)
             .map(|_| SERIES_PAYLOAD_FOOTER.len()),
         _ => Ok(0),
@@ -659,6 +731,7 @@ mod tests {
     use std::{
         io::{self, copy},
         num::NonZeroU32,
+        sync::Arc,
     };
 
     use bytes::{BufMut, Bytes, BytesMut};
@@ -668,20 +741,25 @@ mod tests {
         arbitrary::any, collection::btree_map, num::f64::POSITIVE as ARB_POSITIVE_F64, prop_assert,
         proptest, strategy::Strategy, string::string_regex,
     };
+    use prost::Message;
     use vector_core::{
         config::log_schema,
-        event::{metric::TagValue, Metric, MetricKind, MetricTags, MetricValue},
+        event::{
+            metric::TagValue, DatadogMetricMetadata, DatadogMetricOrigin, Metric, MetricKind,
+            MetricTags, MetricValue,
+        },
         metric_tags,
         metrics::AgentDDSketch,
     };
 
     use super::{
-        encode_tags, encode_timestamp, generate_series_metrics, get_compressor,
+        ddmetric_proto, encode_tags, encode_timestamp, generate_series_metrics, get_compressor,
         max_compression_overhead_len, max_uncompressed_header_len, validate_payload_size_limits,
         write_payload_footer, write_payload_header, DatadogMetricsEncoder, EncoderError,
     };
     use crate::{
-        common::datadog::DatadogMetricType, sinks::datadog::metrics::config::DatadogMetricsEndpoint,
+        common::datadog::DatadogMetricType,
+        sinks::datadog::metrics::config::{DatadogMetricsEndpoint, SeriesApiVersion},
     };
 
     fn get_simple_counter() -> Metric {
@@ -706,10 +784,16 @@ mod tests {
     fn get_compressed_empty_series_payload() -> Bytes {
         let mut compressor = get_compressor();
 
-        _ = write_payload_header(DatadogMetricsEndpoint::Series, &mut compressor)
-            .expect("should not fail");
-        _ = write_payload_footer(DatadogMetricsEndpoint::Series, &mut compressor)
-            .expect("should not fail");
+        _ = write_payload_header(
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
+            &mut compressor,
+        )
+        .expect("should not fail");
+        _ = write_payload_footer(
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
+            &mut compressor,
+        )
+        .expect("should not fail");
 
         compressor.finish().expect("should not fail").freeze()
     }
@@ -771,8 +855,9 @@ mod tests {
 
         // And sketches can't go to the series endpoint.
         // Series metrics can't go to the sketches endpoint.
-        let mut series_encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series, None)
-            .expect("default payload size limits should be valid");
+        let mut series_encoder =
+            DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series(SeriesApiVersion::V1), None)
+                .expect("default payload size limits should be valid");
         let sketch_result = series_encoder.try_encode(get_simple_sketch());
         assert!(matches!(
             sketch_result.err(),
@@ -811,8 +896,9 @@ mod tests {
     fn encode_single_series_metric_with_default_limits() {
         // This is a simple test where we ensure that a single metric, with the default limits, can
         // be encoded without hitting any errors.
-        let mut encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series, None)
-            .expect("default payload size limits should be valid");
+        let mut encoder =
+            DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series(SeriesApiVersion::V1), None)
+                .expect("default payload size limits should be valid");
         let counter = get_simple_counter();
         let expected = counter.clone();
 
@@ -863,6 +949,87 @@ mod tests {
         assert_eq!(70, raw_bytes);
     }
 
+    #[test]
+    fn encode_series_v2_with_datadog_metric_metadata() {
+        let mut encoder =
+            DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Series(SeriesApiVersion::V2), None)
+                .expect("default payload size limits should be valid");
+        let mut counter = get_simple_rate_counter(20.0, 10000).with_tags(Some(metric_tags! {
+            log_schema().host_key() => "host-1",
+            "device" => "sda",
+            "env" => "prod",
+        }));
+        counter
+            .metadata_mut()
+            .set_datadog_metric_metadata(Arc::new(DatadogMetricMetadata {
+                unit: Some("byte".to_owned()),
+                origin: Some(DatadogMetricOrigin {
+                    product: 10,
+                    category: 11,
+                    service: 12,
+                }),
+            }));
+
+        assert_eq!(encoder.try_encode(counter).unwrap(), None);
+        assert_eq!(encoder.try_encode(get_simple_counter()).unwrap(), None);
+        let (payload, processed, _) = encoder.finish().unwrap();
+        assert_eq!(processed.len(), 2);
+
+        let payload = decompress_payload(payload).unwrap();
+        let series = ddmetric_proto::MetricPayload::decode(payload)
+            .unwrap()
+            .series;
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].metric, "basic_counter");
+        assert_eq!(
+            series[0].r#type,
+            i32::from(ddmetric_proto::metric_payload::MetricType::Rate)
+        );
+        assert_eq!(series[0].interval, 10);
+        assert_eq!(series[0].points[0].value, 2.0);
+        assert_eq!(series[0].resources[0].name, "host-1");
+        assert_eq!(series[0].tags, ["env:prod", "device:sda"]);
+        assert_eq!(series[0].unit, "byte");
+        let origin = series[0].metadata.clone().unwrap().origin.unwrap();
+        assert_eq!(
+            (
+                origin.origin_product,
+                origin.origin_category,
+                origin.origin_service
+            ),
+            (10, 11, 12)
+        );
+        assert_eq!(series[1].unit, "");
+        assert_eq!(series[1].metadata, None);
+    }
+
+    #[test]
+    fn encode_sketch_with_datadog_metric_origin() {
+        let mut encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Sketches, None)
+            .expect("default payload size limits should be valid");
+        let mut sketch = get_simple_sketch();
+        sketch
+            .metadata_mut()
+            .set_datadog_metric_metadata(Arc::new(DatadogMetricMetadata {
+                unit: None,
+                origin: Some(DatadogMetricOrigin {
+                    product: 10,
+                    category: 11,
+                    service: 12,
+                }),
+            }));
+
+        assert_eq!(encoder.try_encode(sketch).unwrap(), None);
+        let (payload, _, _) = encoder.finish().unwrap();
+
+        let payload = decompress_payload(payload).unwrap();
+        let sketches = ddmetric_proto::SketchPayload::decode(payload)
+            .unwrap()
+            .sketches;
+        let origin = sketches[0].metadata.clone().unwrap().origin.unwrap();
+        assert_eq!(origin.origin_service, 12);
+    }
+
     #[test]
     fn payload_size_limits() {
         // Get the maximum length of the header/trailer data.
@@ -897,7 +1064,7 @@ mod tests {
         // uncompressed payload would exceed the limit.
         let header_len = max_uncompressed_header_len();
         let mut encoder = DatadogMetricsEncoder::with_payload_limits(
-            DatadogMetricsEndpoint::Series,
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
             None,
             header_len + 1,
             usize::MAX,
@@ -934,7 +1101,7 @@ mod tests {
         let uncompressed_limit = 128;
         let compressed_limit = 32;
         let mut encoder = DatadogMetricsEncoder::with_payload_limits(
-            DatadogMetricsEndpoint::Series,
+            DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
             None,
             uncompressed_limit,
             compressed_limit,
@@ -995,7 +1162,7 @@ mod tests {
             // We check this with targeted unit tests as well but this is some cheap insurance to
             // show that we're hopefully not missing any particular corner cases.
             let result = DatadogMetricsEncoder::with_payload_limits(
-                DatadogMetricsEndpoint::Series,
+                DatadogMetricsEndpoint::Series(SeriesApiVersion::V1),
                 None,
                 uncompressed_limit,
                 compressed_limit,
@@ -9,7 +9,7 @@ use vector_core::{
 };
 
 use super::{
-    config::{DatadogMetricsEndpoint, DatadogMetricsEndpointConfiguration},
+    config::{DatadogMetricsEndpoint, DatadogMetricsEndpointConfiguration, SeriesApiVersion},
     encoder::{CreateError, DatadogMetricsEncoder, EncoderError, FinishError},
     service::DatadogMetricsRequest,
 };
@@ -132,11 +132,12 @@ impl DatadogMetricsRequestBuilder {
     pub fn new(
         endpoint_configuration: DatadogMetricsEndpointConfiguration,
         default_namespace: Option<String>,
+        series_api_version: SeriesApiVersion,
     ) -> Result<Self, RequestBuilderError> {
         Ok(Self {
             endpoint_configuration,
             series_encoder: DatadogMetricsEncoder::new(
-                DatadogMetricsEndpoint::Series,
+                DatadogMetricsEndpoint::Series(series_api_version),
                 default_namespace.clone(),
             )?,
             sketches_encoder: DatadogMetricsEncoder::new(
@@ -148,7 +149,7 @@ impl DatadogMetricsRequestBuilder {
 
     fn get_encoder(&mut self, endpoint: DatadogMetricsEndpoint) -> &mut DatadogMetricsEncoder {
         match endpoint {
-            DatadogMetricsEndpoint::Series => &mut self.series_encoder,
+            DatadogMetricsEndpoint::Series(_) => &mut self.series_encoder,
             DatadogMetricsEndpoint::Sketches => &mut self.sketches_encoder,
         }
     }
@@ -17,8 +17,10 @@ use vector_core::{
 };
 
 use super::{
-    config::DatadogMetricsEndpoint, normalizer::DatadogMetricsNormalizer,
-    request_builder::DatadogMetricsRequestBuilder, service::DatadogMetricsRequest,
+    config::{DatadogMetricsEndpoint, SeriesApiVersion},
+    normalizer::DatadogMetricsNormalizer,
+    request_builder::DatadogMetricsRequestBuilder,
+    service::DatadogMetricsRequest,
 };
 use crate::{
     internal_events::DatadogMetricsEncodingError,
@@ -34,20 +36,23 @@ use crate::{
 /// Generally speaking, all "basic" metrics -- counter, gauge, set, aggregated summary-- are sent to
 /// the Series API, while distributions, aggregated histograms, and sketches (hehe) are sent to the
 /// Sketches API.
-struct DatadogMetricsTypePartitioner;
+struct DatadogMetricsTypePartitioner {
+    series_api_version: SeriesApiVersion,
+}
 
 impl Partitioner for DatadogMetricsTypePartitioner {
     type Item = Metric;
     type Key = (Option<Arc<str>>, DatadogMetricsEndpoint);
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
+        let series = DatadogMetricsEndpoint::Series(self.series_api_version);
         let endpoint = match item.data().value() {
-            MetricValue::Counter { .. } => DatadogMetricsEndpoint::Series,
-            MetricValue::Gauge { .. } => DatadogMetricsEndpoint::Series,
-            MetricValue::Set { .. } => DatadogMetricsEndpoint::Series,
+            MetricValue::Counter { .. } => series,
+            MetricValue::Gauge { .. } => series,
+            MetricValue::Set { .. } => series,
             MetricValue::Distribution { .. } => DatadogMetricsEndpoint::Sketches,
             MetricValue::AggregatedHistogram { .. } => DatadogMetricsEndpoint::Sketches,
-            MetricValue::AggregatedSummary { .. } => DatadogMetricsEndpoint::Series,
+            MetricValue::AggregatedSummary { .. } => series,
             MetricValue::Sketch { .. } => DatadogMetricsEndpoint::Sketches,
         };
         (item.metadata().datadog_api_key(), endpoint)
@@ -59,6 +64,7 @@ pub(crate) struct DatadogMetricsSink<S> {
     request_builder: DatadogMetricsRequestBuilder,
     batch_settings: BatcherSettings,
     protocol: String,
+    series_api_version: SeriesApiVersion,
 }
 
 impl<S> DatadogMetricsSink<S>
@@ -74,12 +80,14 @@ where
         request_builder: DatadogMetricsRequestBuilder,
         batch_settings: BatcherSettings,
         protocol: String,
+        series_api_version: SeriesApiVersion,
     ) -> Self {
         DatadogMetricsSink {
             service,
             request_builder,
             batch_settings,
             protocol,
+            series_api_version,
         }
     }
 
@@ -99,7 +107,12 @@ where
             .normalized_with_default::<DatadogMetricsNormalizer>()
             // We batch metrics by their endpoint: series endpoint for counters, gauge, and sets vs sketch endpoint for
             // distributions, aggregated histograms, and sketches.
-            .batched_partitioned(DatadogMetricsTypePartitioner, self.batch_settings)
+            .batched_partitioned(
+                DatadogMetricsTypePartitioner {
+                    series_api_version: self.series_api_version,
+                },
+                self.batch_settings,
+            )
             // Aggregate counters with identical timestamps, otherwise identical counters (same
             // series and same timestamp, when rounded to whole seconds) will be dropped in a
             // last-write-wins situation when they hit the DD metrics intake.
@@ -14,7 +14,7 @@ use super::{
 };
 use crate::{
     config::{GenerateConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{HttpClient, HttpPoolConfig},
     sinks::{
         datadog::{
             traces::{
@@ -188,7 +188,11 @@ impl DatadogTracesConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    pub fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    pub fn build_client(
+        &self,
+        proxy: &ProxyConfig,
+        pool: &HttpPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls_settings = MaybeTlsSettings::from_config(
             &Some(
                 self.dd_common
@@ -198,7 +202,7 @@ impl DatadogTracesConfig {
             ),
             false,
         )?;
-        Ok(HttpClient::new(tls_settings, proxy)?)
+        Ok(HttpClient::new_with_pool(tls_settings, proxy, pool)?)
     }
 
     fn get_protocol(&self) -> String {
@@ -214,7 +218,7 @@ impl DatadogTracesConfig {
 #[typetag::serde(name = "datadog_traces")]
 impl SinkConfig for DatadogTracesConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.build_client(&cx.proxy)?;
+        let client = self.build_client(&cx.proxy, &cx.connection_pool)?;
         let healthcheck = self.dd_common.build_healthcheck(client.clone(), None)?;
         let sink = self.build_sink(client)?;
 
@@ -328,7 +328,7 @@ impl DatadogArchivesSinkConfig {
                 let auth = gcs_config.auth.build(Scope::DevStorageReadWrite).await?;
                 let base_url = format!("{}{}/", BASE_URL, self.bucket);
                 let tls = TlsSettings::from_options(&self.tls)?;
-                let client = HttpClient::new(tls, cx.proxy())?;
+                let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
                 let healthcheck = gcs_common::config::build_healthcheck(
                     self.bucket.clone(),
                     client.clone(),
@@ -375,6 +375,7 @@ impl DatadogArchivesSinkConfig {
         let partitioner = S3KeyPartitioner::new(
             Template::try_from(KEY_TEMPLATE).expect("invalid object key format"),
             None,
+            None,
         );
 
         let s3_config = self
@@ -685,6 +686,7 @@ impl RequestBuilder<(S3PartitionKey, Vec<Event>)> for DatadogS3RequestBuilder {
                 ssekms_key_id: s3_options.ssekms_key_id,
                 storage_class: s3_options.storage_class,
                 tags: s3_options.tags.map(|tags| tags.into_iter().collect()),
+                checksum_algorithm: None,
                 content_encoding: None,
                 content_type: None,
             },
@@ -1095,6 +1097,7 @@ mod tests {
         let partitioner = S3KeyPartitioner::new(
             Template::try_from(KEY_TEMPLATE).expect("invalid object key format"),
             None,
+            None,
         );
         let key = partitioner.partition(&log).expect("key wasn't provided");
 
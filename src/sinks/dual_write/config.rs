@@ -0,0 +1,115 @@
+use futures::{future, FutureExt};
+use vector_config::configurable_component;
+
+use super::{
+    mode::{DualWriteMode, DualWriteModes},
+    sink::DualWriteSink,
+};
+use crate::{
+    config::{
+        AcknowledgementsConfig, BoxedSink, GenerateConfig, Input, Resource, SinkConfig, SinkContext,
+    },
+    sinks::{Healthcheck, VectorSink},
+};
+
+/// Configuration for the `dual_write` sink.
+#[configurable_component(sink(
+    "dual_write",
+    "Write events to two sinks at once, to migrate between destinations."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DualWriteConfig {
+    /// The sink being migrated from.
+    ///
+    /// Its options are those of a sink of the same `type`, without `inputs`.
+    #[configurable(metadata(docs::hidden))]
+    pub primary: BoxedSink,
+
+    /// The sink being migrated to.
+    ///
+    /// Its options are those of a sink of the same `type`, without `inputs`.
+    #[configurable(metadata(docs::hidden))]
+    pub secondary: BoxedSink,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: DualWriteMode,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for DualWriteConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"primary.type = "blackhole"
+            secondary.type = "blackhole""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dual_write")]
+impl SinkConfig for DualWriteConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let (primary, primary_healthcheck) = self.primary.build(cx.clone()).await?;
+        let (secondary, secondary_healthcheck) = self.secondary.build(cx).await?;
+
+        let sink = DualWriteSink::new(primary, secondary, DualWriteModes::register(self.mode));
+        let healthcheck = future::try_join(primary_healthcheck, secondary_healthcheck)
+            .map(|result| result.map(|_| ()))
+            .boxed();
+
+        Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.primary.input().data_type() & self.secondary.input().data_type())
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        let mut resources = self.primary.resources();
+        resources.extend(self.secondary.resources());
+        resources
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DualWriteConfig>();
+    }
+
+    #[test]
+    fn parses_nested_sinks() {
+        let config: DualWriteConfig = toml::from_str(indoc::indoc! {r#"
+            mode = "secondary"
+
+            [primary]
+            type = "console"
+            encoding.codec = "json"
+
+            [secondary]
+            type = "blackhole"
+        "#})
+        .unwrap();
+
+        assert_eq!(config.mode, DualWriteMode::Secondary);
+        assert_eq!(config.primary.get_component_name(), "console");
+        assert_eq!(config.secondary.get_component_name(), "blackhole");
+    }
+}
@@ -0,0 +1,15 @@
+//! Dual-write sink
+//!
+//! This sink writes every batch of events to two sinks, to migrate between destinations while both
+//! receive the same events. The outcome and the latency of each batch on both sinks are compared
+//! and reported as internal metrics, and events are acknowledged according to whichever sink is
+//! authoritative. The authoritative sink is set by `mode`, and can be flipped through the API
+//! without reloading the configuration.
+mod config;
+mod mode;
+mod sink;
+
+pub use self::{
+    config::DualWriteConfig,
+    mode::{DualWriteMode, DualWriteModes},
+};
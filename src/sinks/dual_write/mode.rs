@@ -0,0 +1,90 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+};
+
+use once_cell::sync::Lazy;
+use vector_config::configurable_component;
+
+use crate::{config::ComponentKey, sinks::util::service::RequestLimits};
+
+static REGISTRY: Lazy<Mutex<HashMap<ComponentKey, Weak<Mutex<DualWriteMode>>>>> =
+    Lazy::new(Default::default);
+
+/// The sink whose outcome is used to acknowledge events.
+///
+/// Both sinks are written to in every mode. The outcome of the other sink is only reported in the
+/// internal metrics.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DualWriteMode {
+    /// Events are acknowledged once the primary sink has delivered them.
+    #[default]
+    Primary,
+
+    /// Events are acknowledged once the secondary sink has delivered them.
+    Secondary,
+}
+
+/// A handle on the mode of a running `dual_write` sink.
+#[derive(Clone, Debug)]
+pub struct DualWriteModes {
+    mode: Arc<Mutex<DualWriteMode>>,
+}
+
+impl DualWriteModes {
+    /// Creates the mode of the sink being built, registered under its key.
+    pub(super) fn register(mode: DualWriteMode) -> Self {
+        let modes = Self {
+            mode: Arc::new(Mutex::new(mode)),
+        };
+        if let Some(key) = RequestLimits::current_sink() {
+            let mut registry = REGISTRY.lock().expect("Dual-write mutex is poisoned");
+            registry.retain(|_, mode| mode.strong_count() > 0);
+            registry.insert(key, Arc::downgrade(&modes.mode));
+        }
+        modes
+    }
+
+    /// Gets the mode of a running `dual_write` sink.
+    pub fn get(key: &ComponentKey) -> Option<Self> {
+        REGISTRY
+            .lock()
+            .expect("Dual-write mutex is poisoned")
+            .get(key)
+            .and_then(Weak::upgrade)
+            .map(|mode| Self { mode })
+    }
+
+    pub fn mode(&self) -> DualWriteMode {
+        *self.mode.lock().expect("Dual-write mutex is poisoned")
+    }
+
+    /// Sets the mode of the sink, until its configuration is reloaded.
+    pub fn set_mode(&self, mode: DualWriteMode) {
+        *self.mode.lock().expect("Dual-write mutex is poisoned") = mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn registers_modes_of_built_sinks() {
+        let key = ComponentKey::from("dual_write_mode_test");
+        let modes = RequestLimits::scope(key.clone(), async {
+            DualWriteModes::register(DualWriteMode::Primary)
+        })
+        .await;
+
+        DualWriteModes::get(&key)
+            .unwrap()
+            .set_mode(DualWriteMode::Secondary);
+        assert_eq!(modes.mode(), DualWriteMode::Secondary);
+
+        drop(modes);
+        assert!(DualWriteModes::get(&key).is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::{channel::mpsc, future, stream::BoxStream, SinkExt, StreamExt};
+use vector_core::{
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, EventArray, EventContainer,
+        EventFinalizers, EventStatus, Finalizable,
+    },
+    sink::StreamSink,
+};
+
+use super::mode::{DualWriteMode, DualWriteModes};
+use crate::{internal_events::DualWriteBatchReconciled, sinks::VectorSink};
+
+pub(super) struct DualWriteSink {
+    primary: VectorSink,
+    secondary: VectorSink,
+    modes: DualWriteModes,
+}
+
+impl DualWriteSink {
+    pub(super) const fn new(
+        primary: VectorSink,
+        secondary: VectorSink,
+        modes: DualWriteModes,
+    ) -> Self {
+        Self {
+            primary,
+            secondary,
+            modes,
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<EventArray> for DualWriteSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, EventArray>) -> Result<(), ()> {
+        let Self {
+            primary,
+            secondary,
+            modes,
+        } = *self;
+
+        // The sinks are fed one batch at a time, so that the slowest one holds back the input.
+        let (primary_tx, primary_rx) = mpsc::channel(0);
+        let (secondary_tx, secondary_rx) = mpsc::channel(0);
+
+        let (input, primary, secondary) = tokio::join!(
+            write_both(input, primary_tx, secondary_tx, modes),
+            primary.run(primary_rx),
+            secondary.run(secondary_rx),
+        );
+        input.and(primary).and(secondary)
+    }
+}
+
+/// Sends a copy of every batch to both sinks, and acknowledges it once both are done with it.
+async fn write_both(
+    mut input: BoxStream<'_, EventArray>,
+    mut primary: mpsc::Sender<EventArray>,
+    mut secondary: mpsc::Sender<EventArray>,
+    modes: DualWriteModes,
+) -> Result<(), ()> {
+    while let Some(mut events) = input.next().await {
+        let finalizers = events.take_finalizers();
+        let count = events.len();
+
+        let mut secondary_events = events.clone();
+        let primary_status = timed(notify(&mut events));
+        let secondary_status = timed(notify(&mut secondary_events));
+        tokio::spawn(reconcile(
+            finalizers,
+            count,
+            modes.mode(),
+            primary_status,
+            secondary_status,
+        ));
+
+        // Either sink stopping stops the other one, as the events could no longer be written to
+        // both.
+        primary.send(events).await.map_err(|_| ())?;
+        secondary.send(secondary_events).await.map_err(|_| ())?;
+    }
+    Ok(())
+}
+
+fn notify(events: &mut EventArray) -> BatchStatusReceiver {
+    BatchNotifier::apply_to(std::slice::from_mut(events))
+}
+
+/// Waits for the status of a batch, timing it from now.
+fn timed(receiver: BatchStatusReceiver) -> impl future::Future<Output = (BatchStatus, Duration)> {
+    let start = Instant::now();
+    async move {
+        let status = receiver.await;
+        (status, start.elapsed())
+    }
+}
+
+async fn reconcile(
+    finalizers: EventFinalizers,
+    count: usize,
+    mode: DualWriteMode,
+    primary: impl future::Future<Output = (BatchStatus, Duration)>,
+    secondary: impl future::Future<Output = (BatchStatus, Duration)>,
+) {
+    let ((primary, primary_latency), (secondary, secondary_latency)) =
+        future::join(primary, secondary).await;
+
+    emit!(DualWriteBatchReconciled {
+        count,
+        primary,
+        secondary,
+        primary_latency,
+        secondary_latency,
+    });
+
+    let status = match mode {
+        DualWriteMode::Primary => primary,
+        DualWriteMode::Secondary => secondary,
+    };
+    finalizers.update_status(match status {
+        BatchStatus::Delivered => EventStatus::Delivered,
+        BatchStatus::Errored => EventStatus::Errored,
+        BatchStatus::Rejected => EventStatus::Rejected,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+    use vector_core::event::{Event, LogEvent};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        test_util::mock::oneshot_sink,
+    };
+
+    /// Writes a batch of events, sets its status on the primary and secondary sinks, and returns
+    /// the status it's acknowledged with.
+    async fn write(mode: DualWriteMode, statuses: [EventStatus; 2]) -> BatchStatus {
+        let (primary_tx, primary_rx) = oneshot::channel();
+        let (secondary_tx, secondary_rx) = oneshot::channel();
+        let cx = SinkContext::new_test();
+        let (primary, _) = oneshot_sink(primary_tx).build(cx.clone()).await.unwrap();
+        let (secondary, _) = oneshot_sink(secondary_tx).build(cx).await.unwrap();
+        let sink = DualWriteSink::new(primary, secondary, DualWriteModes::register(mode));
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from(LogEvent::from("hello").with_batch_notifier(&batch));
+        drop(batch);
+
+        VectorSink::Stream(Box::new(sink))
+            .run(futures::stream::iter([EventArray::from(event)]))
+            .await
+            .unwrap();
+        for (rx, status) in [primary_rx, secondary_rx].into_iter().zip(statuses) {
+            let mut events = rx.await.unwrap();
+            assert_eq!(events.len(), 1);
+            events.take_finalizers().update_status(status);
+        }
+
+        receiver.await
+    }
+
+    #[tokio::test]
+    async fn writes_to_both_sinks() {
+        let statuses = [EventStatus::Delivered, EventStatus::Delivered];
+        assert_eq!(
+            write(DualWriteMode::Primary, statuses).await,
+            BatchStatus::Delivered
+        );
+    }
+
+    #[tokio::test]
+    async fn acknowledges_with_the_authoritative_sink() {
+        let statuses = [EventStatus::Delivered, EventStatus::Rejected];
+        assert_eq!(
+            write(DualWriteMode::Primary, statuses).await,
+            BatchStatus::Delivered
+        );
+        assert_eq!(
+            write(DualWriteMode::Secondary, statuses).await,
+            BatchStatus::Rejected
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use http::HeaderValue;
+use lookup::lookup_v2::ConfigValuePath;
+use vector_common::sensitive_string::SensitiveString;
+
+use crate::{
+    http::HttpClient,
+    sinks::{prelude::*, util::UriSerde},
+};
+
+use super::{
+    encoder::DynatraceEncoder,
+    request_builder::DynatraceRequestBuilder,
+    service::{DynatraceRetryLogic, DynatraceService},
+    sink::DynatraceSink,
+};
+
+/// Batches are kept under the payload and line limits of the metrics ingest API, which are
+/// lower than the ones of the log ingest API.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DynatraceDefaultBatchSettings;
+
+impl SinkBatchSettings for DynatraceDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `dynatrace` sink.
+#[configurable_component(sink("dynatrace", "Deliver log and metric events to Dynatrace."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DynatraceConfig {
+    /// The URL of the Dynatrace environment.
+    ///
+    /// The ingest APIs are reached at `/api/v2/logs/ingest` and `/api/v2/metrics/ingest` under
+    /// this URL, either the URL of a SaaS environment or the environment URL of an ActiveGate.
+    #[configurable(metadata(docs::examples = "https://abc12345.live.dynatrace.com"))]
+    #[configurable(metadata(docs::examples = "https://activegate.example.com:9999/e/abc12345"))]
+    pub endpoint: UriSerde,
+
+    /// The API token used to authenticate.
+    ///
+    /// The token needs the `logs.ingest` scope to send logs, and the `metrics.ingest` scope to
+    /// send metrics.
+    #[configurable(metadata(docs::examples = "${DYNATRACE_API_TOKEN}"))]
+    pub api_token: SensitiveString,
+
+    /// Maps Dynatrace attributes to the fields of log events they are taken from.
+    ///
+    /// The message of the event is sent as the `content` attribute, its timestamp as the
+    /// `timestamp` attribute, and its host as the `host.name` attribute unless that attribute is
+    /// mapped here. Mapped fields are removed from the event, and its remaining fields are sent
+    /// as attributes named after their path.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The field the attribute is taken from."
+    ))]
+    #[configurable(metadata(docs::examples = "attributes_examples()"))]
+    pub attributes: BTreeMap<String, ConfigValuePath>,
+
+    /// The namespace prepended to the keys of metrics, separated by a period.
+    ///
+    /// The namespace of a metric takes precedence over this one.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub default_namespace: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<DynatraceDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn attributes_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("log.source".to_owned(), "file".to_owned()),
+        (
+            "service.name".to_owned(),
+            "kubernetes.container_name".to_owned(),
+        ),
+        ("severity".to_owned(), "level".to_owned()),
+    ])
+}
+
+impl GenerateConfig for DynatraceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://abc12345.live.dynatrace.com"
+            api_token = "${DYNATRACE_API_TOKEN}"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dynatrace")]
+impl SinkConfig for DynatraceConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let encoder = DynatraceEncoder::new(
+            self.encoding.clone(),
+            self.attributes.clone(),
+            self.default_namespace.clone(),
+        );
+        let request_builder = DynatraceRequestBuilder::new(encoder);
+
+        let mut authorization =
+            HeaderValue::from_str(&format!("Api-Token {}", self.api_token.inner()))?;
+        authorization.set_sensitive(true);
+
+        let endpoint = self.endpoint.with_default_parts();
+        let service = DynatraceService::new(
+            client,
+            endpoint.append_path("/api/v2/logs/ingest")?.uri,
+            endpoint.append_path("/api/v2/metrics/ingest")?.uri,
+            authorization,
+        );
+        let service = ServiceBuilder::new()
+            .settings(request_settings, DynatraceRetryLogic)
+            .service(service);
+
+        let sink = DynatraceSink::new(batch_settings, request_builder, service);
+
+        // The ingest APIs cannot be checked without sending data to them, and checking the token
+        // would require the token to have an additional scope.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DynatraceConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let cfg = toml::from_str::<DynatraceConfig>(
+            r#"
+            endpoint = "https://abc12345.live.dynatrace.com"
+            api_token = "dt0c01.token"
+            attributes."service.name" = "kubernetes.container_name"
+            default_namespace = "vector"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.attributes.len(), 1);
+        assert_eq!(cfg.default_namespace.as_deref(), Some("vector"));
+    }
+}
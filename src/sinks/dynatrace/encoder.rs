@@ -0,0 +1,293 @@
+use std::{collections::BTreeMap, fmt::Write as _, io};
+
+use lookup::{lookup_v2::ConfigValuePath, PathPrefix};
+use serde_json::{Map, Value as JsonValue};
+use vector_core::event::{Metric, MetricValue};
+
+use crate::sinks::prelude::*;
+
+/// Encodes the events of a batch, which are either all logs or all metrics, as the batches are
+/// partitioned by event type.
+#[derive(Clone)]
+pub(super) struct DynatraceEncoder {
+    transformer: Transformer,
+    attributes: BTreeMap<String, ConfigValuePath>,
+    default_namespace: Option<String>,
+}
+
+impl DynatraceEncoder {
+    pub(super) const fn new(
+        transformer: Transformer,
+        attributes: BTreeMap<String, ConfigValuePath>,
+        default_namespace: Option<String>,
+    ) -> Self {
+        Self {
+            transformer,
+            attributes,
+            default_namespace,
+        }
+    }
+
+    /// Builds the log ingest record of an event, with the fields mapped to attributes taking
+    /// precedence over the remaining fields of the same name.
+    fn encode_log(&self, mut event: Event) -> Map<String, JsonValue> {
+        self.transformer.transform(&mut event);
+        let mut log = event.into_log();
+        let mut record = Map::new();
+
+        if let Some(content) = log
+            .message_path()
+            .and_then(|path| log.remove(path.as_str()))
+        {
+            record.insert(
+                "content".to_owned(),
+                JsonValue::String(content.to_string_lossy().into_owned()),
+            );
+        }
+        if let Some(timestamp) = log.remove_timestamp() {
+            record.insert("timestamp".to_owned(), to_json(timestamp));
+        }
+        if !self.attributes.contains_key("host.name") {
+            if let Some(host) = log.host_path().and_then(|path| log.remove(path.as_str())) {
+                record.insert("host.name".to_owned(), to_json(host));
+            }
+        }
+        for (attribute, path) in &self.attributes {
+            if let Some(value) = log.remove((PathPrefix::Event, path)) {
+                record.insert(attribute.clone(), to_json(value));
+            }
+        }
+
+        if let (Value::Object(fields), _) = log.into_parts() {
+            flatten_into(&mut record, None, fields);
+        }
+        record
+    }
+
+    /// Writes the line of a metric in the metric ingestion protocol:
+    ///
+    /// `<key>[,<dimension>=<value>]... <payload> [<timestamp>]`
+    fn encode_metric(&self, metric: &Metric, lines: &mut String) {
+        let payload = match metric.value() {
+            MetricValue::Counter { value } => format!("count,delta={}", value),
+            MetricValue::Gauge { value } => format!("gauge,{}", value),
+            MetricValue::Set { values } => format!("gauge,{}", values.len()),
+            MetricValue::Distribution { samples, .. } => {
+                let samples = samples.iter().filter(|sample| sample.rate > 0);
+                let (mut min, mut max, mut sum, mut count) = (f64::MAX, f64::MIN, 0.0, 0);
+                for sample in samples {
+                    min = min.min(sample.value);
+                    max = max.max(sample.value);
+                    sum += sample.value * f64::from(sample.rate);
+                    count += sample.rate;
+                }
+                if count == 0 {
+                    return;
+                }
+                format!("gauge,min={},max={},sum={},count={}", min, max, sum, count)
+            }
+            // The other types are rejected when the metrics are normalized.
+            _ => return,
+        };
+
+        let key = match metric.namespace().or(self.default_namespace.as_deref()) {
+            Some(namespace) => format!("{}.{}", namespace, metric.name()),
+            None => metric.name().to_owned(),
+        };
+        lines.push_str(&sanitize(&key, false));
+
+        if let Some(tags) = metric.tags() {
+            for (key, value) in tags.iter_single() {
+                _ = write!(
+                    lines,
+                    ",{}=\"{}\"",
+                    sanitize(key, true),
+                    value.replace('\\', "\\\\").replace('"', "\\\"")
+                );
+            }
+        }
+
+        _ = write!(lines, " {}", payload);
+        if let Some(timestamp) = metric.timestamp() {
+            _ = write!(lines, " {}", timestamp.timestamp_millis());
+        }
+        lines.push('\n');
+    }
+}
+
+impl encoding::Encoder<Vec<Event>> for DynatraceEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let count = events.len();
+        let body = if matches!(events.first(), Some(Event::Metric(_))) {
+            let mut lines = String::new();
+            for event in events {
+                self.encode_metric(event.as_metric(), &mut lines);
+            }
+            lines.into_bytes()
+        } else {
+            let records = events
+                .into_iter()
+                .map(|event| self.encode_log(event))
+                .collect::<Vec<_>>();
+            serde_json::to_vec(&records)?
+        };
+
+        write_all(writer, count, &body)?;
+        Ok(body.len())
+    }
+}
+
+fn to_json(value: Value) -> JsonValue {
+    serde_json::to_value(value).unwrap_or(JsonValue::Null)
+}
+
+/// Attributes are flat, so nested fields are sent as attributes named after their dotted path.
+fn flatten_into(
+    record: &mut Map<String, JsonValue>,
+    prefix: Option<&str>,
+    fields: BTreeMap<String, Value>,
+) {
+    for (key, value) in fields {
+        let key = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, key),
+            None => key,
+        };
+        match value {
+            Value::Object(fields) => flatten_into(record, Some(&key), fields),
+            value => {
+                record.entry(key).or_insert_with(|| to_json(value));
+            }
+        }
+    }
+}
+
+/// Replaces the characters not allowed in metric keys, or in dimension keys which must also be
+/// lowercase.
+fn sanitize(key: &str, dimension: bool) -> String {
+    key.chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' | '_' | '-' | '.' => c,
+            'A'..='Z' if dimension => c.to_ascii_lowercase(),
+            'A'..='Z' => c,
+            ':' if dimension => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use lookup::lookup_v2::parse_value_path;
+    use vector_core::{
+        event::{metric::Sample, MetricKind, StatisticKind},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::sinks::util::encoding::Encoder as _;
+
+    fn encoder(attributes: &[(&str, &str)]) -> DynatraceEncoder {
+        let attributes = attributes
+            .iter()
+            .map(|(attribute, path)| {
+                (
+                    attribute.to_string(),
+                    ConfigValuePath(parse_value_path(path).unwrap()),
+                )
+            })
+            .collect();
+        DynatraceEncoder::new(
+            Transformer::default(),
+            attributes,
+            Some("vector".to_owned()),
+        )
+    }
+
+    fn encode(encoder: &DynatraceEncoder, events: Vec<Event>) -> String {
+        let mut body = Vec::new();
+        encoder.encode_input(events, &mut body).unwrap();
+        String::from_utf8(body).unwrap()
+    }
+
+    #[test]
+    fn encodes_logs_with_attributes() {
+        let mut log = LogEvent::from("GET /index.html 200");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap(),
+        );
+        log.insert("host", "web-1");
+        log.insert("level", "info");
+        log.insert("http.method", "GET");
+        log.insert("kubernetes.pod_name", "web-1-abcde");
+
+        let body = encode(
+            &encoder(&[
+                ("severity", "level"),
+                ("http.request.method", "http.method"),
+            ]),
+            vec![Event::Log(log)],
+        );
+        let records: JsonValue = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            records,
+            serde_json::json!([{
+                "content": "GET /index.html 200",
+                "timestamp": "2023-06-01T12:00:00Z",
+                "host.name": "web-1",
+                "severity": "info",
+                "http.request.method": "GET",
+                "kubernetes.pod_name": "web-1-abcde",
+            }])
+        );
+    }
+
+    #[test]
+    fn encodes_metrics_in_line_protocol() {
+        let timestamp = Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap();
+        let metric = |name: &str, kind, value| {
+            Event::Metric(
+                Metric::new(name, kind, value)
+                    .with_tags(Some(metric_tags!("Host" => "web 1", "path" => "/\"a\"")))
+                    .with_timestamp(Some(timestamp)),
+            )
+        };
+        let events = vec![
+            metric(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 5.0 },
+            ),
+            metric(
+                "cpu idle",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 97.5 },
+            ),
+            metric(
+                "latency",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vec![
+                        Sample {
+                            value: 1.0,
+                            rate: 2,
+                        },
+                        Sample {
+                            value: 4.0,
+                            rate: 1,
+                        },
+                    ],
+                    statistic: StatisticKind::Histogram,
+                },
+            ),
+        ];
+
+        assert_eq!(
+            encode(&encoder(&[]), events),
+            "vector.requests,host=\"web 1\",path=\"/\\\"a\\\"\" count,delta=5 1685620800000\n\
+             vector.cpu_idle,host=\"web 1\",path=\"/\\\"a\\\"\" gauge,97.5 1685620800000\n\
+             vector.latency,host=\"web 1\",path=\"/\\\"a\\\"\" gauge,min=1,max=4,sum=6,count=3 1685620800000\n"
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! Dynatrace sink
+//!
+//! This sink ships log events to the generic log ingest API, and metric events to the metrics
+//! ingest API of a Dynatrace environment, authenticating with an API token.
+//!
+//! <https://docs.dynatrace.com/docs/shortlink/lma-generic-log-ingestion>
+//! <https://docs.dynatrace.com/docs/shortlink/metric-ingestion-protocol>
+//!
+//! Logs are sent as JSON records with their fields mapped to Dynatrace attributes, and metrics
+//! are encoded in the metric ingestion line protocol. Batches are partitioned by event type, as
+//! each type has its own API.
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::DynatraceConfig;
@@ -0,0 +1,65 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::sinks::prelude::*;
+
+use super::{encoder::DynatraceEncoder, service::DynatraceRequest, sink::EventKind};
+
+pub(super) struct DynatraceMetadata {
+    kind: EventKind,
+    finalizers: EventFinalizers,
+}
+
+#[derive(Clone)]
+pub(super) struct DynatraceRequestBuilder {
+    encoder: DynatraceEncoder,
+}
+
+impl DynatraceRequestBuilder {
+    pub(super) const fn new(encoder: DynatraceEncoder) -> Self {
+        Self { encoder }
+    }
+}
+
+impl RequestBuilder<(EventKind, Vec<Event>)> for DynatraceRequestBuilder {
+    type Metadata = DynatraceMetadata;
+    type Events = Vec<Event>;
+    type Encoder = DynatraceEncoder;
+    type Payload = Bytes;
+    type Request = DynatraceRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (EventKind, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (kind, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (DynatraceMetadata { kind, finalizers }, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        dynatrace_metadata: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let DynatraceMetadata { kind, finalizers } = dynatrace_metadata;
+        DynatraceRequest {
+            kind,
+            finalizers,
+            payload: payload.into_payload(),
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::{header, HeaderValue, StatusCode, Uri};
+use snafu::Snafu;
+use tracing::Instrument;
+
+use crate::{http::HttpClient, sinks::prelude::*};
+
+use super::sink::EventKind;
+
+#[derive(Clone)]
+pub struct DynatraceRetryLogic;
+
+impl RetryLogic for DynatraceRetryLogic {
+    type Error = DynatraceError;
+    type Response = DynatraceResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            DynatraceError::ServerError { code } => {
+                *code == StatusCode::TOO_MANY_REQUESTS || code.is_server_error()
+            }
+            DynatraceError::HttpError { .. } => true,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum DynatraceError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+}
+
+#[derive(Debug)]
+pub struct DynatraceResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for DynatraceResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct DynatraceRequest {
+    pub kind: EventKind,
+    pub finalizers: EventFinalizers,
+    pub payload: Bytes,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for DynatraceRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for DynatraceRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+#[derive(Clone)]
+pub struct DynatraceService {
+    client: HttpClient,
+    logs_uri: Uri,
+    metrics_uri: Uri,
+    authorization: HeaderValue,
+}
+
+impl DynatraceService {
+    pub const fn new(
+        client: HttpClient,
+        logs_uri: Uri,
+        metrics_uri: Uri,
+        authorization: HeaderValue,
+    ) -> Self {
+        Self {
+            client,
+            logs_uri,
+            metrics_uri,
+            authorization,
+        }
+    }
+
+    fn build_request(&self, request: DynatraceRequest) -> http::Request<hyper::Body> {
+        let (uri, content_type) = match request.kind {
+            EventKind::Log => (&self.logs_uri, "application/json; charset=utf-8"),
+            EventKind::Metric => (&self.metrics_uri, "text/plain; charset=utf-8"),
+        };
+        http::Request::post(uri)
+            .header(header::CONTENT_TYPE, content_type)
+            .header(header::AUTHORIZATION, self.authorization.clone())
+            .body(hyper::Body::from(request.payload))
+            .expect("building the request should not fail")
+    }
+}
+
+impl Service<DynatraceRequest> for DynatraceService {
+    type Response = DynatraceResponse;
+    type Error = DynatraceError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: DynatraceRequest) -> Self::Future {
+        let metadata = request.get_metadata();
+        let kind = request.kind;
+        let req = self.build_request(request);
+        let mut client = self.client.clone();
+
+        Box::pin(async move {
+            match client.call(req).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if !status.is_success() {
+                        return Err(DynatraceError::ServerError { code: status });
+                    }
+
+                    // The log ingest API answers `204 No Content` once all records were ingested,
+                    // and `200 OK` with the reasons when some of them were invalid.
+                    if kind == EventKind::Log && status == StatusCode::OK {
+                        let body = hyper::body::to_bytes(response.into_body())
+                            .await
+                            .unwrap_or_default();
+                        warn!(
+                            message = "Some log records were not ingested by Dynatrace.",
+                            response = %String::from_utf8_lossy(&body),
+                            internal_log_rate_limit = true,
+                        );
+                    }
+                    Ok(DynatraceResponse { metadata })
+                }
+                Err(error) => Err(DynatraceError::HttpError { error }),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sends_to_the_api_of_the_event_type() {
+        let service = DynatraceService::new(
+            HttpClient::new(None, &Default::default()).unwrap(),
+            "https://abc12345.live.dynatrace.com/api/v2/logs/ingest"
+                .parse()
+                .unwrap(),
+            "https://abc12345.live.dynatrace.com/api/v2/metrics/ingest"
+                .parse()
+                .unwrap(),
+            HeaderValue::from_static("Api-Token dt0c01.token"),
+        );
+        let request = |kind| DynatraceRequest {
+            kind,
+            finalizers: EventFinalizers::default(),
+            payload: Bytes::new(),
+            metadata: RequestMetadata::default(),
+        };
+
+        let req = service.build_request(request(EventKind::Metric));
+        assert_eq!(req.uri().path(), "/api/v2/metrics/ingest");
+        assert_eq!(
+            req.headers()[header::CONTENT_TYPE],
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            req.headers()[header::AUTHORIZATION],
+            "Api-Token dt0c01.token"
+        );
+
+        let req = service.build_request(request(EventKind::Log));
+        assert_eq!(req.uri().path(), "/api/v2/logs/ingest");
+        assert_eq!(
+            req.headers()[header::CONTENT_TYPE],
+            "application/json; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn retries_throttling_and_server_errors() {
+        let logic = DynatraceRetryLogic;
+        let retriable = |code| logic.is_retriable_error(&DynatraceError::ServerError { code });
+        assert!(retriable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(retriable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!retriable(StatusCode::BAD_REQUEST));
+        assert!(!retriable(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+}
@@ -0,0 +1,109 @@
+use std::num::NonZeroUsize;
+
+use vector_core::event::{Metric, MetricValue};
+
+use crate::{
+    internal_events::{DynatraceInvalidMetricError, SinkRequestBuildError},
+    sinks::{
+        prelude::*,
+        util::buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet},
+    },
+};
+
+use super::{
+    request_builder::DynatraceRequestBuilder,
+    service::{DynatraceRetryLogic, DynatraceService},
+};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EventKind {
+    Log,
+    Metric,
+}
+
+/// Logs and metrics are sent to different APIs, so they are batched separately.
+struct EventKindPartitioner;
+
+impl Partitioner for EventKindPartitioner {
+    type Item = Event;
+    type Key = EventKind;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Metric(_) => EventKind::Metric,
+            _ => EventKind::Log,
+        }
+    }
+}
+
+#[derive(Default)]
+struct DynatraceMetricNormalize;
+
+impl MetricNormalize for DynatraceMetricNormalize {
+    fn normalize(&mut self, state: &mut MetricSet, metric: Metric) -> Option<Metric> {
+        // Counters are sent as deltas, and distributions as the summary of an interval.
+        match &metric.value() {
+            MetricValue::Gauge { .. } => state.make_absolute(metric),
+            MetricValue::Counter { .. }
+            | MetricValue::Set { .. }
+            | MetricValue::Distribution { .. } => state.make_incremental(metric),
+            _ => {
+                emit!(DynatraceInvalidMetricError { metric: &metric });
+                None
+            }
+        }
+    }
+}
+
+pub struct DynatraceSink {
+    batch_settings: BatcherSettings,
+    request_builder: DynatraceRequestBuilder,
+    service: Svc<DynatraceService, DynatraceRetryLogic>,
+}
+
+impl DynatraceSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        request_builder: DynatraceRequestBuilder,
+        service: Svc<DynatraceService, DynatraceRetryLogic>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+        let mut normalizer = MetricNormalizer::<DynatraceMetricNormalize>::default();
+        input
+            .filter_map(move |event| {
+                future::ready(match event {
+                    Event::Metric(metric) => normalizer.normalize(metric).map(Event::Metric),
+                    event => Some(event),
+                })
+            })
+            .batched_partitioned(EventKindPartitioner, self.batch_settings)
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for DynatraceSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
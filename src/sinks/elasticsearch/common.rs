@@ -99,8 +99,8 @@ impl ElasticsearchCommon {
             format!("{}s", tower_request.timeout.as_secs()),
         );
 
-        if let Some(pipeline) = &config.pipeline {
-            query_params.insert("pipeline".into(), pipeline.into());
+        if let Some(pipeline) = config.pipeline.as_ref().filter(|p| !p.is_dynamic()) {
+            query_params.insert("pipeline".into(), pipeline.get_ref().into());
         }
 
         let bulk_url = {
@@ -190,6 +190,7 @@ impl ElasticsearchCommon {
                 doc_type,
                 suppress_type_name,
             },
+            retain_events: config.request_retry_partial,
         };
 
         Ok(Self {
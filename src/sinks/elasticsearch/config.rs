@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     convert::TryFrom,
+    sync::Arc,
 };
 
 use futures::{FutureExt, TryFutureExt};
@@ -11,15 +12,16 @@ use crate::{
     codecs::Transformer,
     config::{AcknowledgementsConfig, DataType, Input, SinkConfig, SinkContext},
     event::{EventRef, LogEvent, Value},
-    http::HttpClient,
+    http::{HttpClient, HttpVersion},
     internal_events::TemplateRenderingError,
     sinks::{
         elasticsearch::{
             health::ElasticsearchHealthLogic,
+            index_management::IndexManager,
             retry::ElasticsearchRetryLogic,
-            service::{ElasticsearchService, HttpRequestBuilder},
+            service::{ElasticsearchService, HttpRequestBuilder, PartialRetrySettings},
             sink::ElasticsearchSink,
-            ElasticsearchApiVersion, ElasticsearchAuth, ElasticsearchCommon,
+            DocumentTemplates, ElasticsearchApiVersion, ElasticsearchAuth, ElasticsearchCommon,
             ElasticsearchCommonMode, ElasticsearchMode,
         },
         util::{
@@ -92,6 +94,14 @@ pub struct ElasticsearchConfig {
 
     /// Whether or not to retry successful requests containing partial failures.
     ///
+    /// Only the items that failed with a transient error, such as `429 Too Many Requests`
+    /// (`es_rejected_execution_exception`) or a server error, are retried, with the backoff and
+    /// the number of attempts taken from the `request` options. Retries happen within the request
+    /// timeout. Items that failed because the write index of their data stream or alias is closed
+    /// (`index_closed_exception`) or undefined are retried once the write index is rolled over or
+    /// resolved again. Items that failed with any other error, such as `mapper_parsing_exception`,
+    /// are not retried and the request is marked as rejected.
+    ///
     /// To avoid duplicates in Elasticsearch, please use option `id_key`.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
@@ -111,10 +121,37 @@ pub struct ElasticsearchConfig {
     pub id_key: Option<String>,
 
     /// The name of the pipeline to apply.
+    ///
+    /// If the template is dynamic, the pipeline is set on each document's bulk action rather than
+    /// on the whole bulk request.
     #[serde(default)]
     #[configurable(metadata(docs::advanced))]
     #[configurable(metadata(docs::examples = "pipeline-name"))]
-    pub pipeline: Option<String>,
+    #[configurable(metadata(docs::examples = "{{ pipeline }}"))]
+    pub pipeline: Option<Template>,
+
+    /// The [routing][es_routing] value to set on each document.
+    ///
+    /// [es_routing]: https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-routing-field.html
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ user_id }}"))]
+    pub routing: Option<Template>,
+
+    /// The [version][es_versioning] to set on each document.
+    ///
+    /// The rendered value must be a non-negative integer. Events for which it is not are dropped.
+    ///
+    /// [es_versioning]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-index_.html#index-versioning
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "{{ sequence }}"))]
+    pub version: Option<Template>,
+
+    #[serde(default)]
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub version_type: Option<VersionType>,
 
     #[serde(default)]
     #[configurable(derived)]
@@ -154,6 +191,10 @@ pub struct ElasticsearchConfig {
     #[configurable(derived)]
     pub aws: Option<RegionOrEndpoint>,
 
+    #[serde(default)]
+    #[configurable(derived)]
+    pub http_version: HttpVersion,
+
     #[serde(default)]
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
@@ -207,6 +248,9 @@ impl Default for ElasticsearchConfig {
             request_retry_partial: false,
             id_key: None,
             pipeline: None,
+            routing: None,
+            version: None,
+            version_type: None,
             mode: Default::default(),
             compression: Default::default(),
             encoding: Default::default(),
@@ -215,6 +259,7 @@ impl Default for ElasticsearchConfig {
             auth: None,
             query: None,
             aws: None,
+            http_version: Default::default(),
             tls: None,
             endpoint_health: None,
             bulk: BulkConfig::default(), // the default mode is Bulk
@@ -237,6 +282,33 @@ impl ElasticsearchConfig {
             )),
         }
     }
+
+    pub fn document_templates(&self) -> DocumentTemplates {
+        DocumentTemplates {
+            routing: self.routing.clone(),
+            // A static pipeline is sent once per request as a query parameter instead.
+            pipeline: self.pipeline.clone().filter(Template::is_dynamic),
+            version: self.version.clone(),
+            version_type: self.version_type,
+        }
+    }
+}
+
+/// The [version type][es_version_type] to set on each document.
+///
+/// [es_version_type]: https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-index_.html#index-version-types
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionType {
+    /// Versioning is handled by Elasticsearch.
+    Internal,
+
+    /// Only index the document if the given version is strictly higher than the stored version.
+    External,
+
+    /// Only index the document if the given version is equal to or higher than the stored version.
+    ExternalGte,
 }
 
 /// Elasticsearch bulk mode configuration.
@@ -318,6 +390,24 @@ pub struct DataStreamConfig {
     /// This ensures that fields match the name of the data stream that is receiving events.
     #[serde(default = "DataStreamConfig::default_sync_fields")]
     pub sync_fields: bool,
+
+    /// Creates the index template of the data streams on startup, if it doesn't exist yet.
+    ///
+    /// The template matches `<type>-<dataset>-<namespace>`, where templated values, and the
+    /// dataset and namespace when `auto_routing` is enabled, are replaced by wildcards. Data
+    /// streams matching it are then created by Elasticsearch on the first write. If none of the
+    /// values are templated, the data stream itself is also created on startup.
+    ///
+    /// An existing template of the same name is never overwritten. The type can't be templated.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub auto_create: bool,
+
+    /// The index lifecycle management (ILM) policy set in the index template created by
+    /// `auto_create`.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::advanced))]
+    pub ilm_policy: Option<String>,
 }
 
 impl Default for DataStreamConfig {
@@ -328,6 +418,8 @@ impl Default for DataStreamConfig {
             namespace: Self::default_namespace(),
             auto_routing: Self::default_auto_routing(),
             sync_fields: Self::default_sync_fields(),
+            auto_create: false,
+            ilm_policy: None,
         }
     }
 }
@@ -463,6 +555,59 @@ impl DataStreamConfig {
         };
         Some(format!("{}-{}-{}", dtype, dataset, namespace))
     }
+
+    /// Returns the name and the body of the index template matching the data streams written to,
+    /// or `None` if the type is templated, as the template would then match any index.
+    pub fn index_template(&self) -> Option<(String, serde_json::Value)> {
+        if self.dtype.is_dynamic() {
+            return None;
+        }
+        let dtype = self.dtype.get_ref();
+        let static_part = |template: &Template| {
+            (!self.auto_routing && !template.is_dynamic()).then(|| template.get_ref())
+        };
+        let dataset = static_part(&self.dataset);
+        let namespace = static_part(&self.namespace);
+
+        let pattern = format!(
+            "{}-{}-{}",
+            dtype,
+            dataset.unwrap_or("*"),
+            namespace.unwrap_or("*")
+        );
+        let name = [Some(dtype), dataset, namespace]
+            .into_iter()
+            .flatten()
+            .fold("vector".to_owned(), |name, part| name + "-" + part);
+
+        let mut template = serde_json::json!({
+            "index_patterns": [pattern],
+            "data_stream": {},
+            // Takes precedence over the built-in templates of Elasticsearch, with priority 100,
+            // which would otherwise overlap.
+            "priority": 200,
+            "_meta": { "managed_by": "vector" },
+        });
+        if let Some(policy) = &self.ilm_policy {
+            template["template"] = serde_json::json!({
+                "settings": { "index.lifecycle.name": policy }
+            });
+        }
+        Some((name, template))
+    }
+
+    /// Returns the name of the only data stream written to, if none of its parts are templated.
+    pub fn static_name(&self) -> Option<String> {
+        let parts = [&self.dtype, &self.dataset, &self.namespace];
+        (!self.auto_routing && parts.iter().all(|part| !part.is_dynamic())).then(|| {
+            format!(
+                "{}-{}-{}",
+                self.dtype.get_ref(),
+                self.dataset.get_ref(),
+                self.namespace.get_ref()
+            )
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -472,7 +617,12 @@ impl SinkConfig for ElasticsearchConfig {
         let commons = ElasticsearchCommon::parse_many(self, cx.proxy()).await?;
         let common = commons[0].clone();
 
-        let client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
+        let client = HttpClient::new_with_version(
+            common.tls_settings.clone(),
+            cx.proxy(),
+            self.http_version,
+            cx.connection_pool(),
+        )?;
 
         let request_limits = self
             .request
@@ -481,23 +631,47 @@ impl SinkConfig for ElasticsearchConfig {
 
         let health_config = self.endpoint_health.clone().unwrap_or_default();
 
+        if let ElasticsearchCommonMode::DataStream(data_stream) = &common.mode {
+            if data_stream.auto_create {
+                // Endpoints are expected to be nodes of the same cluster, so this is done once.
+                let manager = IndexManager::new(
+                    client.clone(),
+                    Arc::new(HttpRequestBuilder::new(&common, self)),
+                );
+                // Like the API version detection, this is not fatal, as the cluster could be
+                // unavailable for the time being.
+                if let Err(error) = manager.ensure_data_stream(data_stream).await {
+                    warn!(
+                        message = "Failed to create the data stream. Events will be rejected if it doesn't exist.",
+                        %error
+                    );
+                }
+            }
+        }
+
         let services = commons
             .iter()
             .cloned()
             .map(|common| {
                 let endpoint = common.base_url.clone();
 
-                let http_request_builder = HttpRequestBuilder::new(&common, self);
-                let service = ElasticsearchService::new(client.clone(), http_request_builder);
+                let http_request_builder = Arc::new(HttpRequestBuilder::new(&common, self));
+                let partial_retry = self.request_retry_partial.then(|| {
+                    PartialRetrySettings::new(
+                        common.request_builder.clone(),
+                        IndexManager::new(client.clone(), Arc::clone(&http_request_builder)),
+                        &request_limits,
+                    )
+                });
+                let service =
+                    ElasticsearchService::new(client.clone(), http_request_builder, partial_retry);
 
                 (endpoint, service)
             })
             .collect::<Vec<_>>();
 
         let service = request_limits.distributed_service(
-            ElasticsearchRetryLogic {
-                retry_partial: self.request_retry_partial,
-            },
+            ElasticsearchRetryLogic,
             services,
             health_config,
             ElasticsearchHealthLogic,
@@ -617,4 +791,48 @@ mod tests {
         assert_eq!(config.mode, ElasticsearchMode::Bulk);
         assert_eq!(config.bulk, BulkConfig::default());
     }
+
+    #[test]
+    fn data_stream_index_template() {
+        let config = DataStreamConfig {
+            auto_routing: false,
+            namespace: Template::try_from("{{ environment }}").unwrap(),
+            ilm_policy: Some("logs".to_owned()),
+            ..Default::default()
+        };
+        let (name, template) = config.index_template().unwrap();
+        assert_eq!(name, "vector-logs-generic");
+        assert_eq!(
+            template,
+            serde_json::json!({
+                "index_patterns": ["logs-generic-*"],
+                "data_stream": {},
+                "priority": 200,
+                "_meta": { "managed_by": "vector" },
+                "template": { "settings": { "index.lifecycle.name": "logs" } },
+            })
+        );
+        assert_eq!(config.static_name(), None);
+
+        let config = DataStreamConfig::default();
+        let (name, template) = config.index_template().unwrap();
+        assert_eq!(name, "vector-logs");
+        assert_eq!(template["index_patterns"], serde_json::json!(["logs-*-*"]));
+        assert_eq!(config.static_name(), None);
+
+        let config = DataStreamConfig {
+            auto_routing: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.static_name(),
+            Some("logs-generic-default".to_owned())
+        );
+
+        let config = DataStreamConfig {
+            dtype: Template::try_from("{{ type }}").unwrap(),
+            ..Default::default()
+        };
+        assert!(config.index_template().is_none());
+    }
 }
@@ -9,17 +9,49 @@ use crate::{
     codecs::Transformer,
     event::{EventFinalizers, Finalizable, LogEvent},
     sinks::{
-        elasticsearch::BulkAction,
+        elasticsearch::{BulkAction, VersionType},
         util::encoding::{as_tracked_write, Encoder},
     },
 };
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
     pub log: LogEvent,
     pub id: Option<String>,
+    pub document_metadata: DocumentMetadata,
+}
+
+/// Rendered per-document metadata added to the bulk action.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DocumentMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_type: Option<VersionType>,
+}
+
+impl ByteSizeOf for DocumentMetadata {
+    fn allocated_bytes(&self) -> usize {
+        self.routing.allocated_bytes() + self.pipeline.allocated_bytes()
+    }
+}
+
+#[derive(Serialize)]
+struct BulkActionMetadata<'a> {
+    #[serde(rename = "_index")]
+    index: &'a str,
+    #[serde(rename = "_type", skip_serializing_if = "Option::is_none")]
+    doc_type: Option<&'a str>,
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(flatten)]
+    document_metadata: &'a DocumentMetadata,
 }
 
 impl Finalizable for ProcessedEvent {
@@ -30,7 +62,10 @@ impl Finalizable for ProcessedEvent {
 
 impl ByteSizeOf for ProcessedEvent {
     fn allocated_bytes(&self) -> usize {
-        self.index.allocated_bytes() + self.log.allocated_bytes() + self.id.allocated_bytes()
+        self.index.allocated_bytes()
+            + self.log.allocated_bytes()
+            + self.id.allocated_bytes()
+            + self.document_metadata.allocated_bytes()
     }
 }
 
@@ -74,6 +109,7 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
                 &self.doc_type,
                 self.suppress_type_name,
                 &event.id,
+                &event.document_metadata,
             )?;
             written_bytes +=
                 as_tracked_write::<_, _, io::Error>(writer, &log, |mut writer, log| {
@@ -94,35 +130,22 @@ fn write_bulk_action(
     doc_type: &str,
     suppress_type: bool,
     id: &Option<String>,
+    document_metadata: &DocumentMetadata,
 ) -> std::io::Result<usize> {
-    as_tracked_write(
+    let metadata = BulkActionMetadata {
+        index,
+        doc_type: (!suppress_type).then_some(doc_type),
+        id: id.as_deref(),
+        document_metadata,
+    };
+    as_tracked_write::<_, _, io::Error>(
         writer,
-        (bulk_action, index, doc_type, id, suppress_type),
-        |writer, (bulk_action, index, doc_type, id, suppress_type)| match (id, suppress_type) {
-            (Some(id), true) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, id
-                )
-            }
-            (Some(id), false) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, doc_type, id
-                )
-            }
-            (None, true) => {
-                write!(writer, r#"{{"{}":{{"_index":"{}"}}}}"#, bulk_action, index)
-            }
-            (None, false) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}"}}}}"#,
-                    bulk_action, index, doc_type
-                )
-            }
+        (bulk_action, metadata),
+        |mut writer, (bulk_action, metadata)| {
+            write!(writer, r#"{{"{}":"#, bulk_action)?;
+            serde_json::to_writer(&mut writer, &metadata)?;
+            writer.write_all(b"}")?;
+            Ok(())
         },
     )
 }
@@ -142,6 +165,7 @@ mod tests {
             "TYPE",
             true,
             &Some("ID".to_string()),
+            &DocumentMetadata::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -163,7 +187,15 @@ mod tests {
     fn suppress_type_without_id() {
         let mut writer = Vec::new();
 
-        _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", true, &None);
+        _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &DocumentMetadata::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -190,6 +222,7 @@ mod tests {
             "TYPE",
             false,
             &Some("ID".to_string()),
+            &DocumentMetadata::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -212,7 +245,15 @@ mod tests {
     fn type_without_id() {
         let mut writer = Vec::new();
 
-        _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", false, &None);
+        _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            false,
+            &None,
+            &DocumentMetadata::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -228,4 +269,38 @@ mod tests {
         assert!(nested.contains_key("_type"));
         assert_eq!(nested.get("_type").unwrap().as_str(), Some("TYPE"));
     }
+
+    #[test]
+    fn with_document_metadata() {
+        let mut writer = Vec::new();
+
+        _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &DocumentMetadata {
+                routing: Some("ROUTING".to_string()),
+                pipeline: Some("PIPELINE".to_string()),
+                version: Some(42),
+                version_type: Some(VersionType::ExternalGte),
+            },
+        );
+
+        let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
+        let nested = value.get("ACTION").unwrap().as_object().unwrap();
+
+        assert_eq!(nested.get("_index").unwrap().as_str(), Some("INDEX"));
+        assert_eq!(nested.get("routing").unwrap().as_str(), Some("ROUTING"));
+        assert_eq!(nested.get("pipeline").unwrap().as_str(), Some("PIPELINE"));
+        assert_eq!(nested.get("version").unwrap().as_u64(), Some(42));
+        assert_eq!(
+            nested.get("version_type").unwrap().as_str(),
+            Some("external_gte")
+        );
+        assert!(!nested.contains_key("_id"));
+        assert!(!nested.contains_key("_type"));
+    }
 }
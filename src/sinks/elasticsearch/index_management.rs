@@ -0,0 +1,198 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bytes::Bytes;
+use http::{Method, Response, StatusCode};
+use serde::Deserialize;
+
+use crate::http::HttpClient;
+
+use super::{retry::WriteIndexFailure, service::HttpRequestBuilder, DataStreamConfig};
+
+/// Manages the indices written to through the index APIs of Elasticsearch.
+#[derive(Clone)]
+pub struct IndexManager {
+    client: HttpClient,
+    request_builder: Arc<HttpRequestBuilder>,
+}
+
+impl IndexManager {
+    pub fn new(client: HttpClient, request_builder: Arc<HttpRequestBuilder>) -> Self {
+        Self {
+            client,
+            request_builder,
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Bytes,
+    ) -> crate::Result<Response<Bytes>> {
+        let request = self
+            .request_builder
+            .build_api_request(method, path, body)
+            .await?;
+        let response = self.client.send(request.map(hyper::Body::from)).await?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        Ok(Response::from_parts(parts, body))
+    }
+
+    /// Creates the index template of the data streams written to, and the data stream itself if
+    /// its name is static, unless they already exist.
+    pub async fn ensure_data_stream(&self, data_stream: &DataStreamConfig) -> crate::Result<()> {
+        let (name, template) = data_stream
+            .index_template()
+            .ok_or("The index template can't be created if `data_stream.type` is templated.")?;
+
+        let path = format!("/_index_template/{}", name);
+        match self.send(Method::HEAD, &path, Bytes::new()).await?.status() {
+            StatusCode::OK => debug!(message = "Index template already exists.", %name),
+            StatusCode::NOT_FOUND => {
+                let body = Bytes::from(serde_json::to_vec(&template)?);
+                let response = self.send(Method::PUT, &path, body).await?;
+                if !response.status().is_success() {
+                    return Err(format!(
+                        "Failed to create index template {}: {}",
+                        name,
+                        String::from_utf8_lossy(response.body())
+                    )
+                    .into());
+                }
+                info!(message = "Created index template.", %name);
+            }
+            status => {
+                return Err(format!("Unexpected status getting index template: {}", status).into())
+            }
+        }
+
+        if let Some(stream) = data_stream.static_name() {
+            let path = format!("/_data_stream/{}", stream);
+            let response = self.send(Method::GET, &path, Bytes::new()).await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                let response = self.send(Method::PUT, &path, Bytes::new()).await?;
+                if !response.status().is_success() {
+                    return Err(format!(
+                        "Failed to create data stream {}: {}",
+                        stream,
+                        String::from_utf8_lossy(response.body())
+                    )
+                    .into());
+                }
+                info!(message = "Created data stream.", %stream);
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the write index of the data stream or alias items failed to be written to.
+    ///
+    /// A closed write index is rolled over, so that the target keeps its name and `None` is
+    /// returned. An alias without a write index is resolved to its newest index.
+    pub async fn resolve_write_index(
+        &self,
+        target: &str,
+        failure: WriteIndexFailure,
+    ) -> crate::Result<Option<String>> {
+        match failure {
+            WriteIndexFailure::IndexClosed => {
+                let path = format!("/{}/_rollover", target);
+                let response = self.send(Method::POST, &path, Bytes::new()).await?;
+                if !response.status().is_success() {
+                    return Err(format!(
+                        "Failed to roll over {}: {}",
+                        target,
+                        String::from_utf8_lossy(response.body())
+                    )
+                    .into());
+                }
+                Ok(None)
+            }
+            WriteIndexFailure::NoWriteIndex => {
+                let path = format!("/_alias/{}", target);
+                let response = self.send(Method::GET, &path, Bytes::new()).await?;
+                if !response.status().is_success() {
+                    return Err(format!(
+                        "Failed to get indices of alias {}: {}",
+                        target,
+                        String::from_utf8_lossy(response.body())
+                    )
+                    .into());
+                }
+                let indices: HashMap<String, AliasIndex> = serde_json::from_slice(response.body())?;
+                select_write_index(target, indices)
+                    .map(Some)
+                    .ok_or_else(|| format!("Alias {} has no indices.", target).into())
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct AliasIndex {
+    #[serde(default)]
+    aliases: HashMap<String, AliasSettings>,
+}
+
+#[derive(Deserialize)]
+struct AliasSettings {
+    is_write_index: Option<bool>,
+}
+
+/// Selects the write index of the alias, falling back to its newest index. Indices rolled over
+/// have an incrementing suffix, so the newest one sorts last.
+fn select_write_index(alias: &str, indices: HashMap<String, AliasIndex>) -> Option<String> {
+    let is_write_index = |index: &AliasIndex| {
+        index
+            .aliases
+            .get(alias)
+            .and_then(|settings| settings.is_write_index)
+            .unwrap_or(false)
+    };
+    let write_index = indices
+        .iter()
+        .find(|(_, index)| is_write_index(index))
+        .map(|(name, _)| name.clone());
+    write_index.or_else(|| indices.into_keys().max())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> HashMap<String, AliasIndex> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn selects_write_index_of_alias() {
+        let indices = parse(
+            r#"{
+                "logs-000001": {"aliases": {"logs": {"is_write_index": false}}},
+                "logs-000002": {"aliases": {"logs": {"is_write_index": true}}},
+                "logs-000003": {"aliases": {"logs": {}}}
+            }"#,
+        );
+        assert_eq!(
+            select_write_index("logs", indices),
+            Some("logs-000002".to_owned())
+        );
+    }
+
+    #[test]
+    fn selects_newest_index_without_write_index() {
+        let indices = parse(
+            r#"{
+                "logs-000002": {"aliases": {"logs": {}}},
+                "logs-000010": {"aliases": {"logs": {}}},
+                "logs-000001": {"aliases": {"logs": {}}}
+            }"#,
+        );
+        assert_eq!(
+            select_write_index("logs", indices),
+            Some("logs-000010".to_owned())
+        );
+        assert_eq!(select_write_index("logs", HashMap::new()), None);
+    }
+}
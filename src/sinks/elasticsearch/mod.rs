@@ -2,6 +2,7 @@ mod common;
 mod config;
 mod encoder;
 mod health;
+mod index_management;
 mod request_builder;
 mod retry;
 mod service;
@@ -18,7 +19,7 @@ use std::convert::TryFrom;
 
 pub use common::*;
 pub use config::*;
-pub use encoder::ElasticsearchEncoder;
+pub use encoder::{DocumentMetadata, ElasticsearchEncoder};
 use http::{uri::InvalidUri, Request};
 use snafu::Snafu;
 use vector_common::sensitive_string::SensitiveString;
@@ -27,7 +28,7 @@ use vector_config::configurable_component;
 use crate::aws::AwsAuthentication;
 use crate::{
     event::{EventRef, LogEvent},
-    internal_events::TemplateRenderingError,
+    internal_events::{ElasticsearchInvalidDocumentVersionError, TemplateRenderingError},
     template::{Template, TemplateParseError},
 };
 
@@ -170,6 +171,69 @@ impl ElasticsearchCommonMode {
     }
 }
 
+/// Per-document bulk action metadata templates.
+#[derive(Clone, Debug, Default)]
+pub struct DocumentTemplates {
+    pub routing: Option<Template>,
+    pub pipeline: Option<Template>,
+    pub version: Option<Template>,
+    pub version_type: Option<VersionType>,
+}
+
+impl DocumentTemplates {
+    /// Renders the templates against the given event.
+    ///
+    /// Returns `None`, after emitting an error, if the event should be dropped because one of the
+    /// templates could not be rendered.
+    fn render(&self, log: &LogEvent) -> Option<DocumentMetadata> {
+        let routing = render_optional(&self.routing, log, "routing")?;
+        let pipeline = render_optional(&self.pipeline, log, "pipeline")?;
+        let version = match render_optional(&self.version, log, "version")? {
+            Some(version) => match version.parse::<u64>() {
+                Ok(version) => Some(version),
+                Err(error) => {
+                    emit!(ElasticsearchInvalidDocumentVersionError {
+                        version: &version,
+                        error,
+                    });
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        Some(DocumentMetadata {
+            routing,
+            pipeline,
+            version,
+            version_type: self.version_type,
+        })
+    }
+}
+
+/// Renders an optional template, returning `None` if rendering failed and `Some(None)` if there is
+/// no template to render.
+fn render_optional(
+    template: &Option<Template>,
+    log: &LogEvent,
+    field: &'static str,
+) -> Option<Option<String>> {
+    match template {
+        Some(template) => template
+            .render_string(log)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+            .map(Some),
+        None => Some(None),
+    }
+}
+
 /// Configuration for Elasticsearch API version.
 #[configurable_component]
 #[derive(Clone, Debug, Eq, PartialEq)]
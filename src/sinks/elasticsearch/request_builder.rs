@@ -20,12 +20,16 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    /// Whether to keep a copy of the events in the request so that failed items can be re-encoded
+    /// and retried on their own.
+    pub retain_events: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: JsonSize,
+    original_events: Vec<ProcessedEvent>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -56,10 +60,18 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
 
+        let finalizers = events.take_finalizers();
+        let original_events = if self.retain_events {
+            events.clone()
+        } else {
+            Vec::new()
+        };
+
         let es_metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers,
             batch_size: events.len(),
             events_byte_size,
+            original_events,
         };
         (es_metadata, metadata_builder, events)
     }
@@ -76,6 +88,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             batch_size: es_metadata.batch_size,
             events_byte_size: es_metadata.events_byte_size,
             metadata,
+            original_events: es_metadata.original_events,
         }
     }
 }
@@ -1,21 +1,44 @@
+use std::collections::HashMap;
+
 use http::StatusCode;
 use serde::Deserialize;
 
 use crate::{
     http::HttpError,
+    internal_events::ElasticsearchBulkActionRejected,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
         util::retries::{RetryAction, RetryLogic},
     },
 };
 
+/// The reason items failed to be written to the write index of their data stream or alias.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WriteIndexFailure {
+    /// The write index is closed.
+    IndexClosed,
+    /// The alias has no write index.
+    NoWriteIndex,
+}
+
+/// The failed items of a bulk request, by position.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct FailedItems {
+    /// Items that failed with a retriable error.
+    pub(super) retriable: Vec<usize>,
+    /// Items that can be retried once the write index of their target is resolved again.
+    pub(super) write_index: Vec<(usize, WriteIndexFailure)>,
+    /// The number of items that failed with an error that can't be fixed by retrying.
+    pub(super) rejected: usize,
+}
+
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
+pub(super) struct EsResultResponse {
     items: Vec<EsResultItem>,
 }
 
 impl EsResultResponse {
-    fn parse(body: &str) -> Result<Self, String> {
+    pub(super) fn parse(body: &str) -> Result<Self, String> {
         serde_json::from_str::<EsResultResponse>(body).map_err(|json_error| {
             format!(
                 "some messages failed, could not parse response, error: {}",
@@ -24,20 +47,35 @@ impl EsResultResponse {
         })
     }
 
-    /// Returns iterator over status codes for items and optional error details.
-    fn iter_status(&self) -> impl Iterator<Item = (StatusCode, Option<&EsErrorDetails>)> {
-        self.items.iter().filter_map(|item| {
-            item.result()
+    /// Classifies the items that failed by whether they can be retried.
+    ///
+    /// Items are returned by Elasticsearch in the same order as the actions of the bulk request.
+    pub(super) fn classify_failed_items(&self) -> FailedItems {
+        let mut failed = FailedItems::default();
+        for (index, item) in self.items.iter().enumerate() {
+            let result = item.result();
+            let status = result
                 .status
-                .and_then(|status| StatusCode::from_u16(status).ok())
-                .map(|status| (status, item.result().error.as_ref()))
-        })
+                .and_then(|status| StatusCode::from_u16(status).ok());
+            match status {
+                Some(status) if status.is_success() => {}
+                status if is_retriable_item(status, result.error.as_ref()) => {
+                    failed.retriable.push(index)
+                }
+                _ => match result.error.as_ref().and_then(write_index_failure) {
+                    Some(failure) => failed.write_index.push((index, failure)),
+                    None => failed.rejected += 1,
+                },
+            }
+        }
+        failed
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.items.len()
     }
 
     /// Selects the first error since logging all errors would be quite verbose and many are duplicates.
-    /// If partial retry is enabled and we don't retry, this is because there is no retriable error in the
-    /// response, thus all errors are equally interesting so logging the first is sufficient.
-    /// When partial retry is disabled, we don't retry on any error.
     fn get_error_reason(&self, body: &str) -> String {
         match self
             .items
@@ -48,6 +86,54 @@ impl EsResultResponse {
             None => format!("error response: {}", body),
         }
     }
+
+    /// Emits one error per distinct error type found among the rejected items, so that rejection
+    /// reasons are visible in the component's metrics.
+    pub(super) fn emit_rejections(&self) {
+        let mut rejections: HashMap<&str, (&str, usize)> = HashMap::new();
+        for error in self
+            .items
+            .iter()
+            .filter_map(|item| item.result().error.as_ref())
+        {
+            rejections
+                .entry(error.err_type.as_str())
+                .or_insert((error.reason.as_str(), 0))
+                .1 += 1;
+        }
+
+        for (error_code, (reason, count)) in rejections {
+            emit!(ElasticsearchBulkActionRejected {
+                error_code,
+                reason,
+                count,
+            });
+        }
+    }
+}
+
+/// Backpressure and server errors are transient, so the item can be retried. Other errors, such as
+/// `mapper_parsing_exception`, fail again no matter how often the item is retried.
+fn is_retriable_item(status: Option<StatusCode>, error: Option<&EsErrorDetails>) -> bool {
+    matches!(status, Some(status) if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error())
+        || matches!(error, Some(error) if error.err_type == "es_rejected_execution_exception")
+}
+
+/// Writes to a closed write index, or to an alias without one, fail until the write index is
+/// rolled over or resolved again.
+fn write_index_failure(error: &EsErrorDetails) -> Option<WriteIndexFailure> {
+    match error.err_type.as_str() {
+        "index_closed_exception" => Some(WriteIndexFailure::IndexClosed),
+        "illegal_argument_exception"
+            if error.reason.contains("no write index is defined")
+                || error
+                    .reason
+                    .contains("has more than one index associated with it") =>
+        {
+            Some(WriteIndexFailure::NoWriteIndex)
+        }
+        _ => None,
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -82,9 +168,7 @@ struct EsErrorDetails {
 }
 
 #[derive(Clone)]
-pub struct ElasticsearchRetryLogic {
-    pub retry_partial: bool,
-}
+pub struct ElasticsearchRetryLogic;
 
 impl RetryLogic for ElasticsearchRetryLogic {
     type Error = HttpError;
@@ -118,32 +202,11 @@ impl RetryLogic for ElasticsearchRetryLogic {
                 let body = String::from_utf8_lossy(response.http_response.body());
 
                 if body.contains("\"errors\":true") {
+                    // Retrying the whole request would duplicate the items that succeeded. If
+                    // partial retries are enabled, the failed items have already been retried
+                    // individually by the service, so whatever is left is not retried again.
                     match EsResultResponse::parse(&body) {
-                        Ok(resp) => {
-                            if self.retry_partial {
-                                // We will retry if there exists at least one item that
-                                // failed with a retriable error.
-                                // Those are backpressure and server errors.
-                                if let Some((status, error)) =
-                                    resp.iter_status().find(|(status, _)| {
-                                        *status == StatusCode::TOO_MANY_REQUESTS
-                                            || status.is_server_error()
-                                    })
-                                {
-                                    let msg = if let Some(error) = error {
-                                        format!(
-                                            "partial error, status: {}, error type: {}, reason: {}",
-                                            status, error.err_type, error.reason
-                                        )
-                                    } else {
-                                        format!("partial error, status: {}", status)
-                                    };
-                                    return RetryAction::Retry(msg.into());
-                                }
-                            }
-
-                            RetryAction::DontRetry(resp.get_error_reason(&body).into())
-                        }
+                        Ok(resp) => RetryAction::DontRetry(resp.get_error_reason(&body).into()),
                         Err(msg) => RetryAction::DontRetry(msg.into()),
                     }
                 } else {
@@ -172,9 +235,7 @@ mod tests {
             .status(StatusCode::OK)
             .body(Bytes::from(json))
             .unwrap();
-        let logic = ElasticsearchRetryLogic {
-            retry_partial: false,
-        };
+        let logic = ElasticsearchRetryLogic;
         assert!(matches!(
             logic.should_retry_response(&ElasticsearchResponse {
                 http_response: response,
@@ -187,15 +248,13 @@ mod tests {
     }
 
     #[test]
-    fn handles_partial_error_response() {
+    fn does_not_retry_whole_request_on_partial_error() {
         let json = "{\"took\":34,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-asjkf1234\",\"_type\":\"log_lines\",\"_id\":\"4Z3QLYEBT52RtoOEKz2H\",\"status\":429}}]}";
         let response = Response::builder()
             .status(StatusCode::OK)
             .body(Bytes::from(json))
             .unwrap();
-        let logic = ElasticsearchRetryLogic {
-            retry_partial: true,
-        };
+        let logic = ElasticsearchRetryLogic;
         assert!(matches!(
             logic.should_retry_response(&ElasticsearchResponse {
                 http_response: response,
@@ -203,10 +262,50 @@ mod tests {
                 batch_size: 1,
                 events_byte_size: JsonSize::new(1),
             }),
-            RetryAction::Retry(_)
+            RetryAction::DontRetry(_)
         ));
     }
 
+    #[test]
+    fn classifies_failed_items() {
+        let json = r#"{"took":34,"errors":true,"items":[
+            {"index":{"_index":"test","status":201}},
+            {"index":{"_index":"test","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}},
+            {"index":{"_index":"test","status":400,"error":{"type":"mapper_parsing_exception","reason":"failed to parse"}}},
+            {"create":{"_index":"test","status":503,"error":{"type":"unavailable_shards_exception","reason":"primary shard is not active"}}},
+            {"create":{"_index":"test","status":201}}
+        ]}"#;
+        let resp = EsResultResponse::parse(json).unwrap();
+        let failed = resp.classify_failed_items();
+        assert_eq!(failed.retriable, vec![1, 3]);
+        assert!(failed.write_index.is_empty());
+        assert_eq!(failed.rejected, 1);
+        assert_eq!(resp.len(), 5);
+    }
+
+    #[test]
+    fn classifies_write_index_failures() {
+        let json = r#"{"took":3,"errors":true,"items":[
+            {"create":{"_index":".ds-logs-generic-default-2023.06.01-000001","status":400,"error":{"type":"index_closed_exception","reason":"closed"}}},
+            {"index":{"_index":"logs","status":400,"error":{"type":"illegal_argument_exception","reason":"no write index is defined for alias [logs]. The write index may be explicitly disabled using is_write_index=false or the alias points to multiple indices without one being designated as a write index"}}},
+            {"index":{"_index":"logs","status":400,"error":{"type":"illegal_argument_exception","reason":"mapper [message] of different type, current_type [long], merged_type [text]"}}}
+        ]}"#;
+        let failed = EsResultResponse::parse(json)
+            .unwrap()
+            .classify_failed_items();
+        assert_eq!(
+            failed,
+            FailedItems {
+                retriable: vec![],
+                write_index: vec![
+                    (0, WriteIndexFailure::IndexClosed),
+                    (1, WriteIndexFailure::NoWriteIndex)
+                ],
+                rejected: 1,
+            }
+        );
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
@@ -1,14 +1,15 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::Arc,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use aws_types::credentials::SharedCredentialsProvider;
 use aws_types::region::Region;
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use http::{Response, Uri};
+use http::{Method, Response, Uri};
 use hyper::{service::Service, Body, Request};
 use tower::ServiceExt;
 use vector_common::{
@@ -23,11 +24,18 @@ use crate::{
     http::{Auth, HttpClient},
     sinks::util::{
         http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+        retries::ExponentialBackoff,
+        Compression, ElementCount, RequestBuilder, TowerRequestSettings,
     },
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{
+    encoder::ProcessedEvent,
+    index_management::IndexManager,
+    request_builder::ElasticsearchRequestBuilder,
+    retry::{EsResultResponse, FailedItems, WriteIndexFailure},
+    ElasticsearchCommon, ElasticsearchConfig,
+};
 
 #[derive(Clone, Debug)]
 pub struct ElasticsearchRequest {
@@ -36,11 +44,15 @@ pub struct ElasticsearchRequest {
     pub batch_size: usize,
     pub events_byte_size: JsonSize,
     pub metadata: RequestMetadata,
+    /// The events encoded in `payload`. Only kept when partial retries are enabled.
+    pub original_events: Vec<ProcessedEvent>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
     fn allocated_bytes(&self) -> usize {
-        self.payload.allocated_bytes() + self.finalizers.allocated_bytes()
+        self.payload.allocated_bytes()
+            + self.finalizers.allocated_bytes()
+            + self.original_events.allocated_bytes()
     }
 }
 
@@ -68,25 +80,138 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    partial_retry: Option<PartialRetrySettings>,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
-        http_request_builder: HttpRequestBuilder,
+        http_request_builder: Arc<HttpRequestBuilder>,
+        partial_retry: Option<PartialRetrySettings>,
     ) -> ElasticsearchService {
-        let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
             let request_builder = Arc::clone(&http_request_builder);
             let future: BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>> =
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            partial_retry,
+        }
+    }
+}
+
+/// Settings for retrying the failed items of a bulk request on their own.
+#[derive(Clone)]
+pub struct PartialRetrySettings {
+    request_builder: ElasticsearchRequestBuilder,
+    index_manager: IndexManager,
+    attempts: usize,
+    timeout: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl PartialRetrySettings {
+    pub fn new(
+        request_builder: ElasticsearchRequestBuilder,
+        index_manager: IndexManager,
+        settings: &TowerRequestSettings,
+    ) -> Self {
+        Self {
+            request_builder,
+            index_manager,
+            attempts: settings.retry_attempts,
+            timeout: settings.timeout,
+            initial_backoff: settings.retry_initial_backoff_secs,
+            max_backoff: settings.retry_max_duration_secs,
+        }
+    }
+
+    fn backoff(&self) -> ExponentialBackoff {
+        // Doubles the delay on every attempt, starting from the initial backoff.
+        let factor = (self.initial_backoff.as_millis() / 2).max(1) as u64;
+        ExponentialBackoff::from_millis(2)
+            .factor(factor)
+            .max_delay(self.max_backoff)
+    }
+
+    /// Resolves the write index of the targets of the items that failed to be written to it, once
+    /// per target, and returns the positions of the items that can be retried. Items whose target
+    /// is written to a different index from then on are updated.
+    async fn resolve_write_indices(
+        &self,
+        events: &mut [ProcessedEvent],
+        failures: &[(usize, WriteIndexFailure)],
+        resolved: &mut HashSet<String>,
+    ) -> Vec<usize> {
+        // For each target, `None` if it couldn't be resolved, or else the index to write to instead
+        // of it, if any.
+        let mut targets: HashMap<String, Option<Option<String>>> = HashMap::new();
+        for (position, failure) in failures {
+            let target = events[*position].index.clone();
+            if !targets.contains_key(&target) {
+                // A target that still fails once resolved is not resolved again.
+                let index = if resolved.insert(target.clone()) {
+                    self.index_manager
+                        .resolve_write_index(&target, *failure)
+                        .await
+                        .map_err(|error| {
+                            warn!(
+                                message = "Failed to resolve write index.",
+                                %target,
+                                %error,
+                                internal_log_rate_limit = true,
+                            );
+                        })
+                        .ok()
+                } else {
+                    None
+                };
+                targets.insert(target.clone(), index);
+            }
+        }
+
+        let mut retriable = Vec::new();
+        for (position, _) in failures {
+            let event = &mut events[*position];
+            if let Some(index) = &targets[&event.index] {
+                if let Some(index) = index {
+                    event.index = index.clone();
+                }
+                retriable.push(*position);
+            }
+        }
+        retriable
+    }
+
+    /// Builds a request containing only the events at the given positions of the previous attempt.
+    fn retry_request(
+        &self,
+        request: &ElasticsearchRequest,
+        events: Vec<ProcessedEvent>,
+        retriable: Vec<usize>,
+    ) -> crate::Result<ElasticsearchRequest> {
+        let mut retriable = retriable.into_iter().peekable();
+        let events = events
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, event)| retriable.next_if_eq(&index).map(|_| event))
+            .collect::<Vec<_>>();
+
+        let payload = self.request_builder.encode_events(events.clone())?;
+        Ok(ElasticsearchRequest {
+            payload: payload.into_payload(),
+            finalizers: EventFinalizers::default(),
+            original_events: events,
+            ..request.clone()
+        })
     }
 }
 
 pub struct HttpRequestBuilder {
+    pub base_url: String,
     pub bulk_uri: Uri,
     pub query_params: HashMap<String, String>,
     pub region: Option<Region>,
@@ -99,6 +224,7 @@ pub struct HttpRequestBuilder {
 impl HttpRequestBuilder {
     pub fn new(common: &ElasticsearchCommon, config: &ElasticsearchConfig) -> HttpRequestBuilder {
         HttpRequestBuilder {
+            base_url: common.base_url.clone(),
             bulk_uri: common.bulk_uri.clone(),
             http_request_config: config.request.clone(),
             http_auth: common.http_auth.clone(),
@@ -143,6 +269,38 @@ impl HttpRequestBuilder {
 
         Ok(request)
     }
+
+    /// Builds a request to another API than the bulk API, such as the index APIs.
+    pub async fn build_api_request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Bytes,
+    ) -> Result<Request<Bytes>, crate::Error> {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path));
+
+        if !body.is_empty() {
+            builder = builder.header("Content-Type", "application/json");
+        }
+
+        for (header, value) in &self.http_request_config.headers {
+            builder = builder.header(&header[..], &value[..]);
+        }
+
+        if let Some(auth) = &self.http_auth {
+            builder = auth.apply_builder(builder);
+        }
+
+        let mut request = builder.body(body)?;
+
+        if let Some(credentials_provider) = &self.credentials_provider {
+            sign_request(&mut request, credentials_provider, &self.region).await?;
+        }
+
+        Ok(request)
+    }
 }
 
 pub struct ElasticsearchResponse {
@@ -173,30 +331,106 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     }
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
-    fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
+    fn call(&mut self, mut req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let partial_retry = self.partial_retry.clone();
         Box::pin(async move {
-            http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size = req.events_byte_size;
-            let http_response = http_service.call(req).await?;
-
-            let event_status = get_event_status(&http_response);
-            Ok(ElasticsearchResponse {
-                event_status,
-                http_response,
-                batch_size,
-                events_byte_size,
-            })
+
+            let started = Instant::now();
+            let mut backoff = partial_retry.as_ref().map(PartialRetrySettings::backoff);
+            let mut attempts = 0;
+            let mut rejected = false;
+            let mut resolved = HashSet::new();
+
+            loop {
+                http_service.ready().await?;
+                let mut events = std::mem::take(&mut req.original_events);
+                let http_response = http_service.call(req.clone()).await?;
+
+                if let (Some(settings), Some(backoff)) = (&partial_retry, &mut backoff) {
+                    if let Some(result) = get_partial_failures(&http_response) {
+                        let FailedItems {
+                            mut retriable,
+                            write_index,
+                            rejected: mut rejected_items,
+                        } = result.classify_failed_items();
+                        // The items of the response map to the events of the request by position,
+                        // which can only be relied upon if both have the same length.
+                        let same_length = result.len() == events.len();
+
+                        if same_length && !write_index.is_empty() && attempts < settings.attempts {
+                            let resolved_items = settings
+                                .resolve_write_indices(&mut events, &write_index, &mut resolved)
+                                .await;
+                            rejected_items += write_index.len() - resolved_items.len();
+                            retriable.extend(resolved_items);
+                            retriable.sort_unstable();
+                        } else {
+                            rejected_items += write_index.len();
+                        }
+
+                        let delay = backoff.next().unwrap_or(settings.max_backoff);
+                        let can_retry = !retriable.is_empty() && same_length;
+                        // Retries must complete within the request timeout, otherwise the whole
+                        // request would be retried, including the items that already succeeded.
+                        let within_timeout = started.elapsed() + delay < settings.timeout;
+
+                        if can_retry && attempts < settings.attempts && within_timeout {
+                            result.emit_rejections();
+                            rejected |= rejected_items > 0;
+
+                            req = settings.retry_request(&req, events, retriable)?;
+                            warn!(
+                                message = "Retrying failed items of bulk request.",
+                                count = req.original_events.len(),
+                                delay_ms = %delay.as_millis(),
+                                internal_log_rate_limit = true,
+                            );
+                            tokio::time::sleep(delay).await;
+                            attempts += 1;
+                            continue;
+                        }
+                    }
+                }
+
+                let mut event_status = get_event_status(&http_response);
+                // Items rejected by a previous attempt are not part of this response.
+                if rejected && event_status == EventStatus::Delivered {
+                    event_status = EventStatus::Rejected;
+                }
+                return Ok(ElasticsearchResponse {
+                    event_status,
+                    http_response,
+                    batch_size,
+                    events_byte_size,
+                });
+            }
         })
     }
 }
 
+fn get_partial_failures(response: &Response<Bytes>) -> Option<EsResultResponse> {
+    if !response.status().is_success() {
+        return None;
+    }
+    let body = String::from_utf8_lossy(response.body());
+    if body.contains("\"errors\":true") {
+        EsResultResponse::parse(&body).ok()
+    } else {
+        None
+    }
+}
+
 fn get_event_status(response: &Response<Bytes>) -> EventStatus {
     let status = response.status();
     if status.is_success() {
         let body = String::from_utf8_lossy(response.body());
         if body.contains("\"errors\":true") {
+            if let Ok(result) = EsResultResponse::parse(&body) {
+                result.emit_rejections();
+            }
             EventStatus::Rejected
         } else {
             EventStatus::Delivered
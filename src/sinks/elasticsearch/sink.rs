@@ -12,7 +12,7 @@ use crate::{
     sinks::{
         elasticsearch::{
             encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder,
-            service::ElasticsearchRequest, BulkAction, ElasticsearchCommonMode,
+            service::ElasticsearchRequest, BulkAction, DocumentTemplates, ElasticsearchCommonMode,
         },
         util::{SinkBuilderExt, StreamSink},
     },
@@ -35,6 +35,7 @@ pub struct ElasticsearchSink<S> {
     pub metric_to_log: MetricToLog,
     pub mode: ElasticsearchCommonMode,
     pub id_key_field: Option<String>,
+    pub document_templates: DocumentTemplates,
 }
 
 impl<S> ElasticsearchSink<S> {
@@ -53,6 +54,7 @@ impl<S> ElasticsearchSink<S> {
             metric_to_log: common.metric_to_log.clone(),
             mode: common.mode.clone(),
             id_key_field: config.id_key.clone(),
+            document_templates: config.document_templates(),
         })
     }
 }
@@ -69,6 +71,7 @@ where
 
         let mode = self.mode;
         let id_key_field = self.id_key_field;
+        let document_templates = self.document_templates;
         let transformer = self.transformer.clone();
 
         input
@@ -86,7 +89,13 @@ where
             })
             .filter_map(|x| async move { x })
             .filter_map(move |log| {
-                future::ready(process_log(log, &mode, &id_key_field, &transformer))
+                future::ready(process_log(
+                    log,
+                    &mode,
+                    &id_key_field,
+                    &document_templates,
+                    &transformer,
+                ))
             })
             .batched(self.batch_settings.into_byte_size_config())
             .request_builder(request_builder_concurrency_limit, self.request_builder)
@@ -111,10 +120,12 @@ pub(super) fn process_log(
     mut log: LogEvent,
     mode: &ElasticsearchCommonMode,
     id_key_field: &Option<String>,
+    document_templates: &DocumentTemplates,
     transformer: &Transformer,
 ) -> Option<ProcessedEvent> {
     let index = mode.index(&log)?;
     let bulk_action = mode.bulk_action(&log)?;
+    let document_metadata = document_templates.render(&log)?;
 
     if let Some(cfg) = mode.as_data_stream_config() {
         cfg.sync_fields(&mut log);
@@ -138,6 +149,7 @@ pub(super) fn process_log(
         bulk_action,
         log,
         id,
+        document_metadata,
     })
 }
 
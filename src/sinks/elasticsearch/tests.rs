@@ -6,7 +6,7 @@ use crate::{
     sinks::{
         elasticsearch::{
             sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion,
-            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode,
+            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode, VersionType,
         },
         util::encoding::Encoder,
     },
@@ -53,7 +53,7 @@ async fn sets_create_action_when_configured() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -107,7 +107,7 @@ async fn encode_datastream_mode() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -158,7 +158,7 @@ async fn encode_datastream_mode_no_routing() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -194,7 +194,7 @@ async fn handle_metrics() {
     es.request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -303,7 +303,7 @@ async fn encode_datastream_mode_no_sync() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -343,7 +343,7 @@ async fn allows_using_except_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -378,7 +378,7 @@ async fn allows_using_only_fields() {
         .request_builder
         .encoder
         .encode_input(
-            vec![process_log(log, &es.mode, &None, &config.encoding).unwrap()],
+            vec![process_log(log, &es.mode, &None, &Default::default(), &config.encoding).unwrap()],
             &mut encoded,
         )
         .unwrap();
@@ -389,3 +389,54 @@ async fn allows_using_only_fields() {
     assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
     assert_eq!(encoded.len(), encoded_size);
 }
+
+#[tokio::test]
+async fn encodes_templated_document_metadata() {
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            index: parse_template("vector"),
+            ..Default::default()
+        },
+        pipeline: Some(parse_template("{{ pipeline }}")),
+        routing: Some(parse_template("{{ user }}")),
+        version: Some(parse_template("{{ seq }}")),
+        version_type: Some(VersionType::External),
+        endpoints: vec![String::from("https://example.com")],
+        api_version: ElasticsearchApiVersion::V7,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+    // Dynamic pipelines are set per document rather than on the request.
+    assert!(!es.query_params.contains_key("pipeline"));
+
+    let document_templates = config.document_templates();
+    let mut log = LogEvent::from("hello there");
+    log.insert("pipeline", "geoip");
+    log.insert("user", "alice");
+    log.insert("seq", 7);
+
+    let mut encoded = vec![];
+    es.request_builder
+        .encoder
+        .encode_input(
+            vec![process_log(
+                log.clone(),
+                &es.mode,
+                &None,
+                &document_templates,
+                &config.encoding,
+            )
+            .unwrap()],
+            &mut encoded,
+        )
+        .unwrap();
+
+    let expected = r#"{"index":{"_index":"vector","routing":"alice","pipeline":"geoip","version":7,"version_type":"external"}}
+{"message":"hello there","pipeline":"geoip","seq":7,"user":"alice"}
+"#;
+    assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
+
+    // Documents whose version isn't an integer are dropped.
+    log.insert("seq", "seven");
+    assert!(process_log(log, &es.mode, &None, &document_templates, &config.encoding).is_none());
+}
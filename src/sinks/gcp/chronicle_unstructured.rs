@@ -199,7 +199,7 @@ impl SinkConfig for ChronicleUnstructuredConfig {
         let creds = self.auth.build(Scope::MalachiteIngestion).await?;
 
         let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
 
         let endpoint = self.create_endpoint("v2/unstructuredlogentries:batchCreate")?;
 
@@ -210,7 +210,7 @@ impl SinkConfig for GcsSinkConfig {
         let auth = self.auth.build(Scope::DevStorageReadWrite).await?;
         let base_url = format!("{}{}/", BASE_URL, self.bucket);
         let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
         let healthcheck = build_healthcheck(
             self.bucket.clone(),
             client.clone(),
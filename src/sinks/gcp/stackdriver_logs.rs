@@ -55,8 +55,24 @@ pub struct StackdriverConfig {
     pub log_id: Template,
 
     /// The monitored resource to associate the logs with.
+    ///
+    /// When `resource_detection` is set, the type and labels configured here override the detected
+    /// ones.
+    #[serde(default)]
     pub resource: StackdriverResource,
 
+    #[configurable(derived)]
+    pub resource_detection: Option<StackdriverResourceDetection>,
+
+    /// Labels to attach to each log entry.
+    ///
+    /// Label values are templates, rendered for each event. Labels whose value fails to render are
+    /// left out of the entry.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "A log entry label."))]
+    #[configurable(metadata(docs::examples = "entry_label_examples()"))]
+    pub labels: HashMap<String, Template>,
+
     /// The field of the log event from which to take the outgoing log’s `severity` field.
     ///
     /// The named field is removed from the log event if present, and must be either an integer
@@ -127,8 +143,9 @@ pub enum StackdriverLogName {
     ///
     ///	Exactly one of `billing_account_id`, `folder_id`, `organization_id`, or `project_id` must be set.
     #[serde(rename = "billing_account_id")]
+    #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "012345-6789AB-CDEF01"))]
-    BillingAccount(String),
+    BillingAccount(Template),
 
     /// The folder ID to which to publish logs.
     ///
@@ -138,8 +155,9 @@ pub enum StackdriverLogName {
     ///
     /// [folder_docs]: https://cloud.google.com/resource-manager/docs/creating-managing-folders
     #[serde(rename = "folder_id")]
+    #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "My Folder"))]
-    Folder(String),
+    Folder(Template),
 
     /// The organization ID to which to publish logs.
     ///
@@ -147,8 +165,9 @@ pub enum StackdriverLogName {
     ///
     ///	Exactly one of `billing_account_id`, `folder_id`, `organization_id`, or `project_id` must be set.
     #[serde(rename = "organization_id")]
+    #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "622418129737"))]
-    Organization(String),
+    Organization(Template),
 
     /// The project ID to which to publish logs.
     ///
@@ -159,8 +178,9 @@ pub enum StackdriverLogName {
     /// [project_docs]: https://cloud.google.com/resource-manager/docs/creating-managing-projects
     #[derivative(Default)]
     #[serde(rename = "project_id")]
+    #[configurable(metadata(docs::templateable))]
     #[configurable(metadata(docs::examples = "vector-123456"))]
-    Project(String),
+    Project(Template),
 }
 
 /// A monitored resource.
@@ -186,7 +206,9 @@ pub struct StackdriverResource {
     ///
     /// [gcp_resources]: https://cloud.google.com/monitoring/api/resources
     #[serde(rename = "type")]
-    pub type_: String,
+    #[configurable(metadata(docs::examples = "global", docs::examples = "{{ resource_type }}"))]
+    #[configurable(metadata(docs::templateable))]
+    pub type_: Template,
 
     /// Type-specific labels.
     #[serde(flatten)]
@@ -202,6 +224,141 @@ fn label_examples() -> HashMap<String, String> {
     example
 }
 
+fn entry_label_examples() -> HashMap<String, String> {
+    let mut example = HashMap::new();
+    example.insert(
+        "app".to_string(),
+        "{{ kubernetes.pod_labels.app }}".to_string(),
+    );
+    example.insert("team".to_string(), "observability".to_string());
+    example
+}
+
+const METADATA_URL: &str = "http://metadata.google.internal/computeMetadata/v1";
+
+/// Detection of the monitored resource from the Compute Engine metadata server.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StackdriverResourceDetection {
+    /// Associate logs with the Compute Engine instance Vector runs on, as a `gce_instance`.
+    Gce,
+
+    /// Associate logs with the Kubernetes container they originate from, as a `k8s_container`.
+    ///
+    /// The cluster is detected from the metadata server of the GKE node Vector runs on, and the
+    /// Namespace, Pod, and container names are taken from the fields added by the
+    /// `kubernetes_logs` source.
+    Gke,
+}
+
+/// The attributes of the instance Vector runs on, as reported by the metadata server.
+#[derive(Debug, Default)]
+struct InstanceMetadata {
+    project_id: String,
+    instance_id: String,
+    zone: String,
+    cluster_name: Option<String>,
+    cluster_location: Option<String>,
+}
+
+impl InstanceMetadata {
+    async fn fetch(client: &HttpClient) -> crate::Result<Self> {
+        let zone = fetch_metadata(client, "instance/zone")
+            .await?
+            .ok_or("metadata server did not report the instance zone")?;
+        Ok(Self {
+            project_id: fetch_metadata(client, "project/project-id")
+                .await?
+                .ok_or("metadata server did not report the project ID")?,
+            instance_id: fetch_metadata(client, "instance/id")
+                .await?
+                .ok_or("metadata server did not report the instance ID")?,
+            // The zone is reported as `projects/<number>/zones/<zone>`.
+            zone: zone.rsplit('/').next().unwrap_or_default().to_owned(),
+            cluster_name: fetch_metadata(client, "instance/attributes/cluster-name").await?,
+            cluster_location: fetch_metadata(client, "instance/attributes/cluster-location")
+                .await?,
+        })
+    }
+}
+
+/// Fetches a value from the metadata server, or `None` if it isn't defined.
+async fn fetch_metadata(client: &HttpClient, path: &str) -> crate::Result<Option<String>> {
+    let request = Request::get(format!("{}/{}", METADATA_URL, path))
+        .header("Metadata-Flavor", "Google")
+        .body(Body::empty())?;
+    let response = client.send(request).await?;
+    let status = response.status();
+    if status == http::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(format!("metadata server returned {} for {}", status, path).into());
+    }
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    Ok(Some(String::from_utf8_lossy(&body).trim().to_owned()))
+}
+
+impl StackdriverResourceDetection {
+    fn resource(self, metadata: &InstanceMetadata) -> crate::Result<StackdriverResource> {
+        let literal = |value: &str| Template::try_from(value).map_err(crate::Error::from);
+        let (type_, labels) = match self {
+            Self::Gce => (
+                "gce_instance",
+                vec![
+                    ("project_id", literal(&metadata.project_id)?),
+                    ("instance_id", literal(&metadata.instance_id)?),
+                    ("zone", literal(&metadata.zone)?),
+                ],
+            ),
+            Self::Gke => {
+                let cluster_name = metadata
+                    .cluster_name
+                    .as_deref()
+                    .ok_or("`gke` resource detection requires running on a GKE node")?;
+                let location = metadata
+                    .cluster_location
+                    .as_deref()
+                    .unwrap_or(&metadata.zone);
+                (
+                    "k8s_container",
+                    vec![
+                        ("project_id", literal(&metadata.project_id)?),
+                        ("location", literal(location)?),
+                        ("cluster_name", literal(cluster_name)?),
+                        ("namespace_name", literal("{{ kubernetes.pod_namespace }}")?),
+                        ("pod_name", literal("{{ kubernetes.pod_name }}")?),
+                        (
+                            "container_name",
+                            literal("{{ kubernetes.container_name }}")?,
+                        ),
+                    ],
+                )
+            }
+        };
+
+        Ok(StackdriverResource {
+            type_: literal(type_)?,
+            labels: labels
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        })
+    }
+}
+
+impl StackdriverResource {
+    /// Overrides the type and labels of a detected resource with the configured ones.
+    fn merge_into(&self, mut detected: StackdriverResource) -> StackdriverResource {
+        if !self.type_.is_empty() {
+            detected.type_ = self.type_.clone();
+        }
+        detected.labels.extend(self.labels.clone());
+        detected
+    }
+}
+
 impl_generate_config_from_default!(StackdriverConfig);
 
 #[async_trait::async_trait]
@@ -221,10 +378,22 @@ impl SinkConfig for StackdriverConfig {
                 .rate_limit_num(1000),
         );
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
+
+        let mut config = self.clone();
+        match self.resource_detection {
+            Some(detection) => {
+                let metadata = InstanceMetadata::fetch(&HttpClient::new(None, cx.proxy())?).await?;
+                config.resource = self.resource.merge_into(detection.resource(&metadata)?);
+            }
+            None if self.resource.type_.is_empty() => {
+                return Err("`resource.type` must be set unless `resource_detection` is".into());
+            }
+            None => {}
+        }
 
         let sink = StackdriverSink {
-            config: self.clone(),
+            config,
             auth: auth.clone(),
             severity_key: self.severity_key.clone(),
             uri: self.endpoint.parse().unwrap(),
@@ -278,13 +447,44 @@ impl HttpEventEncoder<serde_json::Value> for StackdriverEventEncoder {
                 .ok()?;
             labels.insert(key.clone(), value);
         }
+        let resource_type = self
+            .config
+            .resource
+            .type_
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(crate::internal_events::TemplateRenderingError {
+                    error,
+                    field: Some("resource.type"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+        let entry_labels = self
+            .config
+            .labels
+            .iter()
+            .filter_map(|(key, template)| {
+                template
+                    .render_string(&event)
+                    .map_err(|error| {
+                        emit!(crate::internal_events::TemplateRenderingError {
+                            error,
+                            field: Some("labels"),
+                            drop_event: false,
+                        });
+                    })
+                    .ok()
+                    .map(|value| (key.clone(), value))
+            })
+            .collect::<HashMap<_, _>>();
         let log_name = self
             .config
             .log_name(&event)
             .map_err(|error| {
                 emit!(crate::internal_events::TemplateRenderingError {
                     error,
-                    field: Some("log_id"),
+                    field: Some("log_name"),
                     drop_event: true,
                 });
             })
@@ -303,17 +503,20 @@ impl HttpEventEncoder<serde_json::Value> for StackdriverEventEncoder {
 
         let log = event.into_log();
 
-        let mut entry = map::Map::with_capacity(5);
+        let mut entry = map::Map::with_capacity(6);
         entry.insert("logName".into(), json!(log_name));
         entry.insert("jsonPayload".into(), json!(log));
         entry.insert("severity".into(), json!(severity));
         entry.insert(
             "resource".into(),
             json!({
-                "type": self.config.resource.type_,
+                "type": resource_type,
                 "labels": labels,
             }),
         );
+        if !entry_labels.is_empty() {
+            entry.insert("labels".into(), json!(entry_labels));
+        }
 
         // If the event contains a timestamp, send it in the main message so gcp can pick it up.
         if let Some(timestamp) = log.get_timestamp() {
@@ -406,10 +609,20 @@ impl StackdriverConfig {
         let log_id = self.log_id.render_string(event)?;
 
         Ok(match &self.log_name {
-            BillingAccount(acct) => format!("billingAccounts/{}/logs/{}", acct, log_id),
-            Folder(folder) => format!("folders/{}/logs/{}", folder, log_id),
-            Organization(org) => format!("organizations/{}/logs/{}", org, log_id),
-            Project(project) => format!("projects/{}/logs/{}", project, log_id),
+            BillingAccount(acct) => format!(
+                "billingAccounts/{}/logs/{}",
+                acct.render_string(event)?,
+                log_id
+            ),
+            Folder(folder) => format!("folders/{}/logs/{}", folder.render_string(event)?, log_id),
+            Organization(org) => format!(
+                "organizations/{}/logs/{}",
+                org.render_string(event)?,
+                log_id
+            ),
+            Project(project) => {
+                format!("projects/{}/logs/{}", project.render_string(event)?, log_id)
+            }
         })
     }
 }
@@ -502,6 +715,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_dynamic_names_and_labels() {
+        let config: StackdriverConfig = toml::from_str(indoc! {r#"
+            project_id = "{{ project }}"
+            log_id = "testlogs"
+            resource.type = "{{ resource_type }}"
+            labels.app = "{{ app }}"
+            labels.missing = "{{ missing }}"
+            labels.team = "observability"
+            encoding.only_fields = ["message"]
+        "#})
+        .unwrap();
+
+        let sink = StackdriverSink {
+            config,
+            auth: GcpAuthenticator::None,
+            severity_key: None,
+            uri: default_endpoint().parse().unwrap(),
+        };
+        let mut encoder = sink.build_encoder();
+
+        let log = [
+            ("message", "hello world"),
+            ("project", "tenant-a"),
+            ("resource_type", "global"),
+            ("app", "api"),
+        ]
+        .iter()
+        .copied()
+        .collect::<LogEvent>();
+        let json = encoder.encode_event(Event::from(log)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "logName":"projects/tenant-a/logs/testlogs",
+                "jsonPayload":{"message":"hello world"},
+                "severity":0,
+                "resource":{"type":"global","labels":{}},
+                "labels":{"app":"api","team":"observability"}
+            })
+        );
+    }
+
+    #[test]
+    fn detects_gke_resource() {
+        let metadata = InstanceMetadata {
+            project_id: "project".into(),
+            instance_id: "1234".into(),
+            zone: "us-central1-a".into(),
+            cluster_name: Some("cluster".into()),
+            cluster_location: Some("us-central1".into()),
+        };
+        let configured = StackdriverResource {
+            type_: Template::default(),
+            labels: HashMap::from([("cluster_name".to_owned(), "override".try_into().unwrap())]),
+        };
+        let resource = configured.merge_into(
+            StackdriverResourceDetection::Gke
+                .resource(&metadata)
+                .unwrap(),
+        );
+
+        let mut log = LogEvent::from("hello");
+        log.insert("kubernetes.pod_namespace", "default");
+        log.insert("kubernetes.pod_name", "api-0");
+        log.insert("kubernetes.container_name", "api");
+        let event = Event::from(log);
+
+        assert_eq!(
+            resource.type_.render_string(&event).unwrap(),
+            "k8s_container"
+        );
+        let labels = resource
+            .labels
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.render_string(&event).unwrap()))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(
+            labels,
+            HashMap::from([
+                ("project_id", "project".to_owned()),
+                ("location", "us-central1".to_owned()),
+                ("cluster_name", "override".to_owned()),
+                ("namespace_name", "default".to_owned()),
+                ("pod_name", "api-0".to_owned()),
+                ("container_name", "api".to_owned()),
+            ])
+        );
+
+        let metadata = InstanceMetadata {
+            cluster_name: None,
+            ..metadata
+        };
+        assert!(StackdriverResourceDetection::Gke
+            .resource(&metadata)
+            .is_err());
+    }
+
     #[test]
     fn encode_inserts_timestamp() {
         let config: StackdriverConfig = toml::from_str(indoc! {r#"
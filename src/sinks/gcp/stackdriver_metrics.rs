@@ -109,7 +109,7 @@ impl SinkConfig for StackdriverConfig {
                 .rate_limit_num(1000),
         );
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let batch_settings = self.batch.into_batch_settings()?;
 
         auth.spawn_regenerate_token();
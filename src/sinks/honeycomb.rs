@@ -1,7 +1,9 @@
 use bytes::Bytes;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
+use hyper::Body;
 use serde_json::json;
+use tokio::sync::mpsc;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vrl::value::Kind;
@@ -9,7 +11,7 @@ use vrl::value::Kind;
 use crate::{
     codecs::Transformer,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, Value},
+    event::{Event, LogEvent, Value},
     http::HttpClient,
     schema,
     sinks::util::{
@@ -37,6 +39,20 @@ pub struct HoneycombConfig {
     // but this limits us in how we can do our healthcheck.
     dataset: String,
 
+    /// Whether or not to create the dataset if it doesn't exist.
+    ///
+    /// The dataset is looked up with the management API when the sink starts, which requires the
+    /// API key to have the permission to create datasets.
+    #[serde(default)]
+    create_dataset: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    format: HoneycombFormat,
+
+    #[configurable(derived)]
+    markers: Option<MarkersConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     batch: BatchConfig<HoneycombDefaultBatchSettings>,
@@ -62,7 +78,42 @@ pub struct HoneycombConfig {
 }
 
 fn default_endpoint() -> String {
-    "https://api.honeycomb.io/1/batch".to_string()
+    "https://api.honeycomb.io/1".to_string()
+}
+
+/// The format of the batches sent to Honeycomb.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HoneycombFormat {
+    /// Send batches as JSON.
+    #[default]
+    Json,
+
+    /// Send batches as [MessagePack][msgpack], which is more compact and cheaper to encode than
+    /// JSON.
+    ///
+    /// [msgpack]: https://msgpack.org/
+    Msgpack,
+}
+
+/// Configuration for sending events as Honeycomb markers.
+///
+/// Markers annotate the graphs of a dataset with points in time, such as deployments.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MarkersConfig {
+    /// The field that flags an event as a marker.
+    ///
+    /// Events with this field are sent to the Markers API instead of the dataset. The value of the
+    /// field is either the type of the marker, or an object with optional `message`, `type`, and
+    /// `url` keys. The timestamp of the event is the time of the marker, and, if not set by the
+    /// object, its message is the message of the event.
+    ///
+    /// Markers are not batched, and failures to send them are logged rather than retried.
+    #[configurable(metadata(docs::examples = "honeycomb_marker"))]
+    field: String,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -96,10 +147,23 @@ impl SinkConfig for HoneycombConfig {
 
         let buffer = JsonArrayBuffer::new(batch_settings.size);
 
-        let client = HttpClient::new(None, cx.proxy())?;
+        let client = HttpClient::new_with_pool(None, cx.proxy(), cx.connection_pool())?;
+
+        if self.create_dataset {
+            self.ensure_dataset(&client).await?;
+        }
+
+        let markers = self.markers.is_some().then(|| {
+            let (markers, receiver) = mpsc::unbounded_channel();
+            tokio::spawn(send_markers(self.clone(), client.clone(), receiver));
+            markers
+        });
 
         let sink = BatchedHttpSink::new(
-            self.clone(),
+            HoneycombSink {
+                config: self.clone(),
+                markers,
+            },
             buffer,
             request_settings,
             batch_settings.timeout,
@@ -127,6 +191,44 @@ impl SinkConfig for HoneycombConfig {
 
 pub struct HoneycombEventEncoder {
     transformer: Transformer,
+    marker_field: Option<String>,
+    markers: Option<mpsc::UnboundedSender<serde_json::Value>>,
+}
+
+impl HoneycombEventEncoder {
+    /// Builds the Markers API payload of the event if it is flagged as a marker.
+    fn encode_marker(&self, log: &mut LogEvent) -> Option<serde_json::Value> {
+        let marker = log.remove(self.marker_field.as_deref()?)?;
+
+        let mut payload = serde_json::Map::new();
+        match marker {
+            Value::Object(fields) => {
+                for key in ["message", "type", "url"] {
+                    if let Some(value) = fields.get(key) {
+                        payload.insert(key.into(), value.to_string_lossy().into_owned().into());
+                    }
+                }
+            }
+            marker => {
+                payload.insert("type".into(), marker.to_string_lossy().into_owned().into());
+            }
+        }
+        if !payload.contains_key("message") {
+            if let Some(message) = log.get_message() {
+                payload.insert(
+                    "message".into(),
+                    message.to_string_lossy().into_owned().into(),
+                );
+            }
+        }
+        let timestamp = match log.get_timestamp() {
+            Some(Value::Timestamp(ts)) => *ts,
+            _ => chrono::Utc::now(),
+        };
+        payload.insert("start_time".into(), timestamp.timestamp().into());
+
+        Some(payload.into())
+    }
 }
 
 impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
@@ -134,6 +236,13 @@ impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
         self.transformer.transform(&mut event);
         let mut log = event.into_log();
 
+        if let Some(marker) = self.encode_marker(&mut log) {
+            if let Some(markers) = &self.markers {
+                _ = markers.send(marker);
+            }
+            return None;
+        }
+
         let timestamp = if let Some(Value::Timestamp(ts)) = log.remove_timestamp() {
             ts
         } else {
@@ -149,40 +258,131 @@ impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
     }
 }
 
+pub struct HoneycombSink {
+    config: HoneycombConfig,
+    markers: Option<mpsc::UnboundedSender<serde_json::Value>>,
+}
+
 #[async_trait::async_trait]
-impl HttpSink for HoneycombConfig {
+impl HttpSink for HoneycombSink {
     type Input = serde_json::Value;
     type Output = Vec<BoxedRawValue>;
     type Encoder = HoneycombEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HoneycombEventEncoder {
-            transformer: self.encoding.clone(),
+            transformer: self.config.encoding.clone(),
+            marker_field: self
+                .config
+                .markers
+                .as_ref()
+                .map(|markers| markers.field.clone()),
+            markers: self.markers.clone(),
         }
     }
 
     async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
-        let uri = self.build_uri();
+        self.config.build_request(events)
+    }
+}
+
+impl HoneycombConfig {
+    fn build_request(&self, events: Vec<BoxedRawValue>) -> crate::Result<Request<Bytes>> {
+        let uri = self.build_uri("batch");
         let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.inner());
-        let body = crate::serde::json::to_bytes(&events).unwrap().freeze();
+        let (request, body) = match self.format {
+            HoneycombFormat::Json => (
+                request.header("Content-Type", "application/json"),
+                crate::serde::json::to_bytes(&events).unwrap().freeze(),
+            ),
+            HoneycombFormat::Msgpack => {
+                // The batch buffer holds raw JSON, so parse it back to encode it as MessagePack.
+                let events = events
+                    .iter()
+                    .map(|event| serde_json::from_str(event.get()))
+                    .collect::<Result<Vec<serde_json::Value>, _>>()?;
+                (
+                    request.header("Content-Type", "application/msgpack"),
+                    rmp_serde::to_vec_named(&events)?.into(),
+                )
+            }
+        };
 
         request.body(body).map_err(Into::into)
     }
-}
 
-impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", self.endpoint, self.dataset);
+    fn build_uri(&self, api: &str) -> Uri {
+        let uri = format!("{}/{}/{}", self.endpoint, api, self.dataset);
 
         uri.parse::<Uri>().expect("This should be a valid uri")
     }
+
+    /// Creates the dataset with the management API if it doesn't exist yet.
+    async fn ensure_dataset(&self, client: &HttpClient) -> crate::Result<()> {
+        let request = Request::get(self.build_uri("datasets"))
+            .header("X-Honeycomb-Team", self.api_key.inner())
+            .body(Body::empty())?;
+        let response = client.send(request).await?;
+        match response.status() {
+            status if status.is_success() => return Ok(()),
+            StatusCode::NOT_FOUND => {}
+            status => {
+                return Err(
+                    format!("Failed to look up dataset, server returned {}.", status).into(),
+                )
+            }
+        }
+
+        let uri = format!("{}/datasets", self.endpoint)
+            .parse::<Uri>()
+            .expect("This should be a valid uri");
+        let body = serde_json::to_vec(&json!({ "name": self.dataset }))?;
+        let request = Request::post(uri)
+            .header("X-Honeycomb-Team", self.api_key.inner())
+            .header("Content-Type", "application/json")
+            .body(Body::from(body))?;
+        let response = client.send(request).await?;
+        let status = response.status();
+        if status.is_success() || status == StatusCode::CONFLICT {
+            info!(message = "Created Honeycomb dataset.", dataset = %self.dataset);
+            Ok(())
+        } else {
+            let body = hyper::body::to_bytes(response.into_body()).await?;
+            Err(format!(
+                "Failed to create dataset, server returned {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )
+            .into())
+        }
+    }
+}
+
+/// Sends markers to the Markers API until the sink shuts down.
+async fn send_markers(
+    config: HoneycombConfig,
+    client: HttpClient,
+    mut markers: mpsc::UnboundedReceiver<serde_json::Value>,
+) {
+    let uri = config.build_uri("markers");
+    while let Some(marker) = markers.recv().await {
+        let request = Request::post(uri.clone())
+            .header("X-Honeycomb-Team", config.api_key.inner())
+            .header("Content-Type", "application/json")
+            .body(Body::from(marker.to_string()))
+            .expect("This should be a valid request");
+        match client.send(request).await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => {
+                warn!(message = "Failed to send Honeycomb marker.", status = %response.status());
+            }
+            Err(error) => warn!(message = "Failed to send Honeycomb marker.", %error),
+        }
+    }
 }
 
 async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Result<()> {
-    let req = config
-        .build_request(Vec::new())
-        .await?
-        .map(hyper::Body::from);
+    let req = config.build_request(Vec::new())?.map(hyper::Body::from);
 
     let res = client.send(req).await?;
 
@@ -217,6 +417,7 @@ async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Resu
 }
 #[cfg(test)]
 mod test {
+    use chrono::TimeZone;
     use futures::{future::ready, stream};
     use serde::Deserialize;
     use vector_core::event::{Event, LogEvent};
@@ -229,13 +430,71 @@ mod test {
         },
     };
 
-    use super::HoneycombConfig;
+    use super::{HoneycombConfig, HoneycombSink};
+    use crate::sinks::util::http::{HttpEventEncoder, HttpSink};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<HoneycombConfig>();
     }
 
+    #[test]
+    fn encodes_markers() {
+        let config: HoneycombConfig = toml::from_str(
+            r#"api_key = "key"
+            dataset = "dataset"
+            markers.field = "marker""#,
+        )
+        .unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut encoder = HoneycombSink {
+            config,
+            markers: Some(sender),
+        }
+        .build_encoder();
+
+        let mut log = LogEvent::from("deployed v1.2.3");
+        log.insert("marker.type", "deploy");
+        log.insert("marker.url", "https://example.com/deploys/123");
+        log.insert(
+            "timestamp",
+            chrono::Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+        );
+        assert!(encoder.encode_event(Event::Log(log)).is_none());
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            serde_json::json!({
+                "message": "deployed v1.2.3",
+                "type": "deploy",
+                "url": "https://example.com/deploys/123",
+                "start_time": 1_600_000_000,
+            })
+        );
+
+        let log = LogEvent::from("not a marker");
+        assert!(encoder.encode_event(Event::Log(log)).is_some());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn encodes_msgpack_batches() {
+        let config: HoneycombConfig = toml::from_str(
+            r#"api_key = "key"
+            dataset = "dataset"
+            format = "msgpack""#,
+        )
+        .unwrap();
+        let event = serde_json::json!({ "time": "2020-09-13T12:26:40Z", "data": { "a": 1 } });
+        let request = config
+            .build_request(vec![serde_json::value::to_raw_value(&event).unwrap()])
+            .unwrap();
+
+        assert_eq!(request.uri(), "https://api.honeycomb.io/1/batch/dataset");
+        assert_eq!(request.headers()["Content-Type"], "application/msgpack");
+        let decoded: Vec<serde_json::Value> = rmp_serde::from_slice(&request.body()[..]).unwrap();
+        assert_eq!(decoded, vec![event]);
+    }
+
     #[tokio::test]
     async fn component_spec_compliance() {
         let mock_endpoint = spawn_blackhole_http_server(always_200_response).await;
@@ -1,4 +1,4 @@
-use std::io::Write;
+use std::{io::Write, num::NonZeroUsize};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{CharacterDelimitedEncoder, Framer, Serializer};
@@ -11,23 +11,28 @@ use hyper::Body;
 use indexmap::IndexMap;
 use tokio_util::codec::Encoder as _;
 use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
     components::validation::*,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
-    http::{Auth, HttpClient, MaybeAuth},
+    http::{Auth, HttpClient, HttpVersion, MaybeAuth},
     register_validatable_component,
     sinks::util::{
         self,
         http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, Compressor, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        zstd::ZstdCompressionLevel,
+        Batch, BatchConfig, Buffer, Compression, Compressor, PushResult,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
     tls::{TlsConfig, TlsSettings},
+    zstd_dictionary::{ZstdDictionaryConfig, ZstdDictionaryNegotiation, DICTIONARY_HEADER},
 };
 
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
 /// Configuration for the `http` sink.
 #[configurable_component(sink("http", "Deliver observability event data to an HTTP server."))]
 #[derive(Clone, Debug)]
@@ -57,9 +62,29 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub compression: Compression,
 
+    /// A trained zstd dictionary to compress payloads with.
+    ///
+    /// The dictionary is only used once the server lists its ID in the `vector-zstd-dictionary`
+    /// response header, as the `http_server` source does for its `compression_dictionaries`.
+    /// Until then, payloads are compressed without it.
+    ///
+    /// Requires the `zstd` compression.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub compression_dictionary: Option<ZstdDictionaryConfig>,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    /// The maximum number of batches encoded at the same time.
+    ///
+    /// Encoding and compressing batches is CPU-bound, so encoding several batches at the same
+    /// time spreads the work across threads. If unset, batches are encoded one at a time.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = 4))]
+    #[serde(default)]
+    pub encoding_concurrency: Option<NonZeroUsize>,
+
     /// A string to prefix the payload with.
     ///
     /// This option is ignored if the encoding is not character delimited JSON.
@@ -78,6 +103,19 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub payload_suffix: String,
 
+    /// Whether to send an `Idempotency-Key` header with each request.
+    ///
+    /// The key is derived from the [dedupe IDs][dedupe_id] of the events of the request, so that a
+    /// server supporting [idempotency keys][idempotency_key] can discard a request it already
+    /// processed, such as a retry of a request whose response was lost. The header is only sent if
+    /// every event of the request has a dedupe ID.
+    ///
+    /// [dedupe_id]: https://vector.dev/docs/reference/configuration/sources/#dedupe_id
+    /// [idempotency_key]: https://datatracker.ietf.org/doc/draft-ietf-httpapi-idempotency-key-header/
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub idempotency_key: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
@@ -86,6 +124,10 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub request: RequestConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub http_version: HttpVersion,
+
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
 
@@ -162,7 +204,12 @@ impl GenerateConfig for HttpSinkConfig {
 impl HttpSinkConfig {
     fn build_http_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        Ok(HttpClient::new(tls, cx.proxy())?)
+        Ok(HttpClient::new_with_version(
+            tls,
+            cx.proxy(),
+            self.http_version,
+            cx.connection_pool(),
+        )?)
     }
 }
 
@@ -173,8 +220,11 @@ struct HttpSink {
     pub payload_prefix: String,
     pub payload_suffix: String,
     pub compression: Compression,
+    pub compression_dictionary: Option<ZstdDictionaryNegotiation>,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
+    pub encoding_concurrency: Option<NonZeroUsize>,
+    pub idempotency_key: bool,
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     pub tower: TowerRequestConfig,
     pub headers: IndexMap<HeaderName, HeaderValue>,
@@ -190,8 +240,11 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
         method: Default::default(),
         auth: Default::default(),
         compression: Default::default(),
+        compression_dictionary: Default::default(),
         transformer: Default::default(),
         encoder,
+        encoding_concurrency: Default::default(),
+        idempotency_key: Default::default(),
         payload_prefix: Default::default(),
         payload_suffix: Default::default(),
         batch: Default::default(),
@@ -226,13 +279,27 @@ impl SinkConfig for HttpSinkConfig {
         let (payload_prefix, payload_suffix) =
             validate_payload_wrapper(&self.payload_prefix, &self.payload_suffix, &encoder)?;
 
+        let compression_dictionary = match (&self.compression_dictionary, self.compression) {
+            (None, _) => None,
+            (Some(dictionary), Compression::Zstd(level)) => {
+                let level = ZstdCompressionLevel::from(level).level();
+                Some(ZstdDictionaryNegotiation::new(dictionary.load(level)?))
+            }
+            (Some(_), _) => {
+                return Err("`compression_dictionary` requires the `zstd` compression".into())
+            }
+        };
+
         let sink = HttpSink {
             uri: self.uri.with_default_parts(),
             method: self.method,
             auth: self.auth.choose_one(&self.uri.auth)?,
             compression: self.compression,
+            compression_dictionary,
             transformer: self.encoding.transformer(),
             encoder,
+            encoding_concurrency: self.encoding_concurrency,
+            idempotency_key: self.idempotency_key,
             batch: self.batch,
             tower: request.tower,
             headers,
@@ -245,7 +312,10 @@ impl SinkConfig for HttpSinkConfig {
         let batch = sink.batch.into_batch_settings()?;
         let sink = BatchedHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            HttpSinkBatch::new(
+                Buffer::new(batch.size, Compression::None),
+                sink.idempotency_key,
+            ),
             request,
             batch.timeout,
             client,
@@ -284,12 +354,15 @@ impl ValidatableComponent for HttpSinkConfig {
             auth: None,
             headers: None,
             compression: Compression::default(),
+            compression_dictionary: None,
             batch: BatchConfig::default(),
             request: RequestConfig::default(),
+            http_version: HttpVersion::default(),
             tls: None,
             acknowledgements: AcknowledgementsConfig::default(),
             payload_prefix: String::new(),
             payload_suffix: String::new(),
+            idempotency_key: false,
         };
 
         let external_resource = ExternalResource::new(
@@ -307,33 +380,148 @@ register_validatable_component!(HttpSinkConfig);
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
     transformer: Transformer,
+    idempotency_key: bool,
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<HttpSinkEvent> for HttpSinkEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<HttpSinkEvent> {
+        let dedupe_id = self
+            .idempotency_key
+            .then(|| event.metadata().dedupe_id().map(str::to_owned))
+            .flatten();
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(HttpSinkEvent { body, dedupe_id })
+    }
+}
+
+/// An encoded event, along with its dedupe ID when sending the `Idempotency-Key` header.
+pub struct HttpSinkEvent {
+    body: BytesMut,
+    dedupe_id: Option<String>,
+}
+
+/// The encoded events of a request, along with its `Idempotency-Key` header.
+#[derive(Clone)]
+pub struct HttpSinkPayload {
+    body: BytesMut,
+    idempotency_key: Option<String>,
+}
+
+impl ByteSizeOf for HttpSinkPayload {
+    fn allocated_bytes(&self) -> usize {
+        self.body.allocated_bytes() + self.idempotency_key.allocated_bytes()
+    }
+}
+
+/// Buffers encoded events, keeping track of their dedupe IDs to derive the `Idempotency-Key` of
+/// the request from.
+struct HttpSinkBatch {
+    buffer: Buffer,
+    idempotency_key: bool,
+    /// The dedupe IDs of the buffered events, or `None` once an event without one was buffered.
+    dedupe_ids: Option<Vec<String>>,
+}
+
+impl HttpSinkBatch {
+    fn new(buffer: Buffer, idempotency_key: bool) -> Self {
+        Self {
+            buffer,
+            idempotency_key,
+            dedupe_ids: idempotency_key.then(Vec::new),
+        }
+    }
+}
+
+impl Batch for HttpSinkBatch {
+    type Input = HttpSinkEvent;
+    type Output = HttpSinkPayload;
+
+    fn push(&mut self, item: Self::Input) -> PushResult<Self::Input> {
+        let HttpSinkEvent { body, dedupe_id } = item;
+        let num_items = Batch::num_items(&self.buffer);
+        match Batch::push(&mut self.buffer, body) {
+            PushResult::Overflow(body) => PushResult::Overflow(HttpSinkEvent { body, dedupe_id }),
+            PushResult::Ok(full) => {
+                // Events too large for a request are dropped rather than buffered.
+                if Batch::num_items(&self.buffer) > num_items {
+                    match (dedupe_id, self.dedupe_ids.as_mut()) {
+                        (Some(dedupe_id), Some(dedupe_ids)) => dedupe_ids.push(dedupe_id),
+                        _ => self.dedupe_ids = None,
+                    }
+                }
+                PushResult::Ok(full)
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        Batch::is_empty(&self.buffer)
+    }
+
+    fn fresh(&self) -> Self {
+        Self::new(self.buffer.fresh(), self.idempotency_key)
+    }
+
+    fn finish(self) -> Self::Output {
+        let idempotency_key = self
+            .dedupe_ids
+            .filter(|dedupe_ids| !dedupe_ids.is_empty())
+            .map(|dedupe_ids| {
+                let dedupe_ids = dedupe_ids.join("\n");
+                format!(
+                    "{:016x}{:016x}",
+                    seahash::hash(dedupe_ids.as_bytes()),
+                    seahash::hash_seeded(dedupe_ids.as_bytes(), 1, 2, 3, 4)
+                )
+            });
+        HttpSinkPayload {
+            body: self.buffer.finish(),
+            idempotency_key,
+        }
+    }
+
+    fn num_items(&self) -> usize {
+        Batch::num_items(&self.buffer)
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = HttpSinkEvent;
+    type Output = HttpSinkPayload;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
             transformer: self.transformer.clone(),
+            idempotency_key: self.idempotency_key,
+        }
+    }
+
+    fn build_concurrency(&self) -> Option<NonZeroUsize> {
+        self.encoding_concurrency
+    }
+
+    fn observe_response(&self, response: &http::Response<Bytes>) {
+        if let Some(negotiation) = &self.compression_dictionary {
+            let header = response
+                .headers()
+                .get(DICTIONARY_HEADER)
+                .and_then(|value| value.to_str().ok());
+            negotiation.observe(header);
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, payload: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let HttpSinkPayload {
+            mut body,
+            idempotency_key,
+        } = payload;
         let method: Method = self.method.into();
         let uri: Uri = self.uri.uri.clone();
 
@@ -341,7 +529,7 @@ impl util::http::HttpSink for HttpSink {
             use Framer::*;
             use Serializer::*;
             match (self.encoder.serializer(), self.encoder.framer()) {
-                (RawMessage(_) | Text(_), _) => Some("text/plain"),
+                (RawMessage(_) | Text(_) | TextWithContext(_), _) => Some("text/plain"),
                 (Json(_), NewlineDelimited(_)) => {
                     if !body.is_empty() {
                         // Remove trailing newline for backwards-compatibility
@@ -376,9 +564,22 @@ impl util::http::HttpSink for HttpSink {
             builder = builder.header("Content-Type", content_type);
         }
 
-        let compression = self.compression;
+        if let Some(idempotency_key) = idempotency_key {
+            builder = builder.header(IDEMPOTENCY_KEY_HEADER, idempotency_key);
+        }
 
-        if compression.is_compressed() {
+        let compression = self.compression;
+        let dictionary = self
+            .compression_dictionary
+            .as_ref()
+            .and_then(ZstdDictionaryNegotiation::dictionary);
+
+        if let Some(dictionary) = dictionary {
+            builder = builder
+                .header("Content-Encoding", "zstd")
+                .header(DICTIONARY_HEADER, dictionary.id());
+            body = dictionary.compress(&body)?.as_slice().into();
+        } else if compression.is_compressed() {
             builder = builder.header(
                 "Content-Encoding",
                 compression
@@ -490,6 +691,7 @@ mod tests {
         sinks::util::{
             http::{HeaderValidationError, HttpSink},
             test::{build_test_server, build_test_server_generic, build_test_server_status},
+            BatchSize,
         },
         test_util::{
             components,
@@ -509,7 +711,7 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let bytes = encoder.encode_event(event).unwrap().body;
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
@@ -526,7 +728,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let bytes = encoder.encode_event(event).unwrap().body;
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -541,6 +743,69 @@ mod tests {
         assert_eq!(output.message, "hello world".to_string());
     }
 
+    fn event_with_dedupe_id(dedupe_id: &str) -> Event {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.metadata_mut().set_dedupe_id(dedupe_id.to_owned());
+        event
+    }
+
+    fn idempotency_key(idempotency_key: bool, events: Vec<Event>) -> Option<String> {
+        let mut sink =
+            default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
+        sink.idempotency_key = idempotency_key;
+        let mut encoder = sink.build_encoder();
+
+        let mut batch = HttpSinkBatch::new(
+            Buffer::new(BatchSize::const_default(), Compression::None),
+            idempotency_key,
+        );
+        for event in events {
+            let event = encoder.encode_event(event).unwrap();
+            assert!(matches!(batch.push(event), PushResult::Ok(false)));
+        }
+        batch.finish().idempotency_key
+    }
+
+    #[test]
+    fn http_idempotency_key_from_dedupe_ids() {
+        let events = || vec![event_with_dedupe_id("a"), event_with_dedupe_id("b")];
+        let key = idempotency_key(true, events()).unwrap();
+        assert_eq!(key.len(), 32);
+        assert_eq!(idempotency_key(true, events()), Some(key.clone()));
+        assert_ne!(
+            idempotency_key(true, vec![event_with_dedupe_id("a")]),
+            Some(key)
+        );
+
+        assert_eq!(idempotency_key(false, events()), None);
+        assert_eq!(idempotency_key(true, vec![]), None);
+        assert_eq!(
+            idempotency_key(
+                true,
+                vec![
+                    event_with_dedupe_id("a"),
+                    Event::Log(LogEvent::from("hello world"))
+                ]
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn http_sends_idempotency_key() {
+        let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::default()).into());
+        let payload = |idempotency_key: Option<&str>| HttpSinkPayload {
+            body: BytesMut::from("hello world\n"),
+            idempotency_key: idempotency_key.map(str::to_owned),
+        };
+
+        let request = sink.build_request(payload(Some("key"))).await.unwrap();
+        assert_eq!(request.headers()[IDEMPOTENCY_KEY_HEADER], "key");
+
+        let request = sink.build_request(payload(None)).await.unwrap();
+        assert!(!request.headers().contains_key(IDEMPOTENCY_KEY_HEADER));
+    }
+
     #[test]
     fn http_validates_normal_headers() {
         let config = r#"
@@ -572,6 +837,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn http_encoding_concurrency() {
+        let config = r#"
+        uri = "http://$IN_ADDR/"
+        encoding.codec = "json"
+        encoding_concurrency = 4
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+        let sink = HttpSink {
+            encoding_concurrency: config.encoding_concurrency,
+            ..default_sink(config.encoding)
+        };
+
+        assert_eq!(sink.build_concurrency(), NonZeroUsize::new(4));
+    }
+
     #[test]
     fn http_validates_payload_prefix_and_suffix() {
         let config = r#"
@@ -190,6 +190,7 @@ impl HumioLogsConfig {
             endpoint: self.endpoint.clone(),
             host_key: self.host_key.clone(),
             indexed_fields: self.indexed_fields.clone(),
+            dedupe_id_field: None,
             index: self.index.clone(),
             sourcetype: self.event_type.clone(),
             source: self.source.clone(),
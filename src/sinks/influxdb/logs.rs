@@ -164,7 +164,7 @@ impl SinkConfig for InfluxDbLogsConfig {
         let tags: HashSet<String> = self.tags.clone().into_iter().collect();
 
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let healthcheck = self.healthcheck(client.clone())?;
 
         let batch = self.batch.into_batch_settings()?;
@@ -392,7 +392,7 @@ impl InfluxDbLogsConfig {
     }
 }
 
-fn to_field(value: &Value) -> Field {
+pub(in crate::sinks) fn to_field(value: &Value) -> Field {
     match value {
         Value::Integer(num) => Field::Int(*num),
         Value::Float(num) => Field::Float(num.into_inner()),
@@ -126,7 +126,7 @@ impl_generate_config_from_default!(InfluxDbConfig);
 impl SinkConfig for InfluxDbConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let healthcheck = healthcheck(
             self.clone().endpoint,
             self.clone().influxdb1_settings,
@@ -316,7 +316,7 @@ fn encode_events(
     output
 }
 
-fn get_type_and_fields(
+pub(in crate::sinks) fn get_type_and_fields(
     value: &MetricValue,
     quantiles: &[f64],
 ) -> (&'static str, Option<HashMap<String, Field>>) {
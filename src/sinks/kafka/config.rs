@@ -110,6 +110,28 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<String>,
 
+    /// The name of the header the [dedupe ID][dedupe_id] of the event is propagated in.
+    ///
+    /// Kafka doesn't deduplicate records by header, and the transactions of `exactly_once` don't
+    /// cover events read again by the source after a restart, so consumers must discard the records
+    /// whose ID they already processed. If omitted, or if the event has no dedupe ID, no header is
+    /// written.
+    ///
+    /// [dedupe_id]: https://vector.dev/docs/reference/configuration/sources/#dedupe_id
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "vector-dedupe-id"))]
+    pub dedupe_id_header: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub profile: KafkaProfile,
+
+    #[configurable(derived)]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+
+    #[configurable(derived)]
+    pub exactly_once: Option<ExactlyOnceConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -119,6 +141,70 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The broker implementation that the producer is tuned for.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaProfile {
+    /// Apache Kafka, using librdkafka's producer defaults.
+    #[default]
+    Kafka,
+
+    /// [Redpanda][redpanda].
+    ///
+    /// Idempotent production is enabled by default, which requires `acks=all`. Records rejected by
+    /// Redpanda's server-side schema ID validation are reported as such.
+    ///
+    /// [redpanda]: https://redpanda.com/
+    Redpanda,
+}
+
+/// Schema registry wire format configuration.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistryConfig {
+    /// The ID of the registered schema that encoded payloads conform to.
+    ///
+    /// Each payload is prefixed with the schema registry wire format header: a zero magic byte
+    /// followed by the schema ID as a 4-byte big-endian integer. The encoded payload itself must
+    /// match the registered schema.
+    #[configurable(metadata(docs::examples = 1))]
+    pub schema_id: u32,
+}
+
+/// Exactly-once delivery configuration.
+///
+/// Events are produced in batches, each within a Kafka transaction that is committed once the
+/// brokers acknowledged all of its records. Consumers reading with `isolation.level` set to
+/// `read_committed` never see the records of a transaction that was aborted and retried.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExactlyOnceConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// The ID must be unique to this sink across all Vector instances producing to the cluster, and
+    /// stay the same across restarts: a producer starting with the ID of another fences it off, and
+    /// aborts its open transaction.
+    #[configurable(metadata(docs::examples = "vector-${HOSTNAME}-kafka"))]
+    pub transactional_id: String,
+
+    /// The time, in milliseconds, after which the brokers abort an open transaction.
+    ///
+    /// librdkafka lowers the message timeout to this value if it is higher.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::examples = 60000))]
+    #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+    pub transaction_timeout_ms: Duration,
+}
+
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(60000) // default in librdkafka
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -169,6 +255,14 @@ impl KafkaSinkConfig {
                         &self.message_timeout_ms.as_millis().to_string(),
                     );
 
+                if self.profile == KafkaProfile::Redpanda {
+                    self.apply_redpanda_defaults(&mut client_config)?;
+                }
+
+                if let Some(exactly_once) = &self.exactly_once {
+                    self.apply_exactly_once(exactly_once, &mut client_config)?;
+                }
+
                 if let Some(value) = self.batch.timeout_secs {
                     // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                     // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -242,6 +336,78 @@ impl KafkaSinkConfig {
 
         Ok(client_config)
     }
+
+    fn apply_redpanda_defaults(&self, client_config: &mut ClientConfig) -> crate::Result<()> {
+        // Options set in `librdkafka_options` are applied afterwards and take precedence.
+        if self.librdkafka_options.contains_key("enable.idempotence") {
+            return Ok(());
+        }
+
+        if let Some((key, acks)) = self.conflicting_acks() {
+            return Err(format!("The `redpanda` profile enables idempotence, which requires `librdkafka_options.{}` to be `all`, but it is set to `{}`.\
+                            Set `librdkafka_options.enable.idempotence` to `false` to use other acknowledgement settings.", key, acks).into());
+        }
+
+        debug!(
+            librdkafka_option = "enable.idempotence",
+            value = "true",
+            "Applying redpanda profile default."
+        );
+        client_config.set("enable.idempotence", "true");
+        Ok(())
+    }
+
+    fn apply_exactly_once(
+        &self,
+        exactly_once: &ExactlyOnceConfig,
+        client_config: &mut ClientConfig,
+    ) -> crate::Result<()> {
+        if let Some(val) = self.librdkafka_options.get("transactional.id") {
+            return Err(format!("Setting `exactly_once.transactional_id` sets `librdkafka_options.transactional.id={}`.\
+                            The config already sets this as `librdkafka_options.transactional.id={}`.\
+                            Please delete one.", exactly_once.transactional_id, val).into());
+        }
+        if self
+            .librdkafka_options
+            .get("enable.idempotence")
+            .map(String::as_str)
+            == Some("false")
+        {
+            return Err("Transactions require idempotence, but `librdkafka_options.enable.idempotence` is set to `false`.".into());
+        }
+        if let Some((key, acks)) = self.conflicting_acks() {
+            return Err(format!(
+                "Transactions require `librdkafka_options.{}` to be `all`, but it is set to `{}`.",
+                key, acks
+            )
+            .into());
+        }
+
+        debug!(
+            librdkafka_option = "transactional.id",
+            value = %exactly_once.transactional_id,
+            "Applying exactly-once option as librdkafka option."
+        );
+        client_config
+            .set("transactional.id", &exactly_once.transactional_id)
+            .set(
+                "transaction.timeout.ms",
+                &exactly_once.transaction_timeout_ms.as_millis().to_string(),
+            );
+        Ok(())
+    }
+
+    /// Returns the acknowledgement option set in `librdkafka_options` that does not wait for all
+    /// replicas, which librdkafka refuses for an idempotent producer. Pointing at it gives a
+    /// clearer error than a generic producer creation failure.
+    fn conflicting_acks(&self) -> Option<(&'static str, &str)> {
+        ["acks", "request.required.acks"]
+            .into_iter()
+            .find_map(|key| {
+                let acks = self.librdkafka_options.get(key)?;
+                (acks != "all" && acks != "-1").then_some((key, acks.as_str()))
+            })
+    }
 }
 
 impl GenerateConfig for KafkaSinkConfig {
@@ -258,6 +424,10 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            dedupe_id_header: None,
+            profile: Default::default(),
+            schema_registry: None,
+            exactly_once: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -293,4 +463,97 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn redpanda_config(librdkafka_options: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            profile = "redpanda"
+            {}
+        "#,
+            librdkafka_options
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn redpanda_profile_enables_idempotence() {
+        let client_config = redpanda_config("").to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+    }
+
+    #[test]
+    fn redpanda_profile_respects_explicit_idempotence() {
+        let client_config = redpanda_config(
+            r#"librdkafka_options = { "enable.idempotence" = "false", "acks" = "1" }"#,
+        )
+        .to_rdkafka(KafkaRole::Producer)
+        .unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), Some("false"));
+    }
+
+    #[test]
+    fn redpanda_profile_rejects_conflicting_acks() {
+        let error = redpanda_config(r#"librdkafka_options = { "acks" = "1" }"#)
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap_err();
+        assert!(error.to_string().contains("librdkafka_options.acks"));
+    }
+
+    fn exactly_once_config(librdkafka_options: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            exactly_once.transactional_id = "vector-1"
+            {}
+        "#,
+            librdkafka_options
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn exactly_once_sets_transactional_id() {
+        let client_config = exactly_once_config("")
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap();
+        assert_eq!(client_config.get("transactional.id"), Some("vector-1"));
+        assert_eq!(client_config.get("transaction.timeout.ms"), Some("60000"));
+
+        let client_config = exactly_once_config("")
+            .to_rdkafka(KafkaRole::Consumer)
+            .unwrap();
+        assert_eq!(client_config.get("transactional.id"), None);
+    }
+
+    #[test]
+    fn exactly_once_rejects_conflicting_options() {
+        for options in [
+            r#"librdkafka_options = { "transactional.id" = "other" }"#,
+            r#"librdkafka_options = { "enable.idempotence" = "false" }"#,
+            r#"librdkafka_options = { "acks" = "1" }"#,
+        ] {
+            assert!(exactly_once_config(options)
+                .to_rdkafka(KafkaRole::Producer)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn kafka_profile_keeps_librdkafka_defaults() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+        "#,
+        )
+        .unwrap();
+        let client_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(client_config.get("enable.idempotence"), None);
+    }
 }
@@ -1,6 +1,6 @@
 use std::num::NonZeroUsize;
 
-use bytes::{Bytes, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use rdkafka::message::{Header, OwnedHeaders};
 use tokio_util::codec::Encoder as _;
 
@@ -18,9 +18,11 @@ use crate::{
 pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
     pub headers_key: Option<String>,
+    pub dedupe_id_header: Option<String>,
     pub topic_template: Template,
     pub transformer: Transformer,
     pub encoder: Encoder<()>,
+    pub schema_id: Option<u32>,
 }
 
 impl KafkaRequestBuilder {
@@ -43,11 +45,20 @@ impl KafkaRequestBuilder {
             finalizers: event.take_finalizers(),
             key: get_key(&event, &self.key_field),
             timestamp_millis: get_timestamp_millis(&event),
-            headers: get_headers(&event, &self.headers_key),
+            headers: with_dedupe_id_header(
+                get_headers(&event, &self.headers_key),
+                &event,
+                &self.dedupe_id_header,
+            ),
             topic,
         };
         self.transformer.transform(&mut event);
         let mut body = BytesMut::new();
+        if let Some(schema_id) = self.schema_id {
+            // Schema registry wire format: magic byte, then the big-endian schema ID.
+            body.put_u8(0);
+            body.put_u32(schema_id);
+        }
         self.encoder.encode(event, &mut body).ok()?;
         let body = body.freeze();
 
@@ -117,11 +128,27 @@ fn get_headers(event: &Event, headers_key: &Option<String>) -> Option<OwnedHeade
     })
 }
 
+fn with_dedupe_id_header(
+    headers: Option<OwnedHeaders>,
+    event: &Event,
+    dedupe_id_header: &Option<String>,
+) -> Option<OwnedHeaders> {
+    let (Some(key), Some(id)) = (dedupe_id_header, event.metadata().dedupe_id()) else {
+        return headers;
+    };
+    let headers = headers.unwrap_or_else(|| OwnedHeaders::new_with_capacity(1));
+    Some(headers.insert(Header {
+        key,
+        value: Some(id.as_bytes()),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use bytes::Bytes;
+    use codecs::JsonSerializerConfig;
     use rdkafka::message::Headers;
 
     use super::*;
@@ -143,4 +170,44 @@ mod tests {
         assert_eq!(headers.get(1).key, "b-key");
         assert_eq!(headers.get(1).value.unwrap(), "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_adds_dedupe_id_header() {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        let header = Some("vector-dedupe-id".to_string());
+        assert!(with_dedupe_id_header(None, &event, &header).is_none());
+
+        event
+            .metadata_mut()
+            .set_dedupe_id("0123456789abcdef".to_string());
+        assert!(with_dedupe_id_header(None, &event, &None).is_none());
+
+        let headers = OwnedHeaders::new().insert(Header {
+            key: "a-key",
+            value: Some("a-value".as_bytes()),
+        });
+        let headers = with_dedupe_id_header(Some(headers), &event, &header).unwrap();
+        assert_eq!(headers.count(), 2);
+        assert_eq!(headers.get(1).key, "vector-dedupe-id");
+        assert_eq!(headers.get(1).value.unwrap(), "0123456789abcdef".as_bytes());
+    }
+
+    #[test]
+    fn kafka_prefixes_schema_registry_header() {
+        let mut request_builder = KafkaRequestBuilder {
+            key_field: None,
+            headers_key: None,
+            dedupe_id_header: None,
+            topic_template: Template::try_from("logs").unwrap(),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            schema_id: Some(258),
+        };
+
+        let request = request_builder
+            .build_request(Event::Log(LogEvent::from("hello")))
+            .unwrap();
+        assert_eq!(&request.body[..5], &[0, 0, 0, 1, 2]);
+        assert_eq!(request.body[5], b'{');
+    }
 }
@@ -5,13 +5,16 @@ use rdkafka::{
     error::KafkaError,
     message::OwnedHeaders,
     producer::{FutureProducer, FutureRecord},
+    types::RDKafkaErrorCode,
     util::Timeout,
 };
 use vector_core::internal_event::{
     ByteSize, BytesSent, CountByteSize, InternalEventHandle as _, Protocol, Registered,
 };
 
-use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
+use crate::{
+    internal_events::KafkaRecordRejectedError, kafka::KafkaStatisticsContext, sinks::prelude::*,
+};
 
 pub struct KafkaRequest {
     pub body: Bytes,
@@ -57,13 +60,51 @@ impl MetaDescriptive for KafkaRequest {
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
     bytes_sent: Registered<BytesSent>,
+    schema_id: Option<u32>,
 }
 
 impl KafkaService {
-    pub(crate) fn new(kafka_producer: FutureProducer<KafkaStatisticsContext>) -> KafkaService {
+    pub(crate) fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        schema_id: Option<u32>,
+    ) -> KafkaService {
         KafkaService {
             kafka_producer,
             bytes_sent: register!(BytesSent::from(Protocol("kafka".into()))),
+            schema_id,
+        }
+    }
+
+    /// Produces the record of a request, resolving once the brokers acknowledged it.
+    pub(super) async fn send(&self, request: &KafkaRequest) -> Result<(), KafkaError> {
+        let mut record = FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
+        if let Some(key) = &request.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = request.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = &request.metadata.headers {
+            record = record.headers(headers.clone());
+        }
+
+        // rdkafka will internally retry forever if the queue is full
+        match self.kafka_producer.send(record, Timeout::Never).await {
+            Ok((_partition, _offset)) => {
+                self.bytes_sent.emit(ByteSize(
+                    request.body.len() + request.metadata.key.as_ref().map_or(0, |key| key.len()),
+                ));
+                Ok(())
+            }
+            Err((kafka_err, _original_record)) => {
+                if let KafkaError::MessageProduction(RDKafkaErrorCode::InvalidRecord) = kafka_err {
+                    emit!(KafkaRecordRejectedError {
+                        topic: &request.metadata.topic,
+                        schema_id: self.schema_id,
+                    });
+                }
+                Err(kafka_err)
+            }
         }
     }
 }
@@ -85,28 +126,8 @@ impl Service<KafkaRequest> for KafkaService {
                 .get_metadata()
                 .events_estimated_json_encoded_byte_size();
 
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
-
-            // rdkafka will internally retry forever if the queue is full
-            match this.kafka_producer.send(record, Timeout::Never).await {
-                Ok((_partition, _offset)) => {
-                    this.bytes_sent.emit(ByteSize(
-                        request.body.len() + request.metadata.key.map(|x| x.len()).unwrap_or(0),
-                    ));
-                    Ok(KafkaResponse { event_byte_size })
-                }
-                Err((kafka_err, _original_record)) => Err(kafka_err),
-            }
+            this.send(&request).await?;
+            Ok(KafkaResponse { event_byte_size })
         })
     }
 }
@@ -13,7 +13,10 @@ use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
     kafka::KafkaStatisticsContext,
     sinks::kafka::{
-        config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder, service::KafkaService,
+        config::QUEUED_MIN_MESSAGES,
+        request_builder::KafkaRequestBuilder,
+        service::{KafkaRequest, KafkaService},
+        transaction::{KafkaTransactionRequest, KafkaTransactionService},
     },
     sinks::prelude::*,
 };
@@ -34,6 +37,10 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<String>,
     headers_key: Option<String>,
+    dedupe_id_header: Option<String>,
+    schema_id: Option<u32>,
+    /// Set when producing with `exactly_once`, along with the batches of the transactions.
+    transactions: Option<(KafkaTransactionService, BatcherSettings)>,
 }
 
 pub(crate) fn create_producer(
@@ -52,36 +59,75 @@ impl KafkaSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let schema_id = config.schema_registry.map(|registry| registry.schema_id);
+        let service = KafkaService::new(producer.clone(), schema_id);
+
+        let transactions = match &config.exactly_once {
+            Some(exactly_once) => Some((
+                KafkaTransactionService::new(
+                    service.clone(),
+                    producer,
+                    exactly_once.transaction_timeout_ms,
+                ),
+                config.batch.into_batcher_settings()?,
+            )),
+            None => None,
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key,
+            dedupe_id_header: config.dedupe_id_header,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service,
             topic: config.topic,
             key_field: config.key_field,
+            schema_id,
+            transactions,
         })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
-        // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
-        let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
+            dedupe_id_header: self.dedupe_id_header,
             topic_template: self.topic,
             transformer: self.transformer,
             encoder: self.encoder,
+            schema_id: self.schema_id,
         };
 
-        input
-            .filter_map(|event|
-                // request_builder is fallible but the places it can fail are emitting
-                // `Error` and `DroppedEvent` internal events appropriately so no need to here.
-                future::ready(request_builder.build_request(event)))
-            .into_driver(service)
-            .run()
-            .await
+        let requests = input.filter_map(|event|
+            // request_builder is fallible but the places it can fail are emitting
+            // `Error` and `DroppedEvent` internal events appropriately so no need to here.
+            future::ready(request_builder.build_request(event)));
+
+        match self.transactions {
+            None => {
+                // rdkafka will internally retry forever, so we need some limit to prevent this from overflowing
+                let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
+                requests.into_driver(service).run().await
+            }
+            Some((service, batch_settings)) => {
+                if let Err(error) = service.init_transactions().await {
+                    error!(message = "Failed to initialize Kafka transactions.", %error);
+                    return Err(());
+                }
+
+                // A producer runs one transaction at a time.
+                let service = ConcurrencyLimit::new(service, 1);
+                requests
+                    .batched(
+                        batch_settings
+                            .into_item_size_config(|request: &KafkaRequest| request.body.len()),
+                    )
+                    .map(KafkaTransactionRequest::new)
+                    .into_driver(service)
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
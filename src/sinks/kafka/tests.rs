@@ -25,7 +25,10 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{
+                    ExactlyOnceConfig, KafkaProfile, KafkaRole, KafkaSinkConfig,
+                    SchemaRegistryConfig,
+                },
                 sink::KafkaSink,
                 *,
             },
@@ -46,6 +49,11 @@ mod integration_test {
         format!("{}:{}", kafka_host(), port)
     }
 
+    fn redpanda_address() -> String {
+        let host = std::env::var("REDPANDA_HOST").unwrap_or_else(|_| "localhost".into());
+        format!("{}:9094", host)
+    }
+
     #[tokio::test]
     async fn healthcheck() {
         crate::test_util::trace_init();
@@ -64,6 +72,10 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            dedupe_id_header: None,
+            profile: Default::default(),
+            schema_registry: None,
+            exactly_once: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -119,6 +131,10 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            dedupe_id_header: None,
+            profile: Default::default(),
+            schema_registry: None,
+            exactly_once: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -250,6 +266,10 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            dedupe_id_header: None,
+            profile: Default::default(),
+            schema_registry: None,
+            exactly_once: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -338,4 +358,140 @@ mod integration_test {
         assert_eq!(out.len(), input.len());
         assert_eq!(out, input);
     }
+
+    #[tokio::test]
+    async fn kafka_exactly_once() {
+        crate::test_util::trace_init();
+
+        let server = kafka_address(9091);
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            dedupe_id_header: None,
+            profile: Default::default(),
+            schema_registry: None,
+            exactly_once: Some(ExactlyOnceConfig {
+                transactional_id: format!("vector-{}", random_string(10)),
+                transaction_timeout_ms: Duration::from_millis(60000),
+            }),
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < input.len() {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
+    #[tokio::test]
+    async fn redpanda_happy_path_with_schema_registry_header() {
+        crate::test_util::trace_init();
+
+        let server = redpanda_address();
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: server.clone(),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            dedupe_id_header: None,
+            profile: KafkaProfile::Redpanda,
+            schema_registry: Some(SchemaRegistryConfig { schema_id: 7 }),
+            exactly_once: None,
+            acknowledgements: Default::default(),
+        };
+        self::sink::healthcheck(config.clone()).await.unwrap();
+
+        let num_events = 100;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", server.as_str());
+        client_config.set("group.id", &random_string(10));
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < input.len() {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let payload = msg.payload().unwrap();
+                    assert_eq!(&payload[..5], &[0, 0, 0, 0, 7]);
+                    out.push(String::from_utf8(payload[5..].to_vec()).unwrap());
+                }
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
 }
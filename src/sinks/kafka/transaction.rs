@@ -0,0 +1,294 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::join_all;
+use rdkafka::{
+    error::KafkaError,
+    producer::{FutureProducer, Producer},
+    types::RDKafkaErrorCode,
+};
+use tokio::time::sleep;
+
+use crate::{
+    kafka::KafkaStatisticsContext,
+    sinks::{
+        kafka::service::{KafkaRequest, KafkaService},
+        prelude::*,
+        util::retries::ExponentialBackoff,
+    },
+};
+
+/// The records of a batch, produced within a single transaction.
+pub struct KafkaTransactionRequest {
+    requests: Vec<KafkaRequest>,
+    finalizers: EventFinalizers,
+    request_metadata: RequestMetadata,
+}
+
+impl KafkaTransactionRequest {
+    pub fn new(mut requests: Vec<KafkaRequest>) -> Self {
+        let finalizers = requests.take_finalizers();
+        let request_metadata =
+            RequestMetadata::from_batch(requests.iter().map(MetaDescriptive::get_metadata));
+        Self {
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.request_metadata
+    }
+}
+
+pub struct KafkaTransactionResponse {
+    event_count: usize,
+    event_byte_size: JsonSize,
+}
+
+impl DriverResponse for KafkaTransactionResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(self.event_count, self.event_byte_size)
+    }
+}
+
+/// Produces each request in a transaction, committed once all of its records were acknowledged.
+///
+/// A producer runs one transaction at a time, so the service must not be called concurrently.
+/// Once a transaction fails with a fatal error, the producer can't be used anymore, so the service
+/// stops being ready, which stops the sink.
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    service: KafkaService,
+    producer: FutureProducer<KafkaStatisticsContext>,
+    timeout: Duration,
+    fatal_error: Arc<Mutex<Option<KafkaError>>>,
+}
+
+impl KafkaTransactionService {
+    pub(crate) fn new(
+        service: KafkaService,
+        producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            service,
+            producer,
+            timeout,
+            fatal_error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Registers the transactional ID with the brokers, fencing off any previous producer that
+    /// used it and aborting its open transaction.
+    pub(crate) async fn init_transactions(&self) -> Result<(), KafkaError> {
+        self.blocking(|producer, timeout| producer.init_transactions(timeout))
+            .await
+    }
+
+    /// The transactional calls of librdkafka block until the brokers answered.
+    async fn blocking<F>(&self, f: F) -> Result<(), KafkaError>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>, Duration) -> Result<(), KafkaError>
+            + Send
+            + 'static,
+    {
+        let producer = self.producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || f(&producer, timeout))
+            .await
+            .expect("Kafka transaction task panicked.")
+    }
+
+    async fn commit(&self) -> Result<(), KafkaError> {
+        let mut backoff = fresh_backoff();
+        loop {
+            match self
+                .blocking(|producer, timeout| producer.commit_transaction(timeout))
+                .await
+            {
+                // The outcome of the commit is unknown, so it must be retried rather than aborted,
+                // which would send the records again if the commit succeeded.
+                Err(KafkaError::Transaction(error)) if error.is_retriable() => {
+                    warn!(message = "Retrying to commit Kafka transaction.", %error);
+                    sleep(backoff.next().unwrap()).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn run_transaction(&self, requests: &[KafkaRequest]) -> Result<(), KafkaError> {
+        self.blocking(|producer, _| producer.begin_transaction())
+            .await?;
+
+        let sent = join_all(requests.iter().map(|request| self.service.send(request))).await;
+        let result = match sent.into_iter().find_map(Result::err) {
+            Some(error) => Err(error),
+            None => self.commit().await,
+        };
+
+        if let Err(error) = result {
+            if !is_fatal(&error) {
+                self.blocking(|producer, timeout| producer.abort_transaction(timeout))
+                    .await?;
+            }
+            return Err(error);
+        }
+        Ok(())
+    }
+}
+
+const fn fresh_backoff() -> ExponentialBackoff {
+    ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(60))
+}
+
+/// A fatal error leaves the producer unusable, most often because another producer with the same
+/// transactional ID fenced it off.
+fn is_fatal(error: &KafkaError) -> bool {
+    match error {
+        KafkaError::Transaction(error) => error.is_fatal(),
+        KafkaError::MessageProduction(code) => {
+            matches!(
+                code,
+                RDKafkaErrorCode::Fenced | RDKafkaErrorCode::ProducerFenced
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Aborted transactions are retried, unless the brokers rejected a record, which they would do
+/// again.
+fn is_retriable(error: &KafkaError) -> bool {
+    match error {
+        KafkaError::Transaction(error) => !error.is_fatal(),
+        KafkaError::MessageProduction(code) => !matches!(
+            code,
+            RDKafkaErrorCode::InvalidRecord | RDKafkaErrorCode::MessageSizeTooLarge
+        ),
+        _ => false,
+    }
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionService {
+    type Response = KafkaTransactionResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.fatal_error.lock().expect("poisoned lock").clone() {
+            Some(error) => Poll::Ready(Err(error)),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let response = KafkaTransactionResponse {
+                event_count: request.request_metadata.event_count(),
+                event_byte_size: request
+                    .request_metadata
+                    .events_estimated_json_encoded_byte_size(),
+            };
+
+            let mut backoff = fresh_backoff();
+            loop {
+                match this.run_transaction(&request.requests).await {
+                    Ok(()) => return Ok(response),
+                    Err(error) if is_fatal(&error) => {
+                        error!(
+                            message = "Kafka producer failed with a fatal error, stopping the sink.",
+                            %error,
+                        );
+                        *this.fatal_error.lock().expect("poisoned lock") = Some(error.clone());
+                        return Err(error);
+                    }
+                    Err(error) if is_retriable(&error) => {
+                        warn!(message = "Kafka transaction aborted, retrying.", %error);
+                        sleep(backoff.next().unwrap()).await;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::task::noop_waker_ref;
+    use rdkafka::ClientConfig;
+
+    use super::*;
+
+    #[test]
+    fn stops_being_ready_after_fatal_error() {
+        let producer: FutureProducer<KafkaStatisticsContext> = ClientConfig::new()
+            .set("bootstrap.servers", "localhost:9092")
+            .create_with_context(KafkaStatisticsContext::default())
+            .unwrap();
+        let mut service = KafkaTransactionService::new(
+            KafkaService::new(producer.clone(), None),
+            producer,
+            Duration::from_secs(1),
+        );
+        let mut cx = Context::from_waker(noop_waker_ref());
+        assert!(matches!(service.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+
+        *service.fatal_error.lock().unwrap() = Some(KafkaError::MessageProduction(
+            RDKafkaErrorCode::ProducerFenced,
+        ));
+        assert!(matches!(
+            service.clone().poll_ready(&mut cx),
+            Poll::Ready(Err(_))
+        ));
+    }
+
+    #[test]
+    fn rejected_records_are_not_retried() {
+        assert!(is_retriable(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::RequestTimedOut
+        )));
+        assert!(!is_retriable(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::InvalidRecord
+        )));
+        assert!(!is_retriable(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::MessageSizeTooLarge
+        )));
+        assert!(!is_retriable(&KafkaError::Canceled));
+    }
+
+    #[test]
+    fn fenced_producers_are_fatal() {
+        assert!(is_fatal(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::ProducerFenced
+        )));
+        assert!(is_fatal(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::Fenced
+        )));
+        assert!(!is_fatal(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::RequestTimedOut
+        )));
+    }
+}
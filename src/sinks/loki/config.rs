@@ -1,5 +1,14 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+};
 
+use http::uri::Scheme;
+use lookup::{
+    lookup_v2::{ConfigValuePath, OptionalValuePath},
+    owned_value_path,
+};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
 use vrl::value::Kind;
 
 use super::{healthcheck::healthcheck, sink::LokiSink};
@@ -21,6 +30,10 @@ pub enum ExtendedCompression {
 }
 
 /// Compression configuration.
+///
+/// `snappy` sends push requests as Protocol Buffers compressed with Snappy, which is what Loki
+/// expects for native pushes. The other algorithms send push requests as JSON, compressed with the
+/// algorithm and with the matching `Content-Encoding` header.
 #[configurable_component]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[serde(untagged)]
@@ -41,16 +54,71 @@ impl CompressionConfigAdapter {
     }
 }
 
+/// The protocol push requests are sent to Loki with.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LokiProtocol {
+    /// Send push requests to the HTTP push API, at `path`.
+    #[derivative(Default)]
+    Http,
+
+    /// Send push requests to the `Push` method of the `logproto.Pusher` gRPC service.
+    ///
+    /// Push requests are sent as Protocol Buffers, whatever the `compression`, and the tenant ID as
+    /// the `X-Scope-OrgID` metadata. TLS is used unless the scheme of the endpoint is `http`. The
+    /// healthcheck is skipped, as it uses the HTTP API.
+    Grpc,
+}
+
 impl Default for CompressionConfigAdapter {
     fn default() -> Self {
         CompressionConfigAdapter::Extended(ExtendedCompression::Snappy)
     }
 }
 
-fn default_loki_path() -> String {
+/// The most label names the per-stream delivery metrics can be keyed by.
+const MAX_STREAM_METRICS_LABELS: usize = 5;
+
+pub(super) fn default_loki_path() -> String {
     "/loki/api/v1/push".to_string()
 }
 
+/// The path of the OTLP endpoint of Loki, used instead of the default `path` with the `otlp`
+/// endpoint format.
+pub const OTLP_PATH: &str = "/otlp/v1/logs";
+
+/// The format of the push requests sent to the HTTP API of Loki.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LokiEndpointFormat {
+    /// Send push requests to the Loki push API, as JSON or, with `snappy` compression, as
+    /// Protocol Buffers.
+    #[derivative(Default)]
+    Push,
+
+    /// Send the entries as OTLP log records to the OTLP endpoint of Loki.
+    ///
+    /// The entries of each stream are sent as one resource, with the labels of the stream as its
+    /// attributes, and their structured metadata as the attributes of the log records. Loki only
+    /// indexes some resource attributes as labels, such as `service.name`, unless its `otlp_config`
+    /// lists others, and label names can contain dots to match them. If `path` is not set,
+    /// requests are sent to `/otlp/v1/logs`. Only the `http` protocol is supported, and `snappy`
+    /// compression is not.
+    Otlp,
+}
+
+fn default_reorder_window_secs() -> NonZeroU64 {
+    NonZeroU64::new(5).expect("static")
+}
+
+fn default_reorder_max_bytes() -> NonZeroUsize {
+    NonZeroUsize::new(10 * 1024 * 1024).expect("static")
+}
+
 /// Configuration for the `loki` sink.
 #[configurable_component(sink("loki", "Deliver log event data to the Loki aggregation system."))]
 #[derive(Clone, Debug)]
@@ -66,9 +134,27 @@ pub struct LokiConfig {
     #[serde(default = "default_loki_path")]
     pub path: String,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: LokiProtocol,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub endpoint_format: LokiEndpointFormat,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
+    /// The maximum number of batches encoded at the same time.
+    ///
+    /// Encoding and compressing batches is CPU-bound, so encoding several batches at the same
+    /// time spreads the work across threads. If unset, batches are encoded one at a time, unless
+    /// `out_of_order_action` is `accept`, in which case up to 50 are encoded at the same time.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = 4))]
+    #[serde(default)]
+    pub encoding_concurrency: Option<NonZeroUsize>,
+
     /// The [tenant ID][tenant_id] to specify in requests to Loki.
     ///
     /// When running Loki locally, a tenant ID is not required.
@@ -96,7 +182,25 @@ pub struct LokiConfig {
     #[configurable(metadata(docs::additional_props_description = "A Loki label."))]
     pub labels: HashMap<Template, Template>,
 
-    /// Whether or not to delete fields from the event when they are used as labels.
+    /// A set of [structured metadata][structured_metadata] that is attached to each entry.
+    ///
+    /// Both keys and values are templateable. Unlike labels, structured metadata is not indexed,
+    /// so fields with many distinct values, such as request IDs, can be attached to entries without
+    /// creating a stream for each value. Entries are sent without the structured metadata whose
+    /// value is empty.
+    ///
+    /// Requires Loki 2.9.0 or newer, with structured metadata allowed.
+    ///
+    /// [structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+    #[configurable(metadata(docs::examples = "loki_structured_metadata_examples()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "A structured metadata value of Loki entries."
+    ))]
+    #[serde(default)]
+    pub structured_metadata: HashMap<Template, Template>,
+
+    /// Whether or not to delete fields from the event when they are used as labels or structured
+    /// metadata.
     #[serde(default = "crate::serde::default_false")]
     pub remove_label_fields: bool,
 
@@ -114,6 +218,109 @@ pub struct LokiConfig {
     #[serde(default)]
     pub out_of_order_action: OutOfOrderAction,
 
+    /// How long the events of a stream are held to be reordered, in seconds.
+    ///
+    /// Only used when `out_of_order_action` is `reorder`. A longer window tolerates events arriving
+    /// further out of order, at the cost of delaying every event by the window and of buffering its
+    /// events in memory.
+    #[serde(default = "default_reorder_window_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub reorder_window_secs: NonZeroU64,
+
+    /// The maximum size of the events held to be reordered, in bytes.
+    ///
+    /// Only used when `out_of_order_action` is `reorder`. Once the events held take more than this,
+    /// the streams held the longest are sent before their window ends.
+    #[serde(default = "default_reorder_max_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub reorder_max_bytes: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub label_normalization: LabelNormalizationConfig,
+
+    /// The label policies of tenants, by tenant ID.
+    ///
+    /// Events routed to a tenant listed here can only carry the labels its policy allows, so that a
+    /// template meant for one tenant does not add labels to the streams of another.
+    #[configurable(metadata(
+        docs::additional_props_description = "The label policy of a tenant."
+    ))]
+    #[serde(default)]
+    pub tenant_labels: HashMap<String, TenantLabelsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub trace_correlation: TraceCorrelationConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub attachment: AttachmentConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub max_label_cardinality: LabelCardinalityConfig,
+
+    /// The maximum number of labels of a stream.
+    ///
+    /// The labels past the limit, in the order of their names, are dropped, since Loki rejects the
+    /// whole push request if a stream has more labels than its `max_label_names_per_series` limit.
+    /// The `__shard__` label of sharded streams is added on top of them. If unset, the number of
+    /// labels is not limited.
+    #[configurable(metadata(docs::examples = 15))]
+    pub max_labels_per_stream: Option<NonZeroUsize>,
+
+    /// The maximum length, in bytes, of a label name.
+    ///
+    /// The labels with a longer name are dropped, since Loki rejects the whole push request if a
+    /// label name is longer than its `max_label_name_length` limit. If unset, names are not limited.
+    #[configurable(metadata(docs::examples = 1024))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_label_name_length: Option<NonZeroUsize>,
+
+    /// The maximum length, in bytes, of a label value.
+    ///
+    /// Longer values are truncated on a character boundary, since Loki rejects the whole push
+    /// request if a label value is longer than its `max_label_value_length` limit. If unset, values
+    /// are not limited.
+    ///
+    /// Values are truncated the same way as with `label_normalization.max_value_length`, but are
+    /// also counted as limited labels. Only one of the two can be set.
+    #[configurable(metadata(docs::examples = 2048))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_label_value_length: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub stream_sharding: StreamShardingConfig,
+
+    /// The names of the labels the per-stream delivery metrics are keyed by.
+    ///
+    /// When set, the entries sent, dropped as out of order, and rejected by Loki are counted for
+    /// each combination of the values of these labels, tagged with a `stream` selector such as
+    /// `{app="api"}`. Each combination creates metric series, so only labels with a bounded set
+    /// of values should be used, and at most five of them. If empty, the per-stream metrics are not
+    /// emitted.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "app"))]
+    #[configurable(metadata(docs::examples = "namespace"))]
+    pub stream_metrics_labels: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tenant_rate_limit: TenantRateLimitConfig,
+
+    /// The ID of a `dead_letter` source the events Loki rejects are sent to.
+    ///
+    /// Events in a push request Loki rejects without it being retried, such as for an entry too
+    /// far behind or a line too long, are sent to the source as they were received by the sink,
+    /// so that a sink taking the source as input can store them for later replay. They are still
+    /// reported as rejected to the sources acknowledging them. Loki may have accepted some entries
+    /// of a rejected request, which are sent to the source as well. If unset, rejected events are
+    /// dropped.
+    #[configurable(metadata(docs::examples = "loki_rejected"))]
+    pub dead_letter: Option<String>,
+
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
@@ -137,6 +344,13 @@ pub struct LokiConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+fn loki_structured_metadata_examples() -> HashMap<String, String> {
+    let mut examples = HashMap::new();
+    examples.insert("request_id".to_string(), "{{ request_id }}".to_string());
+    examples.insert("user_id".to_string(), "{{ user.id }}".to_string());
+    examples
+}
+
 fn loki_labels_examples() -> HashMap<String, String> {
     let mut examples = HashMap::new();
     examples.insert("source".to_string(), "vector".to_string());
@@ -169,8 +383,8 @@ impl SinkBatchSettings for LokiDefaultBatchSettings {
 /// was not supported and would result in an error during the push request.
 ///
 /// If you're using Loki 2.4.0 or newer, `Accept` is the preferred action, which lets Loki handle
-/// any necessary sorting/reordering. If you're using an earlier version, then you must use `Drop`
-/// or `RewriteTimestamp` depending on which option makes the most sense for your use case.
+/// any necessary sorting/reordering. If you're using an earlier version, then you must use `Drop`,
+/// `RewriteTimestamp`, or `Reorder` depending on which option makes the most sense for your use case.
 #[configurable_component]
 #[derive(Copy, Clone, Debug, Derivative)]
 #[derivative(Default)]
@@ -189,6 +403,354 @@ pub enum OutOfOrderAction {
     ///
     /// Requires Loki 2.4.0 or newer.
     Accept,
+
+    /// Reorder the events of each stream before sending them.
+    ///
+    /// The events of a stream, identified by its tenant and labels, are held for
+    /// `reorder_window_secs` and sent sorted by timestamp. An event arriving after later events of
+    /// its stream were sent has its timestamp rewritten to the timestamp of the latest of them.
+    Reorder,
+}
+
+/// Label normalization configuration.
+///
+/// Loki rejects the entire push request if any stream in it has an invalid label. These options
+/// rewrite labels before they are sent so that a single malformed label does not cause the whole
+/// batch to be rejected.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LabelNormalizationConfig {
+    /// Whether or not to replace characters that are invalid in a label name.
+    ///
+    /// Label names must match `[a-zA-Z_][a-zA-Z0-9_]*`. When enabled, every invalid character is
+    /// replaced with `_`, and names starting with a digit are prefixed with `_`. If several labels
+    /// are replaced with the same name, only the first of them by name is kept.
+    #[serde(default = "crate::serde::default_false")]
+    pub replace_invalid_characters: bool,
+
+    /// The maximum length, in bytes, of a label value.
+    ///
+    /// Values longer than this are truncated on a character boundary. If unset, values are not
+    /// truncated. Can't be set along with `max_label_value_length`, which truncates values the
+    /// same way.
+    #[configurable(metadata(docs::examples = 1024))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_value_length: Option<NonZeroUsize>,
+
+    /// Whether or not to drop labels whose value is empty.
+    #[serde(default = "crate::serde::default_false")]
+    pub drop_empty_values: bool,
+}
+
+/// Label policy of a tenant.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantLabelsConfig {
+    /// The labels that events routed to the tenant can carry.
+    ///
+    /// Names ending with `*` allow every label starting with the rest of the name. Labels are
+    /// matched once normalized. If empty, every label is allowed.
+    #[configurable(metadata(docs::examples = "app", docs::examples = "pod_labels_*"))]
+    #[serde(default)]
+    pub allowed_labels: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub disallowed_labels: DisallowedLabelAction,
+
+    /// The prefix prepended to the names of the labels that are not allowed, when they are
+    /// renamed.
+    #[configurable(metadata(docs::examples = "team_a_"))]
+    pub label_prefix: Option<String>,
+}
+
+impl TenantLabelsConfig {
+    pub(super) fn allows(&self, label: &str) -> bool {
+        self.allowed_labels.is_empty()
+            || self
+                .allowed_labels
+                .iter()
+                .any(|allowed| match allowed.strip_suffix('*') {
+                    Some(prefix) => label.starts_with(prefix),
+                    None => label == allowed,
+                })
+    }
+}
+
+/// What happens to the labels of events routed to a tenant that its policy does not allow.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DisallowedLabelAction {
+    /// Drop the label.
+    #[derivative(Default)]
+    Drop,
+
+    /// Send the label with `label_prefix` prepended to its name.
+    ///
+    /// This keeps the label apart from those allowed, which a query can then exclude.
+    Rename,
+}
+
+/// Trace correlation configuration.
+///
+/// The trace and span IDs of events are attached to their entries as [structured
+/// metadata][structured_metadata], which lets Grafana link logs to their traces without indexing
+/// the IDs as labels.
+///
+/// Requires Loki 2.9.0 or newer, with structured metadata allowed.
+///
+/// [structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TraceCorrelationConfig {
+    /// Whether or not to attach the trace context of events to their entries.
+    #[serde(default = "crate::serde::default_false")]
+    pub enabled: bool,
+
+    /// The field holding the trace ID.
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: OptionalValuePath,
+
+    /// The field holding the span ID.
+    #[serde(default = "default_span_id_field")]
+    pub span_id_field: OptionalValuePath,
+
+    /// The field holding a [W3C `traceparent` header][traceparent].
+    ///
+    /// The trace and span IDs are taken from it for events without a trace ID field.
+    ///
+    /// [traceparent]: https://www.w3.org/TR/trace-context/#traceparent-header
+    #[serde(default = "default_traceparent_field")]
+    pub traceparent_field: OptionalValuePath,
+}
+
+/// Attachment configuration.
+///
+/// Events, or some of their fields, are attached to their entries as a JSON object in
+/// [structured metadata][structured_metadata], so that the original event can be recovered
+/// whatever the encoding of the line and the fields removed from it.
+///
+/// The attachment is not a file or a separate part of the request: it is the value of a single
+/// structured metadata key of the entry, `original` by default. Like the rest of the structured
+/// metadata, it is not indexed, and queries can read it back like a label, for example with
+/// `| line_format "{{.original}}"` for the default key.
+///
+/// Requires Loki 2.9.0 or newer, with structured metadata allowed.
+///
+/// [structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AttachmentConfig {
+    /// Whether or not to attach events to their entries.
+    #[serde(default = "crate::serde::default_false")]
+    pub enabled: bool,
+
+    /// The structured metadata key the attachment is sent under.
+    ///
+    /// The attachment is the value of this key, next to the other structured metadata of the
+    /// entry.
+    #[serde(default = "default_attachment_key")]
+    pub key: String,
+
+    /// The fields attached, as an object keyed by their path.
+    ///
+    /// Missing fields are left out of the object, and events without any of them get no
+    /// attachment. If empty, the whole event is attached, as received by the sink.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "request", docs::examples = "user.id"))]
+    pub fields: Vec<ConfigValuePath>,
+
+    /// The maximum size, in bytes, of an attachment.
+    ///
+    /// Larger attachments are not sent, as truncating them would leave invalid JSON, and as Loki
+    /// rejects entries whose structured metadata exceeds its `max_structured_metadata_size` limit.
+    #[serde(default = "default_attachment_max_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: NonZeroUsize,
+}
+
+fn default_attachment_key() -> String {
+    "original".to_owned()
+}
+
+fn default_attachment_max_bytes() -> NonZeroUsize {
+    NonZeroUsize::new(8192).expect("static")
+}
+
+impl Default for AttachmentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key: default_attachment_key(),
+            fields: Vec::new(),
+            max_bytes: default_attachment_max_bytes(),
+        }
+    }
+}
+
+fn default_trace_id_field() -> OptionalValuePath {
+    owned_value_path!("trace_id").into()
+}
+
+fn default_span_id_field() -> OptionalValuePath {
+    owned_value_path!("span_id").into()
+}
+
+fn default_traceparent_field() -> OptionalValuePath {
+    owned_value_path!("traceparent").into()
+}
+
+/// Stream sharding configuration.
+///
+/// When Loki rejects a push request because a stream exceeds its per-stream rate limit, the
+/// entries of the stream are spread across several streams by adding a `__shard__` label to them,
+/// so that its throughput isn't limited to that of a single stream.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StreamShardingConfig {
+    /// Whether or not to shard the streams exceeding the per-stream rate limit.
+    #[serde(default = "crate::serde::default_false")]
+    pub enabled: bool,
+
+    /// The number of streams the entries of a sharded stream are spread across.
+    #[serde(default = "default_stream_shards")]
+    pub shards: NonZeroU32,
+
+    /// How long a stream stays sharded after Loki last rejected it, in seconds.
+    #[serde(default = "default_stream_sharding_duration_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub duration_secs: NonZeroU64,
+}
+
+fn default_stream_shards() -> NonZeroU32 {
+    NonZeroU32::new(4).expect("static")
+}
+
+fn default_stream_sharding_duration_secs() -> NonZeroU64 {
+    NonZeroU64::new(300).expect("static")
+}
+
+impl Default for StreamShardingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shards: default_stream_shards(),
+            duration_secs: default_stream_sharding_duration_secs(),
+        }
+    }
+}
+
+/// Label cardinality guard configuration.
+///
+/// The distinct values of each label name are tracked over a sliding window. Once a label name has
+/// `limit` distinct values within the window, events carrying a new value for it are handled
+/// according to `action`, so that a label with unbounded values does not create an unbounded number
+/// of streams.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LabelCardinalityConfig {
+    /// The maximum number of distinct values of a label name within the window.
+    ///
+    /// If unset, the cardinality of labels is not limited.
+    #[configurable(metadata(docs::examples = 1000))]
+    pub limit: Option<NonZeroUsize>,
+
+    /// The length of the sliding window, in seconds.
+    ///
+    /// A value stops counting towards the limit once it has not been seen for that long.
+    #[serde(default = "default_label_cardinality_window_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub window_secs: NonZeroU64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: LabelCardinalityAction,
+}
+
+fn default_label_cardinality_window_secs() -> NonZeroU64 {
+    NonZeroU64::new(3600).expect("static")
+}
+
+impl Default for LabelCardinalityConfig {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            window_secs: default_label_cardinality_window_secs(),
+            action: LabelCardinalityAction::default(),
+        }
+    }
+}
+
+/// The action to take on an event carrying a label value over the cardinality limit.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelCardinalityAction {
+    /// Drop the label.
+    ///
+    /// Its value stays in the log line, unless `remove_label_fields` is set.
+    #[derivative(Default)]
+    DropLabel,
+
+    /// Move the label to the structured metadata of the entry.
+    ///
+    /// Requires Loki 2.9.0 or newer, with structured metadata allowed.
+    StructuredMetadata,
+
+    /// Drop the event.
+    DropEvent,
+}
+
+/// Per-tenant rate limiting configuration.
+///
+/// Each tenant gets its own token bucket. Its rate is halved each time Loki rejects a push request
+/// of the tenant with `429 Too Many Requests`, and recovers as its requests succeed, so that a
+/// rate-limited tenant does not slow down the requests of the other tenants.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TenantRateLimitConfig {
+    /// Whether or not to rate limit the push requests of each tenant.
+    #[serde(default = "crate::serde::default_false")]
+    pub enabled: bool,
+
+    /// The highest rate of push requests of a tenant, in requests per second.
+    #[serde(default = "default_tenant_max_requests_per_sec")]
+    #[configurable(metadata(docs::type_unit = "requests"))]
+    pub max_requests_per_sec: NonZeroU32,
+}
+
+fn default_tenant_max_requests_per_sec() -> NonZeroU32 {
+    NonZeroU32::new(10).expect("static")
+}
+
+impl Default for TenantRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_requests_per_sec: default_tenant_max_requests_per_sec(),
+        }
+    }
+}
+
+impl Default for TraceCorrelationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trace_id_field: default_trace_id_field(),
+            span_id_field: default_span_id_field(),
+            traceparent_field: default_traceparent_field(),
+        }
+    }
 }
 
 impl GenerateConfig for LokiConfig {
@@ -205,9 +767,30 @@ impl GenerateConfig for LokiConfig {
 impl LokiConfig {
     pub(super) fn build_client(&self, cx: SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
         Ok(client)
     }
+
+    /// Builds the channel of the `grpc` protocol, which connects on the first push request.
+    pub(super) fn build_channel(&self) -> crate::Result<Channel> {
+        let uri = &self.endpoint.uri;
+        let mut endpoint = Endpoint::from(uri.clone());
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            let tls = TlsSettings::from_options(&self.tls)?;
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(host) = uri.host() {
+                tls_config = tls_config.domain_name(host);
+            }
+            if let Some((cert, key)) = tls.identity_pem() {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+            for authority in tls.authorities_pem() {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+            }
+            endpoint = endpoint.tls_config(tls_config)?;
+        }
+        Ok(endpoint.connect_lazy())
+    }
 }
 
 #[async_trait::async_trait]
@@ -222,11 +805,64 @@ impl SinkConfig for LokiConfig {
         }
 
         for label in self.labels.keys() {
-            if !valid_label_name(label) {
+            let valid = match self.endpoint_format {
+                LokiEndpointFormat::Push => valid_label_name(label),
+                LokiEndpointFormat::Otlp => valid_attribute_name(label),
+            };
+            if !valid {
                 return Err(format!("Invalid label name {:?}", label.get_ref()).into());
             }
         }
 
+        for key in self.structured_metadata.keys() {
+            if !valid_label_name(key) || key.get_ref().trim().ends_with('*') {
+                return Err(format!("Invalid structured metadata name {:?}", key.get_ref()).into());
+            }
+        }
+
+        for (tenant_id, policy) in &self.tenant_labels {
+            match (&policy.label_prefix, policy.disallowed_labels) {
+                (Some(prefix), _) if !valid_label_prefix(prefix) => {
+                    return Err(format!("Invalid label prefix {:?}", prefix).into());
+                }
+                (None, DisallowedLabelAction::Rename) => {
+                    return Err(format!(
+                        "`tenant_labels.{}.label_prefix` must be set to rename disallowed labels.",
+                        tenant_id
+                    )
+                    .into());
+                }
+                _ => {}
+            }
+        }
+
+        if self.max_label_value_length.is_some()
+            && self.label_normalization.max_value_length.is_some()
+        {
+            return Err("Only one of `max_label_value_length` and \
+                `label_normalization.max_value_length` can be set."
+                .into());
+        }
+
+        if self.stream_metrics_labels.len() > MAX_STREAM_METRICS_LABELS {
+            return Err(format!(
+                "`stream_metrics_labels` must include at most {} labels.",
+                MAX_STREAM_METRICS_LABELS
+            )
+            .into());
+        }
+
+        if self.endpoint_format == LokiEndpointFormat::Otlp {
+            if self.protocol == LokiProtocol::Grpc {
+                return Err("The `otlp` endpoint format is only supported over HTTP.".into());
+            }
+            if let CompressionConfigAdapter::Extended(_) = self.compression {
+                return Err(
+                    "The `otlp` endpoint format does not support `snappy` compression.".into(),
+                );
+            }
+        }
+
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
@@ -236,7 +872,10 @@ impl SinkConfig for LokiConfig {
 
         let sink = LokiSink::new(config.clone(), client.clone())?;
 
-        let healthcheck = healthcheck(config, client).boxed();
+        let healthcheck = match config.protocol {
+            LokiProtocol::Http => healthcheck(config, client).boxed(),
+            LokiProtocol::Grpc => future::ok(()).boxed(),
+        };
 
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
@@ -279,11 +918,36 @@ pub fn valid_label_name(label: &Template) -> bool {
     }
 }
 
+/// Whether the label is a valid name of a resource attribute, which can also contain dots, such as
+/// `service.name`.
+fn valid_attribute_name(label: &Template) -> bool {
+    Template::try_from(label.get_ref().replace('.', "_").as_str())
+        .map_or(false, |label| valid_label_name(&label))
+}
+
+fn valid_label_prefix(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    chars
+        .next()
+        .map_or(false, |ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryInto;
 
-    use super::valid_label_name;
+    use super::{valid_attribute_name, valid_label_name, valid_label_prefix, TenantLabelsConfig};
+
+    #[test]
+    fn valid_attribute_names() {
+        assert!(valid_attribute_name(&"service.name".try_into().unwrap()));
+        assert!(valid_attribute_name(&"k8s.pod.name".try_into().unwrap()));
+        assert!(valid_attribute_name(&"app".try_into().unwrap()));
+
+        assert!(!valid_label_name(&"service.name".try_into().unwrap()));
+        assert!(!valid_attribute_name(&"service-name".try_into().unwrap()));
+    }
 
     #[test]
     fn valid_label_names() {
@@ -301,4 +965,28 @@ mod tests {
 
         assert!(valid_label_name(&"{{field}}".try_into().unwrap()));
     }
+
+    #[test]
+    fn valid_label_prefixes() {
+        assert!(valid_label_prefix("team_a_"));
+        assert!(valid_label_prefix("_x"));
+
+        assert!(!valid_label_prefix(""));
+        assert!(!valid_label_prefix("1x_"));
+        assert!(!valid_label_prefix("team-a_"));
+    }
+
+    #[test]
+    fn tenant_allowed_labels() {
+        let policy = TenantLabelsConfig {
+            allowed_labels: vec!["app".to_owned(), "pod_labels_*".to_owned()],
+            ..Default::default()
+        };
+        assert!(policy.allows("app"));
+        assert!(policy.allows("pod_labels_name"));
+        assert!(!policy.allows("application"));
+        assert!(!policy.allows("pod_name"));
+
+        assert!(TenantLabelsConfig::default().allows("anything"));
+    }
 }
@@ -2,10 +2,18 @@ use std::{collections::HashMap, io};
 
 use crate::sinks::prelude::*;
 use bytes::Bytes;
+use opentelemetry_proto::proto::{
+    common::v1::{any_value, AnyValue, KeyValue},
+    logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    resource::v1::Resource,
+};
 use serde::{ser::SerializeSeq, Serialize};
 use vector_buffers::EventCount;
 
-use crate::sinks::util::encoding::{write_all, Encoder};
+use crate::{
+    internal_events::EncoderWriteError,
+    sinks::util::encoding::{as_tracked_write, Encoder},
+};
 
 pub type Labels = Vec<(String, String)>;
 
@@ -13,6 +21,20 @@ pub type Labels = Vec<(String, String)>;
 pub enum LokiBatchEncoding {
     Json,
     Protobuf,
+    /// Protocol Buffers, without compression, as sent over gRPC.
+    Grpc,
+    /// OTLP log records, as Protocol Buffers.
+    Otlp,
+}
+
+impl LokiBatchEncoding {
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            LokiBatchEncoding::Json => "application/json",
+            LokiBatchEncoding::Protobuf | LokiBatchEncoding::Otlp => "application/x-protobuf",
+            LokiBatchEncoding::Grpc => "application/grpc",
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -25,45 +47,159 @@ impl Encoder<Vec<LokiRecord>> for LokiBatchEncoder {
         writer: &mut dyn io::Write,
     ) -> io::Result<usize> {
         let count = input.len();
-        let batch = LokiBatch::from(input);
-        let body = match self.0 {
-            LokiBatchEncoding::Json => {
-                let streams: Vec<LokiStream> = batch.stream_by_labels.into_values().collect();
-                let body = serde_json::json!({ "streams": streams });
-                serde_json::to_vec(&body)?
-            }
-            LokiBatchEncoding::Protobuf => {
-                let streams = batch.stream_by_labels.into_values();
-                let batch = loki_logproto::util::Batch(
-                    streams
-                        .map(|stream| {
-                            let labels = stream.stream;
-                            let entries = stream
-                                .values
-                                .iter()
-                                .map(|event| {
-                                    loki_logproto::util::Entry(
-                                        event.timestamp,
-                                        String::from_utf8_lossy(&event.event).into_owned(),
-                                    )
-                                })
-                                .collect();
-                            loki_logproto::util::Stream(labels, entries)
-                        })
-                        .collect(),
-                );
-                batch.encode()
-            }
+        let result = match self.0 {
+            LokiBatchEncoding::Json => as_tracked_write(writer, input, write_json),
+            LokiBatchEncoding::Protobuf => as_tracked_write(writer, input, write_protobuf),
+            LokiBatchEncoding::Grpc => as_tracked_write(writer, input, write_grpc),
+            LokiBatchEncoding::Otlp => as_tracked_write(writer, input, write_otlp),
         };
-        write_all(writer, count, &body).map(|()| body.len())
+        result.map_err(|error| {
+            emit!(EncoderWriteError {
+                error: &error,
+                count,
+            });
+            error
+        })
     }
 }
 
-#[derive(Debug, Default, Serialize)]
-pub struct LokiBatch {
-    stream_by_labels: HashMap<String, LokiStream>,
-    #[serde(skip)]
-    finalizers: EventFinalizers,
+/// Groups the records into streams, ordered by labels, with the entries of each stream ordered
+/// by timestamp.
+///
+/// The records are sorted in place and each stream is only built once it is reached, so a
+/// single stream is held apart from the records at a time.
+fn streams(mut records: Vec<LokiRecord>) -> impl Iterator<Item = LokiStream> {
+    for record in &mut records {
+        record.labels.sort();
+    }
+    records.sort_by(|a, b| {
+        a.labels
+            .cmp(&b.labels)
+            .then(a.event.timestamp.cmp(&b.event.timestamp))
+    });
+    let mut records = records.into_iter().peekable();
+    std::iter::from_fn(move || {
+        let first = records.next()?;
+        let mut values = vec![first.event];
+        while let Some(record) = records.next_if(|record| record.labels == first.labels) {
+            values.push(record.event);
+        }
+        Some(LokiStream {
+            stream: first.labels.into_iter().collect(),
+            values,
+        })
+    })
+}
+
+fn logproto_stream(stream: LokiStream) -> loki_logproto::util::Stream {
+    let entries = stream
+        .values
+        .into_iter()
+        .map(|event| {
+            loki_logproto::util::Entry(
+                event.timestamp,
+                String::from_utf8_lossy(&event.event).into_owned(),
+                event.structured_metadata,
+            )
+        })
+        .collect();
+    loki_logproto::util::Stream(stream.stream, entries)
+}
+
+fn string_attribute(key: String, value: String) -> KeyValue {
+    KeyValue {
+        key,
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+/// Converts the stream into the OTLP resource it is sent as.
+fn otlp_resource_logs(stream: LokiStream) -> ResourceLogs {
+    let mut labels = stream.stream.into_iter().collect::<Vec<_>>();
+    labels.sort();
+    let log_records = stream
+        .values
+        .into_iter()
+        .map(|event| LogRecord {
+            time_unix_nano: event.timestamp as u64,
+            body: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(
+                    String::from_utf8_lossy(&event.event).into_owned(),
+                )),
+            }),
+            attributes: event
+                .structured_metadata
+                .into_iter()
+                .map(|(key, value)| string_attribute(key, value))
+                .collect(),
+            ..Default::default()
+        })
+        .collect();
+    ResourceLogs {
+        resource: Some(Resource {
+            attributes: labels
+                .into_iter()
+                .map(|(key, value)| string_attribute(key, value))
+                .collect(),
+            dropped_attributes_count: 0,
+        }),
+        scope_logs: vec![ScopeLogs {
+            log_records,
+            ..Default::default()
+        }],
+        schema_url: String::new(),
+    }
+}
+
+/// Writes the JSON body of a push request into the writer, one stream at a time, instead of
+/// building the whole body in memory first.
+fn write_json(writer: &mut dyn io::Write, records: Vec<LokiRecord>) -> io::Result<()> {
+    writer.write_all(br#"{"streams":["#)?;
+    for (index, stream) in streams(records).enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut *writer, &stream)?;
+    }
+    writer.write_all(b"]}")
+}
+
+/// Writes the snappy compressed protobuf body of a push request into the writer.
+///
+/// Snappy compresses the whole request at once, so it can't be written as it is encoded. Streams
+/// are still converted one at a time, releasing their events once encoded.
+fn write_protobuf(writer: &mut dyn io::Write, records: Vec<LokiRecord>) -> io::Result<()> {
+    let mut request = loki_logproto::util::BatchEncoder::default();
+    for stream in streams(records) {
+        request.push(logproto_stream(stream));
+    }
+    writer.write_all(&request.finish())
+}
+
+/// Writes the uncompressed protobuf body of a push request into the writer, one stream at a
+/// time.
+fn write_grpc(writer: &mut dyn io::Write, records: Vec<LokiRecord>) -> io::Result<()> {
+    let mut buf = Vec::new();
+    for stream in streams(records) {
+        buf.clear();
+        loki_logproto::util::encode_stream(logproto_stream(stream), &mut buf);
+        writer.write_all(&buf)?;
+    }
+    Ok(())
+}
+
+/// Writes an OTLP export request into the writer, one resource at a time.
+fn write_otlp(writer: &mut dyn io::Write, records: Vec<LokiRecord>) -> io::Result<()> {
+    let mut buf = Vec::new();
+    for stream in streams(records) {
+        buf.clear();
+        // The resources are the repeated field 1 of the request.
+        prost::encoding::message::encode(1, &otlp_resource_logs(stream), &mut buf);
+        writer.write_all(&buf)?;
+    }
+    Ok(())
 }
 
 #[derive(Debug, Default, Serialize)]
@@ -72,66 +208,45 @@ pub struct LokiStream {
     values: Vec<LokiEvent>,
 }
 
-impl From<Vec<LokiRecord>> for LokiBatch {
-    fn from(events: Vec<LokiRecord>) -> Self {
-        let mut result = events
-            .into_iter()
-            .fold(Self::default(), |mut res, mut item| {
-                res.finalizers.merge(item.take_finalizers());
-                item.labels.sort();
-                // Convert a HashMap of keys and values into a string in the
-                // format "k1,v1,k2,v2,". If any of the keys or values contain
-                // a comma, it escapes the comma by adding a backslash before
-                // it (e.g. "val,ue" becomes "val\,ue").
-                let labels: String = item
-                    .labels
-                    .iter()
-                    .flat_map(|(a, b)| [a, b])
-                    .map(|s| {
-                        let mut escaped: String = s
-                            .chars()
-                            .map(|c| match c {
-                                '\\' => "\\\\".to_string(),
-                                ',' => "\\,".to_string(),
-                                c => c.to_string(),
-                            })
-                            .collect();
-                        escaped.push(',');
-                        escaped
-                    })
-                    .collect();
-                if !res.stream_by_labels.contains_key(&labels) {
-                    res.stream_by_labels.insert(
-                        labels.clone(),
-                        LokiStream {
-                            stream: item.labels.into_iter().collect(),
-                            values: Vec::new(),
-                        },
-                    );
-                }
-                let stream = res
-                    .stream_by_labels
-                    .get_mut(&labels)
-                    .expect("stream must exist");
-                stream.values.push(item.event);
-                res
-            });
-        for (_k, stream) in result.stream_by_labels.iter_mut() {
-            stream.values.sort_by_key(|e| e.timestamp);
-        }
-        result
-    }
+/// Gets the key of the stream with the sorted labels.
+///
+/// The labels are converted into a string in the format "k1,v1,k2,v2,". If any of the keys or
+/// values contain a comma, it escapes the comma by adding a backslash before it (e.g. "val,ue"
+/// becomes "val\,ue").
+pub fn stream_key(labels: &[(String, String)]) -> String {
+    labels
+        .iter()
+        .flat_map(|(a, b)| [a, b])
+        .map(|s| {
+            let mut escaped: String = s
+                .chars()
+                .map(|c| match c {
+                    '\\' => "\\\\".to_string(),
+                    ',' => "\\,".to_string(),
+                    c => c.to_string(),
+                })
+                .collect();
+            escaped.push(',');
+            escaped
+        })
+        .collect()
 }
 
 #[derive(Clone, Debug)]
 pub struct LokiEvent {
     pub timestamp: i64,
     pub event: Bytes,
+    /// Key/value pairs attached to the entry without being indexed, unlike labels.
+    pub structured_metadata: Vec<(String, String)>,
 }
 
 impl ByteSizeOf for LokiEvent {
     fn allocated_bytes(&self) -> usize {
-        self.timestamp.allocated_bytes() + self.event.allocated_bytes()
+        self.timestamp.allocated_bytes()
+            + self.event.allocated_bytes()
+            + self.structured_metadata.iter().fold(0, |res, item| {
+                res + item.0.allocated_bytes() + item.1.allocated_bytes()
+            })
     }
 }
 
@@ -140,10 +255,23 @@ impl Serialize for LokiEvent {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(2))?;
+        let len = if self.structured_metadata.is_empty() {
+            2
+        } else {
+            3
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.timestamp.to_string())?;
         let event = String::from_utf8_lossy(&self.event);
         seq.serialize_element(&event)?;
+        if !self.structured_metadata.is_empty() {
+            let structured_metadata: HashMap<&str, &str> = self
+                .structured_metadata
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            seq.serialize_element(&structured_metadata)?;
+        }
         seq.end()
     }
 }
@@ -155,6 +283,9 @@ pub struct LokiRecord {
     pub event: LokiEvent,
     pub json_byte_size: JsonSize,
     pub finalizers: EventFinalizers,
+    /// The event as it was received, without its finalizers, kept to be sent to the dead letter
+    /// source if Loki rejects it.
+    pub original: Option<Event>,
 }
 
 impl ByteSizeOf for LokiRecord {
@@ -164,6 +295,7 @@ impl ByteSizeOf for LokiRecord {
                 res + item.0.allocated_bytes() + item.1.allocated_bytes()
             })
             + self.event.allocated_bytes()
+            + self.original.allocated_bytes()
     }
 }
 
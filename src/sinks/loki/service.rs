@@ -1,17 +1,53 @@
-use std::task::{Context, Poll};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
 
-use bytes::Bytes;
-use http::StatusCode;
+use bytes::{BufMut, Bytes};
+use chrono::Utc;
+use http::{uri::PathAndQuery, HeaderMap, StatusCode};
+use loki_logproto::logproto::PushResponse;
 use snafu::Snafu;
+use tokio::time::Instant;
+use tonic::{
+    client::Grpc,
+    codec::{Codec, EncodeBuf, ProstCodec},
+    metadata::{AsciiMetadataValue, MetadataMap},
+    transport::Channel,
+};
 use tracing::Instrument;
 use vector_core::internal_event::CountByteSize;
 
-use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
+use super::sink::StreamShards;
+use crate::sinks::loki::config::{CompressionConfigAdapter, TenantRateLimitConfig};
 use crate::{
+    dead_letter,
     http::{Auth, HttpClient},
-    sinks::{prelude::*, util::UriSerde},
+    internal_events::{
+        LokiDeadLettersError, LokiDeadLettersSent, LokiStreamEntriesRejected,
+        LokiStreamEntriesSent, LokiStreamsSharded, LokiTenantRateLimited,
+    },
+    sinks::{
+        prelude::*,
+        util::{http::retry_after, UriSerde},
+    },
 };
 
+/// The lowest rate of push requests of a rate-limited tenant, in requests per second.
+const MIN_TENANT_RATE: f64 = 0.1;
+
+/// The longest delay asked by Loki with the `Retry-After` header that is honored.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// How long the limit of a tenant without any request is kept, longer than any honored
+/// `Retry-After` delay.
+const TENANT_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The gRPC method push requests are sent to.
+const GRPC_PUSH_PATH: &str = "/logproto.Pusher/Push";
+
 #[derive(Clone)]
 pub struct LokiRetryLogic;
 
@@ -28,6 +64,16 @@ impl RetryLogic for LokiRetryLogic {
                 _ => false,
             },
             LokiError::HttpError { .. } => true,
+            LokiError::GrpcError { status } => matches!(
+                status.code(),
+                tonic::Code::Unknown
+                    | tonic::Code::DeadlineExceeded
+                    | tonic::Code::ResourceExhausted
+                    | tonic::Code::Aborted
+                    | tonic::Code::Internal
+                    | tonic::Code::Unavailable
+            ),
+            LokiError::InvalidTenantId { .. } => false,
         }
     }
 }
@@ -38,6 +84,17 @@ pub enum LokiError {
     ServerError { code: StatusCode },
     #[snafu(display("Failed to make HTTP(S) request: {}", error))]
     HttpError { error: crate::http::HttpError },
+    #[snafu(display("gRPC request failed: {}", status))]
+    GrpcError { status: tonic::Status },
+    #[snafu(display("Invalid tenant ID {:?}", tenant_id))]
+    InvalidTenantId { tenant_id: String },
+}
+
+/// A push request Loki rejected because of its rate limits.
+struct RateLimited {
+    retry_after: Option<Duration>,
+    /// Whether a stream exceeded its rate limit, as opposed to the tenant.
+    stream: bool,
 }
 
 #[derive(Debug, Snafu)]
@@ -65,9 +122,16 @@ impl DriverResponse for LokiResponse {
 #[derive(Clone)]
 pub struct LokiRequest {
     pub compression: CompressionConfigAdapter,
+    pub content_type: &'static str,
     pub finalizers: EventFinalizers,
     pub payload: Bytes,
     pub tenant_id: Option<String>,
+    /// The keys of the streams of the request, when streams are sharded.
+    pub streams: Vec<String>,
+    /// The number of entries of each stream of the request, keyed for the per-stream metrics.
+    pub stream_counts: Vec<(String, usize)>,
+    /// The events of the request as they were received, when a dead letter source is configured.
+    pub dead_letters: Vec<Event>,
     pub metadata: RequestMetadata,
 }
 
@@ -83,75 +147,513 @@ impl MetaDescriptive for LokiRequest {
     }
 }
 
+/// How push requests are sent to Loki.
 #[derive(Debug, Clone)]
-pub struct LokiService {
-    endpoint: UriSerde,
-    client: HttpClient,
+pub enum LokiTransport {
+    /// To the HTTP push API.
+    Http {
+        endpoint: UriSerde,
+        client: HttpClient,
+    },
+    /// To the `Push` method of the `logproto.Pusher` gRPC service.
+    Grpc {
+        client: Grpc<Channel>,
+        headers: MetadataMap,
+    },
 }
 
-impl LokiService {
-    pub fn new(
+impl LokiTransport {
+    pub fn http(
         client: HttpClient,
         endpoint: UriSerde,
         path: String,
         auth: Option<Auth>,
     ) -> crate::Result<Self> {
         let endpoint = endpoint.append_path(&path)?.with_auth(auth);
+        Ok(Self::Http { endpoint, client })
+    }
 
-        Ok(Self { client, endpoint })
+    pub fn grpc(channel: Channel, auth: Option<&Auth>) -> Self {
+        let mut headers = HeaderMap::new();
+        if let Some(auth) = auth {
+            auth.apply_headers_map(&mut headers);
+        }
+        Self::Grpc {
+            client: Grpc::new(channel),
+            headers: MetadataMap::from_headers(headers),
+        }
     }
 }
 
-impl Service<LokiRequest> for LokiService {
-    type Response = LokiResponse;
-    type Error = LokiError;
-    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+#[derive(Debug, Clone)]
+pub struct LokiService {
+    transport: LokiTransport,
+    stream_shards: Option<StreamShards>,
+    rate_limiter: Arc<TenantRateLimiter>,
+    dead_letter: Option<String>,
+}
 
-    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
+impl LokiService {
+    pub fn new(
+        transport: LokiTransport,
+        stream_shards: Option<StreamShards>,
+        rate_limiter: TenantRateLimiter,
+        dead_letter: Option<String>,
+    ) -> Self {
+        Self {
+            transport,
+            stream_shards,
+            rate_limiter: Arc::new(rate_limiter),
+            dead_letter,
+        }
     }
+}
+
+/// Holds back the push requests of the tenants Loki rate limits.
+///
+/// Requests of a tenant are held for as long as asked by the `Retry-After` header of its last
+/// rejected request, and, if enabled, by a token bucket whose rate adapts to the rejections. The
+/// limits of tenants idle for `TENANT_IDLE_TIMEOUT` are dropped, so tenants that stopped sending
+/// don't accumulate.
+#[derive(Debug, Default)]
+pub struct TenantRateLimiter {
+    /// The highest rate of push requests of a tenant, if token buckets are enabled.
+    max_rate: Option<f64>,
+    tenants: Mutex<TenantLimits>,
+}
+
+#[derive(Debug, Default)]
+struct TenantLimits {
+    limits: HashMap<Option<String>, TenantLimit>,
+    /// When idle tenants were last dropped.
+    evicted: Option<Instant>,
+}
 
-    fn call(&mut self, request: LokiRequest) -> Self::Future {
-        let content_type = match request.compression {
-            CompressionConfigAdapter::Original(_) => "application/json",
-            CompressionConfigAdapter::Extended(ExtendedCompression::Snappy) => {
-                "application/x-protobuf"
+#[derive(Debug)]
+struct TenantLimit {
+    /// Requests are held until then.
+    retry_after: Option<Instant>,
+    /// The rate of the token bucket, in requests per second.
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+    /// When the limit was last used.
+    used: Instant,
+}
+
+impl TenantRateLimiter {
+    pub fn new(config: &TenantRateLimitConfig) -> Self {
+        Self {
+            max_rate: config
+                .enabled
+                .then(|| f64::from(config.max_requests_per_sec.get())),
+            tenants: Mutex::default(),
+        }
+    }
+
+    fn with_tenant<T>(
+        &self,
+        tenant_id: &Option<String>,
+        now: Instant,
+        f: impl FnOnce(&mut TenantLimit) -> T,
+    ) -> T {
+        let mut tenants = self.tenants.lock().expect("poisoned lock");
+        if tenants.evicted.map_or(true, |evicted| {
+            now.saturating_duration_since(evicted) >= TENANT_IDLE_TIMEOUT
+        }) {
+            tenants.limits.retain(|_, limit| {
+                now.saturating_duration_since(limit.used) < TENANT_IDLE_TIMEOUT
+                    || limit.retry_after.map_or(false, |until| until > now)
+            });
+            tenants.evicted = Some(now);
+        }
+
+        let rate = self.max_rate.unwrap_or(MIN_TENANT_RATE);
+        let limit = tenants
+            .limits
+            .entry(tenant_id.clone())
+            .or_insert_with(|| TenantLimit {
+                retry_after: None,
+                rate,
+                tokens: rate.max(1.0),
+                updated: now,
+                used: now,
+            });
+        limit.used = limit.used.max(now);
+        f(limit)
+    }
+
+    /// Reserves a request of the tenant, returning how long to hold it before sending it.
+    fn reserve(&self, tenant_id: &Option<String>, now: Instant) -> Duration {
+        let buckets = self.max_rate.is_some();
+        self.with_tenant(tenant_id, now, |limit| {
+            let mut delay = limit
+                .retry_after
+                .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+            if buckets {
+                // Up to a second worth of requests can be sent at once.
+                let elapsed = now.saturating_duration_since(limit.updated).as_secs_f64();
+                limit.tokens = (limit.tokens + elapsed * limit.rate).min(limit.rate.max(1.0));
+                limit.updated = now;
+                limit.tokens -= 1.0;
+                if limit.tokens < 0.0 {
+                    delay = delay.max(Duration::from_secs_f64(-limit.tokens / limit.rate));
+                }
             }
-        };
-        let mut req = http::Request::post(&self.endpoint.uri).header("Content-Type", content_type);
+            delay
+        })
+    }
 
-        let metadata = request.get_metadata();
+    /// Slows the tenant down after Loki rejected one of its requests.
+    fn throttle(&self, tenant_id: &Option<String>, retry_after: Option<Duration>, now: Instant) {
+        self.with_tenant(tenant_id, now, |limit| {
+            if let Some(retry_after) = retry_after {
+                let until = now + retry_after.min(MAX_RETRY_AFTER);
+                limit.retry_after = Some(limit.retry_after.map_or(until, |held| held.max(until)));
+            }
+            limit.rate = (limit.rate / 2.0).max(MIN_TENANT_RATE);
+        });
+    }
 
-        if let Some(tenant_id) = request.tenant_id {
-            req = req.header("X-Scope-OrgID", tenant_id);
+    /// Speeds the tenant back up after one of its requests succeeded.
+    fn recover(&self, tenant_id: &Option<String>, now: Instant) {
+        if let Some(max_rate) = self.max_rate {
+            self.with_tenant(tenant_id, now, |limit| {
+                limit.rate = (limit.rate + max_rate / 10.0).min(max_rate);
+            });
+        }
+    }
+}
+
+/// Whether Loki rejected a push request because a stream exceeded its rate limit, as opposed to
+/// the rate limit of the tenant.
+fn is_stream_rate_limited(body: &[u8]) -> bool {
+    String::from_utf8_lossy(body)
+        .to_lowercase()
+        .contains("per stream rate limit exceeded")
+}
+
+async fn push_http(
+    mut client: HttpClient,
+    endpoint: UriSerde,
+    request: LokiRequest,
+) -> Result<(), (LokiError, Option<RateLimited>)> {
+    let mut req = http::Request::post(&endpoint.uri).header("Content-Type", request.content_type);
+
+    if let Some(tenant_id) = &request.tenant_id {
+        req = req.header("X-Scope-OrgID", tenant_id);
+    }
+
+    if let Some(ce) = request.compression.content_encoding() {
+        req = req.header("Content-Encoding", ce);
+    }
+
+    let body = hyper::Body::from(request.payload);
+    let mut req = req.body(body).unwrap();
+
+    if let Some(auth) = &endpoint.auth {
+        auth.apply(&mut req);
+    }
+
+    match client.call(req).in_current_span().await {
+        Ok(response) => {
+            let status = response.status();
+
+            if status.is_success() {
+                Ok(())
+            } else if status == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = retry_after(response.headers(), Utc::now());
+                let body = hyper::body::to_bytes(response.into_body())
+                    .await
+                    .unwrap_or_default();
+                let rate_limited = RateLimited {
+                    retry_after,
+                    stream: is_stream_rate_limited(&body),
+                };
+                Err((LokiError::ServerError { code: status }, Some(rate_limited)))
+            } else {
+                Err((LokiError::ServerError { code: status }, None))
+            }
         }
+        Err(error) => Err((LokiError::HttpError { error }, None)),
+    }
+}
 
-        if let Some(ce) = request.compression.content_encoding() {
-            req = req.header("Content-Encoding", ce);
+async fn push_grpc(
+    mut client: Grpc<Channel>,
+    mut headers: MetadataMap,
+    request: LokiRequest,
+) -> Result<(), (LokiError, Option<RateLimited>)> {
+    if let Some(tenant_id) = request.tenant_id {
+        match AsciiMetadataValue::try_from(tenant_id.as_str()) {
+            Ok(value) => {
+                headers.insert("x-scope-orgid", value);
+            }
+            Err(_) => return Err((LokiError::InvalidTenantId { tenant_id }, None)),
         }
+    }
+    let mut push = tonic::Request::new(request.payload);
+    *push.metadata_mut() = headers;
 
-        let body = hyper::Body::from(request.payload);
-        let mut req = req.body(body).unwrap();
+    let result = async {
+        client.ready().await.map_err(|error| {
+            tonic::Status::new(
+                tonic::Code::Unknown,
+                format!("Service was not ready: {}", error),
+            )
+        })?;
+        client
+            .unary(push, PathAndQuery::from_static(GRPC_PUSH_PATH), PushCodec)
+            .await
+    }
+    .in_current_span()
+    .await;
 
-        if let Some(auth) = &self.endpoint.auth {
-            auth.apply(&mut req);
+    match result {
+        Ok(_) => Ok(()),
+        Err(status) => {
+            // Loki answers rate limited pushes with `ResourceExhausted`, without a delay to wait.
+            let rate_limited =
+                (status.code() == tonic::Code::ResourceExhausted).then(|| RateLimited {
+                    retry_after: None,
+                    stream: is_stream_rate_limited(status.message().as_bytes()),
+                });
+            Err((LokiError::GrpcError { status }, rate_limited))
         }
+    }
+}
+
+/// Sends the events of a rejected push request to the dead letter source.
+async fn send_dead_letters(id: &str, events: Vec<Event>) {
+    let count = events.len();
+    if count == 0 {
+        return;
+    }
+    match dead_letter::send(id, events).await {
+        Ok(()) => emit!(LokiDeadLettersSent {
+            dead_letter: id,
+            count
+        }),
+        Err(error) => emit!(LokiDeadLettersError { error, count }),
+    }
+}
+
+/// Sends the push requests as they were encoded, since they are already `PushRequest` messages,
+/// and decodes the responses with `prost`.
+#[derive(Debug, Clone, Copy, Default)]
+struct PushCodec;
+
+impl Codec for PushCodec {
+    type Encode = Bytes;
+    type Decode = PushResponse;
+    type Encoder = PushEncoder;
+    type Decoder = <ProstCodec<PushResponse, PushResponse> as Codec>::Decoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        PushEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        ProstCodec::<PushResponse, PushResponse>::default().decoder()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PushEncoder;
+
+impl tonic::codec::Encoder for PushEncoder {
+    type Item = Bytes;
+    type Error = tonic::Status;
 
-        let mut client = self.client.clone();
+    fn encode(&mut self, item: Bytes, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put(item);
+        Ok(())
+    }
+}
+
+impl Service<LokiRequest> for LokiService {
+    type Response = LokiResponse;
+    type Error = LokiError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: LokiRequest) -> Self::Future {
+        let metadata = request.get_metadata();
+        let tenant_id = request.tenant_id.clone();
+        let streams = std::mem::take(&mut request.streams);
+        let stream_counts = std::mem::take(&mut request.stream_counts);
+        let dead_letters = std::mem::take(&mut request.dead_letters);
+        let dead_letter = self.dead_letter.clone();
+        let stream_shards = self.stream_shards.clone();
+        let rate_limiter = Arc::clone(&self.rate_limiter);
+
+        let push = match &self.transport {
+            LokiTransport::Http { endpoint, client } => {
+                push_http(client.clone(), endpoint.clone(), request).boxed()
+            }
+            LokiTransport::Grpc { client, headers } => {
+                push_grpc(client.clone(), headers.clone(), request).boxed()
+            }
+        };
 
         Box::pin(async move {
-            match client.call(req).in_current_span().await {
-                Ok(response) => {
-                    let status = response.status();
-
-                    if status.is_success() {
-                        Ok(LokiResponse { metadata })
-                    } else {
-                        Err(LokiError::ServerError { code: status })
+            let delay = rate_limiter.reserve(&tenant_id, Instant::now());
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
+            match push.await {
+                Ok(()) => {
+                    rate_limiter.recover(&tenant_id, Instant::now());
+                    for (stream, count) in &stream_counts {
+                        emit!(LokiStreamEntriesSent {
+                            stream,
+                            count: *count
+                        });
                     }
+                    Ok(LokiResponse { metadata })
+                }
+                Err((error, rate_limited)) => {
+                    // Retried requests are counted once they are sent or rejected.
+                    if !LokiRetryLogic.is_retriable_error(&error) {
+                        for (stream, count) in &stream_counts {
+                            emit!(LokiStreamEntriesRejected {
+                                stream,
+                                count: *count
+                            });
+                        }
+                        if let Some(dead_letter) = dead_letter {
+                            send_dead_letters(&dead_letter, dead_letters).await;
+                        }
+                    }
+                    if let Some(RateLimited {
+                        retry_after,
+                        stream,
+                    }) = rate_limited
+                    {
+                        rate_limiter.throttle(&tenant_id, retry_after, Instant::now());
+                        emit!(LokiTenantRateLimited { retry_after });
+                        if let (true, Some(stream_shards)) = (stream, stream_shards) {
+                            let count = stream_shards.shard(&streams);
+                            if count > 0 {
+                                emit!(LokiStreamsSharded { count });
+                            }
+                        }
+                    }
+                    Err(error)
                 }
-                Err(error) => Err(LokiError::HttpError { error }),
             }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+
+    fn limiter(enabled: bool) -> TenantRateLimiter {
+        TenantRateLimiter::new(&TenantRateLimitConfig {
+            enabled,
+            max_requests_per_sec: NonZeroU32::new(2).unwrap(),
+        })
+    }
+
+    #[test]
+    fn retries_transient_grpc_errors() {
+        let retriable = |code| {
+            LokiRetryLogic.is_retriable_error(&LokiError::GrpcError {
+                status: tonic::Status::new(code, "error"),
+            })
+        };
+
+        assert!(retriable(tonic::Code::Unavailable));
+        assert!(retriable(tonic::Code::ResourceExhausted));
+        assert!(retriable(tonic::Code::DeadlineExceeded));
+        assert!(!retriable(tonic::Code::InvalidArgument));
+        assert!(!retriable(tonic::Code::Unauthenticated));
+        assert!(!retriable(tonic::Code::Unimplemented));
+    }
+
+    #[test]
+    fn holds_only_the_rate_limited_tenant() {
+        let limiter = limiter(false);
+        let now = Instant::now();
+        let noisy = Some("noisy".to_owned());
+        let quiet = Some("quiet".to_owned());
+
+        limiter.throttle(&noisy, Some(Duration::from_secs(10)), now);
+        assert_eq!(limiter.reserve(&noisy, now), Duration::from_secs(10));
+        assert_eq!(
+            limiter.reserve(&noisy, now + Duration::from_secs(4)),
+            Duration::from_secs(6)
+        );
+        assert_eq!(limiter.reserve(&quiet, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(&None, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn adapts_the_rate_of_each_tenant() {
+        let limiter = limiter(true);
+        let now = Instant::now();
+        let tenant = Some("tenant".to_owned());
+
+        // A burst of two requests, then one every half second.
+        assert_eq!(limiter.reserve(&tenant, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(&tenant, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(&tenant, now), Duration::from_millis(500));
+
+        // Halved to one request per second.
+        limiter.throttle(&tenant, None, now);
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(&tenant, later), Duration::ZERO);
+        assert_eq!(limiter.reserve(&tenant, later), Duration::from_secs(1));
+        assert_eq!(limiter.reserve(&None, later), Duration::ZERO);
+
+        for _ in 0..10 {
+            limiter.recover(&tenant, later);
+        }
+        let later = later + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(&tenant, later), Duration::ZERO);
+        assert_eq!(limiter.reserve(&tenant, later), Duration::ZERO);
+        assert_eq!(limiter.reserve(&tenant, later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn drops_idle_tenants() {
+        let limiter = limiter(true);
+        let now = Instant::now();
+        let idle = Some("idle".to_owned());
+        let active = Some("active".to_owned());
+        let tenants = || {
+            let mut tenants = limiter
+                .tenants
+                .lock()
+                .unwrap()
+                .limits
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+            tenants.sort();
+            tenants
+        };
+
+        limiter.throttle(&idle, None, now);
+        limiter.throttle(&active, None, now);
+        let later = now + TENANT_IDLE_TIMEOUT / 2;
+        limiter.reserve(&active, later);
+        assert_eq!(tenants(), vec![active.clone(), idle.clone()]);
+
+        limiter.reserve(&active, now + TENANT_IDLE_TIMEOUT);
+        assert_eq!(tenants(), vec![active.clone()]);
+
+        // A dropped tenant starts over at the highest rate.
+        let later = now + TENANT_IDLE_TIMEOUT * 2;
+        assert_eq!(limiter.reserve(&idle, later), Duration::ZERO);
+        assert_eq!(limiter.reserve(&idle, later), Duration::ZERO);
+    }
+}
@@ -1,22 +1,40 @@
-use std::{collections::HashMap, num::NonZeroUsize};
+use std::{
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use lookup::{lookup_v2::OptionalValuePath, PathPrefix};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use snafu::Snafu;
+use tokio::time::{interval, Instant, MissedTickBehavior};
 use tokio_util::codec::Encoder as _;
 
 use super::{
-    config::{LokiConfig, OutOfOrderAction},
-    event::{LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
-    service::{LokiRequest, LokiRetryLogic, LokiService},
+    config::{
+        default_loki_path, AttachmentConfig, DisallowedLabelAction, LabelCardinalityAction,
+        LabelCardinalityConfig, LabelNormalizationConfig, LokiConfig, LokiEndpointFormat,
+        LokiProtocol, OutOfOrderAction, StreamShardingConfig, TenantLabelsConfig,
+        TraceCorrelationConfig, OTLP_PATH,
+    },
+    event::{stream_key, Labels, LokiBatchEncoder, LokiEvent, LokiRecord, PartitionKey},
+    service::{LokiRequest, LokiRetryLogic, LokiService, LokiTransport, TenantRateLimiter},
 };
 use crate::sinks::loki::config::{CompressionConfigAdapter, ExtendedCompression};
 use crate::sinks::loki::event::LokiBatchEncoding;
 use crate::{
     http::{get_http_scheme_from_uri, HttpClient},
     internal_events::{
-        LokiEventUnlabeledError, LokiOutOfOrderEventDroppedError, LokiOutOfOrderEventRewritten,
+        LokiAttachmentTooLarge, LokiEventUnlabeledError, LokiLabelCardinalityExceeded,
+        LokiLabelsLimited, LokiOutOfOrderEventDroppedError, LokiOutOfOrderEventRewritten,
+        LokiStreamEntriesDropped, LokiStreamsSharded, LokiTenantLabelsDisallowed,
         SinkRequestBuildError,
     },
     sinks::prelude::*,
@@ -66,6 +84,8 @@ impl Partitioner for RecordPartitioner {
 pub struct LokiRequestBuilder {
     compression: CompressionConfigAdapter,
     encoder: LokiBatchEncoder,
+    stream_sharding: bool,
+    stream_metrics: StreamMetrics,
 }
 
 #[derive(Debug, Snafu)]
@@ -83,7 +103,13 @@ impl From<std::io::Error> for RequestBuildError {
 }
 
 impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
-    type Metadata = (Option<String>, EventFinalizers);
+    type Metadata = (
+        Option<String>,
+        EventFinalizers,
+        Vec<String>,
+        Vec<(String, usize)>,
+        Vec<Event>,
+    );
     type Events = Vec<LokiRecord>;
     type Encoder = LokiBatchEncoder;
     type Payload = Bytes;
@@ -91,9 +117,12 @@ impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
     type Error = RequestBuildError;
 
     fn compression(&self) -> Compression {
-        match self.compression {
-            CompressionConfigAdapter::Original(compression) => compression,
-            CompressionConfigAdapter::Extended(_) => Compression::None,
+        match (&self.encoder.0, self.compression) {
+            (
+                LokiBatchEncoding::Json | LokiBatchEncoding::Otlp,
+                CompressionConfigAdapter::Original(compression),
+            ) => compression,
+            _ => Compression::None,
         }
     }
 
@@ -109,8 +138,34 @@ impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
 
         let metadata_builder = RequestMetadataBuilder::from_events(&events);
         let finalizers = events.take_finalizers();
+        // The streams are only needed to shard them if Loki rejects the request.
+        let streams = if self.stream_sharding {
+            events
+                .iter()
+                .map(|record| StreamShards::unsharded_key(key.tenant_id.as_deref(), &record.labels))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let stream_counts = self.stream_metrics.count(&events);
+        let dead_letters = events
+            .iter_mut()
+            .filter_map(|record| record.original.take())
+            .collect();
 
-        ((key.tenant_id, finalizers), metadata_builder, events)
+        (
+            (
+                key.tenant_id,
+                finalizers,
+                streams,
+                stream_counts,
+                dead_letters,
+            ),
+            metadata_builder,
+            events,
+        )
     }
 
     fn build_request(
@@ -119,14 +174,18 @@ impl RequestBuilder<(PartitionKey, Vec<LokiRecord>)> for LokiRequestBuilder {
         metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
-        let (tenant_id, finalizers) = loki_metadata;
+        let (tenant_id, finalizers, streams, stream_counts, dead_letters) = loki_metadata;
         let compression = self.compression;
 
         LokiRequest {
             compression,
+            content_type: self.encoder.0.content_type(),
             finalizers,
             payload: payload.into_payload(),
             tenant_id,
+            streams,
+            stream_counts,
+            dead_letters,
             metadata,
         }
     }
@@ -138,8 +197,16 @@ pub(super) struct EventEncoder {
     transformer: Transformer,
     encoder: Encoder<()>,
     labels: HashMap<Template, Template>,
+    structured_metadata: HashMap<Template, Template>,
+    label_normalization: LabelNormalizationConfig,
+    tenant_labels: HashMap<String, TenantLabelsConfig>,
+    label_cardinality: Option<LabelCardinality>,
+    label_limits: LabelLimits,
+    trace_correlation: TraceCorrelationConfig,
+    attachment: AttachmentConfig,
     remove_label_fields: bool,
     remove_timestamp: bool,
+    stream_shards: Option<StreamShards>,
 }
 
 impl EventEncoder {
@@ -201,8 +268,9 @@ impl EventEncoder {
 
                 // key_* -> key_one, key_two, key_three
                 // * -> one, two, three
-                for (k, v) in output.unwrap() {
-                    let key = slugify_text(format!("{}{}", opening_prefix, k));
+                let mut expanded = Vec::new();
+                flatten_dynamic_labels(opening_prefix.to_owned(), output.unwrap(), &mut expanded);
+                for (key, v) in expanded {
                     let val = Value::from(v).to_string_lossy().into_owned();
                     if val == "<null>" {
                         warn!("Encountered \"null\" value for dynamic label. key: {}", key);
@@ -231,12 +299,238 @@ impl EventEncoder {
             };
         }
 
-        Vec::from_iter(dynamic_labels)
+        self.normalize_labels(dynamic_labels)
+    }
+
+    fn normalize_labels(&self, labels: HashMap<String, String>) -> Vec<(String, String)> {
+        let normalization = &self.label_normalization;
+        if *normalization == LabelNormalizationConfig::default() {
+            return Vec::from_iter(labels);
+        }
+
+        // Labels are normalized in the order of their names, so that when several are normalized
+        // into the same name, the first of them is kept whatever the order of the map.
+        let mut labels = Vec::from_iter(labels);
+        labels.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut normalized: HashMap<String, String> = HashMap::with_capacity(labels.len());
+        for (mut key, mut value) in labels {
+            if normalization.replace_invalid_characters {
+                key = normalize_label_name(&key);
+            }
+            if let Some(max_length) = normalization.max_value_length {
+                truncate_to_char_boundary(&mut value, max_length.get());
+            }
+            if normalization.drop_empty_values && value.is_empty() {
+                continue;
+            }
+            match normalized.entry(key) {
+                Entry::Occupied(kept) => warn!(
+                    "Normalized label collides with another label. \
+                key: {}, value: {}, discarded value: {}",
+                    kept.key(),
+                    kept.get(),
+                    value
+                ),
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+
+        Vec::from_iter(normalized)
+    }
+
+    fn isolate_labels(
+        &self,
+        tenant_id: Option<&String>,
+        labels: Vec<(String, String)>,
+    ) -> Vec<(String, String)> {
+        let (tenant_id, policy) = match tenant_id
+            .and_then(|id| self.tenant_labels.get_key_value(id))
+        {
+            Some((tenant_id, policy)) if !policy.allowed_labels.is_empty() => (tenant_id, policy),
+            _ => return labels,
+        };
+
+        let mut isolated: HashMap<String, String> = HashMap::with_capacity(labels.len());
+        let mut disallowed = 0;
+        for (key, value) in labels {
+            let key = if policy.allows(&key) {
+                key
+            } else {
+                disallowed += 1;
+                match policy.disallowed_labels {
+                    DisallowedLabelAction::Drop => continue,
+                    DisallowedLabelAction::Rename => {
+                        format!(
+                            "{}{}",
+                            policy.label_prefix.as_deref().unwrap_or_default(),
+                            key
+                        )
+                    }
+                }
+            };
+            if let Some(discarded_v) = isolated.insert(key.clone(), value.clone()) {
+                warn!(
+                    "Renamed label collides with another label. \
+                key: {}, value: {}, discarded value: {}",
+                    key, value, discarded_v
+                );
+            }
+        }
+
+        if disallowed > 0 {
+            emit!(LokiTenantLabelsDisallowed {
+                tenant_id,
+                count: disallowed,
+                action: match policy.disallowed_labels {
+                    DisallowedLabelAction::Drop => "drop",
+                    DisallowedLabelAction::Rename => "rename",
+                },
+            });
+        }
+
+        Vec::from_iter(isolated)
+    }
+
+    /// Holds the labels to the cardinality limit, returning whether the event is kept.
+    fn limit_label_cardinality(
+        &mut self,
+        labels: &mut Vec<(String, String)>,
+        structured_metadata: &mut Vec<(String, String)>,
+    ) -> bool {
+        let Some(cardinality) = &mut self.label_cardinality else {
+            return true;
+        };
+
+        let now = Instant::now();
+        let (admitted, exceeded): (Vec<_>, Vec<_>) = labels
+            .drain(..)
+            .partition(|(key, value)| cardinality.admit(key, value, now));
+        *labels = admitted;
+        if exceeded.is_empty() {
+            return true;
+        }
+
+        let action = cardinality.action;
+        emit!(LokiLabelCardinalityExceeded {
+            count: exceeded.len(),
+            action: match action {
+                LabelCardinalityAction::DropLabel => "drop_label",
+                LabelCardinalityAction::StructuredMetadata => "structured_metadata",
+                LabelCardinalityAction::DropEvent => "drop_event",
+            },
+        });
+        match action {
+            LabelCardinalityAction::DropLabel => true,
+            LabelCardinalityAction::StructuredMetadata => {
+                structured_metadata.extend(exceeded);
+                true
+            }
+            LabelCardinalityAction::DropEvent => false,
+        }
+    }
+
+    fn build_structured_metadata(&self, event: &Event) -> Vec<(String, String)> {
+        let mut structured_metadata = Vec::with_capacity(self.structured_metadata.len());
+        for (key_template, value_template) in &self.structured_metadata {
+            let rendered = key_template
+                .render_string(event)
+                .and_then(|key| Ok((key, value_template.render_string(event)?)));
+            match rendered {
+                Ok((key, value)) if !value.is_empty() => structured_metadata.push((key, value)),
+                Ok(_) => {}
+                Err(error) => emit!(TemplateRenderingError {
+                    field: Some(
+                        format!(
+                            "structured_metadata \"{}\" with value \"{}\"",
+                            key_template, value_template
+                        )
+                        .as_str()
+                    ),
+                    drop_event: false,
+                    error,
+                }),
+            }
+        }
+        // Keys are sorted so that entries carry them in the same order.
+        structured_metadata.sort();
+        structured_metadata
+    }
+
+    fn trace_context(&self, event: &Event) -> Vec<(String, String)> {
+        let correlation = &self.trace_correlation;
+        let mut trace_context = Vec::new();
+        if !correlation.enabled {
+            return trace_context;
+        }
+
+        let log = event.as_log();
+        let field = |path: &OptionalValuePath| {
+            path.path
+                .as_ref()
+                .and_then(|path| log.get((PathPrefix::Event, path)))
+                .map(|value| value.to_string_lossy().into_owned())
+                .filter(|value| !value.is_empty())
+        };
+
+        if let Some(trace_id) = field(&correlation.trace_id_field) {
+            trace_context.push(("trace_id".to_string(), trace_id));
+            if let Some(span_id) = field(&correlation.span_id_field) {
+                trace_context.push(("span_id".to_string(), span_id));
+            }
+        } else if let Some(traceparent) = field(&correlation.traceparent_field) {
+            if let Some((trace_id, span_id)) = parse_traceparent(&traceparent) {
+                trace_context.push(("trace_id".to_string(), trace_id.to_string()));
+                trace_context.push(("span_id".to_string(), span_id.to_string()));
+            }
+        }
+        trace_context
+    }
+
+    /// The attachment of the event, the JSON of the event or of the selected fields of it.
+    fn attachment(&self, event: &Event) -> Option<(String, String)> {
+        let attachment = &self.attachment;
+        if !attachment.enabled {
+            return None;
+        }
+
+        let log = event.as_log();
+        let json = if attachment.fields.is_empty() {
+            serde_json::to_string(log.value()).ok()?
+        } else {
+            let fields = attachment
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    log.get((PathPrefix::Event, &field.0))
+                        .map(|value| (field.0.to_string(), value))
+                })
+                .collect::<BTreeMap<_, _>>();
+            if fields.is_empty() {
+                return None;
+            }
+            serde_json::to_string(&fields).ok()?
+        };
+
+        if json.len() > attachment.max_bytes.get() {
+            emit!(LokiAttachmentTooLarge {
+                size: json.len(),
+                max_bytes: attachment.max_bytes.get(),
+            });
+            return None;
+        }
+        Some((attachment.key.clone(), json))
     }
 
     fn remove_label_fields(&self, event: &mut Event) {
         if self.remove_label_fields {
-            for template in self.labels.values() {
+            for template in self
+                .labels
+                .values()
+                .chain(self.structured_metadata.values())
+            {
                 if let Some(fields) = template.get_fields() {
                     for field in fields {
                         event.as_mut_log().remove(field.as_str());
@@ -250,7 +544,13 @@ impl EventEncoder {
         let tenant_id = self.key_partitioner.partition(&event);
         let finalizers = event.take_finalizers();
         let json_byte_size = event.estimated_json_encoded_size_of();
-        let mut labels = self.build_labels(&event);
+        let mut labels = self.isolate_labels(tenant_id.as_ref(), self.build_labels(&event));
+        let mut structured_metadata = self.build_structured_metadata(&event);
+        structured_metadata.extend(self.trace_context(&event));
+        structured_metadata.extend(self.attachment(&event));
+        if !self.limit_label_cardinality(&mut labels, &mut structured_metadata) {
+            return None;
+        }
         self.remove_label_fields(&mut event);
 
         let timestamp = match event.as_log().get_timestamp() {
@@ -266,6 +566,8 @@ impl EventEncoder {
         let mut bytes = BytesMut::new();
         self.encoder.encode(event, &mut bytes).ok();
 
+        self.label_limits.apply(&mut labels);
+
         // If no labels are provided we set our own default
         // `{agent="vector"}` label. This can happen if the only
         // label is a templatable one but the event doesn't match.
@@ -274,6 +576,10 @@ impl EventEncoder {
             labels = vec![("agent".to_string(), "vector".to_string())]
         }
 
+        if let Some(stream_shards) = &self.stream_shards {
+            stream_shards.assign(tenant_id.as_deref(), &mut labels);
+        }
+
         let partition = PartitionKey { tenant_id };
 
         Some(LokiRecord {
@@ -281,12 +587,215 @@ impl EventEncoder {
             event: LokiEvent {
                 timestamp,
                 event: bytes.freeze(),
+                structured_metadata,
             },
             partition,
             finalizers,
             json_byte_size,
+            original: None,
+        })
+    }
+}
+
+/// The limits of the labels of a stream, checked before Loki rejects the whole push request.
+#[derive(Clone, Copy, Debug, Default)]
+struct LabelLimits {
+    max_labels: Option<NonZeroUsize>,
+    max_name_length: Option<NonZeroUsize>,
+    max_value_length: Option<NonZeroUsize>,
+}
+
+impl LabelLimits {
+    /// Drops the labels whose name is too long, truncates the values that are too long, then
+    /// drops the labels past the maximum number of labels, in the order of their names.
+    fn apply(&self, labels: &mut Vec<(String, String)>) {
+        if let Some(max_length) = self.max_name_length {
+            let count = labels.len();
+            labels.retain(|(name, _)| name.len() <= max_length.get());
+            if labels.len() < count {
+                emit!(LokiLabelsLimited {
+                    count: count - labels.len(),
+                    limit: "max_label_name_length",
+                });
+            }
+        }
+
+        if let Some(max_length) = self.max_value_length {
+            let mut count = 0;
+            for (_, value) in labels.iter_mut() {
+                if value.len() > max_length.get() {
+                    truncate_to_char_boundary(value, max_length.get());
+                    count += 1;
+                }
+            }
+            if count > 0 {
+                emit!(LokiLabelsLimited {
+                    count,
+                    limit: "max_label_value_length",
+                });
+            }
+        }
+
+        if let Some(max_labels) = self.max_labels {
+            if labels.len() > max_labels.get() {
+                labels.sort_unstable();
+                emit!(LokiLabelsLimited {
+                    count: labels.len() - max_labels.get(),
+                    limit: "max_labels_per_stream",
+                });
+                labels.truncate(max_labels.get());
+            }
+        }
+    }
+}
+
+/// The distinct values of each label name seen within a sliding window.
+#[derive(Clone, Debug)]
+struct LabelCardinality {
+    limit: usize,
+    window: Duration,
+    action: LabelCardinalityAction,
+    /// The time each value of each label name was last seen.
+    values: HashMap<String, HashMap<String, Instant>>,
+}
+
+impl LabelCardinality {
+    fn new(config: &LabelCardinalityConfig) -> Option<Self> {
+        config.limit.map(|limit| Self {
+            limit: limit.get(),
+            window: Duration::from_secs(config.window_secs.get()),
+            action: config.action,
+            values: HashMap::new(),
         })
     }
+
+    /// Records the value of the label, returning whether it is within the limit.
+    fn admit(&mut self, key: &str, value: &str, now: Instant) -> bool {
+        let values = self.values.entry(key.to_owned()).or_default();
+        if let Some(seen) = values.get_mut(value) {
+            *seen = now;
+            return true;
+        }
+
+        if values.len() >= self.limit {
+            // Values are only expired once the limit is reached.
+            let window = self.window;
+            values.retain(|_, seen| now.saturating_duration_since(*seen) < window);
+            if values.len() >= self.limit {
+                return false;
+            }
+        }
+        values.insert(value.to_owned(), now);
+        true
+    }
+}
+
+/// The streams sharded after Loki rejected their entries for exceeding the per-stream rate limit.
+///
+/// The entries of a sharded stream are spread across its shards by a `__shard__` label, until it
+/// hasn't been rejected for the configured duration.
+#[derive(Clone, Debug)]
+pub(super) struct StreamShards {
+    shards: u32,
+    duration: Duration,
+    /// The time until which each stream is sharded, keyed by the stream key of its labels.
+    sharded: Arc<Mutex<HashMap<String, Instant>>>,
+    next: Arc<AtomicU32>,
+}
+
+impl StreamShards {
+    pub(super) const LABEL: &'static str = "__shard__";
+
+    fn new(config: &StreamShardingConfig) -> Self {
+        Self {
+            shards: config.shards.get(),
+            duration: Duration::from_secs(config.duration_secs.get()),
+            sharded: Arc::default(),
+            next: Arc::default(),
+        }
+    }
+
+    /// Gets the key of the stream of the tenant with the labels, leaving out the shard label.
+    pub(super) fn unsharded_key(tenant_id: Option<&str>, labels: &[(String, String)]) -> String {
+        let mut labels = labels
+            .iter()
+            .filter(|(key, _)| key != Self::LABEL)
+            .cloned()
+            .collect::<Vec<_>>();
+        labels.sort();
+        format!("{}/{}", tenant_id.unwrap_or_default(), stream_key(&labels))
+    }
+
+    /// Shards the streams, or keeps them sharded for longer, returning how many were not sharded.
+    pub(super) fn shard(&self, streams: &[String]) -> usize {
+        let until = Instant::now() + self.duration;
+        let mut sharded = self
+            .sharded
+            .lock()
+            .expect("Stream shards mutex is poisoned");
+        streams
+            .iter()
+            .filter(|stream| sharded.insert((*stream).clone(), until).is_none())
+            .count()
+    }
+
+    /// Adds the shard label to the labels of a sharded stream, rotating across its shards.
+    fn assign(&self, tenant_id: Option<&str>, labels: &mut Labels) {
+        let key = Self::unsharded_key(tenant_id, labels);
+        let mut sharded = self
+            .sharded
+            .lock()
+            .expect("Stream shards mutex is poisoned");
+        match sharded.get(&key) {
+            Some(until) if *until > Instant::now() => {
+                let shard = self.next.fetch_add(1, Ordering::Relaxed) % self.shards;
+                labels.push((Self::LABEL.to_owned(), shard.to_string()));
+            }
+            Some(_) => {
+                sharded.remove(&key);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Keys the per-stream delivery metrics by the values of a few labels of the streams.
+#[derive(Clone, Debug, Default)]
+struct StreamMetrics {
+    label_names: Vec<String>,
+}
+
+impl StreamMetrics {
+    /// Gets the selector of the stream with the labels, such as `{app="api"}`, if the metrics are
+    /// enabled.
+    fn key(&self, labels: &[(String, String)]) -> Option<String> {
+        if self.label_names.is_empty() {
+            return None;
+        }
+        let selector = self
+            .label_names
+            .iter()
+            .filter_map(|name| {
+                labels
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| format!("{}={:?}", name, value))
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        Some(format!("{{{}}}", selector))
+    }
+
+    /// Counts the records of each stream.
+    fn count(&self, records: &[LokiRecord]) -> Vec<(String, usize)> {
+        let mut counts = BTreeMap::<String, usize>::new();
+        for record in records {
+            if let Some(key) = self.key(&record.labels) {
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+        counts.into_iter().collect()
+    }
 }
 
 struct FilteredRecord {
@@ -325,7 +834,7 @@ impl ByteSizeOf for FilteredRecord {
 }
 
 struct RecordFilter {
-    timestamps: HashMap<PartitionKey, i64>,
+    timestamps: HashMap<(PartitionKey, Labels), i64>,
     out_of_order_action: OutOfOrderAction,
 }
 
@@ -339,12 +848,23 @@ impl RecordFilter {
 }
 
 impl RecordFilter {
+    fn stream_key(&self, record: &LokiRecord) -> (PartitionKey, Labels) {
+        // Reordered streams are released independently of each other, so only the events of the
+        // same stream are compared.
+        let labels = match self.out_of_order_action {
+            OutOfOrderAction::Reorder => record.labels.clone(),
+            _ => Labels::new(),
+        };
+        (record.partition.clone(), labels)
+    }
+
     pub fn filter_record(&mut self, mut record: LokiRecord) -> Option<FilteredRecord> {
-        if let Some(latest) = self.timestamps.get_mut(&record.partition) {
+        let key = self.stream_key(&record);
+        if let Some(latest) = self.timestamps.get_mut(&key) {
             if record.event.timestamp < *latest {
                 match self.out_of_order_action {
                     OutOfOrderAction::Drop => None,
-                    OutOfOrderAction::RewriteTimestamp => {
+                    OutOfOrderAction::RewriteTimestamp | OutOfOrderAction::Reorder => {
                         record.event.timestamp = *latest;
                         Some(FilteredRecord::rewritten(record))
                     }
@@ -355,8 +875,7 @@ impl RecordFilter {
                 Some(FilteredRecord::valid(record))
             }
         } else {
-            self.timestamps
-                .insert(record.partition.clone(), record.event.timestamp);
+            self.timestamps.insert(key, record.event.timestamp);
             Some(FilteredRecord::valid(record))
         }
     }
@@ -367,14 +886,23 @@ pub struct LokiSink {
     pub(super) encoder: EventEncoder,
     batch_settings: BatcherSettings,
     out_of_order_action: OutOfOrderAction,
+    reorder_window: Duration,
+    reorder_max_bytes: usize,
+    encoding_concurrency: Option<NonZeroUsize>,
     service: Svc<LokiService, LokiRetryLogic>,
     protocol: &'static str,
+    stream_metrics: StreamMetrics,
+    keep_dead_letters: bool,
 }
 
 impl LokiSink {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
     pub fn new(config: LokiConfig, client: HttpClient) -> crate::Result<Self> {
         let compression = config.compression;
+        let stream_shards = config
+            .stream_sharding
+            .enabled
+            .then(|| StreamShards::new(&config.stream_sharding));
 
         // if Vector is configured to allow events with out of order timestamps, then we can
         // safely enable concurrency settings.
@@ -386,50 +914,99 @@ impl LokiSink {
         // of 1 for now.
         let request_limits = match config.out_of_order_action {
             OutOfOrderAction::Accept => config.request.unwrap_with(&Default::default()),
-            OutOfOrderAction::Drop | OutOfOrderAction::RewriteTimestamp => {
+            OutOfOrderAction::Drop
+            | OutOfOrderAction::RewriteTimestamp
+            | OutOfOrderAction::Reorder => {
                 let mut settings = config.request.unwrap_with(&Default::default());
                 settings.concurrency = Some(1);
                 settings
             }
         };
 
-        let protocol = get_http_scheme_from_uri(&config.endpoint.uri);
+        let (protocol, transport) = match config.protocol {
+            LokiProtocol::Http => {
+                let path = match config.endpoint_format {
+                    LokiEndpointFormat::Otlp if config.path == default_loki_path() => {
+                        OTLP_PATH.to_owned()
+                    }
+                    _ => config.path,
+                };
+                (
+                    get_http_scheme_from_uri(&config.endpoint.uri),
+                    LokiTransport::http(client, config.endpoint, path, config.auth)?,
+                )
+            }
+            LokiProtocol::Grpc => (
+                "grpc",
+                LokiTransport::grpc(config.build_channel()?, config.auth.as_ref()),
+            ),
+        };
         let service = tower::ServiceBuilder::new()
             .settings(request_limits, LokiRetryLogic)
             .service(LokiService::new(
-                client,
-                config.endpoint,
-                config.path,
-                config.auth,
-            )?);
+                transport,
+                stream_shards.clone(),
+                TenantRateLimiter::new(&config.tenant_rate_limit),
+                config.dead_letter.clone(),
+            ));
 
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
+        let encoding_concurrency = config.encoding_concurrency;
         let encoder = Encoder::<()>::new(serializer);
-        let batch_encoder = match config.compression {
-            CompressionConfigAdapter::Original(_) => LokiBatchEncoder(LokiBatchEncoding::Json),
-            CompressionConfigAdapter::Extended(ExtendedCompression::Snappy) => {
-                LokiBatchEncoder(LokiBatchEncoding::Protobuf)
+        let batch_encoder = match (config.protocol, config.compression) {
+            (LokiProtocol::Grpc, _) => LokiBatchEncoder(LokiBatchEncoding::Grpc),
+            (LokiProtocol::Http, _) if config.endpoint_format == LokiEndpointFormat::Otlp => {
+                LokiBatchEncoder(LokiBatchEncoding::Otlp)
+            }
+            (LokiProtocol::Http, CompressionConfigAdapter::Original(_)) => {
+                LokiBatchEncoder(LokiBatchEncoding::Json)
             }
+            (
+                LokiProtocol::Http,
+                CompressionConfigAdapter::Extended(ExtendedCompression::Snappy),
+            ) => LokiBatchEncoder(LokiBatchEncoding::Protobuf),
+        };
+        let stream_metrics = StreamMetrics {
+            label_names: config.stream_metrics_labels,
         };
 
         Ok(Self {
             request_builder: LokiRequestBuilder {
                 compression,
                 encoder: batch_encoder,
+                stream_sharding: stream_shards.is_some(),
+                stream_metrics: stream_metrics.clone(),
             },
             encoder: EventEncoder {
                 key_partitioner: KeyPartitioner::new(config.tenant_id),
                 transformer,
                 encoder,
                 labels: config.labels,
+                structured_metadata: config.structured_metadata,
+                label_normalization: config.label_normalization,
+                tenant_labels: config.tenant_labels,
+                label_cardinality: LabelCardinality::new(&config.max_label_cardinality),
+                label_limits: LabelLimits {
+                    max_labels: config.max_labels_per_stream,
+                    max_name_length: config.max_label_name_length,
+                    max_value_length: config.max_label_value_length,
+                },
+                trace_correlation: config.trace_correlation,
+                attachment: config.attachment,
                 remove_label_fields: config.remove_label_fields,
                 remove_timestamp: config.remove_timestamp,
+                stream_shards,
             },
             batch_settings: config.batch.into_batcher_settings()?,
             out_of_order_action: config.out_of_order_action,
+            reorder_window: Duration::from_secs(config.reorder_window_secs.get()),
+            reorder_max_bytes: config.reorder_max_bytes.get(),
+            encoding_concurrency,
             service,
             protocol,
+            stream_metrics,
+            keep_dead_letters: config.dead_letter.is_some(),
         })
     }
 
@@ -438,18 +1015,53 @@ impl LokiSink {
         let mut filter = RecordFilter::new(self.out_of_order_action);
 
         // out_of_order_action's that require a complete ordering are limited to building 1 request
-        // at a time
-        let request_builder_concurrency = match self.out_of_order_action {
-            OutOfOrderAction::Accept => NonZeroUsize::new(50).expect("static"),
-            OutOfOrderAction::Drop | OutOfOrderAction::RewriteTimestamp => {
-                NonZeroUsize::new(1).expect("static")
+        // at a time, unless the encoding concurrency is configured: requests are built in parallel
+        // but still sent in the order of their batches.
+        let request_builder_concurrency =
+            self.encoding_concurrency
+                .unwrap_or_else(|| match self.out_of_order_action {
+                    OutOfOrderAction::Accept => NonZeroUsize::new(50).expect("static"),
+                    OutOfOrderAction::Drop
+                    | OutOfOrderAction::RewriteTimestamp
+                    | OutOfOrderAction::Reorder => NonZeroUsize::new(1).expect("static"),
+                });
+
+        let keep_dead_letters = self.keep_dead_letters;
+        let records = input
+            .map(|event| {
+                let original = keep_dead_letters.then(|| {
+                    let mut original = event.clone();
+                    drop(original.take_finalizers());
+                    original
+                });
+                encoder
+                    .encode_event(event)
+                    .map(|record| LokiRecord { original, ..record })
+            })
+            .filter_map(|event| async { event });
+        let records = match self.out_of_order_action {
+            OutOfOrderAction::Reorder => {
+                reorder_records(records, self.reorder_window, self.reorder_max_bytes).boxed()
             }
+            _ => records.boxed(),
         };
 
-        input
-            .map(|event| encoder.encode_event(event))
-            .filter_map(|event| async { event })
-            .map(|record| filter.filter_record(record))
+        let stream_metrics = self.stream_metrics.clone();
+        let drops = matches!(self.out_of_order_action, OutOfOrderAction::Drop);
+        records
+            .map(|record| {
+                // The stream is only keyed when its records can be dropped, as it is costly.
+                let stream = drops.then(|| stream_metrics.key(&record.labels)).flatten();
+                let filtered = filter.filter_record(record);
+                if let (None, Some(stream)) = (&filtered, stream) {
+                    emit!(LokiStreamEntriesDropped {
+                        stream,
+                        count: 1,
+                        reason: "out_of_order",
+                    });
+                }
+                filtered
+            })
             .batched_partitioned(RecordPartitioner::default(), self.batch_settings)
             .filter_map(|(partition, batch)| async {
                 if let Some(partition) = partition {
@@ -490,6 +1102,87 @@ impl LokiSink {
     }
 }
 
+/// Holds the records of each stream for `window`, then releases them sorted by timestamp.
+///
+/// A stream is held from the arrival of its first record since it was last released, and every
+/// record arriving in the meantime is sorted with it. Once the records held take more than
+/// `max_bytes`, the streams held the longest are released early. Every record is released once
+/// the input ends.
+fn reorder_records<'a>(
+    input: impl Stream<Item = LokiRecord> + Send + 'a,
+    window: Duration,
+    max_bytes: usize,
+) -> impl Stream<Item = LokiRecord> + Send + 'a {
+    fn sorted(mut records: Vec<LokiRecord>) -> impl Iterator<Item = LokiRecord> {
+        records.sort_by_key(|record| record.event.timestamp);
+        records.into_iter()
+    }
+
+    async_stream::stream! {
+        // The records of each stream, with when the stream started being held and their size.
+        let mut streams: HashMap<(PartitionKey, Labels), (Instant, usize, Vec<LokiRecord>)> =
+            HashMap::new();
+        let mut held_bytes = 0;
+        let mut ticks = interval((window / 10).max(Duration::from_millis(10)));
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(input);
+
+        loop {
+            let now = tokio::select! {
+                record = input.next() => match record {
+                    Some(record) => {
+                        let size = record.size_of();
+                        let key = (record.partition.clone(), record.labels.clone());
+                        let stream = streams
+                            .entry(key)
+                            .or_insert_with(|| (Instant::now(), 0, Vec::new()));
+                        stream.1 += size;
+                        stream.2.push(record);
+                        held_bytes += size;
+                        None
+                    }
+                    None => break,
+                },
+                now = ticks.tick() => Some(now),
+            };
+
+            while held_bytes > max_bytes {
+                let oldest = streams
+                    .iter()
+                    .min_by_key(|(_, (since, _, _))| *since)
+                    .map(|(key, _)| key.clone())
+                    .expect("records are held");
+                let (_, size, records) = streams.remove(&oldest).expect("oldest stream");
+                held_bytes -= size;
+                for record in sorted(records) {
+                    yield record;
+                }
+            }
+
+            if let Some(now) = now {
+                let expired = streams
+                    .iter()
+                    .filter(|(_, (since, _, _))| now.duration_since(*since) >= window)
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<_>>();
+                for key in expired {
+                    let (_, size, records) = streams.remove(&key).expect("expired stream");
+                    held_bytes -= size;
+                    for record in sorted(records) {
+                        yield record;
+                    }
+                }
+            }
+        }
+
+        for (_, (_, _, records)) in streams {
+            for record in sorted(records) {
+                yield record;
+            }
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl StreamSink<Event> for LokiSink {
     async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -504,21 +1197,114 @@ fn slugify_text(input: String) -> String {
     result.to_lowercase()
 }
 
+/// Expands an object into dynamic labels, the keys of nested objects being joined with `_`.
+///
+/// key_* with {"a": {"b": "c"}} -> key_a_b = c
+fn flatten_dynamic_labels(
+    prefix: String,
+    object: serde_json::Map<String, serde_json::Value>,
+    labels: &mut Vec<(String, serde_json::Value)>,
+) {
+    for (key, value) in object {
+        let name = format!("{}{}", prefix, key);
+        match value {
+            serde_json::Value::Object(nested) => {
+                flatten_dynamic_labels(format!("{}_", name), nested, labels)
+            }
+            value => {
+                let mut name = slugify_text(name);
+                // Label names can't start with a digit, which keys of expanded objects often do.
+                if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+                    name.insert(0, '_');
+                }
+                labels.push((name, value));
+            }
+        }
+    }
+}
+
+fn normalize_label_name(input: &str) -> String {
+    let mut result = RE.replace_all(input, "_").into_owned();
+    if result.starts_with(|ch: char| ch.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+    result
+}
+
+/// Gets the trace and span IDs of a [W3C `traceparent` header][traceparent].
+///
+/// [traceparent]: https://www.w3.org/TR/trace-context/#traceparent-header
+fn parse_traceparent(traceparent: &str) -> Option<(&str, &str)> {
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len
+            && s.chars().all(|ch| ch.is_ascii_hexdigit())
+            && s.chars().any(|ch| ch != '0')
+    };
+
+    let mut parts = traceparent.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+    // Later versions may append fields, but the ones known so far can't change.
+    if version.len() != 2
+        || version == "ff"
+        || (version == "00" && parts.next().is_some())
+        || flags.len() != 2
+        || !is_hex(trace_id, 32)
+        || !is_hex(span_id, 16)
+    {
+        return None;
+    }
+    Some((trace_id, span_id))
+}
+
+fn truncate_to_char_boundary(input: &mut String, max_length: usize) {
+    if input.len() > max_length {
+        let mut end = max_length;
+        while !input.is_char_boundary(end) {
+            end -= 1;
+        }
+        input.truncate(end);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
         collections::{BTreeMap, HashMap},
         convert::TryFrom,
+        num::{NonZeroU64, NonZeroUsize},
+        time::Duration,
     };
 
     use codecs::JsonSerializerConfig;
     use futures::stream::StreamExt;
-    use vector_core::event::{Event, LogEvent, Value};
+    use lookup::lookup_v2::ConfigValuePath;
+    use tokio::time::Instant;
+    use vector_core::{
+        event::{Event, LogEvent, Value},
+        ByteSizeOf,
+    };
 
-    use super::{EventEncoder, KeyPartitioner, RecordFilter};
+    use super::{
+        parse_traceparent, reorder_records, EventEncoder, KeyPartitioner, LabelCardinality,
+        LokiRequestBuilder, RecordFilter, StreamMetrics, StreamShards,
+    };
     use crate::{
-        codecs::Encoder, config::log_schema, sinks::loki::config::OutOfOrderAction,
-        template::Template, test_util::random_lines,
+        codecs::{Encoder, Transformer},
+        config::log_schema,
+        sinks::loki::{
+            config::{
+                AttachmentConfig, CompressionConfigAdapter, DisallowedLabelAction,
+                LabelCardinalityAction, LabelCardinalityConfig, LabelNormalizationConfig,
+                OutOfOrderAction, StreamShardingConfig, TenantLabelsConfig, TraceCorrelationConfig,
+            },
+            event::{LokiBatchEncoder, LokiBatchEncoding, LokiEvent, LokiRecord, PartitionKey},
+        },
+        sinks::util::{Compression, RequestBuilder},
+        template::Template,
+        test_util::random_lines,
     };
 
     #[test]
@@ -528,8 +1314,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            stream_shards: None,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -574,8 +1368,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            stream_shards: None,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -627,8 +1429,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            stream_shards: None,
         };
 
         let message = r###"
@@ -660,6 +1470,63 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encoder_with_nested_dynamic_labels() -> Result<(), serde_json::Error> {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("k8s_*").unwrap(),
+            Template::try_from("{{ kubernetes }}").unwrap(),
+        );
+        labels.insert(
+            Template::try_from("*").unwrap(),
+            Template::try_from("{{ zones }}").unwrap(),
+        );
+
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
+            remove_label_fields: false,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+
+        let message = r###"
+        {
+        	"kubernetes": {
+        		"pod_labels": {
+        			"app.kubernetes.io/name": "web-server"
+        		},
+        		"pod_namespace": "shop"
+        	},
+        	"zones": {
+        		"1a": "primary"
+        	}
+        }
+        "###;
+        let msg: BTreeMap<String, Value> = serde_json::from_str(message)?;
+        let event = Event::Log(LogEvent::from(msg));
+        let record = encoder.encode_event(event).unwrap();
+
+        assert_eq!(record.labels.len(), 3);
+        let labels: HashMap<String, String> = record.labels.into_iter().collect();
+        assert_eq!(
+            labels["k8s_pod_labels_app_kubernetes_io_name"],
+            "web-server".to_string()
+        );
+        assert_eq!(labels["k8s_pod_namespace"], "shop".to_string());
+        assert_eq!(labels["_1a"], "primary".to_string());
+        Ok(())
+    }
+
     #[test]
     fn encoder_with_colliding_dynamic_labels() -> Result<(), serde_json::Error> {
         let mut labels = HashMap::default();
@@ -677,8 +1544,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            stream_shards: None,
         };
 
         let message = r###"
@@ -715,8 +1590,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            stream_shards: None,
         };
 
         let msg: BTreeMap<String, Value> = serde_json::from_str("{}")?;
@@ -729,6 +1612,503 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encoder_with_label_normalization() {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("{{ name }}").unwrap(),
+            Template::try_from("{{ value }}").unwrap(),
+        );
+        labels.insert(
+            Template::try_from("empty").unwrap(),
+            Template::try_from("{{ empty }}").unwrap(),
+        );
+        labels.insert(
+            Template::try_from("{{ digit }}").unwrap(),
+            Template::try_from("ok").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: LabelNormalizationConfig {
+                replace_invalid_characters: true,
+                max_value_length: NonZeroUsize::new(2),
+                drop_empty_values: true,
+            },
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
+            remove_label_fields: false,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        let log = event.as_mut_log();
+        log.insert("name", "service.name-x");
+        log.insert("value", "héllo");
+        log.insert("empty", "");
+        log.insert("digit", "1st");
+
+        let record = encoder.encode_event(event).unwrap();
+        let labels: HashMap<String, String> = record.labels.into_iter().collect();
+        assert_eq!(labels.len(), 2);
+        // `é` is two bytes wide, so truncating at two bytes must not split it.
+        assert_eq!(labels["service_name_x"], "h".to_string());
+        assert_eq!(labels["_1st"], "ok".to_string());
+    }
+
+    #[test]
+    fn label_normalization_keeps_first_colliding_label() {
+        let normalization = LabelNormalizationConfig {
+            replace_invalid_characters: true,
+            max_value_length: None,
+            drop_empty_values: false,
+        };
+        let encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            label_normalization: normalization,
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
+            remove_label_fields: false,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+
+        // `service-name` sorts before `service.name`, whichever order the map yields them in.
+        for _ in 0..16 {
+            let labels = [
+                ("service.name".to_owned(), "dotted".to_owned()),
+                ("service-name".to_owned(), "dashed".to_owned()),
+            ]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+            assert_eq!(
+                encoder.normalize_labels(labels),
+                vec![("service_name".to_owned(), "dashed".to_owned())]
+            );
+        }
+    }
+
+    #[test]
+    fn stream_metrics_key() {
+        let labels = vec![
+            ("namespace".to_owned(), "prod".to_owned()),
+            ("app".to_owned(), "api".to_owned()),
+            ("pod".to_owned(), "api-7d9f".to_owned()),
+        ];
+
+        assert_eq!(StreamMetrics::default().key(&labels), None);
+
+        let metrics = StreamMetrics {
+            label_names: vec!["app".to_owned(), "namespace".to_owned(), "team".to_owned()],
+        };
+        assert_eq!(
+            metrics.key(&labels),
+            Some(r#"{app="api",namespace="prod"}"#.to_owned())
+        );
+        assert_eq!(metrics.key(&[]), Some("{}".to_owned()));
+    }
+
+    #[test]
+    fn label_limits() {
+        let label = |name: &str, value: &str| (name.to_string(), value.to_string());
+        let mut labels = vec![
+            label("zone", "eu"),
+            label("app", "api"),
+            label("a_label_with_a_long_name", "x"),
+            label("pod", "api-7d9f8"),
+            label("host", "node-1"),
+        ];
+
+        LabelLimits {
+            max_labels: NonZeroUsize::new(3),
+            max_name_length: NonZeroUsize::new(8),
+            max_value_length: NonZeroUsize::new(5),
+        }
+        .apply(&mut labels);
+
+        assert_eq!(
+            labels,
+            vec![
+                label("app", "api"),
+                label("host", "node-"),
+                label("pod", "api-7"),
+            ]
+        );
+    }
+
+    #[test]
+    fn encoder_with_tenant_labels() {
+        let mut labels = HashMap::default();
+        for name in ["app", "pod_labels_name", "stray"] {
+            labels.insert(
+                Template::try_from(name).unwrap(),
+                Template::try_from("{{ value }}").unwrap(),
+            );
+        }
+        let mut tenant_labels = HashMap::default();
+        tenant_labels.insert(
+            "team_a".to_string(),
+            TenantLabelsConfig {
+                allowed_labels: vec!["app".to_string(), "pod_labels_*".to_string()],
+                ..Default::default()
+            },
+        );
+        tenant_labels.insert(
+            "team_b".to_string(),
+            TenantLabelsConfig {
+                allowed_labels: vec!["app".to_string()],
+                disallowed_labels: DisallowedLabelAction::Rename,
+                label_prefix: Some("team_b_".to_string()),
+            },
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(Some(Template::try_from("{{ tenant }}").unwrap())),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels,
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
+            remove_label_fields: false,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+        let mut encode = |tenant: &str| {
+            let mut event = Event::Log(LogEvent::from("hello world"));
+            let log = event.as_mut_log();
+            log.insert("tenant", tenant);
+            log.insert("value", "x");
+            let record = encoder.encode_event(event).unwrap();
+            let mut labels = record
+                .labels
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>();
+            labels.sort();
+            labels
+        };
+
+        assert_eq!(encode("team_a"), vec!["app", "pod_labels_name"]);
+        assert_eq!(
+            encode("team_b"),
+            vec!["app", "team_b_pod_labels_name", "team_b_stray"]
+        );
+        assert_eq!(encode("team_c"), vec!["app", "pod_labels_name", "stray"]);
+    }
+
+    #[test]
+    fn encoder_with_label_cardinality() {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("app").unwrap(),
+            Template::try_from("api").unwrap(),
+        );
+        labels.insert(
+            Template::try_from("pod").unwrap(),
+            Template::try_from("{{ pod }}").unwrap(),
+        );
+        let encoder = |action| EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels: labels.clone(),
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: LabelCardinality::new(&LabelCardinalityConfig {
+                limit: NonZeroUsize::new(2),
+                action,
+                ..Default::default()
+            }),
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
+            remove_label_fields: false,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+        let event = |pod: &str| {
+            let mut event = Event::Log(LogEvent::from("hello world"));
+            event.as_mut_log().insert("pod", pod);
+            event
+        };
+
+        let mut encoder_drop_event = encoder(LabelCardinalityAction::DropEvent);
+        assert!(encoder_drop_event.encode_event(event("a")).is_some());
+        assert!(encoder_drop_event.encode_event(event("b")).is_some());
+        assert!(encoder_drop_event.encode_event(event("c")).is_none());
+        // Values within the limit are still admitted.
+        assert!(encoder_drop_event.encode_event(event("a")).is_some());
+
+        let mut encoder = encoder(LabelCardinalityAction::StructuredMetadata);
+        encoder.encode_event(event("a")).unwrap();
+        encoder.encode_event(event("b")).unwrap();
+        let record = encoder.encode_event(event("c")).unwrap();
+        assert_eq!(record.labels, vec![("app".to_string(), "api".to_string())]);
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![("pod".to_string(), "c".to_string())]
+        );
+    }
+
+    #[test]
+    fn label_cardinality_expires_values() {
+        let mut cardinality = LabelCardinality::new(&LabelCardinalityConfig {
+            limit: NonZeroUsize::new(1),
+            window_secs: NonZeroU64::new(60).unwrap(),
+            action: LabelCardinalityAction::DropLabel,
+        })
+        .unwrap();
+        let now = Instant::now();
+
+        assert!(cardinality.admit("pod", "a", now));
+        assert!(!cardinality.admit("pod", "b", now + Duration::from_secs(30)));
+        assert!(cardinality.admit("node", "b", now + Duration::from_secs(30)));
+        // Seen again, so it stays in the window.
+        assert!(cardinality.admit("pod", "a", now + Duration::from_secs(50)));
+        assert!(!cardinality.admit("pod", "b", now + Duration::from_secs(100)));
+        assert!(cardinality.admit("pod", "b", now + Duration::from_secs(110)));
+    }
+
+    #[test]
+    fn encoder_with_trace_correlation() {
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: TraceCorrelationConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            attachment: Default::default(),
+            remove_label_fields: false,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("trace_id", "abc");
+        event.as_mut_log().insert("span_id", "def");
+        let record = encoder.encode_event(event).unwrap();
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![
+                ("trace_id".to_string(), "abc".to_string()),
+                ("span_id".to_string(), "def".to_string()),
+            ]
+        );
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        );
+        let record = encoder.encode_event(event).unwrap();
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![
+                (
+                    "trace_id".to_string(),
+                    "4bf92f3577b34da6a3ce929d0e0e4736".to_string()
+                ),
+                ("span_id".to_string(), "00f067aa0ba902b7".to_string()),
+            ]
+        );
+
+        let record = encoder
+            .encode_event(Event::Log(LogEvent::from("hello world")))
+            .unwrap();
+        assert!(record.event.structured_metadata.is_empty());
+    }
+
+    #[test]
+    fn encoder_with_structured_metadata() {
+        let mut structured_metadata = HashMap::default();
+        structured_metadata.insert(
+            Template::try_from("request_id").unwrap(),
+            Template::try_from("{{ request_id }}").unwrap(),
+        );
+        structured_metadata.insert(
+            Template::try_from("user_id").unwrap(),
+            Template::try_from("{{ user_id }}").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels: HashMap::default(),
+            structured_metadata,
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: TraceCorrelationConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            attachment: Default::default(),
+            remove_label_fields: true,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("request_id", "r-42");
+        event.as_mut_log().insert("user_id", "");
+        event.as_mut_log().insert("trace_id", "abc");
+        let record = encoder.encode_event(event).unwrap();
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![
+                ("request_id".to_string(), "r-42".to_string()),
+                ("trace_id".to_string(), "abc".to_string()),
+            ]
+        );
+        let body = String::from_utf8_lossy(&record.event.event);
+        assert!(!body.contains("request_id"));
+        assert!(body.contains("trace_id"));
+    }
+
+    #[test]
+    fn encoder_with_attachment() {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("app").unwrap(),
+            Template::try_from("{{ app }}").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Transformer::new(None, Some(vec!["secret".into()]), None).unwrap(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: AttachmentConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            remove_label_fields: true,
+            remove_timestamp: false,
+            stream_shards: None,
+        };
+        let event = || {
+            let mut event = Event::Log(LogEvent::from("hello world"));
+            event.as_mut_log().insert("app", "web");
+            event.as_mut_log().insert("request.path", "/");
+            event.as_mut_log().insert("secret", "s3cr3t");
+            event
+        };
+        let path = |path: &str| ConfigValuePath::try_from(path.to_owned()).unwrap();
+
+        // The whole event is attached, before the label fields are removed and the transformer
+        // applied to it.
+        let record = encoder.encode_event(event()).unwrap();
+        let body = String::from_utf8_lossy(&record.event.event);
+        assert!(!body.contains("app"));
+        assert!(!body.contains("secret"));
+        assert_eq!(record.event.structured_metadata.len(), 1);
+        let (key, json) = &record.event.structured_metadata[0];
+        assert_eq!(key, "original");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(json).unwrap(),
+            serde_json::json!({
+                "app": "web",
+                "message": "hello world",
+                "request": {"path": "/"},
+                "secret": "s3cr3t",
+            })
+        );
+
+        // The attachment is sent as structured metadata by the JSON encoding too.
+        let mut body = Vec::new();
+        crate::sinks::util::encoding::Encoder::encode_input(
+            &LokiBatchEncoder(LokiBatchEncoding::Json),
+            vec![record],
+            &mut body,
+        )
+        .unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(
+            body["streams"][0]["values"][0][2]["original"],
+            serde_json::Value::String(json.clone())
+        );
+
+        encoder.attachment.key = "request".to_owned();
+        encoder.attachment.fields = vec![path("request"), path("user.id")];
+        let record = encoder.encode_event(event()).unwrap();
+        assert_eq!(
+            record.event.structured_metadata,
+            vec![(
+                "request".to_owned(),
+                r#"{"request":{"path":"/"}}"#.to_owned()
+            )]
+        );
+
+        // Events without any of the fields get no attachment.
+        encoder.attachment.fields = vec![path("user.id")];
+        let record = encoder.encode_event(event()).unwrap();
+        assert!(record.event.structured_metadata.is_empty());
+
+        // Neither do events whose attachment is too large.
+        encoder.attachment.fields = Vec::new();
+        encoder.attachment.max_bytes = NonZeroUsize::new(16).unwrap();
+        let record = encoder.encode_event(event()).unwrap();
+        assert!(record.event.structured_metadata.is_empty());
+    }
+
+    #[test]
+    fn parse_traceparents() {
+        assert_eq!(
+            parse_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            Some(("4bf92f3577b34da6a3ce929d0e0e4736", "00f067aa0ba902b7"))
+        );
+        assert_eq!(
+            parse_traceparent("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"),
+            Some(("4bf92f3577b34da6a3ce929d0e0e4736", "00f067aa0ba902b7"))
+        );
+
+        assert_eq!(
+            parse_traceparent("00-00000000000000000000000000000000-00f067aa0ba902b7-01"),
+            None
+        );
+        assert_eq!(
+            parse_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"),
+            None
+        );
+        assert_eq!(
+            parse_traceparent("ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            None
+        );
+        assert_eq!(parse_traceparent("not a traceparent"), None);
+    }
+
     #[test]
     fn encoder_no_ts() {
         let mut encoder = EventEncoder {
@@ -736,8 +2116,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: true,
+            stream_shards: None,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -769,8 +2157,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: true,
             remove_timestamp: false,
+            stream_shards: None,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
         let log = event.as_mut_log();
@@ -794,8 +2190,16 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
+            label_normalization: Default::default(),
+            tenant_labels: HashMap::default(),
+            label_cardinality: None,
+            label_limits: Default::default(),
+            trace_correlation: Default::default(),
+            attachment: Default::default(),
             remove_label_fields: false,
             remove_timestamp: false,
+            stream_shards: None,
         };
         let base = chrono::Utc::now();
         let events = random_lines(100)
@@ -834,4 +2238,244 @@ mod tests {
         }
         assert_eq!(result.len(), 17);
     }
+
+    fn record(stream: &str, timestamp: i64) -> LokiRecord {
+        LokiRecord {
+            partition: PartitionKey { tenant_id: None },
+            labels: vec![("stream".to_string(), stream.to_string())],
+            event: LokiEvent {
+                timestamp,
+                event: format!("{}-{}", stream, timestamp).into(),
+                structured_metadata: Vec::new(),
+            },
+            json_byte_size: vector_common::json_size::JsonSize::new(0),
+            finalizers: Default::default(),
+            original: None,
+        }
+    }
+
+    #[test]
+    fn request_carries_dead_letters() {
+        let request_builder = LokiRequestBuilder {
+            compression: CompressionConfigAdapter::Original(Compression::None),
+            encoder: LokiBatchEncoder(LokiBatchEncoding::Json),
+            stream_sharding: false,
+            stream_metrics: StreamMetrics::default(),
+        };
+        let mut kept = record("a", 1);
+        kept.original = Some(Event::Log(LogEvent::from("a-1")));
+
+        let ((_, _, _, _, dead_letters), _, records) = request_builder
+            .split_input((PartitionKey { tenant_id: None }, vec![kept, record("a", 2)]));
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|record| record.original.is_none()));
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].as_log()["message"], "a-1".into());
+    }
+
+    #[test]
+    fn record_size_counts_dead_letter() {
+        let original = Event::Log(LogEvent::from("a-1"));
+        let mut kept = record("a", 1);
+        kept.original = Some(original.clone());
+
+        assert_eq!(
+            kept.allocated_bytes(),
+            record("a", 1).allocated_bytes() + Some(original).allocated_bytes()
+        );
+    }
+
+    #[test]
+    fn encodes_batch_one_stream_at_a_time() {
+        use prost::Message;
+
+        let records = || {
+            vec![
+                record("b", 2),
+                record("a", 3),
+                record("b", 1),
+                record("a", 1),
+            ]
+        };
+        let encode = |encoding| {
+            let mut body = Vec::new();
+            crate::sinks::util::encoding::Encoder::encode_input(
+                &LokiBatchEncoder(encoding),
+                records(),
+                &mut body,
+            )
+            .unwrap();
+            body
+        };
+
+        let request = loki_logproto::logproto::PushRequest::decode(
+            encode(LokiBatchEncoding::Grpc).as_slice(),
+        )
+        .unwrap();
+        let streams = request
+            .streams
+            .iter()
+            .map(|stream| {
+                let lines = stream
+                    .entries
+                    .iter()
+                    .map(|entry| entry.line.as_str())
+                    .collect::<Vec<_>>();
+                (stream.labels.as_str(), lines)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            streams,
+            vec![
+                (r#"{stream="a"}"#, vec!["a-1", "a-3"]),
+                (r#"{stream="b"}"#, vec!["b-1", "b-2"]),
+            ]
+        );
+
+        let request =
+            opentelemetry_proto::proto::collector::logs::v1::ExportLogsServiceRequest::decode(
+                encode(LokiBatchEncoding::Otlp).as_slice(),
+            )
+            .unwrap();
+        assert_eq!(request.resource_logs.len(), 2);
+        assert_eq!(request.resource_logs[1].scope_logs[0].log_records.len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reorder_records_sorts_each_stream() {
+        let window = Duration::from_secs(5);
+        let records = vec![
+            record("a", 3),
+            record("b", 2),
+            record("a", 1),
+            record("a", 2),
+        ];
+        let input = futures::stream::iter(records).chain(futures::stream::pending());
+        let start = tokio::time::Instant::now();
+        let stream = reorder_records(input, window, usize::MAX);
+        tokio::pin!(stream);
+
+        let mut released = Vec::new();
+        for _ in 0..4 {
+            let record = stream.next().await.unwrap();
+            assert!(start.elapsed() >= window);
+            released.push((record.labels[0].1.clone(), record.event.timestamp));
+        }
+        let stream_a = released
+            .iter()
+            .filter(|(stream, _)| stream == "a")
+            .map(|(_, timestamp)| *timestamp)
+            .collect::<Vec<_>>();
+        assert_eq!(stream_a, vec![1, 2, 3]);
+        assert!(released.contains(&("b".to_string(), 2)));
+    }
+
+    #[tokio::test]
+    async fn reorder_records_flushes_at_end_of_input() {
+        let records = vec![record("a", 2), record("a", 1)];
+        let released = reorder_records(
+            futures::stream::iter(records),
+            Duration::from_secs(60),
+            usize::MAX,
+        )
+        .map(|record| record.event.timestamp)
+        .collect::<Vec<_>>()
+        .await;
+        assert_eq!(released, vec![1, 2]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reorder_records_releases_oldest_stream_over_max_bytes() {
+        let window = Duration::from_secs(60);
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let stream = reorder_records(rx, window, record("a", 1).size_of() * 2);
+        tokio::pin!(stream);
+
+        tx.unbounded_send(record("a", 2)).unwrap();
+        assert!(futures::poll!(stream.next()).is_pending());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        tx.unbounded_send(record("b", 1)).unwrap();
+        assert!(futures::poll!(stream.next()).is_pending());
+        tokio::time::advance(Duration::from_secs(1)).await;
+
+        // The third record takes the held records over the limit, so the oldest stream is
+        // released without waiting for its window to end.
+        let start = tokio::time::Instant::now();
+        tx.unbounded_send(record("a", 1)).unwrap();
+        let released = stream
+            .as_mut()
+            .take(2)
+            .map(|record| (record.labels[0].1.clone(), record.event.timestamp))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(released, vec![("a".to_string(), 1), ("a".to_string(), 2)]);
+
+        drop(tx);
+        let rest = stream
+            .map(|record| (record.labels[0].1.clone(), record.event.timestamp))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(rest, vec![("b".to_string(), 1)]);
+    }
+
+    #[test]
+    fn filter_reorder_compares_streams_separately() {
+        let mut filter = RecordFilter::new(OutOfOrderAction::Reorder);
+        assert!(!filter.filter_record(record("a", 5)).unwrap().rewritten);
+        assert!(!filter.filter_record(record("b", 3)).unwrap().rewritten);
+
+        let late = filter.filter_record(record("a", 4)).unwrap();
+        assert!(late.rewritten);
+        assert_eq!(late.inner.event.timestamp, 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stream_shards_rotate_while_sharded() {
+        let shards = StreamShards::new(&StreamShardingConfig {
+            enabled: true,
+            shards: std::num::NonZeroU32::new(2).unwrap(),
+            duration_secs: std::num::NonZeroU64::new(60).unwrap(),
+        });
+        let labels = vec![
+            ("app".to_string(), "checkout".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ];
+        let other = vec![("app".to_string(), "search".to_string())];
+        let assign = |labels: &Vec<(String, String)>| {
+            let mut labels = labels.clone();
+            shards.assign(None, &mut labels);
+            labels
+                .into_iter()
+                .find(|(key, _)| key == StreamShards::LABEL)
+                .map(|(_, shard)| shard)
+        };
+
+        assert_eq!(assign(&labels), None);
+        let mut reversed = labels.clone();
+        reversed.reverse();
+        assert_eq!(
+            shards.shard(&[StreamShards::unsharded_key(None, &reversed)]),
+            1
+        );
+        assert_eq!(
+            shards.shard(&[StreamShards::unsharded_key(None, &labels)]),
+            0
+        );
+
+        let assigned = (0..4).map(|_| assign(&labels)).collect::<Vec<_>>();
+        assert_eq!(
+            assigned,
+            vec![
+                Some("0".to_string()),
+                Some("1".to_string()),
+                Some("0".to_string()),
+                Some("1".to_string())
+            ]
+        );
+        assert_eq!(assign(&other), None);
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        assert_eq!(assign(&labels), None);
+    }
 }
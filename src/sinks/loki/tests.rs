@@ -146,3 +146,137 @@ async fn healthcheck_grafana_cloud() {
         .await
         .expect("healthcheck failed");
 }
+
+#[tokio::test]
+async fn sends_compression_headers() {
+    for (compression, content_type, content_encoding) in [
+        ("snappy", "application/x-protobuf", Some("snappy")),
+        ("gzip", "application/json", Some("gzip")),
+        ("zstd", "application/json", Some("zstd")),
+        ("none", "application/json", None),
+    ] {
+        let addr = test_util::next_addr();
+        let (rx, trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let (config, cx) = load_sink::<LokiConfig>(&format!(
+            r#"
+                endpoint = "http://{}"
+                labels = {{test_name = "placeholder"}}
+                encoding.codec = "json"
+                compression = "{}"
+            "#,
+            addr, compression
+        ))
+        .unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        sink.run_events(vec![Event::Log(LogEvent::from("hello"))])
+            .await
+            .unwrap();
+        drop(trigger);
+
+        let requests = rx.collect::<Vec<_>>().await;
+        assert_eq!(requests.len(), 1, "compression {}", compression);
+        let (parts, body) = &requests[0];
+        assert_eq!(parts.headers["content-type"], content_type);
+        assert_eq!(
+            parts
+                .headers
+                .get("content-encoding")
+                .map(|value| value.to_str().unwrap()),
+            content_encoding,
+        );
+
+        if compression == "gzip" {
+            let mut json = String::new();
+            std::io::Read::read_to_string(
+                &mut flate2::read::MultiGzDecoder::new(&body[..]),
+                &mut json,
+            )
+            .unwrap();
+            assert!(json.contains(r#""test_name":"placeholder""#));
+        }
+    }
+}
+
+#[tokio::test]
+async fn sends_otlp_requests() {
+    use opentelemetry_proto::proto::{
+        collector::logs::v1::ExportLogsServiceRequest, common::v1::any_value,
+    };
+    use prost::Message;
+
+    let addr = test_util::next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let (config, cx) = load_sink::<LokiConfig>(&format!(
+        r#"
+            endpoint = "http://{}"
+            endpoint_format = "otlp"
+            labels = {{"service.name" = "api"}}
+            encoding.codec = "text"
+        "#,
+        addr
+    ))
+    .unwrap();
+    let (sink, _) = config.build(cx).await.unwrap();
+    sink.run_events(vec![Event::Log(LogEvent::from("hello"))])
+        .await
+        .unwrap();
+    drop(trigger);
+
+    let requests = rx.collect::<Vec<_>>().await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.uri.path(), "/otlp/v1/logs");
+    assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+
+    let request = ExportLogsServiceRequest::decode(&body[..]).unwrap();
+    let resource_logs = &request.resource_logs[0];
+    let attribute = &resource_logs.resource.as_ref().unwrap().attributes[0];
+    assert_eq!(attribute.key, "service.name");
+    assert_eq!(
+        attribute.value.as_ref().unwrap().value,
+        Some(any_value::Value::StringValue("api".to_owned()))
+    );
+    let record = &resource_logs.scope_logs[0].log_records[0];
+    assert_eq!(
+        record.body.as_ref().unwrap().value,
+        Some(any_value::Value::StringValue("hello".to_owned()))
+    );
+    assert!(record.time_unix_nano > 0);
+}
+
+#[tokio::test]
+async fn otlp_requires_http() {
+    let (config, cx) = load_sink::<LokiConfig>(
+        r#"
+            endpoint = "http://localhost:3100"
+            endpoint_format = "otlp"
+            protocol = "grpc"
+            labels = {service_name = "api"}
+            encoding.codec = "text"
+        "#,
+    )
+    .unwrap();
+    assert!(config.build(cx).await.is_err());
+}
+
+#[tokio::test]
+async fn max_label_value_length_conflicts_with_normalization() {
+    let (config, cx) = load_sink::<LokiConfig>(
+        r#"
+            endpoint = "http://localhost:3100"
+            labels = {service = "api"}
+            encoding.codec = "text"
+            max_label_value_length = 2048
+            label_normalization.max_value_length = 1024
+        "#,
+    )
+    .unwrap();
+    let error = config.build(cx).await.err().expect("build should fail");
+    assert!(error
+        .to_string()
+        .contains("Only one of `max_label_value_length`"));
+}
@@ -164,7 +164,7 @@ impl SinkConfig for MezmoConfig {
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batch_settings()?;
-        let client = HttpClient::new(None, cx.proxy())?;
+        let client = HttpClient::new_with_pool(None, cx.proxy(), cx.connection_pool())?;
 
         let sink = PartitionHttpSink::new(
             self.clone(),
@@ -28,6 +28,8 @@ pub mod axiom;
 pub mod azure_blob;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_logs_ingestion")]
+pub mod azure_logs_ingestion;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
@@ -47,6 +49,10 @@ pub mod databend;
 pub mod datadog;
 #[cfg(feature = "sinks-datadog_archives")]
 pub mod datadog_archives;
+#[cfg(feature = "sinks-dual_write")]
+pub mod dual_write;
+#[cfg(feature = "sinks-dynatrace")]
+pub mod dynatrace;
 #[cfg(feature = "sinks-elasticsearch")]
 pub mod elasticsearch;
 #[cfg(feature = "sinks-file")]
@@ -75,12 +81,18 @@ pub mod nats;
 pub mod new_relic;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod opendal_common;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 pub mod pulsar;
+#[cfg(feature = "sinks-questdb")]
+pub mod questdb;
+#[cfg(feature = "sinks-quickwit")]
+pub mod quickwit;
 #[cfg(feature = "sinks-redis")]
 pub mod redis;
 #[cfg(all(
@@ -96,8 +108,12 @@ pub mod socket;
 pub mod splunk_hec;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
+#[cfg(feature = "sinks-sumo_logic")]
+pub mod sumo_logic;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
+#[cfg(feature = "sinks-victorialogs")]
+pub mod victorialogs;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod webhdfs;
 #[cfg(feature = "sinks-websocket")]
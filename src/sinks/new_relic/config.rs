@@ -153,7 +153,7 @@ impl SinkConfig for NewRelicConfig {
 
         let request_limits = self.request.unwrap_with(&Default::default());
         let tls_settings = TlsSettings::from_options(&None)?;
-        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let client = HttpClient::new_with_pool(tls_settings, &cx.proxy, &cx.connection_pool)?;
         let credentials = Arc::from(NewRelicCredentials::from(self));
 
         let healthcheck = self.build_healthcheck(client.clone(), Arc::clone(&credentials))?;
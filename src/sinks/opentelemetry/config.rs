@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use vector_core::tls::MaybeTlsSettings;
+
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::UriSerde,
+        vector::{new_client, HyperSvc},
+    },
+};
+
+use super::{
+    service::{OpentelemetryRetryLogic, OpentelemetryService},
+    sink::OpentelemetrySink,
+};
+
+/// Batches are kept well under the 4 MiB default message size limit of gRPC servers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpentelemetryDefaultBatchSettings;
+
+impl SinkBatchSettings for OpentelemetryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// The transports of the OpenTelemetry Protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    /// OTLP/gRPC, served on port 4317 by default.
+    #[default]
+    Grpc,
+
+    /// OTLP/HTTP with Protocol Buffers payloads, served on port 4318 by default.
+    ///
+    /// Each signal is exported to its own path under the endpoint, `/v1/logs`, `/v1/metrics`, and
+    /// `/v1/traces`.
+    Http,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink(
+    "opentelemetry",
+    "Export log, metric, and trace events with the OpenTelemetry Protocol (OTLP)."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    /// The URL of the OTLP endpoint.
+    #[configurable(metadata(docs::examples = "http://otel-collector:4317"))]
+    #[configurable(metadata(docs::examples = "https://otlp.example.com:4318"))]
+    pub endpoint: UriSerde,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: OtlpProtocol,
+
+    /// Resource attributes set on the exported telemetry.
+    ///
+    /// Events are batched by resource, which is made of the resource attributes the
+    /// `opentelemetry` source attaches to log events, and of these attributes, which override
+    /// them. Traces also get the `service.name` attribute from the service of their spans.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The template of the value of the attribute."
+    ))]
+    #[configurable(metadata(docs::examples = "resource_attributes_examples()"))]
+    pub resource_attributes: BTreeMap<String, Template>,
+
+    /// Headers sent with each export, as HTTP headers or gRPC metadata.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "The value of the header."))]
+    #[configurable(metadata(docs::examples = "headers_examples()"))]
+    pub headers: BTreeMap<String, String>,
+
+    /// The compression of the exports.
+    ///
+    /// Only `gzip` compression is supported by the OpenTelemetry Protocol.
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<OpentelemetryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn resource_attributes_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("deployment.environment".to_owned(), "production".to_owned()),
+        (
+            "service.name".to_owned(),
+            "{{ kubernetes.container_name }}".to_owned(),
+        ),
+    ])
+}
+
+fn headers_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([(
+        "authorization".to_owned(),
+        "Bearer ${OTLP_TOKEN}".to_owned(),
+    )])
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://otel-collector:4317""#).unwrap()
+    }
+}
+
+impl OpentelemetryConfig {
+    fn headers(&self) -> crate::Result<HeaderMap> {
+        self.headers
+            .iter()
+            .map(|(name, value)| -> crate::Result<_> {
+                Ok((
+                    HeaderName::from_bytes(name.as_bytes())?,
+                    HeaderValue::from_str(value)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if !matches!(self.compression, Compression::None | Compression::Gzip(_)) {
+            return Err("The OpenTelemetry Protocol only supports gzip compression.".into());
+        }
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let headers = self.headers()?;
+        let endpoint = self.endpoint.with_default_parts();
+
+        let service = match self.protocol {
+            OtlpProtocol::Grpc => {
+                let client = new_client(&MaybeTlsSettings::from(tls), cx.proxy())?;
+                OpentelemetryService::grpc(
+                    HyperSvc::new(endpoint.uri, client),
+                    headers,
+                    self.compression.is_compressed(),
+                    request_settings.retry_max_duration_secs,
+                )
+            }
+            OtlpProtocol::Http => OpentelemetryService::http(
+                HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?,
+                &endpoint,
+                headers,
+                self.compression,
+                request_settings.retry_max_duration_secs,
+            )?,
+        };
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink =
+            OpentelemetrySink::new(batch_settings, self.resource_attributes.clone(), service);
+
+        // OTLP has no health check, and exporting an empty request is not guaranteed to be
+        // accepted by every endpoint.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config = toml::from_str::<OpentelemetryConfig>(
+            r#"
+            endpoint = "https://otlp.example.com:4318"
+            protocol = "http"
+            compression = "gzip"
+            resource_attributes."service.name" = "{{ service }}"
+            headers.authorization = "Bearer token"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.protocol, OtlpProtocol::Http);
+        assert_eq!(config.resource_attributes.len(), 1);
+        assert_eq!(
+            config.headers().unwrap()[http::header::AUTHORIZATION],
+            "Bearer token"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_compression() {
+        let config = toml::from_str::<OpentelemetryConfig>(
+            r#"
+            endpoint = "http://otel-collector:4317"
+            compression = "zstd"
+        "#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+}
@@ -0,0 +1,639 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use lookup::{event_path, metadata_path};
+use opentelemetry_proto::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY,
+        RESOURCE_KEY, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY, TRACE_ID_KEY,
+    },
+    proto::{
+        common::v1::{any_value, AnyValue, ArrayValue, KeyValue, KeyValueList},
+        logs::v1::LogRecord,
+        metrics::v1::{
+            metric, number_data_point, summary_data_point::ValueAtQuantile, AggregationTemporality,
+            Gauge, Histogram, HistogramDataPoint, Metric as OtlpMetric, NumberDataPoint, Sum,
+            Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{span::SpanKind, status::StatusCode, Span, Status},
+    },
+};
+use vector_core::{
+    config::LogNamespace,
+    event::{Metric, MetricKind, MetricValue, TraceEvent},
+};
+
+use crate::sinks::prelude::*;
+
+/// The resource attribute naming the service that produced the telemetry.
+const SERVICE_NAME: &str = "service.name";
+
+/// Builds the resource of an event, from the resource attributes it carries and the templates of
+/// the configured resource attributes, which take precedence.
+pub(super) fn resource(event: &Event, templates: &BTreeMap<String, Template>) -> Resource {
+    let mut attributes = match event {
+        Event::Log(log) => match otel_field(log, RESOURCE_KEY) {
+            Some(Value::Object(attributes)) => attributes.clone(),
+            _ => BTreeMap::new(),
+        },
+        Event::Trace(trace) => span_service(trace)
+            .map(|service| BTreeMap::from([(SERVICE_NAME.to_owned(), service.clone())]))
+            .unwrap_or_default(),
+        Event::Metric(_) => BTreeMap::new(),
+    };
+    for (key, template) in templates {
+        match template.render_string(event) {
+            Ok(value) => {
+                attributes.insert(key.clone(), value.into());
+            }
+            Err(error) => emit!(TemplateRenderingError {
+                error,
+                field: Some(key.as_str()),
+                drop_event: false,
+            }),
+        }
+    }
+    Resource {
+        attributes: key_values(attributes),
+        dropped_attributes_count: 0,
+    }
+}
+
+/// The service of the first span of a trace.
+fn span_service(trace: &TraceEvent) -> Option<&Value> {
+    match trace.as_map().get("spans") {
+        Some(Value::Array(spans)) => match spans.first() {
+            Some(Value::Object(span)) => span.get("service"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The OpenTelemetry field of a log event, either a field of the event or a metadata field
+/// depending on its namespace, as the `opentelemetry` source inserts them.
+fn otel_field<'a>(log: &'a LogEvent, key: &str) -> Option<&'a Value> {
+    match log.namespace() {
+        LogNamespace::Vector => log.get(metadata_path!("opentelemetry", key)),
+        LogNamespace::Legacy => log.get(event_path!(key)),
+    }
+}
+
+fn take_otel_field(log: &mut LogEvent, key: &str) -> Option<Value> {
+    match log.namespace() {
+        LogNamespace::Vector => log.remove(metadata_path!("opentelemetry", key)),
+        LogNamespace::Legacy => log.remove(event_path!(key)),
+    }
+}
+
+pub(super) fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => any_value::Value::StringValue(string),
+            Err(error) => any_value::Value::BytesValue(error.into_bytes()),
+        },
+        Value::Regex(regex) => any_value::Value::StringValue(regex.as_str().to_owned()),
+        Value::Integer(integer) => any_value::Value::IntValue(integer),
+        Value::Float(float) => any_value::Value::DoubleValue(float.into_inner()),
+        Value::Boolean(boolean) => any_value::Value::BoolValue(boolean),
+        Value::Timestamp(timestamp) => {
+            any_value::Value::StringValue(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        Value::Object(map) => any_value::Value::KvlistValue(KeyValueList {
+            values: key_values(map),
+        }),
+        Value::Array(values) => any_value::Value::ArrayValue(ArrayValue {
+            values: values.into_iter().map(any_value).collect(),
+        }),
+        Value::Null => return AnyValue { value: None },
+    };
+    AnyValue { value: Some(value) }
+}
+
+fn key_values(map: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    map.into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(any_value(value)),
+        })
+        .collect()
+}
+
+fn unix_nanos(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos().max(0) as u64
+}
+
+fn timestamp_nanos(value: Option<Value>) -> u64 {
+    match value {
+        Some(Value::Timestamp(timestamp)) => unix_nanos(timestamp),
+        _ => 0,
+    }
+}
+
+fn integer(value: Option<Value>) -> i64 {
+    match value {
+        Some(Value::Integer(integer)) => integer,
+        _ => 0,
+    }
+}
+
+/// Decodes the hexadecimal trace and span IDs the `opentelemetry` source encodes.
+fn hex_id(value: Option<Value>) -> Vec<u8> {
+    match value {
+        Some(Value::Bytes(id)) => hex::decode(id).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Converts a log event to a log record.
+///
+/// The fields of the OpenTelemetry data model are taken from where the `opentelemetry` source
+/// puts them. With the legacy namespace, the message is the body of the record and the other
+/// fields are attributes. With the Vector namespace, the whole event is the body.
+pub(super) fn log_record(mut log: LogEvent) -> LogRecord {
+    let mut attributes = match take_otel_field(&mut log, ATTRIBUTES_KEY) {
+        Some(Value::Object(attributes)) => attributes,
+        _ => BTreeMap::new(),
+    };
+    // The resource is sent once for the whole batch.
+    take_otel_field(&mut log, RESOURCE_KEY);
+    let trace_id = hex_id(take_otel_field(&mut log, TRACE_ID_KEY));
+    let span_id = hex_id(take_otel_field(&mut log, SPAN_ID_KEY));
+    let severity_text = take_otel_field(&mut log, SEVERITY_TEXT_KEY)
+        .map(|text| text.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let severity_number = integer(take_otel_field(&mut log, SEVERITY_NUMBER_KEY)) as i32;
+    let flags = integer(take_otel_field(&mut log, FLAGS_KEY)) as u32;
+    let dropped_attributes_count =
+        integer(take_otel_field(&mut log, DROPPED_ATTRIBUTES_COUNT_KEY)) as u32;
+    let observed_time_unix_nano =
+        timestamp_nanos(take_otel_field(&mut log, OBSERVED_TIMESTAMP_KEY));
+    let time_unix_nano = timestamp_nanos(log.remove_timestamp());
+
+    let body = match log.namespace() {
+        LogNamespace::Vector => log.into_parts().0,
+        LogNamespace::Legacy => {
+            log.remove(log.source_type_path());
+            let body = log
+                .message_path()
+                .and_then(|path| log.remove(path.as_str()))
+                .unwrap_or(Value::Null);
+            if let (Value::Object(fields), _) = log.into_parts() {
+                for (key, value) in fields {
+                    attributes.entry(key).or_insert(value);
+                }
+            }
+            body
+        }
+    };
+
+    LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano,
+        severity_number,
+        severity_text,
+        body: Some(any_value(body)),
+        attributes: key_values(attributes),
+        dropped_attributes_count,
+        flags,
+        trace_id,
+        span_id,
+    }
+}
+
+/// Whether a metric has an equivalent in the OpenTelemetry data model.
+///
+/// Distributions and sketches hold samples that can't be turned into explicit bucket histograms
+/// without choosing the buckets.
+pub(super) const fn is_supported(value: &MetricValue) -> bool {
+    !matches!(
+        value,
+        MetricValue::Distribution { .. } | MetricValue::Sketch { .. }
+    )
+}
+
+/// Converts a metric, if it is supported.
+///
+/// Incremental metrics are sent with the delta temporality, and absolute ones with the cumulative
+/// temporality. The namespace of the metric is prepended to its name, separated by a period.
+pub(super) fn metric(metric: Metric) -> Option<OtlpMetric> {
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}.{}", namespace, metric.name()),
+        None => metric.name().to_owned(),
+    };
+    let attributes = metric
+        .tags()
+        .map(|tags| {
+            tags.iter_single()
+                .map(|(key, value)| KeyValue {
+                    key: key.to_owned(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue(value.to_owned())),
+                    }),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let time_unix_nano = metric.timestamp().map_or(0, unix_nanos);
+    let start_time_unix_nano = match (metric.kind(), metric.timestamp(), metric.interval_ms()) {
+        (MetricKind::Incremental, Some(timestamp), Some(interval)) => {
+            unix_nanos(timestamp - chrono::Duration::milliseconds(i64::from(interval.get())))
+        }
+        _ => 0,
+    };
+    let aggregation_temporality = match metric.kind() {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let number = |value: f64| NumberDataPoint {
+        attributes: attributes.clone(),
+        start_time_unix_nano,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        exemplars: Vec::new(),
+        flags: 0,
+    };
+
+    let data = match metric.value() {
+        MetricValue::Counter { value } => metric::Data::Sum(Sum {
+            data_points: vec![number(*value)],
+            aggregation_temporality,
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => match metric.kind() {
+            MetricKind::Absolute => metric::Data::Gauge(Gauge {
+                data_points: vec![number(*value)],
+            }),
+            // An incremental gauge is a change of the value, which can be negative.
+            MetricKind::Incremental => metric::Data::Sum(Sum {
+                data_points: vec![number(*value)],
+                aggregation_temporality,
+                is_monotonic: false,
+            }),
+        },
+        MetricValue::Set { values } => metric::Data::Gauge(Gauge {
+            data_points: vec![number(values.len() as f64)],
+        }),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let (explicit_bounds, mut bucket_counts): (Vec<_>, Vec<_>) = buckets
+                .iter()
+                .filter(|bucket| bucket.upper_limit.is_finite())
+                .map(|bucket| (bucket.upper_limit, bucket.count))
+                .unzip();
+            // The values above the last bound fall in a final bucket, the one of the infinite
+            // bound if there is one.
+            let bucketed = bucket_counts.iter().sum::<u64>();
+            bucket_counts.push(count.saturating_sub(bucketed));
+            metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    count: *count,
+                    sum: Some(*sum),
+                    bucket_counts,
+                    explicit_bounds,
+                    exemplars: Vec::new(),
+                    flags: 0,
+                    min: None,
+                    max: None,
+                }],
+                aggregation_temporality,
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => metric::Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: attributes.clone(),
+                start_time_unix_nano,
+                time_unix_nano,
+                count: *count,
+                sum: *sum,
+                quantile_values: quantiles
+                    .iter()
+                    .map(|quantile| ValueAtQuantile {
+                        quantile: quantile.quantile,
+                        value: quantile.value,
+                    })
+                    .collect(),
+                flags: 0,
+            }],
+        }),
+        MetricValue::Distribution { .. } | MetricValue::Sketch { .. } => return None,
+    };
+
+    Some(OtlpMetric {
+        name,
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    })
+}
+
+/// Converts the spans of a trace event, in the format of the `datadog_agent` source.
+pub(super) fn spans(trace: TraceEvent) -> Vec<Span> {
+    let (mut fields, _) = trace.into_parts();
+    match fields.remove("spans") {
+        Some(Value::Array(spans)) => spans
+            .into_iter()
+            .filter_map(|span| match span {
+                Value::Object(span) => Some(span_from(span)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Encodes a trace or span ID, either an integer in the low-order bytes of the ID, or a
+/// hexadecimal string.
+fn span_id(value: Option<Value>, len: usize) -> Vec<u8> {
+    match value {
+        Some(Value::Integer(0)) | None => Vec::new(),
+        Some(Value::Integer(id)) => {
+            let mut bytes = vec![0; len - 8];
+            bytes.extend((id as u64).to_be_bytes());
+            bytes
+        }
+        value => hex_id(value),
+    }
+}
+
+fn span_from(mut span: BTreeMap<String, Value>) -> Span {
+    let start_time_unix_nano = timestamp_nanos(span.remove("start"));
+    let duration = integer(span.remove("duration")).max(0) as u64;
+    let error = integer(span.remove("error")) != 0;
+
+    let mut attributes = BTreeMap::new();
+    for key in ["metrics", "meta"] {
+        if let Some(Value::Object(map)) = span.remove(key) {
+            attributes.extend(map);
+        }
+    }
+    // These are the attributes the Datadog exporter of the OpenTelemetry collector maps to the
+    // resource and type of spans.
+    if let Some(resource) = span.remove("resource") {
+        attributes.insert("resource.name".to_owned(), resource);
+    }
+    if let Some(kind) = span.remove("type") {
+        attributes.insert("span.type".to_owned(), kind);
+    }
+    let kind = match attributes
+        .get("span.kind")
+        .map(Value::to_string_lossy)
+        .as_deref()
+    {
+        Some("internal") => SpanKind::Internal,
+        Some("server") => SpanKind::Server,
+        Some("client") => SpanKind::Client,
+        Some("producer") => SpanKind::Producer,
+        Some("consumer") => SpanKind::Consumer,
+        _ => SpanKind::Unspecified,
+    };
+    let status = Status {
+        message: String::new(),
+        code: if error {
+            StatusCode::Error
+        } else {
+            StatusCode::Unset
+        } as i32,
+    };
+
+    Span {
+        trace_id: span_id(span.remove("trace_id"), 16),
+        span_id: span_id(span.remove("span_id"), 8),
+        trace_state: String::new(),
+        parent_span_id: span_id(span.remove("parent_id"), 8),
+        name: span
+            .remove("name")
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        kind: kind as i32,
+        start_time_unix_nano,
+        end_time_unix_nano: start_time_unix_nano + duration,
+        attributes: key_values(attributes),
+        dropped_attributes_count: 0,
+        events: Vec::new(),
+        dropped_events_count: 0,
+        links: Vec::new(),
+        dropped_links_count: 0,
+        status: Some(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::{
+        metric::{Bucket, MetricTags},
+        EventMetadata,
+    };
+
+    use super::*;
+
+    fn string(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_owned())),
+        })
+    }
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(
+            any_value(Value::Bytes(bytes::Bytes::from_static(&[0xff, 0xfe]))).value,
+            Some(any_value::Value::BytesValue(vec![0xff, 0xfe]))
+        );
+        assert_eq!(any_value(Value::Null).value, None);
+        let object = Value::from(BTreeMap::from([
+            ("a".to_owned(), Value::from(1)),
+            ("b".to_owned(), Value::from(vec![Value::from(true)])),
+        ]));
+        let Some(any_value::Value::KvlistValue(list)) = any_value(object).value else {
+            panic!("not a key-value list");
+        };
+        assert_eq!(list.values[0].key, "a");
+        assert_eq!(
+            list.values[0].value.as_ref().unwrap().value,
+            Some(any_value::Value::IntValue(1))
+        );
+        assert_eq!(list.values[1].key, "b");
+    }
+
+    #[test]
+    fn converts_legacy_logs() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let mut log = LogEvent::from("GET /");
+        log.insert("timestamp", timestamp);
+        log.insert("source_type", "opentelemetry");
+        log.insert("trace_id", "4bf92f3577b34da6a3ce929d0e0e4736");
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("resources.host", "web-1");
+        log.insert("attributes.method", "GET");
+        log.insert("status", 200);
+
+        let record = log_record(log);
+        assert_eq!(record.body, string("GET /"));
+        assert_eq!(record.time_unix_nano, unix_nanos(timestamp));
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(
+            hex::encode(record.trace_id),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        let keys = record
+            .attributes
+            .iter()
+            .map(|attribute| attribute.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["method", "status"]);
+    }
+
+    #[test]
+    fn builds_resources() {
+        let mut log = LogEvent::from("GET /");
+        log.insert("resources.host", "web-1");
+        log.insert("service", "checkout");
+        let templates = BTreeMap::from([(
+            "service.name".to_owned(),
+            Template::try_from("{{ service }}").unwrap(),
+        )]);
+
+        let resource = resource(&Event::Log(log), &templates);
+        assert_eq!(resource.attributes.len(), 2);
+        assert_eq!(resource.attributes[0].key, "host");
+        assert_eq!(resource.attributes[0].value, string("web-1"));
+        assert_eq!(resource.attributes[1].key, "service.name");
+        assert_eq!(resource.attributes[1].value, string("checkout"));
+    }
+
+    #[test]
+    fn converts_metrics() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 3.0 },
+        )
+        .with_namespace(Some("http"))
+        .with_tags(Some(MetricTags::from([(
+            "host".to_owned(),
+            "web-1".to_owned(),
+        )])))
+        .with_timestamp(Some(timestamp));
+        let otlp = metric(counter).unwrap();
+        assert_eq!(otlp.name, "http.requests");
+        let Some(metric::Data::Sum(sum)) = otlp.data else {
+            panic!("not a sum");
+        };
+        assert!(sum.is_monotonic);
+        assert_eq!(
+            sum.aggregation_temporality,
+            AggregationTemporality::Delta as i32
+        );
+        assert_eq!(sum.data_points[0].time_unix_nano, unix_nanos(timestamp));
+        assert_eq!(sum.data_points[0].attributes[0].value, string("web-1"));
+
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.1,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 3,
+                    },
+                    Bucket {
+                        upper_limit: f64::INFINITY,
+                        count: 1,
+                    },
+                ],
+                count: 6,
+                sum: 4.5,
+            },
+        );
+        let Some(metric::Data::Histogram(histogram)) = metric(histogram).unwrap().data else {
+            panic!("not a histogram");
+        };
+        assert_eq!(
+            histogram.aggregation_temporality,
+            AggregationTemporality::Cumulative as i32
+        );
+        assert_eq!(histogram.data_points[0].explicit_bounds, [0.1, 1.0]);
+        assert_eq!(histogram.data_points[0].bucket_counts, [2, 3, 1]);
+
+        let distribution = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: Vec::new(),
+                statistic: vector_core::event::StatisticKind::Histogram,
+            },
+        );
+        assert!(!is_supported(distribution.value()));
+        assert!(metric(distribution).is_none());
+    }
+
+    #[test]
+    fn converts_spans() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        let span = BTreeMap::from([
+            ("service".to_owned(), Value::from("checkout")),
+            ("name".to_owned(), Value::from("http.request")),
+            ("resource".to_owned(), Value::from("GET /cart")),
+            ("trace_id".to_owned(), Value::from(1)),
+            ("span_id".to_owned(), Value::from(2)),
+            ("parent_id".to_owned(), Value::from(0)),
+            ("start".to_owned(), Value::from(start)),
+            ("duration".to_owned(), Value::from(1_000)),
+            ("error".to_owned(), Value::from(1)),
+            (
+                "meta".to_owned(),
+                Value::from(BTreeMap::from([(
+                    "span.kind".to_owned(),
+                    Value::from("server"),
+                )])),
+            ),
+        ]);
+        let trace = TraceEvent::from_parts(
+            BTreeMap::from([("spans".to_owned(), Value::from(vec![Value::from(span)]))]),
+            EventMetadata::default(),
+        );
+
+        let resource = resource(&Event::Trace(trace.clone()), &BTreeMap::new());
+        assert_eq!(resource.attributes[0].key, SERVICE_NAME);
+        assert_eq!(resource.attributes[0].value, string("checkout"));
+
+        let spans = spans(trace);
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.name, "http.request");
+        assert_eq!(
+            span.trace_id,
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert_eq!(span.span_id, [0, 0, 0, 0, 0, 0, 0, 2]);
+        assert!(span.parent_span_id.is_empty());
+        assert_eq!(span.end_time_unix_nano - span.start_time_unix_nano, 1_000);
+        assert_eq!(span.kind, SpanKind::Server as i32);
+        assert_eq!(span.status.as_ref().unwrap().code, StatusCode::Error as i32);
+        let keys = span
+            .attributes
+            .iter()
+            .map(|attribute| attribute.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["resource.name", "span.kind"]);
+    }
+}
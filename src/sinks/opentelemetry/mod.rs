@@ -0,0 +1,18 @@
+//! OpenTelemetry sink
+//!
+//! This sink exports log, metric and trace events to an OpenTelemetry collector, or any other
+//! endpoint of the OpenTelemetry Protocol (OTLP), over gRPC or HTTP.
+//!
+//! <https://opentelemetry.io/docs/specs/otlp/>
+//!
+//! Events are converted to the OTLP data model, and batched by signal and by resource, so that
+//! each export request holds a single resource. The resource attributes are taken from the
+//! metadata the `opentelemetry` source attaches to events, and from templates rendered for each
+//! event. Failed exports are retried when the OTLP specification deems them retryable, after the
+//! delay requested by the server if any.
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::OpentelemetryConfig;
@@ -0,0 +1,503 @@
+use std::{
+    io::Write,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::{header, HeaderMap, HeaderValue, StatusCode, Uri};
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+};
+use prost::Message;
+use snafu::Snafu;
+use tonic::{codec::CompressionEncoding, metadata::MetadataMap, Code};
+use tracing::Instrument;
+
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::{
+        prelude::*,
+        util::{Compressor, UriSerde},
+        vector::HyperSvc,
+    },
+};
+
+#[derive(Clone)]
+pub enum OtlpPayload {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl OtlpPayload {
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+            Self::Traces(request) => request.encode_to_vec(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpentelemetryRequest {
+    pub payload: OtlpPayload,
+    pub finalizers: EventFinalizers,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub struct OpentelemetryResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("Export failed: {}", source))]
+    Grpc {
+        source: tonic::Status,
+        retry_after: Option<Duration>,
+    },
+    #[snafu(display("Failed to make HTTP(S) request: {}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Server responded with an error: {}", status))]
+    Response {
+        status: StatusCode,
+        retry_after: Option<Duration>,
+    },
+}
+
+impl OpentelemetrySinkError {
+    /// Whether the export can be retried, per the OTLP specification.
+    ///
+    /// <https://opentelemetry.io/docs/specs/otlp/#failures>
+    /// <https://opentelemetry.io/docs/specs/otlp/#failures-1>
+    fn is_retriable(&self) -> bool {
+        match self {
+            Self::Grpc {
+                source,
+                retry_after,
+            } => match source.code() {
+                Code::Cancelled
+                | Code::DeadlineExceeded
+                | Code::Aborted
+                | Code::OutOfRange
+                | Code::Unavailable
+                | Code::DataLoss => true,
+                // The server is out of capacity, for good unless it says when to retry.
+                Code::ResourceExhausted => retry_after.is_some(),
+                _ => false,
+            },
+            Self::Http { .. } => true,
+            Self::Response { status, .. } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+        }
+    }
+
+    /// The delay the server asked to wait before retrying.
+    const fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::Grpc { retry_after, .. } | Self::Response { retry_after, .. } => *retry_after,
+            Self::Http { .. } => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+/// The `google.rpc.Status` message gRPC servers put in the details of errors.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<prost_types::Any>,
+}
+
+/// The `google.rpc.RetryInfo` detail of throttled exports.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RetryInfo {
+    #[prost(message, optional, tag = "1")]
+    retry_delay: Option<prost_types::Duration>,
+}
+
+const RETRY_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.RetryInfo";
+
+fn grpc_retry_after(status: &tonic::Status) -> Option<Duration> {
+    let details = RpcStatus::decode(status.details()).ok()?;
+    let retry_info = details
+        .details
+        .iter()
+        .find(|detail| detail.type_url == RETRY_INFO_TYPE_URL)?;
+    let delay = RetryInfo::decode(retry_info.value.as_slice())
+        .ok()?
+        .retry_delay?;
+    Some(Duration::new(
+        delay.seconds.max(0) as u64,
+        delay.nanos.max(0) as u32,
+    ))
+}
+
+/// Parses a `Retry-After` header, in its delay-seconds form.
+fn http_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[derive(Clone)]
+struct GrpcTransport {
+    logs: LogsServiceClient<HyperSvc>,
+    metrics: MetricsServiceClient<HyperSvc>,
+    traces: TraceServiceClient<HyperSvc>,
+    metadata: MetadataMap,
+}
+
+impl GrpcTransport {
+    async fn export(mut self, payload: OtlpPayload) -> Result<(), OpentelemetrySinkError> {
+        let metadata = self.metadata;
+        let result = match payload {
+            OtlpPayload::Logs(request) => self
+                .logs
+                .export(grpc_request(request, metadata))
+                .await
+                .map(drop),
+            OtlpPayload::Metrics(request) => self
+                .metrics
+                .export(grpc_request(request, metadata))
+                .await
+                .map(drop),
+            OtlpPayload::Traces(request) => self
+                .traces
+                .export(grpc_request(request, metadata))
+                .await
+                .map(drop),
+        };
+        result.map_err(|source| OpentelemetrySinkError::Grpc {
+            retry_after: grpc_retry_after(&source),
+            source,
+        })
+    }
+}
+
+fn grpc_request<T>(message: T, metadata: MetadataMap) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    *request.metadata_mut() = metadata;
+    request
+}
+
+#[derive(Clone)]
+struct HttpTransport {
+    client: HttpClient,
+    logs_uri: Uri,
+    metrics_uri: Uri,
+    traces_uri: Uri,
+    headers: HeaderMap,
+    compression: Compression,
+}
+
+impl HttpTransport {
+    fn build_request(&self, payload: &OtlpPayload) -> http::Request<hyper::Body> {
+        let uri = match payload {
+            OtlpPayload::Logs(_) => &self.logs_uri,
+            OtlpPayload::Metrics(_) => &self.metrics_uri,
+            OtlpPayload::Traces(_) => &self.traces_uri,
+        };
+        let mut body = Bytes::from(payload.encode_to_vec());
+        let mut builder =
+            http::Request::post(uri).header(header::CONTENT_TYPE, "application/x-protobuf");
+        if let Some(encoding) = self.compression.content_encoding() {
+            let mut compressor = Compressor::from(self.compression);
+            compressor
+                .write_all(&body)
+                .expect("compressing in memory should not fail");
+            body = compressor
+                .finish()
+                .expect("compressing in memory should not fail")
+                .freeze();
+            builder = builder.header(header::CONTENT_ENCODING, encoding);
+        }
+        let mut request = builder
+            .body(hyper::Body::from(body))
+            .expect("building the request should not fail");
+        request.headers_mut().extend(self.headers.clone());
+        request
+    }
+
+    async fn export(self, payload: OtlpPayload) -> Result<(), OpentelemetrySinkError> {
+        let request = self.build_request(&payload);
+        let response = self
+            .client
+            .send(request)
+            .in_current_span()
+            .await
+            .map_err(|source| OpentelemetrySinkError::Http { source })?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(OpentelemetrySinkError::Response {
+                status,
+                retry_after: http_retry_after(response.headers()),
+            })
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Transport {
+    Grpc(GrpcTransport),
+    Http(HttpTransport),
+}
+
+#[derive(Clone)]
+pub struct OpentelemetryService {
+    transport: Transport,
+    /// The longest delay requested by the server that is waited for.
+    max_retry_after: Duration,
+}
+
+impl OpentelemetryService {
+    /// Exports over gRPC, through `service`.
+    pub fn grpc(
+        service: HyperSvc,
+        headers: HeaderMap,
+        gzip: bool,
+        max_retry_after: Duration,
+    ) -> Self {
+        let mut logs = LogsServiceClient::new(service.clone());
+        let mut metrics = MetricsServiceClient::new(service.clone());
+        let mut traces = TraceServiceClient::new(service);
+        if gzip {
+            logs = logs.send_compressed(CompressionEncoding::Gzip);
+            metrics = metrics.send_compressed(CompressionEncoding::Gzip);
+            traces = traces.send_compressed(CompressionEncoding::Gzip);
+        }
+        Self {
+            transport: Transport::Grpc(GrpcTransport {
+                logs,
+                metrics,
+                traces,
+                metadata: MetadataMap::from_headers(headers),
+            }),
+            max_retry_after,
+        }
+    }
+
+    /// Exports over HTTP, to the paths of each signal under `endpoint`.
+    pub fn http(
+        client: HttpClient,
+        endpoint: &UriSerde,
+        headers: HeaderMap,
+        compression: Compression,
+        max_retry_after: Duration,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            transport: Transport::Http(HttpTransport {
+                client,
+                logs_uri: endpoint.append_path("/v1/logs")?.uri,
+                metrics_uri: endpoint.append_path("/v1/metrics")?.uri,
+                traces_uri: endpoint.append_path("/v1/traces")?.uri,
+                headers,
+                compression,
+            }),
+            max_retry_after,
+        })
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = OpentelemetrySinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let metadata = request.get_metadata();
+        let transport = self.transport.clone();
+        let max_retry_after = self.max_retry_after;
+
+        Box::pin(async move {
+            let result = match transport {
+                Transport::Grpc(grpc) => grpc.export(request.payload).await,
+                Transport::Http(http) => http.export(request.payload).await,
+            };
+            match result {
+                Ok(()) => Ok(OpentelemetryResponse { metadata }),
+                Err(error) => {
+                    // The retries only back off exponentially, so the delay the server asked for
+                    // is waited for before the export fails.
+                    if let Some(delay) = error.retry_after().filter(|_| error.is_retriable()) {
+                        tokio::time::sleep(delay.min(max_retry_after)).await;
+                    }
+                    Err(error)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http_service(compression: Compression) -> HttpTransport {
+        let endpoint = "https://otel.example.com:4318/otlp"
+            .parse::<UriSerde>()
+            .unwrap();
+        let service = OpentelemetryService::http(
+            HttpClient::new(None, &Default::default()).unwrap(),
+            &endpoint,
+            HeaderMap::from_iter([(
+                header::AUTHORIZATION,
+                HeaderValue::from_static("Bearer token"),
+            )]),
+            compression,
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        let Transport::Http(http) = service.transport else {
+            panic!("not HTTP");
+        };
+        http
+    }
+
+    #[tokio::test]
+    async fn builds_http_requests() {
+        let payload = OtlpPayload::Metrics(ExportMetricsServiceRequest::default());
+
+        let request = http_service(Compression::None).build_request(&payload);
+        assert_eq!(request.uri().path(), "/otlp/v1/metrics");
+        assert_eq!(
+            request.headers()[header::CONTENT_TYPE],
+            "application/x-protobuf"
+        );
+        assert_eq!(request.headers()[header::AUTHORIZATION], "Bearer token");
+        assert!(!request.headers().contains_key(header::CONTENT_ENCODING));
+
+        let request = http_service(Compression::gzip_default()).build_request(&payload);
+        assert_eq!(request.headers()[header::CONTENT_ENCODING], "gzip");
+    }
+
+    #[test]
+    fn retries_per_the_specification() {
+        let logic = OpentelemetryRetryLogic;
+        let grpc = |code, retry_after| {
+            logic.is_retriable_error(&OpentelemetrySinkError::Grpc {
+                source: tonic::Status::new(code, "failed"),
+                retry_after,
+            })
+        };
+        assert!(grpc(Code::Unavailable, None));
+        assert!(grpc(Code::ResourceExhausted, Some(Duration::from_secs(1))));
+        assert!(!grpc(Code::ResourceExhausted, None));
+        assert!(!grpc(Code::InvalidArgument, None));
+
+        let http = |status| {
+            logic.is_retriable_error(&OpentelemetrySinkError::Response {
+                status,
+                retry_after: None,
+            })
+        };
+        assert!(http(StatusCode::TOO_MANY_REQUESTS));
+        assert!(http(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!http(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!http(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn parses_retry_delays() {
+        let retry_info = RetryInfo {
+            retry_delay: Some(prost_types::Duration {
+                seconds: 2,
+                nanos: 500_000_000,
+            }),
+        };
+        let details = RpcStatus {
+            code: Code::ResourceExhausted as i32,
+            message: "throttled".to_owned(),
+            details: vec![prost_types::Any {
+                type_url: RETRY_INFO_TYPE_URL.to_owned(),
+                value: retry_info.encode_to_vec(),
+            }],
+        };
+        let status = tonic::Status::with_details(
+            Code::ResourceExhausted,
+            "throttled",
+            details.encode_to_vec().into(),
+        );
+        assert_eq!(
+            grpc_retry_after(&status),
+            Some(Duration::from_millis(2_500))
+        );
+        assert_eq!(grpc_retry_after(&tonic::Status::unavailable("down")), None);
+
+        let headers = HeaderMap::from_iter([(header::RETRY_AFTER, HeaderValue::from_static("30"))]);
+        assert_eq!(http_retry_after(&headers), Some(Duration::from_secs(30)));
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, num::NonZeroUsize};
+
+use bytes::Bytes;
+use opentelemetry_proto::proto::{
+    collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+    logs::v1::{ResourceLogs, ScopeLogs},
+    metrics::v1::{ResourceMetrics, ScopeMetrics},
+    resource::v1::Resource,
+    trace::v1::{ResourceSpans, ScopeSpans},
+};
+use prost::Message;
+
+use crate::{internal_events::OpentelemetryUnsupportedMetricError, sinks::prelude::*};
+
+use super::{
+    encoder,
+    service::{OpentelemetryRequest, OpentelemetryRetryLogic, OpentelemetryService, OtlpPayload},
+};
+
+/// The OpenTelemetry signals, each exported through its own service.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PartitionKey {
+    signal: Signal,
+    /// The encoded resource of the events.
+    resource: Bytes,
+}
+
+/// Events are batched by signal, and by resource so that each request holds a single resource.
+struct ResourcePartitioner {
+    resource_attributes: BTreeMap<String, Template>,
+}
+
+impl Partitioner for ResourcePartitioner {
+    type Item = Event;
+    type Key = PartitionKey;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let signal = match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        };
+        let resource = encoder::resource(item, &self.resource_attributes);
+        PartitionKey {
+            signal,
+            resource: resource.encode_to_vec().into(),
+        }
+    }
+}
+
+fn build_request(key: PartitionKey, mut events: Vec<Event>) -> OpentelemetryRequest {
+    let finalizers = events.take_finalizers();
+    let builder = RequestMetadataBuilder::from_events(&events);
+    let resource = Resource::decode(key.resource).ok();
+
+    let payload = match key.signal {
+        Signal::Logs => OtlpPayload::Logs(ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource,
+                scope_logs: vec![ScopeLogs {
+                    scope: None,
+                    log_records: events
+                        .into_iter()
+                        .map(|event| encoder::log_record(event.into_log()))
+                        .collect(),
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }),
+        Signal::Metrics => OtlpPayload::Metrics(ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: events
+                        .into_iter()
+                        .filter_map(|event| encoder::metric(event.into_metric()))
+                        .collect(),
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }),
+        Signal::Traces => OtlpPayload::Traces(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource,
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: events
+                        .into_iter()
+                        .flat_map(|event| encoder::spans(event.into_trace()))
+                        .collect(),
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }),
+    };
+
+    let size = NonZeroUsize::new(payload.encoded_len().max(1)).expect("size is not zero");
+    OpentelemetryRequest {
+        payload,
+        finalizers,
+        metadata: builder.with_request_size(size),
+    }
+}
+
+pub struct OpentelemetrySink {
+    batch_settings: BatcherSettings,
+    resource_attributes: BTreeMap<String, Template>,
+    service: Svc<OpentelemetryService, OpentelemetryRetryLogic>,
+}
+
+impl OpentelemetrySink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        resource_attributes: BTreeMap<String, Template>,
+        service: Svc<OpentelemetryService, OpentelemetryRetryLogic>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            resource_attributes,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let partitioner = ResourcePartitioner {
+            resource_attributes: self.resource_attributes,
+        };
+        input
+            .filter(|event| {
+                future::ready(match event {
+                    Event::Metric(metric) if !encoder::is_supported(metric.value()) => {
+                        emit!(OpentelemetryUnsupportedMetricError { metric });
+                        false
+                    }
+                    _ => true,
+                })
+            })
+            .batched_partitioned(partitioner, self.batch_settings)
+            .map(|(key, events)| build_request(key, events))
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for OpentelemetrySink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{Metric, MetricKind, MetricValue};
+
+    use super::*;
+
+    #[test]
+    fn partitions_by_signal_and_resource() {
+        let partitioner = ResourcePartitioner {
+            resource_attributes: BTreeMap::from([(
+                "service.name".to_owned(),
+                Template::try_from("{{ service }}").unwrap(),
+            )]),
+        };
+        let log = |service: &str| {
+            let mut log = LogEvent::from("GET /");
+            log.insert("service", service);
+            Event::Log(log)
+        };
+
+        let checkout = partitioner.partition(&log("checkout"));
+        assert_eq!(checkout, partitioner.partition(&log("checkout")));
+        assert_ne!(checkout, partitioner.partition(&log("cart")));
+
+        let metric = Event::Metric(Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        assert_eq!(partitioner.partition(&metric).signal, Signal::Metrics);
+    }
+
+    #[test]
+    fn builds_one_resource_per_request() {
+        let partitioner = ResourcePartitioner {
+            resource_attributes: BTreeMap::new(),
+        };
+        let mut log = LogEvent::from("GET /");
+        log.insert("resources.host", "web-1");
+        let events = vec![Event::Log(log.clone()), Event::Log(log)];
+        let key = partitioner.partition(&events[0]);
+
+        let request = build_request(key, events);
+        let OtlpPayload::Logs(payload) = request.payload else {
+            panic!("not logs");
+        };
+        assert_eq!(payload.resource_logs.len(), 1);
+        let resource_logs = &payload.resource_logs[0];
+        assert_eq!(
+            resource_logs.resource.as_ref().unwrap().attributes[0].key,
+            "host"
+        );
+        assert_eq!(resource_logs.scope_logs[0].log_records.len(), 2);
+        assert_eq!(request.metadata.event_count(), 2);
+    }
+}
@@ -1,16 +1,19 @@
 use std::{collections::BTreeMap, fmt::Write as _};
 
 use chrono::Utc;
-use indexmap::map::IndexMap;
-use prometheus_parser::{proto, METRIC_NAME_LABEL};
-use vector_core::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile};
+use indexmap::{map::IndexMap, set::IndexSet};
+use prometheus_parser::{
+    proto::{self, write_v2},
+    METRIC_NAME_LABEL,
+};
+use vector_core::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile, Sample};
 
 use crate::{
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
     sinks::util::{encode_namespace, statistic::DistributionStatistic},
 };
 
-pub(super) trait MetricCollector {
+pub(crate) trait MetricCollector {
     type Output;
 
     fn new() -> Self;
@@ -218,7 +221,7 @@ pub(super) trait MetricCollector {
     }
 }
 
-pub(super) struct StringCollector {
+pub(crate) struct StringCollector {
     // BTreeMap ensures we get sorted output, which whilst not required is preferable
     processed: BTreeMap<String, String>,
 }
@@ -414,6 +417,213 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// The bucket schema of the native histograms, whose buckets grow by a factor of `2^(2^-3)`, about
+/// 1.09, which is the default resolution of the Prometheus client libraries.
+const NATIVE_HISTOGRAM_SCHEMA: i32 = 3;
+
+/// A series of a Remote Write 2.0 request, with the metadata of its metric.
+struct SeriesV2 {
+    samples: Vec<write_v2::Sample>,
+    histograms: Vec<write_v2::Histogram>,
+    metadata: write_v2::Metadata,
+}
+
+/// Collects the series of a Remote Write 2.0 request, whose strings are interned in a symbols
+/// table.
+pub(super) struct TimeSeriesV2 {
+    buffer: IndexMap<Labels, SeriesV2>,
+    symbols: IndexSet<String>,
+    metadata: write_v2::Metadata,
+    timestamp: Option<i64>,
+}
+
+impl TimeSeriesV2 {
+    fn symbol(&mut self, value: String) -> u32 {
+        self.symbols.insert_full(value).0 as u32
+    }
+
+    fn series(&mut self, labels: Labels) -> &mut SeriesV2 {
+        let metadata = self.metadata.clone();
+        self.buffer.entry(labels).or_insert_with(|| SeriesV2 {
+            samples: Vec::new(),
+            histograms: Vec::new(),
+            metadata,
+        })
+    }
+
+    /// Encodes a metric, sending absolute histogram distributions as native histograms rather
+    /// than as the series of their buckets.
+    pub(super) fn encode_metric_native(
+        &mut self,
+        default_namespace: Option<&str>,
+        buckets: &[f64],
+        quantiles: &[f64],
+        metric: &Metric,
+    ) {
+        let MetricValue::Distribution {
+            samples,
+            statistic: StatisticKind::Histogram,
+        } = metric.value()
+        else {
+            return self.encode_metric(default_namespace, buckets, quantiles, metric);
+        };
+        if metric.kind() != MetricKind::Absolute {
+            return;
+        }
+
+        let name = encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+        self.emit_metadata(metric.name(), &name, metric.value());
+        let timestamp = metric
+            .timestamp()
+            .map_or_else(|| self.default_timestamp(), |t| t.timestamp_millis());
+        let labels = TimeSeries::make_labels(metric.tags(), &name, "", None);
+        self.series(labels)
+            .histograms
+            .push(native_histogram(samples, timestamp));
+    }
+
+    fn default_timestamp(&mut self) -> i64 {
+        *self
+            .timestamp
+            .get_or_insert_with(|| Utc::now().timestamp_millis())
+    }
+}
+
+impl MetricCollector for TimeSeriesV2 {
+    type Output = write_v2::Request;
+
+    fn new() -> Self {
+        Self {
+            buffer: Default::default(),
+            // The first symbol must be the empty string, referenced by the optional fields.
+            symbols: IndexSet::from([String::new()]),
+            metadata: Default::default(),
+            timestamp: None,
+        }
+    }
+
+    fn emit_metadata(&mut self, name: &str, _fullname: &str, value: &MetricValue) {
+        // The metric types of both versions of the protocol have the same values.
+        self.metadata = write_v2::Metadata {
+            r#type: prometheus_metric_type(value) as i32,
+            help_ref: self.symbol(name.into()),
+            unit_ref: 0,
+        };
+    }
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        let labels = TimeSeries::make_labels(tags, name, suffix, extra);
+        self.series(labels)
+            .samples
+            .push(write_v2::Sample { value, timestamp });
+    }
+
+    fn finish(mut self) -> write_v2::Request {
+        let buffer = std::mem::take(&mut self.buffer);
+        let timeseries = buffer
+            .into_iter()
+            .map(|(labels, series)| write_v2::TimeSeries {
+                labels_refs: labels
+                    .into_iter()
+                    .flat_map(|label| [self.symbol(label.name), self.symbol(label.value)])
+                    .collect(),
+                samples: series.samples,
+                histograms: series.histograms,
+                exemplars: Vec::new(),
+                metadata: Some(series.metadata),
+                created_timestamp: 0,
+            })
+            .collect();
+        write_v2::Request {
+            symbols: self.symbols.into_iter().collect(),
+            timeseries,
+        }
+    }
+}
+
+/// Builds an exponential native histogram of the samples of a distribution.
+fn native_histogram(samples: &[Sample], timestamp: i64) -> write_v2::Histogram {
+    use write_v2::histogram::{Count, ResetHint, ZeroCount};
+
+    let mut positive = BTreeMap::<i32, u64>::new();
+    let mut negative = BTreeMap::<i32, u64>::new();
+    let (mut count, mut zero_count, mut sum) = (0, 0, 0.0);
+    for sample in samples.iter().filter(|sample| sample.value.is_finite()) {
+        let rate = u64::from(sample.rate);
+        count += rate;
+        sum += sample.value * f64::from(sample.rate);
+        let buckets = match sample.value {
+            value if value > 0.0 => &mut positive,
+            value if value < 0.0 => &mut negative,
+            _ => {
+                zero_count += rate;
+                continue;
+            }
+        };
+        *buckets
+            .entry(native_bucket_index(sample.value.abs()))
+            .or_default() += rate;
+    }
+
+    let (positive_spans, positive_deltas) = native_spans(&positive);
+    let (negative_spans, negative_deltas) = native_spans(&negative);
+    write_v2::Histogram {
+        count: Some(Count::CountInt(count)),
+        sum,
+        schema: NATIVE_HISTOGRAM_SCHEMA,
+        zero_threshold: 0.0,
+        zero_count: Some(ZeroCount::ZeroCountInt(zero_count)),
+        negative_spans,
+        negative_deltas,
+        negative_counts: Vec::new(),
+        positive_spans,
+        positive_deltas,
+        positive_counts: Vec::new(),
+        reset_hint: ResetHint::Unspecified as i32,
+        timestamp,
+        custom_values: Vec::new(),
+    }
+}
+
+/// The index of the bucket of a positive value, bucket `i` holding the values in
+/// `(base^(i-1), base^i]` where `base = 2^(2^-schema)`.
+fn native_bucket_index(value: f64) -> i32 {
+    (value.log2() * f64::from(1 << NATIVE_HISTOGRAM_SCHEMA)).ceil() as i32
+}
+
+/// Encodes the populated buckets as spans of consecutive buckets, and their counts as the deltas
+/// from the count of the previous bucket.
+fn native_spans(buckets: &BTreeMap<i32, u64>) -> (Vec<write_v2::BucketSpan>, Vec<i64>) {
+    let mut spans = Vec::<write_v2::BucketSpan>::new();
+    let mut deltas = Vec::with_capacity(buckets.len());
+    let mut previous: Option<(i32, u64)> = None;
+    for (&index, &count) in buckets {
+        match (previous, spans.last_mut()) {
+            (Some((previous, _)), Some(span)) if index == previous + 1 => span.length += 1,
+            (Some((previous, _)), _) => spans.push(write_v2::BucketSpan {
+                offset: index - previous - 1,
+                length: 1,
+            }),
+            (None, _) => spans.push(write_v2::BucketSpan {
+                offset: index,
+                length: 1,
+            }),
+        }
+        deltas.push(count as i64 - previous.map_or(0, |(_, count)| count as i64));
+        previous = Some((index, count));
+    }
+    (spans, deltas)
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -968,4 +1178,111 @@ mod tests {
             "#}
         );
     }
+
+    #[test]
+    fn encodes_request_v2_with_symbols() {
+        let counter = |name: &str, value| {
+            Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value })
+                .with_tags(Some(tags()))
+                .with_timestamp(Some(timestamp()))
+        };
+        let mut collector = TimeSeriesV2::new();
+        collector.encode_metric(Some("vector"), &[], &[], &counter("hits", 10.0));
+        collector.encode_metric(Some("vector"), &[], &[], &counter("misses", 2.0));
+        let request = collector.finish();
+
+        assert_eq!(
+            request.symbols,
+            vec![
+                "",
+                "hits",
+                "misses",
+                "__name__",
+                "vector_hits",
+                "code",
+                "200",
+                "vector_misses"
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>()
+        );
+        let metadata = |help_ref| write_v2::Metadata {
+            r#type: write_v2::MetricType::Counter as i32,
+            help_ref,
+            unit_ref: 0,
+        };
+        assert_eq!(
+            request.timeseries,
+            vec![
+                write_v2::TimeSeries {
+                    labels_refs: vec![3, 4, 5, 6],
+                    samples: vec![write_v2::Sample {
+                        value: 10.0,
+                        timestamp: 1612325106789,
+                    }],
+                    metadata: Some(metadata(1)),
+                    ..Default::default()
+                },
+                write_v2::TimeSeries {
+                    labels_refs: vec![3, 7, 5, 6],
+                    samples: vec![write_v2::Sample {
+                        value: 2.0,
+                        timestamp: 1612325106789,
+                    }],
+                    metadata: Some(metadata(2)),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_native_histogram() {
+        use write_v2::histogram::{Count, ZeroCount};
+
+        let sample = |value, rate| Sample { value, rate };
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vec![
+                    sample(1.0, 2),
+                    sample(1.25, 1),
+                    sample(2.0, 1),
+                    sample(0.0, 1),
+                    sample(-1.0, 1),
+                ],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        let mut collector = TimeSeriesV2::new();
+        collector.encode_metric_native(None, &[], &[], &metric);
+        let request = collector.finish();
+
+        assert_eq!(request.timeseries.len(), 1);
+        let series = &request.timeseries[0];
+        assert!(series.samples.is_empty());
+        assert_eq!(
+            series.metadata.as_ref().unwrap().r#type,
+            write_v2::MetricType::Histogram as i32
+        );
+
+        let span = |offset, length| write_v2::BucketSpan { offset, length };
+        let histogram = &series.histograms[0];
+        assert_eq!(histogram.count, Some(Count::CountInt(6)));
+        assert_eq!(histogram.zero_count, Some(ZeroCount::ZeroCountInt(1)));
+        assert_eq!(histogram.sum, 4.25);
+        assert_eq!(histogram.schema, NATIVE_HISTOGRAM_SCHEMA);
+        // 1.0, 1.25 and 2.0 fall in the buckets 0, 3 and 8.
+        assert_eq!(
+            histogram.positive_spans,
+            vec![span(0, 1), span(2, 1), span(4, 1)]
+        );
+        assert_eq!(histogram.positive_deltas, vec![2, -1, 0]);
+        assert_eq!(histogram.negative_spans, vec![span(0, 1)]);
+        assert_eq!(histogram.negative_deltas, vec![1]);
+        assert_eq!(histogram.timestamp, 1612325106789);
+    }
 }
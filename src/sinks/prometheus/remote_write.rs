@@ -1,5 +1,8 @@
 use std::io::Read;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::task;
 
 use aws_types::credentials::SharedCredentialsProvider;
@@ -50,6 +53,8 @@ enum Errors {
     SetMetricInvalid,
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     AwsRegionRequired,
+    #[snafu(display(r#"native_histograms requires protocol_version = "2.0""#))]
+    NativeHistogramsRequireV2,
 }
 
 /// Configuration for the `prometheus_remote_write` sink.
@@ -132,6 +137,22 @@ pub struct RemoteWriteConfig {
     #[configurable(metadata(docs::advanced))]
     #[serde(default)]
     pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol_version: RemoteWriteVersion,
+
+    /// Whether to send [distribution][dist_metric_docs] metrics as native histograms.
+    ///
+    /// Native histograms have exponential buckets, which grow by a factor of about 1.09, rather
+    /// than the ones set in `buckets`. This requires the 2.0 protocol, and a receiver supporting
+    /// native histograms. When the receiver falls back to the 1.0 protocol, distributions are
+    /// aggregated into the histograms of `buckets` instead.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub native_histograms: bool,
 }
 
 impl_generate_config_from_default!(RemoteWriteConfig);
@@ -153,6 +174,44 @@ pub enum Compression {
     Zstd,
 }
 
+/// The version of the remote write protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RemoteWriteVersion {
+    /// [Remote Write 1.0][rw1], supported by all receivers.
+    ///
+    /// [rw1]: https://prometheus.io/docs/specs/remote_write_spec/
+    #[default]
+    #[serde(rename = "1.0")]
+    V1,
+
+    /// [Remote Write 2.0][rw2], which sends the metadata of each series along with it and
+    /// supports native histograms.
+    ///
+    /// Receivers not supporting it answer with `415 Unsupported Media Type`, in which case the
+    /// request is sent again with the 1.0 protocol, which is used for the subsequent requests.
+    ///
+    /// [rw2]: https://prometheus.io/docs/specs/remote_write_spec_2_0/
+    #[serde(rename = "2.0")]
+    V2,
+}
+
+impl RemoteWriteVersion {
+    const fn header(self) -> &'static str {
+        match self {
+            Self::V1 => "0.1.0",
+            Self::V2 => "2.0.0",
+        }
+    }
+
+    const fn content_type(self) -> &'static str {
+        match self {
+            Self::V1 => "application/x-protobuf",
+            Self::V2 => "application/x-protobuf;proto=io.prometheus.write.v2.Request",
+        }
+    }
+}
+
 const fn convert_compression_to_content_encoding(compression: Compression) -> &'static str {
     match compression {
         Compression::Snappy => "snappy",
@@ -169,13 +228,16 @@ impl SinkConfig for RemoteWriteConfig {
         cx: config::SinkContext,
     ) -> crate::Result<(sinks::VectorSink, sinks::Healthcheck)> {
         let endpoint = self.endpoint.parse::<Uri>().context(sinks::UriParseSnafu)?;
+        if self.native_histograms && self.protocol_version != RemoteWriteVersion::V2 {
+            return Err(Errors::NativeHistogramsRequireV2.into());
+        }
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let batch = self.batch.into_batch_settings()?;
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let buckets = self.buckets.clone();
         let quantiles = self.quantiles.clone();
 
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client = HttpClient::new_with_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let tenant_id = self.tenant_id.clone();
 
         let (http_auth, credentials_provider, aws_region) = match &self.auth {
@@ -219,7 +281,12 @@ impl SinkConfig for RemoteWriteConfig {
             compression: self.compression,
         });
 
-        let healthcheck = healthcheck(client.clone(), Arc::clone(&http_request_builder)).boxed();
+        let healthcheck = healthcheck(
+            client.clone(),
+            Arc::clone(&http_request_builder),
+            self.protocol_version,
+        )
+        .boxed();
         let service = RemoteWriteService {
             default_namespace: self.default_namespace.clone(),
             client,
@@ -227,6 +294,9 @@ impl SinkConfig for RemoteWriteConfig {
             quantiles,
             http_request_builder,
             compression: self.compression,
+            protocol_version: self.protocol_version,
+            native_histograms: self.native_histograms,
+            fallen_back: Arc::new(AtomicBool::new(false)),
         };
 
         let sink = {
@@ -286,10 +356,11 @@ struct PartitionKey {
 async fn healthcheck(
     client: HttpClient,
     http_request_builder: Arc<HttpRequestBuilder>,
+    version: RemoteWriteVersion,
 ) -> crate::Result<()> {
     let body = bytes::Bytes::new();
     let request = http_request_builder
-        .build_request(http::Method::GET, body.into(), None)
+        .build_request(http::Method::GET, body.into(), None, version)
         .await?;
     let response = client.send(request).await?;
 
@@ -316,27 +387,89 @@ struct RemoteWriteService {
     quantiles: Vec<f64>,
     http_request_builder: Arc<HttpRequestBuilder>,
     compression: Compression,
+    protocol_version: RemoteWriteVersion,
+    native_histograms: bool,
+    /// Set once the receiver rejected the 2.0 protocol.
+    fallen_back: Arc<AtomicBool>,
 }
 
 impl RemoteWriteService {
-    fn encode_events(&self, metrics: Vec<Metric>) -> Bytes {
-        let mut time_series = collector::TimeSeries::new();
-        for metric in metrics {
-            time_series.encode_metric(
-                self.default_namespace.as_deref(),
-                &self.buckets,
-                &self.quantiles,
-                &metric,
-            );
+    fn version(&self) -> RemoteWriteVersion {
+        if self.fallen_back.load(Ordering::Relaxed) {
+            RemoteWriteVersion::V1
+        } else {
+            self.protocol_version
         }
-        let request = time_series.finish();
+    }
+
+    fn encode_events(&self, metrics: &[Metric], version: RemoteWriteVersion) -> Vec<u8> {
+        let namespace = self.default_namespace.as_deref();
+        let body = match version {
+            RemoteWriteVersion::V1 => {
+                let mut time_series = collector::TimeSeries::new();
+                for metric in metrics {
+                    time_series.encode_metric(namespace, &self.buckets, &self.quantiles, metric);
+                }
+                encode_message(time_series.finish())
+            }
+            RemoteWriteVersion::V2 => {
+                let mut time_series = collector::TimeSeriesV2::new();
+                for metric in metrics {
+                    if self.native_histograms {
+                        time_series.encode_metric_native(
+                            namespace,
+                            &self.buckets,
+                            &self.quantiles,
+                            metric,
+                        );
+                    } else {
+                        time_series.encode_metric(
+                            namespace,
+                            &self.buckets,
+                            &self.quantiles,
+                            metric,
+                        );
+                    }
+                }
+                encode_message(time_series.finish())
+            }
+        };
+        compress_block(self.compression, body)
+    }
 
-        let mut out = BytesMut::with_capacity(request.encoded_len());
-        request.encode(&mut out).expect("Out of memory");
-        out.freeze()
+    async fn send(
+        &self,
+        body: Vec<u8>,
+        tenant_id: Option<String>,
+        version: RemoteWriteVersion,
+    ) -> crate::Result<http::Response<Bytes>> {
+        let request = self
+            .http_request_builder
+            .build_request(http::Method::POST, body, tenant_id, version)
+            .await?;
+
+        let (protocol, endpoint) = uri::protocol_endpoint(request.uri().clone());
+
+        let response = self.client.send(request).await?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+
+        emit!(EndpointBytesSent {
+            byte_size: body.len(),
+            protocol: &protocol,
+            endpoint: &endpoint
+        });
+
+        Ok(hyper::Response::from_parts(parts, body))
     }
 }
 
+fn encode_message(message: impl Message) -> Bytes {
+    let mut out = BytesMut::with_capacity(message.encoded_len());
+    message.encode(&mut out).expect("Out of memory");
+    out.freeze()
+}
+
 impl Service<PartitionInnerBuffer<Vec<Metric>, PartitionKey>> for RemoteWriteService {
     type Response = http::Response<Bytes>;
     type Error = crate::Error;
@@ -350,30 +483,27 @@ impl Service<PartitionInnerBuffer<Vec<Metric>, PartitionKey>> for RemoteWriteSer
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, buffer: PartitionInnerBuffer<Vec<Metric>, PartitionKey>) -> Self::Future {
         let (events, key) = buffer.into_parts();
-        let body = self.encode_events(events);
-        let body = compress_block(self.compression, body);
-
-        let client = self.client.clone();
-        let request_builder = Arc::clone(&self.http_request_builder);
+        let version = self.version();
+        let body = self.encode_events(&events, version);
+        let service = self.clone();
 
         Box::pin(async move {
-            let request = request_builder
-                .build_request(http::Method::POST, body, key.tenant_id)
-                .await?;
-
-            let (protocol, endpoint) = uri::protocol_endpoint(request.uri().clone());
-
-            let response = client.send(request).await?;
-            let (parts, body) = response.into_parts();
-            let body = hyper::body::to_bytes(body).await?;
-
-            emit!(EndpointBytesSent {
-                byte_size: body.len(),
-                protocol: &protocol,
-                endpoint: &endpoint
-            });
-
-            Ok(hyper::Response::from_parts(parts, body))
+            let response = service.send(body, key.tenant_id.clone(), version).await?;
+            if version == RemoteWriteVersion::V2
+                && response.status() == http::StatusCode::UNSUPPORTED_MEDIA_TYPE
+            {
+                if !service.fallen_back.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        message = "Remote write receiver does not support the 2.0 protocol, falling back to 1.0.",
+                        status = %response.status(),
+                    );
+                }
+                let body = service.encode_events(&events, RemoteWriteVersion::V1);
+                return service
+                    .send(body, key.tenant_id, RemoteWriteVersion::V1)
+                    .await;
+            }
+            Ok(response)
         })
     }
 }
@@ -392,15 +522,16 @@ impl HttpRequestBuilder {
         method: http::Method,
         body: Vec<u8>,
         tenant_id: Option<String>,
+        version: RemoteWriteVersion,
     ) -> Result<Request<hyper::Body>, crate::Error> {
         let content_encoding = convert_compression_to_content_encoding(self.compression);
 
         let mut builder = http::Request::builder()
             .method(method)
             .uri(self.endpoint.clone())
-            .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+            .header("X-Prometheus-Remote-Write-Version", version.header())
             .header("Content-Encoding", content_encoding)
-            .header("Content-Type", "application/x-protobuf");
+            .header("Content-Type", version.content_type());
 
         if let Some(tenant_id) = &tenant_id {
             builder = builder.header("X-Scope-OrgID", tenant_id);
@@ -453,7 +584,7 @@ mod tests {
     use futures::StreamExt;
     use http::HeaderMap;
     use indoc::indoc;
-    use prometheus_parser::proto;
+    use prometheus_parser::proto::{self, write_v2};
     use vector_core::metric_tags;
 
     use super::*;
@@ -501,6 +632,53 @@ mod tests {
         assert_eq!(req.metadata[0].metric_family_name, "gauge-2");
     }
 
+    #[tokio::test]
+    async fn sends_v2_request() {
+        let outputs = send_request_with::<write_v2::Request>(
+            r#"protocol_version = "2.0""#,
+            vec![create_event("gauge-2".into(), 32.0)],
+        )
+        .await;
+        assert_eq!(outputs.len(), 1);
+        let (_, req) = &outputs[0];
+
+        assert_eq!(req.symbols[0], "");
+        assert_eq!(req.timeseries.len(), 1);
+        let series = &req.timeseries[0];
+        let labels = series
+            .labels_refs
+            .iter()
+            .map(|r| req.symbols[*r as usize].as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            [
+                "__name__",
+                "gauge-2",
+                "production",
+                "true",
+                "region",
+                "us-west-1"
+            ]
+        );
+        assert_eq!(series.samples.len(), 1);
+        assert_eq!(series.samples[0].value, 32.0);
+        let metadata = series.metadata.as_ref().unwrap();
+        assert_eq!(metadata.r#type, write_v2::MetricType::Gauge as i32);
+        assert_eq!(req.symbols[metadata.help_ref as usize], "gauge-2");
+        assert_eq!(metadata.unit_ref, 0);
+    }
+
+    #[tokio::test]
+    async fn native_histograms_require_v2() {
+        let config: RemoteWriteConfig = toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:9090/api/v1/write"
+            native_histograms = true
+        "#})
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
     #[tokio::test]
     async fn sends_authenticated_request() {
         let outputs = send_request(
@@ -617,6 +795,13 @@ mod tests {
         config: &str,
         events: Vec<Event>,
     ) -> Vec<(HeaderMap, proto::WriteRequest)> {
+        send_request_with(config, events).await
+    }
+
+    async fn send_request_with<T: Message + Default>(
+        config: &str,
+        events: Vec<Event>,
+    ) -> Vec<(HeaderMap, T)> {
         assert_sink_compliance(&HTTP_SINK_TAGS, async {
             let addr = test_util::next_addr();
             let (rx, trigger, server) = build_test_server(addr);
@@ -635,9 +820,13 @@ mod tests {
                 assert_eq!(parts.method, "POST");
                 assert_eq!(parts.uri.path(), "/write");
                 let headers = parts.headers;
-                assert_eq!(headers["x-prometheus-remote-write-version"], "0.1.0");
+                let version = config.protocol_version;
+                assert_eq!(
+                    headers["x-prometheus-remote-write-version"],
+                    version.header()
+                );
                 assert_eq!(headers["content-encoding"], "snappy");
-                assert_eq!(headers["content-type"], "application/x-protobuf");
+                assert_eq!(headers["content-type"], version.content_type());
 
                 if config.auth.is_some() {
                     assert!(headers.contains_key("authorization"));
@@ -646,8 +835,7 @@ mod tests {
                 let decoded = snap::raw::Decoder::new()
                     .decompress_vec(&body)
                     .expect("Invalid snappy compressed data");
-                let request =
-                    proto::WriteRequest::decode(Bytes::from(decoded)).expect("Invalid protobuf");
+                let request = T::decode(Bytes::from(decoded)).expect("Invalid protobuf");
                 (headers, request)
             })
             .collect::<Vec<_>>()
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use base64::prelude::{Engine as _, BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::{EcGroup, EcKey, EcPoint},
+    ecdsa::EcdsaSig,
+    nid::Nid,
+    pkey::Private,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::{sinks::util::tcp::TcpHandshake, tls::MaybeTlsStream};
+
+/// The maximum length of the challenge sent by QuestDB.
+const MAX_CHALLENGE_LEN: usize = 512;
+
+#[derive(Debug, Snafu)]
+pub(super) enum AuthError {
+    #[snafu(display("The private key is not valid base64url: {}", source))]
+    InvalidEncoding { source: base64::DecodeError },
+    #[snafu(display("The private key is not a valid P-256 key: {}", source))]
+    InvalidKey { source: openssl::error::ErrorStack },
+}
+
+/// Authentication with the ECDSA key of a QuestDB user.
+///
+/// The key is the JSON Web Key of the user, as configured in the `auth.db.path` file of QuestDB.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuestDbAuthConfig {
+    /// The key ID of the user, the `kid` of the JSON Web Key.
+    #[configurable(metadata(docs::examples = "testUser1"))]
+    pub key_id: String,
+
+    /// The private key of the user, the `d` of the JSON Web Key, in base64url.
+    #[configurable(metadata(docs::examples = "${QUESTDB_PRIVATE_KEY}"))]
+    pub private_key: SensitiveString,
+}
+
+impl QuestDbAuthConfig {
+    pub(super) fn build(&self) -> Result<QuestDbAuth, AuthError> {
+        let d = BASE64_URL_SAFE_NO_PAD
+            .decode(self.private_key.inner().trim_end_matches('='))
+            .context(InvalidEncodingSnafu)?;
+        let key = private_key(&d).context(InvalidKeySnafu)?;
+        Ok(QuestDbAuth {
+            key_id: self.key_id.clone(),
+            key,
+        })
+    }
+}
+
+/// Builds the P-256 key of the private scalar `d`.
+fn private_key(d: &[u8]) -> Result<EcKey<Private>, openssl::error::ErrorStack> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    let d = BigNum::from_slice(d)?;
+    let context = BigNumContext::new()?;
+    let mut public = EcPoint::new(&group)?;
+    public.mul_generator(&group, &d, &context)?;
+    let key = EcKey::from_private_components(&group, &d, &public)?;
+    key.check_key()?;
+    Ok(key)
+}
+
+/// The challenge-response authentication of QuestDB.
+///
+/// The key ID is sent, then the challenge sent back by QuestDB is signed with the key.
+#[derive(Debug)]
+pub(super) struct QuestDbAuth {
+    key_id: String,
+    key: EcKey<Private>,
+}
+
+impl QuestDbAuth {
+    /// The base64 of the DER-encoded ECDSA signature of the SHA-256 of the challenge.
+    fn sign(&self, challenge: &[u8]) -> tokio::io::Result<String> {
+        let digest = openssl::sha::sha256(challenge);
+        let signature = EcdsaSig::sign(&digest, &self.key)
+            .and_then(|signature| signature.to_der())
+            .map_err(|error| tokio::io::Error::new(tokio::io::ErrorKind::Other, error))?;
+        Ok(BASE64_STANDARD.encode(signature))
+    }
+}
+
+#[async_trait]
+impl TcpHandshake for QuestDbAuth {
+    async fn handshake(&self, stream: &mut MaybeTlsStream<TcpStream>) -> tokio::io::Result<()> {
+        stream
+            .write_all(format!("{}\n", self.key_id).as_bytes())
+            .await?;
+
+        let mut challenge = Vec::new();
+        loop {
+            match stream.read_u8().await? {
+                b'\n' => break,
+                _ if challenge.len() == MAX_CHALLENGE_LEN => {
+                    return Err(tokio::io::Error::new(
+                        tokio::io::ErrorKind::InvalidData,
+                        "authentication challenge is too long",
+                    ));
+                }
+                byte => challenge.push(byte),
+            }
+        }
+
+        let signature = self.sign(&challenge)?;
+        stream
+            .write_all(format!("{}\n", signature).as_bytes())
+            .await?;
+        stream.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The private key of the example user of the QuestDB documentation.
+    const PRIVATE_KEY: &str = "5UjEMuA0Pj5pjK8a-fa24dyIf-Es5mYny3oE_Wmus48";
+
+    fn config(private_key: &str) -> QuestDbAuthConfig {
+        QuestDbAuthConfig {
+            key_id: "testUser1".to_owned(),
+            private_key: private_key.to_owned().into(),
+        }
+    }
+
+    #[test]
+    fn signs_challenges() {
+        let auth = config(PRIVATE_KEY).build().unwrap();
+        let signature = auth.sign(b"challenge").unwrap();
+
+        let signature = EcdsaSig::from_der(&BASE64_STANDARD.decode(signature).unwrap()).unwrap();
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let mut context = BigNumContext::new().unwrap();
+        let public = EcKey::from_public_key(&group, auth.key.public_key()).unwrap();
+        assert!(signature
+            .verify(&openssl::sha::sha256(b"challenge"), &public)
+            .unwrap());
+
+        // The public key is the one of the example user.
+        let (mut x, mut y) = (BigNum::new().unwrap(), BigNum::new().unwrap());
+        public
+            .public_key()
+            .affine_coordinates(&group, &mut x, &mut y, &mut context)
+            .unwrap();
+        assert_eq!(
+            BASE64_URL_SAFE_NO_PAD.encode(x.to_vec()),
+            "fLKYEaoEb9lrn3nkwLDA-M_xnuFOdSt9y0Z7_vWSHLU"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(matches!(
+            config("not base64!").build(),
+            Err(AuthError::InvalidEncoding { .. })
+        ));
+        assert!(matches!(
+            config("AAAA").build(),
+            Err(AuthError::InvalidKey { .. })
+        ));
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use lookup::lookup_v2::OptionalValuePath;
+use vector_config::configurable_component;
+
+use super::{auth::QuestDbAuthConfig, encoder::QuestDbEncoder};
+use crate::{
+    codecs::Transformer,
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig,
+        SinkContext,
+    },
+    sinks::{
+        influxdb::metrics::default_summary_quantiles,
+        util::tcp::{TcpHandshake, TcpSinkConfig},
+        Healthcheck, VectorSink,
+    },
+    tcp::TcpKeepaliveConfig,
+    template::Template,
+    tls::TlsEnableableConfig,
+};
+
+/// Configuration for the `questdb` sink.
+#[configurable_component(sink(
+    "questdb",
+    "Deliver log and metric events to QuestDB with the InfluxDB Line Protocol."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuestDbConfig {
+    /// The address of the InfluxDB Line Protocol endpoint of QuestDB.
+    ///
+    /// The address _must_ include a port.
+    #[configurable(metadata(docs::examples = "localhost:9009"))]
+    pub address: String,
+
+    /// The table to write events to.
+    ///
+    /// Tables are created by QuestDB on the first write.
+    #[configurable(metadata(docs::examples = "{{ service }}_logs"))]
+    #[configurable(metadata(docs::examples = "metrics"))]
+    pub table: Template,
+
+    /// The log fields to write as `SYMBOL` columns.
+    ///
+    /// The other fields are written as columns of their type. The tags of metrics are always
+    /// written as `SYMBOL` columns.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "host"))]
+    #[configurable(metadata(docs::examples = "service"))]
+    pub symbols: Vec<String>,
+
+    /// The log field holding the designated timestamp of rows.
+    ///
+    /// The field is removed from the columns. When the field is missing, or isn't a timestamp, the
+    /// time of encoding is used.
+    ///
+    /// By default, the [global `log_schema.timestamp_key` option][global_timestamp_key] is used.
+    ///
+    /// [global_timestamp_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.timestamp_key
+    #[configurable(metadata(docs::examples = "time"))]
+    pub timestamp_field: Option<OptionalValuePath>,
+
+    /// The quantiles to write for summaries.
+    #[serde(default = "default_summary_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    pub auth: Option<QuestDbAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    pub keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    /// Configures the send buffer size using the `SO_SNDBUF` option on the socket.
+    pub send_buffer_bytes: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for QuestDbConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "localhost:9009"
+            table = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "questdb")]
+impl SinkConfig for QuestDbConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let handshake = self
+            .auth
+            .as_ref()
+            .map(|auth| {
+                auth.build()
+                    .map(|auth| Arc::new(auth) as Arc<dyn TcpHandshake>)
+            })
+            .transpose()?;
+
+        let timestamp_field = self.timestamp_field.as_ref().map_or_else(
+            || log_schema().timestamp_key().cloned(),
+            |field| field.path.clone(),
+        );
+
+        let encoder = QuestDbEncoder {
+            table: self.table.clone(),
+            symbols: self.symbols.iter().cloned().collect(),
+            timestamp_field,
+            quantiles: self.quantiles.clone(),
+            transformer: self.encoding.clone(),
+        };
+
+        TcpSinkConfig::new(
+            self.address.clone(),
+            self.keepalive,
+            self.tls.clone(),
+            self.send_buffer_bytes,
+        )
+        .build_with_handshake(Transformer::default(), encoder, handshake)
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuestDbConfig>();
+    }
+}
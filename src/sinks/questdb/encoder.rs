@@ -0,0 +1,205 @@
+use std::collections::{HashMap, HashSet};
+
+use bytes::BytesMut;
+use lookup::{OwnedValuePath, PathPrefix};
+use vector_core::event::{LogEvent, Metric, MetricTags, Value};
+
+use crate::{
+    codecs::Transformer,
+    event::Event,
+    internal_events::{InfluxdbEncodingError, TemplateRenderingError},
+    sinks::{
+        influxdb::{
+            encode_timestamp, influx_line_protocol, logs::to_field, metrics::get_type_and_fields,
+            Field, ProtocolVersion,
+        },
+        util::encode_namespace,
+    },
+    template::Template,
+};
+
+/// Encodes events as rows of the InfluxDB Line Protocol.
+#[derive(Clone, Debug)]
+pub(super) struct QuestDbEncoder {
+    pub(super) table: Template,
+    pub(super) symbols: HashSet<String>,
+    pub(super) timestamp_field: Option<OwnedValuePath>,
+    pub(super) quantiles: Vec<f64>,
+    pub(super) transformer: Transformer,
+}
+
+type Row = (Option<MetricTags>, Option<HashMap<String, Field>>, i64);
+
+impl QuestDbEncoder {
+    fn encode_log(&self, mut log: LogEvent) -> Row {
+        let timestamp = self.timestamp_field.as_ref().and_then(|field| {
+            let field = (PathPrefix::Event, field);
+            match log.get(field) {
+                Some(Value::Timestamp(timestamp)) => {
+                    let timestamp = *timestamp;
+                    log.remove(field);
+                    Some(timestamp)
+                }
+                _ => None,
+            }
+        });
+
+        let log = {
+            let mut event = Event::from(log);
+            self.transformer.transform(&mut event);
+            event.into_log()
+        };
+
+        let mut symbols = MetricTags::default();
+        let mut columns = HashMap::new();
+        log.convert_to_fields().for_each(|(key, value)| {
+            if self.symbols.contains(&key) {
+                symbols.replace(key, value.to_string_lossy().into_owned());
+            } else {
+                columns.insert(key, to_field(value));
+            }
+        });
+
+        (Some(symbols), Some(columns), encode_timestamp(timestamp))
+    }
+
+    fn encode_metric(&self, metric: Metric) -> Row {
+        let (metric_type, columns) = get_type_and_fields(metric.value(), &self.quantiles);
+
+        let mut symbols = metric.tags().cloned().unwrap_or_default();
+        symbols.replace(
+            "name".to_owned(),
+            encode_namespace(metric.namespace(), '.', metric.name()),
+        );
+        symbols.replace("metric_type".to_owned(), metric_type.to_owned());
+
+        (Some(symbols), columns, encode_timestamp(metric.timestamp()))
+    }
+}
+
+impl tokio_util::codec::Encoder<Event> for QuestDbEncoder {
+    type Error = codecs::encoding::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let table = self.table.render_string(&event).map_err(|error| {
+            let message = error.to_string();
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("table"),
+                drop_event: true,
+            });
+            Self::Error::SerializingError(message.into())
+        })?;
+
+        let (symbols, columns, timestamp) = match event {
+            Event::Log(log) => self.encode_log(log),
+            Event::Metric(metric) => self.encode_metric(metric),
+            Event::Trace(_) => unreachable!("traces are not accepted by the questdb sink"),
+        };
+
+        influx_line_protocol(
+            ProtocolVersion::V1,
+            &table,
+            symbols,
+            columns,
+            timestamp,
+            buffer,
+        )
+        .map_err(|error_message| {
+            emit!(InfluxdbEncodingError {
+                error_message,
+                count: 1,
+            });
+            Self::Error::SerializingError(error_message.into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tokio_util::codec::Encoder as _;
+    use vector_core::{
+        event::{MetricKind, MetricValue},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::sinks::influxdb::metrics::default_summary_quantiles;
+
+    fn encoder(table: &str, symbols: &[&str]) -> QuestDbEncoder {
+        QuestDbEncoder {
+            table: Template::try_from(table).unwrap(),
+            symbols: symbols.iter().map(|symbol| symbol.to_string()).collect(),
+            timestamp_field: Some(lookup::owned_value_path!("timestamp")),
+            quantiles: default_summary_quantiles(),
+            transformer: Transformer::default(),
+        }
+    }
+
+    fn encode(encoder: &mut QuestDbEncoder, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        encoder.encode(event, &mut buffer).unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    fn timestamp() -> chrono::DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000, 123_456_789)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let mut log = LogEvent::default();
+        log.insert("service", "api");
+        log.insert("host", "a");
+        log.insert("message", "hello");
+        log.insert("status", 200);
+        log.insert("timestamp", timestamp());
+
+        let line = encode(&mut encoder("{{ service }}_logs", &["host"]), log.into());
+        let (row, ts) = line.trim_end().rsplit_once(' ').unwrap();
+        assert_eq!(ts, "1700000000123456789");
+
+        let (table_and_symbols, columns) = row.split_once(' ').unwrap();
+        assert_eq!(table_and_symbols, "api_logs,host=a");
+        let mut columns = columns.split(',').collect::<Vec<_>>();
+        columns.sort_unstable();
+        assert_eq!(
+            columns,
+            ["message=\"hello\"", "service=\"api\"", "status=200i"]
+        );
+    }
+
+    #[test]
+    fn encodes_metrics() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.5 },
+        )
+        .with_namespace(Some("http"))
+        .with_tags(Some(metric_tags!("host" => "a")))
+        .with_timestamp(Some(timestamp()));
+
+        let line = encode(&mut encoder("metrics", &[]), metric.into());
+        assert_eq!(
+            line,
+            "metrics,host=a,metric_type=counter,name=http.requests value=1.5 1700000000123456789\n"
+        );
+    }
+
+    #[test]
+    fn drops_events_without_table() {
+        let mut buffer = BytesMut::new();
+        let error = encoder("{{ service }}", &[])
+            .encode(LogEvent::from("hello").into(), &mut buffer)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            codecs::encoding::Error::SerializingError(_)
+        ));
+        assert!(buffer.is_empty());
+    }
+}
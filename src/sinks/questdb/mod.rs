@@ -0,0 +1,16 @@
+//! QuestDB sink
+//!
+//! This sink writes log and metric events to [QuestDB][questdb] with the InfluxDB Line Protocol
+//! (ILP) over TCP.
+//!
+//! Each event is written as a row of the table rendered from the `table` template. Metric tags
+//! and the configured log fields are written as `SYMBOL` columns, the other fields as columns of
+//! their type, and the timestamp of the event as the designated timestamp of the row. Connections
+//! are authenticated, when configured, with the ECDSA challenge of QuestDB.
+//!
+//! [questdb]: https://questdb.io/docs/reference/api/ilp/overview/
+mod auth;
+mod config;
+mod encoder;
+
+pub use self::config::QuestDbConfig;
@@ -0,0 +1,199 @@
+use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+
+use crate::{
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prelude::*,
+        util::{RealtimeSizeBasedDefaultBatchSettings, UriSerde},
+    },
+};
+
+use super::{
+    healthcheck::healthcheck,
+    request_builder::QuickwitRequestBuilder,
+    service::{QuickwitRetryLogic, QuickwitService},
+    sink::QuickwitSink,
+};
+
+/// Configuration for the `quickwit` sink.
+#[configurable_component(sink("quickwit", "Deliver log events to Quickwit."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuickwitConfig {
+    /// The base URL of the Quickwit instance.
+    ///
+    /// The ingest path, `/api/v1/<index>/ingest`, is appended to this URL.
+    #[configurable(metadata(docs::examples = "http://localhost:7280"))]
+    pub endpoint: UriSerde,
+
+    /// The index to ingest events into.
+    ///
+    /// The index must already exist. Events for which the template cannot be rendered are dropped.
+    #[configurable(metadata(docs::examples = "application-logs"))]
+    #[configurable(metadata(docs::examples = "logs-{{ .service }}"))]
+    pub index: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub commit: QuickwitCommitMode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// When Quickwit should commit ingested documents, making them searchable.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickwitCommitMode {
+    /// Documents are committed on the index's regular commit timeout.
+    ///
+    /// The request returns as soon as the documents are persisted.
+    #[default]
+    Auto,
+
+    /// The request waits until the documents have been committed by the regular commit cycle.
+    WaitFor,
+
+    /// A commit is forced, and the request waits until it completes.
+    ///
+    /// Forcing commits creates many small splits and should be reserved for low-volume indexes.
+    Force,
+}
+
+impl QuickwitCommitMode {
+    pub(super) const fn as_query_value(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::WaitFor => "wait_for",
+            Self::Force => "force",
+        }
+    }
+}
+
+impl GenerateConfig for QuickwitConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:7280"
+            index = "application-logs"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+impl QuickwitConfig {
+    fn build_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
+        Ok(client)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "quickwit")]
+impl SinkConfig for QuickwitConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+        let endpoint = self.endpoint.with_default_parts().with_auth(auth);
+        let client = self.build_client(&cx)?;
+
+        let healthcheck = healthcheck(endpoint.clone(), client.clone()).boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let encoder = Encoder::<Framer>::new(
+            NewlineDelimitedEncoderConfig::default().build().into(),
+            JsonSerializerConfig::default().build().into(),
+        );
+        let request_builder =
+            QuickwitRequestBuilder::new(self.compression, (self.encoding.clone(), encoder));
+
+        let service = QuickwitService::new(client, endpoint, self.commit);
+        let service = ServiceBuilder::new()
+            .settings(request_settings, QuickwitRetryLogic)
+            .service(service);
+
+        let sink = QuickwitSink::new(self.index.clone(), batch_settings, request_builder, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuickwitConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let cfg = toml::from_str::<QuickwitConfig>(
+            r#"
+            endpoint = "http://localhost:7280"
+            index = "logs-{{ .service }}"
+            commit = "wait_for"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.endpoint.uri, "http://localhost:7280");
+        assert!(cfg.index.is_dynamic());
+        assert_eq!(cfg.commit, QuickwitCommitMode::WaitFor);
+        assert_eq!(cfg.compression, Compression::None);
+    }
+
+    #[test]
+    fn commit_defaults_to_auto() {
+        let cfg = toml::from_str::<QuickwitConfig>(
+            r#"
+            endpoint = "http://localhost:7280"
+            index = "application-logs"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.commit, QuickwitCommitMode::Auto);
+        assert_eq!(cfg.commit.as_query_value(), "auto");
+    }
+}
@@ -0,0 +1,16 @@
+//! Quickwit sink
+//!
+//! This sink ships log events to Quickwit through its ingest REST API,
+//! `POST /api/v1/<index>/ingest`, as newline-delimited JSON.
+//!
+//! <https://quickwit.io/docs/reference/rest-api#ingest-data-into-an-index>
+//!
+//! Events are partitioned by their rendered `index` so that each request
+//! targets a single index.
+mod config;
+mod healthcheck;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::{QuickwitCommitMode, QuickwitConfig};
@@ -0,0 +1,73 @@
+use std::io;
+
+use bytes::Bytes;
+use codecs::encoding::Framer;
+
+use crate::sinks::prelude::*;
+
+use super::service::QuickwitRequest;
+
+pub(super) struct QuickwitMetadata {
+    index: String,
+    finalizers: EventFinalizers,
+}
+
+#[derive(Clone)]
+pub(super) struct QuickwitRequestBuilder {
+    compression: Compression,
+    encoder: (Transformer, Encoder<Framer>),
+}
+
+impl QuickwitRequestBuilder {
+    pub(super) const fn new(
+        compression: Compression,
+        encoder: (Transformer, Encoder<Framer>),
+    ) -> Self {
+        Self {
+            compression,
+            encoder,
+        }
+    }
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for QuickwitRequestBuilder {
+    type Metadata = QuickwitMetadata;
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = QuickwitRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (index, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (QuickwitMetadata { index, finalizers }, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        quickwit_metadata: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        QuickwitRequest {
+            index: quickwit_metadata.index,
+            compression: self.compression,
+            finalizers: quickwit_metadata.finalizers,
+            payload: payload.into_payload(),
+            metadata,
+        }
+    }
+}
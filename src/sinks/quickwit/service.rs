@@ -0,0 +1,196 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::StatusCode;
+use snafu::Snafu;
+use tracing::Instrument;
+
+use crate::{
+    http::HttpClient,
+    sinks::{prelude::*, util::UriSerde},
+};
+
+use super::config::QuickwitCommitMode;
+
+#[derive(Clone)]
+pub struct QuickwitRetryLogic;
+
+impl RetryLogic for QuickwitRetryLogic {
+    type Error = QuickwitError;
+    type Response = QuickwitResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            QuickwitError::ServerError { code } => match *code {
+                StatusCode::TOO_MANY_REQUESTS => true,
+                StatusCode::NOT_IMPLEMENTED => false,
+                _ if code.is_server_error() => true,
+                _ => false,
+            },
+            QuickwitError::HttpError { .. } => true,
+            QuickwitError::InvalidIndex { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum QuickwitError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+    #[snafu(display("Index {:?} does not form a valid URI: {}", index, error))]
+    InvalidIndex { index: String, error: crate::Error },
+}
+
+#[derive(Debug, Snafu)]
+pub struct QuickwitResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for QuickwitResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct QuickwitRequest {
+    pub index: String,
+    pub compression: Compression,
+    pub finalizers: EventFinalizers,
+    pub payload: Bytes,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for QuickwitRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for QuickwitRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QuickwitService {
+    endpoint: UriSerde,
+    client: HttpClient,
+    commit: QuickwitCommitMode,
+}
+
+impl QuickwitService {
+    pub const fn new(client: HttpClient, endpoint: UriSerde, commit: QuickwitCommitMode) -> Self {
+        Self {
+            client,
+            endpoint,
+            commit,
+        }
+    }
+}
+
+fn ingest_uri(
+    endpoint: &UriSerde,
+    index: &str,
+    commit: QuickwitCommitMode,
+) -> crate::Result<UriSerde> {
+    endpoint.append_path(&format!(
+        "api/v1/{}/ingest?commit={}",
+        index,
+        commit.as_query_value()
+    ))
+}
+
+impl Service<QuickwitRequest> for QuickwitService {
+    type Response = QuickwitResponse;
+    type Error = QuickwitError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: QuickwitRequest) -> Self::Future {
+        let uri = match ingest_uri(&self.endpoint, &request.index, self.commit) {
+            Ok(uri) => uri,
+            Err(error) => {
+                let index = request.index;
+                return Box::pin(async move { Err(QuickwitError::InvalidIndex { index, error }) });
+            }
+        };
+
+        let mut req = http::Request::post(&uri.uri).header("Content-Type", "application/x-ndjson");
+
+        if let Some(ce) = request.compression.content_encoding() {
+            req = req.header("Content-Encoding", ce);
+        }
+
+        let metadata = request.get_metadata();
+        let body = hyper::Body::from(request.payload);
+        let mut req = req.body(body).unwrap();
+
+        if let Some(auth) = &uri.auth {
+            auth.apply(&mut req);
+        }
+
+        let mut client = self.client.clone();
+
+        Box::pin(async move {
+            match client.call(req).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        Ok(QuickwitResponse { metadata })
+                    } else {
+                        Err(QuickwitError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(QuickwitError::HttpError { error }),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_ingest_uri() {
+        let endpoint: UriSerde = "http://localhost:7280/".parse().unwrap();
+        let uri = ingest_uri(&endpoint, "app-logs", QuickwitCommitMode::Force).unwrap();
+        assert_eq!(
+            uri.uri.to_string(),
+            "http://localhost:7280/api/v1/app-logs/ingest?commit=force"
+        );
+    }
+
+    #[test]
+    fn does_not_retry_client_errors() {
+        let logic = QuickwitRetryLogic;
+        assert!(logic.is_retriable_error(&QuickwitError::ServerError {
+            code: StatusCode::TOO_MANY_REQUESTS
+        }));
+        assert!(logic.is_retriable_error(&QuickwitError::ServerError {
+            code: StatusCode::SERVICE_UNAVAILABLE
+        }));
+        assert!(!logic.is_retriable_error(&QuickwitError::ServerError {
+            code: StatusCode::BAD_REQUEST
+        }));
+    }
+}
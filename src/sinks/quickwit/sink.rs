@@ -0,0 +1,79 @@
+use std::num::NonZeroUsize;
+
+use crate::{internal_events::SinkRequestBuildError, sinks::prelude::*};
+
+use super::{
+    request_builder::QuickwitRequestBuilder,
+    service::{QuickwitRetryLogic, QuickwitService},
+};
+
+/// Partitions events by their rendered index.
+struct IndexPartitioner(Template);
+
+impl Partitioner for IndexPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.0
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("index"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub struct QuickwitSink {
+    index: Template,
+    batch_settings: BatcherSettings,
+    request_builder: QuickwitRequestBuilder,
+    service: Svc<QuickwitService, QuickwitRetryLogic>,
+}
+
+impl QuickwitSink {
+    pub(super) const fn new(
+        index: Template,
+        batch_settings: BatcherSettings,
+        request_builder: QuickwitRequestBuilder,
+        service: Svc<QuickwitService, QuickwitRetryLogic>,
+    ) -> Self {
+        Self {
+            index,
+            batch_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+        input
+            .batched_partitioned(IndexPartitioner(self.index), self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |index| (index, batch)) })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for QuickwitSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
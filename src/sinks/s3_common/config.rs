@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use aws_sdk_s3::{
     error::PutObjectError,
-    model::{ObjectCannedAcl, ServerSideEncryption, StorageClass},
+    model::{ChecksumAlgorithm, ObjectCannedAcl, ServerSideEncryption, StorageClass},
     Client as S3Client,
 };
 use aws_smithy_client::SdkError;
@@ -102,10 +102,23 @@ pub struct S3Options {
     pub storage_class: S3StorageClass,
 
     /// The tag-set for the object.
+    ///
+    /// Tag values are templates, so objects can be tagged based on the events they contain. Events
+    /// for which a tag value fails to render are dropped.
     #[configurable(metadata(docs::additional_props_description = "A single tag."))]
     #[configurable(metadata(docs::examples = "example_tags()"))]
     pub tags: Option<BTreeMap<String, String>>,
 
+    /// The algorithm used to compute an additional checksum of the created objects.
+    ///
+    /// S3 verifies the checksum on upload and stores it with the object. This is in addition to
+    /// the `Content-MD5` header that is always sent.
+    ///
+    /// See [Checking object integrity][checksums] for more details.
+    ///
+    /// [checksums]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/checking-object-integrity.html
+    pub checksum_algorithm: Option<S3ChecksumAlgorithm>,
+
     /// Overrides what content encoding has been applied to the object.
     ///
     /// Directly comparable to the `Content-Encoding` HTTP header.
@@ -130,6 +143,7 @@ fn example_tags() -> HashMap<String, String> {
             ("Project".to_string(), "Blue".to_string()),
             ("Classification".to_string(), "confidential".to_string()),
             ("PHI".to_string(), "True".to_string()),
+            ("Team".to_string(), "{{ team }}".to_string()),
         ]
         .into_iter(),
     )
@@ -214,6 +228,35 @@ impl From<S3ServerSideEncryption> for ServerSideEncryption {
     }
 }
 
+/// Algorithms for the additional checksum of S3 objects.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum S3ChecksumAlgorithm {
+    /// CRC32.
+    Crc32,
+
+    /// CRC32C.
+    Crc32c,
+
+    /// SHA-1.
+    Sha1,
+
+    /// SHA-256.
+    Sha256,
+}
+
+impl From<S3ChecksumAlgorithm> for ChecksumAlgorithm {
+    fn from(x: S3ChecksumAlgorithm) -> Self {
+        match x {
+            S3ChecksumAlgorithm::Crc32 => Self::Crc32,
+            S3ChecksumAlgorithm::Crc32c => Self::Crc32C,
+            S3ChecksumAlgorithm::Sha1 => Self::Sha1,
+            S3ChecksumAlgorithm::Sha256 => Self::Sha256,
+        }
+    }
+}
+
 /// S3 Canned ACLs.
 ///
 /// For more information, see [Canned ACL][canned_acl].
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use vector_core::{event::Event, partition::Partitioner};
 
 use crate::{internal_events::TemplateRenderingError, template::Template};
@@ -6,17 +8,23 @@ use crate::{internal_events::TemplateRenderingError, template::Template};
 pub struct S3PartitionKey {
     pub key_prefix: String,
     pub ssekms_key_id: Option<String>,
+    pub tags: Option<BTreeMap<String, String>>,
 }
 
 /// Partitions items based on the generated key for the given event.
-pub struct S3KeyPartitioner(Template, Option<Template>);
+pub struct S3KeyPartitioner(
+    Template,
+    Option<Template>,
+    Option<BTreeMap<String, Template>>,
+);
 
 impl S3KeyPartitioner {
     pub const fn new(
         key_prefix_template: Template,
         ssekms_key_id_template: Option<Template>,
+        tags_templates: Option<BTreeMap<String, Template>>,
     ) -> Self {
-        Self(key_prefix_template, ssekms_key_id_template)
+        Self(key_prefix_template, ssekms_key_id_template, tags_templates)
     }
 }
 
@@ -50,9 +58,66 @@ impl Partitioner for S3KeyPartitioner {
             })
             .transpose()
             .ok()?;
+        let tags = self
+            .2
+            .as_ref()
+            .map(|tags| {
+                tags.iter()
+                    .map(|(name, value)| {
+                        value
+                            .render_string(item)
+                            .map(|value| (name.clone(), value))
+                            .map_err(|error| {
+                                emit!(TemplateRenderingError {
+                                    error,
+                                    field: Some("tags"),
+                                    drop_event: true,
+                                });
+                            })
+                    })
+                    .collect::<Result<BTreeMap<_, _>, _>>()
+            })
+            .transpose()
+            .ok()?;
         Some(S3PartitionKey {
             key_prefix,
             ssekms_key_id,
+            tags,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn renders_tags() {
+        let partitioner = S3KeyPartitioner::new(
+            Template::try_from("logs/").unwrap(),
+            Some(Template::try_from("alias/{{ team }}").unwrap()),
+            Some(BTreeMap::from([
+                ("Team".to_owned(), Template::try_from("{{ team }}").unwrap()),
+                ("Static".to_owned(), Template::try_from("yes").unwrap()),
+            ])),
+        );
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("team", "billing");
+        let key = partitioner.partition(&event).unwrap();
+
+        assert_eq!(key.ssekms_key_id.as_deref(), Some("alias/billing"));
+        assert_eq!(
+            key.tags,
+            Some(BTreeMap::from([
+                ("Static".to_owned(), "yes".to_owned()),
+                ("Team".to_owned(), "billing".to_owned()),
+            ]))
+        );
+
+        let event = Event::Log(LogEvent::from("hello"));
+        assert_eq!(partitioner.partition(&event), None);
+    }
+}
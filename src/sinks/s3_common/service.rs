@@ -146,6 +146,7 @@ impl Service<S3Request> for S3Service {
                 .set_ssekms_key_id(options.ssekms_key_id)
                 .set_storage_class(Some(options.storage_class.into()))
                 .set_tagging(tagging)
+                .set_checksum_algorithm(options.checksum_algorithm.map(Into::into))
                 .content_md5(content_md5);
 
             let result = request.send().in_current_span().await;
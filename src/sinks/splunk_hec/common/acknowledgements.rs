@@ -6,8 +6,12 @@ use std::{
 };
 
 use hyper::Body;
+use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc::Receiver, oneshot::Sender};
+use tokio::{
+    sync::{mpsc::Receiver, oneshot::Sender},
+    time::Instant,
+};
 use vector_config::configurable_component;
 use vector_core::event::EventStatus;
 
@@ -36,6 +40,13 @@ pub struct HecClientAcknowledgementsConfig {
     #[configurable(metadata(docs::type_unit = "seconds"))]
     pub query_interval: NonZeroU8,
 
+    /// The maximum amount of time added at random to each `query_interval`.
+    ///
+    /// This spreads the queries of the acknowledgement channels, and of Vector instances sending
+    /// to the same Splunk HEC collector, over time.
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub query_interval_jitter_ms: u64,
+
     /// The maximum number of times an acknowledgement ID is queried for its status.
     pub retry_limit: NonZeroU8,
 
@@ -44,6 +55,16 @@ pub struct HecClientAcknowledgementsConfig {
     /// Once reached, the sink begins applying backpressure.
     pub max_pending_acks: NonZeroU64,
 
+    /// The number of [channels][splunk_channel_docs] events are sent on.
+    ///
+    /// The acknowledgements of each channel are queried independently, and `max_pending_acks` is
+    /// split evenly between the channels. Events are sent on the channels that have room for
+    /// additional pending acknowledgements, so a channel whose acknowledgements are slow to come
+    /// doesn't hold back the others.
+    ///
+    /// [splunk_channel_docs]: https://docs.splunk.com/Documentation/Splunk/8.2.3/Data/AboutHECIDXAck#About_channels_and_sending_data
+    pub channels: NonZeroU8,
+
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -53,13 +74,38 @@ pub struct HecClientAcknowledgementsConfig {
     pub inner: AcknowledgementsConfig,
 }
 
+impl HecClientAcknowledgementsConfig {
+    /// The number of pending acknowledgements allowed on each channel, adding up to
+    /// `max_pending_acks`. There are no more channels than pending acknowledgements.
+    pub(super) fn pending_acks_per_channel(&self) -> Vec<u64> {
+        let max_pending_acks = self.max_pending_acks.get();
+        let channels = u64::from(self.channels.get()).min(max_pending_acks);
+        (0..channels)
+            .map(|channel| {
+                max_pending_acks / channels + u64::from(channel < max_pending_acks % channels)
+            })
+            .collect()
+    }
+
+    /// The amount of time to wait until the next acknowledgement query.
+    fn next_query_delay(&self) -> Duration {
+        let jitter = match self.query_interval_jitter_ms {
+            0 => 0,
+            max => thread_rng().gen_range(0..=max),
+        };
+        Duration::from_secs(u64::from(self.query_interval.get())) + Duration::from_millis(jitter)
+    }
+}
+
 impl Default for HecClientAcknowledgementsConfig {
     fn default() -> Self {
         Self {
             indexer_acknowledgements_enabled: true,
             query_interval: NonZeroU8::new(10).unwrap(),
+            query_interval_jitter_ms: 0,
             retry_limit: NonZeroU8::new(30).unwrap(),
             max_pending_acks: NonZeroU64::new(1_000_000).unwrap(),
+            channels: NonZeroU8::new(1).unwrap(),
             inner: Default::default(),
         }
     }
@@ -88,6 +134,7 @@ struct HecAckClient {
     retry_limit: u8,
     client: HttpClient,
     http_request_builder: Arc<HttpRequestBuilder>,
+    channel: Arc<str>,
 }
 
 impl HecAckClient {
@@ -95,12 +142,14 @@ impl HecAckClient {
         retry_limit: u8,
         client: HttpClient,
         http_request_builder: Arc<HttpRequestBuilder>,
+        channel: Arc<str>,
     ) -> Self {
         Self {
             acks: HashMap::new(),
             retry_limit,
             client,
             http_request_builder,
+            channel,
         }
     }
 
@@ -222,6 +271,7 @@ impl HecAckClient {
                 request_body_bytes,
                 "/services/collector/ack",
                 None,
+                Some(&self.channel),
                 MetadataFields::default(),
                 false,
             )
@@ -248,25 +298,30 @@ impl HecAckClient {
     }
 }
 
+/// Queries the status of the ack ids of a channel until the sending side of `receiver` is dropped.
 pub async fn run_acknowledgements(
     mut receiver: Receiver<(u64, Sender<EventStatus>)>,
     client: HttpClient,
     http_request_builder: Arc<HttpRequestBuilder>,
+    channel: Arc<str>,
     indexer_acknowledgements: HecClientAcknowledgementsConfig,
 ) {
-    let mut interval = tokio::time::interval(Duration::from_secs(
-        indexer_acknowledgements.query_interval.get() as u64,
-    ));
+    let next_query = tokio::time::sleep(indexer_acknowledgements.next_query_delay());
+    tokio::pin!(next_query);
     let mut ack_client = HecAckClient::new(
         indexer_acknowledgements.retry_limit.get(),
         client,
         http_request_builder,
+        channel,
     );
 
     loop {
         tokio::select! {
-            _ = interval.tick() => {
+            _ = &mut next_query => {
                 ack_client.run().await;
+                next_query
+                    .as_mut()
+                    .reset(Instant::now() + indexer_acknowledgements.next_query_delay());
             },
             ack_info = receiver.recv() => {
                 match ack_info {
@@ -283,13 +338,17 @@ pub async fn run_acknowledgements(
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{
+        num::{NonZeroU64, NonZeroU8},
+        sync::Arc,
+        time::Duration,
+    };
 
     use futures_util::{stream::FuturesUnordered, StreamExt};
     use tokio::sync::oneshot::{self, Receiver};
     use vector_core::{config::proxy::ProxyConfig, event::EventStatus};
 
-    use super::HecAckClient;
+    use super::{HecAckClient, HecClientAcknowledgementsConfig};
     use crate::{
         http::HttpClient,
         sinks::{
@@ -308,7 +367,8 @@ mod tests {
             String::from(""),
             Compression::default(),
         );
-        HecAckClient::new(retry_limit, client, Arc::new(http_request_builder))
+        let channel = Arc::from(http_request_builder.channel.as_str());
+        HecAckClient::new(retry_limit, client, Arc::new(http_request_builder), channel)
     }
 
     fn populate_ack_client(
@@ -378,4 +438,39 @@ mod tests {
             assert_eq!(EventStatus::Rejected, status.unwrap());
         }
     }
+
+    #[test]
+    fn test_pending_acks_per_channel() {
+        let pending_acks_per_channel = |max_pending_acks, channels| {
+            HecClientAcknowledgementsConfig {
+                max_pending_acks: NonZeroU64::new(max_pending_acks).unwrap(),
+                channels: NonZeroU8::new(channels).unwrap(),
+                ..Default::default()
+            }
+            .pending_acks_per_channel()
+        };
+
+        assert_eq!(pending_acks_per_channel(10, 1), vec![10]);
+        assert_eq!(pending_acks_per_channel(10, 3), vec![4, 3, 3]);
+        assert_eq!(pending_acks_per_channel(2, 4), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_next_query_delay() {
+        let config = HecClientAcknowledgementsConfig {
+            query_interval: NonZeroU8::new(2).unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(config.next_query_delay(), Duration::from_secs(2));
+
+        let config = HecClientAcknowledgementsConfig {
+            query_interval_jitter_ms: 500,
+            ..config
+        };
+        for _ in 0..100 {
+            let delay = config.next_query_delay();
+            assert!(delay >= Duration::from_secs(2));
+            assert!(delay <= Duration::from_millis(2500));
+        }
+    }
 }
@@ -19,6 +19,8 @@ pub struct HecRequest {
     pub source: Option<String>,
     pub sourcetype: Option<String>,
     pub host: Option<String>,
+    /// The Splunk channel the request is sent on, if not the default one.
+    pub channel: Option<Arc<str>>,
 }
 
 impl ByteSizeOf for HecRequest {
@@ -32,9 +32,16 @@ use crate::{
 
 pub struct HecService<S> {
     pub inner: S,
-    ack_finalizer_tx: Option<mpsc::Sender<(u64, oneshot::Sender<EventStatus>)>>,
-    ack_slots: PollSemaphore,
-    current_ack_slot: Option<OwnedSemaphorePermit>,
+    ack_channels: Vec<AckChannel>,
+    next_ack_channel: usize,
+    current_ack_slot: Option<(usize, OwnedSemaphorePermit)>,
+}
+
+/// A Splunk channel whose ack ids are queried by its own acknowledgement client.
+struct AckChannel {
+    id: Arc<str>,
+    finalizer_tx: mpsc::Sender<(u64, oneshot::Sender<EventStatus>)>,
+    slots: PollSemaphore,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -56,28 +63,70 @@ where
         http_request_builder: Arc<HttpRequestBuilder>,
         indexer_acknowledgements: HecClientAcknowledgementsConfig,
     ) -> Self {
-        let max_pending_acks = indexer_acknowledgements.max_pending_acks.get();
-        let tx = if let Some(ack_client) = ack_client {
-            let (tx, rx) = mpsc::channel(128);
-            tokio::spawn(run_acknowledgements(
-                rx,
-                ack_client,
-                Arc::clone(&http_request_builder),
-                indexer_acknowledgements,
-            ));
-            Some(tx)
-        } else {
-            None
+        let ack_channels = match ack_client {
+            Some(ack_client) => indexer_acknowledgements
+                .pending_acks_per_channel()
+                .into_iter()
+                .enumerate()
+                .map(|(index, max_pending_acks)| {
+                    // The first channel is the one used when indexer acknowledgements are disabled.
+                    let id = if index == 0 {
+                        Arc::from(http_request_builder.channel.as_str())
+                    } else {
+                        Arc::from(Uuid::new_v4().hyphenated().to_string())
+                    };
+                    let (tx, rx) = mpsc::channel(128);
+                    tokio::spawn(run_acknowledgements(
+                        rx,
+                        ack_client.clone(),
+                        Arc::clone(&http_request_builder),
+                        Arc::clone(&id),
+                        indexer_acknowledgements.clone(),
+                    ));
+                    AckChannel {
+                        id,
+                        finalizer_tx: tx,
+                        slots: PollSemaphore::new(Arc::new(Semaphore::new(
+                            max_pending_acks as usize,
+                        ))),
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
         };
 
-        let ack_slots = PollSemaphore::new(Arc::new(Semaphore::new(max_pending_acks as usize)));
         Self {
             inner,
-            ack_finalizer_tx: tx,
-            ack_slots,
+            ack_channels,
+            next_ack_channel: 0,
             current_ack_slot: None,
         }
     }
+
+    /// Acquires an ack slot on the first channel with room for additional pending acks, starting
+    /// from the channel after the one last used.
+    fn poll_acquire_ack_slot(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<Result<(usize, OwnedSemaphorePermit), crate::Error>> {
+        let channels = self.ack_channels.len();
+        for offset in 0..channels {
+            let index = (self.next_ack_channel + offset) % channels;
+            match self.ack_channels[index].slots.poll_acquire(cx) {
+                Poll::Ready(Some(permit)) => {
+                    self.next_ack_channel = (index + 1) % channels;
+                    return Poll::Ready(Ok((index, permit)));
+                }
+                Poll::Ready(None) => {
+                    return Poll::Ready(Err(
+                        "Indexer acknowledgements semaphore unexpectedly closed".into(),
+                    ))
+                }
+                Poll::Pending => {}
+            }
+        }
+        Poll::Pending
+    }
 }
 
 impl<S> Service<HecRequest> for HecService<S>
@@ -93,25 +142,28 @@ where
 
     fn poll_ready(&mut self, cx: &mut Context) -> std::task::Poll<Result<(), Self::Error>> {
         // Ready if indexer acknowledgements is disabled or there is room for
-        // additional pending acks. Otherwise, wait until there is room.
-        if self.ack_finalizer_tx.is_none() || self.current_ack_slot.is_some() {
+        // additional pending acks on a channel. Otherwise, wait until there is room.
+        if self.ack_channels.is_empty() || self.current_ack_slot.is_some() {
             self.inner.poll_ready(cx).map_err(Into::into)
         } else {
-            match ready!(self.ack_slots.poll_acquire(cx)) {
-                Some(permit) => {
-                    self.current_ack_slot.replace(permit);
-                    self.inner.poll_ready(cx).map_err(Into::into)
-                }
-                None => Poll::Ready(Err(
-                    "Indexer acknowledgements semaphore unexpectedly closed".into(),
-                )),
-            }
+            let ack_slot = ready!(self.poll_acquire_ack_slot(cx))?;
+            self.current_ack_slot.replace(ack_slot);
+            self.inner.poll_ready(cx).map_err(Into::into)
         }
     }
 
-    fn call(&mut self, req: HecRequest) -> Self::Future {
-        let ack_finalizer_tx = self.ack_finalizer_tx.clone();
-        let ack_slot = self.current_ack_slot.take();
+    fn call(&mut self, mut req: HecRequest) -> Self::Future {
+        let (ack_finalizer_tx, ack_slot) = if self.ack_channels.is_empty() {
+            (None, None)
+        } else {
+            let (index, permit) = self
+                .current_ack_slot
+                .take()
+                .expect("poll_ready not called before invoking call");
+            let channel = &self.ack_channels[index];
+            req.channel = Some(Arc::clone(&channel.id));
+            (Some(channel.finalizer_tx.clone()), Some(permit))
+        };
 
         let events_count = req.get_metadata().event_count();
         let events_byte_size = req.get_metadata().events_estimated_json_encoded_byte_size();
@@ -121,7 +173,8 @@ where
             let response = response.await.map_err(Into::into)?;
             let event_status = if response.is_successful() {
                 if let Some(ack_finalizer_tx) = ack_finalizer_tx {
-                    let _ack_slot = ack_slot.expect("poll_ready not called before invoking call");
+                    // The ack slot is held until the ack id is finalized
+                    let _ack_slot = ack_slot;
                     let body = serde_json::from_slice::<HecAckResponseBody>(response.body());
                     match body {
                         Ok(body) => {
@@ -212,11 +265,13 @@ impl HttpRequestBuilder {
         }
     }
 
+    /// Builds a request sent on `channel`, or on the default channel if `None`.
     pub(super) fn build_request(
         &self,
         body: Bytes,
         path: &str,
         passthrough_token: Option<Arc<str>>,
+        channel: Option<&str>,
         metadata_fields: MetadataFields,
         auto_extract_timestamp: bool,
     ) -> Result<Request<Bytes>, crate::Error> {
@@ -255,7 +310,10 @@ impl HttpRequestBuilder {
                     passthrough_token.unwrap_or_else(|| self.default_token.as_str().into())
                 ),
             )
-            .header("X-Splunk-Request-Channel", self.channel.as_str());
+            .header(
+                "X-Splunk-Request-Channel",
+                channel.unwrap_or(self.channel.as_str()),
+            );
 
         if let Some(ce) = self.compression.content_encoding() {
             builder = builder.header("Content-Encoding", ce);
@@ -352,6 +410,7 @@ mod tests {
             source: None,
             sourcetype: None,
             host: None,
+            channel: None,
         }
     }
 
@@ -594,4 +653,43 @@ mod tests {
             Poll::Ready(Ok(_))
         ));
     }
+
+    #[tokio::test]
+    async fn acknowledgements_multiple_channels() {
+        let mock_server = get_hec_mock_server(true, ack_response_always_succeed).await;
+
+        let acknowledgements_config = HecClientAcknowledgementsConfig {
+            query_interval: NonZeroU8::new(1).unwrap(),
+            query_interval_jitter_ms: 100,
+            // Allow a single pending ack on each channel
+            max_pending_acks: NonZeroU64::new(2).unwrap(),
+            channels: NonZeroU8::new(2).unwrap(),
+            ..Default::default()
+        };
+        let mut service = get_hec_service(mock_server.uri(), acknowledgements_config);
+        assert_eq!(service.ack_channels.len(), 2);
+        assert_ne!(service.ack_channels[0].id, service.ack_channels[1].id);
+
+        // Grab the ack slot of each channel
+        let first_call = service.ready().await.unwrap().call(get_hec_request());
+        let second_call = service.ready().await.unwrap().call(get_hec_request());
+        // The service should return pending for additional requests
+        assert!(matches!(
+            poll!(poll_fn(|cx| service.poll_ready(cx))),
+            Poll::Pending
+        ));
+        // Both channels query their own ack ids
+        assert_eq!(
+            EventStatus::Delivered,
+            first_call.await.unwrap().event_status
+        );
+        assert_eq!(
+            EventStatus::Delivered,
+            second_call.await.unwrap().event_status
+        );
+        assert!(matches!(
+            poll!(poll_fn(|cx| service.poll_ready(cx))),
+            Poll::Ready(Ok(_))
+        ));
+    }
 }
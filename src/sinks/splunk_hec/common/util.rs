@@ -14,7 +14,7 @@ use super::{
     EndpointTarget,
 };
 use crate::{
-    http::HttpClient,
+    http::{HttpClient, HttpPoolConfig},
     internal_events::TemplateRenderingError,
     sinks::{
         self,
@@ -45,9 +45,10 @@ pub enum HealthcheckError {
 pub fn create_client(
     tls: &Option<TlsConfig>,
     proxy_config: &ProxyConfig,
+    pool: &HttpPoolConfig,
 ) -> crate::Result<HttpClient> {
     let tls_settings = TlsSettings::from_options(tls)?;
-    Ok(HttpClient::new(tls_settings, proxy_config)?)
+    Ok(HttpClient::new_with_pool(tls_settings, proxy_config, pool)?)
 }
 
 pub fn build_http_batch_service(
@@ -67,6 +68,7 @@ pub fn build_http_batch_service(
                         EndpointTarget::Raw => "/services/collector/raw",
                     },
                     req.passthrough_token,
+                    req.channel.as_deref(),
                     MetadataFields {
                         source: req.source,
                         sourcetype: req.sourcetype,
@@ -169,13 +171,16 @@ mod tests {
         Mock, MockServer, ResponseTemplate,
     };
 
-    use crate::sinks::{
-        splunk_hec::common::{
-            build_healthcheck, build_uri, create_client,
-            service::{HttpRequestBuilder, MetadataFields},
-            EndpointTarget, HOST_FIELD, SOURCE_FIELD,
+    use crate::{
+        http::HttpPoolConfig,
+        sinks::{
+            splunk_hec::common::{
+                build_healthcheck, build_uri, create_client,
+                service::{HttpRequestBuilder, MetadataFields},
+                EndpointTarget, HOST_FIELD, SOURCE_FIELD,
+            },
+            util::Compression,
         },
-        util::Compression,
     };
 
     #[tokio::test]
@@ -189,7 +194,8 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert!(healthcheck.await.is_ok())
@@ -206,7 +212,8 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert_eq!(
@@ -226,7 +233,8 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert_eq!(
@@ -246,7 +254,8 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert_eq!(
@@ -273,6 +282,7 @@ mod tests {
                 events.clone(),
                 "/services/collector/event",
                 None,
+                None,
                 MetadataFields::default(),
                 false,
             )
@@ -316,6 +326,7 @@ mod tests {
                 events.clone(),
                 "/services/collector/event",
                 None,
+                None,
                 MetadataFields::default(),
                 false,
             )
@@ -362,6 +373,7 @@ mod tests {
                 events,
                 "/services/collector/event",
                 None,
+                None,
                 MetadataFields::default(),
                 false,
             )
@@ -400,13 +412,14 @@ mod integration_tests {
         integration_test_helpers::{get_token, splunk_hec_address},
     };
     use crate::{
-        assert_downcast_matches, sinks::splunk_hec::common::HealthcheckError,
+        assert_downcast_matches, http::HttpPoolConfig, sinks::splunk_hec::common::HealthcheckError,
         test_util::retry_until,
     };
 
     #[tokio::test]
     async fn splunk_healthcheck_ok() {
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let address = splunk_hec_address();
         let token = get_token().await;
 
@@ -420,7 +433,8 @@ mod integration_tests {
 
     #[tokio::test]
     async fn splunk_healthcheck_server_not_listening() {
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let healthcheck = build_healthcheck(
             "http://localhost:1111/".to_string(),
             get_token().await,
@@ -432,7 +446,8 @@ mod integration_tests {
 
     #[tokio::test]
     async fn splunk_healthcheck_server_unavailable() {
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client =
+            create_client(&None, &ProxyConfig::default(), &HttpPoolConfig::default()).unwrap();
         let healthcheck = build_healthcheck(
             "http://localhost:5503/".to_string(),
             get_token().await,
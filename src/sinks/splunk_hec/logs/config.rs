@@ -75,6 +75,19 @@ pub struct HecLogsSinkConfig {
     #[configurable(metadata(docs::examples = "field1", docs::examples = "field2"))]
     pub indexed_fields: Vec<String>,
 
+    /// The name of the [indexed field][splunk_field_index_docs] to send the [dedupe ID][dedupe_id]
+    /// of events in.
+    ///
+    /// Splunk doesn't deduplicate events on it, so the field is only propagated, for searches to
+    /// discard the copies of an event sent more than once, such as after Vector restarted. Events
+    /// without a dedupe ID are sent without it.
+    ///
+    /// [splunk_field_index_docs]: https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/IFXandHEC
+    /// [dedupe_id]: https://vector.dev/docs/reference/configuration/sources/#dedupe_id
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "vector_dedupe_id"))]
+    pub dedupe_id_field: Option<String>,
+
     /// The name of the index to send events to.
     ///
     /// If not specified, the default index defined within Splunk is used.
@@ -167,6 +180,7 @@ impl GenerateConfig for HecLogsSinkConfig {
             endpoint: "endpoint".to_owned(),
             host_key: host_key(),
             indexed_fields: vec![],
+            dedupe_id_field: None,
             index: None,
             sourcetype: None,
             source: None,
@@ -193,7 +207,7 @@ impl SinkConfig for HecLogsSinkConfig {
             return Err("`auto_extract_timestamp` cannot be set for the `raw` endpoint.".into());
         }
 
-        let client = create_client(&self.tls, cx.proxy())?;
+        let client = create_client(&self.tls, cx.proxy(), cx.connection_pool())?;
         let healthcheck = build_healthcheck(
             self.endpoint.clone(),
             self.default_token.inner().to_owned(),
@@ -273,6 +287,7 @@ impl HecLogsSinkConfig {
             source: self.source.clone(),
             index: self.index.clone(),
             indexed_fields: self.indexed_fields.clone(),
+            dedupe_id_field: self.dedupe_id_field.clone(),
             host: self.host_key.clone(),
             timestamp_nanos_key: self.timestamp_nanos_key.clone(),
             timestamp_key: self.timestamp_key.path.clone(),
@@ -109,6 +109,7 @@ async fn config(encoding: EncodingConfig, indexed_fields: Vec<String>) -> HecLog
         endpoint: splunk_hec_address(),
         host_key: "host".into(),
         indexed_fields,
+        dedupe_id_field: None,
         index: None,
         sourcetype: None,
         source: None,
@@ -85,6 +85,7 @@ impl RequestBuilder<(Option<Partitioned>, Vec<HecProcessedEvent>)> for HecLogsRe
             sourcetype: hec_metadata.sourcetype,
             index: hec_metadata.index,
             host: hec_metadata.host,
+            channel: None,
             metadata,
         }
     }
@@ -39,6 +39,7 @@ pub struct HecLogsSink<S> {
     pub source: Option<Template>,
     pub index: Option<Template>,
     pub indexed_fields: Vec<String>,
+    pub dedupe_id_field: Option<String>,
     pub host: String,
     pub timestamp_nanos_key: Option<String>,
     pub timestamp_key: Option<OwnedValuePath>,
@@ -50,6 +51,7 @@ pub struct HecLogData<'a> {
     pub source: Option<&'a Template>,
     pub index: Option<&'a Template>,
     pub indexed_fields: &'a [String],
+    pub dedupe_id_field: Option<&'a str>,
     pub host_key: &'a str,
     pub timestamp_nanos_key: Option<&'a String>,
     pub timestamp_key: Option<OwnedValuePath>,
@@ -71,6 +73,7 @@ where
             source: self.source.as_ref(),
             index: self.index.as_ref(),
             indexed_fields: self.indexed_fields.as_slice(),
+            dedupe_id_field: self.dedupe_id_field.as_deref(),
             host_key: self.host.as_ref(),
             timestamp_nanos_key: self.timestamp_nanos_key.as_ref(),
             timestamp_key: self.timestamp_key.clone(),
@@ -270,11 +273,14 @@ pub fn process_log(event: Event, data: &HecLogData) -> HecProcessedEvent {
         }
     });
 
-    let fields = data
+    let mut fields = data
         .indexed_fields
         .iter()
         .filter_map(|field| log.get(field.as_str()).map(|value| (field, value.clone())))
         .collect::<LogEvent>();
+    if let (Some(field), Some(dedupe_id)) = (data.dedupe_id_field, log.metadata().dedupe_id()) {
+        fields.insert(event_path!(field), dedupe_id.to_owned());
+    }
 
     let metadata = HecLogsProcessedEventMetadata {
         event_byte_size,
@@ -91,6 +91,7 @@ fn get_processed_event_timestamp(
             index: index.as_ref(),
             host_key: "host_key",
             indexed_fields: indexed_fields.as_slice(),
+            dedupe_id_field: None,
             timestamp_nanos_key: timestamp_nanos_key.as_ref(),
             timestamp_key,
             endpoint_target: EndpointTarget::Event,
@@ -126,6 +127,32 @@ fn splunk_process_log_event() {
     assert!(metadata.fields.contains("event_field2"));
 }
 
+#[test]
+fn splunk_process_log_dedupe_id() {
+    let mut event = Event::Log(LogEvent::from("hello world"));
+    event.metadata_mut().set_dedupe_id("abc123".to_string());
+
+    let processed_event = process_log(
+        event,
+        &super::sink::HecLogData {
+            sourcetype: None,
+            source: None,
+            index: None,
+            host_key: "host_key",
+            indexed_fields: &[],
+            dedupe_id_field: Some("vector_dedupe_id"),
+            timestamp_nanos_key: None,
+            timestamp_key: None,
+            endpoint_target: EndpointTarget::Event,
+        },
+    );
+
+    assert_eq!(
+        processed_event.metadata.fields.get("vector_dedupe_id"),
+        Some(&Value::from("abc123"))
+    );
+}
+
 fn hec_encoder(encoding: EncodingConfig) -> HecLogsEncoder {
     let transformer = encoding.transformer();
     let serializer = encoding.build().unwrap();
@@ -202,6 +229,7 @@ async fn splunk_passthrough_token() {
         endpoint: format!("http://{}", addr),
         host_key: "host".into(),
         indexed_fields: Vec::new(),
+        dedupe_id_field: None,
         index: None,
         sourcetype: None,
         source: None,
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+
+use codecs::{encoding::Framer, NewlineDelimitedEncoder};
+
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        prometheus::{default_histogram_buckets, default_summary_quantiles},
+        util::UriSerde,
+    },
+};
+
+use super::{
+    encoder::{MetricsEncoder, SumoLogicEncoder},
+    request_builder::SumoLogicRequestBuilder,
+    service::{SumoLogicRetryLogic, SumoLogicService},
+    sink::{SumoLogicSink, SumoTemplates},
+};
+
+/// Batches are kept under the size Sumo Logic recommends for a request.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SumoLogicDefaultBatchSettings;
+
+impl SinkBatchSettings for SumoLogicDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `sumo_logic` sink.
+#[configurable_component(sink(
+    "sumo_logic",
+    "Deliver log and metric events to a Sumo Logic HTTP source."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SumoLogicConfig {
+    /// The URL of the HTTP Logs and Metrics source.
+    ///
+    /// The URL contains the token of the source, so it should be kept secret.
+    #[configurable(metadata(
+        docs::examples = "https://endpoint1.collection.us2.sumologic.com/receiver/v1/http/${SUMO_TOKEN}"
+    ))]
+    pub endpoint: UriSerde,
+
+    /// The source category of the events, sent in the `X-Sumo-Category` header.
+    ///
+    /// Overrides the source category configured on the source. Events for which the template
+    /// cannot be rendered are dropped.
+    #[configurable(metadata(docs::examples = "prod/web/nginx"))]
+    #[configurable(metadata(docs::examples = "{{ .environment }}/{{ .service }}"))]
+    pub source_category: Option<Template>,
+
+    /// The source name of the events, sent in the `X-Sumo-Name` header.
+    ///
+    /// Overrides the source name configured on the source. Events for which the template cannot
+    /// be rendered are dropped.
+    #[configurable(metadata(docs::examples = "{{ .file }}"))]
+    pub source_name: Option<Template>,
+
+    /// The source host of the events, sent in the `X-Sumo-Host` header.
+    ///
+    /// Overrides the source host configured on the source. Events for which the template cannot
+    /// be rendered are dropped.
+    #[configurable(metadata(docs::examples = "{{ .host }}"))]
+    pub source_host: Option<Template>,
+
+    /// Fields attached to the events, sent in the `X-Sumo-Fields` header.
+    ///
+    /// Fields must be defined in Sumo Logic to be searchable. Events for which a template
+    /// cannot be rendered are dropped.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "A field and its value."))]
+    #[configurable(metadata(docs::examples = "fields_examples()"))]
+    pub fields: BTreeMap<String, Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub metrics_format: SumoMetricsFormat,
+
+    /// Default buckets to use for aggregating [distribution][dist_metric_docs] metrics into histograms.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_histogram_buckets")]
+    #[configurable(metadata(docs::advanced))]
+    pub buckets: Vec<f64>,
+
+    /// Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_summary_quantiles")]
+    #[configurable(metadata(docs::advanced))]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SumoLogicDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The format metrics are sent in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SumoMetricsFormat {
+    /// The [Carbon 2.0][carbon2] format, with the tags of the metrics as intrinsic tags.
+    ///
+    /// [carbon2]: https://help.sumologic.com/docs/metrics/introduction/supported-metrics-formats/#carbon-20
+    #[default]
+    Carbon2,
+
+    /// The Prometheus text exposition format.
+    Prometheus,
+}
+
+impl SumoMetricsFormat {
+    pub(super) const fn content_type(self) -> &'static str {
+        match self {
+            Self::Carbon2 => "application/vnd.sumologic.carbon2",
+            Self::Prometheus => "application/vnd.sumologic.prometheus",
+        }
+    }
+}
+
+fn fields_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("environment".to_owned(), "production".to_owned()),
+        ("service".to_owned(), "{{ .service }}".to_owned()),
+    ])
+}
+
+impl GenerateConfig for SumoLogicConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://endpoint1.collection.us2.sumologic.com/receiver/v1/http/token"
+            encoding.codec = "json"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sumo_logic")]
+impl SinkConfig for SumoLogicConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if matches!(self.compression, Compression::Zstd(_)) {
+            return Err("Sumo Logic only accepts gzip and zlib (deflate) compression.".into());
+        }
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let serializer = self.encoding.build()?;
+        let logs = Encoder::<Framer>::new(NewlineDelimitedEncoder::new().into(), serializer);
+        let metrics = MetricsEncoder::new(
+            self.metrics_format,
+            self.buckets.clone(),
+            self.quantiles.clone(),
+        );
+        let encoder = SumoLogicEncoder::new((self.encoding.transformer(), logs), metrics);
+        let request_builder =
+            SumoLogicRequestBuilder::new(self.compression, encoder, self.metrics_format);
+
+        let service = SumoLogicService::new(client, self.endpoint.with_default_parts());
+        let service = ServiceBuilder::new()
+            .settings(request_settings, SumoLogicRetryLogic)
+            .service(service);
+
+        let templates = SumoTemplates {
+            category: self.source_category.clone(),
+            name: self.source_name.clone(),
+            host: self.source_host.clone(),
+            fields: self.fields.clone(),
+        };
+        let sink = SumoLogicSink::new(templates, batch_settings, request_builder, service);
+
+        // Sumo Logic has no endpoint to check an HTTP source without sending data to it.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new((self.encoding.config().input_type() & DataType::Log) | DataType::Metric)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SumoLogicConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let cfg = toml::from_str::<SumoLogicConfig>(
+            r#"
+            endpoint = "https://endpoint1.collection.us2.sumologic.com/receiver/v1/http/token"
+            source_category = "{{ .environment }}/nginx"
+            fields.service = "{{ .service }}"
+            metrics_format = "prometheus"
+            encoding.codec = "text"
+        "#,
+        )
+        .unwrap();
+        assert!(cfg.source_category.unwrap().is_dynamic());
+        assert!(cfg.source_name.is_none());
+        assert_eq!(cfg.fields.len(), 1);
+        assert_eq!(cfg.metrics_format, SumoMetricsFormat::Prometheus);
+        assert_eq!(cfg.buckets, default_histogram_buckets());
+    }
+}
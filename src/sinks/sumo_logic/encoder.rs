@@ -0,0 +1,223 @@
+use std::{fmt::Write as _, io};
+
+use chrono::Utc;
+use codecs::encoding::Framer;
+use vector_core::event::{metric::MetricTags, Metric, MetricValue};
+
+use crate::sinks::{
+    prelude::*,
+    prometheus::collector::{MetricCollector, StringCollector},
+};
+
+use super::config::SumoMetricsFormat;
+
+/// Encodes the metrics of a batch in the configured format.
+#[derive(Clone)]
+pub(super) struct MetricsEncoder {
+    format: SumoMetricsFormat,
+    buckets: Vec<f64>,
+    quantiles: Vec<f64>,
+}
+
+impl MetricsEncoder {
+    pub(super) const fn new(
+        format: SumoMetricsFormat,
+        buckets: Vec<f64>,
+        quantiles: Vec<f64>,
+    ) -> Self {
+        Self {
+            format,
+            buckets,
+            quantiles,
+        }
+    }
+
+    fn encode<C: MetricCollector<Output = String>>(&self, metrics: &[Metric]) -> String {
+        let mut collector = C::new();
+        for metric in metrics {
+            collector.encode_metric(None, &self.buckets, &self.quantiles, metric);
+        }
+        collector.finish()
+    }
+
+    pub(super) fn encode_metrics(&self, metrics: &[Metric]) -> String {
+        match self.format {
+            SumoMetricsFormat::Carbon2 => self.encode::<Carbon2Collector>(metrics),
+            SumoMetricsFormat::Prometheus => self.encode::<StringCollector>(metrics),
+        }
+    }
+}
+
+/// Encodes the events of a batch, which are either all logs or all metrics, as the batches are
+/// partitioned by event type.
+#[derive(Clone)]
+pub(super) struct SumoLogicEncoder {
+    logs: (Transformer, Encoder<Framer>),
+    metrics: MetricsEncoder,
+}
+
+impl SumoLogicEncoder {
+    pub(super) const fn new(logs: (Transformer, Encoder<Framer>), metrics: MetricsEncoder) -> Self {
+        Self { logs, metrics }
+    }
+}
+
+impl encoding::Encoder<Vec<Event>> for SumoLogicEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        if !matches!(events.first(), Some(Event::Metric(_))) {
+            return self.logs.encode_input(events, writer);
+        }
+
+        let count = events.len();
+        let metrics = events
+            .into_iter()
+            .filter_map(Event::try_into_metric)
+            .collect::<Vec<_>>();
+        let body = self.metrics.encode_metrics(&metrics);
+        write_all(writer, count, body.as_bytes())?;
+        Ok(body.len())
+    }
+}
+
+/// Collects metrics in the Carbon 2.0 format, one line per value:
+///
+/// `metric=<name> <tag>=<value>...  <value> <timestamp>`
+///
+/// The name and the tags are the intrinsic tags of the series, and no meta tags are sent.
+pub(super) struct Carbon2Collector {
+    result: String,
+}
+
+impl Carbon2Collector {
+    /// Spaces and `=` separate the tags, so they are replaced in names and values.
+    fn write_tag(&mut self, key: &str, value: &str) {
+        let escape = |s: &str| s.replace([' ', '='], "_");
+        _ = write!(self.result, " {}={}", escape(key), escape(value));
+    }
+}
+
+impl MetricCollector for Carbon2Collector {
+    type Output = String;
+
+    fn new() -> Self {
+        Self {
+            result: String::new(),
+        }
+    }
+
+    fn emit_metadata(&mut self, _name: &str, _fullname: &str, _value: &MetricValue) {}
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        self.result.push_str("metric=");
+        self.result.push_str(&name.replace([' ', '='], "_"));
+        self.result.push_str(suffix);
+
+        let mut tags = tags
+            .map(|tags| tags.iter_single().collect::<Vec<_>>())
+            .unwrap_or_default();
+        if let Some((key, value)) = &extra {
+            tags.push((*key, value.as_str()));
+        }
+        tags.sort_unstable();
+        for (key, value) in tags {
+            self.write_tag(key, value);
+        }
+
+        // Carbon 2.0 requires a timestamp, in seconds.
+        let timestamp = timestamp_millis.unwrap_or_else(|| Utc::now().timestamp_millis()) / 1000;
+        _ = writeln!(self.result, "  {} {}", value, timestamp);
+    }
+
+    fn finish(self) -> String {
+        self.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::{
+        event::{metric::Quantile, MetricKind},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::sinks::util::encoding::Encoder as _;
+
+    fn metric(name: &str, value: MetricValue) -> Metric {
+        Metric::new(name, MetricKind::Absolute, value)
+            .with_tags(Some(metric_tags!("host" => "web 1", "region" => "us")))
+            .with_timestamp(Some(Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap()))
+    }
+
+    fn encoder(format: SumoMetricsFormat) -> MetricsEncoder {
+        MetricsEncoder::new(format, vec![1.0], vec![0.5])
+    }
+
+    #[test]
+    fn encodes_carbon2() {
+        let metrics = vec![
+            metric("cpu_idle", MetricValue::Gauge { value: 97.5 }),
+            metric(
+                "latency",
+                MetricValue::AggregatedSummary {
+                    quantiles: vec![Quantile {
+                        quantile: 0.5,
+                        value: 2.0,
+                    }],
+                    count: 3,
+                    sum: 6.0,
+                },
+            ),
+        ];
+        let body = encoder(SumoMetricsFormat::Carbon2).encode_metrics(&metrics);
+        assert_eq!(
+            body,
+            "metric=cpu_idle host=web_1 region=us  97.5 1685620800\n\
+             metric=latency host=web_1 quantile=0.5 region=us  2 1685620800\n\
+             metric=latency_sum host=web_1 region=us  6 1685620800\n\
+             metric=latency_count host=web_1 region=us  3 1685620800\n"
+        );
+    }
+
+    #[test]
+    fn encodes_prometheus() {
+        let metrics = vec![metric("requests", MetricValue::Counter { value: 10.0 })];
+        let body = encoder(SumoMetricsFormat::Prometheus).encode_metrics(&metrics);
+        assert_eq!(
+            body,
+            "# HELP requests requests\n\
+             # TYPE requests counter\n\
+             requests{host=\"web 1\",region=\"us\"} 10 1685620800000\n"
+        );
+    }
+
+    #[test]
+    fn encodes_logs_with_framing() {
+        let encoder = SumoLogicEncoder::new(
+            (
+                Transformer::default(),
+                Encoder::<Framer>::new(
+                    codecs::NewlineDelimitedEncoder::new().into(),
+                    codecs::TextSerializerConfig::default().build().into(),
+                ),
+            ),
+            encoder(SumoMetricsFormat::Carbon2),
+        );
+        let events = vec![
+            Event::Log(LogEvent::from("first")),
+            Event::Log(LogEvent::from("second")),
+        ];
+        let mut body = Vec::new();
+        encoder.encode_input(events, &mut body).unwrap();
+        assert_eq!(body, b"first\nsecond");
+    }
+}
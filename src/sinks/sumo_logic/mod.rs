@@ -0,0 +1,17 @@
+//! Sumo Logic sink
+//!
+//! This sink ships log and metric events to a Sumo Logic HTTP Logs and Metrics source. Logs are
+//! sent as newline-delimited records, and metrics in the Carbon 2.0 or Prometheus formats.
+//!
+//! <https://help.sumologic.com/docs/send-data/hosted-collectors/http-source/logs-metrics/upload-logs/>
+//!
+//! The source category, name, host, and fields of the events are sent as `X-Sumo-*` headers,
+//! so events are partitioned by their rendered values, along with their type, so that each
+//! request carries a single set of headers.
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::{SumoLogicConfig, SumoMetricsFormat};
@@ -0,0 +1,87 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::sinks::prelude::*;
+
+use super::{
+    config::SumoMetricsFormat,
+    encoder::SumoLogicEncoder,
+    service::SumoLogicRequest,
+    sink::{EventKind, PartitionKey},
+};
+
+pub(super) struct SumoLogicMetadata {
+    key: PartitionKey,
+    finalizers: EventFinalizers,
+}
+
+#[derive(Clone)]
+pub(super) struct SumoLogicRequestBuilder {
+    compression: Compression,
+    encoder: SumoLogicEncoder,
+    metrics_format: SumoMetricsFormat,
+}
+
+impl SumoLogicRequestBuilder {
+    pub(super) const fn new(
+        compression: Compression,
+        encoder: SumoLogicEncoder,
+        metrics_format: SumoMetricsFormat,
+    ) -> Self {
+        Self {
+            compression,
+            encoder,
+            metrics_format,
+        }
+    }
+}
+
+impl RequestBuilder<(PartitionKey, Vec<Event>)> for SumoLogicRequestBuilder {
+    type Metadata = SumoLogicMetadata;
+    type Events = Vec<Event>;
+    type Encoder = SumoLogicEncoder;
+    type Payload = Bytes;
+    type Request = SumoLogicRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (PartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (SumoLogicMetadata { key, finalizers }, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        sumo_metadata: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let SumoLogicMetadata { key, finalizers } = sumo_metadata;
+        // Logs are sent without a content type, which Sumo Logic ingests as text.
+        let content_type = match key.kind {
+            EventKind::Log => None,
+            EventKind::Metric => Some(self.metrics_format.content_type()),
+        };
+        SumoLogicRequest {
+            headers: key.headers,
+            content_type,
+            compression: self.compression,
+            finalizers,
+            payload: payload.into_payload(),
+            metadata,
+        }
+    }
+}
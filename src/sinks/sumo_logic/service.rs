@@ -0,0 +1,279 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use http::{header, StatusCode};
+use snafu::Snafu;
+use tokio::time::Instant;
+use tracing::Instrument;
+
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::{http::retry_after, UriSerde},
+    },
+};
+
+use super::sink::SumoHeaders;
+
+/// The longest delay asked by Sumo Logic with the `Retry-After` header that is honored.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+
+#[derive(Clone)]
+pub struct SumoLogicRetryLogic;
+
+impl RetryLogic for SumoLogicRetryLogic {
+    type Error = SumoLogicError;
+    type Response = SumoLogicResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // Sumo Logic answers `429 Too Many Requests` when the ingestion rate of the account
+            // exceeds its limits, and `503 Service Unavailable` while it is scaling up.
+            SumoLogicError::ServerError { code } => match *code {
+                StatusCode::TOO_MANY_REQUESTS => true,
+                StatusCode::NOT_IMPLEMENTED => false,
+                _ if code.is_server_error() => true,
+                _ => false,
+            },
+            SumoLogicError::HttpError { .. } => true,
+            SumoLogicError::InvalidRequest { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum SumoLogicError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+    #[snafu(display("Invalid request, a header may not be a valid header value: {}", error))]
+    InvalidRequest { error: http::Error },
+}
+
+#[derive(Debug)]
+pub struct SumoLogicResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for SumoLogicResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct SumoLogicRequest {
+    pub headers: SumoHeaders,
+    pub content_type: Option<&'static str>,
+    pub compression: Compression,
+    pub finalizers: EventFinalizers,
+    pub payload: Bytes,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for SumoLogicRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for SumoLogicRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+#[derive(Clone)]
+pub struct SumoLogicService {
+    client: HttpClient,
+    endpoint: UriSerde,
+    /// Requests are held until then, once Sumo Logic asked to retry later.
+    throttled_until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl SumoLogicService {
+    pub fn new(client: HttpClient, endpoint: UriSerde) -> Self {
+        Self {
+            client,
+            endpoint,
+            throttled_until: Arc::default(),
+        }
+    }
+}
+
+fn build_request(
+    endpoint: &UriSerde,
+    request: SumoLogicRequest,
+) -> Result<http::Request<hyper::Body>, http::Error> {
+    let mut req = http::Request::post(&endpoint.uri);
+
+    let SumoHeaders {
+        category,
+        name,
+        host,
+        fields,
+    } = request.headers;
+    for (header, value) in [
+        ("X-Sumo-Category", category),
+        ("X-Sumo-Name", name),
+        ("X-Sumo-Host", host),
+        ("X-Sumo-Fields", fields),
+    ] {
+        if let Some(value) = value {
+            req = req.header(header, value);
+        }
+    }
+
+    if let Some(content_type) = request.content_type {
+        req = req.header(header::CONTENT_TYPE, content_type);
+    }
+    if let Some(ce) = request.compression.content_encoding() {
+        req = req.header(header::CONTENT_ENCODING, ce);
+    }
+
+    let mut req = req.body(hyper::Body::from(request.payload))?;
+    if let Some(auth) = &endpoint.auth {
+        auth.apply(&mut req);
+    }
+    Ok(req)
+}
+
+impl Service<SumoLogicRequest> for SumoLogicService {
+    type Response = SumoLogicResponse;
+    type Error = SumoLogicError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SumoLogicRequest) -> Self::Future {
+        let metadata = request.get_metadata();
+        let req = match build_request(&self.endpoint, request) {
+            Ok(req) => req,
+            Err(error) => {
+                return Box::pin(async move { Err(SumoLogicError::InvalidRequest { error }) })
+            }
+        };
+
+        let mut client = self.client.clone();
+        let throttled_until = Arc::clone(&self.throttled_until);
+
+        Box::pin(async move {
+            let until = *throttled_until.lock().expect("poisoned lock");
+            if let Some(until) = until {
+                tokio::time::sleep_until(until).await;
+            }
+
+            match client.call(req).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        Ok(SumoLogicResponse { metadata })
+                    } else {
+                        if status == StatusCode::TOO_MANY_REQUESTS {
+                            // All requests are held, as the limits apply to the whole account.
+                            if let Some(delay) = retry_after(response.headers(), Utc::now()) {
+                                let until = Instant::now() + delay.min(MAX_RETRY_AFTER);
+                                let mut throttled_until =
+                                    throttled_until.lock().expect("poisoned lock");
+                                *throttled_until =
+                                    Some(throttled_until.map_or(until, |held| held.max(until)));
+                            }
+                        }
+                        Err(SumoLogicError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(SumoLogicError::HttpError { error }),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: SumoHeaders) -> SumoLogicRequest {
+        SumoLogicRequest {
+            headers,
+            content_type: Some("application/vnd.sumologic.carbon2"),
+            compression: Compression::gzip_default(),
+            finalizers: EventFinalizers::default(),
+            payload: Bytes::from_static(b"metric=cpu  1 1685620800\n"),
+            metadata: RequestMetadata::default(),
+        }
+    }
+
+    #[test]
+    fn sets_sumo_headers() {
+        let endpoint: UriSerde =
+            "https://endpoint1.collection.us2.sumologic.com/receiver/v1/http/token"
+                .parse()
+                .unwrap();
+        let req = build_request(
+            &endpoint,
+            request(SumoHeaders {
+                category: Some("prod/nginx".to_owned()),
+                fields: Some("env=prod,service=web".to_owned()),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+        let headers = req.headers();
+        assert_eq!(headers["X-Sumo-Category"], "prod/nginx");
+        assert_eq!(headers["X-Sumo-Fields"], "env=prod,service=web");
+        assert!(!headers.contains_key("X-Sumo-Name"));
+        assert_eq!(
+            headers[header::CONTENT_TYPE],
+            "application/vnd.sumologic.carbon2"
+        );
+        assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+    }
+
+    #[test]
+    fn rejects_invalid_header_values() {
+        let endpoint: UriSerde =
+            "https://endpoint1.collection.us2.sumologic.com/receiver/v1/http/token"
+                .parse()
+                .unwrap();
+        assert!(build_request(
+            &endpoint,
+            request(SumoHeaders {
+                name: Some("line\nbreak".to_owned()),
+                ..Default::default()
+            }),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn retries_throttling_and_server_errors() {
+        let logic = SumoLogicRetryLogic;
+        let retriable = |code| logic.is_retriable_error(&SumoLogicError::ServerError { code });
+        assert!(retriable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(retriable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!retriable(StatusCode::UNAUTHORIZED));
+        assert!(!retriable(StatusCode::NOT_FOUND));
+    }
+}
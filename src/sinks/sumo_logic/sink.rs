@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, num::NonZeroUsize};
+
+use crate::{
+    internal_events::SinkRequestBuildError,
+    sinks::{
+        prelude::*, prometheus::remote_write::PrometheusMetricNormalize,
+        util::buffer::metrics::MetricNormalizer,
+    },
+};
+
+use super::{
+    request_builder::SumoLogicRequestBuilder,
+    service::{SumoLogicRetryLogic, SumoLogicService},
+};
+
+/// The templates of the `X-Sumo-*` headers.
+pub(super) struct SumoTemplates {
+    pub(super) category: Option<Template>,
+    pub(super) name: Option<Template>,
+    pub(super) host: Option<Template>,
+    pub(super) fields: BTreeMap<String, Template>,
+}
+
+/// The rendered values of the `X-Sumo-*` headers of a request.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SumoHeaders {
+    pub category: Option<String>,
+    pub name: Option<String>,
+    pub host: Option<String>,
+    /// The fields, formatted as `<key>=<value>` pairs separated by commas.
+    pub fields: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(super) enum EventKind {
+    Log,
+    Metric,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct PartitionKey {
+    pub(super) kind: EventKind,
+    pub(super) headers: SumoHeaders,
+}
+
+fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+impl Partitioner for SumoTemplates {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let render_optional = |template: &Option<Template>, field| match template {
+            Some(template) => render(template, item, field).map(Some),
+            None => Some(None),
+        };
+
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for (key, template) in &self.fields {
+            fields.push(format!("{}={}", key, render(template, item, "fields")?));
+        }
+
+        let headers = SumoHeaders {
+            category: render_optional(&self.category, "source_category")?,
+            name: render_optional(&self.name, "source_name")?,
+            host: render_optional(&self.host, "source_host")?,
+            fields: (!fields.is_empty()).then(|| fields.join(",")),
+        };
+        let kind = match item {
+            Event::Metric(_) => EventKind::Metric,
+            _ => EventKind::Log,
+        };
+        Some(PartitionKey { kind, headers })
+    }
+}
+
+pub struct SumoLogicSink {
+    templates: SumoTemplates,
+    batch_settings: BatcherSettings,
+    request_builder: SumoLogicRequestBuilder,
+    service: Svc<SumoLogicService, SumoLogicRetryLogic>,
+}
+
+impl SumoLogicSink {
+    pub(super) const fn new(
+        templates: SumoTemplates,
+        batch_settings: BatcherSettings,
+        request_builder: SumoLogicRequestBuilder,
+        service: Svc<SumoLogicService, SumoLogicRetryLogic>,
+    ) -> Self {
+        Self {
+            templates,
+            batch_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+        // Incremental metrics are made absolute, as both formats expect them to be.
+        let mut normalizer = MetricNormalizer::<PrometheusMetricNormalize>::default();
+        input
+            .filter_map(move |event| {
+                future::ready(match event {
+                    Event::Metric(metric) => normalizer.normalize(metric).map(Event::Metric),
+                    event => Some(event),
+                })
+            })
+            .batched_partitioned(self.templates, self.batch_settings)
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for SumoLogicSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partitions_by_rendered_headers() {
+        let templates = SumoTemplates {
+            category: Some(Template::try_from("{{ .environment }}/nginx").unwrap()),
+            name: None,
+            host: None,
+            fields: BTreeMap::from([
+                ("env".to_owned(), Template::try_from("prod").unwrap()),
+                (
+                    "service".to_owned(),
+                    Template::try_from("{{ .service }}").unwrap(),
+                ),
+            ]),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("environment", "staging");
+        log.insert("service", "web");
+        let key = templates.partition(&Event::Log(log)).unwrap();
+        assert_eq!(key.kind, EventKind::Log);
+        assert_eq!(
+            key.headers,
+            SumoHeaders {
+                category: Some("staging/nginx".to_owned()),
+                fields: Some("env=prod,service=web".to_owned()),
+                ..Default::default()
+            }
+        );
+
+        let mut log = LogEvent::from("hello");
+        log.insert("environment", "staging");
+        assert!(templates.partition(&Event::Log(log)).is_none());
+    }
+}
@@ -17,7 +17,10 @@ use crate::{
         AdaptiveConcurrencyAveragedRtt, AdaptiveConcurrencyInFlight, AdaptiveConcurrencyLimit,
         AdaptiveConcurrencyLimitData, AdaptiveConcurrencyObservedRtt,
     },
-    sinks::util::retries::{RetryAction, RetryLogic},
+    sinks::util::{
+        retries::{RetryAction, RetryLogic},
+        service::RequestLimits,
+    },
     stats::{EwmaVar, Mean, MeanVariance},
 };
 
@@ -29,6 +32,7 @@ pub(super) struct Controller<L> {
     concurrency: Option<usize>,
     settings: AdaptiveConcurrencySettings,
     logic: L,
+    limits: Option<RequestLimits>,
     pub(super) inner: Arc<Mutex<Inner>>,
     #[cfg(test)]
     pub(super) stats: Arc<Mutex<ControllerStatistics>>,
@@ -64,6 +68,7 @@ impl<L> Controller<L> {
         concurrency: Option<usize>,
         settings: AdaptiveConcurrencySettings,
         logic: L,
+        limits: Option<RequestLimits>,
     ) -> Self {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
@@ -75,6 +80,7 @@ impl<L> Controller<L> {
             concurrency,
             settings,
             logic,
+            limits,
             inner: Arc::new(Mutex::new(Inner {
                 current_limit,
                 in_flight: 0,
@@ -106,9 +112,36 @@ impl<L> Controller<L> {
     }
 
     pub(super) fn acquire(&self) -> impl Future<Output = OwnedSemaphorePermit> + Send + 'static {
+        self.apply_limits();
         Arc::clone(&self.semaphore).acquire()
     }
 
+    /// The concurrency set through the request limits of the sink, if any.
+    fn concurrency_override(&self) -> Option<usize> {
+        self.limits.as_ref().and_then(RequestLimits::concurrency)
+    }
+
+    /// Resizes the semaphore to the overridden concurrency, or back to the configured concurrency
+    /// once the override is removed.
+    ///
+    /// Adaptive concurrency resumes from the overridden limit.
+    fn apply_limits(&self) {
+        if self.limits.is_none() {
+            return;
+        }
+        let Some(target) = self.concurrency_override().or(self.concurrency) else {
+            return;
+        };
+
+        let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
+        if target > inner.current_limit {
+            self.semaphore.add_permits(target - inner.current_limit);
+        } else if target < inner.current_limit {
+            self.semaphore.forget_permits(inner.current_limit - target);
+        }
+        inner.current_limit = target;
+    }
+
     pub(super) fn start_request(&self) {
         let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
 
@@ -130,6 +163,7 @@ impl<L> Controller<L> {
     /// given (backpressure or not) and if it should be used as a valid
     /// RTT measurement.
     fn adjust_to_response_inner(&self, start: Instant, is_back_pressure: bool, use_rtt: bool) {
+        self.apply_limits();
         let now = instant_now();
         let mut inner = self.inner.lock().expect("Controller mutex is poisoned");
 
@@ -195,8 +229,9 @@ impl<L> Controller<L> {
                         self.averaged_rtt.emit(Duration::from_secs_f64(current_rtt));
                     }
 
-                    // Only manage the concurrency if `concurrency` was set to "adaptive"
-                    if self.concurrency.is_none() {
+                    // Only manage the concurrency if `concurrency` was set to "adaptive", and
+                    // isn't overridden
+                    if self.concurrency.is_none() && self.concurrency_override().is_none() {
                         self.manage_limit(&mut inner, past_rtt, current_rtt);
                     }
 
@@ -1,7 +1,7 @@
 use tower::Layer;
 
 use super::{AdaptiveConcurrencyLimit, AdaptiveConcurrencySettings};
-use crate::sinks::util::retries::RetryLogic;
+use crate::sinks::util::{retries::RetryLogic, service::RequestLimits};
 
 /// Enforces a limit on the concurrent number of requests the underlying
 /// service can handle.
@@ -10,6 +10,7 @@ pub struct AdaptiveConcurrencyLimitLayer<L> {
     concurrency: Option<usize>,
     options: AdaptiveConcurrencySettings,
     logic: L,
+    limits: Option<RequestLimits>,
 }
 
 impl<L> AdaptiveConcurrencyLimitLayer<L> {
@@ -23,14 +24,27 @@ impl<L> AdaptiveConcurrencyLimitLayer<L> {
             concurrency,
             options,
             logic,
+            limits: None,
         }
     }
+
+    /// Let the concurrency limit be overridden by the request limits of the sink.
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
 }
 
 impl<S, L: RetryLogic> Layer<S> for AdaptiveConcurrencyLimitLayer<L> {
     type Service = AdaptiveConcurrencyLimit<S, L>;
 
     fn layer(&self, service: S) -> Self::Service {
-        AdaptiveConcurrencyLimit::new(service, self.logic.clone(), self.concurrency, self.options)
+        AdaptiveConcurrencyLimit::new(
+            service,
+            self.logic.clone(),
+            self.concurrency,
+            self.options,
+            self.limits.clone(),
+        )
     }
 }
@@ -11,7 +11,7 @@ use tokio::sync::OwnedSemaphorePermit;
 use tower::{load::Load, Service};
 
 use super::{controller::Controller, future::ResponseFuture, AdaptiveConcurrencySettings};
-use crate::sinks::util::retries::RetryLogic;
+use crate::sinks::util::{retries::RetryLogic, service::RequestLimits};
 
 /// Enforces a limit on the concurrent number of requests the underlying
 /// service can handle. Automatically expands and contracts the actual
@@ -35,10 +35,11 @@ impl<S, L> AdaptiveConcurrencyLimit<S, L> {
         logic: L,
         concurrency: Option<usize>,
         options: AdaptiveConcurrencySettings,
+        limits: Option<RequestLimits>,
     ) -> Self {
         AdaptiveConcurrencyLimit {
             inner,
-            controller: Arc::new(Controller::new(concurrency, options, logic)),
+            controller: Arc::new(Controller::new(concurrency, options, logic, limits)),
             state: State::Empty,
         }
     }
@@ -3,6 +3,7 @@ use std::{
     future::Future,
     hash::Hash,
     marker::PhantomData,
+    num::NonZeroUsize,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -10,13 +11,15 @@ use std::{
 };
 
 use bytes::{Buf, Bytes};
+use chrono::{DateTime, Utc};
 use futures::{future::BoxFuture, Sink};
 use headers::HeaderName;
-use http::{header, HeaderValue, StatusCode};
+use http::{header, HeaderMap, HeaderValue, StatusCode};
 use hyper::{body, Body};
 use indexmap::IndexMap;
 use pin_project::pin_project;
 use snafu::{ResultExt, Snafu};
+use tokio::sync::Semaphore;
 use tower::{Service, ServiceBuilder};
 use tower_http::decompression::DecompressionLayer;
 use vector_config::configurable_component;
@@ -46,6 +49,16 @@ pub trait HttpSink: Send + Sync + 'static {
 
     fn build_encoder(&self) -> Self::Encoder;
     async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>>;
+
+    /// The maximum number of requests built at the same time, each on its own task.
+    ///
+    /// By default, requests are built on the task driving the sink.
+    fn build_concurrency(&self) -> Option<NonZeroUsize> {
+        None
+    }
+
+    /// Inspects every response of the server, whether successful or not.
+    fn observe_response(&self, _response: &http::Response<Bytes>) {}
 }
 
 /// Provides a simple wrapper around internal tower and
@@ -132,7 +145,10 @@ where
             Box::pin(async move { sink.build_request(b).await })
         };
 
-        let svc = HttpBatchService::new(client, request_builder);
+        let sink2 = Arc::clone(&sink);
+        let svc = HttpBatchService::new(client, request_builder)
+            .with_build_concurrency(sink.build_concurrency())
+            .with_response_observer(move |response| sink2.observe_response(response));
         let inner = request_settings.batch_sink(retry_logic, svc, batch, batch_timeout);
         let encoder = sink.build_encoder();
 
@@ -276,7 +292,10 @@ where
             Box::pin(async move { sink.build_request(b).await })
         };
 
-        let svc = HttpBatchService::new(client, request_builder);
+        let sink2 = Arc::clone(&sink);
+        let svc = HttpBatchService::new(client, request_builder)
+            .with_build_concurrency(sink.build_concurrency())
+            .with_response_observer(move |response| sink2.observe_response(response));
         let inner = request_settings.partition_sink(retry_logic, svc, batch, batch_timeout);
         let encoder = sink.build_encoder();
 
@@ -358,6 +377,8 @@ where
 pub struct HttpBatchService<F, B = Bytes> {
     inner: HttpClient<Body>,
     request_builder: Arc<dyn Fn(B) -> F + Send + Sync>,
+    build_permits: Option<Arc<Semaphore>>,
+    response_observer: Option<Arc<dyn Fn(&http::Response<Bytes>) + Send + Sync>>,
 }
 
 impl<F, B> HttpBatchService<F, B> {
@@ -368,8 +389,27 @@ impl<F, B> HttpBatchService<F, B> {
         HttpBatchService {
             inner,
             request_builder: Arc::new(Box::new(request_builder)),
+            build_permits: None,
+            response_observer: None,
         }
     }
+
+    /// Builds requests on their own tasks, at most `concurrency` at the same time, so that
+    /// CPU-bound encoding and compression of several requests runs in parallel.
+    pub fn with_build_concurrency(mut self, concurrency: Option<NonZeroUsize>) -> Self {
+        self.build_permits =
+            concurrency.map(|concurrency| Arc::new(Semaphore::new(concurrency.get())));
+        self
+    }
+
+    /// Calls `observer` with every response of the server.
+    pub fn with_response_observer(
+        mut self,
+        observer: impl Fn(&http::Response<Bytes>) + Send + Sync + 'static,
+    ) -> Self {
+        self.response_observer = Some(Arc::new(observer));
+        self
+    }
 }
 
 impl<F, B> Service<B> for HttpBatchService<F, B>
@@ -387,10 +427,24 @@ where
 
     fn call(&mut self, body: B) -> Self::Future {
         let request_builder = Arc::clone(&self.request_builder);
+        let build_permits = self.build_permits.clone();
+        let response_observer = self.response_observer.clone();
         let http_client = self.inner.clone();
 
         Box::pin(async move {
-            let request = request_builder(body).await.map_err(|error| {
+            let request = match build_permits {
+                Some(permits) => {
+                    let _permit = permits
+                        .acquire_owned()
+                        .await
+                        .expect("Build semaphore is never closed");
+                    tokio::spawn(request_builder(body))
+                        .await
+                        .unwrap_or_else(|error| Err(error.into()))
+                }
+                None => request_builder(body).await,
+            };
+            let request = request.map_err(|error| {
                 emit!(SinkRequestBuildError { error: &error });
                 error
             })?;
@@ -416,10 +470,11 @@ where
 
             let (parts, body) = response.into_parts();
             let mut body = body::aggregate(body).await?;
-            Ok(hyper::Response::from_parts(
-                parts,
-                body.copy_to_bytes(body.remaining()),
-            ))
+            let response = hyper::Response::from_parts(parts, body.copy_to_bytes(body.remaining()));
+            if let Some(observer) = response_observer {
+                observer(&response);
+            }
+            Ok(response)
         })
     }
 }
@@ -429,6 +484,8 @@ impl<F, B> Clone for HttpBatchService<F, B> {
         Self {
             inner: self.inner.clone(),
             request_builder: Arc::clone(&self.request_builder),
+            build_permits: self.build_permits.clone(),
+            response_observer: self.response_observer.clone(),
         }
     }
 }
@@ -598,6 +655,19 @@ pub fn validate_headers(
     Ok(validated_headers)
 }
 
+/// Parses the `Retry-After` header, either a number of seconds or an HTTP date.
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => DateTime::parse_from_rfc2822(value).ok().map(|date| {
+            (date.with_timezone(&Utc) - now)
+                .to_std()
+                .unwrap_or_default()
+        }),
+    }
+}
+
 #[cfg(test)]
 mod test {
     #![allow(clippy::print_stderr)] //tests
@@ -611,6 +681,33 @@ mod test {
     use super::*;
     use crate::{config::ProxyConfig, test_util::next_addr};
 
+    #[test]
+    fn util_http_parses_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RETRY_AFTER, value.parse().unwrap());
+            headers
+        };
+
+        assert_eq!(
+            retry_after(&headers("120"), now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry_after(&headers("Wed, 21 Oct 2015 07:28:30 GMT"), now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry_after(&headers("Wed, 21 Oct 2015 07:27:00 GMT"), now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(retry_after(&headers("soon"), now), None);
+        assert_eq!(retry_after(&HeaderMap::new(), now), None);
+    }
+
     #[test]
     fn util_http_retry_logic() {
         let logic = HttpRetryLogic;
@@ -632,6 +729,15 @@ mod test {
 
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
+        makes_http_requests(None).await;
+    }
+
+    #[tokio::test]
+    async fn util_http_it_makes_http_requests_built_on_tasks() {
+        makes_http_requests(NonZeroUsize::new(2)).await;
+    }
+
+    async fn makes_http_requests(build_concurrency: Option<NonZeroUsize>) {
         let addr = next_addr();
 
         let uri = format!("http://{}:{}/", addr.ip(), addr.port())
@@ -645,7 +751,8 @@ mod test {
             Box::pin(ready(
                 http::Request::post(&uri).body(body).map_err(Into::into),
             ))
-        });
+        })
+        .with_build_concurrency(build_concurrency);
 
         let (tx, rx) = futures::channel::mpsc::channel(10);
 
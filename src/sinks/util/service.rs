@@ -7,7 +7,6 @@ use tower::{
     buffer::{Buffer, BufferLayer},
     discover::Change,
     layer::{util::Stack, Layer},
-    limit::RateLimit,
     retry::Retry,
     timeout::Timeout,
     Service, ServiceBuilder,
@@ -17,6 +16,10 @@ use vector_config::configurable_component;
 pub use crate::sinks::util::service::{
     concurrency::{concurrency_is_none, Concurrency},
     health::{HealthConfig, HealthLogic, HealthService},
+    limits::{
+        DynamicRateLimit, DynamicRateLimitLayer, RequestLimits, RequestLimitsConfig,
+        RequestLimitsController,
+    },
     map::Map,
 };
 use crate::{
@@ -34,15 +37,17 @@ use crate::{
 
 mod concurrency;
 mod health;
+mod limits;
 mod map;
 pub mod net;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> =
+    DynamicRateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
 // Distributed service types
-pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
+pub type DistributedService<S, RL, HL, K, Req> = DynamicRateLimit<
     Retry<FixedRetryPolicy<RL>, Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>>,
 >;
 pub type DiscoveryService<S, RL, HL, K> =
@@ -265,6 +270,7 @@ impl TowerRequestConfig {
                     .unwrap(),
             ),
             adaptive_concurrency: self.adaptive_concurrency,
+            limits: RequestLimits::current(),
         }
     }
 }
@@ -279,6 +285,8 @@ pub struct TowerRequestSettings {
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    /// The overrides of the rate limit and concurrency, adjusted while the sink is running.
+    pub limits: RequestLimits,
 }
 
 impl TowerRequestSettings {
@@ -366,11 +374,14 @@ impl TowerRequestSettings {
             .map(|(endpoint, inner)| {
                 // Build individual service
                 ServiceBuilder::new()
-                    .layer(AdaptiveConcurrencyLimitLayer::new(
-                        self.concurrency,
-                        self.adaptive_concurrency,
-                        retry_logic.clone(),
-                    ))
+                    .layer(
+                        AdaptiveConcurrencyLimitLayer::new(
+                            self.concurrency,
+                            self.adaptive_concurrency,
+                            retry_logic.clone(),
+                        )
+                        .with_limits(self.limits.clone()),
+                    )
                     .service(
                         health_config.build(
                             health_logic.clone(),
@@ -388,7 +399,11 @@ impl TowerRequestSettings {
 
         // Build sink service
         ServiceBuilder::new()
-            .rate_limit(self.rate_limit_num, self.rate_limit_duration)
+            .layer(DynamicRateLimitLayer::new(
+                self.rate_limit_num,
+                self.rate_limit_duration,
+                self.limits.clone(),
+            ))
             .retry(policy)
             .layer(BufferLayer::new(max_concurrency))
             .service(Balance::new(Box::pin(stream::iter(services)) as Pin<Box<_>>))
@@ -416,15 +431,19 @@ where
     fn layer(&self, inner: S) -> Self::Service {
         let policy = self.settings.retry_policy(self.retry_logic.clone());
         ServiceBuilder::new()
-            .rate_limit(
+            .layer(DynamicRateLimitLayer::new(
                 self.settings.rate_limit_num,
                 self.settings.rate_limit_duration,
-            )
-            .layer(AdaptiveConcurrencyLimitLayer::new(
-                self.settings.concurrency,
-                self.settings.adaptive_concurrency,
-                self.retry_logic.clone(),
+                self.settings.limits.clone(),
             ))
+            .layer(
+                AdaptiveConcurrencyLimitLayer::new(
+                    self.settings.concurrency,
+                    self.settings.adaptive_concurrency,
+                    self.retry_logic.clone(),
+                )
+                .with_limits(self.settings.limits.clone()),
+            )
             .retry(policy)
             .timeout(self.settings.timeout)
             .service(inner)
@@ -0,0 +1,604 @@
+//! Request limits that can be adjusted while a sink is running.
+//!
+//! The rate limit and concurrency of a sink are set from its `request` options when it's built, and
+//! can then be overridden through the API, or by expressions evaluated over the internal metrics of
+//! the sink.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, Weak},
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::time::{Instant, Sleep};
+use tower::{Layer, Service};
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+use vector_core::compile_vrl;
+use vrl::{
+    compiler::{
+        runtime::Runtime, CompilationResult, CompileConfig, Program, TargetValue, TypeState,
+    },
+    diagnostic::Formatter,
+    value::{Secrets, Value},
+};
+
+use crate::{
+    config::ComponentKey,
+    event::{Metric, MetricValue},
+    internal_events::RequestLimitsEvaluationError,
+};
+
+tokio::task_local! {
+    /// The sink being built, whose request limits are registered under its key.
+    static CURRENT_SINK: ComponentKey;
+}
+
+static REGISTRY: Lazy<Mutex<HashMap<ComponentKey, Weak<Mutex<Overrides>>>>> =
+    Lazy::new(Default::default);
+
+/// Overrides of the request limits configured for a sink.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Overrides {
+    pub rate_limit_num: Option<u64>,
+    pub concurrency: Option<usize>,
+    /// Whether the sink has `ordering = "strict"`, which pins its concurrency to 1.
+    pub strict_ordering: bool,
+}
+
+/// A handle on the request limits of a sink, shared by all of its services.
+#[derive(Clone, Debug, Default)]
+pub struct RequestLimits {
+    overrides: Arc<Mutex<Overrides>>,
+}
+
+impl RequestLimits {
+    /// Runs the build of a sink, registering the request limits it creates under its key.
+    pub async fn scope<F: Future>(key: ComponentKey, build: F) -> F::Output {
+        CURRENT_SINK.scope(key, build).await
+    }
+
+    /// Gets the request limits of the sink being built, or unregistered limits outside of a build.
+    ///
+    /// The services of a sink share the same limits, so that they can be adjusted together.
+    pub fn current() -> Self {
+        CURRENT_SINK
+            .try_with(|key| {
+                let mut registry = REGISTRY.lock().expect("Request limits mutex is poisoned");
+                registry.retain(|_, overrides| overrides.strong_count() > 0);
+                match registry.get(key).and_then(Weak::upgrade) {
+                    Some(overrides) => Self { overrides },
+                    None => {
+                        let limits = Self::default();
+                        registry.insert(key.clone(), Arc::downgrade(&limits.overrides));
+                        limits
+                    }
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets the key of the sink being built, if any.
+    pub fn current_sink() -> Option<ComponentKey> {
+        CURRENT_SINK.try_with(Clone::clone).ok()
+    }
+
+    /// Gets the request limits of a running sink.
+    pub fn get(key: &ComponentKey) -> Option<Self> {
+        REGISTRY
+            .lock()
+            .expect("Request limits mutex is poisoned")
+            .get(key)
+            .and_then(Weak::upgrade)
+            .map(|overrides| Self { overrides })
+    }
+
+    pub fn overrides(&self) -> Overrides {
+        *self.lock()
+    }
+
+    pub fn rate_limit_num(&self) -> Option<u64> {
+        self.lock().rate_limit_num
+    }
+
+    pub fn concurrency(&self) -> Option<usize> {
+        let overrides = self.lock();
+        if overrides.strict_ordering {
+            Some(1)
+        } else {
+            overrides.concurrency
+        }
+    }
+
+    /// Overrides the `request.rate_limit_num` of the sink, or restores it with `None`.
+    pub fn set_rate_limit_num(&self, rate_limit_num: Option<u64>) {
+        self.lock().rate_limit_num = rate_limit_num.map(|num| num.max(1));
+    }
+
+    /// Overrides the `request.concurrency` of the sink, or restores it with `None`.
+    pub fn set_concurrency(&self, concurrency: Option<usize>) {
+        self.lock().concurrency = concurrency.map(|concurrency| concurrency.max(1));
+    }
+
+    /// Sends the requests of the sink one at a time, whatever its concurrency.
+    pub fn set_strict_ordering(&self) {
+        self.lock().strict_ordering = true;
+    }
+
+    pub fn strict_ordering(&self) -> bool {
+        self.lock().strict_ordering
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Overrides> {
+        self.overrides
+            .lock()
+            .expect("Request limits mutex is poisoned")
+    }
+}
+
+/// Enforces a rate limit on the requests of the underlying service, which can be overridden by
+/// the request limits of the sink.
+///
+/// Requests are limited to `num` per window of `per`, and an overridden limit takes effect from
+/// the next window. The clones of the service share the same window, so that they are limited
+/// together.
+pub struct DynamicRateLimit<S> {
+    inner: S,
+    num: u64,
+    per: Duration,
+    limits: RequestLimits,
+    window: Arc<Mutex<Window>>,
+    /// Whether `poll_ready` reserved a request of the window for the next call.
+    reserved: bool,
+    sleep: Pin<Box<Sleep>>,
+}
+
+#[derive(Debug)]
+struct Window {
+    until: Instant,
+    rem: u64,
+}
+
+impl<S> DynamicRateLimit<S> {
+    pub fn new(inner: S, num: u64, per: Duration, limits: RequestLimits) -> Self {
+        let until = Instant::now();
+        let rem = limits.rate_limit_num().unwrap_or(num);
+        Self {
+            inner,
+            num,
+            per,
+            limits,
+            window: Arc::new(Mutex::new(Window { until, rem })),
+            reserved: false,
+            sleep: Box::pin(tokio::time::sleep_until(until)),
+        }
+    }
+
+    fn window_num(&self) -> u64 {
+        self.limits.rate_limit_num().unwrap_or(self.num)
+    }
+}
+
+impl<S, Request> Service<Request> for DynamicRateLimit<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        while !self.reserved {
+            let now = Instant::now();
+            let mut window = self.window.lock().expect("Rate limit mutex is poisoned");
+            if now >= window.until {
+                window.until = now + self.per;
+                window.rem = self.window_num();
+            }
+
+            if window.rem > 0 {
+                window.rem -= 1;
+                self.reserved = true;
+            } else {
+                let until = window.until;
+                drop(window);
+                self.sleep.as_mut().reset(until);
+                ready!(self.sleep.as_mut().poll(cx));
+            }
+        }
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if !self.reserved {
+            panic!("Rate limit exceeded; poll_ready must be called first");
+        }
+        self.reserved = false;
+        self.inner.call(request)
+    }
+}
+
+impl<S: Clone> Clone for DynamicRateLimit<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            num: self.num,
+            per: self.per,
+            limits: self.limits.clone(),
+            window: Arc::clone(&self.window),
+            reserved: false,
+            sleep: Box::pin(tokio::time::sleep_until(Instant::now())),
+        }
+    }
+}
+
+/// Enforces a rate limit that can be overridden by the request limits of the sink.
+#[derive(Clone, Debug)]
+pub struct DynamicRateLimitLayer {
+    num: u64,
+    per: Duration,
+    limits: RequestLimits,
+}
+
+impl DynamicRateLimitLayer {
+    pub const fn new(num: u64, per: Duration, limits: RequestLimits) -> Self {
+        Self { num, per, limits }
+    }
+}
+
+impl<S> Layer<S> for DynamicRateLimitLayer {
+    type Service = DynamicRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DynamicRateLimit::new(inner, self.num, self.per, self.limits.clone())
+    }
+}
+
+/// Request limits computed from the internal metrics of the sink.
+///
+/// The expressions are written in [Vector Remap Language][vrl] (VRL), and evaluated against an
+/// object with the current value of each counter and gauge of the sink under `.metrics`, and the
+/// per-second rate of each counter since the last evaluation under `.rates`, such as
+/// `.rates.component_errors_total`. The values of the metrics with the same name are summed up.
+///
+/// An expression evaluates to an integer to override the corresponding `request` option, or to
+/// `null` to restore it.
+///
+/// [vrl]: https://vector.dev/docs/reference/vrl
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RequestLimitsConfig {
+    /// An expression computing the maximum number of requests allowed within the
+    /// `request.rate_limit_duration_secs` time window.
+    #[configurable(metadata(
+        docs::examples = "if (float(.rates.component_errors_total) ?? 0.0) > 1 { 10 } else { null }"
+    ))]
+    pub rate_limit_num: Option<String>,
+
+    /// An expression computing the number of concurrent requests allowed.
+    #[configurable(metadata(
+        docs::examples = "if (float(.metrics.buffer_events) ?? 0.0) > 10000 { 50 } else { null }"
+    ))]
+    pub concurrency: Option<String>,
+
+    /// The interval between evaluations of the expressions.
+    #[serde(default = "default_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Evaluation Interval"))]
+    pub interval_secs: u64,
+}
+
+const fn default_interval_secs() -> u64 {
+    10
+}
+
+impl RequestLimitsConfig {
+    /// Compiles the expressions, returning `None` if there are none to evaluate.
+    pub fn build(&self) -> crate::Result<Option<RequestLimitsController>> {
+        if self.rate_limit_num.is_none() && self.concurrency.is_none() {
+            return Ok(None);
+        }
+        if self.interval_secs == 0 {
+            return Err("`request_limits.interval_secs` must be greater than zero".into());
+        }
+
+        Ok(Some(RequestLimitsController {
+            rate_limit_num: self.rate_limit_num.as_deref().map(compile).transpose()?,
+            concurrency: self.concurrency.as_deref().map(compile).transpose()?,
+            interval: Duration::from_secs(self.interval_secs),
+        }))
+    }
+}
+
+fn compile(source: &str) -> crate::Result<Program> {
+    let mut functions = vrl::stdlib::all()
+        .into_iter()
+        .chain(vector_vrl_functions::all())
+        .collect::<Vec<_>>();
+    vector_core::fips::retain_approved_vrl_functions(&mut functions);
+
+    let mut config = CompileConfig::default();
+    config.set_read_only();
+
+    let CompilationResult {
+        program, warnings, ..
+    } = compile_vrl(source, &functions, &TypeState::default(), config)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+/// Evaluates the request limit expressions of a sink on an interval.
+pub struct RequestLimitsController {
+    rate_limit_num: Option<Program>,
+    concurrency: Option<Program>,
+    interval: Duration,
+}
+
+impl RequestLimitsController {
+    /// Adjusts the request limits of the sink until it's dropped.
+    pub async fn run(self, key: ComponentKey, limits: RequestLimits) {
+        let overrides = Arc::downgrade(&limits.overrides);
+        drop(limits);
+
+        let controller = match crate::metrics::Controller::get() {
+            Ok(controller) => controller,
+            Err(error) => {
+                warn!(message = "Request limits can't be computed without internal metrics.", %error);
+                return;
+            }
+        };
+
+        let mut interval = tokio::time::interval(self.interval);
+        let mut previous: Option<(Instant, BTreeMap<String, f64>)> = None;
+        loop {
+            interval.tick().await;
+            let limits = match overrides.upgrade() {
+                Some(overrides) => RequestLimits { overrides },
+                None => break,
+            };
+
+            let now = Instant::now();
+            let (counters, gauges) = sink_metrics(&key, controller.capture_metrics());
+            let rates = match &previous {
+                Some((then, previous)) => rates(&counters, previous, now - *then),
+                None => BTreeMap::new(),
+            };
+            let object = Value::from(BTreeMap::from([
+                (
+                    "metrics".to_owned(),
+                    counters
+                        .iter()
+                        .chain(gauges.iter())
+                        .map(|(name, value)| (name.clone(), Value::from(*value)))
+                        .collect::<BTreeMap<_, _>>()
+                        .into(),
+                ),
+                ("rates".to_owned(), rates.into()),
+            ]));
+            previous = Some((now, counters));
+
+            if let Some(program) = &self.rate_limit_num {
+                if let Some(num) = evaluate(program, &object, "rate_limit_num") {
+                    limits.set_rate_limit_num(num);
+                }
+            }
+            if let Some(program) = &self.concurrency {
+                if let Some(concurrency) = evaluate(program, &object, "concurrency") {
+                    limits.set_concurrency(concurrency.map(|concurrency| concurrency as usize));
+                }
+            }
+        }
+    }
+}
+
+/// Sums up the counters and gauges of the sink by name.
+fn sink_metrics(
+    key: &ComponentKey,
+    metrics: Vec<Metric>,
+) -> (BTreeMap<String, f64>, BTreeMap<String, f64>) {
+    let mut counters = BTreeMap::new();
+    let mut gauges = BTreeMap::new();
+    for metric in metrics {
+        if metric.tag_value("component_id").as_deref() != Some(key.id()) {
+            continue;
+        }
+        let (totals, value) = match metric.value() {
+            MetricValue::Counter { value } => (&mut counters, *value),
+            MetricValue::Gauge { value } => (&mut gauges, *value),
+            _ => continue,
+        };
+        *totals.entry(metric.name().to_owned()).or_insert(0.0) += value;
+    }
+    (counters, gauges)
+}
+
+fn rates(
+    counters: &BTreeMap<String, f64>,
+    previous: &BTreeMap<String, f64>,
+    elapsed: Duration,
+) -> BTreeMap<String, Value> {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    counters
+        .iter()
+        .map(|(name, value)| {
+            let delta = value - previous.get(name).copied().unwrap_or(0.0);
+            (name.clone(), Value::from(delta.max(0.0) / secs))
+        })
+        .collect()
+}
+
+/// Evaluates an expression, returning the limit to set, or `None` if it failed.
+fn evaluate(program: &Program, object: &Value, limit: &'static str) -> Option<Option<u64>> {
+    let mut target = TargetValue {
+        value: object.clone(),
+        metadata: Value::Object(BTreeMap::new()),
+        secrets: Secrets::new(),
+    };
+    let result = Runtime::default().resolve(&mut target, program, &TimeZone::default());
+    let error = match result {
+        Ok(Value::Null) => return Some(None),
+        Ok(Value::Integer(value)) if value > 0 => return Some(Some(value as u64)),
+        Ok(value) => format!("expected a positive integer or null, got {}", value),
+        Err(error) => error.to_string(),
+    };
+    emit!(RequestLimitsEvaluationError {
+        limit,
+        error: &error,
+    });
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::{self, Ready};
+    use tokio::time::{advance, pause};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn shares_limits_between_services_of_a_sink() {
+        let key = ComponentKey::from("limited_sink");
+        let (first, second) = RequestLimits::scope(key.clone(), async {
+            (RequestLimits::current(), RequestLimits::current())
+        })
+        .await;
+
+        first.set_rate_limit_num(Some(5));
+        assert_eq!(second.rate_limit_num(), Some(5));
+        assert_eq!(RequestLimits::get(&key).unwrap().rate_limit_num(), Some(5));
+
+        drop((first, second));
+        assert!(RequestLimits::get(&key).is_none());
+        assert_eq!(RequestLimits::current().rate_limit_num(), None);
+    }
+
+    #[test]
+    fn strict_ordering_pins_concurrency() {
+        let limits = RequestLimits::default();
+        limits.set_concurrency(Some(10));
+        assert_eq!(limits.concurrency(), Some(10));
+
+        limits.set_strict_ordering();
+        assert_eq!(limits.concurrency(), Some(1));
+        limits.set_concurrency(None);
+        assert_eq!(limits.concurrency(), Some(1));
+    }
+
+    fn remaining<S>(service: &DynamicRateLimit<S>) -> u64 {
+        service.window.lock().unwrap().rem
+    }
+
+    #[tokio::test]
+    async fn rate_limit_follows_overrides() {
+        pause();
+
+        let limits = RequestLimits::default();
+        let inner = tower::service_fn(|()| -> Ready<Result<(), ()>> { future::ok(()) });
+        let mut service = DynamicRateLimit::new(inner, 1, Duration::from_secs(1), limits.clone());
+
+        service.ready().await.unwrap().call(()).await.unwrap();
+        assert!(futures::poll!(service.ready()).is_pending());
+
+        limits.set_rate_limit_num(Some(3));
+        advance(Duration::from_secs(1)).await;
+        for rem in [2, 1, 0] {
+            service.ready().await.unwrap().call(()).await.unwrap();
+            assert_eq!(remaining(&service), rem);
+        }
+        assert!(futures::poll!(service.ready()).is_pending());
+    }
+
+    #[tokio::test]
+    async fn clones_share_the_window() {
+        pause();
+
+        let inner = tower::service_fn(|()| -> Ready<Result<(), ()>> { future::ok(()) });
+        let mut service =
+            DynamicRateLimit::new(inner, 2, Duration::from_secs(1), RequestLimits::default());
+        service.ready().await.unwrap().call(()).await.unwrap();
+
+        let mut clone = service.clone();
+        clone.ready().await.unwrap().call(()).await.unwrap();
+        assert!(futures::poll!(clone.ready()).is_pending());
+        assert!(futures::poll!(service.ready()).is_pending());
+
+        advance(Duration::from_secs(1)).await;
+        clone.ready().await.unwrap().call(()).await.unwrap();
+        assert_eq!(remaining(&service), 1);
+    }
+
+    #[test]
+    fn evaluates_expressions_over_metrics() {
+        let config: RequestLimitsConfig = toml::from_str(
+            r#"
+            rate_limit_num = "if (float(.rates.component_errors_total) ?? 0.0) > 1 { 10 } else { null }"
+            concurrency = "to_int!(.metrics.buffer_events) + 1"
+            "#,
+        )
+        .unwrap();
+        let controller = config.build().unwrap().unwrap();
+
+        let object = |errors: f64| {
+            Value::from(BTreeMap::from([
+                (
+                    "metrics".to_owned(),
+                    Value::from(BTreeMap::from([(
+                        "buffer_events".to_owned(),
+                        Value::from(4.0),
+                    )])),
+                ),
+                (
+                    "rates".to_owned(),
+                    Value::from(BTreeMap::from([(
+                        "component_errors_total".to_owned(),
+                        Value::from(errors),
+                    )])),
+                ),
+            ]))
+        };
+
+        let rate_limit_num = controller.rate_limit_num.as_ref().unwrap();
+        assert_eq!(
+            evaluate(rate_limit_num, &object(5.0), "rate_limit_num"),
+            Some(Some(10))
+        );
+        assert_eq!(
+            evaluate(rate_limit_num, &object(0.0), "rate_limit_num"),
+            Some(None)
+        );
+        let concurrency = controller.concurrency.as_ref().unwrap();
+        assert_eq!(
+            evaluate(concurrency, &object(0.0), "concurrency"),
+            Some(Some(5))
+        );
+    }
+
+    #[test]
+    fn computes_counter_rates() {
+        let counters = BTreeMap::from([("component_errors_total".to_owned(), 30.0)]);
+        let previous = BTreeMap::from([("component_errors_total".to_owned(), 10.0)]);
+        let rates = rates(&counters, &previous, Duration::from_secs(10));
+        assert_eq!(rates["component_errors_total"], Value::from(2.0));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        let config = RequestLimitsConfig {
+            concurrency: Some("if {".to_owned()),
+            ..Default::default()
+        };
+        assert!(config.build().is_err());
+    }
+}
@@ -121,12 +121,15 @@ pub enum NetError {
     ServiceSocketChannelClosed,
 }
 
+/// Resolves once the records of the host of a connection change.
+type RecordsChanged = BoxFuture<'static, ()>;
+
 enum NetworkServiceState {
     /// The service is currently disconnected.
     Disconnected,
 
     /// The service is currently attempting to connect to the endpoint.
-    Connecting(BoxFuture<'static, NetworkConnection>),
+    Connecting(BoxFuture<'static, (NetworkConnection, Option<RecordsChanged>)>),
 
     /// The service is connected and idle.
     Connected(NetworkConnection),
@@ -217,20 +220,30 @@ impl NetworkConnector {
         emit!(SocketOutgoingConnectionError { error });
     }
 
-    async fn connect(&self) -> Result<(NetworkConnection, Option<ConnectionMetadata>), NetError> {
+    async fn connect(
+        &self,
+    ) -> Result<
+        (
+            NetworkConnection,
+            Option<ConnectionMetadata>,
+            Option<RecordsChanged>,
+        ),
+        NetError,
+    > {
         match &self.inner {
             ConnectorType::Tcp(connector) => {
-                let (peer_addr, stream) = connector.connect().await?;
+                let (peer_addr, stream, records_changed) = connector.connect().await?;
 
                 Ok((
                     NetworkConnection::Tcp(stream),
                     Some(ConnectionMetadata::Tcp { peer_addr }),
+                    Some(records_changed),
                 ))
             }
             ConnectorType::Udp(connector) => {
-                let socket = connector.connect().await?;
+                let (socket, records_changed) = connector.connect().await?;
 
-                Ok((NetworkConnection::Udp(socket), None))
+                Ok((NetworkConnection::Udp(socket), None, Some(records_changed)))
             }
             #[cfg(unix)]
             ConnectorType::Unix(connector) => {
@@ -239,12 +252,13 @@ impl NetworkConnector {
                 Ok((
                     NetworkConnection::Unix(socket),
                     Some(ConnectionMetadata::Unix { path }),
+                    None,
                 ))
             }
         }
     }
 
-    async fn connect_backoff(&self) -> NetworkConnection {
+    async fn connect_backoff(&self) -> (NetworkConnection, Option<RecordsChanged>) {
         // TODO: Make this configurable.
         let mut backoff = ExponentialBackoff::from_millis(2)
             .factor(250)
@@ -252,12 +266,12 @@ impl NetworkConnector {
 
         loop {
             match self.connect().await {
-                Ok((connection, maybe_metadata)) => {
+                Ok((connection, maybe_metadata, records_changed)) => {
                     if let Some(metadata) = maybe_metadata {
                         self.on_connected(metadata);
                     }
 
-                    return connection;
+                    return (connection, records_changed);
                 }
                 Err(error) => {
                     self.on_connection_error(error);
@@ -285,6 +299,7 @@ impl NetworkConnector {
 pub struct NetworkService {
     connector: NetworkConnector,
     state: NetworkServiceState,
+    records_changed: Option<RecordsChanged>,
 }
 
 impl NetworkService {
@@ -292,6 +307,7 @@ impl NetworkService {
         Self {
             connector,
             state: NetworkServiceState::Disconnected,
+            records_changed: None,
         }
     }
 }
@@ -311,10 +327,18 @@ impl Service<Vec<u8>> for NetworkService {
                     }))
                 }
                 NetworkServiceState::Connecting(fut) => {
-                    let socket = ready!(fut.poll_unpin(cx));
+                    let (socket, records_changed) = ready!(fut.poll_unpin(cx));
+                    self.records_changed = records_changed;
                     NetworkServiceState::Connected(socket)
                 }
-                NetworkServiceState::Connected(_) => break,
+                NetworkServiceState::Connected(_) => {
+                    // The connection is dropped once the records of its host change, to connect
+                    // to the new records instead.
+                    match self.records_changed.as_mut().map(|fut| fut.poll_unpin(cx)) {
+                        Some(Poll::Ready(())) => NetworkServiceState::Disconnected,
+                        _ => break,
+                    }
+                }
                 NetworkServiceState::Sending(fut) => {
                     match ready!(fut.poll_unpin(cx)) {
                         // When a send concludes, and there's an error, the request future sends
@@ -1,5 +1,6 @@
 use std::net::SocketAddr;
 
+use futures_util::FutureExt;
 use snafu::ResultExt;
 use tokio::net::TcpStream;
 
@@ -11,7 +12,7 @@ use vector_core::{
 
 use crate::dns;
 
-use super::{net_error::*, ConnectorType, HostAndPort, NetError, NetworkConnector};
+use super::{net_error::*, ConnectorType, HostAndPort, NetError, NetworkConnector, RecordsChanged};
 
 /// TCP configuration.
 #[configurable_component]
@@ -66,23 +67,29 @@ pub(super) struct TcpConnector {
 }
 
 impl TcpConnector {
+    /// Connects to the first address of the host accepting the connection.
     pub(super) async fn connect(
         &self,
-    ) -> Result<(SocketAddr, MaybeTlsStream<TcpStream>), NetError> {
-        let ip = dns::Resolver
+    ) -> Result<(SocketAddr, MaybeTlsStream<TcpStream>, RecordsChanged), NetError> {
+        let records = dns::Resolver
             .lookup_ip(self.address.host.clone())
             .await
-            .context(FailedToResolve)?
-            .next()
-            .ok_or(NetError::NoAddresses)?;
-
-        let addr = SocketAddr::new(ip, self.address.port);
+            .context(FailedToResolve)?;
 
         let tls = MaybeTlsSettings::from_config(&self.tls, false).context(FailedToConfigureTLS)?;
-        let mut stream = tls
-            .connect(self.address.host.as_str(), &addr)
-            .await
-            .context(FailedToConnectTLS)?;
+        let mut connected = Err(NetError::NoAddresses);
+        for ip in records.clone() {
+            let addr = SocketAddr::new(ip, self.address.port);
+            connected = tls
+                .connect(self.address.host.as_str(), &addr)
+                .await
+                .context(FailedToConnectTLS)
+                .map(|stream| (addr, stream));
+            if connected.is_ok() {
+                break;
+            }
+        }
+        let (addr, mut stream) = connected?;
 
         if let Some(send_buffer_size) = self.send_buffer_size {
             if let Err(error) = stream.set_send_buffer_bytes(send_buffer_size) {
@@ -96,6 +103,7 @@ impl TcpConnector {
             }
         }
 
-        Ok((addr, stream))
+        let records_changed = records.changed(self.address.host.clone()).boxed();
+        Ok((addr, stream, records_changed))
     }
 }
@@ -1,5 +1,6 @@
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
+use futures_util::FutureExt;
 use snafu::ResultExt;
 use tokio::net::UdpSocket;
 
@@ -7,7 +8,7 @@ use vector_config::configurable_component;
 
 use crate::{dns, net};
 
-use super::{net_error::*, ConnectorType, HostAndPort, NetError, NetworkConnector};
+use super::{net_error::*, ConnectorType, HostAndPort, NetError, NetworkConnector, RecordsChanged};
 
 /// UDP configuration.
 #[configurable_component]
@@ -50,13 +51,12 @@ pub(super) struct UdpConnector {
 }
 
 impl UdpConnector {
-    pub(super) async fn connect(&self) -> Result<UdpSocket, NetError> {
-        let ip = dns::Resolver
+    pub(super) async fn connect(&self) -> Result<(UdpSocket, RecordsChanged), NetError> {
+        let records = dns::Resolver
             .lookup_ip(self.address.host.clone())
             .await
-            .context(FailedToResolve)?
-            .next()
-            .ok_or(NetError::NoAddresses)?;
+            .context(FailedToResolve)?;
+        let ip = records.clone().next().ok_or(NetError::NoAddresses)?;
 
         let addr = SocketAddr::new(ip, self.address.port);
         let bind_address = find_bind_address(&addr);
@@ -71,7 +71,8 @@ impl UdpConnector {
 
         socket.connect(addr).await.context(FailedToConnect)?;
 
-        Ok(socket)
+        let records_changed = records.changed(self.address.host.clone()).boxed();
+        Ok((socket, records_changed))
     }
 }
 
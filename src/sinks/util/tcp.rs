@@ -1,7 +1,9 @@
 use std::{
+    fmt,
     io::ErrorKind,
     net::SocketAddr,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -49,10 +51,19 @@ enum TcpError {
     DnsError { source: dns::DnsError },
     #[snafu(display("No addresses returned."))]
     NoAddresses,
+    #[snafu(display("Handshake error: {}", source))]
+    HandshakeError { source: tokio::io::Error },
     #[snafu(display("Send error: {}", source))]
     SendError { source: tokio::io::Error },
 }
 
+/// An exchange with the server on each new connection, before any event is sent to it, such as
+/// to authenticate.
+#[async_trait]
+pub trait TcpHandshake: fmt::Debug + Send + Sync {
+    async fn handshake(&self, stream: &mut MaybeTlsStream<TcpStream>) -> tokio::io::Result<()>;
+}
+
 /// A TCP sink.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -108,12 +119,25 @@ impl TcpSinkConfig {
         &self,
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.build_with_handshake(transformer, encoder, None)
+    }
+
+    /// Builds the sink, with a handshake run on each new connection.
+    pub fn build_with_handshake(
+        &self,
+        transformer: Transformer,
+        encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+        handshake: Option<Arc<dyn TcpHandshake>>,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
         let uri = self.address.parse::<http::Uri>()?;
         let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
         let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+        let connector = TcpConnector {
+            handshake,
+            ..TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes)
+        };
         let sink = TcpSink::new(connector.clone(), transformer, encoder);
 
         Ok((
@@ -130,6 +154,7 @@ struct TcpConnector {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: MaybeTlsSettings,
     send_buffer_bytes: Option<usize>,
+    handshake: Option<Arc<dyn TcpHandshake>>,
 }
 
 impl TcpConnector {
@@ -146,6 +171,7 @@ impl TcpConnector {
             keepalive,
             tls,
             send_buffer_bytes,
+            handshake: None,
         }
     }
 
@@ -161,45 +187,58 @@ impl TcpConnector {
             .max_delay(Duration::from_secs(60))
     }
 
-    async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
-        let ip = dns::Resolver
+    /// Connects to the first address of the host accepting the connection, returning the
+    /// records of the host to notice when they change.
+    async fn connect(&self) -> Result<(MaybeTlsStream<TcpStream>, dns::LookupIp), TcpError> {
+        let records = dns::Resolver
             .lookup_ip(self.host.clone())
             .await
-            .context(DnsSnafu)?
-            .next()
-            .ok_or(TcpError::NoAddresses)?;
+            .context(DnsSnafu)?;
+
+        let mut error = TcpError::NoAddresses;
+        for ip in records.clone() {
+            let addr = SocketAddr::new(ip, self.port);
+            let mut maybe_tls = match self.tls.connect(&self.host, &addr).await {
+                Ok(maybe_tls) => maybe_tls,
+                Err(source) => {
+                    error = TcpError::ConnectError { source };
+                    continue;
+                }
+            };
 
-        let addr = SocketAddr::new(ip, self.port);
-        self.tls
-            .connect(&self.host, &addr)
-            .await
-            .context(ConnectSnafu)
-            .map(|mut maybe_tls| {
-                if let Some(keepalive) = self.keepalive {
-                    if let Err(error) = maybe_tls.set_keepalive(keepalive) {
-                        warn!(message = "Failed configuring TCP keepalive.", %error);
-                    }
+            if let Some(keepalive) = self.keepalive {
+                if let Err(error) = maybe_tls.set_keepalive(keepalive) {
+                    warn!(message = "Failed configuring TCP keepalive.", %error);
                 }
+            }
 
-                if let Some(send_buffer_bytes) = self.send_buffer_bytes {
-                    if let Err(error) = maybe_tls.set_send_buffer_bytes(send_buffer_bytes) {
-                        warn!(message = "Failed configuring send buffer size on TCP socket.", %error);
-                    }
+            if let Some(send_buffer_bytes) = self.send_buffer_bytes {
+                if let Err(error) = maybe_tls.set_send_buffer_bytes(send_buffer_bytes) {
+                    warn!(message = "Failed configuring send buffer size on TCP socket.", %error);
                 }
+            }
+
+            if let Some(handshake) = &self.handshake {
+                handshake
+                    .handshake(&mut maybe_tls)
+                    .await
+                    .context(HandshakeSnafu)?;
+            }
 
-                maybe_tls
-            })
+            return Ok((maybe_tls, records));
+        }
+        Err(error)
     }
 
-    async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
+    async fn connect_backoff(&self) -> (MaybeTlsStream<TcpStream>, dns::LookupIp) {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
-                Ok(socket) => {
+                Ok((socket, records)) => {
                     emit!(TcpSocketConnectionEstablished {
                         peer_addr: socket.peer_addr().ok(),
                     });
-                    return socket;
+                    return (socket, records);
                 }
                 Err(error) => {
                     emit!(TcpSocketOutgoingConnectionError { error });
@@ -235,9 +274,12 @@ where
         }
     }
 
-    async fn connect(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
-        let stream = self.connector.connect_backoff().await;
-        BytesSink::new(stream, Self::shutdown_check, SocketMode::Tcp)
+    async fn connect(&self) -> (BytesSink<MaybeTlsStream<TcpStream>>, dns::LookupIp) {
+        let (stream, records) = self.connector.connect_backoff().await;
+        (
+            BytesSink::new(stream, Self::shutdown_check, SocketMode::Tcp),
+            records,
+        )
     }
 
     fn shutdown_check(stream: &mut MaybeTlsStream<TcpStream>) -> ShutdownCheck {
@@ -296,10 +338,16 @@ where
         });
 
         while let Some(item) = input.next().await {
-            let mut sink = self.connect().await;
+            let (mut sink, records) = self.connect().await;
             let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
 
-            let mut mapped_input = stream::once(ready(item)).chain(&mut input).map(Ok);
+            // The connection is closed once the records of the host change, and reopened to
+            // the new records with the next event.
+            let records_changed = Box::pin(records.changed(self.connector.host.clone()));
+            let mut mapped_input = stream::once(ready(item))
+                .chain(&mut input)
+                .take_until(records_changed)
+                .map(Ok);
 
             let result = match sink.send_all(&mut mapped_input).await {
                 Ok(()) => sink.close().await,
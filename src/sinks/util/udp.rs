@@ -117,13 +117,14 @@ impl UdpConnector {
             .max_delay(Duration::from_secs(60))
     }
 
-    async fn connect(&self) -> Result<UdpSocket, UdpError> {
-        let ip = dns::Resolver
+    /// Connects to the first address of the host, returning the records of the host to notice
+    /// when they change.
+    async fn connect(&self) -> Result<(UdpSocket, dns::LookupIp), UdpError> {
+        let records = dns::Resolver
             .lookup_ip(self.host.clone())
             .await
-            .context(DnsSnafu)?
-            .next()
-            .ok_or(UdpError::NoAddresses)?;
+            .context(DnsSnafu)?;
+        let ip = records.clone().next().ok_or(UdpError::NoAddresses)?;
 
         let addr = SocketAddr::new(ip, self.port);
         let bind_address = find_bind_address(&addr);
@@ -138,16 +139,16 @@ impl UdpConnector {
 
         socket.connect(addr).await.context(ConnectSnafu)?;
 
-        Ok(socket)
+        Ok((socket, records))
     }
 
-    async fn connect_backoff(&self) -> UdpSocket {
+    async fn connect_backoff(&self) -> (UdpSocket, dns::LookupIp) {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
-                Ok(socket) => {
+                Ok((socket, records)) => {
                     emit!(UdpSocketConnectionEstablished {});
-                    return socket;
+                    return (socket, records);
                 }
                 Err(error) => {
                     emit!(UdpSocketOutgoingConnectionError { error });
@@ -196,8 +197,11 @@ where
 
         let mut encoder = self.encoder.clone();
         while Pin::new(&mut input).peek().await.is_some() {
-            let mut socket = self.connector.connect_backoff().await;
-            while let Some(mut event) = input.next().await {
+            let (mut socket, records) = self.connector.connect_backoff().await;
+            // The socket is connected again once the records of the host change.
+            let mut events =
+                (&mut input).take_until(Box::pin(records.changed(self.connector.host.clone())));
+            while let Some(mut event) = events.next().await {
                 let byte_size = event.estimated_json_encoded_size_of();
 
                 self.transformer.transform(&mut event);
@@ -18,6 +18,12 @@ impl From<CompressionLevel> for ZstdCompressionLevel {
     }
 }
 
+impl ZstdCompressionLevel {
+    pub const fn level(&self) -> i32 {
+        self.0
+    }
+}
+
 impl Display for ZstdCompressionLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -16,6 +16,7 @@ use crate::{
         AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext,
         SinkHealthcheckOptions,
     },
+    dns::HttpResolver,
     proto::vector as proto,
     sinks::{
         util::{
@@ -25,6 +26,7 @@ use crate::{
         Healthcheck, VectorSink as VectorSinkType,
     },
     tls::{tls_connector_builder, MaybeTlsSettings, TlsEnableableConfig},
+    zstd_dictionary::{ZstdDictionaryConfig, ZstdDictionaryNegotiation},
 };
 
 /// Configuration for the `vector` sink.
@@ -57,6 +59,15 @@ pub struct VectorConfig {
     #[serde(default)]
     compression: bool,
 
+    /// A trained zstd dictionary to compress the events of requests with.
+    ///
+    /// Dictionaries greatly reduce the size of small batches of similar events. The dictionary is
+    /// only used once the downstream Vector instance lists it among its
+    /// `compression_dictionaries`, and events are sent without it until then.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    compression_dictionary: Option<ZstdDictionaryConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
@@ -97,6 +108,7 @@ fn default_config(address: &str) -> VectorConfig {
         version: None,
         address: address.to_owned(),
         compression: false,
+        compression_dictionary: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -121,7 +133,13 @@ impl SinkConfig for VectorConfig {
             .unwrap_or_else(|| uri.clone());
         let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let dictionary = self
+            .compression_dictionary
+            .as_ref()
+            .map(|dictionary| dictionary.load(zstd::DEFAULT_COMPRESSION_LEVEL))
+            .transpose()?
+            .map(ZstdDictionaryNegotiation::new);
+        let service = VectorService::new(client, uri, self.compression).with_dictionary(dictionary);
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -205,11 +223,14 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
     }
 }
 
-fn new_client(
+/// Builds the HTTP/2 client gRPC requests are sent with.
+pub(crate) fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
-) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
-    let mut http = HttpConnector::new();
+) -> crate::Result<
+    hyper::Client<ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>, BoxBody>,
+> {
+    let mut http = HttpConnector::new_with_resolver(HttpResolver);
     http.enforce_http(false);
 
     let tls = tls_connector_builder(tls_settings)?;
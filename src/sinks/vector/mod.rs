@@ -6,7 +6,9 @@ mod config;
 mod service;
 mod sink;
 
+pub(crate) use config::new_client;
 pub use config::VectorConfig;
+pub(crate) use service::HyperSvc;
 
 /// Marker type for the version two of the configuration for the `vector` sink.
 #[configurable_component]
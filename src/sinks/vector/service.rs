@@ -6,7 +6,7 @@ use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use prost::Message;
-use tonic::{body::BoxBody, IntoRequest};
+use tonic::{body::BoxBody, metadata::MetadataValue, IntoRequest};
 use tower::Service;
 use vector_common::{
     json_size::JsonSize,
@@ -16,10 +16,12 @@ use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
 use super::VectorSinkError;
 use crate::{
+    dns::HttpResolver,
     event::{EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
     proto::vector as proto_vector,
     sinks::util::uri,
+    zstd_dictionary::{ZstdDictionaryNegotiation, DICTIONARY_HEADER},
     Error,
 };
 
@@ -28,6 +30,7 @@ pub struct VectorService {
     pub client: proto_vector::Client<HyperSvc>,
     pub protocol: String,
     pub endpoint: String,
+    dictionary: Option<ZstdDictionaryNegotiation>,
 }
 
 pub struct VectorResponse {
@@ -66,15 +69,15 @@ impl MetaDescriptive for VectorRequest {
 
 impl VectorService {
     pub fn new(
-        hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+        hyper_client: hyper::Client<
+            ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>,
+            BoxBody,
+        >,
         uri: Uri,
         compression: bool,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
-        let mut proto_client = proto_vector::Client::new(HyperSvc {
-            uri,
-            client: hyper_client,
-        });
+        let mut proto_client = proto_vector::Client::new(HyperSvc::new(uri, hyper_client));
 
         if compression {
             proto_client = proto_client.send_compressed(tonic::codec::CompressionEncoding::Gzip);
@@ -83,6 +86,58 @@ impl VectorService {
             client: proto_client,
             protocol,
             endpoint,
+            dictionary: None,
+        }
+    }
+
+    /// Compresses the events of requests with the zstd dictionary, once the downstream Vector
+    /// instance accepts it.
+    pub fn with_dictionary(mut self, dictionary: Option<ZstdDictionaryNegotiation>) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+
+    /// Builds the request to send, compressing its events with the dictionary if it is accepted.
+    fn build_request(
+        &self,
+        request: proto_vector::PushEventsRequest,
+    ) -> tonic::Request<proto_vector::PushEventsRequest> {
+        let dictionary = self
+            .dictionary
+            .as_ref()
+            .and_then(ZstdDictionaryNegotiation::dictionary);
+        if let Some(dictionary) = dictionary {
+            match dictionary.compress(&request.encode_to_vec()) {
+                Ok(compressed_events) => {
+                    let mut compressed = proto_vector::PushEventsRequest {
+                        events: Vec::new(),
+                        compressed_events,
+                    }
+                    .into_request();
+                    compressed
+                        .metadata_mut()
+                        .insert(DICTIONARY_HEADER, MetadataValue::from(dictionary.id()));
+                    return compressed;
+                }
+                Err(error) => {
+                    warn!(
+                        message = "Failed compressing events with zstd dictionary, sending them uncompressed.",
+                        %error,
+                        internal_log_rate_limit = true
+                    );
+                }
+            }
+        }
+        request.into_request()
+    }
+
+    fn observe_dictionaries(&self, metadata: &tonic::metadata::MetadataMap) {
+        if let Some(dictionary) = &self.dictionary {
+            dictionary.observe(
+                metadata
+                    .get(DICTIONARY_HEADER)
+                    .and_then(|value| value.to_str().ok()),
+            );
         }
     }
 }
@@ -104,18 +159,20 @@ impl Service<VectorRequest> for VectorService {
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
     fn call(&mut self, list: VectorRequest) -> Self::Future {
-        let mut service = self.clone();
-        let byte_size = list.request.encoded_len();
+        let service = self.clone();
         let events_count = list.get_metadata().event_count();
         let events_byte_size = list
             .get_metadata()
             .events_estimated_json_encoded_byte_size();
+        let request = service.build_request(list.request);
+        let byte_size = request.get_ref().encoded_len();
 
         let future = async move {
-            service
-                .client
-                .push_events(list.request.into_request())
-                .map_ok(|_response| {
+            let mut client = service.client.clone();
+            client
+                .push_events(request)
+                .map_ok(|response| {
+                    service.observe_dictionaries(response.metadata());
                     emit!(EndpointBytesSent {
                         byte_size,
                         protocol: &service.protocol,
@@ -126,7 +183,14 @@ impl Service<VectorRequest> for VectorService {
                         events_byte_size,
                     }
                 })
-                .map_err(|source| VectorSinkError::Request { source }.into())
+                .map_err(|source| {
+                    // The downstream instance lists its dictionaries when rejecting one, which
+                    // is retried without it.
+                    if source.metadata().contains_key(DICTIONARY_HEADER) {
+                        service.observe_dictionaries(source.metadata());
+                    }
+                    VectorSinkError::Request { source }.into()
+                })
                 .await
         };
 
@@ -137,7 +201,17 @@ impl Service<VectorRequest> for VectorService {
 #[derive(Clone, Debug)]
 pub struct HyperSvc {
     uri: Uri,
-    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>, BoxBody>,
+}
+
+impl HyperSvc {
+    /// Sends the gRPC requests of a client to `uri`, through the HTTP/2 client.
+    pub(crate) const fn new(
+        uri: Uri,
+        client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector<HttpResolver>>>, BoxBody>,
+    ) -> Self {
+        Self { uri, client }
+    }
 }
 
 impl Service<hyper::Request<BoxBody>> for HyperSvc {
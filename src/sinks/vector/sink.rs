@@ -72,6 +72,7 @@ where
 
                 let encoded_events = proto_vector::PushEventsRequest {
                     events: event_collection.events,
+                    ..Default::default()
                 };
 
                 let byte_size = encoded_events.encoded_len();
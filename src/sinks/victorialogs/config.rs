@@ -0,0 +1,234 @@
+use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+
+use crate::{
+    config::log_schema,
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prelude::*,
+        util::{RealtimeSizeBasedDefaultBatchSettings, UriSerde},
+    },
+};
+
+use super::{
+    healthcheck::healthcheck,
+    request_builder::VictoriaLogsRequestBuilder,
+    service::{Tenant, VictoriaLogsRetryLogic, VictoriaLogsService},
+    sink::VictoriaLogsSink,
+};
+
+/// Configuration for the `victorialogs` sink.
+#[configurable_component(sink("victorialogs", "Deliver log events to VictoriaLogs."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VictoriaLogsConfig {
+    /// The base URL of the VictoriaLogs instance.
+    ///
+    /// The JSON stream API path, `/insert/jsonline`, is appended to this URL.
+    #[configurable(metadata(docs::examples = "http://localhost:9428"))]
+    pub endpoint: UriSerde,
+
+    /// The event fields that identify the log stream of an event.
+    ///
+    /// Events with the same values for these fields belong to the same stream, much like Loki labels.
+    /// Stream fields should have a low number of distinct values.
+    ///
+    /// If empty, all events are written to the same stream.
+    #[configurable(metadata(docs::examples = "host"))]
+    #[configurable(metadata(docs::examples = "kubernetes.pod_name"))]
+    #[serde(default)]
+    pub stream_fields: Vec<String>,
+
+    /// The event field holding the log timestamp.
+    ///
+    /// Defaults to the global `log_schema.timestamp_key` option.
+    #[configurable(metadata(docs::examples = "timestamp"))]
+    pub time_field: Option<String>,
+
+    /// The event field holding the log message.
+    ///
+    /// Defaults to the global `log_schema.message_key` option.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub message_field: Option<String>,
+
+    /// The `AccountID` of the tenant to ingest logs into.
+    ///
+    /// If unset, VictoriaLogs uses the default tenant.
+    #[configurable(metadata(docs::examples = 12))]
+    pub account_id: Option<u32>,
+
+    /// The `ProjectID` of the tenant to ingest logs into.
+    ///
+    /// If unset, VictoriaLogs uses the default tenant.
+    #[configurable(metadata(docs::examples = 34))]
+    pub project_id: Option<u32>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for VictoriaLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:9428"
+            stream_fields = ["host"]
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+impl VictoriaLogsConfig {
+    fn build_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_pool(tls, cx.proxy(), cx.connection_pool())?;
+        Ok(client)
+    }
+
+    /// The query parameters that tell VictoriaLogs how to interpret the ingested fields.
+    fn ingest_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+
+        if !self.stream_fields.is_empty() {
+            query.push(("_stream_fields", self.stream_fields.join(",")));
+        }
+
+        let time_field = self
+            .time_field
+            .clone()
+            .or_else(|| log_schema().timestamp_key().map(ToString::to_string));
+        if let Some(time_field) = time_field {
+            query.push(("_time_field", time_field));
+        }
+
+        let message_field = self
+            .message_field
+            .clone()
+            .unwrap_or_else(|| log_schema().message_key().to_string());
+        query.push(("_msg_field", message_field));
+
+        query
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "victorialogs")]
+impl SinkConfig for VictoriaLogsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+        let endpoint = self.endpoint.with_default_parts().with_auth(auth);
+        let client = self.build_client(&cx)?;
+
+        let healthcheck = healthcheck(endpoint.clone(), client.clone()).boxed();
+
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let encoder = Encoder::<Framer>::new(
+            NewlineDelimitedEncoderConfig::default().build().into(),
+            JsonSerializerConfig::default().build().into(),
+        );
+        let request_builder =
+            VictoriaLogsRequestBuilder::new(self.compression, (self.encoding.clone(), encoder));
+
+        let tenant = Tenant {
+            account_id: self.account_id,
+            project_id: self.project_id,
+        };
+        let service = VictoriaLogsService::new(client, endpoint, &self.ingest_query(), tenant)?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, VictoriaLogsRetryLogic)
+            .service(service);
+
+        let sink = VictoriaLogsSink::new(batch_settings, request_builder, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VictoriaLogsConfig>();
+    }
+
+    #[test]
+    fn ingest_query_uses_log_schema_by_default() {
+        let cfg = toml::from_str::<VictoriaLogsConfig>(
+            r#"
+            endpoint = "http://localhost:9428"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.ingest_query(),
+            vec![
+                ("_time_field", "timestamp".to_string()),
+                ("_msg_field", "message".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ingest_query_with_stream_fields() {
+        let cfg = toml::from_str::<VictoriaLogsConfig>(
+            r#"
+            endpoint = "http://localhost:9428"
+            stream_fields = ["host", "kubernetes.pod_name"]
+            time_field = "ts"
+            message_field = "msg"
+            account_id = 12
+        "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.account_id, Some(12));
+        assert_eq!(
+            cfg.ingest_query(),
+            vec![
+                ("_stream_fields", "host,kubernetes.pod_name".to_string()),
+                ("_time_field", "ts".to_string()),
+                ("_msg_field", "msg".to_string()),
+            ]
+        );
+    }
+}
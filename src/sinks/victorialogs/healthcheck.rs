@@ -0,0 +1,19 @@
+use crate::{http::HttpClient, sinks::util::UriSerde};
+
+pub(super) async fn healthcheck(endpoint: UriSerde, client: HttpClient) -> crate::Result<()> {
+    let endpoint = endpoint.append_path("health")?;
+
+    let mut req = http::Request::get(endpoint.uri)
+        .body(hyper::Body::empty())
+        .expect("Building request never fails.");
+
+    if let Some(auth) = &endpoint.auth {
+        auth.apply(&mut req);
+    }
+
+    let status = client.send(req).await?.status();
+    match status {
+        http::StatusCode::OK => Ok(()),
+        _ => Err(format!("A non-successful status returned: {}", status).into()),
+    }
+}
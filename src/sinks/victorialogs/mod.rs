@@ -0,0 +1,16 @@
+//! VictoriaLogs sink
+//!
+//! This sink ships log events to VictoriaLogs through its JSON stream API,
+//! `POST /insert/jsonline`, as newline-delimited JSON.
+//!
+//! <https://docs.victoriametrics.com/VictoriaLogs/data-ingestion/#json-stream-api>
+//!
+//! Log streams are selected on the server side from the configured
+//! `stream_fields`, much like Loki labels.
+mod config;
+mod healthcheck;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::VictoriaLogsConfig;
@@ -0,0 +1,67 @@
+use std::io;
+
+use bytes::Bytes;
+use codecs::encoding::Framer;
+
+use crate::sinks::prelude::*;
+
+use super::service::VictoriaLogsRequest;
+
+#[derive(Clone)]
+pub(super) struct VictoriaLogsRequestBuilder {
+    compression: Compression,
+    encoder: (Transformer, Encoder<Framer>),
+}
+
+impl VictoriaLogsRequestBuilder {
+    pub(super) const fn new(
+        compression: Compression,
+        encoder: (Transformer, Encoder<Framer>),
+    ) -> Self {
+        Self {
+            compression,
+            encoder,
+        }
+    }
+}
+
+impl RequestBuilder<Vec<Event>> for VictoriaLogsRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = VictoriaLogsRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: Vec<Event>,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let mut events = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (finalizers, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        VictoriaLogsRequest {
+            compression: self.compression,
+            finalizers,
+            payload: payload.into_payload(),
+            metadata,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::StatusCode;
+use snafu::Snafu;
+use tracing::Instrument;
+
+use crate::{
+    http::HttpClient,
+    sinks::{prelude::*, util::UriSerde},
+};
+
+#[derive(Clone)]
+pub struct VictoriaLogsRetryLogic;
+
+impl RetryLogic for VictoriaLogsRetryLogic {
+    type Error = VictoriaLogsError;
+    type Response = VictoriaLogsResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            VictoriaLogsError::ServerError { code } => match *code {
+                StatusCode::TOO_MANY_REQUESTS => true,
+                StatusCode::NOT_IMPLEMENTED => false,
+                _ if code.is_server_error() => true,
+                _ => false,
+            },
+            VictoriaLogsError::HttpError { .. } => true,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum VictoriaLogsError {
+    #[snafu(display("Server responded with an error: {}", code))]
+    ServerError { code: StatusCode },
+    #[snafu(display("Failed to make HTTP(S) request: {}", error))]
+    HttpError { error: crate::http::HttpError },
+}
+
+#[derive(Debug, Snafu)]
+pub struct VictoriaLogsResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for VictoriaLogsResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> CountByteSize {
+        CountByteSize(
+            self.metadata.event_count(),
+            self.metadata.events_estimated_json_encoded_byte_size(),
+        )
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+#[derive(Clone)]
+pub struct VictoriaLogsRequest {
+    pub compression: Compression,
+    pub finalizers: EventFinalizers,
+    pub payload: Bytes,
+    pub metadata: RequestMetadata,
+}
+
+impl Finalizable for VictoriaLogsRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for VictoriaLogsRequest {
+    fn get_metadata(&self) -> RequestMetadata {
+        self.metadata
+    }
+}
+
+/// The tenant that ingested logs are assigned to.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tenant {
+    pub account_id: Option<u32>,
+    pub project_id: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VictoriaLogsService {
+    endpoint: UriSerde,
+    client: HttpClient,
+    tenant: Tenant,
+}
+
+impl VictoriaLogsService {
+    pub fn new(
+        client: HttpClient,
+        endpoint: UriSerde,
+        query: &[(&str, String)],
+        tenant: Tenant,
+    ) -> crate::Result<Self> {
+        let endpoint = endpoint.append_path(&ingest_path(query))?;
+
+        Ok(Self {
+            client,
+            endpoint,
+            tenant,
+        })
+    }
+}
+
+fn ingest_path(query: &[(&str, String)]) -> String {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in query {
+        serializer.append_pair(key, value);
+    }
+    let query = serializer.finish();
+
+    if query.is_empty() {
+        "insert/jsonline".to_string()
+    } else {
+        format!("insert/jsonline?{}", query)
+    }
+}
+
+impl Service<VictoriaLogsRequest> for VictoriaLogsService {
+    type Response = VictoriaLogsResponse;
+    type Error = VictoriaLogsError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: VictoriaLogsRequest) -> Self::Future {
+        let mut req = http::Request::post(&self.endpoint.uri)
+            .header("Content-Type", "application/stream+json");
+
+        if let Some(account_id) = self.tenant.account_id {
+            req = req.header("AccountID", account_id);
+        }
+
+        if let Some(project_id) = self.tenant.project_id {
+            req = req.header("ProjectID", project_id);
+        }
+
+        if let Some(ce) = request.compression.content_encoding() {
+            req = req.header("Content-Encoding", ce);
+        }
+
+        let metadata = request.get_metadata();
+        let body = hyper::Body::from(request.payload);
+        let mut req = req.body(body).unwrap();
+
+        if let Some(auth) = &self.endpoint.auth {
+            auth.apply(&mut req);
+        }
+
+        let mut client = self.client.clone();
+
+        Box::pin(async move {
+            match client.call(req).in_current_span().await {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        Ok(VictoriaLogsResponse { metadata })
+                    } else {
+                        Err(VictoriaLogsError::ServerError { code: status })
+                    }
+                }
+                Err(error) => Err(VictoriaLogsError::HttpError { error }),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_ingest_path() {
+        let path = ingest_path(&[
+            ("_stream_fields", "host,kubernetes.pod_name".to_string()),
+            ("_time_field", "timestamp".to_string()),
+            ("_msg_field", "message".to_string()),
+        ]);
+        assert_eq!(
+            path,
+            "insert/jsonline?_stream_fields=host%2Ckubernetes.pod_name&_time_field=timestamp&_msg_field=message"
+        );
+        assert_eq!(ingest_path(&[]), "insert/jsonline");
+    }
+}
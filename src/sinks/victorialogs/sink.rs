@@ -0,0 +1,54 @@
+use std::num::NonZeroUsize;
+
+use crate::{internal_events::SinkRequestBuildError, sinks::prelude::*};
+
+use super::{
+    request_builder::VictoriaLogsRequestBuilder,
+    service::{VictoriaLogsRetryLogic, VictoriaLogsService},
+};
+
+pub struct VictoriaLogsSink {
+    batch_settings: BatcherSettings,
+    request_builder: VictoriaLogsRequestBuilder,
+    service: Svc<VictoriaLogsService, VictoriaLogsRetryLogic>,
+}
+
+impl VictoriaLogsSink {
+    pub(super) const fn new(
+        batch_settings: BatcherSettings,
+        request_builder: VictoriaLogsRequestBuilder,
+        service: Svc<VictoriaLogsService, VictoriaLogsRetryLogic>,
+    ) -> Self {
+        Self {
+            batch_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+        input
+            .batched(self.batch_settings.into_byte_size_config())
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for VictoriaLogsSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -106,22 +106,30 @@ impl WebSocketConnector {
             .max_delay(Duration::from_secs(60))
     }
 
-    async fn tls_connect(&self) -> Result<MaybeTlsStream<TcpStream>, WebSocketError> {
-        let ip = dns::Resolver
+    /// Connects to the first address of the host accepting the connection, returning the
+    /// records of the host to notice when they change.
+    async fn tls_connect(
+        &self,
+    ) -> Result<(MaybeTlsStream<TcpStream>, dns::LookupIp), WebSocketError> {
+        let records = dns::Resolver
             .lookup_ip(self.host.clone())
             .await
-            .context(DnsSnafu)?
-            .next()
-            .ok_or(WebSocketError::NoAddresses)?;
-
-        let addr = SocketAddr::new(ip, self.port);
-        self.tls
-            .connect(&self.host, &addr)
-            .await
-            .context(ConnectSnafu)
+            .context(DnsSnafu)?;
+
+        let mut error = WebSocketError::NoAddresses;
+        for ip in records.clone() {
+            let addr = SocketAddr::new(ip, self.port);
+            match self.tls.connect(&self.host, &addr).await {
+                Ok(maybe_tls) => return Ok((maybe_tls, records)),
+                Err(source) => error = WebSocketError::ConnectError { source },
+            }
+        }
+        Err(error)
     }
 
-    async fn connect(&self) -> Result<WsStream<MaybeTlsStream<TcpStream>>, WebSocketError> {
+    async fn connect(
+        &self,
+    ) -> Result<(WsStream<MaybeTlsStream<TcpStream>>, dns::LookupIp), WebSocketError> {
         let mut request = (&self.uri)
             .into_client_request()
             .context(CreateFailedSnafu)?;
@@ -130,7 +138,7 @@ impl WebSocketConnector {
             auth.apply(&mut request);
         }
 
-        let maybe_tls = self.tls_connect().await?;
+        let (maybe_tls, records) = self.tls_connect().await?;
 
         let ws_config = WebSocketConfig {
             max_send_queue: None, // don't buffer messages
@@ -141,16 +149,16 @@ impl WebSocketConnector {
             .await
             .context(CreateFailedSnafu)?;
 
-        Ok(ws_stream)
+        Ok((ws_stream, records))
     }
 
-    async fn connect_backoff(&self) -> WsStream<MaybeTlsStream<TcpStream>> {
+    async fn connect_backoff(&self) -> (WsStream<MaybeTlsStream<TcpStream>>, dns::LookupIp) {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
-                Ok(ws_stream) => {
+                Ok(connection) => {
                     emit!(WsConnectionEstablished {});
-                    return ws_stream;
+                    return connection;
                 }
                 Err(error) => {
                     emit!(WsConnectionFailedError {
@@ -218,9 +226,11 @@ impl WebSocketSink {
     ) -> (
         impl Sink<Message, Error = WsError>,
         impl Stream<Item = Result<Message, WsError>>,
+        dns::LookupIp,
     ) {
-        let ws_stream = self.connector.connect_backoff().await;
-        ws_stream.split()
+        let (ws_stream, records) = self.connector.connect_backoff().await;
+        let (ws_sink, ws_stream) = ws_stream.split();
+        (ws_sink, ws_stream, records)
     }
 
     fn check_received_pong_time(&self, last_pong: Instant) -> Result<(), WsError> {
@@ -241,6 +251,7 @@ impl WebSocketSink {
         input: &mut I,
         ws_stream: &mut WS,
         ws_sink: &mut O,
+        records: dns::LookupIp,
     ) -> Result<(), ()>
     where
         I: Stream<Item = Event> + Unpin,
@@ -262,8 +273,15 @@ impl WebSocketSink {
         let bytes_sent = register!(BytesSent::from(Protocol("websocket".into())));
         let events_sent = register!(EventsSent::from(Output(None)));
 
+        // The connection is closed once the records of the host change, to reconnect to the new
+        // records.
+        let records_changed = records.changed(self.connector.host.clone());
+        pin_mut!(records_changed);
+
         loop {
             let result = tokio::select! {
+                _ = &mut records_changed => break,
+
                 _ = ping_interval.tick() => {
                     match self.check_received_pong_time(last_pong) {
                         Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await.map(|_| ()),
@@ -342,14 +360,14 @@ impl StreamSink<Event> for WebSocketSink {
         pin_mut!(input);
 
         while input.as_mut().peek().await.is_some() {
-            let (ws_sink, ws_stream) = self.create_sink_and_stream().await;
+            let (ws_sink, ws_stream, records) = self.create_sink_and_stream().await;
             pin_mut!(ws_sink);
             pin_mut!(ws_stream);
 
             let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
 
             if self
-                .handle_events(&mut input, &mut ws_stream, &mut ws_sink)
+                .handle_events(&mut input, &mut ws_stream, &mut ws_sink, records)
                 .await
                 .is_ok()
             {
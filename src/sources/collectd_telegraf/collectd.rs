@@ -0,0 +1,286 @@
+//! Parser for the [collectd binary protocol][protocol], as sent by its `network` plugin.
+//!
+//! [protocol]: https://collectd.org/wiki/index.php/Binary_protocol
+
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
+
+const PART_HOST: u16 = 0x0000;
+const PART_TIME: u16 = 0x0001;
+const PART_PLUGIN: u16 = 0x0002;
+const PART_PLUGIN_INSTANCE: u16 = 0x0003;
+const PART_TYPE: u16 = 0x0004;
+const PART_TYPE_INSTANCE: u16 = 0x0005;
+const PART_VALUES: u16 = 0x0006;
+const PART_TIME_HR: u16 = 0x0008;
+const PART_ENCRYPTION: u16 = 0x0210;
+
+const VALUE_COUNTER: u8 = 0;
+const VALUE_GAUGE: u8 = 1;
+const VALUE_DERIVE: u8 = 2;
+const VALUE_ABSOLUTE: u8 = 3;
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum ParseError {
+    #[snafu(display("Truncated collectd packet"))]
+    Truncated,
+
+    #[snafu(display("Invalid length {} for collectd part {:#06x}", length, part))]
+    InvalidLength { part: u16, length: usize },
+
+    #[snafu(display("Unknown collectd value type {}", value_type))]
+    UnknownValueType { value_type: u8 },
+
+    #[snafu(display("Encrypted collectd packets are not supported"))]
+    Encrypted,
+}
+
+/// The identifier of the values that follow it in a packet.
+///
+/// Parts only update the fields they carry, so that values sent in a row by the same plugin don't
+/// repeat them.
+#[derive(Debug, Default)]
+struct State {
+    host: String,
+    time: Option<DateTime<Utc>>,
+    plugin: String,
+    plugin_instance: String,
+    type_: String,
+    type_instance: String,
+}
+
+impl State {
+    fn metrics(&self, types: &[u8], values: &[u8]) -> Result<Vec<Metric>, ParseError> {
+        let name = if self.plugin == self.type_ {
+            self.plugin.clone()
+        } else {
+            format!("{}_{}", self.plugin, self.type_)
+        };
+
+        let mut tags = MetricTags::default();
+        for (tag, value) in [
+            ("host", &self.host),
+            ("plugin_instance", &self.plugin_instance),
+            ("type_instance", &self.type_instance),
+        ] {
+            if !value.is_empty() {
+                tags.replace(tag.to_owned(), value.clone());
+            }
+        }
+
+        types
+            .iter()
+            .zip(values.chunks_exact(8))
+            .enumerate()
+            .map(|(index, (value_type, value))| {
+                let value: [u8; 8] = value.try_into().expect("chunks are 8 bytes long");
+                let (kind, value) = match *value_type {
+                    VALUE_COUNTER => (
+                        MetricKind::Absolute,
+                        MetricValue::Counter {
+                            value: u64::from_be_bytes(value) as f64,
+                        },
+                    ),
+                    VALUE_GAUGE => (
+                        MetricKind::Absolute,
+                        MetricValue::Gauge {
+                            value: f64::from_le_bytes(value),
+                        },
+                    ),
+                    VALUE_DERIVE => (
+                        MetricKind::Absolute,
+                        MetricValue::Counter {
+                            value: i64::from_be_bytes(value) as f64,
+                        },
+                    ),
+                    VALUE_ABSOLUTE => (
+                        MetricKind::Incremental,
+                        MetricValue::Counter {
+                            value: u64::from_be_bytes(value) as f64,
+                        },
+                    ),
+                    value_type => return Err(ParseError::UnknownValueType { value_type }),
+                };
+
+                // The names of the values of multi-valued types are only known from the `types.db`
+                // of collectd, so they are told apart by their position.
+                let name = if types.len() > 1 {
+                    format!("{}_{}", name, index)
+                } else {
+                    name.clone()
+                };
+                Ok(Metric::new(name, kind, value)
+                    .with_namespace(Some("collectd"))
+                    .with_tags(Some(tags.clone()))
+                    .with_timestamp(self.time))
+            })
+            .collect()
+    }
+}
+
+fn string(part: u16, payload: &[u8]) -> Result<String, ParseError> {
+    match payload.split_last() {
+        Some((0, string)) => Ok(String::from_utf8_lossy(string).into_owned()),
+        _ => Err(ParseError::InvalidLength {
+            part,
+            length: payload.len(),
+        }),
+    }
+}
+
+fn number(part: u16, payload: &[u8]) -> Result<u64, ParseError> {
+    let bytes: [u8; 8] = payload.try_into().map_err(|_| ParseError::InvalidLength {
+        part,
+        length: payload.len(),
+    })?;
+    Ok(u64::from_be_bytes(bytes))
+}
+
+/// Parses the values of a collectd packet into metrics.
+///
+/// Notifications, signatures, and parts unknown to this parser are skipped.
+pub fn parse(mut packet: &[u8]) -> Result<Vec<Metric>, ParseError> {
+    let mut state = State::default();
+    let mut metrics = Vec::new();
+
+    while !packet.is_empty() {
+        if packet.len() < 4 {
+            return Err(ParseError::Truncated);
+        }
+        let part = u16::from_be_bytes([packet[0], packet[1]]);
+        let length = u16::from_be_bytes([packet[2], packet[3]]) as usize;
+        if length < 4 {
+            return Err(ParseError::InvalidLength { part, length });
+        }
+        if packet.len() < length {
+            return Err(ParseError::Truncated);
+        }
+        let payload = &packet[4..length];
+        packet = &packet[length..];
+
+        match part {
+            PART_HOST => state.host = string(part, payload)?,
+            PART_PLUGIN => state.plugin = string(part, payload)?,
+            PART_PLUGIN_INSTANCE => state.plugin_instance = string(part, payload)?,
+            PART_TYPE => state.type_ = string(part, payload)?,
+            PART_TYPE_INSTANCE => state.type_instance = string(part, payload)?,
+            PART_TIME => {
+                let seconds = number(part, payload)?;
+                state.time = Utc.timestamp_opt(seconds as i64, 0).single();
+            }
+            PART_TIME_HR => {
+                // High resolution times are in units of 2^-30 seconds.
+                let time = number(part, payload)?;
+                let nanos = ((time & 0x3fff_ffff) * 1_000_000_000) >> 30;
+                state.time = Utc
+                    .timestamp_opt((time >> 30) as i64, nanos as u32)
+                    .single();
+            }
+            PART_VALUES => {
+                let count = match payload {
+                    [high, low, ..] => u16::from_be_bytes([*high, *low]) as usize,
+                    _ => return Err(ParseError::InvalidLength { part, length }),
+                };
+                if payload.len() != 2 + count * 9 {
+                    return Err(ParseError::InvalidLength { part, length });
+                }
+                let (types, values) = payload[2..].split_at(count);
+                metrics.extend(state.metrics(types, values)?);
+            }
+            PART_ENCRYPTION => return Err(ParseError::Encrypted),
+            _ => {}
+        }
+    }
+
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    fn part(part: u16, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = part.to_be_bytes().to_vec();
+        bytes.extend((payload.len() as u16 + 4).to_be_bytes());
+        bytes.extend(payload);
+        bytes
+    }
+
+    fn string_part(kind: u16, value: &str) -> Vec<u8> {
+        part(kind, &[value.as_bytes(), &[0]].concat())
+    }
+
+    #[test]
+    fn parses_values() {
+        let mut packet = string_part(PART_HOST, "web-1");
+        packet.extend(part(
+            PART_TIME_HR,
+            &((1_600_000_000u64 << 30) + (1 << 29)).to_be_bytes(),
+        ));
+        packet.extend(string_part(PART_PLUGIN, "cpu"));
+        packet.extend(string_part(PART_PLUGIN_INSTANCE, "0"));
+        packet.extend(string_part(PART_TYPE, "cpu"));
+        packet.extend(string_part(PART_TYPE_INSTANCE, "idle"));
+        packet.extend(part(
+            PART_VALUES,
+            &[&[0, 1, VALUE_DERIVE][..], &42i64.to_be_bytes()].concat(),
+        ));
+        packet.extend(string_part(PART_PLUGIN, "load"));
+        packet.extend(string_part(PART_PLUGIN_INSTANCE, ""));
+        packet.extend(string_part(PART_TYPE, "load"));
+        packet.extend(string_part(PART_TYPE_INSTANCE, ""));
+        packet.extend(part(
+            PART_VALUES,
+            &[
+                &[0, 2, VALUE_GAUGE, VALUE_GAUGE][..],
+                &0.5f64.to_le_bytes(),
+                &0.25f64.to_le_bytes(),
+            ]
+            .concat(),
+        ));
+
+        let metrics = parse(&packet).unwrap();
+        assert_eq!(metrics.len(), 3);
+
+        let timestamp = Utc.timestamp_opt(1_600_000_000, 500_000_000).single();
+        assert_eq!(
+            metrics[0],
+            Metric::new(
+                "cpu",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 42.0 }
+            )
+            .with_namespace(Some("collectd"))
+            .with_tags(Some(MetricTags::from([
+                ("host".to_owned(), "web-1".to_owned()),
+                ("plugin_instance".to_owned(), "0".to_owned()),
+                ("type_instance".to_owned(), "idle".to_owned()),
+            ])))
+            .with_timestamp(timestamp)
+        );
+        assert_eq!(metrics[1].name(), "load_0");
+        assert_eq!(metrics[1].value(), &MetricValue::Gauge { value: 0.5 });
+        assert_eq!(metrics[2].name(), "load_1");
+        assert_eq!(metrics[2].tag_value("plugin_instance"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_packets() {
+        assert_eq!(parse(&[0, 0, 0]), Err(ParseError::Truncated));
+        assert_eq!(
+            parse(&part(PART_VALUES, &[0, 2, VALUE_GAUGE])),
+            Err(ParseError::InvalidLength {
+                part: PART_VALUES,
+                length: 7
+            })
+        );
+        assert_eq!(
+            parse(&part(PART_ENCRYPTION, &[0; 8])),
+            Err(ParseError::Encrypted)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Parser for the [InfluxDB line protocol][protocol], as sent by Telegraf's `socket_writer` output.
+//!
+//! [protocol]: https://docs.influxdata.com/influxdb/v1.8/write_protocols/line_protocol_reference/
+
+use chrono::{TimeZone, Utc};
+use snafu::Snafu;
+
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum ParseError {
+    #[snafu(display("Line has no fields"))]
+    MissingFields,
+
+    #[snafu(display("Invalid tag or field {:?}", pair))]
+    InvalidPair { pair: String },
+
+    #[snafu(display("Invalid value {:?} for field {:?}", value, field))]
+    InvalidValue { field: String, value: String },
+
+    #[snafu(display("Invalid timestamp {:?}", timestamp))]
+    InvalidTimestamp { timestamp: String },
+}
+
+/// Splits `input` on `separator`, skipping the separators escaped with a backslash or enclosed in
+/// double quotes.
+fn split_unescaped(input: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut escaped, mut quoted) = (0, false, false);
+    for (index, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&input[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// Removes the backslashes escaping the next character.
+fn unescape(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.extend(chars.next()),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+fn split_pair(pair: &str) -> Result<(String, &str), ParseError> {
+    match split_unescaped(pair, '=').as_slice() {
+        [key, value] if !key.is_empty() => Ok((unescape(key), value)),
+        _ => Err(ParseError::InvalidPair {
+            pair: pair.to_owned(),
+        }),
+    }
+}
+
+/// Parses a field value, returning `None` for strings, which can't be represented as metrics.
+fn parse_value(field: &str, value: &str) -> Result<Option<f64>, ParseError> {
+    let invalid = || ParseError::InvalidValue {
+        field: field.to_owned(),
+        value: value.to_owned(),
+    };
+    let number = match value {
+        "t" | "T" | "true" | "True" | "TRUE" => 1.0,
+        "f" | "F" | "false" | "False" | "FALSE" => 0.0,
+        _ if value.starts_with('"') => return Ok(None),
+        _ => match value.strip_suffix('i') {
+            Some(integer) => integer.parse::<i64>().map_err(|_| invalid())? as f64,
+            None => match value.strip_suffix('u') {
+                Some(unsigned) => unsigned.parse::<u64>().map_err(|_| invalid())? as f64,
+                None => value.parse::<f64>().map_err(|_| invalid())?,
+            },
+        },
+    };
+    Ok(Some(number))
+}
+
+/// Parses a line into a gauge per numeric or boolean field, named `<measurement>_<field>`.
+///
+/// String fields are skipped, and the timestamp is read in nanoseconds.
+pub fn parse_line(line: &str) -> Result<Vec<Metric>, ParseError> {
+    let line = line.trim();
+    let sections = split_unescaped(line, ' ')
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .collect::<Vec<_>>();
+    let (series, fields, timestamp) = match sections.as_slice() {
+        [series, fields] => (*series, *fields, None),
+        [series, fields, timestamp] => (*series, *fields, Some(*timestamp)),
+        _ => return Err(ParseError::MissingFields),
+    };
+
+    let mut series = split_unescaped(series, ',').into_iter();
+    let measurement = unescape(series.next().unwrap_or_default());
+    let tags = series
+        .map(|pair| split_pair(pair).map(|(key, value)| (key, unescape(value))))
+        .collect::<Result<MetricTags, _>>()?;
+
+    let timestamp = timestamp
+        .map(|timestamp| {
+            timestamp
+                .parse::<i64>()
+                .ok()
+                .map(|nanos| Utc.timestamp_nanos(nanos))
+                .ok_or_else(|| ParseError::InvalidTimestamp {
+                    timestamp: timestamp.to_owned(),
+                })
+        })
+        .transpose()?;
+
+    let mut metrics = Vec::new();
+    for pair in split_unescaped(fields, ',') {
+        let (field, value) = split_pair(pair)?;
+        if let Some(value) = parse_value(&field, value)? {
+            metrics.push(
+                Metric::new(
+                    format!("{}_{}", measurement, field),
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                )
+                .with_tags((!tags.is_empty()).then(|| tags.clone()))
+                .with_timestamp(timestamp),
+            );
+        }
+    }
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn parses_fields_as_gauges() {
+        let metrics = parse_line(
+            r#"cpu,host=web\ 1,cpu=cpu-total usage_idle=98.5,usage_user=1i,healthy=true,label="a b" 1600000000000000000"#,
+        )
+        .unwrap();
+
+        let tags = MetricTags::from([
+            ("host".to_owned(), "web 1".to_owned()),
+            ("cpu".to_owned(), "cpu-total".to_owned()),
+        ]);
+        let timestamp = Some(Utc.timestamp_nanos(1_600_000_000_000_000_000));
+        let gauge = |name: &str, value| {
+            Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+                .with_tags(Some(tags.clone()))
+                .with_timestamp(timestamp)
+        };
+        assert_eq!(
+            metrics,
+            vec![
+                gauge("cpu_usage_idle", 98.5),
+                gauge("cpu_usage_user", 1.0),
+                gauge("cpu_healthy", 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_lines_without_tags_or_timestamp() {
+        let metrics = parse_line("mem used=42u").unwrap();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name(), "mem_used");
+        assert_eq!(metrics[0].tags(), None);
+        assert_eq!(metrics[0].timestamp(), None);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(parse_line("cpu"), Err(ParseError::MissingFields));
+        assert_eq!(
+            parse_line("cpu usage=abc"),
+            Err(ParseError::InvalidValue {
+                field: "usage".to_owned(),
+                value: "abc".to_owned()
+            })
+        );
+        assert!(parse_line("cpu usage=1 yesterday").is_err());
+    }
+}
@@ -0,0 +1,488 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use codecs::{
+    decoding::{self, Deserializer, Framer},
+    BytesDecoder, NewlineDelimitedDecoder,
+};
+use futures::{StreamExt, TryFutureExt};
+use listenfd::ListenFd;
+use serde_with::serde_as;
+use smallvec::SmallVec;
+use tokio_util::udp::UdpFramed;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use super::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource};
+use crate::{
+    codecs::Decoder,
+    config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    internal_events::{
+        EventsReceived, SocketBindError, SocketBytesReceived, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsSourceConfig},
+    SourceSender,
+};
+
+mod collectd;
+mod influx;
+#[cfg(unix)]
+mod unix;
+
+#[cfg(unix)]
+use unix::{collectd_telegraf_unix, UnixConfig};
+
+/// Configuration for the `collectd_telegraf` source.
+#[configurable_component(source(
+    "collectd_telegraf",
+    "Collect metrics sent by collectd or Telegraf."
+))]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of socket to use."))]
+#[allow(clippy::large_enum_variant)] // just used for configuration
+pub enum CollectdTelegrafConfig {
+    /// Listen on TCP, for the InfluxDB line protocol.
+    Tcp(TcpConfig),
+
+    /// Listen on UDP, for the collectd binary protocol or the InfluxDB line protocol.
+    Udp(UdpConfig),
+
+    /// Listen on a Unix domain Socket (UDS), for the InfluxDB line protocol.
+    #[cfg(unix)]
+    Unix(UnixConfig),
+}
+
+/// The protocol of the metrics received over UDP.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UdpFormat {
+    /// The [collectd binary protocol][collectd], as sent by the `network` plugin of collectd.
+    ///
+    /// Each datagram is a packet of the protocol. Signed packets are accepted without verifying
+    /// their signature, and encrypted packets are rejected.
+    ///
+    /// [collectd]: https://collectd.org/wiki/index.php/Binary_protocol
+    Collectd,
+
+    /// The [InfluxDB line protocol][influx], as sent by the `socket_writer` output of Telegraf.
+    ///
+    /// [influx]: https://docs.influxdata.com/influxdb/v1.8/write_protocols/line_protocol_reference/
+    Influx,
+}
+
+/// UDP configuration for the `collectd_telegraf` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UdpConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    #[configurable(derived)]
+    format: UdpFormat,
+
+    /// The size of the receive buffer used for each connection.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+}
+
+impl UdpConfig {
+    pub const fn from_address(address: SocketListenAddr, format: UdpFormat) -> Self {
+        Self {
+            address,
+            format,
+            receive_buffer_bytes: None,
+        }
+    }
+}
+
+/// TCP configuration for the `collectd_telegraf` source.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct TcpConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    #[configurable(derived)]
+    keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsSourceConfig>,
+
+    /// The timeout before a connection is forcefully closed during shutdown.
+    #[serde(default = "default_shutdown_timeout_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Shutdown Timeout"))]
+    shutdown_timeout_secs: Duration,
+
+    /// The size of the receive buffer used for each connection.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of TCP connections that are allowed at any given time.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    connection_limit: Option<u32>,
+}
+
+impl TcpConfig {
+    #[cfg(test)]
+    pub const fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            keepalive: None,
+            tls: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            receive_buffer_bytes: None,
+            connection_limit: None,
+        }
+    }
+}
+
+const fn default_shutdown_timeout_secs() -> Duration {
+    Duration::from_secs(30)
+}
+
+impl GenerateConfig for CollectdTelegrafConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::Udp(UdpConfig::from_address(
+            SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::new(127, 0, 0, 1),
+                25826,
+            ))),
+            UdpFormat::Collectd,
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "collectd_telegraf")]
+impl SourceConfig for CollectdTelegrafConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        match self {
+            Self::Udp(config) => Ok(Box::pin(collectd_telegraf_udp(
+                config.clone(),
+                cx.shutdown,
+                cx.out,
+            ))),
+            Self::Tcp(config) => {
+                let tls_config = config.tls.as_ref().map(|tls| tls.tls_config.clone());
+                let tls_client_metadata_key = config
+                    .tls
+                    .as_ref()
+                    .and_then(|tls| tls.client_metadata_key.clone())
+                    .and_then(|k| k.path);
+                let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
+                InfluxTcpSource.run(
+                    config.address,
+                    config.keepalive,
+                    config.shutdown_timeout_secs,
+                    tls,
+                    tls_client_metadata_key,
+                    config.receive_buffer_bytes,
+                    None,
+                    cx,
+                    false.into(),
+                    config.connection_limit,
+                    Default::default(),
+                    Self::NAME,
+                    LogNamespace::Legacy,
+                )
+            }
+            #[cfg(unix)]
+            Self::Unix(config) => collectd_telegraf_unix(config.clone(), cx.shutdown, cx.out),
+        }
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        vec![SourceOutput::new_metrics()]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match self.clone() {
+            Self::Tcp(tcp) => vec![tcp.address.as_tcp_resource()],
+            Self::Udp(udp) => vec![udp.address.as_udp_resource()],
+            #[cfg(unix)]
+            Self::Unix(_) => vec![],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone)]
+struct MetricsDeserializer {
+    format: UdpFormat,
+    socket_mode: Option<SocketMode>,
+    events_received: Option<Registered<EventsReceived>>,
+}
+
+impl MetricsDeserializer {
+    fn udp(format: UdpFormat) -> Self {
+        Self {
+            format,
+            socket_mode: Some(SocketMode::Udp),
+            // The other modes emit a different `EventsReceived`.
+            events_received: Some(register!(EventsReceived)),
+        }
+    }
+
+    const fn tcp() -> Self {
+        Self {
+            format: UdpFormat::Influx,
+            socket_mode: None,
+            events_received: None,
+        }
+    }
+
+    #[cfg(unix)]
+    const fn unix() -> Self {
+        Self {
+            format: UdpFormat::Influx,
+            socket_mode: Some(SocketMode::Unix),
+            events_received: None,
+        }
+    }
+
+    fn decoder(self) -> Decoder {
+        let framer = match self.format {
+            UdpFormat::Collectd => Framer::Bytes(BytesDecoder::new()),
+            UdpFormat::Influx => Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        };
+        Decoder::new(framer, Deserializer::Boxed(Box::new(self)))
+    }
+}
+
+impl decoding::format::Deserializer for MetricsDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        _log_namespace: LogNamespace,
+    ) -> crate::Result<SmallVec<[Event; 1]>> {
+        // The other modes already emit BytesReceived
+        if self.socket_mode == Some(SocketMode::Udp) {
+            emit!(SocketBytesReceived {
+                mode: SocketMode::Udp,
+                byte_size: bytes.len(),
+            });
+        }
+
+        let metrics = match self.format {
+            UdpFormat::Collectd => collectd::parse(&bytes)?,
+            UdpFormat::Influx => influx::parse_line(&String::from_utf8_lossy(&bytes))?,
+        };
+        let events = metrics
+            .into_iter()
+            .map(Event::Metric)
+            .collect::<SmallVec<_>>();
+
+        if let Some(events_received) = &self.events_received {
+            let byte_size = events
+                .iter()
+                .map(EstimatedJsonEncodedSizeOf::estimated_json_encoded_size_of)
+                .sum();
+            events_received.emit(CountByteSize(events.len(), byte_size));
+        }
+        Ok(events)
+    }
+}
+
+async fn collectd_telegraf_udp(
+    config: UdpConfig,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error
+            })
+        })
+        .await?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(
+        message = "Listening.",
+        addr = %config.address,
+        r#type = "udp"
+    );
+
+    let codec = MetricsDeserializer::udp(config.format).decoder();
+    let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok(((events, _byte_size), _sock)) => {
+                let count = events.len();
+                if (out.send_batch(events).await).is_err() {
+                    emit!(StreamClosedError { count });
+                }
+            }
+            Err(error) => {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct InfluxTcpSource;
+
+impl TcpSource for InfluxTcpSource {
+    type Error = codecs::decoding::Error;
+    type Item = SmallVec<[Event; 1]>;
+    type Decoder = Decoder;
+    type Acker = TcpNullAcker;
+
+    fn decoder(&self) -> Self::Decoder {
+        MetricsDeserializer::tcp().decoder()
+    }
+
+    fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
+        TcpNullAcker
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::{
+        io::AsyncWriteExt,
+        net::UdpSocket,
+        time::{sleep, Duration, Instant},
+    };
+    use vector_core::{config::ComponentKey, event::EventContainer};
+
+    use super::*;
+    use crate::test_util::{
+        collect_limited,
+        components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+        metrics::{assert_counter, assert_gauge},
+        next_addr,
+    };
+    use crate::{series, test_util::metrics::AbsoluteMetricState};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CollectdTelegrafConfig>();
+    }
+
+    /// A collectd packet with a `load` gauge, followed by an `if_octets` derive.
+    fn collectd_packet() -> Vec<u8> {
+        let mut packet = Vec::new();
+        let mut part = |kind: u16, payload: &[u8]| {
+            packet.extend(kind.to_be_bytes());
+            packet.extend((payload.len() as u16 + 4).to_be_bytes());
+            packet.extend(payload);
+        };
+        part(0x0000, b"web-1\0");
+        part(0x0002, b"load\0");
+        part(0x0004, b"load\0");
+        part(0x0006, &[&[0, 1, 1][..], &1.5f64.to_le_bytes()].concat());
+        part(0x0002, b"interface\0");
+        part(0x0004, b"if_octets\0");
+        part(0x0006, &[&[0, 1, 2][..], &1024i64.to_be_bytes()].concat());
+        packet
+    }
+
+    async fn run_source(
+        config: CollectdTelegrafConfig,
+        send: impl std::future::Future<Output = ()>,
+    ) -> Vec<Event> {
+        let component_key = ComponentKey::from("collectd_telegraf");
+        let (tx, rx) = SourceSender::new_with_buffer(4096);
+        let (source_ctx, shutdown) = SourceContext::new_shutdown(&component_key, tx);
+        let source = config
+            .build(source_ctx)
+            .await
+            .expect("failed to build source");
+        tokio::spawn(async move {
+            source.await.expect("source should not fail");
+        });
+
+        // Give the source time to start listening.
+        sleep(Duration::from_millis(250)).await;
+        send.await;
+        sleep(Duration::from_millis(250)).await;
+        shutdown
+            .shutdown_all(Some(Instant::now() + Duration::from_millis(100)))
+            .await;
+
+        collect_limited(rx)
+            .await
+            .into_iter()
+            .flat_map(EventContainer::into_events)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn receives_collectd_over_udp() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async move {
+            let in_addr = next_addr();
+            let config = CollectdTelegrafConfig::Udp(UdpConfig::from_address(
+                in_addr.into(),
+                UdpFormat::Collectd,
+            ));
+            let events = run_source(config, async move {
+                let socket = UdpSocket::bind(next_addr()).await.unwrap();
+                socket.send_to(&collectd_packet(), in_addr).await.unwrap();
+            })
+            .await;
+
+            let metrics = events.into_iter().collect::<AbsoluteMetricState>().finish();
+            let mut load = series!("load", "host" => "web-1");
+            load.name.namespace = Some("collectd".into());
+            assert_gauge(&metrics, load, 1.5);
+            let mut octets = series!("interface_if_octets", "host" => "web-1");
+            octets.name.namespace = Some("collectd".into());
+            assert_counter(&metrics, octets, 1024.0);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn receives_influx_over_tcp() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async move {
+            let in_addr = next_addr();
+            let config = CollectdTelegrafConfig::Tcp(TcpConfig::from_address(in_addr.into()));
+            let events = run_source(config, async move {
+                tokio::net::TcpStream::connect(in_addr)
+                    .await
+                    .unwrap()
+                    .write_all(b"cpu,host=web-1 usage_idle=98.5\nmem,host=web-1 used=42i\n")
+                    .await
+                    .unwrap();
+            })
+            .await;
+
+            let metrics = events.into_iter().collect::<AbsoluteMetricState>().finish();
+            assert_gauge(&metrics, series!("cpu_usage_idle", "host" => "web-1"), 98.5);
+            assert_gauge(&metrics, series!("mem_used", "host" => "web-1"), 42.0);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use vector_config::configurable_component;
+
+use super::MetricsDeserializer;
+use crate::{
+    shutdown::ShutdownSignal,
+    sources::{util::build_unix_stream_source, Source},
+    SourceSender,
+};
+
+/// Unix domain socket configuration for the `collectd_telegraf` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UnixConfig {
+    /// The Unix socket path.
+    ///
+    /// This should be an absolute path.
+    #[configurable(metadata(docs::examples = "/path/to/socket"))]
+    pub path: PathBuf,
+}
+
+pub fn collectd_telegraf_unix(
+    config: UnixConfig,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> crate::Result<Source> {
+    build_unix_stream_source(
+        config.path,
+        None,
+        MetricsDeserializer::unix().decoder(),
+        |_events, _host| {},
+        shutdown,
+        out,
+    )
+}
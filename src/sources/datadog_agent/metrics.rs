@@ -14,13 +14,13 @@ use crate::{
     config::log_schema,
     event::{
         metric::{Metric, MetricValue},
-        Event, MetricKind, MetricTags,
+        DatadogMetricMetadata, DatadogMetricOrigin, Event, MetricKind, MetricTags,
     },
     internal_events::EventsReceived,
     schema,
     sources::{
         datadog_agent::{
-            ddmetric_proto::{metric_payload, MetricPayload, SketchPayload},
+            ddmetric_proto::{self, metric_payload, MetricPayload, SketchPayload},
             handle_request, ApiKeyQueryParams, DatadogAgentSource,
         },
         util::{extract_tag_key_and_value, ErrorMessage},
@@ -252,9 +252,10 @@ pub(crate) fn decode_ddseries_v2(
             });
             (!serie.source_type_name.is_empty())
                 .then(|| tags.replace("source_type_name".into(), serie.source_type_name));
-            // As per https://github.com/DataDog/datadog-agent/blob/a62ac9fb13e1e5060b89e731b8355b2b20a07c5b/pkg/serializer/internal/metrics/iterable_series.go#L224
-            // serie.unit is omitted
-            match metric_payload::MetricType::from_i32(serie.r#type) {
+            // The unit and origin are not part of the metric, so they are kept in its metadata to be
+            // forwarded by the `datadog_metrics` sink.
+            let dd_metric_metadata = datadog_metric_metadata(serie.unit, serie.metadata);
+            let metrics = match metric_payload::MetricType::from_i32(serie.r#type) {
                 Some(metric_payload::MetricType::Count) => serie
                     .points
                     .iter()
@@ -325,7 +326,10 @@ pub(crate) fn decode_ddseries_v2(
                     warn!("Unspecified metric type ({}).", serie.r#type);
                     Vec::new()
                 }
-            }
+            };
+            metrics
+                .into_iter()
+                .map(move |metric| with_datadog_metric_metadata(metric, &dd_metric_metadata))
         })
         .map(|mut metric| {
             if let Some(k) = &api_key {
@@ -481,6 +485,34 @@ fn into_vector_metric(
     .collect()
 }
 
+/// Gathers the metadata of a series or a sketch, which is empty for agents not sending it.
+fn datadog_metric_metadata(
+    unit: String,
+    metadata: Option<ddmetric_proto::Metadata>,
+) -> Option<Arc<DatadogMetricMetadata>> {
+    let unit = (!unit.is_empty()).then_some(unit);
+    let origin = metadata
+        .and_then(|metadata| metadata.origin)
+        .map(|origin| DatadogMetricOrigin {
+            product: origin.origin_product,
+            category: origin.origin_category,
+            service: origin.origin_service,
+        });
+    (unit.is_some() || origin.is_some()).then(|| Arc::new(DatadogMetricMetadata { unit, origin }))
+}
+
+fn with_datadog_metric_metadata(
+    mut metric: Metric,
+    metadata: &Option<Arc<DatadogMetricMetadata>>,
+) -> Metric {
+    if let Some(metadata) = metadata {
+        metric
+            .metadata_mut()
+            .set_datadog_metric_metadata(Arc::clone(metadata));
+    }
+    metric
+}
+
 /// Parses up to the first '.' of the input metric name into a namespace.
 /// If no delimiter, the namespace is None type.
 fn namespace_name_from_dd_metric(dd_metric_name: &str) -> (Option<&str>, &str) {
@@ -507,6 +539,7 @@ pub(crate) fn decode_ddsketch(
                 log_schema().host_key().to_string(),
                 sketch_series.host.clone(),
             );
+            let dd_metric_metadata = datadog_metric_metadata(String::new(), sketch_series.metadata);
             sketch_series.dogsketches.into_iter().map(move |sketch| {
                 let k: Vec<i16> = sketch.k.iter().map(|k| *k as i16).collect();
                 let n: Vec<u16> = sketch.n.iter().map(|n| *n as u16).collect();
@@ -535,7 +568,7 @@ pub(crate) fn decode_ddsketch(
                     metric.metadata_mut().set_datadog_api_key(Arc::clone(k));
                 }
 
-                metric.into()
+                with_datadog_metric_metadata(metric, &dd_metric_metadata).into()
             })
         })
         .collect())
@@ -34,7 +34,7 @@ use crate::{
     event::{
         into_event_stream,
         metric::{MetricKind, MetricSketch, MetricValue},
-        Event, EventStatus, Metric, Value,
+        DatadogMetricMetadata, DatadogMetricOrigin, Event, EventStatus, Metric, Value,
     },
     schema,
     serde::{default_decoding, default_framing_message_based},
@@ -960,6 +960,7 @@ async fn decode_sketches() {
                 k: vec![1517, 1559],
                 n: vec![1, 1],
             }],
+            metadata: None,
         };
 
         let sketch_payload = ddmetric_proto::SketchPayload {
@@ -1882,9 +1883,16 @@ async fn decode_series_endpoint_v2() {
                     },
                 ],
                 r#type: ddmetric_proto::metric_payload::MetricType::Gauge as i32,
-                unit: "".to_string(),
+                unit: "byte".to_string(),
                 source_type_name: "a_random_source_type_name".to_string(),
                 interval: 0,
+                metadata: Some(ddmetric_proto::Metadata {
+                    origin: Some(ddmetric_proto::Origin {
+                        origin_product: 10,
+                        origin_category: 11,
+                        origin_service: 12,
+                    }),
+                }),
             },
             ddmetric_proto::metric_payload::MetricSeries {
                 resources: vec![ddmetric_proto::metric_payload::Resource {
@@ -1901,6 +1909,7 @@ async fn decode_series_endpoint_v2() {
                 unit: "".to_string(),
                 source_type_name: "another_random_source_type_name".to_string(),
                 interval: 10,
+                metadata: None,
             },
             ddmetric_proto::metric_payload::MetricSeries {
                 resources: vec![ddmetric_proto::metric_payload::Resource {
@@ -1917,6 +1926,7 @@ async fn decode_series_endpoint_v2() {
                 unit: "".to_string(),
                 source_type_name: "a_very_random_source_type_name".to_string(),
                 interval: 0,
+                metadata: None,
             },
         ];
 
@@ -1970,6 +1980,17 @@ async fn decode_series_endpoint_v2() {
                 &events[0].metadata().datadog_api_key().as_ref().unwrap()[..],
                 "12345678abcdefgh12345678abcdefgh"
             );
+            assert_eq!(
+                events[0].metadata().datadog_metric_metadata(),
+                Some(&DatadogMetricMetadata {
+                    unit: Some("byte".to_owned()),
+                    origin: Some(DatadogMetricOrigin {
+                        product: 10,
+                        category: 11,
+                        service: 12,
+                    }),
+                })
+            );
 
             metric = events[1].as_metric();
             assert_eq!(metric.name(), "dd_gauge");
@@ -2026,6 +2047,7 @@ async fn decode_series_endpoint_v2() {
                 &events[2].metadata().datadog_api_key().as_ref().unwrap()[..],
                 "12345678abcdefgh12345678abcdefgh"
             );
+            assert_eq!(events[2].metadata().datadog_metric_metadata(), None);
 
             metric = events[3].as_metric();
             assert_eq!(metric.name(), "dd_count");
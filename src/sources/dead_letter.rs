@@ -0,0 +1,102 @@
+use tokio::sync::mpsc;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, schema::Definition, EstimatedJsonEncodedSizeOf};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    dead_letter,
+    event::Event,
+    internal_events::{EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `dead_letter` source.
+#[configurable_component(source(
+    "dead_letter",
+    "Emit the events sinks could not deliver and sent to this source."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DeadLetterConfig {}
+
+impl_generate_config_from_default!(DeadLetterConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dead_letter")]
+impl SourceConfig for DeadLetterConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let queue = dead_letter::register(cx.key.id());
+        Ok(Box::pin(run(queue, cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // The events are emitted as the sinks received them.
+        vec![SourceOutput::new_logs(DataType::all(), Definition::any())]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn run(
+    mut queue: mpsc::Receiver<Vec<Event>>,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+
+    loop {
+        let events = tokio::select! {
+            _ = &mut shutdown => break,
+            events = queue.recv() => match events {
+                Some(events) => events,
+                None => break,
+            },
+        };
+
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+        if out.send_batch(events).await.is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DeadLetterConfig>();
+    }
+
+    #[tokio::test]
+    async fn emits_dead_letters() {
+        let (tx, rx) = SourceSender::new_test();
+        let queue = dead_letter::register("dead_letter_source_test");
+        tokio::spawn(run(queue, tx, ShutdownSignal::noop()));
+
+        dead_letter::send(
+            "dead_letter_source_test",
+            vec![LogEvent::from("rejected").into()],
+        )
+        .await
+        .unwrap();
+
+        let events = rx.take(1).collect::<Vec<_>>().await;
+        assert_eq!(events[0].as_log()["message"], "rejected".into());
+    }
+}
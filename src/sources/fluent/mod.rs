@@ -20,7 +20,9 @@ use vector_core::schema::Definition;
 use vrl::value::kind::Collection;
 use vrl::value::{Kind, Value};
 
-use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
+use super::util::net::{
+    SocketListenAddr, TcpConnectionLimitsConfig, TcpSource, TcpSourceAck, TcpSourceAcker,
+};
 use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
@@ -44,9 +46,14 @@ pub struct FluentConfig {
     address: SocketListenAddr,
 
     /// The maximum number of TCP connections that are allowed at any given time.
+    #[serde(alias = "max_connections")]
     #[configurable(metadata(docs::type_unit = "connections"))]
     connection_limit: Option<u32>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    connection_limits: TcpConnectionLimitsConfig,
+
     #[configurable(derived)]
     keepalive: Option<TcpKeepaliveConfig>,
 
@@ -79,6 +86,7 @@ impl GenerateConfig for FluentConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
+            connection_limits: Default::default(),
             log_namespace: None,
         })
         .unwrap()
@@ -110,6 +118,7 @@ impl SourceConfig for FluentConfig {
             cx,
             self.acknowledgements,
             self.connection_limit,
+            self.connection_limits,
             FluentConfig::NAME,
             log_namespace,
         )
@@ -899,6 +908,7 @@ mod tests {
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
             connection_limit: None,
+            connection_limits: Default::default(),
             log_namespace: None,
         }
         .build(SourceContext::new_test(sender, None))
@@ -963,6 +973,7 @@ mod tests {
             receive_buffer_bytes: None,
             acknowledgements: false.into(),
             connection_limit: None,
+            connection_limits: Default::default(),
             log_namespace: Some(true),
         };
 
@@ -1018,6 +1029,7 @@ mod tests {
             receive_buffer_bytes: None,
             acknowledgements: false.into(),
             connection_limit: None,
+            connection_limits: Default::default(),
             log_namespace: None,
         };
 
@@ -1239,6 +1251,7 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                connection_limits: Default::default(),
                 log_namespace: None,
             }
             .build(SourceContext::new_test(sender, None))
@@ -0,0 +1,421 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde_with::serde_as;
+use tokio::time::{self, Instant};
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, schema::Definition, EstimatedJsonEncodedSizeOf};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, LogEvent, Metric, MetricValue},
+    internal_events::{EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `heartbeat` source.
+#[serde_as]
+#[configurable_component(source(
+    "heartbeat",
+    "Emit periodic heartbeats with the last activity of the components of the running Vector instance."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HeartbeatConfig {
+    /// The interval between heartbeats, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[serde(default = "default_interval")]
+    #[configurable(metadata(docs::human_name = "Interval"))]
+    pub interval_secs: Duration,
+
+    /// How long a component can go without sending events before it is reported as idle, in
+    /// seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[serde(default = "default_idle_after")]
+    #[configurable(metadata(docs::human_name = "Idle After"))]
+    pub idle_after_secs: Duration,
+
+    /// The IDs of the components heartbeats are emitted for.
+    ///
+    /// If empty, a heartbeat is emitted for every component of the running Vector instance.
+    #[configurable(metadata(docs::examples = "app_logs"))]
+    #[serde(default)]
+    pub components: Vec<String>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_idle_after() -> Duration {
+    Duration::from_secs(300)
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_interval(),
+            idle_after_secs: default_idle_after(),
+            components: Vec::new(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(HeartbeatConfig);
+
+impl HeartbeatConfig {
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let fields = Collection::empty()
+            .with_known("component_id", Kind::bytes())
+            .with_known("status", Kind::bytes())
+            .with_known("last_activity", Kind::timestamp().or_null())
+            .with_known("idle_secs", Kind::float())
+            .with_known("events_total", Kind::float().or_null())
+            .with_known("errors_total", Kind::float().or_null());
+
+        Definition::new_with_default_metadata(Kind::object(fields), [log_namespace])
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "heartbeat")]
+impl SourceConfig for HeartbeatConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.interval_secs.is_zero() {
+            return Err("`interval_secs` must be greater than zero".into());
+        }
+        let controller = crate::metrics::Controller::get()?;
+
+        Ok(Box::pin(
+            Heartbeat {
+                components: self.components.clone(),
+                interval: self.interval_secs,
+                idle_after: self.idle_after_secs,
+                controller,
+                log_namespace: cx.log_namespace(self.log_namespace),
+                out: cx.out,
+                shutdown: cx.shutdown,
+            }
+            .run(),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The events sent and the errors of a component since Vector started.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Totals {
+    events: f64,
+    errors: f64,
+}
+
+/// Sums the counters of each component, across its outputs.
+fn totals(metrics: &[Metric]) -> BTreeMap<String, Totals> {
+    let mut totals = BTreeMap::<String, Totals>::new();
+    for metric in metrics {
+        let (Some(component_id), MetricValue::Counter { value }) =
+            (metric.tag_value("component_id"), metric.value())
+        else {
+            continue;
+        };
+        match metric.name() {
+            "component_sent_events_total" => {
+                totals.entry(component_id).or_default().events += value
+            }
+            "component_errors_total" => totals.entry(component_id).or_default().errors += value,
+            _ => {}
+        }
+    }
+    totals
+}
+
+/// The last time a component sent events.
+struct Activity {
+    events: f64,
+    last: Option<(Instant, DateTime<Utc>)>,
+}
+
+/// Tracks the last activity of the components from their totals at each heartbeat.
+struct Tracker {
+    started: Instant,
+    components: BTreeMap<String, Activity>,
+}
+
+impl Tracker {
+    /// Starts tracking from the current totals, which are not counted as activity.
+    fn new(started: Instant, totals: &BTreeMap<String, Totals>) -> Self {
+        let components = totals
+            .iter()
+            .map(|(id, totals)| {
+                let activity = Activity {
+                    events: totals.events,
+                    last: None,
+                };
+                (id.clone(), activity)
+            })
+            .collect();
+        Self {
+            started,
+            components,
+        }
+    }
+
+    fn update(&mut self, totals: &BTreeMap<String, Totals>, now: Instant, now_utc: DateTime<Utc>) {
+        for (id, totals) in totals {
+            match self.components.entry(id.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(Activity {
+                        events: totals.events,
+                        last: (totals.events > 0.0).then_some((now, now_utc)),
+                    });
+                }
+                Entry::Occupied(mut entry) => {
+                    let activity = entry.get_mut();
+                    // Counters restart when their component is reloaded.
+                    if totals.events != activity.events && totals.events > 0.0 {
+                        activity.last = Some((now, now_utc));
+                    }
+                    activity.events = totals.events;
+                }
+            }
+        }
+    }
+
+    fn heartbeat(
+        &self,
+        component_id: &str,
+        totals: Option<&Totals>,
+        now: Instant,
+        idle_after: Duration,
+        log_namespace: LogNamespace,
+    ) -> LogEvent {
+        let last = self
+            .components
+            .get(component_id)
+            .and_then(|activity| activity.last);
+        // Components that have not sent events are idle since the source started.
+        let idle = now.saturating_duration_since(last.map_or(self.started, |(at, _)| at));
+        let status = match totals {
+            None => "missing",
+            Some(_) if idle < idle_after => "active",
+            Some(_) => "idle",
+        };
+
+        let mut log = LogEvent::default();
+        log.insert("component_id", component_id.to_owned());
+        log.insert("status", status);
+        log.insert("last_activity", last.map(|(_, at)| at));
+        log.insert("idle_secs", idle.as_secs_f64());
+        log.insert("events_total", totals.map(|totals| totals.events));
+        log.insert("errors_total", totals.map(|totals| totals.errors));
+
+        log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            HeartbeatConfig::NAME,
+            Utc::now(),
+        );
+        log
+    }
+}
+
+struct Heartbeat {
+    components: Vec<String>,
+    interval: Duration,
+    idle_after: Duration,
+    controller: &'static crate::metrics::Controller,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl Heartbeat {
+    async fn run(mut self) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let mut tracker = Tracker::new(Instant::now(), &totals(&self.controller.capture_metrics()));
+
+        let mut interval = IntervalStream::new(time::interval_at(
+            Instant::now() + self.interval,
+            self.interval,
+        ))
+        .take_until(self.shutdown.clone());
+        while interval.next().await.is_some() {
+            let now = Instant::now();
+            let totals = totals(&self.controller.capture_metrics());
+            tracker.update(&totals, now, Utc::now());
+
+            let events = if self.components.is_empty() {
+                totals
+                    .iter()
+                    .map(|(id, component)| {
+                        tracker.heartbeat(
+                            id,
+                            Some(component),
+                            now,
+                            self.idle_after,
+                            self.log_namespace,
+                        )
+                    })
+                    .map(Event::from)
+                    .collect::<Vec<_>>()
+            } else {
+                self.components
+                    .iter()
+                    .map(|id| {
+                        tracker.heartbeat(
+                            id,
+                            totals.get(id),
+                            now,
+                            self.idle_after,
+                            self.log_namespace,
+                        )
+                    })
+                    .map(Event::from)
+                    .collect::<Vec<_>>()
+            };
+
+            let count = events.len();
+            if count == 0 {
+                continue;
+            }
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            if self.out.send_batch(events).await.is_err() {
+                emit!(StreamClosedError { count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{MetricKind, MetricTags};
+
+    use super::*;
+    use crate::event::Value;
+
+    fn counter(name: &str, component_id: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value }).with_tags(Some(
+            MetricTags::from([("component_id".to_owned(), component_id.to_owned())]),
+        ))
+    }
+
+    fn sent(component_id: &str, events: f64) -> BTreeMap<String, Totals> {
+        BTreeMap::from([(
+            component_id.to_owned(),
+            Totals {
+                events,
+                errors: 0.0,
+            },
+        )])
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HeartbeatConfig>();
+    }
+
+    #[test]
+    fn sums_counters_of_each_component() {
+        let metrics = vec![
+            counter("component_sent_events_total", "in", 10.0),
+            counter("component_sent_events_total", "in", 5.0),
+            counter("component_errors_total", "in", 2.0),
+            counter("component_received_events_total", "in", 100.0),
+            counter("component_sent_events_total", "out", 7.0),
+        ];
+
+        assert_eq!(
+            totals(&metrics),
+            BTreeMap::from([
+                (
+                    "in".to_owned(),
+                    Totals {
+                        events: 15.0,
+                        errors: 2.0
+                    }
+                ),
+                (
+                    "out".to_owned(),
+                    Totals {
+                        events: 7.0,
+                        errors: 0.0
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_idle_components() {
+        let start = Instant::now();
+        let idle_after = Duration::from_secs(300);
+        let heartbeat = |tracker: &Tracker, totals: &BTreeMap<String, Totals>, now| {
+            tracker.heartbeat(
+                "in",
+                totals.get("in"),
+                now,
+                idle_after,
+                LogNamespace::Legacy,
+            )
+        };
+
+        // Events sent before the source started are not activity.
+        let mut tracker = Tracker::new(start, &sent("in", 10.0));
+        let later = start + Duration::from_secs(400);
+        tracker.update(&sent("in", 10.0), later, Utc::now());
+        let log = heartbeat(&tracker, &sent("in", 10.0), later);
+        assert_eq!(log["status"], "idle".into());
+        assert_eq!(log["last_activity"], Value::Null);
+        assert_eq!(log["idle_secs"], Value::from(400.0));
+        assert_eq!(log["source_type"], "heartbeat".into());
+
+        let active_at = Utc::now();
+        tracker.update(&sent("in", 12.0), later, active_at);
+        let log = heartbeat(&tracker, &sent("in", 12.0), later + Duration::from_secs(60));
+        assert_eq!(log["status"], "active".into());
+        assert_eq!(log["last_activity"], active_at.into());
+        assert_eq!(log["events_total"], Value::from(12.0));
+
+        let log = heartbeat(
+            &tracker,
+            &sent("in", 12.0),
+            later + Duration::from_secs(300),
+        );
+        assert_eq!(log["status"], "idle".into());
+
+        let log = heartbeat(&tracker, &BTreeMap::new(), later);
+        assert_eq!(log["status"], "missing".into());
+        assert_eq!(log["events_total"], Value::Null);
+    }
+}
@@ -0,0 +1,560 @@
+#[cfg(target_os = "linux")]
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use tokio::process::Command;
+use vector_config::configurable_component;
+use vector_core::metric_tags;
+
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+use super::HostMetrics;
+
+const MEBIBYTE: f64 = 1024.0 * 1024.0;
+
+/// Options for the GPU metrics collector.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(default)]
+pub struct GpuConfig {
+    /// The path of the `nvidia-smi` command, used to query NVIDIA GPUs through NVML.
+    ///
+    /// `nvidia-smi` is installed along with the NVIDIA driver. Hosts without it have no NVIDIA GPU
+    /// metrics.
+    #[derivative(Default(value = "default_nvidia_smi_path()"))]
+    #[serde(default = "default_nvidia_smi_path")]
+    #[configurable(metadata(docs::examples = "/usr/bin/nvidia-smi"))]
+    nvidia_smi_path: PathBuf,
+
+    /// Whether or not to report the GPU memory used by each process.
+    #[derivative(Default(value = "true"))]
+    #[serde(default = "crate::serde::default_true")]
+    processes: bool,
+}
+
+fn default_nvidia_smi_path() -> PathBuf {
+    PathBuf::from("nvidia-smi")
+}
+
+#[derive(Debug, PartialEq)]
+struct NvidiaGpu {
+    index: String,
+    uuid: String,
+    model: String,
+    utilization_percent: Option<f64>,
+    memory_total_mib: Option<f64>,
+    memory_used_mib: Option<f64>,
+    temperature_celsius: Option<f64>,
+}
+
+#[derive(Debug, PartialEq)]
+struct NvidiaProcess {
+    gpu_uuid: String,
+    pid: String,
+    name: String,
+    memory_used_mib: Option<f64>,
+}
+
+impl HostMetrics {
+    pub async fn gpu_metrics(&self, output: &mut super::MetricsBuffer) {
+        output.name = "gpu";
+        self.nvidia_gpu_metrics(output).await;
+        #[cfg(target_os = "linux")]
+        self.amd_gpu_metrics(output).await;
+    }
+
+    async fn nvidia_gpu_metrics(&self, output: &mut super::MetricsBuffer) {
+        let nvidia_smi_path = &self.config.gpu.nvidia_smi_path;
+        let gpus = match nvidia_smi(
+            nvidia_smi_path,
+            "--query-gpu=index,uuid,name,utilization.gpu,memory.total,memory.used,temperature.gpu",
+        )
+        .await
+        {
+            Ok(Some(gpus)) => parse_nvidia_gpus(&gpus),
+            Ok(None) => return,
+            Err(error) => {
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to load NVIDIA GPU info.",
+                    error,
+                });
+                return;
+            }
+        };
+
+        for gpu in &gpus {
+            let tags = metric_tags!(
+                "gpu" => gpu.index.clone(),
+                "model" => gpu.model.clone(),
+                "vendor" => "nvidia",
+            );
+            if let Some(utilization) = gpu.utilization_percent {
+                output.gauge("gpu_utilization_ratio", utilization / 100.0, tags.clone());
+            }
+            if let Some(memory_total) = gpu.memory_total_mib {
+                output.gauge(
+                    "gpu_memory_total_bytes",
+                    memory_total * MEBIBYTE,
+                    tags.clone(),
+                );
+            }
+            if let Some(memory_used) = gpu.memory_used_mib {
+                output.gauge(
+                    "gpu_memory_used_bytes",
+                    memory_used * MEBIBYTE,
+                    tags.clone(),
+                );
+            }
+            if let Some(temperature) = gpu.temperature_celsius {
+                output.gauge("gpu_temperature_celsius", temperature, tags);
+            }
+        }
+
+        if !self.config.gpu.processes || gpus.is_empty() {
+            return;
+        }
+        let processes = match nvidia_smi(
+            nvidia_smi_path,
+            "--query-compute-apps=gpu_uuid,pid,process_name,used_memory",
+        )
+        .await
+        {
+            Ok(processes) => parse_nvidia_processes(&processes.unwrap_or_default()),
+            Err(error) => {
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to load NVIDIA GPU processes info.",
+                    error,
+                });
+                return;
+            }
+        };
+        for process in processes {
+            let gpu = gpus.iter().find(|gpu| gpu.uuid == process.gpu_uuid);
+            if let (Some(gpu), Some(memory_used)) = (gpu, process.memory_used_mib) {
+                output.gauge(
+                    "gpu_process_memory_used_bytes",
+                    memory_used * MEBIBYTE,
+                    metric_tags!(
+                        "gpu" => gpu.index.clone(),
+                        "model" => gpu.model.clone(),
+                        "vendor" => "nvidia",
+                        "pid" => process.pid,
+                        "process" => process.name,
+                    ),
+                );
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn amd_gpu_metrics(&self, output: &mut super::MetricsBuffer) {
+        let sysfs_root = PathBuf::from(heim::os::linux::sysfs_root());
+        let procfs_root = std::env::var_os("PROCFS_ROOT")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("/proc"));
+        let processes = self.config.gpu.processes;
+
+        // Finding the processes using GPUs reads the file descriptors of every process.
+        let result = tokio::task::spawn_blocking(move || {
+            let gpus = amd_gpus(&sysfs_root)?;
+            let processes = if processes && !gpus.is_empty() {
+                amd_processes(&procfs_root)
+            } else {
+                Vec::new()
+            };
+            Ok::<_, io::Error>((gpus, processes))
+        })
+        .await
+        .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+        let (gpus, processes) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to load AMD GPU info.",
+                    error,
+                });
+                return;
+            }
+        };
+
+        for gpu in &gpus {
+            let tags = metric_tags!("gpu" => gpu.card.clone(), "vendor" => "amd");
+            if let Some(utilization) = gpu.utilization_percent {
+                output.gauge("gpu_utilization_ratio", utilization / 100.0, tags.clone());
+            }
+            if let Some(memory_total) = gpu.memory_total_bytes {
+                output.gauge("gpu_memory_total_bytes", memory_total, tags.clone());
+            }
+            if let Some(memory_used) = gpu.memory_used_bytes {
+                output.gauge("gpu_memory_used_bytes", memory_used, tags.clone());
+            }
+            if let Some(temperature) = gpu.temperature_celsius {
+                output.gauge("gpu_temperature_celsius", temperature, tags);
+            }
+        }
+
+        for process in processes {
+            if let Some(gpu) = gpus
+                .iter()
+                .find(|gpu| gpu.pci_address == process.pci_address)
+            {
+                output.gauge(
+                    "gpu_process_memory_used_bytes",
+                    process.memory_used_bytes,
+                    metric_tags!(
+                        "gpu" => gpu.card.clone(),
+                        "vendor" => "amd",
+                        "pid" => process.pid,
+                        "process" => process.name,
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Runs a query of `nvidia-smi`, returning `None` if it is not installed.
+async fn nvidia_smi(path: &Path, query: &str) -> io::Result<Option<String>> {
+    let output = match Command::new(path)
+        .arg(query)
+        .arg("--format=csv,noheader,nounits")
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    if !output.status.success() {
+        // `nvidia-smi` writes its errors to stdout.
+        let message = [output.stderr, output.stdout]
+            .iter()
+            .map(|output| String::from_utf8_lossy(output).trim().to_owned())
+            .find(|message| !message.is_empty())
+            .unwrap_or_else(|| output.status.to_string());
+        return Err(io::Error::new(io::ErrorKind::Other, message));
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// Parses a value of `nvidia-smi`, which is `[N/A]` or `[Not Supported]` when unavailable.
+fn parse_nvidia_value(value: &str) -> Option<f64> {
+    value.parse().ok()
+}
+
+fn parse_nvidia_gpus(output: &str) -> Vec<NvidiaGpu> {
+    output
+        .lines()
+        .filter_map(
+            |line| match line.split(',').map(str::trim).collect::<Vec<_>>()[..] {
+                [index, uuid, model, utilization, memory_total, memory_used, temperature] => {
+                    Some(NvidiaGpu {
+                        index: index.to_owned(),
+                        uuid: uuid.to_owned(),
+                        model: model.to_owned(),
+                        utilization_percent: parse_nvidia_value(utilization),
+                        memory_total_mib: parse_nvidia_value(memory_total),
+                        memory_used_mib: parse_nvidia_value(memory_used),
+                        temperature_celsius: parse_nvidia_value(temperature),
+                    })
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+fn parse_nvidia_processes(output: &str) -> Vec<NvidiaProcess> {
+    output
+        .lines()
+        .filter_map(
+            |line| match line.split(',').map(str::trim).collect::<Vec<_>>()[..] {
+                [gpu_uuid, pid, name, memory_used] => Some(NvidiaProcess {
+                    gpu_uuid: gpu_uuid.to_owned(),
+                    pid: pid.to_owned(),
+                    name: name.to_owned(),
+                    memory_used_mib: parse_nvidia_value(memory_used),
+                }),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, PartialEq)]
+struct AmdGpu {
+    card: String,
+    pci_address: String,
+    utilization_percent: Option<f64>,
+    memory_total_bytes: Option<f64>,
+    memory_used_bytes: Option<f64>,
+    temperature_celsius: Option<f64>,
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, PartialEq)]
+struct AmdProcess {
+    pid: String,
+    name: String,
+    pci_address: String,
+    memory_used_bytes: f64,
+}
+
+/// A DRM client, as listed in the `fdinfo` of the file descriptors of a process.
+#[cfg(target_os = "linux")]
+#[derive(Debug, PartialEq)]
+struct DrmClient {
+    driver: String,
+    pci_address: String,
+    id: String,
+    vram_bytes: f64,
+}
+
+/// Reads the GPUs of the `amdgpu` driver from sysfs.
+#[cfg(target_os = "linux")]
+fn amd_gpus(sysfs_root: &Path) -> io::Result<Vec<AmdGpu>> {
+    let entries = match fs::read_dir(sysfs_root.join("class/drm")) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut gpus = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let card = entry.file_name().to_string_lossy().into_owned();
+        // Connectors of the cards, such as `card0-DP-1`, are listed alongside them.
+        if !card.starts_with("card") || card.contains('-') {
+            continue;
+        }
+        let device = entry.path().join("device");
+        let driver = fs::read_link(device.join("driver")).ok();
+        if driver.as_deref().and_then(Path::file_name) != Some("amdgpu".as_ref()) {
+            continue;
+        }
+
+        let read = |file: &str| {
+            fs::read_to_string(device.join(file))
+                .ok()
+                .and_then(|value| value.trim().parse::<f64>().ok())
+        };
+        let temperature_celsius = fs::read_dir(device.join("hwmon"))
+            .ok()
+            .and_then(|mut hwmons| hwmons.find_map(Result::ok))
+            .and_then(|hwmon| fs::read_to_string(hwmon.path().join("temp1_input")).ok())
+            .and_then(|millidegrees| millidegrees.trim().parse::<f64>().ok())
+            .map(|millidegrees| millidegrees / 1000.0);
+
+        gpus.push(AmdGpu {
+            pci_address: fs::read_link(&device)?
+                .file_name()
+                .map(|address| address.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            card,
+            utilization_percent: read("gpu_busy_percent"),
+            memory_total_bytes: read("mem_info_vram_total"),
+            memory_used_bytes: read("mem_info_vram_used"),
+            temperature_celsius,
+        });
+    }
+    gpus.sort_by(|a, b| a.card.cmp(&b.card));
+    Ok(gpus)
+}
+
+/// Finds the VRAM used by each process through the `fdinfo` of its DRM file descriptors.
+///
+/// Processes whose file descriptors can't be read, such as those of other users when not running
+/// as root, are skipped.
+#[cfg(target_os = "linux")]
+fn amd_processes(procfs_root: &Path) -> Vec<AmdProcess> {
+    let mut usage = HashMap::<(String, String), f64>::new();
+    for process in fs::read_dir(procfs_root).into_iter().flatten().flatten() {
+        let pid = process.file_name().to_string_lossy().into_owned();
+        if !pid.chars().all(|ch| ch.is_ascii_digit()) {
+            continue;
+        }
+        let fdinfos = match fs::read_dir(process.path().join("fdinfo")) {
+            Ok(fdinfos) => fdinfos,
+            Err(_) => continue,
+        };
+
+        // A client is listed once for each file descriptor sharing it.
+        let mut clients = HashSet::new();
+        for fdinfo in fdinfos.flatten() {
+            let client = fs::read_to_string(fdinfo.path())
+                .ok()
+                .and_then(|fdinfo| parse_drm_fdinfo(&fdinfo));
+            if let Some(client) = client {
+                if client.driver == "amdgpu" && clients.insert(client.id.clone()) {
+                    *usage.entry((pid.clone(), client.pci_address)).or_default() +=
+                        client.vram_bytes;
+                }
+            }
+        }
+    }
+
+    usage
+        .into_iter()
+        .map(|((pid, pci_address), memory_used_bytes)| AmdProcess {
+            name: fs::read_to_string(procfs_root.join(&pid).join("comm"))
+                .map(|name| name.trim().to_owned())
+                .unwrap_or_default(),
+            pid,
+            pci_address,
+            memory_used_bytes,
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_drm_fdinfo(fdinfo: &str) -> Option<DrmClient> {
+    let mut driver = None;
+    let mut pci_address = None;
+    let mut id = None;
+    let mut vram_bytes = 0.0;
+    for line in fdinfo.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        match key {
+            "drm-driver" => driver = Some(value.to_owned()),
+            "drm-pdev" => pci_address = Some(value.to_owned()),
+            "drm-client-id" => id = Some(value.to_owned()),
+            "drm-memory-vram" => {
+                let (amount, unit) = value.split_once(' ').unwrap_or((value, ""));
+                let multiplier = match unit {
+                    "" => 1.0,
+                    "KiB" => 1024.0,
+                    "MiB" => MEBIBYTE,
+                    _ => continue,
+                };
+                vram_bytes = amount.parse::<f64>().ok()? * multiplier;
+            }
+            _ => {}
+        }
+    }
+    Some(DrmClient {
+        driver: driver?,
+        pci_address: pci_address?,
+        id: id?,
+        vram_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nvidia_gpus() {
+        let gpus = parse_nvidia_gpus(
+            "0, GPU-4c2e, NVIDIA A100-SXM4-40GB, 35, 40960, 1024, 45\n\
+             1, GPU-9f1a, Tesla T4, [N/A], 15360, 0, [Not Supported]\n",
+        );
+        assert_eq!(
+            gpus,
+            vec![
+                NvidiaGpu {
+                    index: "0".to_owned(),
+                    uuid: "GPU-4c2e".to_owned(),
+                    model: "NVIDIA A100-SXM4-40GB".to_owned(),
+                    utilization_percent: Some(35.0),
+                    memory_total_mib: Some(40960.0),
+                    memory_used_mib: Some(1024.0),
+                    temperature_celsius: Some(45.0),
+                },
+                NvidiaGpu {
+                    index: "1".to_owned(),
+                    uuid: "GPU-9f1a".to_owned(),
+                    model: "Tesla T4".to_owned(),
+                    utilization_percent: None,
+                    memory_total_mib: Some(15360.0),
+                    memory_used_mib: Some(0.0),
+                    temperature_celsius: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_nvidia_processes() {
+        let processes = parse_nvidia_processes("GPU-4c2e, 4242, /usr/bin/python3, 512\n");
+        assert_eq!(
+            processes,
+            vec![NvidiaProcess {
+                gpu_uuid: "GPU-4c2e".to_owned(),
+                pid: "4242".to_owned(),
+                name: "/usr/bin/python3".to_owned(),
+                memory_used_mib: Some(512.0),
+            }]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_drm_fdinfo() {
+        let client = parse_drm_fdinfo(
+            "pos:\t0\nflags:\t02100002\ndrm-driver:\tamdgpu\ndrm-pdev:\t0000:03:00.0\n\
+             drm-client-id:\t12\ndrm-memory-vram:\t2048 KiB\ndrm-memory-gtt:\t4 KiB\n",
+        );
+        assert_eq!(
+            client,
+            Some(DrmClient {
+                driver: "amdgpu".to_owned(),
+                pci_address: "0000:03:00.0".to_owned(),
+                id: "12".to_owned(),
+                vram_bytes: 2048.0 * 1024.0,
+            })
+        );
+
+        assert_eq!(parse_drm_fdinfo("pos:\t0\nflags:\t02100002\n"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_amd_gpus() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let pci = sysfs.path().join("devices/pci0000:00/0000:03:00.0");
+        fs::create_dir_all(pci.join("hwmon/hwmon3")).unwrap();
+        fs::create_dir_all(sysfs.path().join("bus/pci/drivers/amdgpu")).unwrap();
+        std::os::unix::fs::symlink(
+            sysfs.path().join("bus/pci/drivers/amdgpu"),
+            pci.join("driver"),
+        )
+        .unwrap();
+        fs::write(pci.join("gpu_busy_percent"), "42\n").unwrap();
+        fs::write(pci.join("mem_info_vram_total"), "17163091968\n").unwrap();
+        fs::write(pci.join("mem_info_vram_used"), "1073741824\n").unwrap();
+        fs::write(pci.join("hwmon/hwmon3/temp1_input"), "51000\n").unwrap();
+
+        let drm = sysfs.path().join("class/drm");
+        fs::create_dir_all(drm.join("card0")).unwrap();
+        fs::create_dir_all(drm.join("card0-DP-1")).unwrap();
+        std::os::unix::fs::symlink(&pci, drm.join("card0/device")).unwrap();
+
+        assert_eq!(
+            amd_gpus(sysfs.path()).unwrap(),
+            vec![AmdGpu {
+                card: "card0".to_owned(),
+                pci_address: "0000:03:00.0".to_owned(),
+                utilization_percent: Some(42.0),
+                memory_total_bytes: Some(17163091968.0),
+                memory_used_bytes: Some(1073741824.0),
+                temperature_celsius: Some(51.0),
+            }]
+        );
+    }
+}
@@ -32,8 +32,12 @@ mod cgroups;
 mod cpu;
 mod disk;
 mod filesystem;
+mod gpu;
 mod memory;
 mod network;
+// Paths are validated on every system, but only collected on Windows.
+#[cfg_attr(not(windows), allow(dead_code))]
+mod perf_counters;
 
 /// Collector types.
 #[serde_as]
@@ -55,6 +59,12 @@ pub enum Collector {
     /// Metrics related to filesystem space utilization.
     Filesystem,
 
+    /// Metrics related to GPU utilization.
+    ///
+    /// NVIDIA GPUs are queried with `nvidia-smi`, and AMD GPUs are read from the `amdgpu` driver
+    /// on Linux.
+    Gpu,
+
     /// Metrics related to the system load average.
     Load,
 
@@ -66,6 +76,12 @@ pub enum Collector {
 
     /// Metrics related to network utilization.
     Network,
+
+    /// Metrics of Windows performance counters.
+    ///
+    /// Only available on Windows.
+    #[serde(rename = "perf_counters")]
+    PerfCounters,
 }
 
 /// Filtering configuration.
@@ -98,7 +114,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors except `gpu` and `perf_counters`.
     #[configurable(metadata(docs::examples = "example_collectors()"))]
     #[derivative(Default(value = "default_collectors()"))]
     #[serde(default = "default_collectors")]
@@ -122,9 +138,17 @@ pub struct HostMetricsConfig {
     #[serde(default)]
     pub filesystem: filesystem::FilesystemConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub gpu: gpu::GpuConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub perf_counters: perf_counters::PerfCountersConfig,
 }
 
 /// Options for the cgroups (controller groups) metrics collector.
@@ -170,16 +194,18 @@ pub fn default_namespace() -> Option<String> {
     Some(String::from("host"))
 }
 
-const fn example_collectors() -> [&'static str; 8] {
+const fn example_collectors() -> [&'static str; 10] {
     [
         "cgroups",
         "cpu",
         "disk",
         "filesystem",
+        "gpu",
         "load",
         "host",
         "memory",
         "network",
+        "perf_counters",
     ]
 }
 
@@ -263,6 +289,16 @@ impl SourceConfig for HostMetricsConfig {
             }
         }
 
+        #[cfg(not(windows))]
+        {
+            if self.has_collector(Collector::PerfCounters) {
+                return Err(
+                    "Performance counters collector is only available on Windows systems".into(),
+                );
+            }
+        }
+        self.perf_counters.paths()?;
+
         let mut config = self.clone();
         config.namespace = config.namespace.filter(|namespace| !namespace.is_empty());
 
@@ -317,6 +353,8 @@ pub struct HostMetrics {
     config: HostMetricsConfig,
     #[cfg(target_os = "linux")]
     root_cgroup: Option<cgroups::CGroupRoot>,
+    #[cfg(windows)]
+    perf_counters: perf_counters::PerfCounterQueries,
     events_received: Registered<EventsReceived>,
 }
 
@@ -325,6 +363,8 @@ impl HostMetrics {
     pub fn new(config: HostMetricsConfig) -> Self {
         Self {
             config,
+            #[cfg(windows)]
+            perf_counters: Default::default(),
             events_received: register!(EventsReceived),
         }
     }
@@ -360,6 +400,9 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Filesystem) {
             self.filesystem_metrics(&mut buffer).await;
         }
+        if self.config.has_collector(Collector::Gpu) {
+            self.gpu_metrics(&mut buffer).await;
+        }
         if self.config.has_collector(Collector::Load) {
             self.loadavg_metrics(&mut buffer).await;
         }
@@ -373,6 +416,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        #[cfg(windows)]
+        if self.config.has_collector(Collector::PerfCounters) {
+            self.perf_counters_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         self.events_received.emit(CountByteSize(
@@ -0,0 +1,410 @@
+use glob::Pattern;
+use vector_config::configurable_component;
+
+#[cfg(windows)]
+use {
+    crate::internal_events::HostMetricsScrapeDetailError,
+    std::io,
+    vector_core::metric_tags,
+    windows_sys::Win32::System::Performance::{
+        PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+        PdhOpenQueryW, PDH_CSTATUS_NEW_DATA, PDH_CSTATUS_VALID_DATA, PDH_FMT_COUNTERVALUE_ITEM_W,
+        PDH_FMT_DOUBLE, PDH_FMT_NOCAP100, PDH_MORE_DATA,
+    },
+};
+
+#[cfg(windows)]
+use super::HostMetrics;
+
+/// The instance summing up all the others, which wildcards don't match unless `include_total` is
+/// set.
+const TOTAL_INSTANCE: &str = "_Total";
+
+/// Options for the Windows performance counters metrics collector.
+///
+/// This collector is only available on Windows.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default)]
+pub struct PerfCountersConfig {
+    /// The paths of the performance counters to report, as listed by `typeperf -q`.
+    ///
+    /// Paths are written `\Object(Instance)\Counter`, or `\Object\Counter` for objects without
+    /// instances. They are given in English, and translated to the language of the system.
+    ///
+    /// The instance can be a pattern, matched using globbing, such as `*` for every instance. Each
+    /// instance is reported with its name in the `instance` tag, followed by `#1`, `#2`, and so on
+    /// for instances sharing the same name.
+    #[configurable(metadata(docs::examples = "\\Processor(*)\\% Processor Time"))]
+    #[configurable(metadata(docs::examples = "\\Process(w3wp*)\\Working Set"))]
+    #[configurable(metadata(docs::examples = "\\Memory\\Available Bytes"))]
+    counters: Vec<String>,
+
+    /// Whether or not to report the `_Total` instance when it is matched by a pattern.
+    include_total: bool,
+}
+
+impl PerfCountersConfig {
+    /// Parses the paths of the counters.
+    pub(super) fn paths(&self) -> Result<Vec<CounterPath>, String> {
+        self.counters
+            .iter()
+            .map(|path| CounterPath::parse(path))
+            .collect()
+    }
+}
+
+/// A parsed path of a performance counter.
+#[derive(Debug, PartialEq)]
+pub(super) struct CounterPath {
+    object: String,
+    instance: Option<Pattern>,
+    counter: String,
+    /// The name of the metric, derived from the object and the counter.
+    name: String,
+}
+
+impl CounterPath {
+    fn parse(path: &str) -> Result<Self, String> {
+        let invalid =
+            |reason: &str| format!("Invalid performance counter path {:?}: {}.", path, reason);
+
+        if path.starts_with(r"\\") {
+            return Err(invalid("counters of remote computers are not supported"));
+        }
+        let (object, counter) = path
+            .strip_prefix('\\')
+            .and_then(|path| path.rsplit_once('\\'))
+            .ok_or_else(|| invalid(r"expected `\Object(Instance)\Counter`"))?;
+        let (object, instance) = match object.strip_suffix(')') {
+            Some(object) => {
+                let (object, instance) = object
+                    .split_once('(')
+                    .ok_or_else(|| invalid("unbalanced parentheses"))?;
+                let instance = Pattern::new(instance)
+                    .map_err(|error| invalid(&format!("invalid instance pattern, {}", error)))?;
+                (object, Some(instance))
+            }
+            None => (object, None),
+        };
+        if object.is_empty() || counter.is_empty() {
+            return Err(invalid("the object and the counter can't be empty"));
+        }
+        if counter.contains(['*', '?']) {
+            return Err(invalid("counter names can't contain wildcards"));
+        }
+
+        Ok(Self {
+            name: format!("{}_{}", metric_name(object), metric_name(counter)),
+            object: object.to_owned(),
+            instance,
+            counter: counter.to_owned(),
+        })
+    }
+
+    /// The path added to the query, matching every instance of the object.
+    #[cfg(windows)]
+    fn query_path(&self) -> String {
+        match &self.instance {
+            Some(_) => format!(r"\{}(*)\{}", self.object, self.counter),
+            None => format!(r"\{}\{}", self.object, self.counter),
+        }
+    }
+
+    /// Whether an instance of the object is reported.
+    fn matches(&self, instance: &str, include_total: bool) -> bool {
+        match &self.instance {
+            None => true,
+            Some(pattern) if pattern.as_str() == instance => true,
+            Some(pattern) => {
+                pattern.matches(instance) && (include_total || instance != TOTAL_INSTANCE)
+            }
+        }
+    }
+}
+
+/// Converts the name of an object or a counter to snake case, such as `% Processor Time` to
+/// `percent_processor_time` and `Bytes Total/sec` to `bytes_total_per_sec`.
+fn metric_name(name: &str) -> String {
+    let name = name
+        .replace('%', " percent ")
+        .replace('/', " per ")
+        .replace('#', " number ")
+        .to_lowercase();
+    name.split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Names the instances, suffixing those sharing the same name with `#1`, `#2`, and so on, as
+/// Performance Monitor does.
+fn instance_names(names: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashMap::<String, usize>::new();
+    names
+        .into_iter()
+        .map(|name| {
+            let count = seen.entry(name.clone()).or_default();
+            *count += 1;
+            match *count {
+                1 => name,
+                count => format!("{}#{}", name, count - 1),
+            }
+        })
+        .collect()
+}
+
+/// A PDH query of the configured counters, which is kept between scrapes as rates are computed
+/// from consecutive samples.
+#[cfg(windows)]
+pub(super) struct PerfCounterQuery {
+    query: isize,
+    counters: Vec<(CounterPath, isize)>,
+}
+
+#[cfg(windows)]
+impl PerfCounterQuery {
+    fn new(paths: Vec<CounterPath>) -> io::Result<Self> {
+        let mut query = 0;
+        // SAFETY: A null data source queries real-time data.
+        pdh_result(unsafe { PdhOpenQueryW(std::ptr::null(), 0, &mut query) })?;
+        let mut this = Self {
+            query,
+            counters: Vec::with_capacity(paths.len()),
+        };
+
+        for path in paths {
+            let query_path = wide(&path.query_path());
+            let mut counter = 0;
+            // SAFETY: The path is null terminated, and the query is open.
+            let result = pdh_result(unsafe {
+                PdhAddEnglishCounterW(this.query, query_path.as_ptr(), 0, &mut counter)
+            });
+            match result {
+                Ok(()) => this.counters.push((path, counter)),
+                // The object may not exist on this host, such as that of a role not installed.
+                Err(error) => emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to add performance counter.",
+                    error: format!("{}: {}", path.query_path(), error),
+                }),
+            }
+        }
+        Ok(this)
+    }
+
+    /// Samples the counters, returning the values of each instance reported.
+    fn collect(&self, include_total: bool) -> io::Result<Vec<(&CounterPath, Option<String>, f64)>> {
+        // SAFETY: The query is open.
+        pdh_result(unsafe { PdhCollectQueryData(self.query) })?;
+
+        let mut values = Vec::new();
+        for (path, counter) in &self.counters {
+            // Rates are invalid until the second sample, and instances may have ended since.
+            let items = match formatted_values(*counter) {
+                Ok(items) => items,
+                Err(_) => continue,
+            };
+            let (names, values_of_counter): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+            for (name, value) in instance_names(names).into_iter().zip(values_of_counter) {
+                let Some(value) = value else { continue };
+                if path.instance.is_none() {
+                    values.push((path, None, value));
+                } else if path.matches(&name, include_total) {
+                    values.push((path, Some(name), value));
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(windows)]
+impl Drop for PerfCounterQuery {
+    fn drop(&mut self) {
+        // SAFETY: The query is open, and closing it closes its counters.
+        unsafe { PdhCloseQuery(self.query) };
+    }
+}
+
+/// Gets the values of each instance of a counter, or `None` for those without a valid value.
+#[cfg(windows)]
+fn formatted_values(counter: isize) -> io::Result<Vec<(String, Option<f64>)>> {
+    let format = PDH_FMT_DOUBLE | PDH_FMT_NOCAP100;
+    let mut size = 0;
+    let mut count = 0;
+    // SAFETY: A null buffer asks for the size of the buffer needed.
+    let status = unsafe {
+        PdhGetFormattedCounterArrayW(counter, format, &mut size, &mut count, std::ptr::null_mut())
+    };
+    if status != PDH_MORE_DATA as u32 {
+        pdh_result(status)?;
+        return Ok(Vec::new());
+    }
+
+    // The names of the instances are stored after the items, so the buffer is sized in bytes.
+    let mut buffer = vec![0u64; (size as usize + 7) / 8];
+    let items = buffer.as_mut_ptr().cast::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+    // SAFETY: The buffer holds `size` bytes, and is aligned for the items.
+    pdh_result(unsafe {
+        PdhGetFormattedCounterArrayW(counter, format, &mut size, &mut count, items)
+    })?;
+    // SAFETY: The first `count` items were written to the buffer.
+    let items = unsafe { std::slice::from_raw_parts(items, count as usize) };
+
+    Ok(items
+        .iter()
+        .map(|item| {
+            // SAFETY: The names are null terminated strings in the buffer.
+            let name = unsafe { from_wide(item.szName) };
+            let value = [PDH_CSTATUS_VALID_DATA, PDH_CSTATUS_NEW_DATA]
+                .contains(&(item.FmtValue.CStatus as _))
+                // SAFETY: The values were formatted as doubles.
+                .then(|| unsafe { item.FmtValue.Anonymous.doubleValue });
+            (name, value)
+        })
+        .collect())
+}
+
+#[cfg(windows)]
+fn pdh_result(status: u32) -> io::Result<()> {
+    match status {
+        0 => Ok(()),
+        status => Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("PDH error {:#010x}", status),
+        )),
+    }
+}
+
+#[cfg(windows)]
+fn wide(string: &str) -> Vec<u16> {
+    string.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(windows)]
+unsafe fn from_wide(string: *const u16) -> String {
+    if string.is_null() {
+        return String::new();
+    }
+    let len = (0..).take_while(|&i| *string.add(i) != 0).count();
+    String::from_utf16_lossy(std::slice::from_raw_parts(string, len))
+}
+
+#[cfg(windows)]
+impl HostMetrics {
+    pub async fn perf_counters_metrics(&self, output: &mut super::MetricsBuffer) {
+        output.name = "perf_counters";
+        let config = &self.config.perf_counters;
+        let mut query = self
+            .perf_counters
+            .lock()
+            .expect("Performance counters mutex is poisoned");
+
+        if query.is_none() {
+            // The paths were validated when building the source.
+            let paths = config.paths().unwrap_or_default();
+            match PerfCounterQuery::new(paths) {
+                Ok(new_query) => *query = Some(new_query),
+                Err(error) => {
+                    emit!(HostMetricsScrapeDetailError {
+                        message: "Failed to open performance counters query.",
+                        error,
+                    });
+                    return;
+                }
+            }
+        }
+        let query = query.as_ref().expect("query was just opened");
+
+        let values = match query.collect(config.include_total) {
+            Ok(values) => values,
+            Err(error) => {
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to collect performance counters.",
+                    error,
+                });
+                return;
+            }
+        };
+        for (path, instance, value) in values {
+            let mut tags = metric_tags!(
+                "object" => path.object.clone(),
+                "counter" => path.counter.clone(),
+            );
+            if let Some(instance) = instance {
+                tags.replace("instance".into(), instance);
+            }
+            output.gauge(&path.name, value, tags);
+        }
+    }
+}
+
+#[cfg(windows)]
+pub(super) type PerfCounterQueries = std::sync::Mutex<Option<PerfCounterQuery>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_paths() {
+        let path = CounterPath::parse(r"\Processor(*)\% Processor Time").unwrap();
+        assert_eq!(path.object, "Processor");
+        assert_eq!(path.instance, Some(Pattern::new("*").unwrap()));
+        assert_eq!(path.counter, "% Processor Time");
+        assert_eq!(path.name, "processor_percent_processor_time");
+
+        let path =
+            CounterPath::parse(r"\Network Interface(Intel(R) Ethernet)\Bytes Total/sec").unwrap();
+        assert_eq!(path.object, "Network Interface");
+        assert_eq!(
+            path.instance,
+            Some(Pattern::new("Intel(R) Ethernet").unwrap())
+        );
+        assert_eq!(path.name, "network_interface_bytes_total_per_sec");
+
+        let path = CounterPath::parse(r"\Memory\Available Bytes").unwrap();
+        assert_eq!(path.instance, None);
+        assert_eq!(path.name, "memory_available_bytes");
+    }
+
+    #[test]
+    fn rejects_invalid_paths() {
+        for path in [
+            r"Memory\Available Bytes",
+            r"\\server\Memory\Available Bytes",
+            r"\Memory",
+            r"\Process(*)\*",
+            r"\Process(*\Working Set",
+        ] {
+            assert!(CounterPath::parse(path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn matches_instances() {
+        let path = CounterPath::parse(r"\Process(w3wp*)\Working Set").unwrap();
+        assert!(path.matches("w3wp", false));
+        assert!(path.matches("w3wp#1", false));
+        assert!(!path.matches("svchost", false));
+
+        let path = CounterPath::parse(r"\Processor(*)\% Processor Time").unwrap();
+        assert!(path.matches("0", false));
+        assert!(!path.matches("_Total", false));
+        assert!(path.matches("_Total", true));
+
+        let path = CounterPath::parse(r"\Processor(_Total)\% Processor Time").unwrap();
+        assert!(path.matches("_Total", false));
+    }
+
+    #[test]
+    fn names_instances() {
+        let names = ["svchost", "w3wp", "svchost", "svchost"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            instance_names(names),
+            vec!["svchost", "w3wp", "svchost#1", "svchost#2"]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Persistence of the conditions of the requests of the `http_client` source.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal_events::HttpClientCheckpointError;
+
+const CHECKPOINT_FILENAME: &str = "checkpoints.json";
+
+/// The conditions of the next request to a URL, derived from the last response.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub(super) struct Checkpoint {
+    /// The `ETag` header of the last response, sent back with `If-None-Match`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The `Last-Modified` header of the last response, sent back with `If-Modified-Since`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// The cursor of the last event of the last response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    committed: HashMap<String, Checkpoint>,
+    in_flight: HashSet<String>,
+}
+
+/// The checkpoints of the URLs called by a source, persisted as a JSON object keyed by URL.
+pub(super) struct Checkpointer {
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+impl Checkpointer {
+    /// Loads the checkpoints persisted in the data directory, if any.
+    pub(super) fn load(data_dir: PathBuf) -> io::Result<Self> {
+        let path = data_dir.join(CHECKPOINT_FILENAME);
+        let committed = match std::fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self {
+            path,
+            state: Mutex::new(State {
+                committed,
+                in_flight: HashSet::new(),
+            }),
+        })
+    }
+
+    /// Starts a request to the URL, returning its checkpoint.
+    ///
+    /// Returns `None` while the events of the previous request are not acknowledged yet.
+    pub(super) fn begin(self: &Arc<Self>, url: &str) -> Option<Pending> {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if !state.in_flight.insert(url.to_owned()) {
+            return None;
+        }
+        Some(Pending {
+            checkpointer: Arc::clone(self),
+            url: url.to_owned(),
+            checkpoint: state.committed.get(url).cloned().unwrap_or_default(),
+        })
+    }
+
+    fn commit(&self, url: &str, checkpoint: Checkpoint) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if state.committed.get(url) == Some(&checkpoint) {
+            return;
+        }
+        state.committed.insert(url.to_owned(), checkpoint);
+        if let Err(error) = self.persist(&state.committed) {
+            emit!(HttpClientCheckpointError {
+                error,
+                path: self.path.to_string_lossy().into_owned(),
+            });
+        }
+    }
+
+    fn persist(&self, committed: &HashMap<String, Checkpoint>) -> io::Result<()> {
+        // Written to a temporary file first so that a crash never leaves a truncated checkpoint.
+        let temporary = self.path.with_extension("json.tmp");
+        std::fs::write(&temporary, serde_json::to_vec(committed)?)?;
+        std::fs::rename(&temporary, &self.path)
+    }
+}
+
+/// A request whose checkpoint is not advanced yet.
+///
+/// Dropping it without committing keeps the previous checkpoint, so that the request is replayed.
+pub(super) struct Pending {
+    checkpointer: Arc<Checkpointer>,
+    url: String,
+    checkpoint: Checkpoint,
+}
+
+impl Pending {
+    /// The checkpoint the request is made from.
+    pub(super) const fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// Advances the checkpoint of the URL.
+    pub(super) fn commit(self, checkpoint: Checkpoint) {
+        self.checkpointer.commit(&self.url, checkpoint);
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        self.checkpointer
+            .state
+            .lock()
+            .expect("poisoned lock")
+            .in_flight
+            .remove(&self.url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(cursor: &str) -> Checkpoint {
+        Checkpoint {
+            etag: Some(format!("\"{}\"", cursor)),
+            last_modified: None,
+            cursor: Some(cursor.to_owned()),
+        }
+    }
+
+    #[test]
+    fn skips_urls_in_flight() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpointer = Arc::new(Checkpointer::load(dir.path().to_owned()).unwrap());
+
+        let pending = checkpointer.begin("http://a").unwrap();
+        assert!(checkpointer.begin("http://a").is_none());
+        assert!(checkpointer.begin("http://b").is_some());
+
+        drop(pending);
+        assert!(checkpointer.begin("http://a").is_some());
+    }
+
+    #[test]
+    fn persists_committed_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpointer = Arc::new(Checkpointer::load(dir.path().to_owned()).unwrap());
+
+        let pending = checkpointer.begin("http://a").unwrap();
+        assert_eq!(pending.checkpoint(), &Checkpoint::default());
+        pending.commit(checkpoint("1"));
+        // Dropped without committing, so the checkpoint is kept.
+        drop(checkpointer.begin("http://a").unwrap());
+
+        let checkpointer = Arc::new(Checkpointer::load(dir.path().to_owned()).unwrap());
+        let pending = checkpointer.begin("http://a").unwrap();
+        assert_eq!(pending.checkpoint(), &checkpoint("1"));
+    }
+}
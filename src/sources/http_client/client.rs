@@ -4,18 +4,22 @@
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use futures_util::FutureExt;
-use http::{response::Parts, Uri};
+use http::{header, response::Parts, Uri};
+use hyper::{Body, Request};
+use lookup::{lookup_v2::OptionalValuePath, PathPrefix};
 use serde_with::serde_as;
 use snafu::ResultExt;
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio_util::codec::Decoder as _;
 
+use super::checkpoint::{Checkpoint, Checkpointer, Pending};
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{SourceConfig, SourceContext},
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext},
+    event::{BatchNotifier, BatchStatus},
     http::Auth,
     register_validatable_component,
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     sources,
     sources::util::{
         http::HttpMethod,
@@ -103,12 +107,50 @@ pub struct HttpClientConfig {
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint: CheckpointConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     pub log_namespace: Option<bool>,
 }
 
+/// Checkpointing options.
+///
+/// When enabled, the `ETag` and `Last-Modified` headers of the last response and its cursor are
+/// persisted in the global `data_dir`. They are sent back with the `If-None-Match` and
+/// `If-Modified-Since` headers and the `cursor_parameter` query parameter of the next request,
+/// including after a restart. A `304 Not Modified` response yields no events.
+///
+/// With acknowledgements enabled, the checkpoint only advances once the events of the response are
+/// delivered, and the endpoint is not called again until then. Otherwise, the previous request is
+/// replayed, so that events are ingested at least once.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckpointConfig {
+    /// Whether or not requests are checkpointed.
+    pub enabled: bool,
+
+    /// The field of the decoded events holding the cursor.
+    ///
+    /// The value of the last event of the response holding it is used.
+    #[configurable(metadata(docs::examples = "id"))]
+    pub cursor_field: OptionalValuePath,
+
+    /// The query parameter the cursor is sent with.
+    ///
+    /// It is appended to the parameters of the `endpoint` and `query` options.
+    #[configurable(metadata(docs::examples = "since"))]
+    pub cursor_parameter: Option<String>,
+}
+
 const fn default_http_method() -> HttpMethod {
     HttpMethod::Get
 }
@@ -159,6 +201,8 @@ impl Default for HttpClientConfig {
             method: default_http_method(),
             tls: None,
             auth: None,
+            checkpoint: CheckpointConfig::default(),
+            acknowledgements: SourceAcknowledgementsConfig::default(),
             log_namespace: None,
         }
     }
@@ -187,14 +231,34 @@ impl SourceConfig for HttpClientConfig {
 
         let content_type = self.decoding.content_type(&self.framing).to_string();
 
-        // the only specific context needed is the codec decoding
-        let context = HttpClientContext {
+        let checkpoint = if self.checkpoint.enabled {
+            if self.checkpoint.cursor_field.path.is_some()
+                != self.checkpoint.cursor_parameter.is_some()
+            {
+                return Err(
+                    "`checkpoint.cursor_field` and `checkpoint.cursor_parameter` must be set together"
+                        .into(),
+                );
+            }
+            let data_dir = cx.globals.resolve_and_make_data_subdir(None, cx.key.id())?;
+            Some(CheckpointSettings {
+                checkpointer: Arc::new(Checkpointer::load(data_dir)?),
+                cursor_field: self.checkpoint.cursor_field.clone(),
+                cursor_parameter: self.checkpoint.cursor_parameter.clone(),
+                acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+            })
+        } else {
+            None
+        };
+
+        let context = HttpClientContextBuilder {
             decoder,
             log_namespace,
+            checkpoint,
         };
 
         let inputs = GenericHttpClientInputs {
-            urls,
+            urls: urls.into(),
             interval: self.interval,
             headers: self.headers.clone(),
             content_type,
@@ -224,7 +288,7 @@ impl SourceConfig for HttpClientConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.checkpoint.enabled
     }
 }
 
@@ -262,11 +326,29 @@ impl HttpClientConfig {
     }
 }
 
+/// The checkpointing of the requests, if enabled.
+#[derive(Clone)]
+struct CheckpointSettings {
+    checkpointer: Arc<Checkpointer>,
+    cursor_field: OptionalValuePath,
+    cursor_parameter: Option<String>,
+    acknowledgements: bool,
+}
+
 /// Captures the configuration options required to decode the incoming requests into events.
 #[derive(Clone)]
+pub struct HttpClientContextBuilder {
+    pub decoder: Decoder,
+    pub log_namespace: LogNamespace,
+    checkpoint: Option<CheckpointSettings>,
+}
+
+/// The context of a single request.
 pub struct HttpClientContext {
     pub decoder: Decoder,
     pub log_namespace: LogNamespace,
+    checkpoint: Option<CheckpointSettings>,
+    pending: Option<Pending>,
 }
 
 impl HttpClientContext {
@@ -291,25 +373,98 @@ impl HttpClientContext {
         }
         events
     }
+
+    /// Advances the checkpoint of the request once its events are delivered.
+    fn checkpoint_events(&mut self, parts: &Parts, events: &mut Vec<Event>) {
+        let (Some(settings), Some(pending)) = (&self.checkpoint, self.pending.take()) else {
+            return;
+        };
+        let header_value = |name: header::HeaderName| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let cursor = settings.cursor_field.path.as_ref().and_then(|path| {
+            events.iter().rev().find_map(|event| {
+                event
+                    .maybe_as_log()
+                    .and_then(|log| log.get((PathPrefix::Event, path)))
+                    .map(|value| value.to_string_lossy().into_owned())
+            })
+        });
+        let checkpoint = Checkpoint {
+            etag: header_value(header::ETAG),
+            last_modified: header_value(header::LAST_MODIFIED),
+            cursor: cursor.or_else(|| pending.checkpoint().cursor.clone()),
+        };
+
+        match BatchNotifier::maybe_apply_to(settings.acknowledgements, events) {
+            Some(receiver) => {
+                tokio::spawn(async move {
+                    if receiver.await == BatchStatus::Delivered {
+                        pending.commit(checkpoint);
+                    }
+                });
+            }
+            None => pending.commit(checkpoint),
+        }
+    }
 }
 
-impl HttpClientBuilder for HttpClientContext {
+impl HttpClientBuilder for HttpClientContextBuilder {
     type Context = HttpClientContext;
 
     /// No additional context from request data is needed from this particular client.
     fn build(&self, _uri: &Uri) -> Self::Context {
-        self.clone()
+        HttpClientContext {
+            decoder: self.decoder.clone(),
+            log_namespace: self.log_namespace,
+            checkpoint: self.checkpoint.clone(),
+            pending: None,
+        }
     }
 }
 
 impl http_client::HttpClientContext for HttpClientContext {
+    /// Makes the request conditional on the checkpoint of the previous one.
+    fn on_request(&mut self, url: &Uri, request: &mut Request<Body>) -> bool {
+        let Some(settings) = &self.checkpoint else {
+            return true;
+        };
+        let Some(pending) = settings.checkpointer.begin(&url.to_string()) else {
+            // The events of the previous request are not acknowledged yet.
+            return false;
+        };
+
+        let checkpoint = pending.checkpoint();
+        let headers = request.headers_mut();
+        for (name, value) in [
+            (header::IF_NONE_MATCH, &checkpoint.etag),
+            (header::IF_MODIFIED_SINCE, &checkpoint.last_modified),
+        ] {
+            if let Some(value) = value.as_deref().and_then(|value| value.parse().ok()) {
+                headers.insert(name, value);
+            }
+        }
+        if let (Some(parameter), Some(cursor)) = (&settings.cursor_parameter, &checkpoint.cursor) {
+            let query = HashMap::from([(parameter.clone(), vec![cursor.clone()])]);
+            *request.uri_mut() = build_url(url, &query);
+        }
+
+        self.pending = Some(pending);
+        true
+    }
+
     /// Decodes the HTTP response body into events per the decoder configured.
-    fn on_response(&mut self, _url: &Uri, _header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
+    fn on_response(&mut self, _url: &Uri, header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
         // get the body into a byte array
         let mut buf = BytesMut::new();
         buf.extend_from_slice(body);
 
-        let events = self.decode_events(&mut buf);
+        let mut events = self.decode_events(&mut buf);
+        self.checkpoint_events(header, &mut events);
 
         Some(events)
     }
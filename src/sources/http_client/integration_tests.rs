@@ -60,6 +60,8 @@ async fn invalid_endpoint() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -78,6 +80,8 @@ async fn collected_logs_bytes() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -102,6 +106,8 @@ async fn collected_logs_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -126,6 +132,8 @@ async fn collected_metrics_native_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -155,6 +163,8 @@ async fn collected_trace_native_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -179,6 +189,8 @@ async fn unauthorized_no_auth() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -200,6 +212,8 @@ async fn unauthorized_wrong_auth() {
             user: "white_rabbit".to_string(),
             password: "morpheus".to_string().into(),
         }),
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -221,6 +235,8 @@ async fn authorized() {
             user: "user".to_string(),
             password: "pass".to_string().into(),
         }),
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -242,6 +258,8 @@ async fn tls_invalid_ca() {
             ..Default::default()
         }),
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -263,6 +281,8 @@ async fn tls_valid() {
             ..Default::default()
         }),
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -282,6 +302,8 @@ async fn shutdown() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     };
 
@@ -1,4 +1,6 @@
 #[cfg(feature = "sources-http_client")]
+mod checkpoint;
+#[cfg(feature = "sources-http_client")]
 pub mod client;
 
 #[cfg(test)]
@@ -1,18 +1,28 @@
 use codecs::CharacterDelimitedDecoderConfig;
-use std::collections::HashMap;
+use futures::StreamExt;
+use lookup::lookup_v2::OptionalValuePath;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use tokio::time::Duration;
-use warp::{http::HeaderMap, Filter};
+use warp::{
+    http::{HeaderMap, StatusCode},
+    Filter, Reply,
+};
 
 use crate::sources::util::http::HttpMethod;
 use crate::{serde::default_decoding, serde::default_framing_message_based};
 use codecs::decoding::{CharacterDelimitedDecoderOptions, DeserializerConfig, FramingConfig};
 use vector_core::event::Event;
 
-use super::HttpClientConfig;
+use super::{client::CheckpointConfig, HttpClientConfig};
+use crate::config::{SourceConfig, SourceContext};
 use crate::test_util::{
     components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
     next_addr, test_generate_config, wait_for_tcp,
 };
+use crate::SourceSender;
 
 pub(crate) const INTERVAL: Duration = Duration::from_secs(1);
 
@@ -54,6 +64,8 @@ async fn bytes_decoding() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -82,6 +94,8 @@ async fn json_decoding_newline_delimited() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -115,6 +129,8 @@ async fn json_decoding_character_delimited() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -148,6 +164,8 @@ async fn request_query_applied() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -213,6 +231,8 @@ async fn headers_applied() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
@@ -241,7 +261,74 @@ async fn accept_header_override() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        checkpoint: Default::default(),
+        acknowledgements: Default::default(),
         log_namespace: None,
     })
     .await;
 }
+
+/// Checkpointed requests should be conditional on the previous response, also after a restart.
+#[tokio::test]
+async fn checkpoint_conditions_requests() {
+    let in_addr = next_addr();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let received = Arc::clone(&requests);
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::optional::<String>("if-none-match"))
+        .map(move |query: String, etag: Option<String>| {
+            received.lock().unwrap().push((query, etag.clone()));
+            if etag.as_deref() == Some("\"2\"") {
+                StatusCode::NOT_MODIFIED.into_response()
+            } else {
+                warp::reply::with_header(r#"{"id": "2"}"#, "ETag", "\"2\"").into_response()
+            }
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let data_dir = tempfile::tempdir().unwrap();
+    let config = HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        decoding: DeserializerConfig::Json(Default::default()),
+        checkpoint: CheckpointConfig {
+            enabled: true,
+            cursor_field: OptionalValuePath::try_from("id".to_owned()).unwrap(),
+            cursor_parameter: Some("since".to_owned()),
+        },
+        ..Default::default()
+    };
+    let run = |config: HttpClientConfig| {
+        let data_dir = data_dir.path().to_owned();
+        async move {
+            let (tx, rx) = SourceSender::new_test();
+            let mut cx = SourceContext::new_test(tx, None);
+            cx.globals.data_dir = Some(data_dir);
+            let source = tokio::spawn(config.build(cx).await.unwrap());
+            tokio::time::sleep(INTERVAL + INTERVAL / 2).await;
+            source.abort();
+            rx.collect::<Vec<_>>().await
+        }
+    };
+
+    let events = run(config.clone()).await;
+    assert_eq!(events.len(), 1);
+    // Restarted from the persisted checkpoint.
+    let events = run(config).await;
+    assert!(events.is_empty());
+
+    let etag = Some("\"2\"".to_owned());
+    assert_eq!(
+        *requests.lock().unwrap(),
+        vec![
+            (String::new(), None),
+            ("since=2".to_owned(), etag.clone()),
+            ("since=2".to_owned(), etag.clone()),
+            ("since=2".to_owned(), etag),
+        ]
+    );
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -34,6 +34,7 @@ use crate::{
         Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
+    zstd_dictionary::{ZstdDictionaries, ZstdDictionaryConfig},
 };
 
 /// Configuration for the `http` source.
@@ -142,6 +143,15 @@ pub struct SimpleHttpConfig {
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
 
+    /// Trained zstd dictionaries that clients may compress their payloads with.
+    ///
+    /// The IDs of the dictionaries are listed in the `vector-zstd-dictionary` response header,
+    /// and payloads compressed with one of them name its ID in the same request header. The
+    /// `http` sink does so when configured with one of them in its `compression_dictionary`.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    compression_dictionaries: Vec<ZstdDictionaryConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -246,6 +256,7 @@ impl Default for SimpleHttpConfig {
             framing: None,
             decoding: Some(default_decoding()),
             acknowledgements: SourceAcknowledgementsConfig::default(),
+            compression_dictionaries: Vec::new(),
             log_namespace: None,
         }
     }
@@ -323,6 +334,7 @@ impl SourceConfig for SimpleHttpConfig {
             path_key: self.path_key.clone(),
             decoder,
             log_namespace,
+            zstd_dictionaries: Arc::new(ZstdDictionaries::load(&self.compression_dictionaries)?),
         };
         source.run(
             self.address,
@@ -368,6 +380,7 @@ struct SimpleHttpSource {
     path_key: OptionalValuePath,
     decoder: Decoder,
     log_namespace: LogNamespace,
+    zstd_dictionaries: Arc<ZstdDictionaries>,
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -456,6 +469,10 @@ impl HttpSource for SimpleHttpSource {
 
         Ok(events)
     }
+
+    fn zstd_dictionaries(&self) -> Option<&ZstdDictionaries> {
+        Some(&self.zstd_dictionaries)
+    }
 }
 
 #[cfg(test)]
@@ -484,6 +501,7 @@ mod tests {
 
     use super::{remove_duplicates, SimpleHttpConfig};
     use crate::sources::http_server::HttpMethod;
+    use crate::zstd_dictionary::{ZstdDictionaryConfig, DICTIONARY_HEADER};
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
         event::{Event, EventStatus, Value},
@@ -538,6 +556,7 @@ mod tests {
                 framing,
                 decoding,
                 acknowledgements: acknowledgements.into(),
+                compression_dictionaries: Vec::new(),
                 log_namespace: None,
             }
             .build(context)
@@ -1093,6 +1112,63 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_zstd_dictionary() {
+        let samples = (0..1000)
+            .map(|i| format!("request {} handled by edge-{}", i, i % 7).into_bytes())
+            .collect::<Vec<_>>();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zstd.dict");
+        std::fs::write(&path, zstd::dict::from_samples(&samples, 4096).unwrap()).unwrap();
+        let dictionary = ZstdDictionaryConfig { path };
+        let compressor = dictionary.load(zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+
+        let (sender, rx) = SourceSender::new_test();
+        let address = next_addr();
+        let source = SimpleHttpConfig {
+            address,
+            compression_dictionaries: vec![dictionary],
+            ..Default::default()
+        }
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let send = |dictionary: u32| {
+            reqwest::Client::new()
+                .post(&format!("http://{}/", address))
+                .header("Content-Encoding", "zstd")
+                .header(DICTIONARY_HEADER, dictionary)
+                .body(
+                    compressor
+                        .compress(b"request 4242 handled by edge-3")
+                        .unwrap(),
+                )
+                .send()
+        };
+
+        let response = send(compressor.id()).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()[DICTIONARY_HEADER],
+            compressor.id().to_string().as_str()
+        );
+        let events = crate::test_util::collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "request 4242 handled by edge-3".into()
+        );
+
+        let response = send(compressor.id().wrapping_add(1)).await.unwrap();
+        assert_eq!(response.status(), 503);
+        assert_eq!(
+            response.headers()[DICTIONARY_HEADER],
+            compressor.id().to_string().as_str()
+        );
+    }
+
     #[tokio::test]
     async fn http_path() {
         let mut events = assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
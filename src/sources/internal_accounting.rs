@@ -0,0 +1,308 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde_with::serde_as;
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, schema::Definition, EstimatedJsonEncodedSizeOf};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{ComponentKey, DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, LogEvent, Value},
+    internal_events::{EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    topology::accounting::{self, Counts, Direction, Entry},
+    SourceSender,
+};
+
+/// Configuration for the `internal_accounting` source.
+#[serde_as]
+#[configurable_component(source(
+    "internal_accounting",
+    "Emit summaries of the events and bytes accounted by the running Vector instance."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct InternalAccountingConfig {
+    /// The interval between summaries, in seconds.
+    ///
+    /// Each summary holds the events and bytes accounted since the previous one.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[serde(default = "default_interval")]
+    #[configurable(metadata(docs::human_name = "Interval"))]
+    pub interval_secs: Duration,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl Default for InternalAccountingConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_interval(),
+            log_namespace: None,
+        }
+    }
+}
+
+fn default_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+impl_generate_config_from_default!(InternalAccountingConfig);
+
+impl InternalAccountingConfig {
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let dimensions = Kind::object(Collection::empty().with_unknown(Kind::bytes().or_null()));
+        let fields = Collection::empty()
+            .with_known("component_id", Kind::bytes())
+            .with_known("direction", Kind::bytes())
+            .with_known("dimensions", dimensions)
+            .with_known("overflow", Kind::boolean())
+            .with_known("events", Kind::integer())
+            .with_known("bytes", Kind::integer())
+            .with_known("window_start", Kind::timestamp());
+
+        Definition::new_with_default_metadata(Kind::object(fields), [log_namespace])
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "internal_accounting")]
+impl SourceConfig for InternalAccountingConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.interval_secs.is_zero() {
+            return Err("`interval_secs` must be greater than zero".into());
+        }
+
+        Ok(Box::pin(
+            InternalAccounting {
+                interval: self.interval_secs,
+                log_namespace: cx.log_namespace(self.log_namespace),
+                out: cx.out,
+                shutdown: cx.shutdown,
+            }
+            .run(),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+type GroupKey = (
+    ComponentKey,
+    Direction,
+    Option<Vec<(String, Option<String>)>>,
+);
+
+/// The counts of the previous summary, to compute those of the next one.
+#[derive(Default)]
+struct Previous {
+    counts: HashMap<GroupKey, Counts>,
+}
+
+impl Previous {
+    /// The counts accounted since the previous summary, of the groups that have any.
+    fn deltas(&mut self, entries: Vec<Entry>) -> Vec<(Entry, Counts)> {
+        let mut deltas = Vec::new();
+        let mut counts = HashMap::with_capacity(entries.len());
+        for entry in entries {
+            let key = (
+                entry.component.clone(),
+                entry.direction,
+                entry.dimensions.clone(),
+            );
+            let previous = self.counts.get(&key).copied().unwrap_or_default();
+            // The counts are cleared when the dimensions change.
+            let delta = if entry.counts.events >= previous.events {
+                Counts {
+                    events: entry.counts.events - previous.events,
+                    bytes: entry.counts.bytes.saturating_sub(previous.bytes),
+                }
+            } else {
+                entry.counts
+            };
+            counts.insert(key, entry.counts);
+            if delta.events > 0 {
+                deltas.push((entry, delta));
+            }
+        }
+        self.counts = counts;
+        deltas
+    }
+}
+
+fn summary(
+    entry: Entry,
+    counts: Counts,
+    window_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+    log_namespace: LogNamespace,
+) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert("component_id", entry.component.id().to_owned());
+    log.insert("direction", entry.direction.as_str());
+    log.insert("overflow", entry.dimensions.is_none());
+    let dimensions = entry
+        .dimensions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name, value.map_or(Value::Null, Value::from)))
+        .collect::<BTreeMap<_, _>>();
+    log.insert("dimensions", Value::Object(dimensions));
+    log.insert("events", counts.events as i64);
+    log.insert("bytes", counts.bytes as i64);
+    log.insert("window_start", window_start);
+
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        InternalAccountingConfig::NAME,
+        now,
+    );
+    log
+}
+
+struct InternalAccounting {
+    interval: time::Duration,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl InternalAccounting {
+    async fn run(mut self) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let mut previous = Previous::default();
+        // Events accounted before the source started are not summarized.
+        previous.deltas(accounting::snapshot());
+        let mut window_start = Utc::now();
+
+        let mut interval = IntervalStream::new(time::interval_at(
+            time::Instant::now() + self.interval,
+            self.interval,
+        ))
+        .take_until(self.shutdown);
+        while interval.next().await.is_some() {
+            let now = Utc::now();
+            let events = previous
+                .deltas(accounting::snapshot())
+                .into_iter()
+                .map(|(entry, counts)| {
+                    Event::from(summary(
+                        entry,
+                        counts,
+                        window_start,
+                        now,
+                        self.log_namespace,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            window_start = now;
+
+            let count = events.len();
+            if count == 0 {
+                continue;
+            }
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            if self.out.send_batch(events).await.is_err() {
+                emit!(StreamClosedError { count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(tenant: Option<&str>, events: u64, bytes: u64) -> Entry {
+        Entry {
+            component: ComponentKey::from("in"),
+            direction: Direction::Ingested,
+            dimensions: Some(vec![("tenant".to_owned(), tenant.map(str::to_owned))]),
+            counts: Counts { events, bytes },
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<InternalAccountingConfig>();
+    }
+
+    #[test]
+    fn summarizes_counts_since_previous_summary() {
+        let mut previous = Previous::default();
+        assert_eq!(previous.deltas(vec![entry(Some("a"), 10, 1000)]).len(), 1);
+
+        let deltas = previous.deltas(vec![entry(Some("a"), 15, 1600), entry(None, 2, 100)]);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(
+            deltas[0].1,
+            Counts {
+                events: 5,
+                bytes: 600
+            }
+        );
+        assert_eq!(
+            deltas[1].1,
+            Counts {
+                events: 2,
+                bytes: 100
+            }
+        );
+
+        // Unchanged groups aren't summarized.
+        assert!(previous
+            .deltas(vec![entry(Some("a"), 15, 1600), entry(None, 2, 100)])
+            .is_empty());
+    }
+
+    #[test]
+    fn emits_summaries() {
+        let now = Utc::now();
+        let log = summary(
+            entry(Some("a"), 5, 600),
+            Counts {
+                events: 5,
+                bytes: 600,
+            },
+            now - chrono::Duration::seconds(60),
+            now,
+            LogNamespace::Legacy,
+        );
+
+        assert_eq!(log["component_id"], "in".into());
+        assert_eq!(log["direction"], "ingested".into());
+        assert_eq!(log["dimensions.tenant"], "a".into());
+        assert_eq!(log["overflow"], false.into());
+        assert_eq!(log["events"], Value::from(5_i64));
+        assert_eq!(log["bytes"], Value::from(600_i64));
+        assert_eq!(log["source_type"], "internal_accounting".into());
+    }
+}
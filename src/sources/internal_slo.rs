@@ -0,0 +1,619 @@
+use std::{collections::VecDeque, time::Duration};
+
+use chrono::Utc;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use serde_with::serde_as;
+use tokio::time::{self, Instant};
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, schema::Definition, EstimatedJsonEncodedSizeOf};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{Event, LogEvent, Metric, MetricValue},
+    internal_events::{EventsReceived, SloEvaluated, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `internal_slo` source.
+#[serde_as]
+#[configurable_component(source(
+    "internal_slo",
+    "Evaluate service level objectives of the running Vector instance from its internal metrics."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InternalSloConfig {
+    /// The interval between evaluations of the objectives, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[serde(default = "default_interval")]
+    #[configurable(metadata(docs::human_name = "Interval"))]
+    pub interval_secs: Duration,
+
+    /// The window over which the objectives are evaluated, in seconds.
+    ///
+    /// Until Vector has run for that long, objectives are evaluated since the source started.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[serde(default = "default_window")]
+    #[configurable(metadata(docs::human_name = "Window"))]
+    pub window_secs: Duration,
+
+    /// The objectives, keyed by name.
+    #[configurable(metadata(docs::additional_props_description = "An objective."))]
+    pub objectives: IndexMap<String, ObjectiveConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_window() -> Duration {
+    Duration::from_secs(3600)
+}
+
+fn default_errors_metric() -> String {
+    "component_errors_total".to_owned()
+}
+
+fn default_total_metric() -> String {
+    "component_received_events_total".to_owned()
+}
+
+/// A service level objective.
+///
+/// An objective is met while the ratio of good events over the window, its indicator, is at least
+/// its target.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "type")]
+#[configurable(metadata(docs::enum_tag_description = "The indicator of the objective."))]
+pub enum ObjectiveConfig {
+    /// The ratio of the observations of a histogram metric that are at most a threshold.
+    ///
+    /// Observations are counted by the buckets of the histogram, so the threshold should be the
+    /// upper limit of one of them.
+    Latency {
+        /// The name of the histogram metric.
+        #[configurable(metadata(docs::examples = "http_client_rtt_seconds"))]
+        metric: String,
+
+        /// The highest latency of a good observation, in seconds.
+        #[configurable(metadata(docs::examples = 30.0))]
+        threshold_secs: f64,
+
+        /// The lowest ratio of good observations, between `0.0` and `1.0`.
+        #[configurable(metadata(docs::examples = 0.99))]
+        target: f64,
+
+        /// The component the metric is measured for.
+        ///
+        /// If unset, the metric is measured for all components.
+        component_id: Option<String>,
+    },
+
+    /// The ratio of the events processed without error.
+    ErrorRate {
+        /// The name of the counter of errors.
+        #[serde(default = "default_errors_metric")]
+        errors_metric: String,
+
+        /// The name of the counter of events.
+        #[serde(default = "default_total_metric")]
+        total_metric: String,
+
+        /// The lowest ratio of events processed without error, between `0.0` and `1.0`.
+        #[configurable(metadata(docs::examples = 0.999))]
+        target: f64,
+
+        /// The component the metrics are measured for.
+        ///
+        /// If unset, the metrics are measured for all components.
+        component_id: Option<String>,
+    },
+}
+
+impl ObjectiveConfig {
+    const fn indicator(&self) -> &'static str {
+        match self {
+            Self::Latency { .. } => "latency",
+            Self::ErrorRate { .. } => "error_rate",
+        }
+    }
+
+    const fn target(&self) -> f64 {
+        match self {
+            Self::Latency { target, .. } | Self::ErrorRate { target, .. } => *target,
+        }
+    }
+
+    fn component_id(&self) -> Option<&str> {
+        match self {
+            Self::Latency { component_id, .. } | Self::ErrorRate { component_id, .. } => {
+                component_id.as_deref()
+            }
+        }
+    }
+
+    /// Counts the good and total events of the objective since Vector started.
+    fn count(&self, metrics: &[Metric]) -> Counts {
+        let mut counts = Counts::default();
+        let metrics = metrics.iter().filter(|metric| {
+            self.component_id().map_or(true, |id| {
+                metric.tag_value("component_id").as_deref() == Some(id)
+            })
+        });
+        match self {
+            Self::Latency {
+                metric: name,
+                threshold_secs,
+                ..
+            } => {
+                for metric in metrics.filter(|metric| metric.name() == name) {
+                    if let MetricValue::AggregatedHistogram { buckets, count, .. } = metric.value()
+                    {
+                        counts.total += *count as f64;
+                        counts.good += buckets
+                            .iter()
+                            .filter(|bucket| bucket.upper_limit <= *threshold_secs)
+                            .map(|bucket| bucket.count as f64)
+                            .sum::<f64>();
+                    }
+                }
+            }
+            Self::ErrorRate {
+                errors_metric,
+                total_metric,
+                ..
+            } => {
+                let mut errors = 0.0;
+                for metric in metrics {
+                    if let MetricValue::Counter { value } = metric.value() {
+                        if metric.name() == errors_metric {
+                            errors += value;
+                        } else if metric.name() == total_metric {
+                            counts.total += value;
+                        }
+                    }
+                }
+                counts.good = (counts.total - errors).max(0.0);
+            }
+        }
+        counts
+    }
+}
+
+impl Default for InternalSloConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_interval(),
+            window_secs: default_window(),
+            objectives: IndexMap::from([(
+                "error_rate".to_owned(),
+                ObjectiveConfig::ErrorRate {
+                    errors_metric: default_errors_metric(),
+                    total_metric: default_total_metric(),
+                    target: 0.999,
+                    component_id: None,
+                },
+            )]),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(InternalSloConfig);
+
+impl InternalSloConfig {
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let fields = Collection::empty()
+            .with_known("objective", Kind::bytes())
+            .with_known("indicator", Kind::bytes())
+            .with_known("target", Kind::float())
+            .with_known("value", Kind::float().or_null())
+            .with_known("status", Kind::bytes())
+            .with_known("error_budget_remaining", Kind::float().or_null())
+            .with_known("good", Kind::float())
+            .with_known("total", Kind::float())
+            .with_known("window_secs", Kind::float());
+
+        Definition::new_with_default_metadata(Kind::object(fields), [log_namespace])
+            .with_standard_vector_source_metadata()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "internal_slo")]
+impl SourceConfig for InternalSloConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.interval_secs.is_zero() {
+            return Err("`interval_secs` must be greater than zero".into());
+        }
+        if self.window_secs < self.interval_secs {
+            return Err("`window_secs` must be at least `interval_secs`".into());
+        }
+        for (name, objective) in &self.objectives {
+            if !(0.0..=1.0).contains(&objective.target()) {
+                return Err(format!(
+                    "The target of objective {:?} must be between 0.0 and 1.0",
+                    name
+                )
+                .into());
+            }
+        }
+        let controller = crate::metrics::Controller::get()?;
+
+        Ok(Box::pin(
+            InternalSlo {
+                objectives: self.objectives.clone(),
+                interval: self.interval_secs,
+                window: Window::new(self.window_secs),
+                controller,
+                log_namespace: cx.log_namespace(self.log_namespace),
+                out: cx.out,
+                shutdown: cx.shutdown,
+            }
+            .run(),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The good and total events of an objective.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Counts {
+    good: f64,
+    total: f64,
+}
+
+/// The counts of the objectives at each evaluation within the window.
+struct Window {
+    length: Duration,
+    samples: VecDeque<(Instant, Vec<Counts>)>,
+}
+
+impl Window {
+    fn new(length: Duration) -> Self {
+        Self {
+            length,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Adds the counts of an evaluation, returning the counts over the window.
+    fn push(&mut self, now: Instant, counts: Vec<Counts>) -> Vec<Counts> {
+        // The oldest sample within the window is kept as its start.
+        while self.samples.len() > 1 && now - self.samples[1].0 >= self.length {
+            self.samples.pop_front();
+        }
+        let deltas = match self.samples.front() {
+            Some((_, start)) => counts
+                .iter()
+                .zip(start)
+                .map(|(counts, start)| {
+                    // Counters restart when their component is reloaded.
+                    if counts.total >= start.total && counts.good >= start.good {
+                        Counts {
+                            good: counts.good - start.good,
+                            total: counts.total - start.total,
+                        }
+                    } else {
+                        *counts
+                    }
+                })
+                .collect(),
+            None => vec![Counts::default(); counts.len()],
+        };
+        self.samples.push_back((now, counts));
+        deltas
+    }
+}
+
+/// The status of an objective over the window.
+#[derive(Debug, PartialEq)]
+struct Evaluation {
+    value: Option<f64>,
+    error_budget_remaining: Option<f64>,
+}
+
+impl Evaluation {
+    fn new(counts: Counts, target: f64) -> Self {
+        let value = (counts.total > 0.0).then(|| counts.good / counts.total);
+        // The error budget is the ratio of bad events allowed by the target.
+        let error_budget_remaining = value
+            .filter(|_| target < 1.0)
+            .map(|value| 1.0 - (1.0 - value) / (1.0 - target));
+        Self {
+            value,
+            error_budget_remaining,
+        }
+    }
+
+    fn status(&self, target: f64) -> &'static str {
+        match self.value {
+            None => "no_data",
+            Some(value) if value >= target => "met",
+            Some(_) => "violated",
+        }
+    }
+}
+
+fn status_event(
+    name: &str,
+    objective: &ObjectiveConfig,
+    counts: Counts,
+    window: Duration,
+    log_namespace: LogNamespace,
+) -> LogEvent {
+    let target = objective.target();
+    let evaluation = Evaluation::new(counts, target);
+    emit!(SloEvaluated {
+        objective: name,
+        value: evaluation.value,
+        met: evaluation.status(target) != "violated",
+        error_budget_remaining: evaluation.error_budget_remaining,
+    });
+
+    let mut log = LogEvent::default();
+    log.insert("objective", name.to_owned());
+    log.insert("indicator", objective.indicator());
+    log.insert("target", target);
+    log.insert("value", evaluation.value);
+    log.insert("status", evaluation.status(target));
+    log.insert("error_budget_remaining", evaluation.error_budget_remaining);
+    log.insert("good", counts.good);
+    log.insert("total", counts.total);
+    log.insert("window_secs", window.as_secs_f64());
+
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        InternalSloConfig::NAME,
+        Utc::now(),
+    );
+    log
+}
+
+struct InternalSlo {
+    objectives: IndexMap<String, ObjectiveConfig>,
+    interval: Duration,
+    window: Window,
+    controller: &'static crate::metrics::Controller,
+    log_namespace: LogNamespace,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl InternalSlo {
+    fn counts(&self) -> Vec<Counts> {
+        let metrics = self.controller.capture_metrics();
+        self.objectives
+            .values()
+            .map(|objective| objective.count(&metrics))
+            .collect()
+    }
+
+    async fn run(mut self) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        // Events before the source started are not evaluated.
+        let counts = self.counts();
+        self.window.push(Instant::now(), counts);
+
+        let mut interval = IntervalStream::new(time::interval_at(
+            Instant::now() + self.interval,
+            self.interval,
+        ))
+        .take_until(self.shutdown.clone());
+        while interval.next().await.is_some() {
+            let now = Instant::now();
+            let counts = self.counts();
+            let counts = self.window.push(now, counts);
+            let window = now - self.window.samples[0].0;
+            let events = self
+                .objectives
+                .iter()
+                .zip(counts)
+                .map(|((name, objective), counts)| {
+                    Event::from(status_event(
+                        name,
+                        objective,
+                        counts,
+                        window,
+                        self.log_namespace,
+                    ))
+                })
+                .collect::<Vec<_>>();
+
+            let count = events.len();
+            if count == 0 {
+                continue;
+            }
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            if self.out.send_batch(events).await.is_err() {
+                emit!(StreamClosedError { count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{metric::Bucket, MetricKind, MetricTags};
+
+    use super::*;
+    use crate::event::Value;
+
+    fn metric(name: &str, component_id: &str, value: MetricValue) -> Metric {
+        Metric::new(name, MetricKind::Absolute, value).with_tags(Some(MetricTags::from([(
+            "component_id".to_owned(),
+            component_id.to_owned(),
+        )])))
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<InternalSloConfig>();
+    }
+
+    #[test]
+    fn counts_latency_observations_under_threshold() {
+        let objective = ObjectiveConfig::Latency {
+            metric: "http_client_rtt_seconds".to_owned(),
+            threshold_secs: 1.0,
+            target: 0.99,
+            component_id: Some("out".to_owned()),
+        };
+        let histogram = |count_under: u64, count_over: u64| MetricValue::AggregatedHistogram {
+            buckets: vec![
+                Bucket {
+                    upper_limit: 0.5,
+                    count: count_under,
+                },
+                Bucket {
+                    upper_limit: 1.0,
+                    count: count_under,
+                },
+                Bucket {
+                    upper_limit: 5.0,
+                    count: count_over,
+                },
+            ],
+            count: 2 * count_under + count_over,
+            sum: 0.0,
+        };
+        let metrics = vec![
+            metric("http_client_rtt_seconds", "out", histogram(45, 10)),
+            metric("http_client_rtt_seconds", "other", histogram(0, 100)),
+        ];
+
+        assert_eq!(
+            objective.count(&metrics),
+            Counts {
+                good: 90.0,
+                total: 100.0
+            }
+        );
+    }
+
+    #[test]
+    fn counts_events_without_errors() {
+        let objective = ObjectiveConfig::ErrorRate {
+            errors_metric: default_errors_metric(),
+            total_metric: default_total_metric(),
+            target: 0.999,
+            component_id: None,
+        };
+        let metrics = vec![
+            metric(
+                "component_received_events_total",
+                "in",
+                MetricValue::Counter { value: 900.0 },
+            ),
+            metric(
+                "component_received_events_total",
+                "out",
+                MetricValue::Counter { value: 100.0 },
+            ),
+            metric(
+                "component_errors_total",
+                "out",
+                MetricValue::Counter { value: 2.0 },
+            ),
+        ];
+
+        assert_eq!(
+            objective.count(&metrics),
+            Counts {
+                good: 998.0,
+                total: 1000.0
+            }
+        );
+    }
+
+    #[test]
+    fn evaluates_counts_over_window() {
+        let start = Instant::now();
+        let mut window = Window::new(Duration::from_secs(120));
+        let counts = |good: f64, total: f64| vec![Counts { good, total }];
+
+        assert_eq!(window.push(start, counts(0.0, 0.0)), counts(0.0, 0.0));
+        assert_eq!(
+            window.push(start + Duration::from_secs(60), counts(10.0, 10.0)),
+            counts(10.0, 10.0)
+        );
+        assert_eq!(
+            window.push(start + Duration::from_secs(120), counts(15.0, 20.0)),
+            counts(15.0, 20.0)
+        );
+        // The sample at 0s is now out of the window.
+        assert_eq!(
+            window.push(start + Duration::from_secs(180), counts(20.0, 30.0)),
+            counts(10.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn emits_status() {
+        let objective = ObjectiveConfig::ErrorRate {
+            errors_metric: default_errors_metric(),
+            total_metric: default_total_metric(),
+            target: 0.99,
+            component_id: None,
+        };
+        let log = status_event(
+            "errors",
+            &objective,
+            Counts {
+                good: 995.0,
+                total: 1000.0,
+            },
+            Duration::from_secs(3600),
+            LogNamespace::Legacy,
+        );
+
+        assert_eq!(log["objective"], "errors".into());
+        assert_eq!(log["indicator"], "error_rate".into());
+        assert_eq!(log["status"], "met".into());
+        assert_eq!(log["value"], Value::from(0.995));
+        let remaining = log["error_budget_remaining"]
+            .as_float()
+            .unwrap()
+            .into_inner();
+        assert!((remaining - 0.5).abs() < 1e-9);
+        assert_eq!(log["source_type"], "internal_slo".into());
+
+        let log = status_event(
+            "errors",
+            &objective,
+            Counts::default(),
+            Duration::from_secs(3600),
+            LogNamespace::Legacy,
+        );
+        assert_eq!(log["status"], "no_data".into());
+        assert_eq!(log["value"], Value::Null);
+    }
+}
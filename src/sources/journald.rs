@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     io::SeekFrom,
     path::PathBuf,
     process::Stdio,
@@ -18,6 +18,7 @@ use nix::{
     unistd::Pid,
 };
 use once_cell::sync::Lazy;
+use regex::Regex;
 use serde_json::{Error as JsonError, Value as JsonValue};
 use snafu::{ResultExt, Snafu};
 use tokio::{
@@ -50,8 +51,8 @@ use crate::{
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, LogEvent},
     internal_events::{
         EventsReceived, JournaldCheckpointFileOpenError, JournaldCheckpointSetError,
-        JournaldInvalidRecordError, JournaldReadError, JournaldStartJournalctlError,
-        StreamClosedError,
+        JournaldInvalidRecordError, JournaldReadError, JournaldReliabilitySignal,
+        JournaldStartJournalctlError, StreamClosedError,
     },
     serde::bool_or_struct,
     shutdown::ShutdownSignal,
@@ -197,6 +198,17 @@ pub struct JournaldConfig {
     )]
     remap_priority: bool,
 
+    /// Enables recognizing the records of well-known reliability signals.
+    ///
+    /// The records of systemd units starting, stopping, failing, being scheduled for a restart,
+    /// or being killed by the OOM killer, of processes killed by the kernel OOM killer or
+    /// crashing with a segfault, and of core dumps get a `reliability` object with the `kind` of
+    /// the signal and, when known, the `unit`, `process`, and `pid` involved. They are also counted
+    /// by the `journald_reliability_events_total` internal metric, tagged with their `kind` and
+    /// `unit`.
+    #[serde(default)]
+    pub reliability_signals: bool,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -278,6 +290,21 @@ impl JournaldConfig {
                 Some("host"),
             );
 
+        if self.reliability_signals {
+            let fields = Collection::empty()
+                .with_known("kind", Kind::bytes())
+                .with_known("unit", Kind::bytes().or_undefined())
+                .with_known("process", Kind::bytes().or_undefined())
+                .with_known("pid", Kind::integer().or_undefined());
+            schema_definition = schema_definition.with_source_metadata(
+                JournaldConfig::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("reliability"))),
+                &owned_value_path!("reliability"),
+                Kind::object(fields).or_undefined(),
+                None,
+            );
+        }
+
         // for metadata that is added to the events dynamically through the Record
         if log_namespace == LogNamespace::Legacy {
             schema_definition = schema_definition.unknown_fields(Kind::bytes());
@@ -303,6 +330,7 @@ impl Default for JournaldConfig {
             journal_namespace: None,
             acknowledgements: Default::default(),
             remap_priority: false,
+            reliability_signals: false,
             log_namespace: None,
         }
     }
@@ -368,6 +396,7 @@ impl SourceConfig for JournaldConfig {
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
+                reliability_signals: self.reliability_signals,
                 out: cx.out,
                 acknowledgements,
                 starter,
@@ -395,6 +424,7 @@ struct JournaldSource {
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
+    reliability_signals: bool,
     out: SourceSender,
     acknowledgements: bool,
     starter: StartJournalctl,
@@ -560,6 +590,12 @@ impl<'a> Batch<'a> {
                         ) {
                             self.record_size += bytes.len();
 
+                            let signal = self
+                                .source
+                                .reliability_signals
+                                .then(|| ReliabilitySignal::from_record(&record))
+                                .flatten();
+
                             let mut event = create_log_event_from_record(
                                 record,
                                 &self.batch,
@@ -568,6 +604,14 @@ impl<'a> Batch<'a> {
 
                             enrich_log_event(&mut event, self.source.log_namespace);
 
+                            if let Some(signal) = signal {
+                                emit!(JournaldReliabilitySignal {
+                                    kind: signal.kind,
+                                    unit: signal.unit.as_deref(),
+                                });
+                                signal.insert(&mut event, self.source.log_namespace);
+                            }
+
                             self.events.push(event);
                         }
                     }
@@ -797,6 +841,99 @@ fn create_log_event_from_record(
     }
 }
 
+/// The IDs of the messages of systemd about units, from `sd-messages.h`.
+const UNIT_STARTED: &str = "39f53479d3a045ac8e11786248231fbf";
+const UNIT_STOPPED: &str = "9d1aaa27d60140bd96365438aad20286";
+const UNIT_FAILED: &str = "be02cf6855d2428ba40df7e9d022f03d";
+const UNIT_RESTART_SCHEDULED: &str = "5eb03494b6584870a536b337290809b3";
+const UNIT_OUT_OF_MEMORY: &str = "fe6faa94e7774663a0da52717891d8ef";
+const COREDUMP: &str = "fc2e22bc6ee647b6b90729ab34a250b1";
+
+static OOM_KILL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Killed process (\d+) \(([^)]*)\)").expect("valid regex"));
+static SEGFAULT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\S+)\[(\d+)\]: segfault at ").expect("valid regex"));
+
+/// A record of a well-known reliability signal, such as a unit failing or a process killed by
+/// the OOM killer.
+#[derive(Debug, PartialEq)]
+struct ReliabilitySignal {
+    kind: &'static str,
+    unit: Option<String>,
+    process: Option<String>,
+    pid: Option<i64>,
+}
+
+impl ReliabilitySignal {
+    fn from_record(record: &Record) -> Option<Self> {
+        if let Some(message_id) = record.get("MESSAGE_ID") {
+            let kind = match message_id.as_str() {
+                UNIT_STARTED => "unit_started",
+                UNIT_STOPPED => "unit_stopped",
+                UNIT_FAILED => "unit_failed",
+                UNIT_RESTART_SCHEDULED => "unit_restart_scheduled",
+                UNIT_OUT_OF_MEMORY => "unit_oom_kill",
+                COREDUMP => {
+                    return Some(Self {
+                        kind: "coredump",
+                        unit: record.get("COREDUMP_UNIT").cloned(),
+                        process: record.get("COREDUMP_COMM").cloned(),
+                        pid: record.get("COREDUMP_PID").and_then(|pid| pid.parse().ok()),
+                    })
+                }
+                _ => return None,
+            };
+            // Units of the system manager are in `UNIT`, those of user managers in `USER_UNIT`.
+            let unit = record.get("UNIT").or_else(|| record.get("USER_UNIT"));
+            return Some(Self {
+                kind,
+                unit: unit.cloned(),
+                process: None,
+                pid: None,
+            });
+        }
+
+        if record.get("_TRANSPORT").map(String::as_str) != Some("kernel") {
+            return None;
+        }
+        let message = record.get(MESSAGE)?;
+        let (kind, captures) = if let Some(captures) = OOM_KILL.captures(message) {
+            ("oom_kill", [captures.get(2), captures.get(1)])
+        } else if let Some(captures) = SEGFAULT.captures(message) {
+            ("segfault", [captures.get(1), captures.get(2)])
+        } else {
+            return None;
+        };
+        let [process, pid] = captures.map(|capture| capture.map(|capture| capture.as_str()));
+        Some(Self {
+            kind,
+            unit: None,
+            process: process.map(str::to_owned),
+            pid: pid.and_then(|pid| pid.parse().ok()),
+        })
+    }
+
+    fn insert(self, log: &mut LogEvent, log_namespace: LogNamespace) {
+        let mut signal = BTreeMap::from([("kind".to_owned(), Value::from(self.kind))]);
+        if let Some(unit) = self.unit {
+            signal.insert("unit".to_owned(), unit.into());
+        }
+        if let Some(process) = self.process {
+            signal.insert("process".to_owned(), process.into());
+        }
+        if let Some(pid) = self.pid {
+            signal.insert("pid".to_owned(), pid.into());
+        }
+        log_namespace.insert_source_metadata(
+            JournaldConfig::NAME,
+            log,
+            Some(LegacyKey::Overwrite(path!("reliability"))),
+            path!("reliability"),
+            signal,
+        );
+    }
+}
+
 /// Map the given unit name into a valid systemd unit
 /// by appending ".service" if no extension is present.
 fn fixup_unit(unit: &str) -> String {
@@ -1326,6 +1463,102 @@ mod tests {
         assert_eq!(checkpointer.get().await.unwrap().as_deref(), Some("8"));
     }
 
+    fn record(fields: &[(&str, &str)]) -> Record {
+        fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn recognizes_reliability_signals() {
+        let signal = |fields| ReliabilitySignal::from_record(&record(fields));
+
+        assert_eq!(
+            signal(&[("MESSAGE_ID", UNIT_FAILED), ("UNIT", "nginx.service")]),
+            Some(ReliabilitySignal {
+                kind: "unit_failed",
+                unit: Some("nginx.service".to_owned()),
+                process: None,
+                pid: None,
+            })
+        );
+        assert_eq!(
+            signal(&[
+                ("MESSAGE_ID", UNIT_RESTART_SCHEDULED),
+                ("USER_UNIT", "app.service")
+            ])
+            .map(|signal| (signal.kind, signal.unit)),
+            Some(("unit_restart_scheduled", Some("app.service".to_owned())))
+        );
+        assert_eq!(
+            signal(&[
+                ("_TRANSPORT", "kernel"),
+                (
+                    MESSAGE,
+                    "Out of memory: Killed process 4242 (java) total-vm:8123456kB"
+                ),
+            ]),
+            Some(ReliabilitySignal {
+                kind: "oom_kill",
+                unit: None,
+                process: Some("java".to_owned()),
+                pid: Some(4242),
+            })
+        );
+        assert_eq!(
+            signal(&[
+                ("_TRANSPORT", "kernel"),
+                (
+                    MESSAGE,
+                    "nginx[1234]: segfault at 0 ip 00007f sp 00007ffd error 4 in libc.so.6"
+                ),
+            ])
+            .map(|signal| (signal.kind, signal.process, signal.pid)),
+            Some(("segfault", Some("nginx".to_owned()), Some(1234)))
+        );
+        assert_eq!(
+            signal(&[
+                ("MESSAGE_ID", COREDUMP),
+                ("COREDUMP_UNIT", "app.service"),
+                ("COREDUMP_COMM", "app"),
+                ("COREDUMP_PID", "99"),
+            ]),
+            Some(ReliabilitySignal {
+                kind: "coredump",
+                unit: Some("app.service".to_owned()),
+                process: Some("app".to_owned()),
+                pid: Some(99),
+            })
+        );
+
+        // Kernel messages are only recognized from the kernel.
+        assert_eq!(
+            signal(&[(MESSAGE, "Out of memory: Killed process 4242 (java)")]),
+            None
+        );
+        assert_eq!(
+            signal(&[("MESSAGE_ID", "0123456789abcdef0123456789abcdef")]),
+            None
+        );
+    }
+
+    #[test]
+    fn inserts_reliability_signals() {
+        let signal = ReliabilitySignal {
+            kind: "unit_failed",
+            unit: Some("nginx.service".to_owned()),
+            process: None,
+            pid: None,
+        };
+
+        let mut log = LogEvent::default();
+        signal.insert(&mut log, LogNamespace::Legacy);
+        assert_eq!(log["reliability.kind"], "unit_failed".into());
+        assert_eq!(log["reliability.unit"], "nginx.service".into());
+        assert!(log.get("reliability.pid").is_none());
+    }
+
     #[test]
     fn filter_matches_works_correctly() {
         let empty: Matches = HashMap::new();
@@ -8,7 +8,7 @@ use file_source::paths_provider::PathsProvider;
 use k8s_openapi::api::core::v1::{Namespace, Pod};
 use kube::runtime::reflector::{store::Store, ObjectRef};
 
-use super::path_helpers::build_pod_logs_directory;
+use super::{path_helpers::build_pod_logs_directory, pod_filter::PodFilter};
 use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
 
 /// A paths provider implementation that uses the state obtained from the
@@ -16,6 +16,7 @@ use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
 pub struct K8sPathsProvider {
     pod_state: Store<Pod>,
     namespace_state: Store<Namespace>,
+    pod_filter: PodFilter,
     exclude_paths: Vec<glob::Pattern>,
 }
 
@@ -24,11 +25,13 @@ impl K8sPathsProvider {
     pub fn new(
         pod_state: Store<Pod>,
         namespace_state: Store<Namespace>,
+        pod_filter: PodFilter,
         exclude_paths: Vec<glob::Pattern>,
     ) -> Self {
         Self {
             pod_state,
             namespace_state,
+            pod_filter,
             exclude_paths,
         }
     }
@@ -54,6 +57,8 @@ impl PathsProvider for K8sPathsProvider {
                     false
                 }
             })
+            // filter out pods that are not selected, so that their files are never opened
+            .filter(|pod| self.pod_filter.matches(pod))
             .flat_map(|pod| {
                 trace!(message = "Providing log paths for pod.", pod = ?pod.metadata.name);
                 let paths_iter = list_pod_log_paths(real_glob, pod.as_ref());
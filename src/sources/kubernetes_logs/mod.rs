@@ -5,7 +5,7 @@
 
 #![deny(missing_docs)]
 
-use std::{path::PathBuf, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use bytes::Bytes;
 use chrono::Utc;
@@ -29,11 +29,10 @@ use kube::{
 };
 use lifecycle::Lifecycle;
 use lookup::{lookup_v2::OptionalTargetPath, owned_value_path, path, OwnedTargetPath};
+use pod_filter::PodFilter;
+use routing::Router;
 use serde_with::serde_as;
-use vector_common::{
-    internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol},
-    TimeZone,
-};
+use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
 use vector_config::configurable_component;
 use vector_core::{
     config::LegacyKey, config::LogNamespace, transform::TaskTransform, EstimatedJsonEncodedSizeOf,
@@ -66,7 +65,9 @@ mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
+mod pod_filter;
 mod pod_metadata_annotator;
+mod routing;
 mod transform_utils;
 mod util;
 
@@ -81,6 +82,9 @@ const FILE_KEY: &str = "file";
 /// The `self_node_name` value env var key.
 const SELF_NODE_NAME_ENV_KEY: &str = "VECTOR_SELF_NODE_NAME";
 
+/// The maximum number of events split between the named outputs at once.
+const ROUTING_CHUNK_SIZE: usize = 1000;
+
 /// Configuration for the `kubernetes_logs` source.
 #[serde_as]
 #[configurable_component(source("kubernetes_logs", "Collect Pod logs from Kubernetes Nodes."))]
@@ -132,6 +136,9 @@ pub struct Config {
     ))]
     extra_field_selector: String,
 
+    #[configurable(derived)]
+    pod_filter: pod_filter::FilterConfig,
+
     /// Whether or not to automatically merge partial events.
     ///
     /// Partial events are messages that were split by the Kubernetes Container Runtime
@@ -156,6 +163,9 @@ pub struct Config {
     #[configurable(derived)]
     node_annotation_fields: node_metadata_annotator::FieldsSpec,
 
+    #[configurable(derived)]
+    routing: routing::RoutingConfig,
+
     /// A list of glob patterns to exclude from reading the files.
     #[configurable(metadata(docs::examples = "**/exclude/**"))]
     exclude_paths_glob_patterns: Vec<PathBuf>,
@@ -211,9 +221,6 @@ pub struct Config {
     #[configurable(metadata(docs::examples = ".ingest_timestamp", docs::examples = "ingest_ts"))]
     ingestion_timestamp_field: Option<OptionalTargetPath>,
 
-    /// The default time zone for timestamps without an explicit zone.
-    timezone: Option<TimeZone>,
-
     /// Optional path to a readable [kubeconfig][kubeconfig] file.
     ///
     /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
@@ -263,11 +270,13 @@ impl Default for Config {
             extra_namespace_label_selector: "".to_string(),
             self_node_name: default_self_node_name_env_template(),
             extra_field_selector: "".to_string(),
+            pod_filter: pod_filter::FilterConfig::default(),
             auto_partial_merge: true,
             data_dir: None,
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
             node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
+            routing: routing::RoutingConfig::default(),
             exclude_paths_glob_patterns: default_path_exclusion(),
             read_from: default_read_from(),
             ignore_older_secs: None,
@@ -276,7 +285,6 @@ impl Default for Config {
             fingerprint_lines: default_fingerprint_lines(),
             glob_minimum_cooldown_ms: default_glob_minimum_cooldown_ms(),
             ingestion_timestamp_field: None,
-            timezone: None,
             kube_config_file: None,
             use_apiserver_cache: false,
             delay_deletion_ms: default_delay_deletion_ms(),
@@ -290,6 +298,7 @@ impl Default for Config {
 impl SourceConfig for Config {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
+        self.routing.validate(log_namespace)?;
         let source = Source::new(self, &cx.globals, &cx.key).await?;
 
         Ok(Box::pin(
@@ -314,6 +323,18 @@ impl SourceConfig for Config {
                 Kind::bytes(),
                 None,
             )
+            .with_source_metadata(
+                Self::NAME,
+                self.routing
+                    .route_field
+                    .path
+                    .clone()
+                    .map(|k| k.path)
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("route"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
             .with_source_metadata(
                 Self::NAME,
                 self.pod_annotation_fields
@@ -499,7 +520,14 @@ impl SourceConfig for Config {
             )
             .with_standard_vector_source_metadata();
 
-        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+        let mut outputs = vec![SourceOutput::new_logs(
+            DataType::Log,
+            schema_definition.clone(),
+        )];
+        outputs.extend(self.routing.outputs.iter().map(|output| {
+            SourceOutput::new_logs(DataType::Log, schema_definition.clone()).with_port(output)
+        }));
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -515,11 +543,13 @@ struct Source {
     pod_fields_spec: pod_metadata_annotator::FieldsSpec,
     namespace_fields_spec: namespace_metadata_annotator::FieldsSpec,
     node_field_spec: node_metadata_annotator::FieldsSpec,
+    routing: routing::RoutingConfig,
     field_selector: String,
     label_selector: String,
     namespace_label_selector: String,
     node_selector: String,
     self_node_name: String,
+    pod_filter: PodFilter,
     exclude_paths: Vec<glob::Pattern>,
     read_from: ReadFrom,
     ignore_older_secs: Option<u64>,
@@ -556,6 +586,7 @@ impl Source {
         let namespace_label_selector =
             prepare_label_selector(config.extra_namespace_label_selector.as_ref());
         let node_selector = prepare_node_selector(self_node_name.as_str())?;
+        let pod_filter = PodFilter::new(&config.pod_filter)?;
 
         // If the user passed a custom Kubeconfig use it, otherwise
         // we attempt to load the local kubeconfig, followed by the
@@ -592,11 +623,13 @@ impl Source {
             pod_fields_spec: config.pod_annotation_fields.clone(),
             namespace_fields_spec: config.namespace_annotation_fields.clone(),
             node_field_spec: config.node_annotation_fields.clone(),
+            routing: config.routing.clone(),
             field_selector,
             label_selector,
             namespace_label_selector,
             node_selector,
             self_node_name,
+            pod_filter,
             exclude_paths,
             read_from: ReadFrom::from(config.read_from),
             ignore_older_secs: config.ignore_older_secs,
@@ -623,11 +656,13 @@ impl Source {
             pod_fields_spec,
             namespace_fields_spec,
             node_field_spec,
+            routing,
             field_selector,
             label_selector,
             namespace_label_selector,
             node_selector,
             self_node_name,
+            pod_filter,
             exclude_paths,
             read_from,
             ignore_older_secs,
@@ -717,12 +752,20 @@ impl Source {
             delay_deletion,
         )));
 
-        let paths_provider =
-            K8sPathsProvider::new(pod_state.clone(), ns_state.clone(), exclude_paths);
-        let annotator = PodMetadataAnnotator::new(pod_state, pod_fields_spec, log_namespace);
+        let paths_provider = K8sPathsProvider::new(
+            pod_state.clone(),
+            ns_state.clone(),
+            pod_filter,
+            exclude_paths,
+        );
+        let annotator =
+            PodMetadataAnnotator::new(pod_state.clone(), pod_fields_spec, log_namespace);
         let ns_annotator =
-            NamespaceMetadataAnnotator::new(ns_state, namespace_fields_spec, log_namespace);
+            NamespaceMetadataAnnotator::new(ns_state.clone(), namespace_fields_spec, log_namespace);
         let node_annotator = NodeMetadataAnnotator::new(node_state, node_field_spec, log_namespace);
+        let has_routes = !routing.outputs.is_empty();
+        let router = Arc::new(Router::new(pod_state, ns_state, routing, log_namespace));
+        let route_annotator = Arc::clone(&router);
 
         let ignore_before = calculate_ignore_before(ignore_older_secs);
 
@@ -827,6 +870,10 @@ impl Source {
                     }
                 }
 
+                if let Some(file_info) = file_info.as_ref() {
+                    route_annotator.annotate(&mut event, file_info);
+                }
+
                 let node_info = node_annotator.annotate(&mut event, self_node_name.as_str());
 
                 if node_info.is_none() {
@@ -845,7 +892,22 @@ impl Source {
         let (events_count, _) = events.size_hint();
 
         let mut stream = partial_events_merger.transform(Box::pin(events));
-        let event_processing_loop = out.send_event_stream(&mut stream);
+        let event_processing_loop = async move {
+            if !has_routes {
+                return out.send_event_stream(&mut stream).await;
+            }
+
+            let mut chunks = stream.ready_chunks(ROUTING_CHUNK_SIZE);
+            while let Some(events) = chunks.next().await {
+                for (output, events) in router.partition(events) {
+                    match output {
+                        Some(output) => out.send_batch_named(&output, events).await?,
+                        None => out.send_batch(events).await?,
+                    }
+                }
+            }
+            Ok(())
+        };
 
         let mut lifecycle = Lifecycle::new();
         {
@@ -1272,6 +1334,11 @@ mod tests {
                         Kind::bytes().or_undefined(),
                         None
                     )
+                    .with_metadata_field(
+                        &owned_value_path!("kubernetes_logs", "route"),
+                        Kind::bytes().or_undefined(),
+                        None
+                    )
                     .with_metadata_field(
                         &owned_value_path!("kubernetes_logs", "stream"),
                         Kind::bytes(),
@@ -1388,6 +1455,11 @@ mod tests {
                     Kind::bytes().or_undefined(),
                     None
                 )
+                .with_event_field(
+                    &owned_value_path!("kubernetes", "route"),
+                    Kind::bytes().or_undefined(),
+                    None
+                )
                 .with_event_field(&owned_value_path!("stream"), Kind::bytes(), None)
                 .with_event_field(
                     &owned_value_path!("timestamp"),
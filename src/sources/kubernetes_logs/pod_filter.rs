@@ -0,0 +1,483 @@
+//! Selection of the Pods to collect logs from, evaluated against the local Pod cache.
+
+#![deny(missing_docs)]
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::Pod;
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+/// Configuration for selecting Pods to collect logs from.
+///
+/// Unlike `extra_label_selector` and `extra_field_selector`, which are sent to the Kubernetes API,
+/// these selectors are evaluated by Vector against the Pods it already knows about. Log files of
+/// Pods that are not selected are never opened.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct FilterConfig {
+    /// A [label selector][label_selector] that Pods must match to have their logs collected.
+    ///
+    /// Both equality-based (`=`, `==`, `!=`) and set-based (`in`, `notin`, `key`, `!key`)
+    /// requirements are supported. If empty, all Pods are included.
+    ///
+    /// [label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+    #[configurable(metadata(docs::examples = "app in (api, worker),tier!=cache"))]
+    pub include_label_selector: String,
+
+    /// A [label selector][label_selector] matching Pods whose logs must not be collected.
+    ///
+    /// Takes precedence over `include_label_selector`. If empty, no Pods are excluded.
+    ///
+    /// [label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+    #[configurable(metadata(docs::examples = "environment notin (production)"))]
+    #[configurable(metadata(docs::examples = "!logging"))]
+    pub exclude_label_selector: String,
+
+    /// A [field selector][field_selector] that Pods must match to have their logs collected.
+    ///
+    /// If empty, all Pods are included.
+    ///
+    /// [field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+    #[configurable(metadata(docs::examples = "status.phase=Running"))]
+    pub include_field_selector: String,
+
+    /// A [field selector][field_selector] matching Pods whose logs must not be collected.
+    ///
+    /// Takes precedence over `include_field_selector`. If empty, no Pods are excluded.
+    ///
+    /// [field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+    #[configurable(metadata(docs::examples = "metadata.namespace=kube-system"))]
+    pub exclude_field_selector: String,
+}
+
+/// Errors raised while parsing selectors.
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum SelectorError {
+    /// A requirement could not be parsed.
+    #[snafu(display("Invalid selector requirement {:?}.", requirement))]
+    InvalidRequirement {
+        /// The offending requirement.
+        requirement: String,
+    },
+
+    /// A field selector refers to a field that cannot be selected on.
+    #[snafu(display("Field {:?} is not supported in Pod field selectors.", field))]
+    UnsupportedField {
+        /// The offending field.
+        field: String,
+    },
+}
+
+/// Decides whether the logs of a Pod are collected.
+#[derive(Clone, Debug, Default)]
+pub struct PodFilter {
+    include_labels: Option<LabelSelector>,
+    exclude_labels: Option<LabelSelector>,
+    include_fields: Option<FieldSelector>,
+    exclude_fields: Option<FieldSelector>,
+}
+
+impl PodFilter {
+    /// Create a new [`PodFilter`] from its configuration.
+    pub fn new(config: &FilterConfig) -> Result<Self, SelectorError> {
+        Ok(Self {
+            include_labels: LabelSelector::parse_non_empty(&config.include_label_selector)?,
+            exclude_labels: LabelSelector::parse_non_empty(&config.exclude_label_selector)?,
+            include_fields: FieldSelector::parse_non_empty(&config.include_field_selector)?,
+            exclude_fields: FieldSelector::parse_non_empty(&config.exclude_field_selector)?,
+        })
+    }
+
+    /// Returns whether the logs of the Pod must be collected.
+    pub fn matches(&self, pod: &Pod) -> bool {
+        let empty = BTreeMap::new();
+        let labels = pod.metadata.labels.as_ref().unwrap_or(&empty);
+
+        self.include_labels
+            .as_ref()
+            .map_or(true, |selector| selector.matches(labels))
+            && self
+                .include_fields
+                .as_ref()
+                .map_or(true, |selector| selector.matches(pod))
+            && !self
+                .exclude_labels
+                .as_ref()
+                .map_or(false, |selector| selector.matches(labels))
+            && !self
+                .exclude_fields
+                .as_ref()
+                .map_or(false, |selector| selector.matches(pod))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum LabelRequirement {
+    Exists(String),
+    DoesNotExist(String),
+    In(String, Vec<String>),
+    NotIn(String, Vec<String>),
+}
+
+impl LabelRequirement {
+    fn parse(requirement: &str) -> Result<Self, SelectorError> {
+        let invalid = || SelectorError::InvalidRequirement {
+            requirement: requirement.to_owned(),
+        };
+        let key = |key: &str| {
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                Err(invalid())
+            } else {
+                Ok(key.to_owned())
+            }
+        };
+
+        if let Some(negated) = requirement.strip_prefix('!') {
+            return Ok(Self::DoesNotExist(key(negated)?));
+        }
+        if let Some((name, value)) = requirement.split_once("!=") {
+            return Ok(Self::NotIn(key(name)?, vec![value.trim().to_owned()]));
+        }
+        if let Some((name, value)) = requirement
+            .split_once("==")
+            .or_else(|| requirement.split_once('='))
+        {
+            return Ok(Self::In(key(name)?, vec![value.trim().to_owned()]));
+        }
+
+        if let Some(open) = requirement.find('(') {
+            let values = requirement[open + 1..]
+                .strip_suffix(')')
+                .ok_or_else(invalid)?
+                .split(',')
+                .map(|value| value.trim().to_owned())
+                .collect();
+            let mut words = requirement[..open].split_whitespace();
+            return match (words.next(), words.next(), words.next()) {
+                (Some(name), Some("in"), None) => Ok(Self::In(key(name)?, values)),
+                (Some(name), Some("notin"), None) => Ok(Self::NotIn(key(name)?, values)),
+                _ => Err(invalid()),
+            };
+        }
+
+        Ok(Self::Exists(key(requirement)?))
+    }
+
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        match self {
+            Self::Exists(key) => labels.contains_key(key),
+            Self::DoesNotExist(key) => !labels.contains_key(key),
+            Self::In(key, values) => labels
+                .get(key)
+                .map_or(false, |value| values.contains(value)),
+            // As in Kubernetes, Pods without the label match `notin` and `!=` requirements.
+            Self::NotIn(key, values) => labels
+                .get(key)
+                .map_or(true, |value| !values.contains(value)),
+        }
+    }
+}
+
+/// A label selector; a Pod matches if it satisfies all of the requirements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LabelSelector(Vec<LabelRequirement>);
+
+impl LabelSelector {
+    fn parse_non_empty(selector: &str) -> Result<Option<Self>, SelectorError> {
+        if selector.trim().is_empty() {
+            return Ok(None);
+        }
+        split_requirements(selector)
+            .into_iter()
+            .map(LabelRequirement::parse)
+            .collect::<Result<_, _>>()
+            .map(|requirements| Some(Self(requirements)))
+    }
+
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.0.iter().all(|requirement| requirement.matches(labels))
+    }
+}
+
+/// Splits a selector on the commas that are not enclosed in the parentheses of a set.
+fn split_requirements(selector: &str) -> Vec<&str> {
+    let mut requirements = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in selector.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                requirements.push(selector[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    requirements.push(selector[start..].trim());
+    requirements
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PodField {
+    Name,
+    Namespace,
+    Uid,
+    NodeName,
+    ServiceAccountName,
+    RestartPolicy,
+    SchedulerName,
+    HostNetwork,
+    Phase,
+    PodIp,
+    NominatedNodeName,
+}
+
+impl PodField {
+    fn parse(field: &str) -> Result<Self, SelectorError> {
+        Ok(match field {
+            "metadata.name" => Self::Name,
+            "metadata.namespace" => Self::Namespace,
+            "metadata.uid" => Self::Uid,
+            "spec.nodeName" => Self::NodeName,
+            "spec.serviceAccountName" => Self::ServiceAccountName,
+            "spec.restartPolicy" => Self::RestartPolicy,
+            "spec.schedulerName" => Self::SchedulerName,
+            "spec.hostNetwork" => Self::HostNetwork,
+            "status.phase" => Self::Phase,
+            "status.podIP" => Self::PodIp,
+            "status.nominatedNodeName" => Self::NominatedNodeName,
+            _ => {
+                return Err(SelectorError::UnsupportedField {
+                    field: field.to_owned(),
+                })
+            }
+        })
+    }
+
+    /// Returns the value of the field, with unset fields reading as an empty string like they do
+    /// in the Kubernetes API.
+    fn get(self, pod: &Pod) -> String {
+        let spec = pod.spec.as_ref();
+        let status = pod.status.as_ref();
+        let value = match self {
+            Self::Name => pod.metadata.name.clone(),
+            Self::Namespace => pod.metadata.namespace.clone(),
+            Self::Uid => pod.metadata.uid.clone(),
+            Self::NodeName => spec.and_then(|spec| spec.node_name.clone()),
+            Self::ServiceAccountName => spec.and_then(|spec| spec.service_account_name.clone()),
+            Self::RestartPolicy => spec.and_then(|spec| spec.restart_policy.clone()),
+            Self::SchedulerName => spec.and_then(|spec| spec.scheduler_name.clone()),
+            Self::HostNetwork => Some(
+                spec.and_then(|spec| spec.host_network)
+                    .unwrap_or(false)
+                    .to_string(),
+            ),
+            Self::Phase => status.and_then(|status| status.phase.clone()),
+            Self::PodIp => status.and_then(|status| status.pod_ip.clone()),
+            Self::NominatedNodeName => status.and_then(|status| status.nominated_node_name.clone()),
+        };
+        value.unwrap_or_default()
+    }
+}
+
+/// A field selector; a Pod matches if it satisfies all of the requirements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FieldSelector(Vec<(PodField, bool, String)>);
+
+impl FieldSelector {
+    fn parse_non_empty(selector: &str) -> Result<Option<Self>, SelectorError> {
+        if selector.trim().is_empty() {
+            return Ok(None);
+        }
+        selector
+            .split(',')
+            .map(|requirement| {
+                let requirement = requirement.trim();
+                let (field, equal, value) =
+                    if let Some((field, value)) = requirement.split_once("!=") {
+                        (field, false, value)
+                    } else if let Some((field, value)) = requirement
+                        .split_once("==")
+                        .or_else(|| requirement.split_once('='))
+                    {
+                        (field, true, value)
+                    } else {
+                        return Err(SelectorError::InvalidRequirement {
+                            requirement: requirement.to_owned(),
+                        });
+                    };
+                Ok((
+                    PodField::parse(field.trim())?,
+                    equal,
+                    value.trim().to_owned(),
+                ))
+            })
+            .collect::<Result<_, _>>()
+            .map(|requirements| Some(Self(requirements)))
+    }
+
+    fn matches(&self, pod: &Pod) -> bool {
+        self.0
+            .iter()
+            .all(|(field, equal, value)| (&field.get(pod) == value) == *equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::{
+        api::core::v1::{PodSpec, PodStatus},
+        apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    };
+
+    use super::*;
+
+    fn pod(labels: &[(&str, &str)], namespace: &str, phase: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("pod".to_owned()),
+                namespace: Some(namespace.to_owned()),
+                labels: Some(
+                    labels
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                ),
+                ..ObjectMeta::default()
+            },
+            spec: Some(PodSpec::default()),
+            status: Some(PodStatus {
+                phase: Some(phase.to_owned()),
+                ..PodStatus::default()
+            }),
+        }
+    }
+
+    fn filter(
+        include_label_selector: &str,
+        exclude_label_selector: &str,
+        include_field_selector: &str,
+        exclude_field_selector: &str,
+    ) -> PodFilter {
+        PodFilter::new(&FilterConfig {
+            include_label_selector: include_label_selector.to_owned(),
+            exclude_label_selector: exclude_label_selector.to_owned(),
+            include_field_selector: include_field_selector.to_owned(),
+            exclude_field_selector: exclude_field_selector.to_owned(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_label_requirements() {
+        let cases = vec![
+            ("app", LabelRequirement::Exists("app".to_owned())),
+            ("!app", LabelRequirement::DoesNotExist("app".to_owned())),
+            (
+                "app=api",
+                LabelRequirement::In("app".to_owned(), vec!["api".to_owned()]),
+            ),
+            (
+                "app == api",
+                LabelRequirement::In("app".to_owned(), vec!["api".to_owned()]),
+            ),
+            (
+                "app!=api",
+                LabelRequirement::NotIn("app".to_owned(), vec!["api".to_owned()]),
+            ),
+            (
+                "app in (api, worker)",
+                LabelRequirement::In(
+                    "app".to_owned(),
+                    vec!["api".to_owned(), "worker".to_owned()],
+                ),
+            ),
+            (
+                "app notin (api)",
+                LabelRequirement::NotIn("app".to_owned(), vec!["api".to_owned()]),
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(LabelRequirement::parse(input), Ok(expected), "{}", input);
+        }
+
+        for input in ["app in api", "app within (api)", "two words", "app in (api"] {
+            assert!(LabelRequirement::parse(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn splits_outside_of_sets() {
+        assert_eq!(
+            split_requirements("app in (api, worker),tier!=cache, !canary"),
+            vec!["app in (api, worker)", "tier!=cache", "!canary"]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_fields() {
+        assert_eq!(
+            FieldSelector::parse_non_empty("spec.containers=foo"),
+            Err(SelectorError::UnsupportedField {
+                field: "spec.containers".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert!(PodFilter::default().matches(&Pod::default()));
+        assert!(filter("", "", "", "").matches(&pod(&[], "default", "Running")));
+    }
+
+    #[test]
+    fn includes_by_labels() {
+        let filter = filter("app in (api, worker),tier!=cache", "", "", "");
+
+        assert!(filter.matches(&pod(&[("app", "api")], "default", "Running")));
+        assert!(filter.matches(&pod(
+            &[("app", "worker"), ("tier", "web")],
+            "default",
+            "Running"
+        )));
+        assert!(!filter.matches(&pod(
+            &[("app", "api"), ("tier", "cache")],
+            "default",
+            "Running"
+        )));
+        assert!(!filter.matches(&pod(&[("app", "db")], "default", "Running")));
+        assert!(!filter.matches(&pod(&[], "default", "Running")));
+    }
+
+    #[test]
+    fn exclusion_takes_precedence() {
+        let filter = filter("app", "!logging", "", "metadata.namespace=kube-system");
+
+        assert!(filter.matches(&pod(
+            &[("app", "api"), ("logging", "on")],
+            "default",
+            "Running"
+        )));
+        assert!(!filter.matches(&pod(&[("app", "api")], "default", "Running")));
+        assert!(!filter.matches(&pod(
+            &[("app", "api"), ("logging", "on")],
+            "kube-system",
+            "Running"
+        )));
+    }
+
+    #[test]
+    fn includes_by_fields() {
+        let filter = filter("", "", "status.phase=Running,metadata.namespace!=test", "");
+
+        assert!(filter.matches(&pod(&[], "default", "Running")));
+        assert!(!filter.matches(&pod(&[], "default", "Succeeded")));
+        assert!(!filter.matches(&pod(&[], "test", "Running")));
+    }
+}
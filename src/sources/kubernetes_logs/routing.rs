@@ -0,0 +1,270 @@
+//! Routes events according to an annotation on their Pod or Namespace.
+
+#![deny(missing_docs)]
+
+use std::collections::HashMap;
+
+use k8s_openapi::{
+    api::core::v1::{Namespace, Pod},
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+};
+use kube::runtime::reflector::{store::Store, ObjectRef};
+use lookup::{
+    lookup_v2::OptionalTargetPath, metadata_path, owned_value_path, path, OwnedTargetPath,
+};
+use vector_config::configurable_component;
+use vector_core::config::{LegacyKey, LogNamespace};
+
+use super::{path_helpers::LogFileInfo, Config};
+use crate::event::{Event, LogEvent};
+
+/// Configuration for routing events based on an annotation of their Pod or Namespace.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct RoutingConfig {
+    /// The annotation holding the route of the events of a Pod.
+    ///
+    /// The annotation is looked up on the Pod first, and then on its Namespace, so that a Pod can
+    /// override the route of its Namespace.
+    #[configurable(metadata(docs::examples = "example.com/pipeline"))]
+    pub annotation: String,
+
+    /// Event field for the route.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.route"))]
+    #[configurable(metadata(docs::examples = "k8s.route"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub route_field: OptionalTargetPath,
+
+    /// The routes that are exposed as named outputs of the source.
+    ///
+    /// Events whose route is listed here are sent to the output of the same name, such as
+    /// `<component_id>.audit` for the `audit` route. All other events, including those with an
+    /// unlisted route, are sent to the default output.
+    #[configurable(metadata(docs::examples = "audit"))]
+    pub outputs: Vec<String>,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            annotation: "vector.dev/route".to_owned(),
+            route_field: OwnedTargetPath::event(owned_value_path!("kubernetes", "route")).into(),
+            outputs: Vec::new(),
+        }
+    }
+}
+
+impl RoutingConfig {
+    /// Checks that the route of events can be read back to pick their output.
+    pub fn validate(&self, log_namespace: LogNamespace) -> crate::Result<()> {
+        if !self.outputs.is_empty()
+            && log_namespace == LogNamespace::Legacy
+            && self.route_field.path.is_none()
+        {
+            return Err(
+                "`routing.route_field` cannot be suppressed while `routing.outputs` is set".into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Annotate the event with its route, and send it to the matching output.
+pub struct Router {
+    pods_state_reader: Store<Pod>,
+    namespace_state_reader: Store<Namespace>,
+    config: RoutingConfig,
+    log_namespace: LogNamespace,
+}
+
+impl Router {
+    /// Create a new [`Router`].
+    pub const fn new(
+        pods_state_reader: Store<Pod>,
+        namespace_state_reader: Store<Namespace>,
+        config: RoutingConfig,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        Self {
+            pods_state_reader,
+            namespace_state_reader,
+            config,
+            log_namespace,
+        }
+    }
+
+    /// Annotates an event with the route of the Pod it originates from, if any.
+    pub fn annotate(&self, event: &mut Event, file_info: &LogFileInfo<'_>) -> Option<()> {
+        let pod = self
+            .pods_state_reader
+            .get(&ObjectRef::<Pod>::new(file_info.pod_name).within(file_info.pod_namespace));
+        let namespace = self
+            .namespace_state_reader
+            .get(&ObjectRef::<Namespace>::new(file_info.pod_namespace));
+
+        let route = pod
+            .as_ref()
+            .and_then(|pod| self.lookup(&pod.metadata))
+            .or_else(|| {
+                namespace
+                    .as_ref()
+                    .and_then(|namespace| self.lookup(&namespace.metadata))
+            })?
+            .to_owned();
+
+        let legacy_key = self
+            .config
+            .route_field
+            .path
+            .as_ref()
+            .map(|k| &k.path)
+            .map(LegacyKey::Overwrite);
+
+        self.log_namespace.insert_source_metadata(
+            Config::NAME,
+            event.as_mut_log(),
+            legacy_key,
+            path!("route"),
+            route,
+        );
+        Some(())
+    }
+
+    fn lookup<'a>(&self, metadata: &'a ObjectMeta) -> Option<&'a str> {
+        metadata
+            .annotations
+            .as_ref()?
+            .get(&self.config.annotation)
+            .map(|route| route.trim())
+            .filter(|route| !route.is_empty())
+    }
+
+    /// Returns the named output an annotated event must be sent to, or `None` for the default
+    /// output.
+    fn output_of(&self, log: &LogEvent) -> Option<String> {
+        let route = match self.log_namespace {
+            LogNamespace::Vector => log.get(metadata_path!("kubernetes_logs", "route")),
+            LogNamespace::Legacy => log.get(self.config.route_field.path.as_ref()?),
+        }?
+        .as_str()?;
+
+        self.config
+            .outputs
+            .iter()
+            .find(|output| route == output.as_str())
+            .cloned()
+    }
+
+    /// Splits events by the output they must be sent to.
+    pub fn partition(&self, events: Vec<Event>) -> HashMap<Option<String>, Vec<Event>> {
+        let mut outputs: HashMap<_, Vec<_>> = HashMap::new();
+        for event in events {
+            outputs
+                .entry(self.output_of(event.as_log()))
+                .or_default()
+                .push(event);
+        }
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kube::runtime::reflector::store::Writer;
+    use kube::runtime::watcher;
+
+    use super::*;
+    use crate::sources::kubernetes_logs::path_helpers::parse_log_file_path;
+
+    const FILE: &str = "/var/log/pods/ns_pod_uid/container/0.log";
+
+    fn metadata(name: &str, namespace: Option<&str>, route: Option<&str>) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_owned()),
+            namespace: namespace.map(ToOwned::to_owned),
+            annotations: route.map(|route| {
+                vec![("vector.dev/route".to_owned(), route.to_owned())]
+                    .into_iter()
+                    .collect()
+            }),
+            ..ObjectMeta::default()
+        }
+    }
+
+    fn router(
+        pod_route: Option<&str>,
+        namespace_route: Option<&str>,
+        log_namespace: LogNamespace,
+    ) -> Router {
+        let mut pods = Writer::default();
+        pods.apply_watcher_event(&watcher::Event::Applied(Pod {
+            metadata: metadata("pod", Some("ns"), pod_route),
+            ..Pod::default()
+        }));
+        let mut namespaces = Writer::default();
+        namespaces.apply_watcher_event(&watcher::Event::Applied(Namespace {
+            metadata: metadata("ns", None, namespace_route),
+            ..Namespace::default()
+        }));
+
+        Router::new(
+            pods.as_reader(),
+            namespaces.as_reader(),
+            RoutingConfig {
+                outputs: vec!["audit".to_owned()],
+                ..RoutingConfig::default()
+            },
+            log_namespace,
+        )
+    }
+
+    fn route(router: &Router) -> (Option<String>, Event) {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        router.annotate(&mut event, &parse_log_file_path(FILE).unwrap());
+        (router.output_of(event.as_log()), event)
+    }
+
+    #[test]
+    fn pod_annotation_overrides_namespace() {
+        let (output, event) = route(&router(Some("audit"), Some("debug"), LogNamespace::Legacy));
+        assert_eq!(output.as_deref(), Some("audit"));
+        assert_eq!(event.as_log()["kubernetes.route"], "audit".into());
+
+        let (output, event) = route(&router(None, Some("debug"), LogNamespace::Legacy));
+        assert_eq!(output, None);
+        assert_eq!(event.as_log()["kubernetes.route"], "debug".into());
+    }
+
+    #[test]
+    fn unannotated_events_use_default_output() {
+        let (output, event) = route(&router(None, None, LogNamespace::Legacy));
+        assert_eq!(output, None);
+        assert!(event.as_log().get("kubernetes.route").is_none());
+    }
+
+    #[test]
+    fn routes_with_vector_namespace() {
+        let (output, event) = route(&router(None, Some("audit"), LogNamespace::Vector));
+        assert_eq!(output.as_deref(), Some("audit"));
+        assert_eq!(
+            event
+                .as_log()
+                .get(metadata_path!("kubernetes_logs", "route")),
+            Some(&"audit".into())
+        );
+    }
+
+    #[test]
+    fn rejects_suppressed_field_with_outputs() {
+        let config = RoutingConfig {
+            route_field: OptionalTargetPath::none(),
+            outputs: vec!["audit".to_owned()],
+            ..RoutingConfig::default()
+        };
+        assert!(config.validate(LogNamespace::Legacy).is_err());
+        assert!(config.validate(LogNamespace::Vector).is_ok());
+    }
+}
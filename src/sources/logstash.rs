@@ -22,7 +22,9 @@ use vector_core::{
 use vrl::value::kind::Collection;
 use vrl::value::Kind;
 
-use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
+use super::util::net::{
+    SocketListenAddr, TcpConnectionLimitsConfig, TcpSource, TcpSourceAck, TcpSourceAcker,
+};
 use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
@@ -56,10 +58,15 @@ pub struct LogstashConfig {
     receive_buffer_bytes: Option<usize>,
 
     /// The maximum number of TCP connections that are allowed at any given time.
+    #[serde(alias = "max_connections")]
     #[configurable(metadata(docs::type_unit = "connections"))]
     #[configurable(metadata(docs::advanced))]
     connection_limit: Option<u32>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    connection_limits: TcpConnectionLimitsConfig,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -121,6 +128,7 @@ impl Default for LogstashConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: None,
+            connection_limits: Default::default(),
             log_namespace: None,
         }
     }
@@ -162,6 +170,7 @@ impl SourceConfig for LogstashConfig {
             cx,
             self.acknowledgements,
             self.connection_limit,
+            self.connection_limits,
             LogstashConfig::NAME,
             log_namespace,
         )
@@ -720,6 +729,7 @@ mod test {
                 receive_buffer_bytes: None,
                 acknowledgements: true.into(),
                 connection_limit: None,
+                connection_limits: Default::default(),
                 log_namespace: None,
             }
             .build(SourceContext::new_test(sender, None))
@@ -962,6 +972,7 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                connection_limits: Default::default(),
                 log_namespace: None,
             }
             .build(SourceContext::new_test(sender, None))
@@ -0,0 +1,205 @@
+//! Requests and responses of the Loki query and tail APIs.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+use url::Url;
+
+const QUERY_RANGE_PATH: &str = "/loki/api/v1/query_range";
+const TAIL_PATH: &str = "/loki/api/v1/tail";
+
+/// The labels of a stream.
+pub type Labels = BTreeMap<String, String>;
+
+/// A stream of entries sharing the same labels.
+#[derive(Debug, Deserialize)]
+pub struct Stream {
+    pub stream: Labels,
+    pub values: Vec<Entry>,
+}
+
+/// A log entry, sent as an array of its timestamp in nanoseconds, its line, and, for Loki
+/// instances accepting structured metadata, its structured metadata.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Vec<Value>")]
+pub struct Entry {
+    pub timestamp: i64,
+    pub line: String,
+    pub structured_metadata: Option<Labels>,
+}
+
+impl TryFrom<Vec<Value>> for Entry {
+    type Error = String;
+
+    fn try_from(values: Vec<Value>) -> Result<Self, Self::Error> {
+        let mut values = values.into_iter();
+        let timestamp = values
+            .next()
+            .as_ref()
+            .and_then(Value::as_str)
+            .and_then(|timestamp| timestamp.parse().ok())
+            .ok_or("entry has no timestamp")?;
+        let line = match values.next() {
+            Some(Value::String(line)) => line,
+            _ => return Err("entry has no line".to_owned()),
+        };
+        // Structured metadata with values other than strings is not produced by Loki.
+        let structured_metadata = values
+            .next()
+            .and_then(|metadata| serde_json::from_value::<Labels>(metadata).ok())
+            .filter(|metadata| !metadata.is_empty());
+
+        Ok(Self {
+            timestamp,
+            line,
+            structured_metadata,
+        })
+    }
+}
+
+/// A message of the tail API.
+#[derive(Debug, Deserialize)]
+pub struct TailResponse {
+    #[serde(default)]
+    pub streams: Vec<Stream>,
+
+    /// The entries Loki dropped because the client was not reading fast enough.
+    #[serde(default)]
+    pub dropped_entries: Vec<Value>,
+}
+
+/// A response of the range query API, for queries returning logs.
+#[derive(Debug, Deserialize)]
+pub struct QueryResponse {
+    pub data: QueryData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QueryData {
+    pub result: Vec<Stream>,
+}
+
+/// Flattens the entries of the streams, ordered by their timestamp.
+pub fn entries(streams: Vec<Stream>) -> Vec<(Labels, Entry)> {
+    let mut entries = streams
+        .into_iter()
+        .flat_map(|stream| {
+            let labels = stream.stream;
+            stream
+                .values
+                .into_iter()
+                .map(move |entry| (labels.clone(), entry))
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|(_, entry)| entry.timestamp);
+    entries
+}
+
+/// Appends the path of an API to the path of the endpoint, which Loki may be served under.
+fn append_path(url: &mut Url, path: &str) {
+    let path = format!("{}{}", url.path().trim_end_matches('/'), path);
+    url.set_path(&path);
+}
+
+/// The URL of the range query for the logs from `start`, inclusive, to `end`, exclusive, in
+/// nanoseconds.
+pub fn query_range_url(endpoint: &Url, query: &str, start: i64, end: i64, limit: u32) -> Url {
+    let mut url = endpoint.clone();
+    append_path(&mut url, QUERY_RANGE_PATH);
+    url.query_pairs_mut()
+        .append_pair("query", query)
+        .append_pair("start", &start.to_string())
+        .append_pair("end", &end.to_string())
+        .append_pair("limit", &limit.to_string())
+        .append_pair("direction", "forward");
+    url
+}
+
+/// The WebSocket URL of the tail of the logs from `start`, in nanoseconds.
+pub fn tail_url(endpoint: &Url, query: &str, start: i64, delay_for_secs: u64) -> Url {
+    let mut url = endpoint.clone();
+    let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+    url.set_scheme(scheme)
+        .expect("HTTP schemes can be changed to WebSocket schemes");
+    append_path(&mut url, TAIL_PATH);
+    url.query_pairs_mut()
+        .append_pair("query", query)
+        .append_pair("start", &start.to_string())
+        .append_pair("delay_for", &delay_for_secs.to_string());
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tail_response() {
+        let response: TailResponse = serde_json::from_str(
+            r#"{
+                "streams": [
+                    {
+                        "stream": {"app": "api"},
+                        "values": [
+                            ["1686000000000000002", "second", {"trace_id": "abc"}],
+                            ["1686000000000000000", "first"]
+                        ]
+                    },
+                    {"stream": {"app": "web"}, "values": [["1686000000000000001", "other"]]}
+                ],
+                "dropped_entries": [{"labels": {"app": "api"}, "timestamp": "1685999999000000000"}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.dropped_entries.len(), 1);
+        let entries = entries(response.streams);
+        let lines = entries
+            .iter()
+            .map(|(labels, entry)| (labels["app"].as_str(), entry.line.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [("api", "first"), ("web", "other"), ("api", "second")]
+        );
+        assert_eq!(entries[0].1.timestamp, 1_686_000_000_000_000_000);
+        assert_eq!(entries[0].1.structured_metadata, None);
+        assert_eq!(
+            entries[2].1.structured_metadata,
+            Some(Labels::from([("trace_id".to_owned(), "abc".to_owned())]))
+        );
+    }
+
+    #[test]
+    fn parse_query_response() {
+        let response: QueryResponse = serde_json::from_str(
+            r#"{
+                "status": "success",
+                "data": {
+                    "resultType": "streams",
+                    "result": [{"stream": {"app": "api"}, "values": [["1686000000000000000", "line"]]}],
+                    "stats": {}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.data.result.len(), 1);
+        assert_eq!(response.data.result[0].values[0].line, "line");
+    }
+
+    #[test]
+    fn build_urls() {
+        let endpoint = Url::parse("https://loki.example.com:3100").unwrap();
+
+        assert_eq!(
+            tail_url(&endpoint, r#"{app="api"}"#, 10, 2).as_str(),
+            "wss://loki.example.com:3100/loki/api/v1/tail?query=%7Bapp%3D%22api%22%7D&start=10&delay_for=2"
+        );
+        assert_eq!(
+            query_range_url(&endpoint, r#"{app="api"}"#, 10, 20, 100).as_str(),
+            "https://loki.example.com:3100/loki/api/v1/query_range?query=%7Bapp%3D%22api%22%7D&start=10&end=20&limit=100&direction=forward"
+        );
+    }
+}
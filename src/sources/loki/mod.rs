@@ -0,0 +1,641 @@
+use std::{
+    net::SocketAddr,
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use codecs::{BytesDeserializer, BytesDeserializerConfig};
+use futures::StreamExt;
+use http::{HeaderMap, HeaderValue, Request};
+use hyper::Body;
+use lookup::{owned_value_path, path};
+use tokio::{net::TcpStream, time};
+use tokio_tungstenite::{
+    client_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    WebSocketStream,
+};
+use url::Url;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use self::api::{Entry, Labels, Stream};
+use crate::{
+    config::{DataType, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    dns,
+    event::{Event, LogEvent},
+    http::{Auth, HttpClient, MaybeAuth},
+    internal_events::{
+        EventsReceived, LokiSourceEntriesDropped, LokiSourceParseError, LokiSourceRequestError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sinks::util::UriSerde,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+mod api;
+
+/// The most Loki lets the tail API wait for entries arriving late.
+const MAX_DELAY_FOR_SECS: u64 = 5;
+
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Configuration for the `loki` source.
+#[configurable_component(source(
+    "loki",
+    "Collect logs from a Loki instance, tailing the logs matching a LogQL query."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LokiConfig {
+    /// The base URL of the Loki instance.
+    ///
+    /// The paths of the tail and query APIs are appended to this.
+    #[configurable(metadata(docs::examples = "http://localhost:3100"))]
+    pub endpoint: UriSerde,
+
+    /// The [LogQL][logql] query selecting the logs to collect.
+    ///
+    /// Only log queries are supported, as metric queries don't return log entries.
+    ///
+    /// [logql]: https://grafana.com/docs/loki/latest/logql/
+    #[configurable(metadata(docs::examples = "{app=\"api\"}"))]
+    #[configurable(metadata(docs::examples = "{namespace=\"production\"} |= \"error\""))]
+    pub query: String,
+
+    /// The [tenant ID][tenant_id] to specify in requests to Loki.
+    ///
+    /// When running Loki locally, a tenant ID is not required.
+    ///
+    /// [tenant_id]: https://grafana.com/docs/loki/latest/operations/multi-tenancy/
+    #[configurable(metadata(docs::examples = "some_tenant_id"))]
+    pub tenant_id: Option<String>,
+
+    /// How long Loki waits before sending entries of the tail, in seconds.
+    ///
+    /// Waiting lets Loki send entries arriving late in order. Loki accepts up to five seconds.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub delay_for_secs: u64,
+
+    /// How far back to query logs before tailing them, in seconds.
+    ///
+    /// The logs since then are collected through range queries, one page at a time, before the
+    /// logs arriving from the start of the source are tailed. If not set, only the logs arriving
+    /// from the start of the source are collected.
+    #[configurable(metadata(docs::examples = 3600))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub backfill_secs: Option<NonZeroU64>,
+
+    /// The maximum number of entries in a page of the range queries of the backfill.
+    ///
+    /// This must not exceed the `max_entries_limit_per_query` of Loki.
+    #[serde(default = "default_backfill_page_size")]
+    pub backfill_page_size: NonZeroU32,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_backfill_page_size() -> NonZeroU32 {
+    NonZeroU32::new(1000).expect("static")
+}
+
+impl GenerateConfig for LokiConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "http://localhost:3100"
+            query = "{app=\"api\"}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "loki")]
+impl SourceConfig for LokiConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.delay_for_secs > MAX_DELAY_FOR_SECS {
+            return Err(format!(
+                "`delay_for_secs` must be at most {} seconds",
+                MAX_DELAY_FOR_SECS
+            )
+            .into());
+        }
+
+        let endpoint = Url::parse(&self.endpoint.uri.to_string())?;
+        if !matches!(endpoint.scheme(), "http" | "https") {
+            return Err("The scheme of `endpoint` must be `http` or `https`".into());
+        }
+
+        let mut headers = HeaderMap::new();
+        if let Some(auth) = self.auth.choose_one(&self.endpoint.auth)? {
+            auth.apply_headers_map(&mut headers);
+        }
+        if let Some(tenant_id) = &self.tenant_id {
+            headers.insert("X-Scope-OrgID", HeaderValue::from_str(tenant_id)?);
+        }
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls.clone(), &cx.proxy)?;
+
+        Ok(Box::pin(
+            LokiSource {
+                endpoint,
+                query: self.query.clone(),
+                headers,
+                delay_for_secs: self.delay_for_secs,
+                backfill: self
+                    .backfill_secs
+                    .map(|secs| Duration::from_secs(secs.get())),
+                page_size: self.backfill_page_size.get(),
+                client,
+                tls,
+                log_namespace: cx.log_namespace(self.log_namespace),
+                bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+                events_received: register!(EventsReceived),
+                out: cx.out,
+            }
+            .run(cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let labels = Kind::object(Collection::empty().with_unknown(Kind::bytes()));
+        let schema_definition = BytesDeserializerConfig
+            .schema_definition(global_log_namespace.merge(self.log_namespace))
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("labels"))),
+                &owned_value_path!("labels"),
+                labels.clone(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(
+                    "structured_metadata"
+                ))),
+                &owned_value_path!("structured_metadata"),
+                labels.or_undefined(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Creates the event of an entry, with the labels of its stream.
+fn create_event(labels: Labels, entry: Entry, log_namespace: LogNamespace) -> LogEvent {
+    let mut log = BytesDeserializer::new().parse_single(Bytes::from(entry.line), log_namespace);
+
+    log_namespace.insert_source_metadata(
+        LokiConfig::NAME,
+        &mut log,
+        log_schema().timestamp_key().map(LegacyKey::Overwrite),
+        path!("timestamp"),
+        Utc.timestamp_nanos(entry.timestamp),
+    );
+    log_namespace.insert_source_metadata(
+        LokiConfig::NAME,
+        &mut log,
+        Some(LegacyKey::Overwrite(path!("labels"))),
+        path!("labels"),
+        labels_value(labels),
+    );
+    if let Some(structured_metadata) = entry.structured_metadata {
+        log_namespace.insert_source_metadata(
+            LokiConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!("structured_metadata"))),
+            path!("structured_metadata"),
+            labels_value(structured_metadata),
+        );
+    }
+    log_namespace.insert_standard_vector_source_metadata(&mut log, LokiConfig::NAME, Utc::now());
+
+    log
+}
+
+fn labels_value(labels: Labels) -> Value {
+    Value::Object(
+        labels
+            .into_iter()
+            .map(|(name, value)| (name, Value::from(value)))
+            .collect(),
+    )
+}
+
+/// The position of the backfill in the logs.
+///
+/// Pages start at the timestamp of the last entry sent, as other entries may share it, so the
+/// entries with this timestamp are remembered to only send them once.
+struct Cursor {
+    timestamp: i64,
+    sent: Vec<(Labels, String)>,
+}
+
+impl Cursor {
+    const fn new(timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            sent: Vec::new(),
+        }
+    }
+
+    /// Filters out the entries already sent from the ordered entries of a page, advancing past
+    /// the others.
+    fn advance(&mut self, entries: Vec<(Labels, Entry)>) -> Vec<(Labels, Entry)> {
+        entries
+            .into_iter()
+            .filter(|(labels, entry)| {
+                if entry.timestamp < self.timestamp {
+                    return false;
+                }
+                if entry.timestamp > self.timestamp {
+                    self.timestamp = entry.timestamp;
+                    self.sent.clear();
+                }
+                let key = (labels.clone(), entry.line.clone());
+                if self.sent.contains(&key) {
+                    return false;
+                }
+                self.sent.push(key);
+                true
+            })
+            .collect()
+    }
+
+    /// Moves past the timestamp of the cursor, when a whole page of entries shares it.
+    fn skip(&mut self) {
+        self.timestamp += 1;
+        self.sent.clear();
+    }
+}
+
+struct LokiSource {
+    endpoint: Url,
+    query: String,
+    headers: HeaderMap,
+    delay_for_secs: u64,
+    backfill: Option<Duration>,
+    page_size: u32,
+    client: HttpClient,
+    tls: TlsSettings,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+}
+
+impl LokiSource {
+    async fn run(mut self, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let now = Utc::now().timestamp_nanos();
+        if let Some(backfill) = self.backfill {
+            let start = now.saturating_sub(backfill.as_nanos() as i64);
+            if !self.backfill(start, now, &mut shutdown).await? {
+                return Ok(());
+            }
+        }
+        self.tail(now, &mut shutdown).await
+    }
+
+    /// Sends the logs from `start` to `end` through range queries, returning `false` if the
+    /// source shut down first.
+    async fn backfill(
+        &mut self,
+        start: i64,
+        end: i64,
+        shutdown: &mut ShutdownSignal,
+    ) -> Result<bool, ()> {
+        let mut cursor = Cursor::new(start);
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            let url = api::query_range_url(
+                &self.endpoint,
+                &self.query,
+                cursor.timestamp,
+                end,
+                self.page_size,
+            );
+            let streams = tokio::select! {
+                result = self.query_range(url) => result,
+                _ = &mut *shutdown => return Ok(false),
+            };
+            let entries = match streams {
+                Ok(streams) => api::entries(streams),
+                Err(error) => {
+                    emit!(LokiSourceRequestError {
+                        api: "query_range",
+                        error,
+                    });
+                    if !wait(&mut backoff, shutdown).await {
+                        return Ok(false);
+                    }
+                    continue;
+                }
+            };
+            backoff = MIN_BACKOFF;
+
+            let last_page = entries.len() < self.page_size as usize;
+            let entries = cursor.advance(entries);
+            if entries.is_empty() {
+                if last_page {
+                    return Ok(true);
+                }
+                // More entries share this timestamp than fit in a page, which can't be paged
+                // through: the remaining ones are skipped.
+                cursor.skip();
+                continue;
+            }
+            self.send(entries).await?;
+            if last_page {
+                return Ok(true);
+            }
+        }
+    }
+
+    async fn query_range(&self, url: Url) -> crate::Result<Vec<Stream>> {
+        let mut request = Request::get(url.as_str()).body(Body::empty())?;
+        request.headers_mut().extend(self.headers.clone());
+
+        let response = self.client.send(request).await?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        if !parts.status.is_success() {
+            return Err(format!(
+                "Loki responded with {}: {}",
+                parts.status,
+                String::from_utf8_lossy(&body).trim()
+            )
+            .into());
+        }
+        self.bytes_received.emit(ByteSize(body.len()));
+
+        match serde_json::from_slice::<api::QueryResponse>(&body) {
+            Ok(response) => Ok(response.data.result),
+            Err(error) => {
+                emit!(LokiSourceParseError {
+                    api: "query_range",
+                    error
+                });
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Tails the logs from `start`, reconnecting whenever the connection is closed.
+    async fn tail(&mut self, mut start: i64, shutdown: &mut ShutdownSignal) -> Result<(), ()> {
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            let connection = tokio::select! {
+                result = self.connect(start) => result,
+                _ = &mut *shutdown => return Ok(()),
+            };
+            let mut stream = match connection {
+                Ok(stream) => stream,
+                Err(error) => {
+                    emit!(LokiSourceRequestError { api: "tail", error });
+                    if !wait(&mut backoff, shutdown).await {
+                        return Ok(());
+                    }
+                    continue;
+                }
+            };
+            backoff = MIN_BACKOFF;
+
+            loop {
+                let message = tokio::select! {
+                    message = stream.next() => message,
+                    _ = &mut *shutdown => return Ok(()),
+                };
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    // Pings are answered by tungstenite while reading from the stream.
+                    Some(Ok(Message::Binary(_) | Message::Ping(_) | Message::Pong(_))) => continue,
+                    // Loki closes tails after their maximum duration.
+                    Some(Ok(Message::Close(_) | Message::Frame(_))) | None => {
+                        debug!(message = "Tail closed by Loki, reconnecting.");
+                        break;
+                    }
+                    Some(Err(error)) => {
+                        emit!(LokiSourceRequestError {
+                            api: "tail",
+                            error: error.into(),
+                        });
+                        break;
+                    }
+                };
+                self.bytes_received.emit(ByteSize(text.len()));
+
+                let response = match serde_json::from_str::<api::TailResponse>(&text) {
+                    Ok(response) => response,
+                    Err(error) => {
+                        emit!(LokiSourceParseError { api: "tail", error });
+                        continue;
+                    }
+                };
+                if !response.dropped_entries.is_empty() {
+                    emit!(LokiSourceEntriesDropped {
+                        count: response.dropped_entries.len()
+                    });
+                }
+                let entries = api::entries(response.streams);
+                if let Some((_, entry)) = entries.last() {
+                    // The tail is resumed after the last entry sent.
+                    start = start.max(entry.timestamp + 1);
+                }
+                self.send(entries).await?;
+            }
+        }
+    }
+
+    /// Connects to the tail API, from `start`.
+    async fn connect(
+        &self,
+        start: i64,
+    ) -> crate::Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+        let url = api::tail_url(&self.endpoint, &self.query, start, self.delay_for_secs);
+        let mut request = url.as_str().into_client_request()?;
+        request.headers_mut().extend(self.headers.clone());
+
+        let host = url.host_str().ok_or("The endpoint has no host")?.to_owned();
+        let port = url.port_or_known_default().unwrap_or(80);
+        let tls = if url.scheme() == "wss" {
+            MaybeTlsSettings::Tls(self.tls.clone())
+        } else {
+            MaybeTlsSettings::Raw(())
+        };
+
+        let mut error: crate::Error = "No addresses returned".into();
+        for ip in dns::Resolver.lookup_ip(host.clone()).await? {
+            match tls.connect(&host, &SocketAddr::new(ip, port)).await {
+                Ok(stream) => {
+                    let (stream, _response) = client_async(request, stream).await?;
+                    return Ok(stream);
+                }
+                Err(connect_error) => error = connect_error.into(),
+            }
+        }
+        Err(error)
+    }
+
+    async fn send(&mut self, entries: Vec<(Labels, Entry)>) -> Result<(), ()> {
+        let count = entries.len();
+        if count == 0 {
+            return Ok(());
+        }
+        let events = entries
+            .into_iter()
+            .map(|(labels, entry)| Event::from(create_event(labels, entry, self.log_namespace)))
+            .collect::<Vec<_>>();
+        self.events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        self.out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })
+    }
+}
+
+/// Waits before retrying, returning `false` if the source shut down first.
+async fn wait(backoff: &mut Duration, shutdown: &mut ShutdownSignal) -> bool {
+    let delay = *backoff;
+    *backoff = (*backoff * 2).min(MAX_BACKOFF);
+    tokio::select! {
+        _ = time::sleep(delay) => true,
+        _ = shutdown => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::value;
+
+    use super::*;
+
+    fn entry(timestamp: i64, line: &str) -> Entry {
+        Entry {
+            timestamp,
+            line: line.to_owned(),
+            structured_metadata: None,
+        }
+    }
+
+    fn labels(app: &str) -> Labels {
+        Labels::from([("app".to_owned(), app.to_owned())])
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LokiConfig>();
+    }
+
+    #[test]
+    fn create_legacy_event() {
+        let entry = Entry {
+            structured_metadata: Some(Labels::from([("trace_id".to_owned(), "abc".to_owned())])),
+            ..entry(1_686_000_000_000_000_001, "GET /health 200")
+        };
+
+        let log = create_event(labels("api"), entry, LogNamespace::Legacy);
+
+        assert_eq!(log["message"], "GET /health 200".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp_nanos(1_686_000_000_000_000_001).into()
+        );
+        assert_eq!(log["labels"], value!({"app": "api"}));
+        assert_eq!(log["structured_metadata"], value!({"trace_id": "abc"}));
+        assert_eq!(log["source_type"], "loki".into());
+    }
+
+    #[test]
+    fn create_vector_event() {
+        let log = create_event(labels("api"), entry(1, "line"), LogNamespace::Vector);
+
+        assert_eq!(*log.value(), "line".into());
+        let metadata = log.metadata().value();
+        assert_eq!(
+            metadata.get(path!("loki", "labels")),
+            Some(&value!({"app": "api"}))
+        );
+        assert_eq!(
+            metadata.get(path!("loki", "timestamp")),
+            Some(&Value::from(Utc.timestamp_nanos(1)))
+        );
+        assert_eq!(metadata.get(path!("loki", "structured_metadata")), None);
+    }
+
+    #[test]
+    fn cursor_sends_entries_once() {
+        let mut cursor = Cursor::new(10);
+        let lines = |entries: Vec<(Labels, Entry)>| {
+            entries
+                .into_iter()
+                .map(|(_, entry)| entry.line)
+                .collect::<Vec<_>>()
+        };
+
+        let page = vec![
+            (labels("api"), entry(10, "a")),
+            (labels("api"), entry(11, "b")),
+            (labels("web"), entry(12, "c")),
+        ];
+        assert_eq!(lines(cursor.advance(page)), ["a", "b", "c"]);
+        assert_eq!(cursor.timestamp, 12);
+
+        // The next page starts at the timestamp of the last entry sent.
+        let page = vec![
+            (labels("web"), entry(12, "c")),
+            (labels("api"), entry(12, "c")),
+            (labels("api"), entry(13, "d")),
+        ];
+        assert_eq!(lines(cursor.advance(page)), ["c", "d"]);
+        assert_eq!(cursor.timestamp, 13);
+
+        cursor.skip();
+        assert_eq!(
+            lines(cursor.advance(vec![(labels("api"), entry(13, "d"))])),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,551 @@
+use std::{path::PathBuf, process::Stdio, time::Duration};
+
+use chrono::{DateTime, Utc};
+use lookup::{lookup_v2::parse_value_path, owned_value_path, path, OwnedTargetPath};
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    time::sleep,
+};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{log_schema, DataType, SourceConfig, SourceContext, SourceOutput},
+    event::LogEvent,
+    internal_events::{
+        EventsReceived, MacosUnifiedLogInvalidRecordError, MacosUnifiedLogReadError,
+        MacosUnifiedLogStartError, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const BACKOFF_DURATION: Duration = Duration::from_secs(1);
+
+/// The fields of entries, other than their message and timestamp.
+const BYTES_FIELDS: [&str; 6] = [
+    "subsystem",
+    "category",
+    "process",
+    "sender",
+    "level",
+    "event_type",
+];
+const INTEGER_FIELDS: [&str; 3] = ["process_id", "thread_id", "activity_id"];
+
+/// Configuration for the `macos_unified_log` source.
+#[configurable_component(source(
+    "macos_unified_log",
+    "Collect logs from the macOS unified logging system."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MacosUnifiedLogConfig {
+    /// A [predicate][predicate] filtering the entries read.
+    ///
+    /// Predicates are evaluated by the logging system, so filtering is cheaper there than in a
+    /// transform.
+    ///
+    /// [predicate]: https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/Predicates/AdditionalChapters/Introduction.html
+    #[configurable(metadata(
+        docs::examples = "subsystem == \"com.apple.sharing\"",
+        docs::examples = "process == \"sshd\" AND messageType == error"
+    ))]
+    pub predicate: Option<String>,
+
+    /// The processes whose entries are read, by name or ID.
+    ///
+    /// If empty, entries of all processes are read.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "sshd", docs::examples = "1"))]
+    pub processes: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub level: UnifiedLogLevel,
+
+    /// Whether or not to read activity and trace entries, along with log entries.
+    #[serde(default)]
+    pub include_activities: bool,
+
+    /// The full path of the `log` executable.
+    #[serde(default = "default_log_path")]
+    pub log_path: PathBuf,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// The lowest level of the entries read.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnifiedLogLevel {
+    /// Entries of the default, error, and fault levels.
+    #[default]
+    Default,
+
+    /// Entries of the info level, along with those of the default level.
+    Info,
+
+    /// Entries of every level.
+    ///
+    /// Debug entries are numerous, and are usually better read with a predicate.
+    Debug,
+}
+
+impl UnifiedLogLevel {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        }
+    }
+}
+
+fn default_log_path() -> PathBuf {
+    PathBuf::from("/usr/bin/log")
+}
+
+impl Default for MacosUnifiedLogConfig {
+    fn default() -> Self {
+        Self {
+            predicate: None,
+            processes: Vec::new(),
+            level: UnifiedLogLevel::default(),
+            include_activities: false,
+            log_path: default_log_path(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(MacosUnifiedLogConfig);
+
+impl MacosUnifiedLogConfig {
+    fn command(&self) -> Command {
+        let mut command = Command::new(&self.log_path);
+        command.args([
+            "stream",
+            "--style",
+            "ndjson",
+            "--level",
+            self.level.as_str(),
+        ]);
+        if !self.include_activities {
+            command.args(["--type", "log"]);
+        }
+        if let Some(predicate) = &self.predicate {
+            command.arg("--predicate").arg(predicate);
+        }
+        for process in &self.processes {
+            command.arg("--process").arg(process);
+        }
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        command
+    }
+
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        let mut definition = match log_namespace {
+            LogNamespace::Vector => {
+                Definition::new_with_default_metadata(Kind::bytes(), [log_namespace])
+                    .with_meaning(OwnedTargetPath::event_root(), "message")
+            }
+            LogNamespace::Legacy => Definition::new_with_default_metadata(
+                Kind::object(Collection::empty()),
+                [log_namespace],
+            )
+            .with_event_field(
+                &parse_value_path(log_schema().message_key()).expect("valid message key"),
+                Kind::bytes(),
+                Some("message"),
+            ),
+        }
+        .with_standard_vector_source_metadata()
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .timestamp_key()
+                .cloned()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("timestamp"),
+            Kind::timestamp(),
+            Some("timestamp"),
+        )
+        .with_source_metadata(
+            Self::NAME,
+            parse_value_path(log_schema().host_key())
+                .ok()
+                .map(LegacyKey::Overwrite),
+            &owned_value_path!("host"),
+            Kind::bytes().or_undefined(),
+            Some("host"),
+        );
+
+        for field in BYTES_FIELDS {
+            definition = definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(field))),
+                &owned_value_path!(field),
+                Kind::bytes().or_undefined(),
+                (field == "level").then_some("severity"),
+            );
+        }
+        for field in INTEGER_FIELDS {
+            definition = definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(field))),
+                &owned_value_path!(field),
+                Kind::integer().or_undefined(),
+                None,
+            );
+        }
+        definition
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "macos_unified_log")]
+impl SourceConfig for MacosUnifiedLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if !cfg!(target_os = "macos") {
+            return Err("The macos_unified_log source is only available on macOS.".into());
+        }
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        Ok(Box::pin(run(
+            self.clone(),
+            cx.out,
+            cx.shutdown,
+            log_namespace,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition =
+            self.schema_definition(global_log_namespace.merge(self.log_namespace));
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// An entry, as written by `log stream --style ndjson`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    event_message: Option<String>,
+    event_type: Option<String>,
+    message_type: Option<String>,
+    subsystem: Option<String>,
+    category: Option<String>,
+    process_image_path: Option<String>,
+    sender_image_path: Option<String>,
+    #[serde(rename = "processID")]
+    process_id: Option<i64>,
+    #[serde(rename = "threadID")]
+    thread_id: Option<i64>,
+    activity_identifier: Option<i64>,
+    timestamp: Option<String>,
+}
+
+async fn run(
+    config: MacosUnifiedLogConfig,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+    log_namespace: LogNamespace,
+) -> Result<(), ()> {
+    let hostname = crate::get_hostname().ok();
+    let bytes_received = register!(BytesReceived::from(Protocol::from("unified_log")));
+    let events_received = register!(EventsReceived);
+
+    loop {
+        let mut child = match config.command().spawn() {
+            Ok(child) => child,
+            Err(error) => {
+                emit!(MacosUnifiedLogStartError { error });
+                tokio::select! {
+                    _ = &mut shutdown => return Ok(()),
+                    _ = sleep(BACKOFF_DURATION) => continue,
+                }
+            }
+        };
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut lines = BufReader::new(stdout).lines();
+
+        loop {
+            let line = tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                line = lines.next_line() => line,
+            };
+            let line = match line {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(error) => {
+                    emit!(MacosUnifiedLogReadError { error });
+                    break;
+                }
+            };
+            bytes_received.emit(ByteSize(line.len()));
+
+            // `log` starts its output with a line describing the filter.
+            if !line.starts_with('{') {
+                continue;
+            }
+            let entry = match serde_json::from_str::<Entry>(&line) {
+                Ok(entry) => entry,
+                Err(error) => {
+                    emit!(MacosUnifiedLogInvalidRecordError { error, text: line });
+                    continue;
+                }
+            };
+
+            let log = create_log(entry, hostname.as_deref(), log_namespace);
+            events_received.emit(CountByteSize(1, log.estimated_json_encoded_size_of()));
+            if out.send_event(log).await.is_err() {
+                emit!(StreamClosedError { count: 1 });
+                return Err(());
+            }
+        }
+
+        // The logging system may have been restarted, so `log` is restarted as well.
+        drop(child);
+        tokio::select! {
+            _ = &mut shutdown => return Ok(()),
+            _ = sleep(BACKOFF_DURATION) => debug!("Restarting log stream."),
+        }
+    }
+}
+
+fn create_log(entry: Entry, hostname: Option<&str>, log_namespace: LogNamespace) -> LogEvent {
+    let now = Utc::now();
+    let mut log = match log_namespace {
+        LogNamespace::Vector => {
+            log_namespace.new_log_from_data(entry.event_message.unwrap_or_default())
+        }
+        LogNamespace::Legacy => {
+            let mut log = LogEvent::default();
+            log.insert(
+                log_schema().message_key(),
+                entry.event_message.unwrap_or_default(),
+            );
+            log
+        }
+    };
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        MacosUnifiedLogConfig::NAME,
+        now,
+    );
+
+    let timestamp = entry
+        .timestamp
+        .as_deref()
+        .and_then(parse_timestamp)
+        .unwrap_or(now);
+    log_namespace.insert_source_metadata(
+        MacosUnifiedLogConfig::NAME,
+        &mut log,
+        log_schema().timestamp_key().map(LegacyKey::Overwrite),
+        path!("timestamp"),
+        timestamp,
+    );
+    if let Some(hostname) = hostname {
+        log_namespace.insert_source_metadata(
+            MacosUnifiedLogConfig::NAME,
+            &mut log,
+            Some(LegacyKey::Overwrite(path!(log_schema().host_key()))),
+            path!("host"),
+            hostname.to_owned(),
+        );
+    }
+
+    let bytes_fields = [
+        (
+            "subsystem",
+            entry.subsystem.filter(|value| !value.is_empty()),
+        ),
+        ("category", entry.category.filter(|value| !value.is_empty())),
+        (
+            "process",
+            entry.process_image_path.as_deref().map(file_name),
+        ),
+        ("sender", entry.sender_image_path.as_deref().map(file_name)),
+        (
+            "level",
+            entry.message_type.map(|level| level.to_lowercase()),
+        ),
+        ("event_type", entry.event_type),
+    ];
+    for (field, value) in bytes_fields {
+        if let Some(value) = value {
+            log_namespace.insert_source_metadata(
+                MacosUnifiedLogConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(field))),
+                path!(field),
+                value,
+            );
+        }
+    }
+    let integer_fields = [
+        ("process_id", entry.process_id),
+        ("thread_id", entry.thread_id),
+        (
+            "activity_id",
+            entry.activity_identifier.filter(|&id| id != 0),
+        ),
+    ];
+    for (field, value) in integer_fields {
+        if let Some(value) = value {
+            log_namespace.insert_source_metadata(
+                MacosUnifiedLogConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(field))),
+                path!(field),
+                value,
+            );
+        }
+    }
+    log
+}
+
+/// Parses timestamps such as `2023-06-20 10:11:12.123456-0700`.
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f%z")
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit('/').next().unwrap_or(path).to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vrl::value::Value;
+
+    use super::*;
+
+    const ENTRY: &str = r#"{"traceID":1234,"eventMessage":"Accepted publickey for admin","eventType":"logEvent","source":null,"formatString":"%{public}s","activityIdentifier":0,"subsystem":"com.openssh.sshd","category":"auth","threadID":5678,"senderImageUUID":"AB","backtrace":{"frames":[]},"bootUUID":"","processImagePath":"\/usr\/sbin\/sshd","timestamp":"2023-06-20 10:11:12.500000-0700","senderImagePath":"\/usr\/lib\/libsystem_trace.dylib","machTimestamp":1,"messageType":"Error","processImageUUID":"CD","processID":321,"senderProgramCounter":2,"parentActivityIdentifier":0,"timezoneName":""}"#;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MacosUnifiedLogConfig>();
+    }
+
+    #[test]
+    fn builds_command() {
+        let config: MacosUnifiedLogConfig = toml::from_str(
+            r#"
+            predicate = 'subsystem == "com.openssh.sshd"'
+            processes = ["sshd", "1"]
+            level = "info"
+            "#,
+        )
+        .unwrap();
+        let command = config.command();
+        let args = command
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(command.as_std().get_program(), "/usr/bin/log");
+        assert_eq!(
+            args,
+            vec![
+                "stream",
+                "--style",
+                "ndjson",
+                "--level",
+                "info",
+                "--type",
+                "log",
+                "--predicate",
+                r#"subsystem == "com.openssh.sshd""#,
+                "--process",
+                "sshd",
+                "--process",
+                "1",
+            ]
+        );
+    }
+
+    #[test]
+    fn creates_legacy_events() {
+        let entry = serde_json::from_str(ENTRY).unwrap();
+        let log = create_log(entry, Some("mac-1"), LogNamespace::Legacy);
+
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Accepted publickey for admin".into()
+        );
+        assert_eq!(
+            log[log_schema().timestamp_key().unwrap().to_string().as_str()],
+            Value::from(
+                Utc.with_ymd_and_hms(2023, 6, 20, 17, 11, 12).unwrap()
+                    + chrono::Duration::milliseconds(500)
+            )
+        );
+        assert_eq!(log[log_schema().host_key()], "mac-1".into());
+        assert_eq!(log["subsystem"], "com.openssh.sshd".into());
+        assert_eq!(log["category"], "auth".into());
+        assert_eq!(log["process"], "sshd".into());
+        assert_eq!(log["sender"], "libsystem_trace.dylib".into());
+        assert_eq!(log["level"], "error".into());
+        assert_eq!(log["process_id"], 321_i64.into());
+        assert_eq!(log["thread_id"], 5678_i64.into());
+        assert!(log.get("activity_id").is_none());
+    }
+
+    #[test]
+    fn creates_vector_namespace_events() {
+        let entry = serde_json::from_str(ENTRY).unwrap();
+        let log = create_log(entry, None, LogNamespace::Vector);
+
+        assert_eq!(log.value(), &Value::from("Accepted publickey for admin"));
+        let metadata = log.metadata().value();
+        assert_eq!(
+            metadata.get(path!("macos_unified_log", "subsystem")),
+            Some(&Value::from("com.openssh.sshd"))
+        );
+        assert_eq!(
+            metadata.get(path!("macos_unified_log", "process_id")),
+            Some(&Value::from(321_i64))
+        );
+        assert_eq!(
+            metadata.get(path!("vector", "source_type")),
+            Some(&Value::from("macos_unified_log"))
+        );
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(
+            parse_timestamp("2023-06-20 10:11:12.000000+0200"),
+            Some(Utc.with_ymd_and_hms(2023, 6, 20, 8, 11, 12).unwrap())
+        );
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+}
@@ -13,8 +13,12 @@ pub mod aws_kinesis_firehose;
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-collectd_telegraf")]
+pub mod collectd_telegraf;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog_agent;
+#[cfg(feature = "sources-dead_letter")]
+pub mod dead_letter;
 #[cfg(feature = "sources-demo_logs")]
 pub mod demo_logs;
 #[cfg(all(unix, feature = "sources-dnstap"))]
@@ -36,6 +40,8 @@ pub mod file_descriptors;
 pub mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-heartbeat")]
+pub mod heartbeat;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -44,10 +50,14 @@ pub mod host_metrics;
 pub mod http_client;
 #[cfg(feature = "sources-http_server")]
 pub mod http_server;
+#[cfg(feature = "sources-internal_accounting")]
+pub mod internal_accounting;
 #[cfg(feature = "sources-internal_logs")]
 pub mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 pub mod internal_metrics;
+#[cfg(feature = "sources-internal_slo")]
+pub mod internal_slo;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
@@ -56,12 +66,18 @@ pub mod kafka;
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
 pub mod logstash;
+#[cfg(feature = "sources-loki")]
+pub mod loki;
+#[cfg(feature = "sources-macos_unified_log")]
+pub mod macos_unified_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-okta")]
+pub mod okta;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
 #[cfg(feature = "sources-postgresql_metrics")]
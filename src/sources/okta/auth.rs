@@ -0,0 +1,260 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use base64::prelude::{Engine as _, BASE64_URL_SAFE_NO_PAD};
+use http::{header, HeaderValue, Request};
+use hyper::Body;
+use openssl::{
+    hash::MessageDigest,
+    pkey::{Id, PKey, Private},
+    sign::Signer,
+};
+use serde::Deserialize;
+use serde_json::json;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::http::HttpClient;
+
+/// The scope granting read access to the System Log.
+const LOGS_SCOPE: &str = "okta.logs.read";
+
+/// How long the client assertions are valid for. Okta rejects assertions valid for more than an
+/// hour.
+const ASSERTION_LIFETIME: Duration = Duration::from_secs(300);
+
+/// Access tokens are renewed this long before they expire, so that they don't expire in flight.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// Authentication options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(docs::enum_tag_description = "The authentication strategy to use."))]
+pub enum OktaAuthConfig {
+    /// Authenticate with an API token.
+    ///
+    /// API tokens act on behalf of the admin who created them, with the permissions of that admin,
+    /// and expire after 30 days without being used.
+    ApiToken {
+        /// The API token.
+        #[configurable(metadata(docs::examples = "${OKTA_API_TOKEN}"))]
+        token: SensitiveString,
+    },
+
+    /// Authenticate as an API service app, through the OAuth 2.0 client credentials flow.
+    ///
+    /// The app authenticates with a client assertion signed by its private key, and must be
+    /// granted the `okta.logs.read` scope. Demonstrating Proof-of-Possession (DPoP) must be
+    /// disabled for the app, as it is not supported.
+    Oauth {
+        /// The client ID of the app.
+        #[configurable(metadata(docs::examples = "0oa1b2c3d4e5f6g7h8i9"))]
+        client_id: String,
+
+        /// The path of the RSA private key of the app, in PEM format.
+        ///
+        /// The matching public key must be registered with the app.
+        #[configurable(metadata(docs::examples = "/etc/vector/okta.pem"))]
+        private_key_file: PathBuf,
+
+        /// The ID of the key, sent in the `kid` header of the client assertions.
+        ///
+        /// Required when several public keys are registered with the app.
+        #[configurable(metadata(docs::examples = "vector"))]
+        key_id: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Provides the `Authorization` header of the requests to the System Log API.
+pub(super) enum Authorizer {
+    ApiToken(HeaderValue),
+    OAuth(OAuthClient),
+}
+
+impl Authorizer {
+    /// Builds the authorizer of the Okta organization at `endpoint`.
+    pub(super) fn new(config: &OktaAuthConfig, endpoint: &str) -> crate::Result<Self> {
+        Ok(match config {
+            OktaAuthConfig::ApiToken { token } => {
+                let mut value = HeaderValue::from_str(&format!("SSWS {}", token.inner()))?;
+                value.set_sensitive(true);
+                Self::ApiToken(value)
+            }
+            OktaAuthConfig::Oauth {
+                client_id,
+                private_key_file,
+                key_id,
+            } => {
+                let pem = std::fs::read(private_key_file).map_err(|error| {
+                    format!(
+                        "Could not read private key file {:?}: {}",
+                        private_key_file, error
+                    )
+                })?;
+                let key = PKey::private_key_from_pem(&pem)?;
+                if key.id() != Id::RSA {
+                    return Err("The private key of the app must be an RSA key".into());
+                }
+                Self::OAuth(OAuthClient {
+                    token_url: format!("{}/oauth2/v1/token", endpoint.trim_end_matches('/')),
+                    client_id: client_id.clone(),
+                    key,
+                    key_id: key_id.clone(),
+                    token: None,
+                })
+            }
+        })
+    }
+
+    /// Returns the `Authorization` header, requesting an access token first if needed.
+    pub(super) async fn authorization(
+        &mut self,
+        client: &HttpClient,
+    ) -> crate::Result<HeaderValue> {
+        match self {
+            Self::ApiToken(value) => Ok(value.clone()),
+            Self::OAuth(oauth) => oauth.authorization(client).await,
+        }
+    }
+
+    /// Discards the access token, after Okta rejected it.
+    pub(super) fn invalidate(&mut self) {
+        if let Self::OAuth(oauth) = self {
+            oauth.token = None;
+        }
+    }
+}
+
+pub(super) struct OAuthClient {
+    token_url: String,
+    client_id: String,
+    key: PKey<Private>,
+    key_id: Option<String>,
+    token: Option<(HeaderValue, Instant)>,
+}
+
+impl OAuthClient {
+    async fn authorization(&mut self, client: &HttpClient) -> crate::Result<HeaderValue> {
+        if let Some((value, expiry)) = &self.token {
+            if Instant::now() < *expiry {
+                return Ok(value.clone());
+            }
+        }
+
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", "client_credentials")
+            .append_pair("scope", LOGS_SCOPE)
+            .append_pair(
+                "client_assertion_type",
+                "urn:ietf:params:oauth:client-assertion-type:jwt-bearer",
+            )
+            .append_pair("client_assertion", &self.client_assertion()?)
+            .finish();
+        let request = Request::post(&self.token_url)
+            .header(header::ACCEPT, "application/json")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))?;
+
+        let requested = Instant::now();
+        let response = client.send(request).await?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        if !parts.status.is_success() {
+            return Err(format!(
+                "Okta responded to the token request with {}: {}",
+                parts.status,
+                String::from_utf8_lossy(&body).trim()
+            )
+            .into());
+        }
+
+        let response = serde_json::from_slice::<TokenResponse>(&body)?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", response.access_token))?;
+        value.set_sensitive(true);
+        let lifetime = Duration::from_secs(response.expires_in).saturating_sub(EXPIRY_MARGIN);
+        self.token = Some((value.clone(), requested + lifetime));
+        Ok(value)
+    }
+
+    /// Builds a `private_key_jwt` client assertion, a JWT signed with RS256 by the key of the app.
+    fn client_assertion(&self) -> crate::Result<String> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut header = json!({ "alg": "RS256", "typ": "JWT" });
+        if let Some(key_id) = &self.key_id {
+            header["kid"] = key_id.as_str().into();
+        }
+        let claims = json!({
+            "aud": self.token_url,
+            "iss": self.client_id,
+            "sub": self.client_id,
+            "iat": now,
+            "exp": now + ASSERTION_LIFETIME.as_secs(),
+            "jti": uuid::Uuid::new_v4().to_string(),
+        });
+
+        let signing_input = format!(
+            "{}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?),
+            BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims)?)
+        );
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key)?;
+        signer.update(signing_input.as_bytes())?;
+        let signature = signer.sign_to_vec()?;
+
+        Ok(format!(
+            "{}.{}",
+            signing_input,
+            BASE64_URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{rsa::Rsa, sign::Verifier};
+
+    use super::*;
+
+    #[test]
+    fn signs_client_assertions() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let client = OAuthClient {
+            token_url: "https://example.okta.com/oauth2/v1/token".to_owned(),
+            client_id: "0oa1b2c3".to_owned(),
+            key: key.clone(),
+            key_id: Some("vector".to_owned()),
+            token: None,
+        };
+
+        let assertion = client.client_assertion().unwrap();
+        let parts = assertion.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+
+        let decode = |part: &str| -> serde_json::Value {
+            serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(part).unwrap()).unwrap()
+        };
+        let header = decode(parts[0]);
+        assert_eq!(header["alg"], "RS256");
+        assert_eq!(header["kid"], "vector");
+        let claims = decode(parts[1]);
+        assert_eq!(claims["aud"], "https://example.okta.com/oauth2/v1/token");
+        assert_eq!(claims["iss"], "0oa1b2c3");
+        assert_eq!(claims["sub"], "0oa1b2c3");
+
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+        verifier
+            .update(format!("{}.{}", parts[0], parts[1]).as_bytes())
+            .unwrap();
+        let signature = BASE64_URL_SAFE_NO_PAD.decode(parts[2]).unwrap();
+        assert!(verifier.verify(&signature).unwrap());
+    }
+}
@@ -0,0 +1,78 @@
+//! Persistence of the position of the `okta` source in the System Log.
+
+use std::{io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::internal_events::OktaCheckpointError;
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+struct Checkpoint {
+    /// The URL of the next page of events, from the `Link` header of the last page delivered.
+    next: String,
+}
+
+/// The URL of the next page of events, persisted in the data directory of the source.
+pub(super) struct Checkpointer {
+    path: PathBuf,
+}
+
+impl Checkpointer {
+    pub(super) fn new(data_dir: PathBuf) -> Self {
+        Self {
+            path: data_dir.join(CHECKPOINT_FILENAME),
+        }
+    }
+
+    /// Loads the URL of the next page, if a checkpoint was persisted.
+    pub(super) fn load(&self) -> io::Result<Option<String>> {
+        match std::fs::read(&self.path) {
+            Ok(contents) => Ok(Some(serde_json::from_slice::<Checkpoint>(&contents)?.next)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Persists the URL of the next page.
+    pub(super) fn commit(&self, next: &str) {
+        let checkpoint = Checkpoint {
+            next: next.to_owned(),
+        };
+        if let Err(error) = self.persist(&checkpoint) {
+            emit!(OktaCheckpointError {
+                error,
+                path: self.path.to_string_lossy().into_owned(),
+            });
+        }
+    }
+
+    fn persist(&self, checkpoint: &Checkpoint) -> io::Result<()> {
+        // Written to a temporary file first so that a crash never leaves a truncated checkpoint.
+        let temporary = self.path.with_extension("json.tmp");
+        std::fs::write(&temporary, serde_json::to_vec(checkpoint)?)?;
+        std::fs::rename(&temporary, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_the_next_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpointer = Checkpointer::new(dir.path().to_owned());
+        assert_eq!(checkpointer.load().unwrap(), None);
+
+        checkpointer.commit("https://example.okta.com/api/v1/logs?after=1");
+        checkpointer.commit("https://example.okta.com/api/v1/logs?after=2");
+
+        let checkpointer = Checkpointer::new(dir.path().to_owned());
+        assert_eq!(
+            checkpointer.load().unwrap().as_deref(),
+            Some("https://example.okta.com/api/v1/logs?after=2")
+        );
+    }
+}
@@ -0,0 +1,602 @@
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use codecs::JsonDeserializerConfig;
+use http::{header, HeaderMap, Request, StatusCode};
+use hyper::Body;
+use lookup::{owned_value_path, path};
+use tokio::time;
+use url::Url;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{Kind, Value};
+
+use self::{
+    auth::{Authorizer, OktaAuthConfig},
+    checkpoint::Checkpointer,
+};
+use crate::{
+    config::{
+        DataType, GenerateConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
+        SourceOutput,
+    },
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    http::HttpClient,
+    internal_events::{
+        EventsReceived, OktaParseError, OktaRateLimited, OktaRequestError, StreamClosedError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    sinks::util::UriSerde,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+mod auth;
+mod checkpoint;
+
+/// The path of the System Log API.
+const LOGS_PATH: &str = "/api/v1/logs";
+
+/// The most events Okta returns in a page.
+const MAX_PAGE_SIZE: u32 = 1000;
+
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Configuration for the `okta` source.
+#[configurable_component(source(
+    "okta",
+    "Collect audit events from the System Log of an Okta organization."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OktaConfig {
+    /// The URL of the Okta organization.
+    #[configurable(metadata(docs::examples = "https://example.okta.com"))]
+    pub endpoint: UriSerde,
+
+    #[configurable(derived)]
+    pub auth: OktaAuthConfig,
+
+    /// An expression filtering the events to collect, in the [filter syntax][filter] of the System
+    /// Log API.
+    ///
+    /// [filter]: https://developer.okta.com/docs/reference/api/system-log/#expression-filter
+    #[configurable(metadata(docs::examples = "eventType eq \"user.session.start\""))]
+    #[configurable(metadata(docs::examples = "severity eq \"WARN\" or severity eq \"ERROR\""))]
+    pub filter: Option<String>,
+
+    /// How far back to collect events from when the source first starts, in seconds.
+    ///
+    /// Once a checkpoint is persisted, the source resumes from it instead. Okta retains events for
+    /// 90 days. By default, only the events published since the first start are collected.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = 86400))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub backfill_secs: u64,
+
+    /// The maximum number of events in a page.
+    ///
+    /// Okta returns at most 1000 events in a page.
+    #[serde(default = "default_page_size")]
+    pub page_size: NonZeroU32,
+
+    /// The interval between requests once all events published so far are collected, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Poll Interval"))]
+    pub poll_interval_secs: NonZeroU64,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_page_size() -> NonZeroU32 {
+    NonZeroU32::new(MAX_PAGE_SIZE).expect("static")
+}
+
+fn default_poll_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(15).expect("static")
+}
+
+impl GenerateConfig for OktaConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "https://example.okta.com"
+            auth.strategy = "api_token"
+            auth.token = "${OKTA_API_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "okta")]
+impl SourceConfig for OktaConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.page_size.get() > MAX_PAGE_SIZE {
+            return Err(format!("`page_size` must be at most {}", MAX_PAGE_SIZE).into());
+        }
+
+        let endpoint = self.endpoint.with_default_parts().uri.to_string();
+        let authorizer = Authorizer::new(&self.auth, &endpoint)?;
+        let start_url = self.start_url(&endpoint, Utc::now())?;
+
+        let data_dir = cx.globals.resolve_and_make_data_subdir(None, cx.key.id())?;
+        let checkpointer = Checkpointer::new(data_dir);
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        Ok(Box::pin(
+            OktaSource {
+                client,
+                authorizer,
+                checkpointer,
+                start_url,
+                page_size: self.page_size.get() as usize,
+                poll_interval: Duration::from_secs(self.poll_interval_secs.get()),
+                acknowledgements: cx.do_acknowledgements(self.acknowledgements),
+                log_namespace: cx.log_namespace(self.log_namespace),
+                bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+                events_received: register!(EventsReceived),
+                out: cx.out,
+            }
+            .run(cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let schema_definition = JsonDeserializerConfig::default()
+            .schema_definition(global_log_namespace.merge(self.log_namespace))
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                log_schema()
+                    .timestamp_key()
+                    .cloned()
+                    .map(LegacyKey::Overwrite),
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            );
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl OktaConfig {
+    /// The URL of the first page of events, when no checkpoint was persisted.
+    fn start_url(&self, endpoint: &str, now: DateTime<Utc>) -> crate::Result<String> {
+        let since = now - chrono::Duration::seconds(self.backfill_secs as i64);
+        let mut url = Url::parse(endpoint)?.join(LOGS_PATH)?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("since", &since.to_rfc3339_opts(SecondsFormat::Millis, true))
+                .append_pair("sortOrder", "ASCENDING")
+                .append_pair("limit", &self.page_size.to_string());
+            if let Some(filter) = &self.filter {
+                query.append_pair("filter", filter);
+            }
+        }
+        Ok(url.into())
+    }
+}
+
+/// A page of events returned by the System Log API.
+struct Page {
+    events: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// The URL of the next page, which Okta returns even once all events published so far were
+    /// returned, to poll for the events published next.
+    next: Option<String>,
+    /// How long to wait before the next request, when the rate limit is exhausted.
+    rate_limit_delay: Option<Duration>,
+}
+
+enum Response {
+    Page(Page),
+    RateLimited(Duration),
+}
+
+struct OktaSource {
+    client: HttpClient,
+    authorizer: Authorizer,
+    checkpointer: Checkpointer,
+    start_url: String,
+    page_size: usize,
+    poll_interval: Duration,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    out: SourceSender,
+}
+
+impl OktaSource {
+    async fn run(mut self, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut url = match self.checkpointer.load() {
+            Ok(next) => next.unwrap_or_else(|| self.start_url.clone()),
+            Err(error) => {
+                error!(
+                    message = "Could not load checkpoint, starting over.",
+                    %error,
+                );
+                self.start_url.clone()
+            }
+        };
+        let mut backoff = MIN_BACKOFF;
+
+        loop {
+            let response = tokio::select! {
+                response = self.fetch(&url) => response,
+                _ = &mut shutdown => return Ok(()),
+            };
+            let page = match response {
+                Ok(Response::Page(page)) => page,
+                Ok(Response::RateLimited(delay)) => {
+                    emit!(OktaRateLimited { delay });
+                    if !sleep(delay, &mut shutdown).await {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                Err(error) => {
+                    emit!(OktaRequestError { error });
+                    let delay = backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    if !sleep(delay, &mut shutdown).await {
+                        return Ok(());
+                    }
+                    continue;
+                }
+            };
+            backoff = MIN_BACKOFF;
+
+            let caught_up = page.events.len() < self.page_size;
+            let delivered = tokio::select! {
+                delivered = self.send(page.events) => delivered?,
+                _ = &mut shutdown => return Ok(()),
+            };
+            // The page is requested again unless its events were delivered, so that they are
+            // collected at least once.
+            if delivered {
+                if let Some(next) = page.next {
+                    if next != url {
+                        self.checkpointer.commit(&next);
+                        url = next;
+                    }
+                }
+            }
+
+            let delay = match (caught_up || !delivered, page.rate_limit_delay) {
+                (true, Some(delay)) => Some(delay.max(self.poll_interval)),
+                (true, None) => Some(self.poll_interval),
+                (false, Some(delay)) => {
+                    emit!(OktaRateLimited { delay });
+                    Some(delay)
+                }
+                (false, None) => None,
+            };
+            if let Some(delay) = delay {
+                if !sleep(delay, &mut shutdown).await {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    async fn fetch(&mut self, url: &str) -> crate::Result<Response> {
+        let authorization = self.authorizer.authorization(&self.client).await?;
+        let request = Request::get(url)
+            .header(header::ACCEPT, "application/json")
+            .header(header::AUTHORIZATION, authorization)
+            .body(Body::empty())?;
+
+        let response = self.client.send(request).await?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        let rate_limit_delay = rate_limit_delay(&parts.headers, Utc::now());
+
+        match parts.status {
+            status if status.is_success() => {}
+            StatusCode::TOO_MANY_REQUESTS => {
+                return Ok(Response::RateLimited(
+                    rate_limit_delay.unwrap_or(MAX_BACKOFF),
+                ));
+            }
+            status => {
+                if status == StatusCode::UNAUTHORIZED {
+                    // The access token may have been revoked before it expired.
+                    self.authorizer.invalidate();
+                }
+                return Err(format!(
+                    "Okta responded with {}: {}",
+                    status,
+                    String::from_utf8_lossy(&body).trim()
+                )
+                .into());
+            }
+        }
+        self.bytes_received.emit(ByteSize(body.len()));
+
+        let events = serde_json::from_slice(&body).unwrap_or_else(|error| {
+            emit!(OktaParseError { error });
+            Vec::new()
+        });
+        Ok(Response::Page(Page {
+            events,
+            next: next_link(&parts.headers),
+            rate_limit_delay,
+        }))
+    }
+
+    /// Sends the events of a page, returning whether they were delivered.
+    async fn send(
+        &mut self,
+        events: Vec<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<bool, ()> {
+        let count = events.len();
+        if count == 0 {
+            return Ok(true);
+        }
+        let mut events = events
+            .into_iter()
+            .map(|event| Event::from(create_event(event, self.log_namespace)))
+            .collect::<Vec<_>>();
+        self.events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+        self.out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })?;
+
+        Ok(match receiver {
+            Some(receiver) => receiver.await == BatchStatus::Delivered,
+            None => true,
+        })
+    }
+}
+
+/// Creates the event of a System Log event, timestamped with the time it was published.
+fn create_event(
+    event: serde_json::Map<String, serde_json::Value>,
+    log_namespace: LogNamespace,
+) -> LogEvent {
+    let now = Utc::now();
+    let published = event
+        .get("published")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|published| DateTime::parse_from_rfc3339(published).ok())
+        .map_or(now, |published| published.with_timezone(&Utc));
+
+    let mut log = log_namespace.new_log_from_data(Value::from(serde_json::Value::Object(event)));
+    log_namespace.insert_source_metadata(
+        OktaConfig::NAME,
+        &mut log,
+        log_schema().timestamp_key().map(LegacyKey::Overwrite),
+        path!("timestamp"),
+        published,
+    );
+    log_namespace.insert_standard_vector_source_metadata(&mut log, OktaConfig::NAME, now);
+
+    log
+}
+
+/// Finds the URL of the next page in the `Link` headers of a response.
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let (target, params) = link.trim().split_once(';')?;
+            params
+                .split(';')
+                .any(|param| param.trim().replace('"', "") == "rel=next")
+                .then(|| target.trim().trim_start_matches('<').trim_end_matches('>'))
+                .map(str::to_owned)
+        })
+}
+
+/// How long to wait for the rate limit to reset, if no request is remaining until then.
+///
+/// Okta sends the number of requests remaining in the `X-Rate-Limit-Remaining` header, and the
+/// time the limit resets at in the `X-Rate-Limit-Reset` header, in seconds since the epoch.
+fn rate_limit_delay(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<i64>().ok())
+    };
+    if header("x-rate-limit-remaining")? > 0 {
+        return None;
+    }
+    let reset = Utc
+        .timestamp_opt(header("x-rate-limit-reset")?, 0)
+        .single()?;
+    // A second is added, as the reset time is rounded down to the second.
+    Some(
+        (reset - now)
+            .to_std()
+            .unwrap_or_default()
+            .saturating_add(Duration::from_secs(1)),
+    )
+}
+
+/// Sleeps for `delay`, returning `false` if the source shut down first.
+async fn sleep(delay: Duration, shutdown: &mut ShutdownSignal) -> bool {
+    tokio::select! {
+        _ = time::sleep(delay) => true,
+        _ = shutdown => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+    use vrl::value;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OktaConfig>();
+    }
+
+    #[test]
+    fn start_url_with_backfill_and_filter() {
+        let config = toml::from_str::<OktaConfig>(
+            r#"
+            endpoint = "https://example.okta.com"
+            auth.strategy = "api_token"
+            auth.token = "token"
+            filter = 'eventType eq "user.session.start"'
+            backfill_secs = 3600
+            page_size = 100
+            "#,
+        )
+        .unwrap();
+        let now = Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap();
+
+        let url = Url::parse(&config.start_url("https://example.okta.com", now).unwrap()).unwrap();
+        assert_eq!(url.path(), "/api/v1/logs");
+        assert_eq!(
+            url.query_pairs().into_owned().collect::<Vec<_>>(),
+            [
+                ("since".to_owned(), "2023-06-01T11:00:00.000Z".to_owned()),
+                ("sortOrder".to_owned(), "ASCENDING".to_owned()),
+                ("limit".to_owned(), "100".to_owned()),
+                (
+                    "filter".to_owned(),
+                    "eventType eq \"user.session.start\"".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_the_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            header::LINK,
+            HeaderValue::from_static(
+                "<https://example.okta.com/api/v1/logs?limit=2>; rel=\"self\"",
+            ),
+        );
+        headers.append(
+            header::LINK,
+            HeaderValue::from_static(
+                "<https://example.okta.com/api/v1/logs?limit=2&after=1685620800000_1>; rel=\"next\"",
+            ),
+        );
+        assert_eq!(
+            next_link(&headers).as_deref(),
+            Some("https://example.okta.com/api/v1/logs?limit=2&after=1685620800000_1")
+        );
+
+        headers.remove(header::LINK);
+        assert_eq!(next_link(&headers), None);
+    }
+
+    #[test]
+    fn waits_for_the_rate_limit_to_reset() {
+        let now = Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap();
+        let headers = |remaining: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-rate-limit-limit", HeaderValue::from_static("120"));
+            headers.insert(
+                "x-rate-limit-remaining",
+                HeaderValue::from_static(remaining),
+            );
+            headers.insert(
+                "x-rate-limit-reset",
+                HeaderValue::from_str(&(now.timestamp() + 30).to_string()).unwrap(),
+            );
+            headers
+        };
+
+        assert_eq!(rate_limit_delay(&headers("12"), now), None);
+        assert_eq!(
+            rate_limit_delay(&headers("0"), now),
+            Some(Duration::from_secs(31))
+        );
+        assert_eq!(rate_limit_delay(&HeaderMap::new(), now), None);
+    }
+
+    #[test]
+    fn create_legacy_event() {
+        let event = serde_json::json!({
+            "uuid": "dc9fd3c0-598c-11ea-a2c0-0b8d1a2a5a8d",
+            "published": "2023-06-01T12:00:00.123Z",
+            "eventType": "user.session.start",
+            "actor": {"alternateId": "jane.doe@example.com"},
+        });
+        let serde_json::Value::Object(event) = event else {
+            unreachable!()
+        };
+
+        let log = create_event(event, LogNamespace::Legacy);
+
+        assert_eq!(log["eventType"], "user.session.start".into());
+        assert_eq!(
+            log["actor"],
+            value!({"alternateId": "jane.doe@example.com"})
+        );
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp_millis_opt(1_685_620_800_123).unwrap().into()
+        );
+        assert_eq!(log["source_type"], "okta".into());
+    }
+
+    #[test]
+    fn create_vector_event() {
+        let event = serde_json::json!({
+            "published": "2023-06-01T12:00:00Z",
+            "eventType": "user.session.start",
+        });
+        let serde_json::Value::Object(event) = event else {
+            unreachable!()
+        };
+
+        let log = create_event(event, LogNamespace::Vector);
+
+        assert_eq!(log["eventType"], "user.session.start".into());
+        assert_eq!(
+            log.metadata().value().get(path!("okta", "timestamp")),
+            Some(&Value::from(
+                Utc.with_ymd_and_hms(2023, 6, 1, 12, 0, 0).unwrap()
+            ))
+        );
+    }
+}
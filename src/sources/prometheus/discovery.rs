@@ -0,0 +1,514 @@
+//! Discovery of scrape targets from the Kubernetes API.
+
+use std::{path::PathBuf, time::Duration};
+
+use k8s_openapi::{
+    api::core::v1::{Endpoints, Pod, Service},
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+};
+use kube::{
+    api::ListParams,
+    config::{self, KubeConfigOptions},
+    Api, Client, Config as ClientConfig,
+};
+use serde::de::DeserializeOwned;
+use serde_with::serde_as;
+use vector_common::shutdown::ShutdownSignal;
+use vector_config::configurable_component;
+
+use super::relabel::Labels;
+
+/// The kind of Kubernetes objects to discover scrape targets from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum KubernetesRole {
+    /// Discover a target for each declared port of each container of each Pod.
+    ///
+    /// Containers without ports are discovered as a single target with the Pod IP as address.
+    #[default]
+    Pod,
+
+    /// Discover a target for each port of each Service, with the DNS name of the Service as address.
+    Service,
+
+    /// Discover a target for each port of each address of each Endpoints object.
+    Endpoints,
+}
+
+/// Configuration for discovering scrape targets from the Kubernetes API.
+///
+/// Discovered targets have `__address__`, `__scheme__`, and `__metrics_path__` labels, along with
+/// `__meta_kubernetes_*` labels describing the object they were discovered from, following the
+/// naming of Prometheus' [`kubernetes_sd_config`][kubernetes_sd_config]. These labels are usually
+/// combined with `relabel_configs` to select targets and set their tags.
+///
+/// [kubernetes_sd_config]: https://prometheus.io/docs/prometheus/latest/configuration/configuration/#kubernetes_sd_config
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesSdConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub role: KubernetesRole,
+
+    /// The Namespaces to discover targets in.
+    ///
+    /// If empty, targets are discovered in all Namespaces.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "monitoring"))]
+    pub namespaces: Vec<String>,
+
+    /// A label selector restricting the discovered objects.
+    #[configurable(metadata(docs::examples = "app.kubernetes.io/part-of=shop"))]
+    pub label_selector: Option<String>,
+
+    /// A field selector restricting the discovered objects.
+    #[configurable(metadata(docs::examples = "status.phase=Running"))]
+    pub field_selector: Option<String>,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    pub kube_config_file: Option<PathBuf>,
+
+    /// The interval between refreshes of the discovered targets, in seconds.
+    #[serde(default = "default_refresh_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "refresh_interval_secs")]
+    #[configurable(metadata(docs::human_name = "Refresh Interval"))]
+    pub refresh_interval: Duration,
+}
+
+const fn default_refresh_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+impl KubernetesSdConfig {
+    pub(super) async fn client(&self) -> crate::Result<Client> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        Ok(Client::try_from(client_config)?)
+    }
+
+    /// Lists the targets of all configured Namespaces.
+    async fn discover(&self, client: &Client) -> Result<Vec<Labels>, kube::Error> {
+        let mut params = ListParams::default();
+        if let Some(selector) = &self.label_selector {
+            params = params.labels(selector);
+        }
+        if let Some(selector) = &self.field_selector {
+            params = params.fields(selector);
+        }
+
+        let mut targets = Vec::new();
+        match self.role {
+            KubernetesRole::Pod => {
+                for pod in self.list::<Pod>(client, &params).await? {
+                    targets.extend(pod_targets(&pod));
+                }
+            }
+            KubernetesRole::Service => {
+                for service in self.list::<Service>(client, &params).await? {
+                    targets.extend(service_targets(&service));
+                }
+            }
+            KubernetesRole::Endpoints => {
+                for endpoints in self.list::<Endpoints>(client, &params).await? {
+                    targets.extend(endpoints_targets(&endpoints));
+                }
+            }
+        }
+        Ok(targets)
+    }
+
+    async fn list<K>(&self, client: &Client, params: &ListParams) -> Result<Vec<K>, kube::Error>
+    where
+        K: kube::Resource<Scope = k8s_openapi::NamespaceResourceScope>
+            + Clone
+            + DeserializeOwned
+            + std::fmt::Debug,
+        K::DynamicType: Default,
+    {
+        if self.namespaces.is_empty() {
+            return Ok(Api::<K>::all(client.clone()).list(params).await?.items);
+        }
+
+        let mut items = Vec::new();
+        for namespace in &self.namespaces {
+            let api = Api::<K>::namespaced(client.clone(), namespace);
+            items.extend(api.list(params).await?.items);
+        }
+        Ok(items)
+    }
+
+    /// Refreshes the discovered targets at each interval, until shutdown.
+    ///
+    /// Targets are kept as they were when a refresh fails.
+    pub(super) async fn run(
+        self,
+        client: Client,
+        mut update: impl FnMut(Vec<Labels>) + Send,
+        shutdown: ShutdownSignal,
+    ) {
+        let mut interval = tokio::time::interval(self.refresh_interval);
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+
+            match self.discover(&client).await {
+                Ok(targets) => {
+                    debug!(
+                        message = "Discovered scrape targets.",
+                        count = targets.len()
+                    );
+                    update(targets);
+                }
+                Err(error) => {
+                    warn!(message = "Failed to discover scrape targets from Kubernetes.", %error);
+                }
+            }
+        }
+    }
+}
+
+/// Replaces the characters that are invalid in label names with `_`.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Returns the labels common to all targets, and the labels describing the metadata of an object.
+fn meta_labels(kind: &str, metadata: &ObjectMeta) -> Labels {
+    let mut labels = Labels::new();
+    labels.insert("__scheme__".into(), "http".into());
+    labels.insert("__metrics_path__".into(), "/metrics".into());
+    labels.insert(
+        "__meta_kubernetes_namespace".into(),
+        metadata.namespace.clone().unwrap_or_default(),
+    );
+    labels.insert(
+        format!("__meta_kubernetes_{}_name", kind),
+        metadata.name.clone().unwrap_or_default(),
+    );
+
+    let tables = [
+        ("label", metadata.labels.as_ref()),
+        ("annotation", metadata.annotations.as_ref()),
+    ];
+    for (table, values) in tables {
+        for (name, value) in values.into_iter().flatten() {
+            let name = sanitize(name);
+            labels.insert(
+                format!("__meta_kubernetes_{}_{}_{}", kind, table, name),
+                value.clone(),
+            );
+            labels.insert(
+                format!("__meta_kubernetes_{}_{}present_{}", kind, table, name),
+                "true".into(),
+            );
+        }
+    }
+    labels
+}
+
+fn pod_targets(pod: &Pod) -> Vec<Labels> {
+    let status = pod.status.as_ref();
+    let Some(ip) = status.and_then(|status| status.pod_ip.as_deref()) else {
+        return Vec::new();
+    };
+
+    let mut base = meta_labels("pod", &pod.metadata);
+    base.insert("__meta_kubernetes_pod_ip".into(), ip.to_owned());
+    if let Some(node) = pod.spec.as_ref().and_then(|spec| spec.node_name.clone()) {
+        base.insert("__meta_kubernetes_pod_node_name".into(), node);
+    }
+    if let Some(phase) = status.and_then(|status| status.phase.clone()) {
+        base.insert("__meta_kubernetes_pod_phase".into(), phase);
+    }
+    let ready = status
+        .and_then(|status| status.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .any(|condition| condition.type_ == "Ready" && condition.status == "True");
+    base.insert("__meta_kubernetes_pod_ready".into(), ready.to_string());
+
+    let containers = pod.spec.as_ref().map(|spec| spec.containers.as_slice());
+    let mut targets = Vec::new();
+    for container in containers.unwrap_or_default() {
+        let mut labels = base.clone();
+        labels.insert(
+            "__meta_kubernetes_pod_container_name".into(),
+            container.name.clone(),
+        );
+
+        let ports = container.ports.as_deref().unwrap_or_default();
+        if ports.is_empty() {
+            labels.insert("__address__".into(), ip.to_owned());
+            targets.push(labels);
+            continue;
+        }
+        for port in ports {
+            let mut labels = labels.clone();
+            labels.insert(
+                "__address__".into(),
+                format!("{}:{}", ip, port.container_port),
+            );
+            labels.insert(
+                "__meta_kubernetes_pod_container_port_name".into(),
+                port.name.clone().unwrap_or_default(),
+            );
+            labels.insert(
+                "__meta_kubernetes_pod_container_port_number".into(),
+                port.container_port.to_string(),
+            );
+            labels.insert(
+                "__meta_kubernetes_pod_container_port_protocol".into(),
+                port.protocol.clone().unwrap_or_else(|| "TCP".into()),
+            );
+            targets.push(labels);
+        }
+    }
+    targets
+}
+
+fn service_targets(service: &Service) -> Vec<Labels> {
+    let mut base = meta_labels("service", &service.metadata);
+    let spec = service.spec.as_ref();
+    if let Some(type_) = spec.and_then(|spec| spec.type_.clone()) {
+        base.insert("__meta_kubernetes_service_type".into(), type_);
+    }
+    if let Some(ip) = spec.and_then(|spec| spec.cluster_ip.clone()) {
+        base.insert("__meta_kubernetes_service_cluster_ip".into(), ip);
+    }
+
+    let name = service.metadata.name.as_deref().unwrap_or_default();
+    let namespace = service.metadata.namespace.as_deref().unwrap_or_default();
+    spec.and_then(|spec| spec.ports.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|port| {
+            let mut labels = base.clone();
+            labels.insert(
+                "__address__".into(),
+                format!("{}.{}.svc:{}", name, namespace, port.port),
+            );
+            labels.insert(
+                "__meta_kubernetes_service_port_name".into(),
+                port.name.clone().unwrap_or_default(),
+            );
+            labels.insert(
+                "__meta_kubernetes_service_port_number".into(),
+                port.port.to_string(),
+            );
+            labels.insert(
+                "__meta_kubernetes_service_port_protocol".into(),
+                port.protocol.clone().unwrap_or_else(|| "TCP".into()),
+            );
+            labels
+        })
+        .collect()
+}
+
+fn endpoints_targets(endpoints: &Endpoints) -> Vec<Labels> {
+    let mut base = meta_labels("endpoints", &endpoints.metadata);
+    base.insert(
+        "__meta_kubernetes_service_name".into(),
+        endpoints.metadata.name.clone().unwrap_or_default(),
+    );
+
+    let mut targets = Vec::new();
+    for subset in endpoints.subsets.iter().flatten() {
+        let addresses = subset
+            .addresses
+            .iter()
+            .flatten()
+            .map(|address| (address, true))
+            .chain(
+                subset
+                    .not_ready_addresses
+                    .iter()
+                    .flatten()
+                    .map(|address| (address, false)),
+            );
+        for (address, ready) in addresses {
+            for port in subset.ports.iter().flatten() {
+                let mut labels = base.clone();
+                labels.insert(
+                    "__address__".into(),
+                    format!("{}:{}", address.ip, port.port),
+                );
+                labels.insert("__meta_kubernetes_endpoint_ready".into(), ready.to_string());
+                labels.insert(
+                    "__meta_kubernetes_endpoint_port_name".into(),
+                    port.name.clone().unwrap_or_default(),
+                );
+                labels.insert(
+                    "__meta_kubernetes_endpoint_port_protocol".into(),
+                    port.protocol.clone().unwrap_or_else(|| "TCP".into()),
+                );
+                if let Some(hostname) = &address.hostname {
+                    labels.insert(
+                        "__meta_kubernetes_endpoint_hostname".into(),
+                        hostname.clone(),
+                    );
+                }
+                if let Some(node) = &address.node_name {
+                    labels.insert("__meta_kubernetes_endpoint_node_name".into(), node.clone());
+                }
+                if let Some(target) = &address.target_ref {
+                    labels.insert(
+                        "__meta_kubernetes_endpoint_address_target_kind".into(),
+                        target.kind.clone().unwrap_or_default(),
+                    );
+                    labels.insert(
+                        "__meta_kubernetes_endpoint_address_target_name".into(),
+                        target.name.clone().unwrap_or_default(),
+                    );
+                }
+                targets.push(labels);
+            }
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::{
+        Container, ContainerPort, EndpointAddress, EndpointPort, EndpointSubset, PodSpec,
+        PodStatus, ServicePort, ServiceSpec,
+    };
+
+    use super::*;
+
+    fn metadata(name: &str) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_owned()),
+            namespace: Some("shop".to_owned()),
+            labels: Some([("app.kubernetes.io/name".to_owned(), "api".to_owned())].into()),
+            ..ObjectMeta::default()
+        }
+    }
+
+    #[test]
+    fn discovers_pod_container_ports() {
+        let pod = Pod {
+            metadata: metadata("api-0"),
+            spec: Some(PodSpec {
+                containers: vec![
+                    Container {
+                        name: "api".to_owned(),
+                        ports: Some(vec![ContainerPort {
+                            name: Some("metrics".to_owned()),
+                            container_port: 9100,
+                            ..ContainerPort::default()
+                        }]),
+                        ..Container::default()
+                    },
+                    Container {
+                        name: "sidecar".to_owned(),
+                        ..Container::default()
+                    },
+                ],
+                ..PodSpec::default()
+            }),
+            status: Some(PodStatus {
+                pod_ip: Some("10.0.0.7".to_owned()),
+                phase: Some("Running".to_owned()),
+                ..PodStatus::default()
+            }),
+        };
+
+        let targets = pod_targets(&pod);
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0]["__address__"], "10.0.0.7:9100");
+        assert_eq!(
+            targets[0]["__meta_kubernetes_pod_container_port_name"],
+            "metrics"
+        );
+        assert_eq!(
+            targets[0]["__meta_kubernetes_pod_label_app_kubernetes_io_name"],
+            "api"
+        );
+        assert_eq!(targets[0]["__meta_kubernetes_pod_ready"], "false");
+        assert_eq!(targets[1]["__address__"], "10.0.0.7");
+        assert_eq!(
+            targets[1]["__meta_kubernetes_pod_container_name"],
+            "sidecar"
+        );
+
+        let pending = Pod {
+            status: None,
+            ..pod
+        };
+        assert!(pod_targets(&pending).is_empty());
+    }
+
+    #[test]
+    fn discovers_service_ports() {
+        let service = Service {
+            metadata: metadata("api"),
+            spec: Some(ServiceSpec {
+                ports: Some(vec![ServicePort {
+                    name: Some("http".to_owned()),
+                    port: 80,
+                    ..ServicePort::default()
+                }]),
+                ..ServiceSpec::default()
+            }),
+            ..Service::default()
+        };
+
+        let targets = service_targets(&service);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0]["__address__"], "api.shop.svc:80");
+        assert_eq!(targets[0]["__meta_kubernetes_service_name"], "api");
+        assert_eq!(targets[0]["__meta_kubernetes_service_port_protocol"], "TCP");
+    }
+
+    #[test]
+    fn discovers_endpoint_addresses() {
+        let endpoints = Endpoints {
+            metadata: metadata("api"),
+            subsets: Some(vec![EndpointSubset {
+                addresses: Some(vec![EndpointAddress {
+                    ip: "10.0.0.7".to_owned(),
+                    ..EndpointAddress::default()
+                }]),
+                not_ready_addresses: Some(vec![EndpointAddress {
+                    ip: "10.0.0.8".to_owned(),
+                    ..EndpointAddress::default()
+                }]),
+                ports: Some(vec![EndpointPort {
+                    name: Some("metrics".to_owned()),
+                    port: 9100,
+                    ..EndpointPort::default()
+                }]),
+            }]),
+        };
+
+        let targets = endpoints_targets(&endpoints);
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0]["__address__"], "10.0.0.7:9100");
+        assert_eq!(targets[0]["__meta_kubernetes_endpoint_ready"], "true");
+        assert_eq!(targets[1]["__meta_kubernetes_endpoint_ready"], "false");
+        assert_eq!(targets[1]["__meta_kubernetes_service_name"], "api");
+    }
+}
@@ -1,4 +1,6 @@
+mod discovery;
 pub(crate) mod parser;
+mod relabel;
 mod remote_write;
 mod scrape;
 
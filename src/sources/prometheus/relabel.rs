@@ -0,0 +1,311 @@
+//! Prometheus-style relabeling of scrape targets.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+/// The labels of a scrape target.
+pub(super) type Labels = BTreeMap<String, String>;
+
+#[derive(Debug, Snafu)]
+pub(super) enum RelabelError {
+    #[snafu(display("Invalid relabeling regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+
+    #[snafu(display("Relabeling action `{}` requires `target_label` to be set", action))]
+    MissingTargetLabel { action: &'static str },
+}
+
+/// The action of a relabeling step.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Set `target_label` to `replacement` if `regex` matches the joined `source_labels`.
+    ///
+    /// Capture groups of `regex` can be referenced in `replacement`, such as `$1`.
+    #[default]
+    Replace,
+
+    /// Drop the target if `regex` doesn't match the joined `source_labels`.
+    Keep,
+
+    /// Drop the target if `regex` matches the joined `source_labels`.
+    Drop,
+
+    /// Copy the labels whose name matches `regex` to the label named by `replacement`.
+    Labelmap,
+
+    /// Remove the labels whose name matches `regex`.
+    Labeldrop,
+
+    /// Remove the labels whose name doesn't match `regex`.
+    Labelkeep,
+
+    /// Set `target_label` to the lowercased joined `source_labels`.
+    Lowercase,
+
+    /// Set `target_label` to the uppercased joined `source_labels`.
+    Uppercase,
+}
+
+impl RelabelAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Keep => "keep",
+            Self::Drop => "drop",
+            Self::Labelmap => "labelmap",
+            Self::Labeldrop => "labeldrop",
+            Self::Labelkeep => "labelkeep",
+            Self::Lowercase => "lowercase",
+            Self::Uppercase => "uppercase",
+        }
+    }
+}
+
+/// A relabeling step, applied to the labels of scrape targets.
+///
+/// This follows the semantics of Prometheus' [`relabel_config`][relabel_config].
+///
+/// [relabel_config]: https://prometheus.io/docs/prometheus/latest/configuration/configuration/#relabel_config
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The labels whose values are joined with `separator` and matched against `regex`.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::examples = "__meta_kubernetes_pod_annotation_prometheus_io_scrape"
+    ))]
+    pub source_labels: Vec<String>,
+
+    /// The separator placed between the values of `source_labels`.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+
+    /// The regular expression matched against the joined `source_labels`, or against label names
+    /// for the `labelmap`, `labeldrop`, and `labelkeep` actions.
+    ///
+    /// The regular expression is anchored on both ends.
+    #[serde(default = "default_regex")]
+    #[configurable(metadata(
+        docs::examples = "true",
+        docs::examples = "__meta_kubernetes_pod_label_(.+)"
+    ))]
+    pub regex: String,
+
+    /// The label written by the `replace`, `lowercase`, and `uppercase` actions.
+    #[configurable(metadata(docs::examples = "__metrics_path__"))]
+    pub target_label: Option<String>,
+
+    /// The value written by the `replace` and `labelmap` actions.
+    ///
+    /// Capture groups of `regex` can be referenced, such as `$1`.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+/// A compiled relabeling step.
+#[derive(Clone, Debug)]
+pub(super) struct Relabeler {
+    config: RelabelConfig,
+    regex: Regex,
+}
+
+impl Relabeler {
+    pub(super) fn new(config: &RelabelConfig) -> Result<Self, RelabelError> {
+        let needs_target = matches!(
+            config.action,
+            RelabelAction::Replace | RelabelAction::Lowercase | RelabelAction::Uppercase
+        );
+        if needs_target && config.target_label.is_none() {
+            return Err(RelabelError::MissingTargetLabel {
+                action: config.action.as_str(),
+            });
+        }
+
+        let regex = Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu {
+            regex: config.regex.clone(),
+        })?;
+        Ok(Self {
+            config: config.clone(),
+            regex,
+        })
+    }
+
+    /// Applies the step to the labels, returning `false` if the target must be dropped.
+    fn apply(&self, labels: &mut Labels) -> bool {
+        let config = &self.config;
+        let value = || {
+            config
+                .source_labels
+                .iter()
+                .map(|name| labels.get(name).map(String::as_str).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(&config.separator)
+        };
+        let target = config.target_label.clone().unwrap_or_default();
+
+        match config.action {
+            RelabelAction::Replace => {
+                let value = value();
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut replacement = String::new();
+                    captures.expand(&config.replacement, &mut replacement);
+                    if replacement.is_empty() {
+                        labels.remove(&target);
+                    } else {
+                        labels.insert(target, replacement);
+                    }
+                }
+            }
+            RelabelAction::Keep => return self.regex.is_match(&value()),
+            RelabelAction::Drop => return !self.regex.is_match(&value()),
+            RelabelAction::Labelmap => {
+                let mapped = labels
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        let captures = self.regex.captures(name)?;
+                        let mut name = String::new();
+                        captures.expand(&config.replacement, &mut name);
+                        Some((name, value.clone()))
+                    })
+                    .collect::<Vec<_>>();
+                labels.extend(mapped);
+            }
+            RelabelAction::Labeldrop => labels.retain(|name, _| !self.regex.is_match(name)),
+            RelabelAction::Labelkeep => labels.retain(|name, _| self.regex.is_match(name)),
+            RelabelAction::Lowercase => {
+                let value = value().to_lowercase();
+                labels.insert(target, value);
+            }
+            RelabelAction::Uppercase => {
+                let value = value().to_uppercase();
+                labels.insert(target, value);
+            }
+        }
+        true
+    }
+}
+
+/// Applies relabeling steps in order, returning `None` if the target is dropped.
+pub(super) fn relabel(relabelers: &[Relabeler], mut labels: Labels) -> Option<Labels> {
+    relabelers
+        .iter()
+        .all(|relabeler| relabeler.apply(&mut labels))
+        .then_some(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relabeler(config: &str) -> Relabeler {
+        Relabeler::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_and_drops_targets() {
+        let keep = relabeler(
+            r#"
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+            regex = "true"
+            action = "keep"
+            "#,
+        );
+        let scraped = labels(&[(
+            "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+            "true",
+        )]);
+        assert!(relabel(&[keep.clone()], scraped).is_some());
+        assert!(relabel(&[keep], labels(&[])).is_none());
+
+        let drop = relabeler(
+            r#"
+            source_labels = ["namespace"]
+            regex = "kube-.*"
+            action = "drop"
+            "#,
+        );
+        assert!(relabel(&[drop.clone()], labels(&[("namespace", "kube-system")])).is_none());
+        assert!(relabel(&[drop], labels(&[("namespace", "default")])).is_some());
+    }
+
+    #[test]
+    fn replaces_with_captures() {
+        let replace = relabeler(
+            r#"
+            source_labels = ["__address__", "__meta_port"]
+            regex = "([^:]+)(?::\\d+)?;(\\d+)"
+            replacement = "$1:$2"
+            target_label = "__address__"
+            "#,
+        );
+        let relabeled = relabel(
+            &[replace],
+            labels(&[("__address__", "10.0.0.1:80"), ("__meta_port", "9100")]),
+        )
+        .unwrap();
+        assert_eq!(relabeled["__address__"], "10.0.0.1:9100");
+    }
+
+    #[test]
+    fn maps_and_filters_label_names() {
+        let relabelers = [
+            relabeler(
+                r#"
+                regex = "__meta_kubernetes_pod_label_(.+)"
+                action = "labelmap"
+                "#,
+            ),
+            relabeler(
+                r#"
+                regex = "tier"
+                action = "labeldrop"
+                "#,
+            ),
+        ];
+        let relabeled = relabel(
+            &relabelers,
+            labels(&[
+                ("__meta_kubernetes_pod_label_app", "api"),
+                ("__meta_kubernetes_pod_label_tier", "web"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(relabeled.get("app").map(String::as_str), Some("api"));
+        assert!(!relabeled.contains_key("tier"));
+    }
+
+    #[test]
+    fn requires_target_label() {
+        let config: RelabelConfig = toml::from_str(r#"source_labels = ["a"]"#).unwrap();
+        assert!(Relabeler::new(&config).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -7,9 +8,16 @@ use http::{response::Parts, Uri};
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
-use vector_core::{config::LogNamespace, event::Event};
+use vector_core::{
+    config::LogNamespace,
+    event::{Event, Metric},
+};
 
-use super::parser;
+use super::{
+    discovery::KubernetesSdConfig,
+    parser,
+    relabel::{relabel, Labels, RelabelConfig, Relabeler},
+};
 use crate::sources::util::http::HttpMethod;
 use crate::{
     config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
@@ -19,7 +27,7 @@ use crate::{
         self,
         util::http_client::{
             build_url, call, default_interval, GenericHttpClientInputs, HttpClientBuilder,
-            HttpClientContext,
+            HttpClientContext, HttpClientUrls,
         },
     },
     tls::{TlsConfig, TlsSettings},
@@ -50,7 +58,7 @@ enum ConfigError {
 pub struct PrometheusScrapeConfig {
     /// Endpoints to scrape metrics from.
     #[configurable(metadata(docs::examples = "http://localhost:9090/metrics"))]
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
 
     /// The interval between scrapes, in seconds.
@@ -77,11 +85,27 @@ pub struct PrometheusScrapeConfig {
     /// If `true`, the new tag is not added if the scraped metric has the tag already. If `false`, the conflicting tag
     /// is renamed by prepending `exported_` to the original name.
     ///
-    /// This matches Prometheus’ `honor_labels` configuration.
+    /// This matches Prometheus’ `honor_labels` configuration, and also applies to the tags set by
+    /// `relabel_configs`.
     #[serde(default = "crate::serde::default_false")]
     #[configurable(metadata(docs::advanced))]
     honor_labels: bool,
 
+    /// Relabeling steps applied to the targets before they are scraped.
+    ///
+    /// Each target starts with an `__address__` label for its `host:port`, a `__scheme__` label, a
+    /// `__metrics_path__` label, and a `__param_<name>` label for each query string parameter of
+    /// its endpoint. Targets discovered by `kubernetes_sd` also have `__meta_kubernetes_*` labels.
+    ///
+    /// After relabeling, the target is scraped at the URL built from these labels, and its labels
+    /// that don't start with `__` are added as tags to the scraped metrics.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    relabel_configs: Vec<RelabelConfig>,
+
+    #[configurable(derived)]
+    kubernetes_sd: Option<KubernetesSdConfig>,
+
     /// Custom parameters for the scrape request query string.
     ///
     /// One or more values for the same parameter key can be provided. The parameters provided in this option are
@@ -117,6 +141,8 @@ impl GenerateConfig for PrometheusScrapeConfig {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::new(),
             tls: None,
             auth: None,
@@ -129,18 +155,59 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[typetag::serde(name = "prometheus_scrape")]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> Result<sources::Source> {
-        let urls = self
+        let endpoints = self
             .endpoints
             .iter()
             .map(|s| s.parse::<Uri>().context(sources::UriParseSnafu))
-            .map(|r| r.map(|uri| build_url(&uri, &self.query)))
             .collect::<std::result::Result<Vec<Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
 
+        let (urls, targets) = if self.relabel_configs.is_empty() && self.kubernetes_sd.is_none() {
+            let urls = endpoints
+                .iter()
+                .map(|uri| build_url(uri, &self.query))
+                .collect::<Vec<_>>();
+            (urls.into(), None)
+        } else {
+            let relabelers = self
+                .relabel_configs
+                .iter()
+                .map(Relabeler::new)
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let targets = Targets::new(
+                endpoints
+                    .iter()
+                    .map(endpoint_labels)
+                    .filter_map(|labels| relabel(&relabelers, labels))
+                    .filter_map(|labels| target(labels, &self.query))
+                    .collect(),
+            );
+
+            if let Some(sd) = &self.kubernetes_sd {
+                let client = sd.client().await?;
+                let (discovered, query) = (targets.clone(), self.query.clone());
+                let update = move |labels: Vec<Labels>| {
+                    discovered.set_discovered(
+                        labels
+                            .into_iter()
+                            .filter_map(|labels| relabel(&relabelers, labels))
+                            .filter_map(|labels| target(labels, &query))
+                            .collect(),
+                    );
+                };
+                tokio::spawn(sd.clone().run(client, update, cx.shutdown.clone()));
+            }
+
+            let urls = targets.clone();
+            let urls = HttpClientUrls::Dynamic(Arc::new(move || urls.urls()));
+            (urls, Some(targets))
+        };
+
         let builder = PrometheusScrapeBuilder {
             honor_labels: self.honor_labels,
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
+            targets,
         };
 
         let inputs = GenericHttpClientInputs {
@@ -166,6 +233,104 @@ impl SourceConfig for PrometheusScrapeConfig {
     }
 }
 
+/// The targets to scrape, with the tags set by relabeling.
+#[derive(Clone)]
+struct Targets {
+    static_targets: Arc<HashMap<Uri, Labels>>,
+    discovered: Arc<RwLock<HashMap<Uri, Labels>>>,
+}
+
+impl Targets {
+    fn new(static_targets: HashMap<Uri, Labels>) -> Self {
+        Self {
+            static_targets: Arc::new(static_targets),
+            discovered: Arc::default(),
+        }
+    }
+
+    fn set_discovered(&self, discovered: HashMap<Uri, Labels>) {
+        *self.discovered.write().expect("lock poisoned") = discovered;
+    }
+
+    fn urls(&self) -> Vec<Uri> {
+        let discovered = self.discovered.read().expect("lock poisoned");
+        self.static_targets
+            .keys()
+            .chain(
+                discovered
+                    .keys()
+                    .filter(|url| !self.static_targets.contains_key(url)),
+            )
+            .cloned()
+            .collect()
+    }
+
+    fn tags(&self, url: &Uri) -> Labels {
+        self.static_targets
+            .get(url)
+            .cloned()
+            .or_else(|| {
+                self.discovered
+                    .read()
+                    .expect("lock poisoned")
+                    .get(url)
+                    .cloned()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the labels of a target configured in `endpoints`, before relabeling.
+fn endpoint_labels(uri: &Uri) -> Labels {
+    let mut labels = Labels::new();
+    labels.insert(
+        "__address__".into(),
+        uri.authority().map(ToString::to_string).unwrap_or_default(),
+    );
+    labels.insert(
+        "__scheme__".into(),
+        uri.scheme_str().unwrap_or("http").to_owned(),
+    );
+    labels.insert("__metrics_path__".into(), uri.path().to_owned());
+    for (name, value) in url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+        labels.insert(format!("__param_{}", name), value.into_owned());
+    }
+    labels
+}
+
+/// Builds the URL of a relabeled target, and the tags it adds to the scraped metrics.
+fn target(labels: Labels, query: &HashMap<String, Vec<String>>) -> Option<(Uri, Labels)> {
+    let address = labels
+        .get("__address__")
+        .filter(|address| !address.is_empty())?;
+    let scheme = labels.get("__scheme__").map_or("http", String::as_str);
+    let path = labels.get("__metrics_path__").map_or("", String::as_str);
+
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    for (name, value) in &labels {
+        if let Some(name) = name.strip_prefix("__param_") {
+            serializer.append_pair(name, value);
+        }
+    }
+    let url = match serializer.finish() {
+        params if params.is_empty() => format!("{}://{}{}", scheme, address, path),
+        params => format!("{}://{}{}?{}", scheme, address, path, params),
+    };
+    let url = match url.parse::<Uri>() {
+        Ok(url) => build_url(&url, query),
+        Err(error) => {
+            warn!(message = "Dropping target with an invalid URL.", %url, %error);
+            return None;
+        }
+    };
+
+    let tags = labels
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with("__"))
+        .collect();
+    Some((url, tags))
+}
+
 // InstanceInfo stores the scraped instance info and the tag to insert into the log event with. It
 // is used to join these two pieces of info to avoid storing the instance if instance_tag is not
 // configured
@@ -192,6 +357,7 @@ struct PrometheusScrapeBuilder {
     honor_labels: bool,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
+    targets: Option<Targets>,
 }
 
 impl HttpClientBuilder for PrometheusScrapeBuilder {
@@ -223,6 +389,12 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
         PrometheusScrapeContext {
             instance_info,
             endpoint_info,
+            target_tags: self
+                .targets
+                .as_ref()
+                .map(|targets| targets.tags(url))
+                .unwrap_or_default(),
+            honor_labels: self.honor_labels,
         }
     }
 }
@@ -231,28 +403,38 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
 struct PrometheusScrapeContext {
     instance_info: Option<InstanceInfo>,
     endpoint_info: Option<EndpointInfo>,
+    target_tags: Labels,
+    honor_labels: bool,
+}
+
+/// Adds a tag to a scraped metric, handling conflicts per the `honor_labels` option.
+fn add_tag(metric: &mut Metric, tag: &str, value: &str, honor_label: bool) {
+    match (honor_label, metric.tag_value(tag)) {
+        (false, Some(old_value)) => {
+            metric.replace_tag(format!("exported_{}", tag), old_value);
+            metric.replace_tag(tag.to_owned(), value.to_owned());
+        }
+        (true, Some(_)) => {}
+        (_, None) => {
+            metric.replace_tag(tag.to_owned(), value.to_owned());
+        }
+    }
 }
 
 impl HttpClientContext for PrometheusScrapeContext {
     fn enrich_events(&mut self, events: &mut Vec<Event>) {
         for event in events.iter_mut() {
             let metric = event.as_mut_metric();
+            for (tag, value) in &self.target_tags {
+                add_tag(metric, tag, value, self.honor_labels);
+            }
             if let Some(InstanceInfo {
                 tag,
                 instance,
                 honor_label,
             }) = &self.instance_info
             {
-                match (honor_label, metric.tag_value(tag)) {
-                    (false, Some(old_instance)) => {
-                        metric.replace_tag(format!("exported_{}", tag), old_instance);
-                        metric.replace_tag(tag.clone(), instance.clone());
-                    }
-                    (true, Some(_)) => {}
-                    (_, None) => {
-                        metric.replace_tag(tag.clone(), instance.clone());
-                    }
-                }
+                add_tag(metric, tag, instance, *honor_label);
             }
             if let Some(EndpointInfo {
                 tag,
@@ -260,16 +442,7 @@ impl HttpClientContext for PrometheusScrapeContext {
                 honor_label,
             }) = &self.endpoint_info
             {
-                match (honor_label, metric.tag_value(tag)) {
-                    (false, Some(old_endpoint)) => {
-                        metric.replace_tag(format!("exported_{}", tag), old_endpoint);
-                        metric.replace_tag(tag.clone(), endpoint.clone());
-                    }
-                    (true, Some(_)) => {}
-                    (_, None) => {
-                        metric.replace_tag(tag.clone(), endpoint.clone());
-                    }
-                }
+                add_tag(metric, tag, endpoint, *honor_label);
             }
         }
     }
@@ -317,6 +490,7 @@ mod test {
     };
     use similar_asserts::assert_eq;
     use tokio::time::{sleep, Duration};
+    use vector_core::metric_tags;
     use warp::Filter;
 
     use super::*;
@@ -335,6 +509,71 @@ mod test {
         crate::test_util::test_generate_config::<PrometheusScrapeConfig>();
     }
 
+    #[test]
+    fn relabels_endpoint_targets() {
+        let relabelers = [
+            toml::from_str::<RelabelConfig>(
+                r#"
+                source_labels = ["__address__"]
+                regex = "([^:]+):\\d+"
+                target_label = "host"
+                "#,
+            )
+            .unwrap(),
+            toml::from_str::<RelabelConfig>(
+                r#"
+                target_label = "__metrics_path__"
+                replacement = "/federate"
+                "#,
+            )
+            .unwrap(),
+        ]
+        .iter()
+        .map(|config| Relabeler::new(config).unwrap())
+        .collect::<Vec<_>>();
+
+        let uri = "http://localhost:9090/metrics?key1=val1".parse().unwrap();
+        let labels = relabel(&relabelers, endpoint_labels(&uri)).unwrap();
+        let query = HashMap::from([("key2".to_string(), vec!["val2".to_string()])]);
+        let (url, tags) = target(labels, &query).unwrap();
+
+        assert_eq!(url, "http://localhost:9090/federate?key1=val1&key2=val2");
+        assert_eq!(
+            tags,
+            Labels::from([("host".to_string(), "localhost".to_string())])
+        );
+    }
+
+    #[test]
+    fn target_tags_follow_honor_labels() {
+        let mut events = vec![Event::Metric(
+            Metric::new(
+                "requests",
+                vector_core::event::MetricKind::Absolute,
+                vector_core::event::MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(metric_tags!("app" => "scraped"))),
+        )];
+        let mut context = PrometheusScrapeContext {
+            instance_info: None,
+            endpoint_info: None,
+            target_tags: Labels::from([
+                ("app".to_string(), "target".to_string()),
+                ("team".to_string(), "shop".to_string()),
+            ]),
+            honor_labels: false,
+        };
+        context.enrich_events(&mut events);
+
+        let metric = events[0].as_metric();
+        assert_eq!(metric.tag_value("app"), Some("target".to_string()));
+        assert_eq!(
+            metric.tag_value("exported_app"),
+            Some("scraped".to_string())
+        );
+        assert_eq!(metric.tag_value("team"), Some("shop".to_string()));
+    }
+
     #[tokio::test]
     async fn test_prometheus_sets_headers() {
         let in_addr = next_addr();
@@ -354,6 +593,8 @@ mod test {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::new(),
             auth: None,
             tls: None,
@@ -387,6 +628,8 @@ mod test {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::new(),
             auth: None,
             tls: None,
@@ -438,6 +681,8 @@ mod test {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::new(),
             auth: None,
             tls: None,
@@ -503,6 +748,8 @@ mod test {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::new(),
             auth: None,
             tls: None,
@@ -558,6 +805,8 @@ mod test {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::from([
                 ("key1".to_string(), vec!["val2".to_string()]),
                 (
@@ -666,6 +915,8 @@ mod test {
                 instance_tag: None,
                 endpoint_tag: None,
                 honor_labels: false,
+                relabel_configs: Vec::new(),
+                kubernetes_sd: None,
                 query: HashMap::new(),
                 interval: Duration::from_secs(1),
                 tls: None,
@@ -756,6 +1007,8 @@ mod integration_tests {
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
+            relabel_configs: Vec::new(),
+            kubernetes_sd: None,
             query: HashMap::new(),
             auth: None,
             tls: None,
@@ -145,6 +145,7 @@ impl SourceConfig for SocketConfig {
                     cx,
                     false.into(),
                     config.connection_limit,
+                    config.connection_limits,
                     SocketConfig::NAME,
                     log_namespace,
                 )
@@ -324,6 +325,7 @@ mod test {
     use std::{
         collections::{BTreeMap, HashMap},
         net::{SocketAddr, UdpSocket},
+        num::NonZeroU32,
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc,
@@ -341,7 +343,7 @@ mod test {
     use tokio::net::TcpStream;
     use tokio::{
         task::JoinHandle,
-        time::{timeout, Duration, Instant},
+        time::{sleep, timeout, Duration, Instant},
     };
     use vector_core::event::EventContainer;
     use vrl::btreemap;
@@ -371,7 +373,7 @@ mod test {
         event::{Event, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
-        sources::util::net::SocketListenAddr,
+        sources::util::net::{SocketListenAddr, TcpConnectionLimitsConfig},
         test_util::{
             collect_n, collect_n_limited,
             components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
@@ -839,6 +841,37 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn tcp_connections_per_ip_limit() {
+        let (tx, _) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut source_config = TcpConfig::from_address(addr.into());
+        source_config.set_connection_limits(TcpConnectionLimitsConfig {
+            max_connections_per_ip: NonZeroU32::new(1),
+            ..Default::default()
+        });
+        let source_task = SocketConfig::from(source_config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+
+        drop(tokio::spawn(source_task));
+        wait_for_tcp(addr).await;
+
+        // `wait_for_tcp` connects once, so wait for that connection to be released.
+        sleep(Duration::from_millis(100)).await;
+        let _first = TcpStream::connect(addr).await.unwrap();
+        sleep(Duration::from_millis(100)).await;
+        let mut second = TcpStream::connect(addr).await.unwrap();
+
+        let mut buffer = [0u8; 10];
+        let read = timeout(Duration::from_secs(1), second.read(&mut buffer))
+            .await
+            .expect("second connection should be closed");
+        assert_eq!(read.unwrap(), 0);
+    }
+
     //////// UDP TESTS ////////
     fn send_lines_udp(addr: SocketAddr, lines: impl IntoIterator<Item = String>) -> SocketAddr {
         let bind = next_addr();
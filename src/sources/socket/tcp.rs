@@ -12,7 +12,7 @@ use crate::{
     codecs::Decoder,
     event::Event,
     serde::default_decoding,
-    sources::util::net::{SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{SocketListenAddr, TcpConnectionLimitsConfig, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::TlsSourceConfig,
 };
@@ -72,9 +72,14 @@ pub struct TcpConfig {
     max_connection_duration_secs: Option<u64>,
 
     /// The maximum number of TCP connections that are allowed at any given time.
+    #[serde(alias = "max_connections")]
     #[configurable(metadata(docs::type_unit = "connections"))]
     pub connection_limit: Option<u32>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub connection_limits: TcpConnectionLimitsConfig,
+
     #[configurable(derived)]
     pub(super) framing: Option<FramingConfig>,
 
@@ -110,6 +115,7 @@ impl TcpConfig {
             framing: None,
             decoding: default_decoding(),
             connection_limit: None,
+            connection_limits: Default::default(),
             log_namespace: None,
         }
     }
@@ -159,6 +165,11 @@ impl TcpConfig {
         self
     }
 
+    pub fn set_connection_limits(&mut self, val: TcpConnectionLimitsConfig) -> &mut Self {
+        self.connection_limits = val;
+        self
+    }
+
     pub fn set_shutdown_timeout_secs(&mut self, val: u64) -> &mut Self {
         self.shutdown_timeout_secs = Duration::from_secs(val);
         self
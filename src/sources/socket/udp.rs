@@ -1,4 +1,3 @@
-use bytes::BytesMut;
 use chrono::Utc;
 use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
@@ -27,7 +26,7 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::{
         socket::SocketConfig,
-        util::net::{try_bind_udp_socket, SocketListenAddr},
+        util::net::{try_bind_udp_socket, SocketListenAddr, UdpBatchReceiver},
         Source,
     },
     SourceSender,
@@ -174,119 +173,110 @@ pub(super) fn udp(
 
         info!(message = "Listening.", address = %config.address);
 
-        // We add 1 to the max_length in order to determine if the received data has been truncated.
-        let mut buf = BytesMut::with_capacity(max_length + 1);
+        let mut receiver = UdpBatchReceiver::new(socket, max_length);
         loop {
-            buf.resize(max_length + 1, 0);
-            tokio::select! {
-                recv = socket.recv_from(&mut buf) => {
-                    let (byte_size, address) = match recv {
-                        Ok(res) => res,
-                        Err(error) => {
-                            #[cfg(windows)]
-                            if let Some(err) = error.raw_os_error() {
-                                if err == 10040 {
-                                    // 10040 is the Windows error that the Udp message has exceeded max_length
-                                    warn!(
-                                        message = "Discarding frame larger than max_length.",
-                                        max_length = max_length,
-                                        internal_log_rate_limit = true
-                                    );
-                                    continue;
-                                }
+            let datagrams = tokio::select! {
+                recv = receiver.recv_batch() => match recv {
+                    Ok(datagrams) => datagrams,
+                    Err(error) => {
+                        return Err(emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error
+                        }));
+                    }
+                },
+                _ = &mut shutdown => return Ok(()),
+            };
+
+            // The events of all datagrams read at once are sent together.
+            let mut batch = Vec::new();
+            for datagram in datagrams {
+                bytes_received.emit(ByteSize(datagram.payload.len()));
+
+                let mut stream =
+                    FramedRead::new(datagram.payload.as_ref(), decoder.clone()).peekable();
+
+                while let Some(result) = stream.next().await {
+                    let last = Pin::new(&mut stream).peek().await.is_none();
+                    match result {
+                        Ok((mut events, _byte_size)) => {
+                            if last && datagram.truncated {
+                                // The last event in this payload was truncated, so we want to drop it.
+                                _ = events.pop();
+                                warn!(
+                                    message = "Discarding frame larger than max_length.",
+                                    max_length = max_length,
+                                    internal_log_rate_limit = true
+                                );
                             }
 
-                            return Err(emit!(SocketReceiveError {
+                            if events.is_empty() {
+                                continue;
+                            }
+
+                            emit!(SocketEventsReceived {
                                 mode: SocketMode::Udp,
-                                error
-                            }));
-                       }
-                    };
-
-                    bytes_received.emit(ByteSize(byte_size));
-
-                    let payload = buf.split_to(byte_size);
-                    let truncated = byte_size == max_length + 1;
-
-                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone()).peekable();
-
-                    while let Some(result) = stream.next().await {
-                        let last = Pin::new(&mut stream).peek().await.is_none();
-                        match result {
-                            Ok((mut events, _byte_size)) => {
-                                if last && truncated {
-                                    // The last event in this payload was truncated, so we want to drop it.
-                                    _ = events.pop();
-                                    warn!(
-                                        message = "Discarding frame larger than max_length.",
-                                        max_length = max_length,
-                                        internal_log_rate_limit = true
+                                byte_size: events.estimated_json_encoded_size_of(),
+                                count: events.len(),
+                            });
+
+                            let now = Utc::now();
+
+                            for event in &mut events {
+                                if let Event::Log(ref mut log) = event {
+                                    log_namespace.insert_standard_vector_source_metadata(
+                                        log,
+                                        SocketConfig::NAME,
+                                        now,
                                     );
-                                }
 
-                                if events.is_empty() {
-                                    continue;
-                                }
+                                    let legacy_host_key = config.host_key.clone().path;
 
-                                let count = events.len();
-                                emit!(SocketEventsReceived {
-                                    mode: SocketMode::Udp,
-                                    byte_size: events.estimated_json_encoded_size_of(),
-                                    count,
-                                });
-
-                                let now = Utc::now();
-
-                                for event in &mut events {
-                                    if let Event::Log(ref mut log) = event {
-                                        log_namespace.insert_standard_vector_source_metadata(
-                                            log,
-                                            SocketConfig::NAME,
-                                            now,
-                                        );
-
-                                        let legacy_host_key = config.host_key.clone().path;
-
-                                        log_namespace.insert_source_metadata(
-                                            SocketConfig::NAME,
-                                            log,
-                                            legacy_host_key.as_ref().map(LegacyKey::InsertIfEmpty),
-                                            path!("host"),
-                                            address.ip().to_string()
-                                        );
-
-                                        let legacy_port_key = config.port_key.clone().path;
-
-                                        log_namespace.insert_source_metadata(
-                                            SocketConfig::NAME,
-                                            log,
-                                            legacy_port_key.as_ref().map(LegacyKey::InsertIfEmpty),
-                                            path!("port"),
-                                            address.port()
-                                        );
-                                    }
-                                }
+                                    log_namespace.insert_source_metadata(
+                                        SocketConfig::NAME,
+                                        log,
+                                        legacy_host_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                                        path!("host"),
+                                        datagram.address.ip().to_string(),
+                                    );
+
+                                    let legacy_port_key = config.port_key.clone().path;
 
-                                tokio::select!{
-                                    result = out.send_batch(events) => {
-                                        if result.is_err() {
-                                            emit!(StreamClosedError { count });
-                                            return Ok(())
-                                        }
-                                    }
-                                    _ = &mut shutdown => return Ok(()),
+                                    log_namespace.insert_source_metadata(
+                                        SocketConfig::NAME,
+                                        log,
+                                        legacy_port_key.as_ref().map(LegacyKey::InsertIfEmpty),
+                                        path!("port"),
+                                        datagram.address.port(),
+                                    );
                                 }
                             }
-                            Err(error) => {
-                                // Error is logged by `crate::codecs::Decoder`, no
-                                // further handling is needed here.
-                                if !error.can_continue() {
-                                    break;
-                                }
+
+                            batch.extend(events);
+                        }
+                        Err(error) => {
+                            // Error is logged by `crate::codecs::Decoder`, no
+                            // further handling is needed here.
+                            if !error.can_continue() {
+                                break;
                             }
                         }
                     }
                 }
+            }
+
+            if batch.is_empty() {
+                continue;
+            }
+
+            let count = batch.len();
+            tokio::select! {
+                result = out.send_batch(batch) => {
+                    if result.is_err() {
+                        emit!(StreamClosedError { count });
+                        return Ok(())
+                    }
+                }
                 _ = &mut shutdown => return Ok(()),
             }
         }
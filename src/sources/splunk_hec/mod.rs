@@ -1276,6 +1276,7 @@ mod tests {
             endpoint: format!("http://{}", address),
             host_key: "host".to_owned(),
             indexed_fields: vec![],
+            dedupe_id_field: None,
             index: None,
             sourcetype: None,
             source: None,
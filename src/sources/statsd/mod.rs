@@ -6,19 +6,21 @@ use std::{
 use bytes::Bytes;
 use codecs::{
     decoding::{self, Deserializer, Framer},
-    NewlineDelimitedDecoder,
+    NewlineDelimitedDecoder, StreamDecodingError,
 };
 use futures::{StreamExt, TryFutureExt};
 use listenfd::ListenFd;
 use serde_with::serde_as;
 use smallvec::{smallvec, SmallVec};
-use tokio_util::udp::UdpFramed;
+use tokio_util::codec::FramedRead;
 use vector_common::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
 use vector_config::configurable_component;
 use vector_core::EstimatedJsonEncodedSizeOf;
 
 use self::parser::ParseError;
-use super::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource};
+use super::util::net::{
+    try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource, UdpBatchReceiver,
+};
 use crate::{
     codecs::Decoder,
     config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
@@ -69,6 +71,7 @@ pub struct UdpConfig {
     address: SocketListenAddr,
 
     /// The size of the receive buffer used for each connection.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
     receive_buffer_bytes: Option<usize>,
 }
 
@@ -168,6 +171,7 @@ impl SourceConfig for StatsdConfig {
                     cx,
                     false.into(),
                     config.connection_limit,
+                    Default::default(),
                     StatsdConfig::NAME,
                     LogNamespace::Legacy,
                 )
@@ -261,7 +265,7 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 
 async fn statsd_udp(
     config: UdpConfig,
-    shutdown: ShutdownSignal,
+    mut shutdown: ShutdownSignal,
     mut out: SourceSender,
 ) -> Result<(), ()> {
     let listenfd = ListenFd::from_env();
@@ -286,25 +290,48 @@ async fn statsd_udp(
         r#type = "udp"
     );
 
-    let codec = Decoder::new(
+    let decoder = Decoder::new(
         Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
         Deserializer::Boxed(Box::new(StatsdDeserializer::udp())),
     );
-    let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
-    while let Some(frame) = stream.next().await {
-        match frame {
-            Ok(((events, _byte_size), _sock)) => {
-                let count = events.len();
-                if (out.send_batch(events).await).is_err() {
-                    emit!(StreamClosedError { count });
+    // Datagrams are never longer than the buffer of the receiver, so none are truncated.
+    let mut receiver = UdpBatchReceiver::new(socket, usize::MAX);
+    loop {
+        let datagrams = tokio::select! {
+            recv = receiver.recv_batch() => match recv {
+                Ok(datagrams) => datagrams,
+                Err(error) => {
+                    emit!(SocketReceiveError {
+                        mode: SocketMode::Udp,
+                        error
+                    });
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+
+        // The metrics of all datagrams read at once are sent together.
+        let mut events = Vec::new();
+        for datagram in datagrams {
+            let mut frames = FramedRead::new(datagram.payload.as_ref(), decoder.clone());
+            while let Some(frame) = frames.next().await {
+                match frame {
+                    Ok((frame_events, _byte_size)) => events.extend(frame_events),
+                    // Errors are logged by `crate::codecs::Decoder`.
+                    Err(error) if error.can_continue() => {}
+                    Err(_) => break,
                 }
             }
-            Err(error) => {
-                emit!(SocketReceiveError {
-                    mode: SocketMode::Udp,
-                    error
-                });
-            }
+        }
+
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        if out.send_batch(events).await.is_err() {
+            emit!(StreamClosedError { count });
         }
     }
 
@@ -2,20 +2,19 @@
 use std::path::PathBuf;
 use std::{net::SocketAddr, time::Duration};
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use codecs::{
     decoding::{Deserializer, Framer},
     BytesDecoder, OctetCountingDecoder, SyslogDeserializerConfig,
 };
-use futures::StreamExt;
 use listenfd::ListenFd;
 use lookup::{
     lookup_v2::{parse_value_path, OptionalValuePath},
     path, OwnedValuePath, PathPrefix,
 };
 use smallvec::SmallVec;
-use tokio_util::udp::UdpFramed;
+use tokio_util::codec::Decoder as _;
 use vector_config::configurable_component;
 use vector_core::config::{LegacyKey, LogNamespace};
 
@@ -31,7 +30,10 @@ use crate::{
     internal_events::{SocketBindError, SocketMode, SocketReceiveError},
     net,
     shutdown::ShutdownSignal,
-    sources::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{
+        try_bind_udp_socket, SocketListenAddr, TcpConnectionLimitsConfig, TcpNullAcker, TcpSource,
+        UdpBatchReceiver,
+    },
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsSourceConfig},
     SourceSender,
@@ -91,7 +93,12 @@ pub enum Mode {
         receive_buffer_bytes: Option<usize>,
 
         /// The maximum number of TCP connections that are allowed at any given time.
+        #[serde(alias = "max_connections")]
         connection_limit: Option<u32>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        connection_limits: TcpConnectionLimitsConfig,
     },
 
     /// Listen on UDP.
@@ -144,6 +151,7 @@ impl Default for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limits: Default::default(),
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -175,6 +183,7 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                connection_limits,
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
@@ -199,6 +208,7 @@ impl SourceConfig for SyslogConfig {
                     cx,
                     false.into(),
                     connection_limit,
+                    connection_limits,
                     SyslogConfig::NAME,
                     log_namespace,
                 )
@@ -300,10 +310,10 @@ impl TcpSource for SyslogTcpSource {
 
 pub fn udp(
     addr: SocketListenAddr,
-    _max_length: usize,
+    max_length: usize,
     host_key: Option<OwnedValuePath>,
     receive_buffer_bytes: Option<usize>,
-    shutdown: ShutdownSignal,
+    mut shutdown: ShutdownSignal,
     log_namespace: LogNamespace,
     mut out: SourceSender,
 ) -> super::Source {
@@ -328,48 +338,60 @@ pub fn udp(
             r#type = "udp"
         );
 
-        let mut stream = UdpFramed::new(
-            socket,
-            Decoder::new(
-                Framer::Bytes(BytesDecoder::new()),
-                Deserializer::Syslog(
-                    SyslogDeserializerConfig::from_source(SyslogConfig::NAME).build(),
-                ),
-            ),
-        )
-        .take_until(shutdown)
-        .filter_map(|frame| {
-            let host_key = host_key.clone();
-            async move {
-                match frame {
-                    Ok(((mut events, _byte_size), received_from)) => {
-                        let received_from = received_from.ip().to_string().into();
-                        handle_events(&mut events, &host_key, Some(received_from), log_namespace);
-                        Some(events.remove(0))
-                    }
+        let mut decoder = Decoder::new(
+            Framer::Bytes(BytesDecoder::new()),
+            Deserializer::Syslog(SyslogDeserializerConfig::from_source(SyslogConfig::NAME).build()),
+        );
+        let mut receiver = UdpBatchReceiver::new(socket, max_length);
+        loop {
+            let datagrams = tokio::select! {
+                recv = receiver.recv_batch() => match recv {
+                    Ok(datagrams) => datagrams,
                     Err(error) => {
                         emit!(SocketReceiveError {
                             mode: SocketMode::Udp,
                             error: &error,
                         });
-                        None
+                        continue;
                     }
+                },
+                _ = &mut shutdown => break,
+            };
+
+            let mut events = Vec::with_capacity(datagrams.len());
+            for datagram in datagrams {
+                if datagram.truncated {
+                    warn!(
+                        message = "Discarding frame larger than max_length.",
+                        max_length = max_length,
+                        internal_log_rate_limit = true
+                    );
+                    continue;
+                }
+
+                // Errors are logged by `crate::codecs::Decoder`.
+                if let Ok(Some((mut decoded, _byte_size))) =
+                    decoder.decode_eof(&mut BytesMut::from(datagram.payload.as_ref()))
+                {
+                    let received_from = datagram.address.ip().to_string().into();
+                    handle_events(&mut decoded, &host_key, Some(received_from), log_namespace);
+                    events.push(decoded.remove(0));
                 }
             }
-        })
-        .boxed();
 
-        match out.send_event_stream(&mut stream).await {
-            Ok(()) => {
-                debug!("Finished sending.");
-                Ok(())
+            if events.is_empty() {
+                continue;
             }
-            Err(_) => {
-                let (count, _) = stream.size_hint();
+
+            let count = events.len();
+            if out.send_batch(events).await.is_err() {
                 emit!(StreamClosedError { count });
-                Err(())
+                return Err(());
             }
         }
+
+        debug!("Finished sending.");
+        Ok(())
     })
 }
 
@@ -1116,6 +1138,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limits: Default::default(),
             });
 
             let key = ComponentKey::from("in");
@@ -1173,7 +1196,7 @@ mod test {
     #[tokio::test]
     async fn test_unix_stream_syslog() {
         use crate::test_util::components::SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS;
-        use futures_util::{stream, SinkExt};
+        use futures_util::{stream, SinkExt, StreamExt};
         use std::os::unix::net::UnixStream as StdUnixStream;
         use tokio::io::AsyncWriteExt;
         use tokio::net::UnixStream;
@@ -1259,6 +1282,7 @@ mod test {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limits: Default::default(),
             });
 
             let key = ComponentKey::from("in");
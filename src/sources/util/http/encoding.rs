@@ -6,7 +6,7 @@ use snap::raw::Decoder as SnappyDecoder;
 use warp::http::StatusCode;
 
 use super::error::ErrorMessage;
-use crate::internal_events::HttpDecompressError;
+use crate::{internal_events::HttpDecompressError, zstd_dictionary::ZstdDictionaries};
 
 pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
     if let Some(encodings) = header {
@@ -47,6 +47,42 @@ pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMe
     Ok(body)
 }
 
+/// Decodes a payload compressed with the zstd dictionary named by the `dictionary` header, or as
+/// [`decode`] does without that header.
+pub fn decode_with_dictionary(
+    header: &Option<String>,
+    dictionary: Option<&str>,
+    dictionaries: Option<&ZstdDictionaries>,
+    body: Bytes,
+) -> Result<Bytes, ErrorMessage> {
+    let id = match dictionary {
+        Some(id) => id,
+        None => return decode(header, body),
+    };
+
+    if header.as_deref().map(str::trim) != Some("zstd") {
+        return Err(ErrorMessage::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Zstd dictionaries require the zstd encoding".to_owned(),
+        ));
+    }
+    // Clients stop using a dictionary that isn't listed in the response, so the request is
+    // rejected as retriable.
+    let dictionary = dictionaries
+        .and_then(|dictionaries| dictionaries.get(id))
+        .ok_or_else(|| {
+            ErrorMessage::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Unknown zstd dictionary {}", id),
+            )
+        })?;
+
+    dictionary
+        .decompress(&body)
+        .map(Into::into)
+        .map_err(|error| handle_decode_error("zstd", error))
+}
+
 fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMessage {
     emit!(HttpDecompressError {
         encoding,
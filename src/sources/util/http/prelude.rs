@@ -14,7 +14,7 @@ use warp::{
         path::{FullPath, Tail},
         BoxedFilter,
     },
-    http::{HeaderMap, StatusCode},
+    http::{header::HeaderName, HeaderMap, HeaderValue, StatusCode},
     reject::Rejection,
     Filter,
 };
@@ -26,12 +26,13 @@ use crate::{
     },
     sources::util::http::HttpMethod,
     tls::{MaybeTlsSettings, TlsEnableableConfig},
+    zstd_dictionary::{ZstdDictionaries, DICTIONARY_HEADER},
     SourceSender,
 };
 
 use super::{
     auth::{HttpSourceAuth, HttpSourceAuthConfig},
-    encoding::decode,
+    encoding::decode_with_dictionary,
     error::ErrorMessage,
 };
 
@@ -57,6 +58,11 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// The zstd dictionaries that clients may compress their payloads with.
+    fn zstd_dictionaries(&self) -> Option<&ZstdDictionaries> {
+        None
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -74,6 +80,16 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
         let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(acknowledgements);
+
+        // Clients only compress with a dictionary once they see it listed in a response.
+        let mut response_headers = HeaderMap::new();
+        if let Some(dictionaries) = self.zstd_dictionaries().filter(|d| !d.is_empty()) {
+            response_headers.insert(
+                HeaderName::from_static(DICTIONARY_HEADER),
+                HeaderValue::from_str(dictionaries.header_value())?,
+            );
+        }
+
         Ok(Box::pin(async move {
             let span = Span::current();
             let mut filter: BoxedFilter<()> = match method {
@@ -109,6 +125,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 .and(warp::path::full())
                 .and(warp::header::optional::<String>("authorization"))
                 .and(warp::header::optional::<String>("content-encoding"))
+                .and(warp::header::optional::<String>(DICTIONARY_HEADER))
                 .and(warp::header::headers_cloned())
                 .and(warp::body::bytes())
                 .and(warp::query::<HashMap<String, String>>())
@@ -116,6 +133,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                     move |path: FullPath,
                           auth_header,
                           encoding_header,
+                          dictionary_header: Option<String>,
                           headers: HeaderMap,
                           body: Bytes,
                           query_parameters: HashMap<String, String>| {
@@ -130,7 +148,14 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
                         let events = auth
                             .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
+                            .and_then(|()| {
+                                decode_with_dictionary(
+                                    &encoding_header,
+                                    dictionary_header.as_deref(),
+                                    self.zstd_dictionaries(),
+                                    body,
+                                )
+                            })
                             .and_then(|body| {
                                 self.build_events(body, &headers, &query_parameters, path.as_str())
                             })
@@ -158,18 +183,21 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                 .with(warp::trace(move |_info| span.clone()));
 
             let ping = warp::get().and(warp::path("ping")).map(|| "pong");
-            let routes = svc.or(ping).recover(|r: Rejection| async move {
-                if let Some(e_msg) = r.find::<ErrorMessage>() {
-                    let json = warp::reply::json(e_msg);
-                    Ok(warp::reply::with_status(json, e_msg.status_code()))
-                } else {
-                    //other internal error - will return 500 internal server error
-                    emit!(HttpInternalError {
-                        message: &format!("Internal error: {:?}", r)
-                    });
-                    Err(r)
-                }
-            });
+            let routes = svc
+                .or(ping)
+                .recover(|r: Rejection| async move {
+                    if let Some(e_msg) = r.find::<ErrorMessage>() {
+                        let json = warp::reply::json(e_msg);
+                        Ok(warp::reply::with_status(json, e_msg.status_code()))
+                    } else {
+                        //other internal error - will return 500 internal server error
+                        emit!(HttpInternalError {
+                            message: &format!("Internal error: {:?}", r)
+                        });
+                        Err(r)
+                    }
+                })
+                .with(warp::reply::with::headers(response_headers));
 
             info!(message = "Building HTTP server.", address = %address);
 
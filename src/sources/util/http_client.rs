@@ -9,11 +9,11 @@
 //!     context.
 
 use bytes::Bytes;
-use futures_util::{stream, FutureExt, StreamExt, TryFutureExt};
+use futures_util::{future::Either, stream, FutureExt, StreamExt, TryFutureExt};
 use http::{response::Parts, Uri};
 use hyper::{Body, Request};
 use std::time::{Duration, Instant};
-use std::{collections::HashMap, future::ready};
+use std::{collections::HashMap, future::ready, sync::Arc};
 use tokio_stream::wrappers::IntervalStream;
 use vector_common::json_size::JsonSize;
 
@@ -30,10 +30,34 @@ use crate::{
 use vector_common::shutdown::ShutdownSignal;
 use vector_core::{config::proxy::ProxyConfig, event::Event, EstimatedJsonEncodedSizeOf};
 
+/// The URLs to call at each interval.
+#[derive(Clone)]
+pub(crate) enum HttpClientUrls {
+    /// A fixed list of URLs.
+    Static(Vec<Uri>),
+    /// URLs that change while the source runs, for example through service discovery.
+    Dynamic(Arc<dyn Fn() -> Vec<Uri> + Send + Sync>),
+}
+
+impl HttpClientUrls {
+    fn current(&self) -> Vec<Uri> {
+        match self {
+            Self::Static(urls) => urls.clone(),
+            Self::Dynamic(urls) => urls(),
+        }
+    }
+}
+
+impl From<Vec<Uri>> for HttpClientUrls {
+    fn from(urls: Vec<Uri>) -> Self {
+        Self::Static(urls)
+    }
+}
+
 /// Contains the inputs generic to any http client.
 pub(crate) struct GenericHttpClientInputs {
-    /// Array of URLs to call.
-    pub urls: Vec<Uri>,
+    /// URLs to call.
+    pub urls: HttpClientUrls,
     /// Interval between calls.
     pub interval: Duration,
     /// Map of Header+Value to apply to HTTP request.
@@ -65,7 +89,14 @@ pub(crate) trait HttpClientContext {
     /// Called after the HTTP request succeeds and returns the decoded/parsed Event array.
     fn on_response(&mut self, url: &Uri, header: &Parts, body: &Bytes) -> Option<Vec<Event>>;
 
-    /// (Optional) Called if the HTTP response is not 200 ('OK').
+    /// (Optional) Called before the HTTP request is sent, for example to make it conditional.
+    ///
+    /// Returning `false` skips the request for this interval.
+    fn on_request(&mut self, _url: &Uri, _request: &mut Request<Body>) -> bool {
+        true
+    }
+
+    /// (Optional) Called if the HTTP response is neither 200 ('OK') nor 304 ('Not Modified').
     fn on_http_response_error(&self, _uri: &Uri, _header: &Parts) {}
 
     // This function can be defined to enrich events with additional HTTP
@@ -116,7 +147,7 @@ pub(crate) async fn call<
 ) -> Result<(), ()> {
     let mut stream = IntervalStream::new(tokio::time::interval(inputs.interval))
         .take_until(inputs.shutdown)
-        .map(move |_| stream::iter(inputs.urls.clone()))
+        .map(move |_| stream::iter(inputs.urls.current()))
         .flatten()
         .map(move |url| {
             // Building the HttpClient should not fail as it is just setting up the client with the
@@ -156,8 +187,12 @@ pub(crate) async fn call<
                 auth.apply(&mut request);
             }
 
+            if !context.on_request(&url, &mut request) {
+                return Either::Left(stream::empty());
+            }
+
             let start = Instant::now();
-            client
+            let responses = client
                 .send(request)
                 .map_err(Error::from)
                 .and_then(|response| async move {
@@ -206,6 +241,15 @@ pub(crate) async fn call<
                                 stream::iter(events)
                             })
                         }
+                        Ok((header, _)) if header.status == hyper::StatusCode::NOT_MODIFIED => {
+                            // The response of a conditional request, nothing changed since the
+                            // previous one.
+                            emit!(RequestCompleted {
+                                start,
+                                end: Instant::now()
+                            });
+                            None
+                        }
                         Ok((header, _)) => {
                             context.on_http_response_error(&url, &header);
                             emit!(HttpClientHttpResponseError {
@@ -223,7 +267,8 @@ pub(crate) async fn call<
                         }
                     })
                 })
-                .flatten()
+                .flatten();
+            Either::Right(responses)
         })
         .flatten()
         .boxed();
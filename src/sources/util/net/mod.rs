@@ -11,9 +11,11 @@ use vector_config::configurable_component;
 use crate::config::{Protocol, Resource};
 
 #[cfg(feature = "sources-utils-net-tcp")]
-pub use self::tcp::{TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
+pub use self::tcp::{
+    TcpConnectionLimitsConfig, TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker,
+};
 #[cfg(feature = "sources-utils-net-udp")]
-pub use self::udp::try_bind_udp_socket;
+pub use self::udp::{try_bind_udp_socket, UdpBatchReceiver};
 
 #[derive(Clone, Debug, Eq, PartialEq, Snafu)]
 pub enum SocketListenAddrParseError {
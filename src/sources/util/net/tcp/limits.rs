@@ -0,0 +1,228 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::{sleep, Instant};
+use vector_config::configurable_component;
+
+/// Limits of the connections of a TCP source, protecting it from misbehaving clients.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TcpConnectionLimitsConfig {
+    /// The maximum number of connections open at the same time from a single IP address.
+    ///
+    /// Further connections from the address are closed as soon as they are accepted.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    pub max_connections_per_ip: Option<NonZeroU32>,
+
+    /// The maximum number of bytes read per second from each connection.
+    ///
+    /// Reading from a connection pauses while it exceeds the rate, letting TCP flow control slow
+    /// down the client.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes_per_sec: Option<NonZeroU32>,
+
+    /// The maximum number of bytes read per second from all connections of a single IP address.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes_per_sec_per_ip: Option<NonZeroU32>,
+}
+
+/// A token bucket holding up to a second worth of bytes.
+struct RateLimiter {
+    bytes_per_sec: f64,
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_sec: NonZeroU32) -> Self {
+        let bytes_per_sec = f64::from(bytes_per_sec.get());
+        Self {
+            bytes_per_sec,
+            bucket: Mutex::new((bytes_per_sec, Instant::now())),
+        }
+    }
+
+    /// Takes the bytes read from the bucket, returning how long reading must pause for the
+    /// bucket to refill.
+    fn consume(&self, bytes: usize) -> Duration {
+        let mut bucket = self.bucket.lock().expect("Rate limiter mutex is poisoned");
+        let (available, updated) = &mut *bucket;
+        let now = Instant::now();
+        *available = (*available + now.duration_since(*updated).as_secs_f64() * self.bytes_per_sec)
+            .min(self.bytes_per_sec)
+            - bytes as f64;
+        *updated = now;
+
+        if *available < 0.0 {
+            Duration::from_secs_f64(-*available / self.bytes_per_sec)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+struct Peer {
+    connections: u32,
+    rate: Option<Arc<RateLimiter>>,
+}
+
+/// Enforces the connection limits of a TCP source.
+#[derive(Clone)]
+pub struct TcpConnectionLimiter {
+    config: TcpConnectionLimitsConfig,
+    peers: Arc<Mutex<HashMap<IpAddr, Peer>>>,
+}
+
+impl TcpConnectionLimiter {
+    pub fn new(config: TcpConnectionLimitsConfig) -> Self {
+        Self {
+            config,
+            peers: Arc::default(),
+        }
+    }
+
+    /// Admits a new connection from the address, unless the address has the maximum number of
+    /// connections open.
+    pub fn admit(&self, ip: IpAddr) -> Option<ConnectionLimits> {
+        let mut peers = self
+            .peers
+            .lock()
+            .expect("Connection limiter mutex is poisoned");
+        let peer = peers.entry(ip).or_insert_with(|| Peer {
+            connections: 0,
+            rate: self
+                .config
+                .max_bytes_per_sec_per_ip
+                .map(|rate| Arc::new(RateLimiter::new(rate))),
+        });
+        if let Some(max) = self.config.max_connections_per_ip {
+            if peer.connections >= max.get() {
+                return None;
+            }
+        }
+        peer.connections += 1;
+
+        Some(ConnectionLimits {
+            ip,
+            peers: Arc::clone(&self.peers),
+            connection_rate: self.config.max_bytes_per_sec.map(RateLimiter::new),
+            peer_rate: peer.rate.clone(),
+        })
+    }
+
+    pub fn max_connections_per_ip(&self) -> Option<NonZeroU32> {
+        self.config.max_connections_per_ip
+    }
+}
+
+/// The limits of an admitted connection, which frees its slot for its address when dropped.
+pub struct ConnectionLimits {
+    ip: IpAddr,
+    peers: Arc<Mutex<HashMap<IpAddr, Peer>>>,
+    connection_rate: Option<RateLimiter>,
+    peer_rate: Option<Arc<RateLimiter>>,
+}
+
+impl ConnectionLimits {
+    /// Accounts the bytes read from the connection, and pauses for as long as reading exceeds
+    /// its rates.
+    pub async fn throttle(&self, bytes: usize) {
+        let connection = self
+            .connection_rate
+            .as_ref()
+            .map_or(Duration::ZERO, |rate| rate.consume(bytes));
+        let peer = self
+            .peer_rate
+            .as_ref()
+            .map_or(Duration::ZERO, |rate| rate.consume(bytes));
+        let pause = connection.max(peer);
+        if !pause.is_zero() {
+            sleep(pause).await;
+        }
+    }
+}
+
+impl Drop for ConnectionLimits {
+    fn drop(&mut self) {
+        let mut peers = self
+            .peers
+            .lock()
+            .expect("Connection limiter mutex is poisoned");
+        if let Some(peer) = peers.get_mut(&self.ip) {
+            peer.connections -= 1;
+            if peer.connections == 0 {
+                peers.remove(&self.ip);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(
+        max_connections_per_ip: Option<u32>,
+        max_bytes_per_sec: Option<u32>,
+        max_bytes_per_sec_per_ip: Option<u32>,
+    ) -> TcpConnectionLimiter {
+        TcpConnectionLimiter::new(TcpConnectionLimitsConfig {
+            max_connections_per_ip: max_connections_per_ip.and_then(NonZeroU32::new),
+            max_bytes_per_sec: max_bytes_per_sec.and_then(NonZeroU32::new),
+            max_bytes_per_sec_per_ip: max_bytes_per_sec_per_ip.and_then(NonZeroU32::new),
+        })
+    }
+
+    #[test]
+    fn limits_connections_per_ip() {
+        let limiter = limiter(Some(2), None, None);
+        let a: IpAddr = "10.0.0.1".parse().unwrap();
+        let b: IpAddr = "10.0.0.2".parse().unwrap();
+
+        let first = limiter.admit(a).unwrap();
+        let _second = limiter.admit(a).unwrap();
+        assert!(limiter.admit(a).is_none());
+        assert!(limiter.admit(b).is_some());
+
+        drop(first);
+        assert!(limiter.admit(a).is_some());
+    }
+
+    #[test]
+    fn forgets_disconnected_peers() {
+        let limiter = limiter(None, None, Some(100));
+        let connection = limiter.admit("10.0.0.1".parse().unwrap()).unwrap();
+        assert_eq!(limiter.peers.lock().unwrap().len(), 1);
+        drop(connection);
+        assert!(limiter.peers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttles_reads() {
+        let limiter = limiter(None, Some(1000), Some(1500));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let first = limiter.admit(ip).unwrap();
+        let second = limiter.admit(ip).unwrap();
+
+        // A second worth of bytes is let through right away.
+        let start = Instant::now();
+        first.throttle(1000).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // The connection exceeds its own rate.
+        first.throttle(500).await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+
+        // The other connection is within its own rate, but exceeds the rate of the address,
+        // which refilled 750 bytes meanwhile.
+        let start = Instant::now();
+        second.throttle(1000).await;
+        let elapsed = start.elapsed().as_millis();
+        assert!((166..=167).contains(&elapsed));
+    }
+}
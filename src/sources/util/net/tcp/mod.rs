@@ -1,3 +1,4 @@
+mod limits;
 mod request_limiter;
 
 use std::{collections::BTreeMap, io, mem::drop, net::SocketAddr, time::Duration};
@@ -24,7 +25,11 @@ use vector_core::{
 };
 use vrl::value::Value;
 
-use self::request_limiter::RequestLimiter;
+pub use self::limits::TcpConnectionLimitsConfig;
+use self::{
+    limits::{ConnectionLimits, TcpConnectionLimiter},
+    request_limiter::RequestLimiter,
+};
 use super::SocketListenAddr;
 use crate::{
     codecs::ReadyFrames,
@@ -32,8 +37,8 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event},
     internal_events::{
         ConnectionOpen, DecoderFramingError, OpenGauge, SocketBindError, SocketEventsReceived,
-        SocketMode, SocketReceiveError, StreamClosedError, TcpBytesReceived, TcpSendAckError,
-        TcpSocketTlsConnectionError,
+        SocketMode, SocketReceiveError, StreamClosedError, TcpBytesReceived,
+        TcpConnectionsPerIpLimitReached, TcpSendAckError, TcpSocketTlsConnectionError,
     },
     shutdown::ShutdownSignal,
     sources::util::AfterReadExt,
@@ -118,6 +123,7 @@ where
         cx: SourceContext,
         acknowledgements: SourceAcknowledgementsConfig,
         max_connections: Option<u32>,
+        connection_limits: TcpConnectionLimitsConfig,
         source_name: &'static str,
         log_namespace: LogNamespace,
     ) -> crate::Result<crate::sources::Source> {
@@ -154,6 +160,7 @@ where
 
             let request_limiter =
                 RequestLimiter::new(MAX_IN_FLIGHT_EVENTS_TARGET, crate::num_threads());
+            let connection_limiter = TcpConnectionLimiter::new(connection_limits);
 
             listener
                 .accept_stream_limited(max_connections)
@@ -165,6 +172,7 @@ where
                     let out = cx.out.clone();
                     let connection_gauge = connection_gauge.clone();
                     let request_limiter = request_limiter.clone();
+                    let connection_limiter = connection_limiter.clone();
                     let tls_client_metadata_key = tls_client_metadata_key.clone();
 
                     async move {
@@ -180,6 +188,18 @@ where
                         };
 
                         let peer_addr = socket.peer_addr();
+                        let limits = match connection_limiter.admit(peer_addr.ip()) {
+                            Some(limits) => limits,
+                            None => {
+                                emit!(TcpConnectionsPerIpLimitReached {
+                                    peer_addr,
+                                    limit: connection_limiter
+                                        .max_connections_per_ip()
+                                        .map_or(0, |limit| limit.get()),
+                                });
+                                return;
+                            }
+                        };
                         let span = info_span!("connection", %peer_addr);
 
                         let tripwire = tripwire
@@ -209,6 +229,7 @@ where
                                 out,
                                 acknowledgements,
                                 request_limiter,
+                                limits,
                                 tls_client_metadata_key.clone(),
                                 source_name,
                                 log_namespace,
@@ -243,6 +264,7 @@ async fn handle_stream<T>(
     mut out: SourceSender,
     acknowledgements: bool,
     request_limiter: RequestLimiter,
+    limits: ConnectionLimits,
     tls_client_metadata_key: Option<OwnedValuePath>,
     source_name: &'static str,
     log_namespace: LogNamespace,
@@ -298,6 +320,7 @@ async fn handle_stream<T>(
     tokio::pin!(connection_close_timeout);
 
     loop {
+        let mut read_bytes = 0;
         let mut permit = tokio::select! {
             _ = &mut tripwire => break,
             Some(_) = &mut connection_close_timeout  => {
@@ -335,7 +358,8 @@ async fn handle_stream<T>(
             }
             res = reader.next() => {
                 match res {
-                    Some(Ok((frames, _byte_size))) => {
+                    Some(Ok((frames, byte_size))) => {
+                        read_bytes = byte_size;
                         let _num_frames = frames.len();
                         let acker = source.build_acker(&frames);
                         let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
@@ -427,6 +451,11 @@ async fn handle_stream<T>(
         }
 
         drop(permit);
+
+        tokio::select! {
+            _ = &mut tripwire => break,
+            _ = limits.throttle(read_bytes) => {},
+        }
     }
 }
 
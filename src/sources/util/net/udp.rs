@@ -1,9 +1,17 @@
-use std::io;
+use std::{io, net::SocketAddr};
 
+use bytes::Bytes;
 use listenfd::ListenFd;
-use tokio::net::UdpSocket;
+use tokio::{io::Interest, net::UdpSocket};
 
 use super::SocketListenAddr;
+use crate::internal_events::UdpReceiveBufferOverflow;
+
+/// The maximum number of datagrams read from the socket at once.
+const BATCH_SIZE: usize = 32;
+
+/// The maximum size of the payload of a UDP datagram.
+const MAX_DATAGRAM_LENGTH: usize = 65_535;
 
 /// Binds a UDP socket to the listen address.
 pub async fn try_bind_udp_socket(
@@ -21,3 +29,323 @@ pub async fn try_bind_udp_socket(
         },
     }
 }
+
+/// A datagram read from a UDP socket.
+pub struct Datagram {
+    /// The payload of the datagram, holding one byte past the maximum length when truncated.
+    pub payload: Bytes,
+    pub address: SocketAddr,
+    pub truncated: bool,
+}
+
+/// Reads datagrams from a UDP socket in batches.
+///
+/// On Linux, a batch is read with a single `recvmmsg` call, and the datagrams the kernel dropped
+/// because the receive buffer of the socket was full are reported as they are noticed.
+pub struct UdpBatchReceiver {
+    socket: UdpSocket,
+    state: ReceiveState,
+}
+
+impl UdpBatchReceiver {
+    /// Datagrams longer than `max_length` are marked as truncated.
+    pub fn new(socket: UdpSocket, max_length: usize) -> Self {
+        // One byte is added to the maximum length to detect whether a datagram was truncated.
+        let slot_length = max_length.min(MAX_DATAGRAM_LENGTH) + 1;
+
+        #[cfg(target_os = "linux")]
+        if let Err(error) = linux::report_drops(&socket) {
+            warn!(message = "Failed enabling drop reporting on UDP socket.", %error);
+        }
+
+        Self {
+            socket,
+            state: ReceiveState {
+                max_length,
+                slot_length,
+                buffer: vec![0; slot_length * BATCH_SIZE],
+                #[cfg(target_os = "linux")]
+                dropped: 0,
+            },
+        }
+    }
+
+    /// Waits for datagrams, and reads as many as are available, up to the batch size.
+    pub async fn recv_batch(&mut self) -> io::Result<Vec<Datagram>> {
+        loop {
+            self.socket.readable().await?;
+            let mut datagrams = Vec::new();
+            let socket = &self.socket;
+            let state = &mut self.state;
+            match socket.try_io(Interest::READABLE, || {
+                state.try_recv_batch(socket, &mut datagrams)
+            }) {
+                Ok(()) => return Ok(datagrams),
+                // Datagrams read before the socket would block are still returned.
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                    if !datagrams.is_empty() {
+                        return Ok(datagrams);
+                    }
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+struct ReceiveState {
+    max_length: usize,
+    slot_length: usize,
+    buffer: Vec<u8>,
+    /// The number of datagrams dropped by the socket, as last reported by the kernel.
+    #[cfg(target_os = "linux")]
+    dropped: u32,
+}
+
+impl ReceiveState {
+    #[cfg(target_os = "linux")]
+    fn try_recv_batch(
+        &mut self,
+        socket: &UdpSocket,
+        datagrams: &mut Vec<Datagram>,
+    ) -> io::Result<()> {
+        let received = linux::recv_mmsg(socket, &mut self.buffer, self.slot_length)?;
+        self.push_datagrams(received, datagrams);
+        Ok(())
+    }
+
+    /// Turns the datagrams read into the slots of the buffer, in order, into `Datagram`s, skipping
+    /// the slots whose sender isn't an IP address.
+    #[cfg(target_os = "linux")]
+    fn push_datagrams(
+        &mut self,
+        received: Vec<(usize, Option<SocketAddr>, Option<u32>)>,
+        datagrams: &mut Vec<Datagram>,
+    ) {
+        let mut dropped = self.dropped;
+        for (index, (length, address, drops)) in received.into_iter().enumerate() {
+            if let Some(drops) = drops {
+                dropped = drops;
+            }
+            let Some(address) = address else {
+                continue;
+            };
+            let start = index * self.slot_length;
+            datagrams.push(Datagram {
+                payload: Bytes::copy_from_slice(&self.buffer[start..start + length]),
+                address,
+                truncated: length > self.max_length,
+            });
+        }
+
+        let newly_dropped = dropped.wrapping_sub(self.dropped);
+        if newly_dropped > 0 {
+            emit!(UdpReceiveBufferOverflow {
+                count: u64::from(newly_dropped)
+            });
+            self.dropped = dropped;
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn try_recv_batch(
+        &mut self,
+        socket: &UdpSocket,
+        datagrams: &mut Vec<Datagram>,
+    ) -> io::Result<()> {
+        let slot = &mut self.buffer[..self.slot_length];
+        while datagrams.len() < BATCH_SIZE {
+            match socket.try_recv_from(slot) {
+                Ok((length, address)) => datagrams.push(Datagram {
+                    payload: Bytes::copy_from_slice(&slot[..length]),
+                    address,
+                    truncated: length > self.max_length,
+                }),
+                Err(error) => {
+                    #[cfg(windows)]
+                    if error.raw_os_error() == Some(10040) {
+                        // 10040 is the Windows error that the Udp message has exceeded max_length
+                        warn!(
+                            message = "Discarding frame larger than max_length.",
+                            max_length = self.max_length,
+                            internal_log_rate_limit = true
+                        );
+                        continue;
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{io, mem, net::SocketAddr, os::unix::io::AsRawFd, ptr};
+
+    use nix::{
+        libc,
+        sys::socket::{setsockopt, sockopt::RxqOvfl},
+    };
+    use socket2::SockAddr;
+    use tokio::net::UdpSocket;
+
+    use super::BATCH_SIZE;
+
+    /// Has the kernel attach the number of datagrams dropped by the socket to the datagrams read.
+    pub(super) fn report_drops(socket: &UdpSocket) -> io::Result<()> {
+        setsockopt(socket.as_raw_fd(), RxqOvfl, &1).map_err(io::Error::from)
+    }
+
+    /// Reads the available datagrams into consecutive slots of the buffer, returning their
+    /// lengths, senders, and the drop count of the socket when attached.
+    ///
+    /// There is one entry for each slot read, in order, so the sender is `None` rather than the
+    /// entry being left out when it isn't an IP address.
+    pub(super) fn recv_mmsg(
+        socket: &UdpSocket,
+        buffer: &mut [u8],
+        slot_length: usize,
+    ) -> io::Result<Vec<(usize, Option<SocketAddr>, Option<u32>)>> {
+        // Control messages must be aligned like their headers, hence the `u64` words.
+        // SAFETY: `CMSG_SPACE` only computes a size.
+        let control_length = unsafe { libc::CMSG_SPACE(mem::size_of::<u32>() as u32) } as usize;
+        let control_words = (control_length + 7) / 8;
+        let mut controls = vec![0u64; control_words * BATCH_SIZE];
+        // SAFETY: An all-zero `sockaddr_storage` is valid.
+        let mut addresses: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; BATCH_SIZE];
+        let mut iovecs = buffer
+            .chunks_exact_mut(slot_length)
+            .map(|slot| libc::iovec {
+                iov_base: slot.as_mut_ptr().cast(),
+                iov_len: slot.len(),
+            })
+            .collect::<Vec<_>>();
+        let mut headers = iovecs
+            .iter_mut()
+            .zip(addresses.iter_mut())
+            .zip(controls.chunks_exact_mut(control_words))
+            .map(|((iovec, address), control)| {
+                // SAFETY: An all-zero `mmsghdr` is valid.
+                let mut header: libc::mmsghdr = unsafe { mem::zeroed() };
+                header.msg_hdr.msg_name = (address as *mut libc::sockaddr_storage).cast();
+                header.msg_hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as _;
+                header.msg_hdr.msg_iov = iovec;
+                header.msg_hdr.msg_iovlen = 1;
+                header.msg_hdr.msg_control = control.as_mut_ptr().cast();
+                header.msg_hdr.msg_controllen = control_length as _;
+                header
+            })
+            .collect::<Vec<_>>();
+
+        // SAFETY: The headers point to buffers living until the end of the function.
+        let received = unsafe {
+            libc::recvmmsg(
+                socket.as_raw_fd(),
+                headers.as_mut_ptr(),
+                headers.len() as _,
+                libc::MSG_DONTWAIT as _,
+                ptr::null_mut(),
+            )
+        };
+        if received < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(headers
+            .iter()
+            .zip(addresses.iter())
+            .take(received as usize)
+            .map(|(header, address)| {
+                // SAFETY: The kernel wrote an address of the given length.
+                let address =
+                    unsafe { SockAddr::new(*address, header.msg_hdr.msg_namelen) }.as_socket();
+                (header.msg_len as usize, address, drops(&header.msg_hdr))
+            })
+            .collect())
+    }
+
+    fn drops(header: &libc::msghdr) -> Option<u32> {
+        // SAFETY: The control messages were written by the kernel within `msg_controllen`.
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(header);
+            while !cmsg.is_null() {
+                if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SO_RXQ_OVFL
+                {
+                    return Some(ptr::read_unaligned(libc::CMSG_DATA(cmsg).cast::<u32>()));
+                }
+                cmsg = libc::CMSG_NXTHDR(header, cmsg);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::next_addr;
+
+    #[tokio::test]
+    async fn receives_batches() {
+        let addr = next_addr();
+        let socket = UdpSocket::bind(addr).await.unwrap();
+        let mut receiver = UdpBatchReceiver::new(socket, 8);
+
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        for payload in ["first", "second", "truncated"] {
+            sender.send_to(payload.as_bytes(), addr).await.unwrap();
+        }
+
+        let mut datagrams = Vec::new();
+        while datagrams.len() < 3 {
+            datagrams.extend(receiver.recv_batch().await.unwrap());
+        }
+
+        let payloads = datagrams
+            .iter()
+            .map(|datagram| (datagram.payload.clone(), datagram.truncated))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            payloads,
+            vec![
+                (Bytes::from("first"), false),
+                (Bytes::from("second"), false),
+                (Bytes::from("truncated"), true),
+            ]
+        );
+        assert!(datagrams
+            .iter()
+            .all(|datagram| datagram.address == sender.local_addr().unwrap()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn skips_slots_without_address() {
+        let mut state = ReceiveState {
+            max_length: 4,
+            slot_length: 5,
+            buffer: b"first\0\0\0\0\0third".to_vec(),
+            dropped: 0,
+        };
+        let address = next_addr();
+
+        let mut datagrams = Vec::new();
+        state.push_datagrams(
+            vec![
+                (5, Some(address), None),
+                (3, None, Some(2)),
+                (5, Some(address), None),
+            ],
+            &mut datagrams,
+        );
+
+        let payloads = datagrams
+            .iter()
+            .map(|datagram| datagram.payload.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(payloads, vec![Bytes::from("first"), Bytes::from("third")]);
+        assert_eq!(state.dropped, 2);
+    }
+}
@@ -1,10 +1,11 @@
 //! The `vector` source. See [VectorConfig].
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 use chrono::Utc;
 use codecs::NativeDeserializerConfig;
 use futures::TryFutureExt;
-use tonic::{Request, Response, Status};
+use prost::Message;
+use tonic::{metadata::MetadataValue, Request, Response, Status};
 use vector_common::internal_event::{CountByteSize, InternalEventHandle as _};
 use vector_config::configurable_component;
 use vector_core::{
@@ -23,6 +24,7 @@ use crate::{
     serde::bool_or_struct,
     sources::{util::grpc::run_grpc_server, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
+    zstd_dictionary::{ZstdDictionaries, ZstdDictionaryConfig, DICTIONARY_HEADER},
     SourceSender,
 };
 
@@ -40,6 +42,52 @@ struct Service {
     pipeline: SourceSender,
     acknowledgements: bool,
     log_namespace: LogNamespace,
+    dictionaries: Arc<ZstdDictionaries>,
+}
+
+impl Service {
+    /// Decompresses the events of a request compressed with one of the zstd dictionaries.
+    fn decompress(
+        &self,
+        dictionary: Option<&str>,
+        compressed_events: &[u8],
+    ) -> Result<proto::PushEventsRequest, Status> {
+        let dictionary = dictionary
+            .and_then(|id| self.dictionaries.get(id))
+            .ok_or_else(|| {
+                // List the dictionaries, for the upstream instance to stop using its own.
+                let mut status = Status::failed_precondition("Unknown zstd dictionary");
+                status
+                    .metadata_mut()
+                    .insert(DICTIONARY_HEADER, self.dictionaries_header());
+                status
+            })?;
+
+        let invalid = |error: &dyn std::fmt::Display| {
+            Status::invalid_argument(format!("Invalid compressed events: {}", error))
+        };
+        let decompressed = dictionary
+            .decompress(compressed_events)
+            .map_err(|error| invalid(&error))?;
+        proto::PushEventsRequest::decode(decompressed.as_slice()).map_err(|error| invalid(&error))
+    }
+
+    fn dictionaries_header(&self) -> MetadataValue<tonic::metadata::Ascii> {
+        self.dictionaries
+            .header_value()
+            .parse()
+            .expect("Dictionary IDs are valid metadata")
+    }
+
+    /// Lists the dictionaries in the metadata of a response, if there are any.
+    fn with_dictionaries<T>(&self, mut response: Response<T>) -> Response<T> {
+        if !self.dictionaries.is_empty() {
+            response
+                .metadata_mut()
+                .insert(DICTIONARY_HEADER, self.dictionaries_header());
+        }
+        response
+    }
 }
 
 #[tonic::async_trait]
@@ -48,12 +96,17 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
-            .into_inner()
-            .events
-            .into_iter()
-            .map(Event::from)
-            .collect();
+        let dictionary = request
+            .metadata()
+            .get(DICTIONARY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let mut request = request.into_inner();
+        if !request.compressed_events.is_empty() {
+            request = self.decompress(dictionary.as_deref(), &request.compressed_events)?;
+        }
+
+        let mut events: Vec<Event> = request.events.into_iter().map(Event::from).collect();
 
         let now = Utc::now();
         for event in &mut events {
@@ -84,7 +137,7 @@ impl proto::Service for Service {
             .and_then(|_| handle_batch_status(receiver))
             .await?;
 
-        Ok(Response::new(proto::PushEventsResponse {}))
+        Ok(self.with_dictionaries(Response::new(proto::PushEventsResponse {})))
     }
 
     // TODO: figure out a way to determine if the current Vector instance is "healthy".
@@ -96,7 +149,7 @@ impl proto::Service for Service {
             status: proto::ServingStatus::Serving.into(),
         };
 
-        Ok(Response::new(message))
+        Ok(self.with_dictionaries(Response::new(message)))
     }
 }
 
@@ -134,6 +187,14 @@ pub struct VectorConfig {
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
 
+    /// Trained zstd dictionaries that upstream Vector instances may compress their events with.
+    ///
+    /// The IDs of the dictionaries are listed in responses, and upstream instances configured with
+    /// one of them in their `compression_dictionary` start using it.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    compression_dictionaries: Vec<ZstdDictionaryConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[serde(default)]
     #[configurable(metadata(docs::hidden))]
@@ -157,6 +218,7 @@ impl Default for VectorConfig {
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
             acknowledgements: Default::default(),
+            compression_dictionaries: Vec::new(),
             log_namespace: None,
         }
     }
@@ -176,10 +238,13 @@ impl SourceConfig for VectorConfig {
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let log_namespace = cx.log_namespace(self.log_namespace);
 
+        let dictionaries = Arc::new(ZstdDictionaries::load(&self.compression_dictionaries)?);
+
         let service = proto::Server::new(Service {
             pipeline: cx.out,
             acknowledgements,
             log_namespace,
+            dictionaries,
         })
         .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
 
@@ -344,4 +409,72 @@ mod tests {
         })
         .await;
     }
+
+    fn dictionary_service(
+        dictionaries: ZstdDictionaries,
+    ) -> (Service, impl futures::Stream<Item = Event> + Unpin) {
+        let (tx, rx) = SourceSender::new_test();
+        let service = Service {
+            pipeline: tx,
+            acknowledgements: false,
+            log_namespace: LogNamespace::Legacy,
+            dictionaries: Arc::new(dictionaries),
+        };
+        (service, rx)
+    }
+
+    #[tokio::test]
+    async fn receive_dictionary_compressed_message() {
+        let samples = (0..1000)
+            .map(|i| format!("message {} from edge-{}", i, i % 7).into_bytes())
+            .collect::<Vec<_>>();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zstd.dict");
+        std::fs::write(&path, zstd::dict::from_samples(&samples, 4096).unwrap()).unwrap();
+        let config = ZstdDictionaryConfig { path };
+        let dictionary = config.load(zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+        let (service, rx) = dictionary_service(ZstdDictionaries::load(&[config]).unwrap());
+
+        let (events, _) = test_util::random_events_with_stream(100, 10, None);
+        let payload = proto::PushEventsRequest {
+            events: events.iter().cloned().map(Into::into).collect(),
+            ..Default::default()
+        };
+        let mut request = Request::new(proto::PushEventsRequest {
+            compressed_events: dictionary.compress(&payload.encode_to_vec()).unwrap(),
+            ..Default::default()
+        });
+        request
+            .metadata_mut()
+            .insert(DICTIONARY_HEADER, MetadataValue::from(dictionary.id()));
+
+        let response = proto::Service::push_events(&service, request)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.metadata().get(DICTIONARY_HEADER).unwrap(),
+            dictionary.id().to_string().as_str()
+        );
+        assert_eq!(test_util::collect_ready(rx).await.len(), events.len());
+    }
+
+    #[tokio::test]
+    async fn reject_unknown_dictionary() {
+        let (service, _rx) = dictionary_service(ZstdDictionaries::default());
+
+        let mut request = Request::new(proto::PushEventsRequest {
+            compressed_events: vec![1, 2, 3],
+            ..Default::default()
+        });
+        request
+            .metadata_mut()
+            .insert(DICTIONARY_HEADER, MetadataValue::from(42_u32));
+
+        let status = proto::Service::push_events(&service, request)
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        // The empty list of dictionaries tells the sink to stop using its own.
+        assert_eq!(status.metadata().get(DICTIONARY_HEADER).unwrap(), "");
+    }
 }
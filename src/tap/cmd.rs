@@ -1,4 +1,8 @@
-use std::{borrow::Cow, collections::BTreeMap, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 
 use colored::{ColoredString, Colorize};
 use tokio_stream::StreamExt;
@@ -7,7 +11,7 @@ use vector_api_client::{
     connect_subscription_client,
     gql::{
         output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns,
-        TapEncodingFormat, TapSubscriptionExt,
+        FailedRequestsQueryExt, TapEncodingFormat, TapSubscriptionExt,
     },
     Client,
 };
@@ -34,8 +38,13 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
 
     // Return early with instructions for enabling the API if the endpoint isn't reachable
     // via a healthcheck.
-    if Client::new_with_healthcheck(url.clone()).await.is_none() {
-        return exitcode::UNAVAILABLE;
+    let client = match Client::new_with_healthcheck(url.clone()).await {
+        Some(client) => client,
+        None => return exitcode::UNAVAILABLE,
+    };
+
+    if opts.errors {
+        return failed_requests(client, opts, signal_rx).await;
     }
 
     // Change the HTTP schema to WebSockets.
@@ -82,6 +91,73 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
     exitcode::OK
 }
 
+/// Prints the failed requests captured by the matching sinks as they are captured, polling them
+/// at the tap interval.
+async fn failed_requests(
+    client: Client,
+    opts: &super::Opts,
+    mut signal_rx: SignalRx,
+) -> exitcode::ExitCode {
+    let patterns = if opts.component_id_patterns.is_empty() {
+        vec!["*".to_string()]
+    } else {
+        opts.component_id_patterns.clone()
+    };
+
+    // The ID of the last printed request of each sink. Requests captured before tapping are
+    // printed too.
+    let mut printed = HashMap::<String, i64>::new();
+    let mut interval = tokio::time::interval(Duration::from_millis(opts.interval.into()));
+
+    #[allow(clippy::print_stdout)]
+    #[allow(clippy::print_stderr)]
+    loop {
+        tokio::select! {
+            biased;
+            Ok(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => break,
+            _ = interval.tick() => {
+                let requests = match client.sink_failed_requests(patterns.clone()).await {
+                    Ok(response) => match response.data {
+                        Some(data) => data.sink_failed_requests,
+                        None => Vec::new(),
+                    },
+                    Err(error) => {
+                        eprintln!("[tap] Couldn't query failed requests: {}", error);
+                        if opts.no_reconnect {
+                            return exitcode::UNAVAILABLE;
+                        }
+                        continue;
+                    }
+                };
+
+                // A reloaded sink numbers its requests from zero again.
+                let mut latest = HashMap::<&str, i64>::new();
+                for request in &requests {
+                    let id = latest.entry(request.component_id.as_str()).or_default();
+                    *id = request.id.max(*id);
+                }
+                printed.retain(|component_id, last| {
+                    latest.get(component_id.as_str()).map_or(true, |latest| latest >= last)
+                });
+
+                for request in &requests {
+                    let last = printed.get(&request.component_id).copied();
+                    if last.map_or(true, |last| request.id > last) {
+                        printed.insert(request.component_id.clone(), request.id);
+                        println!(
+                            "{}",
+                            serde_json::to_string(request)
+                                .expect("Failed requests are serializable")
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
 async fn run(
     url: Url,
     opts: &super::Opts,
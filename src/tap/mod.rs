@@ -44,6 +44,10 @@ pub struct Opts {
     #[arg(short, long)]
     meta: bool,
 
+    /// Print the failed requests captured by the sinks matching the component ID patterns, instead of events. Sinks capture them when `debug.capture_failed_requests` is set.
+    #[arg(long)]
+    errors: bool,
+
     /// Whether to reconnect if the underlying Vector API connection drops. By default, tap will attempt to reconnect if the connection drops.
     #[arg(short, long)]
     no_reconnect: bool,
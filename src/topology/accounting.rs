@@ -0,0 +1,319 @@
+//! Accounting of the events ingested by sources and delivered to sinks.
+//!
+//! The counts of every component are held in a global registry, grouped by the configured
+//! dimensions. The registry outlives configuration reloads, so counts are kept since Vector
+//! started, unless the dimensions change.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use once_cell::sync::Lazy;
+use vector_core::{
+    event::{EventArray, LogEvent, Metric, TraceEvent},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::config::{accounting::Options, ComponentKey};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| {
+    Mutex::new(Registry {
+        dimensions: Arc::default(),
+        max_groups: Options::default().max_groups,
+        components: HashMap::new(),
+    })
+});
+
+struct Registry {
+    dimensions: Arc<Vec<(String, String)>>,
+    max_groups: usize,
+    components: HashMap<(ComponentKey, Direction), Arc<Mutex<Groups>>>,
+}
+
+/// Whether the events were ingested by a source, or delivered to a sink.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Direction {
+    Ingested,
+    Delivered,
+}
+
+impl Direction {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ingested => "ingested",
+            Self::Delivered => "delivered",
+        }
+    }
+}
+
+/// The values of the dimensions of a group, or `None` for the overflow group.
+pub type Group = Option<Vec<Option<String>>>;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    pub events: u64,
+    pub bytes: u64,
+}
+
+struct Groups {
+    dimensions: Arc<Vec<(String, String)>>,
+    max_groups: usize,
+    counts: HashMap<Group, Counts>,
+}
+
+impl Groups {
+    fn record(&mut self, group: Vec<Option<String>>, bytes: usize) {
+        let group = Some(group);
+        let group = if self.counts.len() < self.max_groups || self.counts.contains_key(&group) {
+            group
+        } else {
+            None
+        };
+        let counts = self.counts.entry(group).or_default();
+        counts.events += 1;
+        counts.bytes += bytes as u64;
+    }
+}
+
+/// The counts of a group of a component.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub component: ComponentKey,
+    pub direction: Direction,
+    /// The names and values of the dimensions, or `None` for the overflow group.
+    pub dimensions: Option<Vec<(String, Option<String>)>>,
+    pub counts: Counts,
+}
+
+/// Applies the configured accounting options.
+///
+/// The counts are cleared if the dimensions changed, as they no longer group the same events.
+pub fn apply(options: &Options) {
+    let dimensions = options
+        .dimensions
+        .iter()
+        .map(|(name, field)| (name.clone(), field.clone()))
+        .collect::<Vec<_>>();
+
+    let mut registry = REGISTRY
+        .lock()
+        .expect("Accounting registry mutex is poisoned");
+    if *registry.dimensions != dimensions || registry.max_groups != options.max_groups {
+        registry.dimensions = Arc::new(dimensions);
+        registry.max_groups = options.max_groups;
+        for groups in registry.components.values() {
+            let mut groups = groups.lock().expect("Accounting mutex is poisoned");
+            groups.dimensions = Arc::clone(&registry.dimensions);
+            groups.max_groups = registry.max_groups;
+            groups.counts.clear();
+        }
+    }
+    ENABLED.store(options.enabled, Ordering::Relaxed);
+}
+
+/// Whether events are accounted.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The counts of every group of every component, ordered by component.
+pub fn snapshot() -> Vec<Entry> {
+    let registry = REGISTRY
+        .lock()
+        .expect("Accounting registry mutex is poisoned");
+    let mut components = registry.components.iter().collect::<Vec<_>>();
+    components.sort_by(|((a, a_direction), _), ((b, b_direction), _)| {
+        a.id().cmp(b.id()).then(a_direction.cmp(b_direction))
+    });
+
+    let mut entries = Vec::new();
+    for ((component, direction), groups) in components {
+        let groups = groups.lock().expect("Accounting mutex is poisoned");
+        for (group, counts) in &groups.counts {
+            let dimensions = group.as_ref().map(|values| {
+                groups
+                    .dimensions
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .zip(values.iter().cloned())
+                    .collect()
+            });
+            entries.push(Entry {
+                component: component.clone(),
+                direction: *direction,
+                dimensions,
+                counts: *counts,
+            });
+        }
+    }
+    entries
+}
+
+/// Accounts the events flowing through a component.
+#[derive(Clone)]
+pub struct Accountant {
+    groups: Arc<Mutex<Groups>>,
+}
+
+impl Accountant {
+    pub fn new(key: &ComponentKey, direction: Direction) -> Self {
+        let mut registry = REGISTRY
+            .lock()
+            .expect("Accounting registry mutex is poisoned");
+        let dimensions = Arc::clone(&registry.dimensions);
+        let max_groups = registry.max_groups;
+        let groups = registry
+            .components
+            .entry((key.clone(), direction))
+            .or_insert_with(|| {
+                Arc::new(Mutex::new(Groups {
+                    dimensions,
+                    max_groups,
+                    counts: HashMap::new(),
+                }))
+            });
+        Self {
+            groups: Arc::clone(groups),
+        }
+    }
+
+    /// Adds the events of the array to the counts of their groups.
+    pub fn record(&self, array: &EventArray) {
+        if !is_enabled() {
+            return;
+        }
+
+        let mut groups = self.groups.lock().expect("Accounting mutex is poisoned");
+        let dimensions = Arc::clone(&groups.dimensions);
+        match array {
+            EventArray::Logs(logs) => {
+                for log in logs {
+                    groups.record(
+                        log_group(&dimensions, log),
+                        log.estimated_json_encoded_size_of().get(),
+                    );
+                }
+            }
+            EventArray::Metrics(metrics) => {
+                for metric in metrics {
+                    groups.record(
+                        metric_group(&dimensions, metric),
+                        metric.estimated_json_encoded_size_of().get(),
+                    );
+                }
+            }
+            EventArray::Traces(traces) => {
+                for trace in traces {
+                    groups.record(
+                        trace_group(&dimensions, trace),
+                        trace.estimated_json_encoded_size_of().get(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn log_group(dimensions: &[(String, String)], log: &LogEvent) -> Vec<Option<String>> {
+    dimensions
+        .iter()
+        .map(|(_, field)| {
+            log.get(field.as_str())
+                .map(|value| value.to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
+fn metric_group(dimensions: &[(String, String)], metric: &Metric) -> Vec<Option<String>> {
+    dimensions
+        .iter()
+        .map(|(_, tag)| metric.tag_value(tag))
+        .collect()
+}
+
+fn trace_group(dimensions: &[(String, String)], trace: &TraceEvent) -> Vec<Option<String>> {
+    dimensions
+        .iter()
+        .map(|(_, field)| {
+            trace
+                .get(field.as_str())
+                .map(|value| value.to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The registry is shared by every test running a topology, so these use their own groups.
+    fn accountant(max_groups: usize) -> Accountant {
+        Accountant {
+            groups: Arc::new(Mutex::new(Groups {
+                dimensions: Arc::new(vec![("tenant".to_owned(), "tenant".to_owned())]),
+                max_groups,
+                counts: HashMap::new(),
+            })),
+        }
+    }
+
+    fn logs(tenants: &[Option<&str>]) -> EventArray {
+        tenants
+            .iter()
+            .map(|tenant| {
+                let mut log = LogEvent::from("message");
+                if let Some(tenant) = tenant {
+                    log.insert("tenant", *tenant);
+                }
+                log
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn counts(accountant: &Accountant, group: Group) -> Counts {
+        accountant.groups.lock().unwrap().counts[&group]
+    }
+
+    #[test]
+    fn groups_events_by_dimensions() {
+        ENABLED.store(true, Ordering::Relaxed);
+        let accountant = accountant(10);
+        accountant.record(&logs(&[Some("a"), Some("b"), Some("a"), None]));
+
+        let a = counts(&accountant, Some(vec![Some("a".to_owned())]));
+        assert_eq!(a.events, 2);
+        assert!(a.bytes > 0);
+        assert_eq!(
+            counts(&accountant, Some(vec![Some("b".to_owned())])).events,
+            1
+        );
+        assert_eq!(counts(&accountant, Some(vec![None])).events, 1);
+    }
+
+    #[test]
+    fn overflows_groups() {
+        ENABLED.store(true, Ordering::Relaxed);
+        let accountant = accountant(2);
+        accountant.record(&logs(&[
+            Some("a"),
+            Some("b"),
+            Some("c"),
+            Some("a"),
+            Some("d"),
+        ]));
+
+        assert_eq!(
+            counts(&accountant, Some(vec![Some("a".to_owned())])).events,
+            2
+        );
+        assert_eq!(counts(&accountant, None).events, 2);
+        assert_eq!(accountant.groups.lock().unwrap().counts.len(), 3);
+    }
+}
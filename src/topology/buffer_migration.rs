@@ -0,0 +1,120 @@
+//! Migration of the disk buffers left behind by sinks.
+//!
+//! A disk buffer is stored in a directory named after its sink, so renaming the sink, or changing
+//! its buffer to one without a disk stage, leaves the buffer and the events it holds behind. Sinks
+//! with `buffer_migration.enabled` take these buffers over when they are built: the buffer of a
+//! previous ID is renamed when the sink doesn't have a disk buffer yet, which is instantaneous,
+//! and any other buffer is drained into the sink ahead of its own buffer.
+//!
+//! A drained buffer is opened without being written to, so its reader ends once every event read
+//! from it has been acknowledged, at which point its directory is deleted. Events left unread when
+//! Vector stops stay on disk, and are drained again on the next start.
+
+use std::{
+    future,
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+};
+
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use tokio::fs;
+use vector_buffers::{config::disk_buffer_dir, BufferConfig, BufferType, WhenFull};
+
+use crate::{
+    config::{ComponentKey, OutputId, SinkOuter},
+    event::EventArray,
+};
+
+/// Takes over the disk buffers left behind by the sink, returning the events of the buffers
+/// drained into it.
+pub(super) async fn migrate(
+    key: &ComponentKey,
+    sink: &SinkOuter<OutputId>,
+    global_data_dir: Option<&Path>,
+) -> crate::Result<BoxStream<'static, EventArray>> {
+    // Without a data directory, there are no disk buffers to take over.
+    let data_dir = match global_data_dir {
+        Some(data_dir) if sink.buffer_migration.enabled => data_dir,
+        _ => return Ok(stream::empty().boxed()),
+    };
+
+    let mut left_behind = sink
+        .buffer_migration
+        .previous_ids
+        .iter()
+        .filter(|previous_id| disk_buffer_dir(data_dir, previous_id).exists())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let dir = disk_buffer_dir(data_dir, key);
+    if sink.buffer.has_disk_stage() {
+        if !dir.exists() && !left_behind.is_empty() {
+            let previous_id = left_behind.remove(0);
+            fs::rename(disk_buffer_dir(data_dir, &previous_id), &dir).await?;
+            info!(
+                message = "Renamed disk buffer of previous sink ID.",
+                previous_id = %previous_id,
+                component_id = %key.id(),
+            );
+        }
+    } else if dir.exists() {
+        left_behind.insert(0, key.clone());
+    }
+
+    let mut drained = Vec::with_capacity(left_behind.len());
+    for id in left_behind {
+        drained.push(drain(key, data_dir, id).await?);
+    }
+    Ok(stream::iter(drained).flatten().boxed())
+}
+
+/// Opens a disk buffer left behind, and deletes it once all of its events are acknowledged.
+async fn drain(
+    key: &ComponentKey,
+    data_dir: &Path,
+    id: ComponentKey,
+) -> crate::Result<BoxStream<'static, EventArray>> {
+    info!(
+        message = "Draining disk buffer left behind.",
+        buffer_id = %id,
+        component_id = %key.id(),
+    );
+
+    let span = error_span!(
+        "sink",
+        component_kind = "sink",
+        component_id = %key.id(),
+        component_name = %key.id(),
+        buffer_type = "disk",
+        buffer_id = %id,
+    );
+    // Nothing is written to the buffer, so its size limit doesn't matter.
+    let buffer = BufferConfig::Single(BufferType::DiskV2 {
+        max_size: NonZeroU64::new(u64::MAX).expect("not zero"),
+        when_full: WhenFull::Block,
+        evictable: false,
+    });
+    // Dropping the writer marks it as done, so the reader ends once the buffer is empty.
+    let (_, rx) = buffer
+        .build::<EventArray>(Some(data_dir.to_owned()), id.to_string(), span)
+        .await?;
+
+    let dir = disk_buffer_dir(data_dir, &id);
+    let cleanup = stream::once(remove(dir, id)).filter_map(|()| future::ready(None));
+    Ok(rx.into_stream().chain(cleanup).boxed())
+}
+
+async fn remove(dir: PathBuf, id: ComponentKey) {
+    match fs::remove_dir_all(&dir).await {
+        Ok(()) => info!(message = "Drained disk buffer left behind.", buffer_id = %id),
+        Err(error) => warn!(
+            message = "Failed removing drained disk buffer.",
+            buffer_id = %id,
+            path = ?dir,
+            %error,
+        ),
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     future::ready,
     num::NonZeroUsize,
+    path::PathBuf,
     sync::{Arc, Mutex},
     time::Instant,
 };
@@ -33,19 +34,29 @@ use vector_core::{
 };
 
 use super::{
+    accounting::{Accountant, Direction},
+    buffer_migration,
     fanout::{self, Fanout},
+    fault_injection::FaultInjector,
+    in_flight::InFlightBytes,
     schema,
+    source_dedupe_ids::DedupeIdStamper,
+    source_timestamps::TimestampParser,
+    source_wal::SourceWal,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
     config::{
         ComponentKey, DataType, EnrichmentTableConfig, Input, Inputs, OutputId, ProxyConfig,
-        SinkContext, SourceContext, TransformContext, TransformOuter, TransformOutput,
+        SinkContext, SinkOrdering, SourceContext, SourceWalConfig, TransformContext,
+        TransformOuter, TransformOutput,
     },
     event::{EventArray, EventContainer},
     internal_events::EventsReceived,
+    request_capture::RequestCapture,
     shutdown::SourceShutdownCoordinator,
+    sinks::util::service::RequestLimits,
     source_sender::CHUNK_SIZE,
     spawn_named,
     topology::task::TaskError,
@@ -207,6 +218,22 @@ impl<'a> Builder<'a> {
         &ENRICHMENT_TABLES
     }
 
+    /// Resolves the directory of the write-ahead log of a source output. The log itself is only
+    /// opened by the pump of the output, once the topology is running.
+    fn source_wal_dir(
+        &self,
+        key: &ComponentKey,
+        port: Option<&str>,
+        config: &SourceWalConfig,
+    ) -> crate::Result<PathBuf> {
+        Ok(self
+            .config
+            .global
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?
+            .join("wal")
+            .join(port.unwrap_or("_default")))
+    }
+
     async fn build_sources(&mut self) -> HashMap<ComponentKey, Task> {
         let mut source_tasks = HashMap::new();
 
@@ -240,9 +267,47 @@ impl<'a> Builder<'a> {
             let mut pumps = Vec::new();
             let mut controls = HashMap::new();
             let mut schema_definitions = HashMap::with_capacity(source_outputs.len());
+            let mut setup_failed = false;
 
             for output in source_outputs.into_iter() {
+                let wal = match &source.wal {
+                    Some(config) => {
+                        match self.source_wal_dir(key, output.port.as_deref(), config) {
+                            Ok(dir) => Some((dir, config.clone())),
+                            Err(error) => {
+                                self.errors.push(format!(
+                                    "Source \"{}\": Failed to open write-ahead log: {}",
+                                    key, error
+                                ));
+                                setup_failed = true;
+                                break;
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                let timestamps = match TimestampParser::new(
+                    source.timezone,
+                    source.timestamp_format.as_deref(),
+                    self.config.global.timezone(),
+                ) {
+                    Ok(timestamps) => timestamps,
+                    Err(error) => {
+                        self.errors.push(format!(
+                            "Source \"{}\": Invalid timestamp_format: {}",
+                            key, error
+                        ));
+                        setup_failed = true;
+                        break;
+                    }
+                };
+
+                let dedupe_ids = DedupeIdStamper::new(key, source.dedupe_id.as_ref());
+
                 let mut rx = builder.add_source_output(output.clone());
+                let faults = FaultInjector::new(key);
+                let accountant = Accountant::new(key, Direction::Ingested);
 
                 let (mut fanout, control) = Fanout::new();
                 let source = Arc::new(OutputId {
@@ -253,7 +318,62 @@ impl<'a> Builder<'a> {
                 let pump = async move {
                     debug!("Source pump starting.");
 
-                    while let Some(mut array) = rx.next().await {
+                    // The log is opened here rather than while building, as it waits for a
+                    // previous instance of the source to release it on reload.
+                    let mut wal = match wal {
+                        Some((dir, config)) => {
+                            let (wal, replay) = SourceWal::open(dir, &config).await.map_err(|e| {
+                                error!(message = "Failed to open source write-ahead log.", error = %e);
+                                TaskError::wrapped(e)
+                            })?;
+                            for mut array in replay {
+                                array.set_output_id(&source);
+                                fanout.send(array).await.map_err(|e| {
+                                    debug!("Source pump finished with an error.");
+                                    TaskError::wrapped(e)
+                                })?;
+                            }
+                            Some(wal)
+                        }
+                        None => None,
+                    };
+
+                    loop {
+                        // Events that errored downstream are read back from the log and sent
+                        // again in between the events of the source.
+                        let array = match wal.as_mut() {
+                            Some(wal) => tokio::select! {
+                                mut array = wal.next_retry() => {
+                                    array.set_output_id(&source);
+                                    fanout.send(array).await.map_err(|e| {
+                                        debug!("Source pump finished with an error.");
+                                        TaskError::wrapped(e)
+                                    })?;
+                                    continue;
+                                }
+                                array = rx.next() => array,
+                            },
+                            None => rx.next().await,
+                        };
+                        let Some(array) = array else {
+                            break;
+                        };
+                        let Some(mut array) = faults.inject(array).await else {
+                            continue;
+                        };
+                        if let Some(timestamps) = timestamps.as_ref() {
+                            timestamps.apply(&mut array);
+                        }
+                        if let Some(dedupe_ids) = dedupe_ids.as_ref() {
+                            dedupe_ids.apply(&mut array);
+                        }
+                        if let Some(wal) = wal.as_mut() {
+                            wal.append(&mut array).await.map_err(|e| {
+                                error!(message = "Failed to write to source write-ahead log.", error = %e);
+                                TaskError::wrapped(e)
+                            })?;
+                        }
+                        accountant.record(&array);
                         array.set_output_id(&source);
                         fanout.send(array).await.map_err(|e| {
                             debug!("Source pump finished with an error.");
@@ -280,6 +400,10 @@ impl<'a> Builder<'a> {
                 }
             }
 
+            if setup_failed {
+                continue;
+            }
+
             let (pump_error_tx, mut pump_error_rx) = oneshot::channel();
             let pump = async move {
                 debug!("Source pump supervisor starting.");
@@ -460,6 +584,11 @@ impl<'a> Builder<'a> {
                 schema_definitions,
                 merged_schema_definition: merged_definition.clone(),
                 schema: self.config.schema,
+                source_timezones: self
+                    .config
+                    .sources()
+                    .filter_map(|(key, source)| source.timezone.map(|tz| (key.clone(), tz)))
+                    .collect(),
             };
 
             let node = TransformNode::from_parts(
@@ -514,6 +643,7 @@ impl<'a> Builder<'a> {
 
             let typetag = sink.inner.get_component_name();
             let input_type = sink.inner.input().data_type();
+            let ordering = sink.ordering;
 
             // At this point, we've validated that all transforms are valid, including any
             // transform that mutates the schema provided by their sources. We can now validate the
@@ -527,6 +657,26 @@ impl<'a> Builder<'a> {
                 self.errors.append(&mut err);
             };
 
+            let limits_controller = match sink.request_limits.build() {
+                Err(error) => {
+                    self.errors.push(format!("Sink \"{}\": {}", key, error));
+                    continue;
+                }
+                Ok(controller) => controller,
+            };
+
+            // This runs before the buffer is built, as it can rename a disk buffer to the sink's.
+            let migrated =
+                match buffer_migration::migrate(key, sink, self.config.global.data_dir.as_deref())
+                    .await
+                {
+                    Err(error) => {
+                        self.errors.push(format!("Sink \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok(migrated) => migrated,
+                };
+
             let (tx, rx) = if let Some(buffer) = self.buffers.remove(key) {
                 buffer
             } else {
@@ -563,10 +713,13 @@ impl<'a> Builder<'a> {
                 healthcheck,
                 globals: self.config.global.clone(),
                 proxy: ProxyConfig::merge_with_env(&self.config.global.proxy, sink.proxy()),
+                connection_pool: sink.connection_pool,
                 schema: self.config.schema,
             };
 
-            let (sink, healthcheck) = match sink.inner.build(cx).await {
+            let build = RequestLimits::scope(key.clone(), sink.inner.build(cx));
+            let build = RequestCapture::scope(key.clone(), sink.debug, build);
+            let (sink, healthcheck) = match build.await {
                 Err(error) => {
                     self.errors.push(format!("Sink \"{}\": {}", key, error));
                     continue;
@@ -574,6 +727,30 @@ impl<'a> Builder<'a> {
                 Ok(built) => built,
             };
 
+            if ordering == SinkOrdering::Strict {
+                match RequestLimits::get(key) {
+                    Some(limits) => limits.set_strict_ordering(),
+                    None => warn!(
+                        message = "Sink doesn't support limiting its concurrency, only its buffer preserves the order of events.",
+                        component_id = %key.id(),
+                    ),
+                }
+            }
+
+            let limits_controller = limits_controller.and_then(|controller| {
+                let limits = RequestLimits::get(key);
+                if limits.is_none() {
+                    warn!(
+                        message = "Sink doesn't support request limits, `request_limits` is ignored.",
+                        component_id = %key.id(),
+                    );
+                }
+                limits.map(|limits| (controller, limits))
+            });
+            let limits_key = key.clone();
+            let faults = FaultInjector::new(key);
+            let accountant = Accountant::new(key, Direction::Delivered);
+
             let (trigger, tripwire) = Tripwire::new();
 
             let sink = async move {
@@ -593,27 +770,47 @@ impl<'a> Builder<'a> {
 
                 let mut rx = wrap(rx);
 
+                let limits_task = limits_controller.map(|(controller, limits)| {
+                    spawn_named(
+                        controller.run(limits_key, limits).in_current_span(),
+                        "request limits",
+                    )
+                });
+
                 let events_received = register!(EventsReceived);
-                sink.run(
-                    rx.by_ref()
-                        .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                        .inspect(|events| {
-                            events_received.emit(CountByteSize(
-                                events.len(),
-                                events.estimated_json_encoded_size_of(),
-                            ))
-                        })
-                        .take_until_if(tripwire),
-                )
-                .await
-                .map(|_| {
-                    debug!("Sink finished normally.");
-                    TaskOutput::Sink(rx)
-                })
-                .map_err(|_| {
-                    debug!("Sink finished with an error.");
-                    TaskError::Opaque
-                })
+                let in_flight = InFlightBytes::register();
+                let result = sink
+                    .run(
+                        faults
+                            .wrap(migrated.chain(rx.by_ref()))
+                            .filter(|events: &EventArray| {
+                                ready(filter_events_type(events, input_type))
+                            })
+                            .inspect(|events| {
+                                events_received.emit(CountByteSize(
+                                    events.len(),
+                                    events.estimated_json_encoded_size_of(),
+                                ));
+                                accountant.record(events);
+                            })
+                            .then(|events| in_flight.track(events))
+                            .take_until_if(tripwire),
+                    )
+                    .await;
+
+                if let Some(limits_task) = limits_task {
+                    limits_task.abort();
+                }
+
+                result
+                    .map(|_| {
+                        debug!("Sink finished normally.");
+                        TaskOutput::Sink(rx)
+                    })
+                    .map_err(|_| {
+                        debug!("Sink finished with an error.");
+                        TaskError::Opaque
+                    })
             };
 
             let task = Task::new(key.clone(), typetag, sink);
@@ -746,7 +943,8 @@ fn build_sync_transform(
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_details.data_type(), outputs);
+    let faults = FaultInjector::new(&node.key);
+    let runner = Runner::new(t, input_rx, faults, node.input_details.data_type(), outputs);
     let transform = if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
@@ -784,6 +982,7 @@ fn build_sync_transform(
 struct Runner {
     transform: Box<dyn SyncTransform>,
     input_rx: Option<BufferReceiver<EventArray>>,
+    faults: FaultInjector,
     input_type: DataType,
     outputs: TransformOutputs,
     timer: crate::utilization::Timer,
@@ -795,12 +994,14 @@ impl Runner {
     fn new(
         transform: Box<dyn SyncTransform>,
         input_rx: BufferReceiver<EventArray>,
+        faults: FaultInjector,
         input_type: DataType,
         outputs: TransformOutputs,
     ) -> Self {
         Self {
             transform,
             input_rx: Some(input_rx),
+            faults,
             input_type,
             outputs,
             timer: crate::utilization::Timer::new(),
@@ -834,10 +1035,14 @@ impl Runner {
         let mut outputs_buf = self.outputs.new_buf_with_capacity(INLINE_BATCH_SIZE);
 
         let mut input_rx = self
-            .input_rx
-            .take()
-            .expect("can't run runner twice")
-            .into_stream()
+            .faults
+            .clone()
+            .wrap(
+                self.input_rx
+                    .take()
+                    .expect("can't run runner twice")
+                    .into_stream(),
+            )
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
         self.timer.start_wait();
@@ -854,10 +1059,14 @@ impl Runner {
 
     async fn run_concurrently(mut self) -> TaskResult {
         let input_rx = self
-            .input_rx
-            .take()
-            .expect("can't run runner twice")
-            .into_stream()
+            .faults
+            .clone()
+            .wrap(
+                self.input_rx
+                    .take()
+                    .expect("can't run runner twice")
+                    .into_stream(),
+            )
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
         let mut input_rx =
@@ -929,7 +1138,7 @@ fn build_task_transform(
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (mut fanout, control) = Fanout::new();
 
-    let input_rx = crate::utilization::wrap(input_rx.into_stream());
+    let input_rx = crate::utilization::wrap(FaultInjector::new(key).wrap(input_rx.into_stream()));
 
     let events_received = register!(EventsReceived);
     let filtered = input_rx
@@ -14,7 +14,7 @@ use crate::config::enterprise::{
 use crate::internal_events::{
     VectorConfigLoadError, VectorRecoveryError, VectorReloadError, VectorReloaded,
 };
-use crate::{config, topology::RunningTopology};
+use crate::{audit, config, topology::RunningTopology};
 
 #[derive(Clone, Debug)]
 pub struct SharedTopologyController(Arc<Mutex<TopologyController>>);
@@ -64,6 +64,10 @@ impl TopologyController {
     pub async fn reload(&mut self, new_config: Option<config::Config>) -> ReloadOutcome {
         if new_config.is_none() {
             emit!(VectorConfigLoadError);
+            audit::record(audit::AuditAction::ConfigReloadFailed {
+                config_hash: None,
+                rolled_back: false,
+            });
             return ReloadOutcome::NoConfig;
         }
         let mut new_config = new_config.unwrap();
@@ -93,8 +97,13 @@ impl TopologyController {
             }
         }
 
+        let previous_hashes = audit::component_hashes(self.topology.config());
+        let config_hash = audit::config_hash(&new_config);
+
         match self.topology.reload_config_and_respawn(new_config).await {
             Ok(true) => {
+                audit::config_reloaded(&previous_hashes, self.topology.config());
+
                 #[cfg(feature = "api")]
                 // Pass the new config to the API server.
                 if let Some(ref api_server) = self.api_server {
@@ -108,12 +117,20 @@ impl TopologyController {
             }
             Ok(false) => {
                 emit!(VectorReloadError);
+                audit::record(audit::AuditAction::ConfigReloadFailed {
+                    config_hash: Some(config_hash),
+                    rolled_back: true,
+                });
                 ReloadOutcome::RolledBack
             }
             // Trigger graceful shutdown for what remains of the topology
             Err(()) => {
                 emit!(VectorReloadError);
                 emit!(VectorRecoveryError);
+                audit::record(audit::AuditAction::ConfigReloadFailed {
+                    config_hash: Some(config_hash),
+                    rolled_back: false,
+                });
                 ReloadOutcome::FatalError
             }
         }
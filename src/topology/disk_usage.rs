@@ -0,0 +1,144 @@
+//! Reporting of the disk space used in the data directory.
+//!
+//! Components keep their state in a directory named after them under the data directory, and
+//! their disk buffer under `buffer/v2`. The bytes used by each are reported periodically as the
+//! `component_disk_usage_bytes` gauge, along with the usage of the `data_dir_budget` global
+//! option when set.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::{task::JoinHandle, time::interval};
+use vector_buffers::disk_budget;
+
+use crate::internal_events::{ComponentDiskUsage, DataDirBudgetUsage};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+static REPORTER: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(Default::default);
+
+/// Starts reporting the disk space used in the data directory, replacing the previous reporter.
+///
+/// Global options can't change while reloading, so this is only done when a topology is started.
+pub fn apply(data_dir: Option<PathBuf>) {
+    let reporter = data_dir.map(|data_dir| tokio::spawn(report(data_dir)));
+    let previous = std::mem::replace(
+        &mut *REPORTER
+            .lock()
+            .expect("Disk usage reporter mutex is poisoned"),
+        reporter,
+    );
+    if let Some(previous) = previous {
+        previous.abort();
+    }
+}
+
+async fn report(data_dir: PathBuf) {
+    let mut interval = interval(REPORT_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let dir = data_dir.clone();
+        match tokio::task::spawn_blocking(move || usage(&dir)).await {
+            Ok(Ok(usage)) => {
+                for (component_id, kind, bytes) in usage {
+                    emit!(ComponentDiskUsage {
+                        component_id: &component_id,
+                        kind,
+                        bytes,
+                    });
+                }
+            }
+            Ok(Err(error)) => {
+                debug!(message = "Failed measuring disk usage of data directory.", %error);
+            }
+            Err(_) => return,
+        }
+
+        if let Some(budget) = disk_budget::current() {
+            emit!(DataDirBudgetUsage {
+                used_bytes: budget.used_bytes(),
+                max_bytes: budget.max_bytes(),
+            });
+        }
+    }
+}
+
+/// The bytes used by the state and the disk buffer of each component in the data directory.
+fn usage(data_dir: &Path) -> io::Result<Vec<(String, &'static str, u64)>> {
+    let mut usage = Vec::new();
+    for (dir, kind) in [
+        (data_dir.to_path_buf(), "state"),
+        (buffers_dir(data_dir), "buffer"),
+    ] {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !entry.file_type()?.is_dir() || (kind == "state" && name == "buffer") {
+                continue;
+            }
+            usage.push((name, kind, dir_size(&entry.path())?));
+        }
+    }
+    Ok(usage)
+}
+
+fn buffers_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("buffer").join("v2")
+}
+
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            // Files are deleted as buffers are read.
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        };
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_components() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let source_dir = data_dir.path().join("in");
+        fs::create_dir_all(source_dir.join("nested")).unwrap();
+        fs::write(source_dir.join("checkpoints.json"), [0; 10]).unwrap();
+        fs::write(source_dir.join("nested").join("wal"), [0; 5]).unwrap();
+        let buffer_dir = buffers_dir(data_dir.path()).join("out");
+        fs::create_dir_all(&buffer_dir).unwrap();
+        fs::write(buffer_dir.join("buffer-data-0.dat"), [0; 20]).unwrap();
+
+        let mut usage = usage(data_dir.path()).unwrap();
+        usage.sort();
+        assert_eq!(
+            usage,
+            vec![
+                ("in".to_owned(), "state", 15),
+                ("out".to_owned(), "buffer", 20),
+            ]
+        );
+    }
+}
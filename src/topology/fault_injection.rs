@@ -0,0 +1,281 @@
+//! Injection of latency, errors and stalls into running components.
+//!
+//! The faults of every component are held in a global registry, which is replaced by the
+//! `fault_injection` options each time a configuration is applied and can be changed through the
+//! API in between. Components look their faults up for every batch of events, so changes take
+//! effect without rebuilding them.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use futures::{Stream, StreamExt};
+use once_cell::sync::Lazy;
+use tokio::time::{sleep, Sleep};
+use vector_core::event::{EventArray, EventContainer, EventStatus, Finalizable};
+
+use crate::{
+    config::{
+        fault_injection::{ComponentFaults, Options},
+        ComponentKey,
+    },
+    internal_events::FaultInjectionEventsRejected,
+};
+
+/// How often a stalled component checks whether it can resume.
+const STALL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static REGISTRY: Lazy<Mutex<HashMap<ComponentKey, Arc<Mutex<ComponentFaults>>>>> =
+    Lazy::new(Default::default);
+
+fn slot(key: &ComponentKey) -> Arc<Mutex<ComponentFaults>> {
+    let mut registry = REGISTRY.lock().expect("Fault registry mutex is poisoned");
+    Arc::clone(registry.entry(key.clone()).or_default())
+}
+
+/// Replaces the faults of all components with the configured ones.
+pub fn apply(options: &Options) {
+    if options.enabled {
+        warn!("Fault injection is enabled. It must not be used in production.");
+    }
+
+    let mut registry = REGISTRY.lock().expect("Fault registry mutex is poisoned");
+    for (key, faults) in registry.iter() {
+        *faults.lock().expect("Faults mutex is poisoned") =
+            options.components.get(key).copied().unwrap_or_default();
+    }
+    for (key, faults) in &options.components {
+        registry
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(Mutex::new(*faults)));
+    }
+    ENABLED.store(options.enabled, Ordering::Relaxed);
+}
+
+/// Whether faults can be injected.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The faults currently injected into a component.
+pub fn get(key: &ComponentKey) -> ComponentFaults {
+    REGISTRY
+        .lock()
+        .expect("Fault registry mutex is poisoned")
+        .get(key)
+        .map(|faults| *faults.lock().expect("Faults mutex is poisoned"))
+        .unwrap_or_default()
+}
+
+/// Sets the faults injected into a component, until the next configuration is applied.
+///
+/// The caller must check that fault injection is enabled.
+pub fn set(key: &ComponentKey, faults: ComponentFaults) {
+    *slot(key).lock().expect("Faults mutex is poisoned") = faults;
+}
+
+/// Injects the faults of a component into the events flowing through it.
+#[derive(Clone, Debug)]
+pub struct FaultInjector {
+    faults: Arc<Mutex<ComponentFaults>>,
+}
+
+impl FaultInjector {
+    pub fn new(key: &ComponentKey) -> Self {
+        Self { faults: slot(key) }
+    }
+
+    /// The faults to inject, if any.
+    ///
+    /// Faults can only be set while fault injection is enabled, so they aren't checked against it.
+    fn current(&self) -> Option<ComponentFaults> {
+        let faults = *self.faults.lock().expect("Faults mutex is poisoned");
+        (!faults.is_empty()).then_some(faults)
+    }
+
+    fn stalled(&self) -> bool {
+        self.current().map_or(false, |faults| faults.stall)
+    }
+
+    /// Rejects the events at the configured error rate.
+    fn reject(faults: &ComponentFaults, mut array: EventArray) -> Option<EventArray> {
+        if faults.error_rate > 0.0 && rand::random::<f64>() < faults.error_rate {
+            array.take_finalizers().update_status(EventStatus::Rejected);
+            emit!(FaultInjectionEventsRejected { count: array.len() });
+            None
+        } else {
+            Some(array)
+        }
+    }
+
+    /// Waits while the component is stalled, and delays or rejects the events.
+    pub async fn inject(&self, array: EventArray) -> Option<EventArray> {
+        while self.stalled() {
+            sleep(STALL_CHECK_INTERVAL).await;
+        }
+        match self.current() {
+            None => Some(array),
+            Some(faults) => {
+                if faults.latency_ms > 0 {
+                    sleep(Duration::from_millis(faults.latency_ms)).await;
+                }
+                Self::reject(&faults, array)
+            }
+        }
+    }
+
+    /// Wraps a stream of events, injecting faults before they're yielded.
+    ///
+    /// A stalled stream stops polling its inner stream.
+    pub const fn wrap<S>(self, inner: S) -> FaultInjected<S> {
+        FaultInjected {
+            injector: self,
+            inner,
+            pending: None,
+            delay: None,
+        }
+    }
+}
+
+pub struct FaultInjected<S> {
+    injector: FaultInjector,
+    inner: S,
+    pending: Option<EventArray>,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> FaultInjected<S> {
+    /// Consumes this wrapper and returns the inner stream.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> Stream for FaultInjected<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(delay) = this.delay.as_mut() {
+                ready!(delay.as_mut().poll(cx));
+                this.delay = None;
+            }
+
+            let Some(faults) = this.injector.current() else {
+                // Events delayed before the faults were cleared are released first.
+                if let Some(array) = this.pending.take() {
+                    return Poll::Ready(Some(array));
+                }
+                return this.inner.poll_next_unpin(cx);
+            };
+
+            if faults.stall {
+                this.delay = Some(Box::pin(sleep(STALL_CHECK_INTERVAL)));
+                continue;
+            }
+
+            let array = match this.pending.take() {
+                // Already delayed.
+                Some(array) => array,
+                None => match ready!(this.inner.poll_next_unpin(cx)) {
+                    None => return Poll::Ready(None),
+                    Some(array) if faults.latency_ms > 0 => {
+                        this.pending = Some(array);
+                        this.delay =
+                            Some(Box::pin(sleep(Duration::from_millis(faults.latency_ms))));
+                        continue;
+                    }
+                    Some(array) => array,
+                },
+            };
+
+            if let Some(array) = FaultInjector::reject(&faults, array) {
+                return Poll::Ready(Some(array));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use tokio::time::Instant;
+    use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+
+    // The registry is shared by every test running a topology, so these use their own faults.
+    fn injector(faults: ComponentFaults) -> FaultInjector {
+        FaultInjector {
+            faults: Arc::new(Mutex::new(faults)),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delays_events() {
+        let injector = injector(ComponentFaults {
+            latency_ms: 500,
+            ..Default::default()
+        });
+
+        let start = Instant::now();
+        let events = injector
+            .wrap(stream::iter(vec![
+                EventArray::from(LogEvent::from("a")),
+                EventArray::from(LogEvent::from("b")),
+            ]))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(start.elapsed(), Duration::from_millis(1000));
+    }
+
+    #[tokio::test]
+    async fn rejects_events() {
+        let injector = injector(ComponentFaults {
+            error_rate: 1.0,
+            ..Default::default()
+        });
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let array = EventArray::from(LogEvent::from("a").with_batch_notifier(&batch));
+        drop(batch);
+
+        assert!(injector.inject(array).await.is_none());
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn resumes_stalled_components() {
+        let injector = injector(ComponentFaults {
+            stall: true,
+            ..Default::default()
+        });
+        let faults = Arc::clone(&injector.faults);
+
+        let mut stream = injector.wrap(stream::iter(vec![EventArray::from(LogEvent::from("a"))]));
+        let handle = tokio::spawn(async move { stream.next().await });
+
+        sleep(Duration::from_secs(1)).await;
+        assert!(!handle.is_finished());
+
+        *faults.lock().unwrap() = ComponentFaults::default();
+        assert!(handle.await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,155 @@
+//! Limiting of the bytes of events held by sinks.
+//!
+//! Buffers bound the events waiting for a sink, but not those the sink took from its buffer to
+//! batch, encode and send. Events are tracked from when a sink takes them until it is done with
+//! them, once delivered, rejected or dropped, which is when their finalizers are dropped. The
+//! total across all sinks is capped by the `sink_in_flight_bytes_limit` global option.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use once_cell::sync::Lazy;
+use tokio::sync::Semaphore;
+use vector_common::{
+    finalization::{AddBatchNotifier, BatchNotifier},
+    internal_event::InternalEventHandle as _,
+};
+use vector_core::{event::EventArray, ByteSizeOf};
+
+use crate::internal_events::{SinkInFlightBytes, SinkInFlightBytesHandle};
+
+static LIMITER: Lazy<RwLock<Option<Arc<Limiter>>>> = Lazy::new(|| RwLock::new(None));
+
+struct Limiter {
+    limit: usize,
+    permits: Arc<Semaphore>,
+}
+
+/// Applies the limit of the bytes held by all sinks.
+///
+/// Global options can't change while reloading, so this only takes effect for topologies started
+/// afterwards.
+pub fn apply(limit: Option<NonZeroUsize>) {
+    let limiter = limit.map(|limit| {
+        let limit = limit.get().min(Semaphore::MAX_PERMITS);
+        Arc::new(Limiter {
+            limit,
+            permits: Arc::new(Semaphore::new(limit)),
+        })
+    });
+    *LIMITER.write().expect("In-flight limiter lock is poisoned") = limiter;
+}
+
+/// Tracks the bytes of events held by a sink.
+pub struct InFlightBytes {
+    limiter: Option<Arc<Limiter>>,
+    bytes: Arc<AtomicUsize>,
+    gauge: SinkInFlightBytesHandle,
+}
+
+impl InFlightBytes {
+    /// Must be created within the span of the sink, for the gauge to be tagged with it.
+    pub fn register() -> Self {
+        Self {
+            limiter: LIMITER
+                .read()
+                .expect("In-flight limiter lock is poisoned")
+                .clone(),
+            bytes: Arc::default(),
+            gauge: register!(SinkInFlightBytes),
+        }
+    }
+
+    /// Waits for the bytes of the events to fit within the limit, and holds them until the sink
+    /// is done with all of the events.
+    pub async fn track(&self, mut events: EventArray) -> EventArray {
+        let limiter = match &self.limiter {
+            Some(limiter) => limiter,
+            None => return events,
+        };
+
+        let bytes = events.size_of();
+        // Arrays larger than the limit are let through alone, rather than waiting forever.
+        let permits = bytes.min(limiter.limit).min(u32::MAX as usize) as u32;
+        let permit = Arc::clone(&limiter.permits)
+            .acquire_many_owned(permits)
+            .await
+            .expect("In-flight semaphore is never closed");
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        events.add_batch_notifier(batch);
+
+        let held = self.bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.gauge.emit(held);
+
+        let tracked = Arc::clone(&self.bytes);
+        let gauge = self.gauge.clone();
+        tokio::spawn(async move {
+            receiver.await;
+            drop(permit);
+            let held = tracked.fetch_sub(bytes, Ordering::Relaxed) - bytes;
+            gauge.emit(held);
+        });
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn tracker(limit: usize) -> InFlightBytes {
+        InFlightBytes {
+            limiter: Some(Arc::new(Limiter {
+                limit,
+                permits: Arc::new(Semaphore::new(limit)),
+            })),
+            bytes: Arc::default(),
+            gauge: register!(SinkInFlightBytes),
+        }
+    }
+
+    fn events() -> EventArray {
+        vec![LogEvent::from("a message of some size")].into()
+    }
+
+    #[tokio::test]
+    async fn waits_for_held_events() {
+        let size = events().size_of();
+        let tracker = tracker(size * 2);
+
+        let first = tracker.track(events()).await;
+        let second = tracker.track(events()).await;
+        assert_eq!(tracker.bytes.load(Ordering::Relaxed), size * 2);
+
+        // The limit is reached until the sink is done with the events of an array.
+        let third = tracker.track(events());
+        tokio::pin!(third);
+        assert!(tokio::time::timeout(Duration::from_millis(50), &mut third)
+            .await
+            .is_err());
+
+        drop(first);
+        let _third = third.await;
+        drop(second);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(tracker.bytes.load(Ordering::Relaxed), size);
+    }
+
+    #[tokio::test]
+    async fn lets_large_arrays_through() {
+        let tracker = tracker(1);
+        let events = tracker.track(events()).await;
+        assert!(events.size_of() > 1);
+    }
+}
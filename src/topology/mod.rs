@@ -10,10 +10,19 @@
 pub(super) use vector_core::fanout;
 pub mod schema;
 
+pub mod accounting;
+mod buffer_migration;
 pub mod builder;
 mod controller;
+mod disk_usage;
+pub mod fault_injection;
+mod in_flight;
 mod ready_arrays;
 mod running;
+pub mod scaling;
+mod source_dedupe_ids;
+mod source_timestamps;
+mod source_wal;
 mod task;
 
 #[cfg(test)]
@@ -109,6 +118,18 @@ pub async fn start_validated(
         return None;
     }
 
+    if let Err(error) = crate::dns::apply(&config.global.dns) {
+        error!(message = "Invalid DNS configuration.", %error);
+        return None;
+    }
+
+    fault_injection::apply(&config.fault_injection);
+    accounting::apply(&config.accounting);
+    scaling::apply(&config.scaling, &config.global.proxy);
+    in_flight::apply(config.global.sink_in_flight_bytes_limit);
+    vector_buffers::disk_budget::apply(config.global.data_dir_budget);
+    disk_usage::apply(config.global.data_dir.clone());
+
     let mut running_topology = RunningTopology::new(config, abort_tx.clone());
 
     if !running_topology
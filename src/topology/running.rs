@@ -22,10 +22,10 @@ use crate::{
     shutdown::SourceShutdownCoordinator,
     spawn_named,
     topology::{
-        build_or_log_errors, builder,
+        accounting, build_or_log_errors, builder,
         builder::Pieces,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
+        fault_injection, handle_errors, retain, scaling, take_healthchecks,
         task::TaskOutput,
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
@@ -259,6 +259,9 @@ impl RunningTopology {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
+                fault_injection::apply(&self.config.fault_injection);
+                accounting::apply(&self.config.accounting);
+                scaling::apply(&self.config.scaling, &self.config.global.proxy);
 
                 info!("New configuration loaded successfully.");
 
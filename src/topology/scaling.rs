@@ -0,0 +1,381 @@
+//! Scaling signals.
+//!
+//! The internal metrics of buffers and sources are summarized on an interval into signals that an
+//! autoscaler can act on: how full the buffer of each component is, how fast it is filling up and
+//! when it is expected to be full, and how late the events received by each source are. The latest
+//! signals are exposed through the API, and sent to the configured webhook.
+
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http::{header::CONTENT_TYPE, Request};
+use hyper::Body;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use vector_core::{config::proxy::ProxyConfig, tls::TlsSettings};
+
+use crate::{
+    config::scaling::{Options, WebhookOptions},
+    event::{Metric, MetricValue},
+    http::HttpClient,
+    internal_events::{ScalingComponentSignals, ScalingSaturation, ScalingWebhookError},
+};
+
+static TASK: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(Default::default);
+
+static LATEST: Lazy<Mutex<Option<Signals>>> = Lazy::new(Default::default);
+
+/// The signals computed from the internal metrics.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Signals {
+    pub timestamp: DateTime<Utc>,
+
+    /// The highest buffer fill ratio of any component, between `0.0` and `1.0`.
+    pub saturation: f64,
+
+    /// The shortest time until the buffer of a component is full, if any is filling up.
+    pub time_to_full_seconds: Option<f64>,
+
+    /// The highest lag of the events received by any source.
+    pub source_lag_seconds: Option<f64>,
+
+    /// The signals of each component, keyed by component ID.
+    pub components: BTreeMap<String, ComponentSignals>,
+}
+
+/// The signals of a component.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ComponentSignals {
+    /// How full the buffer of the component is, between `0.0` and `1.0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer_fill_ratio: Option<f64>,
+
+    /// How fast the fill ratio of the buffer changed over the interval, per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer_fill_rate: Option<f64>,
+
+    /// The time until the buffer is full at its current fill rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer_time_to_full_seconds: Option<f64>,
+
+    /// The mean lag of the events received by the source over the interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_lag_seconds: Option<f64>,
+}
+
+/// Starts computing scaling signals, replacing the previous task.
+pub fn apply(options: &Options, proxy: &ProxyConfig) {
+    let task = options.enabled.then(|| {
+        let interval = Duration::from_secs(options.interval_secs.get());
+        let webhook =
+            options
+                .webhook
+                .clone()
+                .and_then(|webhook| match Webhook::new(webhook, proxy) {
+                    Ok(webhook) => Some(webhook),
+                    Err(error) => {
+                        emit!(ScalingWebhookError {
+                            error: &error.to_string()
+                        });
+                        None
+                    }
+                });
+        tokio::spawn(run(interval, webhook))
+    });
+
+    let previous = std::mem::replace(
+        &mut *TASK.lock().expect("Scaling task mutex is poisoned"),
+        task,
+    );
+    if let Some(previous) = previous {
+        previous.abort();
+    }
+    if !options.enabled {
+        *LATEST.lock().expect("Scaling signals mutex is poisoned") = None;
+    }
+}
+
+/// Gets the latest signals, if scaling signals are enabled.
+pub fn latest() -> Option<Signals> {
+    LATEST
+        .lock()
+        .expect("Scaling signals mutex is poisoned")
+        .clone()
+}
+
+async fn run(interval: Duration, webhook: Option<Webhook>) {
+    let controller = match crate::metrics::Controller::get() {
+        Ok(controller) => controller,
+        Err(error) => {
+            warn!(message = "Scaling signals can't be computed without internal metrics.", %error);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(interval);
+    let mut previous: Option<(Instant, Sample)> = None;
+    loop {
+        interval.tick().await;
+
+        let now = Instant::now();
+        let sample = Sample::new(controller.capture_metrics());
+        let signals = signals(
+            previous
+                .as_ref()
+                .map(|(then, sample)| (now - *then, sample)),
+            &sample,
+        );
+        previous = Some((now, sample));
+
+        emit!(ScalingSaturation {
+            saturation: signals.saturation
+        });
+        for (component_id, component) in &signals.components {
+            emit!(ScalingComponentSignals {
+                component_id,
+                buffer_fill_ratio: component.buffer_fill_ratio,
+                buffer_fill_rate: component.buffer_fill_rate,
+                buffer_time_to_full_seconds: component.buffer_time_to_full_seconds,
+                source_lag_seconds: component.source_lag_seconds,
+            });
+        }
+        *LATEST.lock().expect("Scaling signals mutex is poisoned") = Some(signals.clone());
+
+        if let Some(webhook) = &webhook {
+            if let Err(error) = webhook.send(&signals).await {
+                emit!(ScalingWebhookError { error: &error });
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct BufferUsage {
+    events: f64,
+    bytes: f64,
+    max_events: f64,
+    max_bytes: f64,
+}
+
+impl BufferUsage {
+    /// The fill ratio of the buffer, by whichever of its limits is the closest to being reached.
+    fn fill_ratio(&self) -> Option<f64> {
+        let events = (self.max_events > 0.0).then(|| self.events / self.max_events);
+        let bytes = (self.max_bytes > 0.0).then(|| self.bytes / self.max_bytes);
+        match (events, bytes) {
+            (Some(events), Some(bytes)) => Some(events.max(bytes)),
+            (ratio, None) | (None, ratio) => ratio,
+        }
+        .map(|ratio| ratio.clamp(0.0, 1.0))
+    }
+}
+
+/// The metrics the signals are computed from, keyed by component ID.
+#[derive(Debug, Default)]
+struct Sample {
+    buffers: BTreeMap<String, BufferUsage>,
+    /// The sum and count of the lag times of the events received by sources.
+    lags: BTreeMap<String, (f64, u64)>,
+}
+
+impl Sample {
+    fn new(metrics: Vec<Metric>) -> Self {
+        let mut sample = Self::default();
+        for metric in metrics {
+            let Some(component_id) = metric.tag_value("component_id") else {
+                continue;
+            };
+            // The stages of a buffer are added up.
+            match (metric.name(), metric.value()) {
+                ("buffer_events", MetricValue::Gauge { value }) => {
+                    sample.buffers.entry(component_id).or_default().events += value;
+                }
+                ("buffer_byte_size", MetricValue::Gauge { value }) => {
+                    sample.buffers.entry(component_id).or_default().bytes += value;
+                }
+                ("buffer_max_event_size", MetricValue::Gauge { value }) => {
+                    sample.buffers.entry(component_id).or_default().max_events += value;
+                }
+                ("buffer_max_byte_size", MetricValue::Gauge { value }) => {
+                    sample.buffers.entry(component_id).or_default().max_bytes += value;
+                }
+                (
+                    "source_lag_time_seconds",
+                    MetricValue::AggregatedHistogram { count, sum, .. },
+                ) => {
+                    let lag = sample.lags.entry(component_id).or_default();
+                    lag.0 += sum;
+                    lag.1 += count;
+                }
+                _ => {}
+            }
+        }
+        sample
+    }
+}
+
+/// Computes the signals of a sample, with rates over the time elapsed since the previous sample.
+fn signals(previous: Option<(Duration, &Sample)>, sample: &Sample) -> Signals {
+    let mut components = BTreeMap::<String, ComponentSignals>::new();
+
+    for (component_id, usage) in &sample.buffers {
+        let Some(ratio) = usage.fill_ratio() else {
+            continue;
+        };
+        let rate = previous.and_then(|(elapsed, previous)| {
+            let previous = previous.buffers.get(component_id)?.fill_ratio()?;
+            Some((ratio - previous) / elapsed.as_secs_f64().max(f64::EPSILON))
+        });
+        let component = components.entry(component_id.clone()).or_default();
+        component.buffer_fill_ratio = Some(ratio);
+        component.buffer_fill_rate = rate;
+        component.buffer_time_to_full_seconds = rate
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| (1.0 - ratio) / rate);
+    }
+
+    if let Some((_, previous)) = previous {
+        for (component_id, (sum, count)) in &sample.lags {
+            let (previous_sum, previous_count) =
+                previous.lags.get(component_id).copied().unwrap_or_default();
+            if *count > previous_count {
+                components
+                    .entry(component_id.clone())
+                    .or_default()
+                    .source_lag_seconds =
+                    Some(((sum - previous_sum) / (count - previous_count) as f64).max(0.0));
+            }
+        }
+    }
+
+    Signals {
+        timestamp: Utc::now(),
+        saturation: components
+            .values()
+            .filter_map(|c| c.buffer_fill_ratio)
+            .reduce(f64::max)
+            .unwrap_or(0.0),
+        time_to_full_seconds: components
+            .values()
+            .filter_map(|c| c.buffer_time_to_full_seconds)
+            .reduce(f64::min),
+        source_lag_seconds: components
+            .values()
+            .filter_map(|c| c.source_lag_seconds)
+            .reduce(f64::max),
+        components,
+    }
+}
+
+struct Webhook {
+    client: HttpClient,
+    options: WebhookOptions,
+}
+
+impl Webhook {
+    fn new(options: WebhookOptions, proxy: &ProxyConfig) -> crate::Result<Self> {
+        let client = HttpClient::new(TlsSettings::from_options(&None)?, proxy)?;
+        Ok(Self { client, options })
+    }
+
+    async fn send(&self, signals: &Signals) -> Result<(), String> {
+        let body = serde_json::to_vec(signals).map_err(|error| error.to_string())?;
+        let mut request = Request::post(&self.options.uri)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(Bytes::from(body)))
+            .map_err(|error| error.to_string())?;
+        if let Some(auth) = &self.options.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|error| error.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("Webhook responded with {}.", response.status()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_gauge(name: &str, component_id: &str, value: f64) -> Metric {
+        Metric::new(
+            name,
+            crate::event::MetricKind::Absolute,
+            MetricValue::Gauge { value },
+        )
+        .with_tags(Some(crate::event::metric::MetricTags::from([(
+            "component_id".to_owned(),
+            component_id.to_owned(),
+        )])))
+    }
+
+    fn buffer(component_id: &str, events: f64, max_events: f64) -> Vec<Metric> {
+        vec![
+            buffer_gauge("buffer_events", component_id, events),
+            buffer_gauge("buffer_max_event_size", component_id, max_events),
+        ]
+    }
+
+    #[test]
+    fn computes_fill_ratio_rate_and_time_to_full() {
+        let previous = Sample::new(buffer("out", 100.0, 1000.0));
+        let sample = Sample::new(
+            buffer("out", 300.0, 1000.0)
+                .into_iter()
+                .chain(buffer("other", 50.0, 100.0))
+                .collect(),
+        );
+        let signals = signals(Some((Duration::from_secs(10), &previous)), &sample);
+
+        let out = &signals.components["out"];
+        assert_eq!(out.buffer_fill_ratio, Some(0.3));
+        assert!((out.buffer_fill_rate.unwrap() - 0.02).abs() < 1e-9);
+        assert!((out.buffer_time_to_full_seconds.unwrap() - 35.0).abs() < 1e-6);
+
+        let other = &signals.components["other"];
+        assert_eq!(other.buffer_fill_ratio, Some(0.5));
+        assert_eq!(other.buffer_fill_rate, None);
+
+        assert_eq!(signals.saturation, 0.5);
+        assert!((signals.time_to_full_seconds.unwrap() - 35.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn draining_buffer_has_no_time_to_full() {
+        let previous = Sample::new(buffer("out", 500.0, 1000.0));
+        let sample = Sample::new(buffer("out", 200.0, 1000.0));
+        let signals = signals(Some((Duration::from_secs(10), &previous)), &sample);
+
+        let out = &signals.components["out"];
+        assert!(out.buffer_fill_rate.unwrap() < 0.0);
+        assert_eq!(out.buffer_time_to_full_seconds, None);
+        assert_eq!(signals.time_to_full_seconds, None);
+    }
+
+    #[test]
+    fn computes_source_lag_over_interval() {
+        let mut previous = Sample::default();
+        previous.lags.insert("in".to_owned(), (10.0, 10));
+        let mut sample = Sample::default();
+        sample.lags.insert("in".to_owned(), (40.0, 20));
+
+        let signals = signals(Some((Duration::from_secs(10), &previous)), &sample);
+        assert_eq!(signals.components["in"].source_lag_seconds, Some(3.0));
+        assert_eq!(signals.source_lag_seconds, Some(3.0));
+        assert_eq!(signals.saturation, 0.0);
+    }
+}
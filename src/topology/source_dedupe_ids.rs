@@ -0,0 +1,187 @@
+//! Stamping of deterministic IDs on events emitted by sources, driven by the per-source
+//! `dedupe_id` option.
+
+use lookup::lookup_v2::ConfigTargetPath;
+use serde::Serialize;
+
+use crate::{
+    config::{ComponentKey, DedupeIdConfig},
+    event::{EventArray, Value},
+};
+
+/// Stamps events with an ID derived from the fields identifying them.
+///
+/// An event read again by the source, after its delivery failed or Vector restarted, gets the same
+/// ID as the first time, so that sinks writing to idempotent destinations can have them discard it.
+pub(super) struct DedupeIdStamper {
+    source: String,
+    fields: Vec<ConfigTargetPath>,
+}
+
+impl DedupeIdStamper {
+    /// Builds a stamper for the events of the `source`, or returns `None` if it has no `dedupe_id`
+    /// option.
+    pub(super) fn new(source: &ComponentKey, config: Option<&DedupeIdConfig>) -> Option<Self> {
+        config.map(|config| Self {
+            source: source.id().to_owned(),
+            fields: config.fields.clone(),
+        })
+    }
+
+    /// Stamps the events of the array that can be identified, replacing any ID they already had.
+    ///
+    /// Log and trace events are identified by the configured fields, and aren't stamped without
+    /// them, as distinct events can have the same content. Metric events are identified by their
+    /// series and timestamp, and aren't stamped without a timestamp.
+    pub(super) fn apply(&self, array: &mut EventArray) {
+        match array {
+            EventArray::Logs(logs) if !self.fields.is_empty() => {
+                for log in logs.iter_mut() {
+                    let id = self.hash(&self.field_values(|path| log.get(path)));
+                    log.metadata_mut().set_dedupe_id(id);
+                }
+            }
+            EventArray::Traces(traces) if !self.fields.is_empty() => {
+                for trace in traces.iter_mut() {
+                    let id = self.hash(&self.field_values(|path| trace.get(path)));
+                    trace.metadata_mut().set_dedupe_id(id);
+                }
+            }
+            EventArray::Metrics(metrics) => {
+                for metric in metrics.iter_mut() {
+                    if let Some(timestamp) = metric.timestamp() {
+                        let id = self.hash(&(metric.series(), metric.value(), timestamp));
+                        metric.metadata_mut().set_dedupe_id(id);
+                    }
+                }
+            }
+            EventArray::Logs(_) | EventArray::Traces(_) => {}
+        }
+    }
+
+    fn field_values<'a>(
+        &'a self,
+        get: impl Fn(&'a ConfigTargetPath) -> Option<&'a Value>,
+    ) -> Vec<(String, Option<&'a Value>)> {
+        self.fields
+            .iter()
+            .map(|path| (path.0.to_string(), get(path)))
+            .collect()
+    }
+
+    /// Hashes the source ID and the content into a 128-bit ID, formatted as hexadecimal.
+    fn hash(&self, content: &impl Serialize) -> String {
+        let mut bytes = self.source.as_bytes().to_vec();
+        bytes.push(0);
+        serde_json::to_writer(&mut bytes, content).expect("Serializing a value can't fail.");
+        format!(
+            "{:016x}{:016x}",
+            seahash::hash(&bytes),
+            seahash::hash_seeded(&bytes, 1, 2, 3, 4)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    fn stamper(source: &str, fields: &[&str]) -> DedupeIdStamper {
+        let config = DedupeIdConfig {
+            fields: fields
+                .iter()
+                .map(|field| ConfigTargetPath::try_from(field.to_string()).unwrap())
+                .collect(),
+        };
+        DedupeIdStamper::new(&ComponentKey::from(source), Some(&config)).unwrap()
+    }
+
+    fn log_id(stamper: &DedupeIdStamper, log: LogEvent) -> Option<String> {
+        let mut array = EventArray::from(log);
+        stamper.apply(&mut array);
+        let EventArray::Logs(logs) = array else {
+            unreachable!()
+        };
+        logs[0].metadata().dedupe_id().map(ToOwned::to_owned)
+    }
+
+    fn log(message: &str, offset: i64) -> LogEvent {
+        let mut log = LogEvent::from(message);
+        log.insert("offset", offset);
+        log
+    }
+
+    #[test]
+    fn disabled_without_option() {
+        assert!(DedupeIdStamper::new(&ComponentKey::from("in"), None).is_none());
+    }
+
+    #[test]
+    fn same_fields_same_id() {
+        let source = stamper("in", &["offset"]);
+        let id = log_id(&source, log("hello", 0)).unwrap();
+        assert_eq!(id.len(), 32);
+        assert_eq!(Some(id.clone()), log_id(&source, log("hello", 0)));
+
+        assert_ne!(Some(id.clone()), log_id(&source, log("hello", 1)));
+        assert_ne!(
+            Some(id),
+            log_id(&stamper("other", &["offset"]), log("hello", 0))
+        );
+    }
+
+    #[test]
+    fn hashes_configured_fields() {
+        let source = stamper("in", &["offset"]);
+        let mut other = log("world", 0);
+        other.insert("host", "example.com");
+
+        assert_eq!(log_id(&source, log("hello", 0)), log_id(&source, other));
+        assert_ne!(
+            log_id(&source, log("hello", 0)),
+            log_id(&source, log("hello", 1))
+        );
+    }
+
+    #[test]
+    fn leaves_logs_without_fields() {
+        // Identical lines are distinct events, which must not be deduplicated.
+        assert_eq!(log_id(&stamper("in", &[]), log("hello", 0)), None);
+    }
+
+    #[test]
+    fn stamps_metrics_with_timestamp() {
+        let source = stamper("in", &[]);
+        let timestamp = Utc::now();
+        let metric = |value, timestamp| {
+            Metric::new(
+                "requests",
+                MetricKind::Absolute,
+                MetricValue::Counter { value },
+            )
+            .with_timestamp(timestamp)
+        };
+        let id = |metric: Metric| {
+            let mut array = EventArray::from(metric);
+            source.apply(&mut array);
+            let EventArray::Metrics(metrics) = array else {
+                unreachable!()
+            };
+            metrics[0].metadata().dedupe_id().map(ToOwned::to_owned)
+        };
+
+        assert!(id(metric(1.0, Some(timestamp))).is_some());
+        assert_eq!(
+            id(metric(1.0, Some(timestamp))),
+            id(metric(1.0, Some(timestamp)))
+        );
+        assert_ne!(
+            id(metric(1.0, Some(timestamp))),
+            id(metric(2.0, Some(timestamp)))
+        );
+        assert_eq!(id(metric(1.0, None)), None);
+    }
+}
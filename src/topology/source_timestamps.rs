@@ -0,0 +1,138 @@
+//! Parsing of string timestamps emitted by sources, driven by the per-source `timezone` and
+//! `timestamp_format` options.
+
+use vector_common::{conversion::Conversion, TimeZone};
+
+use crate::event::{EventArray, Value};
+
+/// Converts string timestamps on log events into proper timestamps.
+///
+/// Sources and decoders that cannot determine the type of the timestamp field, such as the `json`
+/// codec, pass it through as a string. This parses those strings as they leave the source, so that
+/// every pipeline fed by the source sees the same timestamps without repeating the parsing in VRL.
+pub(super) struct TimestampParser {
+    conversion: Conversion,
+}
+
+impl TimestampParser {
+    /// Builds a parser from the source options, or returns `None` if neither option is set.
+    ///
+    /// Timestamps without zone information are interpreted in `timezone`, falling back to
+    /// `global_timezone`. Without a `format`, the common timestamp formats are tried in turn.
+    pub(super) fn new(
+        timezone: Option<TimeZone>,
+        format: Option<&str>,
+        global_timezone: TimeZone,
+    ) -> crate::Result<Option<Self>> {
+        if timezone.is_none() && format.is_none() {
+            return Ok(None);
+        }
+
+        let timezone = timezone.unwrap_or(global_timezone);
+        let conversion = match format {
+            Some(format) => Conversion::parse(format!("timestamp|{}", format), timezone)
+                .map_err(|error| error.to_string())?,
+            None => Conversion::Timestamp(timezone),
+        };
+        Ok(Some(Self { conversion }))
+    }
+
+    /// Parses the timestamp of every log event in the array that is still a string.
+    ///
+    /// Timestamps that fail to parse are left untouched.
+    pub(super) fn apply(&self, array: &mut EventArray) {
+        let EventArray::Logs(logs) = array else {
+            return;
+        };
+
+        for log in logs.iter_mut() {
+            let Some(path) = log.timestamp_path() else {
+                continue;
+            };
+            let parsed = match log.get(path.as_str()) {
+                Some(Value::Bytes(bytes)) => self.conversion.convert::<Value>(bytes.clone()).ok(),
+                _ => None,
+            };
+            if let Some(timestamp @ Value::Timestamp(_)) = parsed {
+                log.insert(path.as_str(), timestamp);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn utc(timestamp: &str) -> Value {
+        Value::Timestamp(
+            DateTime::parse_from_rfc3339(timestamp)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[test]
+    fn disabled_without_options() {
+        assert!(TimestampParser::new(None, None, TimeZone::Local)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn applies_source_timezone() {
+        let timezone = TimeZone::parse("America/New_York");
+        let parser = TimestampParser::new(timezone, None, TimeZone::Local)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            parser
+                .conversion
+                .convert::<Value>(Bytes::from("2023-01-15 10:00:00"))
+                .ok(),
+            Some(utc("2023-01-15T15:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn applies_timestamp_format() {
+        let parser = TimestampParser::new(
+            None,
+            Some("%d/%b/%Y:%H:%M:%S"),
+            TimeZone::parse("UTC").unwrap(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let mut log = LogEvent::from("hello");
+        log.insert("timestamp", "15/Jan/2023:10:00:00");
+        let mut array = EventArray::from(log);
+        parser.apply(&mut array);
+
+        let EventArray::Logs(logs) = array else {
+            panic!("expected logs");
+        };
+        assert_eq!(logs[0]["timestamp"], utc("2023-01-15T10:00:00Z"));
+    }
+
+    #[test]
+    fn leaves_unparseable_timestamps() {
+        let parser = TimestampParser::new(None, Some("%Y-%m-%d"), TimeZone::Local)
+            .unwrap()
+            .unwrap();
+
+        let mut log = LogEvent::from("hello");
+        log.insert("timestamp", "yesterday");
+        let mut array = EventArray::from(log);
+        parser.apply(&mut array);
+
+        let EventArray::Logs(logs) = array else {
+            panic!("expected logs");
+        };
+        assert_eq!(logs[0]["timestamp"], "yesterday".into());
+    }
+}
@@ -0,0 +1,771 @@
+//! Source write-ahead log.
+//!
+//! Each source output with a configured WAL owns a directory of segment files. Every event array
+//! received from the source is appended to the active segment, and synced to disk, before it is
+//! sent to the fanout. A batch notifier is attached to the array so that we learn when it has been
+//! finalized downstream.
+//!
+//! Records are identified by a monotonically increasing ID. The highest ID below which every
+//! record has been finalized -- the acknowledged watermark -- is persisted alongside the segments,
+//! and a segment is deleted once all of its records fall below the watermark. On startup, every
+//! record above the watermark is replayed before the source's own events.
+//!
+//! Records whose events were rejected are finalized too, and their events counted as dropped.
+//! Records whose events errored are read back from disk and sent again by the source, up to
+//! `max_retries` times, after which they are dropped as well.
+//!
+//! A log is locked by its acknowledgement task until all of its records are finalized, so that a
+//! source rebuilt on reload only reopens its log once the previous instance is done with it.
+//!
+//! Records are stored as `[id: u64][length: u32][payload]`, little-endian, where the payload is
+//! the protobuf encoding of the event array. A torn write at the end of a segment, left behind by
+//! a crash, is detected by a short read or a payload that fails to decode, and ends the replay of
+//! that segment.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::StreamExt;
+use futures_util::stream::FuturesUnordered;
+use once_cell::sync::Lazy;
+use tokio::{
+    fs::{self, File},
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    sync::{mpsc, Mutex as AsyncMutex, Notify, OwnedMutexGuard},
+};
+use tracing::Instrument;
+use vector_common::finalization::{
+    AddBatchNotifier, BatchNotifier, BatchStatus, BatchStatusReceiver,
+};
+use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
+use vector_core::buffers::encoding::Encodable;
+
+use crate::{
+    config::SourceWalConfig,
+    event::{EventArray, EventContainer},
+};
+
+/// Segments are rolled over once they grow beyond this size.
+const SEGMENT_MAX_BYTES: u64 = 8 * 1024 * 1024;
+
+const RECORD_HEADER_LEN: usize = 12;
+const WATERMARK_FILE: &str = "acknowledged";
+const SEGMENT_EXTENSION: &str = "wal";
+
+/// The locks of the log directories, held by the acknowledgement tasks of their logs.
+static LOCKS: Lazy<Mutex<HashMap<PathBuf, Arc<AsyncMutex<()>>>>> = Lazy::new(Default::default);
+
+struct ActiveSegment {
+    file: File,
+    first_id: u64,
+    size: u64,
+}
+
+/// State shared between the writer and the acknowledgement task.
+#[derive(Default)]
+struct AckState {
+    /// IDs that have been written, or sent again, but whose events are not finalized yet.
+    pending: BTreeSet<u64>,
+    /// IDs of the records whose events errored, waiting to be sent again.
+    errored: BTreeSet<u64>,
+    /// The segment and offset of each record that is not finalized yet.
+    locations: HashMap<u64, (u64, u64)>,
+    /// The ID that the next written record will have.
+    next_id: u64,
+    /// Segments on disk, keyed by the ID of their first record, along with their size.
+    segments: BTreeMap<u64, u64>,
+}
+
+impl AckState {
+    /// Every record with an ID lower than the watermark has been finalized.
+    fn watermark(&self) -> u64 {
+        let pending = self.pending.first().copied().unwrap_or(self.next_id);
+        let errored = self.errored.first().copied().unwrap_or(self.next_id);
+        pending.min(errored)
+    }
+
+    fn disk_size(&self) -> u64 {
+        self.segments.values().sum()
+    }
+
+    /// Removes all segments that only contain finalized records, except the last one, which may
+    /// still be written to.
+    fn take_finalized_segments(&mut self) -> Vec<u64> {
+        let watermark = self.watermark();
+        let first_ids = self.segments.keys().copied().collect::<Vec<_>>();
+        let finalized = first_ids
+            .windows(2)
+            .take_while(|pair| pair[1] <= watermark)
+            .map(|pair| pair[0])
+            .collect::<Vec<_>>();
+        for first_id in &finalized {
+            self.segments.remove(first_id);
+        }
+        finalized
+    }
+}
+
+pub(super) struct SourceWal {
+    dir: PathBuf,
+    max_size: u64,
+    active: Option<ActiveSegment>,
+    state: Arc<Mutex<AckState>>,
+    space_freed: Arc<Notify>,
+    ack_tx: mpsc::UnboundedSender<(u64, usize, BatchStatusReceiver)>,
+    retry_rx: mpsc::UnboundedReceiver<(u64, EventArray)>,
+}
+
+impl SourceWal {
+    /// Opens the write-ahead log in `dir`, returning it along with the records that must be replayed.
+    ///
+    /// Waits for the acknowledgement task of a previous instance of the log to exit first.
+    pub(super) async fn open(
+        dir: PathBuf,
+        config: &SourceWalConfig,
+    ) -> crate::Result<(Self, Vec<EventArray>)> {
+        let lock = Arc::clone(
+            LOCKS
+                .lock()
+                .expect("poisoned lock")
+                .entry(dir.clone())
+                .or_default(),
+        );
+        let lock = lock.lock_owned().await;
+
+        fs::create_dir_all(&dir).await?;
+
+        let watermark = read_watermark(&dir).await?;
+        let mut segments = BTreeMap::new();
+        let mut locations = HashMap::new();
+        let mut replay = Vec::new();
+        let mut next_id = watermark;
+
+        for (first_id, path) in list_segments(&dir).await? {
+            let data = Bytes::from(fs::read(&path).await?);
+            segments.insert(first_id, data.len() as u64);
+            for (id, offset, array) in read_records(data, &path) {
+                next_id = next_id.max(id + 1);
+                if id >= watermark {
+                    locations.insert(id, (first_id, offset));
+                    replay.push((id, array));
+                }
+            }
+        }
+
+        let state = Arc::new(Mutex::new(AckState {
+            pending: replay.iter().map(|(id, _)| *id).collect(),
+            errored: BTreeSet::new(),
+            locations,
+            next_id,
+            segments,
+        }));
+        let space_freed = Arc::new(Notify::new());
+        let (ack_tx, ack_rx) = mpsc::unbounded_channel();
+        let (retry_tx, retry_rx) = mpsc::unbounded_channel();
+        tokio::spawn(
+            run_acknowledgements(
+                dir.clone(),
+                Arc::clone(&state),
+                Arc::clone(&space_freed),
+                ack_rx,
+                Retries {
+                    max: config.max_retries,
+                    counts: HashMap::new(),
+                    tx: retry_tx,
+                },
+                lock,
+            )
+            .in_current_span(),
+        );
+
+        let wal = Self {
+            dir,
+            max_size: config.max_size.get(),
+            active: None,
+            state,
+            space_freed,
+            ack_tx,
+            retry_rx,
+        };
+
+        if !replay.is_empty() {
+            info!(
+                message = "Replaying events from source write-ahead log.",
+                arrays = replay.len(),
+                path = ?wal.dir,
+            );
+        }
+        let replay = replay
+            .into_iter()
+            .map(|(id, mut array)| {
+                wal.track(id, &mut array);
+                array
+            })
+            .collect();
+
+        Ok((wal, replay))
+    }
+
+    /// Durably appends the array to the log, and attaches a notifier that finalizes its record.
+    pub(super) async fn append(&mut self, array: &mut EventArray) -> crate::Result<()> {
+        let mut payload = BytesMut::new();
+        array
+            .clone()
+            .encode(&mut payload)
+            .map_err(|error| format!("Failed to encode events for write-ahead log: {:?}", error))?;
+        let record_len = (RECORD_HEADER_LEN + payload.len()) as u64;
+
+        self.wait_for_space(record_len).await;
+
+        let id = self.state.lock().expect("poisoned lock").next_id;
+        let mut record = BytesMut::with_capacity(record_len as usize);
+        record.put_u64_le(id);
+        record.put_u32_le(payload.len() as u32);
+        record.extend_from_slice(&payload);
+
+        let active = self.active_segment(id, record_len).await?;
+        active.file.write_all(&record).await?;
+        active.file.sync_data().await?;
+        let offset = active.size;
+        active.size += record_len;
+        let (first_id, size) = (active.first_id, active.size);
+
+        {
+            let mut state = self.state.lock().expect("poisoned lock");
+            state.next_id = id + 1;
+            state.segments.insert(first_id, size);
+            state.pending.insert(id);
+            state.locations.insert(id, (first_id, offset));
+        }
+
+        self.track(id, array);
+        Ok(())
+    }
+
+    /// Waits for the next array whose events errored, read back from the log, to be sent again.
+    pub(super) async fn next_retry(&mut self) -> EventArray {
+        match self.retry_rx.recv().await {
+            Some((id, mut array)) => {
+                {
+                    let mut state = self.state.lock().expect("poisoned lock");
+                    state.errored.remove(&id);
+                    state.pending.insert(id);
+                }
+                self.track(id, &mut array);
+                array
+            }
+            // The acknowledgement task only exits once the log is dropped.
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Finalizes the record with the given ID, which must already be pending, once all of the
+    /// array's events are finalized.
+    fn track(&self, id: u64, array: &mut EventArray) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        array.add_batch_notifier(batch);
+        // The acknowledgement task only exits once this sender is dropped.
+        _ = self.ack_tx.send((id, array.len(), receiver));
+    }
+
+    /// Pauses the source while the log is full, unless no events are in flight, in which case
+    /// waiting would never end. Errored records waiting to be sent again are not in flight, as
+    /// they can only be sent once the source is no longer paused.
+    async fn wait_for_space(&self, record_len: u64) {
+        loop {
+            let notified = self.space_freed.notified();
+            {
+                let state = self.state.lock().expect("poisoned lock");
+                if state.pending.is_empty() || state.disk_size() + record_len <= self.max_size {
+                    return;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Returns the segment to write the record with the given ID to, rolling over to a new segment
+    /// if there is none yet or the current one is full. Segments left over from a previous run are
+    /// never appended to, since they may end with a torn write.
+    async fn active_segment(
+        &mut self,
+        id: u64,
+        record_len: u64,
+    ) -> crate::Result<&mut ActiveSegment> {
+        let roll_over = self
+            .active
+            .as_ref()
+            .map_or(true, |active| active.size + record_len > SEGMENT_MAX_BYTES);
+        if roll_over {
+            let file = fs::OpenOptions::new()
+                .create_new(true)
+                .append(true)
+                .open(segment_path(&self.dir, id))
+                .await?;
+            self.state
+                .lock()
+                .expect("poisoned lock")
+                .segments
+                .insert(id, 0);
+            self.active = Some(ActiveSegment {
+                file,
+                first_id: id,
+                size: 0,
+            });
+        }
+        Ok(self.active.as_mut().expect("active segment was just set"))
+    }
+}
+
+/// How many times the records whose events errored were sent again, out of `max`.
+struct Retries {
+    max: u32,
+    counts: HashMap<u64, u32>,
+    tx: mpsc::UnboundedSender<(u64, EventArray)>,
+}
+
+async fn run_acknowledgements(
+    dir: PathBuf,
+    state: Arc<Mutex<AckState>>,
+    space_freed: Arc<Notify>,
+    mut ack_rx: mpsc::UnboundedReceiver<(u64, usize, BatchStatusReceiver)>,
+    mut retries: Retries,
+    _lock: OwnedMutexGuard<()>,
+) {
+    let mut pending = FuturesUnordered::new();
+    let mut persisted_watermark = None;
+    let mut receiving = true;
+
+    while receiving || !pending.is_empty() {
+        tokio::select! {
+            received = ack_rx.recv(), if receiving => match received {
+                Some((id, count, receiver)) => {
+                    pending.push(async move { (id, count, receiver.await) })
+                }
+                None => receiving = false,
+            },
+            Some((id, count, status)) = pending.next(), if !pending.is_empty() => {
+                match status {
+                    BatchStatus::Delivered => {}
+                    BatchStatus::Rejected => emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                        count,
+                        reason: "Events of source write-ahead log were rejected downstream.",
+                    }),
+                    BatchStatus::Errored => {
+                        if retry(&dir, &state, &mut retries, id).await {
+                            space_freed.notify_waiters();
+                            continue;
+                        }
+                        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                            count,
+                            reason: "Events of source write-ahead log errored downstream too many times.",
+                        });
+                    }
+                }
+
+                let (watermark, finalized) = {
+                    let mut state = state.lock().expect("poisoned lock");
+                    state.pending.remove(&id);
+                    state.locations.remove(&id);
+                    (state.watermark(), state.take_finalized_segments())
+                };
+                retries.counts.remove(&id);
+
+                if persisted_watermark != Some(watermark) {
+                    if let Err(error) = write_watermark(&dir, watermark).await {
+                        error!(message = "Failed to persist source write-ahead log watermark.", %error);
+                    }
+                    persisted_watermark = Some(watermark);
+                }
+
+                for first_id in finalized {
+                    if let Err(error) = fs::remove_file(segment_path(&dir, first_id)).await {
+                        error!(message = "Failed to delete source write-ahead log segment.", %error);
+                    }
+                }
+                space_freed.notify_waiters();
+            }
+        }
+    }
+}
+
+/// Reads the record whose events errored back from the log, for the source to send it again,
+/// unless it was already sent `max_retries` times. Returns whether the record is kept.
+async fn retry(dir: &Path, state: &Mutex<AckState>, retries: &mut Retries, id: u64) -> bool {
+    let count = retries.counts.entry(id).or_default();
+    if *count >= retries.max {
+        return false;
+    }
+    *count += 1;
+    let retry = *count;
+
+    let location = state
+        .lock()
+        .expect("poisoned lock")
+        .locations
+        .get(&id)
+        .copied();
+    let Some((first_id, offset)) = location else {
+        return false;
+    };
+    let array = match read_record(&segment_path(dir, first_id), offset).await {
+        Ok(array) => array,
+        Err(error) => {
+            error!(message = "Failed to read source write-ahead log record.", id, %error);
+            return false;
+        }
+    };
+
+    {
+        let mut state = state.lock().expect("poisoned lock");
+        state.pending.remove(&id);
+        state.errored.insert(id);
+    }
+    warn!(
+        message = "Events of source write-ahead log errored downstream, sending them again.",
+        id,
+        retry,
+        path = ?dir,
+    );
+    // Once the source is gone, the record is replayed on the next start instead.
+    _ = retries.tx.send((id, array));
+    true
+}
+
+fn segment_path(dir: &Path, first_id: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", first_id, SEGMENT_EXTENSION))
+}
+
+async fn list_segments(dir: &Path) -> io::Result<Vec<(u64, PathBuf)>> {
+    let mut segments = Vec::new();
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(first_id) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+        {
+            segments.push((first_id, path));
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Reads the records of a segment, along with their offset in it.
+fn read_records(mut data: Bytes, path: &Path) -> Vec<(u64, u64, EventArray)> {
+    let size = data.len();
+    let mut records = Vec::new();
+    while data.remaining() >= RECORD_HEADER_LEN {
+        let offset = (size - data.remaining()) as u64;
+        let id = data.get_u64_le();
+        let len = data.get_u32_le() as usize;
+        if data.remaining() < len {
+            break;
+        }
+        let payload = data.split_to(len);
+        match EventArray::decode(EventArray::get_metadata(), payload) {
+            Ok(array) => records.push((id, offset, array)),
+            Err(error) => {
+                warn!(
+                    message = "Ignoring corrupted tail of source write-ahead log segment.",
+                    ?error,
+                    ?path,
+                );
+                return records;
+            }
+        }
+    }
+    if data.has_remaining() {
+        warn!(
+            message = "Ignoring truncated record at the end of source write-ahead log segment.",
+            ?path,
+        );
+    }
+    records
+}
+
+async fn read_record(path: &Path, offset: u64) -> io::Result<EventArray> {
+    let mut file = File::open(path).await?;
+    file.seek(io::SeekFrom::Start(offset)).await?;
+    let mut header = [0; RECORD_HEADER_LEN];
+    file.read_exact(&mut header).await?;
+    let len = u32::from_le_bytes(header[8..].try_into().expect("four bytes")) as usize;
+    let mut payload = vec![0; len];
+    file.read_exact(&mut payload).await?;
+    EventArray::decode(EventArray::get_metadata(), Bytes::from(payload)).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid source write-ahead log record: {:?}", error),
+        )
+    })
+}
+
+async fn read_watermark(dir: &Path) -> io::Result<u64> {
+    match fs::read(dir.join(WATERMARK_FILE)).await {
+        Ok(data) => data.try_into().map(u64::from_le_bytes).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid source write-ahead log watermark",
+            )
+        }),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(error) => Err(error),
+    }
+}
+
+async fn write_watermark(dir: &Path, watermark: u64) -> io::Result<()> {
+    // Write to a temporary file first so that a crash never leaves a partially written watermark.
+    let tmp = dir.join(format!("{}.tmp", WATERMARK_FILE));
+    let mut file = File::create(&tmp).await?;
+    file.write_all(&watermark.to_le_bytes()).await?;
+    file.sync_data().await?;
+    fs::rename(tmp, dir.join(WATERMARK_FILE)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, time::Duration};
+
+    use vector_common::finalization::{EventStatus, Finalizable};
+
+    use super::*;
+    use crate::event::{Event, EventContainer, LogEvent};
+
+    fn config() -> SourceWalConfig {
+        SourceWalConfig {
+            max_size: NonZeroU64::new(1024 * 1024).unwrap(),
+            max_retries: 3,
+            data_dir: None,
+        }
+    }
+
+    fn array(message: &str) -> EventArray {
+        Event::Log(LogEvent::from(message)).into()
+    }
+
+    fn messages(arrays: &[EventArray]) -> Vec<String> {
+        arrays
+            .iter()
+            .flat_map(|array| array.clone().into_events())
+            .map(|event| {
+                event
+                    .into_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn replays_undelivered_records() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (mut wal, replay) = SourceWal::open(dir.path().to_path_buf(), &config())
+            .await
+            .unwrap();
+        assert!(replay.is_empty());
+
+        let mut first = array("first");
+        let mut second = array("second");
+        wal.append(&mut first).await.unwrap();
+        wal.append(&mut second).await.unwrap();
+
+        first
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(first);
+        let path = dir.path().to_path_buf();
+        crate::test_util::wait_for(|| {
+            let path = path.clone();
+            async move { read_watermark(&path).await.unwrap_or_default() == 1 }
+        })
+        .await;
+
+        second.take_finalizers().update_status(EventStatus::Errored);
+        drop(second);
+        drop(wal);
+
+        let (_wal, replay) = SourceWal::open(dir.path().to_path_buf(), &config())
+            .await
+            .unwrap();
+        assert_eq!(messages(&replay), vec!["second".to_string()]);
+        assert_eq!(read_watermark(dir.path()).await.unwrap(), 1);
+    }
+
+    async fn wait_for_watermark(dir: &Path, watermark: u64) {
+        let dir = dir.to_path_buf();
+        crate::test_util::wait_for(|| {
+            let dir = dir.clone();
+            async move { read_watermark(&dir).await.unwrap_or_default() == watermark }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn drops_rejected_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SourceWalConfig {
+            max_size: NonZeroU64::new(1).unwrap(),
+            ..config()
+        };
+
+        let (mut wal, _) = SourceWal::open(dir.path().to_path_buf(), &config)
+            .await
+            .unwrap();
+        let mut first = array("first");
+        wal.append(&mut first).await.unwrap();
+
+        // The log is full until the first record is finalized, which rejecting it does.
+        first.take_finalizers().update_status(EventStatus::Rejected);
+        drop(first);
+        let mut second = array("second");
+        tokio::time::timeout(Duration::from_secs(5), wal.append(&mut second))
+            .await
+            .expect("source is blocked by a rejected record")
+            .unwrap();
+        wait_for_watermark(dir.path(), 1).await;
+
+        second
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(second);
+        wait_for_watermark(dir.path(), 2).await;
+        drop(wal);
+
+        let (_wal, replay) = SourceWal::open(dir.path().to_path_buf(), &config)
+            .await
+            .unwrap();
+        assert!(replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn retries_errored_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SourceWalConfig {
+            max_size: NonZeroU64::new(1).unwrap(),
+            max_retries: 1,
+            ..config()
+        };
+
+        let (mut wal, _) = SourceWal::open(dir.path().to_path_buf(), &config)
+            .await
+            .unwrap();
+        let mut first = array("first");
+        wal.append(&mut first).await.unwrap();
+        first.take_finalizers().update_status(EventStatus::Errored);
+        drop(first);
+
+        // The errored record waits to be sent again, so it doesn't block the source.
+        let mut second = array("second");
+        tokio::time::timeout(Duration::from_secs(5), wal.append(&mut second))
+            .await
+            .expect("source is blocked by an errored record")
+            .unwrap();
+        second
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(second);
+
+        let mut retried = tokio::time::timeout(Duration::from_secs(5), wal.next_retry())
+            .await
+            .expect("errored record is not sent again");
+        assert_eq!(messages(&[retried.clone()]), vec!["first".to_string()]);
+        assert_eq!(read_watermark(dir.path()).await.unwrap_or_default(), 0);
+
+        // Erroring once more exhausts its retries, so it is dropped.
+        retried
+            .take_finalizers()
+            .update_status(EventStatus::Errored);
+        drop(retried);
+        wait_for_watermark(dir.path(), 2).await;
+        drop(wal);
+
+        let (_wal, replay) = SourceWal::open(dir.path().to_path_buf(), &config)
+            .await
+            .unwrap();
+        assert!(replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reopens_once_previous_instance_is_finalized() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (mut wal, _) = SourceWal::open(dir.path().to_path_buf(), &config())
+            .await
+            .unwrap();
+        let mut first = array("first");
+        wal.append(&mut first).await.unwrap();
+        drop(wal);
+
+        let path = dir.path().to_path_buf();
+        let reopen = tokio::spawn(async move { SourceWal::open(path, &config()).await });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!reopen.is_finished());
+
+        first
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(first);
+
+        let (_wal, replay) = reopen.await.unwrap().unwrap();
+        assert!(replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ignores_torn_writes() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (mut wal, _) = SourceWal::open(dir.path().to_path_buf(), &config())
+            .await
+            .unwrap();
+        let mut first = array("first");
+        wal.append(&mut first).await.unwrap();
+        drop(wal);
+
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(segment_path(dir.path(), 0))
+            .await
+            .unwrap();
+        file.write_all(&[1, 0, 0, 0, 0, 0, 0, 0, 42, 0])
+            .await
+            .unwrap();
+        drop(file);
+
+        let (_wal, replay) = SourceWal::open(dir.path().to_path_buf(), &config())
+            .await
+            .unwrap();
+        assert_eq!(messages(&replay), vec!["first".to_string()]);
+    }
+
+    #[test]
+    fn only_deletes_fully_finalized_segments() {
+        let mut state = AckState {
+            pending: [5].into_iter().collect(),
+            errored: [12].into_iter().collect(),
+            locations: HashMap::new(),
+            next_id: 15,
+            segments: [(0, 10), (4, 10), (8, 10), (13, 10)].into_iter().collect(),
+        };
+        assert_eq!(state.take_finalized_segments(), vec![0]);
+
+        state.pending.remove(&5);
+        assert_eq!(state.take_finalized_segments(), vec![4]);
+
+        state.errored.remove(&12);
+        assert_eq!(state.take_finalized_segments(), vec![8]);
+        assert_eq!(state.segments.keys().copied().collect::<Vec<_>>(), vec![13]);
+    }
+}
@@ -809,6 +809,7 @@ async fn topology_disk_buffer_flushes_on_idle() {
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::DropNewest,
+        evictable: false,
     });
     config.add_sink_outer("out1", sink1_outer);
 
@@ -842,6 +843,65 @@ async fn topology_disk_buffer_flushes_on_idle() {
     assert_eq!(rest, vec![]);
 }
 
+#[tokio::test]
+async fn topology_drains_disk_buffer_of_previous_sink_id() {
+    trace_init();
+
+    let tmpdir = tempfile::tempdir().expect("no tmpdir");
+    let disk_buffer = BufferConfig::Single(BufferType::DiskV2 {
+        max_size: std::num::NonZeroU64::new(268435488).unwrap(),
+        when_full: WhenFull::Block,
+        evictable: false,
+    });
+
+    // Leave an event behind in the disk buffer of the sink's previous ID.
+    let (mut tx, rx) = disk_buffer
+        .build::<EventArray>(
+            Some(tmpdir.path().to_owned()),
+            "old".to_owned(),
+            tracing::Span::none(),
+        )
+        .await
+        .unwrap();
+    tx.send(EventArray::Logs(vec![LogEvent::from("foo")]))
+        .await
+        .unwrap();
+    tx.flush().await.unwrap();
+    drop((tx, rx));
+
+    let (_in1, source1) = basic_source();
+    let (mut out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.set_data_dir(tmpdir.path());
+    config.add_source("in1", source1);
+    let mut sink1_outer = SinkOuter::new(vec![String::from("in1")], sink1);
+    sink1_outer.buffer_migration.enabled = true;
+    sink1_outer.buffer_migration.previous_ids = vec!["old".into()];
+    config.add_sink_outer("new", sink1_outer);
+
+    let (topology, _) = start_topology(config.build().unwrap(), false).await;
+
+    let res = tokio::time::timeout(Duration::from_secs(1), out1.next())
+        .await
+        .expect("timeout")
+        .map(|array| into_message(array.into_events().next().unwrap()))
+        .expect("no output");
+    assert_eq!("foo", res);
+
+    // The buffer is deleted once its event is acknowledged, which happened when it was dropped.
+    let old_dir = vector_buffers::config::disk_buffer_dir(tmpdir.path(), &"old".into());
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while old_dir.exists() {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("disk buffer not deleted");
+
+    topology.stop().await;
+}
+
 #[tokio::test]
 async fn topology_transform_error_definition() {
     trace_init();
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use lookup::{owned_value_path, PathPrefix};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    transform::{SyncTransform, TransformOutputsBuf},
+};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{
+        metric::{MetricKind, MetricSeries, MetricValue},
+        Event, LogEvent, Metric,
+    },
+    internal_events::AnomalyDetected,
+    schema,
+    transforms::Transform,
+};
+
+/// The name of the output receiving alert events.
+pub(crate) const ALERTS_OUTPUT: &str = "alerts";
+
+const fn default_alpha() -> f64 {
+    0.1
+}
+
+const fn default_threshold() -> f64 {
+    3.0
+}
+
+const fn default_min_samples() -> u64 {
+    10
+}
+
+const fn default_tag_anomalies() -> bool {
+    true
+}
+
+const fn default_season_secs() -> u64 {
+    86400
+}
+
+const fn default_buckets() -> u64 {
+    24
+}
+
+/// Configuration for the `anomaly_detection` transform.
+#[configurable_component(transform(
+    "anomaly_detection",
+    "Detect values of metric series that deviate from their baseline."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AnomalyDetectionConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub baseline: BaselineConfig,
+
+    /// The weight of each new value in the baseline, between `0.0` and `1.0`.
+    ///
+    /// Higher values make the baseline follow recent values more closely.
+    #[serde(default = "default_alpha")]
+    pub alpha: f64,
+
+    /// The number of standard deviations from the baseline beyond which a value is anomalous.
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+
+    /// The number of values a baseline must have seen before its deviations are scored.
+    #[serde(default = "default_min_samples")]
+    pub min_samples: u64,
+
+    /// Whether to add the `anomaly` and `anomaly_score` tags to anomalous metrics.
+    ///
+    /// Alert events are sent to the `alerts` output in any case.
+    #[serde(default = "default_tag_anomalies")]
+    pub tag_anomalies: bool,
+}
+
+/// How the baseline of a series is computed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of baseline."))]
+pub enum BaselineConfig {
+    /// An exponentially weighted moving average, and variance, of the values of the series.
+    #[default]
+    Ewma,
+
+    /// An exponentially weighted moving average, and variance, for each phase of a season.
+    ///
+    /// The season is divided in buckets, and each value is compared to the baseline of the bucket
+    /// its timestamp falls into, such as the same hour of the previous days.
+    Seasonal {
+        /// The duration of the season.
+        #[serde(default = "default_season_secs")]
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        season_secs: u64,
+
+        /// The number of buckets the season is divided in.
+        #[serde(default = "default_buckets")]
+        buckets: u64,
+    },
+}
+
+impl GenerateConfig for AnomalyDetectionConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"threshold = 3.0
+            alpha = 0.1
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "anomaly_detection")]
+impl TransformConfig for AnomalyDetectionConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        AnomalyDetection::new(self).map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let mut alert_definition = schema::Definition::default_legacy_namespace()
+            .with_event_field(&owned_value_path!("name"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("namespace"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("tags"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            )
+            .with_event_field(&owned_value_path!("value"), Kind::float(), None)
+            .with_event_field(&owned_value_path!("baseline"), Kind::float(), None)
+            .with_event_field(&owned_value_path!("deviation"), Kind::float(), None)
+            .with_event_field(&owned_value_path!("score"), Kind::float(), None);
+        if let Some(timestamp_key) = log_schema().timestamp_key() {
+            alert_definition =
+                alert_definition.with_event_field(timestamp_key, Kind::timestamp(), None);
+        }
+
+        vec![
+            TransformOutput::new(DataType::Metric, HashMap::new()),
+            TransformOutput::new(
+                DataType::Log,
+                input_definitions
+                    .iter()
+                    .map(|(output, _)| (output.clone(), alert_definition.clone()))
+                    .collect(),
+            )
+            .with_port(ALERTS_OUTPUT),
+        ]
+    }
+}
+
+/// Exponentially weighted moving average and variance of a series of values.
+#[derive(Clone, Copy, Debug, Default)]
+struct Ewma {
+    samples: u64,
+    mean: f64,
+    variance: f64,
+}
+
+impl Ewma {
+    /// The number of standard deviations between a value and the average.
+    fn score(&self, value: f64) -> f64 {
+        (value - self.mean).abs() / self.variance.sqrt().max(f64::EPSILON)
+    }
+
+    fn update(&mut self, value: f64, alpha: f64) {
+        if self.samples == 0 {
+            self.mean = value;
+        } else {
+            let diff = value - self.mean;
+            let increment = alpha * diff;
+            self.mean += increment;
+            self.variance = (1.0 - alpha) * (self.variance + diff * increment);
+        }
+        self.samples += 1;
+    }
+}
+
+#[derive(Debug, Default)]
+struct SeriesState {
+    /// The previous value of an absolute counter, whose increments are scored.
+    previous: Option<f64>,
+    baselines: Vec<Ewma>,
+}
+
+pub struct AnomalyDetection {
+    alpha: f64,
+    threshold: f64,
+    min_samples: u64,
+    tag_anomalies: bool,
+    season: Option<(u64, u64)>,
+    series: HashMap<MetricSeries, SeriesState>,
+}
+
+impl AnomalyDetection {
+    pub fn new(config: &AnomalyDetectionConfig) -> crate::Result<Self> {
+        if !(config.alpha > 0.0 && config.alpha <= 1.0) {
+            return Err("`alpha` must be greater than 0.0, and at most 1.0".into());
+        }
+        if config.threshold <= 0.0 {
+            return Err("`threshold` must be greater than 0.0".into());
+        }
+        let season = match config.baseline {
+            BaselineConfig::Ewma => None,
+            BaselineConfig::Seasonal {
+                season_secs,
+                buckets,
+            } => {
+                if season_secs == 0 || buckets == 0 {
+                    return Err(
+                        "`baseline.season_secs` and `baseline.buckets` must be greater than zero"
+                            .into(),
+                    );
+                }
+                Some((season_secs, buckets))
+            }
+        };
+
+        Ok(Self {
+            alpha: config.alpha,
+            threshold: config.threshold,
+            min_samples: config.min_samples,
+            tag_anomalies: config.tag_anomalies,
+            season,
+            series: HashMap::new(),
+        })
+    }
+
+    fn bucket(&self, timestamp: DateTime<Utc>) -> usize {
+        match self.season {
+            None => 0,
+            Some((season_secs, buckets)) => {
+                let phase = timestamp.timestamp().rem_euclid(season_secs as i64) as u64;
+                (phase * buckets / season_secs) as usize
+            }
+        }
+    }
+
+    /// The value of a metric to score, if it is a counter or a gauge.
+    fn value(state: &mut SeriesState, metric: &Metric) -> Option<f64> {
+        match (metric.kind(), metric.value()) {
+            (MetricKind::Incremental, MetricValue::Counter { value })
+            | (_, MetricValue::Gauge { value }) => Some(*value),
+            (MetricKind::Absolute, MetricValue::Counter { value }) => state
+                .previous
+                .replace(*value)
+                // A counter reset starts from zero.
+                .map(|previous| {
+                    if *value >= previous {
+                        *value - previous
+                    } else {
+                        *value
+                    }
+                }),
+            _ => None,
+        }
+    }
+
+    /// Scores the value of a metric against the baseline of its series, then folds it into the
+    /// baseline. Returns the baseline and score of anomalous values.
+    fn detect(&mut self, metric: &Metric, now: DateTime<Utc>) -> Option<(f64, f64, f64)> {
+        let bucket = self.bucket(metric.timestamp().unwrap_or(now));
+        let state = self.series.entry(metric.series().clone()).or_default();
+        let value = Self::value(state, metric)?;
+
+        if state.baselines.len() <= bucket {
+            state.baselines.resize(bucket + 1, Ewma::default());
+        }
+        let baseline = &mut state.baselines[bucket];
+        let anomaly = (baseline.samples >= self.min_samples)
+            .then(|| (value, baseline.mean, baseline.score(value)))
+            .filter(|(_, _, score)| *score > self.threshold);
+        baseline.update(value, self.alpha);
+        anomaly
+    }
+
+    fn alert(metric: &Metric, value: f64, baseline: f64, score: f64) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert(
+            log_schema().message_key(),
+            "Metric value deviates from its baseline.",
+        );
+        if let (Some(timestamp_key), Some(timestamp)) =
+            (log_schema().timestamp_key(), metric.timestamp())
+        {
+            log.insert((PathPrefix::Event, timestamp_key), timestamp);
+        }
+        log.insert("name", metric.name().to_owned());
+        if let Some(namespace) = metric.namespace() {
+            log.insert("namespace", namespace.to_owned());
+        }
+        if let Some(tags) = metric.tags() {
+            for (key, tag) in tags.iter_single() {
+                log.insert(format!("tags.\"{}\"", key).as_str(), tag.to_owned());
+            }
+        }
+        log.insert("value", value);
+        log.insert("baseline", baseline);
+        log.insert("deviation", value - baseline);
+        log.insert("score", score);
+        log
+    }
+}
+
+impl SyncTransform for AnomalyDetection {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut metric = event.into_metric();
+        if let Some((value, baseline, score)) = self.detect(&metric, Utc::now()) {
+            emit!(AnomalyDetected { score });
+            output.push_named(
+                ALERTS_OUTPUT,
+                Self::alert(&metric, value, baseline, score).into(),
+            );
+            if self.tag_anomalies {
+                metric.replace_tag("anomaly".to_owned(), "true".to_owned());
+                metric.replace_tag("anomaly_score".to_owned(), format!("{:.2}", score));
+            }
+        }
+        output.push(metric.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::metric::MetricTags;
+
+    use super::*;
+
+    fn detector(config: &str) -> AnomalyDetection {
+        AnomalyDetection::new(&toml::from_str::<AnomalyDetectionConfig>(config).unwrap()).unwrap()
+    }
+
+    fn gauge(host: &str, value: f64) -> Metric {
+        Metric::new("load", MetricKind::Absolute, MetricValue::Gauge { value }).with_tags(Some(
+            MetricTags::from_iter([("host".to_owned(), host.to_owned())]),
+        ))
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AnomalyDetectionConfig>();
+    }
+
+    #[test]
+    fn detects_deviations_per_series() {
+        let mut detector = detector("min_samples = 5");
+        let now = Utc::now();
+
+        for value in [10.0, 11.0, 9.0, 10.0, 11.0, 9.0, 10.0] {
+            assert!(detector.detect(&gauge("a", value), now).is_none());
+        }
+        // Not enough samples for host `b`.
+        assert!(detector.detect(&gauge("b", 100.0), now).is_none());
+
+        let (value, baseline, score) = detector.detect(&gauge("a", 100.0), now).unwrap();
+        assert_eq!(value, 100.0);
+        assert!((baseline - 10.0).abs() < 1.0);
+        assert!(score > 3.0);
+    }
+
+    #[test]
+    fn scores_increments_of_absolute_counters() {
+        let mut detector = detector("min_samples = 3");
+        let now = Utc::now();
+        let counter = |value| {
+            Metric::new(
+                "requests",
+                MetricKind::Absolute,
+                MetricValue::Counter { value },
+            )
+        };
+
+        for value in [100.0, 110.0, 121.0, 130.0, 141.0] {
+            assert!(detector.detect(&counter(value), now).is_none());
+        }
+        assert!(detector.detect(&counter(1141.0), now).is_some());
+    }
+
+    #[test]
+    fn compares_to_the_same_phase_of_the_season() {
+        let mut detector = detector(
+            r#"
+            min_samples = 2
+            threshold = 5.0
+            baseline.type = "seasonal"
+            baseline.season_secs = 86400
+            baseline.buckets = 2
+            "#,
+        );
+        let morning = |day| Utc.with_ymd_and_hms(2023, 1, day, 6, 0, 0).unwrap();
+        let evening = |day| Utc.with_ymd_and_hms(2023, 1, day, 18, 0, 0).unwrap();
+
+        for day in 1..=4 {
+            let value = if day % 2 == 0 { 10.0 } else { 11.0 };
+            assert!(detector.detect(&gauge("a", value), morning(day)).is_none());
+            assert!(detector
+                .detect(&gauge("a", value * 10.0), evening(day))
+                .is_none());
+        }
+        // High in the evening, but not in the morning.
+        assert!(detector.detect(&gauge("a", 105.0), evening(5)).is_none());
+        assert!(detector.detect(&gauge("a", 105.0), morning(5)).is_some());
+    }
+
+    #[test]
+    fn tags_anomalies_and_emits_alerts() {
+        let mut detector = detector("min_samples = 3");
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::Metric, HashMap::new()),
+                TransformOutput::new(DataType::Log, HashMap::new()).with_port(ALERTS_OUTPUT),
+            ],
+            1,
+        );
+
+        for value in [10.0, 11.0, 9.0, 10.0, 1000.0] {
+            detector.transform(gauge("a", value).into(), &mut outputs);
+        }
+
+        let metrics = outputs.drain().collect::<Vec<_>>();
+        let last = metrics.last().unwrap().as_metric();
+        assert_eq!(last.tag_value("anomaly"), Some("true".to_owned()));
+        assert!(last.tag_value("anomaly_score").is_some());
+        assert!(metrics[..4]
+            .iter()
+            .all(|event| event.as_metric().tag_value("anomaly").is_none()));
+
+        let alerts = outputs.drain_named(ALERTS_OUTPUT).collect::<Vec<_>>();
+        assert_eq!(alerts.len(), 1);
+        let alert = alerts[0].as_log();
+        assert_eq!(alert["name"], "load".into());
+        assert_eq!(alert["tags.host"], "a".into());
+        assert_eq!(alert["value"], 1000.0.into());
+    }
+}
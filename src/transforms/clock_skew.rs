@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::ClockSkewDetected,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Weight of the latest observation in the per-group clock offset estimate.
+const OFFSET_SMOOTHING: f64 = 0.1;
+
+const fn default_max_future_secs() -> u64 {
+    300
+}
+
+const fn default_max_past_secs() -> u64 {
+    3600
+}
+
+fn default_skew_field() -> String {
+    "clock_skew_secs".to_string()
+}
+
+/// Configuration for the `clock_skew` transform.
+#[configurable_component(transform(
+    "clock_skew",
+    "Correct event timestamps that are too far in the future or the past."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ClockSkewConfig {
+    /// How far ahead of the ingest time an event timestamp may be before it is considered skewed.
+    #[serde(default = "default_max_future_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub max_future_secs: u64,
+
+    /// How far behind the ingest time an event timestamp may be before it is considered skewed.
+    ///
+    /// Set this below the oldest timestamp accepted by the downstream sink, such as Loki's
+    /// `reject_old_samples_max_age`.
+    #[serde(default = "default_max_past_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub max_past_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub policy: ClockSkewPolicy,
+
+    /// The name of the field identifying the origin of an event, such as its host.
+    ///
+    /// With the `shift` policy, a clock offset is estimated separately for each value of this
+    /// field. If unset, or if the event doesn't have the field, a single estimate is shared by all
+    /// events.
+    #[configurable(metadata(docs::examples = "host"))]
+    pub group_by: Option<String>,
+
+    /// The name of the field in which the `tag` policy stores the skew of an event, in seconds.
+    ///
+    /// The skew is positive for timestamps in the future, and negative for timestamps in the past.
+    #[serde(default = "default_skew_field")]
+    pub skew_field: String,
+}
+
+/// What to do with events whose timestamp is outside of the accepted window.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockSkewPolicy {
+    /// Move the timestamp to the nearest edge of the accepted window.
+    #[default]
+    Clamp,
+
+    /// Shift the timestamp by the estimated clock offset of its origin.
+    ///
+    /// The offset is a moving average of the difference between the ingest time and the event
+    /// timestamps seen for each `group_by` value. Timestamps still outside of the window after
+    /// shifting are clamped.
+    Shift,
+
+    /// Keep the timestamp, and record the skew in `skew_field`.
+    Tag,
+}
+
+impl ClockSkewPolicy {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Clamp => "clamp",
+            Self::Shift => "shift",
+            Self::Tag => "tag",
+        }
+    }
+}
+
+impl GenerateConfig for ClockSkewConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"max_future_secs = 300
+            max_past_secs = 3600
+            policy = "clamp"
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "clock_skew")]
+impl TransformConfig for ClockSkewConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(ClockSkew::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(
+            DataType::Log,
+            input_definitions
+                .iter()
+                .map(|(output, definition)| (output.clone(), definition.clone()))
+                .collect(),
+        )]
+    }
+}
+
+#[derive(Clone)]
+pub struct ClockSkew {
+    max_future: Duration,
+    max_past: Duration,
+    policy: ClockSkewPolicy,
+    group_by: Option<String>,
+    skew_field: String,
+    offsets: HashMap<Option<String>, f64>,
+}
+
+impl ClockSkew {
+    pub fn new(config: &ClockSkewConfig) -> Self {
+        Self {
+            max_future: Duration::seconds(config.max_future_secs as i64),
+            max_past: Duration::seconds(config.max_past_secs as i64),
+            policy: config.policy,
+            group_by: config.group_by.clone(),
+            skew_field: config.skew_field.clone(),
+            offsets: HashMap::new(),
+        }
+    }
+
+    fn correct(&mut self, log: &mut LogEvent, now: DateTime<Utc>) {
+        let Some(path) = log.timestamp_path() else {
+            return;
+        };
+        let timestamp = match log.get(path.as_str()) {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => return,
+        };
+
+        let skew = timestamp - now;
+        let estimate =
+            (self.policy == ClockSkewPolicy::Shift).then(|| self.update_offset(log, skew));
+
+        if skew <= self.max_future && -skew <= self.max_past {
+            return;
+        }
+
+        let skew_secs = skew.num_milliseconds() as f64 / 1000.0;
+        emit!(ClockSkewDetected {
+            skew_secs,
+            policy: self.policy.as_str(),
+        });
+
+        match self.policy {
+            ClockSkewPolicy::Clamp => {
+                log.insert(path.as_str(), self.clamp(timestamp, now));
+            }
+            ClockSkewPolicy::Shift => {
+                let offset = Duration::milliseconds((estimate.unwrap_or_default() * 1000.0) as i64);
+                log.insert(path.as_str(), self.clamp(timestamp - offset, now));
+            }
+            ClockSkewPolicy::Tag => {
+                log.insert(self.skew_field.as_str(), skew_secs);
+            }
+        }
+    }
+
+    /// Folds the skew of an event into the offset estimate of its group, and returns the updated
+    /// estimate in seconds.
+    fn update_offset(&mut self, log: &LogEvent, skew: Duration) -> f64 {
+        let group = self
+            .group_by
+            .as_ref()
+            .and_then(|field| log.get(field.as_str()))
+            .map(|value| value.to_string_lossy().into_owned());
+        let skew_secs = skew.num_milliseconds() as f64 / 1000.0;
+
+        let estimate = self
+            .offsets
+            .entry(group)
+            .and_modify(|offset| *offset += OFFSET_SMOOTHING * (skew_secs - *offset))
+            .or_insert(skew_secs);
+        *estimate
+    }
+
+    fn clamp(&self, timestamp: DateTime<Utc>, now: DateTime<Utc>) -> DateTime<Utc> {
+        timestamp.clamp(now - self.max_past, now + self.max_future)
+    }
+}
+
+impl FunctionTransform for ClockSkew {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        self.correct(event.as_mut_log(), Utc::now());
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(config: &str, events: &[(Option<&str>, Duration)]) -> Vec<LogEvent> {
+        let config = toml::from_str::<ClockSkewConfig>(config).unwrap();
+        let mut transform = ClockSkew::new(&config);
+        let now = Utc::now();
+
+        events
+            .iter()
+            .map(|(host, skew)| {
+                let mut log = LogEvent::from("message");
+                log.insert("timestamp", now + *skew);
+                if let Some(host) = host {
+                    log.insert("host", *host);
+                }
+                transform.correct(&mut log, now);
+                log.insert("now", now);
+                log
+            })
+            .collect()
+    }
+
+    fn skew_of(log: &LogEvent) -> Duration {
+        match (log.get("timestamp"), log.get("now")) {
+            (Some(Value::Timestamp(timestamp)), Some(Value::Timestamp(now))) => *timestamp - *now,
+            _ => panic!("missing timestamps"),
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ClockSkewConfig>();
+    }
+
+    #[test]
+    fn clamps_to_window() {
+        let logs = transform(
+            "",
+            &[
+                (None, Duration::minutes(1)),
+                (None, Duration::hours(2)),
+                (None, -Duration::days(2)),
+            ],
+        );
+
+        assert_eq!(skew_of(&logs[0]), Duration::minutes(1));
+        assert_eq!(skew_of(&logs[1]), Duration::seconds(300));
+        assert_eq!(skew_of(&logs[2]), -Duration::seconds(3600));
+    }
+
+    #[test]
+    fn shifts_by_group_offset() {
+        let logs = transform(
+            r#"
+            policy = "shift"
+            max_future_secs = 60
+            group_by = "host"
+            "#,
+            &[
+                (Some("a"), Duration::hours(2) + Duration::seconds(10)),
+                (Some("a"), Duration::hours(2) + Duration::seconds(10)),
+                (Some("b"), Duration::seconds(30)),
+            ],
+        );
+
+        // Host `a` runs two hours ahead; its events are shifted back by the estimated offset.
+        assert_eq!(skew_of(&logs[1]), Duration::zero());
+        // Host `b` is within the window, and unaffected by the estimate of host `a`.
+        assert_eq!(skew_of(&logs[2]), Duration::seconds(30));
+    }
+
+    #[test]
+    fn tags_skewed_events() {
+        let logs = transform(
+            r#"policy = "tag""#,
+            &[(None, Duration::hours(1)), (None, Duration::seconds(5))],
+        );
+
+        assert_eq!(skew_of(&logs[0]), Duration::hours(1));
+        assert_eq!(logs[0]["clock_skew_secs"], Value::from(3600.0));
+        assert!(logs[1].get("clock_skew_secs").is_none());
+    }
+}
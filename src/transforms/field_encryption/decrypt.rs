@@ -0,0 +1,244 @@
+use lookup::OwnedTargetPath;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, transform::SyncTransform};
+use vector_vrl_functions::envelope::{is_envelope, Envelope};
+use vrl::value::Kind;
+
+use super::{check_fips_mode, decode_keys, parse_fields, EncryptionKey, Key};
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::FieldDecryptionError,
+    schema,
+    transforms::Transform,
+};
+
+/// Configuration for the `decrypt_fields` transform.
+#[configurable_component(transform(
+    "decrypt_fields",
+    "Decrypt the envelopes of fields encrypted by the `encrypt_fields` transform."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DecryptFieldsConfig {
+    /// The fields whose envelopes are decrypted.
+    ///
+    /// Each envelope is decrypted with the key its `kid` names, and replaced with the original
+    /// value. Fields that don't hold an envelope are left as they are, and so are envelopes that
+    /// can't be decrypted, which are reported as errors.
+    #[configurable(metadata(docs::examples = "user.email", docs::examples = "card_number"))]
+    pub fields: Vec<String>,
+
+    /// The keys of the decryption.
+    ///
+    /// These should include every key the fields may have been encrypted with, including the
+    /// rotated ones.
+    pub keys: Vec<EncryptionKey>,
+
+    /// The additional authenticated data the envelopes must be bound to.
+    ///
+    /// Envelopes with other additional authenticated data are not decrypted. If unset, envelopes
+    /// are decrypted regardless of it.
+    #[configurable(metadata(docs::examples = "tenant=acme"))]
+    pub aad: Option<String>,
+}
+
+impl GenerateConfig for DecryptFieldsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"fields = ["user.email"]
+            keys = [{ id = "2024-q1", secret = "SECRET[vault.field_encryption_2024_q1]" }]
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+impl DecryptFieldsConfig {
+    fn build_transform(&self, fips_enabled: bool) -> crate::Result<DecryptFields> {
+        check_fips_mode("decrypt_fields", fips_enabled)?;
+        DecryptFields::new(self)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "decrypt_fields")]
+impl TransformConfig for DecryptFieldsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        self.build_transform(vector_core::fips::is_enabled())
+            .map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let paths = parse_fields(&self.fields)
+            .map(|fields| fields.into_iter().map(|(_, path)| path).collect::<Vec<_>>())
+            .unwrap_or_default();
+        vec![TransformOutput::new(
+            DataType::Log,
+            input_definitions
+                .iter()
+                .map(|(output, definition)| {
+                    let mut definition = definition.clone();
+                    for path in &paths {
+                        definition = definition.with_field(path, Kind::any(), None);
+                    }
+                    (output.clone(), definition)
+                })
+                .collect(),
+        )]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DecryptFields {
+    fields: Vec<(String, OwnedTargetPath)>,
+    keys: Vec<Key>,
+    aad: Option<String>,
+}
+
+impl DecryptFields {
+    pub fn new(config: &DecryptFieldsConfig) -> crate::Result<Self> {
+        Ok(Self {
+            fields: parse_fields(&config.fields)?,
+            keys: decode_keys(&config.keys)?,
+            aad: config.aad.clone(),
+        })
+    }
+
+    fn decrypt(&self, mut log: LogEvent) -> LogEvent {
+        for (field, path) in &self.fields {
+            let Some(value) = log.get(path) else {
+                continue;
+            };
+            if !is_envelope(value) {
+                continue;
+            }
+            match self.open(value) {
+                Ok(value) => {
+                    log.insert(path, value);
+                }
+                Err(error) => emit!(FieldDecryptionError { field, error }),
+            }
+        }
+        log
+    }
+
+    fn open(&self, value: &Value) -> Result<Value, String> {
+        let envelope = Envelope::from_value(value).map_err(|error| error.to_string())?;
+        if self.aad.is_some() && envelope.aad != self.aad {
+            return Err(format!(
+                "unexpected additional authenticated data {:?}",
+                envelope.aad
+            ));
+        }
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.id == envelope.kid)
+            .ok_or_else(|| format!("unknown key ID {:?}", envelope.kid))?;
+        envelope
+            .open(&key.secret)
+            .map_err(|error| error.to_string())
+    }
+}
+
+impl SyncTransform for DecryptFields {
+    fn transform(
+        &mut self,
+        event: Event,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        output.push(self.decrypt(event.into_log()).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_vrl_functions::envelope::KEY_LENGTH;
+
+    use super::*;
+
+    fn config(aad: Option<&str>) -> DecryptFieldsConfig {
+        let mut config: DecryptFieldsConfig = toml::from_str(
+            r#"
+                fields = ["user.email", "status", "message"]
+
+                [[keys]]
+                id = "2024-q1"
+                secret = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQ=="
+            "#,
+        )
+        .unwrap();
+        config.aad = aad.map(ToOwned::to_owned);
+        config
+    }
+
+    fn event(kid: &str, key: u8, aad: Option<&str>) -> LogEvent {
+        let mut log = LogEvent::from("login");
+        log.insert(
+            "user.email",
+            Envelope::seal(&"jane@example.com".into(), kid, &[key; KEY_LENGTH], aad).unwrap(),
+        );
+        log.insert(
+            "status",
+            Envelope::seal(&Value::Integer(200), kid, &[key; KEY_LENGTH], aad).unwrap(),
+        );
+        log
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DecryptFieldsConfig>();
+    }
+
+    #[test]
+    fn unavailable_in_fips_mode() {
+        assert!(config(None).build_transform(false).is_ok());
+        assert!(config(None).build_transform(true).is_err());
+    }
+
+    #[test]
+    fn decrypts_envelopes() {
+        let transform = DecryptFields::new(&config(Some("tenant=acme"))).unwrap();
+
+        let log = transform.decrypt(event("2024-q1", 1, Some("tenant=acme")));
+        assert_eq!(log["user.email"], "jane@example.com".into());
+        assert_eq!(log["status"], Value::Integer(200));
+        // Fields that don't hold an envelope are left as they are.
+        assert_eq!(log["message"], "login".into());
+    }
+
+    #[test]
+    fn leaves_envelopes_that_cant_be_decrypted() {
+        let transform = DecryptFields::new(&config(Some("tenant=acme"))).unwrap();
+
+        for log in [
+            // Unknown key.
+            event("2024-q2", 2, Some("tenant=acme")),
+            // Wrong key.
+            event("2024-q1", 2, Some("tenant=acme")),
+            // Wrong additional authenticated data.
+            event("2024-q1", 1, Some("tenant=other")),
+            event("2024-q1", 1, None),
+        ] {
+            assert_eq!(transform.decrypt(log.clone()), log);
+        }
+
+        // Without expected additional authenticated data, any is accepted.
+        let transform = DecryptFields::new(&config(None)).unwrap();
+        let log = transform.decrypt(event("2024-q1", 1, Some("tenant=other")));
+        assert_eq!(log["user.email"], "jane@example.com".into());
+    }
+}
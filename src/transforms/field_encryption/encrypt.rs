@@ -0,0 +1,258 @@
+use chrono::{DateTime, Utc};
+use lookup::OwnedTargetPath;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, transform::SyncTransform};
+use vector_vrl_functions::envelope::{is_envelope, Envelope};
+use vrl::value::{kind::Collection, Kind};
+
+use super::{check_fips_mode, decode_keys, parse_fields, EncryptionKey, Key};
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::FieldEncryptionError,
+    schema,
+    transforms::Transform,
+};
+
+/// Configuration for the `encrypt_fields` transform.
+#[configurable_component(transform(
+    "encrypt_fields",
+    "Encrypt the values of fields into envelopes that any hop downstream can decrypt."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptFieldsConfig {
+    /// The fields whose values are encrypted.
+    ///
+    /// Each value is replaced with an envelope, an object with the ciphertext and the `kid` of the
+    /// key, which the `decrypt_fields` transform and the `decrypt_envelope` VRL function decrypt.
+    /// Missing fields, and fields that already hold an envelope, are left as they are.
+    #[configurable(metadata(docs::examples = "user.email", docs::examples = "card_number"))]
+    pub fields: Vec<String>,
+
+    /// The keys of the encryption.
+    pub keys: Vec<EncryptionKey>,
+
+    /// Additional authenticated data bound to the envelopes.
+    ///
+    /// It is stored in the clear in the `aad` field of the envelopes, but can't be altered without
+    /// the decryption failing, so a `decrypt_fields` transform expecting it only decrypts
+    /// envelopes that were encrypted for it.
+    #[configurable(metadata(docs::examples = "tenant=acme"))]
+    pub aad: Option<String>,
+}
+
+impl GenerateConfig for EncryptFieldsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"fields = ["user.email"]
+            keys = [{ id = "2024-q1", secret = "SECRET[vault.field_encryption_2024_q1]" }]
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+impl EncryptFieldsConfig {
+    fn build_transform(&self, fips_enabled: bool) -> crate::Result<EncryptFields> {
+        check_fips_mode("encrypt_fields", fips_enabled)?;
+        EncryptFields::new(self)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "encrypt_fields")]
+impl TransformConfig for EncryptFieldsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        self.build_transform(vector_core::fips::is_enabled())
+            .map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let paths = parse_fields(&self.fields)
+            .map(|fields| fields.into_iter().map(|(_, path)| path).collect::<Vec<_>>())
+            .unwrap_or_default();
+        vec![TransformOutput::new(
+            DataType::Log,
+            input_definitions
+                .iter()
+                .map(|(output, definition)| {
+                    let mut definition = definition.clone();
+                    for path in &paths {
+                        definition = definition.with_field(
+                            path,
+                            Kind::object(Collection::any()).or_undefined(),
+                            None,
+                        );
+                    }
+                    (output.clone(), definition)
+                })
+                .collect(),
+        )]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EncryptFields {
+    fields: Vec<(String, OwnedTargetPath)>,
+    /// The keys, ordered by the time they become active.
+    keys: Vec<Key>,
+    aad: Option<String>,
+}
+
+impl EncryptFields {
+    pub fn new(config: &EncryptFieldsConfig) -> crate::Result<Self> {
+        Ok(Self {
+            fields: parse_fields(&config.fields)?,
+            keys: decode_keys(&config.keys)?,
+            aad: config.aad.clone(),
+        })
+    }
+
+    /// The key active at `now`, which is the first one if none is active yet.
+    fn key_at(&self, now: DateTime<Utc>) -> &Key {
+        self.keys
+            .iter()
+            .rev()
+            .find(|key| key.active_from.map_or(true, |from| from <= now))
+            .unwrap_or(&self.keys[0])
+    }
+
+    /// Encrypts the fields of the event, which is dropped if one of them can't be encrypted so
+    /// that its value isn't sent in the clear.
+    fn encrypt(&self, mut log: LogEvent, now: DateTime<Utc>) -> Option<LogEvent> {
+        let key = self.key_at(now);
+        for (field, path) in &self.fields {
+            let Some(value) = log.get(path) else {
+                continue;
+            };
+            if is_envelope(value) {
+                continue;
+            }
+            match Envelope::seal(value, &key.id, &key.secret, self.aad.as_deref()) {
+                Ok(envelope) => {
+                    log.insert(path, envelope);
+                }
+                Err(error) => {
+                    emit!(FieldEncryptionError { field, error });
+                    return None;
+                }
+            }
+        }
+        Some(log)
+    }
+}
+
+impl SyncTransform for EncryptFields {
+    fn transform(
+        &mut self,
+        event: Event,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        if let Some(log) = self.encrypt(event.into_log(), Utc::now()) {
+            output.push(log.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_vrl_functions::envelope::KEY_LENGTH;
+
+    use super::*;
+    use crate::event::Value;
+
+    fn config() -> EncryptFieldsConfig {
+        toml::from_str(
+            r#"
+                fields = ["user.email", "status"]
+                aad = "tenant=acme"
+
+                [[keys]]
+                id = "2024-q2"
+                secret = "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAg=="
+                active_from = "2024-04-01T00:00:00Z"
+
+                [[keys]]
+                id = "2024-q1"
+                secret = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQ=="
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn event() -> LogEvent {
+        let mut log = LogEvent::from("login");
+        log.insert("user.email", "jane@example.com");
+        log.insert("status", 200);
+        log
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EncryptFieldsConfig>();
+    }
+
+    #[test]
+    fn unavailable_in_fips_mode() {
+        assert!(config().build_transform(false).is_ok());
+        assert!(config().build_transform(true).is_err());
+    }
+
+    #[test]
+    fn encrypts_with_active_key() {
+        let transform = EncryptFields::new(&config()).unwrap();
+        let march = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+        let april = Utc.with_ymd_and_hms(2024, 4, 15, 0, 0, 0).unwrap();
+
+        let log = transform.encrypt(event(), march).unwrap();
+        assert_eq!(log["message"], "login".into());
+        let envelope = Envelope::from_value(&log["user.email"]).unwrap();
+        assert_eq!(envelope.kid, "2024-q1");
+        assert_eq!(envelope.aad.as_deref(), Some("tenant=acme"));
+        assert_eq!(
+            envelope.open(&[1; KEY_LENGTH]).unwrap(),
+            "jane@example.com".into()
+        );
+        let envelope = Envelope::from_value(&log["status"]).unwrap();
+        assert_eq!(
+            envelope.open(&[1; KEY_LENGTH]).unwrap(),
+            Value::Integer(200)
+        );
+
+        let log = transform.encrypt(event(), april).unwrap();
+        let envelope = Envelope::from_value(&log["user.email"]).unwrap();
+        assert_eq!(envelope.kid, "2024-q2");
+        assert_eq!(
+            envelope.open(&[2; KEY_LENGTH]).unwrap(),
+            "jane@example.com".into()
+        );
+    }
+
+    #[test]
+    fn leaves_envelopes_and_missing_fields() {
+        let transform = EncryptFields::new(&config()).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+
+        let once = transform.encrypt(event(), now).unwrap();
+        let twice = transform.encrypt(once.clone(), now).unwrap();
+        assert_eq!(once, twice);
+
+        let log = transform.encrypt(LogEvent::from("login"), now).unwrap();
+        assert!(!log.contains("user.email"));
+        assert!(!log.contains("status"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Encryption of fields into envelopes, the format of [`vector_vrl_functions::envelope`], which the
+//! `encrypt_envelope` and `decrypt_envelope` VRL functions share.
+
+use std::collections::HashSet;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use chrono::{DateTime, Utc};
+use lookup::{lookup_v2::parse_target_path, OwnedTargetPath};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_vrl_functions::envelope::KEY_LENGTH;
+
+#[cfg(feature = "transforms-decrypt_fields")]
+pub mod decrypt;
+#[cfg(feature = "transforms-encrypt_fields")]
+pub mod encrypt;
+
+/// A key of the field encryption.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionKey {
+    /// The ID of the key, recorded in the `kid` field of the envelopes encrypted with it.
+    #[configurable(metadata(docs::examples = "2024-q1"))]
+    pub id: String,
+
+    /// The secret of the key, 64 random bytes encoded in base64.
+    ///
+    /// Keys are best loaded from a secrets backend, with `SECRET[<backend>.<key>]`.
+    #[configurable(metadata(docs::examples = "SECRET[vault.field_encryption_2024_q1]"))]
+    pub secret: SensitiveString,
+
+    /// When the key starts being used to encrypt, as an RFC 3339 timestamp.
+    ///
+    /// Fields are encrypted with the key active the latest, which lets keys be rotated on a
+    /// schedule by listing the next key ahead of time. If unset, the key is active from the start.
+    /// Keys are used to decrypt regardless of when they are active.
+    #[configurable(metadata(docs::examples = "2024-01-01T00:00:00Z"))]
+    pub active_from: Option<DateTime<Utc>>,
+}
+
+/// A key, with its secret decoded.
+#[derive(Clone, Debug)]
+struct Key {
+    id: String,
+    secret: Vec<u8>,
+    active_from: Option<DateTime<Utc>>,
+}
+
+/// Decodes the keys, ordered by the time they become active.
+fn decode_keys(keys: &[EncryptionKey]) -> crate::Result<Vec<Key>> {
+    if keys.is_empty() {
+        return Err("`keys` must include at least one key.".into());
+    }
+    let mut ids = HashSet::new();
+    let mut decoded = Vec::with_capacity(keys.len());
+    for key in keys {
+        if !ids.insert(key.id.as_str()) {
+            return Err(format!("Duplicate key ID {:?}.", key.id).into());
+        }
+        let secret = BASE64_STANDARD
+            .decode(key.secret.inner().trim())
+            .map_err(|_| format!("The secret of the key {:?} is not valid base64.", key.id))?;
+        if secret.len() != KEY_LENGTH {
+            return Err(format!(
+                "The secret of the key {:?} must be {} bytes long, found {} bytes.",
+                key.id,
+                KEY_LENGTH,
+                secret.len()
+            )
+            .into());
+        }
+        decoded.push(Key {
+            id: key.id.clone(),
+            secret,
+            active_from: key.active_from,
+        });
+    }
+    // Keys without an activation time come first.
+    decoded.sort_by_key(|key| key.active_from);
+    Ok(decoded)
+}
+
+/// Fails in FIPS mode, as envelopes are sealed with AES and HMAC implementations that aren't part of
+/// a FIPS validated module.
+fn check_fips_mode(transform: &str, fips_enabled: bool) -> crate::Result<()> {
+    if fips_enabled {
+        return Err(format!(
+            "The `{}` transform is not available in FIPS mode, as its cryptography is not FIPS validated.",
+            transform
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn parse_fields(fields: &[String]) -> crate::Result<Vec<(String, OwnedTargetPath)>> {
+    fields
+        .iter()
+        .map(|field| {
+            parse_target_path(field)
+                .map(|path| (field.clone(), path))
+                .map_err(|_| format!("Invalid field path {:?}.", field))
+        })
+        .collect::<Result<_, _>>()
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: &str, secret: &str) -> EncryptionKey {
+        EncryptionKey {
+            id: id.to_owned(),
+            secret: secret.to_owned().into(),
+            active_from: None,
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        let valid = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQ==";
+        assert!(decode_keys(&[key("a", valid)]).is_ok());
+        assert!(decode_keys(&[]).is_err());
+        assert!(decode_keys(&[key("a", valid), key("a", valid)]).is_err());
+        assert!(decode_keys(&[key("a", "not base64!")]).is_err());
+        // 32 bytes, as for AES-256 alone.
+        assert!(decode_keys(&[key("a", "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=")]).is_err());
+    }
+
+    #[test]
+    fn envelope_vrl_functions_not_approved() {
+        let identifiers = vector_vrl_functions::all()
+            .iter()
+            .map(|function| function.identifier())
+            .collect::<Vec<_>>();
+        for identifier in ["decrypt_envelope", "encrypt_envelope"] {
+            assert!(identifiers.contains(&identifier), "{identifier}");
+            assert!(
+                vector_core::fips::NON_APPROVED_VRL_FUNCTIONS.contains(&identifier),
+                "{identifier}"
+            );
+        }
+    }
+}
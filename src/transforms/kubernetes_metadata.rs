@@ -0,0 +1,790 @@
+//! Enriches events with the metadata of the Kubernetes objects they refer to.
+
+use std::{collections::BTreeMap, fmt::Debug, path::PathBuf, pin::Pin};
+
+use futures::{Stream, StreamExt};
+use k8s_openapi::{
+    api::{
+        apps::v1::{Deployment, ReplicaSet},
+        core::v1::{Node, Service, ServiceAccount},
+    },
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+};
+use kube::{
+    api::Api,
+    config::{self, KubeConfigOptions},
+    runtime::{
+        reflector::{self, store::Store, ObjectRef},
+        watcher, WatchStreamExt,
+    },
+    Client, Config as ClientConfig, Resource,
+};
+use lookup::{lookup_v2::OptionalTargetPath, owned_value_path, OwnedTargetPath};
+use serde::de::DeserializeOwned;
+use serde_with::serde_as;
+use tokio::{task::JoinHandle, time::Duration};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::{Event, LogEvent, Value},
+    kubernetes::{custom_reflector, meta_cache::MetaCache},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The well-known label holding the zone of a Node.
+const ZONE_LABEL: &str = "topology.kubernetes.io/zone";
+
+/// The deprecated label holding the zone of a Node, still set by some providers.
+const LEGACY_ZONE_LABEL: &str = "failure-domain.beta.kubernetes.io/zone";
+
+const fn default_delay_deletion_ms() -> Duration {
+    Duration::from_millis(60_000)
+}
+
+/// Configuration for the `kubernetes_metadata` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "kubernetes_metadata",
+    "Enrich events with the metadata of the Kubernetes objects they refer to."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesMetadataConfig {
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    pub kube_config_file: Option<PathBuf>,
+
+    /// Determines if requests to the kube-apiserver can be served by a cache.
+    #[serde(default)]
+    pub use_apiserver_cache: bool,
+
+    /// How long to delay removing metadata entries from the cache when a deletion event is
+    /// received from the watch stream.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_delay_deletion_ms")]
+    #[configurable(metadata(docs::human_name = "Delay Deletion"))]
+    pub delay_deletion_ms: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub lookup_fields: LookupFieldsSpec,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub annotation_fields: FieldsSpec,
+}
+
+/// Configuration for the event fields identifying the Kubernetes objects an event refers to.
+///
+/// The defaults match the fields added by the `kubernetes_logs` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct LookupFieldsSpec {
+    /// Event field holding the name of the Node.
+    ///
+    /// Set to `""` to skip the enrichment with Node metadata.
+    #[configurable(metadata(docs::examples = ".host"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub node_name: OptionalTargetPath,
+
+    /// Event field holding the namespace of the Pod.
+    ///
+    /// Deployments, Services, and ServiceAccounts are looked up in this namespace. Set to `""` to
+    /// only enrich events with Node metadata.
+    #[configurable(metadata(docs::examples = ".k8s.pod_namespace"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub namespace: OptionalTargetPath,
+
+    /// Event field holding the owner reference of the Pod, formatted as `<kind>/<name>`.
+    ///
+    /// Pods owned by a ReplicaSet are resolved to the Deployment owning the ReplicaSet.
+    #[configurable(metadata(docs::examples = ".k8s.pod_owner"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub owner: OptionalTargetPath,
+
+    /// Event field holding the labels of the Pod, matched against the selectors of Services.
+    #[configurable(metadata(docs::examples = ".k8s.pod_labels"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub pod_labels: OptionalTargetPath,
+
+    /// Event field holding the name of the ServiceAccount of the Pod.
+    ///
+    /// Unset by default, as the `kubernetes_logs` source doesn't add it.
+    #[configurable(metadata(docs::examples = ".kubernetes.pod_service_account"))]
+    pub service_account: OptionalTargetPath,
+}
+
+impl Default for LookupFieldsSpec {
+    fn default() -> Self {
+        Self {
+            node_name: OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_node_name"))
+                .into(),
+            namespace: OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_namespace"))
+                .into(),
+            owner: OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_owner")).into(),
+            pod_labels: OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_labels"))
+                .into(),
+            service_account: OptionalTargetPath::none(),
+        }
+    }
+}
+
+/// Configuration for how the events are enriched with the metadata of Kubernetes objects.
+///
+/// Only the objects needed by the fields that are set are watched.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct FieldsSpec {
+    /// Event field for the Node's labels.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.node_labels"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub node_labels: OptionalTargetPath,
+
+    /// Event field for the Node's zone, taken from its `topology.kubernetes.io/zone` label.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.node_zone"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub node_zone: OptionalTargetPath,
+
+    /// Event field for the name of the Deployment owning the Pod.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.deployment_name"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub deployment_name: OptionalTargetPath,
+
+    /// Event field for the labels of the Deployment owning the Pod.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.deployment_labels"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub deployment_labels: OptionalTargetPath,
+
+    /// Event field for the names of the Services selecting the Pod.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.service_names"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub service_names: OptionalTargetPath,
+
+    /// Event field for the labels of the ServiceAccount of the Pod.
+    ///
+    /// Set to `""` to suppress this key.
+    #[configurable(metadata(docs::examples = ".k8s.service_account_labels"))]
+    #[configurable(metadata(docs::examples = ""))]
+    pub service_account_labels: OptionalTargetPath,
+}
+
+impl Default for FieldsSpec {
+    fn default() -> Self {
+        Self {
+            node_labels: OwnedTargetPath::event(owned_value_path!("kubernetes", "node_labels"))
+                .into(),
+            node_zone: OwnedTargetPath::event(owned_value_path!("kubernetes", "node_zone")).into(),
+            deployment_name: OwnedTargetPath::event(owned_value_path!(
+                "kubernetes",
+                "deployment_name"
+            ))
+            .into(),
+            deployment_labels: OwnedTargetPath::event(owned_value_path!(
+                "kubernetes",
+                "deployment_labels"
+            ))
+            .into(),
+            service_names: OwnedTargetPath::event(owned_value_path!("kubernetes", "service_names"))
+                .into(),
+            service_account_labels: OwnedTargetPath::event(owned_value_path!(
+                "kubernetes",
+                "service_account_labels"
+            ))
+            .into(),
+        }
+    }
+}
+
+impl Default for KubernetesMetadataConfig {
+    fn default() -> Self {
+        Self {
+            kube_config_file: None,
+            use_apiserver_cache: false,
+            delay_deletion_ms: default_delay_deletion_ms(),
+            lookup_fields: LookupFieldsSpec::default(),
+            annotation_fields: FieldsSpec::default(),
+        }
+    }
+}
+
+impl_generate_config_from_default!(KubernetesMetadataConfig);
+
+impl KubernetesMetadataConfig {
+    const fn watches_nodes(&self) -> bool {
+        self.lookup_fields.node_name.path.is_some()
+            && (self.annotation_fields.node_labels.path.is_some()
+                || self.annotation_fields.node_zone.path.is_some())
+    }
+
+    const fn watches_deployments(&self) -> bool {
+        self.lookup_fields.namespace.path.is_some()
+            && self.lookup_fields.owner.path.is_some()
+            && (self.annotation_fields.deployment_name.path.is_some()
+                || self.annotation_fields.deployment_labels.path.is_some())
+    }
+
+    const fn watches_services(&self) -> bool {
+        self.lookup_fields.namespace.path.is_some()
+            && self.lookup_fields.pod_labels.path.is_some()
+            && self.annotation_fields.service_names.path.is_some()
+    }
+
+    const fn watches_service_accounts(&self) -> bool {
+        self.lookup_fields.namespace.path.is_some()
+            && self.lookup_fields.service_account.path.is_some()
+            && self.annotation_fields.service_account_labels.path.is_some()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_metadata")]
+impl TransformConfig for KubernetesMetadataConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubeconfig, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let list_semantic = if self.use_apiserver_cache {
+            watcher::ListSemantic::Any
+        } else {
+            watcher::ListSemantic::MostRecent
+        };
+        let mut watchers = Watchers {
+            client,
+            config: watcher::Config {
+                list_semantic,
+                ..Default::default()
+            },
+            delay_deletion: self.delay_deletion_ms,
+            reflectors: Vec::new(),
+        };
+
+        let nodes = self.watches_nodes().then(|| watchers.watch::<Node>());
+        let (replica_sets, deployments) = if self.watches_deployments() {
+            let deployments = self
+                .annotation_fields
+                .deployment_labels
+                .path
+                .is_some()
+                .then(|| watchers.watch::<Deployment>());
+            (Some(watchers.watch::<ReplicaSet>()), deployments)
+        } else {
+            (None, None)
+        };
+        let services = self.watches_services().then(|| watchers.watch::<Service>());
+        let service_accounts = self
+            .watches_service_accounts()
+            .then(|| watchers.watch::<ServiceAccount>());
+
+        Ok(Transform::event_task(KubernetesMetadata {
+            lookup_fields: self.lookup_fields.clone(),
+            fields: self.annotation_fields.clone(),
+            stores: Stores {
+                nodes,
+                replica_sets,
+                deployments,
+                services,
+                service_accounts,
+            },
+            reflectors: watchers.reflectors,
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let labels = Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined();
+        let fields = &self.annotation_fields;
+        let added = [
+            (&fields.node_labels, labels.clone()),
+            (&fields.node_zone, Kind::bytes().or_undefined()),
+            (&fields.deployment_name, Kind::bytes().or_undefined()),
+            (&fields.deployment_labels, labels.clone()),
+            (
+                &fields.service_names,
+                Kind::array(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+            ),
+            (&fields.service_account_labels, labels),
+        ];
+
+        let schema_definition = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                let mut schema_definition = definition.clone();
+                for (field, kind) in &added {
+                    if let Some(path) = &field.path {
+                        schema_definition = schema_definition.with_field(path, kind.clone(), None);
+                    }
+                }
+                (output.clone(), schema_definition)
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, schema_definition)]
+    }
+}
+
+/// Spawns the reflectors keeping the stores of the watched objects up to date.
+struct Watchers {
+    client: Client,
+    config: watcher::Config,
+    delay_deletion: Duration,
+    reflectors: Vec<JoinHandle<()>>,
+}
+
+impl Watchers {
+    fn watch<K>(&mut self) -> Store<K>
+    where
+        K: Resource<DynamicType = ()> + Clone + Debug + DeserializeOwned + Send + Sync + 'static,
+    {
+        let objects_watcher = watcher(Api::<K>::all(self.client.clone()), self.config.clone())
+            .backoff(watcher::default_backoff());
+        let store_w = reflector::store::Writer::default();
+        let store = store_w.as_reader();
+
+        self.reflectors.push(tokio::spawn(custom_reflector(
+            store_w,
+            MetaCache::new(),
+            objects_watcher,
+            self.delay_deletion,
+        )));
+        store
+    }
+}
+
+/// The stores of the watched objects, `None` for the objects that aren't needed.
+#[derive(Default)]
+struct Stores {
+    nodes: Option<Store<Node>>,
+    replica_sets: Option<Store<ReplicaSet>>,
+    deployments: Option<Store<Deployment>>,
+    services: Option<Store<Service>>,
+    service_accounts: Option<Store<ServiceAccount>>,
+}
+
+pub struct KubernetesMetadata {
+    lookup_fields: LookupFieldsSpec,
+    fields: FieldsSpec,
+    stores: Stores,
+    reflectors: Vec<JoinHandle<()>>,
+}
+
+impl Drop for KubernetesMetadata {
+    fn drop(&mut self) {
+        for reflector in &self.reflectors {
+            reflector.abort();
+        }
+    }
+}
+
+impl TaskTransform<Event> for KubernetesMetadata {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let inner = self;
+        Box::pin(task.map(move |mut event| {
+            inner.annotate(event.as_mut_log());
+            event
+        }))
+    }
+}
+
+impl KubernetesMetadata {
+    fn annotate(&self, log: &mut LogEvent) {
+        if let Some(nodes) = &self.stores.nodes {
+            if let Some(node) = lookup_string(log, &self.lookup_fields.node_name)
+                .and_then(|name| nodes.get(&ObjectRef::new(&name)))
+            {
+                insert_labels(log, &self.fields.node_labels, &node.metadata);
+                if let Some(zone) = node_zone(&node.metadata) {
+                    insert(log, &self.fields.node_zone, zone.into());
+                }
+            }
+        }
+
+        let Some(namespace) = lookup_string(log, &self.lookup_fields.namespace) else {
+            return;
+        };
+
+        if let Some(name) = self.deployment_name(log, &namespace) {
+            if let Some(deployment) =
+                self.stores.deployments.as_ref().and_then(|deployments| {
+                    deployments.get(&ObjectRef::new(&name).within(&namespace))
+                })
+            {
+                insert_labels(log, &self.fields.deployment_labels, &deployment.metadata);
+            }
+            insert(log, &self.fields.deployment_name, name.into());
+        }
+
+        if let Some(services) = &self.stores.services {
+            let service_names = lookup_labels(log, &self.lookup_fields.pod_labels)
+                .map(|pod_labels| selecting_services(services, &namespace, &pod_labels));
+            if let Some(names) = service_names.filter(|names| !names.is_empty()) {
+                insert(log, &self.fields.service_names, names.into());
+            }
+        }
+
+        if let Some(service_accounts) = &self.stores.service_accounts {
+            if let Some(service_account) = lookup_string(log, &self.lookup_fields.service_account)
+                .and_then(|name| service_accounts.get(&ObjectRef::new(&name).within(&namespace)))
+            {
+                insert_labels(
+                    log,
+                    &self.fields.service_account_labels,
+                    &service_account.metadata,
+                );
+            }
+        }
+    }
+
+    /// Resolves the owner reference of the Pod to the name of its Deployment.
+    fn deployment_name(&self, log: &LogEvent, namespace: &str) -> Option<String> {
+        let replica_sets = self.stores.replica_sets.as_ref()?;
+        let owner = lookup_string(log, &self.lookup_fields.owner)?;
+        let (kind, name) = owner.split_once('/')?;
+        match kind {
+            "Deployment" => Some(name.to_owned()),
+            "ReplicaSet" => {
+                let replica_set = replica_sets.get(&ObjectRef::new(name).within(namespace))?;
+                replica_set
+                    .metadata
+                    .owner_references
+                    .as_ref()?
+                    .iter()
+                    .find(|owner| owner.kind == "Deployment")
+                    .map(|owner| owner.name.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn lookup_string(log: &LogEvent, field: &OptionalTargetPath) -> Option<String> {
+    let path = field.path.as_ref()?;
+    log.get(path)
+        .map(|value| value.to_string_lossy().into_owned())
+}
+
+fn lookup_labels(log: &LogEvent, field: &OptionalTargetPath) -> Option<BTreeMap<String, String>> {
+    let path = field.path.as_ref()?;
+    let labels = log.get(path)?.as_object()?;
+    Some(
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string_lossy().into_owned()))
+            .collect(),
+    )
+}
+
+fn insert(log: &mut LogEvent, field: &OptionalTargetPath, value: Value) {
+    if let Some(path) = &field.path {
+        log.insert(path, value);
+    }
+}
+
+fn insert_labels(log: &mut LogEvent, field: &OptionalTargetPath, metadata: &ObjectMeta) {
+    if let Some(labels) = &metadata.labels {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::from(value.as_str())))
+            .collect::<BTreeMap<_, _>>();
+        insert(log, field, labels.into());
+    }
+}
+
+fn node_zone(metadata: &ObjectMeta) -> Option<&str> {
+    let labels = metadata.labels.as_ref()?;
+    labels
+        .get(ZONE_LABEL)
+        .or_else(|| labels.get(LEGACY_ZONE_LABEL))
+        .map(String::as_str)
+}
+
+/// The names of the Services of the namespace whose selector matches the labels of the Pod.
+fn selecting_services(
+    services: &Store<Service>,
+    namespace: &str,
+    pod_labels: &BTreeMap<String, String>,
+) -> Vec<Value> {
+    let mut names = services
+        .state()
+        .iter()
+        .filter(|service| service.metadata.namespace.as_deref() == Some(namespace))
+        .filter(|service| {
+            // Services without a selector don't select Pods, their endpoints are managed manually.
+            service
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.selector.as_ref())
+                .filter(|selector| !selector.is_empty())
+                .map_or(false, |selector| {
+                    selector
+                        .iter()
+                        .all(|(key, value)| pod_labels.get(key) == Some(value))
+                })
+        })
+        .filter_map(|service| service.metadata.name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.into_iter().map(Value::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::{
+        api::core::v1::ServiceSpec, apimachinery::pkg::apis::meta::v1::OwnerReference,
+    };
+    use kube::runtime::reflector::store::Writer;
+    use similar_asserts::assert_eq;
+    use vrl::value;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesMetadataConfig>();
+    }
+
+    fn metadata(name: &str, namespace: Option<&str>, labels: &[(&str, &str)]) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_owned()),
+            namespace: namespace.map(ToOwned::to_owned),
+            labels: Some(
+                labels
+                    .iter()
+                    .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                    .collect(),
+            ),
+            ..ObjectMeta::default()
+        }
+    }
+
+    fn store<K>(objects: Vec<K>) -> Store<K>
+    where
+        K: Resource<DynamicType = ()> + Clone,
+    {
+        let mut writer = Writer::default();
+        writer.apply_watcher_event(&watcher::Event::Restarted(objects));
+        writer.as_reader()
+    }
+
+    fn transform(config: &KubernetesMetadataConfig, stores: Stores) -> KubernetesMetadata {
+        KubernetesMetadata {
+            lookup_fields: config.lookup_fields.clone(),
+            fields: config.annotation_fields.clone(),
+            stores,
+            reflectors: Vec::new(),
+        }
+    }
+
+    fn pod_log() -> LogEvent {
+        LogEvent::from(value!({
+            "message": "hello",
+            "kubernetes": {
+                "pod_node_name": "node-1",
+                "pod_namespace": "ns",
+                "pod_owner": "ReplicaSet/web-7d9f8",
+                "pod_labels": {"app": "web", "tier": "frontend"},
+                "pod_service_account": "web",
+            },
+        }))
+    }
+
+    #[test]
+    fn annotates_with_watched_objects() {
+        let mut config = KubernetesMetadataConfig::default();
+        config.lookup_fields.service_account =
+            OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_service_account")).into();
+
+        let transform = transform(
+            &config,
+            Stores {
+                nodes: Some(store(vec![Node {
+                    metadata: metadata(
+                        "node-1",
+                        None,
+                        &[("kubernetes.io/os", "linux"), (ZONE_LABEL, "eu-west-1a")],
+                    ),
+                    ..Node::default()
+                }])),
+                replica_sets: Some(store(vec![ReplicaSet {
+                    metadata: ObjectMeta {
+                        owner_references: Some(vec![OwnerReference {
+                            kind: "Deployment".to_owned(),
+                            name: "web".to_owned(),
+                            ..OwnerReference::default()
+                        }]),
+                        ..metadata("web-7d9f8", Some("ns"), &[])
+                    },
+                    ..ReplicaSet::default()
+                }])),
+                deployments: Some(store(vec![Deployment {
+                    metadata: metadata("web", Some("ns"), &[("team", "checkout")]),
+                    ..Deployment::default()
+                }])),
+                services: Some(store(
+                    [
+                        ("web", "ns", vec![("app", "web")]),
+                        (
+                            "web-frontend",
+                            "ns",
+                            vec![("app", "web"), ("tier", "frontend")],
+                        ),
+                        ("api", "ns", vec![("app", "api")]),
+                        ("web", "other", vec![("app", "web")]),
+                        ("external", "ns", vec![]),
+                    ]
+                    .into_iter()
+                    .map(|(name, namespace, selector)| Service {
+                        metadata: metadata(name, Some(namespace), &[]),
+                        spec: Some(ServiceSpec {
+                            selector: Some(
+                                selector
+                                    .into_iter()
+                                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                                    .collect(),
+                            ),
+                            ..ServiceSpec::default()
+                        }),
+                        ..Service::default()
+                    })
+                    .collect(),
+                )),
+                service_accounts: Some(store(vec![ServiceAccount {
+                    metadata: metadata("web", Some("ns"), &[("iam", "web-role")]),
+                    ..ServiceAccount::default()
+                }])),
+            },
+        );
+
+        let mut log = pod_log();
+        transform.annotate(&mut log);
+
+        assert_eq!(
+            log.get("kubernetes.node_labels").unwrap(),
+            &value!({"kubernetes.io/os": "linux", "topology.kubernetes.io/zone": "eu-west-1a"})
+        );
+        assert_eq!(
+            log.get("kubernetes.node_zone").unwrap(),
+            &value!("eu-west-1a")
+        );
+        assert_eq!(
+            log.get("kubernetes.deployment_name").unwrap(),
+            &value!("web")
+        );
+        assert_eq!(
+            log.get("kubernetes.deployment_labels").unwrap(),
+            &value!({"team": "checkout"})
+        );
+        assert_eq!(
+            log.get("kubernetes.service_names").unwrap(),
+            &value!(["web", "web-frontend"])
+        );
+        assert_eq!(
+            log.get("kubernetes.service_account_labels").unwrap(),
+            &value!({"iam": "web-role"})
+        );
+    }
+
+    #[test]
+    fn skips_unknown_objects() {
+        let config = KubernetesMetadataConfig::default();
+        let transform = transform(
+            &config,
+            Stores {
+                nodes: Some(store(vec![])),
+                replica_sets: Some(store(vec![])),
+                ..Stores::default()
+            },
+        );
+
+        let mut log = pod_log();
+        transform.annotate(&mut log);
+
+        assert_eq!(log, pod_log());
+    }
+
+    #[test]
+    fn resolves_deployment_owners() {
+        let config = KubernetesMetadataConfig::default();
+        let transform = transform(
+            &config,
+            Stores {
+                replica_sets: Some(store(vec![])),
+                ..Stores::default()
+            },
+        );
+
+        let mut log = pod_log();
+        log.insert("kubernetes.pod_owner", "Deployment/api");
+        transform.annotate(&mut log);
+        assert_eq!(
+            log.get("kubernetes.deployment_name").unwrap(),
+            &value!("api")
+        );
+
+        let mut log = pod_log();
+        log.insert("kubernetes.pod_owner", "StatefulSet/db");
+        transform.annotate(&mut log);
+        assert!(log.get("kubernetes.deployment_name").is_none());
+    }
+
+    #[test]
+    fn watches_only_needed_objects() {
+        let mut config = KubernetesMetadataConfig::default();
+        assert!(config.watches_nodes());
+        assert!(config.watches_deployments());
+        assert!(config.watches_services());
+        assert!(!config.watches_service_accounts());
+
+        config.annotation_fields.node_labels = OptionalTargetPath::none();
+        config.annotation_fields.node_zone = OptionalTargetPath::none();
+        config.lookup_fields.namespace = OptionalTargetPath::none();
+        assert!(!config.watches_nodes());
+        assert!(!config.watches_deployments());
+        assert!(!config.watches_services());
+    }
+}
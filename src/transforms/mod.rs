@@ -6,17 +6,30 @@ use snafu::Snafu;
 
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
+#[cfg(feature = "transforms-anomaly_detection")]
+pub mod anomaly_detection;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-clock_skew")]
+pub mod clock_skew;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(any(
+    feature = "transforms-decrypt_fields",
+    feature = "transforms-encrypt_fields"
+))]
+pub mod field_encryption;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
+#[cfg(feature = "transforms-kubernetes_metadata")]
+pub mod kubernetes_metadata;
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-pseudonymize")]
+pub mod pseudonymize;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
@@ -25,10 +38,14 @@ pub mod remap;
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-sessionize")]
+pub mod sessionize;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-top_k")]
+pub mod top_k;
 
 pub use vector_core::transform::{
     FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
@@ -0,0 +1,392 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use lookup::{lookup_v2::parse_target_path, path, OwnedTargetPath};
+use sha2::Sha256;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, transform::SyncTransform};
+use vrl::value::Kind;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    schema,
+    transforms::Transform,
+};
+
+/// The name of the output the mapping between pseudonyms and values is sent to.
+pub(crate) const MAPPING_OUTPUT: &str = "mapping";
+
+/// A key of the keyed hash.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PseudonymizationKey {
+    /// The ID of the key, recorded in the metadata of the events pseudonymized with it.
+    #[configurable(metadata(docs::examples = "2024-q1"))]
+    pub id: String,
+
+    /// The secret of the key.
+    ///
+    /// Keys are best loaded from a secrets backend, with `SECRET[<backend>.<key>]`.
+    #[configurable(metadata(docs::examples = "SECRET[vault.pseudonymization_2024_q1]"))]
+    pub secret: SensitiveString,
+
+    /// When the key starts being used, as an RFC 3339 timestamp.
+    ///
+    /// Events are pseudonymized with the key active the latest, which lets keys be rotated on a
+    /// schedule by listing the next key ahead of time. If unset, the key is active from the start.
+    #[configurable(metadata(docs::examples = "2024-01-01T00:00:00Z"))]
+    pub active_from: Option<DateTime<Utc>>,
+}
+
+/// Configuration for the `pseudonymize` transform.
+#[configurable_component(transform(
+    "pseudonymize",
+    "Replace the values of fields with pseudonyms, keyed hashes of the values."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PseudonymizeConfig {
+    /// The fields whose values are replaced with their pseudonyms.
+    ///
+    /// The pseudonym of a value is the hex-encoded HMAC-SHA256 of the value with the active key, so
+    /// equal values have equal pseudonyms until the key rotates. Values other than strings are
+    /// hashed as their string representation. Missing fields are left as they are.
+    #[configurable(metadata(docs::examples = "user.email", docs::examples = "client_ip"))]
+    pub fields: Vec<String>,
+
+    /// The keys of the keyed hash.
+    ///
+    /// The ID of the key an event was pseudonymized with is recorded in its `pseudonymize.key_id`
+    /// metadata field, available in VRL as `%pseudonymize.key_id`.
+    pub keys: Vec<PseudonymizationKey>,
+
+    /// Whether to send the mapping between the pseudonyms and the values to the `mapping` output.
+    ///
+    /// For each pseudonymized field, an event with its `key_id`, `field`, `pseudonym`, and original
+    /// `value` is sent to `<transform_id>.mapping`, which should only be routed to a sink storing
+    /// them for authorized re-identification.
+    #[serde(default)]
+    pub emit_mapping: bool,
+}
+
+impl GenerateConfig for PseudonymizeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"fields = ["user.email"]
+            keys = [{ id = "2024-q1", secret = "SECRET[vault.pseudonymization_2024_q1]" }]
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+impl PseudonymizeConfig {
+    /// Fails in FIPS mode, as the keyed hash is computed with an HMAC-SHA256 implementation that
+    /// isn't part of a FIPS validated module.
+    fn build_transform(&self, fips_enabled: bool) -> crate::Result<Pseudonymize> {
+        if fips_enabled {
+            return Err("The `pseudonymize` transform is not available in FIPS mode, as its cryptography is not FIPS validated.".into());
+        }
+        Pseudonymize::new(self)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pseudonymize")]
+impl TransformConfig for PseudonymizeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        self.build_transform(vector_core::fips::is_enabled())
+            .map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let paths = self
+            .fields
+            .iter()
+            .filter_map(|field| parse_target_path(field).ok())
+            .collect::<Vec<_>>();
+        let mut outputs = vec![TransformOutput::new(
+            DataType::Log,
+            input_definitions
+                .iter()
+                .map(|(output, definition)| {
+                    let mut definition = definition.clone();
+                    for path in &paths {
+                        definition =
+                            definition.with_field(path, Kind::bytes().or_undefined(), None);
+                    }
+                    (output.clone(), definition)
+                })
+                .collect(),
+        )];
+        if self.emit_mapping {
+            let definition = schema::Definition::any();
+            outputs.push(
+                TransformOutput::new(
+                    DataType::Log,
+                    input_definitions
+                        .iter()
+                        .map(|(output, _)| (output.clone(), definition.clone()))
+                        .collect(),
+                )
+                .with_port(MAPPING_OUTPUT),
+            );
+        }
+        outputs
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Pseudonymize {
+    fields: Vec<(String, OwnedTargetPath)>,
+    /// The keys, ordered by the time they become active.
+    keys: Vec<PseudonymizationKey>,
+    emit_mapping: bool,
+}
+
+impl Pseudonymize {
+    pub fn new(config: &PseudonymizeConfig) -> crate::Result<Self> {
+        if config.keys.is_empty() {
+            return Err("`keys` must include at least one key.".into());
+        }
+        let mut ids = HashSet::new();
+        for key in &config.keys {
+            if !ids.insert(key.id.as_str()) {
+                return Err(format!("Duplicate key ID {:?}.", key.id).into());
+            }
+            if key.secret.inner().is_empty() {
+                return Err(format!("The secret of the key {:?} is empty.", key.id).into());
+            }
+        }
+
+        let fields = config
+            .fields
+            .iter()
+            .map(|field| {
+                parse_target_path(field)
+                    .map(|path| (field.clone(), path))
+                    .map_err(|_| format!("Invalid field path {:?}.", field))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut keys = config.keys.clone();
+        // Keys without an activation time come first.
+        keys.sort_by_key(|key| key.active_from);
+
+        Ok(Self {
+            fields,
+            keys,
+            emit_mapping: config.emit_mapping,
+        })
+    }
+
+    /// The key active at `now`, which is the first one if none is active yet.
+    fn key_at(&self, now: DateTime<Utc>) -> &PseudonymizationKey {
+        self.keys
+            .iter()
+            .rev()
+            .find(|key| key.active_from.map_or(true, |from| from <= now))
+            .unwrap_or(&self.keys[0])
+    }
+
+    fn pseudonymize(
+        &self,
+        mut log: LogEvent,
+        now: DateTime<Utc>,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        let key = self.key_at(now);
+        for (field, path) in &self.fields {
+            let Some(value) = log.get(path) else {
+                continue;
+            };
+            let pseudonym = match value {
+                Value::Bytes(bytes) => pseudonym(key.secret.inner().as_bytes(), bytes),
+                value => pseudonym(
+                    key.secret.inner().as_bytes(),
+                    value.to_string_lossy().as_bytes(),
+                ),
+            };
+            if self.emit_mapping {
+                let mut mapping = LogEvent::default();
+                mapping.insert("key_id", key.id.clone());
+                mapping.insert("field", field.clone());
+                mapping.insert("pseudonym", pseudonym.clone());
+                mapping.insert("value", value.clone());
+                mapping.insert("timestamp", now);
+                output.push_named(MAPPING_OUTPUT, mapping.into());
+            }
+            log.insert(path, pseudonym);
+        }
+        log.metadata_mut()
+            .value_mut()
+            .insert(path!("pseudonymize", "key_id"), key.id.clone());
+        output.push(log.into());
+    }
+}
+
+/// The hex-encoded HMAC-SHA256 of the value.
+fn pseudonym(secret: &[u8], value: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(value);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+impl SyncTransform for Pseudonymize {
+    fn transform(
+        &mut self,
+        event: Event,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        self.pseudonymize(event.into_log(), Utc::now(), output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::TimeZone;
+    use vector_core::transform::TransformOutputsBuf;
+
+    use super::*;
+
+    fn config(emit_mapping: bool) -> PseudonymizeConfig {
+        toml::from_str(&format!(
+            r#"
+                fields = ["user.email", "status"]
+                emit_mapping = {}
+
+                [[keys]]
+                id = "2024-q2"
+                secret = "second"
+                active_from = "2024-04-01T00:00:00Z"
+
+                [[keys]]
+                id = "2024-q1"
+                secret = "first"
+            "#,
+            emit_mapping
+        ))
+        .unwrap()
+    }
+
+    fn outputs(emit_mapping: bool) -> TransformOutputsBuf {
+        let mut outputs = vec![TransformOutput::new(DataType::Log, HashMap::new())];
+        if emit_mapping {
+            outputs.push(
+                TransformOutput::new(DataType::Log, HashMap::new()).with_port(MAPPING_OUTPUT),
+            );
+        }
+        TransformOutputsBuf::new_with_capacity(outputs, 1)
+    }
+
+    fn event() -> LogEvent {
+        let mut log = LogEvent::from("login");
+        log.insert("user.email", "jane@example.com");
+        log.insert("status", 200);
+        log
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PseudonymizeConfig>();
+    }
+
+    #[test]
+    fn unavailable_in_fips_mode() {
+        assert!(config(false).build_transform(false).is_ok());
+        assert!(config(false).build_transform(true).is_err());
+    }
+
+    #[test]
+    fn pseudonymizes_with_active_key() {
+        let transform = Pseudonymize::new(&config(false)).unwrap();
+        let march = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+        let april = Utc.with_ymd_and_hms(2024, 4, 15, 0, 0, 0).unwrap();
+
+        let mut output = outputs(false);
+        transform.pseudonymize(event(), march, &mut output);
+        transform.pseudonymize(event(), march, &mut output);
+        transform.pseudonymize(event(), april, &mut output);
+        let logs = output
+            .drain()
+            .map(|event| event.into_log())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            logs[0]["user.email"],
+            pseudonym(b"first", b"jane@example.com").into()
+        );
+        assert_eq!(logs[0]["status"], pseudonym(b"first", b"200").into());
+        assert_eq!(logs[0]["message"], "login".into());
+        assert_eq!(
+            logs[0]
+                .metadata()
+                .value()
+                .get(path!("pseudonymize", "key_id")),
+            Some(&Value::from("2024-q1"))
+        );
+        // Pseudonyms are stable until the key rotates.
+        assert_eq!(logs[0]["user.email"], logs[1]["user.email"]);
+        assert_ne!(logs[0]["user.email"], logs[2]["user.email"]);
+        assert_eq!(
+            logs[2]
+                .metadata()
+                .value()
+                .get(path!("pseudonymize", "key_id")),
+            Some(&Value::from("2024-q2"))
+        );
+    }
+
+    #[test]
+    fn emits_mapping() {
+        let transform = Pseudonymize::new(&config(true)).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+
+        let mut output = outputs(true);
+        let mut log = LogEvent::from("login");
+        log.insert("user.email", "jane@example.com");
+        transform.pseudonymize(log, now, &mut output);
+
+        let mappings = output
+            .drain_named(MAPPING_OUTPUT)
+            .map(|event| event.into_log())
+            .collect::<Vec<_>>();
+        // Missing fields have no mapping.
+        assert_eq!(mappings.len(), 1);
+        assert_eq!(mappings[0]["key_id"], "2024-q1".into());
+        assert_eq!(mappings[0]["field"], "user.email".into());
+        assert_eq!(
+            mappings[0]["pseudonym"],
+            pseudonym(b"first", b"jane@example.com").into()
+        );
+        assert_eq!(mappings[0]["value"], "jane@example.com".into());
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        let mut config = config(false);
+        config.keys[1].id = "2024-q2".to_owned();
+        assert!(Pseudonymize::new(&config).is_err());
+
+        config.keys.clear();
+        assert!(Pseudonymize::new(&config).is_err());
+    }
+}
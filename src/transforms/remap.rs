@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{
     collections::BTreeMap,
     fs::File,
@@ -10,6 +10,7 @@ use std::{
 use codecs::MetricTagValues;
 use lookup::lookup_v2::{parse_value_path, ValuePath};
 use lookup::{metadata_path, owned_value_path, path, OwnedTargetPath, PathPrefix};
+use once_cell::sync::Lazy;
 use snafu::{ResultExt, Snafu};
 use vector_common::TimeZone;
 use vector_config::configurable_component;
@@ -19,7 +20,7 @@ use vector_core::schema::Definition;
 use vector_vrl_functions::set_semantic_meaning::MeaningList;
 use vrl::compiler::runtime::{Runtime, Terminate};
 use vrl::compiler::state::ExternalEnv;
-use vrl::compiler::{CompileConfig, ExpressionError, Function, Program, TypeState, VrlRuntime};
+use vrl::compiler::{CompileConfig, ExpressionError, Program, TypeState, VrlRuntime};
 use vrl::diagnostic::{DiagnosticMessage, Formatter, Note};
 use vrl::value::kind::merge::{CollisionStrategy, Strategy};
 use vrl::value::kind::Collection;
@@ -31,7 +32,7 @@ use crate::{
         log_schema, ComponentKey, DataType, Input, TransformConfig, TransformContext,
         TransformOutput,
     },
-    event::{Event, TargetEvents, VrlTarget},
+    event::{Event, EventArray, EventContainer, TargetEvents, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
@@ -40,6 +41,27 @@ use crate::{
 
 const DROPPED: &str = "dropped";
 
+/// The maximum number of compiled programs kept for sharing. The cache is emptied once full, as
+/// entries are only reused while a configuration is loaded.
+const MAX_SHARED_PROGRAMS: usize = 1024;
+
+/// Programs compiled by `remap` transforms, shared between transforms with identical sources and
+/// input types, keyed by source.
+///
+/// The schema definitions of a topology compile the program of each transform several times, and
+/// templated configurations often repeat the same program across many transforms.
+static SHARED_PROGRAMS: Lazy<Mutex<HashMap<String, Vec<Arc<CompiledProgram>>>>> =
+    Lazy::new(Default::default);
+
+#[derive(Debug)]
+struct CompiledProgram {
+    event_kind: Kind,
+    metadata_kind: Kind,
+    program: Program,
+    warnings: String,
+    meanings: MeaningList,
+}
+
 /// Configuration for the `remap` transform.
 #[configurable_component(transform(
     "remap",
@@ -85,6 +107,9 @@ pub struct RemapConfig {
     /// This overrides the [global `timezone`][global_timezone] option. The time zone name may be
     /// any name in the [TZ database][tz_database], or `local` to indicate system local time.
     ///
+    /// If unset, the timestamps of events from a source that sets its own `timezone` are
+    /// converted in that timezone instead of the global one.
+    ///
     /// [global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
     /// [tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
     #[serde(default)]
@@ -134,6 +159,16 @@ pub struct RemapConfig {
     #[configurable(metadata(docs::human_name = "Reroute Dropped Events"))]
     pub reroute_dropped: bool,
 
+    /// Runs the program over each batch of events received by the transform at once.
+    ///
+    /// The setup shared by the events of a batch, such as deciding whether the original events
+    /// must be kept in case the program fails or aborts, is done once per batch rather than once
+    /// per event. Each event is still processed by its own run of the program, so the output is
+    /// identical to that of processing the events one at a time.
+    #[serde(default = "crate::serde::default_false")]
+    #[configurable(metadata(docs::advanced, docs::human_name = "Batch Execution"))]
+    pub batch: bool,
+
     #[configurable(derived, metadata(docs::hidden))]
     #[serde(default)]
     pub runtime: VrlRuntime,
@@ -144,7 +179,7 @@ impl RemapConfig {
         &self,
         enrichment_tables: enrichment::TableRegistry,
         merged_schema_definition: schema::Definition,
-    ) -> Result<(Program, String, Vec<Box<dyn Function>>, CompileConfig)> {
+    ) -> Result<Arc<CompiledProgram>> {
         let source = match (&self.source, &self.file) {
             (Some(source), None) => source.to_owned(),
             (None, Some(path)) => {
@@ -160,37 +195,67 @@ impl RemapConfig {
             _ => return Err(Box::new(BuildError::SourceAndOrFile)),
         };
 
+        let event_kind = merged_schema_definition.event_kind();
+        let metadata_kind = merged_schema_definition.metadata_kind();
+
+        // Compiling a program that uses enrichment tables registers the indexes it needs, so such
+        // programs are compiled by each transform.
+        let shareable = !source.contains("enrichment_table");
+        if shareable {
+            let programs = SHARED_PROGRAMS
+                .lock()
+                .expect("Shared programs mutex is poisoned");
+            if let Some(compiled) = programs.get(&source).and_then(|compiled| {
+                compiled.iter().find(|compiled| {
+                    &compiled.event_kind == event_kind && &compiled.metadata_kind == metadata_kind
+                })
+            }) {
+                return Ok(Arc::clone(compiled));
+            }
+        }
+
         let mut functions = vrl::stdlib::all();
         functions.append(&mut enrichment::vrl_functions());
         functions.append(&mut vector_vrl_functions::all());
+        vector_core::fips::retain_approved_vrl_functions(&mut functions);
 
         let state = TypeState {
             local: Default::default(),
-            external: ExternalEnv::new_with_kind(
-                merged_schema_definition.event_kind().clone(),
-                merged_schema_definition.metadata_kind().clone(),
-            ),
+            external: ExternalEnv::new_with_kind(event_kind.clone(), metadata_kind.clone()),
         };
         let mut config = CompileConfig::default();
 
         config.set_custom(enrichment_tables);
         config.set_custom(MeaningList::default());
 
-        compile_vrl(&source, &functions, &state, config)
-            .map_err(|diagnostics| {
-                Formatter::new(&source, diagnostics)
-                    .colored()
-                    .to_string()
-                    .into()
-            })
-            .map(|result| {
-                (
-                    result.program,
-                    Formatter::new(&source, result.warnings).to_string(),
-                    functions,
-                    result.config,
-                )
-            })
+        let result = compile_vrl(&source, &functions, &state, config)
+            .map_err(|diagnostics| Formatter::new(&source, diagnostics).colored().to_string())?;
+        let compiled = Arc::new(CompiledProgram {
+            event_kind: event_kind.clone(),
+            metadata_kind: metadata_kind.clone(),
+            program: result.program,
+            warnings: Formatter::new(&source, result.warnings).to_string(),
+            meanings: result
+                .config
+                .get_custom::<MeaningList>()
+                .cloned()
+                .expect("context exists"),
+        });
+
+        if shareable {
+            let mut programs = SHARED_PROGRAMS
+                .lock()
+                .expect("Shared programs mutex is poisoned");
+            if programs.values().map(Vec::len).sum::<usize>() >= MAX_SHARED_PROGRAMS {
+                programs.clear();
+            }
+            programs
+                .entry(source)
+                .or_default()
+                .push(Arc::clone(&compiled));
+        }
+
+        Ok(compiled)
     }
 }
 
@@ -239,14 +304,10 @@ impl TransformConfig for RemapConfig {
         // step.
         let compiled = self
             .compile_vrl_program(enrichment_tables, merged_definition)
-            .map(|(program, _, _, external_context)| {
+            .map(|compiled| {
                 (
-                    program.final_type_state(),
-                    external_context
-                        .get_custom::<MeaningList>()
-                        .cloned()
-                        .expect("context exists")
-                        .0,
+                    compiled.program.final_type_state(),
+                    compiled.meanings.0.clone(),
                 )
             })
             .map_err(|_| ());
@@ -373,9 +434,11 @@ where
     component_key: Option<ComponentKey>,
     program: Program,
     timezone: TimeZone,
+    source_timezones: Arc<HashMap<ComponentKey, TimeZone>>,
     drop_on_error: bool,
     drop_on_abort: bool,
     reroute_dropped: bool,
+    batch: bool,
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
@@ -422,7 +485,7 @@ impl Remap<AstRunner> {
         config: RemapConfig,
         context: &TransformContext,
     ) -> crate::Result<(Self, String)> {
-        let (program, warnings, _, _) = config.compile_vrl_program(
+        let compiled = config.compile_vrl_program(
             context.enrichment_tables.clone(),
             context.merged_schema_definition.clone(),
         )?;
@@ -430,7 +493,8 @@ impl Remap<AstRunner> {
         let runtime = Runtime::default();
         let runner = AstRunner { runtime };
 
-        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+        Self::new(config, context, compiled.program.clone(), runner)
+            .map(|remap| (remap, compiled.warnings.clone()))
     }
 }
 
@@ -472,9 +536,16 @@ where
             timezone: config
                 .timezone
                 .unwrap_or_else(|| context.globals.timezone()),
+            // The timezone of the transform takes precedence over those of the sources.
+            source_timezones: Arc::new(if config.timezone.is_some() {
+                HashMap::new()
+            } else {
+                context.source_timezones.clone()
+            }),
             drop_on_error: config.drop_on_error,
             drop_on_abort: config.drop_on_abort,
             reroute_dropped: config.reroute_dropped,
+            batch: config.batch,
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
@@ -545,16 +616,25 @@ where
         }
     }
 
-    fn run_vrl(&mut self, target: &mut VrlTarget) -> std::result::Result<Value, Terminate> {
-        self.runner.run(target, &self.program, &self.timezone)
+    fn run_vrl(
+        &mut self,
+        target: &mut VrlTarget,
+        timezone: &TimeZone,
+    ) -> std::result::Result<Value, Terminate> {
+        self.runner.run(target, &self.program, timezone)
     }
-}
 
-impl<Runner> SyncTransform for Remap<Runner>
-where
-    Runner: VrlRunner + Clone + Send + Sync,
-{
-    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+    /// The timezone of the source of the event, or of the transform.
+    fn event_timezone(&self, event: &Event) -> TimeZone {
+        event
+            .metadata()
+            .source_id()
+            .and_then(|source| self.source_timezones.get(&source.component))
+            .copied()
+            .unwrap_or(self.timezone)
+    }
+
+    fn run_settings(&self) -> RunSettings {
         // If a program can fail or abort at runtime and we know that we will still need to forward
         // the event in that case (either to the main output or `dropped`, depending on the
         // config), we need to clone the original event and keep it around, to allow us to discard
@@ -567,23 +647,23 @@ where
         // the event to the `dropped` output.
         let forward_on_error = !self.drop_on_error || self.reroute_dropped;
         let forward_on_abort = !self.drop_on_abort || self.reroute_dropped;
-        let original_event = if (self.program.info().fallible && forward_on_error)
-            || (self.program.info().abortable && forward_on_abort)
-        {
-            Some(event.clone())
-        } else {
-            None
-        };
 
-        let mut target = VrlTarget::new(
-            event,
-            self.program.info(),
-            match self.metric_tag_values {
+        RunSettings {
+            keep_original: (self.program.info().fallible && forward_on_error)
+                || (self.program.info().abortable && forward_on_abort),
+            multi_value_tags: match self.metric_tag_values {
                 MetricTagValues::Single => false,
                 MetricTagValues::Full => true,
             },
-        );
-        let result = self.run_vrl(&mut target);
+        }
+    }
+
+    fn process(&mut self, event: Event, settings: &RunSettings, output: &mut TransformOutputsBuf) {
+        let original_event = settings.keep_original.then(|| event.clone());
+
+        let timezone = self.event_timezone(&event);
+        let mut target = VrlTarget::new(event, self.program.info(), settings.multi_value_tags);
+        let result = self.run_vrl(&mut target, &timezone);
 
         match result {
             Ok(_) => match target.into_events() {
@@ -629,6 +709,37 @@ where
     }
 }
 
+/// How events are run through the program, which only depends on the configuration of the
+/// transform and on its program.
+struct RunSettings {
+    /// Whether the original event is kept, to forward it if the program fails or aborts.
+    keep_original: bool,
+    multi_value_tags: bool,
+}
+
+impl<Runner> SyncTransform for Remap<Runner>
+where
+    Runner: VrlRunner + Clone + Send + Sync,
+{
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let settings = self.run_settings();
+        self.process(event, &settings, output);
+    }
+
+    fn transform_all(&mut self, events: EventArray, output: &mut TransformOutputsBuf) {
+        if self.batch {
+            let settings = self.run_settings();
+            for event in events.into_events() {
+                self.process(event, &settings, output);
+            }
+        } else {
+            for event in events.into_events() {
+                self.transform(event, output);
+            }
+        }
+    }
+}
+
 #[inline]
 fn push_default(
     mut event: Event,
@@ -1147,6 +1258,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn remap_timezone_from_source() {
+        let event_from = |source: &str| {
+            let mut event =
+                Event::try_from(serde_json::json!({"timestamp": "2022-12-27 00:00:00"})).unwrap();
+            event
+                .metadata_mut()
+                .set_source_id(Arc::new(OutputId::from(source)));
+            event
+        };
+        let conf = RemapConfig {
+            source: Some(formatdoc! {r#"
+                .timestamp = parse_timestamp!(.timestamp, format: "%Y-%m-%d %H:%M:%S")
+            "#}),
+            drop_on_error: true,
+            drop_on_abort: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("remapper")),
+            globals: GlobalOptions {
+                timezone: Some(TimeZone::parse("Etc/UTC").unwrap()),
+                ..Default::default()
+            },
+            source_timezones: HashMap::from([(
+                ComponentKey::from("los_angeles"),
+                TimeZone::parse("America/Los_Angeles").unwrap(),
+            )]),
+            ..Default::default()
+        };
+        let mut tform = Remap::new_ast(conf, &context).unwrap().0;
+
+        let output = transform_one_fallible(&mut tform, event_from("los_angeles")).unwrap();
+        assert_eq!(
+            output.as_log()["timestamp"],
+            DateTime::<chrono::Utc>::from(
+                DateTime::parse_from_rfc3339("2022-12-27T00:00:00-08:00").unwrap()
+            )
+            .into()
+        );
+
+        let output = transform_one_fallible(&mut tform, event_from("other")).unwrap();
+        assert_eq!(
+            output.as_log()["timestamp"],
+            DateTime::<chrono::Utc>::from(
+                DateTime::parse_from_rfc3339("2022-12-27T00:00:00Z").unwrap()
+            )
+            .into()
+        );
+    }
+
     #[test]
     fn remap_timezone_override() {
         let error =
@@ -2069,4 +2232,101 @@ mod tests {
             outputs1[0].schema_definitions(true),
         );
     }
+
+    #[test]
+    fn shares_compiled_programs() {
+        let config = |source: &str| RemapConfig {
+            source: Some(source.to_owned()),
+            ..Default::default()
+        };
+        let compile = |config: &RemapConfig, definition: schema::Definition| {
+            config
+                .compile_vrl_program(enrichment::TableRegistry::default(), definition)
+                .unwrap()
+        };
+        let source = ".shares_compiled_programs = true";
+
+        let first = compile(&config(source), Definition::default_legacy_namespace());
+        let second = compile(&config(source), Definition::default_legacy_namespace());
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // Programs are typed by their inputs.
+        let other_input = compile(
+            &config(source),
+            Definition::new_with_default_metadata(Kind::any_object(), [LogNamespace::Legacy]),
+        );
+        assert!(!Arc::ptr_eq(&first, &other_input));
+
+        let other_source = compile(
+            &config(".shares_compiled_programs = false"),
+            Definition::default_legacy_namespace(),
+        );
+        assert!(!Arc::ptr_eq(&first, &other_source));
+
+        // Programs using enrichment tables aren't shared.
+        let enrichment_source = r#"
+            .shares_compiled_programs = true
+            # enrichment_table
+        "#;
+        let first = compile(
+            &config(enrichment_source),
+            Definition::default_legacy_namespace(),
+        );
+        let second = compile(
+            &config(enrichment_source),
+            Definition::default_legacy_namespace(),
+        );
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn batch_execution_matches_per_event_execution() {
+        let config = |batch: bool| RemapConfig {
+            source: Some(
+                indoc! {r#"
+                    .count = to_int!(.count) * 2
+                    if .count > 6 { abort }
+                    .status = "ok"
+                "#}
+                .to_owned(),
+            ),
+            drop_on_error: true,
+            drop_on_abort: true,
+            reroute_dropped: true,
+            batch,
+            ..Default::default()
+        };
+        let events = || {
+            ["1", "two", "3", "4", "5"]
+                .into_iter()
+                .map(|count| {
+                    let mut log = LogEvent::from("message");
+                    log.insert("count", count);
+                    log
+                })
+                .collect::<Vec<_>>()
+        };
+        let run = |batch: bool| {
+            let mut transform = remap(config(batch)).unwrap();
+            let mut outputs = TransformOutputsBuf::new_with_capacity(
+                vec![
+                    TransformOutput::new(DataType::all(), HashMap::new()),
+                    TransformOutput::new(DataType::all(), HashMap::new()).with_port(DROPPED),
+                ],
+                1,
+            );
+            transform.transform_all(events().into(), &mut outputs);
+            (
+                outputs.drain().collect::<Vec<_>>(),
+                outputs.drain_named(DROPPED).collect::<Vec<_>>(),
+            )
+        };
+
+        let (batch, batch_dropped) = run(true);
+        let (single, single_dropped) = run(false);
+        assert_eq!(batch.len(), 2);
+        assert_eq!(batch_dropped.len(), 3);
+        assert_eq!(batch, single);
+        assert_eq!(batch_dropped, single_dropped);
+    }
 }
@@ -1,3 +1,10 @@
+use std::{num::NonZeroUsize, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use rand::{thread_rng, Rng};
+use serde_with::serde_as;
+use snafu::Snafu;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -10,9 +17,17 @@ use crate::{
     event::Event,
     internal_events::SampleEventDiscarded,
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{FunctionTransform, OutputBuffer, TaskTransform, Transform},
 };
 
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    #[snafu(display("Exactly one of `rate` and `reservoir` must be set"))]
+    RateOrReservoir,
+    #[snafu(display("`key_field` only applies to `rate` sampling"))]
+    ReservoirKeyField,
+}
+
 /// Configuration for the `sample` transform.
 #[configurable_component(transform(
     "sample",
@@ -25,7 +40,9 @@ pub struct SampleConfig {
     ///
     /// For example, `rate = 10` means 1 out of every 10 events are forwarded and the rest are
     /// dropped.
-    pub rate: u64,
+    ///
+    /// Required unless `reservoir` is set.
+    pub rate: Option<u64>,
 
     /// The name of the field whose value is hashed to determine if the event should be
     /// sampled.
@@ -38,18 +55,46 @@ pub struct SampleConfig {
     ///
     /// This can be useful to, for example, ensure that all logs for a given transaction are
     /// sampled together, but that overall `1/N` transactions are sampled.
+    ///
+    /// The hash doesn't depend on the instance or on previous events, so Vector instances
+    /// configured with the same `rate` keep the same buckets.
     #[configurable(metadata(docs::examples = "message",))]
     pub key_field: Option<String>,
 
+    #[configurable(derived)]
+    pub reservoir: Option<ReservoirConfig>,
+
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
 }
 
+/// Configuration for reservoir sampling.
+///
+/// Instead of forwarding a share of the events, a fixed number of events is picked uniformly at
+/// random from each window, regardless of the input rate. The picked events are forwarded at the
+/// end of the window, with `sample_rate` set to the number of events received during the window
+/// divided by the number of events forwarded.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ReservoirConfig {
+    /// The number of events forwarded per window.
+    #[configurable(metadata(docs::examples = 100))]
+    pub size: NonZeroUsize,
+
+    /// The length of the window, in seconds.
+    #[serde_as(as = "serde_with::DurationSeconds<f64>")]
+    #[configurable(metadata(docs::human_name = "Time Window"))]
+    pub window_secs: Duration,
+}
+
 impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            rate: 10,
+            rate: Some(10),
             key_field: None,
+            reservoir: None,
             exclude: None::<AnyCondition>,
         })
         .unwrap()
@@ -60,14 +105,28 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        let exclude = self
+            .exclude
+            .as_ref()
+            .map(|condition| condition.build(&context.enrichment_tables))
+            .transpose()?;
+
+        match (self.rate, &self.reservoir) {
+            (Some(rate), None) => Ok(Transform::function(Sample::new(
+                rate,
+                self.key_field.clone(),
+                exclude,
+            ))),
+            (None, Some(_)) if self.key_field.is_some() => {
+                Err(Box::new(ConfigError::ReservoirKeyField))
+            }
+            (None, Some(reservoir)) => Ok(Transform::event_task(ReservoirSample::new(
+                reservoir.size,
+                reservoir.window_secs,
+                exclude,
+            ))),
+            _ => Err(Box::new(ConfigError::RateOrReservoir)),
+        }
     }
 
     fn input(&self) -> Input {
@@ -144,11 +203,7 @@ impl FunctionTransform for Sample {
         self.count = (self.count + 1) % self.rate;
 
         if num % self.rate == 0 {
-            match event {
-                Event::Log(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
-                Event::Trace(ref mut event) => event.insert("sample_rate", self.rate.to_string()),
-                Event::Metric(_) => panic!("component can never receive metric events"),
-            };
+            insert_sample_rate(&mut event, self.rate.to_string());
             output.push(event);
         } else {
             emit!(SampleEventDiscarded);
@@ -156,6 +211,110 @@ impl FunctionTransform for Sample {
     }
 }
 
+fn insert_sample_rate(event: &mut Event, rate: String) {
+    match event {
+        Event::Log(event) => event.insert("sample_rate", rate),
+        Event::Trace(event) => event.insert("sample_rate", rate),
+        Event::Metric(_) => panic!("component can never receive metric events"),
+    };
+}
+
+/// Keeps a uniform random sample of a fixed number of events per window, using reservoir
+/// sampling.
+pub struct ReservoirSample {
+    size: usize,
+    window: Duration,
+    exclude: Option<Condition>,
+    reservoir: Vec<Event>,
+    seen: u64,
+}
+
+impl ReservoirSample {
+    pub fn new(size: NonZeroUsize, window: Duration, exclude: Option<Condition>) -> Self {
+        Self {
+            size: size.get(),
+            window,
+            exclude,
+            reservoir: Vec::with_capacity(size.get()),
+            seen: 0,
+        }
+    }
+
+    fn add(&mut self, event: Event) {
+        self.seen += 1;
+        if self.reservoir.len() < self.size {
+            self.reservoir.push(event);
+            return;
+        }
+
+        // Each of the events seen so far ends up in the reservoir with the same probability.
+        let index = thread_rng().gen_range(0..self.seen);
+        if let Some(slot) = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.reservoir.get_mut(index))
+        {
+            *slot = event;
+        }
+        emit!(SampleEventDiscarded);
+    }
+
+    fn flush(&mut self) -> Vec<Event> {
+        let mut events = std::mem::take(&mut self.reservoir);
+        if !events.is_empty() {
+            let rate = self.seen as f64 / events.len() as f64;
+            for event in &mut events {
+                insert_sample_rate(event, rate.to_string());
+            }
+        }
+        self.seen = 0;
+        events
+    }
+}
+
+impl TaskTransform<Event> for ReservoirSample {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut ticks =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.window, self.window);
+
+        Box::pin(stream! {
+            loop {
+                let (events, done) = tokio::select! {
+                    biased;
+
+                    maybe_event = input_rx.next() => match maybe_event {
+                        Some(event) => {
+                            let (excluded, event) = match self.exclude.as_ref() {
+                                Some(condition) => condition.check(event),
+                                None => (false, event),
+                            };
+                            if excluded {
+                                (vec![event], false)
+                            } else {
+                                self.add(event);
+                                (Vec::new(), false)
+                            }
+                        }
+                        None => (self.flush(), true),
+                    },
+                    _ = ticks.tick() => (self.flush(), false),
+                };
+                for event in events {
+                    yield event;
+                }
+                if done {
+                    break;
+                }
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
@@ -365,8 +524,9 @@ mod tests {
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
             let config = SampleConfig {
-                rate: 1,
+                rate: Some(1),
                 key_field: None,
+                reservoir: None,
                 exclude: None,
             };
             let (tx, rx) = mpsc::channel(1);
@@ -384,6 +544,85 @@ mod tests {
         .await
     }
 
+    #[test]
+    fn hash_samples_the_same_events_across_instances() {
+        let events = random_events(1000);
+        let sample = |skip: usize| {
+            let mut sampler = Sample::new(4, Some(log_schema().message_key().into()), None);
+            // A different number of previous events, as seen by another instance.
+            for event in random_events(skip) {
+                transform_one(&mut sampler, event);
+            }
+            events
+                .iter()
+                .filter_map(|event| transform_one(&mut sampler, event.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(0), sample(3));
+    }
+
+    #[test]
+    fn reservoir_keeps_a_fixed_number_of_events() {
+        let mut sampler =
+            ReservoirSample::new(NonZeroUsize::new(10).unwrap(), Duration::from_secs(1), None);
+        for event in random_events(250) {
+            sampler.add(event);
+        }
+
+        let events = sampler.flush();
+        assert_eq!(events.len(), 10);
+        for event in &events {
+            assert_eq!(event.as_log()["sample_rate"], "25".into());
+        }
+
+        // The next window starts empty, and keeps every event below the size.
+        for event in random_events(4) {
+            sampler.add(event);
+        }
+        let events = sampler.flush();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].as_log()["sample_rate"], "1".into());
+    }
+
+    #[tokio::test]
+    async fn reservoir_flushes_at_the_end_of_each_window() {
+        let config = toml::from_str::<SampleConfig>(
+            r#"
+            reservoir.size = 2
+            reservoir.window_secs = 0.5
+            "#,
+        )
+        .unwrap();
+        let (tx, rx) = mpsc::channel(10);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+        for _ in 0..5 {
+            tx.send(LogEvent::from("hello world").into()).await.unwrap();
+        }
+        let first = out.recv().await.unwrap();
+        let second = out.recv().await.unwrap();
+        assert_eq!(first.as_log()["sample_rate"], "2.5".into());
+        assert_eq!(second.as_log()["sample_rate"], "2.5".into());
+
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_rate_and_reservoir() {
+        let config = toml::from_str::<SampleConfig>(
+            r#"
+            rate = 10
+            reservoir.size = 2
+            reservoir.window_secs = 1
+            "#,
+        )
+        .unwrap();
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10)
             .take(n)
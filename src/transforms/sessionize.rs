@@ -0,0 +1,489 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream, StreamExt};
+use lookup::lookup_v2::parse_target_path;
+use uuid::Uuid;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use vrl::value::Kind;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{discriminant::Discriminant, Event, LogEvent, Value},
+    internal_events::SessionizeSessionClosed,
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+const fn default_inactivity_gap_secs() -> u64 {
+    1800
+}
+
+const fn default_flush_period_ms() -> u64 {
+    1000
+}
+
+fn default_session_id_field() -> String {
+    "session_id".to_string()
+}
+
+/// Configuration for the `sessionize` transform.
+#[configurable_component(transform(
+    "sessionize",
+    "Group events sharing a key into sessions separated by inactivity gaps."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SessionizeConfig {
+    /// An ordered list of fields by which to group events, such as a user ID or a source IP.
+    ///
+    /// Each group with matching values for the specified fields has its own sessions. When no
+    /// fields are specified, all events share the same sessions.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "user_id", docs::examples = "source_ip"))]
+    pub group_by: Vec<String>,
+
+    /// The period of inactivity after which the session of a group is closed.
+    ///
+    /// The next event of the group starts a new session.
+    #[serde(default = "default_inactivity_gap_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub inactivity_gap_secs: u64,
+
+    /// The maximum duration of a session, after which it is closed even if the group is active.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 86400))]
+    pub max_duration_secs: Option<u64>,
+
+    /// The name of the field in which the session ID of an event is stored.
+    #[serde(default = "default_session_id_field")]
+    pub session_id_field: String,
+
+    /// Whether to emit a summary event when a session is closed.
+    ///
+    /// The summary holds the `group_by` fields and the session ID of the session, along with its
+    /// `session_start` and `session_end` timestamps, its `session_duration_secs`, and its number of
+    /// events in `session_events`.
+    #[serde(default)]
+    pub emit_summaries: bool,
+
+    /// The interval to check for and close inactive sessions, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub flush_period_ms: u64,
+}
+
+impl GenerateConfig for SessionizeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"group_by = ["user_id"]
+            inactivity_gap_secs = 1800
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sessionize")]
+impl TransformConfig for SessionizeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Sessionize::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let session_id_path = parse_target_path(&self.session_id_field).ok();
+        vec![TransformOutput::new(
+            DataType::Log,
+            input_definitions
+                .iter()
+                .map(|(output, definition)| {
+                    let mut definition = definition.clone();
+                    if let Some(path) = &session_id_path {
+                        definition = definition.with_field(path, Kind::bytes(), None);
+                    }
+                    if self.emit_summaries {
+                        // Summaries don't share the schema of the events.
+                        definition = definition.merge(schema::Definition::any());
+                    }
+                    (output.clone(), definition)
+                })
+                .collect(),
+        )]
+    }
+}
+
+/// Why a session was closed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum CloseReason {
+    Inactivity,
+    MaxDuration,
+    Shutdown,
+}
+
+impl CloseReason {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Inactivity => "inactivity",
+            Self::MaxDuration => "max_duration",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Session {
+    id: String,
+    group: Vec<(String, Value)>,
+    events: usize,
+    /// The timestamps of the first and last events, which are the times they were received if
+    /// they have no timestamp.
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    /// When the session started and last received an event, to close it without new events.
+    started_at: Instant,
+    last_seen: Instant,
+}
+
+impl Session {
+    fn new(group: Vec<(String, Value)>, timestamp: DateTime<Utc>, now: Instant) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            group,
+            events: 0,
+            start: timestamp,
+            end: timestamp,
+            started_at: now,
+            last_seen: now,
+        }
+    }
+
+    fn summary(&self, session_id_field: &str) -> LogEvent {
+        let mut log = LogEvent::default();
+        for (field, value) in &self.group {
+            log.insert(field.as_str(), value.clone());
+        }
+        log.insert(session_id_field, self.id.clone());
+        log.insert("session_start", self.start);
+        log.insert("session_end", self.end);
+        log.insert(
+            "session_duration_secs",
+            (self.end - self.start).num_milliseconds() as f64 / 1000.0,
+        );
+        log.insert("session_events", self.events as i64);
+        log
+    }
+}
+
+pub struct Sessionize {
+    group_by: Vec<String>,
+    inactivity_gap: Duration,
+    max_duration: Option<Duration>,
+    session_id_field: String,
+    emit_summaries: bool,
+    flush_period: Duration,
+    sessions: HashMap<Discriminant, Session>,
+}
+
+impl Sessionize {
+    pub fn new(config: &SessionizeConfig) -> crate::Result<Self> {
+        if config.inactivity_gap_secs == 0 {
+            return Err("`inactivity_gap_secs` must be greater than zero".into());
+        }
+        if config.flush_period_ms == 0 {
+            return Err("`flush_period_ms` must be greater than zero".into());
+        }
+
+        Ok(Self {
+            group_by: config.group_by.clone(),
+            inactivity_gap: Duration::from_secs(config.inactivity_gap_secs),
+            max_duration: config.max_duration_secs.map(Duration::from_secs),
+            session_id_field: config.session_id_field.clone(),
+            emit_summaries: config.emit_summaries,
+            flush_period: Duration::from_millis(config.flush_period_ms),
+            sessions: HashMap::new(),
+        })
+    }
+
+    fn close(&self, session: Session, reason: CloseReason, output: &mut Vec<Event>) {
+        emit!(SessionizeSessionClosed {
+            reason: reason.as_str(),
+        });
+        if self.emit_summaries {
+            output.push(session.summary(&self.session_id_field).into());
+        }
+    }
+
+    /// Whether an event with the given timestamp starts a new session, rather than extending the
+    /// current one.
+    fn expired(&self, session: &Session, timestamp: DateTime<Utc>) -> Option<CloseReason> {
+        let gap = (timestamp - session.end).to_std().unwrap_or_default();
+        let duration = (timestamp - session.start).to_std().unwrap_or_default();
+        if gap > self.inactivity_gap {
+            Some(CloseReason::Inactivity)
+        } else if self.max_duration.map_or(false, |max| duration > max) {
+            Some(CloseReason::MaxDuration)
+        } else {
+            None
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event, now: Instant) {
+        let mut log = event.into_log();
+        let timestamp = match log.get_timestamp() {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+        let discriminant = Discriminant::from_log_event(&log, &self.group_by);
+
+        let reason = self
+            .sessions
+            .get(&discriminant)
+            .and_then(|session| self.expired(session, timestamp));
+        if let Some(reason) = reason {
+            let session = self
+                .sessions
+                .remove(&discriminant)
+                .expect("Session is present");
+            self.close(session, reason, output);
+        }
+
+        let session = self.sessions.entry(discriminant).or_insert_with(|| {
+            let group = self
+                .group_by
+                .iter()
+                .filter_map(|field| {
+                    log.get(field.as_str())
+                        .map(|value| (field.clone(), value.clone()))
+                })
+                .collect();
+            Session::new(group, timestamp, now)
+        });
+        session.events += 1;
+        session.start = session.start.min(timestamp);
+        session.end = session.end.max(timestamp);
+        session.last_seen = now;
+
+        log.insert(self.session_id_field.as_str(), session.id.clone());
+        output.push(log.into());
+    }
+
+    /// Closes the sessions that haven't received events for the inactivity gap, or that have
+    /// lasted for the maximum duration.
+    fn flush_into(&mut self, output: &mut Vec<Event>, now: Instant) {
+        let expired = self
+            .sessions
+            .iter()
+            .filter_map(|(discriminant, session)| {
+                if now.duration_since(session.last_seen) >= self.inactivity_gap {
+                    Some((discriminant.clone(), CloseReason::Inactivity))
+                } else if self
+                    .max_duration
+                    .map_or(false, |max| now.duration_since(session.started_at) >= max)
+                {
+                    Some((discriminant.clone(), CloseReason::MaxDuration))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (discriminant, reason) in expired {
+            if let Some(session) = self.sessions.remove(&discriminant) {
+                self.close(session, reason, output);
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        let sessions = std::mem::take(&mut self.sessions);
+        for session in sessions.into_values() {
+            self.close(session, CloseReason::Shutdown, output);
+        }
+    }
+}
+
+impl TaskTransform<Event> for Sessionize {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(
+            stream! {
+                loop {
+                    let mut output = Vec::new();
+                    let done = tokio::select! {
+                        _ = flush_stream.tick() => {
+                            me.flush_into(&mut output, Instant::now());
+                            false
+                        }
+                        maybe_event = input_rx.next() => {
+                            match maybe_event {
+                                None => {
+                                    me.flush_all_into(&mut output);
+                                    true
+                                }
+                                Some(event) => {
+                                    me.transform_one(&mut output, event, Instant::now());
+                                    false
+                                }
+                            }
+                        }
+                    };
+                    yield stream::iter(output.into_iter());
+                    if done { break }
+                }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as ChronoDuration;
+
+    use super::*;
+
+    fn sessionize(config: &str) -> Sessionize {
+        Sessionize::new(&toml::from_str::<SessionizeConfig>(config).unwrap()).unwrap()
+    }
+
+    fn event(user: &str, timestamp: DateTime<Utc>) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert("user_id", user);
+        log.insert("timestamp", timestamp);
+        log.into()
+    }
+
+    fn session_id(event: &Event) -> Value {
+        event.as_log()["session_id"].clone()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SessionizeConfig>();
+    }
+
+    #[test]
+    fn splits_sessions_on_inactivity_gaps() {
+        let mut transform = sessionize(
+            r#"
+            group_by = ["user_id"]
+            inactivity_gap_secs = 60
+            "#,
+        );
+        let start = Utc::now();
+        let now = Instant::now();
+
+        let mut output = Vec::new();
+        for (user, offset) in [("a", 0), ("b", 10), ("a", 30), ("a", 120)] {
+            let timestamp = start + ChronoDuration::seconds(offset);
+            transform.transform_one(&mut output, event(user, timestamp), now);
+        }
+
+        assert_eq!(output.len(), 4);
+        assert_eq!(session_id(&output[0]), session_id(&output[2]));
+        assert_ne!(session_id(&output[0]), session_id(&output[1]));
+        assert_ne!(session_id(&output[2]), session_id(&output[3]));
+    }
+
+    #[test]
+    fn splits_sessions_on_max_duration() {
+        let mut transform = sessionize(
+            r#"
+            inactivity_gap_secs = 60
+            max_duration_secs = 100
+            "#,
+        );
+        let start = Utc::now();
+        let now = Instant::now();
+
+        let mut output = Vec::new();
+        for offset in [0, 50, 100, 150] {
+            let timestamp = start + ChronoDuration::seconds(offset);
+            transform.transform_one(&mut output, event("a", timestamp), now);
+        }
+
+        assert_eq!(session_id(&output[0]), session_id(&output[2]));
+        assert_ne!(session_id(&output[2]), session_id(&output[3]));
+    }
+
+    #[test]
+    fn emits_summaries_of_inactive_sessions() {
+        let mut transform = sessionize(
+            r#"
+            group_by = ["user_id"]
+            inactivity_gap_secs = 60
+            emit_summaries = true
+            "#,
+        );
+        let start = Utc::now();
+        let now = Instant::now();
+
+        let mut output = Vec::new();
+        transform.transform_one(&mut output, event("a", start), now);
+        transform.transform_one(
+            &mut output,
+            event("a", start + ChronoDuration::seconds(30)),
+            now,
+        );
+        let id = session_id(&output[0]);
+
+        output.clear();
+        transform.flush_into(&mut output, now + Duration::from_secs(30));
+        assert!(output.is_empty());
+
+        transform.flush_into(&mut output, now + Duration::from_secs(60));
+        assert_eq!(output.len(), 1);
+        let summary = output[0].as_log();
+        assert_eq!(summary["user_id"], Value::from("a"));
+        assert_eq!(summary["session_id"], id);
+        assert_eq!(summary["session_events"], Value::from(2_i64));
+        assert_eq!(summary["session_duration_secs"], Value::from(30.0));
+        assert!(transform.sessions.is_empty());
+    }
+
+    #[test]
+    fn closes_all_sessions_on_shutdown() {
+        let mut transform = sessionize(
+            r#"
+            group_by = ["user_id"]
+            emit_summaries = true
+            "#,
+        );
+        let now = Instant::now();
+
+        let mut output = Vec::new();
+        transform.transform_one(&mut output, event("a", Utc::now()), now);
+        transform.transform_one(&mut output, event("b", Utc::now()), now);
+
+        output.clear();
+        transform.flush_all_into(&mut output);
+        assert_eq!(output.len(), 2);
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroU64,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use redis::aio::ConnectionManager;
+use vector_config::configurable_component;
+
+use crate::internal_events::ThrottleSyncError;
+
+/// Configuration for the Redis backend of the `throttle` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisBackendConfig {
+    /// The URL of the Redis server.
+    #[configurable(metadata(docs::examples = "redis://127.0.0.1:6379/0"))]
+    pub endpoint: String,
+
+    /// The prefix of the keys of the counters in Redis.
+    ///
+    /// The Vector instances enforcing the same threshold must use the same prefix, as well as the
+    /// same `threshold` and `window_secs`.
+    #[serde(default = "default_prefix")]
+    pub prefix: String,
+
+    /// How often the events let through are added to the counters in Redis, in milliseconds.
+    ///
+    /// The Vector instances together let through up to the events each of them lets through
+    /// between two syncs over the threshold.
+    #[serde(default = "default_sync_interval_ms")]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub sync_interval_ms: NonZeroU64,
+}
+
+fn default_prefix() -> String {
+    "vector_throttle".to_owned()
+}
+
+fn default_sync_interval_ms() -> NonZeroU64 {
+    NonZeroU64::new(200).expect("static non-zero number")
+}
+
+/// The count of the events of a key in the current window.
+#[derive(Clone, Debug, Default)]
+struct Count {
+    /// The events of all the instances, as of the last sync.
+    synced: u64,
+    /// The events let through since the last sync.
+    pending: u64,
+}
+
+/// Buckets counting the events of each key in fixed windows, shared by Vector instances through
+/// counters in Redis.
+///
+/// Events are checked against the counts known locally, so they are never held waiting for Redis,
+/// and the counts are synced on an interval.
+#[derive(Clone, Debug)]
+pub struct SharedBuckets {
+    threshold: u64,
+    window: Duration,
+    prefix: String,
+    current: u64,
+    counts: HashMap<Option<String>, Count>,
+    /// The events let through in previous windows that were not synced yet, by Redis key.
+    unsynced: Vec<(String, u64)>,
+}
+
+/// The events let through since the last sync, to add to the counters in Redis.
+pub struct SyncBatch {
+    window: u64,
+    /// The events of previous windows, by Redis key.
+    previous: Vec<(String, u64)>,
+    /// The events of the keys of the current window, whose totals are read back.
+    current: Vec<(Option<String>, u64)>,
+}
+
+impl SharedBuckets {
+    pub fn new(threshold: u32, window: Duration, prefix: String) -> Self {
+        Self {
+            threshold: u64::from(threshold),
+            window,
+            prefix,
+            current: 0,
+            counts: HashMap::new(),
+            unsynced: Vec::new(),
+        }
+    }
+
+    fn window_at(&self, now: SystemTime) -> u64 {
+        let elapsed = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        (elapsed.as_secs_f64() / self.window.as_secs_f64()) as u64
+    }
+
+    fn redis_key(&self, window: u64, key: &Option<String>) -> String {
+        match key {
+            Some(key) => format!("{}:{}:{}", self.prefix, window, key),
+            None => format!("{}:{}", self.prefix, window),
+        }
+    }
+
+    /// Starts counting the window of `now`, keeping the events of the previous one to sync.
+    fn roll(&mut self, now: SystemTime) {
+        let window = self.window_at(now);
+        if window == self.current {
+            return;
+        }
+        let previous = std::mem::take(&mut self.counts);
+        for (key, count) in previous {
+            if count.pending > 0 {
+                let redis_key = self.redis_key(self.current, &key);
+                self.unsynced.push((redis_key, count.pending));
+            }
+        }
+        self.current = window;
+    }
+
+    /// Checks whether an event of the key is under the threshold, counting it if it is.
+    pub fn check(&mut self, key: &Option<String>, now: SystemTime) -> bool {
+        self.roll(now);
+        let threshold = self.threshold;
+        let count = self.counts.entry(key.clone()).or_default();
+        if count.synced + count.pending < threshold {
+            count.pending += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes the events let through since the last sync.
+    pub fn start_sync(&mut self, now: SystemTime) -> SyncBatch {
+        self.roll(now);
+        let current = self
+            .counts
+            .iter_mut()
+            .map(|(key, count)| (key.clone(), std::mem::take(&mut count.pending)))
+            .collect();
+        SyncBatch {
+            window: self.current,
+            previous: std::mem::take(&mut self.unsynced),
+            current,
+        }
+    }
+
+    /// Updates the counts with the totals of the counters in Redis, or takes the events of the
+    /// batch back to sync them again if Redis could not be reached.
+    pub fn finish_sync(&mut self, batch: SyncBatch, totals: Option<Vec<u64>>) {
+        let Some(totals) = totals else {
+            self.unsynced.extend(batch.previous);
+            for (key, pending) in batch.current {
+                if pending == 0 {
+                    continue;
+                }
+                if batch.window == self.current {
+                    self.counts.entry(key).or_default().pending += pending;
+                } else {
+                    let redis_key = self.redis_key(batch.window, &key);
+                    self.unsynced.push((redis_key, pending));
+                }
+            }
+            return;
+        };
+
+        // The counts of a window that has passed are not needed anymore.
+        if batch.window != self.current {
+            return;
+        }
+        let totals = &totals[batch.previous.len().min(totals.len())..];
+        for ((key, _), total) in batch.current.into_iter().zip(totals) {
+            if let Some(count) = self.counts.get_mut(&key) {
+                count.synced = *total;
+            }
+        }
+    }
+
+    /// The commands adding the events of the batch to the counters in Redis, returning their
+    /// totals.
+    fn pipeline(&self, batch: &SyncBatch) -> redis::Pipeline {
+        // The counters are kept for a window after theirs, for instances whose clocks are late.
+        let expire_ms = (self.window.as_millis() * 2) as u64;
+        let current = batch
+            .current
+            .iter()
+            .map(|(key, pending)| (self.redis_key(batch.window, key), *pending));
+
+        let mut pipeline = redis::pipe();
+        for (key, increment) in batch.previous.iter().cloned().chain(current) {
+            pipeline
+                .cmd("INCRBY")
+                .arg(&key)
+                .arg(increment)
+                .cmd("PEXPIRE")
+                .arg(&key)
+                .arg(expire_ms)
+                .ignore();
+        }
+        pipeline
+    }
+}
+
+/// The buckets shared through Redis, with the connection to Redis, which is only opened when
+/// first synced so that Vector starts when Redis is unavailable.
+#[derive(Clone)]
+pub struct Shared {
+    pub buckets: SharedBuckets,
+    pub sync_interval: Duration,
+    client: redis::Client,
+    connection: Option<ConnectionManager>,
+}
+
+impl Shared {
+    pub fn new(
+        config: &RedisBackendConfig,
+        threshold: u32,
+        window: Duration,
+    ) -> crate::Result<Self> {
+        let client = redis::Client::open(config.endpoint.as_str())?;
+        Ok(Self {
+            buckets: SharedBuckets::new(threshold, window, config.prefix.clone()),
+            sync_interval: Duration::from_millis(config.sync_interval_ms.get()),
+            client,
+            connection: None,
+        })
+    }
+
+    /// Syncs the counts with Redis. Until Redis can be reached, the events are checked against the
+    /// counts of this instance alone.
+    pub async fn sync(&mut self) {
+        let batch = self.buckets.start_sync(SystemTime::now());
+        if batch.previous.is_empty() && batch.current.is_empty() {
+            return;
+        }
+        let totals = match self.query(&batch).await {
+            Ok(totals) => Some(totals),
+            Err(error) => {
+                emit!(ThrottleSyncError { error });
+                None
+            }
+        };
+        self.buckets.finish_sync(batch, totals);
+    }
+
+    async fn query(&mut self, batch: &SyncBatch) -> redis::RedisResult<Vec<u64>> {
+        let connection = match self.connection.as_mut() {
+            Some(connection) => connection,
+            None => {
+                let connection = self.client.get_tokio_connection_manager().await?;
+                self.connection.insert(connection)
+            }
+        };
+        self.buckets.pipeline(batch).query_async(connection).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn buckets() -> SharedBuckets {
+        SharedBuckets::new(3, Duration::from_secs(10), "throttle".to_owned())
+    }
+
+    #[test]
+    fn checks_against_counts_of_fleet() {
+        let mut buckets = buckets();
+        let key = Some("api".to_owned());
+
+        assert!(buckets.check(&key, at(100)));
+        let batch = buckets.start_sync(at(101));
+        assert_eq!(batch.current, [(key.clone(), 1)]);
+        // Other instances let through an event of the key too.
+        buckets.finish_sync(batch, Some(vec![2]));
+
+        assert!(buckets.check(&key, at(102)));
+        assert!(!buckets.check(&key, at(103)));
+        // Keys are limited separately.
+        assert!(buckets.check(&None, at(103)));
+        // The counts restart with the window.
+        assert!(buckets.check(&key, at(110)));
+    }
+
+    #[test]
+    fn syncs_events_of_previous_windows() {
+        let mut buckets = buckets();
+        let key = Some("api".to_owned());
+
+        assert!(buckets.check(&key, at(105)));
+        assert!(buckets.check(&key, at(106)));
+        let batch = buckets.start_sync(at(111));
+        assert_eq!(batch.previous, [("throttle:10:api".to_owned(), 2)]);
+        assert!(batch.current.is_empty());
+    }
+
+    #[test]
+    fn keeps_events_when_sync_fails() {
+        let mut buckets = buckets();
+        let key = Some("api".to_owned());
+
+        assert!(buckets.check(&key, at(100)));
+        let batch = buckets.start_sync(at(101));
+        buckets.finish_sync(batch, None);
+        let batch = buckets.start_sync(at(102));
+        assert_eq!(batch.current, [(key.clone(), 1)]);
+
+        // Events of a window that passed during the sync are added to their own counter.
+        buckets.check(&key, at(110));
+        buckets.finish_sync(batch, None);
+        let batch = buckets.start_sync(at(110));
+        assert_eq!(batch.previous, [("throttle:10:api".to_owned(), 1)]);
+        assert_eq!(batch.current, [(key, 1)]);
+    }
+
+    #[test]
+    fn builds_pipeline() {
+        let mut buckets = buckets();
+        assert!(buckets.check(&None, at(100)));
+        let batch = buckets.start_sync(at(100));
+
+        let commands = String::from_utf8(buckets.pipeline(&batch).get_packed_pipeline()).unwrap();
+        assert!(commands.contains("INCRBY\r\n$11\r\nthrottle:10\r\n$1\r\n1"));
+        assert!(commands.contains("PEXPIRE\r\n$11\r\nthrottle:10\r\n$5\r\n20000"));
+    }
+}
@@ -1,4 +1,10 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{
+    num::NonZeroU32,
+    pin::Pin,
+    time::{Duration, SystemTime},
+};
+
+mod distributed;
 
 use async_stream::stream;
 use futures::{Stream, StreamExt};
@@ -8,6 +14,8 @@ use snafu::Snafu;
 use vector_config::configurable_component;
 use vector_core::config::{clone_input_definitions, LogNamespace};
 
+pub use self::distributed::RedisBackendConfig;
+use self::distributed::Shared;
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
@@ -43,6 +51,31 @@ pub struct ThrottleConfig {
 
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    backend: BackendConfig,
+}
+
+/// Where the events let through are counted.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of backend."))]
+pub enum BackendConfig {
+    /// The events are counted by each Vector instance, so each instance lets through up to
+    /// `threshold` events per window.
+    #[default]
+    Memory,
+
+    /// The events are counted in Redis, so the Vector instances sharing the counters let through
+    /// up to `threshold` events per window together.
+    ///
+    /// The events are counted in fixed windows aligned on the Unix epoch, and checked against the
+    /// counts of the last sync, so the instances may let through more than `threshold` events
+    /// between two syncs. If Redis cannot be reached, each instance keeps checking the events
+    /// against the counts it knows, and adds its events to the counters once Redis is back.
+    Redis(RedisBackendConfig),
 }
 
 impl_generate_config_from_default!(ThrottleConfig);
@@ -79,6 +112,7 @@ pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     key_field: Option<Template>,
     exclude: Option<Condition>,
     clock: C,
+    shared: Option<Shared>,
 }
 
 impl<C, I> Throttle<C, I>
@@ -109,6 +143,12 @@ where
             .as_ref()
             .map(|condition| condition.build(&context.enrichment_tables))
             .transpose()?;
+        let shared = match &config.backend {
+            BackendConfig::Memory => None,
+            BackendConfig::Redis(redis) => {
+                Some(Shared::new(redis, threshold.get(), flush_keys_interval)?)
+            }
+        };
 
         Ok(Self {
             quota,
@@ -116,6 +156,7 @@ where
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            shared,
         })
     }
 }
@@ -126,7 +167,7 @@ where
     I: clock::Reference + Send + 'static,
 {
     fn transform(
-        self: Box<Self>,
+        mut self: Box<Self>,
         mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
@@ -135,6 +176,10 @@ where
         let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
 
         let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
+        let mut shared = self.shared.take();
+        let mut sync = shared
+            .as_ref()
+            .map(|shared| tokio::time::interval(shared.sync_interval));
 
         Box::pin(stream! {
           loop {
@@ -165,18 +210,19 @@ where
                                         .ok()
                                 });
 
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
-                                        Some(event)
-                                    }
-                                    _ => {
-                                        if let Some(key) = key {
-                                            emit!(ThrottleEventDiscarded{key})
-                                        } else {
-                                            emit!(ThrottleEventDiscarded{key: "None".to_string()})
-                                        }
-                                        None
+                                let allowed = match shared.as_mut() {
+                                    Some(shared) => shared.buckets.check(&key, SystemTime::now()),
+                                    None => limiter.check_key(&key).is_ok(),
+                                };
+                                if allowed {
+                                    Some(event)
+                                } else {
+                                    if let Some(key) = key {
+                                        emit!(ThrottleEventDiscarded{key})
+                                    } else {
+                                        emit!(ThrottleEventDiscarded{key: "None".to_string()})
                                     }
+                                    None
                                 }
                             } else {
                                 Some(event)
@@ -192,6 +238,12 @@ where
                     limiter.retain_recent();
                     false
                 }
+                _ = tick(&mut sync) => {
+                    if let Some(shared) = shared.as_mut() {
+                        shared.sync().await;
+                    }
+                    false
+                }
             };
             if done { break }
           }
@@ -199,6 +251,16 @@ where
     }
 }
 
+/// Waits for the next tick of the interval, if there is one.
+async fn tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
     #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
@@ -0,0 +1,426 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    num::NonZeroUsize,
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use lookup::{owned_value_path, PathPrefix};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::TemplateRenderingError,
+    schema,
+    template::Template,
+    transforms::{TaskTransform, Transform},
+};
+
+fn default_k() -> NonZeroUsize {
+    NonZeroUsize::new(20).expect("static non-zero number")
+}
+
+const fn default_window_secs() -> u64 {
+    60
+}
+
+/// Configuration for the `top_k` transform.
+#[configurable_component(transform(
+    "top_k",
+    "Track the most frequent or heaviest keys of a stream of events, and periodically summarize them."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TopKConfig {
+    /// The key by which events are counted, such as the service that emitted them.
+    #[configurable(metadata(docs::examples = "{{ service }}"))]
+    #[configurable(metadata(
+        docs::examples = "{{ kubernetes.pod_namespace }}/{{ kubernetes.container_name }}"
+    ))]
+    pub key: Template,
+
+    /// The number of keys in each summary.
+    #[serde(default = "default_k")]
+    pub k: NonZeroUsize,
+
+    /// The number of keys tracked during a window.
+    ///
+    /// Once this many keys are tracked, a new key replaces the key with the lowest count, and
+    /// inherits its count as an overestimate. Tracking more keys than `k` makes the summaries
+    /// more accurate. Defaults to ten times `k`.
+    pub capacity: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub weight: TopKWeight,
+
+    /// The duration of the windows, at the end of which a summary is emitted and the counts are
+    /// reset.
+    #[serde(default = "default_window_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub window_secs: u64,
+}
+
+/// What is counted for each key.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TopKWeight {
+    /// The number of events.
+    #[default]
+    Events,
+
+    /// The estimated size of the events once encoded as JSON, in bytes.
+    Bytes,
+}
+
+impl TopKWeight {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Events => "events",
+            Self::Bytes => "bytes",
+        }
+    }
+}
+
+impl GenerateConfig for TopKConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"key = "{{ service }}"
+            k = 20
+            weight = "bytes"
+            window_secs = 60
+        "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "top_k")]
+impl TransformConfig for TopKConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TopK::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let entry = Kind::object(
+            Collection::empty()
+                .with_known("key", Kind::bytes())
+                .with_known("count", Kind::integer())
+                .with_known("error", Kind::integer()),
+        );
+        let mut definition = schema::Definition::default_legacy_namespace()
+            .with_event_field(&owned_value_path!("window_start"), Kind::timestamp(), None)
+            .with_event_field(&owned_value_path!("weight"), Kind::bytes(), None)
+            .with_event_field(&owned_value_path!("total"), Kind::integer(), None)
+            .with_event_field(
+                &owned_value_path!("top"),
+                Kind::array(Collection::empty().with_unknown(entry)),
+                None,
+            );
+        if let Some(timestamp_key) = log_schema().timestamp_key() {
+            definition = definition.with_event_field(timestamp_key, Kind::timestamp(), None);
+        }
+
+        vec![TransformOutput::new(
+            DataType::Log,
+            input_definitions
+                .iter()
+                .map(|(output, _)| (output.clone(), definition.clone()))
+                .collect(),
+        )]
+    }
+}
+
+/// The count of a key, which overestimates it by at most `error`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Counter {
+    count: u64,
+    error: u64,
+}
+
+/// A Space-Saving summary of the heaviest keys of a stream.
+#[derive(Debug)]
+struct SpaceSaving {
+    capacity: usize,
+    counters: HashMap<String, Counter>,
+    /// The counters ordered by count, to find the lowest one.
+    ordered: BTreeSet<(u64, String)>,
+    total: u64,
+}
+
+impl SpaceSaving {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            counters: HashMap::with_capacity(capacity),
+            ordered: BTreeSet::new(),
+            total: 0,
+        }
+    }
+
+    fn add(&mut self, key: String, weight: u64) {
+        self.total += weight;
+
+        if let Some(counter) = self.counters.get_mut(&key) {
+            self.ordered.remove(&(counter.count, key.clone()));
+            counter.count += weight;
+            self.ordered.insert((counter.count, key));
+            return;
+        }
+
+        let counter = if self.counters.len() < self.capacity {
+            Counter {
+                count: weight,
+                error: 0,
+            }
+        } else {
+            let (min, evicted) = self.ordered.pop_first().expect("Summary is full");
+            self.counters.remove(&evicted);
+            Counter {
+                count: min + weight,
+                error: min,
+            }
+        };
+        self.ordered.insert((counter.count, key.clone()));
+        self.counters.insert(key, counter);
+    }
+
+    /// The `k` heaviest keys, from the heaviest.
+    fn top(&self, k: usize) -> Vec<(&str, Counter)> {
+        self.ordered
+            .iter()
+            .rev()
+            .take(k)
+            .map(|(_, key)| (key.as_str(), self.counters[key]))
+            .collect()
+    }
+}
+
+pub struct TopK {
+    key: Template,
+    k: usize,
+    weight: TopKWeight,
+    window: Duration,
+    summary: SpaceSaving,
+    window_start: DateTime<Utc>,
+}
+
+impl TopK {
+    pub fn new(config: &TopKConfig) -> crate::Result<Self> {
+        if config.window_secs == 0 {
+            return Err("`window_secs` must be greater than zero".into());
+        }
+        let capacity = config
+            .capacity
+            .map_or(config.k.get().saturating_mul(10), NonZeroUsize::get);
+        if capacity < config.k.get() {
+            return Err("`capacity` must be at least `k`".into());
+        }
+
+        Ok(Self {
+            key: config.key.clone(),
+            k: config.k.get(),
+            weight: config.weight,
+            window: Duration::from_secs(config.window_secs),
+            summary: SpaceSaving::new(capacity),
+            window_start: Utc::now(),
+        })
+    }
+
+    fn record(&mut self, event: Event) {
+        let key = match self.key.render_string(&event) {
+            Ok(key) => key,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("key"),
+                    drop_event: false,
+                });
+                return;
+            }
+        };
+        let weight = match self.weight {
+            TopKWeight::Events => 1,
+            TopKWeight::Bytes => event.estimated_json_encoded_size_of() as u64,
+        };
+        self.summary.add(key, weight);
+    }
+
+    /// Summarizes the window ending now, if it had events, and starts the next one.
+    fn flush(&mut self, now: DateTime<Utc>) -> Option<Event> {
+        let summary = std::mem::replace(&mut self.summary, SpaceSaving::new(self.summary.capacity));
+        let window_start = std::mem::replace(&mut self.window_start, now);
+        if summary.total == 0 {
+            return None;
+        }
+
+        let top = summary
+            .top(self.k)
+            .into_iter()
+            .map(|(key, counter)| {
+                Value::Object(
+                    [
+                        ("key".into(), Value::from(key)),
+                        ("count".into(), Value::from(counter.count as i64)),
+                        ("error".into(), Value::from(counter.error as i64)),
+                    ]
+                    .into(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut log = LogEvent::default();
+        if let Some(timestamp_key) = log_schema().timestamp_key() {
+            log.insert((PathPrefix::Event, timestamp_key), now);
+        }
+        log.insert("window_start", window_start);
+        log.insert("weight", self.weight.as_str());
+        log.insert("total", summary.total as i64);
+        log.insert("top", top);
+        Some(log.into())
+    }
+}
+
+impl TaskTransform<Event> for TopK {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+        let mut windows =
+            tokio::time::interval_at(tokio::time::Instant::now() + me.window, me.window);
+
+        Box::pin(stream! {
+            loop {
+                tokio::select! {
+                    _ = windows.tick() => {
+                        if let Some(summary) = me.flush(Utc::now()) {
+                            yield summary;
+                        }
+                    }
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            Some(event) => me.record(event),
+                            None => {
+                                if let Some(summary) = me.flush(Utc::now()) {
+                                    yield summary;
+                                }
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top_k(config: &str) -> TopK {
+        TopK::new(&toml::from_str::<TopKConfig>(config).unwrap()).unwrap()
+    }
+
+    fn event(service: &str) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert("service", service);
+        log.into()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TopKConfig>();
+    }
+
+    #[test]
+    fn space_saving_keeps_heavy_hitters() {
+        let mut summary = SpaceSaving::new(3);
+        for key in ["a", "a", "a", "a", "b", "b", "b", "c", "d", "e", "a"] {
+            summary.add(key.to_owned(), 1);
+        }
+
+        let top = summary.top(2);
+        assert_eq!(top[0], ("a", Counter { count: 5, error: 0 }));
+        assert_eq!(top[1], ("b", Counter { count: 3, error: 0 }));
+        // `e` replaced `d`, which replaced `c`.
+        assert_eq!(summary.top(3)[2], ("e", Counter { count: 3, error: 2 }));
+        assert_eq!(summary.total, 11);
+    }
+
+    #[test]
+    fn summarizes_windows() {
+        let mut transform = top_k(
+            r#"
+            key = "{{ service }}"
+            k = 2
+            "#,
+        );
+        for service in ["api", "db", "api", "web", "api", "db"] {
+            transform.record(event(service));
+        }
+        // Events without the key aren't counted.
+        transform.record(LogEvent::from("message").into());
+
+        let summary = transform.flush(Utc::now()).unwrap();
+        let log = summary.as_log();
+        assert_eq!(log["total"], Value::from(6_i64));
+        assert_eq!(log["weight"], Value::from("events"));
+        assert_eq!(log["top[0].key"], Value::from("api"));
+        assert_eq!(log["top[0].count"], Value::from(3_i64));
+        assert_eq!(log["top[1].key"], Value::from("db"));
+        assert!(log.get("top[2]").is_none());
+
+        // The next window starts empty.
+        assert!(transform.flush(Utc::now()).is_none());
+    }
+
+    #[test]
+    fn weighs_events_by_size() {
+        let mut transform = top_k(
+            r#"
+            key = "{{ service }}"
+            weight = "bytes"
+            "#,
+        );
+        let large = {
+            let mut log = LogEvent::from("x".repeat(1000));
+            log.insert("service", "noisy");
+            Event::from(log)
+        };
+        transform.record(large);
+        for _ in 0..5 {
+            transform.record(event("quiet"));
+        }
+
+        let summary = transform.flush(Utc::now()).unwrap();
+        assert_eq!(summary.as_log()["top[0].key"], Value::from("noisy"));
+    }
+}
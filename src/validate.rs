@@ -10,6 +10,10 @@ use crate::{
     topology::{self, builder::Pieces},
 };
 
+mod preflight;
+
+use preflight::{CheckKind, Report, Status};
+
 const TEMPORARY_DIRECTORY: &str = "validate_tmp";
 
 #[derive(Parser, Debug)]
@@ -19,6 +23,16 @@ pub struct Opts {
     #[arg(long)]
     pub no_environment: bool,
 
+    /// Checks connectivity without starting the topology, on top of the environment checks.
+    /// That includes binding the ports of the components, resolving the endpoints of sinks, and
+    /// completing a TLS handshake with those using TLS.
+    #[arg(long, conflicts_with = "no_environment")]
+    pub preflight: bool,
+
+    /// Writes a JSON report of the checks and their outcome to the given file.
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
     /// Fail validation on warnings that are probably a mistake in the configuration
     /// or are recommended to be fixed.
     #[arg(short, long)]
@@ -90,26 +104,36 @@ impl Opts {
     }
 }
 
-/// Performs topology, component, and health checks.
+/// Performs topology, component, and health checks, and optionally connectivity checks.
 pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
     let mut fmt = Formatter::new(color);
+    let mut report = Report::default();
 
     let mut validated = true;
 
-    let mut config = match validate_config(opts, &mut fmt) {
+    let mut config = match validate_config(opts, &mut fmt, &mut report) {
         Some(config) => config,
-        None => return exitcode::CONFIG,
+        None => {
+            write_report(opts, &report, false, &mut fmt);
+            return exitcode::CONFIG;
+        }
     };
 
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
-            validated &= validate_environment(opts, &config, &mut fmt).await;
+            validated &= validate_environment(opts, &config, &mut fmt, &mut report).await;
             remove_tmp_directory(tmp_directory);
         } else {
             validated = false;
         }
     }
 
+    if opts.preflight {
+        validated &= validate_preflight(&config, &mut fmt, &mut report).await;
+    }
+
+    validated &= write_report(opts, &report, validated, &mut fmt);
+
     if validated {
         fmt.validated();
         exitcode::OK
@@ -118,22 +142,32 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
     }
 }
 
-pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
+pub fn validate_config(opts: &Opts, fmt: &mut Formatter, report: &mut Report) -> Option<Config> {
     // Prepare paths
     let paths = opts.paths_with_formats();
     let paths = if let Some(paths) = config::process_paths(&paths) {
         paths
     } else {
         fmt.error("No config file paths");
+        report.push(
+            CheckKind::Config,
+            None,
+            None,
+            Status::Failed,
+            Some("No config file paths".to_owned()),
+        );
         return None;
     };
 
     // Load
     let paths_list: Vec<_> = paths.iter().map(<&PathBuf>::from).collect();
 
-    let mut report_error = |errors| {
+    let mut report_error = |errors: Vec<String>| {
         fmt.title(format!("Failed to load {:?}", &paths_list));
-        fmt.sub_error(errors);
+        fmt.sub_error(&errors);
+        for error in errors {
+            report.push(CheckKind::Config, None, None, Status::Failed, Some(error));
+        }
     };
     let (builder, load_warnings) = config::load_builder_from_paths(&paths)
         .map_err(&mut report_error)
@@ -158,39 +192,65 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
         }
 
         fmt.title(format!("Loaded with warnings {:?}", &paths_list));
-        fmt.sub_warning(warnings);
+        fmt.sub_warning(&warnings);
+        for warning in warnings {
+            report.push(
+                CheckKind::Config,
+                None,
+                None,
+                Status::Warning,
+                Some(warning),
+            );
+        }
     } else {
         fmt.success(format!("Loaded {:?}", &paths_list));
+        report.push(CheckKind::Config, None, None, Status::Passed, None);
     }
 
     Some(config)
 }
 
-async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
+async fn validate_environment(
+    opts: &Opts,
+    config: &Config,
+    fmt: &mut Formatter,
+    report: &mut Report,
+) -> bool {
     let diff = ConfigDiff::initial(config);
 
-    let mut pieces = if let Some(pieces) = validate_components(config, &diff, fmt).await {
+    let mut pieces = if let Some(pieces) = validate_components(config, &diff, fmt, report).await {
         pieces
     } else {
         return false;
     };
 
-    validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await
+    validate_healthchecks(opts, config, &diff, &mut pieces, fmt, report).await
 }
 
 async fn validate_components(
     config: &Config,
     diff: &ConfigDiff,
     fmt: &mut Formatter,
+    report: &mut Report,
 ) -> Option<Pieces> {
     match topology::builder::build_pieces(config, diff, HashMap::new()).await {
         Ok(pieces) => {
             fmt.success("Component configuration");
+            report.push(CheckKind::Components, None, None, Status::Passed, None);
             Some(pieces)
         }
         Err(errors) => {
             fmt.title("Component errors");
-            fmt.sub_error(errors);
+            fmt.sub_error(&errors);
+            for error in errors {
+                report.push(
+                    CheckKind::Components,
+                    None,
+                    None,
+                    Status::Failed,
+                    Some(error),
+                );
+            }
             None
         }
     }
@@ -202,9 +262,17 @@ async fn validate_healthchecks(
     diff: &ConfigDiff,
     pieces: &mut Pieces,
     fmt: &mut Formatter,
+    report: &mut Report,
 ) -> bool {
     if !config.healthchecks.enabled {
         fmt.warning("Health checks are disabled");
+        report.push(
+            CheckKind::Healthcheck,
+            None,
+            None,
+            Status::Warning,
+            Some("Health checks are disabled".to_owned()),
+        );
         return !opts.deny_warnings;
     }
 
@@ -213,9 +281,16 @@ async fn validate_healthchecks(
     // to parse which errors/warnings/etc. belong to which healthcheck.
     let mut validated = true;
     for (id, healthcheck) in healthchecks {
-        let mut failed = |error| {
+        let mut failed = |error: String| {
             validated = false;
-            fmt.error(error);
+            fmt.error(&error);
+            report.push(
+                CheckKind::Healthcheck,
+                Some(&id),
+                None,
+                Status::Failed,
+                Some(error),
+            );
         };
 
         trace!("Healthcheck for {id} starting.");
@@ -228,8 +303,22 @@ async fn validate_healthchecks(
                     .enabled
                 {
                     fmt.success(format!("Health check \"{}\"", id));
+                    report.push(
+                        CheckKind::Healthcheck,
+                        Some(&id),
+                        None,
+                        Status::Passed,
+                        None,
+                    );
                 } else {
                     fmt.warning(format!("Health check disabled for \"{}\"", id));
+                    report.push(
+                        CheckKind::Healthcheck,
+                        Some(&id),
+                        None,
+                        Status::Warning,
+                        Some("Health check is disabled".to_owned()),
+                    );
                     validated &= !opts.deny_warnings;
                 }
             }
@@ -245,6 +334,42 @@ async fn validate_healthchecks(
     validated
 }
 
+async fn validate_preflight(config: &Config, fmt: &mut Formatter, report: &mut Report) -> bool {
+    let start = report.checks.len();
+    preflight::check_binds(config, report).await;
+    preflight::check_endpoints(config, report).await;
+
+    let checks = &report.checks[start..];
+    if checks.is_empty() {
+        fmt.warning("No ports to bind or endpoints to connect to");
+        return true;
+    }
+
+    let mut validated = true;
+    for check in checks {
+        if check.status == Status::Failed {
+            validated = false;
+            fmt.error(check.to_string());
+        } else {
+            fmt.success(check.to_string());
+        }
+    }
+    validated
+}
+
+fn write_report(opts: &Opts, report: &Report, validated: bool, fmt: &mut Formatter) -> bool {
+    match &opts.report {
+        Some(path) => match report.write(path, validated) {
+            Ok(()) => true,
+            Err(error) => {
+                fmt.error(format!("Failed to write report {:?}: {}", path, error));
+                false
+            }
+        },
+        None => true,
+    }
+}
+
 /// For data directory that we write to:
 /// 1. Create a tmp directory in it.
 /// 2. Change config to point to that tmp directory.
@@ -0,0 +1,363 @@
+//! Connectivity checks of `vector validate --preflight`.
+
+use std::{fmt, fs::File, io, net::SocketAddr, path::Path, time::Duration};
+
+use serde::Serialize;
+use tokio::{
+    net::{TcpListener, UdpSocket},
+    time::timeout,
+};
+use url::Url;
+
+use crate::{
+    config::{ComponentKey, Config, Protocol, Resource},
+    dns::{self, Resolver},
+    tls::{MaybeTlsSettings, TlsConfig, TlsEnableableConfig},
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Top-level sink options holding the addresses the sink connects to.
+const ENDPOINT_OPTIONS: [&str; 6] = [
+    "address",
+    "bootstrap_servers",
+    "endpoint",
+    "endpoints",
+    "uri",
+    "url",
+];
+
+/// Machine readable report of a validation.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn push(
+        &mut self,
+        check: CheckKind,
+        component: Option<&ComponentKey>,
+        target: Option<String>,
+        status: Status,
+        message: Option<String>,
+    ) {
+        self.checks.push(Check {
+            check,
+            component: component.map(|key| key.id().to_owned()),
+            target,
+            status,
+            message,
+        });
+    }
+
+    fn push_result(
+        &mut self,
+        check: CheckKind,
+        component: &ComponentKey,
+        target: String,
+        result: Result<(), String>,
+    ) {
+        let (status, message) = match result {
+            Ok(()) => (Status::Passed, None),
+            Err(error) => (Status::Failed, Some(error)),
+        };
+        self.push(check, Some(component), Some(target), status, message);
+    }
+
+    /// Writes the report as JSON to the file at `path`.
+    pub fn write(&self, path: &Path, validated: bool) -> io::Result<()> {
+        #[derive(Serialize)]
+        struct Output<'a> {
+            validated: bool,
+            checks: &'a [Check],
+        }
+
+        let output = Output {
+            validated,
+            checks: &self.checks,
+        };
+        serde_json::to_writer_pretty(File::create(path)?, &output)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub check: CheckKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let check = match self.check {
+            CheckKind::Config => "Configuration",
+            CheckKind::Components => "Component configuration",
+            CheckKind::Healthcheck => "Health check",
+            CheckKind::Bind => "Bind",
+            CheckKind::Dns => "DNS resolution",
+            CheckKind::Tls => "TLS handshake",
+        };
+        write!(f, "{}", check)?;
+        if let Some(target) = &self.target {
+            write!(f, " {}", target)?;
+        }
+        if let Some(component) = &self.component {
+            write!(f, " for \"{}\"", component)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    Config,
+    Components,
+    Healthcheck,
+    Bind,
+    Dns,
+    Tls,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Passed,
+    Warning,
+    Failed,
+}
+
+/// Checks that the ports of the sources and sinks are free to bind.
+pub async fn check_binds(config: &Config, report: &mut Report) {
+    let sources = config
+        .sources()
+        .map(|(key, source)| (key, source.inner.resources()));
+    let sinks = config.sinks().map(|(key, sink)| (key, sink.resources(key)));
+
+    for (key, resources) in sources.chain(sinks) {
+        for resource in resources {
+            if let Resource::Port(address, protocol) = resource {
+                let result = bind(address, protocol)
+                    .await
+                    .map_err(|error| error.to_string());
+                let target = format!("{:?} {}", protocol, address).to_lowercase();
+                report.push_result(CheckKind::Bind, key, target, result);
+            }
+        }
+    }
+}
+
+async fn bind(address: SocketAddr, protocol: Protocol) -> io::Result<()> {
+    match protocol {
+        Protocol::Tcp => TcpListener::bind(address).await.map(drop),
+        Protocol::Udp => UdpSocket::bind(address).await.map(drop),
+    }
+}
+
+/// A network endpoint a sink connects to.
+#[derive(Debug, PartialEq, Eq)]
+struct Endpoint {
+    host: String,
+    port: u16,
+    tls: bool,
+}
+
+impl Endpoint {
+    /// Parses a URL, or a `host:port` address.
+    fn parse(value: &str, tls_enabled: bool) -> Option<Self> {
+        if let Ok(url) = Url::parse(value) {
+            if let Some(host) = url.host_str() {
+                return Some(Self {
+                    host: host.trim_matches(|c| c == '[' || c == ']').to_owned(),
+                    port: url.port_or_known_default()?,
+                    tls: tls_enabled || matches!(url.scheme(), "https" | "wss" | "tls"),
+                });
+            }
+        }
+
+        let (host, port) = value.rsplit_once(':')?;
+        Some(Self {
+            host: host.trim_matches(|c| c == '[' || c == ']').to_owned(),
+            port: port.parse().ok()?,
+            tls: tls_enabled,
+        })
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// Extracts the endpoints of a sink from the options that usually hold them, along with its TLS
+/// options. Templated endpoints are skipped, as they're only known once events are received.
+fn endpoints(sink: &serde_json::Value) -> (Vec<Endpoint>, Option<TlsEnableableConfig>) {
+    let tls = sink
+        .get("tls")
+        .and_then(|tls| serde_json::from_value::<TlsEnableableConfig>(tls.clone()).ok());
+    let tls_enabled = tls
+        .as_ref()
+        .map_or(false, |tls| tls.enabled.unwrap_or(false));
+
+    let endpoints = ENDPOINT_OPTIONS
+        .iter()
+        .filter_map(|option| sink.get(option))
+        .flat_map(|value| match value {
+            serde_json::Value::String(value) => value.split(',').map(str::to_owned).collect(),
+            serde_json::Value::Array(values) => values
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_owned))
+                .collect(),
+            _ => Vec::new(),
+        })
+        .filter(|value| !value.contains("{{"))
+        .filter_map(|value| Endpoint::parse(value.trim(), tls_enabled))
+        .collect();
+
+    (endpoints, tls)
+}
+
+/// Resolves the endpoints of the sinks, and completes a TLS handshake with those using TLS.
+pub async fn check_endpoints(config: &Config, report: &mut Report) {
+    if let Err(error) = dns::apply(&config.global.dns) {
+        report.push(
+            CheckKind::Dns,
+            None,
+            None,
+            Status::Failed,
+            Some(error.to_string()),
+        );
+        return;
+    }
+
+    for (key, sink) in config.sinks() {
+        let Ok(value) = serde_json::to_value(&sink.inner) else {
+            continue;
+        };
+        let (endpoints, tls) = endpoints(&value);
+
+        for endpoint in endpoints {
+            let resolved = Resolver
+                .lookup_ip(endpoint.host.clone())
+                .await
+                .map_err(|error| error.to_string())
+                .and_then(|mut ips| {
+                    ips.next()
+                        .ok_or_else(|| "No addresses were resolved".to_owned())
+                });
+            report.push_result(
+                CheckKind::Dns,
+                key,
+                endpoint.host.clone(),
+                resolved.as_ref().map(drop).map_err(Clone::clone),
+            );
+
+            if let (true, Ok(ip)) = (endpoint.tls, resolved) {
+                let options = tls.as_ref().map(|tls| tls.options.clone());
+                let address = SocketAddr::new(ip, endpoint.port);
+                let result = handshake(&endpoint.host, address, options).await;
+                report.push_result(CheckKind::Tls, key, endpoint.to_string(), result);
+            }
+        }
+    }
+}
+
+async fn handshake(
+    host: &str,
+    address: SocketAddr,
+    options: Option<TlsConfig>,
+) -> Result<(), String> {
+    let settings = MaybeTlsSettings::tls_client(&options).map_err(|error| error.to_string())?;
+    match timeout(CONNECT_TIMEOUT, settings.connect(host, &address)).await {
+        Ok(Ok(_stream)) => Ok(()),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(_) => Err(format!(
+            "Timed out after {} seconds",
+            CONNECT_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_endpoints() {
+        assert_eq!(
+            Endpoint::parse("https://example.com/path", false),
+            Some(Endpoint {
+                host: "example.com".to_owned(),
+                port: 443,
+                tls: true,
+            })
+        );
+        assert_eq!(
+            Endpoint::parse("localhost:9000", true),
+            Some(Endpoint {
+                host: "localhost".to_owned(),
+                port: 9000,
+                tls: true,
+            })
+        );
+        assert_eq!(
+            Endpoint::parse("[::1]:514", false),
+            Some(Endpoint {
+                host: "::1".to_owned(),
+                port: 514,
+                tls: false,
+            })
+        );
+        assert_eq!(Endpoint::parse("/var/run/socket", false), None);
+    }
+
+    #[test]
+    fn extracts_endpoints_from_sinks() {
+        let (endpoints, tls) = endpoints(&json!({
+            "type": "kafka",
+            "bootstrap_servers": "kafka-1:9092,kafka-2:9092",
+            "topic": "{{ topic }}",
+            "tls": {"enabled": true},
+        }));
+        assert!(tls.is_some());
+        assert_eq!(
+            endpoints
+                .iter()
+                .map(|endpoint| (endpoint.to_string(), endpoint.tls))
+                .collect::<Vec<_>>(),
+            vec![
+                ("kafka-1:9092".to_owned(), true),
+                ("kafka-2:9092".to_owned(), true),
+            ]
+        );
+
+        let (endpoints, _) = endpoints(&json!({
+            "type": "http",
+            "uri": "http://{{ host }}/logs",
+        }));
+        assert!(endpoints.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fails_binding_ports_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        assert!(bind(address, Protocol::Tcp).await.is_err());
+        drop(listener);
+        assert!(bind(address, Protocol::Tcp).await.is_ok());
+    }
+}
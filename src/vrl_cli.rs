@@ -0,0 +1,438 @@
+#![allow(missing_docs)]
+//! The `vector vrl` subcommand.
+//!
+//! Without fixtures, this is the VRL CLI. With fixtures, scripts are run against every event of
+//! the fixtures file and the changes they make are printed, optionally along with the time spent
+//! in each function.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use vrl::{
+    compiler::{
+        compile_with_state, runtime::Runtime, CompileConfig, Function, TargetValue, TimeZone,
+        TypeState,
+    },
+    diagnostic::Formatter,
+    prelude::{
+        ArgumentList, Compiled, Context, Example, Expression, FunctionCompileContext,
+        FunctionExpression, Parameter, Resolved, TypeDef,
+    },
+    value::{Secrets, Value},
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    #[command(flatten)]
+    repl: vrl::cli::Opts,
+
+    /// File of sample events to run the scripts against, either as a JSON array or as one JSON
+    /// object per line.
+    #[arg(long, value_name = "FILE", requires = "scripts")]
+    fixtures: Option<PathBuf>,
+
+    /// File of a VRL script to run against every fixture. Can be repeated to compare scripts.
+    #[arg(long = "script", value_name = "FILE", requires = "fixtures")]
+    scripts: Vec<PathBuf>,
+
+    /// Report the number of calls and the execution time of each function, across all fixtures.
+    ///
+    /// The time of a function includes the time of the functions called by its arguments.
+    #[arg(long, requires = "fixtures")]
+    profile: bool,
+}
+
+fn functions() -> Vec<Box<dyn Function>> {
+    let mut functions = vrl::stdlib::all();
+    functions.extend(vector_vrl_functions::all());
+    vector_core::fips::retain_approved_vrl_functions(&mut functions);
+    functions
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match &opts.fixtures {
+        None => vrl::cli::cmd::cmd(&opts.repl, functions()),
+        Some(fixtures) => match run_fixtures(fixtures, &opts.scripts, opts.profile) {
+            Ok(()) => exitcode::OK,
+            Err(error) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("{}", error);
+                }
+                exitcode::DATAERR
+            }
+        },
+    }
+}
+
+/// Reads the events of a fixtures file.
+fn load_fixtures(contents: &str) -> Result<Vec<Value>, String> {
+    if let Ok(events) = serde_json::from_str::<Vec<serde_json::Value>>(contents) {
+        return Ok(events.into_iter().map(Value::from).collect());
+    }
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .map(Value::from)
+                .map_err(|error| format!("Invalid event on line {}: {}", index + 1, error))
+        })
+        .collect()
+}
+
+#[allow(clippy::print_stdout)]
+fn run_fixtures(path: &Path, scripts: &[PathBuf], profile: bool) -> Result<(), String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("Could not read fixtures {:?}: {}", path, error))?;
+    let fixtures = load_fixtures(&contents)?;
+
+    let profiler = Profiler::default();
+    let functions = if profile {
+        profiler.wrap(functions())
+    } else {
+        functions()
+    };
+
+    let mut programs = Vec::with_capacity(scripts.len());
+    for script in scripts {
+        let source = fs::read_to_string(script)
+            .map_err(|error| format!("Could not read script {:?}: {}", script, error))?;
+        let program = compile_with_state(
+            &source,
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map_err(|diagnostics| {
+            format!(
+                "Could not compile script {:?}:\n{}",
+                script,
+                Formatter::new(&source, diagnostics).colored()
+            )
+        })?
+        .program;
+        programs.push((script.display().to_string(), program));
+    }
+
+    let timezone = TimeZone::default();
+    let mut runtime = Runtime::default();
+    let mut durations = vec![Duration::ZERO; programs.len()];
+    for (index, event) in fixtures.iter().enumerate() {
+        println!("Event {}:", index + 1);
+        for ((name, program), duration) in programs.iter().zip(durations.iter_mut()) {
+            let mut target = TargetValue {
+                value: event.clone(),
+                metadata: Value::Object(BTreeMap::new()),
+                secrets: Secrets::new(),
+            };
+
+            let start = Instant::now();
+            let result = runtime.resolve(&mut target, program, &timezone);
+            *duration += start.elapsed();
+            runtime.clear();
+
+            println!("  {}:", name);
+            match result {
+                Err(error) => println!("    error: {}", error),
+                Ok(_) => {
+                    let changes = diff(event, &target.value);
+                    if changes.is_empty() {
+                        println!("    (unchanged)");
+                    }
+                    for change in changes {
+                        println!("    {}", change);
+                    }
+                }
+            }
+        }
+    }
+
+    if profile {
+        println!();
+        for ((name, _), duration) in programs.iter().zip(&durations) {
+            println!("{}: {:?} for {} events", name, duration, fixtures.len());
+        }
+        println!();
+        println!(
+            "{:<32} {:>10} {:>14} {:>14}",
+            "Function", "Calls", "Total", "Mean"
+        );
+        for (function, stats) in profiler.report() {
+            println!(
+                "{:<32} {:>10} {:>14} {:>14}",
+                function,
+                stats.calls,
+                format!("{:?}", stats.total),
+                format!("{:?}", stats.total / stats.calls.max(1) as u32),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// A change made by a script to a field of an event.
+#[derive(Debug, PartialEq, Eq)]
+enum Change {
+    Added(String, String),
+    Removed(String, String),
+    Updated(String, String, String),
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(path, value) => write!(f, "+ {} = {}", path, value),
+            Self::Removed(path, value) => write!(f, "- {} = {}", path, value),
+            Self::Updated(path, before, after) => {
+                write!(f, "~ {} = {} -> {}", path, before, after)
+            }
+        }
+    }
+}
+
+/// The changes from one event to another, by path of their fields.
+fn diff(before: &Value, after: &Value) -> Vec<Change> {
+    let mut before_fields = BTreeMap::new();
+    flatten(String::new(), before, &mut before_fields);
+    let mut after_fields = BTreeMap::new();
+    flatten(String::new(), after, &mut after_fields);
+
+    let mut changes = Vec::new();
+    for (path, value) in &before_fields {
+        match after_fields.get(path) {
+            None => changes.push(Change::Removed(path.clone(), value.clone())),
+            Some(new) if new != value => {
+                changes.push(Change::Updated(path.clone(), value.clone(), new.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (path, value) in after_fields {
+        if !before_fields.contains_key(&path) {
+            changes.push(Change::Added(path, value));
+        }
+    }
+    changes.sort_by(|a, b| change_path(a).cmp(change_path(b)));
+    changes
+}
+
+fn change_path(change: &Change) -> &str {
+    match change {
+        Change::Added(path, _) | Change::Removed(path, _) | Change::Updated(path, _, _) => path,
+    }
+}
+
+fn flatten(prefix: String, value: &Value, fields: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let key = if !key.is_empty()
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    key.clone()
+                } else {
+                    format!("{:?}", key)
+                };
+                flatten(format!("{}.{}", prefix, key), value, fields);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, value) in items.iter().enumerate() {
+                flatten(format!("{}[{}]", prefix, index), value, fields);
+            }
+        }
+        _ => {
+            let path = if prefix.is_empty() {
+                ".".to_owned()
+            } else {
+                prefix
+            };
+            fields.insert(path, value.to_string());
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct FunctionStats {
+    calls: u64,
+    total: Duration,
+}
+
+/// Measures the calls of functions.
+#[derive(Clone, Debug, Default)]
+struct Profiler {
+    stats: Arc<Mutex<HashMap<&'static str, FunctionStats>>>,
+}
+
+impl Profiler {
+    fn wrap(&self, functions: Vec<Box<dyn Function>>) -> Vec<Box<dyn Function>> {
+        functions
+            .into_iter()
+            .map(|inner| {
+                Box::new(ProfiledFunction {
+                    inner,
+                    profiler: self.clone(),
+                }) as Box<dyn Function>
+            })
+            .collect()
+    }
+
+    fn record(&self, function: &'static str, duration: Duration) {
+        let mut stats = self.stats.lock().expect("Profiler mutex is poisoned");
+        let stats = stats.entry(function).or_default();
+        stats.calls += 1;
+        stats.total += duration;
+    }
+
+    /// The stats of the called functions, from the slowest.
+    fn report(&self) -> Vec<(&'static str, FunctionStats)> {
+        let stats = self.stats.lock().expect("Profiler mutex is poisoned");
+        let mut report = stats
+            .iter()
+            .map(|(function, stats)| (*function, *stats))
+            .collect::<Vec<_>>();
+        report.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+        report
+    }
+}
+
+#[derive(Debug)]
+struct ProfiledFunction {
+    inner: Box<dyn Function>,
+    profiler: Profiler,
+}
+
+impl Function for ProfiledFunction {
+    fn identifier(&self) -> &'static str {
+        self.inner.identifier()
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.inner.parameters()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        self.inner.examples()
+    }
+
+    fn closure(&self) -> Option<vrl::compiler::function::closure::Definition> {
+        self.inner.closure()
+    }
+
+    fn compile(
+        &self,
+        state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let expr = self.inner.compile(state, ctx, arguments)?;
+        Ok(ProfiledFn {
+            function: self.inner.identifier(),
+            expr,
+            profiler: self.profiler.clone(),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ProfiledFn {
+    function: &'static str,
+    expr: Box<dyn Expression>,
+    profiler: Profiler,
+}
+
+impl FunctionExpression for ProfiledFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let start = Instant::now();
+        let result = self.expr.resolve(ctx);
+        self.profiler.record(self.function, start.elapsed());
+        result
+    }
+
+    fn type_def(&self, state: &TypeState) -> TypeDef {
+        self.expr.type_def(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn loads_fixtures() {
+        let array = load_fixtures(r#"[{"message": "a"}, {"message": "b"}]"#).unwrap();
+        let lines = load_fixtures("{\"message\": \"a\"}\n\n{\"message\": \"b\"}\n").unwrap();
+        assert_eq!(array, lines);
+        assert_eq!(array.len(), 2);
+
+        assert!(load_fixtures("{\"message\": \"a\"}\nnot json").is_err());
+    }
+
+    #[test]
+    fn diffs_events() {
+        let before = Value::from(json!({"message": "a", "tags": ["x"], "remove me": 1}));
+        let after =
+            Value::from(json!({"message": "b", "tags": ["x", "y"], "new": {"nested": true}}));
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                Change::Removed(".\"remove me\"".to_owned(), "1".to_owned()),
+                Change::Updated(
+                    ".message".to_owned(),
+                    "\"a\"".to_owned(),
+                    "\"b\"".to_owned()
+                ),
+                Change::Added(".new.nested".to_owned(), "true".to_owned()),
+                Change::Added(".tags[1]".to_owned(), "\"y\"".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn profiles_function_calls() {
+        let profiler = Profiler::default();
+        let functions = profiler.wrap(functions());
+        let program = compile_with_state(
+            r#".message = upcase(downcase(string!(.message)))"#,
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .unwrap()
+        .program;
+
+        let mut target = TargetValue {
+            value: Value::from(json!({"message": "Hello"})),
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut runtime = Runtime::default();
+        for _ in 0..3 {
+            runtime
+                .resolve(&mut target, &program, &TimeZone::default())
+                .unwrap();
+            runtime.clear();
+        }
+
+        assert_eq!(target.value, Value::from(json!({"message": "HELLO"})));
+        let report = profiler.report().into_iter().collect::<HashMap<_, _>>();
+        assert_eq!(report["upcase"].calls, 3);
+        assert_eq!(report["downcase"].calls, 3);
+        assert_eq!(report["string"].calls, 3);
+    }
+}
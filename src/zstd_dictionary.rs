@@ -0,0 +1,290 @@
+#![allow(missing_docs)]
+//! Compression of payloads with trained zstd dictionaries.
+//!
+//! Small payloads that resemble one another, such as the batches sent by edge Vector instances to
+//! an aggregator, compress poorly on their own. A dictionary trained on sample payloads, for
+//! example with `zstd --train`, primes the compressor with their common content.
+//!
+//! Both ends must use the same dictionary, identified by the ID embedded in it. Receivers list the
+//! IDs of their dictionaries in the [`DICTIONARY_HEADER`] header of their responses, and senders
+//! only compress with their dictionary once the receiver listed it, sending its ID in the same
+//! header of their requests. Until then, or if the receiver stops listing it, payloads are
+//! compressed without a dictionary.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
+
+/// The header holding the ID of the dictionary of a request, or the IDs of the dictionaries of
+/// the receiver in a response.
+pub const DICTIONARY_HEADER: &str = "vector-zstd-dictionary";
+
+/// The magic number starting trained dictionaries, which are followed by their ID.
+const DICTIONARY_MAGIC: u32 = 0xEC30_A437;
+
+#[derive(Debug, Snafu)]
+pub enum ZstdDictionaryError {
+    #[snafu(display("Could not read zstd dictionary {:?}: {}", path, source))]
+    Read { path: PathBuf, source: io::Error },
+    #[snafu(display(
+        "{:?} is not a trained zstd dictionary, which starts with a dictionary ID",
+        path
+    ))]
+    NotTrained { path: PathBuf },
+    #[snafu(display(
+        "zstd dictionaries {:?} and {:?} have the same ID {}",
+        first,
+        second,
+        id
+    ))]
+    DuplicateId {
+        first: PathBuf,
+        second: PathBuf,
+        id: u32,
+    },
+}
+
+/// A zstd dictionary.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ZstdDictionaryConfig {
+    /// The path of a trained zstd dictionary, such as one created with `zstd --train`.
+    #[configurable(metadata(docs::examples = "/etc/vector/zstd.dict"))]
+    pub path: PathBuf,
+}
+
+impl ZstdDictionaryConfig {
+    pub fn load(&self, level: i32) -> Result<ZstdDictionary, ZstdDictionaryError> {
+        let bytes = fs::read(&self.path).context(ReadSnafu { path: &self.path })?;
+        let id = dictionary_id(&bytes).ok_or_else(|| ZstdDictionaryError::NotTrained {
+            path: self.path.clone(),
+        })?;
+
+        Ok(ZstdDictionary {
+            id,
+            path: self.path.clone(),
+            encoder: Arc::new(EncoderDictionary::copy(&bytes, level)),
+            decoder: Arc::new(DecoderDictionary::copy(&bytes)),
+        })
+    }
+}
+
+fn dictionary_id(bytes: &[u8]) -> Option<u32> {
+    let magic = u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+    let id = u32::from_le_bytes(bytes.get(4..8)?.try_into().ok()?);
+    (magic == DICTIONARY_MAGIC && id != 0).then_some(id)
+}
+
+/// A loaded zstd dictionary, prepared for compression and decompression.
+#[derive(Clone)]
+pub struct ZstdDictionary {
+    id: u32,
+    path: PathBuf,
+    encoder: Arc<EncoderDictionary<'static>>,
+    decoder: Arc<DecoderDictionary<'static>>,
+}
+
+impl std::fmt::Debug for ZstdDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZstdDictionary")
+            .field("id", &self.id)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl ZstdDictionary {
+    pub const fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn compress(&self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        let mut encoder =
+            zstd::stream::write::Encoder::with_prepared_dictionary(Vec::new(), &self.encoder)?;
+        encoder.write_all(payload)?;
+        encoder.finish()
+    }
+
+    pub fn decompress(&self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        let mut decoder =
+            zstd::stream::read::Decoder::with_prepared_dictionary(payload, &self.decoder)?;
+        let mut decompressed = Vec::new();
+        decoder.read_to_end(&mut decompressed)?;
+        Ok(decompressed)
+    }
+}
+
+/// The dictionaries of a receiver, by ID.
+#[derive(Clone, Debug, Default)]
+pub struct ZstdDictionaries {
+    dictionaries: HashMap<u32, ZstdDictionary>,
+    header_value: String,
+}
+
+impl ZstdDictionaries {
+    pub fn load(configs: &[ZstdDictionaryConfig]) -> Result<Self, ZstdDictionaryError> {
+        let mut dictionaries = HashMap::<u32, ZstdDictionary>::new();
+        for config in configs {
+            let dictionary = config.load(zstd::DEFAULT_COMPRESSION_LEVEL)?;
+            if let Some(existing) = dictionaries.get(&dictionary.id) {
+                return Err(ZstdDictionaryError::DuplicateId {
+                    first: existing.path.clone(),
+                    second: dictionary.path,
+                    id: dictionary.id,
+                });
+            }
+            dictionaries.insert(dictionary.id, dictionary);
+        }
+
+        let mut ids = dictionaries.keys().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let header_value = ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Ok(Self {
+            dictionaries,
+            header_value,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dictionaries.is_empty()
+    }
+
+    /// The value of the [`DICTIONARY_HEADER`] header of responses, listing the dictionary IDs.
+    pub fn header_value(&self) -> &str {
+        &self.header_value
+    }
+
+    /// Gets the dictionary named by the [`DICTIONARY_HEADER`] header of a request.
+    pub fn get(&self, header: &str) -> Option<&ZstdDictionary> {
+        header
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| self.dictionaries.get(&id))
+    }
+}
+
+/// The dictionary of a sender, used once the receiver listed it.
+#[derive(Clone, Debug)]
+pub struct ZstdDictionaryNegotiation {
+    dictionary: ZstdDictionary,
+    accepted: Arc<AtomicBool>,
+}
+
+impl ZstdDictionaryNegotiation {
+    pub fn new(dictionary: ZstdDictionary) -> Self {
+        Self {
+            dictionary,
+            accepted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The dictionary to compress the next payload with, if the receiver accepts it.
+    pub fn dictionary(&self) -> Option<&ZstdDictionary> {
+        self.accepted
+            .load(Ordering::Relaxed)
+            .then_some(&self.dictionary)
+    }
+
+    /// Updates whether the receiver accepts the dictionary, from the [`DICTIONARY_HEADER`]
+    /// header of one of its responses.
+    pub fn observe(&self, header: Option<&str>) {
+        let id = self.dictionary.id.to_string();
+        let accepted = header.map_or(false, |ids| ids.split(',').any(|i| i.trim() == id));
+        if self.accepted.swap(accepted, Ordering::Relaxed) != accepted {
+            if accepted {
+                debug!(
+                    message = "Receiver accepts zstd dictionary.",
+                    id = self.dictionary.id
+                );
+            } else {
+                debug!(
+                    message = "Receiver no longer accepts zstd dictionary.",
+                    id = self.dictionary.id
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<Vec<u8>> {
+        (0..1000)
+            .map(|i| {
+                format!(
+                    r#"{{"host":"edge-{}","service":"checkout","level":"info","message":"Handled request {} in {}ms"}}"#,
+                    i % 7,
+                    i,
+                    i % 93
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    fn dictionary_config() -> (tempfile::TempDir, ZstdDictionaryConfig) {
+        let dictionary = zstd::dict::from_samples(&samples(), 4096).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zstd.dict");
+        fs::write(&path, dictionary).unwrap();
+        (dir, ZstdDictionaryConfig { path })
+    }
+
+    #[test]
+    fn compresses_with_dictionary() {
+        let (_dir, config) = dictionary_config();
+        let dictionary = config.load(zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+        let payload =
+            br#"{"host":"edge-3","service":"checkout","level":"info","message":"Handled request 4242 in 17ms"}"#;
+
+        let compressed = dictionary.compress(payload).unwrap();
+        assert!(compressed.len() < zstd::encode_all(&payload[..], 0).unwrap().len());
+        assert_eq!(dictionary.decompress(&compressed).unwrap(), payload);
+    }
+
+    #[test]
+    fn negotiates_dictionary() {
+        let (_dir, config) = dictionary_config();
+        let dictionaries = ZstdDictionaries::load(&[config.clone()]).unwrap();
+        let negotiation =
+            ZstdDictionaryNegotiation::new(config.load(zstd::DEFAULT_COMPRESSION_LEVEL).unwrap());
+        assert!(negotiation.dictionary().is_none());
+
+        negotiation.observe(Some(dictionaries.header_value()));
+        let id = negotiation.dictionary().unwrap().id();
+        assert!(dictionaries.get(&id.to_string()).is_some());
+
+        negotiation.observe(None);
+        assert!(negotiation.dictionary().is_none());
+    }
+
+    #[test]
+    fn rejects_raw_dictionaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("raw.dict");
+        fs::write(&path, "not a trained dictionary").unwrap();
+
+        let error = ZstdDictionaryConfig { path }.load(0).unwrap_err();
+        assert!(matches!(error, ZstdDictionaryError::NotTrained { .. }));
+    }
+}
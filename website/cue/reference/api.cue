@@ -37,6 +37,16 @@ api: {
 				the API will not be exposed outside the container.
 				"""
 		}
+		mutations: {
+			common:   false
+			required: false
+			type: bool: default: false
+			description: """
+				Whether mutations overriding the configuration of running components, such as
+				`setSinkRequestLimits` and `setDualWriteMode`, are enabled for the API. Those mutations are rejected
+				otherwise.
+				"""
+		}
 		playground: {
 			common:   false
 			required: false
@@ -82,6 +92,23 @@ api: {
 				}
 			}
 		}
+		"/scaling": {
+			GET: {
+				description: """
+					The latest scaling signals, when the `scaling` option is
+					enabled. The `saturation` field can be polled by the KEDA
+					`metrics-api` scaler.
+					"""
+				responses: {
+					"200": {
+						description: "The latest scaling signals."
+					}
+					"404": {
+						description: "Scaling signals are not enabled."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
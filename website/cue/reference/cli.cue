@@ -104,6 +104,10 @@ cli: {
 			_short:      "V"
 			description: "Prints version information"
 		}
+		"fips": {
+			description: env_vars.VECTOR_FIPS.description
+			env_var:     "VECTOR_FIPS"
+		}
 		"watch-config": {
 			_short:      "w"
 			description: env_vars.VECTOR_WATCH_CONFIG.description
@@ -181,9 +185,51 @@ cli: {
 		}
 	}
 
-	options: _core_options
+	options: _core_options & {
+		"audit-log": {
+			description: env_vars.VECTOR_AUDIT_LOG.description
+			type:        "string"
+			env_var:     "VECTOR_AUDIT_LOG"
+		}
+	}
 
 	commands: {
+		"convert": {
+			description: """
+				Convert a Fluent Bit (classic format) or Filebeat configuration into a Vector
+				configuration, then exit. Settings that cannot be converted are marked with
+				`TODO` comments in the output.
+				"""
+
+			example: "vector convert --from fluent-bit /etc/fluent-bit/fluent-bit.conf --file vector.toml"
+
+			flags: _default_flags
+
+			options: {
+				"from": {
+					description: "The format of the configuration to convert"
+					required:    true
+					enum: {
+						"fluent-bit": "A Fluent Bit configuration in the classic format"
+						filebeat:     "A Filebeat YAML configuration"
+					}
+				}
+				"file": {
+					description: "Write the converted configuration to this file instead of stdout"
+					type:        "string"
+					example:     "/etc/vector/vector.toml"
+				}
+			}
+
+			args: {
+				input: {
+					description: "The configuration file to convert"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -244,6 +290,139 @@ cli: {
 			}
 		}
 
+		"replay": {
+			description: """
+				Replay an archive of events through the topology, then exit. The events are
+				read from a newline-delimited JSON archive, optionally gzip compressed, and
+				injected into the given source at a controlled rate. All other sources are
+				disabled for the duration of the replay. The command exits with a non-zero
+				status if the replay is interrupted or stops at an invalid line of the archive.
+				"""
+
+			example: "vector replay --config /etc/vector/vector.toml --source in --timestamps shift --rate 500 archive.json.gz"
+
+			options: {
+				"config": {
+					_short:      "c"
+					description: env_vars.VECTOR_CONFIG.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG"
+				}
+				"config-toml": {
+					description: env_vars.VECTOR_CONFIG_TOML.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_TOML"
+				}
+				"config-json": {
+					description: env_vars.VECTOR_CONFIG_JSON.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_JSON"
+				}
+				"config-yaml": {
+					description: env_vars.VECTOR_CONFIG_YAML.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_YAML"
+				}
+				"source": {
+					description: "The ID of the source to inject the replayed events into"
+					type:        "string"
+					required:    true
+				}
+				"timestamps": {
+					description: "How to set the timestamp of replayed events"
+					default:     "original"
+					enum: {
+						original: "Keep the timestamps recorded in the archive"
+						shift:    "Shift all timestamps so that the first event is stamped with the time the replay started"
+						now:      "Stamp each event with the time it is replayed"
+					}
+				}
+				"rate": {
+					description: "The maximum number of events to replay per second. Defaults to replaying as fast as the topology accepts events"
+					type:        "integer"
+				}
+			}
+
+			args: {
+				archive: {
+					description: "The newline-delimited JSON archive to replay. Archives ending in `.gz` are decompressed"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
+
+		"bench": {
+			description: """
+				Benchmark the target config, then exit. Generated events, or the events of a
+				recorded archive, are injected into a source for the given duration while all
+				other sources are disabled. The end-to-end throughput, the CPU time of the
+				process, the allocations of the components when allocation tracing is enabled,
+				and the utilization of each component are then reported, and optionally
+				compared against the JSON report of a previous run.
+				"""
+
+			example: "vector bench --config /etc/vector/vector.toml --duration 60 --output report.json --baseline baseline.json --max-regression 5"
+
+			options: {
+				"config": {
+					_short:      "c"
+					description: env_vars.VECTOR_CONFIG.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG"
+				}
+				"config-toml": {
+					description: env_vars.VECTOR_CONFIG_TOML.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_TOML"
+				}
+				"config-json": {
+					description: env_vars.VECTOR_CONFIG_JSON.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_JSON"
+				}
+				"config-yaml": {
+					description: env_vars.VECTOR_CONFIG_YAML.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_YAML"
+				}
+				"source": {
+					description: "The ID of the source to inject the benchmark input into. Defaults to the only source of the configuration"
+					type:        "string"
+				}
+				"input": {
+					description: "An archive of recorded events, in the format read by `vector replay`, replayed in a loop. Defaults to generated events"
+					type:        "string"
+				}
+				"message-size": {
+					description: "The size, in bytes, of the message of generated events"
+					type:        "integer"
+					default:     256
+				}
+				"duration": {
+					description: "How long to run the benchmark for, in seconds"
+					type:        "integer"
+					default:     30
+				}
+				"rate": {
+					description: "The maximum number of events to inject per second. Defaults to injecting events as fast as the topology accepts them"
+					type:        "integer"
+				}
+				"output": {
+					description: "Write the report as JSON to this file"
+					type:        "string"
+				}
+				"baseline": {
+					description: "A report of a previous run to compare this one against"
+					type:        "string"
+				}
+				"max-regression": {
+					description: "Exit with an error if the throughput is lower than the baseline by more than this percentage"
+					type:        "integer"
+				}
+			}
+		}
+
 		"test": {
 			description: """
 				Run Vector config unit tests, then exit. This command is experimental and
@@ -405,9 +584,24 @@ cli: {
 					_short:      "d"
 					description: "Fail validation on warnings"
 				}
+				"preflight": {
+					description: """
+						Checks connectivity without starting the topology, on top of the
+						environment checks. That includes binding the ports of the components,
+						resolving the endpoints of sinks, and completing a TLS handshake with
+						those using TLS
+						"""
+				}
 			}
 
 			options: {
+				"report": {
+					description: """
+						Writes a JSON report of the checks and their outcome to the given file,
+						for use in CI/CD pipelines
+						"""
+					type: "string"
+				}
 				"config-toml": {
 					description: """
 						Any number of Vector config files to validate.
@@ -454,9 +648,33 @@ cli: {
 						The same result can be achieved by using `.` as the final expression.
 						"""
 				}
+				"profile": {
+					description: """
+						With `--fixtures`, report the number of calls and the execution time of each
+						function across all fixtures, along with the total time of each script. The
+						time of a function includes the time of the functions called by its arguments.
+						"""
+				}
 			}
 
 			options: {
+				"fixtures": {
+					description: """
+						File of sample events to run the `--script` scripts against, either as a JSON
+						array or as one JSON object per line. The changes each script makes to each
+						event are printed, instead of starting the REPL.
+						"""
+					type: "string"
+				}
+
+				"script": {
+					description: """
+						File of a VRL script to run against every event of `--fixtures`. Can be repeated
+						to compare scripts.
+						"""
+					type: "string"
+				}
+
 				"input": {
 					_short: "i"
 					description: """
@@ -513,6 +731,15 @@ cli: {
 				examples: ["/mnt/host/sys"]
 			}
 		}
+		VECTOR_AUDIT_LOG: {
+			description: """
+				Append a JSON record of every configuration load and reload, API mutation, tap session
+				and shutdown to this file. The file is created if it doesn't exist. The records are also
+				emitted as internal logs with the `vector::audit` target, and can be collected with the
+				`internal_logs` source.
+				"""
+			type: string: default: null
+		}
 		VECTOR_COLOR: {
 			description: "Control when ANSI terminal formatting is used."
 			type: string: {
@@ -613,6 +840,19 @@ cli: {
 				unit:    null
 			}
 		}
+		VECTOR_FIPS: {
+			description: """
+				Run in FIPS mode. OpenSSL is switched to its FIPS module, which must be available, and
+				Vector fails to start otherwise. TLS only uses FIPS approved algorithms, so components
+				configured with non-compliant certificates, keys or protocols fail validation, as do TLS
+				`ciphersuites` that are not FIPS approved. The VRL functions implemented with
+				non-validated cryptography (`decrypt`, `decrypt_envelope`, `encrypt`, `encrypt_envelope`,
+				`hmac`, `md5`, `random_bytes`, `sha1`, `sha2` and `sha3`) are unavailable, and the
+				`encrypt_fields`, `decrypt_fields` and `pseudonymize` transforms fail to build. Builds
+				with the `fips` feature always run in FIPS mode.
+				"""
+			type: bool: default: false
+		}
 		VECTOR_GRACEFUL_SHUTDOWN_LIMIT_SECS: {
 			description: "Set the duration in seconds to wait for graceful shutdown after SIGINT or SIGTERM are received. After the duration has passed, Vector will force shutdown. To never force shutdown, use `--no-graceful-shutdown-limit`."
 			type: uint: {
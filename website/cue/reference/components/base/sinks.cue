@@ -12,6 +12,18 @@ base: components: sinks: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			evictable: {
+				description: """
+					Whether the oldest data files of the buffer can be deleted to make room for other disk
+					buffers, once the disk buffers use up the `data_dir_budget`.
+
+					This only applies when the `evict_oldest` policy is configured for the budget. The
+					events held by the deleted data files are dropped.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: bool: default: false
+			}
 			max_events: {
 				description:   "The maximum number of events allowed in the buffer."
 				relevant_when: "type = \"memory\""
@@ -76,6 +88,147 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	buffer_migration: {
+		description: """
+			Migration of the disk buffers left behind by a sink.
+
+			Disk buffers are stored in a directory named after their sink, so the events they hold are
+			left behind on disk when the sink is renamed, or no longer uses a disk buffer.
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: """
+					Whether the disk buffers left behind by the sink are taken over when it is built.
+
+					When the sink uses a disk buffer and has none yet, the disk buffer of the first of its
+					previous IDs that has one is renamed to it. Any other disk buffer left behind, including its
+					own when it no longer uses a disk buffer, is drained into the sink ahead of its buffer, and
+					deleted once all of its events are acknowledged.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			previous_ids: {
+				description: """
+					The IDs the sink had before being renamed.
+
+					These can't be the IDs of sinks still in the configuration.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["old_sink_id"]
+			}
+		}
+	}
+	connection_pool: {
+		description: """
+			Connection pool settings of the HTTP clients of a sink.
+
+			Load balancers and NATs close connections that are idle for too long, usually without
+			notifying either end, which shows up as connection resets on the next request. Keeping idle
+			connections for less time than them, or sending TCP keepalives, avoids this.
+			"""
+		required: false
+		type: object: options: {
+			http2_adaptive_window: {
+				description: """
+					Whether HTTP/2 flow control windows adapt to the bandwidth and latency of the connection,
+					overriding the initial windows.
+					"""
+				required: false
+				type: bool: default: false
+			}
+			http2_initial_connection_window_bytes: {
+				description: "The initial HTTP/2 flow control window of each connection, shared by its requests."
+				required:    false
+				type: uint: unit: "bytes"
+			}
+			http2_initial_stream_window_bytes: {
+				description: """
+					The initial HTTP/2 flow control window of each request, which bounds the bytes of a
+					response sent before it is read.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
+			idle_timeout_secs: {
+				description: """
+					The number of seconds idle connections are kept open for.
+
+					Defaults to 90 seconds.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			max_connection_lifetime_secs: {
+				description: """
+					The maximum number of seconds connections are used for.
+
+					Afterwards, new requests are sent over new connections, while the old connections are
+					closed once their requests complete. This spreads the connections across the instances
+					behind a load balancer as they change.
+
+					By default, connections are used for as long as they stay open.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+			max_idle_per_host: {
+				description: """
+					The maximum number of idle connections kept open to each host.
+
+					With HTTP/1.1, a connection sends one request at a time, so as many connections as
+					concurrent requests are needed to avoid reconnecting. With HTTP/2, a single connection
+					carries all of the requests to a host.
+
+					By default, the number of idle connections is not limited.
+					"""
+				required: false
+				type: uint: unit: "connections"
+			}
+			tcp_keepalive_secs: {
+				description: """
+					The number of seconds a connection is idle before TCP keepalive probes are sent.
+
+					By default, TCP keepalive is disabled.
+					"""
+				required: false
+				type: uint: unit: "seconds"
+			}
+		}
+	}
+	debug: {
+		description: "Debugging options of a sink."
+		required:    false
+		type: object: options: {
+			capture_failed_requests: {
+				description: """
+					The number of the last failed requests of the sink to keep, along with their responses.
+
+					Requests are failed when they get a response with a status other than 2xx, or no response
+					at all. They are exposed by the `sinkFailedRequests` API query and by
+					`vector tap --errors`.
+
+					Only requests sent over HTTP are captured. While enabled, the body of each request is held
+					in memory until its response is received. Credentials found in headers and query
+					parameters are redacted, but bodies are kept as they are.
+					"""
+				required: false
+				type: uint: {
+					default: 0
+					unit:    "requests"
+				}
+			}
+			captured_body_bytes: {
+				description: "The maximum number of bytes kept of the body of each captured request and response."
+				required:    false
+				type: uint: {
+					default: 4096
+					unit:    "bytes"
+				}
+			}
+		}
+	}
 	healthcheck: {
 		description: "Healthcheck configuration."
 		required:    false
@@ -112,6 +265,23 @@ base: components: sinks: configuration: {
 		required: true
 		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
 	}
+	ordering: {
+		description: "The ordering guarantee of the events sent by a sink."
+		required:    false
+		type: string: {
+			default: "unordered"
+			enum: {
+				strict: """
+					Requests are sent one at a time, and a request is retried before the next one is sent, so
+					that the events of each partition reach the destination in the order they were received.
+
+					This disables `request.concurrency` and its adjustments, and can't be combined with buffers
+					overflowing to another stage.
+					"""
+				unordered: "Requests are sent concurrently, so events can reach the destination out of order."
+			}
+		}
+	}
 	proxy: {
 		description: """
 			Proxy configuration.
@@ -171,4 +341,43 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	request_limits: {
+		description: """
+			Request limits computed from the internal metrics of the sink.
+
+			The expressions are written in [Vector Remap Language][vrl] (VRL), and evaluated against an
+			object with the current value of each counter and gauge of the sink under `.metrics`, and the
+			per-second rate of each counter since the last evaluation under `.rates`, such as
+			`.rates.component_errors_total`. The values of the metrics with the same name are summed up.
+
+			An expression evaluates to an integer to override the corresponding `request` option, or to
+			`null` to restore it.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: object: options: {
+			concurrency: {
+				description: "An expression computing the number of concurrent requests allowed."
+				required:    false
+				type: string: examples: ["if (float(.metrics.buffer_events) ?? 0.0) > 10000 { 50 } else { null }"]
+			}
+			interval_secs: {
+				description: "The interval between evaluations of the expressions."
+				required:    false
+				type: uint: {
+					default: 10
+					unit:    "seconds"
+				}
+			}
+			rate_limit_num: {
+				description: """
+					An expression computing the maximum number of requests allowed within the
+					`request.rate_limit_duration_secs` time window.
+					"""
+				required: false
+				type: string: examples: ["if (float(.rates.component_errors_total) ?? 0.0) > 1 { 10 } else { null }"]
+			}
+		}
+	}
 }
@@ -1,5 +1,34 @@
 package metadata
 
+base: components: sources: configuration: dedupe_id: {
+	description: """
+		Dedupe ID configuration.
+
+		When enabled, each event leaving the source is stamped with a deterministic ID, stored in its
+		metadata as `%vector.dedupe_id`. The ID is a hash of the source ID and of the fields identifying
+		the event, so an event read again after a retry or a restart gets the same ID, while distinct
+		events with the same content don't.
+
+		The `http` sink can send the IDs of a request as an idempotency key, and other sinks can
+		propagate the IDs for the destination or its consumers to discard duplicates.
+		"""
+	required: false
+	type: object: options: fields: {
+		description: """
+			The fields of log and trace events hashed into the ID.
+
+			Together, they must identify an event, such as the file and the offset of a line, or the
+			partition and the offset of a Kafka record. Log and trace events are only stamped if fields
+			are set. Metric events are stamped from their series, value, and timestamp, if they have a
+			timestamp.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["message", "offset", "%kafka.partition"]
+		}
+	}
+}
 base: components: sources: configuration: proxy: {
 	description: """
 		Proxy configuration.
@@ -59,3 +88,82 @@ base: components: sources: configuration: proxy: {
 		}
 	}
 }
+base: components: sources: configuration: timestamp_format: {
+	description: """
+		The format used to parse timestamps emitted by this source that are strings.
+
+		The format uses the [`strptime` specifiers][chrono_strptime]. If unset, while `timezone` is
+		set, a set of common timestamp formats is tried instead.
+
+		[chrono_strptime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+		"""
+	required: false
+	type: string: examples: ["%d/%b/%Y:%H:%M:%S"]
+}
+base: components: sources: configuration: timezone: {
+	description: """
+		The name of the timezone to apply to timestamps emitted by this source that do not contain
+		an explicit time zone.
+
+		Timestamps that the source or its decoder leave as strings are parsed as they leave the
+		source. Downstream `remap` transforms also use it for the VRL timestamp conversions of the
+		events of this source, unless they set their own `timezone`.
+
+		This overrides the [global `timezone`][global_timezone] option. The time zone name may be
+		any name in the [TZ database][tz_database], or `local` to indicate system local time.
+
+		[global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
+		[tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+		"""
+	required: false
+	type: string: examples: ["local", "America/New_York", "EST5EDT"]
+}
+base: components: sources: configuration: wal: {
+	description: """
+		Source write-ahead log configuration.
+
+		When enabled, events are durably written to disk before being sent into the topology, and
+		removed once they have been delivered downstream, or rejected. Events that errored are sent
+		again, and events still pending when Vector stopped are replayed on startup, protecting against
+		data loss from in-memory buffers for sources that cannot replay data themselves, such as UDP
+		syslog or HTTP webhooks.
+
+		Replayed events are delivered at least once, and lose any acknowledgement back to the client
+		that originally sent them.
+		"""
+	required: false
+	type: object: options: {
+		data_dir: {
+			description: """
+				The directory used to persist the write-ahead log.
+
+				By default, the [global `data_dir` option][global_data_dir] is used.
+
+				[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+				"""
+			required: false
+			type: string: {}
+		}
+		max_retries: {
+			description: """
+				The maximum number of times events that errored downstream are sent again.
+
+				Events that still error afterwards are dropped, like rejected events are.
+				"""
+			required: false
+			type: uint: default: 3
+		}
+		max_size: {
+			description: """
+				The maximum size of the write-ahead log on disk.
+
+				Once reached, the source is paused until pending events are delivered downstream.
+				"""
+			required: false
+			type: uint: {
+				default: 268435456
+				unit:    "bytes"
+			}
+		}
+	}
+}
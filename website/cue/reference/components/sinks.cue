@@ -6,8 +6,9 @@ components: sinks: [Name=string]: {
 	features: _
 
 	configuration: {
-		inputs: base.components.sinks.configuration.inputs
-		buffer: base.components.sinks.configuration.buffer
+		inputs:           base.components.sinks.configuration.inputs
+		buffer:           base.components.sinks.configuration.buffer
+		buffer_migration: base.components.sinks.configuration.buffer_migration
 		healthcheck: {
 			description: base.components.sinks.configuration.healthcheck.description
 			required:    base.components.sinks.configuration.healthcheck.required
@@ -26,7 +27,16 @@ components: sinks: [Name=string]: {
 
 		if features.send != _|_ && features.send.proxy != _|_ {
 			if features.send.proxy.enabled {
-				proxy: base.components.sinks.configuration.proxy
+				proxy:           base.components.sinks.configuration.proxy
+				connection_pool: base.components.sinks.configuration.connection_pool
+				debug:           base.components.sinks.configuration.debug
+			}
+		}
+
+		if features.send != _|_ && features.send.request != _|_ {
+			if features.send.request.enabled {
+				request_limits: base.components.sinks.configuration.request_limits
+				ordering:       base.components.sinks.configuration.ordering
 			}
 		}
 
@@ -0,0 +1,113 @@
+package metadata
+
+components: sinks: azure_logs_ingestion: {
+	title: "Azure Monitor Logs Ingestion"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_monitor_logs
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Monitor Logs Ingestion API"
+							url:   urls.azure_monitor_logs_ingestion_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				A data collection rule (DCR) declaring the streams events are sent to, and the identity
+				of Vector with the Monitoring Metrics Publisher role on it.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_logs_ingestion.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body: """
+				Requests carry Microsoft Entra ID access tokens obtained with the default Azure credential
+				chain: a service principal set with the `AZURE_TENANT_ID`, `AZURE_CLIENT_ID`, and
+				`AZURE_CLIENT_SECRET` environment variables, a managed identity, or the Azure CLI. Tokens are
+				refreshed before they expire. The health check obtains a token, without sending events.
+				"""
+		}
+		stream_routing: {
+			title: "Stream routing"
+			body: """
+				Events are batched per rendered `stream_name`, and each batch is sent to that stream of the
+				data collection rule, which transforms the events and writes them to the stream's table.
+				Templating `stream_name` routes events to different tables without running several sinks.
+				"""
+		}
+		request_size: {
+			title: "Request size"
+			body: """
+				The Logs Ingestion API rejects requests over 1MB. Each batch is encoded as a JSON array
+				and split into as many requests as needed to stay under that limit, so `batch.max_bytes`
+				can be set independently of it. Events that don't fit in a request on their own are
+				dropped.
+				"""
+		}
+	}
+}
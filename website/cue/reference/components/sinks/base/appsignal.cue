@@ -95,6 +95,46 @@ base: components: sinks: appsignal: configuration: {
 		description: "Transformations to prepare an event for serialization."
 		required:    false
 		type: object: options: {
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -105,6 +145,49 @@ base: components: sinks: appsignal: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -294,6 +377,19 @@ base: components: sinks: appsignal: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
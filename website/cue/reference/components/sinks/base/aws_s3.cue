@@ -236,6 +236,27 @@ base: components: sinks: aws_s3: configuration: {
 		required: true
 		type: string: examples: ["my-bucket"]
 	}
+	checksum_algorithm: {
+		description: """
+			Algorithms for the additional checksum of S3 objects.
+
+			The algorithm used to compute an additional checksum of the created objects.
+
+			S3 verifies the checksum on upload and stores it with the object. This is in addition to
+			the `Content-MD5` header that is always sent.
+
+			See [Checking object integrity][checksums] for more details.
+
+			[checksums]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/checking-object-integrity.html
+			"""
+		required: false
+		type: string: enum: {
+			crc32:  "CRC32."
+			crc32c: "CRC32C."
+			sha1:   "SHA-1."
+			sha256: "SHA-256."
+		}
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -371,6 +392,13 @@ base: components: sinks: aws_s3: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					text_with_context: """
+						Plain text encoding, with a header line of selected fields before each message.
+
+						This encoding uses the `message` field of a log event, keeping the formatting of messages
+						spanning several lines, and is meant for people reading the output, such as files tailed
+						during an investigation.
+						"""
 				}
 			}
 			csv: {
@@ -391,6 +419,46 @@ base: components: sinks: aws_s3: configuration: {
 					type: array: items: type: string: {}
 				}
 			}
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -422,6 +490,70 @@ base: components: sinks: aws_s3: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			text_with_context: {
+				description:   "Options for the text with context serializer."
+				relevant_when: "codec = \"text_with_context\""
+				required:      true
+				type: object: options: {
+					header_fields: {
+						description: """
+							The fields written in the header line that precedes each message, in order.
+
+							Fields missing from the event are written as `-`, and timestamps are formatted as RFC 3339.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["timestamp", "kubernetes.pod_name", "level"]
+					}
+					separator: {
+						description: "The string written between the fields of the header."
+						required:    false
+						type: string: default: " "
+					}
+				}
+			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -786,13 +918,19 @@ base: components: sinks: aws_s3: configuration: {
 		}
 	}
 	tags: {
-		description: "The tag-set for the object."
-		required:    false
+		description: """
+			The tag-set for the object.
+
+			Tag values are templates, so objects can be tagged based on the events they contain. Events
+			for which a tag value fails to render are dropped.
+			"""
+		required: false
 		type: object: {
 			examples: [{
 				Classification: "confidential"
 				PHI:            "True"
 				Project:        "Blue"
+				Team:           "{{ team }}"
 			}]
 			options: "*": {
 				description: "A single tag."
@@ -824,6 +962,19 @@ base: components: sinks: aws_s3: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -27,6 +27,17 @@ base: components: sinks: clickhouse: configuration: {
 			type: bool: {}
 		}
 	}
+	async_insert: {
+		description: """
+			Sets `async_insert`, letting ClickHouse buffer the inserts of small batches and write them
+			together.
+
+			Requests still wait for the inserts to be written, so that events are only acknowledged
+			once they are stored.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	auth: {
 		description: """
 			Configuration of the authentication strategy for HTTP requests.
@@ -106,6 +117,57 @@ base: components: sinks: clickhouse: configuration: {
 			}
 		}
 	}
+	columns: {
+		description: """
+			The columns of the table and the fields of the events they are taken from.
+
+			Required by the `native` format, in which the values of the columns are converted to their
+			types before being sent. Events with a value that can't be converted are dropped.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				field: {
+					description: """
+						The field of the events the values of the column are taken from.
+
+						Defaults to the name of the column.
+						"""
+					required: false
+					type: string: examples: ["http.status_code"]
+				}
+				name: {
+					description: "The name of the column."
+					required:    true
+					type: string: examples: ["timestamp", "status"]
+				}
+				nullable: {
+					description: """
+						Whether the column is `Nullable`.
+
+						Events missing the field, or whose field is null, insert `NULL` in nullable columns and the
+						default value of the type in the others.
+						"""
+					required: false
+					type: bool: default: false
+				}
+				type: {
+					description: "The type of a ClickHouse column."
+					required:    true
+					type: string: enum: {
+						bool:        "`Bool`."
+						date_time:   "`DateTime`, with a precision of seconds."
+						date_time64: "`DateTime64(3)`, with a precision of milliseconds."
+						float64:     "`Float64`."
+						int64:       "`Int64`."
+						string:      "`String`."
+						uint64:      "`UInt64`."
+					}
+				}
+			}
+		}
+	}
 	compression: {
 		description: """
 			Compression configuration.
@@ -149,6 +211,46 @@ base: components: sinks: clickhouse: configuration: {
 		description: "Transformations to prepare an event for serialization."
 		required:    false
 		type: object: options: {
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -159,6 +261,49 @@ base: components: sinks: clickhouse: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -174,6 +319,22 @@ base: components: sinks: clickhouse: configuration: {
 		required:    true
 		type: string: examples: ["http://localhost:8123"]
 	}
+	format: {
+		description: "The format the events are inserted in."
+		required:    false
+		type: string: {
+			default: "json_each_row"
+			enum: {
+				json_each_row: "Each event is inserted as a row of JSON, in the `JSONEachRow` format."
+				native: """
+					The events of a batch are inserted as a block of typed columns, in the `Native` format.
+
+					The columns are taken from the fields of the events as set in `columns`, which saves
+					ClickHouse from parsing JSON and lets it write the block as it is received.
+					"""
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -350,6 +511,19 @@ base: components: sinks: clickhouse: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
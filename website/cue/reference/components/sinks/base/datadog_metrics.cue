@@ -246,6 +246,28 @@ base: components: sinks: datadog_metrics: configuration: {
 			}
 		}
 	}
+	series_api_version: {
+		description: """
+			The version of the Datadog series API to send series metrics to.
+
+			The unit and origin of series metrics received from the Datadog Agent are only forwarded
+			with the `v2` API. The origin of sketches is forwarded with either version.
+			"""
+		required: false
+		type: string: {
+			default: "v1"
+			enum: {
+				v1: "The v1 series API, which takes series encoded as JSON."
+				v2: """
+					The v2 series API, which takes series encoded as Protocol Buffers, like the Datadog Agent
+					sends them.
+
+					Unlike the v1 series API, it accepts the unit and origin of metrics received from the
+					Datadog Agent.
+					"""
+			}
+		}
+	}
 	site: {
 		description: """
 			The Datadog [site][dd_site] to send observability data to.
@@ -281,6 +303,19 @@ base: components: sinks: datadog_metrics: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -0,0 +1,45 @@
+package metadata
+
+base: components: sinks: dual_write: configuration: {
+	acknowledgements: {
+		description: """
+			Controls how acknowledgements are handled for this sink.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: """
+				Whether or not end-to-end acknowledgements are enabled.
+
+				When enabled for a sink, any source connected to that sink, where the source supports
+				end-to-end acknowledgements as well, waits for events to be acknowledged by the sink
+				before acknowledging them at the source.
+
+				Enabling or disabling acknowledgements at the sink level takes precedence over any global
+				[`acknowledgements`][global_acks] configuration.
+
+				[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+				"""
+			required: false
+			type: bool: {}
+		}
+	mode: {
+		description: """
+			The sink whose outcome is used to acknowledge events.
+
+			Both sinks are written to in every mode. The outcome of the other sink is only reported in the
+			internal metrics.
+			"""
+		required: false
+		type: string: {
+			default: "primary"
+			enum: {
+				primary:   "Events are acknowledged once the primary sink has delivered them."
+				secondary: "Events are acknowledged once the secondary sink has delivered them."
+			}
+		}
+	}
+}
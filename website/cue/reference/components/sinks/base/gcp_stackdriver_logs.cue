@@ -82,7 +82,7 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 			Exactly one of `billing_account_id`, `folder_id`, `organization_id`, or `project_id` must be set.
 			"""
 		required: true
-		type: string: {}
+		type: string: syntax: "template"
 	}
 	credentials_path: {
 		description: """
@@ -104,6 +104,46 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 		description: "Transformations to prepare an event for serialization."
 		required:    false
 		type: object: options: {
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -114,6 +154,49 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -135,7 +218,27 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 			[folder_docs]: https://cloud.google.com/resource-manager/docs/creating-managing-folders
 			"""
 		required: true
-		type: string: {}
+		type: string: syntax: "template"
+	}
+	labels: {
+		description: """
+			Labels to attach to each log entry.
+
+			Label values are templates, rendered for each event. Labels whose value fails to render are
+			left out of the entry.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				app:  "{{ kubernetes.pod_labels.app }}"
+				team: "observability"
+			}]
+			options: "*": {
+				description: "A log entry label."
+				required:    true
+				type: string: syntax: "template"
+			}
+		}
 	}
 	log_id: {
 		description: """
@@ -155,7 +258,7 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 			Exactly one of `billing_account_id`, `folder_id`, `organization_id`, or `project_id` must be set.
 			"""
 		required: true
-		type: string: {}
+		type: string: syntax: "template"
 	}
 	project_id: {
 		description: """
@@ -168,7 +271,7 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 			[project_docs]: https://cloud.google.com/resource-manager/docs/creating-managing-projects
 			"""
 		required: true
-		type: string: {}
+		type: string: syntax: "template"
 	}
 	request: {
 		description: """
@@ -318,8 +421,11 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 			A monitored resource.
 
 			The monitored resource to associate the logs with.
+
+			When `resource_detection` is set, the type and labels configured here override the detected
+			ones.
 			"""
-		required: true
+		required: false
 		type: object: {
 			examples: [{
 				instanceId: "Twilight"
@@ -341,12 +447,29 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 
 						[gcp_resources]: https://cloud.google.com/monitoring/api/resources
 						"""
-					required: true
-					type: string: {}
+					required: false
+					type: string: {
+						examples: ["global", "{{ resource_type }}"]
+						syntax: "template"
+					}
 				}
 			}
 		}
 	}
+	resource_detection: {
+		description: "Detection of the monitored resource from the Compute Engine metadata server."
+		required:    false
+		type: string: enum: {
+			gce: "Associate logs with the Compute Engine instance Vector runs on, as a `gce_instance`."
+			gke: """
+				Associate logs with the Kubernetes container they originate from, as a `k8s_container`.
+
+				The cluster is detected from the metadata server of the GKE node Vector runs on, and the
+				Namespace, Pod, and container names are taken from the fields added by the
+				`kubernetes_logs` source.
+				"""
+		}
+	}
 	severity_key: {
 		description: """
 			The field of the log event from which to take the outgoing log’s `severity` field.
@@ -389,6 +512,19 @@ base: components: sinks: gcp_stackdriver_logs: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
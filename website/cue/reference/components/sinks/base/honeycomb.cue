@@ -64,6 +64,16 @@ base: components: sinks: honeycomb: configuration: {
 			}
 		}
 	}
+	create_dataset: {
+		description: """
+			Whether or not to create the dataset if it doesn't exist.
+
+			The dataset is looked up with the management API when the sink starts, which requires the
+			API key to have the permission to create datasets.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	dataset: {
 		description: "The dataset to which logs are sent."
 		required:    true
@@ -73,6 +83,46 @@ base: components: sinks: honeycomb: configuration: {
 		description: "Transformations to prepare an event for serialization."
 		required:    false
 		type: object: options: {
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -83,6 +133,49 @@ base: components: sinks: honeycomb: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -93,6 +186,44 @@ base: components: sinks: honeycomb: configuration: {
 			}
 		}
 	}
+	format: {
+		description: "The format of the batches sent to Honeycomb."
+		required:    false
+		type: string: {
+			default: "json"
+			enum: {
+				json: "Send batches as JSON."
+				msgpack: """
+					Send batches as [MessagePack][msgpack], which is more compact and cheaper to encode than
+					JSON.
+
+					[msgpack]: https://msgpack.org/
+					"""
+			}
+		}
+	}
+	markers: {
+		description: """
+			Configuration for sending events as Honeycomb markers.
+
+			Markers annotate the graphs of a dataset with points in time, such as deployments.
+			"""
+		required: false
+		type: object: options: field: {
+			description: """
+				The field that flags an event as a marker.
+
+				Events with this field are sent to the Markers API instead of the dataset. The value of the
+				field is either the type of the marker, or an object with optional `message`, `type`, and
+				`url` keys. The timestamp of the event is the time of the marker, and, if not set by the
+				object, its message is the message of the event.
+
+				Markers are not batched, and failures to send them are logged rather than retried.
+				"""
+			required: true
+			type: string: examples: ["honeycomb_marker"]
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -135,6 +135,23 @@ base: components: sinks: http: configuration: {
 			}
 		}
 	}
+	compression_dictionary: {
+		description: """
+			A trained zstd dictionary to compress payloads with.
+
+			The dictionary is only used once the server lists its ID in the `vector-zstd-dictionary`
+			response header, as the `http_server` source does for its `compression_dictionaries`.
+			Until then, payloads are compressed without it.
+
+			Requires the `zstd` compression.
+			"""
+		required: false
+		type: object: options: path: {
+			description: "The path of a trained zstd dictionary, such as one created with `zstd --train`."
+			required:    true
+			type: string: examples: ["/etc/vector/zstd.dict"]
+		}
+	}
 	encoding: {
 		description: "Configures how events are encoded into raw bytes."
 		required:    true
@@ -213,6 +230,13 @@ base: components: sinks: http: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					text_with_context: """
+						Plain text encoding, with a header line of selected fields before each message.
+
+						This encoding uses the `message` field of a log event, keeping the formatting of messages
+						spanning several lines, and is meant for people reading the output, such as files tailed
+						during an investigation.
+						"""
 				}
 			}
 			csv: {
@@ -233,6 +257,46 @@ base: components: sinks: http: configuration: {
 					type: array: items: type: string: {}
 				}
 			}
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -264,6 +328,70 @@ base: components: sinks: http: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			text_with_context: {
+				description:   "Options for the text with context serializer."
+				relevant_when: "codec = \"text_with_context\""
+				required:      true
+				type: object: options: {
+					header_fields: {
+						description: """
+							The fields written in the header line that precedes each message, in order.
+
+							Fields missing from the event are written as `-`, and timestamps are formatted as RFC 3339.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["timestamp", "kubernetes.pod_name", "level"]
+					}
+					separator: {
+						description: "The string written between the fields of the header."
+						required:    false
+						type: string: default: " "
+					}
+				}
+			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -273,6 +401,16 @@ base: components: sinks: http: configuration: {
 				}
 			}
 		}
+	encoding_concurrency: {
+		description: """
+			The maximum number of batches encoded at the same time.
+
+			Encoding and compressing batches is CPU-bound, so encoding several batches at the same
+			time spreads the work across threads. If unset, batches are encoded one at a time.
+			"""
+		required: false
+		type: uint: examples: [4]
+	}
 	}
 	framing: {
 		description: "Framing configuration."
@@ -314,6 +452,48 @@ base: components: sinks: http: configuration: {
 			type: string: {}
 		}
 	}
+	http_version: {
+		description: "The HTTP version used to send requests."
+		required:    false
+		type: string: {
+			default: "http1"
+			enum: {
+				auto: """
+					HTTP/2 when the server supports it, and HTTP/1.1 otherwise.
+
+					Both versions are offered to the server when connecting over TLS. Connections in plain
+					text or through a proxy use HTTP/1.1.
+					"""
+				http1: """
+					HTTP/1.1.
+
+					HTTP/2 is only used when the `tls.alpn_protocols` option negotiates it.
+					"""
+				http2: """
+					HTTP/2.
+
+					Connections in plain text use HTTP/2 right away ("prior knowledge"), which the server must
+					support. Requests to a host are multiplexed over a single connection, up to the number of
+					concurrent streams the server allows.
+					"""
+			}
+		}
+	}
+	idempotency_key: {
+		description: """
+			Whether to send an `Idempotency-Key` header with each request.
+
+			The key is derived from the [dedupe IDs][dedupe_id] of the events of the request, so that a
+			server supporting [idempotency keys][idempotency_key] can discard a request it already
+			processed, such as a retry of a request whose response was lost. The header is only sent if
+			every event of the request has a dedupe ID.
+
+			[dedupe_id]: https://vector.dev/docs/reference/configuration/sources/#dedupe_id
+			[idempotency_key]: https://datatracker.ietf.org/doc/draft-ietf-httpapi-idempotency-key-header/
+			"""
+		required: false
+		type: bool: default: false
+	}
 	method: {
 		description: """
 			HTTP method.
@@ -542,6 +722,19 @@ base: components: sinks: http: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
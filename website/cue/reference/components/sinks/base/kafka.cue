@@ -82,6 +82,20 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	dedupe_id_header: {
+		description: """
+			The name of the header the [dedupe ID][dedupe_id] of the event is propagated in.
+
+			Kafka doesn't deduplicate records by header, and the transactions of `exactly_once` don't
+			cover events read again by the source after a restart, so consumers must discard the records
+			whose ID they already processed. If omitted, or if the event has no dedupe ID, no header is
+			written.
+
+			[dedupe_id]: https://vector.dev/docs/reference/configuration/sources/#dedupe_id
+			"""
+		required: false
+		type: string: examples: ["vector-dedupe-id"]
+	}
 	encoding: {
 		description: "Configures how events are encoded into raw bytes."
 		required:    true
@@ -160,6 +174,13 @@ base: components: sinks: kafka: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					text_with_context: """
+						Plain text encoding, with a header line of selected fields before each message.
+
+						This encoding uses the `message` field of a log event, keeping the formatting of messages
+						spanning several lines, and is meant for people reading the output, such as files tailed
+						during an investigation.
+						"""
 				}
 			}
 			csv: {
@@ -180,6 +201,46 @@ base: components: sinks: kafka: configuration: {
 					type: array: items: type: string: {}
 				}
 			}
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -211,6 +272,70 @@ base: components: sinks: kafka: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			text_with_context: {
+				description:   "Options for the text with context serializer."
+				relevant_when: "codec = \"text_with_context\""
+				required:      true
+				type: object: options: {
+					header_fields: {
+						description: """
+							The fields written in the header line that precedes each message, in order.
+
+							Fields missing from the event are written as `-`, and timestamps are formatted as RFC 3339.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["timestamp", "kubernetes.pod_name", "level"]
+					}
+					separator: {
+						description: "The string written between the fields of the header."
+						required:    false
+						type: string: default: " "
+					}
+				}
+			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -221,6 +346,42 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	exactly_once: {
+		description: """
+			Exactly-once delivery configuration.
+
+			Events are produced in batches, each within a Kafka transaction that is committed once the
+			brokers acknowledged all of its records. Consumers reading with `isolation.level` set to
+			`read_committed` never see the records of a transaction that was aborted and retried.
+			"""
+		required: false
+		type: object: options: {
+			transaction_timeout_ms: {
+				description: """
+					The time, in milliseconds, after which the brokers abort an open transaction.
+
+					librdkafka lowers the message timeout to this value if it is higher.
+					"""
+				required: false
+				type: uint: {
+					default: 60000
+					examples: [60000]
+					unit: "milliseconds"
+				}
+			}
+			transactional_id: {
+				description: """
+					The transactional ID of the producer.
+
+					The ID must be unique to this sink across all Vector instances producing to the cluster, and
+					stay the same across restarts: a producer starting with the ID of another fences it off, and
+					aborts its open transaction.
+					"""
+				required: true
+				type: string: examples: ["vector-${HOSTNAME}-kafka"]
+			}
+		}
+	}
 	headers_key: {
 		description: """
 			The log field name to use for the Kafka headers.
@@ -274,6 +435,24 @@ base: components: sinks: kafka: configuration: {
 			unit: "milliseconds"
 		}
 	}
+	profile: {
+		description: "The broker implementation that the producer is tuned for."
+		required:    false
+		type: string: {
+			default: "kafka"
+			enum: {
+				kafka: "Apache Kafka, using librdkafka's producer defaults."
+				redpanda: """
+					[Redpanda][redpanda].
+
+					Idempotent production is enabled by default, which requires `acks=all`. Records rejected by
+					Redpanda's server-side schema ID validation are reported as such.
+
+					[redpanda]: https://redpanda.com/
+					"""
+			}
+		}
+	}
 	sasl: {
 		description: "Configuration for SASL authentication when interacting with Kafka."
 		required:    false
@@ -312,6 +491,21 @@ base: components: sinks: kafka: configuration: {
 			}
 		}
 	}
+	schema_registry: {
+		description: "Schema registry wire format configuration."
+		required:    false
+		type: object: options: schema_id: {
+			description: """
+				The ID of the registered schema that encoded payloads conform to.
+
+				Each payload is prefixed with the schema registry wire format header: a zero magic byte
+				followed by the schema ID as a 4-byte big-endian integer. The encoded payload itself must
+				match the registered schema.
+				"""
+			required: true
+			type: uint: examples: [1]
+		}
+	}
 	socket_timeout_ms: {
 		description: "Default timeout, in milliseconds, for network requests."
 		required:    false
@@ -344,6 +538,19 @@ base: components: sinks: kafka: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
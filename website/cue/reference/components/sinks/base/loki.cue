@@ -27,6 +27,68 @@ base: components: sinks: loki: configuration: {
 			type: bool: {}
 		}
 	}
+	attachment: {
+		description: """
+			Attachment configuration.
+
+			Events, or some of their fields, are attached to their entries as a JSON object in
+			[structured metadata][structured_metadata], so that the original event can be recovered
+			whatever the encoding of the line and the fields removed from it.
+
+			The attachment is not a file or a separate part of the request: it is the value of a single
+			structured metadata key of the entry, `original` by default. Like the rest of the structured
+			metadata, it is not indexed, and queries can read it back like a label, for example with
+			`| line_format "{{.original}}"` for the default key.
+
+			Requires Loki 2.9.0 or newer, with structured metadata allowed.
+
+			[structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: "Whether or not to attach events to their entries."
+				required:    false
+				type: bool: default: false
+			}
+			fields: {
+				description: """
+					The fields attached, as an object keyed by their path.
+
+					Missing fields are left out of the object, and events without any of them get no
+					attachment. If empty, the whole event is attached, as received by the sink.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["request", "user.id"]
+				}
+			}
+			key: {
+				description: """
+					The structured metadata key the attachment is sent under.
+
+					The attachment is the value of this key, next to the other structured metadata of the
+					entry.
+					"""
+				required: false
+				type: string: default: "original"
+			}
+			max_bytes: {
+				description: """
+					The maximum size, in bytes, of an attachment.
+
+					Larger attachments are not sent, as truncating them would leave invalid JSON, and as Loki
+					rejects entries whose structured metadata exceeds its `max_structured_metadata_size` limit.
+					"""
+				required: false
+				type: uint: {
+					default: 8192
+					unit:    "bytes"
+				}
+			}
+		}
+	}
 	auth: {
 		description: """
 			Configuration of the authentication strategy for HTTP requests.
@@ -110,7 +172,13 @@ base: components: sinks: loki: configuration: {
 		}
 	}
 	compression: {
-		description: "Compression configuration."
+		description: """
+			Compression configuration.
+
+			`snappy` sends push requests as Protocol Buffers compressed with Snappy, which is what Loki
+			expects for native pushes. The other algorithms send push requests as JSON, compressed with the
+			algorithm and with the matching `Content-Encoding` header.
+			"""
 		required:    false
 		type: string: {
 			default: "snappy"
@@ -139,6 +207,20 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	dead_letter: {
+		description: """
+			The ID of a `dead_letter` source the events Loki rejects are sent to.
+
+			Events in a push request Loki rejects without it being retried, such as for an entry too
+			far behind or a line too long, are sent to the source as they were received by the sink,
+			so that a sink taking the source as input can store them for later replay. They are still
+			reported as rejected to the sources acknowledging them. Loki may have accepted some entries
+			of a rejected request, which are sent to the source as well. If unset, rejected events are
+			dropped.
+			"""
+		required: false
+		type: string: examples: ["loki_rejected"]
+	}
 	encoding: {
 		description: "Configures how events are encoded into raw bytes."
 		required:    true
@@ -217,6 +299,13 @@ base: components: sinks: loki: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					text_with_context: """
+						Plain text encoding, with a header line of selected fields before each message.
+
+						This encoding uses the `message` field of a log event, keeping the formatting of messages
+						spanning several lines, and is meant for people reading the output, such as files tailed
+						during an investigation.
+						"""
 				}
 			}
 			csv: {
@@ -237,6 +326,46 @@ base: components: sinks: loki: configuration: {
 					type: array: items: type: string: {}
 				}
 			}
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -268,6 +397,70 @@ base: components: sinks: loki: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			text_with_context: {
+				description:   "Options for the text with context serializer."
+				relevant_when: "codec = \"text_with_context\""
+				required:      true
+				type: object: options: {
+					header_fields: {
+						description: """
+							The fields written in the header line that precedes each message, in order.
+
+							Fields missing from the event are written as `-`, and timestamps are formatted as RFC 3339.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["timestamp", "kubernetes.pod_name", "level"]
+					}
+					separator: {
+						description: "The string written between the fields of the header."
+						required:    false
+						type: string: default: " "
+					}
+				}
+			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -277,6 +470,17 @@ base: components: sinks: loki: configuration: {
 				}
 			}
 		}
+	encoding_concurrency: {
+		description: """
+			The maximum number of batches encoded at the same time.
+
+			Encoding and compressing batches is CPU-bound, so encoding several batches at the same
+			time spreads the work across threads. If unset, batches are encoded one at a time, unless
+			`out_of_order_action` is `accept`, in which case up to 50 are encoded at the same time.
+			"""
+		required: false
+		type: uint: examples: [4]
+	}
 	}
 	endpoint: {
 		description: """
@@ -287,6 +491,71 @@ base: components: sinks: loki: configuration: {
 		required: true
 		type: string: examples: ["http://localhost:3100"]
 	}
+	endpoint_format: {
+		description: "The format of the push requests sent to the HTTP API of Loki."
+		required:    false
+		type: string: {
+			default: "push"
+			enum: {
+				otlp: """
+					Send the entries as OTLP log records to the OTLP endpoint of Loki.
+
+					The entries of each stream are sent as one resource, with the labels of the stream as its
+					attributes, and their structured metadata as the attributes of the log records. Loki only
+					indexes some resource attributes as labels, such as `service.name`, unless its `otlp_config`
+					lists others, and label names can contain dots to match them. If `path` is not set,
+					requests are sent to `/otlp/v1/logs`. Only the `http` protocol is supported, and `snappy`
+					compression is not.
+					"""
+				push: """
+					Send push requests to the Loki push API, as JSON or, with `snappy` compression, as
+					Protocol Buffers.
+					"""
+			}
+		}
+	}
+	label_normalization: {
+		description: """
+			Label normalization configuration.
+
+			Loki rejects the entire push request if any stream in it has an invalid label. These options
+			rewrite labels before they are sent so that a single malformed label does not cause the whole
+			batch to be rejected.
+			"""
+		required: false
+		type: object: options: {
+			drop_empty_values: {
+				description: "Whether or not to drop labels whose value is empty."
+				required:    false
+				type: bool: default: false
+			}
+			max_value_length: {
+				description: """
+					The maximum length, in bytes, of a label value.
+
+					Values longer than this are truncated on a character boundary. If unset, values are not
+					truncated. Can't be set along with `max_label_value_length`, which truncates values the
+					same way.
+					"""
+				required: false
+				type: uint: {
+					examples: [1024]
+					unit: "bytes"
+				}
+			}
+			replace_invalid_characters: {
+				description: """
+					Whether or not to replace characters that are invalid in a label name.
+
+					Label names must match `[a-zA-Z_][a-zA-Z0-9_]*`. When enabled, every invalid character is
+					replaced with `_`, and names starting with a digit are prefixed with `_`. If several labels
+					are replaced with the same name, only the first of them by name is kept.
+					"""
+				required: false
+				type: bool: default: false
+			}
+		}
+	}
 	labels: {
 		description: """
 			A set of labels that are attached to each batch of events.
@@ -317,6 +586,102 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	max_label_cardinality: {
+		description: """
+			Label cardinality guard configuration.
+
+			The distinct values of each label name are tracked over a sliding window. Once a label name has
+			`limit` distinct values within the window, events carrying a new value for it are handled
+			according to `action`, so that a label with unbounded values does not create an unbounded number
+			of streams.
+			"""
+		required: false
+		type: object: options: {
+			action: {
+				description: "The action to take on an event carrying a label value over the cardinality limit."
+				required:    false
+				type: string: {
+					default: "drop_label"
+					enum: {
+						drop_event: "Drop the event."
+						drop_label: """
+							Drop the label.
+
+							Its value stays in the log line, unless `remove_label_fields` is set.
+							"""
+						structured_metadata: """
+							Move the label to the structured metadata of the entry.
+
+							Requires Loki 2.9.0 or newer, with structured metadata allowed.
+							"""
+					}
+				}
+			}
+			limit: {
+				description: """
+					The maximum number of distinct values of a label name within the window.
+
+					If unset, the cardinality of labels is not limited.
+					"""
+				required: false
+				type: uint: examples: [1000]
+			}
+			window_secs: {
+				description: """
+					The length of the sliding window, in seconds.
+
+					A value stops counting towards the limit once it has not been seen for that long.
+					"""
+				required: false
+				type: uint: {
+					default: 3600
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	max_label_name_length: {
+		description: """
+			The maximum length, in bytes, of a label name.
+
+			The labels with a longer name are dropped, since Loki rejects the whole push request if a
+			label name is longer than its `max_label_name_length` limit. If unset, names are not limited.
+			"""
+		required: false
+		type: uint: {
+			examples: [1024]
+			unit: "bytes"
+		}
+	}
+	max_label_value_length: {
+		description: """
+			The maximum length, in bytes, of a label value.
+
+			Longer values are truncated on a character boundary, since Loki rejects the whole push
+			request if a label value is longer than its `max_label_value_length` limit. If unset, values
+			are not limited.
+
+			Values are truncated the same way as with `label_normalization.max_value_length`, but are
+			also counted as limited labels. Only one of the two can be set.
+			"""
+		required: false
+		type: uint: {
+			examples: [2048]
+			unit: "bytes"
+		}
+	}
+	max_labels_per_stream: {
+		description: """
+			The maximum number of labels of a stream.
+
+			The labels past the limit, in the order of their names, are dropped, since Loki rejects the
+			whole push request if a stream has more labels than its `max_label_names_per_series` limit.
+			The `__shard__` label of sharded streams is added on top of them. If unset, the number of
+			labels is not limited.
+			"""
+		required: false
+		type: uint: examples: [15]
+	}
 	out_of_order_action: {
 		description: """
 			Out-of-order event behavior.
@@ -327,8 +692,8 @@ base: components: sinks: loki: configuration: {
 			was not supported and would result in an error during the push request.
 
 			If you're using Loki 2.4.0 or newer, `Accept` is the preferred action, which lets Loki handle
-			any necessary sorting/reordering. If you're using an earlier version, then you must use `Drop`
-			or `RewriteTimestamp` depending on which option makes the most sense for your use case.
+			any necessary sorting/reordering. If you're using an earlier version, then you must use `Drop`,
+			`RewriteTimestamp`, or `Reorder` depending on which option makes the most sense for your use case.
 			"""
 		required: false
 		type: string: {
@@ -341,7 +706,14 @@ base: components: sinks: loki: configuration: {
 
 					Requires Loki 2.4.0 or newer.
 					"""
-				drop:              "Drop the event."
+				drop: "Drop the event."
+				reorder: """
+					Reorder the events of each stream before sending them.
+
+					The events of a stream, identified by its tenant and labels, are held for
+					`reorder_window_secs` and sent sorted by timestamp. An event arriving after later events of
+					its stream were sent has its timestamp rewritten to the timestamp of the latest of them.
+					"""
 				rewrite_timestamp: "Rewrite the timestamp of the event to the timestamp of the latest event seen by the sink."
 			}
 		}
@@ -351,8 +723,28 @@ base: components: sinks: loki: configuration: {
 		required:    false
 		type: string: default: "/loki/api/v1/push"
 	}
+	protocol: {
+		description: "The protocol push requests are sent to Loki with."
+		required:    false
+		type: string: {
+			default: "http"
+			enum: {
+				grpc: """
+					Send push requests to the `Push` method of the `logproto.Pusher` gRPC service.
+
+					Push requests are sent as Protocol Buffers, whatever the `compression`, and the tenant ID as
+					the `X-Scope-OrgID` metadata. TLS is used unless the scheme of the endpoint is `http`. The
+					healthcheck is skipped, as it uses the HTTP API.
+					"""
+				http: "Send push requests to the HTTP push API, at `path`."
+			}
+		}
+	}
 	remove_label_fields: {
-		description: "Whether or not to delete fields from the event when they are used as labels."
+		description: """
+			Whether or not to delete fields from the event when they are used as labels or structured
+			metadata.
+			"""
 		required:    false
 		type: bool: default: false
 	}
@@ -365,6 +757,33 @@ base: components: sinks: loki: configuration: {
 		required: false
 		type: bool: default: true
 	}
+	reorder_max_bytes: {
+		description: """
+			The maximum size of the events held to be reordered, in bytes.
+
+			Only used when `out_of_order_action` is `reorder`. Once the events held take more than this,
+			the streams held the longest are sent before their window ends.
+			"""
+		required: false
+		type: uint: {
+			default: 10485760
+			unit:    "bytes"
+		}
+	}
+	reorder_window_secs: {
+		description: """
+			How long the events of a stream are held to be reordered, in seconds.
+
+			Only used when `out_of_order_action` is `reorder`. A longer window tolerates events arriving
+			further out of order, at the cost of delaying every event by the window and of buffering its
+			events in memory.
+			"""
+		required: false
+		type: uint: {
+			default: 5
+			unit:    "seconds"
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -508,6 +927,78 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	stream_sharding: {
+		description: """
+			Stream sharding configuration.
+
+			When Loki rejects a push request because a stream exceeds its per-stream rate limit, the
+			entries of the stream are spread across several streams by adding a `__shard__` label to them,
+			so that its throughput isn't limited to that of a single stream.
+			"""
+		required: false
+		type: object: options: {
+			duration_secs: {
+				description: "How long a stream stays sharded after Loki last rejected it, in seconds."
+				required:    false
+				type: uint: {
+					default: 300
+					unit:    "seconds"
+				}
+			}
+			enabled: {
+				description: "Whether or not to shard the streams exceeding the per-stream rate limit."
+				required:    false
+				type: bool: default: false
+			}
+			shards: {
+				description: "The number of streams the entries of a sharded stream are spread across."
+				required:    false
+				type: uint: default: 4
+			}
+		}
+	}
+	stream_metrics_labels: {
+		description: """
+			The names of the labels the per-stream delivery metrics are keyed by.
+
+			When set, the entries sent, dropped as out of order, and rejected by Loki are counted for
+			each combination of the values of these labels, tagged with a `stream` selector such as
+			`{app="api"}`. Each combination creates metric series, so only labels with a bounded set
+			of values should be used, and at most five of them. If empty, the per-stream metrics are not
+			emitted.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["app", "namespace"]
+		}
+	}
+	structured_metadata: {
+		description: """
+			A set of [structured metadata][structured_metadata] that is attached to each entry.
+
+			Both keys and values are templateable. Unlike labels, structured metadata is not indexed,
+			so fields with many distinct values, such as request IDs, can be attached to entries without
+			creating a stream for each value. Entries are sent without the structured metadata whose
+			value is empty.
+
+			Requires Loki 2.9.0 or newer, with structured metadata allowed.
+
+			[structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				request_id: "{{ request_id }}"
+				user_id:    "{{ user.id }}"
+			}]
+			options: "*": {
+				description: "A structured metadata value of Loki entries."
+				required:    true
+				type: string: syntax: "template"
+			}
+		}
+	}
 	tenant_id: {
 		description: """
 			The [tenant ID][tenant_id] to specify in requests to Loki.
@@ -522,6 +1013,82 @@ base: components: sinks: loki: configuration: {
 			syntax: "template"
 		}
 	}
+	tenant_labels: {
+		description: """
+			The label policies of tenants, by tenant ID.
+
+			Events routed to a tenant listed here can only carry the labels its policy allows, so that a
+			template meant for one tenant does not add labels to the streams of another.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The label policy of a tenant."
+			required:    true
+			type: object: options: {
+				allowed_labels: {
+					description: """
+						The labels that events routed to the tenant can carry.
+
+						Names ending with `*` allow every label starting with the rest of the name. Labels are
+						matched once normalized. If empty, every label is allowed.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["app", "pod_labels_*"]
+					}
+				}
+				disallowed_labels: {
+					description: "What happens to the labels of events routed to a tenant that its policy does not allow."
+					required:    false
+					type: string: {
+						default: "drop"
+						enum: {
+							drop: "Drop the label."
+							rename: """
+								Send the label with `label_prefix` prepended to its name.
+
+								This keeps the label apart from those allowed, which a query can then exclude.
+								"""
+						}
+					}
+				}
+				label_prefix: {
+					description: """
+						The prefix prepended to the names of the labels that are not allowed, when they are
+						renamed.
+						"""
+					required: false
+					type: string: examples: ["team_a_"]
+				}
+			}
+		}
+	}
+	tenant_rate_limit: {
+		description: """
+			Per-tenant rate limiting configuration.
+
+			Each tenant gets its own token bucket. Its rate is halved each time Loki rejects a push request
+			of the tenant with `429 Too Many Requests`, and recovers as its requests succeed, so that a
+			rate-limited tenant does not slow down the requests of the other tenants.
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: "Whether or not to rate limit the push requests of each tenant."
+				required:    false
+				type: bool: default: false
+			}
+			max_requests_per_sec: {
+				description: "The highest rate of push requests of a tenant, in requests per second."
+				required:    false
+				type: uint: {
+					default: 10
+					unit:    "requests"
+				}
+			}
+		}
+	}
 	tls: {
 		description: "TLS configuration."
 		required:    false
@@ -545,6 +1112,19 @@ base: components: sinks: loki: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -607,4 +1187,46 @@ base: components: sinks: loki: configuration: {
 			}
 		}
 	}
+	trace_correlation: {
+		description: """
+			Trace correlation configuration.
+
+			The trace and span IDs of events are attached to their entries as [structured
+			metadata][structured_metadata], which lets Grafana link logs to their traces without indexing
+			the IDs as labels.
+
+			Requires Loki 2.9.0 or newer, with structured metadata allowed.
+
+			[structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+			"""
+		required: false
+		type: object: options: {
+			enabled: {
+				description: "Whether or not to attach the trace context of events to their entries."
+				required:    false
+				type: bool: default: false
+			}
+			span_id_field: {
+				description: "The field holding the span ID."
+				required:    false
+				type: string: default: "span_id"
+			}
+			trace_id_field: {
+				description: "The field holding the trace ID."
+				required:    false
+				type: string: default: "trace_id"
+			}
+			traceparent_field: {
+				description: """
+					The field holding a [W3C `traceparent` header][traceparent].
+
+					The trace and span IDs are taken from it for events without a trace ID field.
+
+					[traceparent]: https://www.w3.org/TR/trace-context/#traceparent-header
+					"""
+				required: false
+				type: string: default: "traceparent"
+			}
+		}
+	}
 }
@@ -258,6 +258,43 @@ base: components: sinks: prometheus_remote_write: configuration: {
 		required: true
 		type: string: examples: ["https://localhost:8087/api/v1/write"]
 	}
+	native_histograms: {
+		description: """
+			Whether to send [distribution][dist_metric_docs] metrics as native histograms.
+
+			Native histograms have exponential buckets, which grow by a factor of about 1.09, rather
+			than the ones set in `buckets`. This requires the 2.0 protocol, and a receiver supporting
+			native histograms. When the receiver falls back to the 1.0 protocol, distributions are
+			aggregated into the histograms of `buckets` instead.
+
+			[dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+			"""
+		required: false
+		type: bool: default: false
+	}
+	protocol_version: {
+		description: "The version of the remote write protocol."
+		required:    false
+		type: string: {
+			default: "1.0"
+			enum: {
+				"1.0": """
+					[Remote Write 1.0][rw1], supported by all receivers.
+
+					[rw1]: https://prometheus.io/docs/specs/remote_write_spec/
+					"""
+				"2.0": """
+					[Remote Write 2.0][rw2], which sends the metadata of each series along with it and
+					supports native histograms.
+
+					Receivers not supporting it answer with `415 Unsupported Media Type`, in which case the
+					request is sent again with the 1.0 protocol, which is used for the subsequent requests.
+
+					[rw2]: https://prometheus.io/docs/specs/remote_write_spec_2_0/
+					"""
+			}
+		}
+	}
 	quantiles: {
 		description: """
 			Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
@@ -450,6 +487,19 @@ base: components: sinks: prometheus_remote_write: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -5,6 +5,20 @@ base: components: sinks: splunk_hec_logs: configuration: {
 		description: "Splunk HEC acknowledgement configuration."
 		required:    false
 		type: object: options: {
+			channels: {
+				description: """
+					The number of [channels][splunk_channel_docs] events are sent on.
+
+					The acknowledgements of each channel are queried independently, and `max_pending_acks` is
+					split evenly between the channels. Events are sent on the channels that have room for
+					additional pending acknowledgements, so a channel whose acknowledgements are slow to come
+					doesn't hold back the others.
+
+					[splunk_channel_docs]: https://docs.splunk.com/Documentation/Splunk/8.2.3/Data/AboutHECIDXAck#About_channels_and_sending_data
+					"""
+				required: false
+				type: uint: default: 1
+			}
 			enabled: {
 				description: """
 					Whether or not end-to-end acknowledgements are enabled.
@@ -47,6 +61,19 @@ base: components: sinks: splunk_hec_logs: configuration: {
 					unit:    "seconds"
 				}
 			}
+			query_interval_jitter_ms: {
+				description: """
+					The maximum amount of time added at random to each `query_interval`.
+
+					This spreads the queries of the acknowledgement channels, and of Vector instances sending
+					to the same Splunk HEC collector, over time.
+					"""
+				required: false
+				type: uint: {
+					default: 0
+					unit:    "milliseconds"
+				}
+			}
 			retry_limit: {
 				description: "The maximum number of times an acknowledgement ID is queried for its status."
 				required:    false
@@ -129,6 +156,21 @@ base: components: sinks: splunk_hec_logs: configuration: {
 			}
 		}
 	}
+	dedupe_id_field: {
+		description: """
+			The name of the [indexed field][splunk_field_index_docs] to send the [dedupe ID][dedupe_id]
+			of events in.
+
+			Splunk doesn't deduplicate events on it, so the field is only propagated, for searches to
+			discard the copies of an event sent more than once, such as after Vector restarted. Events
+			without a dedupe ID are sent without it.
+
+			[splunk_field_index_docs]: https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/IFXandHEC
+			[dedupe_id]: https://vector.dev/docs/reference/configuration/sources/#dedupe_id
+			"""
+		required: false
+		type: string: examples: ["vector_dedupe_id"]
+	}
 	default_token: {
 		description: """
 			Default Splunk HEC token.
@@ -216,6 +258,13 @@ base: components: sinks: splunk_hec_logs: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					text_with_context: """
+						Plain text encoding, with a header line of selected fields before each message.
+
+						This encoding uses the `message` field of a log event, keeping the formatting of messages
+						spanning several lines, and is meant for people reading the output, such as files tailed
+						during an investigation.
+						"""
 				}
 			}
 			csv: {
@@ -236,6 +285,46 @@ base: components: sinks: splunk_hec_logs: configuration: {
 					type: array: items: type: string: {}
 				}
 			}
+			ecs: {
+				description: """
+					Elastic Common Schema (ECS) mapping.
+
+					Moves the fields that Vector sources commonly set to the [ECS][ecs] fields holding the same
+					data, so that events are indexed like those shipped by Elastic Agent. Events whose root is not
+					an object have it moved to `message`.
+
+					[ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+					"""
+				required: false
+				type: object: options: {
+					enabled: {
+						description: "Whether or not to move fields to their ECS name."
+						required:    false
+						type: bool: default: false
+					}
+					mapping: {
+						description: """
+							Overrides of the mapping, from the path of a field to the path of the ECS field it is moved
+							to.
+
+							Paths starting with `%` refer to the metadata of the event. Mapping a field to an empty path
+							leaves it where it is.
+							"""
+						required: false
+						type: object: {
+							examples: [{
+								app:  "service.name"
+								file: ""
+							}]
+							options: "*": {
+								description: "The path of an ECS field."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+				}
+			}
 			except_fields: {
 				description: "List of fields that are excluded from the encoded event."
 				required:    false
@@ -267,6 +356,70 @@ base: components: sinks: splunk_hec_logs: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			text_with_context: {
+				description:   "Options for the text with context serializer."
+				relevant_when: "codec = \"text_with_context\""
+				required:      true
+				type: object: options: {
+					header_fields: {
+						description: """
+							The fields written in the header line that precedes each message, in order.
+
+							Fields missing from the event are written as `-`, and timestamps are formatted as RFC 3339.
+							"""
+						required: true
+						type: array: items: type: string: examples: ["timestamp", "kubernetes.pod_name", "level"]
+					}
+					separator: {
+						description: "The string written between the fields of the header."
+						required:    false
+						type: string: default: " "
+					}
+				}
+			}
+			timestamp: {
+				description: """
+					Timestamp encoding.
+
+					Converts the timestamp fields of events to the representation, and the unit, expected by the
+					destination, such as the nanoseconds of Loki or the `DateTime64` columns of ClickHouse.
+					"""
+				required: false
+				type: object: options: {
+					format: {
+						description: """
+							The representation of timestamps.
+
+							Either `unix`, for the number of units elapsed since the Unix epoch, `rfc3339`, or a
+							[`strftime` format][strftime].
+
+							[strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+							"""
+						required: false
+						type: string: {
+							default: "unix"
+							examples: ["unix", "rfc3339", "%Y-%m-%d %H:%M:%S%.3f"]
+						}
+					}
+					unit: {
+						description: """
+							The unit of Unix timestamps, and the precision of the others.
+
+							Timestamps are truncated to this unit.
+							"""
+						required: false
+						type: string: {
+							default: "s"
+							enum: {
+								ms: "Milliseconds."
+								ns: "Nanoseconds."
+								s:  "Seconds."
+								us: "Microseconds."
+							}
+						}
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -558,6 +711,19 @@ base: components: sinks: splunk_hec_logs: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -5,6 +5,20 @@ base: components: sinks: splunk_hec_metrics: configuration: {
 		description: "Splunk HEC acknowledgement configuration."
 		required:    false
 		type: object: options: {
+			channels: {
+				description: """
+					The number of [channels][splunk_channel_docs] events are sent on.
+
+					The acknowledgements of each channel are queried independently, and `max_pending_acks` is
+					split evenly between the channels. Events are sent on the channels that have room for
+					additional pending acknowledgements, so a channel whose acknowledgements are slow to come
+					doesn't hold back the others.
+
+					[splunk_channel_docs]: https://docs.splunk.com/Documentation/Splunk/8.2.3/Data/AboutHECIDXAck#About_channels_and_sending_data
+					"""
+				required: false
+				type: uint: default: 1
+			}
 			enabled: {
 				description: """
 					Whether or not end-to-end acknowledgements are enabled.
@@ -47,6 +61,19 @@ base: components: sinks: splunk_hec_metrics: configuration: {
 					unit:    "seconds"
 				}
 			}
+			query_interval_jitter_ms: {
+				description: """
+					The maximum amount of time added at random to each `query_interval`.
+
+					This spreads the queries of the acknowledgement channels, and of Vector instances sending
+					to the same Splunk HEC collector, over time.
+					"""
+				required: false
+				type: uint: {
+					default: 0
+					unit:    "milliseconds"
+				}
+			}
 			retry_limit: {
 				description: "The maximum number of times an acknowledgement ID is queried for its status."
 				required:    false
@@ -361,6 +388,19 @@ base: components: sinks: splunk_hec_metrics: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -81,6 +81,21 @@ base: components: sinks: vector: configuration: {
 		required: false
 		type: bool: default: false
 	}
+	compression_dictionary: {
+		description: """
+			A trained zstd dictionary to compress the events of requests with.
+
+			Dictionaries greatly reduce the size of small batches of similar events. The dictionary is
+			only used once the downstream Vector instance lists it among its
+			`compression_dictionaries`, and events are sent without it until then.
+			"""
+		required: false
+		type: object: options: path: {
+			description: "The path of a trained zstd dictionary, such as one created with `zstd --train`."
+			required:    true
+			type: string: examples: ["/etc/vector/zstd.dict"]
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
@@ -247,6 +262,19 @@ base: components: sinks: vector: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
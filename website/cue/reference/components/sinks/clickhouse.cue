@@ -80,4 +80,17 @@ components: sinks: clickhouse: {
 		metrics: null
 		traces:  false
 	}
+
+	how_it_works: {
+		native_format: {
+			title: "Native format"
+			body: """
+				With `format` set to `native`, each batch is sent as a single block of columns in
+				ClickHouse's `Native` format, over the same HTTP interface. The columns are listed in
+				`columns` with their types, and each event's fields are converted to those types
+				before the batch is sent, so ClickHouse doesn't have to parse JSON on insert. Columns
+				missing from `columns` take their default values from the table.
+				"""
+		}
+	}
 }
@@ -71,4 +71,18 @@ components: sinks: datadog_metrics: {
 		}
 		traces: false
 	}
+
+	how_it_works: {
+		metric_metadata: {
+			title: "Metric metadata"
+			body: """
+				Metrics received from the Datadog Agent by the `datadog_agent` source keep the unit and the
+				origin (product, category and service) the Agent sent them with. This sink forwards the origin
+				of sketches, and, when `series_api_version` is set to `v2`, the unit and origin of series, so
+				that the metrics appear in Datadog as if the Agent had sent them directly.
+
+				This metadata is not kept through disk buffers, and is not available to transforms.
+				"""
+		}
+	}
 }
@@ -0,0 +1,104 @@
+package metadata
+
+components: sinks: dual_write: {
+	title: "Dual Write"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: enabled:    false
+			request: enabled:     false
+			tls: enabled:         false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Both sinks receive every batch of events, so the slowest of them holds back the input of
+				the other.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.sinks.dual_write.configuration & {
+		primary: {
+			description: """
+				The sink being migrated from.
+
+				Its options are those of a sink of the same `type`, without `inputs`.
+				"""
+			required: true
+			type: object: examples: [{type: "elasticsearch", endpoints: ["http://localhost:9200"]}]
+		}
+		secondary: {
+			description: """
+				The sink being migrated to.
+
+				Its options are those of a sink of the same `type`, without `inputs`.
+				"""
+			required: true
+			type: object: examples: [{type: "loki", endpoint: "http://localhost:3100"}]
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		reconciliation: {
+			title: "Reconciliation"
+			body: """
+				Every batch of events is written to both sinks. Once both have finalized it, its outcome
+				and latency on each sink are reported in the `dual_write_events_total`,
+				`dual_write_latency_seconds` and `dual_write_latency_delta_seconds` internal metrics.
+				Batches delivered by one sink but not by the other are counted in
+				`dual_write_divergent_events_total`.
+
+				Only the events accepted by both sinks are received: the input of a `dual_write` sink is
+				the intersection of the inputs of its sinks.
+				"""
+		}
+		switching: {
+			title: "Switching sinks"
+			body: """
+				Events are acknowledged with the outcome of the sink set by `mode`, the primary one by
+				default, so that the other sink can be validated without affecting delivery. Once it is,
+				the mode can be flipped without restarting the sink with the `setDualWriteMode` API
+				mutation, enabled by `api.mutations`, until the configuration is reloaded. Batches already sent keep being
+				acknowledged with the mode they were sent with.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		dual_write_divergent_events_total: components.sources.internal_metrics.output.metrics.dual_write_divergent_events_total
+		dual_write_events_total:           components.sources.internal_metrics.output.metrics.dual_write_events_total
+		dual_write_latency_delta_seconds:  components.sources.internal_metrics.output.metrics.dual_write_latency_delta_seconds
+		dual_write_latency_seconds:        components.sources.internal_metrics.output.metrics.dual_write_latency_seconds
+	}
+}
@@ -0,0 +1,105 @@
+package metadata
+
+components: sinks: dynatrace: {
+	title: "Dynatrace"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Dynatrace"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.dynatrace
+
+				interface: {
+					socket: {
+						api: {
+							title: "Dynatrace log ingest API"
+							url:   urls.dynatrace_log_ingest
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.dynatrace.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    false
+			set:          true
+			summary:      false
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		log_attributes: {
+			title: "Log attributes"
+			body: """
+				Logs are sent to the [log ingest API](\(urls.dynatrace_log_ingest)) as JSON records. The
+				message, timestamp, and host of each event are sent as the `content`, `timestamp`, and
+				`host.name` attributes, and the `attributes` option maps other fields to Dynatrace semantic
+				attributes such as `severity`, `log.source`, or `service.name`. The remaining fields are sent
+				as attributes named after their dotted path.
+				"""
+		}
+		metric_lines: {
+			title: "Metric lines"
+			body: """
+				Metrics are sent to the metrics ingest API in the
+				[metric ingestion protocol](\(urls.dynatrace_metrics_ingest)), with their tags as dimensions.
+				Counters are sent as deltas, gauges and sets as gauges, and distributions as gauge summaries
+				of their minimum, maximum, sum, and count. Aggregated histograms and summaries cannot be
+				represented, and are dropped.
+				"""
+		}
+	}
+}
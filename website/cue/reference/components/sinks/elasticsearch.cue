@@ -104,6 +104,23 @@ components: sinks: elasticsearch: {
 				"""
 		}
 
+		data_stream_management: {
+			title: "Data stream management"
+			body: """
+				Data streams are created by Elasticsearch on the first write if an index template
+				with data streams enabled matches their name. With `data_stream.auto_create`, Vector
+				creates such a template on startup if it doesn't exist yet, named `vector-` followed by
+				the static parts of the data stream name, with a priority of 200 and the ILM policy set
+				in `data_stream.ilm_policy`. If the template can't be created, a warning is logged and
+				the sink starts anyway.
+
+				With `request_retry_partial` enabled, events written to a data stream or alias whose
+				write index is closed are retried after rolling it over, and events written to an alias
+				without a write index are retried against its write index, or else its newest index,
+				instead of being rejected.
+				"""
+		}
+
 		distribution: {
 			title: "Distribution"
 			body: """
@@ -76,5 +76,17 @@ components: sinks: honeycomb: {
 				curl option and use the key provided with the curl example.
 				"""
 		}
+		markers: {
+			title: "Markers"
+			body: """
+				Events flagged by the `markers.field` option are sent to the
+				[Markers API](https://docs.honeycomb.io/api/tag/Markers) of the dataset rather than as events,
+				for example to mark deployments on the graphs of the dataset:
+
+				```coffee
+				.honeycomb_marker = { "type": "deploy", "url": "https://example.com/deploys/123" }
+				```
+				"""
+		}
 	}
 }
@@ -66,7 +66,21 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		exactly_once: {
+			title: "Exactly-once delivery"
+			body: """
+				With `exactly_once`, events are batched with the `batch` options and each batch is produced
+				within a Kafka transaction, committed once all of its records were acknowledged. A failed
+				transaction is aborted and the whole batch is produced again in a new transaction, so
+				consumers reading with `isolation.level` set to `read_committed` see each record once.
+				Consumers reading uncommitted records may still see the records of aborted transactions.
+
+				Only one transaction is open at a time, which lowers throughput. The `transactional_id` must
+				be unique to each Vector instance producing to the cluster.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
@@ -99,6 +99,11 @@ components: sinks: loki: {
 				accepted by Loki. If no timestamp is supplied with events
 				then the Loki sink will supply its own monotonically
 				increasing timestamp.
+
+				Events arriving slightly out of order can be reordered
+				before they are sent by setting `out_of_order_action` to
+				`reorder`: the events of each stream are then held for
+				`reorder_window_secs` and sent sorted by timestamp.
 				"""
 		}
 
@@ -142,6 +147,13 @@ components: sinks: loki: {
 				cluster_name: static_cluster_name
 				cluster_environment: development
 				cluster_version: 1.2.3
+				```
+
+				Nested objects are flattened, their keys being joined with `_`, so that
+				`"k8s_*" = "{{ kubernetes }}"` expands `kubernetes.pod_labels.app` into the
+				`k8s_pod_labels_app` label. Expanded label names are lowercased, characters
+				that aren't valid in a label name are replaced with `_`, and names starting
+				with a digit are prefixed with `_`.
 				"""
 		}
 
@@ -156,9 +168,139 @@ components: sinks: loki: {
 				on the configured `compression`.
 				"""
 		}
+
+		label_cardinality: {
+			title: "Label cardinality"
+			body: """
+				Each distinct set of labels creates a stream in Loki, so a label
+				taking unbounded values, such as a request ID, can create an
+				unbounded number of streams. With `max_label_cardinality.limit`
+				set, the sink tracks the distinct values of each label name over
+				`max_label_cardinality.window_secs`; once a label name reaches the
+				limit, new values of it are dropped, moved to structured metadata,
+				or cause their event to be dropped, according to
+				`max_label_cardinality.action`.
+				"""
+		}
+
+		label_limits: {
+			title: "Label limits"
+			body: """
+				Loki rejects a whole push request when any of its streams has
+				too many labels, or a label name or value that is too long. With
+				`max_labels_per_stream`, `max_label_name_length`, and
+				`max_label_value_length` set to the limits of Loki, the sink drops
+				the labels whose name is too long, truncates the values that are
+				too long, then drops the labels past the maximum number of labels
+				of a stream, in the order of their names, so that a single event
+				does not cause its whole batch to be rejected. Each dropped or
+				truncated label is counted by the `limited_labels_total` metric.
+				"""
+		}
+
+		stream_sharding: {
+			title: "Stream sharding"
+			body: """
+				Loki limits the rate at which entries can be pushed to a single
+				stream. When `stream_sharding.enabled` is set and Loki rejects a
+				push request with a `429` response for exceeding that limit, the
+				streams of the request are sharded: for `stream_sharding.duration_secs`,
+				their entries get a `__shard__` label rotating across
+				`stream_sharding.shards` values, which spreads them across as many
+				streams. The rejected request is retried unchanged.
+				"""
+		}
+
+		dead_letter: {
+			title: "Dead letters"
+			body: """
+				Loki permanently rejects some entries, for example with a `400`
+				response when an entry is too far behind the newest entry of its
+				stream or its line is too long. Such push requests are not
+				retried, and their events are dropped unless `dead_letter` names
+				a `dead_letter` source: the events of the rejected request are
+				then emitted by that source as the sink received them, before
+				their labels and encoding were applied. Sinks have no outputs of
+				their own, so a sink such as `aws_s3` taking the source as input
+				stores them for later replay. The original events are still
+				reported as rejected to sources with acknowledgements enabled.
+				"""
+		}
+
+		attachment: {
+			title: "Original event attachment"
+			body: """
+				With `attachment.enabled` set, each entry carries the JSON of its
+				event, or of the `attachment.fields` of it, as the structured
+				metadata `attachment.key`, so that the original event can be
+				recovered from Loki whatever `encoding` and
+				`remove_label_fields` did to the line. The attachment is taken
+				before the labels are removed from the event and the encoding is
+				applied. Attachments larger than `attachment.max_bytes` are left
+				out, and counted by the `oversized_attachments_total` metric.
+				The attachment is not a file or a separate part of the push
+				request: it is one structured metadata key of the entry, next to
+				the others, and is not indexed like labels are.
+				Structured metadata requires Loki 2.9.0 or newer.
+				"""
+		}
+
+		otlp_endpoint: {
+			title: "OTLP endpoint"
+			body: """
+				Loki 3.0 and later accept logs at `/otlp/v1/logs` in the OpenTelemetry
+				protocol. With `endpoint_format` set to `otlp`, each batch is sent to
+				that path as an OTLP export request, without an OpenTelemetry
+				Collector in between: the entries of each stream become the log
+				records of a resource whose attributes are the labels of the
+				stream, with the line as their body and their structured metadata
+				as their attributes. Loki turns a few resource attributes, such as
+				`service.name`, into labels and stores the others as structured
+				metadata, unless its `otlp_config` says otherwise, so labels
+				should be named after the attributes Loki indexes. Label names
+				can contain dots in this format, such as `service.name`.
+				"""
+		}
+
+		stream_metrics: {
+			title: "Per-stream delivery metrics"
+			body: """
+				With `stream_metrics_labels`, the entries of each stream are
+				counted by the `stream_sent_entries_total`,
+				`stream_dropped_entries_total`, and `stream_rejected_entries_total`
+				metrics, tagged with a `stream` selector made of the values of
+				these labels, such as `{app="api"}`. Entries are counted as sent
+				once Loki accepts their push request, and as rejected once Loki
+				rejects it with an error that isn't retried, which shows which
+				application is losing logs without querying Loki itself.
+				"""
+		}
+
+		tenant_rate_limiting: {
+			title: "Tenant rate limiting"
+			body: """
+				When Loki rejects a push request with a `429` response, the
+				requests of its tenant are held for as long as asked by the
+				`Retry-After` header of the response, up to five minutes, while
+				the requests of other tenants are sent as usual. With
+				`tenant_rate_limit.enabled`, the requests of each tenant also go
+				through a token bucket of up to `tenant_rate_limit.max_requests_per_sec`,
+				whose rate is halved on each rejection and recovers as requests
+				succeed.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
+		dead_letter_events_total: components.sources.internal_metrics.output.metrics.dead_letter_events_total
+		disallowed_labels_total: components.sources.internal_metrics.output.metrics.disallowed_labels_total
+		label_cardinality_exceeded_total: components.sources.internal_metrics.output.metrics.label_cardinality_exceeded_total
+		limited_labels_total: components.sources.internal_metrics.output.metrics.limited_labels_total
+		rate_limited_requests_total: components.sources.internal_metrics.output.metrics.rate_limited_requests_total
+		sharded_streams_total:   components.sources.internal_metrics.output.metrics.sharded_streams_total
+		stream_dropped_entries_total: components.sources.internal_metrics.output.metrics.stream_dropped_entries_total
+		stream_rejected_entries_total: components.sources.internal_metrics.output.metrics.stream_rejected_entries_total
+		stream_sent_entries_total: components.sources.internal_metrics.output.metrics.stream_sent_entries_total
 		streams_total: components.sources.internal_metrics.output.metrics.streams_total
 	}
 }
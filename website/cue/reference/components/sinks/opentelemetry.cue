@@ -0,0 +1,118 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry Protocol"
+							url:   urls.opentelemetry_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "Protocols"
+			body: """
+				Events are exported with the [OpenTelemetry Protocol](\(urls.opentelemetry_protocol)),
+				either over gRPC or over HTTP with Protocol Buffers payloads, to the `/v1/logs`,
+				`/v1/metrics`, and `/v1/traces` paths under the endpoint. Log events become log records,
+				metrics become sums, gauges, histograms, and summaries, and the spans of trace events become
+				OpenTelemetry spans. Distributions and sketches have no OpenTelemetry equivalent, and are
+				dropped.
+				"""
+		}
+		resource_mapping: {
+			title: "Resource mapping"
+			body: """
+				Each export request holds the telemetry of a single resource, as events are batched by
+				resource. The resource attributes are those the `opentelemetry` source attaches to log
+				events, overridden by the templated `resource_attributes`. Traces get the `service.name`
+				attribute from the service of their spans. Log records get the other OpenTelemetry fields,
+				such as the severity and the trace and span IDs, from the `opentelemetry` source, so that
+				logs it receives are exported unchanged.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Failed exports are retried when the OpenTelemetry Protocol specification deems them
+				retryable, such as when the endpoint is unavailable or throttles exports. When the endpoint
+				requests a delay, through the `Retry-After` header or the `RetryInfo` detail of gRPC
+				errors, the export is retried after that delay, capped at `request.retry_max_duration_secs`.
+				"""
+		}
+	}
+}
@@ -109,5 +109,19 @@ components: sinks: prometheus_remote_write: {
 				Vector also supports using Gzip and Zstd.
 				"""
 		}
+		protocol_versions: {
+			title: "Protocol versions"
+			body: """
+				By default, metrics are sent with the 1.0 protocol. With `protocol_version = "2.0"`,
+				they are sent with the [2.0 protocol](\(urls.prometheus_remote_write_spec_2_0)), whose
+				requests intern the strings of labels in a symbols table and carry the type and help of
+				each series. The protocol is negotiated through the `Content-Type` header: receivers
+				only supporting 1.0 answer with `415 Unsupported Media Type`, after which the sink
+				sends the request again, and all subsequent ones, with the 1.0 protocol.
+
+				With `native_histograms` enabled, distributions are sent as native histograms with
+				exponential buckets. Vector metrics carry no exemplars, so none are sent.
+				"""
+		}
 	}
 }
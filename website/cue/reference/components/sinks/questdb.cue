@@ -0,0 +1,111 @@
+package metadata
+
+components: sinks: questdb: {
+	title: "QuestDB"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["QuestDB"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: enabled:           false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.questdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "InfluxDB Line Protocol"
+							url:   urls.questdb_ilp
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.questdb.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		rows: {
+			title: "Rows"
+			body: """
+				Each event is written as a row of the table rendered from `table`, which QuestDB creates on
+				the first write.
+
+				For logs, the fields listed in `symbols` are written as `SYMBOL` columns and the other
+				fields as columns of their type, nested fields being flattened with dots.
+
+				For metrics, the tags, the `name` and the `metric_type` of the metric are written as
+				`SYMBOL` columns, and the values as `DOUBLE` or `LONG` columns, as in the
+				`influxdb_metrics` sink.
+				"""
+		}
+		designated_timestamp: {
+			title: "Designated timestamp"
+			body: """
+				The timestamp of the event is sent as the timestamp of the row, which QuestDB stores in the
+				designated timestamp column of the table, `timestamp` by default.
+
+				For logs, the timestamp is taken from `timestamp_field` and removed from the columns, so
+				that it is not stored twice. Events without a timestamp are written at the time they are
+				encoded.
+				"""
+		}
+		authentication: {
+			title: "Authentication"
+			body: """
+				When `auth` is set, each connection is [authenticated](\(urls.questdb_ilp_auth)) with the
+				ECDSA key of a QuestDB user before writing: the key ID is sent, and the challenge sent back
+				by QuestDB is signed with the private key.
+
+				As the authentication is not encrypted by itself, enabling `tls` is recommended.
+				"""
+		}
+	}
+}
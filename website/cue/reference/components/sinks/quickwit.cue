@@ -0,0 +1,97 @@
+package metadata
+
+components: sinks: quickwit: {
+	title: "Quickwit"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Quickwit"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.quickwit
+
+				interface: {
+					socket: {
+						api: {
+							title: "Quickwit ingest API"
+							url:   urls.quickwit_ingest_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.quickwit.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		index_partitioning: {
+			title: "Index partitioning"
+			body: """
+				Events are batched per rendered `index`, and each batch is sent as newline-delimited JSON
+				to that index's ingest endpoint. The target indexes must already exist in Quickwit.
+				"""
+		}
+		commit_modes: {
+			title: "Commit modes"
+			body: """
+				The `commit` option maps to the `commit` query parameter of the ingest API. With `auto`,
+				documents become searchable after the index's commit timeout. `wait_for` and `force` hold the
+				request open until the documents are searchable, which reduces throughput.
+				"""
+		}
+	}
+}
@@ -0,0 +1,118 @@
+package metadata
+
+components: sinks: sumo_logic: {
+	title: "Sumo Logic"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Sumo Logic"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.sumo_logic
+
+				interface: {
+					socket: {
+						api: {
+							title: "Sumo Logic HTTP source"
+							url:   urls.sumo_logic_http_source
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.sumo_logic.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		sumo_headers: {
+			title: "Source metadata"
+			body: """
+				The `source_category`, `source_name`, `source_host`, and `fields` options are sent in the
+				`X-Sumo-Category`, `X-Sumo-Name`, `X-Sumo-Host`, and `X-Sumo-Fields` headers. Events are
+				batched per rendered set of headers, so templated values are sent in separate requests.
+				Logs and metrics are never sent in the same request.
+				"""
+		}
+		metrics_formats: {
+			title: "Metrics formats"
+			body: """
+				Metrics are sent in the [Carbon 2.0](\(urls.sumo_logic_carbon2)) format by default, with the
+				tags of each metric as intrinsic tags, or in the Prometheus text format. Incremental metrics
+				are made absolute before they are sent, and distributions are aggregated with the
+				`buckets` and `quantiles` options.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				Sumo Logic answers with `429 Too Many Requests` when the ingestion rate of the account is
+				over its limits. Such requests are retried, and all requests are held for the delay given in the
+				`Retry-After` header, up to five minutes.
+				"""
+		}
+	}
+}
@@ -0,0 +1,99 @@
+package metadata
+
+components: sinks: victorialogs: {
+	title: "VictoriaLogs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["VictoriaLogs"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.victorialogs
+
+				interface: {
+					socket: {
+						api: {
+							title: "VictoriaLogs JSON stream API"
+							url:   urls.victorialogs_json_stream_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.victorialogs.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		log_streams: {
+			title: "Log streams"
+			body: """
+				VictoriaLogs groups logs into streams based on the fields listed in `stream_fields`,
+				which are sent as the `_stream_fields` query parameter. Stream fields play the role of
+				Loki labels and should have a low number of distinct values. The `time_field` and
+				`message_field` options tell VictoriaLogs which event fields hold the `_time` and `_msg`
+				of each log entry.
+				"""
+		}
+		multitenancy: {
+			title: "Multitenancy"
+			body: """
+				Setting `account_id` and `project_id` sends the `AccountID` and `ProjectID` headers, which
+				select the tenant that logs are ingested into.
+				"""
+		}
+	}
+}
@@ -14,6 +14,11 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		dedupe_id:        base.components.sources.configuration.dedupe_id
+		timestamp_format: base.components.sources.configuration.timestamp_format
+		timezone:         base.components.sources.configuration.timezone
+		wal:              base.components.sources.configuration.wal
+
 		if !features.auto_generated {
 			if features.collect != _|_ {
 				if features.collect.checkpoint.enabled {
@@ -497,6 +497,19 @@ base: components: sources: aws_s3: configuration: {
 						required: false
 						type: string: examples: ["/path/to/certificate_authority.crt"]
 					}
+					ciphersuites: {
+						description: """
+							Sets the list of supported cipher suites, by their OpenSSL names.
+
+							TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+							`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+							none listed are the OpenSSL defaults.
+
+							In FIPS mode, only the FIPS approved cipher suites can be listed.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+					}
 					crt_file: {
 						description: """
 																Absolute path to a certificate file used to identify this server.
@@ -599,6 +612,19 @@ base: components: sources: aws_s3: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
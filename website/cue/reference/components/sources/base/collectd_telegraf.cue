@@ -0,0 +1,199 @@
+package metadata
+
+base: components: sources: collectd_telegraf: configuration: {
+	address: {
+		description: """
+			The socket address to listen for connections on, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation.
+
+			If a socket address is used, it _must_ include a port.
+			"""
+		relevant_when: "mode = \"tcp\" or mode = \"udp\""
+		required:      true
+		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
+	}
+	connection_limit: {
+		description:   "The maximum number of TCP connections that are allowed at any given time."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: unit: "connections"
+	}
+	format: {
+		description:   "The protocol of the metrics received over UDP."
+		relevant_when: "mode = \"udp\""
+		required:      true
+		type: string: enum: {
+			collectd: """
+				The [collectd binary protocol][collectd], as sent by the `network` plugin of collectd.
+
+				Each datagram is a packet of the protocol. Signed packets are accepted without verifying
+				their signature, and encrypted packets are rejected.
+
+				[collectd]: https://collectd.org/wiki/index.php/Binary_protocol
+				"""
+			influx: """
+				The [InfluxDB line protocol][influx], as sent by the `socket_writer` output of Telegraf.
+
+				[influx]: https://docs.influxdata.com/influxdb/v1.8/write_protocols/line_protocol_reference/
+				"""
+		}
+	}
+	keepalive: {
+		description:   "TCP keepalive settings for socket-based components."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: time_secs: {
+			description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+			required:    false
+			type: uint: unit: "seconds"
+		}
+	}
+	mode: {
+		description: "The type of socket to use."
+		required:    true
+		type: string: enum: {
+			tcp:  "Listen on TCP, for the InfluxDB line protocol."
+			udp:  "Listen on UDP, for the collectd binary protocol or the InfluxDB line protocol."
+			unix: "Listen on a Unix domain Socket (UDS), for the InfluxDB line protocol."
+		}
+	}
+	path: {
+		description: """
+			The Unix socket path.
+
+			This should be an absolute path.
+			"""
+		relevant_when: "mode = \"unix\""
+		required:      true
+		type: string: examples: ["/path/to/socket"]
+	}
+	receive_buffer_bytes: {
+		description:   "The size of the receive buffer used for each connection."
+		relevant_when: "mode = \"tcp\" or mode = \"udp\""
+		required:      false
+		type: uint: unit: "bytes"
+	}
+	shutdown_timeout_secs: {
+		description:   "The timeout before a connection is forcefully closed during shutdown."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: {
+			default: 30
+			unit:    "seconds"
+		}
+	}
+	tls: {
+		description:   "TlsEnableableConfig for `sources`, adding metadata from the client certificate."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			client_metadata_key: {
+				description: "Event field for client certificate metadata."
+				required:    false
+				type: string: {}
+			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
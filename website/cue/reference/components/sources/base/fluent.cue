@@ -37,6 +37,36 @@ base: components: sources: fluent: configuration: {
 		required:    false
 		type: uint: unit: "connections"
 	}
+	connection_limits: {
+		description: "Limits of the connections of a TCP source, protecting it from misbehaving clients."
+		required:    false
+		type: object: options: {
+			max_bytes_per_sec: {
+				description: """
+					The maximum number of bytes read per second from each connection.
+
+					Reading from a connection pauses while it exceeds the rate, letting TCP flow control slow
+					down the client.
+					"""
+				required: false
+				type: uint: unit: "bytes"
+			}
+			max_bytes_per_sec_per_ip: {
+				description: "The maximum number of bytes read per second from all connections of a single IP address."
+				required:    false
+				type: uint: unit: "bytes"
+			}
+			max_connections_per_ip: {
+				description: """
+					The maximum number of connections open at the same time from a single IP address.
+
+					Further connections from the address are closed as soon as they are accepted.
+					"""
+				required: false
+				type: uint: unit: "connections"
+			}
+		}
+	}
 	keepalive: {
 		description: "TCP keepalive settings for socket-based components."
 		required:    false
@@ -88,6 +118,19 @@ base: components: sources: fluent: configuration: {
 				required:    false
 				type: string: {}
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
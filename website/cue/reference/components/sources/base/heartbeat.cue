@@ -0,0 +1,35 @@
+package metadata
+
+base: components: sources: heartbeat: configuration: {
+	components: {
+		description: """
+			The IDs of the components heartbeats are emitted for.
+
+			If empty, a heartbeat is emitted for every component of the running Vector instance.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["app_logs"]
+		}
+	}
+	idle_after_secs: {
+		description: """
+			How long a component can go without sending events before it is reported as idle, in
+			seconds.
+			"""
+		required: false
+		type: float: {
+			default: 300.0
+			unit:    "seconds"
+		}
+	}
+	interval_secs: {
+		description: "The interval between heartbeats, in seconds."
+		required:    false
+		type: float: {
+			default: 60.0
+			unit:    "seconds"
+		}
+	}
+}
@@ -68,7 +68,7 @@ base: components: sources: host_metrics: configuration: {
 		description: """
 			The list of host metric collector services to use.
 
-			Defaults to all collectors.
+			Defaults to all collectors except `gpu` and `perf_counters`.
 			"""
 		required: false
 		type: array: {
@@ -83,12 +83,23 @@ base: components: sources: host_metrics: configuration: {
 					cpu:        "Metrics related to CPU utilization."
 					disk:       "Metrics related to disk I/O utilization."
 					filesystem: "Metrics related to filesystem space utilization."
+					gpu: """
+						Metrics related to GPU utilization.
+
+						NVIDIA GPUs are queried with `nvidia-smi`, and AMD GPUs are read from the `amdgpu` driver
+						on Linux.
+						"""
 					host:       "Metrics related to the host."
 					load:       "Metrics related to the system load average."
 					memory:     "Metrics related to memory utilization."
 					network:    "Metrics related to network utilization."
+					perf_counters: """
+						Metrics of Windows performance counters.
+
+						Only available on Windows.
+						"""
 				}
-				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				examples: ["cgroups", "cpu", "disk", "filesystem", "gpu", "load", "host", "memory", "network", "perf_counters"]
 			}
 		}
 	}
@@ -246,6 +257,30 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	gpu: {
+		description: "Options for the GPU metrics collector."
+		required:    false
+		type: object: options: {
+			nvidia_smi_path: {
+				description: """
+					The path of the `nvidia-smi` command, used to query NVIDIA GPUs through NVML.
+
+					`nvidia-smi` is installed along with the NVIDIA driver. Hosts without it have no NVIDIA GPU
+					metrics.
+					"""
+				required: false
+				type: string: {
+					default: "nvidia-smi"
+					examples: ["/usr/bin/nvidia-smi"]
+				}
+			}
+			processes: {
+				description: "Whether or not to report the GPU memory used by each process."
+				required:    false
+				type: bool: default: true
+			}
+		}
+	}
 	namespace: {
 		description: "Overrides the default namespace for the metrics emitted by the source."
 		required:    false
@@ -291,6 +326,38 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	perf_counters: {
+		description: """
+			Options for the Windows performance counters metrics collector.
+
+			This collector is only available on Windows.
+			"""
+		required: false
+		type: object: options: {
+			counters: {
+				description: """
+					The paths of the performance counters to report, as listed by `typeperf -q`.
+
+					Paths are written `\\Object(Instance)\\Counter`, or `\\Object\\Counter` for objects without
+					instances. They are given in English, and translated to the language of the system.
+
+					The instance can be a pattern, matched using globbing, such as `*` for every instance. Each
+					instance is reported with its name in the `instance` tag, followed by `#1`, `#2`, and so on
+					for instances sharing the same name.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["\\Processor(*)\\% Processor Time", "\\Process(w3wp*)\\Working Set", "\\Memory\\Available Bytes"]
+				}
+			}
+			include_total: {
+				description: "Whether or not to report the `_Total` instance when it is matched by a pattern."
+				required:    false
+				type: bool: default: false
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between metric gathering, in seconds."
 		required:    false
@@ -1,6 +1,27 @@
 package metadata
 
 base: components: sources: http_client: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	auth: {
 		description: "HTTP Authentication."
 		required:    false
@@ -43,6 +64,46 @@ base: components: sources: http_client: configuration: {
 			}
 		}
 	}
+	checkpoint: {
+		description: """
+			Checkpointing options.
+
+			When enabled, the `ETag` and `Last-Modified` headers of the last response and its cursor are
+			persisted in the global `data_dir`. They are sent back with the `If-None-Match` and
+			`If-Modified-Since` headers and the `cursor_parameter` query parameter of the next request,
+			including after a restart. A `304 Not Modified` response yields no events.
+
+			With acknowledgements enabled, the checkpoint only advances once the events of the response are
+			delivered, and the endpoint is not called again until then. Otherwise, the previous request is
+			replayed, so that events are ingested at least once.
+			"""
+		required: false
+		type: object: options: {
+			cursor_field: {
+				description: """
+					The field of the decoded events holding the cursor.
+
+					The value of the last event of the response holding it is used.
+					"""
+				required: false
+				type: string: examples: ["id"]
+			}
+			cursor_parameter: {
+				description: """
+					The query parameter the cursor is sent with.
+
+					It is appended to the parameters of the `endpoint` and `query` options.
+					"""
+				required: false
+				type: string: examples: ["since"]
+			}
+			enabled: {
+				description: "Whether or not requests are checkpointed."
+				required:    false
+				type: bool: default: false
+			}
+		}
+	}
 	decoding: {
 		description: "Decoder to use on the HTTP responses."
 		required:    false
@@ -339,6 +400,19 @@ base: components: sources: http_client: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -47,6 +47,24 @@ base: components: sources: http_server: configuration: {
 			}
 		}
 	}
+	compression_dictionaries: {
+		description: """
+			Trained zstd dictionaries that clients may compress their payloads with.
+
+			The IDs of the dictionaries are listed in the `vector-zstd-dictionary` response header,
+			and payloads compressed with one of them name its ID in the same request header. The
+			`http` sink does so when configured with one of them in its `compression_dictionary`.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: path: {
+				description: "The path of a trained zstd dictionary, such as one created with `zstd --train`."
+				required:    true
+				type: string: examples: ["/etc/vector/zstd.dict"]
+			}
+		}
+	}
 	decoding: {
 		description: "Configures how events are decoded from raw bytes."
 		required:    false
@@ -352,6 +370,19 @@ base: components: sources: http_server: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -0,0 +1,14 @@
+package metadata
+
+base: components: sources: internal_accounting: configuration: interval_secs: {
+	description: """
+		The interval between summaries, in seconds.
+
+		Each summary holds the events and bytes accounted since the previous one.
+		"""
+	required: false
+	type: float: {
+		default: 60.0
+		unit:    "seconds"
+	}
+}
@@ -0,0 +1,85 @@
+package metadata
+
+base: components: sources: internal_slo: configuration: {
+	interval_secs: {
+		description: "The interval between evaluations of the objectives, in seconds."
+		required:    false
+		type: float: {
+			default: 60.0
+			unit:    "seconds"
+		}
+	}
+	objectives: {
+		description: "The objectives, keyed by name."
+		required:    true
+		type: object: options: "*": {
+			description: "An objective."
+			required:    true
+			type: object: options: {
+				component_id: {
+					description: """
+						The component the metric is measured for.
+
+						If unset, the metric is measured for all components.
+						"""
+					required: false
+					type: string: {}
+				}
+				errors_metric: {
+					description:   "The name of the counter of errors."
+					relevant_when: "type = \"error_rate\""
+					required:      false
+					type: string: default: "component_errors_total"
+				}
+				metric: {
+					description:   "The name of the histogram metric."
+					relevant_when: "type = \"latency\""
+					required:      true
+					type: string: examples: ["http_client_rtt_seconds"]
+				}
+				target: {
+					description: "The lowest ratio of good events, between `0.0` and `1.0`."
+					required:    true
+					type: float: examples: [0.99]
+				}
+				threshold_secs: {
+					description:   "The highest latency of a good observation, in seconds."
+					relevant_when: "type = \"latency\""
+					required:      true
+					type: float: examples: [30.0]
+				}
+				total_metric: {
+					description:   "The name of the counter of events."
+					relevant_when: "type = \"error_rate\""
+					required:      false
+					type: string: default: "component_received_events_total"
+				}
+				type: {
+					description: "The indicator of the objective."
+					required:    true
+					type: string: enum: {
+						error_rate: "The ratio of the events processed without error."
+						latency: """
+							The ratio of the observations of a histogram metric that are at most a threshold.
+
+							Observations are counted by the buckets of the histogram, so the threshold should be the
+							upper limit of one of them.
+							"""
+					}
+				}
+			}
+		}
+	}
+	window_secs: {
+		description: """
+			The window over which the objectives are evaluated, in seconds.
+
+			Until Vector has run for that long, objectives are evaluated since the source started.
+			"""
+		required: false
+		type: float: {
+			default: 3600.0
+			unit:    "seconds"
+		}
+	}
+}
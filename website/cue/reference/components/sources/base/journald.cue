@@ -148,6 +148,20 @@ base: components: sources: journald: configuration: {
 		required: false
 		type: string: {}
 	}
+	reliability_signals: {
+		description: """
+			Enables recognizing the records of well-known reliability signals.
+
+			The records of systemd units starting, stopping, failing, being scheduled for a restart,
+			or being killed by the OOM killer, of processes killed by the kernel OOM killer or
+			crashing with a segfault, and of core dumps get a `reliability` object with the `kind` of
+			the signal and, when known, the `unit`, `process`, and `pid` involved. They are also counted
+			by the `journald_reliability_events_total` internal metric, tagged with their `kind` and
+			`unit`.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	remap_priority: {
 		deprecated:         true
 		deprecated_message: "This option has been deprecated, use the `remap` transform and `to_syslog_level` function instead."
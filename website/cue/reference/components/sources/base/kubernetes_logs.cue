@@ -371,6 +371,75 @@ base: components: sources: kubernetes_logs: configuration: {
 			}
 		}
 	}
+	pod_filter: {
+		description: """
+			Configuration for selecting Pods to collect logs from.
+
+			Unlike `extra_label_selector` and `extra_field_selector`, which are sent to the Kubernetes API,
+			these selectors are evaluated by Vector against the Pods it already knows about. Log files of
+			Pods that are not selected are never opened.
+			"""
+		required: false
+		type: object: options: {
+			exclude_field_selector: {
+				description: """
+					A [field selector][field_selector] matching Pods whose logs must not be collected.
+
+					Takes precedence over `include_field_selector`. If empty, no Pods are excluded.
+
+					[field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+					"""
+				required: false
+				type: string: {
+					default: ""
+					examples: ["metadata.namespace=kube-system"]
+				}
+			}
+			exclude_label_selector: {
+				description: """
+					A [label selector][label_selector] matching Pods whose logs must not be collected.
+
+					Takes precedence over `include_label_selector`. If empty, no Pods are excluded.
+
+					[label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+					"""
+				required: false
+				type: string: {
+					default: ""
+					examples: ["environment notin (production)", "!logging"]
+				}
+			}
+			include_field_selector: {
+				description: """
+					A [field selector][field_selector] that Pods must match to have their logs collected.
+
+					If empty, all Pods are included.
+
+					[field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+					"""
+				required: false
+				type: string: {
+					default: ""
+					examples: ["status.phase=Running"]
+				}
+			}
+			include_label_selector: {
+				description: """
+					A [label selector][label_selector] that Pods must match to have their logs collected.
+
+					Both equality-based (`=`, `==`, `!=`) and set-based (`in`, `notin`, `key`, `!key`)
+					requirements are supported. If empty, all Pods are included.
+
+					[label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+					"""
+				required: false
+				type: string: {
+					default: ""
+					examples: ["app in (api, worker),tier!=cache"]
+				}
+			}
+		}
+	}
 	read_from: {
 		description: "File position to use when reading a new file."
 		required:    false
@@ -382,6 +451,51 @@ base: components: sources: kubernetes_logs: configuration: {
 			}
 		}
 	}
+	routing: {
+		description: "Configuration for routing events based on an annotation of their Pod or Namespace."
+		required:    false
+		type: object: options: {
+			annotation: {
+				description: """
+					The annotation holding the route of the events of a Pod.
+
+					The annotation is looked up on the Pod first, and then on its Namespace, so that a Pod can
+					override the route of its Namespace.
+					"""
+				required: false
+				type: string: {
+					default: "vector.dev/route"
+					examples: ["example.com/pipeline"]
+				}
+			}
+			outputs: {
+				description: """
+					The routes that are exposed as named outputs of the source.
+
+					Events whose route is listed here are sent to the output of the same name, such as
+					`<component_id>.audit` for the `audit` route. All other events, including those with an
+					unlisted route, are sent to the default output.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["audit"]
+				}
+			}
+			route_field: {
+				description: """
+					Event field for the route.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.route"
+					examples: [".k8s.route", "k8s.route", ""]
+				}
+			}
+		}
+	}
 	self_node_name: {
 		description: """
 			The name of the Kubernetes [Node][node] that is running.
@@ -394,11 +508,6 @@ base: components: sources: kubernetes_logs: configuration: {
 		required: false
 		type: string: default: "${VECTOR_SELF_NODE_NAME}"
 	}
-	timezone: {
-		description: "The default time zone for timestamps without an explicit zone."
-		required:    false
-		type: string: examples: ["local", "America/New_York", "EST5EDT"]
-	}
 	use_apiserver_cache: {
 		description: "Determines if requests to the kube-apiserver can be served by a cache."
 		required:    false
@@ -0,0 +1,210 @@
+package metadata
+
+base: components: sources: loki: configuration: {
+	auth: {
+		description: "HTTP Authentication."
+		required:    false
+		type: object: options: {
+			password: {
+				description:   "The basic authentication password."
+				relevant_when: "strategy = \"basic\""
+				required:      true
+				type: string: examples: ["${PASSWORD}", "password"]
+			}
+			strategy: {
+				description: "The authentication strategy to use."
+				required:    true
+				type: string: enum: {
+					basic: """
+						Basic authentication.
+
+						The username and password are concatenated and encoded via [base64][base64].
+
+						[base64]: https://en.wikipedia.org/wiki/Base64
+						"""
+					bearer: """
+						Bearer authentication.
+
+						The bearer token value (OAuth2, JWT, etc.) is passed as-is.
+						"""
+				}
+			}
+			token: {
+				description:   "The bearer authentication token."
+				relevant_when: "strategy = \"bearer\""
+				required:      true
+				type: string: {}
+			}
+			user: {
+				description:   "The basic authentication username."
+				relevant_when: "strategy = \"basic\""
+				required:      true
+				type: string: examples: ["${USERNAME}", "username"]
+			}
+		}
+	}
+	backfill_page_size: {
+		description: """
+			The maximum number of entries in a page of the range queries of the backfill.
+
+			This must not exceed the `max_entries_limit_per_query` of Loki.
+			"""
+		required: false
+		type: uint: default: 1000
+	}
+	backfill_secs: {
+		description: """
+			How far back to query logs before tailing them, in seconds.
+
+			The logs since then are collected through range queries, one page at a time, before the
+			logs arriving from the start of the source are tailed. If not set, only the logs arriving
+			from the start of the source are collected.
+			"""
+		required: false
+		type: uint: {
+			examples: [3600]
+			unit: "seconds"
+		}
+	}
+	delay_for_secs: {
+		description: """
+			How long Loki waits before sending entries of the tail, in seconds.
+
+			Waiting lets Loki send entries arriving late in order. Loki accepts up to five seconds.
+			"""
+		required: false
+		type: uint: {
+			default: 0
+			unit:    "seconds"
+		}
+	}
+	endpoint: {
+		description: """
+			The base URL of the Loki instance.
+
+			The paths of the tail and query APIs are appended to this.
+			"""
+		required: true
+		type: string: examples: ["http://localhost:3100"]
+	}
+	query: {
+		description: """
+			The [LogQL][logql] query selecting the logs to collect.
+
+			Only log queries are supported, as metric queries don't return log entries.
+
+			[logql]: https://grafana.com/docs/loki/latest/logql/
+			"""
+		required: true
+		type: string: examples: ["{app=\"api\"}", "{namespace=\"production\"} |= \"error\""]
+	}
+	tenant_id: {
+		description: """
+			The [tenant ID][tenant_id] to specify in requests to Loki.
+
+			When running Loki locally, a tenant ID is not required.
+
+			[tenant_id]: https://grafana.com/docs/loki/latest/operations/multi-tenancy/
+			"""
+		required: false
+		type: string: examples: ["some_tenant_id"]
+	}
+	tls: {
+		description: "TLS configuration."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
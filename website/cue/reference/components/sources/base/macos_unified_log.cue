@@ -0,0 +1,54 @@
+package metadata
+
+base: components: sources: macos_unified_log: configuration: {
+	include_activities: {
+		description: "Whether or not to read activity and trace entries, along with log entries."
+		required:    false
+		type: bool: default: false
+	}
+	level: {
+		description: "The lowest level of the entries read."
+		required:    false
+		type: string: {
+			default: "default"
+			enum: {
+				debug: """
+					Entries of every level.
+
+					Debug entries are numerous, and are usually better read with a predicate.
+					"""
+				default: "Entries of the default, error, and fault levels."
+				info:    "Entries of the info level, along with those of the default level."
+			}
+		}
+	}
+	log_path: {
+		description: "The full path of the `log` executable."
+		required:    false
+		type: string: default: "/usr/bin/log"
+	}
+	predicate: {
+		description: """
+			A [predicate][predicate] filtering the entries read.
+
+			Predicates are evaluated by the logging system, so filtering is cheaper there than in a
+			transform.
+
+			[predicate]: https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/Predicates/AdditionalChapters/Introduction.html
+			"""
+		required: false
+		type: string: examples: ["subsystem == \"com.apple.sharing\"", "process == \"sshd\" AND messageType == error"]
+	}
+	processes: {
+		description: """
+			The processes whose entries are read, by name or ID.
+
+			If empty, entries of all processes are read.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["sshd", "1"]
+		}
+	}
+}
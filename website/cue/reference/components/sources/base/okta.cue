@@ -0,0 +1,226 @@
+package metadata
+
+base: components: sources: okta: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	auth: {
+		description: "Authentication options."
+		required:    true
+		type: object: options: {
+			client_id: {
+				description:   "The client ID of the app."
+				relevant_when: "strategy = \"oauth\""
+				required:      true
+				type: string: examples: ["0oa1b2c3d4e5f6g7h8i9"]
+			}
+			key_id: {
+				description: """
+					The ID of the key, sent in the `kid` header of the client assertions.
+
+					Required when several public keys are registered with the app.
+					"""
+				relevant_when: "strategy = \"oauth\""
+				required:      false
+				type: string: examples: ["vector"]
+			}
+			private_key_file: {
+				description: """
+					The path of the RSA private key of the app, in PEM format.
+
+					The matching public key must be registered with the app.
+					"""
+				relevant_when: "strategy = \"oauth\""
+				required:      true
+				type: string: examples: ["/etc/vector/okta.pem"]
+			}
+			strategy: {
+				description: "The authentication strategy to use."
+				required:    true
+				type: string: enum: {
+					api_token: """
+						Authenticate with an API token.
+
+						API tokens act on behalf of the admin who created them, with the permissions of that admin,
+						and expire after 30 days without being used.
+						"""
+					oauth: """
+						Authenticate as an API service app, through the OAuth 2.0 client credentials flow.
+
+						The app authenticates with a client assertion signed by its private key, and must be
+						granted the `okta.logs.read` scope. Demonstrating Proof-of-Possession (DPoP) must be
+						disabled for the app, as it is not supported.
+						"""
+				}
+			}
+			token: {
+				description:   "The API token."
+				relevant_when: "strategy = \"api_token\""
+				required:      true
+				type: string: examples: ["${OKTA_API_TOKEN}"]
+			}
+		}
+	}
+	backfill_secs: {
+		description: """
+			How far back to collect events from when the source first starts, in seconds.
+
+			Once a checkpoint is persisted, the source resumes from it instead. Okta retains events for
+			90 days. By default, only the events published since the first start are collected.
+			"""
+		required: false
+		type: uint: {
+			default: 0
+			examples: [86400]
+			unit: "seconds"
+		}
+	}
+	endpoint: {
+		description: "The URL of the Okta organization."
+		required:    true
+		type: string: examples: ["https://example.okta.com"]
+	}
+	filter: {
+		description: """
+			An expression filtering the events to collect, in the [filter syntax][filter] of the System
+			Log API.
+
+			[filter]: https://developer.okta.com/docs/reference/api/system-log/#expression-filter
+			"""
+		required: false
+		type: string: examples: ["eventType eq \"user.session.start\"", "severity eq \"WARN\" or severity eq \"ERROR\""]
+	}
+	page_size: {
+		description: """
+			The maximum number of events in a page.
+
+			Okta returns at most 1000 events in a page.
+			"""
+		required: false
+		type: uint: default: 1000
+	}
+	poll_interval_secs: {
+		description: "The interval between requests once all events published so far are collected, in seconds."
+		required:    false
+		type: uint: {
+			default: 15
+			unit:    "seconds"
+		}
+	}
+	tls: {
+		description: "TLS configuration."
+		required:    false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Relevant for both incoming and outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
@@ -59,8 +59,11 @@ base: components: sources: prometheus_scrape: configuration: {
 	}
 	endpoints: {
 		description: "Endpoints to scrape metrics from."
-		required:    true
-		type: array: items: type: string: examples: ["http://localhost:9090/metrics"]
+		required:    false
+		type: array: {
+			default: []
+			items: type: string: examples: ["http://localhost:9090/metrics"]
+		}
 	}
 	honor_labels: {
 		description: """
@@ -69,7 +72,8 @@ base: components: sources: prometheus_scrape: configuration: {
 			If `true`, the new tag is not added if the scraped metric has the tag already. If `false`, the conflicting tag
 			is renamed by prepending `exported_` to the original name.
 
-			This matches Prometheus’ `honor_labels` configuration.
+			This matches Prometheus’ `honor_labels` configuration, and also applies to the tags set by
+			`relabel_configs`.
 			"""
 		required: false
 		type: bool: default: false
@@ -83,6 +87,75 @@ base: components: sources: prometheus_scrape: configuration: {
 		required: false
 		type: string: {}
 	}
+	kubernetes_sd: {
+		description: """
+			Configuration for discovering scrape targets from the Kubernetes API.
+
+			Discovered targets have `__address__`, `__scheme__`, and `__metrics_path__` labels, along with
+			`__meta_kubernetes_*` labels describing the object they were discovered from, following the
+			naming of Prometheus' [`kubernetes_sd_config`][kubernetes_sd_config]. These labels are usually
+			combined with `relabel_configs` to select targets and set their tags.
+
+			[kubernetes_sd_config]: https://prometheus.io/docs/prometheus/latest/configuration/configuration/#kubernetes_sd_config
+			"""
+		required: false
+		type: object: options: {
+			field_selector: {
+				description: "A field selector restricting the discovered objects."
+				required:    false
+				type: string: examples: ["status.phase=Running"]
+			}
+			kube_config_file: {
+				description: """
+					Optional path to a readable [kubeconfig][kubeconfig] file.
+
+					If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+					[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+					"""
+				required: false
+				type: string: examples: ["/path/to/.kube/config"]
+			}
+			label_selector: {
+				description: "A label selector restricting the discovered objects."
+				required:    false
+				type: string: examples: ["app.kubernetes.io/part-of=shop"]
+			}
+			namespaces: {
+				description: """
+					The Namespaces to discover targets in.
+
+					If empty, targets are discovered in all Namespaces.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: string: examples: ["monitoring"]
+				}
+			}
+			refresh_interval_secs: {
+				description: "The interval between refreshes of the discovered targets, in seconds."
+				required:    false
+				type: uint: default: 30
+			}
+			role: {
+				description: "The kind of Kubernetes objects to discover scrape targets from."
+				required:    false
+				type: string: {
+					default: "pod"
+					enum: {
+						endpoints: "Discover a target for each port of each address of each Endpoints object."
+						pod: """
+							Discover a target for each declared port of each container of each Pod.
+
+							Containers without ports are discovered as a single target with the Pod IP as address.
+							"""
+						service: "Discover a target for each port of each Service, with the DNS name of the Service as address."
+					}
+				}
+			}
+		}
+	}
 	query: {
 		description: """
 			Custom parameters for the scrape request query string.
@@ -103,6 +176,85 @@ base: components: sources: prometheus_scrape: configuration: {
 			}
 		}
 	}
+	relabel_configs: {
+		description: """
+			Relabeling steps applied to the targets before they are scraped.
+
+			Each target starts with an `__address__` label for its `host:port`, a `__scheme__` label, a
+			`__metrics_path__` label, and a `__param_<name>` label for each query string parameter of
+			its endpoint. Targets discovered by `kubernetes_sd` also have `__meta_kubernetes_*` labels.
+
+			After relabeling, the target is scraped at the URL built from these labels, and its labels
+			that don't start with `__` are added as tags to the scraped metrics.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				action: {
+					description: "The action of a relabeling step."
+					required:    false
+					type: string: {
+						default: "replace"
+						enum: {
+							drop:      "Drop the target if `regex` matches the joined `source_labels`."
+							keep:      "Drop the target if `regex` doesn't match the joined `source_labels`."
+							labeldrop: "Remove the labels whose name matches `regex`."
+							labelkeep: "Remove the labels whose name doesn't match `regex`."
+							labelmap:  "Copy the labels whose name matches `regex` to the label named by `replacement`."
+							lowercase: "Set `target_label` to the lowercased joined `source_labels`."
+							replace: """
+								Set `target_label` to `replacement` if `regex` matches the joined `source_labels`.
+
+								Capture groups of `regex` can be referenced in `replacement`, such as `$1`.
+								"""
+							uppercase: "Set `target_label` to the uppercased joined `source_labels`."
+						}
+					}
+				}
+				regex: {
+					description: """
+						The regular expression matched against the joined `source_labels`, or against label names
+						for the `labelmap`, `labeldrop`, and `labelkeep` actions.
+
+						The regular expression is anchored on both ends.
+						"""
+					required: false
+					type: string: {
+						default: "(.*)"
+						examples: ["true", "__meta_kubernetes_pod_label_(.+)"]
+					}
+				}
+				replacement: {
+					description: """
+						The value written by the `replace` and `labelmap` actions.
+
+						Capture groups of `regex` can be referenced, such as `$1`.
+						"""
+					required: false
+					type: string: default: "$1"
+				}
+				separator: {
+					description: "The separator placed between the values of `source_labels`."
+					required:    false
+					type: string: default: ";"
+				}
+				source_labels: {
+					description: "The labels whose values are joined with `separator` and matched against `regex`."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+					}
+				}
+				target_label: {
+					description: "The label written by the `replace`, `lowercase`, and `uppercase` actions."
+					required:    false
+					type: string: examples: ["__metrics_path__"]
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: "The interval between scrapes, in seconds."
 		required:    false
@@ -134,6 +286,19 @@ base: components: sources: prometheus_scrape: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
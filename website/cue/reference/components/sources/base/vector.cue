@@ -31,6 +31,23 @@ base: components: sources: vector: configuration: {
 		required: true
 		type: string: {}
 	}
+	compression_dictionaries: {
+		description: """
+			Trained zstd dictionaries that upstream Vector instances may compress their events with.
+
+			The IDs of the dictionaries are listed in responses, and upstream instances configured with
+			one of them in their `compression_dictionary` start using it.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: path: {
+				description: "The path of a trained zstd dictionary, such as one created with `zstd --train`."
+				required:    true
+				type: string: examples: ["/etc/vector/zstd.dict"]
+			}
+		}
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
@@ -54,6 +71,19 @@ base: components: sources: vector: configuration: {
 				required: false
 				type: string: examples: ["/path/to/certificate_authority.crt"]
 			}
+			ciphersuites: {
+				description: """
+					Sets the list of supported cipher suites, by their OpenSSL names.
+
+					TLS 1.3 suites, such as `TLS_AES_256_GCM_SHA384`, and TLS 1.2 suites, such as
+					`ECDHE-RSA-AES256-GCM-SHA384`, can be listed together. The suites of a TLS version that has
+					none listed are the OpenSSL defaults.
+
+					In FIPS mode, only the FIPS approved cipher suites can be listed.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["TLS_AES_256_GCM_SHA384", "ECDHE-RSA-AES256-GCM-SHA384"]
+			}
 			crt_file: {
 				description: """
 					Absolute path to a certificate file used to identify this server.
@@ -0,0 +1,96 @@
+package metadata
+
+components: sources: collectd_telegraf: {
+	_port: 25826
+
+	title: "collectd / Telegraf"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.collectd
+				interface: socket: {
+					api: {
+						title: "collectd binary protocol"
+						url:   urls.collectd_binary_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp", "udp", "unix"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp`"
+			}
+			keepalive: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+		auto_generated: true
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.collectd_telegraf.configuration
+
+	output: metrics: {
+		counter: output._passthrough_counter
+		gauge:   output._passthrough_gauge
+	}
+
+	how_it_works: {
+		collectd: {
+			title: "collectd"
+			body:  """
+				Point the `network` plugin of [collectd](\(urls.collectd)) at a `udp` listener using the
+				`collectd` format. Each value is named after its plugin and type, such as `cpu` or
+				`interface_if_octets`, in the `collectd` namespace, and the host, plugin instance and
+				type instance are set as tags. Types with several values, such as `load`, get one metric
+				per value suffixed with its position.
+
+				`gauge` values become gauges, `counter` and `derive` values become absolute counters, and
+				`absolute` values become incremental counters.
+				"""
+		}
+		telegraf: {
+			title: "Telegraf"
+			body:  """
+				Point the `socket_writer` output of [Telegraf](\(urls.telegraf)) at any listener, using
+				the `influx` format for `udp`. Lines of the
+				[InfluxDB line protocol](\(urls.influxdb_line_protocol)) are turned into one gauge per
+				numeric or boolean field, named `<measurement>_<field>` and tagged with the tags of the
+				line. String fields are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total:    components.sources.internal_metrics.output.metrics.connection_errors_total
+		invalid_record_total:       components.sources.internal_metrics.output.metrics.invalid_record_total
+		invalid_record_bytes_total: components.sources.internal_metrics.output.metrics.invalid_record_bytes_total
+	}
+}
@@ -0,0 +1,65 @@
+package metadata
+
+components: sources: dead_letter: {
+	title:       "Dead Letter"
+	description: "The dead letter source emits the events sinks could not deliver and sent to it, so that they can be routed to another sink, such as an object store, for later replay."
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.dead_letter.configuration
+
+	output: logs: event: {
+		description: "An event a sink could not deliver."
+		fields: "*": {
+			description: "The events are emitted as the sink received them, without modifying or adding fields."
+			required:    true
+			type: "*": {}
+		}
+	}
+
+	how_it_works: {
+		routing: {
+			title: "Routing dead letters"
+			body: """
+				Sinks have no outputs in the topology, so a sink that gives up on
+				events, such as the `loki` sink with its `dead_letter` option, sends
+				them to the `dead_letter` source whose ID the option names. The
+				source emits them as the sink received them to the components
+				taking it as input, for example an `aws_s3` sink storing them for
+				later replay. Events sent while the source is not running, such
+				as during a reload, are dropped with an error logged by the sink.
+				"""
+		}
+	}
+}
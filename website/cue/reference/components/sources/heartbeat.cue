@@ -0,0 +1,115 @@
+package metadata
+
+components: sources: heartbeat: {
+	title:       "Heartbeat"
+	description: "The heartbeat source periodically emits one event for each component of the running Vector instance with its last activity, so that a component that stopped sending events can be detected in the pipeline itself."
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.heartbeat.configuration
+
+	output: logs: heartbeat: {
+		description: "The last activity of a component."
+		fields: {
+			component_id: {
+				description: "The ID of the component."
+				required:    true
+				type: string: {
+					examples: ["app_logs"]
+				}
+			}
+			status: {
+				description: "Whether the component is sending events."
+				required:    true
+				type: string: {
+					enum: {
+						active:  "The component sent events within `idle_after_secs`."
+						idle:    "The component has not sent events for `idle_after_secs`."
+						missing: "The component is not running."
+					}
+				}
+			}
+			last_activity: {
+				description: "When the component last sent events, or `null` if it has not since the source started."
+				required:    true
+				type: timestamp: {}
+			}
+			idle_secs: {
+				description: "How long the component has not sent events for, in seconds, counted from the start of the source if it has not sent any since."
+				required:    true
+				type: float: {
+					examples: [12.5]
+				}
+			}
+			events_total: {
+				description: "The number of events the component sent since Vector started, or `null` if it is missing."
+				required:    true
+				type: float: {
+					examples: [10250.0]
+				}
+			}
+			errors_total: {
+				description: "The number of errors of the component since Vector started, or `null` if it is missing."
+				required:    true
+				type: float: {
+					examples: [0.0]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["heartbeat"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		absence_detection: {
+			title: "Absence detection"
+			body: """
+				At each interval, this source captures the internal metrics of the running Vector
+				instance and compares the `component_sent_events_total` counter of each component to
+				its value at the previous heartbeat. A component whose counter has not grown for
+				`idle_after_secs` is reported as `idle`, such as a `file` source whose files stopped
+				being written to, and a component listed in `components` without metrics is reported
+				as `missing`. Events sent before the source started are not counted as activity.
+				Routing the heartbeats through a `filter` transform on `.status != "active"` to an
+				alerting sink alerts on silent components without an external monitoring system.
+				"""
+		}
+	}
+}
@@ -68,6 +68,21 @@ components: sources: host_metrics: {
 
 	configuration: base.components.sources.host_metrics.configuration
 
+	how_it_works: {
+		perf_counters: {
+			title: "Windows performance counters"
+			body: """
+				The `perf_counters` collector reports the performance counters listed in
+				`perf_counters.counters`, such as `\\Processor(*)\\% Processor Time`. Each counter is
+				reported as a gauge named after its object and counter in snake case, such as
+				`processor_percent_processor_time`, with `object`, `counter`, and `instance` tags.
+
+				Counter paths are given in English, and work on systems in any language. Counters
+				computed as rates are reported from the second scrape onwards.
+				"""
+		}
+	}
+
 	output: metrics: {
 		_host_metrics_tags: {
 			collector: {
@@ -128,6 +143,13 @@ components: sources: host_metrics: {
 		filesystem_used_bytes:  _host & _filesystem_bytes & {description: "The number of bytes used on the named filesystem."}
 		filesystem_used_ratio:  _host & _filesystem_bytes & {description: "The ratio between used and total bytes on the named filesystem."}
 
+		// Host GPU
+		gpu_memory_total_bytes:        _host & _gpu_gauge & {description:   "The total number of bytes of memory of the GPU."}
+		gpu_memory_used_bytes:         _host & _gpu_gauge & {description:   "The number of bytes of memory of the GPU in use."}
+		gpu_process_memory_used_bytes: _host & _gpu_process & {description: "The number of bytes of memory of the GPU used by this process."}
+		gpu_temperature_celsius:       _host & _gpu_gauge & {description:   "The temperature of the GPU, in degrees Celsius."}
+		gpu_utilization_ratio:         _host & _gpu_gauge & {description:   "The ratio of time the GPU was busy over the last sampling period."}
+
 		// Host load
 		load1:  _host & _loadavg & {description: "System load averaged over the last 1 minute."}
 		load5:  _host & _loadavg & {description: "System load averaged over the last 5 minutes."}
@@ -216,6 +238,43 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		_gpu_tags: _host_metrics_tags & {
+			collector: examples: ["gpu"]
+			gpu: {
+				description: "The index of the NVIDIA GPU, or the DRM card of the AMD GPU."
+				required:    true
+				examples: ["0", "card0"]
+			}
+			vendor: {
+				description: "The vendor of the GPU."
+				required:    true
+				examples: ["nvidia", "amd"]
+			}
+			model: {
+				description: "The model of the GPU. Only set for NVIDIA GPUs."
+				required:    false
+				examples: ["Tesla T4"]
+			}
+		}
+		_gpu_gauge: {
+			type: "gauge"
+			tags: _gpu_tags
+		}
+		_gpu_process: {
+			type: "gauge"
+			tags: _gpu_tags & {
+				pid: {
+					description: "The ID of the process."
+					required:    true
+					examples: ["4242"]
+				}
+				process: {
+					description: "The name of the process."
+					required:    true
+					examples: ["python3"]
+				}
+			}
+		}
 		_loadavg: {
 			type: "gauge"
 			tags: _host_metrics_tags & {
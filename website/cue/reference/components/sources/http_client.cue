@@ -13,14 +13,14 @@ components: sources: http_client: {
 	}
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		auto_generated:   true
 		codecs: {
 			enabled:         true
 			default_framing: "`bytes`"
 		}
 		collect: {
-			checkpoint: enabled: false
+			checkpoint: enabled: true
 			from: {
 				service: services.http_scrape
 
@@ -138,6 +138,25 @@ components: sources: http_client: {
 		}
 	}
 
+	how_it_works: {
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				By default, each request is independent of the previous ones, so events can be missed
+				or ingested twice across restarts. With `checkpoint.enabled`, requests are made
+				conditional on the last response: its `ETag` and `Last-Modified` headers are sent back
+				with `If-None-Match` and `If-Modified-Since`, and the cursor of its last event with
+				`checkpoint.cursor_parameter`. The checkpoints are persisted in the `data_dir`, so that
+				the source resumes from them after a restart.
+
+				With end-to-end acknowledgements enabled, the checkpoint of an endpoint only advances
+				once the events of the response are delivered, and the endpoint is not called until
+				then. If they are not delivered, the previous request is replayed, making the source
+				at-least-once.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		http_error_response_total: components.sources.internal_metrics.output.metrics.http_error_response_total
 		http_request_errors_total: components.sources.internal_metrics.output.metrics.http_request_errors_total
@@ -0,0 +1,131 @@
+package metadata
+
+components: sources: internal_accounting: {
+	title:       "Internal Accounting"
+	description: "The internal accounting source periodically emits the events and bytes accounted by the running Vector instance, for cost attribution."
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: [
+			"""
+				The global `accounting.enabled` option must be set to `true`.
+				""",
+		]
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.internal_accounting.configuration
+
+	output: logs: summary: {
+		description: "The events and bytes of a group of events of a component, accounted over an interval."
+		fields: {
+			component_id: {
+				description: "The ID of the source or sink that accounted the events."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_logs"]
+				}
+			}
+			direction: {
+				description: "Whether the events were ingested by a source, or delivered to a sink."
+				required:    true
+				type: string: {
+					enum: {
+						ingested:  "The events were ingested by a source."
+						delivered: "The events were delivered to a sink."
+					}
+				}
+			}
+			dimensions: {
+				description: """
+					The values of the configured dimensions of the group, keyed by name. Missing values
+					are `null`.
+					"""
+				required: true
+				type: object: {
+					examples: [{namespace: "payments", tenant: "acme"}]
+					options: {}
+				}
+			}
+			overflow: {
+				description: """
+					Whether the group holds the events of the groups above `accounting.max_groups`, in
+					which case it has no dimensions.
+					"""
+				required: true
+				type: bool: {}
+			}
+			events: {
+				description: "The number of events accounted during the interval."
+				required:    true
+				type: uint: {
+					examples: [1500]
+					unit: null
+				}
+			}
+			bytes: {
+				description: "The estimated size of the events once encoded as JSON, in bytes."
+				required:    true
+				type: uint: {
+					examples: [1048576]
+					unit: "bytes"
+				}
+			}
+			window_start: {
+				description: "The start of the interval."
+				required:    true
+				type: timestamp: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["internal_accounting"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The end of the interval."
+			}
+		}
+	}
+
+	how_it_works: {
+		accounting: {
+			title: "Accounting"
+			body: """
+				Every source accounts the events it ingests, and every sink the events it receives for
+				delivery, grouped by the configured `accounting.dimensions`. At each interval, this
+				source emits one event for each group that accounted events since the previous
+				interval. Events accounted before the source started are not summarized. The counts
+				since Vector started are also exposed by the `accounting` API query.
+				"""
+		}
+	}
+}
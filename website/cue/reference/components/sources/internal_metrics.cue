@@ -72,6 +72,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		anomalies_detected_total: {
+			description:       "The total number of metric values deviating from their baseline, detected by the `anomaly_detection` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		api_started_total: {
 			description:       "The number of times the Vector GraphQL API has been started."
 			type:              "counter"
@@ -102,6 +108,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		connection_rejected_total: {
+			description:       "The total number of connections closed as soon as they were accepted, because their address had too many connections open."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		connection_send_errors_total: {
 			description:       "The total number of errors sending data via the connection."
 			type:              "counter"
@@ -126,6 +138,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		rate_limited_requests_total: {
+			description:       "The total number of push requests rejected by Loki for exceeding the rate limit of their tenant."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		recover_errors_total: {
 			description:       "The total number of errors caused by Vector failing to recover from a failed reload."
 			type:              "counter"
@@ -203,6 +221,22 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		clock_skew_events_total: {
+			description:       "The total number of events with a timestamp outside of the window accepted by the `clock_skew` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				policy: {
+					description: "The policy applied to the event."
+					required:    true
+					enum: {
+						clamp: "The timestamp was clamped to the accepted window."
+						shift: "The timestamp was shifted by the estimated clock offset."
+						tag:   "The skew was recorded on the event."
+					}
+				}
+			}
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -277,6 +311,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dead_letter_events_total: {
+			description:       "The total number of events a sink sent to a `dead_letter` source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		decode_errors_total: {
 			description:       "The total number of decode errors seen when decoding data in a source component."
 			type:              "counter"
@@ -360,6 +400,52 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dual_write_divergent_events_total: {
+			description:       "The total number of events whose batch had a different outcome on the primary and secondary sinks of a `dual_write` sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		dual_write_events_total: {
+			description:       "The total number of events written by the primary or secondary sink of a `dual_write` sink, by outcome."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				sink: _dual_write_sink
+				status: {
+					description: "The outcome of the batch of events on the sink."
+					required:    true
+					enum: {
+						delivered: "The events were delivered."
+						errored:   "The delivery of the events failed with a transient error."
+						rejected:  "The events were rejected."
+					}
+				}
+			}
+		}
+		dual_write_latency_seconds: {
+			description:       "The time taken by the primary or secondary sink of a `dual_write` sink to finalize a batch of events."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				sink: _dual_write_sink
+			}
+		}
+		dual_write_latency_delta_seconds: {
+			description:       "The difference between the time taken by the primary and secondary sinks of a `dual_write` sink to finalize a batch of events."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				slower: {
+					description: "The sink that took longer to finalize the batch."
+					required:    true
+					enum: {
+						primary:   "The primary sink."
+						secondary: "The secondary sink."
+					}
+				}
+			}
+		}
 		encode_errors_total: {
 			description:       "The total number of errors encountered when encoding an event."
 			type:              "counter"
@@ -416,6 +502,62 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_disk_usage_bytes: {
+			description:       "The number of bytes used in the data directory by the component, measured every 10 seconds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+				kind: {
+					description: "What the bytes are used for."
+					required:    true
+					enum: {
+						buffer: "The disk buffer of the component."
+						state:  "The state the component keeps in its own directory, such as checkpoints."
+					}
+				}
+			}
+		}
+		data_dir_budget_evicted_bytes_total: {
+			description:       "The number of bytes of data files of disk buffers evicted to free the `data_dir_budget`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		data_dir_budget_evicted_files_total: {
+			description:       "The number of data files of disk buffers evicted to free the `data_dir_budget`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		data_dir_budget_exceeded_total: {
+			description:       "The number of times the disk buffers exceeded the `data_dir_budget`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				policy: {
+					description: "The policy of the budget."
+					required:    true
+					enum: {
+						alert:        "An error is logged."
+						block:        "Disk buffers are considered full."
+						evict_oldest: "The oldest data files of the evictable disk buffers are deleted."
+					}
+				}
+			}
+		}
+		data_dir_budget_max_bytes: {
+			description:       "The maximum number of bytes of the `data_dir_budget`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		data_dir_budget_used_bytes: {
+			description:       "The number of bytes used by the disk buffers, counted towards the `data_dir_budget`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
 		component_discarded_events_total: {
 			description:       "The number of events dropped by this component."
 			type:              "counter"
@@ -553,6 +695,25 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {output: _output}
 		}
+		disallowed_labels_total: {
+			description:       "The total number of labels dropped or renamed because the tenant of their event does not allow them."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant_id: {
+					description: "The ID of the tenant."
+					required:    true
+				}
+				action: {
+					description: "What happened to the labels."
+					required:    true
+					enum: {
+						drop:   "The labels were dropped."
+						rename: "The labels were renamed with the `label_prefix` of the tenant."
+					}
+				}
+			}
+		}
 		datadog_logs_received_in_total: {
 			description:       "Number of Datadog logs received."
 			type:              "counter"
@@ -774,6 +935,53 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		journald_reliability_events_total: {
+			description:       "The total number of records of reliability signals the journald source recognized."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				kind: {
+					description: "The kind of the signal, such as `unit_failed` or `oom_kill`."
+					required:    true
+				}
+				unit: {
+					description: "The systemd unit involved, if known."
+					required:    false
+				}
+			}
+		}
+		label_cardinality_exceeded_total: {
+			description:       "The total number of label values over the `max_label_cardinality.limit` of the Loki sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				action: {
+					description: "What happened to the labels or their event."
+					required:    true
+					enum: {
+						drop_label:          "The labels were dropped."
+						structured_metadata: "The labels were moved to the structured metadata of the entry."
+						drop_event:          "The event was dropped."
+					}
+				}
+			}
+		}
+		limited_labels_total: {
+			description:       "The total number of labels dropped or truncated by the label limits of the Loki sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				limit: {
+					description: "The limit the labels were over."
+					required:    true
+					enum: {
+						max_labels_per_stream:  "The labels were dropped, as their stream had too many labels."
+						max_label_name_length:  "The labels were dropped, as their name was too long."
+						max_label_value_length: "The values of the labels were truncated."
+					}
+				}
+			}
+		}
 		logging_driver_errors_total: {
 			description: """
 				The total number of logging driver errors encountered caused by not using either
@@ -855,12 +1063,111 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		scaling_buffer_fill_rate: {
+			description:       "How fast the fill ratio of the buffer of the component changed over the `scaling.interval_secs` interval, per second."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+			}
+		}
+		scaling_buffer_fill_ratio: {
+			description:       "How full the buffer of the component is, between `0.0` and `1.0`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+			}
+		}
+		scaling_buffer_time_to_full_seconds: {
+			description:       "The time until the buffer of the component is full at its current fill rate. Only set while the buffer is filling up."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+			}
+		}
+		scaling_saturation_ratio: {
+			description:       "The highest buffer fill ratio of any component, between `0.0` and `1.0`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags
+		}
+		scaling_source_lag_seconds: {
+			description:       "The mean lag of the events received by the source over the `scaling.interval_secs` interval."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_id: _component_id
+			}
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sessions_closed_total: {
+			description:       "The total number of sessions closed by the `sessionize` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "Why the session was closed."
+					required:    true
+					enum: {
+						inactivity:   "The group didn't receive events for the inactivity gap."
+						max_duration: "The session lasted for the maximum duration."
+						shutdown:     "The transform stopped."
+					}
+				}
+			}
+		}
+		sharded_streams_total: {
+			description:       "The total number of Loki streams sharded after exceeding the per-stream rate limit."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		sink_in_flight_bytes: {
+			description:       "The number of bytes of events the sink took from its buffer and is not yet done sending."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		slo_error_budget_remaining_ratio: {
+			description:       "The ratio of the error budget of the objective left over the `window_secs` window of the `internal_slo` source."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				objective: {
+					description: "The name of the objective."
+					required:    true
+				}
+			}
+		}
+		slo_indicator_ratio: {
+			description:       "The ratio of good events of the objective over the `window_secs` window of the `internal_slo` source."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				objective: {
+					description: "The name of the objective."
+					required:    true
+				}
+			}
+		}
+		slo_objective_met: {
+			description:       "Whether the objective is met (`1`) or violated (`0`) over the `window_secs` window of the `internal_slo` source."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				objective: {
+					description: "The name of the objective."
+					required:    true
+				}
+			}
+		}
 		source_lag_time_seconds: {
 			description:       "The difference between the timestamp recorded in each event and the time when it was ingested, expressed as fractional seconds."
 			type:              "histogram"
@@ -942,6 +1249,46 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		stream_dropped_entries_total: {
+			description:       "The total number of entries of a stream dropped by the Loki sink before being sent."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				stream: {
+					description: "The selector of the stream, made of the values of the `stream_metrics_labels` of the Loki sink."
+					required:    true
+				}
+				reason: {
+					description: "Why the entries were dropped."
+					required:    true
+					enum: {
+						out_of_order: "The entries were older than the last entry sent for their stream."
+					}
+				}
+			}
+		}
+		stream_rejected_entries_total: {
+			description:       "The total number of entries of a stream the Loki sink sent in push requests Loki rejected without a retry."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				stream: {
+					description: "The selector of the stream, made of the values of the `stream_metrics_labels` of the Loki sink."
+					required:    true
+				}
+			}
+		}
+		stream_sent_entries_total: {
+			description:       "The total number of entries of a stream the Loki sink sent in push requests Loki accepted."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				stream: {
+					description: "The selector of the stream, made of the values of the `stream_metrics_labels` of the Loki sink."
+					required:    true
+				}
+			}
+		}
 		stdin_reads_failed_total: {
 			description:       "The total number of errors reading from stdin."
 			type:              "counter"
@@ -957,12 +1304,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tail_dropped_entries_total: {
+			description:       "The total number of entries Loki dropped from the tail of the Loki source, as they were not read fast enough."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		udp_receive_buffer_dropped_datagrams_total: {
+			description:       "The total number of UDP datagrams dropped by the kernel because the receive buffer of the socket was full. Only reported on Linux."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -1114,6 +1473,14 @@ components: sources: internal_metrics: {
 			required:    true
 			examples: ["file", "http", "honeycomb", "splunk_hec"]
 		}
+		_dual_write_sink: {
+			description: "The sink of a `dual_write` sink."
+			required:    true
+			enum: {
+				primary:   "The primary sink."
+				secondary: "The secondary sink."
+			}
+		}
 		_endpoint: {
 			description: "The absolute path of originating file."
 			required:    true
@@ -0,0 +1,149 @@
+package metadata
+
+components: sources: internal_slo: {
+	title:       "Internal SLO"
+	description: "The internal SLO source periodically evaluates service level objectives, such as the latency or the error rate of components, from the internal metrics of the running Vector instance."
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.internal_slo.configuration
+
+	output: logs: status: {
+		description: "The status of an objective over the window."
+		fields: {
+			objective: {
+				description: "The name of the objective."
+				required:    true
+				type: string: {
+					examples: ["sink_latency"]
+				}
+			}
+			indicator: {
+				description: "The indicator of the objective."
+				required:    true
+				type: string: {
+					enum: {
+						latency:    "The ratio of the observations of a histogram metric that are at most a threshold."
+						error_rate: "The ratio of the events processed without error."
+					}
+				}
+			}
+			target: {
+				description: "The target of the objective."
+				required:    true
+				type: float: {
+					examples: [0.99]
+				}
+			}
+			value: {
+				description: "The ratio of good events over the window, or `null` if there were none."
+				required:    true
+				type: float: {
+					examples: [0.995]
+				}
+			}
+			status: {
+				description: "Whether the objective is met."
+				required:    true
+				type: string: {
+					enum: {
+						met:      "The value is at least the target."
+						violated: "The value is below the target."
+						no_data:  "There were no events over the window."
+					}
+				}
+			}
+			error_budget_remaining: {
+				description: """
+					The ratio of the bad events allowed by the target that are left, or `null` if there
+					were no events or the target is `1.0`. It is negative once the objective is violated.
+					"""
+				required: true
+				type: float: {
+					examples: [0.5]
+				}
+			}
+			good: {
+				description: "The number of good events over the window."
+				required:    true
+				type: float: {
+					examples: [995.0]
+				}
+			}
+			total: {
+				description: "The number of events over the window."
+				required:    true
+				type: float: {
+					examples: [1000.0]
+				}
+			}
+			window_secs: {
+				description: "The length of the window the objective was evaluated over, in seconds."
+				required:    true
+				type: float: {
+					examples: [3600.0]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["internal_slo"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		objectives: {
+			title: "Objectives"
+			body: """
+				At each interval, this source captures the internal metrics of the running Vector
+				instance and emits one event for each objective with its status over the last
+				`window_secs`. A `latency` objective counts the observations of a histogram, such as
+				`http_client_rtt_seconds`, in the buckets up to `threshold_secs`; an `error_rate`
+				objective compares the `component_errors_total` counter to the
+				`component_received_events_total` counter. Events before the source started are not
+				evaluated. Routing the events to an alerting sink lets the pipeline alert on itself.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		slo_error_budget_remaining_ratio: components.sources.internal_metrics.output.metrics.slo_error_budget_remaining_ratio
+		slo_indicator_ratio:              components.sources.internal_metrics.output.metrics.slo_indicator_ratio
+		slo_objective_met:                components.sources.internal_metrics.output.metrics.slo_objective_met
+	}
+}
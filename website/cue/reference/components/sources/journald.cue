@@ -63,6 +63,44 @@ components: sources: journald: {
 						examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
 					}
 				}
+				reliability: {
+					description: "The reliability signal of the record, if `reliability_signals` is enabled and the record is one."
+					required:    false
+					type: object: options: {
+						kind: {
+							description: "The kind of the signal."
+							required:    true
+							type: string: enum: {
+								unit_started:           "A systemd unit started."
+								unit_stopped:           "A systemd unit stopped."
+								unit_failed:            "A systemd unit failed."
+								unit_restart_scheduled: "systemd scheduled the restart of a unit."
+								unit_oom_kill:          "A process of a systemd unit was killed by the OOM killer."
+								oom_kill:               "The kernel OOM killer killed a process."
+								segfault:               "A process crashed with a segmentation fault."
+								coredump:               "`systemd-coredump` stored a core dump of a crashed process."
+							}
+						}
+						unit: {
+							description: "The systemd unit involved."
+							required:    false
+							type: string: examples: ["nginx.service"]
+						}
+						process: {
+							description: "The name of the process involved."
+							required:    false
+							type: string: examples: ["nginx"]
+						}
+						pid: {
+							description: "The PID of the process involved."
+							required:    false
+							type: uint: {
+								examples: [4242]
+								unit: null
+							}
+						}
+					}
+				}
 				source_type: {
 					description: "The name of the source type."
 					required:    true
@@ -138,6 +176,21 @@ components: sources: journald: {
 				[issue #1473](\(urls.vector_issues)/1473).
 				"""
 		}
+		reliability_signals: {
+			title: "Reliability signals"
+			body: """
+				With `reliability_signals` enabled, records of well-known events
+				that affect the reliability of a host are recognized: the messages
+				systemd logs with a known `MESSAGE_ID` when a unit starts, stops,
+				fails, is scheduled for a restart, or has a process killed by the
+				OOM killer, the messages of `systemd-coredump`, and the kernel
+				messages of the OOM killer and of segfaults. Their events get a
+				`reliability` object describing the signal, ready to be routed on
+				`exists(.reliability)`, and the `journald_reliability_events_total`
+				internal metric counts them by `kind` and `unit`, so that restart
+				loops and OOM kills can be alerted on without parsing messages.
+				"""
+		}
 		non_ascii: {
 			title: "Non-ASCII Messages"
 			body: """
@@ -151,7 +204,8 @@ components: sources: journald: {
 	}
 
 	telemetry: metrics: {
-		invalid_record_total:       components.sources.internal_metrics.output.metrics.invalid_record_total
-		invalid_record_bytes_total: components.sources.internal_metrics.output.metrics.invalid_record_bytes_total
+		invalid_record_total:              components.sources.internal_metrics.output.metrics.invalid_record_total
+		invalid_record_bytes_total:        components.sources.internal_metrics.output.metrics.invalid_record_bytes_total
+		journald_reliability_events_total: components.sources.internal_metrics.output.metrics.journald_reliability_events_total
 	}
 }
@@ -193,6 +193,15 @@ components: sources: kubernetes_logs: {
 					examples: ["ba46d8c9-9541-4f6b-bbf9-d23b36f2f136"]
 				}
 			}
+			"kubernetes.route": {
+				description: "The route of the Pod, read from the `routing.annotation` annotation of the Pod or of its Namespace."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["audit"]
+				}
+			}
 			message: {
 				description: "The raw line from the Pod log file."
 				required:    true
@@ -318,6 +327,32 @@ components: sources: kubernetes_logs: {
 					will skip logs from the Pods that have a `vector.dev/exclude: "true"` **label**.
 					You can configure additional exclusion rules via label or field selectors,
 					see [the available options](\(urls.vector_kubernetes_logs_source)#configuration).
+
+					The `extra_label_selector` and `extra_field_selector` options are sent to the
+					Kubernetes API, which then only reports the matching Pods. The `pod_filter`
+					options are instead evaluated by Vector against its local cache of Pods, and
+					support exclusion as well as inclusion rules, and set-based label
+					requirements such as `app in (api, worker)`. Either way, the log files of Pods
+					that are not selected are never opened.
+					"""
+		}
+
+		routing: {
+			title: "Routing"
+			body:  """
+					Workloads can declare the pipeline their logs belong to with an **annotation**,
+					`vector.dev/route` by default, on the Pod or on its Namespace. The annotation of
+					the Pod takes precedence over the one of its Namespace:
+
+					```yaml
+					vector.dev/route: "audit"
+					```
+
+					The route is added to each event, at `kubernetes.route` by default. Routes listed
+					in `routing.outputs` are also exposed as named outputs of the source, so that
+					events of the `audit` route can be consumed with `inputs = ["<source_id>.audit"]`.
+					Events without a route, or with a route that is not listed, are sent to the default
+					output.
 					"""
 		}
 
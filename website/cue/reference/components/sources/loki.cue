@@ -0,0 +1,128 @@
+package metadata
+
+components: sources: loki: {
+	title: "Loki"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_most_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["Grafana"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.loki
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.loki.configuration
+
+	output: logs: entry: {
+		description: "An entry of a Loki stream."
+		fields: {
+			message: {
+				description: "The line of the entry."
+				required:    true
+				type: string: {
+					examples: ["GET /health 200"]
+				}
+			}
+			labels: {
+				description: "The labels of the stream of the entry."
+				required:    true
+				type: object: {
+					examples: [{"app": "api", "namespace": "production"}]
+				}
+			}
+			structured_metadata: {
+				description: "The structured metadata of the entry, if it has any."
+				required:    false
+				type: object: {
+					examples: [{"trace_id": "0af7651916cd43dd8448eb211c80319c"}]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["loki"]
+				}
+			}
+			timestamp: {
+				description: "The timestamp of the entry."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		tailing: {
+			title: "Tailing"
+			body: """
+				This source connects to the `/loki/api/v1/tail` WebSocket API of Loki, which sends the
+				entries matching `query` as they are ingested. When the connection is closed, such as
+				when Loki ends a tail after its maximum duration, the source reconnects and resumes the
+				tail after the last entry it received. Entries Loki drops because the source isn't
+				reading fast enough are counted by the `tail_dropped_entries_total` metric.
+				"""
+		}
+		backfill: {
+			title: "Backfill"
+			body: """
+				When `backfill_secs` is set, the source first collects the entries since then through
+				the `/loki/api/v1/query_range` API, one page of `backfill_page_size` entries at a time
+				in the order of their timestamps, and then tails the entries ingested from its start.
+				Pages start at the timestamp of the last entry of the previous page, and the entries
+				of the previous page sharing it are not sent again.
+				"""
+		}
+		replaying: {
+			title: "Replaying logs into another Loki cluster"
+			body: """
+				The labels of each stream are added to its entries under `labels`, so a `loki` sink
+				with `labels."*" = "{{ labels }}"` pushes the entries to another cluster with their
+				original labels, and `out_of_order_action = "accept"` keeps backfilled entries that
+				are older than the ones already pushed.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		tail_dropped_entries_total: components.sources.internal_metrics.output.metrics.tail_dropped_entries_total
+	}
+}
@@ -0,0 +1,148 @@
+package metadata
+
+components: sources: macos_unified_log: {
+	title: "macOS Unified Log"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_most_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.macos_unified_log
+
+				interface: binary: {
+					name: "log"
+					permissions: unix: group: "admin"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-pc-windows-msv":          false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				This source runs `log stream`, which only reads the entries of other users' processes
+				when Vector runs as an administrator.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.macos_unified_log.configuration
+
+	output: logs: {
+		event: {
+			description: "An entry of the unified log."
+			fields: {
+				host: fields._local_host
+				message: {
+					description: "The message of the entry."
+					required:    true
+					type: string: examples: ["Accepted publickey for admin from 10.0.0.4 port 53122 ssh2"]
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: examples: ["macos_unified_log"]
+				}
+				timestamp: fields._current_timestamp & {
+					description: "The time at which the entry was logged."
+				}
+				subsystem: {
+					description: "The subsystem of the entry, usually the bundle ID of the program."
+					required:    false
+					type: string: examples: ["com.openssh.sshd"]
+				}
+				category: {
+					description: "The category of the entry within its subsystem."
+					required:    false
+					type: string: examples: ["auth"]
+				}
+				process: {
+					description: "The name of the process that logged the entry."
+					required:    false
+					type: string: examples: ["sshd"]
+				}
+				process_id: {
+					description: "The ID of the process that logged the entry."
+					required:    false
+					type: uint: {
+						examples: [321]
+						unit: null
+					}
+				}
+				thread_id: {
+					description: "The ID of the thread that logged the entry."
+					required:    false
+					type: uint: {
+						examples: [5678]
+						unit: null
+					}
+				}
+				activity_id: {
+					description: "The ID of the activity the entry belongs to, if any."
+					required:    false
+					type: uint: {
+						examples: [2001]
+						unit: null
+					}
+				}
+				sender: {
+					description: "The name of the library or executable that logged the entry."
+					required:    false
+					type: string: examples: ["libsystem_trace.dylib"]
+				}
+				level: {
+					description: "The level of the entry."
+					required:    false
+					type: string: examples: ["default", "info", "debug", "error", "fault"]
+				}
+				event_type: {
+					description: "The type of the entry."
+					required:    false
+					type: string: examples: ["logEvent", "activityCreateEvent"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		communication_strategy: {
+			title: "Communication Strategy"
+			body:  """
+				Vector reads the unified log by running `log stream --style ndjson`, and restarts it if it
+				exits. Entries logged while Vector is not running are not read.
+
+				The `predicate` option is passed to `log stream`, and uses the same syntax as the
+				`--predicate` option of `log show`. Filtering with a predicate is cheaper than filtering in a
+				transform, as entries that don't match are never sent to Vector.
+				"""
+		}
+	}
+}
@@ -0,0 +1,133 @@
+package metadata
+
+components: sources: okta: {
+	title: "Okta"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Okta"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.okta
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "required"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.okta.configuration
+
+	output: logs: event: {
+		description: "An event of the System Log, with the fields of its [LogEvent object](\(urls.okta_system_log))."
+		fields: {
+			eventType: {
+				description: "The type of the event."
+				required:    true
+				type: string: {
+					examples: ["user.session.start", "user.account.lock"]
+				}
+			}
+			uuid: {
+				description: "The unique identifier of the event."
+				required:    true
+				type: string: {
+					examples: ["dc9fd3c0-598c-11ea-a2c0-0b8d1a2a5a8d"]
+				}
+			}
+			published: {
+				description: "The time the event was published at, as an ISO 8601 string."
+				required:    true
+				type: string: {
+					examples: ["2023-06-01T12:00:00.123Z"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["okta"]
+				}
+			}
+			timestamp: {
+				description: "The time the event was published at."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				This source requests the events of the `/api/v1/logs` API of the organization in the
+				order they were published, one page of `page_size` events at a time, following the
+				`next` link of each page. Once a page has fewer events than requested, all events
+				published so far were collected, and the next page is requested after
+				`poll_interval_secs`.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The `next` link of the last page collected is persisted in the global `data_dir`, and
+				the source resumes from it after a restart. When the source first starts, it collects
+				the events published since `backfill_secs` ago. With acknowledgements enabled, the
+				checkpoint only advances once the events of a page are delivered, and the page is
+				requested again otherwise, so that events are collected at least once.
+				"""
+		}
+		rate_limits: {
+			title: "Rate limits"
+			body: """
+				The System Log API is rate limited per organization. When a response reports that no
+				request remains, or the source is throttled with a `429 Too Many Requests` response,
+				it waits until the time in the `X-Rate-Limit-Reset` header before the next request.
+				Other failed requests are retried with an exponential backoff.
+				"""
+		}
+		authentication: {
+			title: "Authentication"
+			body: """
+				With the `api_token` strategy, the token is sent with the `SSWS` scheme. With the
+				`oauth` strategy, the source requests access tokens with the `okta.logs.read` scope
+				from the org authorization server, authenticating with a client assertion signed by
+				the private key of the app, and renews them before they expire.
+				"""
+		}
+	}
+}
@@ -66,6 +66,35 @@ components: sources: prometheus_scrape: {
 				but will only take the last value for each tag name specified.
 				"""
 		}
+		service_discovery: {
+			title: "Service discovery and relabeling"
+			body: """
+				Besides the fixed `endpoints`, scrape targets can be discovered from the Kubernetes API
+				with the `kubernetes_sd` option, and selected or rewritten with `relabel_configs`,
+				following the semantics of Prometheus' `kubernetes_sd_config` and `relabel_config`. For
+				example, to only scrape the Pods annotated with `prometheus.io/scrape: "true"`, on the
+				port of their `prometheus.io/port` annotation, tagging their metrics with the Pod labels:
+
+				```yaml
+				kubernetes_sd:
+				  role: pod
+				relabel_configs:
+				  - source_labels: [__meta_kubernetes_pod_annotation_prometheus_io_scrape]
+				    regex: "true"
+				    action: keep
+				  - source_labels: [__meta_kubernetes_pod_ip, __meta_kubernetes_pod_annotation_prometheus_io_port]
+				    regex: '(.+);(\\d+)'
+				    replacement: "$1:$2"
+				    target_label: __address__
+				  - regex: __meta_kubernetes_pod_label_(.+)
+				    action: labelmap
+				```
+
+				Discovered targets are refreshed every `kubernetes_sd.refresh_interval_secs`, and are kept
+				as they were if a refresh fails. Vector needs permission to `list` the Pods, Services, or
+				Endpoints of the discovered Namespaces.
+				"""
+		}
 	}
 
 	output: metrics: {
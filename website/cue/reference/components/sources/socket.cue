@@ -111,12 +111,14 @@ components: sources: socket: {
 	]
 
 	telemetry: metrics: {
-		connection_errors_total:          components.sources.internal_metrics.output.metrics.connection_errors_total
-		connection_failed_total:          components.sources.internal_metrics.output.metrics.connection_failed_total
-		connection_established_total:     components.sources.internal_metrics.output.metrics.connection_established_total
-		connection_failed_total:          components.sources.internal_metrics.output.metrics.connection_failed_total
-		connection_send_errors_total:     components.sources.internal_metrics.output.metrics.connection_send_errors_total
-		connection_send_ack_errors_total: components.sources.internal_metrics.output.metrics.connection_send_ack_errors_total
-		connection_shutdown_total:        components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		connection_errors_total:                    components.sources.internal_metrics.output.metrics.connection_errors_total
+		connection_failed_total:                    components.sources.internal_metrics.output.metrics.connection_failed_total
+		connection_established_total:               components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_failed_total:                    components.sources.internal_metrics.output.metrics.connection_failed_total
+		connection_rejected_total:                  components.sources.internal_metrics.output.metrics.connection_rejected_total
+		connection_send_errors_total:               components.sources.internal_metrics.output.metrics.connection_send_errors_total
+		connection_send_ack_errors_total:           components.sources.internal_metrics.output.metrics.connection_send_ack_errors_total
+		connection_shutdown_total:                  components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		udp_receive_buffer_dropped_datagrams_total: components.sources.internal_metrics.output.metrics.udp_receive_buffer_dropped_datagrams_total
 	}
 }
@@ -0,0 +1,79 @@
+package metadata
+
+components: transforms: anomaly_detection: {
+	title: "Anomaly Detection"
+
+	description: """
+		Maintains a baseline of every metric series, and tags metrics or emits alert events when
+		their value deviates from it beyond a threshold, to alert at the edge before the metrics reach
+		a time series database.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.anomaly_detection.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	outputs: [
+		{
+			name: "alerts"
+			description: """
+				A log event for each anomalous value, with the `name`, `namespace` and `tags` of its
+				series, its `value`, the `baseline` it was compared to, its `deviation` from the
+				baseline, and its `score` in standard deviations.
+				"""
+		},
+	]
+
+	how_it_works: {
+		scoring: {
+			title: "Scoring"
+			body: """
+				Only counters and gauges are scored, other metrics are passed through untouched. The
+				increments of absolute counters are scored, rather than their value.
+
+				Each value is scored against the baseline of its series before being folded into it, so
+				that a sustained change of level gradually becomes the new baseline.
+				"""
+		}
+		memory_usage: {
+			title: "Memory usage"
+			body: """
+				The transform keeps the baselines of every series in memory, so it should be placed
+				after transforms limiting the cardinality of the metrics, such as
+				`tag_cardinality_limit`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		anomalies_detected_total: components.sources.internal_metrics.output.metrics.anomalies_detected_total
+	}
+}
@@ -0,0 +1,69 @@
+package metadata
+
+base: components: transforms: anomaly_detection: configuration: {
+	alpha: {
+		description: """
+			The weight of each new value in the baseline, between `0.0` and `1.0`.
+
+			Higher values make the baseline follow recent values more closely.
+			"""
+		required: false
+		type: float: default: 0.1
+	}
+	baseline: {
+		description: "How the baseline of a series is computed."
+		required:    false
+		type: object: options: {
+			buckets: {
+				description:   "The number of buckets the season is divided in."
+				relevant_when: "type = \"seasonal\""
+				required:      false
+				type: uint: default: 24
+			}
+			season_secs: {
+				description:   "The duration of the season."
+				relevant_when: "type = \"seasonal\""
+				required:      false
+				type: uint: {
+					default: 86400
+					unit:    "seconds"
+				}
+			}
+			type: {
+				description: "The type of baseline."
+				required:    false
+				type: string: {
+					default: "ewma"
+					enum: {
+						ewma: "An exponentially weighted moving average, and variance, of the values of the series."
+						seasonal: """
+							An exponentially weighted moving average, and variance, for each phase of a season.
+
+							The season is divided in buckets, and each value is compared to the baseline of the bucket
+							its timestamp falls into, such as the same hour of the previous days.
+							"""
+					}
+				}
+			}
+		}
+	}
+	min_samples: {
+		description: "The number of values a baseline must have seen before its deviations are scored."
+		required:    false
+		type: uint: default: 10
+	}
+	tag_anomalies: {
+		description: """
+			Whether to add the `anomaly` and `anomaly_score` tags to anomalous metrics.
+
+			Alert events are sent to the `alerts` output in any case.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	threshold: {
+		description: "The number of standard deviations from the baseline beyond which a value is anomalous."
+		required:    false
+		type: float: default: 3.0
+	}
+}
@@ -0,0 +1,63 @@
+package metadata
+
+base: components: transforms: clock_skew: configuration: {
+	group_by: {
+		description: """
+			The name of the field identifying the origin of an event, such as its host.
+
+			With the `shift` policy, a clock offset is estimated separately for each value of this
+			field. If unset, or if the event doesn't have the field, a single estimate is shared by all
+			events.
+			"""
+		required: false
+		type: string: examples: ["host"]
+	}
+	max_future_secs: {
+		description: "How far ahead of the ingest time an event timestamp may be before it is considered skewed."
+		required:    false
+		type: uint: {
+			default: 300
+			unit:    "seconds"
+		}
+	}
+	max_past_secs: {
+		description: """
+			How far behind the ingest time an event timestamp may be before it is considered skewed.
+
+			Set this below the oldest timestamp accepted by the downstream sink, such as Loki's
+			`reject_old_samples_max_age`.
+			"""
+		required: false
+		type: uint: {
+			default: 3600
+			unit:    "seconds"
+		}
+	}
+	policy: {
+		description: "What to do with events whose timestamp is outside of the accepted window."
+		required:    false
+		type: string: {
+			default: "clamp"
+			enum: {
+				clamp: "Move the timestamp to the nearest edge of the accepted window."
+				shift: """
+					Shift the timestamp by the estimated clock offset of its origin.
+
+					The offset is a moving average of the difference between the ingest time and the event
+					timestamps seen for each `group_by` value. Timestamps still outside of the window after
+					shifting are clamped.
+					"""
+				tag: "Keep the timestamp, and record the skew in `skew_field`."
+			}
+		}
+	}
+	skew_field: {
+		description: """
+			The name of the field in which the `tag` policy stores the skew of an event, in seconds.
+
+			The skew is positive for timestamps in the future, and negative for timestamps in the past.
+			"""
+		required: false
+		type: string: default: "clock_skew_secs"
+	}
+}
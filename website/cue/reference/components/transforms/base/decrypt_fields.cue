@@ -0,0 +1,61 @@
+package metadata
+
+base: components: transforms: decrypt_fields: configuration: {
+	aad: {
+		description: """
+			The additional authenticated data the envelopes must be bound to.
+
+			Envelopes with other additional authenticated data are not decrypted. If unset, envelopes
+			are decrypted regardless of it.
+			"""
+		required: false
+		type: string: examples: ["tenant=acme"]
+	}
+	fields: {
+		description: """
+			The fields whose envelopes are decrypted.
+
+			Each envelope is decrypted with the key its `kid` names, and replaced with the original
+			value. Fields that don't hold an envelope are left as they are, and so are envelopes that
+			can't be decrypted, which are reported as errors.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["user.email", "card_number"]
+	}
+	keys: {
+		description: """
+			The keys of the decryption.
+
+			These should include every key the fields may have been encrypted with, including the
+			rotated ones.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			active_from: {
+				description: """
+					When the key starts being used to encrypt, as an RFC 3339 timestamp.
+
+					Fields are encrypted with the key active the latest, which lets keys be rotated on a
+					schedule by listing the next key ahead of time. If unset, the key is active from the start.
+					Keys are used to decrypt regardless of when they are active.
+					"""
+				required: false
+				type: string: examples: ["2024-01-01T00:00:00Z"]
+			}
+			id: {
+				description: "The ID of the key, recorded in the `kid` field of the envelopes encrypted with it."
+				required:    true
+				type: string: examples: ["2024-q1"]
+			}
+			secret: {
+				description: """
+					The secret of the key, 64 random bytes encoded in base64.
+
+					Keys are best loaded from a secrets backend, with `SECRET[<backend>.<key>]`.
+					"""
+				required: true
+				type: string: examples: ["SECRET[vault.field_encryption_2024_q1]"]
+			}
+		}
+	}
+}
@@ -0,0 +1,57 @@
+package metadata
+
+base: components: transforms: encrypt_fields: configuration: {
+	aad: {
+		description: """
+			Additional authenticated data bound to the envelopes.
+
+			It is stored in the clear in the `aad` field of the envelopes, but can't be altered without
+			the decryption failing, so a `decrypt_fields` transform expecting it only decrypts
+			envelopes that were encrypted for it.
+			"""
+		required: false
+		type: string: examples: ["tenant=acme"]
+	}
+	fields: {
+		description: """
+			The fields whose values are encrypted.
+
+			Each value is replaced with an envelope, an object with the ciphertext and the `kid` of the
+			key, which the `decrypt_fields` transform and the `decrypt_envelope` VRL function decrypt.
+			Missing fields, and fields that already hold an envelope, are left as they are.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["user.email", "card_number"]
+	}
+	keys: {
+		description: "The keys of the encryption."
+		required:    true
+		type: array: items: type: object: options: {
+			active_from: {
+				description: """
+					When the key starts being used to encrypt, as an RFC 3339 timestamp.
+
+					Fields are encrypted with the key active the latest, which lets keys be rotated on a
+					schedule by listing the next key ahead of time. If unset, the key is active from the start.
+					Keys are used to decrypt regardless of when they are active.
+					"""
+				required: false
+				type: string: examples: ["2024-01-01T00:00:00Z"]
+			}
+			id: {
+				description: "The ID of the key, recorded in the `kid` field of the envelopes encrypted with it."
+				required:    true
+				type: string: examples: ["2024-q1"]
+			}
+			secret: {
+				description: """
+					The secret of the key, 64 random bytes encoded in base64.
+
+					Keys are best loaded from a secrets backend, with `SECRET[<backend>.<key>]`.
+					"""
+				required: true
+				type: string: examples: ["SECRET[vault.field_encryption_2024_q1]"]
+			}
+		}
+	}
+}
@@ -0,0 +1,177 @@
+package metadata
+
+base: components: transforms: kubernetes_metadata: configuration: {
+	annotation_fields: {
+		description: """
+			Configuration for how the events are enriched with the metadata of Kubernetes objects.
+
+			Only the objects needed by the fields that are set are watched.
+			"""
+		required: false
+		type: object: options: {
+			deployment_labels: {
+				description: """
+					Event field for the labels of the Deployment owning the Pod.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.deployment_labels"
+					examples: [".k8s.deployment_labels", ""]
+				}
+			}
+			deployment_name: {
+				description: """
+					Event field for the name of the Deployment owning the Pod.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.deployment_name"
+					examples: [".k8s.deployment_name", ""]
+				}
+			}
+			node_labels: {
+				description: """
+					Event field for the Node's labels.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.node_labels"
+					examples: [".k8s.node_labels", ""]
+				}
+			}
+			node_zone: {
+				description: """
+					Event field for the Node's zone, taken from its `topology.kubernetes.io/zone` label.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.node_zone"
+					examples: [".k8s.node_zone", ""]
+				}
+			}
+			service_account_labels: {
+				description: """
+					Event field for the labels of the ServiceAccount of the Pod.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.service_account_labels"
+					examples: [".k8s.service_account_labels", ""]
+				}
+			}
+			service_names: {
+				description: """
+					Event field for the names of the Services selecting the Pod.
+
+					Set to `""` to suppress this key.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.service_names"
+					examples: [".k8s.service_names", ""]
+				}
+			}
+		}
+	}
+	delay_deletion_ms: {
+		description: """
+			How long to delay removing metadata entries from the cache when a deletion event is
+			received from the watch stream.
+			"""
+		required: false
+		type: uint: {
+			default: 60000
+			unit:    "milliseconds"
+		}
+	}
+	kube_config_file: {
+		description: """
+			Optional path to a readable [kubeconfig][kubeconfig] file.
+
+			If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+			[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+			"""
+		required: false
+		type: string: examples: ["/path/to/.kube/config"]
+	}
+	lookup_fields: {
+		description: """
+			Configuration for the event fields identifying the Kubernetes objects an event refers to.
+
+			The defaults match the fields added by the `kubernetes_logs` source.
+			"""
+		required: false
+		type: object: options: {
+			namespace: {
+				description: """
+					Event field holding the namespace of the Pod.
+
+					Deployments, Services, and ServiceAccounts are looked up in this namespace. Set to `""` to
+					only enrich events with Node metadata.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.pod_namespace"
+					examples: [".k8s.pod_namespace", ""]
+				}
+			}
+			node_name: {
+				description: """
+					Event field holding the name of the Node.
+
+					Set to `""` to skip the enrichment with Node metadata.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.pod_node_name"
+					examples: [".host", ""]
+				}
+			}
+			owner: {
+				description: """
+					Event field holding the owner reference of the Pod, formatted as `<kind>/<name>`.
+
+					Pods owned by a ReplicaSet are resolved to the Deployment owning the ReplicaSet.
+					"""
+				required: false
+				type: string: {
+					default: ".kubernetes.pod_owner"
+					examples: [".k8s.pod_owner", ""]
+				}
+			}
+			pod_labels: {
+				description: "Event field holding the labels of the Pod, matched against the selectors of Services."
+				required:    false
+				type: string: {
+					default: ".kubernetes.pod_labels"
+					examples: [".k8s.pod_labels", ""]
+				}
+			}
+			service_account: {
+				description: """
+					Event field holding the name of the ServiceAccount of the Pod.
+
+					Unset by default, as the `kubernetes_logs` source doesn't add it.
+					"""
+				required: false
+				type: string: examples: [".kubernetes.pod_service_account"]
+			}
+		}
+	}
+	use_apiserver_cache: {
+		description: "Determines if requests to the kube-apiserver can be served by a cache."
+		required:    false
+		type: bool: default: false
+	}
+}
@@ -0,0 +1,61 @@
+package metadata
+
+base: components: transforms: pseudonymize: configuration: {
+	emit_mapping: {
+		description: """
+			Whether to send the mapping between the pseudonyms and the values to the `mapping` output.
+
+			For each pseudonymized field, an event with its `key_id`, `field`, `pseudonym`, and original
+			`value` is sent to `<transform_id>.mapping`, which should only be routed to a sink storing
+			them for authorized re-identification.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	fields: {
+		description: """
+			The fields whose values are replaced with their pseudonyms.
+
+			The pseudonym of a value is the hex-encoded HMAC-SHA256 of the value with the active key, so
+			equal values have equal pseudonyms until the key rotates. Values other than strings are
+			hashed as their string representation. Missing fields are left as they are.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["user.email", "client_ip"]
+	}
+	keys: {
+		description: """
+			The keys of the keyed hash.
+
+			The ID of the key an event was pseudonymized with is recorded in its `pseudonymize.key_id`
+			metadata field, available in VRL as `%pseudonymize.key_id`.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			active_from: {
+				description: """
+					When the key starts being used, as an RFC 3339 timestamp.
+
+					Events are pseudonymized with the key active the latest, which lets keys be rotated on a
+					schedule by listing the next key ahead of time. If unset, the key is active from the start.
+					"""
+				required: false
+				type: string: examples: ["2024-01-01T00:00:00Z"]
+			}
+			id: {
+				description: "The ID of the key, recorded in the metadata of the events pseudonymized with it."
+				required:    true
+				type: string: examples: ["2024-q1"]
+			}
+			secret: {
+				description: """
+					The secret of the key.
+
+					Keys are best loaded from a secrets backend, with `SECRET[<backend>.<key>]`.
+					"""
+				required: true
+				type: string: examples: ["SECRET[vault.pseudonymization_2024_q1]"]
+			}
+		}
+	}
+}
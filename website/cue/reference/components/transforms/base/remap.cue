@@ -1,6 +1,18 @@
 package metadata
 
 base: components: transforms: remap: configuration: {
+	batch: {
+		description: """
+			Runs the program over each batch of events received by the transform at once.
+
+			The setup shared by the events of a batch, such as deciding whether the original events
+			must be kept in case the program fails or aborts, is done once per batch rather than once
+			per event. Each event is still processed by its own run of the program, so the output is
+			identical to that of processing the events one at a time.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	drop_on_abort: {
 		description: """
 			Drops any event that is manually aborted during processing.
@@ -113,6 +125,9 @@ base: components: transforms: remap: configuration: {
 			This overrides the [global `timezone`][global_timezone] option. The time zone name may be
 			any name in the [TZ database][tz_database], or `local` to indicate system local time.
 
+			If unset, the timestamps of events from a source that sets its own `timezone` are
+			converted in that timezone instead of the global one.
+
 			[global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
 			[tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
 			"""
@@ -19,6 +19,9 @@ base: components: transforms: sample: configuration: {
 
 			This can be useful to, for example, ensure that all logs for a given transaction are
 			sampled together, but that overall `1/N` transactions are sampled.
+
+			The hash doesn't depend on the instance or on previous events, so Vector instances
+			configured with the same `rate` keep the same buckets.
 			"""
 		required: false
 		type: string: examples: ["message"]
@@ -29,8 +32,33 @@ base: components: transforms: sample: configuration: {
 
 			For example, `rate = 10` means 1 out of every 10 events are forwarded and the rest are
 			dropped.
+
+			Required unless `reservoir` is set.
 			"""
-		required: true
+		required: false
 		type: uint: {}
 	}
+	reservoir: {
+		description: """
+			Configuration for reservoir sampling.
+
+			Instead of forwarding a share of the events, a fixed number of events is picked uniformly at
+			random from each window, regardless of the input rate. The picked events are forwarded at the
+			end of the window, with `sample_rate` set to the number of events received during the window
+			divided by the number of events forwarded.
+			"""
+		required: false
+		type: object: options: {
+			size: {
+				description: "The number of events forwarded per window."
+				required:    true
+				type: uint: examples: [100]
+			}
+			window_secs: {
+				description: "The length of the window, in seconds."
+				required:    true
+				type: float: {}
+			}
+		}
+	}
 }
@@ -0,0 +1,61 @@
+package metadata
+
+base: components: transforms: sessionize: configuration: {
+	emit_summaries: {
+		description: """
+			Whether to emit a summary event when a session is closed.
+
+			The summary holds the `group_by` fields and the session ID of the session, along with its
+			`session_start` and `session_end` timestamps, its `session_duration_secs`, and its number of
+			events in `session_events`.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	flush_period_ms: {
+		description: "The interval to check for and close inactive sessions, in milliseconds."
+		required:    false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+	group_by: {
+		description: """
+			An ordered list of fields by which to group events, such as a user ID or a source IP.
+
+			Each group with matching values for the specified fields has its own sessions. When no
+			fields are specified, all events share the same sessions.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["user_id", "source_ip"]
+		}
+	}
+	inactivity_gap_secs: {
+		description: """
+			The period of inactivity after which the session of a group is closed.
+
+			The next event of the group starts a new session.
+			"""
+		required: false
+		type: uint: {
+			default: 1800
+			unit:    "seconds"
+		}
+	}
+	max_duration_secs: {
+		description: "The maximum duration of a session, after which it is closed even if the group is active."
+		required:    false
+		type: uint: {
+			examples: [86400]
+			unit: "seconds"
+		}
+	}
+	session_id_field: {
+		description: "The name of the field in which the session ID of an event is stored."
+		required:    false
+		type: string: default: "session_id"
+	}
+}
@@ -1,6 +1,65 @@
 package metadata
 
 base: components: transforms: throttle: configuration: {
+	backend: {
+		description: "Where the events let through are counted."
+		required:    false
+		type: object: options: {
+			endpoint: {
+				description:   "The URL of the Redis server."
+				relevant_when: "type = \"redis\""
+				required:      true
+				type: string: examples: ["redis://127.0.0.1:6379/0"]
+			}
+			prefix: {
+				description: """
+					The prefix of the keys of the counters in Redis.
+
+					The Vector instances enforcing the same threshold must use the same prefix, as well as the
+					same `threshold` and `window_secs`.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: string: default: "vector_throttle"
+			}
+			sync_interval_ms: {
+				description: """
+					How often the events let through are added to the counters in Redis, in milliseconds.
+
+					The Vector instances together let through up to the events each of them lets through
+					between two syncs over the threshold.
+					"""
+				relevant_when: "type = \"redis\""
+				required:      false
+				type: uint: {
+					default: 200
+					unit:    "milliseconds"
+				}
+			}
+			type: {
+				description: "The type of backend."
+				required:    false
+				type: string: {
+					default: "memory"
+					enum: {
+						memory: """
+							The events are counted by each Vector instance, so each instance lets through up to
+							`threshold` events per window.
+							"""
+						redis: """
+							The events are counted in Redis, so the Vector instances sharing the counters let through
+							up to `threshold` events per window together.
+
+							The events are counted in fixed windows aligned on the Unix epoch, and checked against the
+							counts of the last sync, so the instances may let through more than `threshold` events
+							between two syncs. If Redis cannot be reached, each instance keeps checking the events
+							against the counts it knows, and adds its events to the counters once Redis is back.
+							"""
+					}
+				}
+			}
+		}
+	}
 	exclude: {
 		description: "A logical condition used to exclude events from sampling."
 		required:    false
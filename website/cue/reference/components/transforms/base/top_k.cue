@@ -0,0 +1,50 @@
+package metadata
+
+base: components: transforms: top_k: configuration: {
+	capacity: {
+		description: """
+			The number of keys tracked during a window.
+
+			Once this many keys are tracked, a new key replaces the key with the lowest count, and
+			inherits its count as an overestimate. Tracking more keys than `k` makes the summaries
+			more accurate. Defaults to ten times `k`.
+			"""
+		required: false
+		type: uint: {}
+	}
+	k: {
+		description: "The number of keys in each summary."
+		required:    false
+		type: uint: default: 20
+	}
+	key: {
+		description: "The key by which events are counted, such as the service that emitted them."
+		required:    true
+		type: string: {
+			examples: ["{{ service }}", "{{ kubernetes.pod_namespace }}/{{ kubernetes.container_name }}"]
+			syntax: "template"
+		}
+	}
+	weight: {
+		description: "What is counted for each key."
+		required:    false
+		type: string: {
+			default: "events"
+			enum: {
+				bytes:  "The estimated size of the events once encoded as JSON, in bytes."
+				events: "The number of events."
+			}
+		}
+	}
+	window_secs: {
+		description: """
+			The duration of the windows, at the end of which a summary is emitted and the counts are
+			reset.
+			"""
+		required: false
+		type: uint: {
+			default: 60
+			unit:    "seconds"
+		}
+	}
+}
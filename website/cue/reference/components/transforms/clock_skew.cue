@@ -0,0 +1,62 @@
+package metadata
+
+components: transforms: clock_skew: {
+	title: "Clock Skew"
+
+	description: """
+		Detects event timestamps too far in the future or the past relative to the time they are
+		processed, and clamps, shifts, or tags them.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.clock_skew.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		accepted_window: {
+			title: "Accepted window"
+			body: """
+				An event is skewed when its timestamp is more than `max_future_secs` ahead of, or more
+				than `max_past_secs` behind, the time the transform processes it. Events without a
+				timestamp, or whose timestamp is not a timestamp value, are passed through untouched.
+
+				Placing this transform in front of sinks that reject entries outside of a time window,
+				such as Loki, prevents whole batches from being rejected because of a single host with
+				a misconfigured clock.
+				"""
+		}
+		offset_estimation: {
+			title: "Offset estimation"
+			body: """
+				With the `shift` policy, the transform keeps a moving average of the clock offset of
+				every `group_by` value in memory. The `group_by` field should therefore have a low
+				number of distinct values, such as host names.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		clock_skew_events_total: components.sources.internal_metrics.output.metrics.clock_skew_events_total
+	}
+}
@@ -0,0 +1,48 @@
+package metadata
+
+components: transforms: decrypt_fields: {
+	title: "Decrypt Fields"
+
+	description: """
+		Decrypts the envelopes of fields encrypted by the `encrypt_fields` transform or the
+		`encrypt_envelope` VRL function, with the key each envelope names.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.decrypt_fields.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		failures: {
+			title: "Failures"
+			body: """
+				Envelopes that can't be decrypted, because their key is unknown, their additional
+				authenticated data isn't the expected one, or they were altered, are left as they are
+				and reported as errors, so the event still reaches its destination without losing the
+				encrypted value. The envelope format is described in the documentation of the
+				`encrypt_fields` transform.
+				"""
+		}
+	}
+}
@@ -0,0 +1,75 @@
+package metadata
+
+components: transforms: encrypt_fields: {
+	title: "Encrypt Fields"
+
+	description: """
+		Encrypts the values of selected fields into envelopes, self-describing objects that the
+		`decrypt_fields` transform of any Vector downstream, or any consumer holding the key, can
+		detect and decrypt.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.encrypt_fields.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		envelope_format: {
+			title: "Envelope format"
+			body: """
+				Each encrypted value is replaced with an object of the following fields:
+
+				* `v`: the version of the format, `1`.
+				* `alg`: the algorithm, `A256CBC-HS512`, the authenticated encryption of AES-256-CBC and
+				  HMAC-SHA-512 defined by JWE, whose 64-byte key is the MAC key followed by the
+				  encryption key.
+				* `kid`: the ID of the key.
+				* `iv`, `ct`, and `tag`: the IV, the ciphertext, and the authentication tag, encoded in
+				  unpadded base64url.
+				* `aad`: the additional authenticated data, if any.
+
+				The plaintext is the JSON encoding of the value, so its type survives the round trip,
+				except for timestamps, which are decrypted as strings. The same envelopes are produced
+				and decrypted by the `encrypt_envelope` and `decrypt_envelope` VRL functions.
+				"""
+		}
+		key_rotation: {
+			title: "Key rotation"
+			body: """
+				Each event is encrypted with the key whose `active_from` is the latest one that has
+				passed, so the next key can be listed ahead of time and takes over at its activation
+				time. Envelopes record the ID of their key, so keys that have been rotated out must be
+				kept by the decrypting side for as long as their envelopes are stored.
+				"""
+		}
+		failures: {
+			title: "Failures"
+			body: """
+				Fields that already hold an envelope are not encrypted again, so the transform can run
+				at several hops. An event whose field can't be encrypted is dropped rather than sent
+				with the value in the clear.
+				"""
+		}
+	}
+}
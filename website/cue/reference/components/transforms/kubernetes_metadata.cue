@@ -0,0 +1,71 @@
+package metadata
+
+components: transforms: kubernetes_metadata: {
+	title: "Kubernetes Metadata"
+
+	description: """
+		Enriches events with the metadata of the Kubernetes objects they refer to, such as the
+		labels and zone of a Node, the Deployment owning a Pod, or the Services selecting it.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     "Kubernetes API"
+				url:      urls.kubernetes_api
+				versions: ">= 1.19"
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The service account of Vector must be allowed to `list` and `watch` the objects used for
+				the enrichment: `nodes`, `replicasets` and `deployments` of the `apps` group, `services`,
+				and `serviceaccounts`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.kubernetes_metadata.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		watched_objects: {
+			title: "Watched objects"
+			body: """
+				The transform keeps a cache of the objects it needs, kept up to date through the watch
+				API of Kubernetes, so that no request is made per event. An object kind is only watched
+				if both the field it is looked up by and a field it is annotated into are set. For
+				example, ServiceAccounts are only watched once `lookup_fields.service_account` is set.
+
+				Any event carrying the name of a Node can be enriched with the Node's labels and zone,
+				not only the events of the `kubernetes_logs` source. Events referring to objects missing
+				from the cache are passed through unchanged.
+				"""
+		}
+		owner_resolution: {
+			title: "Deployment resolution"
+			body: """
+				Pods created by a Deployment are owned by one of its ReplicaSets. The transform resolves
+				an owner reference such as `ReplicaSet/web-7d9f8` to the Deployment owning that
+				ReplicaSet.
+				"""
+		}
+	}
+}
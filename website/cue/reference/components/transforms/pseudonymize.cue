@@ -0,0 +1,66 @@
+package metadata
+
+components: transforms: pseudonymize: {
+	title: "Pseudonymize"
+
+	description: """
+		Replaces the values of selected fields with pseudonyms, keyed hashes of the values, rotating
+		the key on a schedule, and optionally emits the mapping between the pseudonyms and the values
+		for authorized re-identification.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.pseudonymize.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		{
+			name:        "mapping"
+			description: "The mapping between the pseudonyms and the values, when `emit_mapping` is enabled. It can be referenced as an input by other components with the name `<transform_name>.mapping`."
+		},
+	]
+
+	how_it_works: {
+		key_rotation: {
+			title: "Key rotation"
+			body: """
+				Each event is pseudonymized with the key whose `active_from` is the latest one that has
+				passed, so the next key can be listed ahead of time and takes over at its activation
+				time. The ID of the key used is recorded in the `pseudonymize.key_id` metadata field of
+				the event, so that pseudonyms produced with different keys can be told apart
+				downstream. Secrets are best loaded from a secrets backend, so that they are never
+				written in the configuration.
+				"""
+		}
+		re_identification: {
+			title: "Re-identification"
+			body: """
+				Pseudonyms can't be reversed without the mapping. When `emit_mapping` is enabled, the
+				`mapping` output carries an event with the key ID, the field, the pseudonym, and the
+				original value for every pseudonymized field, and should only be routed to a sink with
+				restricted access.
+				"""
+		}
+	}
+}
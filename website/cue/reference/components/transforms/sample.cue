@@ -31,4 +31,20 @@ components: transforms: sample: {
 		metrics: null
 		traces:  true
 	}
+
+	how_it_works: {
+		modes: {
+			title: "Sampling modes"
+			body: """
+				With `rate`, each event is forwarded immediately with a probability of `1/rate`. When
+				`key_field` is set, the decision is a hash of its value, so every instance forwards the
+				same events.
+
+				With `reservoir`, events are held for `reservoir.window_secs` and at most
+				`reservoir.size` of them are forwarded at the end of each window, which bounds the
+				output rate when the input rate spikes. Both modes set `sample_rate` on the forwarded
+				events to the effective rate, so that counts can be scaled back up downstream.
+				"""
+		}
+	}
 }
@@ -0,0 +1,61 @@
+package metadata
+
+components: transforms: sessionize: {
+	title: "Sessionize"
+
+	description: """
+		Assigns a session ID to events sharing a key, such as a user ID or a source IP, starting a
+		new session after a period of inactivity, and optionally emits a summary event when a session
+		is closed.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.sessionize.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		inactivity_gaps: {
+			title: "Inactivity gaps"
+			body: """
+				The gap between two events of a group is measured with their timestamps, or with the
+				time they are received if they have none, so that replayed or delayed events are
+				assigned to the sessions they belong to. A session is also closed once its group
+				hasn't received events for `inactivity_gap_secs`, checked every `flush_period_ms`, so
+				that its summary is emitted without waiting for the next event of the group.
+				"""
+		}
+		memory_usage: {
+			title: "Memory usage"
+			body: """
+				The transform keeps the open session of every group in memory, until it is closed. The
+				number of groups open at once should therefore be bounded, for example by setting a
+				`max_duration_secs` for groups that are never inactive.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		sessions_closed_total: components.sources.internal_metrics.output.metrics.sessions_closed_total
+	}
+}
@@ -33,6 +33,7 @@ components: transforms: throttle: {
 	}
 
 	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
 
@@ -112,5 +113,21 @@ components: transforms: throttle: {
 				},
 			]
 		}
+		redis_backend: {
+			title: "Fleet-wide Rate Limiting"
+			body:  """
+				With the `redis` backend, the events let through are counted in Redis, so that several Vector
+				instances enforce one `threshold` together. The events of each bucket are counted in fixed windows of
+				`window_secs` aligned on the Unix epoch, instead of by the Generic Cell Rate Algorithm.
+
+				Each instance checks events against the counts of its last sync, and adds the events it let through to
+				the counters in Redis every `backend.sync_interval_ms`. Together, the instances can therefore let
+				through more than `threshold` events of a window, by up to the events each of them lets through between
+				two syncs. The instances sharing a `backend.prefix` must use the same `threshold` and `window_secs`.
+
+				If Redis can't be reached, each instance keeps checking events against the counts it knows, and adds the
+				events it let through to the counters once Redis is back.
+				"""
+		}
 	}
 }
@@ -0,0 +1,100 @@
+package metadata
+
+components: transforms: top_k: {
+	title: "Top K"
+
+	description: """
+		Tracks the keys with the most events, or the most bytes, over windows of time, such as the
+		noisiest services, and emits a summary of the top keys at the end of each window. The input
+		events are consumed.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.top_k.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	output: logs: summary: {
+		description: "The top keys of a window."
+		fields: {
+			timestamp: {
+				description: "The end of the window."
+				required:    true
+				type: timestamp: {}
+			}
+			window_start: {
+				description: "The start of the window."
+				required:    true
+				type: timestamp: {}
+			}
+			weight: {
+				description: "What is counted for each key, either `events` or `bytes`."
+				required:    true
+				type: string: {
+					enum: {
+						bytes:  "The estimated size of the events, in bytes."
+						events: "The number of events."
+					}
+				}
+			}
+			total: {
+				description: "The total weight of the window, across all keys."
+				required:    true
+				type: uint: {
+					examples: [1048576]
+					unit: null
+				}
+			}
+			top: {
+				description: """
+					The top keys, from the heaviest, each with its `key`, its `count`, and the `error` by
+					which the count may be overestimated.
+					"""
+				required: true
+				type: array: items: type: object: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		space_saving: {
+			title: "Space-Saving"
+			body: """
+				Counting every key exactly would take an unbounded amount of memory, so the transform
+				uses the Space-Saving algorithm, which tracks `capacity` keys at most. When a new key is
+				seen while `capacity` keys are tracked, it replaces the key with the lowest count, and
+				starts from that count, which is reported as its `error`. Any key whose true count is
+				above the total divided by `capacity` is guaranteed to be tracked, and the count of a key
+				minus its error is a lower bound of its true count.
+				"""
+		}
+		windows: {
+			title: "Windows"
+			body: """
+				The counts are reset at the end of each window, after its summary is emitted. Windows
+				without events emit no summary. The current window is summarized when Vector shuts down
+				or the transform is reloaded, so its summary may cover less than `window_secs`.
+				"""
+		}
+	}
+}
@@ -90,6 +90,78 @@ configuration: {
 			}
 		}
 
+		sink_in_flight_bytes_limit: {
+			common: false
+			description: """
+				The maximum number of bytes of events held by all sinks together, from when they take
+				events from their buffers until they are done sending them.
+
+				This covers the events being batched and encoded into requests, as well as those of
+				requests in flight or being retried, which are not bounded by buffers. Once reached, sinks
+				wait for their requests to complete before taking more events, applying backpressure to
+				their buffers. The bytes held by each sink are exposed by the `sink_in_flight_bytes`
+				gauge.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [536870912]
+				unit: "bytes"
+			}
+		}
+
+		data_dir_budget: {
+			common: false
+			description: """
+				Disk budget of the global data directory.
+
+				Each disk buffer is bounded by its own `max_size`, but not the total of all disk buffers.
+				This caps the bytes used by the disk buffers together, and decides what happens once they
+				reach it. The bytes used in the data directory by each component are exposed by the
+				`component_disk_usage_bytes` gauge, and those counted towards the budget by the
+				`data_dir_budget_used_bytes` gauge.
+				"""
+			required: false
+			type: object: options: {
+				max_bytes: {
+					common:      false
+					description: """
+						The maximum number of bytes that the disk buffers in the data directory can use in total.
+
+						By default, disk buffers are only bounded by their own `max_size`.
+						"""
+					required: false
+					type: uint: {
+						default: null
+						examples: [10737418240]
+						unit: "bytes"
+					}
+				}
+				policy: {
+					common:      false
+					description: "What happens once the disk buffers use up the disk budget of the data directory."
+					required:    false
+					type: string: {
+						default: "block"
+						enum: {
+							block: """
+								Disk buffers are considered full until space is freed, and apply their `when_full`
+								behavior.
+								"""
+							evict_oldest: """
+								The oldest data files of the disk buffers marked `evictable` are deleted to make room,
+								starting with the largest buffer, and the events they held are dropped.
+
+								The data files being read are never evicted, so disk buffers are considered full when
+								there is nothing left to evict.
+								"""
+							alert: "Disk buffers keep growing past the budget, and an error is logged."
+						}
+					}
+				}
+			}
+		}
+
 		enrichment_tables: {
 			common:      false
 			description: """
@@ -329,6 +401,180 @@ configuration: {
 			}
 		}
 
+		accounting: {
+			common: false
+			description: """
+				Counts the events and bytes ingested by every source and delivered to every sink,
+				grouped by dimensions of the events such as their namespace, service, or tenant, to
+				attribute the cost of the pipeline. The counts since Vector started are exposed by the
+				`accounting` API query, and the `internal_accounting` source emits them into the
+				pipeline periodically. Bytes are the estimated size of the events once encoded as JSON.
+				"""
+			required: false
+			type: object: options: {
+				enabled: {
+					common:      false
+					description: "Whether or not events are accounted."
+					required:    false
+					type: bool: default: false
+				}
+				dimensions: {
+					common: false
+					description: """
+						The dimensions by which events are grouped, keyed by name. Each dimension is the
+						path of a field of log and trace events, or the name of a tag of metrics. Events
+						without the field are grouped under a missing value for the dimension. Changing
+						the dimensions clears the counts.
+						"""
+					required: false
+					type: object: {
+						examples: [{
+							namespace: "kubernetes.pod_namespace"
+							service:   "service"
+							tenant:    "tenant_id"
+						}]
+						options: "*": {
+							description: "The field holding the dimension."
+							required:    true
+							type: string: {}
+						}
+					}
+				}
+				max_groups: {
+					common: false
+					description: """
+						The maximum number of groups accounted for each component. Once reached, events
+						of new groups are accounted together in an overflow group, which has no
+						dimensions.
+						"""
+					required: false
+					type: uint: {
+						default: 10000
+						unit:    "groups"
+					}
+				}
+			}
+		}
+
+		fault_injection: {
+			common: false
+			description: """
+				Injects latency, errors and stalls into components, to rehearse incidents and verify
+				that acknowledgements are handled end-to-end. Faults can also be changed at runtime with
+				the `setComponentFaults` API mutation, until the configuration is reloaded. Not meant
+				to be used in production.
+				"""
+			required: false
+			type: object: options: {
+				enabled: {
+					common:      false
+					description: "Whether or not faults can be injected, through this configuration or the API."
+					required:    false
+					type: bool: default: false
+				}
+				components: {
+					common: false
+					description: """
+						The faults injected into each component, keyed by component ID. For sources,
+						faults apply to the events they send. For transforms and sinks, faults apply to
+						the events they receive.
+						"""
+					required: false
+					type: object: options: "*": {
+						description: "The faults of a component."
+						required:    true
+						type: object: options: {
+							latency_ms: {
+								description: "The latency added to each batch of events."
+								required:    false
+								common:      false
+								type: uint: {
+									default: 0
+									examples: [500]
+									unit: "milliseconds"
+								}
+							}
+							error_rate: {
+								description: """
+									The ratio of batches of events that are rejected, between `0.0` and
+									`1.0`. The events of a rejected batch are dropped, and their
+									acknowledgement is negative.
+									"""
+								required: false
+								common:   false
+								type: float: {
+									default: 0.0
+									examples: [0.1]
+								}
+							}
+							stall: {
+								description: """
+									Whether to stop processing events, until unset. Events back up in
+									the buffers of the component, which are eventually full.
+									"""
+								required: false
+								common:   false
+								type: bool: default: false
+							}
+						}
+					}
+				}
+			}
+		}
+
+		scaling: {
+			common: false
+			description: """
+				Computes signals summarizing how saturated Vector is, so that an autoscaler such as
+				the Kubernetes HorizontalPodAutoscaler or KEDA can scale aggregators before their
+				buffers are full: the fill ratio of the buffer of each component, how fast it changes,
+				the time until it is full at that rate, and the mean lag of the events received by each
+				source. The signals are emitted as `scaling_*` internal metrics, exposed by the `/scaling`
+				API endpoint, and optionally sent to a webhook.
+				"""
+			required: false
+			type: object: options: {
+				enabled: {
+					common:      false
+					description: "Whether or not scaling signals are computed."
+					required:    false
+					type: bool: default: false
+				}
+				interval_secs: {
+					common:      false
+					description: "The interval between two computations of the signals. Rates are computed over this interval."
+					required:    false
+					type: uint: {
+						default: 15
+						unit:    "seconds"
+					}
+				}
+				webhook: {
+					common: false
+					description: """
+						Sends the signals as a JSON object in a `POST` request each time they are
+						computed.
+						"""
+					required: false
+					type: object: options: {
+						uri: {
+							description: "The URI the signals are sent to."
+							required:    true
+							type: string: {
+								examples: ["http://keda-push-scaler.keda:8080/signals"]
+							}
+						}
+						auth: {
+							common:      false
+							description: "The authentication strategy used for the requests, as in the `auth` option of HTTP sinks."
+							required:    false
+							type: object: {}
+						}
+					}
+				}
+			}
+		}
+
 		healthchecks: {
 			common: false
 			description: """
@@ -494,6 +740,81 @@ configuration: {
 				}
 			}
 		}
+
+		dns: {
+			common:      false
+			description: """
+				Configures how the hostnames that components connect to are resolved.
+
+				Connections that stay open for a long time, such as those of the `socket` and `websocket`
+				sinks, resolve their host again whenever its records expire, and reconnect when the records
+				changed.
+				"""
+			required: false
+			type: object: options: {
+				nameservers: {
+					common:      false
+					description: """
+						The nameservers to query, including their port.
+
+						By default, hostnames are resolved by the system resolver, which also looks them up in
+						local sources such as `/etc/hosts`.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: {
+							examples: ["10.0.0.2:53"]
+						}
+					}
+				}
+				min_ttl_secs: {
+					common:      false
+					description: "The minimum number of seconds to use resolved records for, overriding lower TTLs."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+				max_ttl_secs: {
+					common:      false
+					description: """
+						The maximum number of seconds to use resolved records for, overriding higher TTLs.
+
+						The system resolver does not return TTLs, so its records are considered valid for 60
+						seconds, within the minimum and maximum.
+						"""
+					required: false
+					type: uint: {
+						default: null
+						examples: [300]
+						unit: "seconds"
+					}
+				}
+				ip_strategy: {
+					common:      false
+					description: """
+						The IP versions of the addresses to connect to, and their order.
+
+						When connecting, the addresses of a host are tried in order. HTTP connections race the
+						IPv4 and IPv6 addresses ("happy eyeballs") when both are used.
+						"""
+					required: false
+					type: string: {
+						default: "ipv4_and_ipv6"
+						enum: {
+							ipv4_and_ipv6: "Both IPv4 and IPv6 addresses are used, in the order they are resolved."
+							ipv4_only:     "Only IPv4 addresses are used."
+							ipv6_only:     "Only IPv6 addresses are used."
+							prefer_ipv4:   "IPv4 addresses are tried before IPv6 addresses."
+							prefer_ipv6:   "IPv6 addresses are tried before IPv4 addresses."
+						}
+					}
+				}
+			}
+		}
 	}
 
 	how_it_works: {
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: decrypt_envelope: {
+	category: "Cryptography"
+	description: """
+		Decrypts an envelope created by the `encrypt_envelope` function or the `encrypt_fields`
+		transform, with the key its `kid` field names.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The envelope to decrypt."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "keys"
+			description: "The keys, indexed by their ID. The keys should be raw bytes (not encoded)."
+			required:    true
+			type: ["object"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not an envelope, or is of an unsupported version or algorithm",
+		"`keys` has no key with the ID of the envelope",
+		"the key, the ciphertext, or the additional authenticated data is wrong",
+	]
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Decrypt an envelope"
+			source: #"""
+				key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+				envelope = encrypt_envelope!("jane@example.com", key: key, key_id: "2024-q1")
+				decrypt_envelope!(envelope, keys: { "2024-q1": key })
+				"""#
+			return: "jane@example.com"
+		},
+	]
+}
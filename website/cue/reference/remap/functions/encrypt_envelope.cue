@@ -0,0 +1,57 @@
+package metadata
+
+remap: functions: encrypt_envelope: {
+	category: "Cryptography"
+	description: """
+		Encrypts a value into an envelope, an object holding the ciphertext along with the ID of the
+		key, the algorithm, and the additional authenticated data needed to decrypt it.
+
+		Envelopes are the format of the fields encrypted by the `encrypt_fields` transform, so they
+		can be decrypted by the `decrypt_envelope` function, by the `decrypt_fields` transform of any
+		Vector downstream, or by any consumer holding the key. The value is encrypted with
+		A256CBC-HS512, the authenticated encryption algorithm of JWE, and can be of any type, but
+		timestamps are decrypted as RFC 3339 strings.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to encrypt."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "key"
+			description: "The key, which must be 64 bytes long. It should be the raw bytes of the key (not encoded)."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key_id"
+			description: "The ID of the key, recorded in the `kid` field of the envelope to tell the decrypting party which key to use."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "aad"
+			description: "Additional authenticated data, stored in the clear in the `aad` field of the envelope but bound to the ciphertext so it can't be altered."
+			required:    false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`key` is not 64 bytes long",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Encrypt a value into an envelope"
+			source: #"""
+				envelope = encrypt_envelope!("jane@example.com", key: "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef", key_id: "2024-q1")
+				envelope.kid
+				"""#
+			return: "2024-q1"
+		},
+	]
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: collectd: {
+	name:     "collectd"
+	thing:    "a \(name) daemon"
+	url:      urls.collectd
+	versions: null
+
+	description: "[collectd](\(urls.collectd)) is a daemon which periodically collects system and application performance metrics."
+}
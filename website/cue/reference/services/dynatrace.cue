@@ -0,0 +1,10 @@
+package metadata
+
+services: dynatrace: {
+	name:     "Dynatrace"
+	thing:    "a \(name) environment"
+	url:      urls.dynatrace
+	versions: null
+
+	description: "[Dynatrace](\(urls.dynatrace)) is an observability and application security platform that ingests logs, metrics, and traces."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: macos_unified_log: {
+	name:     "macOS unified logging system"
+	thing:    "the \(name)"
+	url:      urls.macos_unified_log
+	versions: ">= 10.12"
+
+	description: "The [macOS unified logging system](\(urls.macos_unified_log)) collects the logs of the kernel, system services, and applications of macOS in a single, structured store."
+}
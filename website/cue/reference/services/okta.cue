@@ -0,0 +1,10 @@
+package metadata
+
+services: okta: {
+	name:     "Okta"
+	thing:    "an \(name) organization"
+	url:      urls.okta
+	versions: null
+
+	description: "[Okta](\(urls.okta)) is an identity and access management platform, recording the activity of the users and admins of an organization in its [System Log](\(urls.okta_system_log))."
+}
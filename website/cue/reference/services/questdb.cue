@@ -0,0 +1,10 @@
+package metadata
+
+services: questdb: {
+	name:     "QuestDB"
+	thing:    "a \(name) database"
+	url:      urls.questdb
+	versions: null
+
+	description: "[QuestDB](\(urls.questdb)) is an open-source time-series database with SQL queries and high-throughput ingestion."
+}
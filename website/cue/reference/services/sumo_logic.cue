@@ -0,0 +1,10 @@
+package metadata
+
+services: sumo_logic: {
+	name:     "Sumo Logic"
+	thing:    "a \(name) HTTP source"
+	url:      urls.sumo_logic
+	versions: null
+
+	description: "[Sumo Logic](\(urls.sumo_logic)) is a cloud-based log management and analytics service for logs and metrics."
+}
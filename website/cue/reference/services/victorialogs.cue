@@ -0,0 +1,10 @@
+package metadata
+
+services: victorialogs: {
+	name:     "VictoriaLogs"
+	thing:    "a \(name) database"
+	url:      urls.victorialogs
+	versions: null
+
+	description: "[VictoriaLogs](\(urls.victorialogs)) is an open source log database from VictoriaMetrics."
+}
@@ -94,6 +94,7 @@ urls: {
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
+	azure_monitor_logs_ingestion_api:           "https://learn.microsoft.com/en-us/azure/azure-monitor/logs/logs-ingestion-api-overview"
 	base16:                                     "\(wikipedia)/wiki/Hexadecimal"
 	base64:                                     "\(wikipedia)/wiki/Base64"
 	base64_padding:                             "\(wikipedia)/wiki/Base64#Output_padding"
@@ -112,6 +113,8 @@ urls: {
 	cloudsmith:                                 "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	collectd:                                   "https://collectd.org/"
+	collectd_binary_protocol:                   "https://collectd.org/wiki/index.php/Binary_protocol"
 	console:                                    "\(wikipedia)/wiki/System_console"
 	conventional_commits:                       "https://www.conventionalcommits.org"
 	contributing:                               "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -173,6 +176,9 @@ urls: {
 	dot_format:                                 "https://graphviz.org/doc/info/lang.html"
 	dpkg:                                       "https://wiki.debian.org/dpkg"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
+	dynatrace:                                  "https://www.dynatrace.com"
+	dynatrace_log_ingest:                       "https://docs.dynatrace.com/docs/shortlink/lma-generic-log-ingestion"
+	dynatrace_metrics_ingest:                   "https://docs.dynatrace.com/docs/shortlink/metric-ingestion-protocol"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
 	elastic_beats:                              "https://www.elastic.co/beats/"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
@@ -308,6 +314,7 @@ urls: {
 	librdkafka:                                 "\(github)/edenhill/librdkafka"
 	librdkafka_config:                          "\(github)/edenhill/librdkafka/blob/master/CONFIGURATION.md"
 	linux_capability:                           "https://man7.org/linux/man-pages/man7/capabilities.7.html"
+	macos_unified_log:                          "https://developer.apple.com/documentation/os/logging"
 	mezmo:                                      "https://mezmo.com/"
 	logfmt:                                     "https://brandur.org/logfmt"
 	logfmt_specs:                               "https://pkg.go.dev/github.com/kr/logfmt#section-documentation"
@@ -377,6 +384,8 @@ urls: {
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	okta:                                       "https://www.okta.com/"
+	okta_system_log:                            "https://developer.okta.com/docs/reference/api/system-log/"
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
@@ -413,9 +422,15 @@ urls: {
 	prometheus_remote_write:                    "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:           "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	prometheus_remote_write_spec:               "https://prometheus.io/docs/concepts/remote_write_spec/#protocol"
+	prometheus_remote_write_spec_2_0:           "https://prometheus.io/docs/specs/remote_write_spec_2_0/"
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	questdb:                                    "https://questdb.io"
+	questdb_ilp:                                "https://questdb.io/docs/reference/api/ilp/overview/"
+	questdb_ilp_auth:                           "https://questdb.io/docs/reference/api/ilp/authenticate/"
+	quickwit:                                   "https://quickwit.io"
+	quickwit_ingest_api:                        "https://quickwit.io/docs/reference/rest-api#ingest-data-into-an-index"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -487,6 +502,9 @@ urls: {
 	stdout:                                     "\(wikipedia)/wiki/Standard_streams#Standard_output_(stdout)"
 	stripe_blog_canonical_log_lines:            "https://stripe.com/blog/canonical-log-lines"
 	strptime_specifiers:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
+	sumo_logic:                                 "https://www.sumologic.com"
+	sumo_logic_carbon2:                         "https://help.sumologic.com/docs/metrics/introduction/supported-metrics-formats/#carbon-20"
+	sumo_logic_http_source:                     "https://help.sumologic.com/docs/send-data/hosted-collectors/http-source/logs-metrics/upload-logs/"
 	sysfs:                                      "https://www.kernel.org/doc/html/latest/filesystems/sysfs.html"
 	syslog:                                     "\(wikipedia)/wiki/Syslog"
 	syslog_3164:                                "https://tools.ietf.org/html/rfc3164"
@@ -498,6 +516,7 @@ urls: {
 	systemd_limit_resources:                    "https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html"
 	systemd_resolved:                           "https://wiki.archlinux.org/index.php/Systemd-resolved"
 	tcp:                                        "\(wikipedia)/wiki/Transmission_Control_Protocol"
+	telegraf:                                   "https://www.influxdata.com/time-series-platform/telegraf/"
 	team:                                       "/community#team"
 	timber:                                     "https://timber.io"
 	toml:                                       "\(github)/toml-lang/toml"
@@ -618,6 +637,8 @@ urls: {
 	vector_twitter:                             "https://twitter.com/vectordotdev"
 	vector_unit_tests:                          "\(vector_configuration)/unit-tests"
 	vector_version_branches:                    "\(vector_repo)/branches/all?query=v"
+	victorialogs:                               "https://docs.victoriametrics.com/VictoriaLogs/"
+	victorialogs_json_stream_api:               "https://docs.victoriametrics.com/VictoriaLogs/data-ingestion/#json-stream-api"
 	vrl_announcement:                           "/blog/vector-remap-language"
 	vrl_boolean_expression:                     "\(vrl_reference)#boolean-expressions"
 	vrl_error_handling:                         "\(vrl_errors_reference)#handling"